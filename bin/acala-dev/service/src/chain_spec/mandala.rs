@@ -216,13 +216,13 @@ fn testnet_genesis(
 	endowed_accounts: Vec<AccountId>,
 ) -> mandala_runtime::GenesisConfig {
 	use mandala_runtime::{
-		dollar, get_all_module_accounts, AcalaOracleConfig, AirDropConfig, BabeConfig, Balance, BalancesConfig,
-		BandOracleConfig, CdpEngineConfig, CdpTreasuryConfig, DexConfig, EVMConfig, EnabledTradingPairs,
-		GeneralCouncilMembershipConfig, GrandpaConfig, HomaCouncilMembershipConfig, HonzonCouncilMembershipConfig,
-		IndicesConfig, NativeTokenExistentialDeposit, OperatorMembershipAcalaConfig, OperatorMembershipBandConfig,
-		OrmlNFTConfig, RenVmBridgeConfig, SessionConfig, StakerStatus, StakingConfig, StakingPoolConfig, SudoConfig,
-		SystemConfig, TechnicalCommitteeMembershipConfig, TokensConfig, TradingPair, VestingConfig, ACA, AUSD, DOT,
-		LDOT, RENBTC, XBTC, BABE_GENESIS_EPOCH_CONFIG
+		dollar, get_all_module_accounts, AcalaOracleConfig, BabeConfig, Balance, BalancesConfig, BandOracleConfig,
+		CdpEngineConfig, CdpTreasuryConfig, DexConfig, EVMConfig, EnabledTradingPairs, GeneralCouncilMembershipConfig,
+		GrandpaConfig, HomaCouncilMembershipConfig, HonzonCouncilMembershipConfig, IndicesConfig,
+		NativeTokenExistentialDeposit, OperatorMembershipAcalaConfig, OperatorMembershipBandConfig, OrmlNFTConfig,
+		RenVmBridgeConfig, SessionConfig, StakerStatus, StakingConfig, StakingPoolConfig, SudoConfig, SystemConfig,
+		TechnicalCommitteeMembershipConfig, TokensConfig, TradingPair, VestingConfig, ACA, AUSD,
+		BABE_GENESIS_EPOCH_CONFIG, DOT, LDOT, RENBTC, XBTC,
 	};
 	#[cfg(feature = "std")]
 	use sp_std::collections::btree_map::BTreeMap;
@@ -377,9 +377,6 @@ fn testnet_genesis(
 			],
 			global_interest_rate_per_sec: FixedU128::saturating_from_rational(1_547_126_000u128, 1_000_000_000_000_000_000u128), /* 5% APR */
 		},
-		module_airdrop: AirDropConfig {
-			airdrop_accounts: vec![],
-		},
 		orml_oracle_Instance1: AcalaOracleConfig {
 			members: Default::default(), // initialized by OperatorMembership
 			phantom: Default::default(),
@@ -389,6 +386,7 @@ fn testnet_genesis(
 			phantom: Default::default(),
 		},
 		module_evm: EVMConfig {
+			chain_id: 595,
 			accounts: evm_genesis_accounts,
 		},
 		module_staking_pool: StakingPoolConfig {
@@ -426,13 +424,13 @@ fn mandala_genesis(
 	endowed_accounts: Vec<AccountId>,
 ) -> mandala_runtime::GenesisConfig {
 	use mandala_runtime::{
-		cent, dollar, get_all_module_accounts, AcalaOracleConfig, AirDropConfig, AirDropCurrencyId, BabeConfig,
-		Balance, BalancesConfig, BandOracleConfig, CdpEngineConfig, CdpTreasuryConfig, DexConfig, EVMConfig,
-		EnabledTradingPairs, GeneralCouncilMembershipConfig, GrandpaConfig, HomaCouncilMembershipConfig,
-		HonzonCouncilMembershipConfig, IndicesConfig, NativeTokenExistentialDeposit, OperatorMembershipAcalaConfig,
-		OperatorMembershipBandConfig, OrmlNFTConfig, RenVmBridgeConfig, SessionConfig, StakerStatus, StakingConfig,
-		StakingPoolConfig, SudoConfig, SystemConfig, TechnicalCommitteeMembershipConfig, TokensConfig, VestingConfig,
-		ACA, AUSD, DOT, LDOT, RENBTC, XBTC, BABE_GENESIS_EPOCH_CONFIG, UnreleasedNativeVaultAccountId,
+		cent, dollar, get_all_module_accounts, AcalaOracleConfig, BabeConfig, Balance, BalancesConfig,
+		BandOracleConfig, CdpEngineConfig, CdpTreasuryConfig, DexConfig, EVMConfig, EnabledTradingPairs,
+		GeneralCouncilMembershipConfig, GrandpaConfig, HomaCouncilMembershipConfig, HonzonCouncilMembershipConfig,
+		IndicesConfig, NativeTokenExistentialDeposit, OperatorMembershipAcalaConfig, OperatorMembershipBandConfig,
+		OrmlNFTConfig, RenVmBridgeConfig, SessionConfig, StakerStatus, StakingConfig, StakingPoolConfig, SudoConfig,
+		SystemConfig, TechnicalCommitteeMembershipConfig, TokensConfig, UnreleasedNativeVaultAccountId, VestingConfig,
+		ACA, AUSD, BABE_GENESIS_EPOCH_CONFIG, DOT, LDOT, RENBTC, XBTC,
 	};
 	#[cfg(feature = "std")]
 	use sp_std::collections::btree_map::BTreeMap;
@@ -583,28 +581,6 @@ fn mandala_genesis(
 			],
 			global_interest_rate_per_sec: FixedU128::saturating_from_rational(1_547_126_000u128, 1_000_000_000_000_000_000u128), /* 5% APR */
 		},
-		module_airdrop: AirDropConfig {
-			airdrop_accounts: {
-				let aca_airdrop_accounts_json =
-					&include_bytes!("../../../../../resources/mandala-airdrop-ACA.json")[..];
-				let aca_airdrop_accounts: Vec<(AccountId, Balance)> =
-					serde_json::from_slice(aca_airdrop_accounts_json).unwrap();
-				let kar_airdrop_accounts_json =
-					&include_bytes!("../../../../../resources/mandala-airdrop-KAR.json")[..];
-				let kar_airdrop_accounts: Vec<(AccountId, Balance)> =
-					serde_json::from_slice(kar_airdrop_accounts_json).unwrap();
-
-				aca_airdrop_accounts
-					.iter()
-					.map(|(account_id, aca_amount)| (account_id.clone(), AirDropCurrencyId::ACA, *aca_amount))
-					.chain(
-						kar_airdrop_accounts
-							.iter()
-							.map(|(account_id, kar_amount)| (account_id.clone(), AirDropCurrencyId::KAR, *kar_amount)),
-					)
-					.collect::<Vec<_>>()
-			},
-		},
 		orml_oracle_Instance1: AcalaOracleConfig {
 			members: Default::default(), // initialized by OperatorMembership
 			phantom: Default::default(),
@@ -614,6 +590,7 @@ fn mandala_genesis(
 			phantom: Default::default(),
 		},
 		module_evm: EVMConfig {
+			chain_id: 595,
 			accounts: evm_genesis_accounts,
 		},
 		module_staking_pool: StakingPoolConfig {
@@ -221,8 +221,8 @@ fn testnet_genesis(
 	endowed_accounts: Vec<AccountId>,
 ) -> mandala_runtime::GenesisConfig {
 	use mandala_runtime::{
-		dollar, get_all_module_accounts, AcalaOracleConfig, AirDropConfig, Balance, BalancesConfig, BandOracleConfig,
-		CdpEngineConfig, CdpTreasuryConfig, DexConfig, EVMConfig, EnabledTradingPairs, GeneralCouncilMembershipConfig,
+		dollar, get_all_module_accounts, AcalaOracleConfig, Balance, BalancesConfig, BandOracleConfig, CdpEngineConfig,
+		CdpTreasuryConfig, DexConfig, EVMConfig, EnabledTradingPairs, GeneralCouncilMembershipConfig,
 		HomaCouncilMembershipConfig, HonzonCouncilMembershipConfig, IndicesConfig, NativeTokenExistentialDeposit,
 		OperatorMembershipAcalaConfig, OperatorMembershipBandConfig, OrmlNFTConfig, ParachainInfoConfig,
 		RenVmBridgeConfig, StakingPoolConfig, SudoConfig, SystemConfig, TechnicalCommitteeMembershipConfig,
@@ -355,9 +355,6 @@ fn testnet_genesis(
 				1_000_000_000_000_000_000u128,
 			), /* 5% APR */
 		},
-		module_airdrop: AirDropConfig {
-			airdrop_accounts: vec![],
-		},
 		orml_oracle_Instance1: AcalaOracleConfig {
 			members: Default::default(), // initialized by OperatorMembership
 			phantom: Default::default(),
@@ -367,6 +364,7 @@ fn testnet_genesis(
 			phantom: Default::default(),
 		},
 		module_evm: EVMConfig {
+			chain_id: 595,
 			accounts: evm_genesis_accounts,
 		},
 		module_staking_pool: StakingPoolConfig {
@@ -400,13 +398,12 @@ fn mandala_genesis(
 	endowed_accounts: Vec<AccountId>,
 ) -> mandala_runtime::GenesisConfig {
 	use mandala_runtime::{
-		cent, dollar, get_all_module_accounts, AcalaOracleConfig, AirDropConfig, AirDropCurrencyId, Balance,
-		BalancesConfig, BandOracleConfig, CdpEngineConfig, CdpTreasuryConfig, DexConfig, EVMConfig,
-		EnabledTradingPairs, GeneralCouncilMembershipConfig, HomaCouncilMembershipConfig,
-		HonzonCouncilMembershipConfig, IndicesConfig, NativeTokenExistentialDeposit, OperatorMembershipAcalaConfig,
-		OperatorMembershipBandConfig, OrmlNFTConfig, ParachainInfoConfig, RenVmBridgeConfig, StakingPoolConfig,
-		SudoConfig, SystemConfig, TechnicalCommitteeMembershipConfig, TokensConfig, UnreleasedNativeVaultAccountId,
-		VestingConfig, ACA, AUSD, DOT, LDOT, RENBTC, XBTC,
+		cent, dollar, get_all_module_accounts, AcalaOracleConfig, Balance, BalancesConfig, BandOracleConfig,
+		CdpEngineConfig, CdpTreasuryConfig, DexConfig, EVMConfig, EnabledTradingPairs, GeneralCouncilMembershipConfig,
+		HomaCouncilMembershipConfig, HonzonCouncilMembershipConfig, IndicesConfig, NativeTokenExistentialDeposit,
+		OperatorMembershipAcalaConfig, OperatorMembershipBandConfig, OrmlNFTConfig, ParachainInfoConfig,
+		RenVmBridgeConfig, StakingPoolConfig, SudoConfig, SystemConfig, TechnicalCommitteeMembershipConfig,
+		TokensConfig, UnreleasedNativeVaultAccountId, VestingConfig, ACA, AUSD, DOT, LDOT, RENBTC, XBTC,
 	};
 	#[cfg(feature = "std")]
 	use sp_std::collections::btree_map::BTreeMap;
@@ -538,28 +535,6 @@ fn mandala_genesis(
 				1_000_000_000_000_000_000u128,
 			), /* 5% APR */
 		},
-		module_airdrop: AirDropConfig {
-			airdrop_accounts: {
-				let aca_airdrop_accounts_json =
-					&include_bytes!("../../../../../resources/mandala-airdrop-ACA.json")[..];
-				let aca_airdrop_accounts: Vec<(AccountId, Balance)> =
-					serde_json::from_slice(aca_airdrop_accounts_json).unwrap();
-				let kar_airdrop_accounts_json =
-					&include_bytes!("../../../../../resources/mandala-airdrop-KAR.json")[..];
-				let kar_airdrop_accounts: Vec<(AccountId, Balance)> =
-					serde_json::from_slice(kar_airdrop_accounts_json).unwrap();
-
-				aca_airdrop_accounts
-					.iter()
-					.map(|(account_id, aca_amount)| (account_id.clone(), AirDropCurrencyId::ACA, *aca_amount))
-					.chain(
-						kar_airdrop_accounts
-							.iter()
-							.map(|(account_id, kar_amount)| (account_id.clone(), AirDropCurrencyId::KAR, *kar_amount)),
-					)
-					.collect::<Vec<_>>()
-			},
-		},
 		orml_oracle_Instance1: AcalaOracleConfig {
 			members: Default::default(), // initialized by OperatorMembership
 			phantom: Default::default(),
@@ -569,6 +544,7 @@ fn mandala_genesis(
 			phantom: Default::default(),
 		},
 		module_evm: EVMConfig {
+			chain_id: 595,
 			accounts: evm_genesis_accounts,
 		},
 		module_staking_pool: StakingPoolConfig {
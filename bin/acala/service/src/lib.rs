@@ -265,11 +265,12 @@ where
 		let client = client.clone();
 		let transaction_pool = transaction_pool.clone();
 
-		Box::new(move |deny_unsafe, _| -> acala_rpc::RpcExtension {
+		Box::new(move |deny_unsafe, subscription_executor| -> acala_rpc::RpcExtension {
 			let deps = acala_rpc::FullDeps {
 				client: client.clone(),
 				pool: transaction_pool.clone(),
 				deny_unsafe,
+				subscription_executor,
 			};
 
 			acala_rpc::create_full(deps)
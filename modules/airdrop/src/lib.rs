@@ -16,81 +16,363 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+//! # Airdrop Module
+//!
+//! ## Overview
+//!
+//! A merkle distributor. Governance creates a drop by posting the merkle root
+//! of the `(account, amount)` entitlements together with the total amount,
+//! which is moved from the treasury into the module account. Recipients claim
+//! their own entitlement by supplying a merkle proof, so the size of a drop
+//! does not affect on-chain state until it is claimed. Once the drop expires,
+//! anyone may return the unclaimed remainder to the treasury.
+//!
+//! Leaves are `blake2_256((account, amount).encode())`, and inner nodes hash
+//! the concatenation of their two children in ascending order.
+//!
+//! The claim records of a reclaimed drop are cleared in bounded batches by
+//! `clear_claimed`.
+
 #![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
 
-use frame_support::{decl_event, decl_module, decl_storage, transactional};
-use frame_system::{self as system, ensure_root};
-use primitives::{AirDropCurrencyId, Balance};
-use sp_runtime::traits::StaticLookup;
+use codec::{Decode, Encode};
+use frame_support::{pallet_prelude::*, storage::migration::storage_iter, transactional, PalletId};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use primitives::{Balance, CurrencyId, TokenSymbol};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating, Zero},
+	RuntimeDebug,
+};
+use sp_std::prelude::*;
 
 mod mock;
 mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub type DropId = u32;
 
-pub trait Config: system::Config {
-	type Event: From<Event<Self>> + Into<<Self as system::Config>::Event>;
+/// A merkle airdrop.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct DropInfo<BlockNumber> {
+	/// The merkle root of the `(account, amount)` entitlements.
+	pub merkle_root: H256,
+	/// The currency being dropped.
+	pub currency_id: CurrencyId,
+	/// The total amount reserved for the drop.
+	pub total: Balance,
+	/// The amount claimed so far.
+	pub claimed: Balance,
+	/// The block from which claims are closed and the remainder is reclaimable.
+	pub expiry: BlockNumber,
 }
 
-decl_storage! {
-	trait Store for Module<T: Config> as AirDrop {
-		AirDrops get(fn airdrops): double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) AirDropCurrencyId => Balance;
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency used to pay out drops.
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The airdrop module id, keeps the funds of open drops.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The account that funds drops and receives unclaimed funds.
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The origin which may create drops.
+		type CreateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
 	}
 
-	add_extra_genesis {
-		config(airdrop_accounts): Vec<(T::AccountId, AirDropCurrencyId, Balance)>;
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The drop does not exist
+		DropNotFound,
+		/// The total of the drop is zero
+		InvalidTotal,
+		/// The expiry is not in the future
+		InvalidExpiry,
+		/// The drop has expired
+		DropExpired,
+		/// The drop has not expired yet
+		DropNotExpired,
+		/// The account has already claimed from this drop
+		AlreadyClaimed,
+		/// The merkle proof does not match the root of the drop
+		InvalidProof,
+		/// The claim exceeds the unclaimed amount of the drop
+		ExceedsTotal,
+		/// No more drop ids are available
+		NoAvailableDropId,
+		/// The drop is still open, its claim records can not be cleared
+		DropNotReclaimed,
+	}
 
-		build(|config: &GenesisConfig<T>| {
-			config.airdrop_accounts.iter().for_each(|(account_id, airdrop_currency_id, initial_balance)| {
-				<AirDrops<T>>::mutate(account_id, airdrop_currency_id, | amount | *amount += *initial_balance)
-			})
-		})
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId", T::BlockNumber = "BlockNumber")]
+	pub enum Event<T: Config> {
+		/// Created a drop. \[drop_id, merkle_root, currency_id, total, expiry\]
+		DropCreated(DropId, H256, CurrencyId, Balance, T::BlockNumber),
+		/// Claimed from a drop. \[drop_id, who, amount\]
+		Claimed(DropId, T::AccountId, Balance),
+		/// Returned the unclaimed remainder of an expired drop to the
+		/// treasury. \[drop_id, amount\]
+		Reclaimed(DropId, Balance),
+		/// Cleared claim records of a reclaimed drop. \[drop_id, count\]
+		ClaimedCleared(DropId, u32),
 	}
-}
 
-decl_event!(
-	pub enum Event<T> where
-		<T as system::Config>::AccountId,
-		AirDropCurrencyId = AirDropCurrencyId,
-		Balance = Balance,
-	{
-		/// \[to, currency_id, amount\]
-		Airdrop(AccountId, AirDropCurrencyId, Balance),
-		/// \[to, currency_id, amount\]
-		UpdateAirdrop(AccountId, AirDropCurrencyId, Balance),
+	/// The id of the next drop.
+	#[pallet::storage]
+	#[pallet::getter(fn next_drop_id)]
+	pub type NextDropId<T: Config> = StorageValue<_, DropId, ValueQuery>;
+
+	/// The open drops.
+	///
+	/// map DropId => Option<DropInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn drops)]
+	pub type Drops<T: Config> = StorageMap<_, Twox64Concat, DropId, DropInfo<T::BlockNumber>, OptionQuery>;
+
+	/// The accounts that have claimed from a drop.
+	///
+	/// double_map DropId, AccountId => bool
+	#[pallet::storage]
+	#[pallet::getter(fn claimed)]
+	pub type Claimed<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, DropId, Twox64Concat, T::AccountId, bool, ValueQuery>;
+
+	/// The airdrop amounts recorded by the legacy airdrop module, which are
+	/// not funded. Kept for governance to create drops covering them.
+	///
+	/// double_map AccountId, CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn legacy_airdrops)]
+	pub type LegacyAirDrops<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// Whether the records of the legacy airdrop module have been migrated.
+	#[pallet::storage]
+	pub(crate) type UpgradedFromLegacyAirDrops<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Move the records of the legacy `AirDrops` storage, keyed by the
+		/// removed `AirDropCurrencyId`, to `LegacyAirDrops`.
+		fn on_runtime_upgrade() -> Weight {
+			if UpgradedFromLegacyAirDrops::<T>::get() {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut count: Weight = 0;
+			for (key, amount) in storage_iter::<Balance>(LEGACY_MODULE_PREFIX, LEGACY_AIRDROPS_PREFIX).drain() {
+				if let Some((who, currency_id)) = Self::decode_legacy_key(&key) {
+					LegacyAirDrops::<T>::mutate(who, currency_id, |total| *total = total.saturating_add(amount));
+				}
+				count += 1;
+			}
+			UpgradedFromLegacyAirDrops::<T>::put(true);
+
+			T::DbWeight::get().reads_writes(count.saturating_mul(2) + 1, count.saturating_mul(2) + 1)
+		}
 	}
-);
 
-decl_module! {
-	pub struct Module<T: Config> for enum Call where origin: T::Origin {
-		fn deposit_event() = default;
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Create a drop funded by the treasury.
+		///
+		/// The dispatch origin of this call must be `CreateOrigin`.
+		///
+		/// - `merkle_root`: the merkle root of the `(account, amount)`
+		///   entitlements
+		/// - `currency_id`: the currency to drop
+		/// - `total`: the sum of all entitlements
+		/// - `expiry`: the block from which claims are closed
+		#[pallet::weight(T::WeightInfo::create_drop())]
+		#[transactional]
+		pub fn create_drop(
+			origin: OriginFor<T>,
+			merkle_root: H256,
+			currency_id: CurrencyId,
+			#[pallet::compact] total: Balance,
+			expiry: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			T::CreateOrigin::ensure_origin(origin)?;
+			ensure!(!total.is_zero(), Error::<T>::InvalidTotal);
+			ensure!(
+				expiry > <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::InvalidExpiry
+			);
+
+			let drop_id = NextDropId::<T>::try_mutate(|id| -> Result<DropId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(1).ok_or(Error::<T>::NoAvailableDropId)?;
+				Ok(current_id)
+			})?;
+
+			T::Currency::transfer(currency_id, &T::TreasuryAccount::get(), &Self::account_id(), total)?;
+			Drops::<T>::insert(
+				drop_id,
+				DropInfo {
+					merkle_root,
+					currency_id,
+					total,
+					claimed: Zero::zero(),
+					expiry,
+				},
+			);
 
-		#[weight = 10_000]
+			Self::deposit_event(Event::DropCreated(drop_id, merkle_root, currency_id, total, expiry));
+			Ok(().into())
+		}
+
+		/// Claim the entitlement of the caller from a drop.
+		///
+		/// - `drop_id`: the drop to claim from
+		/// - `amount`: the entitlement of the caller
+		/// - `proof`: the sibling hashes from the leaf up to the root
+		#[pallet::weight(T::WeightInfo::claim(proof.len() as u32))]
 		#[transactional]
-		pub fn airdrop(
-			origin,
-			to: <T::Lookup as StaticLookup>::Source,
-			currency_id: AirDropCurrencyId,
-			amount: Balance,
-		) {
-			ensure_root(origin)?;
-			let to = T::Lookup::lookup(to)?;
-			<AirDrops<T>>::mutate(&to, currency_id, |balance| *balance += amount);
-			Self::deposit_event(RawEvent::Airdrop(to, currency_id, amount));
+		pub fn claim(
+			origin: OriginFor<T>,
+			drop_id: DropId,
+			#[pallet::compact] amount: Balance,
+			proof: Vec<H256>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!Claimed::<T>::get(drop_id, &who), Error::<T>::AlreadyClaimed);
+
+			Drops::<T>::try_mutate(drop_id, |maybe_drop| -> DispatchResult {
+				let drop = maybe_drop.as_mut().ok_or(Error::<T>::DropNotFound)?;
+				ensure!(
+					<frame_system::Pallet<T>>::block_number() < drop.expiry,
+					Error::<T>::DropExpired
+				);
+				ensure!(
+					Self::verify_proof(&who, amount, &proof, drop.merkle_root),
+					Error::<T>::InvalidProof
+				);
+
+				drop.claimed = drop.claimed.saturating_add(amount);
+				ensure!(drop.claimed <= drop.total, Error::<T>::ExceedsTotal);
+
+				T::Currency::transfer(drop.currency_id, &Self::account_id(), &who, amount)
+			})?;
+			Claimed::<T>::insert(drop_id, &who, true);
+
+			Self::deposit_event(Event::Claimed(drop_id, who, amount));
+			Ok(().into())
+		}
+
+		/// Return the unclaimed remainder of an expired drop to the treasury
+		/// and remove the drop.
+		///
+		/// - `drop_id`: the expired drop
+		#[pallet::weight(T::WeightInfo::reclaim())]
+		#[transactional]
+		pub fn reclaim(origin: OriginFor<T>, drop_id: DropId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let drop = Drops::<T>::get(drop_id).ok_or(Error::<T>::DropNotFound)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= drop.expiry,
+				Error::<T>::DropNotExpired
+			);
+
+			let remainder = drop.total.saturating_sub(drop.claimed);
+			T::Currency::transfer(
+				drop.currency_id,
+				&Self::account_id(),
+				&T::TreasuryAccount::get(),
+				remainder,
+			)?;
+			Drops::<T>::remove(drop_id);
+
+			Self::deposit_event(Event::Reclaimed(drop_id, remainder));
+			Ok(().into())
 		}
 
-		#[weight = 10_000]
+		/// Clear at most `limit` claim records of a reclaimed drop.
+		///
+		/// - `drop_id`: the reclaimed drop
+		/// - `limit`: the max number of records to clear
+		#[pallet::weight(T::WeightInfo::clear_claimed(*limit))]
 		#[transactional]
-		pub fn update_airdrop(
-			origin,
-			to: <T::Lookup as StaticLookup>::Source,
-			currency_id: AirDropCurrencyId,
-			amount: Balance,
-		) {
-			ensure_root(origin)?;
-			let to = T::Lookup::lookup(to)?;
-			<AirDrops<T>>::insert(&to, currency_id, amount);
-			Self::deposit_event(RawEvent::UpdateAirdrop(to, currency_id, amount));
+		pub fn clear_claimed(origin: OriginFor<T>, drop_id: DropId, limit: u32) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(
+				drop_id < Self::next_drop_id() && !Drops::<T>::contains_key(drop_id),
+				Error::<T>::DropNotReclaimed
+			);
+
+			let accounts = Claimed::<T>::iter_prefix(drop_id)
+				.take(limit as usize)
+				.map(|(who, _)| who)
+				.collect::<Vec<_>>();
+			let count = accounts.len() as u32;
+			for who in accounts {
+				Claimed::<T>::remove(drop_id, who);
+			}
+
+			Self::deposit_event(Event::ClaimedCleared(drop_id, count));
+			Ok(Some(T::WeightInfo::clear_claimed(count)).into())
 		}
 	}
 }
 
-impl<T: Config> Pallet<T> {}
+/// The storage prefixes of the legacy `AirDrops` storage.
+const LEGACY_MODULE_PREFIX: &[u8] = b"AirDrop";
+const LEGACY_AIRDROPS_PREFIX: &[u8] = b"AirDrops";
+
+impl<T: Config> Pallet<T> {
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// The leaf of an entitlement in the merkle tree.
+	pub fn leaf_hash(who: &T::AccountId, amount: Balance) -> H256 {
+		sp_io::hashing::blake2_256(&(who, amount).encode()).into()
+	}
+
+	/// Check that `proof` links the entitlement of `who` to `root`.
+	pub fn verify_proof(who: &T::AccountId, amount: Balance, proof: &[H256], root: H256) -> bool {
+		let computed = proof.iter().fold(Self::leaf_hash(who, amount), |node, sibling| {
+			let (left, right) = if node <= *sibling {
+				(node, *sibling)
+			} else {
+				(*sibling, node)
+			};
+			sp_io::hashing::blake2_256(&[left.as_bytes(), right.as_bytes()].concat()).into()
+		});
+		computed == root
+	}
+
+	/// Decode the `twox_64_concat(account) ++ twox_64_concat(currency)` key
+	/// of the legacy storage, the legacy currency is `KAR = 0` or `ACA = 1`.
+	fn decode_legacy_key(key: &[u8]) -> Option<(T::AccountId, CurrencyId)> {
+		let mut input = key.get(8..)?;
+		let who = T::AccountId::decode(&mut input).ok()?;
+		let symbol = match input.get(8)? {
+			0 => TokenSymbol::KAR,
+			1 => TokenSymbol::ACA,
+			_ => return None,
+		};
+		Some((who, CurrencyId::Token(symbol)))
+	}
+}
@@ -22,17 +22,19 @@
 
 use super::*;
 use frame_support::{construct_runtime, parameter_types};
-use sp_core::H256;
+use frame_system::EnsureRoot;
+use orml_traits::parameter_type_with_key;
+use primitives::{Amount, TokenSymbol};
 use sp_runtime::{testing::Header, traits::IdentityLookup};
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
 
-pub const ALICE: AccountId = 0;
-pub const BOB: AccountId = 1;
-pub const CHARLIE: AccountId = 2;
-pub const ACA: AirDropCurrencyId = AirDropCurrencyId::ACA;
-pub const KAR: AirDropCurrencyId = AirDropCurrencyId::KAR;
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const CHARLIE: AccountId = 3;
+pub const TREASURY: AccountId = 10;
+pub const ACA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 
 mod airdrop {
 	pub use super::super::*;
@@ -68,31 +70,62 @@ impl frame_system::Config for Runtime {
 	type OnSetCode = ();
 }
 
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+}
+
+parameter_types! {
+	pub const AirdropPalletId: PalletId = PalletId(*b"aca/aird");
+	pub const TreasuryAccount: AccountId = TREASURY;
+}
+
 impl Config for Runtime {
 	type Event = Event;
+	type Currency = Tokens;
+	type PalletId = AirdropPalletId;
+	type TreasuryAccount = TreasuryAccount;
+	type CreateOrigin = EnsureRoot<AccountId>;
+	type WeightInfo = ();
 }
 
-pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
-pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, Call, u32, ()>;
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
 
 construct_runtime!(
 	pub enum Runtime where
 		Block = Block,
 		NodeBlock = Block,
-		UncheckedExtrinsic = UncheckedExtrinsic
+		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
-		AirDrop: airdrop::{Pallet, Call, Storage, Event<T>, Config<T>},
+		AirDrop: airdrop::{Pallet, Call, Storage, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
 	}
 );
 
 pub type Airdrop = Pallet<Runtime>;
 
-pub struct ExtBuilder();
+pub struct ExtBuilder {
+	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
+}
 
 impl Default for ExtBuilder {
 	fn default() -> Self {
-		Self()
+		Self {
+			endowed_accounts: vec![(TREASURY, ACA, 1_000)],
+		}
 	}
 }
 
@@ -102,11 +135,14 @@ impl ExtBuilder {
 			.build_storage::<Runtime>()
 			.unwrap();
 
-		airdrop::GenesisConfig::<Runtime> {
-			airdrop_accounts: vec![(CHARLIE, KAR, 100), (CHARLIE, KAR, 50), (CHARLIE, ACA, 80)],
+		orml_tokens::GenesisConfig::<Runtime> {
+			endowed_accounts: self.endowed_accounts,
 		}
 		.assimilate_storage(&mut t)
 		.unwrap();
-		t.into()
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
 	}
 }
@@ -21,43 +21,216 @@
 #![cfg(test)]
 
 use super::*;
-use frame_support::{assert_noop, assert_ok};
-use mock::{Airdrop, Event, ExtBuilder, Origin, System, ACA, ALICE, BOB, CHARLIE, KAR};
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::migration::{get_storage_value, put_storage_value},
+	StorageHasher,
+};
+use mock::{
+	AccountId, Airdrop, Event, ExtBuilder, Origin, Runtime, System, Tokens, ACA, ALICE, BOB, CHARLIE, TREASURY,
+};
+use orml_traits::MultiCurrency;
 use sp_runtime::traits::BadOrigin;
 
+fn hash_pair(a: H256, b: H256) -> H256 {
+	let (left, right) = if a <= b { (a, b) } else { (b, a) };
+	sp_io::hashing::blake2_256(&[left.as_bytes(), right.as_bytes()].concat()).into()
+}
+
+fn leaf(who: AccountId, amount: Balance) -> H256 {
+	Airdrop::leaf_hash(&who, amount)
+}
+
+// root
+// ├── node(ALICE 100, BOB 200)
+// └── CHARLIE 300
+fn merkle_root() -> H256 {
+	hash_pair(hash_pair(leaf(ALICE, 100), leaf(BOB, 200)), leaf(CHARLIE, 300))
+}
+
+fn proof_of(who: AccountId) -> Vec<H256> {
+	match who {
+		ALICE => vec![leaf(BOB, 200), leaf(CHARLIE, 300)],
+		BOB => vec![leaf(ALICE, 100), leaf(CHARLIE, 300)],
+		_ => vec![hash_pair(leaf(ALICE, 100), leaf(BOB, 200))],
+	}
+}
+
+#[test]
+fn create_drop_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Airdrop::create_drop(Origin::signed(ALICE), merkle_root(), ACA, 600, 10),
+			BadOrigin
+		);
+		assert_noop!(
+			Airdrop::create_drop(Origin::root(), merkle_root(), ACA, 0, 10),
+			Error::<Runtime>::InvalidTotal
+		);
+		assert_noop!(
+			Airdrop::create_drop(Origin::root(), merkle_root(), ACA, 600, 1),
+			Error::<Runtime>::InvalidExpiry
+		);
+
+		assert_ok!(Airdrop::create_drop(Origin::root(), merkle_root(), ACA, 600, 10));
+		let drop_created_event = Event::airdrop(crate::Event::DropCreated(0, merkle_root(), ACA, 600, 10));
+		assert!(System::events().iter().any(|record| record.event == drop_created_event));
+
+		assert_eq!(Airdrop::next_drop_id(), 1);
+		assert_eq!(
+			Airdrop::drops(0),
+			Some(DropInfo {
+				merkle_root: merkle_root(),
+				currency_id: ACA,
+				total: 600,
+				claimed: 0,
+				expiry: 10,
+			})
+		);
+		assert_eq!(Tokens::free_balance(ACA, &TREASURY), 400);
+		assert_eq!(Tokens::free_balance(ACA, &Airdrop::account_id()), 600);
+	});
+}
+
+#[test]
+fn claim_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Airdrop::create_drop(Origin::root(), merkle_root(), ACA, 600, 10));
+
+		assert_noop!(
+			Airdrop::claim(Origin::signed(ALICE), 1, 100, proof_of(ALICE)),
+			Error::<Runtime>::DropNotFound
+		);
+		assert_noop!(
+			Airdrop::claim(Origin::signed(ALICE), 0, 200, proof_of(ALICE)),
+			Error::<Runtime>::InvalidProof
+		);
+		assert_noop!(
+			Airdrop::claim(Origin::signed(ALICE), 0, 200, proof_of(BOB)),
+			Error::<Runtime>::InvalidProof
+		);
+
+		assert_ok!(Airdrop::claim(Origin::signed(ALICE), 0, 100, proof_of(ALICE)));
+		let claimed_event = Event::airdrop(crate::Event::Claimed(0, ALICE, 100));
+		assert!(System::events().iter().any(|record| record.event == claimed_event));
+		assert_eq!(Tokens::free_balance(ACA, &ALICE), 100);
+		assert_eq!(Airdrop::drops(0).unwrap().claimed, 100);
+		assert!(Airdrop::claimed(0, ALICE));
+
+		assert_noop!(
+			Airdrop::claim(Origin::signed(ALICE), 0, 100, proof_of(ALICE)),
+			Error::<Runtime>::AlreadyClaimed
+		);
+
+		assert_ok!(Airdrop::claim(Origin::signed(CHARLIE), 0, 300, proof_of(CHARLIE)));
+		assert_eq!(Tokens::free_balance(ACA, &CHARLIE), 300);
+		assert_eq!(Airdrop::drops(0).unwrap().claimed, 400);
+
+		System::set_block_number(10);
+		assert_noop!(
+			Airdrop::claim(Origin::signed(BOB), 0, 200, proof_of(BOB)),
+			Error::<Runtime>::DropExpired
+		);
+	});
+}
+
 #[test]
-fn airdrop_work() {
+fn claim_cannot_exceed_total() {
 	ExtBuilder::default().build().execute_with(|| {
-		System::set_block_number(1);
-		assert_noop!(Airdrop::airdrop(Origin::signed(BOB), ALICE, KAR, 10000), BadOrigin,);
-		assert_ok!(Airdrop::airdrop(Origin::root(), ALICE, KAR, 10000));
-		let airdrop_event = Event::airdrop(RawEvent::Airdrop(ALICE, KAR, 10000));
-		assert!(System::events().iter().any(|record| record.event == airdrop_event));
-		assert_eq!(Airdrop::airdrops(ALICE, KAR), 10000);
+		assert_ok!(Airdrop::create_drop(Origin::root(), merkle_root(), ACA, 250, 10));
+		assert_ok!(Airdrop::claim(Origin::signed(BOB), 0, 200, proof_of(BOB)));
+		assert_noop!(
+			Airdrop::claim(Origin::signed(ALICE), 0, 100, proof_of(ALICE)),
+			Error::<Runtime>::ExceedsTotal
+		);
 	});
 }
 
 #[test]
-fn update_airdrop_work() {
+fn reclaim_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		System::set_block_number(1);
-		assert_ok!(Airdrop::airdrop(Origin::root(), ALICE, ACA, 10000));
-		assert_ok!(Airdrop::airdrop(Origin::root(), ALICE, ACA, 10000));
-		assert_eq!(Airdrop::airdrops(ALICE, ACA), 20000);
-		assert_noop!(Airdrop::update_airdrop(Origin::signed(BOB), ALICE, ACA, 0), BadOrigin,);
-		assert_ok!(Airdrop::update_airdrop(Origin::root(), ALICE, ACA, 0));
-		let update_airdrop_event = Event::airdrop(RawEvent::UpdateAirdrop(ALICE, ACA, 0));
-		assert!(System::events()
-			.iter()
-			.any(|record| record.event == update_airdrop_event));
-		assert_eq!(Airdrop::airdrops(ALICE, ACA), 0);
+		assert_ok!(Airdrop::create_drop(Origin::root(), merkle_root(), ACA, 600, 10));
+		assert_ok!(Airdrop::claim(Origin::signed(BOB), 0, 200, proof_of(BOB)));
+
+		assert_noop!(
+			Airdrop::reclaim(Origin::signed(ALICE), 1),
+			Error::<Runtime>::DropNotFound
+		);
+		assert_noop!(
+			Airdrop::reclaim(Origin::signed(ALICE), 0),
+			Error::<Runtime>::DropNotExpired
+		);
+
+		System::set_block_number(10);
+		assert_ok!(Airdrop::reclaim(Origin::signed(ALICE), 0));
+		let reclaimed_event = Event::airdrop(crate::Event::Reclaimed(0, 400));
+		assert!(System::events().iter().any(|record| record.event == reclaimed_event));
+		assert_eq!(Tokens::free_balance(ACA, &TREASURY), 800);
+		assert_eq!(Tokens::free_balance(ACA, &Airdrop::account_id()), 0);
+		assert_eq!(Airdrop::drops(0), None);
+		assert!(Airdrop::claimed(0, BOB));
 	});
 }
 
 #[test]
-fn genesis_config_work() {
+fn clear_claimed_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(Airdrop::airdrops(CHARLIE, KAR), 150);
-		assert_eq!(Airdrop::airdrops(CHARLIE, ACA), 80);
+		assert_ok!(Airdrop::create_drop(Origin::root(), merkle_root(), ACA, 600, 10));
+		assert_ok!(Airdrop::claim(Origin::signed(ALICE), 0, 100, proof_of(ALICE)));
+		assert_ok!(Airdrop::claim(Origin::signed(BOB), 0, 200, proof_of(BOB)));
+
+		assert_noop!(
+			Airdrop::clear_claimed(Origin::signed(CHARLIE), 0, 10),
+			Error::<Runtime>::DropNotReclaimed
+		);
+		assert_noop!(
+			Airdrop::clear_claimed(Origin::signed(CHARLIE), 1, 10),
+			Error::<Runtime>::DropNotReclaimed
+		);
+
+		System::set_block_number(10);
+		assert_ok!(Airdrop::reclaim(Origin::signed(ALICE), 0));
+
+		assert_ok!(Airdrop::clear_claimed(Origin::signed(CHARLIE), 0, 1));
+		let cleared_event = Event::airdrop(crate::Event::ClaimedCleared(0, 1));
+		assert!(System::events().iter().any(|record| record.event == cleared_event));
+		assert_eq!(Claimed::<Runtime>::iter_prefix(0).count(), 1);
+
+		assert_ok!(Airdrop::clear_claimed(Origin::signed(CHARLIE), 0, 10));
+		assert!(!Airdrop::claimed(0, ALICE));
+		assert!(!Airdrop::claimed(0, BOB));
+	});
+}
+
+#[test]
+fn migrate_legacy_airdrops_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let legacy_key = |who: AccountId, currency: u8| {
+			[
+				Twox64Concat::hash(&who.encode()),
+				Twox64Concat::hash(&currency.encode()),
+			]
+			.concat()
+		};
+		put_storage_value(b"AirDrop", b"AirDrops", &legacy_key(ALICE, 0), 100 as Balance);
+		put_storage_value(b"AirDrop", b"AirDrops", &legacy_key(ALICE, 1), 200 as Balance);
+		put_storage_value(b"AirDrop", b"AirDrops", &legacy_key(BOB, 1), 300 as Balance);
+
+		Airdrop::on_runtime_upgrade();
+		assert_eq!(
+			Airdrop::legacy_airdrops(ALICE, CurrencyId::Token(TokenSymbol::KAR)),
+			100
+		);
+		assert_eq!(Airdrop::legacy_airdrops(ALICE, ACA), 200);
+		assert_eq!(Airdrop::legacy_airdrops(BOB, ACA), 300);
+		assert_eq!(
+			get_storage_value::<Balance>(b"AirDrop", b"AirDrops", &legacy_key(ALICE, 0)),
+			None
+		);
+
+		// only migrate once
+		put_storage_value(b"AirDrop", b"AirDrops", &legacy_key(BOB, 1), 300 as Balance);
+		Airdrop::on_runtime_upgrade();
+		assert_eq!(Airdrop::legacy_airdrops(BOB, ACA), 300);
 	});
 }
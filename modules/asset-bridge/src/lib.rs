@@ -0,0 +1,448 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Asset Bridge Module
+//!
+//! ## Overview
+//!
+//! A generic bridge for external assets. A set of attesters, identified by
+//! their Ethereum-style addresses, authorizes the minting of bridged assets:
+//! a mint is executed once `threshold` distinct attesters have signed it, and
+//! each source transaction can be minted only once. Attested messages include
+//! the genesis hash of the chain, so they cannot be replayed on another chain
+//! running the same attester set. Burning a bridged asset emits an event with
+//! the destination on the source chain for the attesters to release the funds.
+//!
+//! The attester set can be rotated by the current attesters or replaced by
+//! governance. Every bridged asset has a mint limit per `MintLimitPeriod`,
+//! and both single assets and the whole bridge can be paused.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode};
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use primitives::{evm::EvmAddress, Balance, CurrencyId};
+use sp_core::{ecdsa, H256};
+use sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256};
+use sp_runtime::{
+	traits::{Saturating, Zero},
+	RuntimeDebug,
+};
+use sp_std::{prelude::*, vec};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub type EcdsaSignature = ecdsa::Signature;
+pub type AttesterSetId = u32;
+pub type BurnId = u64;
+
+/// The bridge state of an asset.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct BridgedAssetInfo<BlockNumber> {
+	/// The maximum amount minted per `MintLimitPeriod`.
+	pub mint_limit: Balance,
+	/// The amount minted in the current period.
+	pub minted: Balance,
+	/// The first block of the current period.
+	pub period_start: BlockNumber,
+	/// Whether minting and burning of the asset is paused.
+	pub paused: bool,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency to mint and burn bridged assets.
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The maximum number of attesters.
+		#[pallet::constant]
+		type MaxAttesters: Get<u32>;
+
+		/// The length in blocks of a mint limit period.
+		#[pallet::constant]
+		type MintLimitPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum length of a burn destination.
+		#[pallet::constant]
+		type MaxDestinationLength: Get<u32>;
+
+		/// The origin which may replace attesters and update bridged assets.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may pause and unpause the bridge.
+		type PauseOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The bridge is paused
+		BridgePaused,
+		/// The asset is paused
+		AssetPaused,
+		/// The asset is not bridged
+		AssetNotBridged,
+		/// The mint exceeds the mint limit of the current period
+		MintLimitExceeded,
+		/// The source transaction has already been minted
+		AlreadyMinted,
+		/// A signature is invalid or not signed by an attester
+		InvalidSignature,
+		/// An attester signed more than once
+		DuplicateSignature,
+		/// Fewer than `threshold` attesters signed
+		InsufficientAttestations,
+		/// The threshold is zero or exceeds the number of attesters
+		InvalidThreshold,
+		/// The attester set exceeds `MaxAttesters`
+		TooManyAttesters,
+		/// The attester set contains an address more than once
+		DuplicateAttester,
+		/// The burn destination exceeds `MaxDestinationLength`
+		DestinationTooLong,
+		/// No more burn ids are available
+		BurnIdOverflow,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	pub enum Event<T: Config> {
+		/// Minted a bridged asset. \[source_tx, currency_id, who, amount\]
+		Minted(H256, CurrencyId, T::AccountId, Balance),
+		/// Burnt a bridged asset. \[burn_id, who, currency_id, amount,
+		/// destination\]
+		Burnt(BurnId, T::AccountId, CurrencyId, Balance, Vec<u8>),
+		/// Updated the attester set. \[attester_set_id, attesters,
+		/// threshold\]
+		AttestersUpdated(AttesterSetId, Vec<EvmAddress>, u32),
+		/// Updated the mint limit of a bridged asset. \[currency_id,
+		/// mint_limit\]
+		BridgedAssetUpdated(CurrencyId, Balance),
+		/// Paused or unpaused a bridged asset. \[currency_id, paused\]
+		AssetPauseUpdated(CurrencyId, bool),
+		/// Paused or unpaused the bridge. \[paused\]
+		BridgePauseUpdated(bool),
+	}
+
+	/// The current attesters.
+	#[pallet::storage]
+	#[pallet::getter(fn attesters)]
+	pub type Attesters<T: Config> = StorageValue<_, Vec<EvmAddress>, ValueQuery>;
+
+	/// The number of attesters required to sign a mint or rotation.
+	#[pallet::storage]
+	#[pallet::getter(fn threshold)]
+	pub type Threshold<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The id of the current attester set, signed over to prevent replaying
+	/// attestations of a previous set.
+	#[pallet::storage]
+	#[pallet::getter(fn attester_set_id)]
+	pub type CurrentAttesterSetId<T: Config> = StorageValue<_, AttesterSetId, ValueQuery>;
+
+	/// The bridged assets.
+	///
+	/// map CurrencyId => Option<BridgedAssetInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn bridged_assets)]
+	pub type BridgedAssets<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, BridgedAssetInfo<T::BlockNumber>, OptionQuery>;
+
+	/// The source transactions that have been minted.
+	///
+	/// map SourceTxHash => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn processed_mints)]
+	pub type ProcessedMints<T: Config> = StorageMap<_, Identity, H256, (), OptionQuery>;
+
+	/// Whether the whole bridge is paused.
+	#[pallet::storage]
+	#[pallet::getter(fn bridge_paused)]
+	pub type BridgePaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The id of the next burn.
+	#[pallet::storage]
+	#[pallet::getter(fn next_burn_id)]
+	pub type NextBurnId<T: Config> = StorageValue<_, BurnId, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Mint a bridged asset attested by the attesters. Anyone may relay
+		/// the attestations.
+		///
+		/// - `source_tx`: the hash of the lock transaction on the source
+		///   chain
+		/// - `signatures`: signatures of at least `threshold` attesters over
+		///   `mint_message`
+		#[pallet::weight(T::WeightInfo::mint(signatures.len() as u32))]
+		#[transactional]
+		pub fn mint(
+			origin: OriginFor<T>,
+			source_tx: H256,
+			currency_id: CurrencyId,
+			who: T::AccountId,
+			#[pallet::compact] amount: Balance,
+			signatures: Vec<EcdsaSignature>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(!Self::bridge_paused(), Error::<T>::BridgePaused);
+			ensure!(!ProcessedMints::<T>::contains_key(source_tx), Error::<T>::AlreadyMinted);
+
+			let message = Self::mint_message(source_tx, currency_id, &who, amount);
+			Self::verify_attestations(&message, &signatures)?;
+
+			BridgedAssets::<T>::try_mutate(currency_id, |maybe_info| -> DispatchResult {
+				let info = maybe_info.as_mut().ok_or(Error::<T>::AssetNotBridged)?;
+				ensure!(!info.paused, Error::<T>::AssetPaused);
+
+				let now = <frame_system::Pallet<T>>::block_number();
+				if now >= info.period_start.saturating_add(T::MintLimitPeriod::get()) {
+					info.period_start = now;
+					info.minted = Zero::zero();
+				}
+				info.minted = info.minted.saturating_add(amount);
+				ensure!(info.minted <= info.mint_limit, Error::<T>::MintLimitExceeded);
+				Ok(())
+			})?;
+
+			T::Currency::deposit(currency_id, &who, amount)?;
+			ProcessedMints::<T>::insert(source_tx, ());
+
+			Self::deposit_event(Event::Minted(source_tx, currency_id, who, amount));
+			Ok(().into())
+		}
+
+		/// Burn a bridged asset to release it to `destination` on the source
+		/// chain.
+		#[pallet::weight(T::WeightInfo::burn())]
+		#[transactional]
+		pub fn burn(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
+			destination: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::bridge_paused(), Error::<T>::BridgePaused);
+			ensure!(
+				destination.len() as u32 <= T::MaxDestinationLength::get(),
+				Error::<T>::DestinationTooLong
+			);
+			let info = Self::bridged_assets(currency_id).ok_or(Error::<T>::AssetNotBridged)?;
+			ensure!(!info.paused, Error::<T>::AssetPaused);
+
+			let burn_id = NextBurnId::<T>::try_mutate(|id| -> Result<BurnId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(1).ok_or(Error::<T>::BurnIdOverflow)?;
+				Ok(current_id)
+			})?;
+			T::Currency::withdraw(currency_id, &who, amount)?;
+
+			Self::deposit_event(Event::Burnt(burn_id, who, currency_id, amount, destination));
+			Ok(().into())
+		}
+
+		/// Rotate the attester set, signed by the current attesters.
+		///
+		/// - `signatures`: signatures of at least `threshold` current
+		///   attesters over `rotation_message`
+		#[pallet::weight(T::WeightInfo::rotate_attesters(signatures.len() as u32))]
+		#[transactional]
+		pub fn rotate_attesters(
+			origin: OriginFor<T>,
+			new_attesters: Vec<EvmAddress>,
+			new_threshold: u32,
+			signatures: Vec<EcdsaSignature>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let message = Self::rotation_message(&new_attesters, new_threshold);
+			Self::verify_attestations(&message, &signatures)?;
+			Self::do_set_attesters(new_attesters, new_threshold)?;
+			Ok(().into())
+		}
+
+		/// Replace the attester set.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::force_set_attesters())]
+		pub fn force_set_attesters(
+			origin: OriginFor<T>,
+			attesters: Vec<EvmAddress>,
+			threshold: u32,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::do_set_attesters(attesters, threshold)?;
+			Ok(().into())
+		}
+
+		/// Add a bridged asset or update its mint limit.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_bridged_asset())]
+		pub fn set_bridged_asset(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] mint_limit: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			BridgedAssets::<T>::mutate(currency_id, |maybe_info| {
+				maybe_info.get_or_insert_with(Default::default).mint_limit = mint_limit;
+			});
+			Self::deposit_event(Event::BridgedAssetUpdated(currency_id, mint_limit));
+			Ok(().into())
+		}
+
+		/// Pause or unpause minting and burning of a bridged asset.
+		///
+		/// The dispatch origin of this call must be `PauseOrigin`.
+		#[pallet::weight(T::WeightInfo::set_asset_paused())]
+		pub fn set_asset_paused(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			paused: bool,
+		) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			BridgedAssets::<T>::try_mutate(currency_id, |maybe_info| -> DispatchResult {
+				maybe_info.as_mut().ok_or(Error::<T>::AssetNotBridged)?.paused = paused;
+				Ok(())
+			})?;
+			Self::deposit_event(Event::AssetPauseUpdated(currency_id, paused));
+			Ok(().into())
+		}
+
+		/// Pause or unpause the whole bridge.
+		///
+		/// The dispatch origin of this call must be `PauseOrigin`.
+		#[pallet::weight(T::WeightInfo::set_bridge_paused())]
+		pub fn set_bridge_paused(origin: OriginFor<T>, paused: bool) -> DispatchResultWithPostInfo {
+			T::PauseOrigin::ensure_origin(origin)?;
+			BridgePaused::<T>::put(paused);
+			Self::deposit_event(Event::BridgePauseUpdated(paused));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The genesis hash of the chain, signed as part of every message so that
+	/// attestations for one chain cannot be replayed on another.
+	pub fn domain_separator() -> T::Hash {
+		frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero())
+	}
+
+	/// The message attesters sign to authorize a mint.
+	pub fn mint_message(source_tx: H256, currency_id: CurrencyId, who: &T::AccountId, amount: Balance) -> [u8; 32] {
+		keccak_256(
+			&(
+				b"acala/bridge-mint",
+				Self::domain_separator(),
+				Self::attester_set_id(),
+				source_tx,
+				currency_id,
+				who,
+				amount,
+			)
+				.encode(),
+		)
+	}
+
+	/// The message attesters sign to rotate the attester set.
+	pub fn rotation_message(new_attesters: &[EvmAddress], new_threshold: u32) -> [u8; 32] {
+		keccak_256(
+			&(
+				b"acala/bridge-rotate",
+				Self::domain_separator(),
+				Self::attester_set_id(),
+				new_attesters,
+				new_threshold,
+			)
+				.encode(),
+		)
+	}
+
+	fn verify_attestations(message: &[u8; 32], signatures: &[EcdsaSignature]) -> DispatchResult {
+		let attesters = Self::attesters();
+		let mut signed = vec![false; attesters.len()];
+		for signature in signatures {
+			let signer = secp256k1_ecdsa_recover(&signature.0, message)
+				.map(|public| EvmAddress::from_slice(&keccak_256(&public)[12..]))
+				.map_err(|_| Error::<T>::InvalidSignature)?;
+			let index = attesters
+				.iter()
+				.position(|attester| *attester == signer)
+				.ok_or(Error::<T>::InvalidSignature)?;
+			ensure!(!signed[index], Error::<T>::DuplicateSignature);
+			signed[index] = true;
+		}
+
+		let threshold = Self::threshold();
+		ensure!(
+			threshold != 0 && signatures.len() as u32 >= threshold,
+			Error::<T>::InsufficientAttestations
+		);
+		Ok(())
+	}
+
+	fn do_set_attesters(attesters: Vec<EvmAddress>, threshold: u32) -> DispatchResult {
+		ensure!(
+			attesters.len() as u32 <= T::MaxAttesters::get(),
+			Error::<T>::TooManyAttesters
+		);
+		ensure!(
+			threshold != 0 && threshold as usize <= attesters.len(),
+			Error::<T>::InvalidThreshold
+		);
+		let mut unique = attesters.clone();
+		unique.sort();
+		unique.dedup();
+		ensure!(unique.len() == attesters.len(), Error::<T>::DuplicateAttester);
+
+		let attester_set_id = CurrentAttesterSetId::<T>::mutate(|id| {
+			*id = id.wrapping_add(1);
+			*id
+		});
+		Attesters::<T>::put(&attesters);
+		Threshold::<T>::put(threshold);
+
+		Self::deposit_event(Event::AttestersUpdated(attester_set_id, attesters, threshold));
+		Ok(())
+	}
+}
@@ -0,0 +1,171 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the asset bridge module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use orml_traits::parameter_type_with_key;
+use primitives::{Amount, TokenSymbol};
+use sp_runtime::{testing::Header, traits::IdentityLookup};
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const RENBTC: CurrencyId = CurrencyId::Token(TokenSymbol::RENBTC);
+pub const XBTC: CurrencyId = CurrencyId::Token(TokenSymbol::XBTC);
+
+mod asset_bridge {
+	pub use super::super::*;
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+}
+
+ord_parameter_types! {
+	pub const Council: AccountId = 10;
+	pub const TechnicalCommittee: AccountId = 11;
+}
+
+parameter_types! {
+	pub const MaxAttesters: u32 = 4;
+	pub const MintLimitPeriod: BlockNumber = 10;
+	pub const MaxDestinationLength: u32 = 32;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Currency = Tokens;
+	type MaxAttesters = MaxAttesters;
+	type MintLimitPeriod = MintLimitPeriod;
+	type MaxDestinationLength = MaxDestinationLength;
+	type UpdateOrigin = EnsureSignedBy<Council, AccountId>;
+	type PauseOrigin = EnsureSignedBy<TechnicalCommittee, AccountId>;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		AssetBridgeModule: asset_bridge::{Pallet, Call, Storage, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
+	}
+);
+
+pub fn attester(index: u8) -> secp256k1::SecretKey {
+	secp256k1::SecretKey::parse(&keccak_256(&[b'a', index])).unwrap()
+}
+
+pub fn attester_address(index: u8) -> EvmAddress {
+	let public = secp256k1::PublicKey::from_secret_key(&attester(index));
+	EvmAddress::from_slice(&keccak_256(&public.serialize()[1..65])[12..])
+}
+
+pub fn sign(index: u8, message: &[u8; 32]) -> EcdsaSignature {
+	let (signature, recovery_id) = secp256k1::sign(&secp256k1::Message::parse(message), &attester(index));
+	let mut r = [0u8; 65];
+	r[0..64].copy_from_slice(&signature.serialize()[..]);
+	r[64] = recovery_id.serialize();
+	EcdsaSignature::from_slice(&r)
+}
+
+pub struct ExtBuilder;
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| {
+			System::set_block_number(1);
+			Attesters::<Runtime>::put(vec![attester_address(0), attester_address(1), attester_address(2)]);
+			Threshold::<Runtime>::put(2);
+			BridgedAssets::<Runtime>::insert(
+				RENBTC,
+				BridgedAssetInfo {
+					mint_limit: 1_000,
+					..Default::default()
+				},
+			);
+		});
+		ext
+	}
+}
@@ -0,0 +1,321 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the asset bridge module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{
+	attester_address, sign, AccountId, AssetBridgeModule, Event, ExtBuilder, Origin, Runtime, System, Tokens, ALICE,
+	BOB, RENBTC, XBTC,
+};
+use sp_runtime::traits::BadOrigin;
+
+const COUNCIL: AccountId = 10;
+const TECHNICAL_COMMITTEE: AccountId = 11;
+
+fn source_tx(index: u8) -> H256 {
+	H256::repeat_byte(index)
+}
+
+fn attest_mint(
+	signers: &[u8],
+	tx: H256,
+	currency_id: CurrencyId,
+	who: AccountId,
+	amount: Balance,
+) -> Vec<EcdsaSignature> {
+	let message = AssetBridgeModule::mint_message(tx, currency_id, &who, amount);
+	signers.iter().map(|index| sign(*index, &message)).collect()
+}
+
+fn mint(
+	signers: &[u8],
+	tx: H256,
+	currency_id: CurrencyId,
+	who: AccountId,
+	amount: Balance,
+) -> DispatchResultWithPostInfo {
+	let signatures = attest_mint(signers, tx, currency_id, who, amount);
+	AssetBridgeModule::mint(Origin::signed(BOB), tx, currency_id, who, amount, signatures)
+}
+
+#[test]
+fn mint_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			mint(&[0], source_tx(1), RENBTC, ALICE, 100),
+			Error::<Runtime>::InsufficientAttestations
+		);
+		assert_noop!(
+			mint(&[0, 0], source_tx(1), RENBTC, ALICE, 100),
+			Error::<Runtime>::DuplicateSignature
+		);
+		assert_noop!(
+			mint(&[0, 9], source_tx(1), RENBTC, ALICE, 100),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_noop!(
+			AssetBridgeModule::mint(
+				Origin::signed(BOB),
+				source_tx(1),
+				RENBTC,
+				ALICE,
+				200,
+				attest_mint(&[0, 1], source_tx(1), RENBTC, ALICE, 100)
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_noop!(
+			mint(&[0, 1], source_tx(1), XBTC, ALICE, 100),
+			Error::<Runtime>::AssetNotBridged
+		);
+
+		assert_ok!(mint(&[0, 2], source_tx(1), RENBTC, ALICE, 100));
+		let minted_event = Event::asset_bridge(crate::Event::Minted(source_tx(1), RENBTC, ALICE, 100));
+		assert!(System::events().iter().any(|record| record.event == minted_event));
+		assert_eq!(Tokens::free_balance(RENBTC, &ALICE), 100);
+		assert_eq!(AssetBridgeModule::processed_mints(source_tx(1)), Some(()));
+
+		assert_noop!(
+			mint(&[0, 1], source_tx(1), RENBTC, ALICE, 100),
+			Error::<Runtime>::AlreadyMinted
+		);
+	});
+}
+
+#[test]
+fn attestations_are_bound_to_genesis_hash() {
+	ExtBuilder::default().build().execute_with(|| {
+		let signatures = attest_mint(&[0, 1], source_tx(1), RENBTC, ALICE, 100);
+		frame_system::BlockHash::<Runtime>::insert(0, H256::repeat_byte(0xff));
+		assert_noop!(
+			AssetBridgeModule::mint(Origin::signed(BOB), source_tx(1), RENBTC, ALICE, 100, signatures),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_ok!(mint(&[0, 1], source_tx(1), RENBTC, ALICE, 100));
+	});
+}
+
+#[test]
+fn mint_limit_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(mint(&[0, 1], source_tx(1), RENBTC, ALICE, 600));
+		assert_noop!(
+			mint(&[0, 1], source_tx(2), RENBTC, ALICE, 500),
+			Error::<Runtime>::MintLimitExceeded
+		);
+		assert_ok!(mint(&[0, 1], source_tx(2), RENBTC, ALICE, 400));
+		assert_eq!(AssetBridgeModule::bridged_assets(RENBTC).unwrap().minted, 1_000);
+
+		System::set_block_number(11);
+		assert_ok!(mint(&[0, 1], source_tx(3), RENBTC, ALICE, 500));
+		let info = AssetBridgeModule::bridged_assets(RENBTC).unwrap();
+		assert_eq!(info.minted, 500);
+		assert_eq!(info.period_start, 11);
+	});
+}
+
+#[test]
+fn burn_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(mint(&[0, 1], source_tx(1), RENBTC, ALICE, 100));
+
+		assert_noop!(
+			AssetBridgeModule::burn(Origin::signed(ALICE), RENBTC, 40, vec![0; 33]),
+			Error::<Runtime>::DestinationTooLong
+		);
+		assert_noop!(
+			AssetBridgeModule::burn(Origin::signed(ALICE), XBTC, 40, vec![1, 2, 3]),
+			Error::<Runtime>::AssetNotBridged
+		);
+
+		assert_ok!(AssetBridgeModule::burn(
+			Origin::signed(ALICE),
+			RENBTC,
+			40,
+			vec![1, 2, 3]
+		));
+		let burnt_event = Event::asset_bridge(crate::Event::Burnt(0, ALICE, RENBTC, 40, vec![1, 2, 3]));
+		assert!(System::events().iter().any(|record| record.event == burnt_event));
+		assert_eq!(Tokens::free_balance(RENBTC, &ALICE), 60);
+		assert_eq!(AssetBridgeModule::next_burn_id(), 1);
+	});
+}
+
+#[test]
+fn pause_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(mint(&[0, 1], source_tx(1), RENBTC, ALICE, 100));
+
+		assert_noop!(
+			AssetBridgeModule::set_asset_paused(Origin::signed(COUNCIL), RENBTC, true),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetBridgeModule::set_asset_paused(Origin::signed(TECHNICAL_COMMITTEE), XBTC, true),
+			Error::<Runtime>::AssetNotBridged
+		);
+
+		assert_ok!(AssetBridgeModule::set_asset_paused(
+			Origin::signed(TECHNICAL_COMMITTEE),
+			RENBTC,
+			true
+		));
+		let asset_paused_event = Event::asset_bridge(crate::Event::AssetPauseUpdated(RENBTC, true));
+		assert!(System::events().iter().any(|record| record.event == asset_paused_event));
+		assert_noop!(
+			mint(&[0, 1], source_tx(2), RENBTC, ALICE, 100),
+			Error::<Runtime>::AssetPaused
+		);
+		assert_noop!(
+			AssetBridgeModule::burn(Origin::signed(ALICE), RENBTC, 40, vec![1, 2, 3]),
+			Error::<Runtime>::AssetPaused
+		);
+		assert_ok!(AssetBridgeModule::set_asset_paused(
+			Origin::signed(TECHNICAL_COMMITTEE),
+			RENBTC,
+			false
+		));
+
+		assert_ok!(AssetBridgeModule::set_bridge_paused(
+			Origin::signed(TECHNICAL_COMMITTEE),
+			true
+		));
+		let bridge_paused_event = Event::asset_bridge(crate::Event::BridgePauseUpdated(true));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == bridge_paused_event));
+		assert_noop!(
+			mint(&[0, 1], source_tx(2), RENBTC, ALICE, 100),
+			Error::<Runtime>::BridgePaused
+		);
+		assert_noop!(
+			AssetBridgeModule::burn(Origin::signed(ALICE), RENBTC, 40, vec![1, 2, 3]),
+			Error::<Runtime>::BridgePaused
+		);
+
+		assert_ok!(AssetBridgeModule::set_bridge_paused(
+			Origin::signed(TECHNICAL_COMMITTEE),
+			false
+		));
+		assert_ok!(mint(&[0, 1], source_tx(2), RENBTC, ALICE, 100));
+	});
+}
+
+#[test]
+fn rotate_attesters_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let new_attesters = vec![attester_address(3), attester_address(4)];
+		let message = AssetBridgeModule::rotation_message(&new_attesters, 1);
+		let signatures = vec![sign(0, &message), sign(1, &message)];
+
+		assert_noop!(
+			AssetBridgeModule::rotate_attesters(Origin::signed(BOB), new_attesters.clone(), 1, vec![sign(0, &message)]),
+			Error::<Runtime>::InsufficientAttestations
+		);
+		assert_ok!(AssetBridgeModule::rotate_attesters(
+			Origin::signed(BOB),
+			new_attesters.clone(),
+			1,
+			signatures.clone()
+		));
+		let attesters_updated_event = Event::asset_bridge(crate::Event::AttestersUpdated(1, new_attesters.clone(), 1));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == attesters_updated_event));
+		assert_eq!(AssetBridgeModule::attesters(), new_attesters);
+		assert_eq!(AssetBridgeModule::threshold(), 1);
+		assert_eq!(AssetBridgeModule::attester_set_id(), 1);
+
+		// attestations of the previous set are rejected
+		assert_noop!(
+			AssetBridgeModule::rotate_attesters(Origin::signed(BOB), new_attesters, 1, signatures),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_noop!(
+			mint(&[0, 1], source_tx(1), RENBTC, ALICE, 100),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_ok!(mint(&[4], source_tx(1), RENBTC, ALICE, 100));
+	});
+}
+
+#[test]
+fn force_set_attesters_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let attesters = vec![attester_address(3), attester_address(4)];
+		assert_noop!(
+			AssetBridgeModule::force_set_attesters(Origin::signed(ALICE), attesters.clone(), 2),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetBridgeModule::force_set_attesters(Origin::signed(COUNCIL), attesters.clone(), 0),
+			Error::<Runtime>::InvalidThreshold
+		);
+		assert_noop!(
+			AssetBridgeModule::force_set_attesters(Origin::signed(COUNCIL), attesters.clone(), 3),
+			Error::<Runtime>::InvalidThreshold
+		);
+		assert_noop!(
+			AssetBridgeModule::force_set_attesters(Origin::signed(COUNCIL), (0..5).map(attester_address).collect(), 2),
+			Error::<Runtime>::TooManyAttesters
+		);
+		assert_noop!(
+			AssetBridgeModule::force_set_attesters(
+				Origin::signed(COUNCIL),
+				vec![attester_address(3), attester_address(3)],
+				2
+			),
+			Error::<Runtime>::DuplicateAttester
+		);
+
+		assert_ok!(AssetBridgeModule::force_set_attesters(
+			Origin::signed(COUNCIL),
+			attesters.clone(),
+			2
+		));
+		assert_eq!(AssetBridgeModule::attesters(), attesters);
+		assert_eq!(AssetBridgeModule::threshold(), 2);
+	});
+}
+
+#[test]
+fn set_bridged_asset_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AssetBridgeModule::set_bridged_asset(Origin::signed(ALICE), XBTC, 500),
+			BadOrigin
+		);
+		assert_ok!(AssetBridgeModule::set_bridged_asset(Origin::signed(COUNCIL), XBTC, 500));
+		let asset_updated_event = Event::asset_bridge(crate::Event::BridgedAssetUpdated(XBTC, 500));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == asset_updated_event));
+		assert_eq!(
+			AssetBridgeModule::bridged_assets(XBTC),
+			Some(BridgedAssetInfo {
+				mint_limit: 500,
+				..Default::default()
+			})
+		);
+		assert_ok!(mint(&[0, 1], source_tx(1), XBTC, ALICE, 500));
+	});
+}
@@ -0,0 +1,514 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Asset Registry Module
+//!
+//! ## Overview
+//!
+//! Asset registry module keeps the metadata of the assets registered by
+//! governance. Each foreign asset is identified by `CurrencyId::ForeignAsset`
+//! and associated with its `MultiLocation` and metadata, which are used by the
+//! XCM currency id conversion, the existential deposits of tokens and the
+//! XCM fee charging in the runtime.
+//!
+//! The locations of native tokens can be registered as well, `AssetIdConvert`
+//! converts the registered assets first, and the runtime only falls back to
+//! its default locations for the native tokens without registered location.
+//! The XCM fee of the registered assets is priced by `fee_per_weight`, such
+//! as the relay chain fee of the `Transact` dispatched by this chain.
+//!
+//! The native tokens and the Erc20 contracts can be registered as well. The
+//! decimals of native tokens must be the decimals of `CurrencyId::Token`, and
+//! the existential deposit of native tokens can only be raised before they are
+//! issued, so no holder is dusted. The Erc20 contracts are mapped by the
+//! `CurrencyIdMapping` on registration, which remains the only source of their
+//! decimals. The registered decimals of foreign assets are provided to the
+//! `CurrencyIdMapping` through `AssetMetadataProvider`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use module_support::{AssetMetadataProvider, CurrencyIdMapping, EVMBridge, InvokeContext};
+use orml_traits::MultiCurrency;
+use primitives::{currency::GetDecimals, evm::EvmAddress, Balance, CurrencyId, ForeignAssetId, TokenSymbol};
+use sp_runtime::{
+	traits::{Convert, One, Zero},
+	RuntimeDebug,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm::v0::MultiLocation;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The metadata of an asset.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct AssetMetadata {
+	pub name: Vec<u8>,
+	pub symbol: Vec<u8>,
+	pub decimals: u8,
+	/// The existential deposit of the asset.
+	pub minimal_balance: Balance,
+	/// The XCM fee paid in the asset per unit of weight, `None` if the
+	/// asset can't be used to pay XCM fees. See `Pallet::xcm_fee`.
+	pub fee_per_weight: Option<Balance>,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may register and update foreign assets.
+		type RegisterOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The max length of the name and the symbol of assets.
+		#[pallet::constant]
+		type StringLimit: Get<u32>;
+
+		/// The EVM bridge to read the metadata of Erc20 contracts.
+		type EVMBridge: EVMBridge<Self::AccountId, Balance>;
+
+		/// The mapping of Erc20 contracts, which provides their decimals.
+		type CurrencyIdMapping: CurrencyIdMapping;
+
+		/// The currency to check the issuance of native tokens.
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The name or the symbol is too long
+		BadMetadata,
+		/// The location has been registered by another asset
+		MultiLocationExisted,
+		/// The foreign asset is not found
+		AssetNotFound,
+		/// No available foreign asset id
+		NoAvailableAssetId,
+		/// The asset has been registered
+		AssetIdExisted,
+		/// The decimals are not the decimals of the native token
+		InvalidDecimals,
+		/// The minimal balance of an issued native token can't be raised
+		MinimalBalanceRaised,
+		/// The Erc20 contract is not mapped
+		Erc20NotMapped,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Foreign asset registered. \[foreign_asset_id, location, metadata\]
+		ForeignAssetRegistered(ForeignAssetId, MultiLocation, AssetMetadata),
+		/// Foreign asset updated. \[foreign_asset_id, location, metadata\]
+		ForeignAssetUpdated(ForeignAssetId, MultiLocation, AssetMetadata),
+		/// Native asset registered. \[symbol, metadata\]
+		NativeAssetRegistered(TokenSymbol, AssetMetadata),
+		/// Native asset updated. \[symbol, metadata\]
+		NativeAssetUpdated(TokenSymbol, AssetMetadata),
+		/// Erc20 asset registered. \[contract, metadata\]
+		Erc20AssetRegistered(EvmAddress, AssetMetadata),
+		/// Erc20 asset updated. \[contract, metadata\]
+		Erc20AssetUpdated(EvmAddress, AssetMetadata),
+		/// Native asset location updated. \[symbol, location\]
+		NativeAssetLocationUpdated(TokenSymbol, Option<MultiLocation>),
+	}
+
+	/// Next available foreign asset id.
+	///
+	/// NextForeignAssetId: ForeignAssetId
+	#[pallet::storage]
+	#[pallet::getter(fn next_foreign_asset_id)]
+	pub type NextForeignAssetId<T: Config> = StorageValue<_, ForeignAssetId, ValueQuery>;
+
+	/// The locations of foreign assets.
+	///
+	/// ForeignAssetLocations: map ForeignAssetId => Option<MultiLocation>
+	#[pallet::storage]
+	#[pallet::getter(fn foreign_asset_locations)]
+	pub type ForeignAssetLocations<T: Config> = StorageMap<_, Twox64Concat, ForeignAssetId, MultiLocation, OptionQuery>;
+
+	/// The locations of native assets.
+	///
+	/// NativeAssetLocations: map TokenSymbol => Option<MultiLocation>
+	#[pallet::storage]
+	#[pallet::getter(fn native_asset_locations)]
+	pub type NativeAssetLocations<T: Config> = StorageMap<_, Twox64Concat, TokenSymbol, MultiLocation, OptionQuery>;
+
+	/// The currency ids of the registered locations.
+	///
+	/// LocationToCurrencyIds: map MultiLocation => Option<CurrencyId>
+	#[pallet::storage]
+	#[pallet::getter(fn location_to_currency_ids)]
+	pub type LocationToCurrencyIds<T: Config> = StorageMap<_, Twox64Concat, MultiLocation, CurrencyId, OptionQuery>;
+
+	/// The metadata of foreign assets.
+	///
+	/// AssetMetadatas: map ForeignAssetId => Option<AssetMetadata>
+	#[pallet::storage]
+	#[pallet::getter(fn asset_metadatas)]
+	pub type AssetMetadatas<T: Config> = StorageMap<_, Twox64Concat, ForeignAssetId, AssetMetadata, OptionQuery>;
+
+	/// The metadata of native assets.
+	///
+	/// NativeAssetMetadatas: map TokenSymbol => Option<AssetMetadata>
+	#[pallet::storage]
+	#[pallet::getter(fn native_asset_metadatas)]
+	pub type NativeAssetMetadatas<T: Config> = StorageMap<_, Twox64Concat, TokenSymbol, AssetMetadata, OptionQuery>;
+
+	/// The metadata of Erc20 assets.
+	///
+	/// Erc20AssetMetadatas: map EvmAddress => Option<AssetMetadata>
+	#[pallet::storage]
+	#[pallet::getter(fn erc20_asset_metadatas)]
+	pub type Erc20AssetMetadatas<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, AssetMetadata, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register the foreign asset at `location` with `metadata`.
+		///
+		/// The dispatch origin of this call must be `RegisterOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::register_foreign_asset())]
+		#[transactional]
+		pub fn register_foreign_asset(
+			origin: OriginFor<T>,
+			location: MultiLocation,
+			metadata: AssetMetadata,
+		) -> DispatchResultWithPostInfo {
+			T::RegisterOrigin::ensure_origin(origin)?;
+			Self::ensure_valid_metadata(&metadata)?;
+
+			let foreign_asset_id =
+				NextForeignAssetId::<T>::try_mutate(|id| -> Result<ForeignAssetId, DispatchError> {
+					let current_id = *id;
+					*id = id.checked_add(One::one()).ok_or(Error::<T>::NoAvailableAssetId)?;
+					Ok(current_id)
+				})?;
+
+			LocationToCurrencyIds::<T>::try_mutate(&location, |maybe_currency_id| -> DispatchResult {
+				ensure!(maybe_currency_id.is_none(), Error::<T>::MultiLocationExisted);
+				*maybe_currency_id = Some(CurrencyId::ForeignAsset(foreign_asset_id));
+				Ok(())
+			})?;
+			ForeignAssetLocations::<T>::insert(foreign_asset_id, &location);
+			AssetMetadatas::<T>::insert(foreign_asset_id, &metadata);
+
+			Self::deposit_event(Event::ForeignAssetRegistered(foreign_asset_id, location, metadata));
+			Ok(().into())
+		}
+
+		/// Update the location and the metadata of the foreign asset.
+		///
+		/// The dispatch origin of this call must be `RegisterOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::update_foreign_asset())]
+		#[transactional]
+		pub fn update_foreign_asset(
+			origin: OriginFor<T>,
+			foreign_asset_id: ForeignAssetId,
+			location: MultiLocation,
+			metadata: AssetMetadata,
+		) -> DispatchResultWithPostInfo {
+			T::RegisterOrigin::ensure_origin(origin)?;
+			Self::ensure_valid_metadata(&metadata)?;
+
+			ForeignAssetLocations::<T>::try_mutate(foreign_asset_id, |maybe_location| -> DispatchResult {
+				let old_location = maybe_location.as_mut().ok_or(Error::<T>::AssetNotFound)?;
+				if *old_location != location {
+					LocationToCurrencyIds::<T>::try_mutate(&location, |maybe_currency_id| -> DispatchResult {
+						ensure!(maybe_currency_id.is_none(), Error::<T>::MultiLocationExisted);
+						*maybe_currency_id = Some(CurrencyId::ForeignAsset(foreign_asset_id));
+						Ok(())
+					})?;
+					LocationToCurrencyIds::<T>::remove(&*old_location);
+					*old_location = location.clone();
+				}
+				Ok(())
+			})?;
+			AssetMetadatas::<T>::insert(foreign_asset_id, &metadata);
+
+			Self::deposit_event(Event::ForeignAssetUpdated(foreign_asset_id, location, metadata));
+			Ok(().into())
+		}
+
+		/// Register the metadata of the native asset `symbol`.
+		///
+		/// The dispatch origin of this call must be `RegisterOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::register_native_asset())]
+		#[transactional]
+		pub fn register_native_asset(
+			origin: OriginFor<T>,
+			symbol: TokenSymbol,
+			metadata: AssetMetadata,
+		) -> DispatchResultWithPostInfo {
+			T::RegisterOrigin::ensure_origin(origin)?;
+			Self::ensure_valid_metadata(&metadata)?;
+
+			NativeAssetMetadatas::<T>::try_mutate(symbol, |maybe_metadata| -> DispatchResult {
+				ensure!(maybe_metadata.is_none(), Error::<T>::AssetIdExisted);
+				Self::ensure_valid_native_metadata(symbol, Zero::zero(), &metadata)?;
+				*maybe_metadata = Some(metadata.clone());
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::NativeAssetRegistered(symbol, metadata));
+			Ok(().into())
+		}
+
+		/// Update the metadata of the native asset `symbol`.
+		///
+		/// The dispatch origin of this call must be `RegisterOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::update_native_asset())]
+		#[transactional]
+		pub fn update_native_asset(
+			origin: OriginFor<T>,
+			symbol: TokenSymbol,
+			metadata: AssetMetadata,
+		) -> DispatchResultWithPostInfo {
+			T::RegisterOrigin::ensure_origin(origin)?;
+			Self::ensure_valid_metadata(&metadata)?;
+
+			NativeAssetMetadatas::<T>::try_mutate(symbol, |maybe_metadata| -> DispatchResult {
+				let old_metadata = maybe_metadata.as_ref().ok_or(Error::<T>::AssetNotFound)?;
+				Self::ensure_valid_native_metadata(symbol, old_metadata.minimal_balance, &metadata)?;
+				*maybe_metadata = Some(metadata.clone());
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::NativeAssetUpdated(symbol, metadata));
+			Ok(().into())
+		}
+
+		/// Set or remove the location of the registered native asset
+		/// `symbol`.
+		///
+		/// The dispatch origin of this call must be `RegisterOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::set_native_asset_location())]
+		#[transactional]
+		pub fn set_native_asset_location(
+			origin: OriginFor<T>,
+			symbol: TokenSymbol,
+			location: Option<MultiLocation>,
+		) -> DispatchResultWithPostInfo {
+			T::RegisterOrigin::ensure_origin(origin)?;
+			ensure!(
+				NativeAssetMetadatas::<T>::contains_key(symbol),
+				Error::<T>::AssetNotFound
+			);
+
+			NativeAssetLocations::<T>::try_mutate(symbol, |maybe_location| -> DispatchResult {
+				if *maybe_location == location {
+					return Ok(());
+				}
+				if let Some(location) = &location {
+					LocationToCurrencyIds::<T>::try_mutate(location, |maybe_currency_id| -> DispatchResult {
+						ensure!(maybe_currency_id.is_none(), Error::<T>::MultiLocationExisted);
+						*maybe_currency_id = Some(CurrencyId::Token(symbol));
+						Ok(())
+					})?;
+				}
+				if let Some(old_location) = maybe_location.take() {
+					LocationToCurrencyIds::<T>::remove(old_location);
+				}
+				*maybe_location = location.clone();
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::NativeAssetLocationUpdated(symbol, location));
+			Ok(().into())
+		}
+
+		/// Register the Erc20 `contract`, the name and the symbol are read
+		/// from the contract. The contract is mapped by `CurrencyIdMapping`,
+		/// which provides the decimals.
+		///
+		/// The dispatch origin of this call must be `RegisterOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::register_erc20_asset())]
+		#[transactional]
+		pub fn register_erc20_asset(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			minimal_balance: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::RegisterOrigin::ensure_origin(origin)?;
+			ensure!(
+				!Erc20AssetMetadatas::<T>::contains_key(contract),
+				Error::<T>::AssetIdExisted
+			);
+
+			let metadata = Self::erc20_metadata(contract, minimal_balance)?;
+			Erc20AssetMetadatas::<T>::insert(contract, &metadata);
+
+			Self::deposit_event(Event::Erc20AssetRegistered(contract, metadata));
+			Ok(().into())
+		}
+
+		/// Update the minimal balance of the Erc20 `contract` and read the
+		/// name, the symbol and the decimals again.
+		///
+		/// The dispatch origin of this call must be `RegisterOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::update_erc20_asset())]
+		#[transactional]
+		pub fn update_erc20_asset(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			minimal_balance: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::RegisterOrigin::ensure_origin(origin)?;
+			ensure!(
+				Erc20AssetMetadatas::<T>::contains_key(contract),
+				Error::<T>::AssetNotFound
+			);
+
+			let metadata = Self::erc20_metadata(contract, minimal_balance)?;
+			Erc20AssetMetadatas::<T>::insert(contract, &metadata);
+
+			Self::deposit_event(Event::Erc20AssetUpdated(contract, metadata));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn ensure_valid_metadata(metadata: &AssetMetadata) -> DispatchResult {
+		let limit = T::StringLimit::get() as usize;
+		ensure!(
+			metadata.name.len() <= limit && metadata.symbol.len() <= limit,
+			Error::<T>::BadMetadata
+		);
+		Ok(())
+	}
+
+	/// The decimals of native tokens are fixed by `CurrencyId::Token`, and
+	/// raising the minimal balance of an issued token would dust the holders.
+	fn ensure_valid_native_metadata(
+		symbol: TokenSymbol,
+		old_minimal_balance: Balance,
+		metadata: &AssetMetadata,
+	) -> DispatchResult {
+		let currency_id = CurrencyId::Token(symbol);
+		ensure!(
+			currency_id.decimals() == Some(metadata.decimals),
+			Error::<T>::InvalidDecimals
+		);
+		ensure!(
+			metadata.minimal_balance <= old_minimal_balance || T::Currency::total_issuance(currency_id).is_zero(),
+			Error::<T>::MinimalBalanceRaised
+		);
+		Ok(())
+	}
+
+	fn erc20_metadata(contract: EvmAddress, minimal_balance: Balance) -> Result<AssetMetadata, DispatchError> {
+		T::CurrencyIdMapping::set_erc20_mapping(contract)?;
+
+		let context = InvokeContext {
+			contract,
+			sender: Default::default(),
+			origin: Default::default(),
+		};
+		let metadata = AssetMetadata {
+			name: T::EVMBridge::name(context)?,
+			symbol: T::EVMBridge::symbol(context)?,
+			decimals: Self::erc20_decimals(contract).ok_or(Error::<T>::Erc20NotMapped)?,
+			minimal_balance,
+			fee_per_weight: None,
+		};
+		Self::ensure_valid_metadata(&metadata)?;
+		Ok(metadata)
+	}
+
+	fn erc20_decimals(contract: EvmAddress) -> Option<u8> {
+		T::CurrencyIdMapping::decimals(CurrencyId::Erc20(contract))
+	}
+
+	/// The registered metadata of `currency_id`, `CurrencyId::DexShare` is
+	/// never registered. The decimals of Erc20 contracts are read from the
+	/// `CurrencyIdMapping`.
+	pub fn asset_metadata(currency_id: CurrencyId) -> Option<AssetMetadata> {
+		match currency_id {
+			CurrencyId::Token(symbol) => Self::native_asset_metadatas(symbol),
+			CurrencyId::Erc20(contract) => Self::erc20_asset_metadatas(contract).map(|metadata| AssetMetadata {
+				decimals: Self::erc20_decimals(contract).unwrap_or(metadata.decimals),
+				..metadata
+			}),
+			CurrencyId::ForeignAsset(foreign_asset_id) => Self::asset_metadatas(foreign_asset_id),
+			CurrencyId::DexShare(_, _) => None,
+		}
+	}
+
+	/// The existential deposit of the asset, zero if not found.
+	pub fn minimal_balance(currency_id: CurrencyId) -> Balance {
+		Self::asset_metadata(currency_id).map_or(0, |metadata| metadata.minimal_balance)
+	}
+
+	/// The XCM fee of `weight` paid in `currency_id`, `None` if
+	/// `currency_id` is not registered or can't pay XCM fees.
+	pub fn xcm_fee(currency_id: CurrencyId, weight: Weight) -> Option<Balance> {
+		Self::asset_metadata(currency_id)
+			.and_then(|metadata| metadata.fee_per_weight)
+			.map(|fee_per_weight| fee_per_weight.saturating_mul(weight.into()))
+	}
+}
+
+impl<T: Config> AssetMetadataProvider for Pallet<T> {
+	// The decimals of Erc20 contracts are provided by the `CurrencyIdMapping`.
+	fn decimals(currency_id: CurrencyId) -> Option<u8> {
+		match currency_id {
+			CurrencyId::Erc20(_) => None,
+			_ => Self::asset_metadata(currency_id).map(|metadata| metadata.decimals),
+		}
+	}
+}
+
+/// Convert the registered foreign assets and native assets between
+/// `CurrencyId` and `MultiLocation`.
+pub struct AssetIdConvert<T>(PhantomData<T>);
+
+impl<T: Config> Convert<CurrencyId, Option<MultiLocation>> for AssetIdConvert<T> {
+	fn convert(currency_id: CurrencyId) -> Option<MultiLocation> {
+		match currency_id {
+			CurrencyId::Token(symbol) => Pallet::<T>::native_asset_locations(symbol),
+			CurrencyId::ForeignAsset(foreign_asset_id) => Pallet::<T>::foreign_asset_locations(foreign_asset_id),
+			_ => None,
+		}
+	}
+}
+
+impl<T: Config> Convert<MultiLocation, Option<CurrencyId>> for AssetIdConvert<T> {
+	fn convert(location: MultiLocation) -> Option<CurrencyId> {
+		Pallet::<T>::location_to_currency_ids(location)
+	}
+}
@@ -0,0 +1,201 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the asset registry module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use module_support::{EVMBridge, InvokeContext};
+use orml_traits::parameter_type_with_key;
+use primitives::{Amount, TokenSymbol};
+use sp_core::{H160, H256};
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchError, DispatchResult};
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const BOB: AccountId = 2;
+pub const ERC20_ADDRESS: H160 = H160([32, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+pub const ERC20_ADDRESS_NOT_EXISTS: H160 = H160([32, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2]);
+pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+
+mod asset_registry {
+	pub use super::super::*;
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+}
+
+ord_parameter_types! {
+	pub const One: AccountId = ALICE;
+}
+
+parameter_types! {
+	pub const StringLimit: u32 = 8;
+}
+
+pub struct MockEVMBridge;
+impl EVMBridge<AccountId, Balance> for MockEVMBridge {
+	fn name(context: InvokeContext) -> Result<Vec<u8>, DispatchError> {
+		ensure!(
+			context.contract == ERC20_ADDRESS,
+			DispatchError::Other("contract not found")
+		);
+		Ok(b"Erc20".to_vec())
+	}
+	fn symbol(context: InvokeContext) -> Result<Vec<u8>, DispatchError> {
+		ensure!(
+			context.contract == ERC20_ADDRESS,
+			DispatchError::Other("contract not found")
+		);
+		Ok(b"ERC".to_vec())
+	}
+	fn decimals(context: InvokeContext) -> Result<u8, DispatchError> {
+		ensure!(
+			context.contract == ERC20_ADDRESS,
+			DispatchError::Other("contract not found")
+		);
+		Ok(17)
+	}
+	fn total_supply(_context: InvokeContext) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn balance_of(_context: InvokeContext, _address: EvmAddress) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn transfer(_context: InvokeContext, _to: EvmAddress, _value: Balance) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn static_call(_context: InvokeContext, _input: Vec<u8>) -> Result<Vec<u8>, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn get_origin() -> Option<AccountId> {
+		None
+	}
+	fn set_origin(_origin: AccountId) {}
+}
+
+pub struct MockCurrencyIdMapping;
+impl CurrencyIdMapping for MockCurrencyIdMapping {
+	fn set_erc20_mapping(address: EvmAddress) -> DispatchResult {
+		ensure!(address == ERC20_ADDRESS, DispatchError::Other("contract not found"));
+		Ok(())
+	}
+	fn get_evm_address(_currency_id: u32) -> Option<EvmAddress> {
+		None
+	}
+	fn decimals(currency_id: CurrencyId) -> Option<u8> {
+		match currency_id {
+			CurrencyId::Erc20(ERC20_ADDRESS) => Some(17),
+			_ => None,
+		}
+	}
+	fn encode_currency_id(_v: CurrencyId) -> Option<[u8; 32]> {
+		None
+	}
+	fn decode_currency_id(_v: &[u8; 32]) -> Option<CurrencyId> {
+		None
+	}
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type RegisterOrigin = EnsureSignedBy<One, AccountId>;
+	type StringLimit = StringLimit;
+	type EVMBridge = MockEVMBridge;
+	type CurrencyIdMapping = MockCurrencyIdMapping;
+	type Currency = Tokens;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
+		AssetRegistry: asset_registry::{Pallet, Storage, Call, Event<T>},
+	}
+);
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,451 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the asset registry module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{
+	AssetRegistry, Event, ExtBuilder, Origin, Runtime, System, Tokens, ALICE, BOB, DOT, ERC20_ADDRESS,
+	ERC20_ADDRESS_NOT_EXISTS,
+};
+use primitives::DexShare;
+use sp_runtime::traits::BadOrigin;
+use xcm::v0::{
+	Junction::{GeneralKey, Parachain, Parent},
+	MultiLocation::X3,
+};
+
+fn location(key: u8) -> MultiLocation {
+	X3(Parent, Parachain { id: 3000 }, GeneralKey(vec![key]))
+}
+
+fn metadata() -> AssetMetadata {
+	AssetMetadata {
+		name: b"Token".to_vec(),
+		symbol: b"TKN".to_vec(),
+		decimals: 12,
+		minimal_balance: 100,
+		fee_per_weight: Some(2),
+	}
+}
+
+#[test]
+fn register_foreign_asset_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AssetRegistry::register_foreign_asset(Origin::signed(BOB), location(0), metadata()),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetRegistry::register_foreign_asset(
+				Origin::signed(ALICE),
+				location(0),
+				AssetMetadata {
+					name: b"Long Token".to_vec(),
+					..metadata()
+				}
+			),
+			Error::<Runtime>::BadMetadata
+		);
+
+		assert_ok!(AssetRegistry::register_foreign_asset(
+			Origin::signed(ALICE),
+			location(0),
+			metadata()
+		));
+		let event = Event::asset_registry(crate::Event::ForeignAssetRegistered(0, location(0), metadata()));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(AssetRegistry::next_foreign_asset_id(), 1);
+		assert_eq!(AssetRegistry::foreign_asset_locations(0), Some(location(0)));
+		assert_eq!(
+			AssetRegistry::location_to_currency_ids(location(0)),
+			Some(CurrencyId::ForeignAsset(0))
+		);
+		assert_eq!(AssetRegistry::asset_metadatas(0), Some(metadata()));
+		assert_eq!(AssetRegistry::minimal_balance(CurrencyId::ForeignAsset(0)), 100);
+		assert_eq!(AssetRegistry::xcm_fee(CurrencyId::ForeignAsset(0), 1_000), Some(2_000));
+		assert_eq!(AssetRegistry::xcm_fee(CurrencyId::ForeignAsset(1), 1_000), None);
+
+		assert_noop!(
+			AssetRegistry::register_foreign_asset(Origin::signed(ALICE), location(0), metadata()),
+			Error::<Runtime>::MultiLocationExisted
+		);
+
+		NextForeignAssetId::<Runtime>::put(ForeignAssetId::MAX);
+		assert_noop!(
+			AssetRegistry::register_foreign_asset(Origin::signed(ALICE), location(1), metadata()),
+			Error::<Runtime>::NoAvailableAssetId
+		);
+	});
+}
+
+#[test]
+fn update_foreign_asset_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AssetRegistry::update_foreign_asset(Origin::signed(ALICE), 0, location(0), metadata()),
+			Error::<Runtime>::AssetNotFound
+		);
+		assert_ok!(AssetRegistry::register_foreign_asset(
+			Origin::signed(ALICE),
+			location(0),
+			metadata()
+		));
+		assert_ok!(AssetRegistry::register_foreign_asset(
+			Origin::signed(ALICE),
+			location(1),
+			metadata()
+		));
+
+		assert_noop!(
+			AssetRegistry::update_foreign_asset(Origin::signed(BOB), 0, location(2), metadata()),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetRegistry::update_foreign_asset(Origin::signed(ALICE), 0, location(1), metadata()),
+			Error::<Runtime>::MultiLocationExisted
+		);
+
+		let new_metadata = AssetMetadata {
+			fee_per_weight: None,
+			..metadata()
+		};
+		assert_ok!(AssetRegistry::update_foreign_asset(
+			Origin::signed(ALICE),
+			0,
+			location(2),
+			new_metadata.clone()
+		));
+		let event = Event::asset_registry(crate::Event::ForeignAssetUpdated(0, location(2), new_metadata.clone()));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(AssetRegistry::foreign_asset_locations(0), Some(location(2)));
+		assert_eq!(AssetRegistry::location_to_currency_ids(location(0)), None);
+		assert_eq!(
+			AssetRegistry::location_to_currency_ids(location(2)),
+			Some(CurrencyId::ForeignAsset(0))
+		);
+		assert_eq!(AssetRegistry::asset_metadatas(0), Some(new_metadata));
+		assert_eq!(AssetRegistry::xcm_fee(CurrencyId::ForeignAsset(0), 1_000), None);
+	});
+}
+
+#[test]
+fn asset_id_convert_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(AssetRegistry::register_foreign_asset(
+			Origin::signed(ALICE),
+			location(0),
+			metadata()
+		));
+		assert_eq!(
+			<AssetIdConvert<Runtime> as Convert<CurrencyId, Option<MultiLocation>>>::convert(CurrencyId::ForeignAsset(
+				0
+			)),
+			Some(location(0))
+		);
+		assert_eq!(
+			<AssetIdConvert<Runtime> as Convert<MultiLocation, Option<CurrencyId>>>::convert(location(0)),
+			Some(CurrencyId::ForeignAsset(0))
+		);
+		assert_eq!(
+			<AssetIdConvert<Runtime> as Convert<MultiLocation, Option<CurrencyId>>>::convert(location(1)),
+			None
+		);
+	});
+}
+
+#[test]
+fn set_native_asset_location_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let dot = CurrencyId::Token(TokenSymbol::DOT);
+		let native_metadata = AssetMetadata {
+			decimals: 10,
+			..metadata()
+		};
+		assert_noop!(
+			AssetRegistry::set_native_asset_location(Origin::signed(ALICE), TokenSymbol::DOT, Some(location(0))),
+			Error::<Runtime>::AssetNotFound
+		);
+		assert_ok!(AssetRegistry::register_native_asset(
+			Origin::signed(ALICE),
+			TokenSymbol::DOT,
+			native_metadata
+		));
+		assert_eq!(AssetRegistry::xcm_fee(dot, 1_000), Some(2_000));
+		assert_ok!(AssetRegistry::register_foreign_asset(
+			Origin::signed(ALICE),
+			location(1),
+			metadata()
+		));
+
+		assert_noop!(
+			AssetRegistry::set_native_asset_location(Origin::signed(BOB), TokenSymbol::DOT, Some(location(0))),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetRegistry::set_native_asset_location(Origin::signed(ALICE), TokenSymbol::DOT, Some(location(1))),
+			Error::<Runtime>::MultiLocationExisted
+		);
+
+		assert_ok!(AssetRegistry::set_native_asset_location(
+			Origin::signed(ALICE),
+			TokenSymbol::DOT,
+			Some(location(0))
+		));
+		let event = Event::asset_registry(crate::Event::NativeAssetLocationUpdated(
+			TokenSymbol::DOT,
+			Some(location(0)),
+		));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(
+			<AssetIdConvert<Runtime> as Convert<CurrencyId, Option<MultiLocation>>>::convert(dot),
+			Some(location(0))
+		);
+		assert_eq!(
+			<AssetIdConvert<Runtime> as Convert<MultiLocation, Option<CurrencyId>>>::convert(location(0)),
+			Some(dot)
+		);
+
+		// move to another location
+		assert_ok!(AssetRegistry::set_native_asset_location(
+			Origin::signed(ALICE),
+			TokenSymbol::DOT,
+			Some(location(2))
+		));
+		assert_eq!(AssetRegistry::location_to_currency_ids(location(0)), None);
+		assert_eq!(AssetRegistry::location_to_currency_ids(location(2)), Some(dot));
+
+		assert_ok!(AssetRegistry::set_native_asset_location(
+			Origin::signed(ALICE),
+			TokenSymbol::DOT,
+			None
+		));
+		assert_eq!(AssetRegistry::native_asset_locations(TokenSymbol::DOT), None);
+		assert_eq!(AssetRegistry::location_to_currency_ids(location(2)), None);
+		assert_eq!(
+			<AssetIdConvert<Runtime> as Convert<CurrencyId, Option<MultiLocation>>>::convert(dot),
+			None
+		);
+	});
+}
+
+#[test]
+fn register_and_update_native_asset_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let native_metadata = AssetMetadata {
+			decimals: 10,
+			fee_per_weight: None,
+			..metadata()
+		};
+		assert_noop!(
+			AssetRegistry::update_native_asset(Origin::signed(ALICE), TokenSymbol::DOT, native_metadata.clone()),
+			Error::<Runtime>::AssetNotFound
+		);
+		assert_noop!(
+			AssetRegistry::register_native_asset(Origin::signed(BOB), TokenSymbol::DOT, native_metadata.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetRegistry::register_native_asset(Origin::signed(ALICE), TokenSymbol::DOT, metadata()),
+			Error::<Runtime>::InvalidDecimals
+		);
+
+		assert_ok!(AssetRegistry::register_native_asset(
+			Origin::signed(ALICE),
+			TokenSymbol::DOT,
+			native_metadata.clone()
+		));
+		let event = Event::asset_registry(crate::Event::NativeAssetRegistered(
+			TokenSymbol::DOT,
+			native_metadata.clone(),
+		));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(
+			AssetRegistry::asset_metadata(CurrencyId::Token(TokenSymbol::DOT)),
+			Some(native_metadata.clone())
+		);
+		assert_eq!(AssetRegistry::minimal_balance(CurrencyId::Token(TokenSymbol::DOT)), 100);
+		assert_eq!(
+			<AssetRegistry as AssetMetadataProvider>::decimals(CurrencyId::Token(TokenSymbol::DOT)),
+			Some(10)
+		);
+		assert_noop!(
+			AssetRegistry::register_native_asset(Origin::signed(ALICE), TokenSymbol::DOT, native_metadata.clone()),
+			Error::<Runtime>::AssetIdExisted
+		);
+
+		assert_noop!(
+			AssetRegistry::update_native_asset(
+				Origin::signed(ALICE),
+				TokenSymbol::DOT,
+				AssetMetadata {
+					decimals: 12,
+					..native_metadata.clone()
+				}
+			),
+			Error::<Runtime>::InvalidDecimals
+		);
+
+		// raise the minimal balance before DOT is issued
+		let new_metadata = AssetMetadata {
+			minimal_balance: 1_000,
+			..native_metadata
+		};
+		assert_ok!(AssetRegistry::update_native_asset(
+			Origin::signed(ALICE),
+			TokenSymbol::DOT,
+			new_metadata.clone()
+		));
+		let event = Event::asset_registry(crate::Event::NativeAssetUpdated(TokenSymbol::DOT, new_metadata.clone()));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(
+			AssetRegistry::minimal_balance(CurrencyId::Token(TokenSymbol::DOT)),
+			1_000
+		);
+	});
+}
+
+#[test]
+fn raise_minimal_balance_of_issued_native_asset_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		let native_metadata = AssetMetadata {
+			decimals: 10,
+			fee_per_weight: None,
+			..metadata()
+		};
+		assert_ok!(AssetRegistry::register_native_asset(
+			Origin::signed(ALICE),
+			TokenSymbol::DOT,
+			native_metadata.clone()
+		));
+		assert_ok!(<Tokens as MultiCurrency<_>>::deposit(DOT, &BOB, 500));
+
+		// AUSD is issued before it's registered
+		assert_ok!(<Tokens as MultiCurrency<_>>::deposit(
+			CurrencyId::Token(TokenSymbol::AUSD),
+			&BOB,
+			500
+		));
+		assert_noop!(
+			AssetRegistry::register_native_asset(Origin::signed(ALICE), TokenSymbol::AUSD, metadata()),
+			Error::<Runtime>::MinimalBalanceRaised
+		);
+		assert_ok!(AssetRegistry::register_native_asset(
+			Origin::signed(ALICE),
+			TokenSymbol::AUSD,
+			AssetMetadata {
+				minimal_balance: 0,
+				..metadata()
+			}
+		));
+		assert_noop!(
+			AssetRegistry::update_native_asset(
+				Origin::signed(ALICE),
+				TokenSymbol::DOT,
+				AssetMetadata {
+					minimal_balance: 1_000,
+					..native_metadata.clone()
+				}
+			),
+			Error::<Runtime>::MinimalBalanceRaised
+		);
+
+		// lower the minimal balance
+		assert_ok!(AssetRegistry::update_native_asset(
+			Origin::signed(ALICE),
+			TokenSymbol::DOT,
+			AssetMetadata {
+				minimal_balance: 10,
+				..native_metadata
+			}
+		));
+		assert_eq!(AssetRegistry::minimal_balance(DOT), 10);
+	});
+}
+
+#[test]
+fn register_and_update_erc20_asset_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let erc20_metadata = AssetMetadata {
+			name: b"Erc20".to_vec(),
+			symbol: b"ERC".to_vec(),
+			decimals: 17,
+			minimal_balance: 1,
+			fee_per_weight: None,
+		};
+		assert_noop!(
+			AssetRegistry::update_erc20_asset(Origin::signed(ALICE), ERC20_ADDRESS, 1),
+			Error::<Runtime>::AssetNotFound
+		);
+		assert_noop!(
+			AssetRegistry::register_erc20_asset(Origin::signed(BOB), ERC20_ADDRESS, 1),
+			BadOrigin
+		);
+		assert_noop!(
+			AssetRegistry::register_erc20_asset(Origin::signed(ALICE), ERC20_ADDRESS_NOT_EXISTS, 1),
+			DispatchError::Other("contract not found")
+		);
+
+		assert_ok!(AssetRegistry::register_erc20_asset(
+			Origin::signed(ALICE),
+			ERC20_ADDRESS,
+			1
+		));
+		let event = Event::asset_registry(crate::Event::Erc20AssetRegistered(
+			ERC20_ADDRESS,
+			erc20_metadata.clone(),
+		));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(
+			AssetRegistry::asset_metadata(CurrencyId::Erc20(ERC20_ADDRESS)),
+			Some(erc20_metadata.clone())
+		);
+		// the decimals of Erc20 are only provided by CurrencyIdMapping
+		assert_eq!(
+			<AssetRegistry as AssetMetadataProvider>::decimals(CurrencyId::Erc20(ERC20_ADDRESS)),
+			None
+		);
+		assert_noop!(
+			AssetRegistry::register_erc20_asset(Origin::signed(ALICE), ERC20_ADDRESS, 1),
+			Error::<Runtime>::AssetIdExisted
+		);
+
+		assert_ok!(AssetRegistry::update_erc20_asset(
+			Origin::signed(ALICE),
+			ERC20_ADDRESS,
+			10
+		));
+		let new_metadata = AssetMetadata {
+			minimal_balance: 10,
+			..erc20_metadata
+		};
+		let event = Event::asset_registry(crate::Event::Erc20AssetUpdated(ERC20_ADDRESS, new_metadata));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(AssetRegistry::minimal_balance(CurrencyId::Erc20(ERC20_ADDRESS)), 10);
+		assert_eq!(
+			AssetRegistry::asset_metadata(CurrencyId::DexShare(
+				DexShare::Token(TokenSymbol::ACA),
+				DexShare::Erc20(ERC20_ADDRESS)
+			)),
+			None
+		);
+	});
+}
@@ -29,7 +29,7 @@
 #![allow(clippy::unused_unit)]
 #![allow(clippy::upper_case_acronyms)]
 
-use frame_support::{log, pallet_prelude::*, transactional};
+use frame_support::{log, pallet_prelude::*, storage::with_transaction, transactional};
 use frame_system::{
 	offchain::{SendTransactionTypes, SubmitTransaction},
 	pallet_prelude::*,
@@ -43,14 +43,16 @@ use sp_runtime::{
 		storage_lock::{StorageLock, Time},
 		Duration,
 	},
-	traits::{CheckedDiv, Saturating, Zero},
+	traits::{CheckedDiv, One, Saturating, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
-	DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
+	DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug, TransactionOutcome,
 };
 use sp_std::prelude::*;
-use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, PriceProvider, Rate};
+use support::{
+	AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, PriceProvider, Rate, Ratio,
+};
 
 mod mock;
 mod tests;
@@ -123,12 +125,38 @@ impl<AccountId, BlockNumber> CollateralAuctionItem<AccountId, BlockNumber> {
 	}
 }
 
+/// Information of a won collateral auction settled in installments
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug)]
+pub struct InstallmentSettlement<AccountId, BlockNumber> {
+	/// The winner of the auction who pays the installments
+	bidder: AccountId,
+	/// Refund recipient of the auction, used when re-auction on default
+	refund_recipient: AccountId,
+	/// Collateral type for sale
+	currency_id: CurrencyId,
+	/// Collateral amount not released to the bidder yet
+	#[codec(compact)]
+	collateral_amount: Balance,
+	/// Payment amount not paid yet
+	#[codec(compact)]
+	remaining_payment: Balance,
+	/// The number of installments not paid yet
+	remaining_installments: u32,
+	/// The next installment must be paid before this block
+	next_due: BlockNumber,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
+	pub trait Config:
+		frame_system::Config
+		+ orml_auction::Config<AuctionId = AuctionId, Balance = Balance>
+		+ SendTransactionTypes<Call<Self>>
+	{
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
 		/// The minimum increment size of each bid compared to the previous one
@@ -151,7 +179,8 @@ pub mod module {
 		/// Currency to transfer assets
 		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
 
-		/// Auction to manager the auction process
+		/// Auction to manager the auction process, must be the same as the
+		/// `orml_auction` pallet which bids are made through
 		type Auction: Auction<Self::AccountId, Self::BlockNumber, AuctionId = AuctionId, Balance = Balance>;
 
 		/// CDP treasury to escrow assets related to auction
@@ -163,6 +192,11 @@ pub mod module {
 		/// The price source of currencies
 		type PriceSource: PriceProvider<CurrencyId>;
 
+		/// The max slippage compared to oracle price when swapping to refund
+		/// the bid of cancelled auction in the currency it was swapped from
+		#[pallet::constant]
+		type MaxRefundSwapSlippage: Get<Ratio>;
+
 		/// A configuration for base priority of unsigned transactions.
 		///
 		/// This is exposed so that it can be tuned for particular runtime, when
@@ -173,6 +207,23 @@ pub mod module {
 		/// Emergency shutdown.
 		type EmergencyShutdown: EmergencyShutdown;
 
+		/// The origin which may designate institutional bidders.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The number of installments for institutional bidders to settle a
+		/// won collateral auction
+		#[pallet::constant]
+		type InstallmentCount: Get<u32>;
+
+		/// The interval blocks between two installments
+		#[pallet::constant]
+		type InstallmentPeriod: Get<Self::BlockNumber>;
+
+		/// The proportion of the payment that institutional bidders bond when
+		/// bid, the rest is paid in installments after winning
+		#[pallet::constant]
+		type InstallmentBondRatio: Get<Ratio>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -191,6 +242,12 @@ pub mod module {
 		InvalidBidPrice,
 		/// Invalid input amount
 		InvalidAmount,
+		/// The installment settlement does not exist
+		InstallmentSettlementNotExists,
+		/// The caller is not the bidder of the installment settlement
+		NotInstallmentBidder,
+		/// The installment is not overdue
+		InstallmentNotOverdue,
 	}
 
 	#[pallet::event]
@@ -207,6 +264,25 @@ pub mod module {
 		/// Dex take collateral auction. \[auction_id, collateral_type,
 		/// collateral_amount, turnover\]
 		DEXTakeCollateralAuction(AuctionId, CurrencyId, Balance, Balance),
+		/// Institutional bidder designated or removed. \[who,
+		/// is_institutional\]
+		InstitutionalBidderUpdated(T::AccountId, bool),
+		/// Won collateral auction will be settled in installments.
+		/// \[auction_id, bidder, collateral_amount, remaining_payment\]
+		InstallmentSettlementCreated(AuctionId, T::AccountId, Balance, Balance),
+		/// Installment paid and collateral released. \[auction_id, bidder,
+		/// payment_amount, collateral_amount\]
+		InstallmentPaid(AuctionId, T::AccountId, Balance, Balance),
+		/// Installment overdue, the remaining collateral is re-auctioned.
+		/// \[auction_id, bidder, collateral_amount, remaining_payment\]
+		InstallmentDefaulted(AuctionId, T::AccountId, Balance, Balance),
+		/// Bid collateral auction with the stable currency swapped from
+		/// other currency. \[auction_id, bidder, supply_currency_id,
+		/// supply_amount, bid_price\]
+		BidWithSwap(AuctionId, T::AccountId, CurrencyId, Balance, Balance),
+		/// The bid of cancelled auction is refunded in the currency it was
+		/// swapped from. \[auction_id, bidder, currency_id, amount\]
+		RefundBidInOriginalCurrency(AuctionId, T::AccountId, CurrencyId, Balance),
 	}
 
 	/// Mapping from auction id to collateral auction info
@@ -226,6 +302,30 @@ pub mod module {
 	#[pallet::getter(fn total_target_in_auction)]
 	pub type TotalTargetInAuction<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+	/// Bidders designated by governance who can settle won collateral
+	/// auctions in installments
+	#[pallet::storage]
+	#[pallet::getter(fn institutional_bidders)]
+	pub type InstitutionalBidders<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, bool, ValueQuery>;
+
+	/// Mapping from auction id to the bond paid by the last bidder if it is
+	/// an institutional bidder
+	#[pallet::storage]
+	#[pallet::getter(fn installment_bonds)]
+	pub type InstallmentBonds<T: Config> = StorageMap<_, Twox64Concat, AuctionId, Balance, OptionQuery>;
+
+	/// Mapping from auction id to its installment settlement
+	#[pallet::storage]
+	#[pallet::getter(fn installment_settlements)]
+	pub type InstallmentSettlements<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, InstallmentSettlement<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	/// Mapping from auction id to the currency and amount swapped to fund
+	/// the payment of the last bid, if it was bid with swap
+	#[pallet::storage]
+	#[pallet::getter(fn bid_fundings)]
+	pub type BidFundings<T: Config> = StorageMap<_, Twox64Concat, AuctionId, (CurrencyId, Balance), OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -257,7 +357,7 @@ pub mod module {
 		/// Cancel active auction after system shutdown
 		///
 		/// The dispatch origin of this call must be _None_.
-		#[pallet::weight(T::WeightInfo::cancel_collateral_auction())]
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_collateral_auction())]
 		#[transactional]
 		pub fn cancel(origin: OriginFor<T>, id: AuctionId) -> DispatchResultWithPostInfo {
 			ensure_none(origin)?;
@@ -266,6 +366,80 @@ pub mod module {
 			Self::deposit_event(Event::CancelAuction(id));
 			Ok(().into())
 		}
+
+		/// Designate or remove an institutional bidder, who only bonds part
+		/// of the payment when bid and settles the rest in installments
+		/// after winning.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `who`: the bidder.
+		/// - `is_institutional`: whether the bidder is institutional.
+		#[pallet::weight((<T as Config>::WeightInfo::set_institutional_bidder(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_institutional_bidder(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			is_institutional: bool,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if is_institutional {
+				InstitutionalBidders::<T>::insert(&who, true);
+			} else {
+				InstitutionalBidders::<T>::remove(&who);
+			}
+			Self::deposit_event(Event::InstitutionalBidderUpdated(who, is_institutional));
+			Ok(().into())
+		}
+
+		/// Pay the next installment of a won collateral auction, the
+		/// collateral is released in proportion to the payment.
+		///
+		/// - `id`: the auction id.
+		#[pallet::weight(<T as Config>::WeightInfo::pay_installment())]
+		#[transactional]
+		pub fn pay_installment(origin: OriginFor<T>, id: AuctionId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_pay_installment(&who, id)?;
+			Ok(().into())
+		}
+
+		/// Bid collateral auction with other currency, which is swapped to
+		/// the stable currency of the payment with DEX. If the auction is
+		/// cancelled after emergency shutdown, the bid is refunded in the
+		/// supply currency where possible.
+		///
+		/// - `id`: the auction id.
+		/// - `supply_currency_id`: the currency to swap from.
+		/// - `max_supply_amount`: the maximum amount of supply currency to
+		///   swap.
+		/// - `bid_price`: the bid price in stable currency.
+		#[pallet::weight(<T as Config>::WeightInfo::bid_with_swap())]
+		#[transactional]
+		pub fn bid_with_swap(
+			origin: OriginFor<T>,
+			id: AuctionId,
+			supply_currency_id: CurrencyId,
+			#[pallet::compact] max_supply_amount: Balance,
+			#[pallet::compact] bid_price: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_bid_with_swap(&who, id, supply_currency_id, max_supply_amount, bid_price)?;
+			Ok(().into())
+		}
+
+		/// Default the overdue installment settlement, re-auction the
+		/// remaining collateral. The bond and paid installments are not
+		/// refunded.
+		///
+		/// - `id`: the auction id.
+		#[pallet::weight(<T as Config>::WeightInfo::default_installment())]
+		#[transactional]
+		pub fn default_installment(origin: OriginFor<T>, id: AuctionId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::do_default_installment(id)?;
+			Ok(().into())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -406,8 +580,39 @@ impl<T: Config> Pallet<T> {
 
 		// if there's bid
 		if let Some((bidder, bid_price)) = last_bid {
-			// refund stable token to the bidder
-			T::CDPTreasury::issue_debit(&bidder, bid_price, false)?;
+			// refund stable token to the bidder, institutional bidder only paid the bond
+			let refund = InstallmentBonds::<T>::take(id).unwrap_or(bid_price);
+			T::CDPTreasury::issue_debit(&bidder, refund, false)?;
+
+			// refund in the currency the bid was swapped from where possible, the swap is
+			// bounded by the oracle price. Otherwise keep the refund in stable currency.
+			if let Some((supply_currency_id, _)) = BidFundings::<T>::take(id) {
+				if let Some(price) = T::PriceSource::get_relative_price(stable_currency_id, supply_currency_id) {
+					let min_target_amount = Ratio::one()
+						.saturating_sub(T::MaxRefundSwapSlippage::get())
+						.saturating_mul_int(price.saturating_mul_int(refund));
+					let swap_result = with_transaction(|| {
+						match T::DEX::swap_with_exact_supply(
+							&bidder,
+							&[stable_currency_id, supply_currency_id],
+							refund,
+							min_target_amount,
+							None,
+						) {
+							Ok(amount) => TransactionOutcome::Commit(Some(amount)),
+							Err(_) => TransactionOutcome::Rollback(None),
+						}
+					});
+					if let Some(amount) = swap_result {
+						Self::deposit_event(Event::RefundBidInOriginalCurrency(
+							id,
+							bidder.clone(),
+							supply_currency_id,
+							amount,
+						));
+					}
+				}
+			}
 
 			// decrease account ref of bidder
 			frame_system::Pallet::<T>::dec_consumers(&bidder);
@@ -499,23 +704,39 @@ impl<T: Config> Pallet<T> {
 
 				let last_bidder = last_bid.as_ref().map(|(who, _)| who);
 
-				let mut payment = collateral_auction.payment_amount(new_bid_price);
+				// institutional bidder only pays the bond
+				let is_institutional = Self::institutional_bidders(&new_bidder);
+				let mut payment = Self::bid_payment_amount(collateral_auction, &new_bidder, new_bid_price);
+				let bond = payment;
 
 				// if there's bid before, return stablecoin from new bidder to last bidder
 				if let Some(last_bidder) = last_bidder {
-					let refund = collateral_auction.payment_amount(last_bid_price);
-					T::Currency::transfer(T::GetStableCurrencyId::get(), &new_bidder, last_bidder, refund)?;
-
-					payment = payment
-						.checked_sub(refund)
-						// This should never fail because new bid payment are always greater or equal to last bid
-						// payment.
-						.ok_or(Error::<T>::InvalidBidPrice)?;
+					let refund = InstallmentBonds::<T>::get(id)
+						.unwrap_or_else(|| collateral_auction.payment_amount(last_bid_price));
+
+					if payment >= refund {
+						T::Currency::transfer(T::GetStableCurrencyId::get(), &new_bidder, last_bidder, refund)?;
+						payment -= refund;
+					} else {
+						// the bond of institutional bidder is not enough to refund the last bidder,
+						// CDP treasury refunds the rest
+						T::Currency::transfer(T::GetStableCurrencyId::get(), &new_bidder, last_bidder, payment)?;
+						T::CDPTreasury::issue_debit(last_bidder, refund - payment, false)?;
+						payment = Zero::zero();
+					}
 				}
 
 				// transfer remain payment from new bidder to CDP treasury
 				T::CDPTreasury::deposit_surplus(&new_bidder, payment)?;
 
+				if is_institutional {
+					InstallmentBonds::<T>::insert(id, bond);
+				} else {
+					InstallmentBonds::<T>::remove(id);
+				}
+				// the funding of last bid is refunded in stable currency
+				BidFundings::<T>::remove(id);
+
 				// if collateral auction will be in reverse stage, refund collateral to it's
 				// origin from auction CDP treasury
 				if collateral_auction.in_reverse_stage(new_bid_price) {
@@ -549,6 +770,9 @@ impl<T: Config> Pallet<T> {
 		collateral_auction: CollateralAuctionItem<T::AccountId, T::BlockNumber>,
 		winner: Option<(T::AccountId, Balance)>,
 	) {
+		let installment_bond = InstallmentBonds::<T>::take(auction_id);
+		let mut settle_in_installments = false;
+
 		if let Some((bidder, bid_price)) = winner {
 			let mut should_deal = true;
 
@@ -577,13 +801,14 @@ impl<T: Config> Pallet<T> {
 					// refund stable currency to the last bidder, it shouldn't fail and affect the
 					// process. but even it failed, just the winner did not get the bid price. it
 					// can be fixed by treasury council.
-					let res = T::CDPTreasury::issue_debit(&bidder, bid_price, false);
+					let refund = installment_bond.unwrap_or(bid_price);
+					let res = T::CDPTreasury::issue_debit(&bidder, refund, false);
 					if let Err(e) = res {
 						log::warn!(
 							target: "auction-manager",
 							"issue_debit: failed to issue stable {:?} to {:?}: {:?}. \
 							This is unexpected but should be safe",
-							bid_price, bidder, e
+							refund, bidder, e
 						);
 						debug_assert!(false);
 					}
@@ -618,7 +843,33 @@ impl<T: Config> Pallet<T> {
 				}
 			}
 
-			if should_deal {
+			if should_deal && installment_bond.is_some() {
+				// institutional winner settles the rest payment in installments, the collateral
+				// is kept in CDP treasury until paid.
+				let remaining_payment = collateral_auction
+					.payment_amount(bid_price)
+					.saturating_sub(installment_bond.unwrap_or_default());
+				InstallmentSettlements::<T>::insert(
+					auction_id,
+					InstallmentSettlement {
+						bidder: bidder.clone(),
+						refund_recipient: collateral_auction.refund_recipient.clone(),
+						currency_id: collateral_auction.currency_id,
+						collateral_amount: collateral_auction.amount,
+						remaining_payment,
+						remaining_installments: T::InstallmentCount::get().max(1),
+						next_due: frame_system::Pallet::<T>::block_number() + T::InstallmentPeriod::get(),
+					},
+				);
+				settle_in_installments = true;
+
+				Self::deposit_event(Event::InstallmentSettlementCreated(
+					auction_id,
+					bidder,
+					collateral_auction.amount,
+					remaining_payment,
+				));
+			} else if should_deal {
 				// transfer collateral to winner from CDP treasury, it shouldn't fail and affect
 				// the process. but even it failed, just the winner did not get the amount. it
 				// can be fixed by treasury council.
@@ -653,13 +904,138 @@ impl<T: Config> Pallet<T> {
 		// decrement recipient account reference
 		frame_system::Pallet::<T>::dec_consumers(&collateral_auction.refund_recipient);
 
-		// update auction records
-		TotalCollateralInAuction::<T>::mutate(collateral_auction.currency_id, |balance| {
-			*balance = balance.saturating_sub(collateral_auction.amount)
-		});
+		// update auction records, the collateral settled in installments is still
+		// recorded until released
+		if !settle_in_installments {
+			TotalCollateralInAuction::<T>::mutate(collateral_auction.currency_id, |balance| {
+				*balance = balance.saturating_sub(collateral_auction.amount)
+			});
+		}
 		TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(collateral_auction.target));
 	}
 
+	fn do_pay_installment(who: &T::AccountId, id: AuctionId) -> DispatchResult {
+		InstallmentSettlements::<T>::try_mutate_exists(id, |maybe_settlement| -> DispatchResult {
+			let mut settlement = maybe_settlement
+				.take()
+				.ok_or(Error::<T>::InstallmentSettlementNotExists)?;
+			ensure!(settlement.bidder == *who, Error::<T>::NotInstallmentBidder);
+
+			let (payment_amount, collateral_amount) = if settlement.remaining_installments <= 1 {
+				(settlement.remaining_payment, settlement.collateral_amount)
+			} else {
+				let payment_amount = settlement.remaining_payment / Balance::from(settlement.remaining_installments);
+				let collateral_amount = Rate::checked_from_rational(payment_amount, settlement.remaining_payment)
+					.and_then(|n| n.checked_mul_int(settlement.collateral_amount))
+					.unwrap_or_default();
+				(payment_amount, collateral_amount)
+			};
+
+			T::CDPTreasury::deposit_surplus(who, payment_amount)?;
+			T::CDPTreasury::withdraw_collateral(who, settlement.currency_id, collateral_amount)?;
+			TotalCollateralInAuction::<T>::mutate(settlement.currency_id, |balance| {
+				*balance = balance.saturating_sub(collateral_amount)
+			});
+
+			settlement.remaining_payment = settlement.remaining_payment.saturating_sub(payment_amount);
+			settlement.collateral_amount = settlement.collateral_amount.saturating_sub(collateral_amount);
+			settlement.remaining_installments = settlement.remaining_installments.saturating_sub(1);
+			settlement.next_due = settlement.next_due + T::InstallmentPeriod::get();
+
+			Self::deposit_event(Event::InstallmentPaid(
+				id,
+				who.clone(),
+				payment_amount,
+				collateral_amount,
+			));
+
+			if !settlement.remaining_installments.is_zero() {
+				*maybe_settlement = Some(settlement);
+			}
+			Ok(())
+		})
+	}
+
+	fn do_default_installment(id: AuctionId) -> DispatchResult {
+		let settlement = Self::installment_settlements(id).ok_or(Error::<T>::InstallmentSettlementNotExists)?;
+		ensure!(
+			frame_system::Pallet::<T>::block_number() > settlement.next_due,
+			Error::<T>::InstallmentNotOverdue
+		);
+		InstallmentSettlements::<T>::remove(id);
+
+		TotalCollateralInAuction::<T>::mutate(settlement.currency_id, |balance| {
+			*balance = balance.saturating_sub(settlement.collateral_amount)
+		});
+		if !settlement.collateral_amount.is_zero() {
+			<Self as AuctionManager<T::AccountId>>::new_collateral_auction(
+				&settlement.refund_recipient,
+				settlement.currency_id,
+				settlement.collateral_amount,
+				settlement.remaining_payment,
+			)?;
+		}
+
+		Self::deposit_event(Event::InstallmentDefaulted(
+			id,
+			settlement.bidder,
+			settlement.collateral_amount,
+			settlement.remaining_payment,
+		));
+		Ok(())
+	}
+
+	/// Return the amount of stable currency paid by `bidder` at specific
+	/// bid price, institutional bidder only pays the bond.
+	fn bid_payment_amount(
+		collateral_auction: &CollateralAuctionItem<T::AccountId, T::BlockNumber>,
+		bidder: &T::AccountId,
+		bid_price: Balance,
+	) -> Balance {
+		let payment = collateral_auction.payment_amount(bid_price);
+		if Self::institutional_bidders(bidder) {
+			T::InstallmentBondRatio::get().saturating_mul_int(payment)
+		} else {
+			payment
+		}
+	}
+
+	fn do_bid_with_swap(
+		who: &T::AccountId,
+		id: AuctionId,
+		supply_currency_id: CurrencyId,
+		max_supply_amount: Balance,
+		bid_price: Balance,
+	) -> DispatchResult {
+		let collateral_auction = Self::collateral_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+		let stable_currency_id = T::GetStableCurrencyId::get();
+
+		// swap the payment of bid from supply currency
+		let payment = Self::bid_payment_amount(&collateral_auction, who, bid_price);
+		let supply_amount = T::DEX::swap_with_exact_target(
+			who,
+			&[supply_currency_id, stable_currency_id],
+			payment,
+			max_supply_amount,
+			None,
+		)?;
+
+		// bid through auction, which checks the auction is in progress and calls
+		// the bid handler to take the payment
+		<orml_auction::Pallet<T>>::bid(frame_system::RawOrigin::Signed(who.clone()).into(), id, bid_price)
+			.map_err(|e| e.error)?;
+
+		BidFundings::<T>::insert(id, (supply_currency_id, supply_amount));
+		Self::deposit_event(Event::BidWithSwap(
+			id,
+			who.clone(),
+			supply_currency_id,
+			supply_amount,
+			bid_price,
+		));
+		Ok(())
+	}
+
 	/// increment `new_bidder` reference and decrement `last_bidder`
 	/// reference if any
 	fn swap_bidders(new_bidder: &T::AccountId, last_bidder: Option<&T::AccountId>) {
@@ -703,6 +1079,7 @@ impl<T: Config> AuctionHandler<T::AccountId, Balance, T::BlockNumber, AuctionId>
 	}
 
 	fn on_auction_ended(id: AuctionId, winner: Option<(T::AccountId, Balance)>) {
+		BidFundings::<T>::remove(id);
 		if let Some(collateral_auction) = <CollateralAuctions<T>>::take(id) {
 			Self::collateral_auction_end_handler(id, collateral_auction, winner.clone());
 		}
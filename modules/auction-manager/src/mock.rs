@@ -32,6 +32,7 @@ use sp_runtime::{
 };
 use sp_std::cell::RefCell;
 pub use support::Price;
+use support::Ratio;
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
@@ -113,6 +114,12 @@ parameter_types! {
 	pub TreasuryAccount: AccountId = PalletId(*b"aca/hztr").into_account();
 }
 
+parameter_types! {
+	pub const SurplusRebalancePeriod: BlockNumber = 10;
+	pub const MaxSurplusAllocations: u32 = 5;
+	pub MaxSurplusSwapSlippage: Ratio = Ratio::saturating_from_rational(5, 100);
+}
+
 impl cdp_treasury::Config for Runtime {
 	type Event = Event;
 	type Currency = Tokens;
@@ -121,6 +128,10 @@ impl cdp_treasury::Config for Runtime {
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = DEXModule;
 	type MaxAuctionsCount = MaxAuctionsCount;
+	type PriceSource = MockPriceSource;
+	type SurplusRebalancePeriod = SurplusRebalancePeriod;
+	type MaxSurplusAllocations = MaxSurplusAllocations;
+	type MaxSurplusSwapSlippage = MaxSurplusSwapSlippage;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
 	type WeightInfo = ();
@@ -154,6 +165,15 @@ parameter_types! {
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub const TradingPathLimit: u32 = 3;
+	pub const ProvisioningPeriod: BlockNumber = 1000;
+	pub const AmplificationRampPeriod: BlockNumber = 10;
+	pub const MaxRangeBuckets: u32 = 10;
+	pub const MaxBasketInputs: u32 = 3;
+	pub const VolumeBucketPeriod: BlockNumber = 10;
+	pub const MaxVolumeBuckets: u32 = 3;
+	pub const ListingBond: Balance = 100;
+	pub const ListingChallengePeriod: BlockNumber = 10;
+	pub const GetNativeCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 	pub EnabledTradingPairs : Vec<TradingPair> = vec![TradingPair::new(AUSD, BTC)];
 }
 
@@ -164,9 +184,22 @@ impl module_dex::Config for Runtime {
 	type TradingPathLimit = TradingPathLimit;
 	type PalletId = DEXPalletId;
 	type CurrencyIdMapping = ();
+	type Erc20ReferenceCounter = ();
 	type DEXIncentives = ();
 	type WeightInfo = ();
 	type ListingOrigin = EnsureSignedBy<One, AccountId>;
+	type ProvisioningPeriod = ProvisioningPeriod;
+	type AmplificationRampPeriod = AmplificationRampPeriod;
+	type MaxRangeBuckets = MaxRangeBuckets;
+	type PositionNFT = ();
+	type MaxBasketInputs = MaxBasketInputs;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type ListingBond = ListingBond;
+	type ListingChallengePeriod = ListingChallengePeriod;
+	type ListingVetoOrigin = EnsureSignedBy<One, AccountId>;
+	type TreasuryAccount = TreasuryAccount;
+	type VolumeBucketPeriod = VolumeBucketPeriod;
+	type MaxVolumeBuckets = MaxVolumeBuckets;
 }
 
 thread_local! {
@@ -189,6 +222,10 @@ parameter_types! {
 	pub const AuctionTimeToClose: u64 = 100;
 	pub const AuctionDurationSoftCap: u64 = 2000;
 	pub const UnsignedPriority: u64 = 1 << 20;
+	pub const InstallmentCount: u32 = 2;
+	pub const InstallmentPeriod: u64 = 10;
+	pub InstallmentBondRatio: Ratio = Ratio::saturating_from_rational(20, 100);
+	pub MaxRefundSwapSlippage: Ratio = Ratio::saturating_from_rational(3, 100);
 }
 
 impl Config for Runtime {
@@ -202,8 +239,13 @@ impl Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type DEX = DEXModule;
 	type PriceSource = MockPriceSource;
+	type MaxRefundSwapSlippage = MaxRefundSwapSlippage;
 	type UnsignedPriority = UnsignedPriority;
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type InstallmentCount = InstallmentCount;
+	type InstallmentPeriod = InstallmentPeriod;
+	type InstallmentBondRatio = InstallmentBondRatio;
 	type WeightInfo = ();
 }
 
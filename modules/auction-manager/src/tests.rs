@@ -23,6 +23,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
 
 #[test]
 fn get_auction_time_to_close_work() {
@@ -426,3 +427,270 @@ fn cancel_collateral_auction_work() {
 		assert_eq!(bob_ref_count_1, bob_ref_count_0 - 1);
 	});
 }
+
+#[test]
+fn bid_with_swap_and_cancel_refund_in_original_currency_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			BTC,
+			AUSD,
+			100,
+			1000,
+			false
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+
+		assert_noop!(
+			AuctionManagerModule::bid_with_swap(Origin::signed(BOB), 0, BTC, 8, 80),
+			module_dex::Error::<Runtime>::ExcessiveSupplyAmount,
+		);
+		assert_ok!(AuctionManagerModule::bid_with_swap(Origin::signed(BOB), 0, BTC, 20, 80));
+		let bid_with_swap_event = Event::auction_manager(crate::Event::BidWithSwap(0, BOB, BTC, 9, 80));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == bid_with_swap_event));
+		assert_eq!(AuctionManagerModule::bid_fundings(0), Some((BTC, 9)));
+		assert_eq!(AuctionManagerModule::get_last_bid(0), Some((BOB, 80)));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 991);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 80);
+		assert_noop!(
+			AuctionManagerModule::bid_with_swap(Origin::signed(CAROL), 0, BTC, 20, 80),
+			orml_auction::Error::<Runtime>::InvalidBidPrice,
+		);
+
+		mock_shutdown();
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(1, 10)));
+		assert_ok!(AuctionManagerModule::cancel(Origin::none(), 0));
+		let refund_event = Event::auction_manager(crate::Event::RefundBidInOriginalCurrency(0, BOB, BTC, 8));
+		assert!(System::events().iter().any(|record| record.event == refund_event));
+		assert_eq!(AuctionManagerModule::bid_fundings(0), None);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 999);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 80);
+	});
+}
+
+#[test]
+fn cancel_refund_in_stable_currency_when_swap_exceeds_slippage() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			BTC,
+			AUSD,
+			100,
+			1000,
+			false
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 100));
+		assert_ok!(AuctionManagerModule::bid_with_swap(Origin::signed(BOB), 0, BTC, 20, 80));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 991);
+
+		// the oracle price of BTC is 1 AUSD, swapping 80 AUSD on DEX can not get
+		// 77 BTC at least
+		mock_shutdown();
+		assert_ok!(AuctionManagerModule::cancel(Origin::none(), 0));
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			Event::auction_manager(crate::Event::RefundBidInOriginalCurrency(..))
+		)));
+		assert_eq!(AuctionManagerModule::bid_fundings(0), None);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1080);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 991);
+	});
+}
+
+#[test]
+fn set_institutional_bidder_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::set_institutional_bidder(Origin::signed(BOB), BOB, true),
+			BadOrigin
+		);
+		assert_eq!(AuctionManagerModule::institutional_bidders(BOB), false);
+
+		assert_ok!(AuctionManagerModule::set_institutional_bidder(
+			Origin::signed(ALICE),
+			BOB,
+			true
+		));
+		let update_event = Event::auction_manager(crate::Event::InstitutionalBidderUpdated(BOB, true));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+		assert_eq!(AuctionManagerModule::institutional_bidders(BOB), true);
+
+		assert_ok!(AuctionManagerModule::set_institutional_bidder(
+			Origin::signed(ALICE),
+			BOB,
+			false
+		));
+		assert_eq!(AuctionManagerModule::institutional_bidders(BOB), false);
+	});
+}
+
+#[test]
+fn collateral_auction_bid_handler_by_institutional_bidder_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 200));
+		assert_ok!(AuctionManagerModule::set_institutional_bidder(
+			Origin::signed(ALICE),
+			BOB,
+			true
+		));
+
+		assert_eq!(
+			AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 100), None).is_ok(),
+			true
+		);
+		assert_eq!(AuctionManagerModule::installment_bonds(0), Some(20));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 980);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 20);
+
+		assert_eq!(
+			AuctionManagerModule::collateral_auction_bid_handler(1, 0, (CAROL, 110), Some((BOB, 100))).is_ok(),
+			true
+		);
+		assert_eq!(AuctionManagerModule::installment_bonds(0), None);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 890);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 110);
+
+		assert_eq!(
+			AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 120), Some((CAROL, 110))).is_ok(),
+			true
+		);
+		assert_eq!(AuctionManagerModule::installment_bonds(0), Some(24));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 976);
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 1000);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 110);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 86);
+	});
+}
+
+#[test]
+fn pay_installment_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 200));
+		assert_ok!(AuctionManagerModule::set_institutional_bidder(
+			Origin::signed(ALICE),
+			BOB,
+			true
+		));
+		assert_eq!(
+			AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 100), None).is_ok(),
+			true
+		);
+		assert_noop!(
+			AuctionManagerModule::pay_installment(Origin::signed(BOB), 0),
+			Error::<Runtime>::InstallmentSettlementNotExists
+		);
+
+		AuctionManagerModule::on_auction_ended(0, Some((BOB, 100)));
+		let settlement_event = Event::auction_manager(crate::Event::InstallmentSettlementCreated(0, BOB, 100, 80));
+		assert!(System::events().iter().any(|record| record.event == settlement_event));
+		assert_eq!(AuctionManagerModule::collateral_auctions(0), None);
+		assert_eq!(AuctionManagerModule::installment_bonds(0), None);
+		assert_eq!(
+			AuctionManagerModule::installment_settlements(0),
+			Some(InstallmentSettlement {
+				bidder: BOB,
+				refund_recipient: ALICE,
+				currency_id: BTC,
+				collateral_amount: 100,
+				remaining_payment: 80,
+				remaining_installments: 2,
+				next_due: 11,
+			})
+		);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 100);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 100);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1000);
+
+		assert_noop!(
+			AuctionManagerModule::pay_installment(Origin::signed(CAROL), 0),
+			Error::<Runtime>::NotInstallmentBidder
+		);
+
+		assert_ok!(AuctionManagerModule::pay_installment(Origin::signed(BOB), 0));
+		let paid_event = Event::auction_manager(crate::Event::InstallmentPaid(0, BOB, 40, 50));
+		assert!(System::events().iter().any(|record| record.event == paid_event));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 940);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1050);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 60);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 50);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 50);
+		assert_eq!(
+			AuctionManagerModule::installment_settlements(0),
+			Some(InstallmentSettlement {
+				bidder: BOB,
+				refund_recipient: ALICE,
+				currency_id: BTC,
+				collateral_amount: 50,
+				remaining_payment: 40,
+				remaining_installments: 1,
+				next_due: 21,
+			})
+		);
+
+		assert_ok!(AuctionManagerModule::pay_installment(Origin::signed(BOB), 0));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 900);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1100);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 0);
+		assert_eq!(AuctionManagerModule::installment_settlements(0), None);
+	});
+}
+
+#[test]
+fn default_installment_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 200));
+		assert_ok!(AuctionManagerModule::set_institutional_bidder(
+			Origin::signed(ALICE),
+			BOB,
+			true
+		));
+		assert_eq!(
+			AuctionManagerModule::collateral_auction_bid_handler(1, 0, (BOB, 100), None).is_ok(),
+			true
+		);
+		AuctionManagerModule::on_auction_ended(0, Some((BOB, 100)));
+		assert_eq!(AuctionManagerModule::installment_settlements(0).is_some(), true);
+
+		System::set_block_number(11);
+		assert_noop!(
+			AuctionManagerModule::default_installment(Origin::signed(CAROL), 0),
+			Error::<Runtime>::InstallmentNotOverdue
+		);
+
+		System::set_block_number(12);
+		assert_ok!(AuctionManagerModule::default_installment(Origin::signed(CAROL), 0));
+		let default_event = Event::auction_manager(crate::Event::InstallmentDefaulted(0, BOB, 100, 80));
+		assert!(System::events().iter().any(|record| record.event == default_event));
+		assert_eq!(AuctionManagerModule::installment_settlements(0), None);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 980);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1000);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 20);
+
+		let new_auction = AuctionManagerModule::collateral_auctions(1).unwrap();
+		assert_eq!(new_auction.refund_recipient, ALICE);
+		assert_eq!(new_auction.amount, 100);
+		assert_eq!(new_auction.target, 80);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 100);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 80);
+	});
+}
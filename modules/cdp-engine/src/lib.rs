@@ -28,7 +28,7 @@
 #![allow(clippy::unused_unit)]
 #![allow(clippy::upper_case_acronyms)]
 
-use frame_support::{log, pallet_prelude::*, traits::UnixTime, transactional};
+use frame_support::{log, pallet_prelude::*, storage::with_transaction, traits::UnixTime, transactional};
 use frame_system::{
 	offchain::{SendTransactionTypes, SubmitTransaction},
 	pallet_prelude::*,
@@ -36,22 +36,24 @@ use frame_system::{
 use loans::Position;
 use orml_traits::Change;
 use orml_utilities::{IterableStorageDoubleMapExtended, OffchainErr};
-use primitives::{Amount, Balance, CurrencyId};
+use primitives::{evm::EvmAddress, Amount, Balance, CurrencyId};
 use sp_runtime::{
 	offchain::{
 		storage::StorageValueRef,
 		storage_lock::{StorageLock, Time},
 		Duration,
 	},
-	traits::{BlakeTwo256, Bounded, Convert, Hash, Saturating, StaticLookup, UniqueSaturatedInto, Zero},
+	traits::{BlakeTwo256, Bounded, Convert, Hash, Saturating, StaticLookup, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
-	DispatchError, DispatchResult, FixedPointNumber, RandomNumberGenerator, RuntimeDebug,
+	DispatchError, DispatchResult, FixedPointNumber, Permill, RandomNumberGenerator, RuntimeDebug, SaturatedConversion,
+	TransactionOutcome,
 };
 use sp_std::prelude::*;
 use support::{
-	CDPTreasury, CDPTreasuryExtended, EmergencyShutdown, ExchangeRate, Price, PriceProvider, Rate, Ratio, RiskManager,
+	math, CDPTreasury, CDPTreasuryExtended, EmergencyShutdown, Erc20ReferenceCounter, ExchangeRate, LiquidationKeeper,
+	ParameterInvariants, Price, PriceProvider, Rate, Ratio, RiskManager,
 };
 
 mod debit_exchange_rate_convertor;
@@ -98,6 +100,18 @@ pub struct RiskManagementParams {
 	pub required_collateral_ratio: Option<Ratio>,
 }
 
+/// Stability fee exemption of a governance-approved CDP
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct StabilityFeeExemption {
+	/// The proportion of the accrued interest to waive, 100% means
+	/// interest-free
+	pub fee_discount: Permill,
+
+	/// The debit value of CDP up to which the interest is discounted, the
+	/// debit value exceeds it accrues interest at the normal rate
+	pub debit_value_cap: Balance,
+}
+
 // typedef to help polkadot.js disambiguate Change with different generic
 // parameters
 type ChangeOptionRate = Change<Option<Rate>>;
@@ -111,6 +125,25 @@ pub enum LiquidationStrategy {
 	Auction,
 	/// Liquidation CDP's collateral by swap with DEX
 	Exchange,
+	/// Liquidation CDP's collateral by keeper contract
+	Keeper,
+}
+
+/// A step of the liquidation pipeline of collateral type, the steps are
+/// tried in order until one of them succeeds.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub enum LiquidationStep {
+	/// Swap collateral to stable currency with DEX directly, in the limit of
+	/// the slippage.
+	DexSwap(Ratio),
+	/// Swap collateral to stable currency with DEX through the stable swap
+	/// pool of the like-valued currency, in the limit of the slippage of
+	/// each swap. \[via_currency_id, slippage_limit\]
+	StableSwap(CurrencyId, Ratio),
+	/// Create collateral auctions.
+	Auction,
+	/// Sell collateral through the keeper contract.
+	KeeperContract(EvmAddress),
 }
 
 #[frame_support::pallet]
@@ -170,12 +203,35 @@ pub mod module {
 		/// Emergency shutdown.
 		type EmergencyShutdown: EmergencyShutdown;
 
+		/// The maximum number of stability fee exemptions for a collateral
+		/// type
+		#[pallet::constant]
+		type MaxStabilityFeeExemptions: Get<u32>;
+
+		/// The maximum number of steps of the liquidation pipeline of a
+		/// collateral type
+		#[pallet::constant]
+		type MaxLiquidationSteps: Get<u32>;
+
+		/// The keeper contracts to liquidate collateral
+		type LiquidationKeeper: LiquidationKeeper<CurrencyId, Balance>;
+
+		/// The cap of total collateral value in stable currency liquidated
+		/// automatically by offchain worker in a block, the liquidations
+		/// exceeding it are deferred to subsequent blocks, and the riskier
+		/// CDPs are prioritized.
+		#[pallet::constant]
+		type MaxLiquidationValuePerBlock: Get<Balance>;
+
 		/// Time used for computing era duration.
 		///
 		/// It is guaranteed to start being called from the first `on_finalize`.
 		/// Thus value at genesis is not used.
 		type UnixTime: UnixTime;
 
+		/// Record the Erc20 currencies referenced by the collateral types.
+		type Erc20ReferenceCounter: Erc20ReferenceCounter;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -205,6 +261,18 @@ pub mod module {
 		AlreadyShutdown,
 		/// Must after system shutdown
 		MustAfterShutdown,
+		/// The required collateral ratio is below the liquidation ratio
+		InvalidCollateralParams,
+		/// Exceed the maximum number of stability fee exemptions
+		TooManyStabilityFeeExemptions,
+		/// The stability fee exemption does not exist
+		StabilityFeeExemptionNotFound,
+		/// The steps of liquidation pipeline are invalid
+		InvalidLiquidationSteps,
+		/// All steps of liquidation pipeline failed
+		LiquidationFailed,
+		/// The liquidated collateral value of this block reaches the cap
+		ExceedLiquidationValuePerBlock,
 	}
 
 	#[pallet::event]
@@ -237,6 +305,18 @@ pub mod module {
 		/// The global interest rate per sec for all types of collateral
 		/// updated. \[new_global_interest_rate_per_sec\]
 		GlobalInterestRatePerSecUpdated(Rate),
+		/// Granted the stability fee exemption to CDP. \[collateral_type,
+		/// owner, fee_discount, debit_value_cap\]
+		StabilityFeeExemptionGranted(CurrencyId, T::AccountId, Permill, Balance),
+		/// Revoked the stability fee exemption of CDP. \[collateral_type,
+		/// owner\]
+		StabilityFeeExemptionRevoked(CurrencyId, T::AccountId),
+		/// Waived the accrued interest of CDP by its stability fee exemption.
+		/// \[collateral_type, owner, waived_debit_value\]
+		StabilityFeeExemptionConsumed(CurrencyId, T::AccountId, Balance),
+		/// The liquidation pipeline for specific collateral type updated, empty
+		/// means the default pipeline. \[collateral_type, liquidation_steps\]
+		LiquidationStepsUpdated(CurrencyId, Vec<LiquidationStep>),
 	}
 
 	/// Mapping from collateral type to its exchange rate of debit units and
@@ -260,6 +340,32 @@ pub mod module {
 	#[pallet::getter(fn last_accumulation_secs)]
 	pub type LastAccumulationSecs<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+	/// Stability fee exemptions of governance-approved CDPs
+	///
+	/// StabilityFeeExemptions: double_map CurrencyId, AccountId =>
+	/// Option<StabilityFeeExemption>
+	#[pallet::storage]
+	#[pallet::getter(fn stability_fee_exemptions)]
+	pub type StabilityFeeExemptions<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyId, Twox64Concat, T::AccountId, StabilityFeeExemption, OptionQuery>;
+
+	/// Mapping from collateral type to its liquidation pipeline, the default
+	/// pipeline is swap with DEX then create collateral auctions.
+	#[pallet::storage]
+	#[pallet::getter(fn liquidation_steps)]
+	pub type LiquidationSteps<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Vec<LiquidationStep>, OptionQuery>;
+
+	/// Total collateral value in stable currency liquidated automatically in
+	/// the current block, cleared when block end
+	#[pallet::storage]
+	#[pallet::getter(fn liquidated_value_in_block)]
+	pub type LiquidatedValueInBlock<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// Whether the Erc20 references of the existing collateral types have
+	/// been recorded.
+	#[pallet::storage]
+	pub(crate) type UpgradedToErc20References<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
 		#[allow(clippy::type_complexity)]
@@ -296,6 +402,7 @@ pub mod module {
 					required_collateral_ratio,
 					maximum_total_debit_value,
 				)| {
+					<Pallet<T>>::inc_erc20_reference(*currency_id);
 					CollateralParams::<T>::insert(
 						currency_id,
 						RiskManagementParams {
@@ -309,6 +416,7 @@ pub mod module {
 				},
 			);
 			GlobalInterestRatePerSec::<T>::put(self.global_interest_rate_per_sec);
+			UpgradedToErc20References::<T>::put(true);
 		}
 	}
 
@@ -317,11 +425,35 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Record the Erc20 references of the collateral types configured
+		/// before the references were tracked.
+		fn on_runtime_upgrade() -> Weight {
+			if UpgradedToErc20References::<T>::get() {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut count: Weight = 0;
+			for (currency_id, _) in CollateralParams::<T>::iter() {
+				Self::inc_erc20_reference(currency_id);
+				count += 1;
+			}
+			UpgradedToErc20References::<T>::put(true);
+
+			T::DbWeight::get().reads_writes(count.saturating_mul(2) + 1, count + 1)
+		}
+
 		/// Issue interest in stable currency for all types of collateral has
 		/// debit when block end, and update their debit exchange rate
 		fn on_initialize(_: T::BlockNumber) -> Weight {
 			let count: u32 = Self::accumulate_interest(T::UnixTime::now().as_secs(), Self::last_accumulation_secs());
 			<T as Config>::WeightInfo::on_initialize(count)
+				// clear the liquidated value in on_finalize
+				.saturating_add(T::DbWeight::get().writes(1))
+		}
+
+		/// Reset the liquidated collateral value cap for the next block
+		fn on_finalize(_: T::BlockNumber) {
+			LiquidatedValueInBlock::<T>::kill();
 		}
 
 		/// Runs after every block. Start offchain worker to check CDP and
@@ -352,7 +484,7 @@ pub mod module {
 		///
 		/// - `currency_id`: CDP's collateral type.
 		/// - `who`: CDP's owner.
-		#[pallet::weight(<T as Config>::WeightInfo::liquidate_by_dex())]
+		#[pallet::weight(Pallet::<T>::max_liquidation_weight())]
 		#[transactional]
 		pub fn liquidate(
 			origin: OriginFor<T>,
@@ -362,8 +494,17 @@ pub mod module {
 			ensure_none(origin)?;
 			let who = T::Lookup::lookup(who)?;
 			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
-			Self::liquidate_unsafe_cdp(who, currency_id)?;
-			Ok(().into())
+
+			let Position { collateral, .. } = <LoansOf<T>>::positions(currency_id, &who);
+			let collateral_value = Self::get_collateral_value(currency_id, collateral);
+			ensure!(
+				!Self::exceed_liquidation_value_per_block(collateral_value),
+				Error::<T>::ExceedLiquidationValuePerBlock
+			);
+
+			let actual_weight = Self::liquidate_unsafe_cdp(who, currency_id)?;
+			LiquidatedValueInBlock::<T>::mutate(|value| *value = value.saturating_add(collateral_value));
+			Ok(Some(actual_weight).into())
 		}
 
 		/// Settle CDP has debit after system shutdown
@@ -456,9 +597,130 @@ pub mod module {
 				collateral_params.maximum_total_debit_value = val;
 				Self::deposit_event(Event::MaximumTotalDebitValueUpdated(currency_id, val));
 			}
+			if !CollateralParams::<T>::contains_key(currency_id) {
+				Self::inc_erc20_reference(currency_id);
+			}
 			CollateralParams::<T>::insert(currency_id, collateral_params);
 			Ok(().into())
 		}
+
+		/// Grant or update the stability fee exemption of CDP, the interest
+		/// accrued on its debit value up to `debit_value_cap` is discounted
+		/// by `fee_discount`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `who`: CDP's owner.
+		/// - `fee_discount`: the proportion of the accrued interest to waive.
+		/// - `debit_value_cap`: the debit value up to which the interest is
+		///   discounted.
+		#[pallet::weight((<T as Config>::WeightInfo::set_stability_fee_exemption(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_stability_fee_exemption(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			who: <T::Lookup as StaticLookup>::Source,
+			fee_discount: Permill,
+			#[pallet::compact] debit_value_cap: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(
+				T::CollateralCurrencyIds::get().contains(&currency_id),
+				Error::<T>::InvalidCollateralType,
+			);
+			if !StabilityFeeExemptions::<T>::contains_key(currency_id, &who) {
+				ensure!(
+					(StabilityFeeExemptions::<T>::iter_prefix(currency_id).count() as u32)
+						< T::MaxStabilityFeeExemptions::get(),
+					Error::<T>::TooManyStabilityFeeExemptions,
+				);
+			}
+
+			StabilityFeeExemptions::<T>::insert(
+				currency_id,
+				&who,
+				StabilityFeeExemption {
+					fee_discount,
+					debit_value_cap,
+				},
+			);
+			Self::deposit_event(Event::StabilityFeeExemptionGranted(
+				currency_id,
+				who,
+				fee_discount,
+				debit_value_cap,
+			));
+			Ok(().into())
+		}
+
+		/// Revoke the stability fee exemption of CDP.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `who`: CDP's owner.
+		#[pallet::weight((<T as Config>::WeightInfo::revoke_stability_fee_exemption(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn revoke_stability_fee_exemption(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			StabilityFeeExemptions::<T>::take(currency_id, &who).ok_or(Error::<T>::StabilityFeeExemptionNotFound)?;
+			Self::deposit_event(Event::StabilityFeeExemptionRevoked(currency_id, who));
+			Ok(().into())
+		}
+
+		/// Update the liquidation pipeline of collateral type.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `steps`: the steps tried in order when liquidate unsafe CDP, can
+		///   not exceed `MaxLiquidationSteps` and must end with the only
+		///   `Auction` step as the last resort, keeper contracts must be
+		///   accepted by `LiquidationKeeper`. Empty to reset to the default
+		///   pipeline.
+		#[pallet::weight((<T as Config>::WeightInfo::set_liquidation_steps(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_liquidation_steps(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			steps: Vec<LiquidationStep>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::CollateralCurrencyIds::get().contains(&currency_id),
+				Error::<T>::InvalidCollateralType,
+			);
+			if let Some((last, rest)) = steps.split_last() {
+				ensure!(
+					steps.len() <= T::MaxLiquidationSteps::get() as usize
+						&& *last == LiquidationStep::Auction
+						&& rest.iter().all(|step| match step {
+							LiquidationStep::StableSwap(via_currency_id, _) => {
+								*via_currency_id != currency_id && *via_currency_id != T::GetStableCurrencyId::get()
+							}
+							LiquidationStep::KeeperContract(contract) => T::LiquidationKeeper::is_keeper(*contract),
+							LiquidationStep::DexSwap(_) => true,
+							LiquidationStep::Auction => false,
+						}),
+					Error::<T>::InvalidLiquidationSteps,
+				);
+			}
+
+			if steps.is_empty() {
+				LiquidationSteps::<T>::remove(currency_id);
+			} else {
+				LiquidationSteps::<T>::insert(currency_id, steps.clone());
+			}
+			Self::deposit_event(Event::LiquidationStepsUpdated(currency_id, steps));
+			Ok(().into())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -473,9 +735,19 @@ pub mod module {
 					if !Self::is_cdp_unsafe(*currency_id, collateral, debit) || T::EmergencyShutdown::is_shutdown() {
 						return InvalidTransaction::Stale.into();
 					}
+					// defer the liquidation to subsequent blocks if the cap of this block is reached
+					if Self::exceed_liquidation_value_per_block(Self::get_collateral_value(*currency_id, collateral)) {
+						return InvalidTransaction::ExhaustsResources.into();
+					}
 
 					ValidTransaction::with_tag_prefix("CDPEngineOffchainWorker")
-						.priority(T::UnsignedPriority::get())
+						.priority(
+							T::UnsignedPriority::get().saturating_add(Self::get_liquidation_priority(
+								*currency_id,
+								collateral,
+								debit,
+							)),
+						)
 						.and_provides((<frame_system::Pallet<T>>::block_number(), currency_id, who))
 						.longevity(64_u64)
 						.propagate(true)
@@ -502,6 +774,12 @@ pub mod module {
 }
 
 impl<T: Config> Pallet<T> {
+	/// Record that the collateral type `currency_id`, held by loans, uses
+	/// the Erc20 currency.
+	fn inc_erc20_reference(currency_id: CurrencyId) {
+		T::Erc20ReferenceCounter::inc_reference(<T as loans::Config>::PalletId::get(), currency_id);
+	}
+
 	fn accumulate_interest(now_secs: u64, last_accumulation_secs: u64) -> u32 {
 		let mut count: u32 = 0;
 
@@ -517,8 +795,16 @@ impl<T: Config> Pallet<T> {
 					let debit_exchange_rate = Self::get_debit_exchange_rate(currency_id);
 					let debit_exchange_rate_increment = debit_exchange_rate.saturating_mul(rate_to_accumulate);
 					let total_debit_value = Self::get_debit_value(currency_id, total_debits);
-					let issued_stable_coin_balance =
-						debit_exchange_rate_increment.saturating_mul_int(total_debit_value);
+					let exempted_interests = Self::get_exempted_interests(currency_id, rate_to_accumulate);
+					let total_exempted_interest = exempted_interests
+						.iter()
+						.fold(Balance::zero(), |acc, (_, interest)| acc.saturating_add(*interest));
+					count = count.saturating_add(exempted_interests.len() as u32);
+
+					// the waived interest is not issued as surplus
+					let issued_stable_coin_balance = debit_exchange_rate_increment
+						.saturating_mul_int(total_debit_value)
+						.saturating_sub(total_exempted_interest);
 
 					// issue stablecoin to surplus pool
 					let res = <T as Config>::CDPTreasury::on_system_surplus(issued_stable_coin_balance);
@@ -528,6 +814,7 @@ impl<T: Config> Pallet<T> {
 							let new_debit_exchange_rate =
 								debit_exchange_rate.saturating_add(debit_exchange_rate_increment);
 							DebitExchangeRate::<T>::insert(currency_id, new_debit_exchange_rate);
+							Self::waive_exempted_interests(currency_id, new_debit_exchange_rate, exempted_interests);
 						}
 						Err(e) => {
 							log::warn!(
@@ -548,6 +835,53 @@ impl<T: Config> Pallet<T> {
 		count
 	}
 
+	/// Get the interest to waive for CDPs which have stability fee
+	/// exemption under specific collateral type.
+	fn get_exempted_interests(currency_id: CurrencyId, rate_to_accumulate: Rate) -> Vec<(T::AccountId, Balance)> {
+		StabilityFeeExemptions::<T>::iter_prefix(currency_id)
+			.filter_map(|(who, exemption)| {
+				let Position { debit, .. } = <LoansOf<T>>::positions(currency_id, &who);
+				let exempted_debit_value = Self::get_debit_value(currency_id, debit).min(exemption.debit_value_cap);
+				let interest = exemption
+					.fee_discount
+					.mul_floor(rate_to_accumulate.saturating_mul_int(exempted_debit_value));
+
+				if interest.is_zero() {
+					None
+				} else {
+					Some((who, interest))
+				}
+			})
+			.collect()
+	}
+
+	/// Deduct the debit of CDPs by the waived interest.
+	fn waive_exempted_interests(
+		currency_id: CurrencyId,
+		debit_exchange_rate: ExchangeRate,
+		exempted_interests: Vec<(T::AccountId, Balance)>,
+	) {
+		for (who, interest) in exempted_interests {
+			let debit_decrement = debit_exchange_rate
+				.reciprocal()
+				.unwrap_or_default()
+				.saturating_mul_int(interest)
+				.min(<LoansOf<T>>::positions(currency_id, &who).debit);
+
+			match <LoansOf<T>>::waive_debit(&who, currency_id, debit_decrement) {
+				Ok(_) => Self::deposit_event(Event::StabilityFeeExemptionConsumed(currency_id, who, interest)),
+				Err(e) => {
+					log::warn!(
+						target: "cdp-engine",
+						"waive_debit: failed to waive {:?} debit of {:?}: {:?}. \
+						This is unexpected but should be safe",
+						debit_decrement, who, e
+					);
+				}
+			}
+		}
+	}
+
 	fn submit_unsigned_liquidation_tx(currency_id: CurrencyId, who: T::AccountId) {
 		let who = T::Lookup::unlookup(who);
 		let call = Call::<T>::liquidate(currency_id, who.clone());
@@ -679,6 +1013,36 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// The value of collateral in stable currency at the feed price.
+	pub fn get_collateral_value(currency_id: CurrencyId, collateral: Balance) -> Balance {
+		T::PriceSource::get_relative_price(currency_id, T::GetStableCurrencyId::get())
+			.map(|price| price.saturating_mul_int(collateral))
+			.unwrap_or_default()
+	}
+
+	/// Whether liquidating collateral of `collateral_value` exceeds the cap
+	/// of this block. The first liquidation of a block is always allowed so
+	/// that the CDP whose collateral value exceeds the cap can be liquidated.
+	fn exceed_liquidation_value_per_block(collateral_value: Balance) -> bool {
+		let liquidated_value = Self::liquidated_value_in_block();
+		!liquidated_value.is_zero()
+			&& liquidated_value.saturating_add(collateral_value) > T::MaxLiquidationValuePerBlock::get()
+	}
+
+	/// The extra priority of liquidation of unsafe CDP, in parts per million
+	/// of the shortfall of its collateral ratio below the liquidation ratio,
+	/// so that the riskier CDPs are liquidated first.
+	fn get_liquidation_priority(currency_id: CurrencyId, collateral: Balance, debit: Balance) -> TransactionPriority {
+		T::PriceSource::get_relative_price(currency_id, T::GetStableCurrencyId::get())
+			.map(|price| {
+				let collateral_ratio = Self::calculate_collateral_ratio(currency_id, collateral, debit, price);
+				Self::get_liquidation_ratio(currency_id)
+					.saturating_sub(collateral_ratio)
+					.saturating_mul_int(1_000_000u64)
+			})
+			.unwrap_or_default()
+	}
+
 	pub fn maximum_total_debit_value(currency_id: CurrencyId) -> Balance {
 		Self::collateral_params(currency_id).maximum_total_debit_value
 	}
@@ -695,10 +1059,7 @@ impl<T: Config> Pallet<T> {
 	}
 
 	pub fn compound_interest_rate(rate_per_sec: Rate, secs: u64) -> Rate {
-		rate_per_sec
-			.saturating_add(Rate::one())
-			.saturating_pow(secs.unique_saturated_into())
-			.saturating_sub(Rate::one())
+		math::compound_rate(rate_per_sec, secs)
 	}
 
 	pub fn get_liquidation_ratio(currency_id: CurrencyId) -> Ratio {
@@ -747,6 +1108,73 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Adjust multiple positions of `who` in order, all or nothing. Each
+	/// position is checked only once after all adjustments, so that the
+	/// intermediate states of a rebalance across positions are allowed.
+	#[transactional]
+	pub fn adjust_positions(who: &T::AccountId, adjustments: &[(CurrencyId, Amount, Amount)]) -> DispatchResult {
+		let collateral_currency_ids = T::CollateralCurrencyIds::get();
+		let mut adjusted_currency_ids: Vec<CurrencyId> = vec![];
+		for (currency_id, collateral_adjustment, debit_adjustment) in adjustments.iter().cloned() {
+			ensure!(
+				collateral_currency_ids.contains(&currency_id),
+				Error::<T>::InvalidCollateralType,
+			);
+			<LoansOf<T>>::adjust_position_unchecked(who, currency_id, collateral_adjustment, debit_adjustment)?;
+			if !adjusted_currency_ids.contains(&currency_id) {
+				adjusted_currency_ids.push(currency_id);
+			}
+		}
+
+		for currency_id in adjusted_currency_ids {
+			<LoansOf<T>>::check_position(who, currency_id)?;
+		}
+		Ok(())
+	}
+
+	/// Simulate `adjust_position` without changing the state, returning the
+	/// position after the adjustment and whether the adjustment would
+	/// succeed.
+	pub fn simulate_adjust_position(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral_adjustment: Amount,
+		debit_adjustment: Amount,
+	) -> (Position, DispatchResult) {
+		let apply_adjustment = |balance: Balance, adjustment: Amount| -> Balance {
+			let abs: Balance = adjustment.saturating_abs().saturated_into();
+			if adjustment.is_negative() {
+				balance.saturating_sub(abs)
+			} else {
+				balance.saturating_add(abs)
+			}
+		};
+		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, who);
+		let position = Position {
+			collateral: apply_adjustment(collateral, collateral_adjustment),
+			debit: apply_adjustment(debit, debit_adjustment),
+		};
+
+		let result = with_transaction(|| {
+			// not allowed to adjust the debit after system shutdown
+			let result = if !debit_adjustment.is_zero() && T::EmergencyShutdown::is_shutdown() {
+				Err(Error::<T>::AlreadyShutdown.into())
+			} else {
+				Self::adjust_position(who, currency_id, collateral_adjustment, debit_adjustment)
+			};
+			TransactionOutcome::Rollback(result)
+		});
+
+		(position, result)
+	}
+
+	/// The collateral ratio of a position at the current feed price, `None`
+	/// if there is no feed price for `currency_id`.
+	pub fn get_collateral_ratio(currency_id: CurrencyId, collateral: Balance, debit: Balance) -> Option<Ratio> {
+		T::PriceSource::get_relative_price(currency_id, T::GetStableCurrencyId::get())
+			.map(|feed_price| Self::calculate_collateral_ratio(currency_id, collateral, debit, feed_price))
+	}
+
 	// settle cdp has debit when emergency shutdown
 	pub fn settle_cdp_has_debit(who: T::AccountId, currency_id: CurrencyId) -> DispatchResult {
 		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
@@ -810,8 +1238,8 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	// liquidate unsafe cdp
-	pub fn liquidate_unsafe_cdp(who: T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+	// liquidate unsafe cdp, returns the weight of the tried liquidation steps
+	pub fn liquidate_unsafe_cdp(who: T::AccountId, currency_id: CurrencyId) -> Result<Weight, DispatchError> {
 		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
 
 		// ensure the cdp is unsafe
@@ -826,39 +1254,21 @@ impl<T: Config> Pallet<T> {
 		let bad_debt_value = Self::get_debit_value(currency_id, debit);
 		let target_stable_amount = Self::get_liquidation_penalty(currency_id).saturating_mul_acc_int(bad_debt_value);
 
-		// try use collateral to swap enough native token in DEX when the price impact
-		// is below the limit, otherwise create collateral auctions.
-		let liquidation_strategy = (|| -> Result<LiquidationStrategy, DispatchError> {
-			// swap exact stable with DEX in limit of price impact
-			if let Ok(actual_supply_collateral) = <T as Config>::CDPTreasury::swap_collateral_to_exact_stable(
-				currency_id,
-				collateral,
-				target_stable_amount,
-				Some(T::MaxSlippageSwapWithDEX::get()),
-				None,
-				false,
-			) {
-				// refund remain collateral to CDP owner
-				let refund_collateral_amount = collateral
-					.checked_sub(actual_supply_collateral)
-					.expect("swap succecced means collateral >= actual_supply_collateral; qed");
-
-				<T as Config>::CDPTreasury::withdraw_collateral(&who, currency_id, refund_collateral_amount)?;
-
-				return Ok(LiquidationStrategy::Exchange);
-			}
-
-			// create collateral auctions by cdp treasury
-			<T as Config>::CDPTreasury::create_collateral_auctions(
-				currency_id,
-				collateral,
-				target_stable_amount,
-				who.clone(),
-				true,
-			)?;
-
-			Ok(LiquidationStrategy::Auction)
-		})()?;
+		// try the steps of liquidation pipeline in order, the failed step is rolled
+		// back and the next one is tried.
+		let mut actual_weight: Weight = 0;
+		let liquidation_strategy = Self::get_liquidation_steps(currency_id)
+			.into_iter()
+			.find_map(|step| {
+				actual_weight = actual_weight.saturating_add(Self::liquidation_step_weight(&step));
+				with_transaction(|| {
+					match Self::try_liquidation_step(&who, currency_id, collateral, target_stable_amount, step) {
+						Ok(strategy) => TransactionOutcome::Commit(Some(strategy)),
+						Err(_) => TransactionOutcome::Rollback(None),
+					}
+				})
+			})
+			.ok_or(Error::<T>::LiquidationFailed)?;
 
 		Self::deposit_event(Event::LiquidateUnsafeCDP(
 			currency_id,
@@ -867,7 +1277,89 @@ impl<T: Config> Pallet<T> {
 			bad_debt_value,
 			liquidation_strategy,
 		));
-		Ok(())
+		Ok(actual_weight)
+	}
+
+	/// The weight of trying a step of liquidation pipeline, the keeper
+	/// contract sells collateral like a swap with DEX.
+	fn liquidation_step_weight(step: &LiquidationStep) -> Weight {
+		match step {
+			LiquidationStep::Auction => <T as Config>::WeightInfo::liquidate_by_auction(),
+			_ => <T as Config>::WeightInfo::liquidate_by_dex(),
+		}
+	}
+
+	/// The weight of trying the longest liquidation pipeline, which ends with
+	/// the auction step.
+	pub fn max_liquidation_weight() -> Weight {
+		<T as Config>::WeightInfo::liquidate_by_dex()
+			.saturating_mul(T::MaxLiquidationSteps::get().saturating_sub(1).max(1) as Weight)
+			.saturating_add(<T as Config>::WeightInfo::liquidate_by_auction())
+	}
+
+	/// The liquidation pipeline of collateral type, fallback to swap with DEX
+	/// in the limit of `MaxSlippageSwapWithDEX` then create collateral
+	/// auctions if not set.
+	pub fn get_liquidation_steps(currency_id: CurrencyId) -> Vec<LiquidationStep> {
+		Self::liquidation_steps(currency_id).unwrap_or_else(|| {
+			vec![
+				LiquidationStep::DexSwap(T::MaxSlippageSwapWithDEX::get()),
+				LiquidationStep::Auction,
+			]
+		})
+	}
+
+	fn try_liquidation_step(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral: Balance,
+		target_stable_amount: Balance,
+		step: LiquidationStep,
+	) -> Result<LiquidationStrategy, DispatchError> {
+		let actual_supply_collateral = match step {
+			LiquidationStep::DexSwap(slippage_limit) => <T as Config>::CDPTreasury::swap_collateral_to_exact_stable(
+				currency_id,
+				collateral,
+				target_stable_amount,
+				Some(slippage_limit),
+				None,
+				false,
+			)?,
+			LiquidationStep::StableSwap(via_currency_id, slippage_limit) => {
+				<T as Config>::CDPTreasury::swap_collateral_to_exact_stable(
+					currency_id,
+					collateral,
+					target_stable_amount,
+					Some(slippage_limit),
+					Some(&[currency_id, via_currency_id, T::GetStableCurrencyId::get()]),
+					false,
+				)?
+			}
+			LiquidationStep::Auction => {
+				<T as Config>::CDPTreasury::create_collateral_auctions(
+					currency_id,
+					collateral,
+					target_stable_amount,
+					who.clone(),
+					true,
+				)?;
+				return Ok(LiquidationStrategy::Auction);
+			}
+			LiquidationStep::KeeperContract(contract) => {
+				T::LiquidationKeeper::liquidate(contract, currency_id, collateral, target_stable_amount)?
+			}
+		};
+
+		// refund remain collateral to CDP owner
+		let refund_collateral_amount = collateral
+			.checked_sub(actual_supply_collateral)
+			.ok_or(Error::<T>::LiquidationFailed)?;
+		<T as Config>::CDPTreasury::withdraw_collateral(who, currency_id, refund_collateral_amount)?;
+
+		Ok(match step {
+			LiquidationStep::KeeperContract(_) => LiquidationStrategy::Keeper,
+			_ => LiquidationStrategy::Exchange,
+		})
 	}
 }
 
@@ -921,3 +1413,18 @@ impl<T: Config> RiskManager<T::AccountId, CurrencyId, Balance, Balance> for Pall
 		Ok(())
 	}
 }
+
+impl<T: Config> ParameterInvariants for Pallet<T> {
+	fn check_invariants() -> DispatchResult {
+		for currency_id in T::CollateralCurrencyIds::get() {
+			if let Some(required_collateral_ratio) = Self::collateral_params(currency_id).required_collateral_ratio {
+				ensure!(
+					required_collateral_ratio >= Self::get_liquidation_ratio(currency_id),
+					Error::<T>::InvalidCollateralParams
+				);
+			}
+		}
+
+		Ok(())
+	}
+}
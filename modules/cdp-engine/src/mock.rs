@@ -31,7 +31,7 @@ use sp_runtime::{
 	traits::{AccountIdConversion, IdentityLookup},
 };
 use sp_std::cell::RefCell;
-use support::{AuctionManager, EmergencyShutdown};
+use support::{AuctionManager, EmergencyShutdown, Erc20ReferenceCounter};
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
@@ -125,6 +125,9 @@ impl orml_currencies::Config for Runtime {
 
 parameter_types! {
 	pub const LoansPalletId: PalletId = PalletId(*b"aca/loan");
+	pub const MaxPositionLabelLength: u32 = 32;
+	pub const GetStakingCurrencyId: CurrencyId = DOT;
+	pub const GetLiquidCurrencyId: CurrencyId = LDOT;
 }
 
 impl loans::Config for Runtime {
@@ -135,6 +138,12 @@ impl loans::Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = ();
+	type MaxPositionLabelLength = MaxPositionLabelLength;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type Homa = ();
+	type StakingCurrencyId = GetStakingCurrencyId;
+	type LiquidCurrencyId = GetLiquidCurrencyId;
+	type CircuitBreaker = ();
 }
 
 thread_local! {
@@ -200,6 +209,12 @@ parameter_types! {
 	pub TreasuryAccount: AccountId = PalletId(*b"aca/hztr").into_account();
 }
 
+parameter_types! {
+	pub const SurplusRebalancePeriod: BlockNumber = 10;
+	pub const MaxSurplusAllocations: u32 = 5;
+	pub MaxSurplusSwapSlippage: Ratio = Ratio::saturating_from_rational(5, 100);
+}
+
 impl cdp_treasury::Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
@@ -208,6 +223,10 @@ impl cdp_treasury::Config for Runtime {
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = DEXModule;
 	type MaxAuctionsCount = MaxAuctionsCount;
+	type PriceSource = MockPriceSource;
+	type SurplusRebalancePeriod = SurplusRebalancePeriod;
+	type MaxSurplusAllocations = MaxSurplusAllocations;
+	type MaxSurplusSwapSlippage = MaxSurplusSwapSlippage;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
 	type WeightInfo = ();
@@ -217,6 +236,14 @@ parameter_types! {
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub const TradingPathLimit: u32 = 3;
+	pub const ProvisioningPeriod: BlockNumber = 1000;
+	pub const AmplificationRampPeriod: BlockNumber = 10;
+	pub const MaxRangeBuckets: u32 = 10;
+	pub const MaxBasketInputs: u32 = 3;
+	pub const VolumeBucketPeriod: BlockNumber = 10;
+	pub const MaxVolumeBuckets: u32 = 3;
+	pub const ListingBond: Balance = 100;
+	pub const ListingChallengePeriod: BlockNumber = 10;
 	pub EnabledTradingPairs : Vec<TradingPair> = vec![TradingPair::new(AUSD, BTC), TradingPair::new(AUSD, DOT)];
 }
 
@@ -227,9 +254,22 @@ impl module_dex::Config for Runtime {
 	type TradingPathLimit = TradingPathLimit;
 	type PalletId = DEXPalletId;
 	type CurrencyIdMapping = ();
+	type Erc20ReferenceCounter = ();
 	type DEXIncentives = ();
 	type WeightInfo = ();
 	type ListingOrigin = EnsureSignedBy<One, AccountId>;
+	type ProvisioningPeriod = ProvisioningPeriod;
+	type AmplificationRampPeriod = AmplificationRampPeriod;
+	type MaxRangeBuckets = MaxRangeBuckets;
+	type PositionNFT = ();
+	type MaxBasketInputs = MaxBasketInputs;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type ListingBond = ListingBond;
+	type ListingChallengePeriod = ListingChallengePeriod;
+	type ListingVetoOrigin = EnsureSignedBy<One, AccountId>;
+	type TreasuryAccount = TreasuryAccount;
+	type VolumeBucketPeriod = VolumeBucketPeriod;
+	type MaxVolumeBuckets = MaxVolumeBuckets;
 }
 
 parameter_types! {
@@ -257,6 +297,55 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 	}
 }
 
+thread_local! {
+	static ERC20_REFERENCES: RefCell<Vec<(PalletId, CurrencyId)>> = RefCell::new(vec![]);
+}
+
+pub fn erc20_references() -> Vec<(PalletId, CurrencyId)> {
+	ERC20_REFERENCES.with(|v| v.borrow().clone())
+}
+
+/// Records the references to all currencies, not only `CurrencyId::Erc20`.
+pub struct MockErc20ReferenceCounter;
+impl Erc20ReferenceCounter for MockErc20ReferenceCounter {
+	fn inc_reference(id: PalletId, currency_id: CurrencyId) {
+		ERC20_REFERENCES.with(|v| v.borrow_mut().push((id, currency_id)));
+	}
+
+	fn dec_reference(id: PalletId, currency_id: CurrencyId) {
+		ERC20_REFERENCES.with(|v| {
+			let mut references = v.borrow_mut();
+			if let Some(position) = references.iter().position(|reference| *reference == (id, currency_id)) {
+				references.remove(position);
+			}
+		});
+	}
+
+	fn references(_address: EvmAddress) -> Vec<(PalletId, u32)> {
+		Default::default()
+	}
+}
+
+pub fn keeper_contract() -> EvmAddress {
+	EvmAddress::repeat_byte(1)
+}
+
+pub struct MockLiquidationKeeper;
+impl LiquidationKeeper<CurrencyId, Balance> for MockLiquidationKeeper {
+	fn is_keeper(contract: EvmAddress) -> bool {
+		contract == keeper_contract()
+	}
+
+	fn liquidate(
+		_contract: EvmAddress,
+		_currency_id: CurrencyId,
+		_collateral_amount: Balance,
+		_target_stable_amount: Balance,
+	) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("keeper failed"))
+	}
+}
+
 ord_parameter_types! {
 	pub const One: AccountId = 1;
 }
@@ -267,6 +356,9 @@ parameter_types! {
 	pub DefaultLiquidationPenalty: Rate = Rate::saturating_from_rational(10, 100);
 	pub const MinimumDebitValue: Balance = 2;
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::saturating_from_rational(50, 100);
+	pub const MaxStabilityFeeExemptions: u32 = 2;
+	pub const MaxLiquidationSteps: u32 = 3;
+	pub const MaxLiquidationValuePerBlock: Balance = 150;
 	pub const UnsignedPriority: u64 = 1 << 20;
 	pub CollateralCurrencyIds: Vec<CurrencyId> = vec![BTC, DOT];
 }
@@ -285,7 +377,12 @@ impl Config for Runtime {
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
 	type UnsignedPriority = UnsignedPriority;
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type MaxStabilityFeeExemptions = MaxStabilityFeeExemptions;
+	type MaxLiquidationSteps = MaxLiquidationSteps;
+	type LiquidationKeeper = MockLiquidationKeeper;
+	type MaxLiquidationValuePerBlock = MaxLiquidationValuePerBlock;
 	type UnixTime = Timestamp;
+	type Erc20ReferenceCounter = MockErc20ReferenceCounter;
 	type WeightInfo = ();
 }
 
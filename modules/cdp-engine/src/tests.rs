@@ -247,6 +247,38 @@ fn set_collateral_params_work() {
 	});
 }
 
+#[test]
+fn check_invariants_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::check_invariants());
+
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(7, 5))),
+			Change::NoChange,
+		));
+		assert_noop!(
+			CDPEngineModule::check_invariants(),
+			Error::<Runtime>::InvalidCollateralParams
+		);
+
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(5, 4))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_ok!(CDPEngineModule::check_invariants());
+	});
+}
+
 #[test]
 fn calculate_collateral_ratio_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -401,6 +433,73 @@ fn adjust_position_work() {
 	});
 }
 
+#[test]
+fn simulate_adjust_position_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_eq!(
+			CDPEngineModule::simulate_adjust_position(&ALICE, ACA, 100, 50),
+			(
+				Position {
+					collateral: 100,
+					debit: 50
+				},
+				Err(Error::<Runtime>::InvalidCollateralType.into())
+			)
+		);
+		assert_eq!(
+			CDPEngineModule::simulate_adjust_position(&ALICE, BTC, 100, 50),
+			(
+				Position {
+					collateral: 100,
+					debit: 50
+				},
+				Ok(())
+			)
+		);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 1000);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_eq!(
+			CDPEngineModule::simulate_adjust_position(&ALICE, BTC, 0, 20),
+			(
+				Position {
+					collateral: 100,
+					debit: 70
+				},
+				Err(Error::<Runtime>::BelowRequiredCollateralRatio.into())
+			)
+		);
+		assert_eq!(
+			CDPEngineModule::simulate_adjust_position(&ALICE, BTC, -10, -20),
+			(
+				Position {
+					collateral: 90,
+					debit: 30
+				},
+				Ok(())
+			)
+		);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+		assert_eq!(
+			CDPEngineModule::get_collateral_ratio(BTC, 100, 50),
+			Some(Ratio::saturating_from_rational(2, 1))
+		);
+	});
+}
+
 #[test]
 fn remain_debit_value_too_small_check() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -477,6 +576,221 @@ fn liquidate_unsafe_cdp_by_collateral_auction() {
 	});
 }
 
+#[test]
+fn set_liquidation_steps_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let steps = vec![
+			LiquidationStep::StableSwap(DOT, Ratio::saturating_from_rational(1, 100)),
+			LiquidationStep::KeeperContract(keeper_contract()),
+			LiquidationStep::Auction,
+		];
+		assert_noop!(
+			CDPEngineModule::set_liquidation_steps(Origin::signed(2), BTC, steps.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_liquidation_steps(Origin::signed(1), AUSD, steps.clone()),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_noop!(
+			CDPEngineModule::set_liquidation_steps(
+				Origin::signed(1),
+				BTC,
+				vec![
+					LiquidationStep::Auction,
+					LiquidationStep::Auction,
+					LiquidationStep::Auction,
+					LiquidationStep::Auction
+				]
+			),
+			Error::<Runtime>::InvalidLiquidationSteps
+		);
+		assert_noop!(
+			CDPEngineModule::set_liquidation_steps(
+				Origin::signed(1),
+				BTC,
+				vec![
+					LiquidationStep::StableSwap(AUSD, Ratio::saturating_from_rational(1, 100)),
+					LiquidationStep::Auction
+				]
+			),
+			Error::<Runtime>::InvalidLiquidationSteps
+		);
+		assert_noop!(
+			CDPEngineModule::set_liquidation_steps(
+				Origin::signed(1),
+				BTC,
+				vec![
+					LiquidationStep::KeeperContract(EvmAddress::default()),
+					LiquidationStep::Auction
+				]
+			),
+			Error::<Runtime>::InvalidLiquidationSteps
+		);
+		assert_noop!(
+			CDPEngineModule::set_liquidation_steps(
+				Origin::signed(1),
+				BTC,
+				vec![LiquidationStep::DexSwap(MaxSlippageSwapWithDEX::get())]
+			),
+			Error::<Runtime>::InvalidLiquidationSteps
+		);
+		assert_noop!(
+			CDPEngineModule::set_liquidation_steps(
+				Origin::signed(1),
+				BTC,
+				vec![
+					LiquidationStep::Auction,
+					LiquidationStep::DexSwap(MaxSlippageSwapWithDEX::get())
+				]
+			),
+			Error::<Runtime>::InvalidLiquidationSteps
+		);
+		assert_eq!(
+			CDPEngineModule::get_liquidation_steps(BTC),
+			vec![
+				LiquidationStep::DexSwap(MaxSlippageSwapWithDEX::get()),
+				LiquidationStep::Auction
+			]
+		);
+
+		assert_ok!(CDPEngineModule::set_liquidation_steps(
+			Origin::signed(1),
+			BTC,
+			steps.clone()
+		));
+		let event = Event::cdp_engine(crate::Event::LiquidationStepsUpdated(BTC, steps.clone()));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(CDPEngineModule::get_liquidation_steps(BTC), steps);
+
+		assert_ok!(CDPEngineModule::set_liquidation_steps(Origin::signed(1), BTC, vec![]));
+		assert_eq!(CDPEngineModule::liquidation_steps(BTC), None);
+	});
+}
+
+#[test]
+fn liquidate_unsafe_cdp_by_liquidation_steps() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		// fallback to the next step, and only pay for the tried steps
+		assert_ok!(CDPEngineModule::set_liquidation_steps(
+			Origin::signed(1),
+			BTC,
+			vec![
+				LiquidationStep::KeeperContract(keeper_contract()),
+				LiquidationStep::Auction
+			]
+		));
+		assert_eq!(
+			CDPEngineModule::liquidate(Origin::none(), BTC, ALICE)
+				.unwrap()
+				.actual_weight,
+			Some(<() as WeightInfo>::liquidate_by_dex().saturating_add(<() as WeightInfo>::liquidate_by_auction()))
+		);
+		let liquidate_unsafe_cdp_event = Event::cdp_engine(crate::Event::LiquidateUnsafeCDP(
+			BTC,
+			ALICE,
+			100,
+			50,
+			LiquidationStrategy::Auction,
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == liquidate_unsafe_cdp_event));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+	});
+}
+
+#[test]
+fn liquidation_throttled_per_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 50));
+		assert_ok!(CDPEngineModule::adjust_position(&BOB, BTC, 100, 40));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_ok!(CDPEngineModule::set_liquidation_steps(
+			Origin::signed(1),
+			BTC,
+			vec![LiquidationStep::Auction]
+		));
+
+		// the riskier CDP has higher priority
+		let alice_liquidation = crate::Call::<Runtime>::liquidate(BTC, ALICE);
+		let bob_liquidation = crate::Call::<Runtime>::liquidate(BTC, BOB);
+		assert_eq!(
+			CDPEngineModule::validate_unsigned(TransactionSource::External, &alice_liquidation)
+				.unwrap()
+				.priority,
+			UnsignedPriority::get() + 1_000_000
+		);
+		assert_eq!(
+			CDPEngineModule::validate_unsigned(TransactionSource::External, &bob_liquidation)
+				.unwrap()
+				.priority,
+			UnsignedPriority::get() + 500_000
+		);
+
+		assert_ok!(CDPEngineModule::liquidate(Origin::none(), BTC, ALICE));
+		assert_eq!(CDPEngineModule::liquidated_value_in_block(), 100);
+
+		// the liquidation exceeds the cap is deferred to the next block
+		assert_eq!(
+			CDPEngineModule::validate_unsigned(TransactionSource::External, &bob_liquidation),
+			InvalidTransaction::ExhaustsResources.into()
+		);
+		assert_noop!(
+			CDPEngineModule::liquidate(Origin::none(), BTC, BOB),
+			Error::<Runtime>::ExceedLiquidationValuePerBlock
+		);
+
+		CDPEngineModule::on_finalize(1);
+		System::set_block_number(2);
+		assert_eq!(CDPEngineModule::liquidated_value_in_block(), 0);
+		assert_ok!(CDPEngineModule::liquidate(Origin::none(), BTC, BOB));
+		assert_eq!(LoansModule::positions(BTC, BOB).collateral, 0);
+	});
+}
+
 #[test]
 fn get_interest_rate_per_sec_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -600,6 +914,114 @@ fn accumulate_interest_work() {
 	});
 }
 
+#[test]
+fn set_stability_fee_exemption_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPEngineModule::set_stability_fee_exemption(Origin::signed(2), BTC, ALICE, Permill::one(), 100),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_stability_fee_exemption(Origin::signed(1), AUSD, ALICE, Permill::one(), 100),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_noop!(
+			CDPEngineModule::revoke_stability_fee_exemption(Origin::signed(1), BTC, ALICE),
+			Error::<Runtime>::StabilityFeeExemptionNotFound
+		);
+
+		assert_ok!(CDPEngineModule::set_stability_fee_exemption(
+			Origin::signed(1),
+			BTC,
+			ALICE,
+			Permill::from_percent(50),
+			100
+		));
+		let event = Event::cdp_engine(crate::Event::StabilityFeeExemptionGranted(
+			BTC,
+			ALICE,
+			Permill::from_percent(50),
+			100,
+		));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(
+			CDPEngineModule::stability_fee_exemptions(BTC, ALICE),
+			Some(StabilityFeeExemption {
+				fee_discount: Permill::from_percent(50),
+				debit_value_cap: 100,
+			})
+		);
+
+		assert_ok!(CDPEngineModule::set_stability_fee_exemption(
+			Origin::signed(1),
+			BTC,
+			BOB,
+			Permill::one(),
+			100
+		));
+		assert_noop!(
+			CDPEngineModule::set_stability_fee_exemption(Origin::signed(1), BTC, CAROL, Permill::one(), 100),
+			Error::<Runtime>::TooManyStabilityFeeExemptions
+		);
+		// updating an existing exemption is not limited
+		assert_ok!(CDPEngineModule::set_stability_fee_exemption(
+			Origin::signed(1),
+			BTC,
+			ALICE,
+			Permill::one(),
+			200
+		));
+
+		assert_ok!(CDPEngineModule::revoke_stability_fee_exemption(
+			Origin::signed(1),
+			BTC,
+			ALICE
+		));
+		let event = Event::cdp_engine(crate::Event::StabilityFeeExemptionRevoked(BTC, ALICE));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(CDPEngineModule::stability_fee_exemptions(BTC, ALICE), None);
+	});
+}
+
+#[test]
+fn accumulate_interest_with_stability_fee_exemption_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::set_stability_fee_exemption(
+			Origin::signed(1),
+			BTC,
+			ALICE,
+			Permill::one(),
+			300
+		));
+
+		CDPEngineModule::accumulate_interest(1, 0);
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 600, 300));
+		assert_ok!(CDPEngineModule::adjust_position(&BOB, BTC, 600, 300));
+
+		CDPEngineModule::accumulate_interest(2, 1);
+		assert_eq!(
+			CDPEngineModule::debit_exchange_rate(BTC),
+			Some(ExchangeRate::saturating_from_rational(101, 100))
+		);
+		let event = Event::cdp_engine(crate::Event::StabilityFeeExemptionConsumed(BTC, ALICE, 3));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(LoansModule::positions(BTC, &ALICE).debit, 298);
+		assert_eq!(LoansModule::positions(BTC, &BOB).debit, 300);
+		assert_eq!(LoansModule::total_positions(BTC).debit, 598);
+	});
+}
+
 #[test]
 fn settle_cdp_has_debit_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -722,3 +1144,51 @@ fn close_cdp_has_debit_by_dex_work() {
 		assert_eq!(CDPTreasuryModule::get_debit_pool(), 50);
 	});
 }
+
+#[test]
+fn set_collateral_params_records_erc20_reference() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(erc20_references(), vec![]);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_eq!(erc20_references(), vec![(LoansPalletId::get(), BTC)]);
+
+		// the collateral type is referenced once
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(10000),
+		));
+		assert_eq!(erc20_references(), vec![(LoansPalletId::get(), BTC)]);
+	});
+}
+
+#[test]
+fn on_runtime_upgrade_records_erc20_references() {
+	ExtBuilder::default().build().execute_with(|| {
+		CollateralParams::<Runtime>::insert(BTC, RiskManagementParams::default());
+		CollateralParams::<Runtime>::insert(DOT, RiskManagementParams::default());
+
+		CDPEngineModule::on_runtime_upgrade();
+		let references = erc20_references();
+		assert_eq!(references.len(), 2);
+		assert!(references.contains(&(LoansPalletId::get(), BTC)));
+		assert!(references.contains(&(LoansPalletId::get(), DOT)));
+		assert!(UpgradedToErc20References::<Runtime>::get());
+
+		// only migrated once
+		CDPEngineModule::on_runtime_upgrade();
+		assert_eq!(erc20_references().len(), 2);
+	});
+}
@@ -53,6 +53,9 @@ pub trait WeightInfo {
 	fn liquidate_by_auction() -> Weight;
 	fn liquidate_by_dex() -> Weight;
 	fn settle() -> Weight;
+	fn set_stability_fee_exemption() -> Weight;
+	fn revoke_stability_fee_exemption() -> Weight;
+	fn set_liquidation_steps() -> Weight;
 }
 
 /// Weights for module_cdp_engine using the Acala node and recommended hardware.
@@ -88,6 +91,19 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(11 as Weight))
 			.saturating_add(T::DbWeight::get().writes(8 as Weight))
 	}
+	fn set_stability_fee_exemption() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn revoke_stability_fee_exemption() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_liquidation_steps() -> Weight {
+		(19_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -122,4 +138,17 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
 	}
+	fn set_stability_fee_exemption() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn revoke_stability_fee_exemption() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_liquidation_steps() -> Weight {
+		(19_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
@@ -24,6 +24,10 @@
 //! CDPs, and handle excessive surplus or debits timely in order to keep the
 //! system healthy with low risk. It's the only entry for issuing/burning stable
 //! coin for whole system.
+//!
+//! Besides the stable currency, the surplus can be diversified into a portfolio
+//! with target allocations set by governance, the portfolio is rebalanced
+//! periodically by swapping through DEX within the slippage limit.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -33,10 +37,11 @@ use frame_system::pallet_prelude::*;
 use orml_traits::{MultiCurrency, MultiCurrencyExtended};
 use primitives::{Balance, CurrencyId};
 use sp_runtime::{
-	traits::{AccountIdConversion, One, Zero},
-	DispatchError, DispatchResult, FixedPointNumber,
+	traits::{AccountIdConversion, One, Saturating, Zero},
+	DispatchError, DispatchResult, FixedPointNumber, Permill,
 };
-use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, Ratio};
+use sp_std::prelude::*;
+use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, Price, PriceProvider, Ratio};
 
 mod mock;
 mod tests;
@@ -71,6 +76,23 @@ pub mod module {
 		/// currency
 		type DEX: DEXManager<Self::AccountId, CurrencyId, Balance>;
 
+		/// The price source to value the surplus portfolio
+		type PriceSource: PriceProvider<CurrencyId>;
+
+		/// The period in blocks to rebalance the surplus portfolio.
+		/// If set to 0, does not work.
+		#[pallet::constant]
+		type SurplusRebalancePeriod: Get<Self::BlockNumber>;
+
+		/// The max count of non-stable assets in the surplus portfolio
+		#[pallet::constant]
+		type MaxSurplusAllocations: Get<u32>;
+
+		/// The max slippage compared to oracle price when swapping to
+		/// rebalance the surplus portfolio
+		#[pallet::constant]
+		type MaxSurplusSwapSlippage: Get<Ratio>;
+
 		/// The cap of lots number when create collateral auction on a
 		/// liquidation or to create debit/surplus auction on block end.
 		/// If set to 0, does not work.
@@ -101,6 +123,10 @@ pub mod module {
 		DebitPoolNotEnough,
 		/// The swap path is invalid
 		InvalidSwapPath,
+		/// The surplus allocations exceed the max count
+		TooManySurplusAllocations,
+		/// The surplus allocations are invalid
+		InvalidSurplusAllocations,
 	}
 
 	#[pallet::event]
@@ -109,6 +135,12 @@ pub mod module {
 		/// The expected amount size for per lot collateral auction of specific
 		/// collateral type updated. \[collateral_type, new_size\]
 		ExpectedCollateralAuctionSizeUpdated(CurrencyId, Balance),
+		/// The target allocations of surplus portfolio updated.
+		/// \[allocations\]
+		SurplusAllocationsUpdated(Vec<(CurrencyId, Permill)>),
+		/// Swapped to rebalance the surplus portfolio. \[supply_currency_id,
+		/// supply_amount, target_currency_id, target_amount\]
+		SurplusRebalanced(CurrencyId, Balance, CurrencyId, Balance),
 	}
 
 	/// The expected amount size for per lot collateral auction of specific
@@ -123,6 +155,22 @@ pub mod module {
 	#[pallet::getter(fn debit_pool)]
 	pub type DebitPool<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+	/// The target allocations of non-stable assets in the surplus
+	/// portfolio, the rest is held in stable currency.
+	///
+	/// SurplusAllocations: Vec<(CurrencyId, Permill)>
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_allocations)]
+	pub type SurplusAllocations<T: Config> = StorageValue<_, Vec<(CurrencyId, Permill)>, ValueQuery>;
+
+	/// The amount of non-stable assets held by the surplus portfolio, which
+	/// are not counted as collaterals.
+	///
+	/// SurplusHoldings: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_holdings)]
+	pub type SurplusHoldings<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig {
 		pub expected_collateral_auction_size: Vec<(CurrencyId, Balance)>,
@@ -153,6 +201,17 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Rebalance the surplus portfolio periodically
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let period = T::SurplusRebalancePeriod::get();
+			if !period.is_zero() && (now % period).is_zero() {
+				let count = Self::rebalance_surplus();
+				T::WeightInfo::on_initialize(count)
+			} else {
+				0
+			}
+		}
+
 		/// Handle excessive surplus or debits of system when block end
 		fn on_finalize(_now: T::BlockNumber) {
 			// offset the same amount between debit pool and surplus pool
@@ -214,6 +273,44 @@ pub mod module {
 			Self::deposit_event(Event::ExpectedCollateralAuctionSizeUpdated(currency_id, size));
 			Ok(().into())
 		}
+
+		/// Update the target allocations of surplus portfolio, the rest of
+		/// surplus is held in stable currency.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `allocations`: target weights of non-stable assets, replace the
+		///   old ones
+		#[pallet::weight((T::WeightInfo::set_surplus_allocations(allocations.len() as u32), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_surplus_allocations(
+			origin: OriginFor<T>,
+			allocations: Vec<(CurrencyId, Permill)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				allocations.len() as u32 <= T::MaxSurplusAllocations::get(),
+				Error::<T>::TooManySurplusAllocations
+			);
+
+			let stable_currency_id = T::GetStableCurrencyId::get();
+			let mut total_parts: u32 = 0;
+			for (i, (currency_id, weight)) in allocations.iter().enumerate() {
+				ensure!(
+					*currency_id != stable_currency_id && !allocations[..i].iter().any(|(id, _)| id == currency_id),
+					Error::<T>::InvalidSurplusAllocations
+				);
+				total_parts = total_parts.saturating_add(weight.deconstruct());
+			}
+			ensure!(
+				total_parts <= Permill::one().deconstruct(),
+				Error::<T>::InvalidSurplusAllocations
+			);
+
+			SurplusAllocations::<T>::put(allocations.clone());
+			Self::deposit_event(Event::SurplusAllocationsUpdated(allocations));
+			Ok(().into())
+		}
 	}
 }
 
@@ -228,17 +325,140 @@ impl<T: Config> Pallet<T> {
 		T::Currency::free_balance(T::GetStableCurrencyId::get(), &Self::account_id())
 	}
 
-	/// Get total collateral amount of cdp treasury module.
+	/// Get total collateral amount of cdp treasury module, the assets held
+	/// by surplus portfolio are excluded.
 	pub fn total_collaterals(currency_id: CurrencyId) -> Balance {
-		T::Currency::free_balance(currency_id, &Self::account_id())
+		T::Currency::free_balance(currency_id, &Self::account_id()).saturating_sub(Self::surplus_holdings(currency_id))
 	}
 
 	/// Get collateral amount not in auction
 	pub fn total_collaterals_not_in_auction(currency_id: CurrencyId) -> Balance {
-		T::Currency::free_balance(currency_id, &Self::account_id())
+		Self::total_collaterals(currency_id)
 			.saturating_sub(T::AuctionManagerHandler::get_total_collateral_in_auction(currency_id))
 	}
 
+	/// Swap the surplus assets to approach the target allocations, sell the
+	/// overweight assets first and then buy the underweight assets with
+	/// the stable surplus which is not used to offset debit. Returns the count
+	/// of assets in the portfolio.
+	fn rebalance_surplus() -> u32 {
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		let max_slippage = T::MaxSurplusSwapSlippage::get();
+		let min_received_rate = Ratio::one().saturating_sub(max_slippage);
+
+		// the holdings which are no longer allocated will be sold out
+		let mut allocations = Self::surplus_allocations();
+		for (currency_id, _) in SurplusHoldings::<T>::iter().collect::<Vec<_>>() {
+			if !allocations.iter().any(|(id, _)| *id == currency_id) {
+				allocations.push((currency_id, Permill::zero()));
+			}
+		}
+		let count = allocations.len() as u32;
+
+		// value the portfolio in stable currency, skip the assets without price
+		let mut total_value = Self::surplus_pool();
+		let mut holdings: Vec<(CurrencyId, Permill, Balance, Price)> = vec![];
+		for (currency_id, target) in allocations {
+			if let Some(price) = T::PriceSource::get_relative_price(currency_id, stable_currency_id) {
+				if !price.is_zero() {
+					let amount = Self::surplus_holdings(currency_id);
+					total_value = total_value.saturating_add(price.saturating_mul_int(amount));
+					holdings.push((currency_id, target, amount, price));
+				}
+			}
+		}
+
+		for (currency_id, target, amount, price) in holdings.iter().copied() {
+			let value = price.saturating_mul_int(amount);
+			let target_value = target * total_value;
+			if value > target_value {
+				let supply_amount = price
+					.reciprocal()
+					.unwrap_or_default()
+					.saturating_mul_int(value.saturating_sub(target_value))
+					.min(amount);
+				let min_target_amount = min_received_rate.saturating_mul_int(price.saturating_mul_int(supply_amount));
+				Self::swap_surplus(
+					currency_id,
+					stable_currency_id,
+					supply_amount,
+					min_target_amount,
+					max_slippage,
+				);
+			}
+		}
+
+		for (currency_id, target, amount, price) in holdings {
+			let value = price.saturating_mul_int(amount);
+			let target_value = target * total_value;
+			if value < target_value {
+				let available = Self::surplus_pool().saturating_sub(Self::debit_pool());
+				let supply_amount = target_value.saturating_sub(value).min(available);
+				let min_target_amount = price
+					.reciprocal()
+					.map(|rate| min_received_rate.saturating_mul_int(rate.saturating_mul_int(supply_amount)))
+					.unwrap_or_default();
+				Self::swap_surplus(
+					stable_currency_id,
+					currency_id,
+					supply_amount,
+					min_target_amount,
+					max_slippage,
+				);
+			}
+		}
+
+		count
+	}
+
+	fn swap_surplus(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: Balance,
+		min_target_amount: Balance,
+		max_slippage: Ratio,
+	) {
+		if supply_amount.is_zero() {
+			return;
+		}
+
+		match T::DEX::swap_with_exact_supply(
+			&Self::account_id(),
+			&[supply_currency_id, target_currency_id],
+			supply_amount,
+			min_target_amount,
+			Some(max_slippage),
+		) {
+			Ok(target_amount) => {
+				let stable_currency_id = T::GetStableCurrencyId::get();
+				if supply_currency_id != stable_currency_id {
+					SurplusHoldings::<T>::mutate_exists(supply_currency_id, |maybe_amount| {
+						let remain = maybe_amount.unwrap_or_default().saturating_sub(supply_amount);
+						*maybe_amount = if remain.is_zero() { None } else { Some(remain) };
+					});
+				}
+				if target_currency_id != stable_currency_id {
+					SurplusHoldings::<T>::mutate(target_currency_id, |amount| {
+						*amount = amount.saturating_add(target_amount)
+					});
+				}
+				Self::deposit_event(Event::SurplusRebalanced(
+					supply_currency_id,
+					supply_amount,
+					target_currency_id,
+					target_amount,
+				));
+			}
+			Err(e) => {
+				log::warn!(
+					target: "cdp-treasury",
+					"rebalance_surplus: Attempt to swap {:?} {:?} to {:?} failed: {:?}",
+					supply_amount, supply_currency_id, target_currency_id, e
+				);
+			}
+		}
+	}
+
 	fn offset_surplus_and_debit() {
 		let offset_amount = sp_std::cmp::min(Self::debit_pool(), Self::surplus_pool());
 
@@ -122,6 +122,14 @@ parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub const TradingPathLimit: u32 = 3;
+	pub const ProvisioningPeriod: BlockNumber = 1000;
+	pub const AmplificationRampPeriod: BlockNumber = 10;
+	pub const MaxRangeBuckets: u32 = 10;
+	pub const MaxBasketInputs: u32 = 3;
+	pub const VolumeBucketPeriod: BlockNumber = 10;
+	pub const MaxVolumeBuckets: u32 = 3;
+	pub const ListingBond: Balance = 100;
+	pub const ListingChallengePeriod: BlockNumber = 10;
 	pub EnabledTradingPairs : Vec<TradingPair> = vec![TradingPair::new(AUSD, BTC), TradingPair::new(AUSD, DOT), TradingPair::new(BTC, DOT)];
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
 }
@@ -133,9 +141,22 @@ impl module_dex::Config for Runtime {
 	type TradingPathLimit = TradingPathLimit;
 	type PalletId = DEXPalletId;
 	type CurrencyIdMapping = ();
+	type Erc20ReferenceCounter = ();
 	type DEXIncentives = ();
 	type WeightInfo = ();
 	type ListingOrigin = EnsureSignedBy<One, AccountId>;
+	type ProvisioningPeriod = ProvisioningPeriod;
+	type AmplificationRampPeriod = AmplificationRampPeriod;
+	type MaxRangeBuckets = MaxRangeBuckets;
+	type PositionNFT = ();
+	type MaxBasketInputs = MaxBasketInputs;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type ListingBond = ListingBond;
+	type ListingChallengePeriod = ListingChallengePeriod;
+	type ListingVetoOrigin = EnsureSignedBy<One, AccountId>;
+	type TreasuryAccount = TreasuryAccount;
+	type VolumeBucketPeriod = VolumeBucketPeriod;
+	type MaxVolumeBuckets = MaxVolumeBuckets;
 }
 
 thread_local! {
@@ -187,6 +208,36 @@ thread_local! {
 	static IS_SHUTDOWN: RefCell<bool> = RefCell::new(false);
 }
 
+thread_local! {
+	static RELATIVE_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::one()));
+}
+
+pub struct MockPriceSource;
+impl MockPriceSource {
+	pub fn set_relative_price(price: Option<Price>) {
+		RELATIVE_PRICE.with(|v| *v.borrow_mut() = price);
+	}
+}
+impl PriceProvider<CurrencyId> for MockPriceSource {
+	fn get_relative_price(_base: CurrencyId, _quote: CurrencyId) -> Option<Price> {
+		RELATIVE_PRICE.with(|v| *v.borrow_mut())
+	}
+
+	fn get_price(_currency_id: CurrencyId) -> Option<Price> {
+		None
+	}
+
+	fn lock_price(_currency_id: CurrencyId) {}
+
+	fn unlock_price(_currency_id: CurrencyId) {}
+}
+
+parameter_types! {
+	pub const SurplusRebalancePeriod: BlockNumber = 10;
+	pub const MaxSurplusAllocations: u32 = 5;
+	pub MaxSurplusSwapSlippage: Ratio = Ratio::saturating_from_rational(5, 100);
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
@@ -195,6 +246,10 @@ impl Config for Runtime {
 	type UpdateOrigin = EnsureOneOf<AccountId, EnsureRoot<AccountId>, EnsureSignedBy<One, AccountId>>;
 	type DEX = DEXModule;
 	type MaxAuctionsCount = MaxAuctionsCount;
+	type PriceSource = MockPriceSource;
+	type SurplusRebalancePeriod = SurplusRebalancePeriod;
+	type MaxSurplusAllocations = MaxSurplusAllocations;
+	type MaxSurplusSwapSlippage = MaxSurplusSwapSlippage;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
 	type WeightInfo = ();
@@ -420,3 +420,110 @@ fn extract_surplus_to_treasury_work() {
 		assert_eq!(Currencies::free_balance(AUSD, &TreasuryAccount::get()), 200);
 	});
 }
+
+#[test]
+fn set_surplus_allocations_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_allocations(Origin::signed(5), vec![(DOT, Permill::from_percent(30))]),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_allocations(Origin::signed(1), vec![(DOT, Permill::from_percent(1)); 6]),
+			Error::<Runtime>::TooManySurplusAllocations
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_allocations(Origin::signed(1), vec![(AUSD, Permill::from_percent(30))]),
+			Error::<Runtime>::InvalidSurplusAllocations
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_allocations(
+				Origin::signed(1),
+				vec![(DOT, Permill::from_percent(30)), (DOT, Permill::from_percent(20))]
+			),
+			Error::<Runtime>::InvalidSurplusAllocations
+		);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_allocations(
+				Origin::signed(1),
+				vec![(DOT, Permill::from_percent(70)), (BTC, Permill::from_percent(40))]
+			),
+			Error::<Runtime>::InvalidSurplusAllocations
+		);
+
+		assert_ok!(CDPTreasuryModule::set_surplus_allocations(
+			Origin::signed(1),
+			vec![(DOT, Permill::from_percent(30)), (BTC, Permill::from_percent(20))]
+		));
+		assert_eq!(
+			CDPTreasuryModule::surplus_allocations(),
+			vec![(DOT, Permill::from_percent(30)), (BTC, Permill::from_percent(20))]
+		);
+		let surplus_allocations_updated_event = Event::cdp_treasury(crate::Event::SurplusAllocationsUpdated(vec![
+			(DOT, Permill::from_percent(30)),
+			(BTC, Permill::from_percent(20)),
+		]));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == surplus_allocations_updated_event));
+	});
+}
+
+#[test]
+fn rebalance_surplus_on_initialize_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(BOB),
+			DOT,
+			AUSD,
+			1000,
+			1000,
+			false
+		));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(100));
+		assert_ok!(CDPTreasuryModule::set_surplus_allocations(
+			Origin::signed(1),
+			vec![(DOT, Permill::from_percent(30))]
+		));
+
+		// not the rebalance period
+		CDPTreasuryModule::on_initialize(5);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+		assert_eq!(CDPTreasuryModule::surplus_holdings(DOT), 0);
+
+		// no surplus to buy when it's needed to offset debit
+		assert_ok!(CDPTreasuryModule::on_system_debit(100));
+		CDPTreasuryModule::on_initialize(10);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+		assert_eq!(CDPTreasuryModule::surplus_holdings(DOT), 0);
+		CDPTreasuryModule::on_finalize(10);
+		assert_ok!(CDPTreasuryModule::on_system_surplus(100));
+
+		// buy the underweight asset
+		CDPTreasuryModule::on_initialize(20);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 70);
+		assert_eq!(CDPTreasuryModule::surplus_holdings(DOT), 29);
+		assert_eq!(Currencies::free_balance(DOT, &CDPTreasuryModule::account_id()), 29);
+		assert_eq!(CDPTreasuryModule::total_collaterals(DOT), 0);
+		let surplus_rebalanced_event = Event::cdp_treasury(crate::Event::SurplusRebalanced(AUSD, 30, DOT, 29));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == surplus_rebalanced_event));
+
+		// the swap exceeds the slippage limit compared to oracle price
+		assert_ok!(CDPTreasuryModule::set_surplus_allocations(Origin::signed(1), vec![]));
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(2, 1)));
+		CDPTreasuryModule::on_initialize(30);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 70);
+		assert_eq!(CDPTreasuryModule::surplus_holdings(DOT), 29);
+
+		// sell out the asset which is no longer allocated
+		MockPriceSource::set_relative_price(Some(Price::one()));
+		CDPTreasuryModule::on_initialize(40);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 99);
+		assert_eq!(CDPTreasuryModule::surplus_holdings(DOT), 0);
+		assert_eq!(SurplusHoldings::<Runtime>::contains_key(DOT), false);
+	});
+}
@@ -48,14 +48,23 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for module_cdp_treasury.
 pub trait WeightInfo {
+	fn on_initialize(c: u32, ) -> Weight;
 	fn extract_surplus_to_treasury() -> Weight;
 	fn auction_collateral() -> Weight;
 	fn set_expected_collateral_auction_size() -> Weight;
+	fn set_surplus_allocations(c: u32, ) -> Weight;
 }
 
 /// Weights for module_cdp_treasury using the Acala node and recommended hardware.
 pub struct AcalaWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
+	fn on_initialize(c: u32, ) -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add((182_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)))
+	}
 	fn extract_surplus_to_treasury() -> Weight {
 		(124_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
@@ -70,10 +79,22 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 		(14_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn set_surplus_allocations(c: u32, ) -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add((1_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	fn on_initialize(c: u32, ) -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add((182_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)))
+	}
 	fn extract_surplus_to_treasury() -> Weight {
 		(124_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
@@ -88,4 +109,9 @@ impl WeightInfo for () {
 		(14_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn set_surplus_allocations(c: u32, ) -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add((1_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
@@ -0,0 +1,381 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Circuit Breaker Module
+//!
+//! ## Overview
+//!
+//! Monitors onchain signals and automatically suspends risky operations for
+//! a cool-down period when they exceed the configured thresholds. Each signal
+//! can be registered with several trigger tiers, a higher tier usually
+//! suspends more operations for a longer period. The monitored signals are:
+//!
+//! - the deviation between the oracle price and the DEX price
+//! - the oracle price drop since the previous block
+//! - the increase of the bad debt in CDP treasury since the previous block, in
+//!   proportion to the issuance of the stable currency
+//!
+//! While withdrawal is tripped, the collateral withdrawn during the trip is
+//! accumulated per currency and capped by the withdrawal threshold, so the cap
+//! can't be bypassed by splitting the withdrawal.
+//!
+//! Other modules can register trigger tiers, report signals and check the
+//! operations through the `CircuitBreaker` trait. Governance can update the
+//! tiers, trip or reset an operation manually to override the automatic
+//! triggers.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{
+	traits::{Bounded, CheckedDiv, Saturating, Zero},
+	FixedPointNumber,
+};
+use sp_std::prelude::*;
+use support::{
+	CDPTreasury, CircuitBreaker, CircuitSignal, DEXManager, GuardedOperation, Price, PriceProvider, Ratio, TriggerTier,
+};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The list of currencies whose prices are monitored
+		#[pallet::constant]
+		type MonitoredCurrencyIds: Get<Vec<CurrencyId>>;
+
+		/// Stable currency id, the quote of monitored prices
+		#[pallet::constant]
+		type GetStableCurrencyId: Get<CurrencyId>;
+
+		/// The oracle price source, the price drop is measured by oracle
+		/// prices
+		type PriceSource: PriceProvider<CurrencyId>;
+
+		/// DEX to get the market prices to compare with the oracle prices
+		type DEX: DEXManager<Self::AccountId, CurrencyId, Balance>;
+
+		/// CDP treasury to get the bad debt and its proportion
+		type CDPTreasury: CDPTreasury<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
+
+		/// The max count of trigger tiers for a signal
+		#[pallet::constant]
+		type MaxTriggerTiers: Get<u32>;
+
+		/// The origin which may update trigger tiers, trip or reset
+		/// operations.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Trigger tiers exceed the max count
+		TooManyTriggerTiers,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The trigger tiers of signal updated. \[signal\]
+		TriggerTiersUpdated(CircuitSignal),
+		/// The withdrawal threshold of currency updated. \[currency_id,
+		/// threshold\]
+		WithdrawalThresholdUpdated(CurrencyId, Balance),
+		/// Operation tripped. \[operation, until\]
+		OperationTripped(GuardedOperation, T::BlockNumber),
+		/// Operation reset. \[operation\]
+		OperationReset(GuardedOperation),
+	}
+
+	/// The trigger tiers of signals, sorted by threshold.
+	///
+	/// TriggerTiers: map CircuitSignal => Vec<TriggerTier>
+	#[pallet::storage]
+	#[pallet::getter(fn trigger_tiers)]
+	pub type TriggerTiers<T: Config> =
+		StorageMap<_, Twox64Concat, CircuitSignal, Vec<TriggerTier<T::BlockNumber>>, ValueQuery>;
+
+	/// The tripped operations, suspended until the block number.
+	///
+	/// TrippedUntil: map GuardedOperation => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn tripped_until)]
+	pub type TrippedUntil<T: Config> = StorageMap<_, Twox64Concat, GuardedOperation, T::BlockNumber, OptionQuery>;
+
+	/// The block number at which the tripped operations were tripped.
+	///
+	/// TrippedSince: map GuardedOperation => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn tripped_since)]
+	pub type TrippedSince<T: Config> = StorageMap<_, Twox64Concat, GuardedOperation, T::BlockNumber, OptionQuery>;
+
+	/// The max total amount of collateral allowed to withdraw while
+	/// withdrawal is tripped.
+	///
+	/// WithdrawalThresholds: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn withdrawal_thresholds)]
+	pub type WithdrawalThresholds<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// The collateral withdrawn while withdrawal is tripped, with the block
+	/// number at which withdrawal was tripped.
+	///
+	/// TrippedWithdrawals: map CurrencyId => (BlockNumber, Balance)
+	#[pallet::storage]
+	#[pallet::getter(fn tripped_withdrawals)]
+	pub type TrippedWithdrawals<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, (T::BlockNumber, Balance), OptionQuery>;
+
+	/// The oracle prices of monitored currencies at the previous block.
+	///
+	/// LastPrices: map CurrencyId => Option<Price>
+	#[pallet::storage]
+	#[pallet::getter(fn last_prices)]
+	pub type LastPrices<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
+
+	/// The bad debt of CDP treasury at the previous block.
+	#[pallet::storage]
+	#[pallet::getter(fn last_debit_pool)]
+	pub type LastDebitPool<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Check the monitored signals and trip the operations if the
+		/// thresholds are reached.
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			Self::monitor();
+			T::WeightInfo::on_initialize(T::MonitoredCurrencyIds::get().len() as u32)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Update the trigger tiers of signal.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `signal`: the monitored signal
+		/// - `tiers`: the new trigger tiers, replace the old ones
+		#[pallet::weight((T::WeightInfo::set_trigger_tiers(), DispatchClass::Operational))]
+		pub fn set_trigger_tiers(
+			origin: OriginFor<T>,
+			signal: CircuitSignal,
+			mut tiers: Vec<TriggerTier<T::BlockNumber>>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				tiers.len() as u32 <= T::MaxTriggerTiers::get(),
+				Error::<T>::TooManyTriggerTiers
+			);
+
+			tiers.sort_by_key(|tier| tier.threshold);
+			TriggerTiers::<T>::insert(signal, tiers);
+			Self::deposit_event(Event::TriggerTiersUpdated(signal));
+			Ok(().into())
+		}
+
+		/// Update the max total amount of collateral allowed to withdraw
+		/// while withdrawal is tripped.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `threshold`: the max total amount allowed to withdraw.
+		#[pallet::weight((T::WeightInfo::set_withdrawal_threshold(), DispatchClass::Operational))]
+		pub fn set_withdrawal_threshold(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			threshold: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			WithdrawalThresholds::<T>::insert(currency_id, threshold);
+			Self::deposit_event(Event::WithdrawalThresholdUpdated(currency_id, threshold));
+			Ok(().into())
+		}
+
+		/// Trip the operation for `cool_down` blocks, override the automatic
+		/// triggers.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight((T::WeightInfo::force_trip(), DispatchClass::Operational))]
+		pub fn force_trip(
+			origin: OriginFor<T>,
+			operation: GuardedOperation,
+			cool_down: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let until = <frame_system::Pallet<T>>::block_number().saturating_add(cool_down);
+			Self::trip(operation, until);
+			Ok(().into())
+		}
+
+		/// Reset the tripped operation, override the automatic triggers.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight((T::WeightInfo::force_reset(), DispatchClass::Operational))]
+		pub fn force_reset(origin: OriginFor<T>, operation: GuardedOperation) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			TrippedUntil::<T>::remove(operation);
+			TrippedSince::<T>::remove(operation);
+			Self::deposit_event(Event::OperationReset(operation));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn monitor() {
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		for currency_id in T::MonitoredCurrencyIds::get() {
+			let oracle_price = match T::PriceSource::get_relative_price(currency_id, stable_currency_id) {
+				Some(price) if !price.is_zero() => price,
+				_ => continue,
+			};
+
+			let (pool_currency, pool_stable) = T::DEX::get_liquidity_pool(currency_id, stable_currency_id);
+			if let Some(dex_price) = Price::checked_from_rational(pool_stable, pool_currency) {
+				if !dex_price.is_zero() {
+					let deviation = if dex_price > oracle_price {
+						dex_price.saturating_sub(oracle_price)
+					} else {
+						oracle_price.saturating_sub(dex_price)
+					};
+					Self::trip_reached_tiers(
+						CircuitSignal::OracleDeviation,
+						deviation.checked_div(&oracle_price).unwrap_or_else(Ratio::max_value),
+					);
+				}
+			}
+
+			if let Some(last_price) = Self::last_prices(currency_id) {
+				if oracle_price < last_price {
+					let drop = last_price
+						.saturating_sub(oracle_price)
+						.checked_div(&last_price)
+						.unwrap_or_default();
+					Self::trip_reached_tiers(CircuitSignal::PriceCrash, drop);
+				}
+			}
+			LastPrices::<T>::insert(currency_id, oracle_price);
+		}
+
+		let debit_pool = T::CDPTreasury::get_debit_pool();
+		let last_debit_pool = Self::last_debit_pool();
+		if debit_pool > last_debit_pool {
+			// in proportion to the issuance of stable currency, rather than the last bad
+			// debt which may be zero
+			let spike = T::CDPTreasury::get_debit_proportion(debit_pool.saturating_sub(last_debit_pool));
+			Self::trip_reached_tiers(CircuitSignal::BadDebtSpike, spike);
+		}
+		if debit_pool != last_debit_pool {
+			LastDebitPool::<T>::put(debit_pool);
+		}
+	}
+
+	fn trip_reached_tiers(signal: CircuitSignal, magnitude: Ratio) {
+		let now = <frame_system::Pallet<T>>::block_number();
+		for tier in Self::trigger_tiers(signal) {
+			if magnitude < tier.threshold {
+				break;
+			}
+
+			let until = now.saturating_add(tier.cool_down);
+			for operation in tier.operations {
+				if Self::tripped_until(operation).map_or(true, |current| current < until) {
+					Self::trip(operation, until);
+				}
+			}
+		}
+	}
+
+	fn trip(operation: GuardedOperation, until: T::BlockNumber) {
+		// a new trip starts if the operation isn't tripped, otherwise the trip is
+		// extended
+		if !Self::is_tripped(operation) {
+			TrippedSince::<T>::insert(operation, <frame_system::Pallet<T>>::block_number());
+		}
+		TrippedUntil::<T>::insert(operation, until);
+		Self::deposit_event(Event::OperationTripped(operation, until));
+	}
+
+	/// Return true if the operation is suspended at the current block.
+	pub fn is_tripped(operation: GuardedOperation) -> bool {
+		Self::tripped_until(operation).map_or(false, |until| <frame_system::Pallet<T>>::block_number() < until)
+	}
+}
+
+impl<T: Config> CircuitBreaker<T::BlockNumber> for Pallet<T> {
+	fn register_trigger(signal: CircuitSignal, tier: TriggerTier<T::BlockNumber>) -> DispatchResult {
+		TriggerTiers::<T>::try_mutate(signal, |tiers| -> DispatchResult {
+			ensure!(
+				(tiers.len() as u32) < T::MaxTriggerTiers::get(),
+				Error::<T>::TooManyTriggerTiers
+			);
+			tiers.push(tier);
+			tiers.sort_by_key(|tier| tier.threshold);
+			Ok(())
+		})
+	}
+
+	fn report(signal: CircuitSignal, magnitude: Ratio) {
+		Self::trip_reached_tiers(signal, magnitude);
+	}
+
+	fn try_allow(operation: GuardedOperation, currency_id: CurrencyId, amount: Balance) -> bool {
+		if !Self::is_tripped(operation) {
+			return true;
+		}
+
+		match operation {
+			GuardedOperation::NewLoan => false,
+			GuardedOperation::Withdrawal => {
+				let since = Self::tripped_since(operation).unwrap_or_default();
+				let withdrawn = Self::tripped_withdrawals(currency_id)
+					.filter(|(tripped_at, _)| *tripped_at == since)
+					.map_or(Zero::zero(), |(_, withdrawn)| withdrawn)
+					.saturating_add(amount);
+				if withdrawn > Self::withdrawal_thresholds(currency_id) {
+					return false;
+				}
+
+				TrippedWithdrawals::<T>::insert(currency_id, (since, withdrawn));
+				true
+			}
+		}
+	}
+}
@@ -0,0 +1,254 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the circuit breaker module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use primitives::TokenSymbol;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchError, DispatchResult};
+use sp_std::cell::RefCell;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
+pub const BTC: CurrencyId = CurrencyId::Token(TokenSymbol::XBTC);
+
+mod circuit_breaker {
+	pub use super::super::*;
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+thread_local! {
+	static ORACLE_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::one()));
+	static LIQUIDITY_POOL: RefCell<(Balance, Balance)> = RefCell::new((1000, 1000));
+	static DEBIT_POOL: RefCell<Balance> = RefCell::new(0);
+}
+
+pub struct MockPriceSource;
+impl MockPriceSource {
+	pub fn set_relative_price(price: Option<Price>) {
+		ORACLE_PRICE.with(|v| *v.borrow_mut() = price);
+	}
+}
+impl PriceProvider<CurrencyId> for MockPriceSource {
+	fn get_relative_price(_base: CurrencyId, _quote: CurrencyId) -> Option<Price> {
+		ORACLE_PRICE.with(|v| *v.borrow_mut())
+	}
+
+	fn get_price(_currency_id: CurrencyId) -> Option<Price> {
+		None
+	}
+
+	fn lock_price(_currency_id: CurrencyId) {}
+
+	fn unlock_price(_currency_id: CurrencyId) {}
+}
+
+pub struct MockDEX;
+impl MockDEX {
+	pub fn set_liquidity_pool(pool: (Balance, Balance)) {
+		LIQUIDITY_POOL.with(|v| *v.borrow_mut() = pool);
+	}
+}
+impl DEXManager<AccountId, CurrencyId, Balance> for MockDEX {
+	fn get_liquidity_pool(_currency_id_a: CurrencyId, _currency_id_b: CurrencyId) -> (Balance, Balance) {
+		LIQUIDITY_POOL.with(|v| *v.borrow_mut())
+	}
+
+	fn get_swap_target_amount(_: &[CurrencyId], _: Balance, _: Option<Ratio>) -> Option<Balance> {
+		unimplemented!()
+	}
+
+	fn get_swap_supply_amount(_: &[CurrencyId], _: Balance, _: Option<Ratio>) -> Option<Balance> {
+		unimplemented!()
+	}
+
+	fn swap_with_exact_supply(
+		_: &AccountId,
+		_: &[CurrencyId],
+		_: Balance,
+		_: Balance,
+		_: Option<Ratio>,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		unimplemented!()
+	}
+
+	fn swap_with_exact_target(
+		_: &AccountId,
+		_: &[CurrencyId],
+		_: Balance,
+		_: Balance,
+		_: Option<Ratio>,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		unimplemented!()
+	}
+
+	fn add_liquidity(_: &AccountId, _: CurrencyId, _: CurrencyId, _: Balance, _: Balance, _: bool) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn remove_liquidity(_: &AccountId, _: CurrencyId, _: CurrencyId, _: Balance, _: bool) -> DispatchResult {
+		unimplemented!()
+	}
+}
+
+pub struct MockCDPTreasury;
+impl MockCDPTreasury {
+	pub fn set_debit_pool(amount: Balance) {
+		DEBIT_POOL.with(|v| *v.borrow_mut() = amount);
+	}
+}
+impl CDPTreasury<AccountId> for MockCDPTreasury {
+	type Balance = Balance;
+	type CurrencyId = CurrencyId;
+
+	fn get_surplus_pool() -> Balance {
+		unimplemented!()
+	}
+
+	fn get_debit_pool() -> Balance {
+		DEBIT_POOL.with(|v| *v.borrow_mut())
+	}
+
+	fn get_total_collaterals(_: CurrencyId) -> Balance {
+		unimplemented!()
+	}
+
+	// the issuance of stable currency is 1000
+	fn get_debit_proportion(amount: Balance) -> Ratio {
+		Ratio::saturating_from_rational(amount, 1000)
+	}
+
+	fn on_system_debit(_: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn on_system_surplus(_: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn issue_debit(_: &AccountId, _: Balance, _: bool) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn burn_debit(_: &AccountId, _: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn deposit_surplus(_: &AccountId, _: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn deposit_collateral(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn withdraw_collateral(_: &AccountId, _: CurrencyId, _: Balance) -> DispatchResult {
+		unimplemented!()
+	}
+}
+
+ord_parameter_types! {
+	pub const One: AccountId = 1;
+}
+
+parameter_types! {
+	pub MonitoredCurrencyIds: Vec<CurrencyId> = vec![BTC];
+	pub const GetStableCurrencyId: CurrencyId = AUSD;
+	pub const MaxTriggerTiers: u32 = 3;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type MonitoredCurrencyIds = MonitoredCurrencyIds;
+	type GetStableCurrencyId = GetStableCurrencyId;
+	type PriceSource = MockPriceSource;
+	type DEX = MockDEX;
+	type CDPTreasury = MockCDPTreasury;
+	type MaxTriggerTiers = MaxTriggerTiers;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		CircuitBreakerModule: circuit_breaker::{Pallet, Storage, Call, Event<T>},
+	}
+);
+
+pub struct ExtBuilder;
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		t.into()
+	}
+}
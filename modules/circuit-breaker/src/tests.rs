@@ -0,0 +1,363 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the circuit breaker module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::BadOrigin;
+
+fn tier(threshold: Ratio, operations: Vec<GuardedOperation>, cool_down: BlockNumber) -> TriggerTier<BlockNumber> {
+	TriggerTier {
+		threshold,
+		operations,
+		cool_down,
+	}
+}
+
+#[test]
+fn set_trigger_tiers_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let low = tier(
+			Ratio::saturating_from_rational(1, 10),
+			vec![GuardedOperation::Withdrawal],
+			10,
+		);
+		let high = tier(
+			Ratio::saturating_from_rational(3, 10),
+			vec![GuardedOperation::NewLoan, GuardedOperation::Withdrawal],
+			100,
+		);
+
+		assert_noop!(
+			CircuitBreakerModule::set_trigger_tiers(Origin::signed(2), CircuitSignal::PriceCrash, vec![]),
+			BadOrigin
+		);
+		assert_noop!(
+			CircuitBreakerModule::set_trigger_tiers(
+				Origin::signed(1),
+				CircuitSignal::PriceCrash,
+				vec![low.clone(), low.clone(), low.clone(), low.clone()]
+			),
+			Error::<Runtime>::TooManyTriggerTiers
+		);
+
+		assert_ok!(CircuitBreakerModule::set_trigger_tiers(
+			Origin::signed(1),
+			CircuitSignal::PriceCrash,
+			vec![high.clone(), low.clone()]
+		));
+		let event = Event::circuit_breaker(crate::Event::TriggerTiersUpdated(CircuitSignal::PriceCrash));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(
+			CircuitBreakerModule::trigger_tiers(CircuitSignal::PriceCrash),
+			vec![low, high]
+		);
+	});
+}
+
+#[test]
+fn register_trigger_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let low = tier(
+			Ratio::saturating_from_rational(1, 10),
+			vec![GuardedOperation::Withdrawal],
+			10,
+		);
+		let high = tier(
+			Ratio::saturating_from_rational(3, 10),
+			vec![GuardedOperation::NewLoan],
+			100,
+		);
+
+		assert_ok!(<CircuitBreakerModule as CircuitBreaker<_>>::register_trigger(
+			CircuitSignal::BadDebtSpike,
+			high.clone()
+		));
+		assert_ok!(<CircuitBreakerModule as CircuitBreaker<_>>::register_trigger(
+			CircuitSignal::BadDebtSpike,
+			low.clone()
+		));
+		assert_ok!(<CircuitBreakerModule as CircuitBreaker<_>>::register_trigger(
+			CircuitSignal::BadDebtSpike,
+			low.clone()
+		));
+		assert_eq!(
+			CircuitBreakerModule::trigger_tiers(CircuitSignal::BadDebtSpike),
+			vec![low.clone(), low.clone(), high]
+		);
+		assert_noop!(
+			<CircuitBreakerModule as CircuitBreaker<_>>::register_trigger(CircuitSignal::BadDebtSpike, low),
+			Error::<Runtime>::TooManyTriggerTiers
+		);
+	});
+}
+
+#[test]
+fn report_trips_reached_tiers() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CircuitBreakerModule::set_trigger_tiers(
+			Origin::signed(1),
+			CircuitSignal::OracleDeviation,
+			vec![
+				tier(
+					Ratio::saturating_from_rational(1, 10),
+					vec![GuardedOperation::Withdrawal],
+					10
+				),
+				tier(
+					Ratio::saturating_from_rational(3, 10),
+					vec![GuardedOperation::NewLoan],
+					100
+				),
+			]
+		));
+
+		<CircuitBreakerModule as CircuitBreaker<_>>::report(
+			CircuitSignal::OracleDeviation,
+			Ratio::saturating_from_rational(5, 100),
+		);
+		assert_eq!(CircuitBreakerModule::tripped_until(GuardedOperation::Withdrawal), None);
+		assert_eq!(CircuitBreakerModule::tripped_until(GuardedOperation::NewLoan), None);
+
+		<CircuitBreakerModule as CircuitBreaker<_>>::report(
+			CircuitSignal::OracleDeviation,
+			Ratio::saturating_from_rational(2, 10),
+		);
+		let event = Event::circuit_breaker(crate::Event::OperationTripped(GuardedOperation::Withdrawal, 11));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(
+			CircuitBreakerModule::tripped_until(GuardedOperation::Withdrawal),
+			Some(11)
+		);
+		assert_eq!(CircuitBreakerModule::tripped_until(GuardedOperation::NewLoan), None);
+
+		<CircuitBreakerModule as CircuitBreaker<_>>::report(CircuitSignal::OracleDeviation, Ratio::one());
+		assert_eq!(
+			CircuitBreakerModule::tripped_until(GuardedOperation::Withdrawal),
+			Some(11)
+		);
+		assert_eq!(
+			CircuitBreakerModule::tripped_until(GuardedOperation::NewLoan),
+			Some(101)
+		);
+	});
+}
+
+#[test]
+fn try_allow_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CircuitBreakerModule::set_withdrawal_threshold(
+			Origin::signed(1),
+			BTC,
+			100
+		));
+		let event = Event::circuit_breaker(crate::Event::WithdrawalThresholdUpdated(BTC, 100));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		assert!(CircuitBreakerModule::try_allow(GuardedOperation::NewLoan, BTC, 1000));
+		assert!(CircuitBreakerModule::try_allow(GuardedOperation::Withdrawal, BTC, 1000));
+		assert_eq!(CircuitBreakerModule::tripped_withdrawals(BTC), None);
+
+		assert_ok!(CircuitBreakerModule::force_trip(
+			Origin::signed(1),
+			GuardedOperation::NewLoan,
+			10
+		));
+		assert_ok!(CircuitBreakerModule::force_trip(
+			Origin::signed(1),
+			GuardedOperation::Withdrawal,
+			10
+		));
+		assert_eq!(
+			CircuitBreakerModule::tripped_since(GuardedOperation::Withdrawal),
+			Some(1)
+		);
+		assert!(!CircuitBreakerModule::try_allow(GuardedOperation::NewLoan, BTC, 1));
+		assert!(!CircuitBreakerModule::try_allow(GuardedOperation::Withdrawal, BTC, 101));
+		assert!(!CircuitBreakerModule::try_allow(GuardedOperation::Withdrawal, AUSD, 1));
+
+		// the withdrawals are accumulated during the trip
+		assert!(CircuitBreakerModule::try_allow(GuardedOperation::Withdrawal, BTC, 60));
+		assert_eq!(CircuitBreakerModule::tripped_withdrawals(BTC), Some((1, 60)));
+		System::set_block_number(2);
+		assert!(!CircuitBreakerModule::try_allow(GuardedOperation::Withdrawal, BTC, 60));
+		assert!(CircuitBreakerModule::try_allow(GuardedOperation::Withdrawal, BTC, 40));
+		assert_eq!(CircuitBreakerModule::tripped_withdrawals(BTC), Some((1, 100)));
+
+		// extending the trip doesn't reset the withdrawals
+		assert_ok!(CircuitBreakerModule::force_trip(
+			Origin::signed(1),
+			GuardedOperation::Withdrawal,
+			10
+		));
+		assert!(!CircuitBreakerModule::try_allow(GuardedOperation::Withdrawal, BTC, 1));
+
+		System::set_block_number(12);
+		assert!(CircuitBreakerModule::try_allow(GuardedOperation::NewLoan, BTC, 1000));
+		assert!(CircuitBreakerModule::try_allow(GuardedOperation::Withdrawal, BTC, 1000));
+
+		// a new trip starts with no withdrawals
+		assert_ok!(CircuitBreakerModule::force_trip(
+			Origin::signed(1),
+			GuardedOperation::Withdrawal,
+			10
+		));
+		assert_eq!(
+			CircuitBreakerModule::tripped_since(GuardedOperation::Withdrawal),
+			Some(12)
+		);
+		assert!(CircuitBreakerModule::try_allow(GuardedOperation::Withdrawal, BTC, 100));
+		assert_eq!(CircuitBreakerModule::tripped_withdrawals(BTC), Some((12, 100)));
+	});
+}
+
+#[test]
+fn force_trip_and_reset_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CircuitBreakerModule::force_trip(Origin::signed(2), GuardedOperation::NewLoan, 10),
+			BadOrigin
+		);
+		assert_ok!(CircuitBreakerModule::force_trip(
+			Origin::signed(1),
+			GuardedOperation::NewLoan,
+			10
+		));
+		let event = Event::circuit_breaker(crate::Event::OperationTripped(GuardedOperation::NewLoan, 11));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert!(CircuitBreakerModule::is_tripped(GuardedOperation::NewLoan));
+
+		assert_noop!(
+			CircuitBreakerModule::force_reset(Origin::signed(2), GuardedOperation::NewLoan),
+			BadOrigin
+		);
+		assert_ok!(CircuitBreakerModule::force_reset(
+			Origin::signed(1),
+			GuardedOperation::NewLoan
+		));
+		let event = Event::circuit_breaker(crate::Event::OperationReset(GuardedOperation::NewLoan));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert!(!CircuitBreakerModule::is_tripped(GuardedOperation::NewLoan));
+		assert_eq!(CircuitBreakerModule::tripped_since(GuardedOperation::NewLoan), None);
+	});
+}
+
+#[test]
+fn monitor_oracle_deviation_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CircuitBreakerModule::set_trigger_tiers(
+			Origin::signed(1),
+			CircuitSignal::OracleDeviation,
+			vec![tier(
+				Ratio::saturating_from_rational(3, 10),
+				vec![GuardedOperation::NewLoan],
+				10
+			)]
+		));
+
+		CircuitBreakerModule::on_initialize(1);
+		assert!(!CircuitBreakerModule::is_tripped(GuardedOperation::NewLoan));
+
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(12, 10)));
+		CircuitBreakerModule::on_initialize(1);
+		assert!(!CircuitBreakerModule::is_tripped(GuardedOperation::NewLoan));
+
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(2, 1)));
+		CircuitBreakerModule::on_initialize(1);
+		assert!(CircuitBreakerModule::is_tripped(GuardedOperation::NewLoan));
+	});
+}
+
+#[test]
+fn monitor_price_crash_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CircuitBreakerModule::set_trigger_tiers(
+			Origin::signed(1),
+			CircuitSignal::PriceCrash,
+			vec![tier(
+				Ratio::saturating_from_rational(3, 10),
+				vec![GuardedOperation::NewLoan],
+				10
+			)]
+		));
+
+		// the DEX price is ignored
+		MockDEX::set_liquidity_pool((1000, 1));
+		CircuitBreakerModule::on_initialize(1);
+		assert_eq!(CircuitBreakerModule::last_prices(BTC), Some(Price::one()));
+		assert!(!CircuitBreakerModule::is_tripped(GuardedOperation::NewLoan));
+
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(8, 10)));
+		CircuitBreakerModule::on_initialize(2);
+		assert_eq!(
+			CircuitBreakerModule::last_prices(BTC),
+			Some(Price::saturating_from_rational(8, 10))
+		);
+		assert!(!CircuitBreakerModule::is_tripped(GuardedOperation::NewLoan));
+
+		// no oracle price
+		MockPriceSource::set_relative_price(None);
+		CircuitBreakerModule::on_initialize(3);
+		assert!(!CircuitBreakerModule::is_tripped(GuardedOperation::NewLoan));
+
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(4, 10)));
+		CircuitBreakerModule::on_initialize(4);
+		assert!(CircuitBreakerModule::is_tripped(GuardedOperation::NewLoan));
+	});
+}
+
+#[test]
+fn monitor_bad_debt_spike_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CircuitBreakerModule::set_trigger_tiers(
+			Origin::signed(1),
+			CircuitSignal::BadDebtSpike,
+			vec![tier(
+				Ratio::saturating_from_rational(1, 10),
+				vec![GuardedOperation::Withdrawal],
+				10
+			)]
+		));
+
+		// the first bad debt is measured in proportion to the stable currency
+		MockCDPTreasury::set_debit_pool(50);
+		CircuitBreakerModule::on_initialize(1);
+		assert_eq!(CircuitBreakerModule::last_debit_pool(), 50);
+		assert!(!CircuitBreakerModule::is_tripped(GuardedOperation::Withdrawal));
+
+		MockCDPTreasury::set_debit_pool(140);
+		CircuitBreakerModule::on_initialize(2);
+		assert!(!CircuitBreakerModule::is_tripped(GuardedOperation::Withdrawal));
+
+		MockCDPTreasury::set_debit_pool(240);
+		CircuitBreakerModule::on_initialize(3);
+		assert_eq!(CircuitBreakerModule::last_debit_pool(), 240);
+		assert!(CircuitBreakerModule::is_tripped(GuardedOperation::Withdrawal));
+	});
+}
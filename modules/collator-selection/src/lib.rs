@@ -0,0 +1,765 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Collator Selection Module
+//!
+//! ## Overview
+//!
+//! Selects the collators of each session as the `SessionManager` of
+//! `pallet_session`. The collators are the invulnerables set by governance,
+//! followed by the candidates with the highest bonds up to
+//! `DesiredCandidates`. Anyone may register as a candidate by reserving
+//! `CandidacyBond`, and leave the candidates to get the bond back.
+//!
+//! The blocks authored by each collator are counted through the
+//! `EventHandler` of `pallet_authorship`. When a session ends, the candidates
+//! which authored fewer blocks than `KickThreshold` of the average of the
+//! session collators are kicked out of the candidates, and `SlashRatio` of
+//! their bonds is slashed to the treasury. Invulnerables are never kicked.
+//!
+//! Token holders may back the candidates by delegating reserved funds to
+//! them, and the candidates are selected by their total backing, the bond
+//! plus the delegations. The undelegated funds can be claimed after
+//! `DelegationExitDelay` blocks.
+//!
+//! The rewards accrued in the pot account of the module during a session are
+//! shared among the candidates of the session by their authored blocks, and
+//! the reward of each candidate is shared pro-rata between the bond and the
+//! delegations. The rewards are claimed along with the undelegated funds.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{BalanceStatus, Currency, ExistenceRequirement, ReservableCurrency},
+	transactional, PalletId,
+};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{
+	traits::{AccountIdConversion, Saturating, Zero},
+	FixedPointNumber, FixedU128, Perbill, Permill, RuntimeDebug, SaturatedConversion,
+};
+use sp_staking::SessionIndex;
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// A candidate and its reserved bond.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct CandidateInfo<AccountId, Balance> {
+	/// The account of the candidate
+	pub who: AccountId,
+	/// The bond reserved by the candidate
+	pub bond: Balance,
+}
+
+/// The funds delegated to a candidate.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Delegation<Balance> {
+	/// The delegated amount
+	pub amount: Balance,
+	/// The reward per share of the candidate when the rewards of the
+	/// delegation were last settled
+	pub reward_per_share_paid: FixedU128,
+}
+
+/// The undelegated funds waiting for the exit delay.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct Unbonding<Balance, BlockNumber> {
+	/// The undelegated amount
+	pub amount: Balance,
+	/// The block from which the amount can be claimed
+	pub unlock_at: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency of the candidacy bonds
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The origin which may set the invulnerables and the selection
+		/// parameters
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The maximum number of candidates
+		#[pallet::constant]
+		type MaxCandidates: Get<u32>;
+
+		/// The maximum number of invulnerables
+		#[pallet::constant]
+		type MaxInvulnerables: Get<u32>;
+
+		/// The account receiving the slashed bonds
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The minimum amount delegated by a delegator to a candidate
+		#[pallet::constant]
+		type MinDelegation: Get<BalanceOf<Self>>;
+
+		/// The maximum number of candidates backed by a delegator
+		#[pallet::constant]
+		type MaxDelegations: Get<u32>;
+
+		/// The number of blocks before the undelegated funds can be claimed
+		#[pallet::constant]
+		type DelegationExitDelay: Get<Self::BlockNumber>;
+
+		/// The collator selection module id, keeps the session rewards.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Too many candidates
+		TooManyCandidates,
+		/// Too many invulnerables
+		TooManyInvulnerables,
+		/// The account is already a candidate
+		AlreadyCandidate,
+		/// The account is not a candidate
+		NotCandidate,
+		/// The account is an invulnerable
+		AlreadyInvulnerable,
+		/// The delegation is below the minimum delegation
+		BelowMinDelegation,
+		/// Too many candidates backed by the delegator
+		TooManyDelegations,
+		/// The candidate is not backed by the delegator
+		NoDelegation,
+		/// The delegation is less than the undelegated amount
+		InsufficientDelegation,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId", BalanceOf<T> = "Balance", T::BlockNumber = "BlockNumber")]
+	pub enum Event<T: Config> {
+		/// Set the invulnerables. \[invulnerables\]
+		InvulnerablesSet(Vec<T::AccountId>),
+		/// Set the desired number of candidates. \[desired_candidates\]
+		DesiredCandidatesSet(u32),
+		/// Set the candidacy bond. \[bond\]
+		CandidacyBondSet(BalanceOf<T>),
+		/// Set the kick threshold and the slash ratio. \[kick_threshold,
+		/// slash_ratio\]
+		KickConfigSet(Permill, Permill),
+		/// Registered as a candidate. \[who, bond\]
+		CandidateAdded(T::AccountId, BalanceOf<T>),
+		/// Left the candidates. \[who\]
+		CandidateRemoved(T::AccountId),
+		/// Kicked out of the candidates for authoring too few blocks in the
+		/// session. \[who, authored_blocks, slashed\]
+		CandidateKicked(T::AccountId, u32, BalanceOf<T>),
+		/// Delegated to a candidate. \[delegator, candidate, amount\]
+		Delegated(T::AccountId, T::AccountId, BalanceOf<T>),
+		/// Undelegated from a candidate. \[delegator, candidate, amount,
+		/// unlock_at\]
+		Undelegated(T::AccountId, T::AccountId, BalanceOf<T>, T::BlockNumber),
+		/// Claimed the undelegated funds and the rewards. \[who, withdrawn,
+		/// rewards\]
+		Claimed(T::AccountId, BalanceOf<T>, BalanceOf<T>),
+		/// Rewarded a candidate and its delegators for the session.
+		/// \[candidate, reward\]
+		CandidateRewarded(T::AccountId, BalanceOf<T>),
+	}
+
+	/// The collators selected regardless of their bonds.
+	///
+	/// Invulnerables: Vec<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn invulnerables)]
+	pub type Invulnerables<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	/// The candidates, in order of registration.
+	///
+	/// Candidates: Vec<CandidateInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn candidates)]
+	pub type Candidates<T: Config> = StorageValue<_, Vec<CandidateInfo<T::AccountId, BalanceOf<T>>>, ValueQuery>;
+
+	/// The number of candidates selected as collators besides the
+	/// invulnerables.
+	///
+	/// DesiredCandidates: u32
+	#[pallet::storage]
+	#[pallet::getter(fn desired_candidates)]
+	pub type DesiredCandidates<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The bond reserved to register as a candidate.
+	///
+	/// CandidacyBond: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn candidacy_bond)]
+	pub type CandidacyBond<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// The candidates authoring fewer blocks than this ratio of the average of
+	/// the session collators are kicked, zero disables kicking.
+	///
+	/// KickThreshold: Permill
+	#[pallet::storage]
+	#[pallet::getter(fn kick_threshold)]
+	pub type KickThreshold<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// The ratio of the bond slashed from the kicked candidates.
+	///
+	/// SlashRatio: Permill
+	#[pallet::storage]
+	#[pallet::getter(fn slash_ratio)]
+	pub type SlashRatio<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// The collators planned for the sessions which have not started yet.
+	///
+	/// map SessionIndex => Vec<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn planned_collators)]
+	pub type PlannedCollators<T: Config> = StorageMap<_, Twox64Concat, SessionIndex, Vec<T::AccountId>, ValueQuery>;
+
+	/// The collators of the current session.
+	///
+	/// SessionCollators: Vec<AccountId>
+	#[pallet::storage]
+	#[pallet::getter(fn session_collators)]
+	pub type SessionCollators<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
+
+	/// The number of blocks authored by the collators in the current session.
+	///
+	/// map AccountId => u32
+	#[pallet::storage]
+	#[pallet::getter(fn authored_blocks)]
+	pub type AuthoredBlocks<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The funds delegated by the delegators to the candidates.
+	///
+	/// double_map (Delegator, Candidate) => Option<Delegation>
+	#[pallet::storage]
+	#[pallet::getter(fn delegations)]
+	pub type Delegations<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		Delegation<BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// The total funds delegated to the candidates.
+	///
+	/// map Candidate => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_delegations)]
+	pub type TotalDelegations<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The accumulated rewards of the candidates per delegated unit.
+	///
+	/// map Candidate => FixedU128
+	#[pallet::storage]
+	#[pallet::getter(fn reward_per_share)]
+	pub type RewardPerShare<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, FixedU128, ValueQuery>;
+
+	/// The undelegated funds of the delegators waiting for the exit delay.
+	///
+	/// map Delegator => Option<Unbonding>
+	#[pallet::storage]
+	#[pallet::getter(fn unbondings)]
+	pub type Unbondings<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Unbonding<BalanceOf<T>, T::BlockNumber>, OptionQuery>;
+
+	/// The settled rewards of the accounts which are not claimed yet.
+	///
+	/// map AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn pending_rewards)]
+	pub type PendingRewards<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// The distributed rewards which are not claimed yet, kept in the pot
+	/// account.
+	///
+	/// UnclaimedRewards: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn unclaimed_rewards)]
+	pub type UnclaimedRewards<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub invulnerables: Vec<T::AccountId>,
+		pub candidacy_bond: BalanceOf<T>,
+		pub desired_candidates: u32,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			GenesisConfig {
+				invulnerables: Default::default(),
+				candidacy_bond: Default::default(),
+				desired_candidates: Default::default(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			assert!(
+				self.invulnerables.len() as u32 <= T::MaxInvulnerables::get(),
+				"too many invulnerables"
+			);
+			Invulnerables::<T>::put(&self.invulnerables);
+			CandidacyBond::<T>::put(self.candidacy_bond);
+			DesiredCandidates::<T>::put(self.desired_candidates);
+		}
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the invulnerables.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `invulnerables`: the collators selected regardless of their bonds
+		#[pallet::weight(T::WeightInfo::set_invulnerables(invulnerables.len() as u32))]
+		pub fn set_invulnerables(origin: OriginFor<T>, invulnerables: Vec<T::AccountId>) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				invulnerables.len() as u32 <= T::MaxInvulnerables::get(),
+				Error::<T>::TooManyInvulnerables
+			);
+
+			Invulnerables::<T>::put(&invulnerables);
+			Self::deposit_event(Event::InvulnerablesSet(invulnerables));
+			Ok(().into())
+		}
+
+		/// Set the number of candidates selected as collators.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `desired_candidates`: the number of candidates selected besides
+		///   the invulnerables
+		#[pallet::weight(T::WeightInfo::set_desired_candidates())]
+		pub fn set_desired_candidates(origin: OriginFor<T>, desired_candidates: u32) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			DesiredCandidates::<T>::put(desired_candidates);
+			Self::deposit_event(Event::DesiredCandidatesSet(desired_candidates));
+			Ok(().into())
+		}
+
+		/// Set the candidacy bond, the bonds of the existing candidates are
+		/// unchanged.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `bond`: the bond reserved to register as a candidate
+		#[pallet::weight(T::WeightInfo::set_candidacy_bond())]
+		pub fn set_candidacy_bond(origin: OriginFor<T>, bond: BalanceOf<T>) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			CandidacyBond::<T>::put(bond);
+			Self::deposit_event(Event::CandidacyBondSet(bond));
+			Ok(().into())
+		}
+
+		/// Set the performance threshold below which the candidates are kicked
+		/// at the end of a session, and the ratio of their bonds slashed.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `kick_threshold`: the ratio of the average authored blocks of the
+		///   session collators, zero disables kicking
+		/// - `slash_ratio`: the ratio of the bond slashed from the kicked
+		///   candidates
+		#[pallet::weight(T::WeightInfo::set_kick_config())]
+		pub fn set_kick_config(
+			origin: OriginFor<T>,
+			kick_threshold: Permill,
+			slash_ratio: Permill,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			KickThreshold::<T>::put(kick_threshold);
+			SlashRatio::<T>::put(slash_ratio);
+			Self::deposit_event(Event::KickConfigSet(kick_threshold, slash_ratio));
+			Ok(().into())
+		}
+
+		/// Register as a candidate by reserving the candidacy bond.
+		#[pallet::weight(T::WeightInfo::register_as_candidate())]
+		pub fn register_as_candidate(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!Self::invulnerables().contains(&who), Error::<T>::AlreadyInvulnerable);
+
+			let bond = Self::candidacy_bond();
+			Candidates::<T>::try_mutate(|candidates| -> DispatchResult {
+				ensure!(
+					!candidates.iter().any(|candidate| candidate.who == who),
+					Error::<T>::AlreadyCandidate
+				);
+				ensure!(
+					(candidates.len() as u32) < T::MaxCandidates::get(),
+					Error::<T>::TooManyCandidates
+				);
+
+				T::Currency::reserve(&who, bond)?;
+				candidates.push(CandidateInfo { who: who.clone(), bond });
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::CandidateAdded(who, bond));
+			Ok(().into())
+		}
+
+		/// Leave the candidates and unreserve the bond.
+		#[pallet::weight(T::WeightInfo::leave_intent())]
+		pub fn leave_intent(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let candidate = Self::remove_candidate(&who)?;
+
+			T::Currency::unreserve(&who, candidate.bond);
+			Self::deposit_event(Event::CandidateRemoved(who));
+			Ok(().into())
+		}
+
+		/// Delegate reserved funds to a candidate.
+		///
+		/// - `candidate`: the candidate to back
+		/// - `amount`: the amount to delegate
+		#[pallet::weight(T::WeightInfo::delegate())]
+		#[transactional]
+		pub fn delegate(
+			origin: OriginFor<T>,
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				Self::candidates().iter().any(|info| info.who == candidate),
+				Error::<T>::NotCandidate
+			);
+
+			Delegations::<T>::try_mutate(&who, &candidate, |maybe_delegation| -> DispatchResult {
+				let mut delegation = match maybe_delegation.take() {
+					Some(delegation) => delegation,
+					None => {
+						ensure!(
+							(Delegations::<T>::iter_prefix(&who).count() as u32) < T::MaxDelegations::get(),
+							Error::<T>::TooManyDelegations
+						);
+						Delegation {
+							amount: Zero::zero(),
+							reward_per_share_paid: Self::reward_per_share(&candidate),
+						}
+					}
+				};
+				Self::settle_rewards(&who, &candidate, &mut delegation);
+
+				delegation.amount = delegation.amount.saturating_add(amount);
+				ensure!(
+					delegation.amount >= T::MinDelegation::get(),
+					Error::<T>::BelowMinDelegation
+				);
+				T::Currency::reserve(&who, amount)?;
+				*maybe_delegation = Some(delegation);
+				Ok(())
+			})?;
+			TotalDelegations::<T>::mutate(&candidate, |total| *total = total.saturating_add(amount));
+
+			Self::deposit_event(Event::Delegated(who, candidate, amount));
+			Ok(().into())
+		}
+
+		/// Undelegate funds from a candidate, which can be claimed after
+		/// `DelegationExitDelay` blocks. Undelegating again postpones the
+		/// claim of all the undelegated funds.
+		///
+		/// - `candidate`: the backed candidate
+		/// - `amount`: the amount to undelegate
+		#[pallet::weight(T::WeightInfo::undelegate())]
+		#[transactional]
+		pub fn undelegate(
+			origin: OriginFor<T>,
+			candidate: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			Delegations::<T>::try_mutate_exists(&who, &candidate, |maybe_delegation| -> DispatchResult {
+				let mut delegation = maybe_delegation.take().ok_or(Error::<T>::NoDelegation)?;
+				ensure!(delegation.amount >= amount, Error::<T>::InsufficientDelegation);
+				Self::settle_rewards(&who, &candidate, &mut delegation);
+
+				delegation.amount -= amount;
+				if !delegation.amount.is_zero() {
+					ensure!(
+						delegation.amount >= T::MinDelegation::get(),
+						Error::<T>::BelowMinDelegation
+					);
+					*maybe_delegation = Some(delegation);
+				}
+				Ok(())
+			})?;
+			TotalDelegations::<T>::mutate(&candidate, |total| *total = total.saturating_sub(amount));
+
+			let unlock_at = <frame_system::Pallet<T>>::block_number().saturating_add(T::DelegationExitDelay::get());
+			Unbondings::<T>::mutate(&who, |maybe_unbonding| {
+				let unbonded = maybe_unbonding
+					.take()
+					.map_or_else(Zero::zero, |unbonding| unbonding.amount);
+				*maybe_unbonding = Some(Unbonding {
+					amount: unbonded.saturating_add(amount),
+					unlock_at,
+				});
+			});
+
+			Self::deposit_event(Event::Undelegated(who, candidate, amount, unlock_at));
+			Ok(().into())
+		}
+
+		/// Claim the undelegated funds whose exit delay has passed, and the
+		/// rewards of the bond and the delegations.
+		#[pallet::weight(T::WeightInfo::claim(T::MaxDelegations::get()))]
+		#[transactional]
+		pub fn claim(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			for (candidate, mut delegation) in Delegations::<T>::iter_prefix(&who).collect::<Vec<_>>() {
+				Self::settle_rewards(&who, &candidate, &mut delegation);
+				Delegations::<T>::insert(&who, &candidate, delegation);
+			}
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let withdrawn = match Self::unbondings(&who) {
+				Some(unbonding) if unbonding.unlock_at <= now => {
+					Unbondings::<T>::remove(&who);
+					T::Currency::unreserve(&who, unbonding.amount);
+					unbonding.amount
+				}
+				_ => Zero::zero(),
+			};
+
+			let rewards = PendingRewards::<T>::take(&who);
+			if !rewards.is_zero() {
+				T::Currency::transfer(&Self::account_id(), &who, rewards, ExistenceRequirement::KeepAlive)?;
+				UnclaimedRewards::<T>::mutate(|unclaimed| *unclaimed = unclaimed.saturating_sub(rewards));
+			}
+
+			Self::deposit_event(Event::Claimed(who, withdrawn, rewards));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Get account of collator selection module.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	fn remove_candidate(who: &T::AccountId) -> Result<CandidateInfo<T::AccountId, BalanceOf<T>>, DispatchError> {
+		Candidates::<T>::try_mutate(|candidates| {
+			let index = candidates
+				.iter()
+				.position(|candidate| &candidate.who == who)
+				.ok_or(Error::<T>::NotCandidate)?;
+			Ok(candidates.remove(index))
+		})
+	}
+
+	/// Move the rewards accrued by `delegation` since the last settlement to
+	/// the pending rewards of `delegator`.
+	fn settle_rewards(delegator: &T::AccountId, candidate: &T::AccountId, delegation: &mut Delegation<BalanceOf<T>>) {
+		let reward_per_share = Self::reward_per_share(candidate);
+		let rewards = reward_per_share
+			.saturating_sub(delegation.reward_per_share_paid)
+			.saturating_mul_int(delegation.amount.saturated_into::<u128>())
+			.saturated_into::<BalanceOf<T>>();
+		delegation.reward_per_share_paid = reward_per_share;
+
+		if !rewards.is_zero() {
+			PendingRewards::<T>::mutate(delegator, |pending| *pending = pending.saturating_add(rewards));
+		}
+	}
+
+	/// The invulnerables, followed by the candidates with the highest total
+	/// backing. The earlier registered candidates are preferred among equal
+	/// backings.
+	fn assemble_collators() -> Vec<T::AccountId> {
+		let mut collators = Self::invulnerables();
+		let mut candidates = Self::candidates()
+			.into_iter()
+			.filter(|candidate| !collators.contains(&candidate.who))
+			.map(|candidate| {
+				let backing = candidate.bond.saturating_add(Self::total_delegations(&candidate.who));
+				(candidate.who, backing)
+			})
+			.collect::<Vec<_>>();
+		candidates.sort_by(|a, b| b.1.cmp(&a.1));
+
+		collators.extend(
+			candidates
+				.into_iter()
+				.take(Self::desired_candidates() as usize)
+				.map(|(who, _)| who),
+		);
+		collators
+	}
+
+	/// Kick the candidates among `collators` which authored fewer blocks than
+	/// `KickThreshold` of the average of `collators` in the ended session, and
+	/// slash `SlashRatio` of their bonds to the treasury.
+	fn kick_underperformers(collators: &[T::AccountId], authored_blocks: &BTreeMap<T::AccountId, u32>) {
+		let kick_threshold = Self::kick_threshold();
+		if collators.is_empty() || kick_threshold.is_zero() {
+			return;
+		}
+
+		let blocks_of = |who: &T::AccountId| authored_blocks.get(who).copied().unwrap_or_default();
+		let total_blocks = collators
+			.iter()
+			.fold(0u32, |total, who| total.saturating_add(blocks_of(who)));
+		let min_blocks = kick_threshold.mul_floor(total_blocks / collators.len() as u32);
+		let invulnerables = Self::invulnerables();
+		let treasury = T::TreasuryAccount::get();
+
+		for who in collators {
+			let blocks = blocks_of(who);
+			if blocks >= min_blocks || invulnerables.contains(who) {
+				continue;
+			}
+			// the candidates which already left are not slashed
+			if let Ok(candidate) = Self::remove_candidate(who) {
+				let slash = Self::slash_ratio().mul_floor(candidate.bond);
+				// the reserved balance which is not slashed is returned
+				let not_slashed =
+					T::Currency::repatriate_reserved(who, &treasury, slash, BalanceStatus::Free).unwrap_or(slash);
+				let slashed = slash.saturating_sub(not_slashed);
+				T::Currency::unreserve(who, candidate.bond.saturating_sub(slashed));
+
+				Self::deposit_event(Event::CandidateKicked(who.clone(), blocks, slashed));
+			}
+		}
+	}
+
+	/// Share the rewards accrued in the pot account during the ended session
+	/// among the candidates in `collators` by their authored blocks. The
+	/// reward of each candidate is shared pro-rata between the bond and the
+	/// delegations.
+	fn distribute_rewards(collators: &[T::AccountId], authored_blocks: &BTreeMap<T::AccountId, u32>) {
+		let rewards = T::Currency::free_balance(&Self::account_id())
+			.saturating_sub(T::Currency::minimum_balance())
+			.saturating_sub(Self::unclaimed_rewards());
+		if rewards.is_zero() {
+			return;
+		}
+
+		let candidates = Self::candidates();
+		let rewarded = collators
+			.iter()
+			.filter_map(|who| candidates.iter().find(|candidate| &candidate.who == who))
+			.map(|candidate| {
+				(
+					candidate,
+					authored_blocks.get(&candidate.who).copied().unwrap_or_default(),
+				)
+			})
+			.filter(|(_, blocks)| !blocks.is_zero())
+			.collect::<Vec<_>>();
+		let total_blocks = rewarded
+			.iter()
+			.fold(0u32, |total, (_, blocks)| total.saturating_add(*blocks));
+		if total_blocks.is_zero() {
+			return;
+		}
+
+		let mut distributed: BalanceOf<T> = Zero::zero();
+		for (candidate, blocks) in rewarded {
+			let reward = Perbill::from_rational(blocks, total_blocks) * rewards;
+			let delegated = Self::total_delegations(&candidate.who);
+			let bond_reward = if delegated.is_zero() {
+				reward
+			} else {
+				Perbill::from_rational(candidate.bond, candidate.bond.saturating_add(delegated)) * reward
+			};
+
+			PendingRewards::<T>::mutate(&candidate.who, |pending| *pending = pending.saturating_add(bond_reward));
+			if !delegated.is_zero() {
+				let increment = FixedU128::saturating_from_rational(
+					reward.saturating_sub(bond_reward).saturated_into::<u128>(),
+					delegated.saturated_into::<u128>(),
+				);
+				RewardPerShare::<T>::mutate(&candidate.who, |reward_per_share| {
+					*reward_per_share = reward_per_share.saturating_add(increment)
+				});
+			}
+			distributed = distributed.saturating_add(reward);
+
+			Self::deposit_event(Event::CandidateRewarded(candidate.who.clone(), reward));
+		}
+		UnclaimedRewards::<T>::mutate(|unclaimed| *unclaimed = unclaimed.saturating_add(distributed));
+	}
+}
+
+impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
+	fn new_session(index: SessionIndex) -> Option<Vec<T::AccountId>> {
+		let collators = Self::assemble_collators();
+		PlannedCollators::<T>::insert(index, &collators);
+		Some(collators)
+	}
+
+	fn start_session(index: SessionIndex) {
+		SessionCollators::<T>::put(PlannedCollators::<T>::take(index));
+	}
+
+	fn end_session(_index: SessionIndex) {
+		let collators = SessionCollators::<T>::take();
+		let authored_blocks = AuthoredBlocks::<T>::drain().collect::<BTreeMap<_, _>>();
+		Self::kick_underperformers(&collators, &authored_blocks);
+		Self::distribute_rewards(&collators, &authored_blocks);
+	}
+}
+
+impl<T: Config> pallet_authorship::EventHandler<T::AccountId, T::BlockNumber> for Pallet<T> {
+	fn note_author(author: T::AccountId) {
+		AuthoredBlocks::<T>::mutate(author, |blocks| *blocks = blocks.saturating_add(1));
+	}
+
+	fn note_uncle(_author: T::AccountId, _age: T::BlockNumber) {}
+}
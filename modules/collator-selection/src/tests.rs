@@ -0,0 +1,429 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the collator selection module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{
+	CollatorSelectionModule, Event, ExtBuilder, Origin, PalletBalances, Runtime, System, ALICE, BOB, CHARLIE, DAVE,
+	EVE, TREASURY,
+};
+use pallet_authorship::EventHandler;
+use pallet_session::SessionManager;
+use sp_runtime::traits::BadOrigin;
+
+const COUNCIL: mock::AccountId = 10;
+
+#[test]
+fn set_invulnerables_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CollatorSelectionModule::set_invulnerables(Origin::signed(ALICE), vec![BOB]),
+			BadOrigin
+		);
+		assert_noop!(
+			CollatorSelectionModule::set_invulnerables(Origin::signed(COUNCIL), vec![ALICE, BOB, CHARLIE]),
+			Error::<Runtime>::TooManyInvulnerables
+		);
+
+		assert_ok!(CollatorSelectionModule::set_invulnerables(
+			Origin::signed(COUNCIL),
+			vec![ALICE, BOB]
+		));
+		let invulnerables_set_event = Event::collator_selection(crate::Event::InvulnerablesSet(vec![ALICE, BOB]));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == invulnerables_set_event));
+		assert_eq!(CollatorSelectionModule::invulnerables(), vec![ALICE, BOB]);
+	});
+}
+
+#[test]
+fn set_selection_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CollatorSelectionModule::set_desired_candidates(Origin::signed(ALICE), 5),
+			BadOrigin
+		);
+		assert_noop!(
+			CollatorSelectionModule::set_candidacy_bond(Origin::signed(ALICE), 200),
+			BadOrigin
+		);
+		assert_noop!(
+			CollatorSelectionModule::set_kick_config(
+				Origin::signed(ALICE),
+				Permill::from_percent(50),
+				Permill::from_percent(10)
+			),
+			BadOrigin
+		);
+
+		assert_ok!(CollatorSelectionModule::set_desired_candidates(
+			Origin::signed(COUNCIL),
+			5
+		));
+		let desired_candidates_set_event = Event::collator_selection(crate::Event::DesiredCandidatesSet(5));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == desired_candidates_set_event));
+		assert_eq!(CollatorSelectionModule::desired_candidates(), 5);
+
+		assert_ok!(CollatorSelectionModule::set_candidacy_bond(
+			Origin::signed(COUNCIL),
+			200
+		));
+		let candidacy_bond_set_event = Event::collator_selection(crate::Event::CandidacyBondSet(200));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == candidacy_bond_set_event));
+		assert_eq!(CollatorSelectionModule::candidacy_bond(), 200);
+
+		assert_ok!(CollatorSelectionModule::set_kick_config(
+			Origin::signed(COUNCIL),
+			Permill::from_percent(50),
+			Permill::from_percent(10)
+		));
+		let kick_config_set_event = Event::collator_selection(crate::Event::KickConfigSet(
+			Permill::from_percent(50),
+			Permill::from_percent(10),
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == kick_config_set_event));
+		assert_eq!(CollatorSelectionModule::kick_threshold(), Permill::from_percent(50));
+		assert_eq!(CollatorSelectionModule::slash_ratio(), Permill::from_percent(10));
+	});
+}
+
+#[test]
+fn register_as_candidate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CollatorSelectionModule::register_as_candidate(Origin::signed(ALICE)),
+			Error::<Runtime>::AlreadyInvulnerable
+		);
+		assert_noop!(
+			CollatorSelectionModule::register_as_candidate(Origin::signed(DAVE)),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(BOB)));
+		let candidate_added_event = Event::collator_selection(crate::Event::CandidateAdded(BOB, 100));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == candidate_added_event));
+		assert_eq!(
+			CollatorSelectionModule::candidates(),
+			vec![CandidateInfo { who: BOB, bond: 100 }]
+		);
+		assert_eq!(PalletBalances::reserved_balance(BOB), 100);
+
+		assert_noop!(
+			CollatorSelectionModule::register_as_candidate(Origin::signed(BOB)),
+			Error::<Runtime>::AlreadyCandidate
+		);
+
+		assert_ok!(CollatorSelectionModule::set_candidacy_bond(Origin::signed(COUNCIL), 10));
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(CHARLIE)));
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(DAVE)));
+		assert_eq!(PalletBalances::reserved_balance(DAVE), 10);
+		assert_noop!(
+			CollatorSelectionModule::register_as_candidate(Origin::signed(TREASURY)),
+			Error::<Runtime>::TooManyCandidates
+		);
+	});
+}
+
+#[test]
+fn leave_intent_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CollatorSelectionModule::leave_intent(Origin::signed(BOB)),
+			Error::<Runtime>::NotCandidate
+		);
+
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(BOB)));
+		assert_eq!(PalletBalances::reserved_balance(BOB), 100);
+
+		assert_ok!(CollatorSelectionModule::leave_intent(Origin::signed(BOB)));
+		let candidate_removed_event = Event::collator_selection(crate::Event::CandidateRemoved(BOB));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == candidate_removed_event));
+		assert_eq!(CollatorSelectionModule::candidates(), vec![]);
+		assert_eq!(PalletBalances::reserved_balance(BOB), 0);
+		assert_eq!(PalletBalances::free_balance(BOB), 1_000);
+	});
+}
+
+#[test]
+fn new_session_select_collators_by_bond() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(CollatorSelectionModule::new_session(1), Some(vec![ALICE]));
+
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(BOB)));
+		assert_ok!(CollatorSelectionModule::set_candidacy_bond(
+			Origin::signed(COUNCIL),
+			200
+		));
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(CHARLIE)));
+		assert_ok!(CollatorSelectionModule::set_desired_candidates(
+			Origin::signed(COUNCIL),
+			1
+		));
+		assert_eq!(CollatorSelectionModule::new_session(2), Some(vec![ALICE, CHARLIE]));
+
+		assert_ok!(CollatorSelectionModule::set_desired_candidates(
+			Origin::signed(COUNCIL),
+			2
+		));
+		assert_eq!(CollatorSelectionModule::new_session(3), Some(vec![ALICE, CHARLIE, BOB]));
+		assert_eq!(CollatorSelectionModule::planned_collators(3), vec![ALICE, CHARLIE, BOB]);
+
+		CollatorSelectionModule::start_session(3);
+		assert_eq!(CollatorSelectionModule::session_collators(), vec![ALICE, CHARLIE, BOB]);
+		assert_eq!(CollatorSelectionModule::planned_collators(3), vec![]);
+	});
+}
+
+#[test]
+fn end_session_kick_underperformers() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(BOB)));
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(CHARLIE)));
+		assert_eq!(CollatorSelectionModule::new_session(1), Some(vec![ALICE, BOB, CHARLIE]));
+		CollatorSelectionModule::start_session(1);
+
+		for _ in 0..6 {
+			CollatorSelectionModule::note_author(BOB);
+		}
+		assert_eq!(CollatorSelectionModule::authored_blocks(BOB), 6);
+
+		// kicking is disabled
+		CollatorSelectionModule::end_session(1);
+		assert_eq!(CollatorSelectionModule::authored_blocks(BOB), 0);
+		assert_eq!(CollatorSelectionModule::candidates().len(), 2);
+
+		assert_ok!(CollatorSelectionModule::set_kick_config(
+			Origin::signed(COUNCIL),
+			Permill::from_percent(50),
+			Permill::from_percent(10)
+		));
+		assert_eq!(CollatorSelectionModule::new_session(2), Some(vec![ALICE, BOB, CHARLIE]));
+		CollatorSelectionModule::start_session(2);
+
+		// the average is 2 blocks, ALICE and CHARLIE authored fewer than 1 block
+		for _ in 0..6 {
+			CollatorSelectionModule::note_author(BOB);
+		}
+		CollatorSelectionModule::end_session(2);
+
+		let candidate_kicked_event = Event::collator_selection(crate::Event::CandidateKicked(CHARLIE, 0, 10));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == candidate_kicked_event));
+		assert_eq!(
+			CollatorSelectionModule::candidates(),
+			vec![CandidateInfo { who: BOB, bond: 100 }]
+		);
+		assert_eq!(CollatorSelectionModule::invulnerables(), vec![ALICE]);
+		assert_eq!(PalletBalances::reserved_balance(CHARLIE), 0);
+		assert_eq!(PalletBalances::free_balance(CHARLIE), 990);
+		assert_eq!(PalletBalances::free_balance(TREASURY), 11);
+		assert_eq!(PalletBalances::reserved_balance(BOB), 100);
+		assert_eq!(CollatorSelectionModule::session_collators(), vec![]);
+
+		assert_eq!(CollatorSelectionModule::new_session(3), Some(vec![ALICE, BOB]));
+	});
+}
+
+#[test]
+fn delegate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			CollatorSelectionModule::delegate(Origin::signed(EVE), BOB, 20),
+			Error::<Runtime>::NotCandidate
+		);
+
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(BOB)));
+		assert_noop!(
+			CollatorSelectionModule::delegate(Origin::signed(EVE), BOB, 5),
+			Error::<Runtime>::BelowMinDelegation
+		);
+		assert_noop!(
+			CollatorSelectionModule::delegate(Origin::signed(DAVE), BOB, 60),
+			pallet_balances::Error::<Runtime>::InsufficientBalance
+		);
+
+		assert_ok!(CollatorSelectionModule::delegate(Origin::signed(EVE), BOB, 20));
+		let delegated_event = Event::collator_selection(crate::Event::Delegated(EVE, BOB, 20));
+		assert!(System::events().iter().any(|record| record.event == delegated_event));
+		assert_ok!(CollatorSelectionModule::delegate(Origin::signed(EVE), BOB, 5));
+		assert_eq!(
+			CollatorSelectionModule::delegations(EVE, BOB),
+			Some(Delegation {
+				amount: 25,
+				reward_per_share_paid: Default::default(),
+			})
+		);
+		assert_eq!(CollatorSelectionModule::total_delegations(BOB), 25);
+		assert_eq!(PalletBalances::reserved_balance(EVE), 25);
+
+		assert_ok!(CollatorSelectionModule::set_candidacy_bond(Origin::signed(COUNCIL), 10));
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(CHARLIE)));
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(DAVE)));
+		assert_ok!(CollatorSelectionModule::delegate(Origin::signed(EVE), CHARLIE, 10));
+		assert_noop!(
+			CollatorSelectionModule::delegate(Origin::signed(EVE), DAVE, 10),
+			Error::<Runtime>::TooManyDelegations
+		);
+	});
+}
+
+#[test]
+fn undelegate_and_claim_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(BOB)));
+		assert_noop!(
+			CollatorSelectionModule::undelegate(Origin::signed(EVE), BOB, 10),
+			Error::<Runtime>::NoDelegation
+		);
+
+		assert_ok!(CollatorSelectionModule::delegate(Origin::signed(EVE), BOB, 25));
+		assert_noop!(
+			CollatorSelectionModule::undelegate(Origin::signed(EVE), BOB, 30),
+			Error::<Runtime>::InsufficientDelegation
+		);
+		assert_noop!(
+			CollatorSelectionModule::undelegate(Origin::signed(EVE), BOB, 20),
+			Error::<Runtime>::BelowMinDelegation
+		);
+
+		assert_ok!(CollatorSelectionModule::undelegate(Origin::signed(EVE), BOB, 10));
+		let undelegated_event = Event::collator_selection(crate::Event::Undelegated(EVE, BOB, 10, 6));
+		assert!(System::events().iter().any(|record| record.event == undelegated_event));
+		assert_eq!(CollatorSelectionModule::total_delegations(BOB), 15);
+		assert_eq!(
+			CollatorSelectionModule::unbondings(EVE),
+			Some(Unbonding {
+				amount: 10,
+				unlock_at: 6
+			})
+		);
+
+		System::set_block_number(3);
+		assert_ok!(CollatorSelectionModule::undelegate(Origin::signed(EVE), BOB, 15));
+		assert_eq!(CollatorSelectionModule::delegations(EVE, BOB), None);
+		assert_eq!(CollatorSelectionModule::total_delegations(BOB), 0);
+		assert_eq!(
+			CollatorSelectionModule::unbondings(EVE),
+			Some(Unbonding {
+				amount: 25,
+				unlock_at: 8
+			})
+		);
+
+		System::set_block_number(7);
+		assert_ok!(CollatorSelectionModule::claim(Origin::signed(EVE)));
+		assert_eq!(PalletBalances::reserved_balance(EVE), 25);
+
+		System::set_block_number(8);
+		assert_ok!(CollatorSelectionModule::claim(Origin::signed(EVE)));
+		let claimed_event = Event::collator_selection(crate::Event::Claimed(EVE, 25, 0));
+		assert!(System::events().iter().any(|record| record.event == claimed_event));
+		assert_eq!(CollatorSelectionModule::unbondings(EVE), None);
+		assert_eq!(PalletBalances::reserved_balance(EVE), 0);
+		assert_eq!(PalletBalances::free_balance(EVE), 1_000);
+	});
+}
+
+#[test]
+fn new_session_select_collators_by_backing() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(BOB)));
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(CHARLIE)));
+		assert_ok!(CollatorSelectionModule::set_desired_candidates(
+			Origin::signed(COUNCIL),
+			1
+		));
+		assert_eq!(CollatorSelectionModule::new_session(1), Some(vec![ALICE, BOB]));
+
+		assert_ok!(CollatorSelectionModule::delegate(Origin::signed(EVE), CHARLIE, 20));
+		assert_eq!(CollatorSelectionModule::new_session(2), Some(vec![ALICE, CHARLIE]));
+	});
+}
+
+#[test]
+fn end_session_distribute_rewards() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(BOB)));
+		assert_ok!(CollatorSelectionModule::register_as_candidate(Origin::signed(CHARLIE)));
+		assert_ok!(CollatorSelectionModule::delegate(Origin::signed(EVE), BOB, 100));
+		assert_eq!(CollatorSelectionModule::new_session(1), Some(vec![ALICE, BOB, CHARLIE]));
+		CollatorSelectionModule::start_session(1);
+
+		let _ = PalletBalances::deposit_creating(&CollatorSelectionModule::account_id(), 301);
+		for _ in 0..3 {
+			CollatorSelectionModule::note_author(ALICE);
+		}
+		for _ in 0..2 {
+			CollatorSelectionModule::note_author(BOB);
+		}
+		CollatorSelectionModule::note_author(CHARLIE);
+
+		// the invulnerables are not rewarded, BOB shares its reward with EVE
+		CollatorSelectionModule::end_session(1);
+		let bob_rewarded_event = Event::collator_selection(crate::Event::CandidateRewarded(BOB, 200));
+		assert!(System::events().iter().any(|record| record.event == bob_rewarded_event));
+		let charlie_rewarded_event = Event::collator_selection(crate::Event::CandidateRewarded(CHARLIE, 100));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == charlie_rewarded_event));
+		assert_eq!(CollatorSelectionModule::pending_rewards(ALICE), 0);
+		assert_eq!(CollatorSelectionModule::pending_rewards(BOB), 100);
+		assert_eq!(CollatorSelectionModule::pending_rewards(CHARLIE), 100);
+		assert_eq!(
+			CollatorSelectionModule::reward_per_share(BOB),
+			FixedU128::saturating_from_integer(1)
+		);
+		assert_eq!(CollatorSelectionModule::unclaimed_rewards(), 300);
+
+		assert_ok!(CollatorSelectionModule::claim(Origin::signed(EVE)));
+		let claimed_event = Event::collator_selection(crate::Event::Claimed(EVE, 0, 100));
+		assert!(System::events().iter().any(|record| record.event == claimed_event));
+		assert_eq!(PalletBalances::free_balance(EVE), 1_000);
+		assert_ok!(CollatorSelectionModule::claim(Origin::signed(BOB)));
+		assert_eq!(PalletBalances::free_balance(BOB), 1_000);
+		assert_eq!(CollatorSelectionModule::unclaimed_rewards(), 100);
+		assert_eq!(PalletBalances::free_balance(CollatorSelectionModule::account_id()), 101);
+
+		// the unclaimed rewards are not distributed again
+		assert_eq!(CollatorSelectionModule::new_session(2), Some(vec![ALICE, BOB, CHARLIE]));
+		CollatorSelectionModule::start_session(2);
+		CollatorSelectionModule::note_author(BOB);
+		CollatorSelectionModule::end_session(2);
+		assert_eq!(CollatorSelectionModule::unclaimed_rewards(), 100);
+		assert_eq!(
+			CollatorSelectionModule::reward_per_share(BOB),
+			FixedU128::saturating_from_integer(1)
+		);
+	});
+}
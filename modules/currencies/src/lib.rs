@@ -29,6 +29,7 @@ use frame_support::{
 		Currency as PalletCurrency, ExistenceRequirement, Get, LockableCurrency as PalletLockableCurrency,
 		ReservableCurrency as PalletReservableCurrency, WithdrawReasons,
 	},
+	transactional,
 };
 use frame_system::pallet_prelude::*;
 use orml_traits::{
@@ -49,7 +50,7 @@ use sp_std::{
 	fmt::Debug,
 	marker, result,
 };
-use support::{AddressMapping, EVMBridge, InvokeContext};
+use support::{AddressMapping, EVMBridge, InvokeContext, MultiCurrencyAllowance};
 
 mod mock;
 mod tests;
@@ -102,6 +103,8 @@ pub mod module {
 		Erc20InvalidOperation,
 		/// EVM account not found
 		EvmAccountNotFound,
+		/// The allowance is too low
+		AllowanceTooLow,
 	}
 
 	#[pallet::event]
@@ -115,7 +118,25 @@ pub mod module {
 		Deposited(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
 		/// Withdraw success. [currency_id, who, amount]
 		Withdrawn(CurrencyIdOf<T>, T::AccountId, BalanceOf<T>),
-	}
+		/// Approve success. [currency_id, owner, spender, amount]
+		Approved(CurrencyIdOf<T>, T::AccountId, T::AccountId, BalanceOf<T>),
+	}
+
+	/// The amount of currency that spenders are allowed to transfer on behalf
+	/// of owners.
+	///
+	/// Allowances: double_map (AccountId, CurrencyId), AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn allowances)]
+	pub type Allowances<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		(T::AccountId, CurrencyIdOf<T>),
+		Twox64Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
@@ -175,6 +196,89 @@ pub mod module {
 			<Self as MultiCurrencyExtended<T::AccountId>>::update_balance(currency_id, &dest, amount)?;
 			Ok(().into())
 		}
+
+		/// Allow `spender` to transfer up to `amount` of `currency_id` on
+		/// behalf of the caller, replacing the current allowance.
+		///
+		/// The dispatch origin for this call must be `Signed` by the owner.
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(
+			origin: OriginFor<T>,
+			spender: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let owner = ensure_signed(origin)?;
+			let spender = T::Lookup::lookup(spender)?;
+			<Self as MultiCurrencyAllowance<T::AccountId>>::approve(currency_id, &owner, &spender, amount)?;
+			Ok(().into())
+		}
+
+		/// Transfer `amount` of `currency_id` from `from` to `dest` within
+		/// the allowance approved by `from` to the caller.
+		///
+		/// The dispatch origin for this call must be `Signed` by the spender.
+		#[pallet::weight(T::WeightInfo::transfer_from())]
+		#[transactional]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			from: <T::Lookup as StaticLookup>::Source,
+			dest: <T::Lookup as StaticLookup>::Source,
+			currency_id: CurrencyIdOf<T>,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let spender = ensure_signed(origin)?;
+			let from = T::Lookup::lookup(from)?;
+			let to = T::Lookup::lookup(dest)?;
+			<Self as MultiCurrencyAllowance<T::AccountId>>::transfer_from(currency_id, &spender, &from, &to, amount)?;
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> MultiCurrencyAllowance<T::AccountId> for Pallet<T> {
+	type CurrencyId = CurrencyIdOf<T>;
+	type Balance = BalanceOf<T>;
+
+	fn allowance(currency_id: Self::CurrencyId, owner: &T::AccountId, spender: &T::AccountId) -> Self::Balance {
+		Self::allowances((owner, currency_id), spender)
+	}
+
+	fn approve(
+		currency_id: Self::CurrencyId,
+		owner: &T::AccountId,
+		spender: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() {
+			Allowances::<T>::remove((owner, currency_id), spender);
+		} else {
+			Allowances::<T>::insert((owner, currency_id), spender, amount);
+		}
+
+		Self::deposit_event(Event::Approved(currency_id, owner.clone(), spender.clone(), amount));
+		Ok(())
+	}
+
+	fn transfer_from(
+		currency_id: Self::CurrencyId,
+		spender: &T::AccountId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		let remaining = Self::allowance(currency_id, from, spender)
+			.checked_sub(&amount)
+			.ok_or(Error::<T>::AllowanceTooLow)?;
+
+		<Self as MultiCurrency<T::AccountId>>::transfer(currency_id, from, to, amount)?;
+
+		if remaining.is_zero() {
+			Allowances::<T>::remove((from, currency_id), spender);
+		} else {
+			Allowances::<T>::insert((from, currency_id), spender, remaining);
+		}
+		Ok(())
 	}
 }
 
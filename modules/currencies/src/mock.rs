@@ -154,7 +154,6 @@ impl module_evm::Config for Runtime {
 
 	type Event = Event;
 	type Precompiles = ();
-	type ChainId = ();
 	type GasToWeight = ();
 	type ChargeTransactionPayment = ();
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId>;
@@ -164,6 +163,11 @@ impl module_evm::Config for Runtime {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
+	type CallFilter = ();
+	type ReapInactivityPeriod = ();
+	type ReapedDepositPot = TreasuryAccount;
+	type ReceiptsHistoryDepth = ();
+	type MaxListedCallers = ();
 
 	type WeightInfo = ();
 }
@@ -299,7 +303,7 @@ impl ExtBuilder {
 				code: from_hex(include!("../../evm-bridge/src/erc20_demo_contract")).unwrap(),
 			},
 		);
-		module_evm::GenesisConfig::<Runtime> { accounts }
+		module_evm::GenesisConfig::<Runtime> { chain_id: 1, accounts }
 			.assimilate_storage(&mut t)
 			.unwrap();
 
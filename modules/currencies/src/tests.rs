@@ -307,6 +307,62 @@ fn call_event_should_work() {
 		});
 }
 
+#[test]
+fn approve_and_transfer_from_should_work() {
+	ExtBuilder::default()
+		.one_hundred_for_alice_n_bob()
+		.build()
+		.execute_with(|| {
+			assert_ok!(Currencies::approve(Some(ALICE).into(), BOB, X_TOKEN_ID, 50));
+			let approved_event = Event::currencies(crate::Event::Approved(X_TOKEN_ID, ALICE, BOB, 50));
+			assert!(System::events().iter().any(|record| record.event == approved_event));
+			assert_eq!(
+				<Currencies as MultiCurrencyAllowance<AccountId>>::allowance(X_TOKEN_ID, &ALICE, &BOB),
+				50
+			);
+
+			assert_noop!(
+				Currencies::transfer_from(Some(BOB).into(), ALICE, EVA, X_TOKEN_ID, 60),
+				Error::<Runtime>::AllowanceTooLow
+			);
+			assert_noop!(
+				Currencies::transfer_from(Some(EVA).into(), ALICE, EVA, X_TOKEN_ID, 10),
+				Error::<Runtime>::AllowanceTooLow
+			);
+
+			assert_ok!(Currencies::transfer_from(Some(BOB).into(), ALICE, EVA, X_TOKEN_ID, 30));
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &ALICE), 70);
+			assert_eq!(Currencies::free_balance(X_TOKEN_ID, &EVA), 30);
+			assert_eq!(Currencies::allowances((ALICE, X_TOKEN_ID), BOB), 20);
+
+			// the allowance is kept if the transfer fails
+			assert_ok!(Currencies::approve(Some(ALICE).into(), BOB, X_TOKEN_ID, 1_000));
+			assert!(Currencies::transfer_from(Some(BOB).into(), ALICE, EVA, X_TOKEN_ID, 500).is_err());
+			assert_eq!(Currencies::allowances((ALICE, X_TOKEN_ID), BOB), 1_000);
+
+			// native currency
+			assert_ok!(Currencies::approve(Some(ALICE).into(), BOB, NATIVE_CURRENCY_ID, 10));
+			assert_ok!(Currencies::transfer_from(
+				Some(BOB).into(),
+				ALICE,
+				EVA,
+				NATIVE_CURRENCY_ID,
+				10
+			));
+			assert_eq!(NativeCurrency::free_balance(&ALICE), 90);
+			assert_eq!(NativeCurrency::free_balance(&EVA), 10);
+			assert!(!Allowances::<Runtime>::contains_key((ALICE, NATIVE_CURRENCY_ID), BOB));
+			assert_noop!(
+				Currencies::transfer_from(Some(BOB).into(), ALICE, EVA, NATIVE_CURRENCY_ID, 1),
+				Error::<Runtime>::AllowanceTooLow
+			);
+
+			// approve zero removes the allowance
+			assert_ok!(Currencies::approve(Some(ALICE).into(), BOB, X_TOKEN_ID, 0));
+			assert!(!Allowances::<Runtime>::contains_key((ALICE, X_TOKEN_ID), BOB));
+		});
+}
+
 #[test]
 fn erc20_total_issuance_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
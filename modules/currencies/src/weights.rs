@@ -53,6 +53,8 @@ pub trait WeightInfo {
 	fn update_balance_non_native_currency() -> Weight;
 	fn update_balance_native_currency_creating() -> Weight;
 	fn update_balance_native_currency_killing() -> Weight;
+	fn approve() -> Weight;
+	fn transfer_from() -> Weight;
 }
 
 /// Weights for module_currencies using the Acala node and recommended hardware.
@@ -77,6 +79,15 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 	fn update_balance_native_currency_killing() -> Weight {
 		(13_000_000 as Weight)
 	}
+	fn approve() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn transfer_from() -> Weight {
+		(72_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -100,4 +111,13 @@ impl WeightInfo for () {
 	fn update_balance_native_currency_killing() -> Weight {
 		(13_000_000 as Weight)
 	}
+	fn approve() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn transfer_from() -> Weight {
+		(72_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
 }
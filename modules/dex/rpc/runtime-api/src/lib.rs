@@ -0,0 +1,155 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for dex module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Codec, Decode, Encode};
+use primitives::CurrencyId;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sp_runtime::traits::{MaybeDisplay, MaybeFromStr};
+use sp_std::prelude::*;
+
+/// The max number of storage entries scanned for share holders in one page.
+pub const MAX_SHARE_HOLDERS_SCAN: u32 = 1000;
+
+/// The LP share amount of a holder.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ShareHolder<AccountId, Balance> {
+	/// The holder account.
+	pub who: AccountId,
+	/// The LP share amount held directly or deposited to incentives.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub share: Balance,
+}
+
+/// A page of the LP share holders of a trading pair.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ShareHoldersPage<AccountId, Balance> {
+	/// The share holders in this page.
+	#[cfg_attr(
+		feature = "std",
+		serde(bound(
+			serialize = "AccountId: Serialize, Balance: std::fmt::Display",
+			deserialize = "AccountId: Deserialize<'de>, Balance: std::str::FromStr"
+		))
+	)]
+	pub holders: Vec<ShareHolder<AccountId, Balance>>,
+	/// The storage key cursor to query the next page, `None` if this is the
+	/// last page.
+	pub next_key: Option<Vec<u8>>,
+}
+
+/// Swap volume and fees of a trading pair, in amounts of the supplied
+/// currency, `a` and `b` follow the order of the queried currencies.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+	feature = "std",
+	serde(bound(serialize = "Balance: std::fmt::Display", deserialize = "Balance: std::str::FromStr"))
+)]
+pub struct VolumeAndFee<Balance> {
+	/// The supplied amount of currency a.
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub volume_a: Balance,
+	/// The supplied amount of currency b.
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub volume_b: Balance,
+	/// The swap fee charged in currency a.
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub fee_a: Balance,
+	/// The swap fee charged in currency b.
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub fee_b: Balance,
+}
+
+/// The recorded swap volume and fees of a trading pair.
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+	feature = "std",
+	serde(bound(serialize = "Balance: std::fmt::Display", deserialize = "Balance: std::str::FromStr"))
+)]
+pub struct TradingVolume<Balance> {
+	/// The volume since it is recorded.
+	pub cumulative: VolumeAndFee<Balance>,
+	/// The volume of the last volume bucket period, 24 hours on the
+	/// production chains.
+	pub rolling: VolumeAndFee<Balance>,
+	/// The volume of each bucket period kept onchain, newest first.
+	pub periods: Vec<VolumeAndFee<Balance>>,
+}
+
+#[cfg(feature = "std")]
+fn serialize_as_string<S: Serializer, T: std::fmt::Display>(t: &T, serializer: S) -> Result<S::Ok, S::Error> {
+	serializer.serialize_str(&t.to_string())
+}
+
+#[cfg(feature = "std")]
+fn deserialize_from_string<'de, D: Deserializer<'de>, T: std::str::FromStr>(deserializer: D) -> Result<T, D::Error> {
+	let s = String::deserialize(deserializer)?;
+	s.parse::<T>()
+		.map_err(|_| serde::de::Error::custom("Parse from string failed"))
+}
+
+sp_api::decl_runtime_apis! {
+	/// Version 2 adds `get_trading_volume`.
+	#[api_version(2)]
+	pub trait DexApi<AccountId, Balance> where
+		AccountId: Codec,
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+	{
+		/// Get a page of the LP share holders of the trading pair of
+		/// `currency_id_a` and `currency_id_b` at the queried block, scanning
+		/// at most `max_scan` storage entries from the cursor `start_key`.
+		/// `max_scan` is capped at `MAX_SHARE_HOLDERS_SCAN`. An account
+		/// holding the LP shares both directly and deposited to incentives
+		/// appears twice.
+		fn get_liquidity_share_holders(
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			start_key: Option<Vec<u8>>,
+			max_scan: u32,
+		) -> ShareHoldersPage<AccountId, Balance>;
+
+		/// Get the recorded swap volume and fees of the trading pair of
+		/// `currency_id_a` and `currency_id_b`.
+		fn get_trading_volume(
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) -> Option<TradingVolume<Balance>>;
+	}
+}
@@ -26,6 +26,13 @@
 //! liquidation by auction when the liquidity is sufficient. And providing
 //! market making liquidity for DEX will also receive stable currency as
 //! additional reward for its participation in the CDP liquidation.
+//!
+//! Trading pairs of like-valued assets can be enabled with the stable swap
+//! invariant, which refers to the design of Curve, to provide lower slippage
+//! for trades near the peg. Trading pairs can also be enabled with
+//! concentrated liquidity, LPs provide liquidity in a range of bucketed
+//! prices as positions minted as NFT tokens, and each bucket trades at its
+//! fixed price, which is a simplified version of Uniswap V3.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::too_many_arguments)]
@@ -35,17 +42,20 @@
 
 use frame_support::{log, pallet_prelude::*, transactional, PalletId};
 use frame_system::pallet_prelude::*;
-use orml_traits::{MultiCurrency, MultiCurrencyExtended};
-use primitives::{Balance, CurrencyId, TradingPair};
-use sp_core::U256;
+use orml_traits::{BalanceStatus, MultiCurrency, MultiCurrencyExtended, MultiReservableCurrency};
+use primitives::{currency::GetDecimals, Balance, CurrencyId, TradingPair};
 use sp_runtime::{
-	traits::{AccountIdConversion, UniqueSaturatedInto, Zero},
-	DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug, SaturatedConversion,
+	traits::{AccountIdConversion, AtLeast32BitUnsigned, One, Saturating, UniqueSaturatedInto, Zero},
+	DispatchError, DispatchResult, FixedPointNumber, Permill, RuntimeDebug, SaturatedConversion,
 };
 use sp_std::{convert::TryInto, prelude::*, vec};
-use support::{CurrencyIdMapping, DEXIncentives, DEXManager, Price, Ratio};
+use support::{
+	math, CurrencyIdMapping, DEXIncentives, DEXManager, Erc20ReferenceCounter, ParameterInvariants, PositionNFT, Price,
+	Ratio,
+};
 
 mod mock;
+mod stable_swap;
 mod tests;
 pub mod weights;
 
@@ -85,6 +95,191 @@ impl<Balance, BlockNumber> Default for TradingPairStatus<Balance, BlockNumber> {
 	}
 }
 
+/// The invariant of the liquidity pool of TradingPair
+#[derive(Clone, Copy, Encode, Decode, RuntimeDebug, PartialEq, Eq)]
+pub enum PoolType<BlockNumber> {
+	/// Default type, the constant product invariant refers to Uniswap V2.
+	ConstantProduct,
+	/// The stable swap invariant for like-valued assets refers to Curve.
+	StableSwap(StableSwapParameters<BlockNumber>),
+	/// Concentrated liquidity provided in the bucketed price ranges.
+	ConcentratedLiquidity(RangeParameters),
+}
+
+impl<BlockNumber> Default for PoolType<BlockNumber> {
+	fn default() -> Self {
+		Self::ConstantProduct
+	}
+}
+
+/// Parameters of the stable swap invariant of TradingPair. The
+/// amplification coefficient changes linearly from `initial_amplification`
+/// to `future_amplification` between `ramp_start` and `ramp_end`.
+#[derive(Clone, Copy, Encode, Decode, RuntimeDebug, PartialEq, Eq)]
+pub struct StableSwapParameters<BlockNumber> {
+	/// The amplification coefficient at `ramp_start`.
+	initial_amplification: u32,
+	/// The amplification coefficient at and after `ramp_end`.
+	future_amplification: u32,
+	/// The block number the ramp starts.
+	ramp_start: BlockNumber,
+	/// The block number the ramp ends.
+	ramp_end: BlockNumber,
+	/// The multipliers normalizing the balances of `TradingPair.0` and
+	/// `TradingPair.1` to the same precision.
+	precision_multipliers: (Balance, Balance),
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> StableSwapParameters<BlockNumber> {
+	/// The amplification coefficient at `now`.
+	fn amplification(&self, now: BlockNumber) -> u32 {
+		if now >= self.ramp_end {
+			return self.future_amplification;
+		}
+		if now <= self.ramp_start {
+			return self.initial_amplification;
+		}
+
+		let elapsed: u128 = now.saturating_sub(self.ramp_start).unique_saturated_into();
+		let duration: u128 = self.ramp_end.saturating_sub(self.ramp_start).unique_saturated_into();
+		let initial = u128::from(self.initial_amplification);
+		let future = u128::from(self.future_amplification);
+		let amplification = if future > initial {
+			initial.saturating_add(future.saturating_sub(initial).saturating_mul(elapsed) / duration)
+		} else {
+			initial.saturating_sub(initial.saturating_sub(future).saturating_mul(elapsed) / duration)
+		};
+		amplification.saturated_into()
+	}
+
+	/// The precision multipliers of `supply_currency_id` and the other
+	/// currency of `trading_pair`.
+	fn oriented_multipliers(&self, trading_pair: TradingPair, supply_currency_id: CurrencyId) -> (Balance, Balance) {
+		if supply_currency_id == trading_pair.0 {
+			self.precision_multipliers
+		} else {
+			(self.precision_multipliers.1, self.precision_multipliers.0)
+		}
+	}
+}
+
+/// Parameters of the buckets of concentrated liquidity TradingPair, the
+/// price of bucket `i` is `min_price + price_step * i`, which is the price of
+/// `TradingPair.0` in `TradingPair.1`.
+#[derive(Clone, Copy, Encode, Decode, RuntimeDebug, PartialEq, Eq)]
+pub struct RangeParameters {
+	/// The price of the lowest bucket.
+	min_price: Price,
+	/// The price difference between adjacent buckets.
+	price_step: Price,
+	/// The number of buckets.
+	bucket_count: u32,
+}
+
+impl RangeParameters {
+	fn bucket_price(&self, bucket_id: u32) -> Price {
+		self.price_step
+			.saturating_mul(Price::saturating_from_integer(bucket_id))
+			.saturating_add(self.min_price)
+	}
+
+	/// The next bucket in the moving direction of price, the price goes down
+	/// when supplying `TradingPair.0` and goes up when supplying
+	/// `TradingPair.1`.
+	fn next_bucket(&self, bucket_id: u32, is_supply_0: bool) -> Option<u32> {
+		if is_supply_0 {
+			bucket_id.checked_sub(1)
+		} else {
+			bucket_id.checked_add(1).filter(|id| *id < self.bucket_count)
+		}
+	}
+}
+
+/// Permissionless proposal to list a TradingPair, the bond of proposer is
+/// reserved until the proposal activates or is vetoed.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct ListingProposal<AccountId, Balance, BlockNumber> {
+	/// The proposer of this listing.
+	proposer: AccountId,
+	/// The reserved bond of proposer.
+	bond: Balance,
+	/// limit contribution per time.
+	min_contribution: (Balance, Balance),
+	/// target provision that trading pair could to be Enabled.
+	target_provision: (Balance, Balance),
+	/// The number of block that status can be converted to Enabled.
+	not_before: BlockNumber,
+	/// The block at which the challenge period ends and the trading pair
+	/// is listed.
+	activate_at: BlockNumber,
+}
+
+/// Liquidity of a bucket of concentrated liquidity TradingPair. Buckets
+/// below the current bucket only hold `TradingPair.1`, and buckets above
+/// the current bucket only hold `TradingPair.0`.
+#[derive(Clone, Copy, Encode, Decode, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct RangeBucket {
+	/// The amount of `TradingPair.0`.
+	#[codec(compact)]
+	reserve_0: Balance,
+	/// The amount of `TradingPair.1`.
+	#[codec(compact)]
+	reserve_1: Balance,
+	/// The total shares of positions in this bucket.
+	#[codec(compact)]
+	total_shares: Balance,
+}
+
+/// Id of concentrated liquidity position, which is the id of the NFT token
+/// minted for the position.
+pub type PositionId = u64;
+
+/// Position of concentrated liquidity, which holds `shares` in every bucket
+/// of `[lower_bucket, upper_bucket]`. The position is owned by the owner of
+/// its NFT token.
+#[derive(Clone, Encode, Decode, RuntimeDebug, PartialEq, Eq)]
+pub struct RangePosition {
+	/// The trading pair of this position.
+	trading_pair: TradingPair,
+	/// The lowest bucket of the range.
+	lower_bucket: u32,
+	/// The highest bucket of the range.
+	upper_bucket: u32,
+	/// The shares in every bucket of the range.
+	#[codec(compact)]
+	shares: Balance,
+}
+
+/// Swap volume and fees of a TradingPair, in amounts of the supplied
+/// currency.
+#[derive(Clone, Copy, Encode, Decode, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct VolumeRecord {
+	/// The supplied amount of `TradingPair.0`.
+	#[codec(compact)]
+	pub volume_0: Balance,
+	/// The supplied amount of `TradingPair.1`.
+	#[codec(compact)]
+	pub volume_1: Balance,
+	/// The swap fee charged in `TradingPair.0`.
+	#[codec(compact)]
+	pub fee_0: Balance,
+	/// The swap fee charged in `TradingPair.1`.
+	#[codec(compact)]
+	pub fee_1: Balance,
+}
+
+impl VolumeRecord {
+	fn accrue(&mut self, is_supply_0: bool, volume: Balance, fee: Balance) {
+		if is_supply_0 {
+			self.volume_0 = self.volume_0.saturating_add(volume);
+			self.fee_0 = self.fee_0.saturating_add(fee);
+		} else {
+			self.volume_1 = self.volume_1.saturating_add(volume);
+			self.fee_1 = self.fee_1.saturating_add(fee);
+		}
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -94,9 +289,10 @@ pub mod module {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
 		/// Currency for transfer currencies
-		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>
+			+ MultiReservableCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
 
-		/// Trading fee rate
+		/// Default trading fee rate, used if not updated by `ListingOrigin`.
 		/// The first item of the tuple is the numerator of the fee rate, second
 		/// item is the denominator, fee_rate = numerator / denominator,
 		/// use (u32, u32) over `Rate` type to minimize internal division
@@ -116,6 +312,12 @@ pub mod module {
 		/// address as LP token.
 		type CurrencyIdMapping: CurrencyIdMapping;
 
+		/// Record the Erc20 currencies referenced by the trading pairs.
+		type Erc20ReferenceCounter: Erc20ReferenceCounter;
+
+		/// Mint the positions of concentrated liquidity as NFT tokens.
+		type PositionNFT: PositionNFT<Self::AccountId, TokenId = PositionId>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 
@@ -124,6 +326,55 @@ pub mod module {
 
 		/// The origin which may list, enable or disable trading pairs.
 		type ListingOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The number of blocks after `not_before` for a Provisioning trading
+		/// pair to reach its target provision, after that the provisioning
+		/// fails and contributors can claim refunds.
+		#[pallet::constant]
+		type ProvisioningPeriod: Get<Self::BlockNumber>;
+
+		/// The number of blocks over which the amplification coefficient of
+		/// a non-empty stable swap trading pair is ramped to the new one.
+		#[pallet::constant]
+		type AmplificationRampPeriod: Get<Self::BlockNumber>;
+
+		/// The max number of buckets of concentrated liquidity trading pair,
+		/// which bounds the weight of swap and range liquidity operations.
+		#[pallet::constant]
+		type MaxRangeBuckets: Get<u32>;
+
+		/// The max number of input currencies of a basket swap.
+		#[pallet::constant]
+		type MaxBasketInputs: Get<u32>;
+
+		/// The native currency id, in which the listing bond is reserved.
+		#[pallet::constant]
+		type GetNativeCurrencyId: Get<CurrencyId>;
+
+		/// The bond reserved from the proposer of a listing proposal.
+		#[pallet::constant]
+		type ListingBond: Get<Balance>;
+
+		/// The number of blocks during which a listing proposal can be vetoed,
+		/// after that the trading pair is listed.
+		#[pallet::constant]
+		type ListingChallengePeriod: Get<Self::BlockNumber>;
+
+		/// The origin which may veto listing proposals.
+		type ListingVetoOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The account which receives the bond of vetoed listing proposals.
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The number of blocks covered by a bucket of swap volume history,
+		/// also the window of the rolling volume.
+		#[pallet::constant]
+		type VolumeBucketPeriod: Get<Self::BlockNumber>;
+
+		/// The max number of buckets of swap volume history kept for a
+		/// TradingPair, older buckets are overwritten.
+		#[pallet::constant]
+		type MaxVolumeBuckets: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -152,12 +403,53 @@ pub mod module {
 		ExcessiveSupplyAmount,
 		/// The swap will cause unacceptable price impact
 		ExceedPriceImpactLimit,
+		/// The swap moves the spot price more than the max price impact
+		ExceedMaxPriceImpact,
 		/// Liquidity is not enough
 		InsufficientLiquidity,
 		/// The supply amount is zero
 		ZeroSupplyAmount,
 		/// The target amount is zero
 		ZeroTargetAmount,
+		/// The exchange fee is invalid
+		InvalidExchangeFee,
+		/// The provisioning of trading pair has failed
+		ProvisioningFailed,
+		/// The provisioning of trading pair has not failed
+		ProvisioningNotFailed,
+		/// No provision to refund
+		NoProvisionToRefund,
+		/// No referrer rewards to claim
+		NoReferrerRewards,
+		/// The amplification coefficient is invalid
+		InvalidAmplification,
+		/// The liquidity pool of trading pair is not empty
+		LiquidityPoolNotEmpty,
+		/// The decimals of currency is unknown
+		UnknownDecimals,
+		/// The parameters of buckets are invalid
+		InvalidRangeParameters,
+		/// The range of buckets is invalid
+		InvalidBucketRange,
+		/// Trading pair is not of concentrated liquidity
+		NotRangeTradingPair,
+		/// Full range liquidity is not allowed for trading pair of
+		/// concentrated liquidity
+		RangeTradingPairNotAllowed,
+		/// Range liquidity amount is more than max amount
+		ExcessiveRangeLiquidityAmount,
+		/// Range position not found
+		RangePositionNotFound,
+		/// Caller is not the owner of range position
+		NotRangePositionOwner,
+		/// The number of basket inputs is invalid
+		InvalidBasketLength,
+		/// The trading pair already has a listing proposal
+		ListingProposalExisted,
+		/// Listing proposal not found
+		ListingProposalNotFound,
+		/// The calculation of amounts overflows
+		Overflow,
 	}
 
 	#[pallet::event]
@@ -176,8 +468,24 @@ pub mod module {
 		/// Use supply currency to swap target currency. \[trader, trading_path,
 		/// supply_currency_amount, target_currency_amount\]
 		Swap(T::AccountId, Vec<CurrencyId>, Balance, Balance),
+		/// Use a basket of supply currencies to swap exact target currency.
+		/// \[trader, supply_amounts, target_currency_id, target_amount\]
+		BasketSwap(T::AccountId, Vec<(CurrencyId, Balance)>, CurrencyId, Balance),
 		/// Enable trading pair. \[trading_pair\]
 		EnableTradingPair(TradingPair),
+		/// Enable trading pair with the stable swap invariant, the
+		/// amplification of a non-empty pool is ramped to the new one.
+		/// \[trading_pair, amplification\]
+		EnableStableSwapTradingPair(TradingPair, u32),
+		/// Enable trading pair with concentrated liquidity.
+		/// \[trading_pair, range_parameters\]
+		EnableRangeTradingPair(TradingPair, RangeParameters),
+		/// Add liquidity to the range of buckets. \[who, position_id,
+		/// pool_0_increment, pool_1_increment, share_increment\]
+		AddRangeLiquidity(T::AccountId, PositionId, Balance, Balance, Balance),
+		/// Remove liquidity from the range of buckets. \[who, position_id,
+		/// pool_0_decrement, pool_1_decrement, share_decrement\]
+		RemoveRangeLiquidity(T::AccountId, PositionId, Balance, Balance, Balance),
 		/// List trading pair. \[trading_pair\]
 		ListTradingPair(TradingPair),
 		/// Disable trading pair. \[trading_pair\]
@@ -185,6 +493,31 @@ pub mod module {
 		/// Provisioning trading pair convert to Enabled. \[trading_pair,
 		/// pool_0_amount, pool_1_amount, total_share_amount\]
 		ProvisioningToEnabled(TradingPair, Balance, Balance, Balance),
+		/// Exchange fee updated. \[fee_numerator, fee_denominator\]
+		ExchangeFeeUpdated(u32, u32),
+		/// Refund provision of failed Provisioning trading pair. \[who,
+		/// currency_id_0, contribution_0, currency_id_1, contribution_1\]
+		RefundProvision(T::AccountId, CurrencyId, Balance, CurrencyId, Balance),
+		/// Referrer fee share updated. \[referrer_fee_share\]
+		ReferrerFeeShareUpdated(Permill),
+		/// Referrer earned a share of the swap fee. \[referrer, currency_id,
+		/// reward_amount\]
+		ReferrerRewarded(T::AccountId, CurrencyId, Balance),
+		/// Referrer claimed the accumulated rewards. \[referrer, currency_id,
+		/// reward_amount\]
+		ClaimReferrerRewards(T::AccountId, CurrencyId, Balance),
+		/// Propose to list trading pair. \[proposer, trading_pair, bond,
+		/// activate_at\]
+		ProposeListing(T::AccountId, TradingPair, Balance, T::BlockNumber),
+		/// Listing proposal is vetoed and the bond is slashed.
+		/// \[trading_pair, proposer, slashed_bond\]
+		VetoListing(TradingPair, T::AccountId, Balance),
+		/// Listing proposal activated after the challenge period.
+		/// \[trading_pair\]
+		ListingProposalActivated(TradingPair),
+		/// The default max price impact of swaps through trading pair
+		/// updated. \[trading_pair, max_price_impact\]
+		MaxPriceImpactUpdated(TradingPair, Option<Ratio>),
 	}
 
 	/// Liquidity pool for TradingPair.
@@ -198,12 +531,104 @@ pub mod module {
 	pub type TradingPairStatuses<T: Config> =
 		StorageMap<_, Twox64Concat, TradingPair, TradingPairStatus<Balance, T::BlockNumber>, ValueQuery>;
 
+	/// The invariant of the liquidity pool for TradingPair.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_types)]
+	pub type PoolTypes<T: Config> = StorageMap<_, Twox64Concat, TradingPair, PoolType<T::BlockNumber>, ValueQuery>;
+
+	/// Liquidity of buckets of concentrated liquidity TradingPair.
+	///
+	/// RangeBuckets: double_map TradingPair, BucketId => RangeBucket
+	#[pallet::storage]
+	#[pallet::getter(fn range_buckets)]
+	pub type RangeBuckets<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, TradingPair, Twox64Concat, u32, RangeBucket, ValueQuery>;
+
+	/// The bucket of current price of concentrated liquidity TradingPair.
+	#[pallet::storage]
+	#[pallet::getter(fn current_buckets)]
+	pub type CurrentBuckets<T: Config> = StorageMap<_, Twox64Concat, TradingPair, u32, ValueQuery>;
+
+	/// Range positions of concentrated liquidity.
+	#[pallet::storage]
+	#[pallet::getter(fn range_positions)]
+	pub type RangePositions<T: Config> = StorageMap<_, Twox64Concat, PositionId, RangePosition, OptionQuery>;
+
 	/// Provision of TradingPair by AccountId.
 	#[pallet::storage]
 	#[pallet::getter(fn provisioning_pool)]
 	pub type ProvisioningPool<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, TradingPair, Twox64Concat, T::AccountId, (Balance, Balance), ValueQuery>;
 
+	/// Trading fee rate, overrides the default `GetExchangeFee`.
+	#[pallet::storage]
+	#[pallet::getter(fn exchange_fee)]
+	pub type ExchangeFee<T: Config> = StorageValue<_, (u32, u32), OptionQuery>;
+
+	/// The fraction of the swap fee credited to the referrer of a swap.
+	#[pallet::storage]
+	#[pallet::getter(fn referrer_fee_share)]
+	pub type ReferrerFeeShare<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// Unclaimed referrer rewards, kept in the DEX account.
+	///
+	/// ReferrerRewards: double_map AccountId, CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn referrer_rewards)]
+	pub type ReferrerRewards<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// Swap volume and fees of TradingPair since the volume is recorded.
+	#[pallet::storage]
+	#[pallet::getter(fn cumulative_volumes)]
+	pub type CumulativeVolumes<T: Config> = StorageMap<_, Twox64Concat, TradingPair, VolumeRecord, ValueQuery>;
+
+	/// Ring of swap volume history buckets of TradingPair, each bucket is
+	/// tagged with the index of the period it records.
+	///
+	/// VolumeBuckets: double_map TradingPair, Slot => (PeriodIndex,
+	/// VolumeRecord)
+	#[pallet::storage]
+	#[pallet::getter(fn volume_buckets)]
+	pub type VolumeBuckets<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, TradingPair, Twox64Concat, u32, (T::BlockNumber, VolumeRecord), OptionQuery>;
+
+	/// Pending listing proposals of TradingPair.
+	#[pallet::storage]
+	#[pallet::getter(fn listing_proposals)]
+	pub type ListingProposals<T: Config> =
+		StorageMap<_, Twox64Concat, TradingPair, ListingProposal<T::AccountId, Balance, T::BlockNumber>, OptionQuery>;
+
+	/// The listing proposals to activate at block.
+	///
+	/// ListingActivations: double_map BlockNumber, TradingPair => ()
+	#[pallet::storage]
+	#[pallet::getter(fn listing_activations)]
+	pub type ListingActivations<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::BlockNumber, Twox64Concat, TradingPair, (), OptionQuery>;
+
+	/// The default max ratio the spot price of TradingPair can move against
+	/// the swaps from the first swap of the block, set by governance.
+	///
+	/// MaxPriceImpacts: map TradingPair => Option<Ratio>
+	#[pallet::storage]
+	#[pallet::getter(fn max_price_impacts)]
+	pub type MaxPriceImpacts<T: Config> = StorageMap<_, Twox64Concat, TradingPair, Ratio, OptionQuery>;
+
+	/// The spot price of TradingPair with the default max price impact before
+	/// the first swap of the block, and the block number.
+	///
+	/// BlockStartPrices: map TradingPair => Option<(BlockNumber, Price)>
+	#[pallet::storage]
+	#[pallet::getter(fn block_start_prices)]
+	pub type BlockStartPrices<T: Config> =
+		StorageMap<_, Twox64Concat, TradingPair, (T::BlockNumber, Price), OptionQuery>;
+
+	/// True if the Erc20 references of the existing trading pairs have been
+	/// recorded.
+	#[pallet::storage]
+	pub(crate) type UpgradedToErc20References<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub initial_listing_trading_pairs: Vec<(TradingPair, (Balance, Balance), (Balance, Balance), T::BlockNumber)>,
@@ -240,6 +665,7 @@ pub mod module {
 							not_before: *not_before,
 						}),
 					);
+					<Pallet<T>>::inc_erc20_references(*trading_pair);
 				},
 			);
 
@@ -249,6 +675,7 @@ pub mod module {
 					"the trading pair is invalid!",
 				);
 				TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::<_, _>::Enabled);
+				<Pallet<T>>::inc_erc20_references(*trading_pair);
 			});
 
 			self.initial_added_liquidity_pools
@@ -277,6 +704,8 @@ pub mod module {
 							assert!(result.is_ok(), "genesis add lidquidity pool failed.");
 						});
 				});
+
+			UpgradedToErc20References::<T>::put(true);
 		}
 	}
 
@@ -284,7 +713,37 @@ pub mod module {
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Record the Erc20 references of the trading pairs listed before the
+		/// references were tracked.
+		fn on_runtime_upgrade() -> Weight {
+			if UpgradedToErc20References::<T>::get() {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut count: Weight = 0;
+			for (trading_pair, status) in TradingPairStatuses::<T>::iter() {
+				if !matches!(status, TradingPairStatus::<_, _>::NotEnabled) {
+					Self::inc_erc20_references(trading_pair);
+				}
+				count += 1;
+			}
+			UpgradedToErc20References::<T>::put(true);
+
+			T::DbWeight::get().reads_writes(count.saturating_mul(3) + 1, count.saturating_mul(2) + 1)
+		}
+
+		/// List the trading pairs whose listing proposals have passed the
+		/// challenge period.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut count: u32 = 0;
+			for (trading_pair, _) in ListingActivations::<T>::drain_prefix(now) {
+				Self::activate_listing(trading_pair);
+				count = count.saturating_add(1);
+			}
+			<T as Config>::WeightInfo::on_initialize(count)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -293,17 +752,39 @@ pub mod module {
 		/// - `path`: trading path.
 		/// - `supply_amount`: exact supply amount.
 		/// - `min_target_amount`: acceptable minimum target amount.
-		#[pallet::weight(<T as Config>::WeightInfo::swap_with_exact_supply(path.len().try_into().unwrap()))]
+		/// - `max_price_impact`: acceptable max ratio the spot price of each
+		///   trading pair in path moves against this swap. The default of
+		///   trading pair applies as well, to the move from the first swap of
+		///   the block.
+		/// - `referrer`: the account credited with a share of the swap fee.
+		#[pallet::weight(<T as Config>::WeightInfo::swap_with_exact_supply(
+			path.len().try_into().unwrap(),
+			Pallet::<T>::max_walked_buckets(path.len()),
+		))]
 		#[transactional]
 		pub fn swap_with_exact_supply(
 			origin: OriginFor<T>,
 			path: Vec<CurrencyId>,
 			#[pallet::compact] supply_amount: Balance,
 			#[pallet::compact] min_target_amount: Balance,
+			max_price_impact: Option<Ratio>,
+			referrer: Option<T::AccountId>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			let _ = Self::do_swap_with_exact_supply(&who, &path, supply_amount, min_target_amount, None)?;
-			Ok(().into())
+			let (_, walked_buckets) = Self::do_swap_with_exact_supply(
+				&who,
+				&path,
+				supply_amount,
+				min_target_amount,
+				None,
+				max_price_impact,
+				referrer.as_ref(),
+			)?;
+			Ok(Some(<T as Config>::WeightInfo::swap_with_exact_supply(
+				path.len().try_into().unwrap(),
+				walked_buckets,
+			))
+			.into())
 		}
 
 		/// Trading with DEX, swap with exact target amount
@@ -311,16 +792,61 @@ pub mod module {
 		/// - `path`: trading path.
 		/// - `target_amount`: exact target amount.
 		/// - `max_supply_amount`: acceptable maxmum supply amount.
-		#[pallet::weight(<T as Config>::WeightInfo::swap_with_exact_target(path.len().try_into().unwrap()))]
+		/// - `max_price_impact`: acceptable max ratio the spot price of each
+		///   trading pair in path moves against this swap. The default of
+		///   trading pair applies as well, to the move from the first swap of
+		///   the block.
+		/// - `referrer`: the account credited with a share of the swap fee.
+		#[pallet::weight(<T as Config>::WeightInfo::swap_with_exact_target(
+			path.len().try_into().unwrap(),
+			Pallet::<T>::max_walked_buckets(path.len()),
+		))]
 		#[transactional]
 		pub fn swap_with_exact_target(
 			origin: OriginFor<T>,
 			path: Vec<CurrencyId>,
 			#[pallet::compact] target_amount: Balance,
 			#[pallet::compact] max_supply_amount: Balance,
+			max_price_impact: Option<Ratio>,
+			referrer: Option<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (_, walked_buckets) = Self::do_swap_with_exact_target(
+				&who,
+				&path,
+				target_amount,
+				max_supply_amount,
+				None,
+				max_price_impact,
+				referrer.as_ref(),
+			)?;
+			Ok(Some(<T as Config>::WeightInfo::swap_with_exact_target(
+				path.len().try_into().unwrap(),
+				walked_buckets,
+			))
+			.into())
+		}
+
+		/// Trading with DEX, swap a basket of supply currencies for exact
+		/// target amount. The inputs are drained in order, each of them swaps
+		/// directly to the target currency, until the target amount is met.
+		/// Dust inputs and inputs that can't be swapped to the target
+		/// currency are skipped.
+		///
+		/// - `inputs`: supply currencies and their acceptable maximum supply
+		///   amounts, can not exceed `MaxBasketInputs`.
+		/// - `target_currency_id`: target currency id.
+		/// - `target_amount`: exact target amount.
+		#[pallet::weight(<T as Config>::WeightInfo::swap_basket_for_exact_target(inputs.len().try_into().unwrap()))]
+		#[transactional]
+		pub fn swap_basket_for_exact_target(
+			origin: OriginFor<T>,
+			inputs: Vec<(CurrencyId, Balance)>,
+			target_currency_id: CurrencyId,
+			#[pallet::compact] target_amount: Balance,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			Self::do_swap_with_exact_target(&who, &path, target_amount, max_supply_amount, None)?;
+			Self::do_swap_basket_for_exact_target(&who, &inputs, target_currency_id, target_amount)?;
 			Ok(().into())
 		}
 
@@ -419,12 +945,55 @@ pub mod module {
 			not_before: T::BlockNumber,
 		) -> DispatchResultWithPostInfo {
 			T::ListingOrigin::ensure_origin(origin)?;
+			Self::set_erc20_mappings(currency_id_a, currency_id_b)?;
+			Self::do_list_trading_pair(
+				currency_id_a,
+				currency_id_b,
+				min_contribution_a,
+				min_contribution_b,
+				target_provision_a,
+				target_provision_b,
+				not_before,
+			)?;
+			Ok(().into())
+		}
+
+		/// Propose to list a new trading pair by reserving `ListingBond`
+		/// from caller, the trading pair will be listed with the provision
+		/// parameters after `ListingChallengePeriod` blocks unless vetoed by
+		/// `ListingVetoOrigin`, and the bond is returned then.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `min_contribution_a`: minimum contribution of currency A.
+		/// - `min_contribution_b`: minimum contribution of currency B.
+		/// - `target_provision_a`: target provision of currency A.
+		/// - `target_provision_b`: target provision of currency B.
+		/// - `not_before`: the block number that the trading pair could be
+		///   converted to Enabled.
+		#[pallet::weight(<T as Config>::WeightInfo::propose_listing())]
+		#[transactional]
+		pub fn propose_listing(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			min_contribution_a: Balance,
+			min_contribution_b: Balance,
+			target_provision_a: Balance,
+			target_provision_b: Balance,
+			not_before: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
 
 			let trading_pair = TradingPair::from_token_currency_ids(currency_id_a, currency_id_b)
 				.ok_or(Error::<T>::InvalidCurrencyId)?;
 			let dex_share_currency_id = trading_pair
 				.get_dex_share_currency_id()
 				.ok_or(Error::<T>::InvalidCurrencyId)?;
+			ensure!(
+				!ListingProposals::<T>::contains_key(trading_pair),
+				Error::<T>::ListingProposalExisted
+			);
 			ensure!(
 				matches!(
 					Self::trading_pair_statuses(trading_pair),
@@ -433,16 +1002,16 @@ pub mod module {
 				Error::<T>::MustBeNotEnabled
 			);
 			ensure!(
-				T::Currency::total_issuance(dex_share_currency_id).is_zero(),
+				Self::is_liquidity_pool_empty(trading_pair, dex_share_currency_id),
 				Error::<T>::NotAllowedList
 			);
 
-			if let CurrencyId::Erc20(address) = currency_id_a {
-				T::CurrencyIdMapping::set_erc20_mapping(address)?;
-			}
-			if let CurrencyId::Erc20(address) = currency_id_b {
-				T::CurrencyIdMapping::set_erc20_mapping(address)?;
-			}
+			// map the ERC20 currencies now, the activation in `on_initialize` can't
+			// afford the EVM calls
+			Self::set_erc20_mappings(currency_id_a, currency_id_b)?;
+
+			let bond = T::ListingBond::get();
+			T::Currency::reserve(T::GetNativeCurrencyId::get(), &who, bond)?;
 
 			let (min_contribution, target_provision) = if currency_id_a == trading_pair.0 {
 				(
@@ -455,17 +1024,55 @@ pub mod module {
 					(target_provision_b, target_provision_a),
 				)
 			};
+			let activate_at =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::ListingChallengePeriod::get());
 
-			TradingPairStatuses::<T>::insert(
+			ListingProposals::<T>::insert(
 				trading_pair,
-				TradingPairStatus::Provisioning(TradingPairProvisionParameters {
+				ListingProposal {
+					proposer: who.clone(),
+					bond,
 					min_contribution,
 					target_provision,
-					accumulated_provision: Default::default(),
 					not_before,
-				}),
+					activate_at,
+				},
 			);
-			Self::deposit_event(Event::ListTradingPair(trading_pair));
+			ListingActivations::<T>::insert(activate_at, trading_pair, ());
+			Self::deposit_event(Event::ProposeListing(who, trading_pair, bond, activate_at));
+			Ok(().into())
+		}
+
+		/// Veto the listing proposal of a trading pair during its challenge
+		/// period, the bond of proposer is slashed to `TreasuryAccount`.
+		///
+		/// The dispatch origin of this call must be `ListingVetoOrigin`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		#[pallet::weight((<T as Config>::WeightInfo::veto_listing(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn veto_listing(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) -> DispatchResultWithPostInfo {
+			T::ListingVetoOrigin::ensure_origin(origin)?;
+
+			let trading_pair = TradingPair::from_token_currency_ids(currency_id_a, currency_id_b)
+				.ok_or(Error::<T>::InvalidCurrencyId)?;
+			let proposal = ListingProposals::<T>::take(trading_pair).ok_or(Error::<T>::ListingProposalNotFound)?;
+			ListingActivations::<T>::remove(proposal.activate_at, trading_pair);
+
+			let unslashed = T::Currency::repatriate_reserved(
+				T::GetNativeCurrencyId::get(),
+				&proposal.proposer,
+				&T::TreasuryAccount::get(),
+				proposal.bond,
+				BalanceStatus::Free,
+			)?;
+			let slashed = proposal.bond.saturating_sub(unslashed);
+			Self::deposit_event(Event::VetoListing(trading_pair, proposal.proposer, slashed));
 			Ok(().into())
 		}
 
@@ -490,88 +1097,536 @@ pub mod module {
 				Error::<T>::MustBeNotEnabled
 			);
 
+			// the invariant can only be changed when the liquidity pool is empty
+			let dex_share_currency_id = trading_pair
+				.get_dex_share_currency_id()
+				.ok_or(Error::<T>::InvalidCurrencyId)?;
+			if Self::is_liquidity_pool_empty(trading_pair, dex_share_currency_id) {
+				PoolTypes::<T>::remove(trading_pair);
+			}
+
+			Self::inc_erc20_references(trading_pair);
 			TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::Enabled);
 			Self::deposit_event(Event::EnableTradingPair(trading_pair));
 			Ok(().into())
 		}
 
-		#[pallet::weight((<T as Config>::WeightInfo::disable_trading_pair(), DispatchClass::Operational))]
+		/// Enable a new trading pair of like-valued assets with the stable
+		/// swap invariant(without the provision process), or re-enable a
+		/// disabled stable swap trading pair with a new amplification. The
+		/// amplification of a non-empty pool is ramped linearly to the new one
+		/// over `AmplificationRampPeriod`, and can not be changed by more than
+		/// `MAX_AMPLIFICATION_CHANGE` times.
+		///
+		/// The dispatch origin of this call must be `ListingOrigin`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `amplification`: the amplification coefficient of the invariant,
+		///   the higher it is, the lower the slippage near the peg.
+		#[pallet::weight((<T as Config>::WeightInfo::enable_stable_swap_trading_pair(), DispatchClass::Operational))]
 		#[transactional]
-		pub fn disable_trading_pair(
+		pub fn enable_stable_swap_trading_pair(
 			origin: OriginFor<T>,
 			currency_id_a: CurrencyId,
 			currency_id_b: CurrencyId,
+			amplification: u32,
 		) -> DispatchResultWithPostInfo {
 			T::ListingOrigin::ensure_origin(origin)?;
+			ensure!(
+				amplification > 0 && amplification <= stable_swap::MAX_AMPLIFICATION,
+				Error::<T>::InvalidAmplification
+			);
+
 			let trading_pair = TradingPair::from_token_currency_ids(currency_id_a, currency_id_b)
 				.ok_or(Error::<T>::InvalidCurrencyId)?;
+			let dex_share_currency_id = trading_pair
+				.get_dex_share_currency_id()
+				.ok_or(Error::<T>::InvalidCurrencyId)?;
+			ensure!(
+				matches!(
+					Self::trading_pair_statuses(trading_pair),
+					TradingPairStatus::<_, _>::NotEnabled
+				),
+				Error::<T>::MustBeNotEnabled
+			);
 
-			match Self::trading_pair_statuses(trading_pair) {
-				// will disable Enabled trading_pair
-				TradingPairStatus::<_, _>::Enabled => {
-					TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::NotEnabled);
-					Self::deposit_event(Event::DisableTradingPair(trading_pair));
+			let now = frame_system::Pallet::<T>::block_number();
+			let stable_swap_parameters = if Self::is_liquidity_pool_empty(trading_pair, dex_share_currency_id) {
+				StableSwapParameters {
+					initial_amplification: amplification,
+					future_amplification: amplification,
+					ramp_start: now,
+					ramp_end: now,
+					precision_multipliers: Self::precision_multipliers(trading_pair)
+						.ok_or(Error::<T>::UnknownDecimals)?,
 				}
-				// will disable Provisioning trading_pair
-				TradingPairStatus::<_, _>::Provisioning(_) => {
-					let module_account_id = Self::account_id();
-
-					// refund provision
-					for (who, contribution) in ProvisioningPool::<T>::drain_prefix(trading_pair) {
-						T::Currency::transfer(trading_pair.0, &module_account_id, &who, contribution.0)?;
-						T::Currency::transfer(trading_pair.1, &module_account_id, &who, contribution.1)?;
-
-						// decrease ref count
-						frame_system::Pallet::<T>::dec_consumers(&who);
-					}
+			} else {
+				// the invariant can only be changed when the liquidity pool is empty
+				let parameters = match Self::pool_types(trading_pair) {
+					PoolType::StableSwap(parameters) => parameters,
+					_ => return Err(Error::<T>::LiquidityPoolNotEmpty.into()),
+				};
 
-					TradingPairStatuses::<T>::remove(trading_pair);
-					Self::deposit_event(Event::DisableTradingPair(trading_pair));
-				}
-				TradingPairStatus::<_, _>::NotEnabled => {
-					return Err(Error::<T>::NotEnabledTradingPair.into());
+				// ramp from the current amplification to avoid the jump of the price
+				let current_amplification = parameters.amplification(now);
+				ensure!(
+					amplification <= current_amplification.saturating_mul(stable_swap::MAX_AMPLIFICATION_CHANGE)
+						&& current_amplification <= amplification.saturating_mul(stable_swap::MAX_AMPLIFICATION_CHANGE),
+					Error::<T>::InvalidAmplification
+				);
+				StableSwapParameters {
+					initial_amplification: current_amplification,
+					future_amplification: amplification,
+					ramp_start: now,
+					ramp_end: now.saturating_add(T::AmplificationRampPeriod::get()),
+					precision_multipliers: parameters.precision_multipliers,
 				}
 			};
+
+			PoolTypes::<T>::insert(trading_pair, PoolType::StableSwap(stable_swap_parameters));
+			Self::inc_erc20_references(trading_pair);
+			TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::Enabled);
+			Self::deposit_event(Event::EnableStableSwapTradingPair(trading_pair, amplification));
 			Ok(().into())
 		}
-	}
-}
 
-impl<T: Config> Pallet<T> {
-	fn account_id() -> T::AccountId {
-		T::PalletId::get().into_account()
-	}
+		/// Enable a new trading pair with concentrated liquidity in the
+		/// bucketed price ranges(without the provision process), or re-enable
+		/// a disabled empty trading pair with new parameters.
+		///
+		/// The dispatch origin of this call must be `ListingOrigin`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `min_price`: the price of the lowest bucket, which is the price
+		///   of `TradingPair.0` in `TradingPair.1`.
+		/// - `price_step`: the price difference between adjacent buckets.
+		/// - `bucket_count`: the number of buckets, can not exceed
+		///   `MaxRangeBuckets`.
+		/// - `initial_bucket`: the bucket of the initial price.
+		#[pallet::weight((<T as Config>::WeightInfo::enable_range_trading_pair(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn enable_range_trading_pair(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			min_price: Price,
+			price_step: Price,
+			bucket_count: u32,
+			initial_bucket: u32,
+		) -> DispatchResultWithPostInfo {
+			T::ListingOrigin::ensure_origin(origin)?;
+			ensure!(
+				!min_price.is_zero()
+					&& !price_step.is_zero()
+					&& !bucket_count.is_zero()
+					&& bucket_count <= T::MaxRangeBuckets::get()
+					&& initial_bucket < bucket_count,
+				Error::<T>::InvalidRangeParameters
+			);
 
-	/// Access status of specific trading_pair,
-	/// if status is Provisioning and able to be `Enabled`, update it and
-	/// return `Enabled`
-	fn convert_to_enabled_if_possible(trading_pair: TradingPair) {
-		if let TradingPairStatus::<_, _>::Provisioning(provision_parameters) = Self::trading_pair_statuses(trading_pair)
-		{
-			// check if able to be converted to Enable status
-			if frame_system::Pallet::<T>::block_number() >= provision_parameters.not_before
-				&& !provision_parameters.accumulated_provision.0.is_zero()
-				&& !provision_parameters.accumulated_provision.1.is_zero()
-				&& (provision_parameters.accumulated_provision.0 >= provision_parameters.target_provision.0
-					|| provision_parameters.accumulated_provision.1 >= provision_parameters.target_provision.1)
-			{
-				let lp_share_currency_id = trading_pair.get_dex_share_currency_id().expect("shouldn't be invalid!");
-				let mut total_shares_issued: Balance = Default::default();
-				for (who, contribution) in ProvisioningPool::<T>::drain_prefix(trading_pair) {
-					let share_amount = if provision_parameters.accumulated_provision.0
-						> provision_parameters.accumulated_provision.1
-					{
-						let initial_price_1_in_0: Price = Price::checked_from_rational(
-							provision_parameters.accumulated_provision.0,
-							provision_parameters.accumulated_provision.1,
-						)
-						.unwrap_or_default();
-						initial_price_1_in_0
-							.saturating_mul_int(contribution.1)
-							.saturating_add(contribution.0)
-					} else {
-						let initial_price_0_in_1: Price = Price::checked_from_rational(
-							provision_parameters.accumulated_provision.1,
+			let trading_pair = TradingPair::from_token_currency_ids(currency_id_a, currency_id_b)
+				.ok_or(Error::<T>::InvalidCurrencyId)?;
+			let dex_share_currency_id = trading_pair
+				.get_dex_share_currency_id()
+				.ok_or(Error::<T>::InvalidCurrencyId)?;
+			ensure!(
+				matches!(
+					Self::trading_pair_statuses(trading_pair),
+					TradingPairStatus::<_, _>::NotEnabled
+				),
+				Error::<T>::MustBeNotEnabled
+			);
+			// the buckets can only be changed when the liquidity pool is empty
+			ensure!(
+				Self::is_liquidity_pool_empty(trading_pair, dex_share_currency_id),
+				Error::<T>::LiquidityPoolNotEmpty
+			);
+
+			let range_parameters = RangeParameters {
+				min_price,
+				price_step,
+				bucket_count,
+			};
+			PoolTypes::<T>::insert(trading_pair, PoolType::ConcentratedLiquidity(range_parameters));
+			CurrentBuckets::<T>::insert(trading_pair, initial_bucket);
+			Self::inc_erc20_references(trading_pair);
+			TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::Enabled);
+			Self::deposit_event(Event::EnableRangeTradingPair(trading_pair, range_parameters));
+			Ok(().into())
+		}
+
+		/// Add liquidity to the range of buckets of concentrated liquidity
+		/// trading pair, a new non-fungible position is created for caller.
+		/// The bucket is added liquidity in proportion to its reserves, the
+		/// share of an empty bucket is worth 1 unit of `TradingPair.1`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `lower_bucket`: the lowest bucket of the range.
+		/// - `upper_bucket`: the highest bucket of the range.
+		/// - `shares`: the shares to add in every bucket of the range.
+		/// - `max_amount_a`: maximum currency A amount allowed to inject.
+		/// - `max_amount_b`: maximum currency B amount allowed to inject.
+		#[pallet::weight(<T as Config>::WeightInfo::add_range_liquidity())]
+		#[transactional]
+		pub fn add_range_liquidity(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			lower_bucket: u32,
+			upper_bucket: u32,
+			#[pallet::compact] shares: Balance,
+			#[pallet::compact] max_amount_a: Balance,
+			#[pallet::compact] max_amount_b: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_add_range_liquidity(
+				&who,
+				currency_id_a,
+				currency_id_b,
+				(lower_bucket, upper_bucket),
+				shares,
+				max_amount_a,
+				max_amount_b,
+			)?;
+			Ok(().into())
+		}
+
+		/// Remove all liquidity of the range position, the NFT token of the
+		/// position is burned.
+		///
+		/// - `position_id`: the range position owned by caller.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_range_liquidity())]
+		#[transactional]
+		pub fn remove_range_liquidity(origin: OriginFor<T>, position_id: PositionId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_remove_range_liquidity(&who, position_id)?;
+			Ok(().into())
+		}
+
+		#[pallet::weight((<T as Config>::WeightInfo::disable_trading_pair(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn disable_trading_pair(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) -> DispatchResultWithPostInfo {
+			T::ListingOrigin::ensure_origin(origin)?;
+			let trading_pair = TradingPair::from_token_currency_ids(currency_id_a, currency_id_b)
+				.ok_or(Error::<T>::InvalidCurrencyId)?;
+
+			match Self::trading_pair_statuses(trading_pair) {
+				// will disable Enabled trading_pair
+				TradingPairStatus::<_, _>::Enabled => {
+					Self::dec_erc20_references(trading_pair);
+					TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::NotEnabled);
+					// prune the volume history, the cumulative volume is kept
+					VolumeBuckets::<T>::remove_prefix(trading_pair);
+					Self::deposit_event(Event::DisableTradingPair(trading_pair));
+				}
+				// will disable Provisioning trading_pair
+				TradingPairStatus::<_, _>::Provisioning(_) => {
+					let module_account_id = Self::account_id();
+
+					// refund provision
+					for (who, contribution) in ProvisioningPool::<T>::drain_prefix(trading_pair) {
+						T::Currency::transfer(trading_pair.0, &module_account_id, &who, contribution.0)?;
+						T::Currency::transfer(trading_pair.1, &module_account_id, &who, contribution.1)?;
+
+						// decrease ref count
+						frame_system::Pallet::<T>::dec_consumers(&who);
+					}
+
+					Self::dec_erc20_references(trading_pair);
+					TradingPairStatuses::<T>::remove(trading_pair);
+					Self::deposit_event(Event::DisableTradingPair(trading_pair));
+				}
+				TradingPairStatus::<_, _>::NotEnabled => {
+					return Err(Error::<T>::NotEnabledTradingPair.into());
+				}
+			};
+			Ok(().into())
+		}
+
+		/// Update the trading fee rate.
+		///
+		/// The dispatch origin of this call must be `ListingOrigin`.
+		///
+		/// - `fee_numerator`: the numerator of the fee rate.
+		/// - `fee_denominator`: the denominator of the fee rate, must be
+		///   greater than `fee_numerator`.
+		#[pallet::weight((<T as Config>::WeightInfo::set_exchange_fee(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_exchange_fee(
+			origin: OriginFor<T>,
+			fee_numerator: u32,
+			fee_denominator: u32,
+		) -> DispatchResultWithPostInfo {
+			T::ListingOrigin::ensure_origin(origin)?;
+			ensure!(fee_numerator < fee_denominator, Error::<T>::InvalidExchangeFee);
+
+			ExchangeFee::<T>::put((fee_numerator, fee_denominator));
+			Self::deposit_event(Event::ExchangeFeeUpdated(fee_numerator, fee_denominator));
+			Ok(().into())
+		}
+
+		/// Claim back the provision of caller from a Provisioning trading
+		/// pair which failed to reach its target provision within
+		/// `ProvisioningPeriod` blocks after `not_before`. The trading pair
+		/// becomes NotEnabled once all provisions are refunded.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_refund())]
+		#[transactional]
+		pub fn claim_refund(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_claim_refund(&who, currency_id_a, currency_id_b)?;
+			Ok(().into())
+		}
+
+		/// Update the fraction of the swap fee credited to referrers.
+		///
+		/// The dispatch origin of this call must be `ListingOrigin`.
+		///
+		/// - `referrer_fee_share`: the fraction of the swap fee.
+		#[pallet::weight((<T as Config>::WeightInfo::set_referrer_fee_share(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_referrer_fee_share(origin: OriginFor<T>, referrer_fee_share: Permill) -> DispatchResultWithPostInfo {
+			T::ListingOrigin::ensure_origin(origin)?;
+			ReferrerFeeShare::<T>::put(referrer_fee_share);
+			Self::deposit_event(Event::ReferrerFeeShareUpdated(referrer_fee_share));
+			Ok(().into())
+		}
+
+		/// Claim the accumulated referrer rewards of caller in specific
+		/// currency.
+		///
+		/// - `currency_id`: currency id of the rewards.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_referrer_rewards())]
+		#[transactional]
+		pub fn claim_referrer_rewards(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let amount = ReferrerRewards::<T>::take(&who, currency_id);
+			ensure!(!amount.is_zero(), Error::<T>::NoReferrerRewards);
+
+			T::Currency::transfer(currency_id, &Self::account_id(), &who, amount)?;
+			Self::deposit_event(Event::ClaimReferrerRewards(who, currency_id, amount));
+			Ok(().into())
+		}
+
+		/// Update the default max ratio the spot price of trading pair can
+		/// move against the swaps in one block, `None` removes the default.
+		///
+		/// The dispatch origin of this call must be `ListingOrigin`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `max_price_impact`: the max ratio the spot price can move.
+		#[pallet::weight((<T as Config>::WeightInfo::set_max_price_impact(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_max_price_impact(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			max_price_impact: Option<Ratio>,
+		) -> DispatchResultWithPostInfo {
+			T::ListingOrigin::ensure_origin(origin)?;
+			let trading_pair = TradingPair::from_token_currency_ids(currency_id_a, currency_id_b)
+				.ok_or(Error::<T>::InvalidCurrencyId)?;
+
+			MaxPriceImpacts::<T>::set(trading_pair, max_price_impact);
+			if max_price_impact.is_none() {
+				BlockStartPrices::<T>::remove(trading_pair);
+			}
+			Self::deposit_event(Event::MaxPriceImpactUpdated(trading_pair, max_price_impact));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// Map the ERC20 currencies of a trading pair to their currency ids,
+	/// which queries the ERC20 info through EVM calls.
+	#[transactional]
+	fn set_erc20_mappings(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> DispatchResult {
+		if let CurrencyId::Erc20(address) = currency_id_a {
+			T::CurrencyIdMapping::set_erc20_mapping(address)?;
+		}
+		if let CurrencyId::Erc20(address) = currency_id_b {
+			T::CurrencyIdMapping::set_erc20_mapping(address)?;
+		}
+		Ok(())
+	}
+
+	/// List the trading pair, the ERC20 currencies must have been mapped by
+	/// `set_erc20_mappings`.
+	#[transactional]
+	fn do_list_trading_pair(
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		min_contribution_a: Balance,
+		min_contribution_b: Balance,
+		target_provision_a: Balance,
+		target_provision_b: Balance,
+		not_before: T::BlockNumber,
+	) -> DispatchResult {
+		let trading_pair =
+			TradingPair::from_token_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+		let dex_share_currency_id = trading_pair
+			.get_dex_share_currency_id()
+			.ok_or(Error::<T>::InvalidCurrencyId)?;
+		ensure!(
+			matches!(
+				Self::trading_pair_statuses(trading_pair),
+				TradingPairStatus::<_, _>::NotEnabled
+			),
+			Error::<T>::MustBeNotEnabled
+		);
+		ensure!(
+			Self::is_liquidity_pool_empty(trading_pair, dex_share_currency_id),
+			Error::<T>::NotAllowedList
+		);
+
+		let (min_contribution, target_provision) = if currency_id_a == trading_pair.0 {
+			(
+				(min_contribution_a, min_contribution_b),
+				(target_provision_a, target_provision_b),
+			)
+		} else {
+			(
+				(min_contribution_b, min_contribution_a),
+				(target_provision_b, target_provision_a),
+			)
+		};
+
+		Self::inc_erc20_references(trading_pair);
+		TradingPairStatuses::<T>::insert(
+			trading_pair,
+			TradingPairStatus::Provisioning(TradingPairProvisionParameters {
+				min_contribution,
+				target_provision,
+				accumulated_provision: Default::default(),
+				not_before,
+			}),
+		);
+		PoolTypes::<T>::remove(trading_pair);
+		Self::deposit_event(Event::ListTradingPair(trading_pair));
+		Ok(())
+	}
+
+	fn activate_listing(trading_pair: TradingPair) {
+		if let Some(proposal) = ListingProposals::<T>::take(trading_pair) {
+			T::Currency::unreserve(T::GetNativeCurrencyId::get(), &proposal.proposer, proposal.bond);
+
+			match Self::do_list_trading_pair(
+				trading_pair.0,
+				trading_pair.1,
+				proposal.min_contribution.0,
+				proposal.min_contribution.1,
+				proposal.target_provision.0,
+				proposal.target_provision.1,
+				proposal.not_before,
+			) {
+				Ok(_) => Self::deposit_event(Event::ListingProposalActivated(trading_pair)),
+				Err(e) => {
+					log::warn!(
+						target: "dex",
+						"activate listing proposal of {:?} failed: {:?}",
+						trading_pair,
+						e
+					);
+				}
+			}
+		}
+	}
+
+	fn inc_erc20_references(trading_pair: TradingPair) {
+		T::Erc20ReferenceCounter::inc_reference(T::PalletId::get(), trading_pair.0);
+		T::Erc20ReferenceCounter::inc_reference(T::PalletId::get(), trading_pair.1);
+	}
+
+	fn dec_erc20_references(trading_pair: TradingPair) {
+		T::Erc20ReferenceCounter::dec_reference(T::PalletId::get(), trading_pair.0);
+		T::Erc20ReferenceCounter::dec_reference(T::PalletId::get(), trading_pair.1);
+	}
+
+	/// The maximum number of range buckets a swap along a path of
+	/// `path_length` could walk, each trading pair walks up to
+	/// `MaxRangeBuckets` buckets.
+	pub fn max_walked_buckets(path_length: usize) -> u32 {
+		T::MaxRangeBuckets::get().saturating_mul(path_length.saturating_sub(1).saturated_into())
+	}
+
+	/// Whether the liquidity pool of trading pair is empty, the invariant
+	/// can only be changed when it's empty.
+	fn is_liquidity_pool_empty(trading_pair: TradingPair, dex_share_currency_id: CurrencyId) -> bool {
+		let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+		pool_0.is_zero() && pool_1.is_zero() && T::Currency::total_issuance(dex_share_currency_id).is_zero()
+	}
+
+	/// Get the trading fee rate, fallback to the default if not updated.
+	pub fn get_exchange_fee() -> (u32, u32) {
+		Self::exchange_fee().unwrap_or_else(T::GetExchangeFee::get)
+	}
+
+	/// Check if the accumulated provision reached the target provision
+	fn is_target_provision_reached(
+		provision_parameters: &TradingPairProvisionParameters<Balance, T::BlockNumber>,
+	) -> bool {
+		!provision_parameters.accumulated_provision.0.is_zero()
+			&& !provision_parameters.accumulated_provision.1.is_zero()
+			&& (provision_parameters.accumulated_provision.0 >= provision_parameters.target_provision.0
+				|| provision_parameters.accumulated_provision.1 >= provision_parameters.target_provision.1)
+	}
+
+	/// Check if the Provisioning trading pair failed to reach the target
+	/// provision within `ProvisioningPeriod` blocks after `not_before`
+	fn is_provisioning_failed(provision_parameters: &TradingPairProvisionParameters<Balance, T::BlockNumber>) -> bool {
+		frame_system::Pallet::<T>::block_number()
+			> provision_parameters
+				.not_before
+				.saturating_add(T::ProvisioningPeriod::get())
+			&& !Self::is_target_provision_reached(provision_parameters)
+	}
+
+	/// Access status of specific trading_pair,
+	/// if status is Provisioning and able to be `Enabled`, update it and
+	/// return `Enabled`
+	fn convert_to_enabled_if_possible(trading_pair: TradingPair) {
+		if let TradingPairStatus::<_, _>::Provisioning(provision_parameters) = Self::trading_pair_statuses(trading_pair)
+		{
+			// check if able to be converted to Enable status
+			if frame_system::Pallet::<T>::block_number() >= provision_parameters.not_before
+				&& Self::is_target_provision_reached(&provision_parameters)
+			{
+				let lp_share_currency_id = trading_pair.get_dex_share_currency_id().expect("shouldn't be invalid!");
+				let mut total_shares_issued: Balance = Default::default();
+				for (who, contribution) in ProvisioningPool::<T>::drain_prefix(trading_pair) {
+					let share_amount = if provision_parameters.accumulated_provision.0
+						> provision_parameters.accumulated_provision.1
+					{
+						let initial_price_1_in_0: Price = Price::checked_from_rational(
+							provision_parameters.accumulated_provision.0,
+							provision_parameters.accumulated_provision.1,
+						)
+						.unwrap_or_default();
+						initial_price_1_in_0
+							.saturating_mul_int(contribution.1)
+							.saturating_add(contribution.0)
+					} else {
+						let initial_price_0_in_1: Price = Price::checked_from_rational(
+							provision_parameters.accumulated_provision.1,
 							provision_parameters.accumulated_provision.0,
 						)
 						.unwrap_or_default();
@@ -632,6 +1687,10 @@ impl<T: Config> Pallet<T> {
 			TradingPairStatus::<_, _>::Provisioning(provision_parameters) => provision_parameters,
 			_ => return Err(Error::<T>::MustBeProvisioning.into()),
 		};
+		ensure!(
+			!Self::is_provisioning_failed(&provision_parameters),
+			Error::<T>::ProvisioningFailed
+		);
 		let (contribution_0, contribution_1) = if currency_id_a == trading_pair.0 {
 			(contribution_a, contribution_b)
 		} else {
@@ -693,6 +1752,63 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// Refund the provision of `who` from failed Provisioning TradingPair
+	fn do_claim_refund(who: &T::AccountId, currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> DispatchResult {
+		let trading_pair =
+			TradingPair::from_token_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+		let mut provision_parameters = match Self::trading_pair_statuses(trading_pair) {
+			TradingPairStatus::<_, _>::Provisioning(provision_parameters) => provision_parameters,
+			_ => return Err(Error::<T>::MustBeProvisioning.into()),
+		};
+		ensure!(
+			Self::is_provisioning_failed(&provision_parameters),
+			Error::<T>::ProvisioningNotFailed
+		);
+
+		let (contribution_0, contribution_1) = ProvisioningPool::<T>::take(trading_pair, who);
+		ensure!(
+			!contribution_0.is_zero() || !contribution_1.is_zero(),
+			Error::<T>::NoProvisionToRefund
+		);
+
+		let module_account_id = Self::account_id();
+		T::Currency::transfer(trading_pair.0, &module_account_id, who, contribution_0)?;
+		T::Currency::transfer(trading_pair.1, &module_account_id, who, contribution_1)?;
+
+		// decrease ref count
+		frame_system::Pallet::<T>::dec_consumers(who);
+
+		provision_parameters.accumulated_provision.0 = provision_parameters
+			.accumulated_provision
+			.0
+			.saturating_sub(contribution_0);
+		provision_parameters.accumulated_provision.1 = provision_parameters
+			.accumulated_provision
+			.1
+			.saturating_sub(contribution_1);
+
+		if ProvisioningPool::<T>::iter_prefix(trading_pair).next().is_none() {
+			// all provisions are refunded, disable the trading pair
+			Self::dec_erc20_references(trading_pair);
+			TradingPairStatuses::<T>::remove(trading_pair);
+			Self::deposit_event(Event::DisableTradingPair(trading_pair));
+		} else {
+			TradingPairStatuses::<T>::insert(
+				trading_pair,
+				TradingPairStatus::<_, _>::Provisioning(provision_parameters),
+			);
+		}
+
+		Self::deposit_event(Event::RefundProvision(
+			who.clone(),
+			trading_pair.0,
+			contribution_0,
+			trading_pair.1,
+			contribution_1,
+		));
+		Ok(())
+	}
+
 	fn do_add_liquidity(
 		who: &T::AccountId,
 		currency_id_a: CurrencyId,
@@ -712,6 +1828,10 @@ impl<T: Config> Pallet<T> {
 			),
 			Error::<T>::MustBeEnabled,
 		);
+		ensure!(
+			!matches!(Self::pool_types(trading_pair), PoolType::ConcentratedLiquidity(_)),
+			Error::<T>::RangeTradingPairNotAllowed
+		);
 
 		LiquidityPool::<T>::try_mutate(trading_pair, |(pool_0, pool_1)| -> DispatchResult {
 			let total_shares = T::Currency::total_issuance(lp_share_currency_id);
@@ -722,7 +1842,16 @@ impl<T: Config> Pallet<T> {
 			};
 			let (pool_0_increment, pool_1_increment, share_increment): (Balance, Balance, Balance) =
 				if total_shares.is_zero() {
-					let share_amount = if max_amount_0 > max_amount_1 {
+					let share_amount = if let PoolType::StableSwap(parameters) = Self::pool_types(trading_pair) {
+						// the initial shares of stable swap pool is the invariant
+						let amplification = parameters.amplification(frame_system::Pallet::<T>::block_number());
+						stable_swap::get_d(
+							(max_amount_0, max_amount_1),
+							amplification,
+							parameters.precision_multipliers,
+						)
+						.unwrap_or_default()
+					} else if max_amount_0 > max_amount_1 {
 						let initial_price_1_in_0: Price =
 							Price::checked_from_rational(max_amount_0, max_amount_1).unwrap_or_default();
 						initial_price_1_in_0
@@ -834,6 +1963,157 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	fn do_add_range_liquidity(
+		who: &T::AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		(lower_bucket, upper_bucket): (u32, u32),
+		shares: Balance,
+		max_amount_a: Balance,
+		max_amount_b: Balance,
+	) -> DispatchResult {
+		let trading_pair =
+			TradingPair::from_token_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+		ensure!(
+			matches!(
+				Self::trading_pair_statuses(trading_pair),
+				TradingPairStatus::<_, _>::Enabled
+			),
+			Error::<T>::MustBeEnabled,
+		);
+		let range_parameters = match Self::pool_types(trading_pair) {
+			PoolType::ConcentratedLiquidity(range_parameters) => range_parameters,
+			_ => return Err(Error::<T>::NotRangeTradingPair.into()),
+		};
+		ensure!(
+			lower_bucket <= upper_bucket && upper_bucket < range_parameters.bucket_count,
+			Error::<T>::InvalidBucketRange
+		);
+		ensure!(!shares.is_zero(), Error::<T>::InvalidLiquidityIncrement);
+
+		let current_bucket = Self::current_buckets(trading_pair);
+		let (mut pool_0_increment, mut pool_1_increment): (Balance, Balance) = (Zero::zero(), Zero::zero());
+		for bucket_id in lower_bucket..=upper_bucket {
+			RangeBuckets::<T>::try_mutate(trading_pair, bucket_id, |bucket| -> DispatchResult {
+				let (increment_0, increment_1) = if !bucket.total_shares.is_zero() {
+					(
+						math::ratio_of_products(
+							&[shares, bucket.reserve_0],
+							&[bucket.total_shares],
+							math::Rounding::Up,
+						),
+						math::ratio_of_products(
+							&[shares, bucket.reserve_1],
+							&[bucket.total_shares],
+							math::Rounding::Up,
+						),
+					)
+				} else if bucket_id > current_bucket {
+					(
+						math::checked_div_int(shares, range_parameters.bucket_price(bucket_id), math::Rounding::Up),
+						Some(Zero::zero()),
+					)
+				} else {
+					(Some(Zero::zero()), Some(shares))
+				};
+				let increment_0 = increment_0.ok_or(Error::<T>::InvalidLiquidityIncrement)?;
+				let increment_1 = increment_1.ok_or(Error::<T>::InvalidLiquidityIncrement)?;
+
+				bucket.reserve_0 = bucket.reserve_0.saturating_add(increment_0);
+				bucket.reserve_1 = bucket.reserve_1.saturating_add(increment_1);
+				bucket.total_shares = bucket.total_shares.saturating_add(shares);
+				pool_0_increment = pool_0_increment.saturating_add(increment_0);
+				pool_1_increment = pool_1_increment.saturating_add(increment_1);
+				Ok(())
+			})?;
+		}
+
+		let (max_amount_0, max_amount_1) = if currency_id_a == trading_pair.0 {
+			(max_amount_a, max_amount_b)
+		} else {
+			(max_amount_b, max_amount_a)
+		};
+		ensure!(
+			pool_0_increment <= max_amount_0 && pool_1_increment <= max_amount_1,
+			Error::<T>::ExcessiveRangeLiquidityAmount
+		);
+
+		let module_account_id = Self::account_id();
+		T::Currency::transfer(trading_pair.0, who, &module_account_id, pool_0_increment)?;
+		T::Currency::transfer(trading_pair.1, who, &module_account_id, pool_1_increment)?;
+		LiquidityPool::<T>::mutate(trading_pair, |(pool_0, pool_1)| {
+			*pool_0 = pool_0.saturating_add(pool_0_increment);
+			*pool_1 = pool_1.saturating_add(pool_1_increment);
+		});
+
+		let position_id = T::PositionNFT::mint_position(
+			T::PalletId::get(),
+			who,
+			(trading_pair, lower_bucket, upper_bucket).encode(),
+		)?;
+		RangePositions::<T>::insert(
+			position_id,
+			RangePosition {
+				trading_pair,
+				lower_bucket,
+				upper_bucket,
+				shares,
+			},
+		);
+
+		Self::deposit_event(Event::AddRangeLiquidity(
+			who.clone(),
+			position_id,
+			pool_0_increment,
+			pool_1_increment,
+			shares,
+		));
+		Ok(())
+	}
+
+	fn do_remove_range_liquidity(who: &T::AccountId, position_id: PositionId) -> DispatchResult {
+		let position = Self::range_positions(position_id).ok_or(Error::<T>::RangePositionNotFound)?;
+		ensure!(
+			T::PositionNFT::position_owner(T::PalletId::get(), position_id).as_ref() == Some(who),
+			Error::<T>::NotRangePositionOwner
+		);
+		let trading_pair = position.trading_pair;
+
+		let (mut pool_0_decrement, mut pool_1_decrement): (Balance, Balance) = (Zero::zero(), Zero::zero());
+		for bucket_id in position.lower_bucket..=position.upper_bucket {
+			RangeBuckets::<T>::mutate(trading_pair, bucket_id, |bucket| {
+				let proportion = Ratio::checked_from_rational(position.shares, bucket.total_shares).unwrap_or_default();
+				let decrement_0 = proportion.saturating_mul_int(bucket.reserve_0);
+				let decrement_1 = proportion.saturating_mul_int(bucket.reserve_1);
+
+				bucket.reserve_0 = bucket.reserve_0.saturating_sub(decrement_0);
+				bucket.reserve_1 = bucket.reserve_1.saturating_sub(decrement_1);
+				bucket.total_shares = bucket.total_shares.saturating_sub(position.shares);
+				pool_0_decrement = pool_0_decrement.saturating_add(decrement_0);
+				pool_1_decrement = pool_1_decrement.saturating_add(decrement_1);
+			});
+		}
+
+		let module_account_id = Self::account_id();
+		T::Currency::transfer(trading_pair.0, &module_account_id, who, pool_0_decrement)?;
+		T::Currency::transfer(trading_pair.1, &module_account_id, who, pool_1_decrement)?;
+		LiquidityPool::<T>::mutate(trading_pair, |(pool_0, pool_1)| {
+			*pool_0 = pool_0.saturating_sub(pool_0_decrement);
+			*pool_1 = pool_1.saturating_sub(pool_1_decrement);
+		});
+		RangePositions::<T>::remove(position_id);
+		T::PositionNFT::burn_position(T::PalletId::get(), who, position_id)?;
+
+		Self::deposit_event(Event::RemoveRangeLiquidity(
+			who.clone(),
+			position_id,
+			pool_0_decrement,
+			pool_1_decrement,
+			position.shares,
+		));
+		Ok(())
+	}
+
 	fn get_liquidity(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
 		let trading_pair = TradingPair::new(currency_id_a, currency_id_b);
 		let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
@@ -846,22 +2126,29 @@ impl<T: Config> Pallet<T> {
 
 	/// Get how much target amount will be got for specific supply amount
 	/// and price impact
-	fn get_target_amount(supply_pool: Balance, target_pool: Balance, supply_amount: Balance) -> Balance {
+	fn get_target_amount(
+		supply_pool: Balance,
+		target_pool: Balance,
+		supply_amount: Balance,
+	) -> sp_std::result::Result<Balance, DispatchError> {
 		if supply_amount.is_zero() || supply_pool.is_zero() || target_pool.is_zero() {
-			Zero::zero()
+			Ok(Zero::zero())
 		} else {
-			let (fee_numerator, fee_denominator) = T::GetExchangeFee::get();
-			let supply_amount_with_fee =
-				supply_amount.saturating_mul(fee_denominator.saturating_sub(fee_numerator).unique_saturated_into());
-			let numerator: U256 = U256::from(supply_amount_with_fee).saturating_mul(U256::from(target_pool));
-			let denominator: U256 = U256::from(supply_pool)
-				.saturating_mul(U256::from(fee_denominator))
-				.saturating_add(U256::from(supply_amount_with_fee));
-
-			numerator
-				.checked_div(denominator)
-				.and_then(|n| TryInto::<Balance>::try_into(n).ok())
-				.unwrap_or_else(Zero::zero)
+			let (fee_numerator, fee_denominator) = Self::get_exchange_fee();
+			let supply_amount_with_fee = supply_amount
+				.checked_mul(fee_denominator.saturating_sub(fee_numerator).unique_saturated_into())
+				.ok_or(Error::<T>::Overflow)?;
+			let denominator = supply_pool
+				.checked_mul(fee_denominator.unique_saturated_into())
+				.and_then(|n| n.checked_add(supply_amount_with_fee))
+				.ok_or(Error::<T>::Overflow)?;
+
+			math::ratio_of_products(
+				&[supply_amount_with_fee, target_pool],
+				&[denominator],
+				math::Rounding::Down,
+			)
+			.ok_or_else(|| Error::<T>::Overflow.into())
 		}
 	}
 
@@ -870,22 +2157,222 @@ impl<T: Config> Pallet<T> {
 		if target_amount.is_zero() || supply_pool.is_zero() || target_pool.is_zero() {
 			Zero::zero()
 		} else {
-			let (fee_numerator, fee_denominator) = T::GetExchangeFee::get();
-			let numerator: U256 = U256::from(supply_pool)
-				.saturating_mul(U256::from(target_amount))
-				.saturating_mul(U256::from(fee_denominator));
-			let denominator: U256 = U256::from(target_pool)
-				.saturating_sub(U256::from(target_amount))
-				.saturating_mul(U256::from(fee_denominator.saturating_sub(fee_numerator)));
-
-			numerator
-				.checked_div(denominator)
-				.and_then(|r| r.checked_add(U256::one())) // add 1 to result so that correct the possible losses caused by remainder discarding in
-				.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+			let (fee_numerator, fee_denominator) = Self::get_exchange_fee();
+
+			math::ratio_of_products(
+				&[supply_pool, target_amount, fee_denominator.unique_saturated_into()],
+				&[
+					target_pool.saturating_sub(target_amount),
+					fee_denominator.saturating_sub(fee_numerator).unique_saturated_into(),
+				],
+				math::Rounding::Down,
+			)
+			.and_then(|r| r.checked_add(1)) // add 1 to result so that correct the possible losses caused by remainder discarding in
+			.unwrap_or_else(Zero::zero)
+		}
+	}
+
+	/// The precision multipliers normalizing the balances of
+	/// `TradingPair.0` and `TradingPair.1` to the same decimals.
+	fn precision_multipliers(trading_pair: TradingPair) -> Option<(Balance, Balance)> {
+		let decimals =
+			|currency_id: CurrencyId| T::CurrencyIdMapping::decimals(currency_id).or_else(|| currency_id.decimals());
+		let decimals_0 = decimals(trading_pair.0)?;
+		let decimals_1 = decimals(trading_pair.1)?;
+		let max_decimals = decimals_0.max(decimals_1);
+
+		Some((
+			10u128.checked_pow(max_decimals.saturating_sub(decimals_0).into())?,
+			10u128.checked_pow(max_decimals.saturating_sub(decimals_1).into())?,
+		))
+	}
+
+	/// Get how much target amount will be got for specific supply amount
+	/// from the stable swap pool.
+	fn get_stable_swap_target_amount(
+		supply_pool: Balance,
+		target_pool: Balance,
+		supply_amount: Balance,
+		amplification: u32,
+		multipliers: (Balance, Balance),
+	) -> Balance {
+		if supply_amount.is_zero() || supply_pool.is_zero() || target_pool.is_zero() {
+			Zero::zero()
+		} else {
+			let (fee_numerator, fee_denominator) = Self::get_exchange_fee();
+
+			math::ratio_of_products(
+				&[
+					supply_amount,
+					fee_denominator.saturating_sub(fee_numerator).unique_saturated_into(),
+				],
+				&[fee_denominator.unique_saturated_into()],
+				math::Rounding::Down,
+			)
+			.and_then(|supply_amount_with_fee| {
+				stable_swap::get_target_amount(
+					supply_pool,
+					target_pool,
+					supply_amount_with_fee,
+					amplification,
+					multipliers,
+				)
+			})
+			.unwrap_or_else(Zero::zero)
+		}
+	}
+
+	/// Get how much supply amount will be paid for specific target amount
+	/// to the stable swap pool.
+	fn get_stable_swap_supply_amount(
+		supply_pool: Balance,
+		target_pool: Balance,
+		target_amount: Balance,
+		amplification: u32,
+		multipliers: (Balance, Balance),
+	) -> Balance {
+		if target_amount.is_zero() || supply_pool.is_zero() || target_pool.is_zero() {
+			Zero::zero()
+		} else {
+			let (fee_numerator, fee_denominator) = Self::get_exchange_fee();
+
+			stable_swap::get_supply_amount(supply_pool, target_pool, target_amount, amplification, multipliers)
+				.and_then(|net_supply_amount| {
+					math::ratio_of_products(
+						&[net_supply_amount, fee_denominator.unique_saturated_into()],
+						&[fee_denominator.saturating_sub(fee_numerator).unique_saturated_into()],
+						math::Rounding::Up,
+					)
+				})
 				.unwrap_or_else(Zero::zero)
 		}
 	}
 
+	/// Get how much supply amount(fee included) will be paid for specific
+	/// target amount from the bucket at `price`.
+	fn get_bucket_supply_amount(target_amount: Balance, price: Price, is_supply_0: bool) -> Option<Balance> {
+		let (fee_numerator, fee_denominator) = Self::get_exchange_fee();
+		let net_supply_amount = if is_supply_0 {
+			math::checked_div_int(target_amount, price, math::Rounding::Up)
+		} else {
+			math::checked_mul_int(price, target_amount, math::Rounding::Up)
+		}?;
+
+		math::ratio_of_products(
+			&[net_supply_amount, fee_denominator.unique_saturated_into()],
+			&[fee_denominator.saturating_sub(fee_numerator).unique_saturated_into()],
+			math::Rounding::Up,
+		)
+	}
+
+	/// Get how much target amount will be got for specific supply
+	/// amount(fee included) from the bucket at `price`.
+	fn get_bucket_target_amount(supply_amount: Balance, price: Price, is_supply_0: bool) -> Option<Balance> {
+		let (fee_numerator, fee_denominator) = Self::get_exchange_fee();
+		let net_supply_amount = math::ratio_of_products(
+			&[
+				supply_amount,
+				fee_denominator.saturating_sub(fee_numerator).unique_saturated_into(),
+			],
+			&[fee_denominator.unique_saturated_into()],
+			math::Rounding::Down,
+		)?;
+
+		if is_supply_0 {
+			math::checked_mul_int(price, net_supply_amount, math::Rounding::Down)
+		} else {
+			math::checked_div_int(net_supply_amount, price, math::Rounding::Down)
+		}
+	}
+
+	/// Get how much target amount will be got for specific supply amount
+	/// from the buckets of concentrated liquidity, which are traded from the
+	/// current bucket in the moving direction of price.
+	///
+	/// Returns `None` if the liquidity is not enough.
+	fn get_range_target_amount(
+		trading_pair: TradingPair,
+		range_parameters: &RangeParameters,
+		supply_currency_id: CurrencyId,
+		supply_amount: Balance,
+	) -> Option<Balance> {
+		let is_supply_0 = supply_currency_id == trading_pair.0;
+		let mut remaining_supply_amount = supply_amount;
+		let mut target_amount: Balance = Zero::zero();
+		let mut bucket_id = Self::current_buckets(trading_pair);
+
+		loop {
+			let bucket = Self::range_buckets(trading_pair, bucket_id);
+			let price = range_parameters.bucket_price(bucket_id);
+			let available = if is_supply_0 {
+				bucket.reserve_1
+			} else {
+				bucket.reserve_0
+			};
+
+			if !available.is_zero() {
+				match Self::get_bucket_supply_amount(available, price, is_supply_0) {
+					// drain this bucket
+					Some(supply_to_drain) if supply_to_drain < remaining_supply_amount => {
+						target_amount = target_amount.checked_add(available)?;
+						remaining_supply_amount -= supply_to_drain;
+					}
+					_ => {
+						let bucket_target_amount =
+							Self::get_bucket_target_amount(remaining_supply_amount, price, is_supply_0)?;
+						return target_amount.checked_add(bucket_target_amount.min(available));
+					}
+				}
+			}
+
+			bucket_id = range_parameters.next_bucket(bucket_id, is_supply_0)?;
+		}
+	}
+
+	/// Get how much supply amount will be paid for specific target amount
+	/// from the buckets of concentrated liquidity, and the fills of buckets
+	/// `(bucket_id, supply_increment, target_decrement)`.
+	///
+	/// Returns `None` if the liquidity is not enough.
+	fn get_range_supply_amount(
+		trading_pair: TradingPair,
+		range_parameters: &RangeParameters,
+		supply_currency_id: CurrencyId,
+		target_amount: Balance,
+	) -> Option<(Balance, Vec<(u32, Balance, Balance)>)> {
+		let is_supply_0 = supply_currency_id == trading_pair.0;
+		let mut remaining_target_amount = target_amount;
+		let mut supply_amount: Balance = Zero::zero();
+		let mut fills: Vec<(u32, Balance, Balance)> = vec![];
+		let mut bucket_id = Self::current_buckets(trading_pair);
+
+		loop {
+			let bucket = Self::range_buckets(trading_pair, bucket_id);
+			let available = if is_supply_0 {
+				bucket.reserve_1
+			} else {
+				bucket.reserve_0
+			};
+			let target_decrement = available.min(remaining_target_amount);
+
+			if !target_decrement.is_zero() {
+				let supply_increment = Self::get_bucket_supply_amount(
+					target_decrement,
+					range_parameters.bucket_price(bucket_id),
+					is_supply_0,
+				)?;
+				supply_amount = supply_amount.checked_add(supply_increment)?;
+				fills.push((bucket_id, supply_increment, target_decrement));
+				remaining_target_amount -= target_decrement;
+			}
+
+			if remaining_target_amount.is_zero() {
+				return Some((supply_amount, fills));
+			}
+			bucket_id = range_parameters.next_bucket(bucket_id, is_supply_0)?;
+		}
+	}
+
 	fn get_target_amounts(
 		path: &[CurrencyId],
 		supply_amount: Balance,
@@ -901,19 +2388,36 @@ impl<T: Config> Pallet<T> {
 
 		let mut i: usize = 0;
 		while i + 1 < path_length {
+			let trading_pair = TradingPair::new(path[i], path[i + 1]);
 			ensure!(
 				matches!(
-					Self::trading_pair_statuses(TradingPair::new(path[i], path[i + 1])),
+					Self::trading_pair_statuses(trading_pair),
 					TradingPairStatus::<_, _>::Enabled
 				),
 				Error::<T>::MustBeEnabled
 			);
+			let pool_type = Self::pool_types(trading_pair);
 			let (supply_pool, target_pool) = Self::get_liquidity(path[i], path[i + 1]);
+			// the pool of concentrated liquidity may only hold the target currency
 			ensure!(
-				!supply_pool.is_zero() && !target_pool.is_zero(),
+				!target_pool.is_zero()
+					&& (!supply_pool.is_zero() || matches!(pool_type, PoolType::ConcentratedLiquidity(_))),
 				Error::<T>::InsufficientLiquidity
 			);
-			let target_amount = Self::get_target_amount(supply_pool, target_pool, target_amounts[i]);
+			let target_amount = match pool_type {
+				PoolType::ConstantProduct => Self::get_target_amount(supply_pool, target_pool, target_amounts[i])?,
+				PoolType::StableSwap(parameters) => Self::get_stable_swap_target_amount(
+					supply_pool,
+					target_pool,
+					target_amounts[i],
+					parameters.amplification(frame_system::Pallet::<T>::block_number()),
+					parameters.oriented_multipliers(trading_pair, path[i]),
+				),
+				PoolType::ConcentratedLiquidity(range_parameters) => {
+					Self::get_range_target_amount(trading_pair, &range_parameters, path[i], target_amounts[i])
+						.unwrap_or_else(Zero::zero)
+				}
+			};
 			ensure!(!target_amount.is_zero(), Error::<T>::ZeroTargetAmount);
 
 			// check price impact if limit exists
@@ -944,19 +2448,37 @@ impl<T: Config> Pallet<T> {
 
 		let mut i: usize = path_length - 1;
 		while i > 0 {
+			let trading_pair = TradingPair::new(path[i - 1], path[i]);
 			ensure!(
 				matches!(
-					Self::trading_pair_statuses(TradingPair::new(path[i - 1], path[i])),
+					Self::trading_pair_statuses(trading_pair),
 					TradingPairStatus::<_, _>::Enabled
 				),
 				Error::<T>::MustBeEnabled
 			);
+			let pool_type = Self::pool_types(trading_pair);
 			let (supply_pool, target_pool) = Self::get_liquidity(path[i - 1], path[i]);
+			// the pool of concentrated liquidity may only hold the target currency
 			ensure!(
-				!supply_pool.is_zero() && !target_pool.is_zero(),
+				!target_pool.is_zero()
+					&& (!supply_pool.is_zero() || matches!(pool_type, PoolType::ConcentratedLiquidity(_))),
 				Error::<T>::InsufficientLiquidity
 			);
-			let supply_amount = Self::get_supply_amount(supply_pool, target_pool, supply_amounts[i]);
+			let supply_amount = match pool_type {
+				PoolType::ConstantProduct => Self::get_supply_amount(supply_pool, target_pool, supply_amounts[i]),
+				PoolType::StableSwap(parameters) => Self::get_stable_swap_supply_amount(
+					supply_pool,
+					target_pool,
+					supply_amounts[i],
+					parameters.amplification(frame_system::Pallet::<T>::block_number()),
+					parameters.oriented_multipliers(trading_pair, path[i - 1]),
+				),
+				PoolType::ConcentratedLiquidity(range_parameters) => {
+					Self::get_range_supply_amount(trading_pair, &range_parameters, path[i - 1], supply_amounts[i])
+						.map(|(supply_amount, _)| supply_amount)
+						.unwrap_or_else(Zero::zero)
+				}
+			};
 			ensure!(!supply_amount.is_zero(), Error::<T>::ZeroSupplyAmount);
 
 			// check price impact if limit exists
@@ -973,13 +2495,25 @@ impl<T: Config> Pallet<T> {
 		Ok(supply_amounts)
 	}
 
+	/// Returns the number of range buckets walked.
 	fn _swap(
 		supply_currency_id: CurrencyId,
 		target_currency_id: CurrencyId,
 		supply_increment: Balance,
 		target_decrement: Balance,
-	) {
+	) -> u32 {
+		let mut walked_buckets: u32 = 0;
 		if let Some(trading_pair) = TradingPair::from_token_currency_ids(supply_currency_id, target_currency_id) {
+			if let PoolType::ConcentratedLiquidity(range_parameters) = Self::pool_types(trading_pair) {
+				walked_buckets = Self::_swap_range_buckets(
+					trading_pair,
+					&range_parameters,
+					supply_currency_id,
+					supply_increment,
+					target_decrement,
+				);
+			}
+
 			LiquidityPool::<T>::mutate(trading_pair, |(pool_0, pool_1)| {
 				if supply_currency_id == trading_pair.0 {
 					*pool_0 = pool_0.saturating_add(supply_increment);
@@ -990,24 +2524,310 @@ impl<T: Config> Pallet<T> {
 				}
 			});
 		}
+		walked_buckets
 	}
 
-	fn _swap_by_path(path: &[CurrencyId], amounts: &[Balance]) {
+	/// Swap with the buckets of concentrated liquidity, the target decrement
+	/// is filled from the current bucket in the moving direction of price,
+	/// and the supply increment is distributed to the filled buckets.
+	///
+	/// Returns the number of buckets walked.
+	fn _swap_range_buckets(
+		trading_pair: TradingPair,
+		range_parameters: &RangeParameters,
+		supply_currency_id: CurrencyId,
+		supply_increment: Balance,
+		target_decrement: Balance,
+	) -> u32 {
+		let is_supply_0 = supply_currency_id == trading_pair.0;
+		let mut remaining_supply_increment = supply_increment;
+		let mut remaining_target_decrement = target_decrement;
+		let mut bucket_id = Self::current_buckets(trading_pair);
+		let mut walked: u32 = 0;
+
+		loop {
+			walked = walked.saturating_add(1);
+			RangeBuckets::<T>::mutate(trading_pair, bucket_id, |bucket| {
+				let available = if is_supply_0 {
+					bucket.reserve_1
+				} else {
+					bucket.reserve_0
+				};
+				let bucket_target_decrement = available.min(remaining_target_decrement);
+				remaining_target_decrement -= bucket_target_decrement;
+
+				// the rest of supply increment goes to the last filled bucket
+				let bucket_supply_increment = if remaining_target_decrement.is_zero() {
+					remaining_supply_increment
+				} else {
+					Self::get_bucket_supply_amount(
+						bucket_target_decrement,
+						range_parameters.bucket_price(bucket_id),
+						is_supply_0,
+					)
+					.unwrap_or_default()
+					.min(remaining_supply_increment)
+				};
+				remaining_supply_increment -= bucket_supply_increment;
+
+				if is_supply_0 {
+					bucket.reserve_0 = bucket.reserve_0.saturating_add(bucket_supply_increment);
+					bucket.reserve_1 = bucket.reserve_1.saturating_sub(bucket_target_decrement);
+				} else {
+					bucket.reserve_0 = bucket.reserve_0.saturating_sub(bucket_target_decrement);
+					bucket.reserve_1 = bucket.reserve_1.saturating_add(bucket_supply_increment);
+				}
+			});
+
+			if remaining_target_decrement.is_zero() {
+				break;
+			}
+			match range_parameters.next_bucket(bucket_id, is_supply_0) {
+				Some(next_bucket_id) => bucket_id = next_bucket_id,
+				None => break,
+			}
+		}
+
+		CurrentBuckets::<T>::insert(trading_pair, bucket_id);
+		walked
+	}
+
+	/// Credit the referrer with its share of the swap fee charged on
+	/// `supply_amount`, return the share which is kept out of the pool.
+	fn reward_referrer(referrer: &T::AccountId, supply_currency_id: CurrencyId, supply_amount: Balance) -> Balance {
+		let fee = Self::swap_fee(supply_amount);
+		let reward = Self::referrer_fee_share().mul_floor(fee);
+
+		if !reward.is_zero() {
+			ReferrerRewards::<T>::mutate(referrer, supply_currency_id, |rewards| {
+				*rewards = rewards.saturating_add(reward)
+			});
+			Self::deposit_event(Event::ReferrerRewarded(referrer.clone(), supply_currency_id, reward));
+		}
+
+		reward
+	}
+
+	/// The swap fee charged on `supply_amount`.
+	fn swap_fee(supply_amount: Balance) -> Balance {
+		let (fee_numerator, fee_denominator) = Self::get_exchange_fee();
+		math::ratio_of_products(
+			&[supply_amount, fee_numerator.unique_saturated_into()],
+			&[fee_denominator.unique_saturated_into()],
+			math::Rounding::Down,
+		)
+		.unwrap_or_else(Zero::zero)
+	}
+
+	fn volume_bucket_slot(period_index: T::BlockNumber) -> u32 {
+		(period_index % T::MaxVolumeBuckets::get().max(1).into()).saturated_into()
+	}
+
+	/// Record the swap volume and fee of `supply_amount` to the cumulative
+	/// volume and the bucket of current period, the bucket of a passed
+	/// period in the same slot is overwritten.
+	fn record_volume(supply_currency_id: CurrencyId, target_currency_id: CurrencyId, supply_amount: Balance) {
+		if let Some(trading_pair) = TradingPair::from_token_currency_ids(supply_currency_id, target_currency_id) {
+			let is_supply_0 = supply_currency_id == trading_pair.0;
+			let fee = Self::swap_fee(supply_amount);
+			CumulativeVolumes::<T>::mutate(trading_pair, |record| record.accrue(is_supply_0, supply_amount, fee));
+
+			let period_index = frame_system::Pallet::<T>::block_number() / T::VolumeBucketPeriod::get();
+			VolumeBuckets::<T>::mutate(trading_pair, Self::volume_bucket_slot(period_index), |maybe_bucket| {
+				match maybe_bucket {
+					Some((index, record)) if *index == period_index => record.accrue(is_supply_0, supply_amount, fee),
+					_ => {
+						let mut record = VolumeRecord::default();
+						record.accrue(is_supply_0, supply_amount, fee);
+						*maybe_bucket = Some((period_index, record));
+					}
+				}
+			});
+		}
+	}
+
+	/// The swap volume of TradingPair in the period of `period_index`, if it
+	/// is still kept in the ring.
+	pub fn period_volume(trading_pair: TradingPair, period_index: T::BlockNumber) -> Option<VolumeRecord> {
+		Self::volume_buckets(trading_pair, Self::volume_bucket_slot(period_index))
+			.filter(|(index, _)| *index == period_index)
+			.map(|(_, record)| record)
+	}
+
+	/// The swap volume of TradingPair in the last `VolumeBucketPeriod` blocks.
+	/// The previous period is weighted by its part remaining in the window.
+	pub fn rolling_volume(trading_pair: TradingPair) -> VolumeRecord {
+		let now = frame_system::Pallet::<T>::block_number();
+		let period = T::VolumeBucketPeriod::get();
+		let period_index = now / period;
+		let mut volume = Self::period_volume(trading_pair, period_index).unwrap_or_default();
+
+		if !period_index.is_zero() {
+			if let Some(previous) = Self::period_volume(trading_pair, period_index - One::one()) {
+				let remaining = Ratio::checked_from_rational(
+					(period - now % period).saturated_into::<Balance>(),
+					period.saturated_into::<Balance>(),
+				)
+				.unwrap_or_else(Ratio::zero);
+				volume.volume_0 = volume
+					.volume_0
+					.saturating_add(remaining.saturating_mul_int(previous.volume_0));
+				volume.volume_1 = volume
+					.volume_1
+					.saturating_add(remaining.saturating_mul_int(previous.volume_1));
+				volume.fee_0 = volume
+					.fee_0
+					.saturating_add(remaining.saturating_mul_int(previous.fee_0));
+				volume.fee_1 = volume
+					.fee_1
+					.saturating_add(remaining.saturating_mul_int(previous.fee_1));
+			}
+		}
+
+		volume
+	}
+
+	/// The swap volume history of TradingPair, newest period first, covering
+	/// `MaxVolumeBuckets` periods up to the current one.
+	pub fn volume_history(trading_pair: TradingPair) -> Vec<VolumeRecord> {
+		let mut period_index = frame_system::Pallet::<T>::block_number() / T::VolumeBucketPeriod::get();
+		let mut history = Vec::new();
+		for _ in 0..T::MaxVolumeBuckets::get() {
+			history.push(Self::period_volume(trading_pair, period_index).unwrap_or_default());
+			if period_index.is_zero() {
+				break;
+			}
+			period_index -= One::one();
+		}
+		history
+	}
+
+	/// Returns the number of range buckets walked.
+	fn _swap_by_path(path: &[CurrencyId], amounts: &[Balance], referrer: Option<&T::AccountId>) -> u32 {
+		let mut walked_buckets: u32 = 0;
 		let mut i: usize = 0;
 		while i + 1 < path.len() {
 			let (supply_currency_id, target_currency_id) = (path[i], path[i + 1]);
-			let (supply_increment, target_decrement) = (amounts[i], amounts[i + 1]);
-			Self::_swap(
+			let (mut supply_increment, target_decrement) = (amounts[i], amounts[i + 1]);
+			Self::record_volume(supply_currency_id, target_currency_id, supply_increment);
+			if let Some(referrer) = referrer {
+				let reward = Self::reward_referrer(referrer, supply_currency_id, supply_increment);
+				supply_increment = supply_increment.saturating_sub(reward);
+			}
+			walked_buckets = walked_buckets.saturating_add(Self::_swap(
 				supply_currency_id,
 				target_currency_id,
 				supply_increment,
 				target_decrement,
-			);
+			));
 			i += 1;
 		}
+		walked_buckets
 	}
 
-	/// Ensured atomic.
+	/// The spot price of TradingPair, the marginal amount of `TradingPair.1`
+	/// got for `TradingPair.0` without the exchange fee.
+	fn spot_price(trading_pair: TradingPair) -> Option<Price> {
+		let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+		match Self::pool_types(trading_pair) {
+			PoolType::ConstantProduct => Price::checked_from_rational(pool_1, pool_0),
+			PoolType::StableSwap(parameters) => stable_swap::get_spot_price(
+				pool_0,
+				pool_1,
+				parameters.amplification(frame_system::Pallet::<T>::block_number()),
+				parameters.precision_multipliers,
+			),
+			PoolType::ConcentratedLiquidity(range_parameters) => {
+				Some(range_parameters.bucket_price(Self::current_buckets(trading_pair)))
+			}
+		}
+	}
+
+	/// The ratio the spot price moved against the swap supplying
+	/// `TradingPair.0` if `is_supply_0`, or `TradingPair.1` otherwise.
+	/// Returns one if the spot price after the swap is undefined.
+	fn price_impact(price_before: Price, price_after: Option<Price>, is_supply_0: bool) -> Ratio {
+		price_after
+			.and_then(|price_after| {
+				if is_supply_0 {
+					price_after.checked_div(&price_before)
+				} else {
+					price_before.checked_div(&price_after)
+				}
+			})
+			.map(|ratio| Ratio::one().saturating_sub(ratio))
+			.unwrap_or_else(Ratio::one)
+	}
+
+	/// The spot prices of the trading pairs in `path` before the swap, `None`
+	/// if neither `max_price_impact` nor the default of the trading pair
+	/// exists. The spot price before the first swap of the block is recorded
+	/// for the trading pairs with the default.
+	fn spot_prices_before_swap(path: &[CurrencyId], max_price_impact: Option<Ratio>) -> Vec<Option<Price>> {
+		let now = frame_system::Pallet::<T>::block_number();
+		let mut spot_prices: Vec<Option<Price>> = vec![];
+		let mut i: usize = 0;
+		while i + 1 < path.len() {
+			let trading_pair = TradingPair::new(path[i], path[i + 1]);
+			let has_default = MaxPriceImpacts::<T>::contains_key(trading_pair);
+			let spot_price = if max_price_impact.is_some() || has_default {
+				Self::spot_price(trading_pair)
+			} else {
+				None
+			};
+
+			if let (true, Some(price)) = (has_default, spot_price) {
+				BlockStartPrices::<T>::mutate(trading_pair, |maybe_start| match maybe_start {
+					Some((block_number, _)) if *block_number == now => {}
+					_ => *maybe_start = Some((now, price)),
+				});
+			}
+			spot_prices.push(spot_price);
+			i += 1;
+		}
+		spot_prices
+	}
+
+	/// Ensure the swap doesn't move the spot price of any trading pair in
+	/// `path` more than `max_price_impact`, and the accumulated move of the
+	/// block doesn't exceed the default of the trading pair, so that it can't
+	/// be bypassed by splitting the swap.
+	fn ensure_max_price_impact(
+		path: &[CurrencyId],
+		spot_prices_before: &[Option<Price>],
+		max_price_impact: Option<Ratio>,
+	) -> DispatchResult {
+		let mut i: usize = 0;
+		while i + 1 < path.len() {
+			if let Some(price_before) = spot_prices_before[i] {
+				let trading_pair = TradingPair::new(path[i], path[i + 1]);
+				let is_supply_0 = path[i] == trading_pair.0;
+				let price_after = Self::spot_price(trading_pair);
+
+				if let Some(limit) = max_price_impact {
+					ensure!(
+						Self::price_impact(price_before, price_after, is_supply_0) <= limit,
+						Error::<T>::ExceedMaxPriceImpact
+					);
+				}
+				if let Some(limit) = Self::max_price_impacts(trading_pair) {
+					let block_start_price = Self::block_start_prices(trading_pair)
+						.map(|(_, price)| price)
+						.unwrap_or(price_before);
+					ensure!(
+						Self::price_impact(block_start_price, price_after, is_supply_0) <= limit,
+						Error::<T>::ExceedMaxPriceImpact
+					);
+				}
+			}
+			i += 1;
+		}
+
+		Ok(())
+	}
+
+	/// Returns the actual target amount and the number of range buckets
+	/// walked. Ensured atomic.
 	#[transactional]
 	fn do_swap_with_exact_supply(
 		who: &T::AccountId,
@@ -1015,18 +2835,22 @@ impl<T: Config> Pallet<T> {
 		supply_amount: Balance,
 		min_target_amount: Balance,
 		price_impact_limit: Option<Ratio>,
-	) -> sp_std::result::Result<Balance, DispatchError> {
+		max_price_impact: Option<Ratio>,
+		referrer: Option<&T::AccountId>,
+	) -> sp_std::result::Result<(Balance, u32), DispatchError> {
 		let amounts = Self::get_target_amounts(&path, supply_amount, price_impact_limit)?;
 		ensure!(
 			amounts[amounts.len() - 1] >= min_target_amount,
 			Error::<T>::InsufficientTargetAmount
 		);
+		let spot_prices_before = Self::spot_prices_before_swap(&path, max_price_impact);
 		let module_account_id = Self::account_id();
 		let actual_target_amount = amounts[amounts.len() - 1];
 
 		T::Currency::transfer(path[0], who, &module_account_id, supply_amount)?;
-		Self::_swap_by_path(&path, &amounts);
+		let walked_buckets = Self::_swap_by_path(&path, &amounts, referrer.filter(|referrer| *referrer != who));
 		T::Currency::transfer(path[path.len() - 1], &module_account_id, who, actual_target_amount)?;
+		Self::ensure_max_price_impact(&path, &spot_prices_before, max_price_impact)?;
 
 		Self::deposit_event(Event::Swap(
 			who.clone(),
@@ -1034,10 +2858,11 @@ impl<T: Config> Pallet<T> {
 			supply_amount,
 			actual_target_amount,
 		));
-		Ok(actual_target_amount)
+		Ok((actual_target_amount, walked_buckets))
 	}
 
-	/// Ensured atomic.
+	/// Returns the actual supply amount and the number of range buckets
+	/// walked. Ensured atomic.
 	#[transactional]
 	fn do_swap_with_exact_target(
 		who: &T::AccountId,
@@ -1045,15 +2870,19 @@ impl<T: Config> Pallet<T> {
 		target_amount: Balance,
 		max_supply_amount: Balance,
 		price_impact_limit: Option<Ratio>,
-	) -> sp_std::result::Result<Balance, DispatchError> {
+		max_price_impact: Option<Ratio>,
+		referrer: Option<&T::AccountId>,
+	) -> sp_std::result::Result<(Balance, u32), DispatchError> {
 		let amounts = Self::get_supply_amounts(&path, target_amount, price_impact_limit)?;
 		ensure!(amounts[0] <= max_supply_amount, Error::<T>::ExcessiveSupplyAmount);
+		let spot_prices_before = Self::spot_prices_before_swap(&path, max_price_impact);
 		let module_account_id = Self::account_id();
 		let actual_supply_amount = amounts[0];
 
 		T::Currency::transfer(path[0], who, &module_account_id, actual_supply_amount)?;
-		Self::_swap_by_path(&path, &amounts);
+		let walked_buckets = Self::_swap_by_path(&path, &amounts, referrer.filter(|referrer| *referrer != who));
 		T::Currency::transfer(path[path.len() - 1], &module_account_id, who, target_amount)?;
+		Self::ensure_max_price_impact(&path, &spot_prices_before, max_price_impact)?;
 
 		Self::deposit_event(Event::Swap(
 			who.clone(),
@@ -1061,12 +2890,102 @@ impl<T: Config> Pallet<T> {
 			actual_supply_amount,
 			target_amount,
 		));
-		Ok(actual_supply_amount)
+		Ok((actual_supply_amount, walked_buckets))
+	}
+
+	/// Ensured atomic.
+	#[transactional]
+	fn do_swap_basket_for_exact_target(
+		who: &T::AccountId,
+		inputs: &[(CurrencyId, Balance)],
+		target_currency_id: CurrencyId,
+		target_amount: Balance,
+	) -> sp_std::result::Result<Vec<(CurrencyId, Balance)>, DispatchError> {
+		ensure!(
+			!inputs.is_empty() && inputs.len() <= T::MaxBasketInputs::get().saturated_into(),
+			Error::<T>::InvalidBasketLength
+		);
+		ensure!(!target_amount.is_zero(), Error::<T>::ZeroTargetAmount);
+
+		let mut remaining_target = target_amount;
+		let mut supply_amounts: Vec<(CurrencyId, Balance)> = vec![];
+		for (supply_currency_id, max_supply_amount) in inputs {
+			ensure!(*supply_currency_id != target_currency_id, Error::<T>::InvalidCurrencyId);
+			if remaining_target.is_zero() {
+				break;
+			}
+			if max_supply_amount.is_zero() {
+				continue;
+			}
+
+			let path = [*supply_currency_id, target_currency_id];
+			let available_target = match Self::get_target_amounts(&path, *max_supply_amount, None) {
+				Ok(target_amounts) => target_amounts[1],
+				// skip the dust inputs and the inputs that can't be swapped to the target
+				Err(e)
+					if e == Error::<T>::ZeroTargetAmount.into()
+						|| e == Error::<T>::MustBeEnabled.into()
+						|| e == Error::<T>::InsufficientLiquidity.into() =>
+				{
+					continue
+				}
+				Err(e) => return Err(e),
+			};
+			if available_target >= remaining_target {
+				// the rest of target can be met by this input
+				let (supply_amount, _) = Self::do_swap_with_exact_target(
+					who,
+					&path,
+					remaining_target,
+					*max_supply_amount,
+					None,
+					None,
+					None,
+				)?;
+				supply_amounts.push((*supply_currency_id, supply_amount));
+				remaining_target = Zero::zero();
+			} else {
+				let (actual_target, _) =
+					Self::do_swap_with_exact_supply(who, &path, *max_supply_amount, Zero::zero(), None, None, None)?;
+				supply_amounts.push((*supply_currency_id, *max_supply_amount));
+				remaining_target = remaining_target.saturating_sub(actual_target);
+			}
+		}
+		ensure!(remaining_target.is_zero(), Error::<T>::InsufficientTargetAmount);
+
+		Self::deposit_event(Event::BasketSwap(
+			who.clone(),
+			supply_amounts.clone(),
+			target_currency_id,
+			target_amount,
+		));
+		Ok(supply_amounts)
 	}
 }
 
 impl<T: Config> DEXManager<T::AccountId, CurrencyId, Balance> for Pallet<T> {
+	/// The aggregate reserves of concentrated liquidity are at different
+	/// prices, the liquidity of the current bucket valued at its price is
+	/// returned instead, so that the ratio of pools is the current price.
 	fn get_liquidity_pool(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+		if let Some(trading_pair) = TradingPair::from_token_currency_ids(currency_id_a, currency_id_b) {
+			if let PoolType::ConcentratedLiquidity(range_parameters) = Self::pool_types(trading_pair) {
+				let bucket_id = Self::current_buckets(trading_pair);
+				let bucket = Self::range_buckets(trading_pair, bucket_id);
+				let price = range_parameters.bucket_price(bucket_id);
+				let pool_1 = price
+					.saturating_mul_int(bucket.reserve_0)
+					.saturating_add(bucket.reserve_1);
+				let pool_0 = math::checked_div_int(pool_1, price, math::Rounding::Down).unwrap_or_default();
+
+				return if currency_id_a == trading_pair.0 {
+					(pool_0, pool_1)
+				} else {
+					(pool_1, pool_0)
+				};
+			}
+		}
+
 		Self::get_liquidity(currency_id_a, currency_id_b)
 	}
 
@@ -1097,7 +3016,16 @@ impl<T: Config> DEXManager<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 		min_target_amount: Balance,
 		price_impact_limit: Option<Ratio>,
 	) -> sp_std::result::Result<Balance, DispatchError> {
-		Self::do_swap_with_exact_supply(who, path, supply_amount, min_target_amount, price_impact_limit)
+		Self::do_swap_with_exact_supply(
+			who,
+			path,
+			supply_amount,
+			min_target_amount,
+			price_impact_limit,
+			None,
+			None,
+		)
+		.map(|(amount, _)| amount)
 	}
 
 	fn swap_with_exact_target(
@@ -1107,7 +3035,16 @@ impl<T: Config> DEXManager<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 		max_supply_amount: Balance,
 		price_impact_limit: Option<Ratio>,
 	) -> sp_std::result::Result<Balance, DispatchError> {
-		Self::do_swap_with_exact_target(who, path, target_amount, max_supply_amount, price_impact_limit)
+		Self::do_swap_with_exact_target(
+			who,
+			path,
+			target_amount,
+			max_supply_amount,
+			price_impact_limit,
+			None,
+			None,
+		)
+		.map(|(amount, _)| amount)
 	}
 
 	// `do_add_liquidity` is used in genesis_build,
@@ -1143,3 +3080,11 @@ impl<T: Config> DEXManager<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 		Self::do_remove_liquidity(who, currency_id_a, currency_id_b, remove_share, by_withdraw)
 	}
 }
+
+impl<T: Config> ParameterInvariants for Pallet<T> {
+	fn check_invariants() -> DispatchResult {
+		let (fee_numerator, fee_denominator) = Self::get_exchange_fee();
+		ensure!(fee_numerator < fee_denominator, Error::<T>::InvalidExchangeFee);
+		Ok(())
+	}
+}
@@ -24,9 +24,10 @@ use super::*;
 use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
 use frame_system::EnsureSignedBy;
 use orml_traits::{parameter_type_with_key, MultiReservableCurrency};
-use primitives::{Amount, TokenSymbol};
+use primitives::{evm::EvmAddress, Amount, TokenSymbol};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
 
 pub type BlockNumber = u64;
 pub type AccountId = u128;
@@ -103,14 +104,98 @@ impl DEXIncentives<AccountId, CurrencyId, Balance> for MockDEXIncentives {
 	}
 }
 
+thread_local! {
+	static POSITION_OWNERS: RefCell<BTreeMap<PositionId, AccountId>> = RefCell::new(BTreeMap::new());
+	static NEXT_POSITION_ID: RefCell<PositionId> = RefCell::new(0);
+}
+
+pub struct MockPositionNFT;
+impl MockPositionNFT {
+	pub fn transfer(token_id: PositionId, to: AccountId) {
+		POSITION_OWNERS.with(|v| {
+			if let Some(owner) = v.borrow_mut().get_mut(&token_id) {
+				*owner = to;
+			}
+		});
+	}
+}
+impl PositionNFT<AccountId> for MockPositionNFT {
+	type TokenId = PositionId;
+
+	fn mint_position(_id: PalletId, owner: &AccountId, _metadata: Vec<u8>) -> Result<PositionId, DispatchError> {
+		let token_id = NEXT_POSITION_ID.with(|v| {
+			let token_id = *v.borrow();
+			*v.borrow_mut() = token_id + 1;
+			token_id
+		});
+		POSITION_OWNERS.with(|v| v.borrow_mut().insert(token_id, *owner));
+		Ok(token_id)
+	}
+
+	fn burn_position(_id: PalletId, owner: &AccountId, token_id: PositionId) -> DispatchResult {
+		POSITION_OWNERS.with(|v| {
+			let mut owners = v.borrow_mut();
+			if owners.get(&token_id) != Some(owner) {
+				return Err(DispatchError::Other("not owner"));
+			}
+			owners.remove(&token_id);
+			Ok(())
+		})
+	}
+
+	fn position_owner(_id: PalletId, token_id: PositionId) -> Option<AccountId> {
+		POSITION_OWNERS.with(|v| v.borrow().get(&token_id).copied())
+	}
+}
+
+thread_local! {
+	static ERC20_REFERENCES: RefCell<Vec<(PalletId, CurrencyId)>> = RefCell::new(vec![]);
+}
+
+pub fn erc20_references() -> Vec<(PalletId, CurrencyId)> {
+	ERC20_REFERENCES.with(|v| v.borrow().clone())
+}
+
+/// Records the references to all currencies, not only `CurrencyId::Erc20`.
+pub struct MockErc20ReferenceCounter;
+impl Erc20ReferenceCounter for MockErc20ReferenceCounter {
+	fn inc_reference(id: PalletId, currency_id: CurrencyId) {
+		ERC20_REFERENCES.with(|v| v.borrow_mut().push((id, currency_id)));
+	}
+
+	fn dec_reference(id: PalletId, currency_id: CurrencyId) {
+		ERC20_REFERENCES.with(|v| {
+			let mut references = v.borrow_mut();
+			if let Some(position) = references.iter().position(|reference| *reference == (id, currency_id)) {
+				references.remove(position);
+			}
+		});
+	}
+
+	fn references(_address: EvmAddress) -> Vec<(PalletId, u32)> {
+		Default::default()
+	}
+}
+
 ord_parameter_types! {
 	pub const ListingOrigin: AccountId = 3;
+	pub const ListingVetoOrigin: AccountId = 4;
 }
 
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 100);
 	pub const TradingPathLimit: u32 = 3;
+	pub const ProvisioningPeriod: BlockNumber = 1000;
+	pub const AmplificationRampPeriod: BlockNumber = 10;
+	pub const MaxRangeBuckets: u32 = 10;
+	pub const MaxBasketInputs: u32 = 3;
+	pub const VolumeBucketPeriod: BlockNumber = 10;
+	pub const MaxVolumeBuckets: u32 = 3;
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
+	pub const GetNativeCurrencyId: CurrencyId = ACA;
+	pub const ListingBond: Balance = 1_000;
+	pub const ListingChallengePeriod: BlockNumber = 10;
+	pub const TreasuryAccount: AccountId = 5;
 }
 
 impl Config for Runtime {
@@ -120,9 +205,22 @@ impl Config for Runtime {
 	type TradingPathLimit = TradingPathLimit;
 	type PalletId = DEXPalletId;
 	type CurrencyIdMapping = ();
+	type Erc20ReferenceCounter = MockErc20ReferenceCounter;
 	type WeightInfo = ();
 	type DEXIncentives = MockDEXIncentives;
 	type ListingOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
+	type ProvisioningPeriod = ProvisioningPeriod;
+	type AmplificationRampPeriod = AmplificationRampPeriod;
+	type MaxRangeBuckets = MaxRangeBuckets;
+	type PositionNFT = MockPositionNFT;
+	type MaxBasketInputs = MaxBasketInputs;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type ListingBond = ListingBond;
+	type ListingChallengePeriod = ListingChallengePeriod;
+	type ListingVetoOrigin = EnsureSignedBy<ListingVetoOrigin, AccountId>;
+	type TreasuryAccount = TreasuryAccount;
+	type VolumeBucketPeriod = VolumeBucketPeriod;
+	type MaxVolumeBuckets = MaxVolumeBuckets;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -157,6 +255,8 @@ impl Default for ExtBuilder {
 				(BOB, XBTC, 1_000_000_000_000_000_000u128),
 				(ALICE, DOT, 1_000_000_000_000_000_000u128),
 				(BOB, DOT, 1_000_000_000_000_000_000u128),
+				(ALICE, ACA, 1_000_000_000_000_000_000u128),
+				(BOB, ACA, 1_000_000_000_000_000_000u128),
 			],
 			initial_listing_trading_pairs: vec![],
 			initial_enabled_trading_pairs: vec![],
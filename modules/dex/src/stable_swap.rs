@@ -0,0 +1,309 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Math of the stable swap invariant for pools of two like-valued assets,
+//! refers to the design of Curve:
+//!
+//! `A * n^n * sum(x_i) + D = A * D * n^n + D^(n + 1) / (n^n * prod(x_i))`
+//!
+//! where `A` is the amplification coefficient, `n` is the number of assets
+//! and `D` is the invariant of the pool. All intermediate values are
+//! calculated with 256 bits precision.
+
+use primitives::Balance;
+use sp_core::U256;
+use sp_runtime::{FixedPointNumber, FixedU128};
+use sp_std::convert::TryInto;
+
+/// The max amplification coefficient.
+pub const MAX_AMPLIFICATION: u32 = 1_000_000;
+/// The max factor by which the amplification coefficient of a non-empty pool
+/// can be ramped up or down.
+pub const MAX_AMPLIFICATION_CHANGE: u32 = 10;
+/// The number of assets in a stable swap pool.
+const N_COINS: u32 = 2;
+/// The max iterations of Newton's method.
+const MAX_ITERATIONS: u32 = 255;
+
+fn is_converged(value: U256, previous: U256) -> bool {
+	if value > previous {
+		value - previous <= U256::one()
+	} else {
+		previous - value <= U256::one()
+	}
+}
+
+/// `A * n^n`
+fn get_ann(amplification: u32) -> Option<U256> {
+	U256::from(amplification).checked_mul(U256::from(N_COINS.pow(N_COINS)))
+}
+
+/// Calculate the invariant `D` of the pool with the normalized balances
+/// `xp` by Newton's method.
+fn compute_d(xp: (U256, U256), amplification: u32) -> Option<U256> {
+	let n = U256::from(N_COINS);
+	let ann = get_ann(amplification)?;
+	let sum = xp.0.checked_add(xp.1)?;
+	if sum.is_zero() {
+		return Some(U256::zero());
+	}
+
+	let mut d = sum;
+	for _ in 0..MAX_ITERATIONS {
+		// d_p = D^(n + 1) / (n^n * prod(x_i))
+		let mut d_p = d;
+		for x in [xp.0, xp.1].iter() {
+			d_p = d_p.checked_mul(d)?.checked_div(x.checked_mul(n)?)?;
+		}
+
+		// D = (Ann * S + n * d_p) * D / ((Ann - 1) * D + (n + 1) * d_p)
+		let previous = d;
+		let numerator = ann.checked_mul(sum)?.checked_add(d_p.checked_mul(n)?)?.checked_mul(d)?;
+		let denominator = ann
+			.checked_sub(U256::one())?
+			.checked_mul(d)?
+			.checked_add(n.checked_add(U256::one())?.checked_mul(d_p)?)?;
+		d = numerator.checked_div(denominator)?;
+
+		if is_converged(d, previous) {
+			return Some(d);
+		}
+	}
+
+	None
+}
+
+/// Calculate the normalized balance of one asset that keeps the invariant
+/// `d` when the normalized balance of the other asset is `x`, by Newton's
+/// method.
+fn compute_y(x: U256, d: U256, amplification: u32) -> Option<U256> {
+	let n = U256::from(N_COINS);
+	let ann = get_ann(amplification)?;
+
+	// c = D^(n + 1) / (n^n * x * Ann)
+	let c = d
+		.checked_mul(d)?
+		.checked_div(x.checked_mul(n)?)?
+		.checked_mul(d)?
+		.checked_div(ann.checked_mul(n)?)?;
+	// b = x + D / Ann
+	let b = x.checked_add(d.checked_div(ann)?)?;
+
+	let mut y = d;
+	for _ in 0..MAX_ITERATIONS {
+		// y = (y^2 + c) / (2 * y + b - D)
+		let previous = y;
+		let numerator = y.checked_mul(y)?.checked_add(c)?;
+		let denominator = y.checked_mul(n)?.checked_add(b)?.checked_sub(d)?;
+		y = numerator.checked_div(denominator)?;
+
+		if is_converged(y, previous) {
+			return Some(y);
+		}
+	}
+
+	None
+}
+
+/// Normalize `balance` to the common precision by `multiplier`.
+fn normalize(balance: Balance, multiplier: Balance) -> Option<U256> {
+	U256::from(balance).checked_mul(U256::from(multiplier))
+}
+
+/// Calculate the invariant `D` of the pool with `balances`, which are
+/// normalized by `multipliers` to the same precision.
+///
+/// Returns `None` if one of the balances is zero, or on overflow or not
+/// converging.
+pub fn get_d(balances: (Balance, Balance), amplification: u32, multipliers: (Balance, Balance)) -> Option<Balance> {
+	let xp = (
+		normalize(balances.0, multipliers.0)?,
+		normalize(balances.1, multipliers.1)?,
+	);
+	compute_d(xp, amplification)?.try_into().ok()
+}
+
+/// Calculate how much target amount will be got for `supply_amount`, the
+/// exchange fee should have been deducted from `supply_amount` already.
+/// `multipliers` are the precision multipliers of the supply and target
+/// currency.
+pub fn get_target_amount(
+	supply_pool: Balance,
+	target_pool: Balance,
+	supply_amount: Balance,
+	amplification: u32,
+	multipliers: (Balance, Balance),
+) -> Option<Balance> {
+	let (supply_multiplier, target_multiplier) = multipliers;
+	let xp_target = normalize(target_pool, target_multiplier)?;
+	let d = compute_d((normalize(supply_pool, supply_multiplier)?, xp_target), amplification)?;
+	let new_xp_supply = normalize(supply_pool.checked_add(supply_amount)?, supply_multiplier)?;
+	let new_xp_target = compute_y(new_xp_supply, d, amplification)?;
+
+	// sub 1 so that the possible losses caused by the iterations are at the
+	// expense of the trader
+	let target_amount: Balance = xp_target
+		.checked_sub(new_xp_target)?
+		.checked_div(U256::from(target_multiplier))?
+		.try_into()
+		.ok()?;
+	target_amount.checked_sub(1)
+}
+
+/// Calculate how much supply amount should be paid for `target_amount`, the
+/// exchange fee is not included in the result. `multipliers` are the
+/// precision multipliers of the supply and target currency.
+pub fn get_supply_amount(
+	supply_pool: Balance,
+	target_pool: Balance,
+	target_amount: Balance,
+	amplification: u32,
+	multipliers: (Balance, Balance),
+) -> Option<Balance> {
+	let (supply_multiplier, target_multiplier) = multipliers;
+	let xp_supply = normalize(supply_pool, supply_multiplier)?;
+	let d = compute_d((xp_supply, normalize(target_pool, target_multiplier)?), amplification)?;
+	let new_xp_target = normalize(target_pool.checked_sub(target_amount)?, target_multiplier)?;
+	let new_xp_supply = compute_y(new_xp_target, d, amplification)?;
+
+	// round up and add 1 so that the possible losses caused by the iterations
+	// are at the expense of the trader
+	let supply_multiplier = U256::from(supply_multiplier);
+	let supply_amount: Balance = new_xp_supply
+		.checked_sub(xp_supply)?
+		.checked_add(supply_multiplier.checked_sub(U256::one())?)?
+		.checked_div(supply_multiplier)?
+		.try_into()
+		.ok()?;
+	supply_amount.checked_add(1)
+}
+
+/// Calculate the spot price, the marginal target amount got for the supply
+/// amount without the exchange fee. `multipliers` are the precision
+/// multipliers of the supply and target currency.
+///
+/// By the partial derivatives of the invariant, the spot price of the
+/// normalized balances is `(k + y) / (k + x)`, where `x` and `y` are the
+/// balances of the supply and target currency and `k = 4 * Ann * x^2 * y^2 /
+/// D^3`. The balances are scaled down by `D` to avoid overflow.
+pub fn get_spot_price(
+	supply_pool: Balance,
+	target_pool: Balance,
+	amplification: u32,
+	multipliers: (Balance, Balance),
+) -> Option<FixedU128> {
+	let (supply_multiplier, target_multiplier) = multipliers;
+	let xp_supply = normalize(supply_pool, supply_multiplier)?;
+	let xp_target = normalize(target_pool, target_multiplier)?;
+	let d = compute_d((xp_supply, xp_target), amplification)?;
+	let n = U256::from(N_COINS);
+	let unit = U256::from(FixedU128::accuracy());
+
+	// the balances in the unit of `D`
+	let u = xp_supply.checked_mul(unit)?.checked_div(d)?;
+	let v = xp_target.checked_mul(unit)?.checked_div(d)?;
+	let k = get_ann(amplification)?
+		.checked_mul(n.checked_mul(n)?)?
+		.checked_mul(u)?
+		.checked_mul(u)?
+		.checked_div(unit)?
+		.checked_mul(v)?
+		.checked_div(unit)?
+		.checked_mul(v)?
+		.checked_div(unit)?;
+
+	let price: u128 = k
+		.checked_add(v)?
+		.checked_mul(U256::from(supply_multiplier))?
+		.checked_mul(unit)?
+		.checked_div(k.checked_add(u)?.checked_mul(U256::from(target_multiplier))?)?
+		.try_into()
+		.ok()?;
+	Some(FixedU128::from_inner(price))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn get_d_works() {
+		assert_eq!(get_d((0, 0), 100, (1, 1)), Some(0));
+		assert_eq!(get_d((0, 1_000_000), 100, (1, 1)), None);
+		assert_eq!(get_d((1_000_000, 1_000_000), 100, (1, 1)), Some(2_000_000));
+		assert_eq!(get_d((1_000_000, 2_000_000), 100, (1, 1)), Some(2_999_068));
+		assert_eq!(get_d((100_000_000, 1_000_000), 100, (1, 100)), Some(200_000_000));
+	}
+
+	#[test]
+	fn get_target_amount_works() {
+		assert_eq!(
+			get_target_amount(1_000_000, 1_000_000, 500_000, 100, (1, 1)),
+			Some(498_355)
+		);
+		assert_eq!(
+			get_target_amount(1_000_000, 1_000_000, 500_000, 1, (1, 1)),
+			Some(422_649)
+		);
+		assert_eq!(get_target_amount(0, 1_000_000, 500_000, 100, (1, 1)), None);
+		assert_eq!(
+			get_target_amount(1_000_000, 100_000_000, 500_000, 100, (100, 1)),
+			Some(49_835_519)
+		);
+	}
+
+	#[test]
+	fn get_spot_price_works() {
+		assert_eq!(
+			get_spot_price(1_000_000, 1_000_000, 100, (1, 1)),
+			Some(FixedU128::one())
+		);
+		assert_eq!(
+			get_spot_price(1_000_000, 100_000_000, 100, (100, 1)),
+			Some(FixedU128::saturating_from_integer(100))
+		);
+		assert_eq!(
+			get_spot_price(1_000_000, 2_000_000, 100, (1, 1)),
+			Some(FixedU128::from_inner(1_004_197_129_205_003_518))
+		);
+		assert_eq!(
+			get_spot_price(2_000_000, 1_000_000, 100, (1, 1)),
+			Some(FixedU128::from_inner(995_820_413_061_401_325))
+		);
+		// closer to the constant product invariant with lower amplification
+		assert_eq!(
+			get_spot_price(1_000_000, 2_000_000, 1, (1, 1)),
+			Some(FixedU128::from_inner(1_284_329_512_780_808_958))
+		);
+		assert_eq!(get_spot_price(0, 1_000_000, 100, (1, 1)), None);
+	}
+
+	#[test]
+	fn get_supply_amount_works() {
+		assert_eq!(
+			get_supply_amount(1_000_000, 1_000_000, 500_000, 100, (1, 1)),
+			Some(501_660)
+		);
+		assert_eq!(get_supply_amount(1_000_000, 1_000_000, 1_000_000, 100, (1, 1)), None);
+		assert_eq!(get_supply_amount(1_000_000, 1_000_000, 2_000_000, 100, (1, 1)), None);
+		assert_eq!(
+			get_supply_amount(1_000_000, 100_000_000, 50_000_000, 100, (100, 1)),
+			Some(501_661)
+		);
+	}
+}
@@ -23,8 +23,9 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{
-	DexModule, Event, ExtBuilder, ListingOrigin, Origin, Runtime, System, Tokens, ACA, ALICE, AUSD, AUSD_DOT_PAIR,
-	AUSD_XBTC_PAIR, BOB, DOT, XBTC,
+	erc20_references, AccountId, AmplificationRampPeriod, DEXPalletId, DexModule, Event, ExtBuilder, ListingBond,
+	ListingOrigin, ListingVetoOrigin, MockPositionNFT, Origin, Runtime, System, Tokens, TreasuryAccount, ACA, ALICE,
+	AUSD, AUSD_DOT_PAIR, AUSD_XBTC_PAIR, BOB, DOT, XBTC,
 };
 use orml_traits::MultiReservableCurrency;
 use sp_runtime::traits::BadOrigin;
@@ -65,6 +66,91 @@ fn enable_new_trading_pair_work() {
 	});
 }
 
+#[test]
+fn enable_stable_swap_trading_pair_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			DexModule::enable_stable_swap_trading_pair(Origin::signed(ALICE), AUSD, DOT, 100),
+			BadOrigin
+		);
+		assert_noop!(
+			DexModule::enable_stable_swap_trading_pair(Origin::signed(ListingOrigin::get()), AUSD, DOT, 0),
+			Error::<Runtime>::InvalidAmplification
+		);
+		assert_noop!(
+			DexModule::enable_stable_swap_trading_pair(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				stable_swap::MAX_AMPLIFICATION + 1
+			),
+			Error::<Runtime>::InvalidAmplification
+		);
+
+		assert_ok!(DexModule::enable_stable_swap_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			100
+		));
+		assert_eq!(
+			DexModule::trading_pair_statuses(AUSD_DOT_PAIR),
+			TradingPairStatus::<_, _>::Enabled
+		);
+		assert_eq!(
+			DexModule::pool_types(AUSD_DOT_PAIR),
+			PoolType::StableSwap(StableSwapParameters {
+				initial_amplification: 100,
+				future_amplification: 100,
+				ramp_start: 1,
+				ramp_end: 1,
+				precision_multipliers: (1, 100),
+			})
+		);
+		let enable_event = Event::dex(crate::Event::EnableStableSwapTradingPair(AUSD_DOT_PAIR, 100));
+		assert!(System::events().iter().any(|record| record.event == enable_event));
+
+		assert_noop!(
+			DexModule::enable_stable_swap_trading_pair(Origin::signed(ListingOrigin::get()), DOT, AUSD, 100),
+			Error::<Runtime>::MustBeNotEnabled
+		);
+
+		// re-enable with the constant product invariant when the pool is empty
+		assert_ok!(DexModule::disable_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT
+		));
+		assert_ok!(DexModule::enable_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT
+		));
+		assert_eq!(DexModule::pool_types(AUSD_DOT_PAIR), PoolType::ConstantProduct);
+
+		// the invariant of the non-empty pool can not be changed
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			1_000_000,
+			1_000_000,
+			false,
+		));
+		assert_ok!(DexModule::disable_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT
+		));
+		assert_noop!(
+			DexModule::enable_stable_swap_trading_pair(Origin::signed(ListingOrigin::get()), AUSD, DOT, 100),
+			Error::<Runtime>::LiquidityPoolNotEmpty
+		);
+	});
+}
+
 #[test]
 fn list_new_trading_pair_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -129,6 +215,160 @@ fn list_new_trading_pair_work() {
 	});
 }
 
+#[test]
+fn propose_listing_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			DexModule::propose_listing(
+				Origin::signed(ALICE),
+				AUSD,
+				AUSD,
+				1_000_000_000_000u128,
+				1_000_000_000_000u128,
+				5_000_000_000_000u128,
+				2_000_000_000_000u128,
+				10,
+			),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+
+		assert_ok!(DexModule::propose_listing(
+			Origin::signed(ALICE),
+			DOT,
+			AUSD,
+			1_000_000_000_000u128,
+			2_000_000_000_000u128,
+			5_000_000_000_000u128,
+			4_000_000_000_000u128,
+			20,
+		));
+		assert_eq!(Tokens::reserved_balance(ACA, &ALICE), ListingBond::get());
+		assert_eq!(
+			DexModule::listing_proposals(AUSD_DOT_PAIR),
+			Some(ListingProposal {
+				proposer: ALICE,
+				bond: ListingBond::get(),
+				min_contribution: (2_000_000_000_000u128, 1_000_000_000_000u128),
+				target_provision: (4_000_000_000_000u128, 5_000_000_000_000u128),
+				not_before: 20,
+				activate_at: 11,
+			})
+		);
+		assert_eq!(DexModule::listing_activations(11, AUSD_DOT_PAIR), Some(()));
+
+		let propose_listing_event = Event::dex(crate::Event::ProposeListing(
+			ALICE,
+			AUSD_DOT_PAIR,
+			ListingBond::get(),
+			11,
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == propose_listing_event));
+
+		assert_noop!(
+			DexModule::propose_listing(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				1_000_000_000_000u128,
+				1_000_000_000_000u128,
+				5_000_000_000_000u128,
+				2_000_000_000_000u128,
+				10,
+			),
+			Error::<Runtime>::ListingProposalExisted
+		);
+
+		DexModule::on_initialize(10);
+		assert_eq!(
+			DexModule::trading_pair_statuses(AUSD_DOT_PAIR),
+			TradingPairStatus::<_, _>::NotEnabled
+		);
+
+		DexModule::on_initialize(11);
+		assert_eq!(
+			DexModule::trading_pair_statuses(AUSD_DOT_PAIR),
+			TradingPairStatus::<_, _>::Provisioning(TradingPairProvisionParameters {
+				min_contribution: (2_000_000_000_000u128, 1_000_000_000_000u128),
+				target_provision: (4_000_000_000_000u128, 5_000_000_000_000u128),
+				accumulated_provision: (0, 0),
+				not_before: 20,
+			})
+		);
+		assert_eq!(DexModule::listing_proposals(AUSD_DOT_PAIR), None);
+		assert_eq!(DexModule::listing_activations(11, AUSD_DOT_PAIR), None);
+		assert_eq!(Tokens::reserved_balance(ACA, &ALICE), 0);
+
+		let activated_event = Event::dex(crate::Event::ListingProposalActivated(AUSD_DOT_PAIR));
+		assert!(System::events().iter().any(|record| record.event == activated_event));
+
+		assert_noop!(
+			DexModule::propose_listing(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				1_000_000_000_000u128,
+				1_000_000_000_000u128,
+				5_000_000_000_000u128,
+				2_000_000_000_000u128,
+				10,
+			),
+			Error::<Runtime>::MustBeNotEnabled
+		);
+	});
+}
+
+#[test]
+fn veto_listing_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(DexModule::propose_listing(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			1_000_000_000_000u128,
+			1_000_000_000_000u128,
+			5_000_000_000_000u128,
+			2_000_000_000_000u128,
+			10,
+		));
+		let alice_aca_balance = Tokens::total_balance(ACA, &ALICE);
+
+		assert_noop!(DexModule::veto_listing(Origin::signed(ALICE), AUSD, DOT), BadOrigin);
+		assert_noop!(
+			DexModule::veto_listing(Origin::signed(ListingVetoOrigin::get()), AUSD, XBTC),
+			Error::<Runtime>::ListingProposalNotFound
+		);
+
+		assert_ok!(DexModule::veto_listing(
+			Origin::signed(ListingVetoOrigin::get()),
+			AUSD,
+			DOT
+		));
+		assert_eq!(DexModule::listing_proposals(AUSD_DOT_PAIR), None);
+		assert_eq!(DexModule::listing_activations(11, AUSD_DOT_PAIR), None);
+		assert_eq!(Tokens::reserved_balance(ACA, &ALICE), 0);
+		assert_eq!(
+			Tokens::total_balance(ACA, &ALICE),
+			alice_aca_balance - ListingBond::get()
+		);
+		assert_eq!(Tokens::free_balance(ACA, &TreasuryAccount::get()), ListingBond::get());
+
+		let veto_listing_event = Event::dex(crate::Event::VetoListing(AUSD_DOT_PAIR, ALICE, ListingBond::get()));
+		assert!(System::events().iter().any(|record| record.event == veto_listing_event));
+
+		DexModule::on_initialize(11);
+		assert_eq!(
+			DexModule::trading_pair_statuses(AUSD_DOT_PAIR),
+			TradingPairStatus::<_, _>::NotEnabled
+		);
+	});
+}
+
 #[test]
 fn disable_enabled_trading_pair_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -437,6 +677,100 @@ fn add_provision_work() {
 		});
 }
 
+#[test]
+fn claim_refund_work() {
+	ExtBuilder::default()
+		.initialize_listing_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				5_000_000_000_000u128,
+				0,
+				false
+			));
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				0,
+				1_000_000_000_000u128,
+				false
+			));
+			let alice_ref_count_0 = System::consumers(&ALICE);
+
+			assert_noop!(
+				DexModule::claim_refund(Origin::signed(ALICE), AUSD, DOT),
+				Error::<Runtime>::ProvisioningNotFailed
+			);
+			assert_noop!(
+				DexModule::claim_refund(Origin::signed(ALICE), AUSD, ACA),
+				Error::<Runtime>::MustBeProvisioning
+			);
+
+			// the deadline is block 1010
+			System::set_block_number(1011);
+			assert_noop!(
+				DexModule::add_liquidity(Origin::signed(ALICE), AUSD, DOT, 5_000_000_000_000u128, 0, false),
+				Error::<Runtime>::ProvisioningFailed
+			);
+			assert_noop!(
+				DexModule::claim_refund(Origin::signed(ListingOrigin::get()), AUSD, DOT),
+				Error::<Runtime>::NoProvisionToRefund
+			);
+
+			assert_ok!(DexModule::claim_refund(Origin::signed(ALICE), DOT, AUSD));
+			assert_eq!(DexModule::provisioning_pool(AUSD_DOT_PAIR, ALICE), (0, 0));
+			assert_eq!(Tokens::free_balance(AUSD, &ALICE), 1_000_000_000_000_000_000u128);
+			assert_eq!(
+				Tokens::free_balance(DOT, &DexModule::account_id()),
+				1_000_000_000_000u128
+			);
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 0);
+			assert_eq!(System::consumers(&ALICE), alice_ref_count_0 - 1);
+			assert_eq!(
+				DexModule::trading_pair_statuses(AUSD_DOT_PAIR),
+				TradingPairStatus::<_, _>::Provisioning(TradingPairProvisionParameters {
+					min_contribution: (5_000_000_000_000u128, 1_000_000_000_000u128),
+					target_provision: (5_000_000_000_000_000u128, 1_000_000_000_000_000u128),
+					accumulated_provision: (0, 1_000_000_000_000u128),
+					not_before: 10,
+				})
+			);
+			let refund_provision_event = Event::dex(crate::Event::RefundProvision(
+				ALICE,
+				AUSD,
+				5_000_000_000_000u128,
+				DOT,
+				0,
+			));
+			assert!(System::events()
+				.iter()
+				.any(|record| record.event == refund_provision_event));
+			assert_noop!(
+				DexModule::claim_refund(Origin::signed(ALICE), AUSD, DOT),
+				Error::<Runtime>::NoProvisionToRefund
+			);
+
+			// the last refund disables the trading pair
+			assert_ok!(DexModule::claim_refund(Origin::signed(BOB), AUSD, DOT));
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000_000_000_000_000u128);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 0);
+			assert_eq!(
+				DexModule::trading_pair_statuses(AUSD_DOT_PAIR),
+				TradingPairStatus::<_, _>::NotEnabled
+			);
+			let disable_trading_pair_event = Event::dex(crate::Event::DisableTradingPair(AUSD_DOT_PAIR));
+			assert!(System::events()
+				.iter()
+				.any(|record| record.event == disable_trading_pair_event));
+		});
+}
+
 #[test]
 fn get_liquidity_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -450,12 +784,20 @@ fn get_liquidity_work() {
 #[test]
 fn get_target_amount_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(DexModule::get_target_amount(10000, 0, 1000), 0);
-		assert_eq!(DexModule::get_target_amount(0, 20000, 1000), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 0), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 1, 1000000), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 10000), 9949);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000), 1801);
+		assert_eq!(DexModule::get_target_amount(10000, 0, 1000), Ok(0));
+		assert_eq!(DexModule::get_target_amount(0, 20000, 1000), Ok(0));
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 0), Ok(0));
+		assert_eq!(DexModule::get_target_amount(10000, 1, 1000000), Ok(0));
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 10000), Ok(9949));
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000), Ok(1801));
+		assert_eq!(
+			DexModule::get_target_amount(Balance::max_value(), 20000, 1000),
+			Err(Error::<Runtime>::Overflow.into())
+		);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 20000, Balance::max_value()),
+			Err(Error::<Runtime>::Overflow.into())
+		);
 	});
 }
 
@@ -467,35 +809,136 @@ fn get_supply_amount_work() {
 		assert_eq!(DexModule::get_supply_amount(10000, 20000, 0), 0);
 		assert_eq!(DexModule::get_supply_amount(10000, 1, 1), 0);
 		assert_eq!(DexModule::get_supply_amount(10000, 20000, 9949), 9999);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 9999), 9949);
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 9999), Ok(9949));
 		assert_eq!(DexModule::get_supply_amount(10000, 20000, 1801), 1000);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000), 1801);
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000), Ok(1801));
 	});
 }
 
 #[test]
-fn get_target_amounts_work() {
-	ExtBuilder::default()
-		.initialize_enabled_trading_pairs()
-		.build()
-		.execute_with(|| {
-			LiquidityPool::<Runtime>::insert(AUSD_DOT_PAIR, (50000, 10000));
-			LiquidityPool::<Runtime>::insert(AUSD_XBTC_PAIR, (100000, 10));
-			assert_noop!(
-				DexModule::get_target_amounts(&vec![DOT], 10000, None),
-				Error::<Runtime>::InvalidTradingPathLength,
-			);
-			assert_noop!(
-				DexModule::get_target_amounts(&vec![DOT, AUSD, XBTC, DOT], 10000, None),
-				Error::<Runtime>::InvalidTradingPathLength,
-			);
-			assert_noop!(
-				DexModule::get_target_amounts(&vec![DOT, AUSD, ACA], 10000, None),
-				Error::<Runtime>::MustBeEnabled,
-			);
-			assert_eq!(
-				DexModule::get_target_amounts(&vec![DOT, AUSD], 10000, None),
-				Ok(vec![10000, 24874])
+fn set_exchange_fee_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(DexModule::get_exchange_fee(), (1, 100));
+		assert_noop!(DexModule::set_exchange_fee(Origin::signed(ALICE), 3, 1000), BadOrigin);
+		assert_noop!(
+			DexModule::set_exchange_fee(Origin::signed(ListingOrigin::get()), 1000, 1000),
+			Error::<Runtime>::InvalidExchangeFee
+		);
+
+		assert_ok!(DexModule::set_exchange_fee(
+			Origin::signed(ListingOrigin::get()),
+			3,
+			1000
+		));
+		let event = Event::dex(crate::Event::ExchangeFeeUpdated(3, 1000));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(DexModule::get_exchange_fee(), (3, 1000));
+		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000), Ok(1813));
+	});
+}
+
+#[test]
+fn check_invariants_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(DexModule::check_invariants());
+
+		ExchangeFee::<Runtime>::put((1000, 1000));
+		assert_noop!(DexModule::check_invariants(), Error::<Runtime>::InvalidExchangeFee);
+
+		ExchangeFee::<Runtime>::put((3, 1000));
+		assert_ok!(DexModule::check_invariants());
+	});
+}
+
+#[test]
+fn set_referrer_fee_share_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(DexModule::referrer_fee_share(), Permill::zero());
+		assert_noop!(
+			DexModule::set_referrer_fee_share(Origin::signed(ALICE), Permill::from_percent(50)),
+			BadOrigin
+		);
+
+		assert_ok!(DexModule::set_referrer_fee_share(
+			Origin::signed(ListingOrigin::get()),
+			Permill::from_percent(50)
+		));
+		let event = Event::dex(crate::Event::ReferrerFeeShareUpdated(Permill::from_percent(50)));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(DexModule::referrer_fee_share(), Permill::from_percent(50));
+	});
+}
+
+#[test]
+fn set_max_price_impact_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(DexModule::max_price_impacts(AUSD_DOT_PAIR), None);
+		assert_noop!(
+			DexModule::set_max_price_impact(Origin::signed(ALICE), AUSD, DOT, Ratio::checked_from_rational(10, 100)),
+			BadOrigin
+		);
+		assert_noop!(
+			DexModule::set_max_price_impact(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				AUSD,
+				Ratio::checked_from_rational(10, 100)
+			),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+
+		assert_ok!(DexModule::set_max_price_impact(
+			Origin::signed(ListingOrigin::get()),
+			DOT,
+			AUSD,
+			Ratio::checked_from_rational(10, 100)
+		));
+		let event = Event::dex(crate::Event::MaxPriceImpactUpdated(
+			AUSD_DOT_PAIR,
+			Ratio::checked_from_rational(10, 100),
+		));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(
+			DexModule::max_price_impacts(AUSD_DOT_PAIR),
+			Ratio::checked_from_rational(10, 100)
+		);
+
+		assert_ok!(DexModule::set_max_price_impact(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			None
+		));
+		assert_eq!(DexModule::max_price_impacts(AUSD_DOT_PAIR), None);
+	});
+}
+
+#[test]
+fn get_target_amounts_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			LiquidityPool::<Runtime>::insert(AUSD_DOT_PAIR, (50000, 10000));
+			LiquidityPool::<Runtime>::insert(AUSD_XBTC_PAIR, (100000, 10));
+			assert_noop!(
+				DexModule::get_target_amounts(&vec![DOT], 10000, None),
+				Error::<Runtime>::InvalidTradingPathLength,
+			);
+			assert_noop!(
+				DexModule::get_target_amounts(&vec![DOT, AUSD, XBTC, DOT], 10000, None),
+				Error::<Runtime>::InvalidTradingPathLength,
+			);
+			assert_noop!(
+				DexModule::get_target_amounts(&vec![DOT, AUSD, ACA], 10000, None),
+				Error::<Runtime>::MustBeEnabled,
+			);
+			assert_eq!(
+				DexModule::get_target_amounts(&vec![DOT, AUSD], 10000, None),
+				Ok(vec![10000, 24874])
 			);
 			assert_eq!(
 				DexModule::get_target_amounts(&vec![DOT, AUSD], 10000, Ratio::checked_from_rational(50, 100)),
@@ -541,7 +984,7 @@ fn calculate_amount_for_big_number_work() {
 				56_000_000_000_000_000_000_000,
 				3_140_495_867_768_595_041_323
 			),
-			1_000_000_000_000_000_000_000
+			Ok(1_000_000_000_000_000_000_000)
 		);
 	});
 }
@@ -616,14 +1059,92 @@ fn _swap_by_path_work() {
 
 			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (50000, 10000));
 			assert_eq!(DexModule::get_liquidity(AUSD, XBTC), (100000, 10));
-			DexModule::_swap_by_path(&vec![DOT, AUSD], &vec![10000, 25000]);
+			DexModule::_swap_by_path(&vec![DOT, AUSD], &vec![10000, 25000], None);
 			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (25000, 20000));
-			DexModule::_swap_by_path(&vec![DOT, AUSD, XBTC], &vec![4000, 10000, 2]);
+			DexModule::_swap_by_path(&vec![DOT, AUSD, XBTC], &vec![4000, 10000, 2], None);
 			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (15000, 24000));
 			assert_eq!(DexModule::get_liquidity(AUSD, XBTC), (110000, 8));
 		});
 }
 
+#[test]
+fn record_volume_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			LiquidityPool::<Runtime>::insert(AUSD_DOT_PAIR, (500000, 100000));
+
+			DexModule::_swap_by_path(&vec![DOT, AUSD], &vec![10000, 40000], None);
+			DexModule::_swap_by_path(&vec![AUSD, DOT], &vec![1000, 200], None);
+			let period_0 = VolumeRecord {
+				volume_0: 1000,
+				volume_1: 10000,
+				fee_0: 10,
+				fee_1: 100,
+			};
+			assert_eq!(DexModule::cumulative_volumes(AUSD_DOT_PAIR), period_0);
+			assert_eq!(DexModule::period_volume(AUSD_DOT_PAIR, 0), Some(period_0));
+			assert_eq!(DexModule::rolling_volume(AUSD_DOT_PAIR), period_0);
+			assert_eq!(DexModule::volume_history(AUSD_DOT_PAIR), vec![period_0]);
+
+			// half of the previous period is still in the rolling window
+			System::set_block_number(15);
+			DexModule::_swap_by_path(&vec![DOT, AUSD], &vec![2000, 8000], None);
+			let period_1 = VolumeRecord {
+				volume_0: 0,
+				volume_1: 2000,
+				fee_0: 0,
+				fee_1: 20,
+			};
+			assert_eq!(
+				DexModule::rolling_volume(AUSD_DOT_PAIR),
+				VolumeRecord {
+					volume_0: 500,
+					volume_1: 7000,
+					fee_0: 5,
+					fee_1: 70,
+				}
+			);
+			assert_eq!(DexModule::volume_history(AUSD_DOT_PAIR), vec![period_1, period_0]);
+
+			// the bucket of period 0 is overwritten by period 3
+			System::set_block_number(35);
+			DexModule::_swap_by_path(&vec![AUSD, DOT], &vec![3000, 500], None);
+			let period_3 = VolumeRecord {
+				volume_0: 3000,
+				volume_1: 0,
+				fee_0: 30,
+				fee_1: 0,
+			};
+			assert_eq!(DexModule::period_volume(AUSD_DOT_PAIR, 0), None);
+			assert_eq!(DexModule::rolling_volume(AUSD_DOT_PAIR), period_3);
+			assert_eq!(
+				DexModule::volume_history(AUSD_DOT_PAIR),
+				vec![period_3, Default::default(), period_1]
+			);
+			assert_eq!(
+				DexModule::cumulative_volumes(AUSD_DOT_PAIR),
+				VolumeRecord {
+					volume_0: 4000,
+					volume_1: 12000,
+					fee_0: 40,
+					fee_1: 120,
+				}
+			);
+
+			// the volume history is pruned when the trading pair is disabled
+			assert_ok!(DexModule::disable_trading_pair(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT
+			));
+			assert_eq!(VolumeBuckets::<Runtime>::iter_prefix(AUSD_DOT_PAIR).count(), 0);
+			assert_eq!(DexModule::cumulative_volumes(AUSD_DOT_PAIR).volume_0, 4000);
+		});
+}
+
 #[test]
 fn add_liquidity_work() {
 	ExtBuilder::default()
@@ -925,6 +1446,8 @@ fn do_swap_with_exact_supply_work() {
 					&[DOT, AUSD],
 					100_000_000_000_000,
 					250_000_000_000_000,
+					None,
+					None,
 					None
 				),
 				Error::<Runtime>::InsufficientTargetAmount
@@ -935,16 +1458,26 @@ fn do_swap_with_exact_supply_work() {
 					&[DOT, AUSD],
 					100_000_000_000_000,
 					0,
-					Ratio::checked_from_rational(10, 100)
+					Ratio::checked_from_rational(10, 100),
+					None,
+					None
 				),
 				Error::<Runtime>::ExceedPriceImpactLimit,
 			);
 			assert_noop!(
-				DexModule::do_swap_with_exact_supply(&BOB, &[DOT, AUSD, XBTC, DOT], 100_000_000_000_000, 0, None),
+				DexModule::do_swap_with_exact_supply(
+					&BOB,
+					&[DOT, AUSD, XBTC, DOT],
+					100_000_000_000_000,
+					0,
+					None,
+					None,
+					None
+				),
 				Error::<Runtime>::InvalidTradingPathLength,
 			);
 			assert_noop!(
-				DexModule::do_swap_with_exact_supply(&BOB, &[DOT, ACA], 100_000_000_000_000, 0, None),
+				DexModule::do_swap_with_exact_supply(&BOB, &[DOT, ACA], 100_000_000_000_000, 0, None, None, None),
 				Error::<Runtime>::MustBeEnabled,
 			);
 
@@ -953,6 +1486,8 @@ fn do_swap_with_exact_supply_work() {
 				&[DOT, AUSD],
 				100_000_000_000_000,
 				200_000_000_000_000,
+				None,
+				None,
 				None
 			));
 			let swap_event_1 = Event::dex(crate::Event::Swap(
@@ -986,6 +1521,8 @@ fn do_swap_with_exact_supply_work() {
 				&[DOT, AUSD, XBTC],
 				200_000_000_000_000,
 				1,
+				None,
+				None,
 				None
 			));
 			let swap_event_2 = Event::dex(crate::Event::Swap(
@@ -1065,6 +1602,8 @@ fn do_swap_with_exact_target_work() {
 					&[DOT, AUSD],
 					250_000_000_000_000,
 					100_000_000_000_000,
+					None,
+					None,
 					None
 				),
 				Error::<Runtime>::ExcessiveSupplyAmount
@@ -1075,7 +1614,9 @@ fn do_swap_with_exact_target_work() {
 					&[DOT, AUSD],
 					250_000_000_000_000,
 					200_000_000_000_000,
-					Ratio::checked_from_rational(10, 100)
+					Ratio::checked_from_rational(10, 100),
+					None,
+					None
 				),
 				Error::<Runtime>::ExceedPriceImpactLimit,
 			);
@@ -1085,12 +1626,22 @@ fn do_swap_with_exact_target_work() {
 					&[DOT, AUSD, XBTC, DOT],
 					250_000_000_000_000,
 					200_000_000_000_000,
+					None,
+					None,
 					None
 				),
 				Error::<Runtime>::InvalidTradingPathLength,
 			);
 			assert_noop!(
-				DexModule::do_swap_with_exact_target(&BOB, &[DOT, ACA], 250_000_000_000_000, 200_000_000_000_000, None),
+				DexModule::do_swap_with_exact_target(
+					&BOB,
+					&[DOT, ACA],
+					250_000_000_000_000,
+					200_000_000_000_000,
+					None,
+					None,
+					None
+				),
 				Error::<Runtime>::MustBeEnabled,
 			);
 
@@ -1099,6 +1650,8 @@ fn do_swap_with_exact_target_work() {
 				&[DOT, AUSD],
 				250_000_000_000_000,
 				200_000_000_000_000,
+				None,
+				None,
 				None
 			));
 			let swap_event_1 = Event::dex(crate::Event::Swap(
@@ -1132,6 +1685,8 @@ fn do_swap_with_exact_target_work() {
 				&[DOT, AUSD, XBTC],
 				5_000_000_000,
 				2_000_000_000_000_000,
+				None,
+				None,
 				None
 			));
 			let swap_event_2 = Event::dex(crate::Event::Swap(
@@ -1163,20 +1718,831 @@ fn do_swap_with_exact_target_work() {
 }
 
 #[test]
-fn initialize_added_liquidity_pools_genesis_work() {
+fn do_swap_basket_for_exact_target_work() {
 	ExtBuilder::default()
 		.initialize_enabled_trading_pairs()
-		.initialize_added_liquidity_pools(ALICE)
 		.build()
 		.execute_with(|| {
 			System::set_block_number(1);
 
-			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1000000, 2000000));
-			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 2000000);
-			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 3000000);
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				100_000,
+				100_000,
+				false,
+			));
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				XBTC,
+				100_000,
+				100_000,
+				false,
+			));
+
+			assert_noop!(
+				DexModule::do_swap_basket_for_exact_target(&BOB, &[], AUSD, 15_000),
+				Error::<Runtime>::InvalidBasketLength
+			);
+			assert_noop!(
+				DexModule::do_swap_basket_for_exact_target(
+					&BOB,
+					&[(DOT, 1_000), (XBTC, 1_000), (DOT, 1_000), (XBTC, 1_000)],
+					AUSD,
+					15_000
+				),
+				Error::<Runtime>::InvalidBasketLength
+			);
+			assert_noop!(
+				DexModule::do_swap_basket_for_exact_target(&BOB, &[(AUSD, 10_000)], AUSD, 15_000),
+				Error::<Runtime>::InvalidCurrencyId
+			);
+			// the input without enabled trading pair and the dust input are skipped
+			assert_noop!(
+				DexModule::do_swap_basket_for_exact_target(&BOB, &[(ACA, 10_000), (DOT, 1)], AUSD, 15_000),
+				Error::<Runtime>::InsufficientTargetAmount
+			);
+			assert_noop!(
+				DexModule::do_swap_basket_for_exact_target(&BOB, &[(DOT, 10_000), (XBTC, 1_000)], AUSD, 15_000),
+				Error::<Runtime>::InsufficientTargetAmount
+			);
+
 			assert_eq!(
-				Tokens::free_balance(AUSD_DOT_PAIR.get_dex_share_currency_id().unwrap(), &ALICE),
-				4000000
+				DexModule::do_swap_basket_for_exact_target(
+					&BOB,
+					&[(ACA, 10_000), (DOT, 10_000), (XBTC, 10_000)],
+					AUSD,
+					15_000
+				),
+				Ok(vec![(DOT, 10_000), (XBTC, 6_439)])
+			);
+			let basket_swap_event = Event::dex(crate::Event::BasketSwap(
+				BOB,
+				vec![(DOT, 10_000), (XBTC, 6_439)],
+				AUSD,
+				15_000,
+			));
+			assert!(System::events().iter().any(|record| record.event == basket_swap_event));
+
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (90_992, 110_000));
+			assert_eq!(DexModule::get_liquidity(AUSD, XBTC), (94_008, 106_439));
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_000_000_000_015_000);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 999_999_999_999_990_000);
+			assert_eq!(Tokens::free_balance(XBTC, &BOB), 999_999_999_999_993_561);
+		});
+}
+
+#[test]
+fn swap_with_max_price_impact_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				500_000_000_000_000,
+				100_000_000_000_000,
+				false,
+			));
+			assert_ok!(DexModule::set_max_price_impact(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Ratio::checked_from_rational(10, 100)
+			));
+
+			// the default of the trading pair rejects a large swap
+			assert_noop!(
+				DexModule::swap_with_exact_supply(
+					Origin::signed(BOB),
+					vec![DOT, AUSD],
+					10_000_000_000_000,
+					0,
+					None,
+					None,
+				),
+				Error::<Runtime>::ExceedMaxPriceImpact
+			);
+			assert_noop!(
+				DexModule::swap_with_exact_target(
+					Origin::signed(BOB),
+					vec![DOT, AUSD],
+					50_000_000_000_000,
+					100_000_000_000_000,
+					None,
+					None,
+				),
+				Error::<Runtime>::ExceedMaxPriceImpact
+			);
+
+			// the stricter limit of the swap applies
+			assert_noop!(
+				DexModule::swap_with_exact_supply(
+					Origin::signed(BOB),
+					vec![DOT, AUSD],
+					1_000_000_000_000,
+					0,
+					Ratio::checked_from_rational(1, 100),
+					None,
+				),
+				Error::<Runtime>::ExceedMaxPriceImpact
+			);
+			assert_ok!(DexModule::swap_with_exact_supply(
+				Origin::signed(BOB),
+				vec![DOT, AUSD],
+				1_000_000_000_000,
+				0,
+				Ratio::checked_from_rational(5, 100),
+				None,
+			));
+
+			// a looser limit of the swap doesn't override the default
+			assert_noop!(
+				DexModule::swap_with_exact_supply(
+					Origin::signed(BOB),
+					vec![DOT, AUSD],
+					10_000_000_000_000,
+					0,
+					Ratio::checked_from_rational(50, 100),
+					None,
+				),
+				Error::<Runtime>::ExceedMaxPriceImpact
+			);
+		});
+}
+
+#[test]
+fn split_swaps_in_block_limited_by_default_max_price_impact() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				500_000_000_000_000,
+				100_000_000_000_000,
+				false,
+			));
+			assert_ok!(DexModule::set_max_price_impact(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Ratio::checked_from_rational(10, 100)
+			));
+
+			assert_ok!(DexModule::swap_with_exact_supply(
+				Origin::signed(BOB),
+				vec![DOT, AUSD],
+				5_000_000_000_000,
+				0,
+				None,
+				None,
+			));
+			assert_eq!(
+				DexModule::block_start_prices(AUSD_DOT_PAIR),
+				Some((1, Price::saturating_from_rational(1, 5)))
+			);
+
+			// the price impact is accumulated from the first swap of the block
+			assert_noop!(
+				DexModule::swap_with_exact_supply(
+					Origin::signed(BOB),
+					vec![DOT, AUSD],
+					5_000_000_000_000,
+					0,
+					None,
+					None,
+				),
+				Error::<Runtime>::ExceedMaxPriceImpact
+			);
+
+			System::set_block_number(2);
+			assert_ok!(DexModule::swap_with_exact_supply(
+				Origin::signed(BOB),
+				vec![DOT, AUSD],
+				5_000_000_000_000,
+				0,
+				None,
+				None,
+			));
+			assert_eq!(
+				DexModule::block_start_prices(AUSD_DOT_PAIR).map(|(block, _)| block),
+				Some(2)
 			);
+
+			assert_ok!(DexModule::set_max_price_impact(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				None
+			));
+			assert_eq!(DexModule::block_start_prices(AUSD_DOT_PAIR), None);
 		});
 }
+
+#[test]
+fn stable_swap_max_price_impact_by_spot_price() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DexModule::enable_stable_swap_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			100
+		));
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			100_000_000,
+			1_000_000,
+			false,
+		));
+		assert_ok!(DexModule::set_max_price_impact(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			Ratio::checked_from_rational(1, 100)
+		));
+
+		// the spot price of the stable swap pool barely moves, though the ratio
+		// of the pool moves by 20%
+		assert_noop!(
+			DexModule::swap_with_exact_supply(
+				Origin::signed(BOB),
+				vec![DOT, AUSD],
+				100_000,
+				0,
+				Ratio::checked_from_rational(1, 10_000),
+				None,
+			),
+			Error::<Runtime>::ExceedMaxPriceImpact
+		);
+		assert_ok!(DexModule::swap_with_exact_supply(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			100_000,
+			0,
+			None,
+			None,
+		));
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (90_104_922, 1_100_000));
+	});
+}
+
+#[test]
+fn swap_with_referrer_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				500_000_000_000_000,
+				100_000_000_000_000,
+				false,
+			));
+			assert_ok!(DexModule::set_referrer_fee_share(
+				Origin::signed(ListingOrigin::get()),
+				Permill::from_percent(50)
+			));
+
+			// self-referral earns nothing
+			assert_ok!(DexModule::swap_with_exact_supply(
+				Origin::signed(BOB),
+				vec![DOT, AUSD],
+				10_000_000_000_000,
+				0,
+				None,
+				Some(BOB),
+			));
+			assert_eq!(DexModule::referrer_rewards(BOB, DOT), 0);
+
+			let (_, pool_dot) = DexModule::get_liquidity(AUSD, DOT);
+			assert_ok!(DexModule::swap_with_exact_supply(
+				Origin::signed(BOB),
+				vec![DOT, AUSD],
+				100_000_000_000_000,
+				0,
+				None,
+				Some(ALICE),
+			));
+			let event = Event::dex(crate::Event::ReferrerRewarded(ALICE, DOT, 500_000_000_000));
+			assert!(System::events().iter().any(|record| record.event == event));
+			assert_eq!(DexModule::referrer_rewards(ALICE, DOT), 500_000_000_000);
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT).1,
+				pool_dot + 100_000_000_000_000 - 500_000_000_000
+			);
+
+			assert_noop!(
+				DexModule::claim_referrer_rewards(Origin::signed(ALICE), AUSD),
+				Error::<Runtime>::NoReferrerRewards
+			);
+			let alice_dot = Tokens::free_balance(DOT, &ALICE);
+			assert_ok!(DexModule::claim_referrer_rewards(Origin::signed(ALICE), DOT));
+			let event = Event::dex(crate::Event::ClaimReferrerRewards(ALICE, DOT, 500_000_000_000));
+			assert!(System::events().iter().any(|record| record.event == event));
+			assert_eq!(DexModule::referrer_rewards(ALICE, DOT), 0);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), alice_dot + 500_000_000_000);
+			assert_eq!(
+				Tokens::free_balance(DOT, &DexModule::account_id()),
+				DexModule::get_liquidity(AUSD, DOT).1
+			);
+		});
+}
+
+#[test]
+fn stable_swap_pool_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DexModule::enable_stable_swap_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			100
+		));
+
+		// the initial shares is the invariant of the pool, the balance of DOT is
+		// normalized to the decimals of AUSD
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			100_000_000,
+			1_000_000,
+			false,
+		));
+		let lp_share_currency_id = AUSD_DOT_PAIR.get_dex_share_currency_id().unwrap();
+		assert_eq!(Tokens::free_balance(lp_share_currency_id, &ALICE), 200_000_000);
+
+		// lower slippage than the constant product invariant
+		assert_eq!(
+			DexModule::get_target_amount(1_000_000, 100_000_000, 100_000),
+			Ok(9_008_189)
+		);
+		assert_eq!(
+			DexModule::get_target_amounts(&vec![DOT, AUSD], 100_000, None),
+			Ok(vec![100_000, 9_895_078])
+		);
+		assert_eq!(
+			DexModule::get_supply_amounts(&vec![DOT, AUSD], 10_000_000, None),
+			Ok(vec![101_063, 10_000_000])
+		);
+
+		assert_ok!(DexModule::swap_with_exact_supply(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			100_000,
+			9_895_078,
+			None,
+			None,
+		));
+		let swap_event = Event::dex(crate::Event::Swap(BOB, vec![DOT, AUSD], 100_000, 9_895_078));
+		assert!(System::events().iter().any(|record| record.event == swap_event));
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (90_104_922, 1_100_000));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_000_000_000_000_000 + 9_895_078);
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000_000_000_000_000 - 100_000);
+	});
+}
+
+#[test]
+fn stable_swap_amplification_ramp_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DexModule::enable_stable_swap_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			100
+		));
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			100_000_000,
+			1_000_000,
+			false,
+		));
+		assert_ok!(DexModule::disable_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT
+		));
+
+		// the amplification of the non-empty pool can not jump
+		assert_noop!(
+			DexModule::enable_stable_swap_trading_pair(Origin::signed(ListingOrigin::get()), AUSD, DOT, 1_001),
+			Error::<Runtime>::InvalidAmplification
+		);
+		assert_noop!(
+			DexModule::enable_stable_swap_trading_pair(Origin::signed(ListingOrigin::get()), AUSD, DOT, 9),
+			Error::<Runtime>::InvalidAmplification
+		);
+
+		assert_ok!(DexModule::enable_stable_swap_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			200
+		));
+		let parameters = StableSwapParameters {
+			initial_amplification: 100,
+			future_amplification: 200,
+			ramp_start: 1,
+			ramp_end: 1 + AmplificationRampPeriod::get(),
+			precision_multipliers: (1, 100),
+		};
+		assert_eq!(DexModule::pool_types(AUSD_DOT_PAIR), PoolType::StableSwap(parameters));
+		assert_eq!(parameters.amplification(1), 100);
+		assert_eq!(parameters.amplification(6), 150);
+		assert_eq!(parameters.amplification(11), 200);
+		assert_eq!(parameters.amplification(20), 200);
+
+		// ramp down from the current amplification
+		System::set_block_number(6);
+		assert_ok!(DexModule::disable_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT
+		));
+		assert_ok!(DexModule::enable_stable_swap_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			100
+		));
+		let parameters = StableSwapParameters {
+			initial_amplification: 150,
+			future_amplification: 100,
+			ramp_start: 6,
+			ramp_end: 6 + AmplificationRampPeriod::get(),
+			precision_multipliers: (1, 100),
+		};
+		assert_eq!(DexModule::pool_types(AUSD_DOT_PAIR), PoolType::StableSwap(parameters));
+		assert_eq!(parameters.amplification(11), 125);
+		assert_eq!(parameters.amplification(16), 100);
+	});
+}
+
+#[test]
+fn enable_range_trading_pair_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let range_parameters = RangeParameters {
+			min_price: Price::saturating_from_integer(1),
+			price_step: Price::saturating_from_integer(1),
+			bucket_count: 4,
+		};
+
+		assert_noop!(
+			DexModule::enable_range_trading_pair(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				Price::saturating_from_integer(1),
+				Price::saturating_from_integer(1),
+				4,
+				1
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			DexModule::enable_range_trading_pair(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Price::zero(),
+				Price::saturating_from_integer(1),
+				4,
+				1
+			),
+			Error::<Runtime>::InvalidRangeParameters
+		);
+		assert_noop!(
+			DexModule::enable_range_trading_pair(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Price::saturating_from_integer(1),
+				Price::saturating_from_integer(1),
+				11,
+				1
+			),
+			Error::<Runtime>::InvalidRangeParameters
+		);
+		assert_noop!(
+			DexModule::enable_range_trading_pair(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Price::saturating_from_integer(1),
+				Price::saturating_from_integer(1),
+				4,
+				4
+			),
+			Error::<Runtime>::InvalidRangeParameters
+		);
+
+		assert_ok!(DexModule::enable_range_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			Price::saturating_from_integer(1),
+			Price::saturating_from_integer(1),
+			4,
+			1
+		));
+		assert_eq!(
+			DexModule::trading_pair_statuses(AUSD_DOT_PAIR),
+			TradingPairStatus::<_, _>::Enabled
+		);
+		assert_eq!(
+			DexModule::pool_types(AUSD_DOT_PAIR),
+			PoolType::ConcentratedLiquidity(range_parameters)
+		);
+		assert_eq!(DexModule::current_buckets(AUSD_DOT_PAIR), 1);
+		let enable_event = Event::dex(crate::Event::EnableRangeTradingPair(AUSD_DOT_PAIR, range_parameters));
+		assert!(System::events().iter().any(|record| record.event == enable_event));
+
+		assert_noop!(
+			DexModule::enable_range_trading_pair(
+				Origin::signed(ListingOrigin::get()),
+				DOT,
+				AUSD,
+				Price::saturating_from_integer(1),
+				Price::saturating_from_integer(1),
+				4,
+				1
+			),
+			Error::<Runtime>::MustBeNotEnabled
+		);
+		assert_noop!(
+			DexModule::add_liquidity(Origin::signed(ALICE), AUSD, DOT, 1_000_000, 1_000_000, false),
+			Error::<Runtime>::RangeTradingPairNotAllowed
+		);
+
+		// the buckets of the non-empty pool can not be changed
+		assert_ok!(DexModule::add_range_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			0,
+			3,
+			12_000,
+			1_000_000,
+			1_000_000
+		));
+		assert_ok!(DexModule::disable_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT
+		));
+		assert_noop!(
+			DexModule::enable_range_trading_pair(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Price::saturating_from_integer(2),
+				Price::saturating_from_integer(1),
+				4,
+				1
+			),
+			Error::<Runtime>::LiquidityPoolNotEmpty
+		);
+	});
+}
+
+#[test]
+fn range_liquidity_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(DexModule::enable_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			XBTC
+		));
+		assert_noop!(
+			DexModule::add_range_liquidity(Origin::signed(ALICE), AUSD, XBTC, 0, 3, 12_000, 1_000_000, 1_000_000),
+			Error::<Runtime>::NotRangeTradingPair
+		);
+
+		// the prices of buckets are 1, 2, 3, 4 and the current bucket is 1
+		assert_ok!(DexModule::enable_range_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			Price::saturating_from_integer(1),
+			Price::saturating_from_integer(1),
+			4,
+			1
+		));
+		assert_noop!(
+			DexModule::add_range_liquidity(Origin::signed(ALICE), AUSD, DOT, 2, 1, 12_000, 1_000_000, 1_000_000),
+			Error::<Runtime>::InvalidBucketRange
+		);
+		assert_noop!(
+			DexModule::add_range_liquidity(Origin::signed(ALICE), AUSD, DOT, 0, 4, 12_000, 1_000_000, 1_000_000),
+			Error::<Runtime>::InvalidBucketRange
+		);
+
+		// buckets above the current bucket only hold AUSD, the others only hold DOT
+		assert_ok!(DexModule::add_range_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			0,
+			3,
+			12_000,
+			1_000_000,
+			1_000_000
+		));
+		let add_range_liquidity_event = Event::dex(crate::Event::AddRangeLiquidity(ALICE, 0, 7_000, 24_000, 12_000));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == add_range_liquidity_event));
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (7_000, 24_000));
+		// the liquidity of the current bucket valued at price 2
+		assert_eq!(
+			<DexModule as DEXManager<AccountId, CurrencyId, Balance>>::get_liquidity_pool(AUSD, DOT),
+			(6_000, 12_000)
+		);
+		assert_eq!(
+			DexModule::range_buckets(AUSD_DOT_PAIR, 2),
+			RangeBucket {
+				reserve_0: 4_000,
+				reserve_1: 0,
+				total_shares: 12_000,
+			}
+		);
+		assert_eq!(
+			DexModule::range_positions(0),
+			Some(RangePosition {
+				trading_pair: AUSD_DOT_PAIR,
+				lower_bucket: 0,
+				upper_bucket: 3,
+				shares: 12_000,
+			})
+		);
+		assert_eq!(MockPositionNFT::position_owner(DEXPalletId::get(), 0), Some(ALICE));
+
+		// drain bucket 2 at price 3, and partially fill bucket 3 at price 4
+		assert_eq!(
+			DexModule::get_target_amounts(&vec![DOT, AUSD], 15_000, None),
+			Ok(vec![15_000, 4_712])
+		);
+		assert_ok!(DexModule::swap_with_exact_supply(
+			Origin::signed(BOB),
+			vec![DOT, AUSD],
+			15_000,
+			4_712,
+			None,
+			None,
+		));
+		assert_eq!(DexModule::current_buckets(AUSD_DOT_PAIR), 3);
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (2_288, 39_000));
+		assert_eq!(
+			<DexModule as DEXManager<AccountId, CurrencyId, Balance>>::get_liquidity_pool(DOT, AUSD),
+			(12_030, 3_007)
+		);
+		assert_eq!(
+			DexModule::range_buckets(AUSD_DOT_PAIR, 2),
+			RangeBucket {
+				reserve_0: 0,
+				reserve_1: 12_122,
+				total_shares: 12_000,
+			}
+		);
+		assert_eq!(
+			DexModule::range_buckets(AUSD_DOT_PAIR, 3),
+			RangeBucket {
+				reserve_0: 2_288,
+				reserve_1: 2_878,
+				total_shares: 12_000,
+			}
+		);
+		assert_eq!(
+			DexModule::get_supply_amounts(&vec![DOT, AUSD], 1_000, None),
+			Ok(vec![4_041, 1_000])
+		);
+
+		// add liquidity in proportion to the reserves of buckets
+		assert_noop!(
+			DexModule::add_range_liquidity(Origin::signed(BOB), AUSD, DOT, 2, 3, 6_000, 1_143, 1_000_000),
+			Error::<Runtime>::ExcessiveRangeLiquidityAmount
+		);
+		assert_ok!(DexModule::add_range_liquidity(
+			Origin::signed(BOB),
+			DOT,
+			AUSD,
+			2,
+			3,
+			6_000,
+			7_500,
+			1_144
+		));
+		let add_range_liquidity_event = Event::dex(crate::Event::AddRangeLiquidity(BOB, 1, 1_144, 7_500, 6_000));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == add_range_liquidity_event));
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (3_432, 46_500));
+
+		// only the owner of the position NFT can remove the position
+		assert_noop!(
+			DexModule::remove_range_liquidity(Origin::signed(ALICE), 2),
+			Error::<Runtime>::RangePositionNotFound
+		);
+		MockPositionNFT::transfer(0, BOB);
+		assert_noop!(
+			DexModule::remove_range_liquidity(Origin::signed(ALICE), 0),
+			Error::<Runtime>::NotRangePositionOwner
+		);
+
+		assert_ok!(DexModule::remove_range_liquidity(Origin::signed(BOB), 0));
+		let remove_range_liquidity_event =
+			Event::dex(crate::Event::RemoveRangeLiquidity(BOB, 0, 2_287, 39_998, 12_000));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == remove_range_liquidity_event));
+		assert_eq!(DexModule::range_positions(0), None);
+		assert_eq!(MockPositionNFT::position_owner(DEXPalletId::get(), 0), None);
+		assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1_145, 6_502));
+		assert_eq!(
+			DexModule::range_buckets(AUSD_DOT_PAIR, 0),
+			RangeBucket {
+				reserve_0: 0,
+				reserve_1: 0,
+				total_shares: 0,
+			}
+		);
+	});
+}
+
+#[test]
+fn initialize_added_liquidity_pools_genesis_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.initialize_added_liquidity_pools(ALICE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1000000, 2000000));
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 2000000);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 3000000);
+			assert_eq!(
+				Tokens::free_balance(AUSD_DOT_PAIR.get_dex_share_currency_id().unwrap(), &ALICE),
+				4000000
+			);
+		});
+}
+
+#[test]
+fn genesis_trading_pairs_record_erc20_references() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			let references = erc20_references();
+			assert_eq!(references.len(), 6);
+			assert!(references.contains(&(DEXPalletId::get(), AUSD)));
+			assert!(references.contains(&(DEXPalletId::get(), DOT)));
+			assert!(references.contains(&(DEXPalletId::get(), XBTC)));
+			assert!(UpgradedToErc20References::<Runtime>::get());
+
+			// genesis trading pairs are not referenced again
+			DexModule::on_runtime_upgrade();
+			assert_eq!(erc20_references().len(), 6);
+		});
+}
+
+#[test]
+fn on_runtime_upgrade_records_erc20_references() {
+	ExtBuilder::default().build().execute_with(|| {
+		UpgradedToErc20References::<Runtime>::kill();
+		TradingPairStatuses::<Runtime>::insert(AUSD_DOT_PAIR, TradingPairStatus::<_, _>::Enabled);
+		TradingPairStatuses::<Runtime>::insert(AUSD_XBTC_PAIR, TradingPairStatus::<_, _>::NotEnabled);
+
+		DexModule::on_runtime_upgrade();
+		let references = erc20_references();
+		assert_eq!(references.len(), 2);
+		assert!(references.contains(&(DEXPalletId::get(), AUSD)));
+		assert!(references.contains(&(DEXPalletId::get(), DOT)));
+		assert!(UpgradedToErc20References::<Runtime>::get());
+
+		// only migrated once
+		DexModule::on_runtime_upgrade();
+		assert_eq!(erc20_references().len(), 2);
+	});
+}
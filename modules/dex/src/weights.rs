@@ -48,14 +48,27 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for module_dex.
 pub trait WeightInfo {
 	fn enable_trading_pair() -> Weight;
+	fn enable_stable_swap_trading_pair() -> Weight;
+	fn enable_range_trading_pair() -> Weight;
+	fn add_range_liquidity() -> Weight;
+	fn remove_range_liquidity() -> Weight;
 	fn disable_trading_pair() -> Weight;
 	fn list_trading_pair() -> Weight;
 	fn add_liquidity() -> Weight;
 	fn add_liquidity_and_deposit() -> Weight;
 	fn remove_liquidity() -> Weight;
 	fn remove_liquidity_by_withdraw() -> Weight;
-	fn swap_with_exact_supply(u: u32, ) -> Weight;
-	fn swap_with_exact_target(u: u32, ) -> Weight;
+	fn swap_with_exact_supply(u: u32, b: u32, ) -> Weight;
+	fn swap_with_exact_target(u: u32, b: u32, ) -> Weight;
+	fn swap_basket_for_exact_target(u: u32, ) -> Weight;
+	fn set_exchange_fee() -> Weight;
+	fn claim_refund() -> Weight;
+	fn set_referrer_fee_share() -> Weight;
+	fn claim_referrer_rewards() -> Weight;
+	fn propose_listing() -> Weight;
+	fn veto_listing() -> Weight;
+	fn on_initialize(c: u32, ) -> Weight;
+	fn set_max_price_impact() -> Weight;
 }
 
 /// Weights for module_dex using the Acala node and recommended hardware.
@@ -66,6 +79,26 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn enable_stable_swap_trading_pair() -> Weight {
+		(35_420_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn enable_range_trading_pair() -> Weight {
+		(36_218_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn add_range_liquidity() -> Weight {
+		(653_354_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(61 as Weight))
+			.saturating_add(T::DbWeight::get().writes(63 as Weight))
+	}
+	fn remove_range_liquidity() -> Weight {
+		(599_817_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(60 as Weight))
+			.saturating_add(T::DbWeight::get().writes(61 as Weight))
+	}
 	fn disable_trading_pair() -> Weight {
 		(28_920_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
@@ -96,19 +129,78 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(12 as Weight))
 			.saturating_add(T::DbWeight::get().writes(12 as Weight))
 	}
-	fn swap_with_exact_supply(u: u32, ) -> Weight {
+	fn swap_with_exact_supply(u: u32, b: u32, ) -> Weight {
 		(156_409_000 as Weight)
-			// Standard Error: 185_000
-			.saturating_add((488_000 as Weight).saturating_mul(u as Weight))
-			.saturating_add(T::DbWeight::get().reads(10 as Weight))
-			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+			// Standard Error: 412_000
+			.saturating_add((9_734_000 as Weight).saturating_mul(u as Weight))
+			// Standard Error: 31_000
+			.saturating_add((3_482_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(b as Weight)))
 	}
-	fn swap_with_exact_target(u: u32, ) -> Weight {
+	fn swap_with_exact_target(u: u32, b: u32, ) -> Weight {
 		(155_993_000 as Weight)
-			// Standard Error: 138_000
-			.saturating_add((654_000 as Weight).saturating_mul(u as Weight))
-			.saturating_add(T::DbWeight::get().reads(10 as Weight))
-			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+			// Standard Error: 412_000
+			.saturating_add((10_215_000 as Weight).saturating_mul(u as Weight))
+			// Standard Error: 31_000
+			.saturating_add((3_517_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(b as Weight)))
+	}
+	fn swap_basket_for_exact_target(u: u32, ) -> Weight {
+		(12_481_000 as Weight)
+			// Standard Error: 162_000
+			.saturating_add((153_620_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(T::DbWeight::get().reads((15 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes((12 as Weight).saturating_mul(u as Weight)))
+	}
+	fn set_exchange_fee() -> Weight {
+		(21_400_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn claim_refund() -> Weight {
+		(146_723_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn set_referrer_fee_share() -> Weight {
+		(20_912_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn claim_referrer_rewards() -> Weight {
+		(92_418_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn propose_listing() -> Weight {
+		(58_216_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn veto_listing() -> Weight {
+		(61_734_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn on_initialize(c: u32, ) -> Weight {
+		(3_412_000 as Weight)
+			// Standard Error: 41_000
+			.saturating_add((72_583_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)))
+	}
+	fn set_max_price_impact() -> Weight {
+		(21_606_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
 }
 
@@ -119,6 +211,26 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn enable_stable_swap_trading_pair() -> Weight {
+		(35_420_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn enable_range_trading_pair() -> Weight {
+		(36_218_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn add_range_liquidity() -> Weight {
+		(653_354_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(61 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(63 as Weight))
+	}
+	fn remove_range_liquidity() -> Weight {
+		(599_817_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(60 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(61 as Weight))
+	}
 	fn disable_trading_pair() -> Weight {
 		(28_920_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
@@ -149,18 +261,77 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
 	}
-	fn swap_with_exact_supply(u: u32, ) -> Weight {
+	fn swap_with_exact_supply(u: u32, b: u32, ) -> Weight {
 		(156_409_000 as Weight)
-			// Standard Error: 185_000
-			.saturating_add((488_000 as Weight).saturating_mul(u as Weight))
-			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+			// Standard Error: 412_000
+			.saturating_add((9_734_000 as Weight).saturating_mul(u as Weight))
+			// Standard Error: 31_000
+			.saturating_add((3_482_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(b as Weight)))
 	}
-	fn swap_with_exact_target(u: u32, ) -> Weight {
+	fn swap_with_exact_target(u: u32, b: u32, ) -> Weight {
 		(155_993_000 as Weight)
-			// Standard Error: 138_000
-			.saturating_add((654_000 as Weight).saturating_mul(u as Weight))
-			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+			// Standard Error: 412_000
+			.saturating_add((10_215_000 as Weight).saturating_mul(u as Weight))
+			// Standard Error: 31_000
+			.saturating_add((3_517_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(b as Weight)))
+	}
+	fn swap_basket_for_exact_target(u: u32, ) -> Weight {
+		(12_481_000 as Weight)
+			// Standard Error: 162_000
+			.saturating_add((153_620_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(RocksDbWeight::get().reads((15 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((12 as Weight).saturating_mul(u as Weight)))
+	}
+	fn set_exchange_fee() -> Weight {
+		(21_400_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn claim_refund() -> Weight {
+		(146_723_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn set_referrer_fee_share() -> Weight {
+		(20_912_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn claim_referrer_rewards() -> Weight {
+		(92_418_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn propose_listing() -> Weight {
+		(58_216_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn veto_listing() -> Weight {
+		(61_734_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn on_initialize(c: u32, ) -> Weight {
+		(3_412_000 as Weight)
+			// Standard Error: 41_000
+			.saturating_add((72_583_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)))
+	}
+	fn set_max_price_impact() -> Weight {
+		(21_606_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
 }
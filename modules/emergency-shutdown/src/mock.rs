@@ -131,6 +131,9 @@ impl Convert<(CurrencyId, Balance), Balance> for MockConvert {
 
 parameter_types! {
 	pub const LoansPalletId: PalletId = PalletId(*b"aca/loan");
+	pub const MaxPositionLabelLength: u32 = 32;
+	pub const GetStakingCurrencyId: CurrencyId = DOT;
+	pub const GetLiquidCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::LDOT);
 }
 
 impl loans::Config for Runtime {
@@ -141,6 +144,12 @@ impl loans::Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = ();
+	type MaxPositionLabelLength = MaxPositionLabelLength;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type Homa = ();
+	type StakingCurrencyId = GetStakingCurrencyId;
+	type LiquidCurrencyId = GetLiquidCurrencyId;
+	type CircuitBreaker = ();
 }
 
 pub struct MockPriceSource;
@@ -197,6 +206,12 @@ parameter_types! {
 	pub TreasuryAccount: AccountId = PalletId(*b"aca/hztr").into_account();
 }
 
+parameter_types! {
+	pub const SurplusRebalancePeriod: BlockNumber = 10;
+	pub const MaxSurplusAllocations: u32 = 5;
+	pub MaxSurplusSwapSlippage: Ratio = Ratio::saturating_from_rational(5, 100);
+}
+
 impl cdp_treasury::Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
@@ -205,6 +220,10 @@ impl cdp_treasury::Config for Runtime {
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = ();
 	type MaxAuctionsCount = MaxAuctionsCount;
+	type PriceSource = MockPriceSource;
+	type SurplusRebalancePeriod = SurplusRebalancePeriod;
+	type MaxSurplusAllocations = MaxSurplusAllocations;
+	type MaxSurplusSwapSlippage = MaxSurplusSwapSlippage;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
 	type WeightInfo = ();
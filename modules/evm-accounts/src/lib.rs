@@ -22,6 +22,10 @@
 //!
 //! Evm Accounts module provide a two way mapping between Substrate accounts and
 //! EVM accounts so user only have deal with one account / private key.
+//!
+//! An EVM address controlled by a smart-contract wallet can also be claimed,
+//! the ownership is proved by the wallet answering the ERC-1271
+//! `isValidSignature` call through the EVM bridge.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -34,10 +38,10 @@ use frame_support::{
 	transactional,
 };
 use frame_system::{ensure_signed, pallet_prelude::*};
-use module_support::AddressMapping;
+use module_support::{AddressMapping, EVMBridge, InvokeContext};
 use orml_traits::{account::MergeAccount, Handler};
 use primitives::{evm::EvmAddress, AccountIndex};
-use sp_core::{crypto::AccountId32, ecdsa};
+use sp_core::{crypto::AccountId32, ecdsa, H256};
 use sp_io::{
 	crypto::secp256k1_ecdsa_recover,
 	hashing::{blake2_256, keccak_256},
@@ -57,6 +61,12 @@ pub use weights::WeightInfo;
 
 pub type EcdsaSignature = ecdsa::Signature;
 
+type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// ERC-1271 `isValidSignature(bytes32,bytes)` method hash, which is also the
+/// magic value returned for a valid signature.
+pub const METHOD_IS_VALID_SIGNATURE: u32 = 0x1626ba7e;
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -77,6 +87,13 @@ pub mod module {
 		/// On claim account hook.
 		type OnClaim: Handler<Self::AccountId>;
 
+		/// EVM bridge for verifying signatures of contract wallets.
+		type EVMBridge: EVMBridge<Self::AccountId, BalanceOf<Self>>;
+
+		/// The chain ID of EVM signed in the claim message, so that the
+		/// signature can't be replayed on other chains.
+		type ChainId: Get<u64>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -149,20 +166,7 @@ pub mod module {
 				.ok_or(Error::<T>::BadSignature)?;
 			ensure!(eth_address == address, Error::<T>::InvalidSignature);
 
-			// check if the evm padded address already exists
-			let account_id = T::AddressMapping::get_account_id(&eth_address);
-			if frame_system::Pallet::<T>::account_exists(&account_id) {
-				// merge balance from `evm padded address` to `origin`
-				T::MergeAccount::merge_account(&account_id, &who)?;
-			}
-
-			Accounts::<T>::insert(eth_address, &who);
-			EvmAddresses::<T>::insert(&who, eth_address);
-
-			T::OnClaim::handle(&who)?;
-
-			Self::deposit_event(Event::ClaimAccount(who, eth_address));
-
+			Self::do_claim_account(who, eth_address)?;
 			Ok(().into())
 		}
 
@@ -184,25 +188,126 @@ pub mod module {
 
 			Ok(().into())
 		}
+
+		/// Claim account mapping between Substrate accounts and an EVM address
+		/// derived from the account and a chosen salt, the address can be
+		/// computed in advance by `salted_evm_address`.
+		/// Ensure eth_address has not been mapped
+		///
+		/// - `salt`: The salt used to derive the EVM address
+		#[pallet::weight(T::WeightInfo::claim_salted_account())]
+		#[transactional]
+		pub fn claim_salted_account(origin: OriginFor<T>, salt: H256) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			// ensure account_id and eth_address has not been mapped
+			ensure!(!EvmAddresses::<T>::contains_key(&who), Error::<T>::AccountIdHasMapped);
+			let eth_address = Self::salted_evm_address(&who, &salt);
+			ensure!(
+				!Accounts::<T>::contains_key(eth_address),
+				Error::<T>::EthAddressHasMapped
+			);
+
+			Self::do_claim_account(who, eth_address)?;
+			Ok(().into())
+		}
+
+		/// Claim account mapping between Substrate accounts and an EVM address
+		/// of a smart-contract wallet. Ownership is proved by the wallet
+		/// accepting the signature through ERC-1271 `isValidSignature`, over
+		/// the same message `claim_account` expects to be signed.
+		/// Ensure eth_address has not been mapped.
+		///
+		/// - `eth_address`: The contract wallet address to bind to the caller's
+		///   account
+		/// - `signature`: The signature to be verified by the contract wallet
+		#[pallet::weight(T::WeightInfo::claim_contract_account())]
+		#[transactional]
+		pub fn claim_contract_account(
+			origin: OriginFor<T>,
+			eth_address: EvmAddress,
+			signature: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			// ensure account_id and eth_address has not been mapped
+			ensure!(!EvmAddresses::<T>::contains_key(&who), Error::<T>::AccountIdHasMapped);
+			ensure!(
+				!Accounts::<T>::contains_key(eth_address),
+				Error::<T>::EthAddressHasMapped
+			);
+
+			// ask the contract wallet to verify the signature
+			let hash = keccak_256(&Self::ethereum_signable_message(
+				&who.using_encoded(to_ascii_hex),
+				&[][..],
+			));
+			ensure!(
+				Self::is_valid_contract_signature(eth_address, hash, &signature),
+				Error::<T>::InvalidSignature
+			);
+
+			Self::do_claim_account(who, eth_address)?;
+			Ok(().into())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	fn do_claim_account(who: T::AccountId, eth_address: EvmAddress) -> DispatchResult {
+		// check if the evm padded address already exists
+		let account_id = T::AddressMapping::get_account_id(&eth_address);
+		if frame_system::Pallet::<T>::account_exists(&account_id) {
+			// merge balance from `evm padded address` to `origin`
+			T::MergeAccount::merge_account(&account_id, &who)?;
+		}
+
+		Accounts::<T>::insert(eth_address, &who);
+		EvmAddresses::<T>::insert(&who, eth_address);
+
+		T::OnClaim::handle(&who)?;
+
+		Self::deposit_event(Event::ClaimAccount(who, eth_address));
+		Ok(())
+	}
+
+	// Calls ERC-1271 `isValidSignature(bytes32,bytes)` on the contract and
+	// returns true only if it answers with the magic value.
+	pub fn is_valid_contract_signature(contract: EvmAddress, hash: [u8; 32], signature: &[u8]) -> bool {
+		let mut input = METHOD_IS_VALID_SIGNATURE.to_be_bytes().to_vec();
+		// append hash
+		input.extend_from_slice(&hash);
+		// append offset of signature data
+		input.extend_from_slice(H256::from_low_u64_be(64).as_bytes());
+		// append signature length
+		input.extend_from_slice(H256::from_low_u64_be(signature.len() as u64).as_bytes());
+		// append signature, padded to 32 bytes
+		input.extend_from_slice(signature);
+		input.resize(input.len() + (32 - signature.len() % 32) % 32, 0);
+
+		let context = InvokeContext {
+			contract,
+			sender: Default::default(),
+			origin: Default::default(),
+		};
+		T::EVMBridge::static_call(context, input)
+			.map(|output| output.len() == 32 && output[0..4] == METHOD_IS_VALID_SIGNATURE.to_be_bytes())
+			.unwrap_or(false)
+	}
+
 	// Constructs the message that Ethereum RPC's `personal_sign` and `eth_sign`
-	// would sign.
+	// would sign, with the chain ID of EVM appended.
 	pub fn ethereum_signable_message(what: &[u8], extra: &[u8]) -> Vec<u8> {
 		let prefix = b"acala evm:";
-		let mut l = prefix.len() + what.len() + extra.len();
-		let mut rev = Vec::new();
-		while l > 0 {
-			rev.push(b'0' + (l % 10) as u8);
-			l /= 10;
-		}
+		let mut suffix = b" chain id:".to_vec();
+		suffix.extend(to_ascii_decimal(T::ChainId::get()));
+		let l = prefix.len() + what.len() + extra.len() + suffix.len();
 		let mut v = b"\x19Ethereum Signed Message:\n".to_vec();
-		v.extend(rev.into_iter().rev());
+		v.extend(to_ascii_decimal(l as u64));
 		v.extend_from_slice(&prefix[..]);
 		v.extend_from_slice(what);
 		v.extend_from_slice(extra);
+		v.extend(suffix);
 		v
 	}
 
@@ -216,6 +321,13 @@ impl<T: Config> Pallet<T> {
 		Some(res)
 	}
 
+	// Returns the EVM address derived from an AccountId and a salt, in the way
+	// like CREATE2: keccak256(0xff ++ "evm:" ++ account_id ++ salt)[12..]
+	pub fn salted_evm_address(account_id: &T::AccountId, salt: &H256) -> EvmAddress {
+		let payload = (0xffu8, b"evm:", account_id, salt);
+		EvmAddress::from_slice(&payload.using_encoded(keccak_256)[12..])
+	}
+
 	// Returns an Etherum public key derived from an Ethereum secret key.
 	pub fn eth_public(secret: &secp256k1::SecretKey) -> secp256k1::PublicKey {
 		secp256k1::PublicKey::from_secret_key(secret)
@@ -337,6 +449,19 @@ impl<T: Config> StaticLookup for Pallet<T> {
 	}
 }
 
+/// Converts the given number into ASCII-encoded decimal.
+pub fn to_ascii_decimal(mut n: u64) -> Vec<u8> {
+	let mut rev = Vec::new();
+	while n > 0 {
+		rev.push(b'0' + (n % 10) as u8);
+		n /= 10;
+	}
+	if rev.is_empty() {
+		rev.push(b'0');
+	}
+	rev.into_iter().rev().collect()
+}
+
 /// Converts the given binary data into ASCII-encoded hex. It will be twice
 /// the length.
 pub fn to_ascii_hex(data: &[u8]) -> Vec<u8> {
@@ -24,9 +24,10 @@ use super::*;
 use frame_support::{construct_runtime, parameter_types};
 use orml_traits::parameter_type_with_key;
 use primitives::{Amount, Balance, CurrencyId, TokenSymbol};
-use sp_core::{crypto::AccountId32, H256};
+use sp_core::{crypto::AccountId32, H160, H256, U256};
 use sp_io::hashing::keccak_256;
-use sp_runtime::{testing::Header, traits::IdentityLookup};
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchError};
+use sp_std::cell::RefCell;
 
 pub type AccountId = AccountId32;
 pub type BlockNumber = u64;
@@ -110,12 +111,89 @@ impl orml_currencies::Config for Runtime {
 }
 pub type AdaptedBasicCurrency = orml_currencies::BasicCurrencyAdapter<Runtime, Balances, Amount, BlockNumber>;
 
+pub fn wallet_address() -> EvmAddress {
+	H160::from_low_u64_be(0x1271)
+}
+
+/// Mocks a contract wallet at `wallet_address` owned by `alice`, its
+/// `isValidSignature` accepts signatures recovered to alice's address.
+pub struct MockEVMBridge;
+impl EVMBridge<AccountId, Balance> for MockEVMBridge {
+	fn name(_context: InvokeContext) -> Result<Vec<u8>, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn symbol(_context: InvokeContext) -> Result<Vec<u8>, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn decimals(_context: InvokeContext) -> Result<u8, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn total_supply(_context: InvokeContext) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn balance_of(_context: InvokeContext, _address: EvmAddress) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn transfer(_context: InvokeContext, _to: EvmAddress, _value: Balance) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
+	fn static_call(context: InvokeContext, input: Vec<u8>) -> Result<Vec<u8>, DispatchError> {
+		ensure!(
+			context.contract == wallet_address(),
+			DispatchError::Other("contract not found")
+		);
+		ensure!(
+			input.len() >= 100 && input[0..4] == METHOD_IS_VALID_SIGNATURE.to_be_bytes(),
+			DispatchError::Other("execution revert")
+		);
+
+		let mut hash = [0u8; 32];
+		hash.copy_from_slice(&input[4..36]);
+		let length = U256::from_big_endian(&input[68..100]).as_usize();
+		ensure!(
+			length == 65 && input.len() >= 100 + length,
+			DispatchError::Other("execution revert")
+		);
+		let mut signature = [0u8; 65];
+		signature.copy_from_slice(&input[100..100 + length]);
+
+		let mut output = [0u8; 32];
+		if let Ok(public) = secp256k1_ecdsa_recover(&signature, &hash) {
+			if keccak_256(&public)[12..] == EvmAccountsModule::eth_address(&alice())[..] {
+				output[0..4].copy_from_slice(&METHOD_IS_VALID_SIGNATURE.to_be_bytes());
+			}
+		}
+		Ok(output.to_vec())
+	}
+	fn get_origin() -> Option<AccountId> {
+		None
+	}
+	fn set_origin(_origin: AccountId) {}
+}
+
+thread_local! {
+	static CHAIN_ID: RefCell<u64> = RefCell::new(595);
+}
+
+pub fn set_chain_id(chain_id: u64) {
+	CHAIN_ID.with(|v| *v.borrow_mut() = chain_id);
+}
+
+pub struct MockChainId;
+impl Get<u64> for MockChainId {
+	fn get() -> u64 {
+		CHAIN_ID.with(|v| *v.borrow())
+	}
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type AddressMapping = EvmAddressMapping<Runtime>;
 	type MergeAccount = Currencies;
 	type OnClaim = ();
+	type EVMBridge = MockEVMBridge;
+	type ChainId = MockChainId;
 	type WeightInfo = ();
 }
 
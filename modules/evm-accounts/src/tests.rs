@@ -22,7 +22,9 @@
 
 use super::*;
 use frame_support::{assert_noop, assert_ok};
-use mock::{alice, bob, Event, EvmAccountsModule, ExtBuilder, Origin, Runtime, System, ALICE, BOB};
+use mock::{
+	alice, bob, set_chain_id, wallet_address, Event, EvmAccountsModule, ExtBuilder, Origin, Runtime, System, ALICE, BOB,
+};
 use std::str::FromStr;
 
 #[test]
@@ -96,6 +98,138 @@ fn claim_account_should_not_work() {
 	});
 }
 
+#[test]
+fn claim_account_signed_for_other_chain_should_not_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_chain_id(686);
+		let signature = EvmAccountsModule::eth_sign(&alice(), &ALICE.encode(), &[][..]);
+
+		set_chain_id(595);
+		assert_noop!(
+			EvmAccountsModule::claim_account(
+				Origin::signed(ALICE),
+				EvmAccountsModule::eth_address(&alice()),
+				signature
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_ok!(EvmAccountsModule::claim_account(
+			Origin::signed(ALICE),
+			EvmAccountsModule::eth_address(&alice()),
+			EvmAccountsModule::eth_sign(&alice(), &ALICE.encode(), &[][..])
+		));
+	});
+}
+
+#[test]
+fn claim_salted_account_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let salt = H256::repeat_byte(1);
+		let salted_address = EvmAccountsModule::salted_evm_address(&ALICE, &salt);
+		assert_ne!(
+			salted_address,
+			EvmAccountsModule::salted_evm_address(&ALICE, &H256::repeat_byte(2))
+		);
+		assert_ne!(salted_address, EvmAccountsModule::salted_evm_address(&BOB, &salt));
+		assert_ne!(
+			salted_address,
+			EvmAddressMapping::<Runtime>::get_default_evm_address(&ALICE)
+		);
+
+		assert_ok!(EvmAccountsModule::claim_salted_account(Origin::signed(ALICE), salt));
+		let event = Event::evm_accounts(crate::Event::ClaimAccount(ALICE, salted_address));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(EvmAccountsModule::accounts(salted_address), Some(ALICE));
+		assert_eq!(EvmAddressMapping::<Runtime>::get_account_id(&salted_address), ALICE);
+		assert_eq!(
+			EvmAddressMapping::<Runtime>::get_or_create_evm_address(&ALICE),
+			salted_address
+		);
+
+		assert_noop!(
+			EvmAccountsModule::claim_salted_account(Origin::signed(ALICE), H256::repeat_byte(2)),
+			Error::<Runtime>::AccountIdHasMapped
+		);
+		assert_noop!(
+			EvmAccountsModule::claim_default_account(Origin::signed(ALICE)),
+			Error::<Runtime>::AccountIdHasMapped
+		);
+	});
+}
+
+#[test]
+fn claim_contract_account_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(EvmAccountsModule::claim_contract_account(
+			Origin::signed(ALICE),
+			wallet_address(),
+			EvmAccountsModule::eth_sign(&alice(), &ALICE.encode(), &[][..])
+				.0
+				.to_vec()
+		));
+		let event = Event::evm_accounts(crate::Event::ClaimAccount(ALICE, wallet_address()));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(EvmAccountsModule::accounts(wallet_address()), Some(ALICE));
+		assert_eq!(EvmAccountsModule::evm_addresses(ALICE), Some(wallet_address()));
+		assert_eq!(EvmAddressMapping::<Runtime>::get_account_id(&wallet_address()), ALICE);
+	});
+}
+
+#[test]
+fn claim_contract_account_should_not_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		// signed by someone else than the wallet owner
+		assert_noop!(
+			EvmAccountsModule::claim_contract_account(
+				Origin::signed(ALICE),
+				wallet_address(),
+				EvmAccountsModule::eth_sign(&bob(), &ALICE.encode(), &[][..]).0.to_vec()
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		// signed for another account
+		assert_noop!(
+			EvmAccountsModule::claim_contract_account(
+				Origin::signed(ALICE),
+				wallet_address(),
+				EvmAccountsModule::eth_sign(&alice(), &BOB.encode(), &[][..]).0.to_vec()
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		// the wallet reverts on malformed signature
+		assert_noop!(
+			EvmAccountsModule::claim_contract_account(Origin::signed(ALICE), wallet_address(), vec![1, 2, 3]),
+			Error::<Runtime>::InvalidSignature
+		);
+		// not a contract wallet
+		assert_noop!(
+			EvmAccountsModule::claim_contract_account(
+				Origin::signed(ALICE),
+				EvmAccountsModule::eth_address(&alice()),
+				EvmAccountsModule::eth_sign(&alice(), &ALICE.encode(), &[][..])
+					.0
+					.to_vec()
+			),
+			Error::<Runtime>::InvalidSignature
+		);
+		assert_ok!(EvmAccountsModule::claim_contract_account(
+			Origin::signed(ALICE),
+			wallet_address(),
+			EvmAccountsModule::eth_sign(&alice(), &ALICE.encode(), &[][..])
+				.0
+				.to_vec()
+		));
+		assert_noop!(
+			EvmAccountsModule::claim_contract_account(
+				Origin::signed(BOB),
+				wallet_address(),
+				EvmAccountsModule::eth_sign(&alice(), &BOB.encode(), &[][..]).0.to_vec()
+			),
+			Error::<Runtime>::EthAddressHasMapped
+		);
+	});
+}
+
 #[test]
 fn evm_get_account_id() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -175,6 +175,15 @@ impl<T: Config> EVMBridgeTrait<AccountIdOf<T>, BalanceOf<T>> for Pallet<T> {
 		Self::handle_exit_reason(info.exit_reason)
 	}
 
+	// Calls the contract with STATICCALL semantics using the given context
+	// and returns the output.
+	fn static_call(context: InvokeContext, input: Vec<u8>) -> Result<Vec<u8>, DispatchError> {
+		let info = T::EVM::execute(context, input, Default::default(), 2_100_000, 0, ExecutionMode::Static)?;
+
+		Self::handle_exit_reason(info.exit_reason)?;
+		Ok(info.output)
+	}
+
 	fn get_origin() -> Option<AccountIdOf<T>> {
 		T::EVM::get_origin()
 	}
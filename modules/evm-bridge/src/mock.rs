@@ -117,7 +117,6 @@ impl module_evm::Config for Runtime {
 
 	type Event = Event;
 	type Precompiles = ();
-	type ChainId = ();
 	type GasToWeight = ();
 	type ChargeTransactionPayment = ();
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId32>;
@@ -127,6 +126,11 @@ impl module_evm::Config for Runtime {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = ();
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
+	type CallFilter = ();
+	type ReapInactivityPeriod = ();
+	type ReapedDepositPot = ();
+	type ReceiptsHistoryDepth = ();
+	type MaxListedCallers = ();
 
 	type WeightInfo = ();
 }
@@ -212,7 +216,7 @@ impl ExtBuilder {
 				code: from_hex(include!("./erc20_demo_contract")).unwrap(),
 			},
 		);
-		module_evm::GenesisConfig::<Runtime> { accounts }
+		module_evm::GenesisConfig::<Runtime> { chain_id: 1, accounts }
 			.assimilate_storage(&mut t)
 			.unwrap();
 
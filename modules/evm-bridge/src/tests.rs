@@ -235,3 +235,37 @@ fn should_transfer() {
 			);
 		});
 }
+
+#[test]
+fn static_call_should_not_change_state() {
+	ExtBuilder::default()
+		.balances(vec![(
+			<Runtime as module_evm::Config>::AddressMapping::get_account_id(&alice()),
+			100000,
+		)])
+		.build()
+		.execute_with(|| {
+			let context = InvokeContext {
+				contract: erc20_address(),
+				sender: alice(),
+				origin: alice(),
+			};
+
+			let mut input = METHOD_BALANCE_OF.to_be_bytes().to_vec();
+			input.extend_from_slice(H256::from(alice()).as_bytes());
+			assert_eq!(
+				EvmBridgeModule::static_call(context, input),
+				Ok(H256::from_uint(&U256::from(u128::max_value())).as_bytes().to_vec())
+			);
+
+			let mut input = METHOD_TRANSFER.to_be_bytes().to_vec();
+			input.extend_from_slice(H256::from(bob()).as_bytes());
+			input.extend_from_slice(H256::from_uint(&U256::from(100)).as_bytes());
+			assert_err!(
+				EvmBridgeModule::static_call(context, input),
+				Error::<Runtime>::ExecutionError
+			);
+
+			assert_eq!(EvmBridgeModule::balance_of(context, bob()), Ok(0));
+		});
+}
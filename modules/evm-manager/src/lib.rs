@@ -23,18 +23,21 @@
 //! Evm manager module provides common support features for Evm, including:
 //! - A two way mapping between `u32` and `Erc20 address` so user can use Erc20
 //!   address as LP token.
+//! - Reference counters of the modules which use an Erc20 CurrencyId.
+//! - The decimals of all CurrencyIds, the decimals of foreign assets are
+//!   registered by governance.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::{ensure, pallet_prelude::*, require_transactional, traits::Currency};
-use module_support::{CurrencyIdMapping, EVMBridge, InvokeContext};
+use frame_support::{ensure, pallet_prelude::*, require_transactional, traits::Currency, PalletId};
+use module_support::{AssetMetadataProvider, CurrencyIdMapping, EVMBridge, Erc20ReferenceCounter, InvokeContext};
 use primitives::{
 	currency::GetDecimals,
 	evm::{Erc20Info, EvmAddress},
 	CurrencyId, DexShare,
 };
-use sp_std::convert::TryInto;
+use sp_std::{convert::TryInto, vec::Vec};
 
 mod mock;
 mod tests;
@@ -49,8 +52,12 @@ pub mod module {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type Currency: Currency<Self::AccountId>;
 		type EVMBridge: EVMBridge<Self::AccountId, BalanceOf<Self>>;
+
+		/// The metadata of the foreign assets registered by governance.
+		type AssetMetadataProvider: AssetMetadataProvider;
 	}
 
 	/// Error for evm accounts module.
@@ -60,6 +67,18 @@ pub mod module {
 		CurrencyIdExisted,
 	}
 
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The decimals of a mapped Erc20 changed, used to audit price
+		/// normalization. \[address, old_decimals, new_decimals\]
+		Erc20DecimalsUpdated(EvmAddress, Option<u8>, u8),
+		/// The decimals reported by a mapped Erc20 differ from the stored
+		/// ones, which are kept. \[address, stored_decimals,
+		/// reported_decimals\]
+		Erc20DecimalsMismatch(EvmAddress, u8, u8),
+	}
+
 	/// Mapping between u32 and Erc20 address.
 	/// Erc20 address is 20 byte, take the first 4 non-zero bytes, if it is less
 	/// than 4, add 0 to the left.
@@ -67,6 +86,14 @@ pub mod module {
 	#[pallet::getter(fn currency_id_map)]
 	pub type CurrencyIdMap<T: Config> = StorageMap<_, Twox64Concat, u32, Erc20Info>;
 
+	/// The number of references to an Erc20 address held by each module.
+	///
+	/// Erc20References: double_map EvmAddress, PalletId => u32
+	#[pallet::storage]
+	#[pallet::getter(fn erc20_references)]
+	pub type Erc20References<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, EvmAddress, Twox64Concat, PalletId, u32, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -79,6 +106,28 @@ pub mod module {
 
 impl<T: Config> Pallet<T> {}
 
+impl<T: Config> Erc20ReferenceCounter for Pallet<T> {
+	fn inc_reference(id: PalletId, currency_id: CurrencyId) {
+		if let CurrencyId::Erc20(address) = currency_id {
+			Erc20References::<T>::mutate(address, id, |count| *count = count.saturating_add(1));
+		}
+	}
+
+	fn dec_reference(id: PalletId, currency_id: CurrencyId) {
+		if let CurrencyId::Erc20(address) = currency_id {
+			Erc20References::<T>::mutate_exists(address, id, |maybe_count| {
+				*maybe_count = maybe_count
+					.map(|count| count.saturating_sub(1))
+					.filter(|count| *count > 0);
+			});
+		}
+	}
+
+	fn references(address: EvmAddress) -> Vec<(PalletId, u32)> {
+		Erc20References::<T>::iter_prefix(address).collect()
+	}
+}
+
 pub struct EvmCurrencyIdMapping<T>(sp_std::marker::PhantomData<T>);
 
 impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
@@ -91,8 +140,23 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 			.expect("CurrencyId::Erc20 into u32 is success; qed");
 
 		CurrencyIdMap::<T>::mutate(id, |maybe_erc20_info| -> DispatchResult {
-			if let Some(erc20_info) = maybe_erc20_info.as_mut() {
+			if let Some(erc20_info) = maybe_erc20_info.as_ref() {
 				ensure!(erc20_info.address == address, Error::<T>::CurrencyIdExisted);
+
+				if let Ok(decimals) = T::EVMBridge::decimals(InvokeContext {
+					contract: address,
+					sender: Default::default(),
+					origin: Default::default(),
+				}) {
+					// keep the stored decimals, balances and prices already rely on them.
+					if decimals != erc20_info.decimals {
+						Pallet::<T>::deposit_event(Event::Erc20DecimalsMismatch(
+							address,
+							erc20_info.decimals,
+							decimals,
+						));
+					}
+				}
 			} else {
 				let info = Erc20Info {
 					address,
@@ -113,6 +177,7 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 					})?,
 				};
 
+				Pallet::<T>::deposit_event(Event::Erc20DecimalsUpdated(address, None, info.decimals));
 				*maybe_erc20_info = Some(info);
 			}
 			Ok(())
@@ -125,38 +190,16 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 	}
 
 	// Returns the decimals associated with a given CurrencyId.
-	// If CurrencyId is CurrencyId::DexShare and contain DexShare::Erc20,
-	// the EvmAddress must have been mapped.
+	// The decimals of CurrencyId::DexShare are the max decimals of its two
+	// currencies. If CurrencyId is CurrencyId::DexShare and contain
+	// DexShare::Erc20, the EvmAddress must have been mapped.
 	fn decimals(currency_id: CurrencyId) -> Option<u8> {
 		match currency_id {
 			CurrencyId::Token(_) => currency_id.decimals(),
 			CurrencyId::DexShare(symbol_0, symbol_1) => {
-				let decimals_0 = match symbol_0 {
-					DexShare::Token(symbol) => CurrencyId::Token(symbol).decimals(),
-					DexShare::Erc20(address) => {
-						let id: u32 = CurrencyId::Erc20(address)
-							.try_into()
-							.expect("CurrencyId::Erc20 into u32 is success; qed");
-						CurrencyIdMap::<T>::get(id)
-							.filter(|v| v.address == address)
-							.map(|v| v.decimals)
-					}
-				};
-				let decimals_1 = match symbol_1 {
-					DexShare::Token(symbol) => CurrencyId::Token(symbol).decimals(),
-					DexShare::Erc20(address) => {
-						let id: u32 = CurrencyId::Erc20(address)
-							.try_into()
-							.expect("CurrencyId::Erc20 into u32 is success; qed");
-						CurrencyIdMap::<T>::get(id)
-							.filter(|v| v.address == address)
-							.map(|v| v.decimals)
-					}
-				};
-				if decimals_0.is_none() || decimals_1.is_none() {
-					return None;
-				}
-				Some(sp_std::cmp::max(decimals_0.unwrap(), decimals_1.unwrap()))
+				let decimals_0 = Self::decimals(symbol_0.into())?;
+				let decimals_1 = Self::decimals(symbol_1.into())?;
+				Some(sp_std::cmp::max(decimals_0, decimals_1))
 			}
 			CurrencyId::Erc20(address) => {
 				let id: u32 = CurrencyId::Erc20(address)
@@ -166,6 +209,8 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 					.filter(|v| v.address == address)
 					.map(|v| v.decimals)
 			}
+			// foreign assets are not exposed to EVM, only the registered decimals
+			CurrencyId::ForeignAsset(_) => T::AssetMetadataProvider::decimals(currency_id),
 		}
 	}
 
@@ -221,6 +266,7 @@ impl<T: Config> CurrencyIdMapping for EvmCurrencyIdMapping<T> {
 			CurrencyId::Erc20(address) => {
 				bytes[12..32].copy_from_slice(&address[..]);
 			}
+			CurrencyId::ForeignAsset(_) => return None,
 		}
 		Some(bytes)
 	}
@@ -144,7 +144,6 @@ impl module_evm::Config for Runtime {
 
 	type Event = Event;
 	type Precompiles = ();
-	type ChainId = ();
 	type GasToWeight = ();
 	type ChargeTransactionPayment = ();
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId>;
@@ -154,6 +153,11 @@ impl module_evm::Config for Runtime {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
+	type CallFilter = ();
+	type ReapInactivityPeriod = ();
+	type ReapedDepositPot = TreasuryAccount;
+	type ReceiptsHistoryDepth = ();
+	type MaxListedCallers = ();
 
 	type WeightInfo = ();
 }
@@ -162,9 +166,21 @@ impl module_evm_bridge::Config for Runtime {
 	type EVM = EVM;
 }
 
+pub struct MockAssetMetadataProvider;
+impl AssetMetadataProvider for MockAssetMetadataProvider {
+	fn decimals(currency_id: CurrencyId) -> Option<u8> {
+		match currency_id {
+			CurrencyId::ForeignAsset(0) => Some(8),
+			_ => None,
+		}
+	}
+}
+
 impl Config for Runtime {
+	type Event = Event;
 	type Currency = Balances;
 	type EVMBridge = EVMBridge;
+	type AssetMetadataProvider = MockAssetMetadataProvider;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -183,7 +199,7 @@ construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
-		EvmManager: evm_manager::{Pallet, Storage},
+		EvmManager: evm_manager::{Pallet, Storage, Event<T>},
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Currencies: orml_currencies::{Pallet, Call, Event<T>},
@@ -225,7 +241,7 @@ impl ExtBuilder {
 				code: from_hex(include!("../../evm-bridge/src/erc20_demo_contract")).unwrap(),
 			},
 		);
-		module_evm::GenesisConfig::<Runtime> { accounts }
+		module_evm::GenesisConfig::<Runtime> { chain_id: 1, accounts }
 			.assimilate_storage(&mut t)
 			.unwrap();
 
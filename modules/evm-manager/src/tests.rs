@@ -22,7 +22,9 @@
 
 use super::*;
 use frame_support::{assert_noop, assert_ok};
-use mock::{ExtBuilder, Runtime, ERC20, ERC20_ADDRESS, ERC20_ADDRESS_NOT_EXISTS, ERC20_NOT_EXISTS};
+use mock::{
+	Event, EvmManager, ExtBuilder, Runtime, System, ERC20, ERC20_ADDRESS, ERC20_ADDRESS_NOT_EXISTS, ERC20_NOT_EXISTS,
+};
 use orml_utilities::with_transaction_result;
 use primitives::TokenSymbol;
 use sp_core::H160;
@@ -48,6 +50,28 @@ fn set_erc20_mapping_works() {
 	});
 }
 
+#[test]
+fn set_erc20_mapping_keeps_stored_decimals() {
+	ExtBuilder::default().build().execute_with(|| {
+		let id: u32 = ERC20.try_into().unwrap();
+		assert_ok!(with_transaction_result(|| -> DispatchResult {
+			EvmCurrencyIdMapping::<Runtime>::set_erc20_mapping(ERC20_ADDRESS)
+		}));
+		let decimals_event = Event::evm_manager(crate::Event::Erc20DecimalsUpdated(ERC20_ADDRESS, None, 17));
+		assert!(System::events().iter().any(|record| record.event == decimals_event));
+
+		CurrencyIdMap::<Runtime>::mutate(id, |maybe_erc20_info| {
+			maybe_erc20_info.as_mut().unwrap().decimals = 18;
+		});
+		assert_ok!(with_transaction_result(|| -> DispatchResult {
+			EvmCurrencyIdMapping::<Runtime>::set_erc20_mapping(ERC20_ADDRESS)
+		}));
+		let mismatch_event = Event::evm_manager(crate::Event::Erc20DecimalsMismatch(ERC20_ADDRESS, 18, 17));
+		assert!(System::events().iter().any(|record| record.event == mismatch_event));
+		assert_eq!(EvmCurrencyIdMapping::<Runtime>::decimals(ERC20), Some(18));
+	});
+}
+
 #[test]
 fn get_evm_address_works() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -79,6 +103,42 @@ fn decimals_works() {
 	});
 }
 
+#[test]
+fn decimals_of_foreign_assets_are_registered() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(with_transaction_result(|| -> DispatchResult {
+			EvmCurrencyIdMapping::<Runtime>::set_erc20_mapping(ERC20_ADDRESS)
+		}));
+
+		assert_eq!(
+			EvmCurrencyIdMapping::<Runtime>::decimals(CurrencyId::Token(TokenSymbol::DOT)),
+			Some(10)
+		);
+		assert_eq!(
+			EvmCurrencyIdMapping::<Runtime>::decimals(CurrencyId::ForeignAsset(0)),
+			Some(8)
+		);
+		assert_eq!(
+			EvmCurrencyIdMapping::<Runtime>::decimals(CurrencyId::ForeignAsset(1)),
+			None
+		);
+		assert_eq!(
+			EvmCurrencyIdMapping::<Runtime>::decimals(CurrencyId::DexShare(
+				DexShare::Token(TokenSymbol::DOT),
+				DexShare::Erc20(ERC20_ADDRESS)
+			)),
+			Some(17)
+		);
+		assert_eq!(
+			EvmCurrencyIdMapping::<Runtime>::decimals(CurrencyId::DexShare(
+				DexShare::Token(TokenSymbol::DOT),
+				DexShare::Erc20(ERC20_ADDRESS_NOT_EXISTS)
+			)),
+			None
+		);
+	});
+}
+
 #[test]
 fn encode_currency_id_works() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -188,3 +248,29 @@ fn decode_currency_id_works() {
 		);
 	});
 }
+
+#[test]
+fn erc20_reference_counter_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let dex_id = PalletId(*b"aca/dexm");
+		let cdp_id = PalletId(*b"aca/cdpe");
+
+		EvmManager::inc_reference(dex_id, ERC20);
+		EvmManager::inc_reference(dex_id, ERC20);
+		EvmManager::inc_reference(cdp_id, ERC20);
+		EvmManager::inc_reference(dex_id, CurrencyId::Token(TokenSymbol::ACA));
+		assert_eq!(EvmManager::erc20_references(ERC20_ADDRESS, dex_id), 2);
+		assert_eq!(EvmManager::erc20_references(ERC20_ADDRESS, cdp_id), 1);
+
+		let mut references = EvmManager::references(ERC20_ADDRESS);
+		references.sort_by_key(|(id, _)| id.0);
+		assert_eq!(references, vec![(cdp_id, 1), (dex_id, 2)]);
+
+		EvmManager::dec_reference(cdp_id, ERC20);
+		EvmManager::dec_reference(cdp_id, ERC20);
+		EvmManager::dec_reference(dex_id, ERC20);
+		assert!(!Erc20References::<Runtime>::contains_key(ERC20_ADDRESS, cdp_id));
+		assert_eq!(EvmManager::references(ERC20_ADDRESS), vec![(dex_id, 1)]);
+		assert_eq!(EvmManager::references(ERC20_ADDRESS_NOT_EXISTS), vec![]);
+	});
+}
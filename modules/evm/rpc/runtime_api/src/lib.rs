@@ -20,7 +20,13 @@
 #![allow(clippy::all)]
 
 use ethereum_types::H160;
-use primitives::evm::{CallInfo, CreateInfo, EstimateResourcesRequest};
+use primitives::{
+	evm::{
+		AccountNonceInfo, BlockGasInfo, BlockLogsInfo, CallInfo, ContractStorageInfo, CreateInfo,
+		EstimateResourcesInfo, EstimateResourcesRequest,
+	},
+	AccountId, Nonce,
+};
 use sp_runtime::{
 	codec::Codec,
 	traits::{MaybeDisplay, MaybeFromStr},
@@ -28,6 +34,10 @@ use sp_runtime::{
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
+	/// Version 2 adds `estimate_resources`, `contract_storage_info` and
+	/// `account_nonce_info`. Version 3 adds `block_gas_info`. Version 4 adds
+	/// `block_logs`.
+	#[api_version(4)]
 	pub trait EVMRuntimeRPCApi<Balance> where
 		Balance: Codec + MaybeDisplay + MaybeFromStr,
 	{
@@ -51,5 +61,26 @@ sp_api::decl_runtime_apis! {
 		) -> Result<CreateInfo, sp_runtime::DispatchError>;
 
 		fn get_estimate_resources_request(data: Vec<u8>) -> Result<EstimateResourcesRequest, sp_runtime::DispatchError>;
+
+		fn estimate_resources(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+		) -> Result<EstimateResourcesInfo<Balance>, sp_runtime::DispatchError>;
+
+		fn contract_storage_info(address: H160) -> Option<ContractStorageInfo<Balance>>;
+
+		fn account_nonce_info(address: H160) -> AccountNonceInfo<AccountId, Nonce>;
+
+		/// The gas used and the gas limit of the current block, and the base
+		/// fee per gas of the next block.
+		fn block_gas_info() -> BlockGasInfo<Balance>;
+
+		/// The logs emitted by the extrinsics of the current block, mapped from
+		/// the `Log` events.
+		fn block_logs() -> BlockLogsInfo;
 	}
 }
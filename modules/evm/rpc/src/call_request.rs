@@ -17,6 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use ethereum_types::{H160, U256};
+use primitives::{AccountId, Nonce};
 use serde::{Deserialize, Serialize};
 use sp_core::Bytes;
 use sp_rpc::number::NumberOrHex;
@@ -51,3 +52,82 @@ pub struct EstimateResourcesResponse {
 	/// Adjusted weight fee
 	pub weight_fee: U256,
 }
+
+/// EstimateCallResources response
+#[derive(Debug, Eq, PartialEq, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EstimateCallResourcesResponse {
+	/// Used gas
+	pub gas: U256,
+	/// Used storage, negative if storage is released
+	pub storage: i32,
+	/// Storage deposit required by the used storage
+	pub storage_deposit: U256,
+}
+
+/// AccountNonce response
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountNonceResponse {
+	/// The account the address is mapped to
+	pub account_id: AccountId,
+	/// The nonce of the mapped account on chain
+	pub account_nonce: Nonce,
+	/// The nonce of the mapped account, adjusted by the ready transactions
+	/// in the pool
+	pub pending_account_nonce: Nonce,
+	/// The evm nonce of the address
+	pub evm_nonce: Nonce,
+}
+
+/// ContractStorageInfo response
+#[derive(Debug, Eq, PartialEq, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractStorageInfoResponse {
+	/// Used storage
+	pub used_storage: u32,
+	/// Reserved deposit
+	pub deposit: U256,
+	/// Maintainer
+	pub maintainer: H160,
+	/// Deployed
+	pub deployed: bool,
+}
+
+/// The tag of a block, in place of the block number
+#[derive(Debug, Eq, PartialEq, Deserialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum BlockTag {
+	/// The genesis block
+	Earliest,
+	/// The best block
+	Latest,
+	/// The best block, the pending block is not built ahead
+	Pending,
+}
+
+/// Block number or block tag
+#[derive(Debug, Eq, PartialEq, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum BlockNumberOrTag {
+	/// Block number
+	Number(NumberOrHex),
+	/// Block tag
+	Tag(BlockTag),
+}
+
+/// FeeHistory response
+#[derive(Debug, PartialEq, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeHistoryResponse {
+	/// The number of the oldest block in the range
+	pub oldest_block: U256,
+	/// The base fee per gas of each block, and the next block of the newest
+	/// one
+	pub base_fee_per_gas: Vec<U256>,
+	/// The ratio of the gas used to the gas limit of each block
+	pub gas_used_ratio: Vec<f64>,
+	/// The priority fee per gas at the requested percentiles of each block
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub reward: Option<Vec<Vec<U256>>>,
+}
@@ -18,14 +18,18 @@
 
 //! EVM rpc interface.
 
-use ethereum_types::H160;
+use ethereum_types::{H160, U256};
 use jsonrpc_core::Result;
 use jsonrpc_derive::rpc;
 use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
 
 pub use rpc_impl_EVMApi::gen_server::EVMApi as EVMApiServer;
 
-use crate::call_request::{CallRequest, EstimateResourcesResponse};
+use crate::call_request::{
+	AccountNonceResponse, BlockNumberOrTag, CallRequest, ContractStorageInfoResponse, EstimateCallResourcesResponse,
+	EstimateResourcesResponse, FeeHistoryResponse,
+};
 
 /// EVM rpc interface.
 #[rpc(server)]
@@ -42,4 +46,39 @@ pub trait EVMApi<BlockHash> {
 		unsigned_extrinsic: Bytes,
 		at: Option<BlockHash>,
 	) -> Result<EstimateResourcesResponse>;
+
+	/// Estimate the gas, storage and storage deposit used by a call, or a
+	/// create if `to` is None, in a single dry run.
+	#[rpc(name = "evm_estimateCallResources")]
+	fn estimate_call_resources(&self, _: CallRequest, at: Option<BlockHash>) -> Result<EstimateCallResourcesResponse>;
+
+	/// Get the storage usage of contract, None if the address is not a
+	/// contract.
+	#[rpc(name = "evm_contractStorageInfo")]
+	fn contract_storage_info(
+		&self,
+		address: H160,
+		at: Option<BlockHash>,
+	) -> Result<Option<ContractStorageInfoResponse>>;
+
+	/// Get the account the address is mapped to with its nonce, including
+	/// the ready transactions in the pool, and the evm nonce of the address.
+	#[rpc(name = "evm_accountNonce")]
+	fn account_nonce(&self, address: H160, at: Option<BlockHash>) -> Result<AccountNonceResponse>;
+
+	/// Get the base fees per gas and the gas used ratios of the
+	/// `block_count` blocks up to `newest_block`, from the recorded gas usage
+	/// of the blocks and the EVM fee multiplier.
+	#[rpc(name = "eth_feeHistory")]
+	fn fee_history(
+		&self,
+		block_count: NumberOrHex,
+		newest_block: BlockNumberOrTag,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> Result<FeeHistoryResponse>;
+
+	/// Get the priority fee per gas needed for a transaction to be included
+	/// in time.
+	#[rpc(name = "eth_maxPriorityFeePerGas")]
+	fn max_priority_fee_per_gas(&self) -> Result<U256>;
 }
@@ -22,10 +22,11 @@ use ethereum_types::{H160, U256};
 use frame_support::log;
 use jsonrpc_core::{Error, ErrorCode, Result, Value};
 use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi;
+use primitives::evm::BlockGasInfo;
 use rustc_hex::ToHex;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_core::{Bytes, Decode};
+use sp_core::{Bytes, Decode, Encode};
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::{
 	codec::Codec,
@@ -33,17 +34,28 @@ use sp_runtime::{
 	traits::{self, Block as BlockT, MaybeDisplay, MaybeFromStr},
 	SaturatedConversion,
 };
+use sp_transaction_pool::{InPoolTransaction, TransactionPool};
 use std::convert::{TryFrom, TryInto};
 use std::{marker::PhantomData, sync::Arc};
 
-use call_request::{CallRequest, EstimateResourcesResponse};
+use call_request::{
+	AccountNonceResponse, BlockNumberOrTag, BlockTag, CallRequest, ContractStorageInfoResponse,
+	EstimateCallResourcesResponse, EstimateResourcesResponse, FeeHistoryResponse,
+};
 pub use module_evm::{ExitError, ExitReason};
 pub use module_evm_rpc_runtime_api::EVMRuntimeRPCApi;
 
 pub use crate::evm_api::{EVMApi as EVMApiT, EVMApiServer};
+pub use crate::pubsub::EVMPubSub;
+pub use crate::pubsub_api::{EVMPubSubApi as EVMPubSubApiT, EVMPubSubApiServer};
 
 mod call_request;
 mod evm_api;
+mod pubsub;
+mod pubsub_api;
+
+/// The max count of blocks served by `eth_feeHistory` in a request.
+const MAX_FEE_HISTORY_BLOCKS: u64 = 1024;
 
 fn internal_err<T: ToString>(message: T) -> Error {
 	Error {
@@ -104,30 +116,64 @@ fn decode_revert_message(data: &[u8]) -> Option<String> {
 	None
 }
 
-pub struct EVMApi<B, C, Balance> {
+pub struct EVMApi<B, C, P, Balance> {
 	client: Arc<C>,
+	pool: Arc<P>,
 	_marker: PhantomData<(B, Balance)>,
 }
 
-impl<B, C, Balance> EVMApi<B, C, Balance> {
-	pub fn new(client: Arc<C>) -> Self {
+impl<B, C, P, Balance> EVMApi<B, C, P, Balance> {
+	pub fn new(client: Arc<C>, pool: Arc<P>) -> Self {
 		Self {
 			client,
+			pool,
 			_marker: Default::default(),
 		}
 	}
 }
 
+impl<B, C, P, Balance> EVMApi<B, C, P, Balance>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B>,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	/// The gas info of the block `number`, queried at the state of the
+	/// block.
+	fn block_gas_info(&self, number: u64) -> Result<BlockGasInfo<Balance>> {
+		let hash = self
+			.client
+			.hash(number.saturated_into())
+			.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+			.ok_or_else(|| internal_err(format!("block not found: {}", number)))?;
+
+		let api = self.client.runtime_api();
+
+		// `block_gas_info` is only available since version 3 of the runtime api
+		let supported = api
+			.has_api_with::<dyn EVMRuntimeRPCApi<B, Balance>, _>(&BlockId::Hash(hash), |version| version >= 3)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+		if !supported {
+			return Err(internal_err("block_gas_info is not supported by the runtime"));
+		}
+
+		api.block_gas_info(&BlockId::Hash(hash))
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))
+	}
+}
+
 fn to_u128(val: NumberOrHex) -> std::result::Result<u128, ()> {
 	val.into_u256().try_into().map_err(|_| ())
 }
 
-impl<B, C, Balance> EVMApiT<B> for EVMApi<B, C, Balance>
+impl<B, C, P, Balance> EVMApiT<B> for EVMApi<B, C, P, Balance>
 where
 	B: BlockT,
 	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
 	C::Api: EVMRuntimeRPCApi<B, Balance>,
 	C::Api: TransactionPaymentApi<B, Balance>,
+	P: TransactionPool + 'static,
 	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
 {
 	fn call(&self, request: CallRequest, at: Option<B>) -> Result<Bytes> {
@@ -416,6 +462,177 @@ where
 			})
 		}
 	}
+
+	fn estimate_call_resources(&self, request: CallRequest, at: Option<B>) -> Result<EstimateCallResourcesResponse> {
+		let hash = at.map_or_else(|| self.client.info().best_hash, |v| v.hash());
+
+		let api = self.client.runtime_api();
+
+		// `estimate_resources` is only available since version 2 of the runtime api
+		let supported = api
+			.has_api_with::<dyn EVMRuntimeRPCApi<B, Balance>, _>(&BlockId::Hash(hash), |version| version >= 2)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+		if !supported {
+			return Err(internal_err("estimate_resources is not supported by the runtime"));
+		}
+
+		let CallRequest {
+			from,
+			to,
+			gas_limit,
+			storage_limit,
+			value,
+			data,
+		} = request;
+
+		let gas_limit = gas_limit.unwrap_or_else(u64::max_value); // TODO: set a limit
+		let storage_limit = storage_limit.unwrap_or_else(u32::max_value); // TODO: set a limit
+		let data = data.map(|d| d.0).unwrap_or_default();
+
+		let balance_value = if let Some(value) = value {
+			to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
+		} else {
+			Ok(Default::default())
+		};
+
+		let balance_value = balance_value.map_err(|_| Error {
+			code: ErrorCode::InvalidParams,
+			message: format!("Invalid parameter value: {:?}", value),
+			data: None,
+		})?;
+
+		let info = api
+			.estimate_resources(
+				&BlockId::Hash(hash),
+				from.unwrap_or_default(),
+				to,
+				data,
+				balance_value,
+				gas_limit,
+				storage_limit,
+			)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+			.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+
+		error_on_execution_failure(&info.exit_reason, &[])?;
+
+		Ok(EstimateCallResourcesResponse {
+			gas: info.used_gas,
+			storage: info.used_storage,
+			storage_deposit: info.storage_deposit.into(),
+		})
+	}
+
+	fn contract_storage_info(&self, address: H160, at: Option<B>) -> Result<Option<ContractStorageInfoResponse>> {
+		let hash = at.map_or_else(|| self.client.info().best_hash, |v| v.hash());
+
+		let info = self
+			.client
+			.runtime_api()
+			.contract_storage_info(&BlockId::Hash(hash), address)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+		Ok(info.map(|info| ContractStorageInfoResponse {
+			used_storage: info.used_storage,
+			deposit: info.deposit.into(),
+			maintainer: info.maintainer,
+			deployed: info.deployed,
+		}))
+	}
+
+	fn account_nonce(&self, address: H160, at: Option<B>) -> Result<AccountNonceResponse> {
+		let hash = at.map_or_else(|| self.client.info().best_hash, |v| v.hash());
+
+		let info = self
+			.client
+			.runtime_api()
+			.account_nonce_info(&BlockId::Hash(hash), address)
+			.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+
+		// the ready transactions of the account provide the tags of consecutive
+		// nonces, same as the `system_accountNextIndex` rpc
+		let mut pending_account_nonce = info.account_nonce;
+		let mut current_tag = (info.account_id.clone(), pending_account_nonce).encode();
+		for tx in self.pool.ready() {
+			if tx.provides().get(0) == Some(&current_tag) {
+				pending_account_nonce = pending_account_nonce.saturating_add(1);
+				current_tag = (info.account_id.clone(), pending_account_nonce).encode();
+			}
+		}
+
+		Ok(AccountNonceResponse {
+			account_id: info.account_id,
+			account_nonce: info.account_nonce,
+			pending_account_nonce,
+			evm_nonce: info.evm_nonce,
+		})
+	}
+
+	fn fee_history(
+		&self,
+		block_count: NumberOrHex,
+		newest_block: BlockNumberOrTag,
+		reward_percentiles: Option<Vec<f64>>,
+	) -> Result<FeeHistoryResponse> {
+		let invalid_params = |message: String| Error {
+			code: ErrorCode::InvalidParams,
+			message,
+			data: None,
+		};
+
+		let best_number: u64 = self.client.info().best_number.saturated_into();
+		let newest_number: u64 = match newest_block {
+			BlockNumberOrTag::Number(number) => to_u128(number)
+				.map_err(|_| invalid_params(format!("Invalid parameter newest block: {:?}", number)))?
+				.saturated_into(),
+			BlockNumberOrTag::Tag(BlockTag::Earliest) => 0,
+			BlockNumberOrTag::Tag(BlockTag::Latest) | BlockNumberOrTag::Tag(BlockTag::Pending) => best_number,
+		};
+		if newest_number > best_number {
+			return Err(invalid_params(format!("Unknown newest block: {}", newest_number)));
+		}
+
+		let block_count: u64 = to_u128(block_count)
+			.map_err(|_| invalid_params(format!("Invalid parameter block count: {:?}", block_count)))?
+			.min(MAX_FEE_HISTORY_BLOCKS.into())
+			.saturated_into();
+		let block_count = block_count.min(newest_number.saturating_add(1));
+		if block_count == 0 {
+			return Ok(Default::default());
+		}
+		let oldest_number = newest_number.saturating_add(1).saturating_sub(block_count);
+
+		// EVM transactions pay no priority fee, the fees are up to the weight
+		// and the fee multiplier
+		let mut response = FeeHistoryResponse {
+			oldest_block: oldest_number.into(),
+			reward: reward_percentiles
+				.map(|percentiles| vec![vec![U256::zero(); percentiles.len()]; block_count as usize]),
+			..Default::default()
+		};
+
+		// the base fee of a block is the next base fee of its parent, the
+		// genesis block uses its own
+		let parent_info = self.block_gas_info(oldest_number.saturating_sub(1))?;
+		response.base_fee_per_gas.push(parent_info.next_base_fee_per_gas.into());
+		for number in oldest_number..=newest_number {
+			let info = self.block_gas_info(number)?;
+			let gas_used_ratio = if info.gas_limit == 0 {
+				0.0
+			} else {
+				info.gas_used as f64 / info.gas_limit as f64
+			};
+			response.gas_used_ratio.push(gas_used_ratio);
+			response.base_fee_per_gas.push(info.next_base_fee_per_gas.into());
+		}
+
+		Ok(response)
+	}
+
+	fn max_priority_fee_per_gas(&self) -> Result<U256> {
+		// EVM transactions pay no priority fee to be included
+		Ok(U256::zero())
+	}
 }
 
 #[test]
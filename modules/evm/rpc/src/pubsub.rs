@@ -0,0 +1,221 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! EVM pubsub rpc implementation.
+
+use ethereum_types::U256;
+use frame_support::log;
+use futures::{future, StreamExt, TryStreamExt};
+use jsonrpc_core::{
+	futures::{Future, Sink},
+	Error, ErrorCode, Result,
+};
+use jsonrpc_pubsub::{manager::SubscriptionManager, typed::Subscriber, SubscriptionId};
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use sc_rpc::SubscriptionTaskExecutor;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{
+	codec::Codec,
+	generic::BlockId,
+	traits::{Block as BlockT, Hash as HashT, Header as HeaderT, MaybeDisplay, MaybeFromStr},
+	SaturatedConversion,
+};
+use sp_transaction_pool::TransactionPool;
+use std::{marker::PhantomData, sync::Arc};
+
+use crate::pubsub_api::{EVMPubSubApi as EVMPubSubApiT, HeadResponse, Kind, LogResponse, LogsFilter, PubSubResult};
+use crate::EVMRuntimeRPCApi;
+
+pub struct EVMPubSub<B, C, P, Balance> {
+	client: Arc<C>,
+	pool: Arc<P>,
+	subscriptions: SubscriptionManager,
+	_marker: PhantomData<(B, Balance)>,
+}
+
+impl<B, C, P, Balance> EVMPubSub<B, C, P, Balance> {
+	pub fn new(client: Arc<C>, pool: Arc<P>, executor: SubscriptionTaskExecutor) -> Self {
+		Self {
+			client,
+			pool,
+			subscriptions: SubscriptionManager::new(Arc::new(executor)),
+			_marker: Default::default(),
+		}
+	}
+}
+
+/// The header of the block, with the gas usage and the logs bloom of it.
+fn head_response<B, C, Balance>(client: &C, header: &B::Header) -> HeadResponse<B::Hash>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	let hash = header.hash();
+	let api = client.runtime_api();
+	// the gas info and the logs are empty if the runtime api is not supported
+	let (gas_used, gas_limit) = api
+		.block_gas_info(&BlockId::Hash(hash))
+		.map_or((0, 0), |info| (info.gas_used, info.gas_limit));
+	let logs_bloom = api
+		.block_logs(&BlockId::Hash(hash))
+		.map(|info| info.logs_bloom)
+		.unwrap_or_default();
+
+	HeadResponse {
+		hash,
+		parent_hash: *header.parent_hash(),
+		number: (*header.number()).saturated_into::<u64>().into(),
+		state_root: *header.state_root(),
+		transactions_root: *header.extrinsics_root(),
+		logs_bloom: Bytes(logs_bloom.0.to_vec()),
+		gas_used: gas_used.into(),
+		gas_limit: gas_limit.into(),
+	}
+}
+
+/// The logs of the block matching `filter`.
+fn log_responses<B, C, Balance>(client: &C, header: &B::Header, filter: &LogsFilter) -> Vec<LogResponse<B::Hash>>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + BlockBackend<B>,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	let hash = header.hash();
+	let info = match client.runtime_api().block_logs(&BlockId::Hash(hash)) {
+		Ok(info) => info,
+		Err(_) => return vec![],
+	};
+	if !filter.may_match_bloom(&info.logs_bloom) {
+		return vec![];
+	}
+
+	let extrinsics = client
+		.block_body(&BlockId::Hash(hash))
+		.ok()
+		.flatten()
+		.unwrap_or_default();
+	let block_number: U256 = (*header.number()).saturated_into::<u64>().into();
+
+	info.logs
+		.into_iter()
+		.enumerate()
+		.filter(|(_, (_, log))| filter.matches(log))
+		.map(|(log_index, (extrinsic_index, log))| LogResponse {
+			address: log.address,
+			topics: log.topics,
+			data: Bytes(log.data),
+			block_hash: hash,
+			block_number,
+			transaction_hash: extrinsics
+				.get(extrinsic_index as usize)
+				.map(|extrinsic| <B::Header as HeaderT>::Hashing::hash_of(extrinsic))
+				.unwrap_or_default(),
+			transaction_index: extrinsic_index.into(),
+			log_index: log_index.into(),
+			removed: false,
+		})
+		.collect()
+}
+
+impl<B, C, P, Balance> EVMPubSubApiT<B::Hash> for EVMPubSub<B, C, P, Balance>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockchainEvents<B> + BlockBackend<B> + Send + Sync + 'static,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	P: TransactionPool<Block = B, Hash = B::Hash> + 'static,
+	Balance: Codec + MaybeDisplay + MaybeFromStr + Send + Sync + 'static,
+{
+	type Metadata = sc_rpc::Metadata;
+
+	fn subscribe(
+		&self,
+		_metadata: Self::Metadata,
+		subscriber: Subscriber<PubSubResult<B::Hash>>,
+		kind: Kind,
+		params: Option<LogsFilter>,
+	) {
+		if kind != Kind::Logs && params.is_some() {
+			let _ = subscriber.reject(Error {
+				code: ErrorCode::InvalidParams,
+				message: "Invalid parameters: only the logs subscription accepts a filter".into(),
+				data: None,
+			});
+			return;
+		}
+
+		let client = self.client.clone();
+		match kind {
+			Kind::NewHeads => {
+				self.subscriptions.add(subscriber, |sink| {
+					let stream = client
+						.import_notification_stream()
+						.filter(|notification| future::ready(notification.is_new_best))
+						.map(move |notification| {
+							let head = head_response::<B, C, Balance>(&*client, &notification.header);
+							Ok::<_, ()>(Ok(PubSubResult::Header(Box::new(head))))
+						})
+						.compat();
+
+					sink.sink_map_err(|e| log::warn!(target: "evm", "Error sending notifications: {:?}", e))
+						.send_all(stream)
+						.map(|_| ())
+				});
+			}
+			Kind::Logs => {
+				let filter = params.unwrap_or_default();
+				self.subscriptions.add(subscriber, |sink| {
+					let stream = client
+						.import_notification_stream()
+						.filter(|notification| future::ready(notification.is_new_best))
+						.flat_map(move |notification| {
+							let logs = log_responses::<B, C, Balance>(&*client, &notification.header, &filter);
+							futures::stream::iter(logs)
+						})
+						.map(|log| Ok::<_, ()>(Ok(PubSubResult::Log(Box::new(log)))))
+						.compat();
+
+					sink.sink_map_err(|e| log::warn!(target: "evm", "Error sending notifications: {:?}", e))
+						.send_all(stream)
+						.map(|_| ())
+				});
+			}
+			Kind::NewPendingTransactions => {
+				let pool = self.pool.clone();
+				self.subscriptions.add(subscriber, |sink| {
+					let stream = pool
+						.import_notification_stream()
+						.map(|hash| Ok::<_, ()>(Ok(PubSubResult::TransactionHash(hash))))
+						.compat();
+
+					sink.sink_map_err(|e| log::warn!(target: "evm", "Error sending notifications: {:?}", e))
+						.send_all(stream)
+						.map(|_| ())
+				});
+			}
+		}
+	}
+
+	fn unsubscribe(&self, _metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool> {
+		Ok(self.subscriptions.cancel(id))
+	}
+}
@@ -0,0 +1,235 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! EVM pubsub rpc interface.
+
+use ethereum_types::{H160, H256, U256};
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId};
+use primitives::evm::{Bloom, Log};
+use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
+
+pub use rpc_impl_EVMPubSubApi::gen_server::EVMPubSubApi as EVMPubSubApiServer;
+
+/// Subscription kind
+#[derive(Debug, Eq, PartialEq, Deserialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum Kind {
+	/// The headers of the new best blocks
+	NewHeads,
+	/// The logs of the new best blocks matching the filter
+	Logs,
+	/// The hashes of the transactions imported to the pool
+	#[serde(alias = "pendingTransactions")]
+	NewPendingTransactions,
+}
+
+/// A single value or a list of values
+#[derive(Debug, Eq, PartialEq, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum VariadicValue<T> {
+	/// Single value
+	Single(T),
+	/// List of values
+	Multiple(Vec<T>),
+}
+
+impl<T: PartialEq> VariadicValue<T> {
+	/// Whether `value` is one of the values.
+	pub fn contains(&self, value: &T) -> bool {
+		match self {
+			VariadicValue::Single(v) => v == value,
+			VariadicValue::Multiple(values) => values.contains(value),
+		}
+	}
+
+	/// Whether any of the values is accepted by `f`.
+	pub fn any(&self, f: impl Fn(&T) -> bool) -> bool {
+		match self {
+			VariadicValue::Single(v) => f(v),
+			VariadicValue::Multiple(values) => values.iter().any(f),
+		}
+	}
+}
+
+/// Logs filter, `None` matches any value
+#[derive(Debug, Default, Eq, PartialEq, Deserialize, Clone)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "camelCase")]
+pub struct LogsFilter {
+	/// The addresses of the contracts emitting the logs
+	pub address: Option<VariadicValue<H160>>,
+	/// The topics of the logs by position
+	pub topics: Option<Vec<Option<VariadicValue<H256>>>>,
+}
+
+impl LogsFilter {
+	/// Whether `log` matches the filter.
+	pub fn matches(&self, log: &Log) -> bool {
+		if let Some(address) = &self.address {
+			if !address.contains(&log.address) {
+				return false;
+			}
+		}
+		self.topics.iter().flatten().enumerate().all(|(i, topic)| match topic {
+			Some(topic) => log.topics.get(i).map_or(false, |t| topic.contains(t)),
+			None => true,
+		})
+	}
+
+	/// Whether the logs in `bloom` may match the filter, the logs are
+	/// checked by `matches` only if so.
+	pub fn may_match_bloom(&self, bloom: &Bloom) -> bool {
+		let contains = |input: &[u8]| {
+			let mut input_bloom = Bloom::default();
+			input_bloom.accrue(input);
+			bloom.contains_bloom(&input_bloom)
+		};
+
+		if let Some(address) = &self.address {
+			if !address.any(|address| contains(address.as_bytes())) {
+				return false;
+			}
+		}
+		self.topics
+			.iter()
+			.flatten()
+			.flatten()
+			.all(|topic| topic.any(|topic| contains(topic.as_bytes())))
+	}
+}
+
+/// NewHeads response
+#[derive(Debug, Eq, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HeadResponse<Hash> {
+	/// Block hash
+	pub hash: Hash,
+	/// Parent block hash
+	pub parent_hash: Hash,
+	/// Block number
+	pub number: U256,
+	/// State root
+	pub state_root: Hash,
+	/// Extrinsics root
+	pub transactions_root: Hash,
+	/// The bloom filter of the logs of the block
+	pub logs_bloom: Bytes,
+	/// The gas used by the EVM transactions of the block
+	pub gas_used: U256,
+	/// The max gas of the block
+	pub gas_limit: U256,
+}
+
+/// Logs response
+#[derive(Debug, Eq, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LogResponse<Hash> {
+	/// The address of the contract emitting the log
+	pub address: H160,
+	/// Topics
+	pub topics: Vec<H256>,
+	/// Data
+	pub data: Bytes,
+	/// Block hash
+	pub block_hash: Hash,
+	/// Block number
+	pub block_number: U256,
+	/// The hash of the extrinsic emitting the log
+	pub transaction_hash: Hash,
+	/// The index of the extrinsic emitting the log
+	pub transaction_index: U256,
+	/// The block-wide index of the log
+	pub log_index: U256,
+	/// Whether the log is removed by a reorg, logs are only sent for the new
+	/// best blocks
+	pub removed: bool,
+}
+
+/// Subscription result
+#[derive(Debug, Eq, PartialEq, Serialize, Clone)]
+#[serde(untagged)]
+pub enum PubSubResult<Hash> {
+	/// Block header
+	Header(Box<HeadResponse<Hash>>),
+	/// Log
+	Log(Box<LogResponse<Hash>>),
+	/// Transaction hash
+	TransactionHash(Hash),
+}
+
+/// EVM pubsub rpc interface.
+#[rpc(server)]
+pub trait EVMPubSubApi<Hash> {
+	/// RPC Metadata
+	type Metadata;
+
+	/// Subscribe to the headers of the new best blocks, the logs of them
+	/// matching the filter, or the hashes of the transactions imported to the
+	/// pool.
+	#[pubsub(subscription = "eth_subscription", subscribe, name = "eth_subscribe")]
+	fn subscribe(
+		&self,
+		metadata: Self::Metadata,
+		subscriber: Subscriber<PubSubResult<Hash>>,
+		kind: Kind,
+		params: Option<LogsFilter>,
+	);
+
+	/// Unsubscribe from the subscription.
+	#[pubsub(subscription = "eth_subscription", unsubscribe, name = "eth_unsubscribe")]
+	fn unsubscribe(&self, metadata: Option<Self::Metadata>, id: SubscriptionId) -> Result<bool>;
+}
+
+#[test]
+fn logs_filter_should_work() {
+	let log = Log {
+		address: H160::from_low_u64_be(1),
+		topics: vec![H256::from_low_u64_be(2), H256::from_low_u64_be(3)],
+		data: vec![],
+	};
+	let mut bloom = Bloom::default();
+	bloom.accrue_log(&log);
+
+	let filter: LogsFilter = serde_json::from_str("{}").unwrap();
+	assert!(filter.matches(&log));
+	assert!(filter.may_match_bloom(&bloom));
+
+	let filter: LogsFilter = serde_json::from_str(
+		r#"{
+			"address": ["0x0000000000000000000000000000000000000001", "0x0000000000000000000000000000000000000004"],
+			"topics": [null, "0x0000000000000000000000000000000000000000000000000000000000000003"]
+		}"#,
+	)
+	.unwrap();
+	assert!(filter.matches(&log));
+	assert!(filter.may_match_bloom(&bloom));
+
+	let filter: LogsFilter =
+		serde_json::from_str(r#"{ "address": "0x0000000000000000000000000000000000000004" }"#).unwrap();
+	assert!(!filter.matches(&log));
+	assert!(!filter.may_match_bloom(&bloom));
+
+	let filter = LogsFilter {
+		address: None,
+		topics: Some(vec![None, None, Some(VariadicValue::Single(H256::from_low_u64_be(3)))]),
+	};
+	assert!(!filter.matches(&log));
+}
@@ -22,6 +22,7 @@
 #![allow(clippy::unused_unit)]
 #![allow(clippy::upper_case_acronyms)]
 
+use crate::runner::handler::STORAGE_SIZE;
 use codec::{Decode, Encode};
 use evm::Config as EvmConfig;
 use frame_support::{
@@ -29,7 +30,10 @@ use frame_support::{
 	ensure,
 	error::BadOrigin,
 	pallet_prelude::*,
-	traits::{Currency, EnsureOrigin, ExistenceRequirement, Get, OnKilledAccount, ReservableCurrency},
+	traits::{
+		BalanceStatus, Currency, EnsureOrigin, ExistenceRequirement, Get, OnKilledAccount, OnRuntimeUpgrade,
+		ReservableCurrency,
+	},
 	transactional,
 	weights::{Pays, PostDispatchInfo, Weight},
 	RuntimeDebug,
@@ -40,13 +44,16 @@ use primitive_types::{H256, U256};
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
 use sp_runtime::{
-	traits::{Convert, DispatchInfoOf, One, PostDispatchInfoOf, SignedExtension, UniqueSaturatedInto},
+	traits::{
+		Convert, DispatchInfoOf, One, PostDispatchInfoOf, Saturating, SignedExtension, UniqueSaturatedInto, Zero,
+	},
 	transaction_validity::TransactionValidityError,
 	Either, TransactionOutcome,
 };
 use sp_std::{marker::PhantomData, vec::Vec};
 pub use support::{
-	AddressMapping, EVMStateRentTrait, ExecutionMode, InvokeContext, TransactionPayment, EVM as EVMTrait,
+	AddressMapping, CallerAccessMode, EVMCallFilter, EVMCallerAccessControl, EVMStateRentTrait, ExecutionMode,
+	InvokeContext, PrecompileDelegateCallFilter, TransactionPayment, EVM as EVMTrait,
 };
 
 pub use crate::precompiles::{Precompile, Precompiles};
@@ -54,7 +61,10 @@ pub use crate::runner::Runner;
 pub use evm::{Context, ExitError, ExitFatal, ExitReason, ExitRevert, ExitSucceed};
 pub use orml_traits::account::MergeAccount;
 pub use primitives::{
-	evm::{Account, CallInfo, CreateInfo, EvmAddress, Log, Vicinity},
+	evm::{
+		Account, AccountNonceInfo, Bloom, CallInfo, ContractStorageInfo, CreateInfo, EstimateResourcesInfo, EvmAddress,
+		Log, Receipt, Vicinity,
+	},
 	MIRRORED_NFT_ADDRESS_START,
 };
 
@@ -147,10 +157,6 @@ pub mod module {
 		/// Precompiles associated with this EVM engine.
 		type Precompiles: Precompiles;
 
-		/// Chain ID of EVM.
-		#[pallet::constant]
-		type ChainId: Get<u64>;
-
 		/// Convert gas to weight.
 		type GasToWeight: Convert<u64, Weight>;
 
@@ -180,8 +186,32 @@ pub mod module {
 		#[pallet::constant]
 		type TreasuryAccount: Get<Self::AccountId>;
 
+		/// Required origin for deploying contracts without fee and managing
+		/// the contract deployment mode and allowed deployers.
 		type FreeDeploymentOrigin: EnsureOrigin<Self::Origin>;
 
+		/// Filter the calls to EVM contracts and precompiles.
+		type CallFilter: EVMCallFilter;
+
+		/// The number of blocks without nonce activity after which an account
+		/// without code and balance can be reaped.
+		#[pallet::constant]
+		type ReapInactivityPeriod: Get<Self::BlockNumber>;
+
+		/// The account holding the developer deposits of reaped accounts until
+		/// claimed by their owners.
+		#[pallet::constant]
+		type ReapedDepositPot: Get<Self::AccountId>;
+
+		/// The number of recent blocks to keep the transaction receipts for,
+		/// zero disables storing receipts.
+		#[pallet::constant]
+		type ReceiptsHistoryDepth: Get<Self::BlockNumber>;
+
+		/// The max number of listed callers of a contract.
+		#[pallet::constant]
+		type MaxListedCallers: Get<u32>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -210,6 +240,24 @@ pub mod module {
 		}
 	}
 
+	/// The restriction on creating contracts.
+	#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode)]
+	pub enum DeploymentMode {
+		/// Anyone can create contracts.
+		Permissionless,
+		/// Only the allowed deployers can create contracts.
+		AllowedDeployers,
+		/// Only the allowed deployers with contract development enabled can
+		/// create contracts.
+		AllowedDevelopers,
+	}
+
+	impl Default for DeploymentMode {
+		fn default() -> Self {
+			DeploymentMode::Permissionless
+		}
+	}
+
 	#[derive(Clone, Copy, Eq, PartialEq, RuntimeDebug, Encode, Decode)]
 	pub struct CodeInfo {
 		pub code_size: u32,
@@ -230,11 +278,49 @@ pub mod module {
 		pub code: Vec<u8>,
 	}
 
+	/// The chain ID of EVM, returned by the `CHAINID` opcode and signed by
+	/// EVM accounts to avoid replaying signatures on other chains. It's
+	/// only set by genesis or runtime upgrade migration, so that the chains
+	/// derived from the same runtime can use different chain IDs.
+	///
+	/// ChainId: u64
+	#[pallet::storage]
+	#[pallet::getter(fn chain_id)]
+	pub type ChainId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
 	/// Accounts info.
 	#[pallet::storage]
 	#[pallet::getter(fn accounts)]
 	pub type Accounts<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, AccountInfo<T>>;
 
+	/// The block number of the last nonce change of an account, or of
+	/// enabling the contract development.
+	#[pallet::storage]
+	#[pallet::getter(fn account_last_active)]
+	pub type AccountLastActive<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, T::BlockNumber, OptionQuery>;
+
+	/// The developer deposits of reaped accounts, claimable by their owners
+	/// from `ReapedDepositPot`.
+	#[pallet::storage]
+	#[pallet::getter(fn reaped_deposits)]
+	pub type ReapedDeposits<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, BalanceOf<T>, ValueQuery>;
+
+	/// True if the last active block of the existing accounts has been
+	/// initialized.
+	#[pallet::storage]
+	pub(crate) type UpgradedToAccountLastActive<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The bytes of storage used by the contracts, include the code, updated
+	/// as the storage is charged or refunded.
+	#[pallet::storage]
+	#[pallet::getter(fn contract_storage_sizes)]
+	pub type ContractStorageSizes<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, u32, ValueQuery>;
+
+	/// True if the storage sizes of the existing contracts have been
+	/// initialized.
+	#[pallet::storage]
+	pub(crate) type UpgradedToContractStorageSizes<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn account_storages)]
 	pub type AccountStorages<T: Config> =
@@ -253,13 +339,93 @@ pub mod module {
 	#[pallet::getter(fn network_contract_index)]
 	pub type NetworkContractIndex<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+	/// The caller access mode of contracts, the contract without access mode
+	/// can be called by anyone.
+	///
+	/// ContractCallerAccessModes: map EvmAddress => Option<CallerAccessMode>
+	#[pallet::storage]
+	#[pallet::getter(fn contract_caller_access_modes)]
+	pub type ContractCallerAccessModes<T: Config> =
+		StorageMap<_, Twox64Concat, EvmAddress, CallerAccessMode, OptionQuery>;
+
+	/// The listed callers of contracts, allowed or denied depending on the
+	/// caller access mode of the contract.
+	///
+	/// ContractListedCallers: double_map EvmAddress, EvmAddress => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn contract_listed_callers)]
+	pub type ContractListedCallers<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, EvmAddress, Twox64Concat, EvmAddress, (), OptionQuery>;
+
+	/// The number of listed callers of contracts, bounded by
+	/// `MaxListedCallers`.
+	///
+	/// ContractListedCallerCounts: map EvmAddress => u32
+	#[pallet::storage]
+	#[pallet::getter(fn contract_listed_caller_counts)]
+	pub type ContractListedCallerCounts<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, u32, ValueQuery>;
+
+	/// The restriction on creating contracts, anyone can create contracts
+	/// by default.
+	///
+	/// ContractDeploymentMode: DeploymentMode
+	#[pallet::storage]
+	#[pallet::getter(fn contract_deployment_mode)]
+	pub type ContractDeploymentMode<T: Config> = StorageValue<_, DeploymentMode, ValueQuery>;
+
+	/// The deployers allowed to create contracts when the deployment mode is
+	/// not permissionless.
+	///
+	/// AllowedDeployers: map EvmAddress => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn allowed_deployers)]
+	pub type AllowedDeployers<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, (), OptionQuery>;
+
+	/// The contracts allowed to run the state-changing precompiles in their
+	/// own context by DELEGATECALL or CALLCODE.
+	///
+	/// PrecompileDelegateCallers: map EvmAddress => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn precompile_delegate_callers)]
+	pub type PrecompileDelegateCallers<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, (), OptionQuery>;
+
 	/// Extrinsics origin for the current tx.
 	#[pallet::storage]
 	#[pallet::getter(fn extrinsic_origin)]
 	pub type ExtrinsicOrigin<T: Config> = StorageValue<_, T::AccountId>;
 
+	/// The receipts of EVM transactions in the recent
+	/// `ReceiptsHistoryDepth` blocks.
+	///
+	/// Receipts: map BlockNumber => Vec<Receipt>
+	#[pallet::storage]
+	#[pallet::getter(fn receipts)]
+	pub type Receipts<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, Vec<Receipt>, ValueQuery>;
+
+	/// The bloom filters of the logs emitted in the recent
+	/// `ReceiptsHistoryDepth` blocks, including the logs of the evm-bridge
+	/// calls.
+	///
+	/// LogsBlooms: map BlockNumber => Bloom
+	#[pallet::storage]
+	#[pallet::getter(fn logs_bloom)]
+	pub type LogsBlooms<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, Bloom, ValueQuery>;
+
+	/// The total gas used and the count of logs of the EVM transactions in
+	/// the current block.
+	///
+	/// BlockReceiptsUsage: (cumulative_used_gas, logs_count)
+	#[pallet::storage]
+	pub type BlockReceiptsUsage<T: Config> = StorageValue<_, (u64, u32), ValueQuery>;
+
+	/// The count and the bloom filter of logs emitted by the current EVM
+	/// transaction.
+	#[pallet::storage]
+	pub type TransactionLogs<T: Config> = StorageValue<_, (u32, Bloom), ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
+		pub chain_id: u64,
 		pub accounts: std::collections::BTreeMap<EvmAddress, GenesisAccount<BalanceOf<T>, T::Index>>,
 	}
 
@@ -267,6 +433,7 @@ pub mod module {
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
 			GenesisConfig {
+				chain_id: Default::default(),
 				accounts: Default::default(),
 			}
 		}
@@ -275,11 +442,14 @@ pub mod module {
 	#[pallet::genesis_build]
 	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
 		fn build(&self) {
+			ChainId::<T>::put(self.chain_id);
+
 			self.accounts.iter().for_each(|(address, account)| {
 				let account_id = T::AddressMapping::get_account_id(address);
 
 				let account_info = <AccountInfo<T>>::new(account.nonce, None);
 				<Accounts<T>>::insert(address, account_info);
+				AccountLastActive::<T>::insert(address, T::BlockNumber::zero());
 
 				T::Currency::deposit_creating(&account_id, account.balance);
 
@@ -294,9 +464,14 @@ pub mod module {
 					for (index, value) in &account.storage {
 						AccountStorages::<T>::insert(address, index, value);
 					}
+					if let Some(size) = <Pallet<T>>::count_contract_storage_size(address) {
+						ContractStorageSizes::<T>::insert(address, size);
+					}
 				}
 			});
 			NetworkContractIndex::<T>::put(MIRRORED_NFT_ADDRESS_START);
+			UpgradedToAccountLastActive::<T>::put(true);
+			UpgradedToContractStorageSizes::<T>::put(true);
 		}
 	}
 
@@ -344,6 +519,30 @@ pub mod module {
 		ContractSetCode(EvmAddress),
 		/// Selfdestructed contract code. \[contract\]
 		ContractSelfdestructed(EvmAddress),
+		/// Reaped an inactive account. \[address, deposit\]
+		AccountReaped(EvmAddress, BalanceOf<T>),
+		/// Claimed the developer deposit of reaped accounts. \[who, amount\]
+		ReapedDepositClaimed(T::AccountId, BalanceOf<T>),
+		/// Updated the caller access mode of contract. \[contract, mode\]
+		CallerAccessModeUpdated(EvmAddress, Option<CallerAccessMode>),
+		/// Added the caller to the caller list of contract. \[contract,
+		/// caller\]
+		ListedCallerAdded(EvmAddress, EvmAddress),
+		/// Removed the caller from the caller list of contract. \[contract,
+		/// caller\]
+		ListedCallerRemoved(EvmAddress, EvmAddress),
+		/// Updated the contract deployment mode. \[mode\]
+		DeploymentModeUpdated(DeploymentMode),
+		/// Added the allowed deployer. \[deployer\]
+		AllowedDeployerAdded(EvmAddress),
+		/// Removed the allowed deployer. \[deployer\]
+		AllowedDeployerRemoved(EvmAddress),
+		/// Added the contract allowed to delegate call precompiles.
+		/// \[contract\]
+		PrecompileDelegateCallerAdded(EvmAddress),
+		/// Removed the contract allowed to delegate call precompiles.
+		/// \[contract\]
+		PrecompileDelegateCallerRemoved(EvmAddress),
 	}
 
 	#[pallet::error]
@@ -370,13 +569,98 @@ pub mod module {
 		OutOfStorage,
 		/// Charge fee failed
 		ChargeFeeFailed,
+		/// Call to the contract is filtered
+		CallFiltered,
+		/// Account not found
+		AccountNotFound,
+		/// Account has code and can not be reaped
+		AccountIsContract,
+		/// Account has free balance and can not be reaped
+		AccountHasBalance,
+		/// Account has been active within the reap inactivity period
+		AccountStillActive,
+		/// Account has sent transactions and can not be reaped
+		AccountHasNonce,
+		/// No reaped deposit to claim
+		NoReapedDeposit,
+		/// The caller is not allowed to call the contract
+		CallerNotAllowed,
+		/// The deployer is not allowed to create contracts
+		DeployerNotAllowed,
+		/// The contract has reached the max number of listed callers
+		TooManyListedCallers,
 	}
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Start the reap inactivity period of the existing accounts from the
+		/// upgrade block, so that they can't be reaped right away. Initialize
+		/// the storage sizes of the existing contracts by counting their
+		/// storage.
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight: Weight = 0;
+
+			if UpgradedToAccountLastActive::<T>::get() {
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			} else {
+				let now = <frame_system::Pallet<T>>::block_number();
+				let mut count: Weight = 0;
+				for (address, _) in Accounts::<T>::iter() {
+					if !AccountLastActive::<T>::contains_key(&address) {
+						AccountLastActive::<T>::insert(&address, now);
+					}
+					count += 1;
+				}
+				UpgradedToAccountLastActive::<T>::put(true);
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(count.saturating_mul(2) + 1, count + 1));
+			}
+
+			if UpgradedToContractStorageSizes::<T>::get() {
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			} else {
+				let mut reads: Weight = 0;
+				let mut writes: Weight = 0;
+				for (address, account_info) in Accounts::<T>::iter() {
+					reads += 1;
+					if account_info.contract_info.is_some() {
+						if let Some(size) = Self::count_contract_storage_size(&address) {
+							ContractStorageSizes::<T>::insert(&address, size);
+							// the account, the code info and the storage items
+							reads = reads.saturating_add((size / STORAGE_SIZE) as Weight + 2);
+							writes += 1;
+						}
+					}
+				}
+				UpgradedToContractStorageSizes::<T>::put(true);
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(reads + 1, writes + 1));
+			}
+
+			weight
+		}
+
+		/// Prune the receipts out of the history depth.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			// the kills in on_finalize
+			let mut weight = T::DbWeight::get().writes(2);
+
+			let depth = T::ReceiptsHistoryDepth::get();
+			if !depth.is_zero() && now >= depth {
+				Receipts::<T>::remove(now - depth);
+				LogsBlooms::<T>::remove(now - depth);
+				weight = weight.saturating_add(T::DbWeight::get().writes(2));
+			}
+
+			weight
+		}
+
+		fn on_finalize(_now: T::BlockNumber) {
+			BlockReceiptsUsage::<T>::kill();
+			TransactionLogs::<T>::kill();
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -389,7 +673,7 @@ pub mod module {
 		/// - `gas_limit`: the maximum gas the call can use
 		/// - `storage_limit`: the total bytes the contract's storage can
 		///   increase by
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(T::GasToWeight::convert(*gas_limit).saturating_add(Pallet::<T>::receipt_weight()))]
 		pub fn call(
 			origin: OriginFor<T>,
 			target: EvmAddress,
@@ -401,6 +685,7 @@ pub mod module {
 			let who = ensure_signed(origin)?;
 			let source = T::AddressMapping::get_or_create_evm_address(&who);
 
+			Self::begin_receipt();
 			let info = Runner::<T>::call(
 				source,
 				source,
@@ -412,16 +697,17 @@ pub mod module {
 				T::config(),
 			)?;
 
+			let used_gas: u64 = info.used_gas.unique_saturated_into();
+			Self::store_receipt(info.exit_reason.is_succeed(), used_gas, None);
+
 			if info.exit_reason.is_succeed() {
 				Pallet::<T>::deposit_event(Event::<T>::Executed(target));
 			} else {
 				Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed(target, info.exit_reason, info.output));
 			}
 
-			let used_gas: u64 = info.used_gas.unique_saturated_into();
-
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(T::GasToWeight::convert(used_gas).saturating_add(Self::receipt_weight())),
 				pays_fee: Pays::Yes,
 			})
 		}
@@ -436,7 +722,7 @@ pub mod module {
 		/// - `gas_limit`: the maximum gas the call can use
 		/// - `storage_limit`: the total bytes the contract's storage can
 		///   increase by
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(T::GasToWeight::convert(*gas_limit).saturating_add(Pallet::<T>::receipt_weight()))]
 		#[transactional]
 		pub fn scheduled_call(
 			origin: OriginFor<T>,
@@ -460,19 +746,20 @@ pub mod module {
 				_payed = imbalance;
 			}
 
+			Self::begin_receipt();
 			let info = Runner::<T>::call(from, from, target, input, value, gas_limit, storage_limit, T::config())?;
 
+			let used_gas: u64 = info.used_gas.unique_saturated_into();
+			Self::store_receipt(info.exit_reason.is_succeed(), used_gas, None);
+
 			if info.exit_reason.is_succeed() {
 				Pallet::<T>::deposit_event(Event::<T>::Executed(target));
 			} else {
 				Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed(target, info.exit_reason, info.output));
 			}
 
-			let used_gas: u64 = info.used_gas.unique_saturated_into();
-
 			#[cfg(not(feature = "with-ethereum-compatibility"))]
 			{
-				use sp_runtime::traits::Zero;
 				let refund_gas = gas_limit.saturating_sub(used_gas);
 				if !refund_gas.is_zero() {
 					// ignore the result to continue. if it fails, just the user will not
@@ -487,7 +774,7 @@ pub mod module {
 			}
 
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(T::GasToWeight::convert(used_gas).saturating_add(Self::receipt_weight())),
 				pays_fee: Pays::Yes,
 			})
 		}
@@ -500,7 +787,7 @@ pub mod module {
 		/// - `gas_limit`: the maximum gas the call can use
 		/// - `storage_limit`: the total bytes the contract's storage can
 		///   increase by
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(T::GasToWeight::convert(*gas_limit).saturating_add(Pallet::<T>::receipt_weight()))]
 		pub fn create(
 			origin: OriginFor<T>,
 			init: Vec<u8>,
@@ -511,18 +798,20 @@ pub mod module {
 			let who = ensure_signed(origin)?;
 			let source = T::AddressMapping::get_or_create_evm_address(&who);
 
+			Self::begin_receipt();
 			let info = Runner::<T>::create(source, init, value, gas_limit, storage_limit, T::config())?;
 
+			let used_gas: u64 = info.used_gas.unique_saturated_into();
+			Self::store_receipt(info.exit_reason.is_succeed(), used_gas, Some(info.address));
+
 			if info.exit_reason.is_succeed() {
 				Pallet::<T>::deposit_event(Event::<T>::Created(info.address));
 			} else {
 				Pallet::<T>::deposit_event(Event::<T>::CreatedFailed(info.address, info.exit_reason, info.output));
 			}
 
-			let used_gas: u64 = info.used_gas.unique_saturated_into();
-
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(T::GasToWeight::convert(used_gas).saturating_add(Self::receipt_weight())),
 				pays_fee: Pays::Yes,
 			})
 		}
@@ -536,7 +825,7 @@ pub mod module {
 		/// - `gas_limit`: the maximum gas the call can use
 		/// - `storage_limit`: the total bytes the contract's storage can
 		///   increase by
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(T::GasToWeight::convert(*gas_limit).saturating_add(Pallet::<T>::receipt_weight()))]
 		pub fn create2(
 			origin: OriginFor<T>,
 			init: Vec<u8>,
@@ -548,18 +837,20 @@ pub mod module {
 			let who = ensure_signed(origin)?;
 			let source = T::AddressMapping::get_or_create_evm_address(&who);
 
+			Self::begin_receipt();
 			let info = Runner::<T>::create2(source, init, salt, value, gas_limit, storage_limit, T::config())?;
 
+			let used_gas: u64 = info.used_gas.unique_saturated_into();
+			Self::store_receipt(info.exit_reason.is_succeed(), used_gas, Some(info.address));
+
 			if info.exit_reason.is_succeed() {
 				Pallet::<T>::deposit_event(Event::<T>::Created(info.address));
 			} else {
 				Pallet::<T>::deposit_event(Event::<T>::CreatedFailed(info.address, info.exit_reason, info.output));
 			}
 
-			let used_gas: u64 = info.used_gas.unique_saturated_into();
-
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(T::GasToWeight::convert(used_gas).saturating_add(Self::receipt_weight())),
 				pays_fee: Pays::Yes,
 			})
 		}
@@ -572,7 +863,7 @@ pub mod module {
 		/// - `gas_limit`: the maximum gas the call can use
 		/// - `storage_limit`: the total bytes the contract's storage can
 		///   increase by
-		#[pallet::weight(T::GasToWeight::convert(*gas_limit))]
+		#[pallet::weight(T::GasToWeight::convert(*gas_limit).saturating_add(Pallet::<T>::receipt_weight()))]
 		pub fn create_network_contract(
 			origin: OriginFor<T>,
 			init: Vec<u8>,
@@ -584,21 +875,23 @@ pub mod module {
 
 			let source = T::NetworkContractSource::get();
 			let address = EvmAddress::from_low_u64_be(Self::network_contract_index());
+			Self::begin_receipt();
 			let info =
 				Runner::<T>::create_at_address(source, init, value, address, gas_limit, storage_limit, T::config())?;
 
 			NetworkContractIndex::<T>::mutate(|v| *v = v.saturating_add(One::one()));
 
+			let used_gas: u64 = info.used_gas.unique_saturated_into();
+			Self::store_receipt(info.exit_reason.is_succeed(), used_gas, Some(info.address));
+
 			if info.exit_reason.is_succeed() {
 				Pallet::<T>::deposit_event(Event::<T>::Created(info.address));
 			} else {
 				Pallet::<T>::deposit_event(Event::<T>::CreatedFailed(info.address, info.exit_reason, info.output));
 			}
 
-			let used_gas: u64 = info.used_gas.unique_saturated_into();
-
 			Ok(PostDispatchInfo {
-				actual_weight: Some(T::GasToWeight::convert(used_gas)),
+				actual_weight: Some(T::GasToWeight::convert(used_gas).saturating_add(Self::receipt_weight())),
 				pays_fee: Pays::Yes,
 			})
 		}
@@ -678,6 +971,7 @@ pub mod module {
 				}
 				Ok(())
 			})?;
+			AccountLastActive::<T>::insert(&address, <frame_system::Pallet<T>>::block_number());
 			Pallet::<T>::deposit_event(Event::<T>::ContractDevelopmentEnabled(who));
 			Ok(().into())
 		}
@@ -733,10 +1027,344 @@ pub mod module {
 
 			Ok(().into())
 		}
+
+		/// Issue an EVM call operation on a contract call scheduled by the
+		/// contract itself, and release the deposit reserved when the call
+		/// was scheduled.
+		///
+		/// - `contract`: the contract which scheduled the call to itself
+		/// - `input`: the data supplied for the call
+		/// - `gas_limit`: the maximum gas the call can use
+		/// - `storage_limit`: the total bytes the contract's storage can
+		///   increase by
+		/// - `deposit`: the deposit reserved when the call was scheduled
+		#[pallet::weight(T::GasToWeight::convert(*gas_limit).saturating_add(Pallet::<T>::receipt_weight()))]
+		pub fn scheduled_self_call(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+			input: Vec<u8>,
+			gas_limit: u64,
+			storage_limit: u32,
+			deposit: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+
+			let result = Self::scheduled_call(
+				frame_system::RawOrigin::Root.into(),
+				contract,
+				contract,
+				input,
+				Default::default(),
+				gas_limit,
+				storage_limit,
+			);
+
+			// release the deposit even if the call failed.
+			T::Currency::unreserve(&T::AddressMapping::get_account_id(&contract), deposit);
+
+			result
+		}
+
+		/// Reap an inactive account without code, nonce and free balance,
+		/// moving its developer deposit to `ReapedDepositPot` where the owner
+		/// can claim it back with `claim_reaped_deposit`.
+		///
+		/// - `address`: the account to reap
+		#[pallet::weight(<T as Config>::WeightInfo::reap_account())]
+		#[transactional]
+		pub fn reap_account(origin: OriginFor<T>, address: EvmAddress) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let account_info = Self::accounts(&address).ok_or(Error::<T>::AccountNotFound)?;
+			ensure!(account_info.contract_info.is_none(), Error::<T>::AccountIsContract);
+			ensure!(account_info.nonce.is_zero(), Error::<T>::AccountHasNonce);
+
+			let account_id = T::AddressMapping::get_account_id(&address);
+			ensure!(
+				T::Currency::free_balance(&account_id).is_zero(),
+				Error::<T>::AccountHasBalance
+			);
+			let last_active = Self::account_last_active(&address).ok_or(Error::<T>::AccountStillActive)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= last_active.saturating_add(T::ReapInactivityPeriod::get()),
+				Error::<T>::AccountStillActive
+			);
+
+			let deposit = account_info.developer_deposit.unwrap_or_default();
+			let remaining = T::Currency::repatriate_reserved(
+				&account_id,
+				&T::ReapedDepositPot::get(),
+				deposit,
+				BalanceStatus::Free,
+			)?;
+
+			let reaped = deposit.saturating_sub(remaining);
+			ReapedDeposits::<T>::mutate(&account_id, |amount| *amount = amount.saturating_add(reaped));
+
+			// the account has no code, removing it only clears the storages.
+			let _ = Self::remove_account(&address);
+
+			Pallet::<T>::deposit_event(Event::<T>::AccountReaped(address, reaped));
+			Ok(().into())
+		}
+
+		/// Claim the developer deposits of the caller's reaped accounts back
+		/// from `ReapedDepositPot`.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_reaped_deposit())]
+		#[transactional]
+		pub fn claim_reaped_deposit(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let amount = ReapedDeposits::<T>::take(&who);
+			ensure!(!amount.is_zero(), Error::<T>::NoReapedDeposit);
+			T::Currency::transfer(
+				&T::ReapedDepositPot::get(),
+				&who,
+				amount,
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			Pallet::<T>::deposit_event(Event::<T>::ReapedDepositClaimed(who, amount));
+			Ok(().into())
+		}
+
+		/// Set the contract deployment mode. Switching back to
+		/// `Permissionless` keeps the allowed deployers.
+		///
+		/// - `mode`: the new deployment mode
+		#[pallet::weight((<T as Config>::WeightInfo::set_deployment_mode(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_deployment_mode(origin: OriginFor<T>, mode: DeploymentMode) -> DispatchResultWithPostInfo {
+			T::FreeDeploymentOrigin::ensure_origin(origin)?;
+			ContractDeploymentMode::<T>::put(mode);
+			Pallet::<T>::deposit_event(Event::<T>::DeploymentModeUpdated(mode));
+			Ok(().into())
+		}
+
+		/// Allow the deployer to create contracts.
+		///
+		/// - `deployer`: the address to allow
+		#[pallet::weight((<T as Config>::WeightInfo::add_allowed_deployer(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn add_allowed_deployer(origin: OriginFor<T>, deployer: EvmAddress) -> DispatchResultWithPostInfo {
+			T::FreeDeploymentOrigin::ensure_origin(origin)?;
+			AllowedDeployers::<T>::insert(deployer, ());
+			Pallet::<T>::deposit_event(Event::<T>::AllowedDeployerAdded(deployer));
+			Ok(().into())
+		}
+
+		/// Disallow the deployer to create contracts.
+		///
+		/// - `deployer`: the address to disallow
+		#[pallet::weight((<T as Config>::WeightInfo::remove_allowed_deployer(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn remove_allowed_deployer(origin: OriginFor<T>, deployer: EvmAddress) -> DispatchResultWithPostInfo {
+			T::FreeDeploymentOrigin::ensure_origin(origin)?;
+			AllowedDeployers::<T>::remove(deployer);
+			Pallet::<T>::deposit_event(Event::<T>::AllowedDeployerRemoved(deployer));
+			Ok(().into())
+		}
+
+		/// Allow the contract to run the state-changing precompiles in its
+		/// own context by DELEGATECALL or CALLCODE. Only audited proxies
+		/// should be added, as the precompiles act on behalf of the caller
+		/// of the contract.
+		///
+		/// - `contract`: the contract address to allow
+		#[pallet::weight((<T as Config>::WeightInfo::add_precompile_delegate_caller(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn add_precompile_delegate_caller(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+		) -> DispatchResultWithPostInfo {
+			T::NetworkContractOrigin::ensure_origin(origin)?;
+			PrecompileDelegateCallers::<T>::insert(contract, ());
+			Pallet::<T>::deposit_event(Event::<T>::PrecompileDelegateCallerAdded(contract));
+			Ok(().into())
+		}
+
+		/// Disallow the contract to delegate call the state-changing
+		/// precompiles.
+		///
+		/// - `contract`: the contract address to disallow
+		#[pallet::weight((<T as Config>::WeightInfo::remove_precompile_delegate_caller(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn remove_precompile_delegate_caller(
+			origin: OriginFor<T>,
+			contract: EvmAddress,
+		) -> DispatchResultWithPostInfo {
+			T::NetworkContractOrigin::ensure_origin(origin)?;
+			PrecompileDelegateCallers::<T>::remove(contract);
+			Pallet::<T>::deposit_event(Event::<T>::PrecompileDelegateCallerRemoved(contract));
+			Ok(().into())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// The weight of `begin_receipt` and `store_receipt` for an EVM
+	/// transaction.
+	pub fn receipt_weight() -> Weight {
+		T::DbWeight::get().reads_writes(5, 6)
+	}
+
+	/// Start the receipt of a new EVM transaction. The logs emitted since
+	/// the last receipt, by the evm-bridge calls of other extrinsics, still
+	/// count in the log index of the block.
+	fn begin_receipt() {
+		let (logs_count, logs_bloom) = TransactionLogs::<T>::take();
+		if !logs_count.is_zero() {
+			BlockReceiptsUsage::<T>::mutate(|(_, logs)| *logs = logs.saturating_add(logs_count));
+			Self::accrue_block_logs_bloom(&logs_bloom);
+		}
+	}
+
+	/// Store the receipt of the current EVM transaction with the logs
+	/// emitted by it.
+	fn store_receipt(status: bool, used_gas: u64, contract_address: Option<EvmAddress>) {
+		let (logs_count, logs_bloom) = TransactionLogs::<T>::take();
+		if T::ReceiptsHistoryDepth::get().is_zero() {
+			return;
+		}
+
+		let (cumulative_used_gas, first_log_index) = BlockReceiptsUsage::<T>::mutate(|(gas, logs)| {
+			let first_log_index = *logs;
+			*gas = gas.saturating_add(used_gas);
+			*logs = logs.saturating_add(logs_count);
+			(*gas, first_log_index)
+		});
+		if !logs_count.is_zero() {
+			Self::accrue_block_logs_bloom(&logs_bloom);
+		}
+
+		Receipts::<T>::append(
+			frame_system::Pallet::<T>::block_number(),
+			Receipt {
+				extrinsic_index: frame_system::Pallet::<T>::extrinsic_index().unwrap_or_default(),
+				status,
+				used_gas,
+				cumulative_used_gas,
+				contract_address,
+				first_log_index,
+				logs_count,
+				logs_bloom,
+			},
+		);
+	}
+
+	/// Add the entries of `logs_bloom` to the bloom filter of the logs of
+	/// the current block.
+	fn accrue_block_logs_bloom(logs_bloom: &Bloom) {
+		if T::ReceiptsHistoryDepth::get().is_zero() {
+			return;
+		}
+		LogsBlooms::<T>::mutate(frame_system::Pallet::<T>::block_number(), |bloom| {
+			bloom.accrue_bloom(logs_bloom)
+		});
+	}
+
+	/// The gas used by the EVM transactions of the block `number`, which is
+	/// zero once the receipts of the block are pruned.
+	pub fn block_gas_used(number: T::BlockNumber) -> u64 {
+		Self::receipts(number)
+			.last()
+			.map_or(0, |receipt| receipt.cumulative_used_gas)
+	}
+
+	/// Check whether the address is a contract marked as deployed.
+	pub fn is_deployed_contract(address: &EvmAddress) -> bool {
+		matches!(
+			Self::accounts(address),
+			Some(AccountInfo {
+				contract_info: Some(ContractInfo { deployed: true, .. }),
+				..
+			})
+		)
+	}
+
+	/// Returns the storage usage of the contract, None if the address is not
+	/// a contract.
+	pub fn contract_storage_info(address: &EvmAddress) -> Option<ContractStorageInfo<BalanceOf<T>>> {
+		let contract_info = Self::accounts(address)?.contract_info?;
+		let used_storage = Self::contract_storage_sizes(address);
+		// the deposit of the used storage and the listed callers, the other
+		// reserves of the contract account, e.g. the deposits of scheduled
+		// calls, are excluded.
+		let listed_caller_count = Self::contract_listed_caller_counts(address);
+		let deposit = T::StorageDepositPerByte::get()
+			.saturating_mul(used_storage.into())
+			.saturating_add(Self::listed_caller_deposit().saturating_mul(listed_caller_count.into()));
+
+		Some(ContractStorageInfo {
+			used_storage,
+			deposit,
+			maintainer: contract_info.maintainer,
+			deployed: contract_info.deployed,
+		})
+	}
+
+	/// Counts the bytes of storage used by the contract, include the code.
+	/// Iterates the storage of the contract, only used to initialize
+	/// `ContractStorageSizes`.
+	fn count_contract_storage_size(address: &EvmAddress) -> Option<u32> {
+		let contract_info = Self::accounts(address)?.contract_info?;
+		let code_size = Self::code_infos(&contract_info.code_hash).map_or(0, |code_info| code_info.code_size);
+		let storage_size = (AccountStorages::<T>::iter_prefix(address).count() as u32).saturating_mul(STORAGE_SIZE);
+
+		Some(
+			code_size
+				.saturating_add(T::NewContractExtraBytes::get())
+				.saturating_add(storage_size),
+		)
+	}
+
+	/// Returns the account the address is mapped to with its nonce, and the
+	/// evm nonce of the address.
+	pub fn account_nonce_info(address: &EvmAddress) -> AccountNonceInfo<T::AccountId, T::Index> {
+		let account_id = T::AddressMapping::get_account_id(address);
+
+		AccountNonceInfo {
+			account_nonce: frame_system::Pallet::<T>::account_nonce(&account_id),
+			evm_nonce: Self::accounts(address).map_or_else(Default::default, |account_info| account_info.nonce),
+			account_id,
+		}
+	}
+
+	/// Simulates a call, or a create if `target` is None, and returns the gas
+	/// used together with the storage deposit required by the incremental
+	/// storage. Nothing is committed.
+	pub fn estimate_resources(
+		source: EvmAddress,
+		target: Option<EvmAddress>,
+		input: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+	) -> Result<EstimateResourcesInfo<BalanceOf<T>>, DispatchError> {
+		let mut config = T::config().clone();
+		config.estimate = true;
+
+		let (exit_reason, used_gas, used_storage) = match target {
+			Some(target) => {
+				let info = Runner::<T>::call(source, source, target, input, value, gas_limit, storage_limit, &config)?;
+				(info.exit_reason, info.used_gas, info.used_storage)
+			}
+			None => {
+				let info = Runner::<T>::create(source, input, value, gas_limit, storage_limit, &config)?;
+				(info.exit_reason, info.used_gas, info.used_storage)
+			}
+		};
+
+		let storage_deposit = T::StorageDepositPerByte::get().saturating_mul((used_storage.max(0) as u32).into());
+
+		Ok(EstimateResourcesInfo {
+			exit_reason,
+			used_gas,
+			used_storage,
+			storage_deposit,
+		})
+	}
+
 	/// Removes an account from Accounts and AccountStorages.
 	pub fn remove_account(address: &EvmAddress) -> Result<u32, ExitError> {
 		let mut size = 0u32;
@@ -760,7 +1388,13 @@ impl<T: Config> Pallet<T> {
 		}
 
 		Accounts::<T>::remove(address);
+		AccountLastActive::<T>::remove(address);
 		AccountStorages::<T>::remove_prefix(address);
+		ContractStorageSizes::<T>::remove(address);
+		ContractCallerAccessModes::<T>::remove(address);
+		// bounded by `MaxListedCallers`
+		ContractListedCallers::<T>::remove_prefix(address);
+		ContractListedCallerCounts::<T>::remove(address);
 
 		Ok(size)
 	}
@@ -871,6 +1505,113 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Return true if the caller is allowed to call the contract by the
+	/// caller access mode of the contract.
+	pub fn is_caller_allowed(contract: EvmAddress, caller: EvmAddress) -> bool {
+		match Self::contract_caller_access_modes(contract) {
+			None => true,
+			Some(CallerAccessMode::AllowList) => ContractListedCallers::<T>::contains_key(contract, caller),
+			Some(CallerAccessMode::DenyList) => !ContractListedCallers::<T>::contains_key(contract, caller),
+		}
+	}
+
+	/// Return true if the deployer is allowed to create contracts by the
+	/// contract deployment mode.
+	pub fn is_deployer_allowed(deployer: EvmAddress) -> bool {
+		match Self::contract_deployment_mode() {
+			DeploymentMode::Permissionless => true,
+			DeploymentMode::AllowedDeployers => AllowedDeployers::<T>::contains_key(deployer),
+			DeploymentMode::AllowedDevelopers => {
+				AllowedDeployers::<T>::contains_key(deployer)
+					&& Self::accounts(deployer).map_or(false, |account_info| account_info.developer_deposit.is_some())
+			}
+		}
+	}
+
+	/// Ensures `who` is the maintainer of the contract.
+	fn ensure_maintainer(who: &T::AccountId, contract: EvmAddress) -> DispatchResult {
+		let maintainer = <Self as EVMStateRentTrait<T::AccountId, BalanceOf<T>>>::query_maintainer(contract)?;
+		let who = T::AddressMapping::get_evm_address(who).ok_or(Error::<T>::AddressNotMapped)?;
+		ensure!(who == maintainer, Error::<T>::NoPermission);
+		Ok(())
+	}
+
+	/// The storage deposit of a listed caller, reserved on the contract
+	/// account like the deposit of contract storage.
+	fn listed_caller_deposit() -> BalanceOf<T> {
+		T::StorageDepositPerByte::get().saturating_mul(STORAGE_SIZE.into())
+	}
+
+	/// Sets the caller access mode of a contract, clears the caller list if
+	/// the restriction is removed and refunds the storage deposit to `who`.
+	#[transactional]
+	fn do_set_caller_access_mode(
+		who: T::AccountId,
+		contract: EvmAddress,
+		mode: Option<CallerAccessMode>,
+	) -> DispatchResult {
+		Self::ensure_maintainer(&who, contract)?;
+
+		match mode {
+			Some(mode) => ContractCallerAccessModes::<T>::insert(contract, mode),
+			None => {
+				ContractCallerAccessModes::<T>::remove(contract);
+				// bounded by `MaxListedCallers`
+				ContractListedCallers::<T>::remove_prefix(contract);
+				let count = ContractListedCallerCounts::<T>::take(contract);
+				let deposit = Self::listed_caller_deposit().saturating_mul(count.into());
+				T::Currency::repatriate_reserved(
+					&T::AddressMapping::get_account_id(&contract),
+					&who,
+					deposit,
+					BalanceStatus::Free,
+				)?;
+			}
+		}
+
+		Pallet::<T>::deposit_event(Event::<T>::CallerAccessModeUpdated(contract, mode));
+		Ok(())
+	}
+
+	/// Adds or removes a caller of the caller list of a contract. `who` pays
+	/// the storage deposit of an added caller, which is reserved on the
+	/// contract account, and gets it back when a caller is removed.
+	#[transactional]
+	fn do_update_listed_caller(
+		who: T::AccountId,
+		contract: EvmAddress,
+		caller: EvmAddress,
+		listed: bool,
+	) -> DispatchResult {
+		Self::ensure_maintainer(&who, contract)?;
+
+		let contract_account_id = T::AddressMapping::get_account_id(&contract);
+		let deposit = Self::listed_caller_deposit();
+		let is_listed = ContractListedCallers::<T>::contains_key(contract, caller);
+
+		if listed {
+			if !is_listed {
+				ContractListedCallerCounts::<T>::try_mutate(contract, |count| -> DispatchResult {
+					ensure!(*count < T::MaxListedCallers::get(), Error::<T>::TooManyListedCallers);
+					*count += 1;
+					Ok(())
+				})?;
+				T::Currency::transfer(&who, &contract_account_id, deposit, ExistenceRequirement::AllowDeath)?;
+				T::Currency::reserve(&contract_account_id, deposit)?;
+				ContractListedCallers::<T>::insert(contract, caller, ());
+			}
+			Pallet::<T>::deposit_event(Event::<T>::ListedCallerAdded(contract, caller));
+		} else {
+			if is_listed {
+				ContractListedCallerCounts::<T>::mutate(contract, |count| *count = count.saturating_sub(1));
+				T::Currency::repatriate_reserved(&contract_account_id, &who, deposit, BalanceStatus::Free)?;
+				ContractListedCallers::<T>::remove(contract, caller);
+			}
+			Pallet::<T>::deposit_event(Event::<T>::ListedCallerRemoved(contract, caller));
+		}
+		Ok(())
+	}
+
 	/// Mark contract as deployed
 	///
 	/// If maintainer is provider then it will check maintainer
@@ -956,6 +1697,11 @@ impl<T: Config> Pallet<T> {
 			ensure!(!contract_info.deployed, Error::<T>::ContractAlreadyDeployed);
 
 			AccountStorages::<T>::remove_prefix(contract);
+			ContractStorageSizes::<T>::remove(contract);
+			ContractCallerAccessModes::<T>::remove(contract);
+			// bounded by `MaxListedCallers`
+			ContractListedCallers::<T>::remove_prefix(contract);
+			ContractListedCallerCounts::<T>::remove(contract);
 
 			CodeInfos::<T>::mutate_exists(&contract_info.code_hash, |maybe_code_info| {
 				if let Some(code_info) = maybe_code_info.as_mut() {
@@ -1001,6 +1747,17 @@ impl<T: Config> EVMTrait<T::AccountId> for Pallet<T> {
 			config.estimate = true;
 		}
 
+		if let ExecutionMode::Static = mode {
+			return Runner::<T>::static_call(
+				context.sender,
+				context.origin,
+				context.contract,
+				input,
+				gas_limit,
+				&config,
+			);
+		}
+
 		frame_support::storage::with_transaction(|| {
 			let result = Runner::<T>::call(
 				context.sender,
@@ -1028,7 +1785,9 @@ impl<T: Config> EVMTrait<T::AccountId> for Pallet<T> {
 							TransactionOutcome::Rollback(Ok(info))
 						}
 					}
-					ExecutionMode::View | ExecutionMode::EstimateGas => TransactionOutcome::Rollback(Ok(info)),
+					ExecutionMode::View | ExecutionMode::EstimateGas | ExecutionMode::Static => {
+						TransactionOutcome::Rollback(Ok(info))
+					}
 				},
 				Err(e) => TransactionOutcome::Rollback(Err(e)),
 			}
@@ -1076,6 +1835,46 @@ impl<T: Config> EVMStateRentTrait<T::AccountId, BalanceOf<T>> for Pallet<T> {
 	}
 }
 
+impl<T: Config> EVMCallerAccessControl<T::AccountId> for Pallet<T> {
+	fn query_caller_access_mode(contract: EvmAddress) -> Option<CallerAccessMode> {
+		Pallet::<T>::contract_caller_access_modes(contract)
+	}
+
+	fn query_is_listed_caller(contract: EvmAddress, caller: EvmAddress) -> bool {
+		ContractListedCallers::<T>::contains_key(contract, caller)
+	}
+
+	fn query_listed_caller_count(contract: EvmAddress) -> u32 {
+		Pallet::<T>::contract_listed_caller_counts(contract)
+	}
+
+	fn is_caller_allowed(contract: EvmAddress, caller: EvmAddress) -> bool {
+		Pallet::<T>::is_caller_allowed(contract, caller)
+	}
+
+	fn set_caller_access_mode(
+		from: T::AccountId,
+		contract: EvmAddress,
+		mode: Option<CallerAccessMode>,
+	) -> DispatchResult {
+		Pallet::<T>::do_set_caller_access_mode(from, contract, mode)
+	}
+
+	fn add_listed_caller(from: T::AccountId, contract: EvmAddress, caller: EvmAddress) -> DispatchResult {
+		Pallet::<T>::do_update_listed_caller(from, contract, caller, true)
+	}
+
+	fn remove_listed_caller(from: T::AccountId, contract: EvmAddress, caller: EvmAddress) -> DispatchResult {
+		Pallet::<T>::do_update_listed_caller(from, contract, caller, false)
+	}
+}
+
+impl<T: Config> PrecompileDelegateCallFilter for Pallet<T> {
+	fn is_allowed(caller: EvmAddress) -> bool {
+		PrecompileDelegateCallers::<T>::contains_key(caller)
+	}
+}
+
 pub struct CallKillAccount<T>(PhantomData<T>);
 impl<T: Config> OnKilledAccount<T::AccountId> for CallKillAccount<T> {
 	fn on_killed_account(who: &T::AccountId) {
@@ -1089,6 +1888,28 @@ impl<T: Config> OnKilledAccount<T::AccountId> for CallKillAccount<T> {
 	}
 }
 
+/// The chain ID of EVM stored on chain.
+pub struct EvmChainId<T>(PhantomData<T>);
+impl<T: Config> Get<u64> for EvmChainId<T> {
+	fn get() -> u64 {
+		Pallet::<T>::chain_id()
+	}
+}
+
+/// Migration to initialize the chain ID of EVM with `ChainIdValue` for the
+/// existing chain, the chain ID is never overwritten once it's set.
+pub struct MigrateChainId<T, ChainIdValue>(PhantomData<(T, ChainIdValue)>);
+impl<T: Config, ChainIdValue: Get<u64>> OnRuntimeUpgrade for MigrateChainId<T, ChainIdValue> {
+	fn on_runtime_upgrade() -> Weight {
+		if ChainId::<T>::exists() {
+			return T::DbWeight::get().reads(1);
+		}
+
+		ChainId::<T>::put(ChainIdValue::get());
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+}
+
 pub fn code_hash(code: &[u8]) -> H256 {
 	H256::from_slice(Keccak256::digest(code).as_slice())
 }
@@ -134,13 +134,16 @@ parameter_types! {
 ord_parameter_types! {
 	pub const CouncilAccount: AccountId32 = AccountId32::from([1u8; 32]);
 	pub const TreasuryAccount: AccountId32 = AccountId32::from([2u8; 32]);
+	pub const ReapedDepositPot: AccountId32 = AccountId32::from([3u8; 32]);
 	pub const NetworkContractAccount: AccountId32 = AccountId32::from([0u8; 32]);
 	pub const NewContractExtraBytes: u32 = 100;
 	pub const StorageDepositPerByte: u64 = 10;
 	pub const DeveloperDeposit: u64 = 1000;
 	pub const DeploymentFee: u64 = 200;
 	pub const MaxCodeSize: u32 = 1000;
-	pub const ChainId: u64 = 1;
+	pub const ReapInactivityPeriod: u64 = 100;
+	pub const ReceiptsHistoryDepth: u64 = 10;
+	pub const MaxListedCallers: u32 = 2;
 }
 
 impl Config for Test {
@@ -153,7 +156,6 @@ impl Config for Test {
 
 	type Event = Event;
 	type Precompiles = ();
-	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = ();
 
@@ -163,6 +165,11 @@ impl Config for Test {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId32>;
+	type CallFilter = ();
+	type ReapInactivityPeriod = ReapInactivityPeriod;
+	type ReapedDepositPot = ReapedDepositPot;
+	type ReceiptsHistoryDepth = ReceiptsHistoryDepth;
+	type MaxListedCallers = MaxListedCallers;
 
 	type WeightInfo = ();
 }
@@ -256,7 +263,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	pallet_balances::GenesisConfig::<Test>::default()
 		.assimilate_storage(&mut t)
 		.unwrap();
-	evm_mod::GenesisConfig::<Test> { accounts }
+	evm_mod::GenesisConfig::<Test> { chain_id: 1, accounts }
 		.assimilate_storage(&mut t)
 		.unwrap();
 
@@ -21,8 +21,8 @@
 use crate::{
 	precompiles::Precompiles,
 	runner::storage_meter::{StorageMeter, StorageMeterHandler},
-	AccountInfo, AccountStorages, Accounts, AddressMapping, Codes, Config, ContractInfo, Error, Event, Log,
-	MergeAccount, Pallet, Vicinity,
+	AccountInfo, AccountLastActive, AccountStorages, Accounts, AddressMapping, Codes, Config, ContractInfo,
+	ContractStorageSizes, EVMCallFilter, Error, Event, Log, MergeAccount, Pallet, TransactionLogs, Vicinity,
 };
 use evm::{Capture, Context, CreateScheme, ExitError, ExitReason, Opcode, Runtime, Stack, Transfer};
 use evm_gasometer::{self as gasometer, Gasometer};
@@ -205,6 +205,7 @@ impl<'vicinity, 'config, T: Config> Handler<'vicinity, 'config, '_, T> {
 				*maybe_account = Some(account_info);
 			}
 		});
+		AccountLastActive::<T>::insert(&address, <frame_system::Pallet<T>>::block_number());
 	}
 
 	pub fn create_address(scheme: CreateScheme) -> H160 {
@@ -362,7 +363,7 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 	}
 
 	fn chain_id(&self) -> U256 {
-		U256::from(T::ChainId::get())
+		U256::from(Pallet::<T>::chain_id())
 	}
 
 	fn exists(&self, _address: H160) -> bool {
@@ -413,7 +414,12 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 	}
 
 	fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) -> Result<(), ExitError> {
-		Pallet::<T>::deposit_event(Event::<T>::Log(Log { address, topics, data }));
+		let log = Log { address, topics, data };
+		TransactionLogs::<T>::mutate(|(count, bloom)| {
+			*count = count.saturating_add(1);
+			bloom.accrue_log(&log);
+		});
+		Pallet::<T>::deposit_event(Event::<T>::Log(log));
 
 		Ok(())
 	}
@@ -455,6 +461,10 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 			return Capture::Exit((ExitError::OutOfGas.into(), None, Vec::new()));
 		}
 
+		if !Pallet::<T>::is_deployer_allowed(caller) {
+			return Capture::Exit((ExitError::Other("DeployerNotAllowed".into()).into(), None, Vec::new()));
+		}
+
 		let mut after_gas = self.gasometer.gas();
 		if self.config.call_l64_after_gas {
 			after_gas = l64(after_gas);
@@ -546,6 +556,14 @@ impl<'vicinity, 'config, 'meter, T: Config> HandlerT for Handler<'vicinity, 'con
 			return Capture::Exit((ExitError::OutOfGas.into(), Vec::new()));
 		}
 
+		if !T::CallFilter::is_allowed(&code_address) {
+			return Capture::Exit((ExitError::Other("CallFiltered".into()).into(), Vec::new()));
+		}
+
+		if !Pallet::<T>::is_caller_allowed(code_address, context.caller) {
+			return Capture::Exit((ExitError::Other("CallerNotAllowed".into()).into(), Vec::new()));
+		}
+
 		let mut after_gas = self.gasometer.gas();
 		if self.config.call_l64_after_gas {
 			after_gas = l64(after_gas);
@@ -717,12 +735,17 @@ impl<T: Config> StorageMeterHandler for StorageMeterHandlerImpl<T> {
 			T::Currency::unreserve(&user, amount);
 			T::Currency::transfer(&user, &contract_acc, amount, ExistenceRequirement::AllowDeath)?;
 			T::Currency::reserve(&contract_acc, amount)?;
+			ContractStorageSizes::<T>::mutate(contract, |size| *size = size.saturating_add(storage));
 		} else {
 			let storage = refunded - used;
 			let amount = T::StorageDepositPerByte::get().saturating_mul(storage.into());
 
 			// user can't be a dead account
 			T::Currency::repatriate_reserved(&contract_acc, &user, amount, BalanceStatus::Reserved)?;
+			ContractStorageSizes::<T>::mutate_exists(contract, |maybe_size| {
+				let size = maybe_size.unwrap_or_default().saturating_sub(storage);
+				*maybe_size = if size.is_zero() { None } else { Some(size) };
+			});
 		};
 
 		Ok(())
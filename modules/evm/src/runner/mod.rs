@@ -19,7 +19,7 @@
 pub mod handler;
 pub mod storage_meter;
 
-use crate::{AddressMapping, BalanceOf, CallInfo, Config, CreateInfo, Error, Pallet, Vicinity};
+use crate::{AddressMapping, BalanceOf, CallInfo, Config, CreateInfo, EVMCallFilter, Error, Pallet, Vicinity};
 use evm::{CreateScheme, ExitError, ExitReason};
 use evm_gasometer::{self as gasometer};
 use evm_runtime::Handler as HandlerT;
@@ -64,6 +64,11 @@ impl<T: Config> Runner<T> {
 			origin: source,
 		};
 
+		// system contracts are created by the network contract origin.
+		if assigned_address.is_none() && !Pallet::<T>::is_deployer_allowed(source) {
+			return Err(Error::<T>::DeployerNotAllowed.into());
+		}
+
 		let address = if let Some(addr) = assigned_address {
 			addr
 		} else {
@@ -195,6 +200,14 @@ impl<T: Config> Runner<T> {
 			origin,
 		};
 
+		if !T::CallFilter::is_allowed(&target) {
+			return Err(Error::<T>::CallFiltered.into());
+		}
+
+		if !Pallet::<T>::is_caller_allowed(target, sender) {
+			return Err(Error::<T>::CallerNotAllowed.into());
+		}
+
 		// if the contract not deployed, the caller must be developer or contract.
 		// if the contract not exists, let evm try to execute it and handle the error.
 		if Handler::<T>::is_undeployed_contract(&target) && !Handler::<T>::has_permission_to_call(&sender) {
@@ -238,6 +251,70 @@ impl<T: Config> Runner<T> {
 		})?
 	}
 
+	/// Execute a call with STATICCALL semantics. State changes are always
+	/// discarded and no storage is reserved from the origin.
+	pub fn static_call(
+		sender: H160,
+		origin: H160,
+		target: H160,
+		input: Vec<u8>,
+		gas_limit: u64,
+		config: &evm::Config,
+	) -> Result<CallInfo, DispatchError> {
+		log::debug!(
+			target: "evm",
+			"static_call: sender:{:?}, origin: {:?}, target: {:?}, input: {:?}, gas_limit: {:?}",
+			sender,
+			origin,
+			target,
+			input,
+			gas_limit,
+		);
+
+		let vicinity = Vicinity {
+			gas_price: U256::one(),
+			origin,
+		};
+
+		if !T::CallFilter::is_allowed(&target) {
+			return Err(Error::<T>::CallFiltered.into());
+		}
+
+		if !Pallet::<T>::is_caller_allowed(target, sender) {
+			return Err(Error::<T>::CallerNotAllowed.into());
+		}
+
+		if Handler::<T>::is_undeployed_contract(&target) && !Handler::<T>::has_permission_to_call(&sender) {
+			return Err(Error::<T>::NoPermission.into());
+		}
+
+		Handler::<T>::run_transaction(&vicinity, gas_limit, 0, target, true, config, |substate| {
+			let code = substate.code(target);
+			let transaction_cost = gasometer::call_transaction_cost(&code);
+			if substate.gasometer.record_transaction(transaction_cost).is_err() {
+				return TransactionOutcome::Rollback(Err(DispatchError::Other("OutOfGas")));
+			}
+
+			let (reason, out) = substate.execute(sender, target, U256::zero(), code, input);
+
+			let call_info = CallInfo {
+				exit_reason: reason,
+				output: out,
+				used_gas: U256::from(substate.used_gas()),
+				used_storage: substate.used_storage(),
+			};
+
+			log::debug!(
+				target: "evm",
+				"static_call-result: call_info {:?}",
+				call_info
+			);
+
+			// discard any state changes, e.g. the ones made by precompiles
+			TransactionOutcome::Rollback(Ok(call_info))
+		})?
+	}
+
 	pub fn create(
 		source: H160,
 		init: Vec<u8>,
@@ -936,7 +936,170 @@ fn should_selfdestruct() {
 			EVM::selfdestruct(Origin::signed(bob_account_id), contract_address),
 			Error::<Test>::NoPermission
 		);
+		assert_eq!(EVM::contract_storage_sizes(contract_address), 284);
 		assert_ok!(EVM::selfdestruct(Origin::signed(alice_account_id), contract_address));
+		assert_eq!(EVM::contract_storage_sizes(contract_address), 0);
+	});
+}
+
+#[test]
+fn scheduled_self_call_should_release_deposit() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex("0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032").unwrap();
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+
+		// create contract
+		let result = Runner::<Test>::create(alice(), contract, 0, 21_000_000, 21_000_000, <Test as Config>::config()).unwrap();
+		let contract_address = result.address;
+		assert!(!EVM::is_deployed_contract(&contract_address));
+
+		assert_ok!(EVM::deploy(Origin::signed(alice_account_id.clone()), contract_address));
+		assert!(EVM::is_deployed_contract(&contract_address));
+		assert!(!EVM::is_deployed_contract(&alice()));
+
+		// reserve the deposit as scheduling the call
+		let contract_account_id = <Test as Config>::AddressMapping::get_account_id(&contract_address);
+		assert_ok!(Balances::transfer(Origin::signed(alice_account_id), contract_account_id.clone(), 1000));
+		let reserved = reserved_balance(contract_address);
+		assert_ok!(Balances::reserve(&contract_account_id, 100));
+		assert_eq!(reserved_balance(contract_address), reserved + 100);
+
+		// multiply(2, 3)
+		let multiply = from_hex("0x165c4a1600000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003").unwrap();
+
+		assert_noop!(EVM::scheduled_self_call(Origin::signed(contract_account_id), contract_address, multiply.clone(), 1000000, 1000000, 100), BadOrigin);
+		assert_ok!(EVM::scheduled_self_call(Origin::root(), contract_address, multiply, 1000000, 1000000, 100));
+		assert_eq!(reserved_balance(contract_address), reserved);
+
+		let event = Event::evm_mod(crate::Event::Executed(contract_address));
+		assert!(System::events().iter().any(|record| record.event == event));
+	});
+}
+
+#[test]
+fn contract_storage_info_should_work() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex("0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032").unwrap();
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+
+		// not a contract
+		assert_eq!(EVM::contract_storage_info(&alice()), None);
+
+		// create contract
+		let result =
+			Runner::<Test>::create(alice(), contract, 0, 21_000_000, 21_000_000, <Test as Config>::config()).unwrap();
+		let contract_address = result.address;
+		assert_eq!(result.used_storage, 284);
+
+		assert_eq!(
+			EVM::contract_storage_info(&contract_address),
+			Some(ContractStorageInfo {
+				used_storage: 284,
+				deposit: 284 * <Test as Config>::StorageDepositPerByte::get(),
+				maintainer: alice(),
+				deployed: false,
+			})
+		);
+
+		assert_ok!(EVM::deploy(Origin::signed(alice_account_id.clone()), contract_address));
+		assert_eq!(
+			EVM::contract_storage_info(&contract_address).map(|info| info.deployed),
+			Some(true)
+		);
+
+		// the other reserves of the contract account are not the storage deposit
+		let contract_account_id = <Test as Config>::AddressMapping::get_account_id(&contract_address);
+		assert_ok!(Balances::transfer(
+			Origin::signed(alice_account_id),
+			contract_account_id.clone(),
+			1000
+		));
+		assert_ok!(Balances::reserve(&contract_account_id, 100));
+		assert_eq!(
+			EVM::contract_storage_info(&contract_address).map(|info| (info.used_storage, info.deposit)),
+			Some((284, 284 * <Test as Config>::StorageDepositPerByte::get()))
+		);
+	});
+}
+
+#[test]
+fn migrate_contract_storage_sizes_should_work() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex("0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032").unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result =
+			Runner::<Test>::create(alice(), contract, 0, 21_000_000, 21_000_000, <Test as Config>::config()).unwrap();
+		let contract_address = result.address;
+		assert_eq!(EVM::contract_storage_sizes(contract_address), 284);
+
+		// the contract created before the storage sizes were tracked
+		ContractStorageSizes::<Test>::remove(contract_address);
+		UpgradedToContractStorageSizes::<Test>::kill();
+		<EVM as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert!(UpgradedToContractStorageSizes::<Test>::get());
+		assert_eq!(EVM::contract_storage_sizes(contract_address), 284);
+		assert_eq!(EVM::contract_storage_sizes(alice()), 0);
+
+		// only migrate once
+		ContractStorageSizes::<Test>::remove(contract_address);
+		<EVM as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(EVM::contract_storage_sizes(contract_address), 0);
+	});
+}
+
+#[test]
+fn account_nonce_info_should_work() {
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+		let evm_nonce = EVM::accounts(alice()).unwrap().nonce;
+
+		assert_eq!(
+			EVM::account_nonce_info(&alice()),
+			AccountNonceInfo {
+				account_id: alice_account_id.clone(),
+				account_nonce: 0,
+				evm_nonce,
+			}
+		);
+
+		Handler::<Test>::inc_nonce(alice());
+		frame_system::Pallet::<Test>::inc_account_nonce(&alice_account_id);
+		frame_system::Pallet::<Test>::inc_account_nonce(&alice_account_id);
+		assert_eq!(
+			EVM::account_nonce_info(&alice()),
+			AccountNonceInfo {
+				account_id: alice_account_id,
+				account_nonce: 2,
+				evm_nonce: evm_nonce + 1,
+			}
+		);
+
+		// not an evm account
+		let address = H160::from_low_u64_be(1234);
+		assert_eq!(EVM::account_nonce_info(&address).evm_nonce, 0);
 	});
 }
 
@@ -1214,3 +1377,618 @@ fn evm_execute_mode_should_work() {
 		assert_eq!(balance(alice()), alice_balance);
 	});
 }
+
+#[test]
+fn should_reap_account() {
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+		let bob_account_id = <Test as Config>::AddressMapping::get_account_id(&bob());
+		let charlie_account_id = <Test as Config>::AddressMapping::get_account_id(&charlie());
+
+		assert_noop!(
+			EVM::reap_account(Origin::signed(bob_account_id.clone()), H160::default()),
+			Error::<Test>::AccountNotFound
+		);
+		assert_noop!(
+			EVM::reap_account(Origin::signed(bob_account_id.clone()), contract_a()),
+			Error::<Test>::AccountIsContract
+		);
+
+		// accounts that have sent transactions are never reaped
+		Balances::make_free_balance_be(&alice_account_id, 0);
+		System::set_block_number(101);
+		assert_noop!(
+			EVM::reap_account(Origin::signed(bob_account_id.clone()), alice()),
+			Error::<Test>::AccountHasNonce
+		);
+
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&charlie_account_id, DeveloperDeposit::get());
+		assert_ok!(EVM::enable_contract_development(Origin::signed(
+			charlie_account_id.clone()
+		)));
+		assert_eq!(EVM::account_last_active(charlie()), Some(1));
+		assert_eq!(EVM::accounts(charlie()).unwrap().nonce, 0);
+
+		Balances::make_free_balance_be(&charlie_account_id, 1);
+		System::set_block_number(101);
+		assert_noop!(
+			EVM::reap_account(Origin::signed(bob_account_id.clone()), charlie()),
+			Error::<Test>::AccountHasBalance
+		);
+		Balances::make_free_balance_be(&charlie_account_id, 0);
+
+		System::set_block_number(100);
+		assert_noop!(
+			EVM::reap_account(Origin::signed(bob_account_id.clone()), charlie()),
+			Error::<Test>::AccountStillActive
+		);
+
+		System::set_block_number(101);
+		assert_ok!(EVM::reap_account(Origin::signed(bob_account_id), charlie()));
+		let event = Event::evm_mod(crate::Event::AccountReaped(charlie(), DeveloperDeposit::get()));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		assert!(Accounts::<Test>::get(charlie()).is_none());
+		assert_eq!(EVM::account_last_active(charlie()), None);
+		assert_eq!(reserved_balance(charlie()), 0);
+		assert_eq!(Balances::free_balance(ReapedDepositPot::get()), DeveloperDeposit::get());
+		assert_eq!(EVM::reaped_deposits(&charlie_account_id), DeveloperDeposit::get());
+
+		assert_ok!(EVM::claim_reaped_deposit(Origin::signed(charlie_account_id.clone())));
+		let event = Event::evm_mod(crate::Event::ReapedDepositClaimed(
+			charlie_account_id.clone(),
+			DeveloperDeposit::get(),
+		));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(EVM::reaped_deposits(&charlie_account_id), 0);
+		assert_eq!(balance(charlie()), DeveloperDeposit::get());
+		assert_eq!(Balances::free_balance(ReapedDepositPot::get()), 0);
+
+		assert_noop!(
+			EVM::claim_reaped_deposit(Origin::signed(charlie_account_id)),
+			Error::<Test>::NoReapedDeposit
+		);
+	});
+}
+
+#[test]
+fn migrate_account_last_active_should_work() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(EVM::account_last_active(alice()), Some(0));
+
+		AccountLastActive::<Test>::remove(alice());
+		UpgradedToAccountLastActive::<Test>::kill();
+		System::set_block_number(5);
+		<EVM as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(EVM::account_last_active(alice()), Some(5));
+		assert_eq!(EVM::account_last_active(bob()), Some(0));
+
+		// only migrate once
+		AccountLastActive::<Test>::remove(alice());
+		<EVM as OnRuntimeUpgrade>::on_runtime_upgrade();
+		assert_eq!(EVM::account_last_active(alice()), None);
+	});
+}
+
+#[test]
+fn caller_access_control_should_work() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex("0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032").unwrap();
+	// multiply(2, 3)
+	let multiply = from_hex("0x165c4a1600000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003").unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result =
+			Runner::<Test>::create(alice(), contract, 0, 21_000_000, 21_000_000, <Test as Config>::config()).unwrap();
+		let contract_address = result.address;
+		assert_ok!(EVM::deploy_free(
+			Origin::signed(CouncilAccount::get()),
+			contract_address
+		));
+
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+		let bob_account_id = <Test as Config>::AddressMapping::get_account_id(&bob());
+
+		// no restriction by default
+		assert_eq!(EVM::query_caller_access_mode(contract_address), None);
+		assert!(EVM::is_caller_allowed(contract_address, bob()));
+
+		// only maintainer can update the caller access mode
+		assert_noop!(
+			EVM::set_caller_access_mode(
+				bob_account_id.clone(),
+				contract_address,
+				Some(CallerAccessMode::AllowList)
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			EVM::add_listed_caller(alice_account_id.clone(), H160::default(), bob()),
+			Error::<Test>::ContractNotFound
+		);
+
+		// allow list
+		assert_ok!(EVM::set_caller_access_mode(
+			alice_account_id.clone(),
+			contract_address,
+			Some(CallerAccessMode::AllowList)
+		));
+		let event = Event::evm_mod(crate::Event::CallerAccessModeUpdated(
+			contract_address,
+			Some(CallerAccessMode::AllowList),
+		));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_noop!(
+			Runner::<Test>::call(
+				bob(),
+				bob(),
+				contract_address,
+				multiply.clone(),
+				0,
+				1000000,
+				1000000,
+				<Test as Config>::config()
+			),
+			Error::<Test>::CallerNotAllowed
+		);
+
+		assert_ok!(EVM::add_listed_caller(
+			alice_account_id.clone(),
+			contract_address,
+			bob()
+		));
+		let event = Event::evm_mod(crate::Event::ListedCallerAdded(contract_address, bob()));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert!(EVM::query_is_listed_caller(contract_address, bob()));
+		let result = Runner::<Test>::call(
+			bob(),
+			bob(),
+			contract_address,
+			multiply.clone(),
+			0,
+			1000000,
+			1000000,
+			<Test as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(U256::from(result.output.as_slice()), 6.into());
+
+		// deny list
+		assert_ok!(EVM::set_caller_access_mode(
+			alice_account_id.clone(),
+			contract_address,
+			Some(CallerAccessMode::DenyList)
+		));
+		assert!(!EVM::is_caller_allowed(contract_address, bob()));
+		assert!(EVM::is_caller_allowed(contract_address, alice()));
+		assert_noop!(
+			Runner::<Test>::call(
+				bob(),
+				bob(),
+				contract_address,
+				multiply.clone(),
+				0,
+				1000000,
+				1000000,
+				<Test as Config>::config()
+			),
+			Error::<Test>::CallerNotAllowed
+		);
+
+		assert_ok!(EVM::remove_listed_caller(
+			alice_account_id.clone(),
+			contract_address,
+			bob()
+		));
+		let event = Event::evm_mod(crate::Event::ListedCallerRemoved(contract_address, bob()));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert!(EVM::is_caller_allowed(contract_address, bob()));
+
+		// remove the restriction clears the caller list
+		assert_ok!(EVM::add_listed_caller(
+			alice_account_id.clone(),
+			contract_address,
+			bob()
+		));
+		assert_ok!(EVM::set_caller_access_mode(alice_account_id, contract_address, None));
+		assert_eq!(EVM::query_caller_access_mode(contract_address), None);
+		assert!(!EVM::query_is_listed_caller(contract_address, bob()));
+		assert_ok!(Runner::<Test>::call(
+			bob(),
+			bob(),
+			contract_address,
+			multiply,
+			0,
+			1000000,
+			1000000,
+			<Test as Config>::config()
+		));
+	});
+}
+
+#[test]
+fn listed_caller_deposit_and_limit_should_work() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	//	 function multiply(uint a, uint b) public pure returns(uint) {
+	// 	 	return a * b;
+	// 	 }
+	// }
+	let contract = from_hex("0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032").unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result =
+			Runner::<Test>::create(alice(), contract, 0, 21_000_000, 21_000_000, <Test as Config>::config()).unwrap();
+		let contract_address = result.address;
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+		let deposit = StorageDepositPerByte::get() * STORAGE_SIZE as u64;
+		let alice_balance = balance(alice());
+		let contract_reserved = reserved_balance(contract_address);
+
+		assert_ok!(EVM::set_caller_access_mode(
+			alice_account_id.clone(),
+			contract_address,
+			Some(CallerAccessMode::AllowList)
+		));
+		assert_ok!(EVM::add_listed_caller(
+			alice_account_id.clone(),
+			contract_address,
+			bob()
+		));
+		// adding a listed caller again takes no deposit
+		assert_ok!(EVM::add_listed_caller(
+			alice_account_id.clone(),
+			contract_address,
+			bob()
+		));
+		assert_ok!(EVM::add_listed_caller(
+			alice_account_id.clone(),
+			contract_address,
+			charlie()
+		));
+		assert_eq!(EVM::query_listed_caller_count(contract_address), 2);
+		assert_eq!(balance(alice()), alice_balance - 2 * deposit);
+		assert_eq!(reserved_balance(contract_address), contract_reserved + 2 * deposit);
+
+		assert_noop!(
+			EVM::add_listed_caller(alice_account_id.clone(), contract_address, H160::default()),
+			Error::<Test>::TooManyListedCallers
+		);
+
+		assert_ok!(EVM::remove_listed_caller(
+			alice_account_id.clone(),
+			contract_address,
+			bob()
+		));
+		assert_eq!(EVM::query_listed_caller_count(contract_address), 1);
+		assert_eq!(balance(alice()), alice_balance - deposit);
+		assert_eq!(reserved_balance(contract_address), contract_reserved + deposit);
+
+		// removing the restriction refunds the deposit of all listed callers
+		assert_ok!(EVM::set_caller_access_mode(alice_account_id, contract_address, None));
+		assert_eq!(EVM::query_listed_caller_count(contract_address), 0);
+		assert_eq!(balance(alice()), alice_balance);
+		assert_eq!(reserved_balance(contract_address), contract_reserved);
+	});
+}
+
+#[test]
+fn estimate_resources_should_work() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Test {
+	// 	function foo() public pure {
+	// 		require(false, "error message");
+	// 	}
+	// }
+	let contract = from_hex("0x608060405234801561001057600080fd5b5060df8061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063c298557814602d575b600080fd5b60336035565b005b600060a8576040517f08c379a000000000000000000000000000000000000000000000000000000000815260040180806020018281038252600d8152602001807f6572726f72206d6573736167650000000000000000000000000000000000000081525060200191505060405180910390fd5b56fea265627a7a7231582066b3ee33bedba8a318d0d66610145030fdc0f982b11f5160d366e15e4d8ba2ef64736f6c63430005110032").unwrap();
+
+	new_test_ext().execute_with(|| {
+		let info = EVM::estimate_resources(alice(), None, contract, 0, 1000000, 1000000).unwrap();
+
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(info.used_storage, 323);
+		assert_eq!(
+			info.storage_deposit,
+			323 * <Test as Config>::StorageDepositPerByte::get()
+		);
+	});
+}
+
+#[test]
+fn deployment_mode_should_work() {
+	// pragma solidity ^0.5.0;
+	//
+	// contract Factory {
+	//     Contract[] newContracts;
+	//
+	//     function createContract () public payable {
+	//         Contract newContract = new Contract();
+	//         newContracts.push(newContract);
+	//     }
+	// }
+	//
+	// contract Contract {}
+	let contract = from_hex("0x608060405234801561001057600080fd5b5061016f806100206000396000f3fe608060405260043610610041576000357c0100000000000000000000000000000000000000000000000000000000900463ffffffff168063412a5a6d14610046575b600080fd5b61004e610050565b005b600061005a6100e2565b604051809103906000f080158015610076573d6000803e3d6000fd5b50905060008190806001815401808255809150509060018203906000526020600020016000909192909190916101000a81548173ffffffffffffffffffffffffffffffffffffffff021916908373ffffffffffffffffffffffffffffffffffffffff1602179055505050565b6040516052806100f28339019056fe6080604052348015600f57600080fd5b50603580601d6000396000f3fe6080604052600080fdfea165627a7a7230582092dc1966a8880ddf11e067f9dd56a632c11a78a4afd4a9f05924d427367958cc0029a165627a7a723058202b2cc7384e11c452cdbf39b68dada2d5e10a632cc0174a354b8b8c83237e28a40029").unwrap();
+	// Factory.createContract
+	let create_contract = from_hex("0x412a5a6d").unwrap();
+
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+		let bob_account_id = <Test as Config>::AddressMapping::get_account_id(&bob());
+
+		// permissionless by default
+		assert_eq!(EVM::contract_deployment_mode(), DeploymentMode::Permissionless);
+		assert!(EVM::is_deployer_allowed(bob()));
+
+		assert_noop!(
+			EVM::set_deployment_mode(Origin::signed(bob_account_id.clone()), DeploymentMode::AllowedDeployers),
+			BadOrigin
+		);
+		assert_noop!(
+			EVM::add_allowed_deployer(Origin::signed(bob_account_id.clone()), alice()),
+			BadOrigin
+		);
+
+		assert_ok!(EVM::set_deployment_mode(
+			Origin::signed(CouncilAccount::get()),
+			DeploymentMode::AllowedDeployers
+		));
+		let event = Event::evm_mod(crate::Event::DeploymentModeUpdated(DeploymentMode::AllowedDeployers));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		assert_noop!(
+			Runner::<Test>::create(
+				alice(),
+				contract.clone(),
+				0,
+				1000000000,
+				1000000000,
+				<Test as Config>::config()
+			),
+			Error::<Test>::DeployerNotAllowed
+		);
+
+		assert_ok!(EVM::add_allowed_deployer(
+			Origin::signed(CouncilAccount::get()),
+			alice()
+		));
+		let event = Event::evm_mod(crate::Event::AllowedDeployerAdded(alice()));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		let result = Runner::<Test>::create(
+			alice(),
+			contract.clone(),
+			0,
+			1000000000,
+			1000000000,
+			<Test as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		let factory_contract_address = result.address;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		deploy_free(factory_contract_address);
+
+		// the factory is not an allowed deployer
+		let result = Runner::<Test>::call(
+			alice(),
+			alice(),
+			factory_contract_address,
+			create_contract.clone(),
+			0,
+			1000000000,
+			1000000000,
+			<Test as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Revert(ExitRevert::Reverted));
+
+		assert_ok!(EVM::add_allowed_deployer(
+			Origin::signed(CouncilAccount::get()),
+			factory_contract_address
+		));
+		let result = Runner::<Test>::call(
+			alice(),
+			alice(),
+			factory_contract_address,
+			create_contract,
+			0,
+			1000000000,
+			1000000000,
+			<Test as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+
+		// allowed deployers must enable contract development
+		assert_ok!(EVM::set_deployment_mode(
+			Origin::signed(CouncilAccount::get()),
+			DeploymentMode::AllowedDevelopers
+		));
+		assert!(!EVM::is_deployer_allowed(alice()));
+		assert_ok!(EVM::enable_contract_development(Origin::signed(alice_account_id)));
+		assert!(EVM::is_deployer_allowed(alice()));
+
+		assert_ok!(EVM::remove_allowed_deployer(
+			Origin::signed(CouncilAccount::get()),
+			alice()
+		));
+		let event = Event::evm_mod(crate::Event::AllowedDeployerRemoved(alice()));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert!(!EVM::is_deployer_allowed(alice()));
+
+		// switch to permissionless
+		assert_ok!(EVM::set_deployment_mode(
+			Origin::signed(CouncilAccount::get()),
+			DeploymentMode::Permissionless
+		));
+		assert_ok!(Runner::<Test>::create(
+			bob(),
+			contract,
+			0,
+			1000000000,
+			1000000000,
+			<Test as Config>::config()
+		));
+	});
+}
+
+#[test]
+fn precompile_delegate_callers_should_work() {
+	new_test_ext().execute_with(|| {
+		let bob_account_id = <Test as Config>::AddressMapping::get_account_id(&bob());
+		assert!(!<EVM as PrecompileDelegateCallFilter>::is_allowed(alice()));
+
+		assert_noop!(
+			EVM::add_precompile_delegate_caller(Origin::signed(bob_account_id.clone()), alice()),
+			BadOrigin
+		);
+		assert_ok!(EVM::add_precompile_delegate_caller(
+			Origin::signed(NetworkContractAccount::get()),
+			alice()
+		));
+		let event = Event::evm_mod(crate::Event::PrecompileDelegateCallerAdded(alice()));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(EVM::precompile_delegate_callers(alice()), Some(()));
+		assert!(<EVM as PrecompileDelegateCallFilter>::is_allowed(alice()));
+		assert!(!<EVM as PrecompileDelegateCallFilter>::is_allowed(bob()));
+
+		assert_noop!(
+			EVM::remove_precompile_delegate_caller(Origin::signed(bob_account_id), alice()),
+			BadOrigin
+		);
+		assert_ok!(EVM::remove_precompile_delegate_caller(
+			Origin::signed(NetworkContractAccount::get()),
+			alice()
+		));
+		let event = Event::evm_mod(crate::Event::PrecompileDelegateCallerRemoved(alice()));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert!(!<EVM as PrecompileDelegateCallFilter>::is_allowed(alice()));
+	});
+}
+
+#[test]
+fn should_store_receipts() {
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+
+		// PUSH1 0x2a PUSH1 0x00 PUSH1 0x00 LOG1 STOP
+		let init = from_hex("0x602a60006000a100").unwrap();
+		assert_ok!(EVM::create(
+			Origin::signed(alice_account_id.clone()),
+			init,
+			0,
+			1000000,
+			1000
+		));
+		assert_ok!(EVM::call(
+			Origin::signed(alice_account_id),
+			contract_b(),
+			Vec::new(),
+			0,
+			1000000,
+			0
+		));
+
+		let receipts = EVM::receipts(1);
+		assert_eq!(receipts.len(), 2);
+
+		let contract_address = receipts[0].contract_address.unwrap();
+		let mut logs_bloom = Bloom::default();
+		logs_bloom.accrue_log(&Log {
+			address: contract_address,
+			topics: vec![H256::from_low_u64_be(0x2a)],
+			data: vec![],
+		});
+		assert_eq!(receipts[0].status, true);
+		assert_eq!(receipts[0].cumulative_used_gas, receipts[0].used_gas);
+		assert_eq!(receipts[0].first_log_index, 0);
+		assert_eq!(receipts[0].logs_count, 1);
+		assert_eq!(receipts[0].logs_bloom, logs_bloom);
+
+		assert_eq!(receipts[1].status, false);
+		assert_eq!(
+			receipts[1].cumulative_used_gas,
+			receipts[0].used_gas + receipts[1].used_gas
+		);
+		assert_eq!(receipts[1].contract_address, None);
+		assert_eq!(receipts[1].first_log_index, 1);
+		assert_eq!(receipts[1].logs_count, 0);
+		assert_eq!(receipts[1].logs_bloom, Bloom::default());
+
+		EVM::on_finalize(1);
+		assert_eq!(BlockReceiptsUsage::<Test>::get(), (0, 0));
+		assert_eq!(EVM::block_gas_used(1), receipts[1].cumulative_used_gas);
+		assert_eq!(EVM::logs_bloom(1), logs_bloom);
+
+		// prune the receipts out of the history depth
+		System::set_block_number(10);
+		EVM::on_initialize(10);
+		assert_eq!(EVM::receipts(1).len(), 2);
+		System::set_block_number(11);
+		EVM::on_initialize(11);
+		assert!(EVM::receipts(1).is_empty());
+		assert_eq!(EVM::block_gas_used(1), 0);
+		assert_eq!(EVM::logs_bloom(1), Bloom::default());
+	});
+}
+
+#[test]
+fn receipts_should_count_logs_of_bridge_calls() {
+	new_test_ext().execute_with(|| {
+		let alice_account_id = <Test as Config>::AddressMapping::get_account_id(&alice());
+
+		// PUSH1 0x2a PUSH1 0x00 PUSH1 0x00 LOG1 STOP
+		let init = from_hex("0x602a60006000a100").unwrap();
+
+		// the runner is used directly by evm-bridge calls, without receipt
+		assert_ok!(Runner::<Test>::create(
+			alice(),
+			init.clone(),
+			0,
+			1000000,
+			1000,
+			<Test as Config>::config()
+		));
+		assert_ok!(EVM::create(Origin::signed(alice_account_id), init, 0, 1000000, 1000));
+
+		let receipts = EVM::receipts(1);
+		assert_eq!(receipts.len(), 1);
+		assert_eq!(receipts[0].first_log_index, 1);
+		assert_eq!(receipts[0].logs_count, 1);
+		assert_eq!(BlockReceiptsUsage::<Test>::get().1, 2);
+		// the block bloom includes the logs of the bridge call
+		assert!(EVM::logs_bloom(1).contains_bloom(&receipts[0].logs_bloom));
+		assert_ne!(EVM::logs_bloom(1), receipts[0].logs_bloom);
+	});
+}
+
+#[test]
+fn migrate_chain_id_should_work() {
+	frame_support::parameter_types! {
+		pub const OldChainId: u64 = 595;
+	}
+
+	new_test_ext().execute_with(|| {
+		assert_eq!(EVM::chain_id(), 1);
+
+		// never overwrite the chain id set by genesis
+		MigrateChainId::<Test, OldChainId>::on_runtime_upgrade();
+		assert_eq!(EVM::chain_id(), 1);
+
+		ChainId::<Test>::kill();
+		MigrateChainId::<Test, OldChainId>::on_runtime_upgrade();
+		assert_eq!(EVM::chain_id(), 595);
+	});
+}
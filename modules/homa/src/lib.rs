@@ -31,9 +31,11 @@
 use frame_support::{pallet_prelude::*, transactional};
 use frame_system::pallet_prelude::*;
 use primitives::{Balance, EraIndex};
-use sp_runtime::RuntimeDebug;
-use support::HomaProtocol;
+use sp_runtime::{traits::StaticLookup, FixedPointNumber, RuntimeDebug};
+use support::{math, HomaProtocol};
 
+mod mock;
+mod tests;
 pub mod weights;
 
 pub use module::*;
@@ -63,10 +65,25 @@ pub mod module {
 		/// The core of Homa protocol.
 		type Homa: HomaProtocol<Self::AccountId, Balance, EraIndex>;
 
+		/// The max number of redeem requests matched in a single mint by the
+		/// Homa protocol, used to weigh the mint.
+		#[pallet::constant]
+		type MaxFastMatchRedeemers: Get<u32>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
 
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The liquid amount minted is less than the minimum expected.
+		InsufficientLiquidAmount,
+		/// The staking amount to inject exceeds the maximum allowed.
+		ExcessiveStakingAmount,
+		/// Overflow.
+		Overflow,
+	}
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -76,14 +93,50 @@ pub mod module {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Inject DOT to staking pool and mint LDOT in a certain exchange rate
-		/// decided by staking pool.
+		/// decided by staking pool, fails if the LDOT minted is less than
+		/// expected.
 		///
 		/// - `amount`: the DOT amount to inject into staking pool.
-		#[pallet::weight(<T as Config>::WeightInfo::mint())]
+		/// - `min_liquid_amount`: the minimum LDOT amount to mint, protects
+		///   from the exchange rate changes before the extrinsic is included.
+		#[pallet::weight(<T as Config>::WeightInfo::mint(T::MaxFastMatchRedeemers::get()))]
 		#[transactional]
-		pub fn mint(origin: OriginFor<T>, #[pallet::compact] amount: Balance) -> DispatchResultWithPostInfo {
+		pub fn mint(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: Balance,
+			#[pallet::compact] min_liquid_amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let liquid_amount = T::Homa::mint(&who, amount)?;
+			ensure!(liquid_amount >= min_liquid_amount, Error::<T>::InsufficientLiquidAmount);
+			Ok(().into())
+		}
+
+		/// Inject DOT to staking pool to mint the target amount of LDOT in a
+		/// certain exchange rate decided by staking pool. The DOT amount to
+		/// inject is rounded up, so the LDOT minted may slightly exceed the
+		/// target.
+		///
+		/// - `liquid_amount`: the target LDOT amount to mint.
+		/// - `max_staking_amount`: the maximum DOT amount to inject.
+		#[pallet::weight(<T as Config>::WeightInfo::mint_for_exact_liquid(T::MaxFastMatchRedeemers::get()))]
+		#[transactional]
+		pub fn mint_for_exact_liquid(
+			origin: OriginFor<T>,
+			#[pallet::compact] liquid_amount: Balance,
+			#[pallet::compact] max_staking_amount: Balance,
+		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			T::Homa::mint(&who, amount)?;
+			// the liquid amount is issued as `reciprocal(exchange_rate) * staking_amount`
+			// rounded down, round up the inverse to make sure the target amount is reached
+			let staking_amount = T::Homa::liquid_exchange_rate()
+				.reciprocal()
+				.and_then(|rate| math::checked_div_int(liquid_amount, rate, math::Rounding::Up))
+				.ok_or(Error::<T>::Overflow)?;
+			ensure!(staking_amount <= max_staking_amount, Error::<T>::ExcessiveStakingAmount);
+
+			let minted = T::Homa::mint(&who, staking_amount)?;
+			ensure!(minted >= liquid_amount, Error::<T>::InsufficientLiquidAmount);
 			Ok(().into())
 		}
 
@@ -125,5 +178,79 @@ pub mod module {
 			T::Homa::withdraw_redemption(&who)?;
 			Ok(().into())
 		}
+
+		/// Request to redeem DOT with LDOT. The LDOT is kept by staking pool
+		/// and the request will be unbonded when next era beginning. If
+		/// `allow_fast_match` is true, the request can be matched by minters
+		/// before that, which redeems DOT immediately with the fast match
+		/// fee deducted.
+		///
+		/// - `amount`: the LDOT amount to redeem.
+		/// - `allow_fast_match`: whether the request can be matched by minters.
+		#[pallet::weight(<T as Config>::WeightInfo::request_redeem())]
+		#[transactional]
+		pub fn request_redeem(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: Balance,
+			allow_fast_match: bool,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			T::Homa::request_redeem(&who, amount, allow_fast_match)?;
+			Ok(().into())
+		}
+
+		/// Cancel the pending redeem request partially or entirely before it
+		/// is unbonded, and get back the LDOT.
+		///
+		/// - `amount`: the LDOT amount to cancel, the whole request is
+		///   cancelled if it exceeds the requested amount.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_redeem_request())]
+		#[transactional]
+		pub fn cancel_redeem_request(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			T::Homa::cancel_redeem_request(&who, amount)?;
+			Ok(().into())
+		}
+
+		/// Update whether the pending redeem request can be matched by
+		/// minters.
+		///
+		/// - `allow_fast_match`: whether the request can be matched by minters.
+		#[pallet::weight(<T as Config>::WeightInfo::set_redeem_request_fast_match())]
+		#[transactional]
+		pub fn set_redeem_request_fast_match(
+			origin: OriginFor<T>,
+			allow_fast_match: bool,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			T::Homa::set_redeem_request_fast_match(&who, allow_fast_match)?;
+			Ok(().into())
+		}
+
+		/// Transfer the claim of unbonding DOT of specific era to another
+		/// account, who can get back the DOT by `withdraw_redemption` after
+		/// the era has matured. This allows the claim to be traded before the
+		/// unbonding period completes.
+		///
+		/// - `to`: the receiver of the claim.
+		/// - `target_era`: the era at which the unbonding DOT will be
+		///   withdrawable.
+		/// - `amount`: the DOT amount of the claim to transfer.
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_unbonding_claim())]
+		#[transactional]
+		pub fn transfer_unbonding_claim(
+			origin: OriginFor<T>,
+			to: <T::Lookup as StaticLookup>::Source,
+			target_era: EraIndex,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			T::Homa::transfer_unbonding(&who, &to, target_era, amount)?;
+			Ok(().into())
+		}
 	}
 }
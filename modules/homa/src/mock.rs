@@ -0,0 +1,216 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the homa module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{construct_runtime, parameter_types};
+use orml_traits::{parameter_type_with_key, MultiCurrency};
+use primitives::{Amount, CurrencyId, TokenSymbol};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchError, DispatchResult};
+use sp_std::cell::RefCell;
+use support::ExchangeRate;
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+pub const LDOT: CurrencyId = CurrencyId::Token(TokenSymbol::LDOT);
+
+mod homa {
+	pub use super::super::*;
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+parameter_type_with_key! {
+	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+		Default::default()
+	};
+}
+
+impl orml_tokens::Config for Runtime {
+	type Event = Event;
+	type Balance = Balance;
+	type Amount = Amount;
+	type CurrencyId = CurrencyId;
+	type WeightInfo = ();
+	type ExistentialDeposits = ExistentialDeposits;
+	type OnDust = ();
+}
+
+thread_local! {
+	static LIQUID_EXCHANGE_RATE: RefCell<ExchangeRate> = RefCell::new(ExchangeRate::one());
+}
+
+pub fn set_liquid_exchange_rate(rate: ExchangeRate) {
+	LIQUID_EXCHANGE_RATE.with(|v| *v.borrow_mut() = rate);
+}
+
+/// Mints at `LIQUID_EXCHANGE_RATE` with the liquid amount rounded down, other
+/// operations are not supported.
+pub struct MockHoma;
+impl HomaProtocol<AccountId, Balance, EraIndex> for MockHoma {
+	type Balance = Balance;
+
+	fn mint(who: &AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		let liquid_amount = Self::liquid_exchange_rate()
+			.reciprocal()
+			.unwrap_or_default()
+			.saturating_mul_int(amount);
+		Tokens::withdraw(DOT, who, amount)?;
+		Tokens::deposit(LDOT, who, liquid_amount)?;
+		Ok(liquid_amount)
+	}
+
+	fn redeem_by_unbond(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn redeem_by_free_unbonded(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn redeem_by_claim_unbonding(_who: &AccountId, _amount: Balance, _target_era: EraIndex) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn withdraw_redemption(_who: &AccountId) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn request_redeem(_who: &AccountId, _amount: Balance, _allow_fast_match: bool) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn cancel_redeem_request(_who: &AccountId, _amount: Balance) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn set_redeem_request_fast_match(_who: &AccountId, _allow_fast_match: bool) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn transfer_unbonding(
+		_from: &AccountId,
+		_to: &AccountId,
+		_target_era: EraIndex,
+		_amount: Balance,
+	) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn liquid_exchange_rate() -> ExchangeRate {
+		LIQUID_EXCHANGE_RATE.with(|v| *v.borrow())
+	}
+
+	fn current_era() -> EraIndex {
+		Default::default()
+	}
+
+	fn total_staking_amount() -> Balance {
+		Default::default()
+	}
+}
+
+parameter_types! {
+	pub const MaxFastMatchRedeemers: u32 = 10;
+}
+
+impl Config for Runtime {
+	type Homa = MockHoma;
+	type MaxFastMatchRedeemers = MaxFastMatchRedeemers;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
+		HomaModule: homa::{Pallet, Call},
+	}
+);
+
+pub struct ExtBuilder {
+	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
+}
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		Self {
+			endowed_accounts: vec![(ALICE, DOT, 1_000)],
+		}
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let mut t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		orml_tokens::GenesisConfig::<Runtime> {
+			endowed_accounts: self.endowed_accounts,
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
+		set_liquid_exchange_rate(ExchangeRate::one());
+
+		t.into()
+	}
+}
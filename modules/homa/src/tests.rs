@@ -0,0 +1,87 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the homa module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{set_liquid_exchange_rate, ExtBuilder, HomaModule, Origin, Runtime, Tokens, ALICE, DOT, LDOT};
+use orml_traits::MultiCurrency;
+use support::ExchangeRate;
+
+#[test]
+fn mint_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_liquid_exchange_rate(ExchangeRate::saturating_from_integer(2));
+		assert_noop!(
+			HomaModule::mint(Origin::signed(ALICE), 300, 151),
+			Error::<Runtime>::InsufficientLiquidAmount,
+		);
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 1_000);
+		assert_eq!(Tokens::free_balance(LDOT, &ALICE), 0);
+
+		assert_ok!(HomaModule::mint(Origin::signed(ALICE), 300, 150));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 700);
+		assert_eq!(Tokens::free_balance(LDOT, &ALICE), 150);
+	});
+}
+
+#[test]
+fn mint_for_exact_liquid_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_liquid_exchange_rate(ExchangeRate::saturating_from_integer(2));
+		assert_noop!(
+			HomaModule::mint_for_exact_liquid(Origin::signed(ALICE), 150, 299),
+			Error::<Runtime>::ExcessiveStakingAmount,
+		);
+
+		assert_ok!(HomaModule::mint_for_exact_liquid(Origin::signed(ALICE), 150, 300));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 700);
+		assert_eq!(Tokens::free_balance(LDOT, &ALICE), 150);
+	});
+}
+
+#[test]
+fn mint_for_exact_liquid_should_round_up_staking_amount() {
+	ExtBuilder::default().build().execute_with(|| {
+		// 101 LDOT needs 50.5 DOT, which is rounded up to make sure the target
+		// amount is reached, and the LDOT minted slightly exceeds the target
+		set_liquid_exchange_rate(ExchangeRate::saturating_from_rational(1, 2));
+		assert_noop!(
+			HomaModule::mint_for_exact_liquid(Origin::signed(ALICE), 101, 50),
+			Error::<Runtime>::ExcessiveStakingAmount,
+		);
+
+		assert_ok!(HomaModule::mint_for_exact_liquid(Origin::signed(ALICE), 101, 51));
+		assert_eq!(Tokens::free_balance(DOT, &ALICE), 949);
+		assert_eq!(Tokens::free_balance(LDOT, &ALICE), 102);
+	});
+}
+
+#[test]
+fn mint_for_exact_liquid_should_fail_with_zero_exchange_rate() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_liquid_exchange_rate(ExchangeRate::zero());
+		assert_noop!(
+			HomaModule::mint_for_exact_liquid(Origin::signed(ALICE), 100, 1_000),
+			Error::<Runtime>::Overflow,
+		);
+	});
+}
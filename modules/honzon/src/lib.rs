@@ -22,7 +22,8 @@
 //!
 //! The entry of the Honzon protocol for users, user can manipulate their CDP
 //! position to loan/payback, and can also authorize others to manage the their
-//! CDP under specific collateral type.
+//! CDP under specific collateral type, and label their CDP to track the
+//! strategy of it.
 //!
 //! After system shutdown, some operations will be restricted.
 
@@ -31,12 +32,13 @@
 
 use frame_support::{pallet_prelude::*, transactional};
 use frame_system::pallet_prelude::*;
-use primitives::{Amount, CurrencyId};
+use primitives::{Amount, Balance, CurrencyId};
 use sp_runtime::{
 	traits::{StaticLookup, Zero},
 	DispatchResult,
 };
-use support::EmergencyShutdown;
+use sp_std::prelude::*;
+use support::{EmergencyShutdown, HonzonManager};
 
 mod mock;
 mod tests;
@@ -113,12 +115,28 @@ pub mod module {
 			debit_adjustment: Amount,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			Self::do_adjust_loan(&who, currency_id, collateral_adjustment, debit_adjustment)?;
+			Ok(().into())
+		}
 
-			// not allowed to adjust the debit after system shutdown
-			if !debit_adjustment.is_zero() {
-				ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+		/// Adjust the loans of multiple collateral types atomically, all or
+		/// nothing. The adjustments are applied in order, and each loan is
+		/// checked only once after all adjustments, so that the caller can
+		/// rebalance across loans.
+		///
+		/// - `adjustments`: vec of (currency_id, collateral_adjustment,
+		///   debit_adjustment).
+		#[pallet::weight(<T as Config>::WeightInfo::adjust_loans(adjustments.len() as u32))]
+		#[transactional]
+		pub fn adjust_loans(
+			origin: OriginFor<T>,
+			adjustments: Vec<(CurrencyId, Amount, Amount)>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			for (_, _, debit_adjustment) in adjustments.iter() {
+				Self::ensure_adjustment_allowed(*debit_adjustment)?;
 			}
-			<cdp_engine::Pallet<T>>::adjust_position(&who, currency_id, collateral_adjustment, debit_adjustment)?;
+			<cdp_engine::Pallet<T>>::adjust_positions(&who, &adjustments)?;
 			Ok(().into())
 		}
 
@@ -130,8 +148,7 @@ pub mod module {
 			maybe_path: Option<sp_std::vec::Vec<CurrencyId>>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
-			<cdp_engine::Pallet<T>>::close_cdp_has_debit_by_dex(who, currency_id, maybe_path.as_deref())?;
+			Self::do_close_loan_by_dex(who, currency_id, maybe_path.as_deref())?;
 			Ok(().into())
 		}
 
@@ -192,6 +209,24 @@ pub mod module {
 			Ok(().into())
 		}
 
+		/// Set the label of caller's loan under `currency_id`, empty label will
+		/// remove the existing label.
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `label`: the label of the loan, can not exceed
+		///   `MaxPositionLabelLength`.
+		#[pallet::weight(<T as Config>::WeightInfo::set_position_label())]
+		#[transactional]
+		pub fn set_position_label(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			label: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			<loans::Pallet<T>>::set_position_label(&who, currency_id, label)?;
+			Ok(().into())
+		}
+
 		/// Cancel all authorization of caller
 		#[pallet::weight(<T as Config>::WeightInfo::unauthorize_all(<T as cdp_engine::Config>::CollateralCurrencyIds::get().len() as u32))]
 		#[transactional]
@@ -205,6 +240,33 @@ pub mod module {
 }
 
 impl<T: Config> Pallet<T> {
+	fn do_adjust_loan(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral_adjustment: Amount,
+		debit_adjustment: Amount,
+	) -> DispatchResult {
+		Self::ensure_adjustment_allowed(debit_adjustment)?;
+		<cdp_engine::Pallet<T>>::adjust_position(who, currency_id, collateral_adjustment, debit_adjustment)
+	}
+
+	fn ensure_adjustment_allowed(debit_adjustment: Amount) -> DispatchResult {
+		// not allowed to adjust the debit after system shutdown
+		if !debit_adjustment.is_zero() {
+			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+		}
+		Ok(())
+	}
+
+	fn do_close_loan_by_dex(
+		who: T::AccountId,
+		currency_id: CurrencyId,
+		maybe_path: Option<&[CurrencyId]>,
+	) -> DispatchResult {
+		ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+		<cdp_engine::Pallet<T>>::close_cdp_has_debit_by_dex(who, currency_id, maybe_path)
+	}
+
 	/// Check if `from` has the authorization of `to` under `currency_id`
 	fn check_authorization(from: &T::AccountId, to: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
 		ensure!(
@@ -214,3 +276,29 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 }
+
+impl<T: Config> HonzonManager<T::AccountId, CurrencyId, Amount, Balance> for Pallet<T> {
+	#[transactional]
+	fn adjust_loan(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral_adjustment: Amount,
+		debit_adjustment: Amount,
+	) -> DispatchResult {
+		Self::do_adjust_loan(who, currency_id, collateral_adjustment, debit_adjustment)
+	}
+
+	#[transactional]
+	fn close_loan_by_dex(
+		who: T::AccountId,
+		currency_id: CurrencyId,
+		maybe_path: Option<&[CurrencyId]>,
+	) -> DispatchResult {
+		Self::do_close_loan_by_dex(who, currency_id, maybe_path)
+	}
+
+	fn get_position(who: &T::AccountId, currency_id: CurrencyId) -> (Balance, Balance) {
+		let loans::Position { collateral, debit } = <loans::Pallet<T>>::positions(currency_id, who);
+		(collateral, debit)
+	}
+}
@@ -32,7 +32,7 @@ use sp_runtime::{
 	FixedPointNumber,
 };
 use sp_std::cell::RefCell;
-use support::{AuctionManager, ExchangeRate, Price, PriceProvider, Rate, Ratio};
+use support::{AuctionManager, CircuitSignal, ExchangeRate, Price, PriceProvider, Rate, Ratio, TriggerTier};
 
 mod honzon {
 	pub use super::super::*;
@@ -125,6 +125,9 @@ impl orml_currencies::Config for Runtime {
 
 parameter_types! {
 	pub const LoansPalletId: PalletId = PalletId(*b"aca/loan");
+	pub const MaxPositionLabelLength: u32 = 32;
+	pub const GetStakingCurrencyId: CurrencyId = DOT;
+	pub const GetLiquidCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::LDOT);
 }
 
 impl loans::Config for Runtime {
@@ -135,6 +138,12 @@ impl loans::Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = ();
+	type MaxPositionLabelLength = MaxPositionLabelLength;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type Homa = ();
+	type StakingCurrencyId = GetStakingCurrencyId;
+	type LiquidCurrencyId = GetLiquidCurrencyId;
+	type CircuitBreaker = MockCircuitBreaker;
 }
 
 pub struct MockPriceSource;
@@ -195,6 +204,30 @@ impl EmergencyShutdown for MockEmergencyShutdown {
 	}
 }
 
+thread_local! {
+	static TRIPPED_OPERATIONS: RefCell<Vec<GuardedOperation>> = RefCell::new(vec![]);
+}
+
+pub fn mock_trip(operation: GuardedOperation) {
+	TRIPPED_OPERATIONS.with(|v| v.borrow_mut().push(operation))
+}
+
+pub struct MockCircuitBreaker;
+impl CircuitBreaker<BlockNumber> for MockCircuitBreaker {
+	fn register_trigger(_signal: CircuitSignal, _tier: TriggerTier<BlockNumber>) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn report(_signal: CircuitSignal, _magnitude: Ratio) {}
+
+	fn try_allow(operation: GuardedOperation, _currency_id: CurrencyId, amount: Balance) -> bool {
+		if !TRIPPED_OPERATIONS.with(|v| v.borrow().contains(&operation)) {
+			return true;
+		}
+		operation == GuardedOperation::Withdrawal && amount <= 10
+	}
+}
+
 ord_parameter_types! {
 	pub const One: AccountId = 1;
 }
@@ -206,6 +239,12 @@ parameter_types! {
 	pub TreasuryAccount: AccountId = PalletId(*b"aca/hztr").into_account();
 }
 
+parameter_types! {
+	pub const SurplusRebalancePeriod: BlockNumber = 10;
+	pub const MaxSurplusAllocations: u32 = 5;
+	pub MaxSurplusSwapSlippage: Ratio = Ratio::saturating_from_rational(5, 100);
+}
+
 impl cdp_treasury::Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
@@ -214,6 +253,10 @@ impl cdp_treasury::Config for Runtime {
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = ();
 	type MaxAuctionsCount = MaxAuctionsCount;
+	type PriceSource = MockPriceSource;
+	type SurplusRebalancePeriod = SurplusRebalancePeriod;
+	type MaxSurplusAllocations = MaxSurplusAllocations;
+	type MaxSurplusSwapSlippage = MaxSurplusSwapSlippage;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
 	type WeightInfo = ();
@@ -236,6 +279,9 @@ parameter_types! {
 	pub DefaultLiquidationPenalty: Rate = Rate::saturating_from_rational(10, 100);
 	pub const MinimumDebitValue: Balance = 2;
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::saturating_from_rational(50, 100);
+	pub const MaxStabilityFeeExemptions: u32 = 2;
+	pub const MaxLiquidationSteps: u32 = 3;
+	pub const MaxLiquidationValuePerBlock: Balance = 1_000;
 	pub const UnsignedPriority: u64 = 1 << 20;
 }
 
@@ -253,7 +299,12 @@ impl cdp_engine::Config for Runtime {
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
 	type UnsignedPriority = UnsignedPriority;
 	type EmergencyShutdown = MockEmergencyShutdown;
+	type MaxStabilityFeeExemptions = MaxStabilityFeeExemptions;
+	type MaxLiquidationSteps = MaxLiquidationSteps;
+	type LiquidationKeeper = ();
+	type MaxLiquidationValuePerBlock = MaxLiquidationValuePerBlock;
 	type UnixTime = Timestamp;
+	type Erc20ReferenceCounter = ();
 	type WeightInfo = ();
 }
 
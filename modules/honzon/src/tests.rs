@@ -23,7 +23,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{Event, *};
-use orml_traits::Change;
+use orml_traits::{Change, MultiCurrency};
 use sp_runtime::FixedPointNumber;
 use support::{Rate, Ratio};
 
@@ -134,6 +134,95 @@ fn adjust_loan_should_work() {
 	});
 }
 
+#[test]
+fn adjust_loans_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		for currency_id in vec![BTC, DOT] {
+			assert_ok!(CDPEngineModule::set_collateral_params(
+				Origin::signed(1),
+				currency_id,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(10000),
+			));
+		}
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, 100, 50));
+
+		assert_noop!(
+			HonzonModule::adjust_loans(Origin::signed(ALICE), vec![(BTC, -60, 0), (AUSD, 10, 0)]),
+			cdp_engine::Error::<Runtime>::InvalidCollateralType,
+		);
+		// all or nothing
+		assert_noop!(
+			HonzonModule::adjust_loans(Origin::signed(ALICE), vec![(DOT, 100, 50), (BTC, -60, 0)]),
+			cdp_engine::Error::<Runtime>::BelowRequiredCollateralRatio,
+		);
+		assert_noop!(
+			HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, -60, 0),
+			cdp_engine::Error::<Runtime>::BelowRequiredCollateralRatio,
+		);
+
+		// the loans are only checked after all adjustments
+		assert_ok!(HonzonModule::adjust_loans(
+			Origin::signed(ALICE),
+			vec![(BTC, -60, 0), (DOT, 100, 50), (BTC, 0, -50)]
+		));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 40);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(DOT, ALICE).collateral, 100);
+		assert_eq!(LoansModule::positions(DOT, ALICE).debit, 50);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 960);
+		assert_eq!(Currencies::free_balance(DOT, &ALICE), 900);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+
+		mock_trip(GuardedOperation::NewLoan);
+		assert_noop!(
+			HonzonModule::adjust_loans(Origin::signed(ALICE), vec![(DOT, 0, -10), (BTC, 100, 10)]),
+			loans::Error::<Runtime>::OperationSuspended,
+		);
+
+		mock_shutdown();
+		assert_noop!(
+			HonzonModule::adjust_loans(Origin::signed(ALICE), vec![(BTC, 10, 0), (DOT, 0, -10)]),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+		assert_ok!(HonzonModule::adjust_loans(Origin::signed(ALICE), vec![(BTC, -10, 0)]));
+	});
+}
+
+#[test]
+fn set_position_label_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			HonzonModule::set_position_label(Origin::signed(ALICE), BTC, b"long".to_vec()),
+			loans::Error::<Runtime>::NoPosition
+		);
+
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, 100, 50));
+		assert_ok!(HonzonModule::set_position_label(
+			Origin::signed(ALICE),
+			BTC,
+			b"long".to_vec()
+		));
+		assert_eq!(LoansModule::position_labels(BTC, ALICE), b"long".to_vec());
+
+		let label_event = Event::loans(loans::Event::PositionLabelSet(ALICE, BTC, b"long".to_vec()));
+		assert!(System::events().iter().any(|record| record.event == label_event));
+	});
+}
+
 #[test]
 fn on_emergency_shutdown_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -153,6 +242,38 @@ fn on_emergency_shutdown_should_work() {
 	});
 }
 
+#[test]
+fn adjust_loan_suspended_by_circuit_breaker() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, 100, 50));
+
+		mock_trip(GuardedOperation::NewLoan);
+		assert_noop!(
+			HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, 100, 10),
+			loans::Error::<Runtime>::OperationSuspended,
+		);
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, 100, -10));
+
+		mock_trip(GuardedOperation::Withdrawal);
+		assert_noop!(
+			HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, -20, 0),
+			loans::Error::<Runtime>::OperationSuspended,
+		);
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, -10, 0));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 190);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 40);
+	});
+}
+
 #[test]
 fn close_loan_has_debit_by_dex_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -178,3 +299,35 @@ fn close_loan_has_debit_by_dex_work() {
 		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
 	});
 }
+
+#[test]
+fn honzon_manager_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(<HonzonModule as HonzonManager<_, _, _, _>>::adjust_loan(
+			&ALICE, BTC, 100, 50
+		));
+		assert_eq!(
+			<HonzonModule as HonzonManager<_, _, _, _>>::get_position(&ALICE, BTC),
+			(100, 50)
+		);
+
+		mock_shutdown();
+		assert_noop!(
+			<HonzonModule as HonzonManager<_, _, _, _>>::adjust_loan(&ALICE, BTC, 0, 10),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+		assert_noop!(
+			<HonzonModule as HonzonManager<_, _, _, _>>::close_loan_by_dex(ALICE, BTC, None),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+	});
+}
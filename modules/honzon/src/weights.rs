@@ -52,8 +52,10 @@ pub trait WeightInfo {
 	fn unauthorize() -> Weight;
 	fn unauthorize_all(c: u32, ) -> Weight;
 	fn adjust_loan() -> Weight;
+	fn adjust_loans(c: u32, ) -> Weight;
 	fn transfer_loan_from() -> Weight;
 	fn close_loan_has_debit_by_dex() -> Weight;
+	fn set_position_label() -> Weight;
 }
 
 /// Weights for module_honzon using the Acala node and recommended hardware.
@@ -78,6 +80,14 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(24 as Weight))
 			.saturating_add(T::DbWeight::get().writes(11 as Weight))
 	}
+	fn adjust_loans(c: u32, ) -> Weight {
+		(31_470_000 as Weight)
+			// Standard Error: 214_000
+			.saturating_add((171_204_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads((17 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((11 as Weight).saturating_mul(c as Weight)))
+	}
 	fn transfer_loan_from() -> Weight {
 		(114_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(21 as Weight))
@@ -88,6 +98,11 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(21 as Weight))
 			.saturating_add(T::DbWeight::get().writes(7 as Weight))
 	}
+	fn set_position_label() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -111,6 +126,14 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(24 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
 	}
+	fn adjust_loans(c: u32, ) -> Weight {
+		(31_470_000 as Weight)
+			// Standard Error: 214_000
+			.saturating_add((171_204_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((17 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((11 as Weight).saturating_mul(c as Weight)))
+	}
 	fn transfer_loan_from() -> Weight {
 		(114_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(21 as Weight))
@@ -121,4 +144,9 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(21 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
 	}
+	fn set_position_label() -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
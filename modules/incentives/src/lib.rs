@@ -23,13 +23,15 @@
 use frame_support::{log, pallet_prelude::*, transactional, PalletId};
 use frame_system::pallet_prelude::*;
 use orml_traits::{Happened, MultiCurrency, RewardHandler};
-use primitives::{Amount, Balance, CurrencyId};
+use primitives::{Amount, Balance, CurrencyId, DexShare};
 use sp_runtime::{
-	traits::{AccountIdConversion, MaybeDisplay, UniqueSaturatedInto, Zero},
-	DispatchResult, FixedPointNumber, RuntimeDebug,
+	traits::{AccountIdConversion, MaybeDisplay, One, Saturating, UniqueSaturatedInto, Zero},
+	DispatchResult, FixedPointNumber, FixedU128, RuntimeDebug,
 };
 use sp_std::{fmt::Debug, vec::Vec};
-use support::{CDPTreasury, DEXIncentives, DEXManager, EmergencyShutdown, Rate};
+use support::{
+	CDPTreasury, DEXIncentives, DEXManager, EmergencyShutdown, Erc20ReferenceCounter, IncentivesManager, Rate,
+};
 
 mod mock;
 mod tests;
@@ -60,6 +62,15 @@ pub enum PoolId<AccountId> {
 	HomaValidatorAllowance(AccountId),
 }
 
+/// Deposit caps of a pool, `None` value means no cap.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct DepositCap<Balance> {
+	/// The maximum total shares of the pool.
+	pub total: Option<Balance>,
+	/// The maximum shares of an account in the pool.
+	pub per_account: Option<Balance>,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -74,7 +85,8 @@ pub mod module {
 		/// The type of validator account id on relaychain.
 		type RelaychainAccountId: Parameter + Member + MaybeSerializeDeserialize + Debug + MaybeDisplay + Ord + Default;
 
-		/// The period to accumulate rewards
+		/// The default period to accumulate rewards, used by pools which have
+		/// no accumulate period of their own.
 		#[pallet::constant]
 		type AccumulatePeriod: Get<Self::BlockNumber>;
 
@@ -117,6 +129,10 @@ pub mod module {
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 
+		/// Record the Erc20 currencies referenced by the pools configured
+		/// with rewards.
+		type Erc20ReferenceCounter: Erc20ReferenceCounter;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -129,6 +145,26 @@ pub mod module {
 		InvalidCurrencyId,
 		/// Invalid pool id
 		InvalidPoolId,
+		/// The pool has been retired
+		PoolRetired,
+		/// The pool has not been retired
+		PoolNotRetired,
+		/// Invalid successor pool for the retired pool
+		InvalidSuccessor,
+		/// The total shares of the pool would exceed its deposit cap
+		ExceedPoolDepositCap,
+		/// The shares of the account would exceed the per account deposit cap
+		ExceedAccountDepositCap,
+		/// There are no snapshot rewards to claim
+		NoSnapshotRewards,
+		/// There are no rewards to compound
+		NoRewardsToCompound,
+		/// The swap path of the rewards is not found or invalid
+		InvalidSwapPath,
+		/// The compounded shares are below the acceptable minimum
+		InsufficientCompoundedShare,
+		/// The accumulate period must not be zero
+		InvalidAccumulatePeriod,
 	}
 
 	#[pallet::event]
@@ -140,38 +176,145 @@ pub mod module {
 		WithdrawDexShare(T::AccountId, CurrencyId, Balance),
 		/// Claim rewards. \[who, pool_id\]
 		ClaimRewards(T::AccountId, PoolId<T::RelaychainAccountId>),
+		/// Pool has been retired. \[pool_id, successor\]
+		PoolRetired(PoolId<T::RelaychainAccountId>, Option<PoolId<T::RelaychainAccountId>>),
+		/// Shares of the retired pool have been migrated. \[pool_id,
+		/// migrated_accounts\]
+		PoolSharesMigrated(PoolId<T::RelaychainAccountId>, u32),
+		/// Deposit caps of the pool have been updated. \[pool_id, total_cap,
+		/// per_account_cap\]
+		DepositCapsUpdated(PoolId<T::RelaychainAccountId>, Option<Balance>, Option<Balance>),
+		/// Accrued rewards have been snapshotted when migrating out of the
+		/// retired pool. \[who, pool_id, amount\]
+		RewardsSnapshotted(T::AccountId, PoolId<T::RelaychainAccountId>, Balance),
+		/// Claim snapshot rewards. \[who, pool_id, amount\]
+		ClaimSnapshotRewards(T::AccountId, PoolId<T::RelaychainAccountId>, Balance),
+		/// Compound the rewards of dex pools into liquidity. \[who,
+		/// dex_share_type, amount_0, amount_1\]
+		CompoundRewards(T::AccountId, CurrencyId, Balance, Balance),
+		/// The accumulate period of the pool has been updated, `None` means
+		/// the default period. \[pool_id, accumulate_period\]
+		AccumulatePeriodUpdated(PoolId<T::RelaychainAccountId>, Option<T::BlockNumber>),
 	}
 
-	/// Mapping from pool to its fixed reward amount per period.
+	/// Mapping from pool to its fixed reward amount per `AccumulatePeriod`
+	/// blocks, pools with their own period accumulate it in proportion to
+	/// the period.
 	#[pallet::storage]
 	#[pallet::getter(fn incentive_reward_amount)]
 	pub type IncentiveRewardAmount<T: Config> =
 		StorageMap<_, Twox64Concat, PoolId<T::RelaychainAccountId>, Balance, ValueQuery>;
 
-	/// Mapping from pool to its fixed reward rate per period.
+	/// Mapping from pool to its fixed reward rate per `AccumulatePeriod`
+	/// blocks, pools with their own period accumulate it in proportion to
+	/// the period.
 	#[pallet::storage]
 	#[pallet::getter(fn dex_saving_reward_rate)]
 	pub type DexSavingRewardRate<T: Config> =
 		StorageMap<_, Twox64Concat, PoolId<T::RelaychainAccountId>, Rate, ValueQuery>;
 
+	/// Mapping from retired pool to its successor pool.
+	/// Retired pools no longer accumulate rewards and only accept
+	/// withdrawals, the shares of stakers are migrated to the successor pool
+	/// if there is one.
+	#[pallet::storage]
+	#[pallet::getter(fn retired_pools)]
+	pub type RetiredPools<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		PoolId<T::RelaychainAccountId>,
+		Option<PoolId<T::RelaychainAccountId>>,
+		OptionQuery,
+	>;
+
+	/// Mapping from dex pool to its deposit caps.
+	#[pallet::storage]
+	#[pallet::getter(fn deposit_caps)]
+	pub type DepositCaps<T: Config> =
+		StorageMap<_, Twox64Concat, PoolId<T::RelaychainAccountId>, DepositCap<Balance>, ValueQuery>;
+
+	/// The accrued but unclaimed rewards of stakers, snapshotted when their
+	/// shares were migrated out of the retired pool.
+	///
+	/// RewardSnapshots: double_map PoolId, AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn reward_snapshots)]
+	pub type RewardSnapshots<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		PoolId<T::RelaychainAccountId>,
+		Twox64Concat,
+		T::AccountId,
+		Balance,
+		ValueQuery,
+	>;
+
+	/// Mapping from pool to its own period to accumulate rewards. Pools
+	/// without an entry accumulate rewards every `AccumulatePeriod` blocks.
+	///
+	/// AccumulatePeriods: map PoolId => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn accumulate_periods)]
+	pub type AccumulatePeriods<T: Config> =
+		StorageMap<_, Twox64Concat, PoolId<T::RelaychainAccountId>, T::BlockNumber, OptionQuery>;
+
+	/// The number of pools of each accumulate period in `AccumulatePeriods`,
+	/// so that blocks in which no pool accumulates rewards are skipped
+	/// without iterating pools.
+	///
+	/// AccumulatePeriodCounts: map BlockNumber => u32
+	#[pallet::storage]
+	pub(crate) type AccumulatePeriodCounts<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, u32, ValueQuery>;
+
+	/// True if the accumulate periods of the existing pools have been
+	/// migrated from the global `AccumulatePeriod`.
+	#[pallet::storage]
+	pub(crate) type UpgradedToPoolAccumulatePeriods<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// True if the Erc20 references of the existing pools configured with
+	/// rewards have been recorded.
+	#[pallet::storage]
+	pub(crate) type UpgradedToErc20References<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			Self::upgrade_to_pool_accumulate_periods().saturating_add(Self::upgrade_to_erc20_references())
+		}
+
 		fn on_initialize(now: T::BlockNumber) -> Weight {
 			// accumulate reward periodically
-			if !T::EmergencyShutdown::is_shutdown() && now % T::AccumulatePeriod::get() == Zero::zero() {
+			if !T::EmergencyShutdown::is_shutdown() {
+				// fast path: skip iterating pools if no pool accumulates rewards in this block
+				let mut period_count: Weight = 0;
+				let is_accumulate_block = now % T::AccumulatePeriod::get() == Zero::zero()
+					|| AccumulatePeriodCounts::<T>::iter_keys().any(|period| {
+						period_count += 1;
+						now % period == Zero::zero()
+					});
+				if !is_accumulate_block {
+					return T::DbWeight::get().reads(period_count + 1);
+				}
+
 				let mut count: u32 = 0;
+				let mut checked: Weight = 0;
 				let native_currency_id = T::NativeCurrencyId::get();
 				let stable_currency_id = T::StableCurrencyId::get();
 
 				for (pool_id, pool_info) in orml_rewards::Pools::<T>::iter() {
-					if !pool_info.total_shares.is_zero() {
+					if pool_info.total_shares.is_zero() {
+						continue;
+					}
+					checked += 1;
+					if now % Self::accumulate_period(&pool_id) == Zero::zero() {
 						match pool_id {
 							PoolId::LoansIncentive(_) | PoolId::DexIncentive(_) | PoolId::HomaIncentive => {
 								count += 1;
-								let incentive_reward_amount = Self::incentive_reward_amount(pool_id.clone());
+								let incentive_reward_amount = Self::period_proportion(&pool_id)
+									.saturating_mul_int(Self::incentive_reward_amount(pool_id.clone()));
 
 								if !incentive_reward_amount.is_zero() {
 									let res = T::Currency::transfer(
@@ -201,7 +344,8 @@ pub mod module {
 
 							PoolId::DexSaving(lp_currency_id) => {
 								count += 1;
-								let dex_saving_reward_rate = Self::dex_saving_reward_rate(pool_id.clone());
+								let dex_saving_reward_rate = Self::dex_saving_reward_rate(pool_id.clone())
+									.saturating_mul(Self::period_proportion(&pool_id));
 
 								if !dex_saving_reward_rate.is_zero() {
 									if let Some((currency_id_a, currency_id_b)) =
@@ -251,7 +395,7 @@ pub mod module {
 					}
 				}
 
-				T::WeightInfo::on_initialize(count)
+				T::WeightInfo::on_initialize(count).saturating_add(T::DbWeight::get().reads(checked + period_count))
 			} else {
 				0
 			}
@@ -291,8 +435,32 @@ pub mod module {
 			pool_id: PoolId<T::RelaychainAccountId>,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			<orml_rewards::Pallet<T>>::claim_rewards(&who, &pool_id);
-			Self::deposit_event(Event::ClaimRewards(who, pool_id));
+			Self::do_claim_rewards(&who, pool_id);
+			Ok(().into())
+		}
+
+		/// Claim the rewards of the dex pools of `lp_currency_id`, convert
+		/// them into the currencies of the trading pair and add liquidity,
+		/// the increased lp shares are deposited back to the pools.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `lp_currency_id`: the dex share currency of the pools.
+		/// - `swap_paths`: the swap paths from the reward currencies which are
+		///   not in the trading pair to one of the currencies of the trading
+		///   pair, at most one path for each of the dex pools.
+		/// - `min_share_increment`: the acceptable minimum lp shares
+		///   compounded.
+		#[pallet::weight(<T as Config>::WeightInfo::compound_rewards())]
+		#[transactional]
+		pub fn compound_rewards(
+			origin: OriginFor<T>,
+			lp_currency_id: CurrencyId,
+			swap_paths: Vec<Vec<CurrencyId>>,
+			#[pallet::compact] min_share_increment: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_compound_rewards(&who, lp_currency_id, &swap_paths, min_share_increment)?;
 			Ok(().into())
 		}
 
@@ -313,7 +481,11 @@ pub mod module {
 						return Err(Error::<T>::InvalidPoolId.into());
 					}
 				}
+				ensure!(!Self::is_retired(&pool_id), Error::<T>::PoolRetired);
 
+				if !IncentiveRewardAmount::<T>::contains_key(&pool_id) {
+					Self::inc_erc20_references(&pool_id);
+				}
 				IncentiveRewardAmount::<T>::insert(pool_id, amount);
 			}
 			Ok(().into())
@@ -335,11 +507,88 @@ pub mod module {
 						return Err(Error::<T>::InvalidPoolId.into());
 					}
 				}
+				ensure!(!Self::is_retired(&pool_id), Error::<T>::PoolRetired);
+
+				if !DexSavingRewardRate::<T>::contains_key(&pool_id) {
+					Self::inc_erc20_references(&pool_id);
+				}
 				DexSavingRewardRate::<T>::insert(pool_id, rate);
 			}
 			Ok(().into())
 		}
 
+		/// Update the deposit caps of dex pools, which are enforced on
+		/// `deposit_dex_share`. Deposited shares are not affected by lowering
+		/// the caps.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `updates`: vec of (pool_id, total_cap, per_account_cap), `None`
+		///   value means no cap.
+		#[pallet::weight(<T as Config>::WeightInfo::update_deposit_caps(updates.len() as u32))]
+		#[transactional]
+		pub fn update_deposit_caps(
+			origin: OriginFor<T>,
+			updates: Vec<(PoolId<T::RelaychainAccountId>, Option<Balance>, Option<Balance>)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (pool_id, total, per_account) in updates {
+				match pool_id {
+					PoolId::DexIncentive(currency_id) | PoolId::DexSaving(currency_id) => {
+						ensure!(currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
+					}
+					_ => {
+						return Err(Error::<T>::InvalidPoolId.into());
+					}
+				}
+
+				if total.is_none() && per_account.is_none() {
+					DepositCaps::<T>::remove(&pool_id);
+				} else {
+					DepositCaps::<T>::insert(&pool_id, DepositCap { total, per_account });
+				}
+				Self::deposit_event(Event::DepositCapsUpdated(pool_id, total, per_account));
+			}
+			Ok(().into())
+		}
+
+		/// Update the accumulate periods of pools, so that short-lived
+		/// campaigns can accumulate rewards more frequently than the default
+		/// `AccumulatePeriod`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `updates`: vec of (pool_id, accumulate_period), `None` value
+		///   means the default period.
+		#[pallet::weight(<T as Config>::WeightInfo::update_accumulate_periods(updates.len() as u32))]
+		#[transactional]
+		pub fn update_accumulate_periods(
+			origin: OriginFor<T>,
+			updates: Vec<(PoolId<T::RelaychainAccountId>, Option<T::BlockNumber>)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			for (pool_id, accumulate_period) in updates {
+				match pool_id {
+					PoolId::DexIncentive(currency_id) | PoolId::DexSaving(currency_id) => {
+						ensure!(currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
+					}
+					PoolId::LoansIncentive(_) | PoolId::HomaIncentive => {}
+					_ => {
+						return Err(Error::<T>::InvalidPoolId.into());
+					}
+				}
+				ensure!(!Self::is_retired(&pool_id), Error::<T>::PoolRetired);
+
+				ensure!(
+					accumulate_period.map_or(true, |period| !period.is_zero()),
+					Error::<T>::InvalidAccumulatePeriod
+				);
+				Self::set_accumulate_period(&pool_id, accumulate_period);
+				Self::deposit_event(Event::AccumulatePeriodUpdated(pool_id, accumulate_period));
+			}
+			Ok(().into())
+		}
+
 		#[pallet::weight(<T as Config>::WeightInfo::add_allowance())]
 		#[transactional]
 		pub fn add_allowance(
@@ -348,6 +597,7 @@ pub mod module {
 			amount: Balance,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
+			ensure!(!Self::is_retired(&pool_id), Error::<T>::PoolRetired);
 
 			match pool_id {
 				PoolId::HomaValidatorAllowance(_) => {
@@ -366,18 +616,479 @@ pub mod module {
 
 			Ok(().into())
 		}
+
+		/// Retire the pool. Rewards stop accumulating for it and stakers can
+		/// only withdraw. If `successor` is specified, the shares of stakers
+		/// will be migrated to it by `migrate_pool_shares`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `pool_id`: the pool to retire.
+		/// - `successor`: the pool to migrate shares into. Dex pools can not
+		///   have a successor, their stakers are forced to exit instead.
+		#[pallet::weight(<T as Config>::WeightInfo::retire_pool())]
+		#[transactional]
+		pub fn retire_pool(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T::RelaychainAccountId>,
+			successor: Option<PoolId<T::RelaychainAccountId>>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(!Self::is_retired(&pool_id), Error::<T>::PoolRetired);
+
+			if let Some(successor_pool_id) = &successor {
+				let is_same_kind = matches!(
+					(&pool_id, successor_pool_id),
+					(PoolId::LoansIncentive(_), PoolId::LoansIncentive(_))
+						| (PoolId::HomaIncentive, PoolId::HomaIncentive)
+						| (PoolId::HomaValidatorAllowance(_), PoolId::HomaValidatorAllowance(_))
+				);
+				ensure!(
+					is_same_kind && *successor_pool_id != pool_id && !Self::is_retired(successor_pool_id),
+					Error::<T>::InvalidSuccessor
+				);
+			}
+
+			if IncentiveRewardAmount::<T>::contains_key(&pool_id) || DexSavingRewardRate::<T>::contains_key(&pool_id) {
+				Self::dec_erc20_references(&pool_id);
+			}
+			IncentiveRewardAmount::<T>::remove(&pool_id);
+			DexSavingRewardRate::<T>::remove(&pool_id);
+			Self::set_accumulate_period(&pool_id, None);
+			RetiredPools::<T>::insert(&pool_id, successor.clone());
+
+			Self::deposit_event(Event::PoolRetired(pool_id, successor));
+			Ok(().into())
+		}
+
+		/// Migrate the shares of at most `limit` stakers of the retired pool.
+		/// Pending rewards are snapshotted to be claimed by
+		/// `claim_snapshot_rewards`, then the shares are moved to the
+		/// successor pool. For dex pools, the deposited dex share is returned
+		/// to the staker.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `pool_id`: the retired pool.
+		/// - `limit`: the maximum number of stakers to migrate.
+		#[pallet::weight(<T as Config>::WeightInfo::migrate_pool_shares(*limit))]
+		#[transactional]
+		pub fn migrate_pool_shares(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T::RelaychainAccountId>,
+			limit: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			ensure!(Self::is_retired(&pool_id), Error::<T>::PoolNotRetired);
+
+			let stakers: Vec<T::AccountId> = orml_rewards::ShareAndWithdrawnReward::<T>::iter_prefix(&pool_id)
+				.take(limit as usize)
+				.map(|(who, _)| who)
+				.collect();
+			let count = stakers.len() as u32;
+
+			for who in stakers {
+				Self::do_migrate_shares(&who, &pool_id)?;
+			}
+
+			Self::deposit_event(Event::PoolSharesMigrated(pool_id, count));
+			Ok(Some(<T as Config>::WeightInfo::migrate_pool_shares(count)).into())
+		}
+
+		/// Claim the rewards snapshotted when the shares of caller were
+		/// migrated out of the retired pool.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `pool_id`: the retired pool.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_snapshot_rewards())]
+		#[transactional]
+		pub fn claim_snapshot_rewards(
+			origin: OriginFor<T>,
+			pool_id: PoolId<T::RelaychainAccountId>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let amount = RewardSnapshots::<T>::take(&pool_id, &who);
+			ensure!(!amount.is_zero(), Error::<T>::NoSnapshotRewards);
+
+			T::Currency::transfer(
+				Self::reward_currency_id(&pool_id),
+				&T::RewardsVaultAccountId::get(),
+				&who,
+				amount,
+			)?;
+
+			Self::deposit_event(Event::ClaimSnapshotRewards(who, pool_id, amount));
+			Ok(().into())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// Pin the accumulate period of the pools configured with rewards to the
+	/// global `AccumulatePeriod`, so that changing the default later doesn't
+	/// change their cadence.
+	fn upgrade_to_pool_accumulate_periods() -> Weight {
+		if UpgradedToPoolAccumulatePeriods::<T>::get() {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let accumulate_period = T::AccumulatePeriod::get();
+		let mut count: Weight = 0;
+		for pool_id in IncentiveRewardAmount::<T>::iter()
+			.map(|(pool_id, _)| pool_id)
+			.chain(DexSavingRewardRate::<T>::iter().map(|(pool_id, _)| pool_id))
+		{
+			if !AccumulatePeriods::<T>::contains_key(&pool_id) {
+				Self::set_accumulate_period(&pool_id, Some(accumulate_period));
+			}
+			count += 1;
+		}
+		UpgradedToPoolAccumulatePeriods::<T>::put(true);
+
+		T::DbWeight::get().reads_writes(count.saturating_mul(3) + 1, count.saturating_mul(2) + 1)
+	}
+
+	/// Record the Erc20 references of the pools configured with rewards
+	/// before the references were tracked.
+	fn upgrade_to_erc20_references() -> Weight {
+		if UpgradedToErc20References::<T>::get() {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut count: Weight = 0;
+		for pool_id in IncentiveRewardAmount::<T>::iter()
+			.map(|(pool_id, _)| pool_id)
+			.chain(DexSavingRewardRate::<T>::iter().map(|(pool_id, _)| pool_id))
+		{
+			Self::inc_erc20_references(&pool_id);
+			count += 1;
+		}
+		UpgradedToErc20References::<T>::put(true);
+
+		T::DbWeight::get().reads_writes(count.saturating_mul(3) + 1, count.saturating_mul(2) + 1)
+	}
+
+	/// The currencies used by the pool, which are referenced while the pool
+	/// is configured with rewards.
+	fn pool_currency_ids(pool_id: &PoolId<T::RelaychainAccountId>) -> Vec<CurrencyId> {
+		match pool_id {
+			PoolId::LoansIncentive(currency_id) => sp_std::vec![*currency_id],
+			PoolId::DexIncentive(lp_currency_id) | PoolId::DexSaving(lp_currency_id) => lp_currency_id
+				.split_dex_share_currency_id()
+				.map(|(currency_id_0, currency_id_1)| sp_std::vec![currency_id_0, currency_id_1])
+				.unwrap_or_default(),
+			PoolId::HomaIncentive | PoolId::HomaValidatorAllowance(_) => Vec::new(),
+		}
+	}
+
+	fn inc_erc20_references(pool_id: &PoolId<T::RelaychainAccountId>) {
+		for currency_id in Self::pool_currency_ids(pool_id) {
+			T::Erc20ReferenceCounter::inc_reference(T::PalletId::get(), currency_id);
+		}
+	}
+
+	fn dec_erc20_references(pool_id: &PoolId<T::RelaychainAccountId>) {
+		for currency_id in Self::pool_currency_ids(pool_id) {
+			T::Erc20ReferenceCounter::dec_reference(T::PalletId::get(), currency_id);
+		}
+	}
+
+	/// Ensure depositing `amount` shares of `who` to the pool doesn't exceed
+	/// its deposit caps.
+	fn ensure_within_deposit_caps(
+		pool_id: &PoolId<T::RelaychainAccountId>,
+		who: &T::AccountId,
+		amount: Balance,
+	) -> DispatchResult {
+		let DepositCap { total, per_account } = Self::deposit_caps(pool_id);
+
+		if let Some(total_cap) = total {
+			let total_shares = <orml_rewards::Pallet<T>>::pools(pool_id).total_shares;
+			ensure!(
+				total_shares.saturating_add(amount) <= total_cap,
+				Error::<T>::ExceedPoolDepositCap
+			);
+		}
+		if let Some(per_account_cap) = per_account {
+			let (share, _) = <orml_rewards::Pallet<T>>::share_and_withdrawn_reward(pool_id, who);
+			ensure!(
+				share.saturating_add(amount) <= per_account_cap,
+				Error::<T>::ExceedAccountDepositCap
+			);
+		}
+		Ok(())
+	}
+
 	pub fn account_id() -> T::AccountId {
 		T::PalletId::get().into_account()
 	}
+
+	pub fn is_retired(pool_id: &PoolId<T::RelaychainAccountId>) -> bool {
+		RetiredPools::<T>::contains_key(pool_id)
+	}
+
+	/// The period to accumulate rewards of `pool_id`, fall back to the
+	/// default `AccumulatePeriod`.
+	pub fn accumulate_period(pool_id: &PoolId<T::RelaychainAccountId>) -> T::BlockNumber {
+		Self::accumulate_periods(pool_id).unwrap_or_else(T::AccumulatePeriod::get)
+	}
+
+	/// The proportion of the accumulate period of `pool_id` to the default
+	/// `AccumulatePeriod`, which scales the rewards accumulated per period
+	/// to keep the emission per block regardless of the period.
+	fn period_proportion(pool_id: &PoolId<T::RelaychainAccountId>) -> FixedU128 {
+		let period: u128 = Self::accumulate_period(pool_id).unique_saturated_into();
+		let default_period: u128 = T::AccumulatePeriod::get().unique_saturated_into();
+		FixedU128::checked_from_rational(period, default_period).unwrap_or_else(FixedU128::one)
+	}
+
+	/// Set the accumulate period of `pool_id`, `None` means the default
+	/// period, and keep the counts of periods in sync.
+	fn set_accumulate_period(pool_id: &PoolId<T::RelaychainAccountId>, accumulate_period: Option<T::BlockNumber>) {
+		AccumulatePeriods::<T>::mutate_exists(pool_id, |maybe_period| {
+			if let Some(old_period) = maybe_period.take() {
+				AccumulatePeriodCounts::<T>::mutate_exists(old_period, |maybe_count| {
+					*maybe_count = maybe_count
+						.map(|count| count.saturating_sub(1))
+						.filter(|count| *count > 0);
+				});
+			}
+			if let Some(new_period) = accumulate_period {
+				AccumulatePeriodCounts::<T>::mutate(new_period, |count| *count = count.saturating_add(1));
+			}
+			*maybe_period = accumulate_period;
+		});
+	}
+
+	/// Get the pool which should receive new shares of `pool_id`, following
+	/// the successors of retired pools. Return `None` if shares can not be
+	/// added to it anymore.
+	pub fn active_pool_id(pool_id: &PoolId<T::RelaychainAccountId>) -> Option<PoolId<T::RelaychainAccountId>> {
+		let mut pool_id = pool_id.clone();
+		// successors are always active when set, so this will terminate.
+		while let Some(successor) = Self::retired_pools(&pool_id) {
+			pool_id = successor?;
+		}
+		Some(pool_id)
+	}
+
+	/// Get the rewards of `who` in `pool_id` which have not been claimed
+	/// yet.
+	pub fn pending_rewards(pool_id: &PoolId<T::RelaychainAccountId>, who: &T::AccountId) -> Balance {
+		let (share, withdrawn_rewards) = <orml_rewards::Pallet<T>>::share_and_withdrawn_reward(pool_id, who);
+		if share.is_zero() {
+			return Zero::zero();
+		}
+
+		let pool_info = <orml_rewards::Pallet<T>>::pools(pool_id);
+		let total_reward_proportion = FixedU128::checked_from_rational(share, pool_info.total_shares)
+			.unwrap_or_default()
+			.saturating_mul_int(pool_info.total_rewards);
+		total_reward_proportion.saturating_sub(withdrawn_rewards).min(
+			pool_info
+				.total_rewards
+				.saturating_sub(pool_info.total_withdrawn_rewards),
+		)
+	}
+
+	fn reward_currency_id(pool_id: &PoolId<T::RelaychainAccountId>) -> CurrencyId {
+		match pool_id {
+			PoolId::LoansIncentive(_) | PoolId::DexIncentive(_) | PoolId::HomaIncentive => T::NativeCurrencyId::get(),
+			PoolId::DexSaving(_) => T::StableCurrencyId::get(),
+			PoolId::HomaValidatorAllowance(_) => T::LiquidCurrencyId::get(),
+		}
+	}
+
+	fn do_claim_rewards(who: &T::AccountId, pool_id: PoolId<T::RelaychainAccountId>) {
+		<orml_rewards::Pallet<T>>::claim_rewards(who, &pool_id);
+		Self::deposit_event(Event::ClaimRewards(who.clone(), pool_id));
+	}
+
+	fn do_compound_rewards(
+		who: &T::AccountId,
+		lp_currency_id: CurrencyId,
+		swap_paths: &[Vec<CurrencyId>],
+		min_share_increment: Balance,
+	) -> DispatchResult {
+		let (currency_id_0, currency_id_1) = match lp_currency_id {
+			CurrencyId::DexShare(symbol_0, symbol_1) => {
+				let to_currency_id = |symbol| match symbol {
+					DexShare::Token(token) => CurrencyId::Token(token),
+					DexShare::Erc20(address) => CurrencyId::Erc20(address),
+				};
+				(to_currency_id(symbol_0), to_currency_id(symbol_1))
+			}
+			_ => return Err(Error::<T>::InvalidCurrencyId.into()),
+		};
+
+		let pool_ids = sp_std::vec![PoolId::DexIncentive(lp_currency_id), PoolId::DexSaving(lp_currency_id)];
+		ensure!(swap_paths.len() <= pool_ids.len(), Error::<T>::InvalidSwapPath);
+		let (share_before, _) =
+			<orml_rewards::Pallet<T>>::share_and_withdrawn_reward(&PoolId::DexIncentive(lp_currency_id), who);
+
+		let (mut amount_0, mut amount_1): (Balance, Balance) = (Zero::zero(), Zero::zero());
+		for pool_id in pool_ids {
+			let reward_currency_id = Self::reward_currency_id(&pool_id);
+			let balance_before = T::Currency::free_balance(reward_currency_id, who);
+			Self::do_claim_rewards(who, pool_id);
+			let reward = T::Currency::free_balance(reward_currency_id, who).saturating_sub(balance_before);
+
+			if reward.is_zero() {
+				continue;
+			} else if reward_currency_id == currency_id_1 {
+				amount_1 = amount_1.saturating_add(reward);
+			} else if reward_currency_id == currency_id_0 {
+				amount_0 = amount_0.saturating_add(reward);
+			} else {
+				// the slippage is bounded by `min_share_increment`
+				let path = swap_paths
+					.iter()
+					.find(|path| path.len() >= 2 && path[0] == reward_currency_id)
+					.ok_or(Error::<T>::InvalidSwapPath)?;
+				let target_currency_id = path[path.len() - 1];
+				ensure!(
+					target_currency_id == currency_id_0 || target_currency_id == currency_id_1,
+					Error::<T>::InvalidSwapPath
+				);
+				let target_amount = T::DEX::swap_with_exact_supply(who, path, reward, 0, None)?;
+				if target_currency_id == currency_id_0 {
+					amount_0 = amount_0.saturating_add(target_amount);
+				} else {
+					amount_1 = amount_1.saturating_add(target_amount);
+				}
+			}
+		}
+		ensure!(
+			!amount_0.is_zero() || !amount_1.is_zero(),
+			Error::<T>::NoRewardsToCompound
+		);
+
+		// swap half of the excess so the amounts match the price of the pool
+		let (pool_0, pool_1) = T::DEX::get_liquidity_pool(currency_id_0, currency_id_1);
+		let amount_1_in_0 = FixedU128::checked_from_rational(pool_0, pool_1)
+			.unwrap_or_default()
+			.saturating_mul_int(amount_1);
+		if amount_0 > amount_1_in_0 {
+			let excess_0 = amount_0.saturating_sub(amount_1_in_0) / 2;
+			if !excess_0.is_zero() {
+				let target_amount =
+					T::DEX::swap_with_exact_supply(who, &[currency_id_0, currency_id_1], excess_0, 0, None)?;
+				amount_0 = amount_0.saturating_sub(excess_0);
+				amount_1 = amount_1.saturating_add(target_amount);
+			}
+		} else {
+			let amount_0_in_1 = FixedU128::checked_from_rational(pool_1, pool_0)
+				.unwrap_or_default()
+				.saturating_mul_int(amount_0);
+			let excess_1 = amount_1.saturating_sub(amount_0_in_1) / 2;
+			if !excess_1.is_zero() {
+				let target_amount =
+					T::DEX::swap_with_exact_supply(who, &[currency_id_1, currency_id_0], excess_1, 0, None)?;
+				amount_1 = amount_1.saturating_sub(excess_1);
+				amount_0 = amount_0.saturating_add(target_amount);
+			}
+		}
+
+		T::DEX::add_liquidity(who, currency_id_0, currency_id_1, amount_0, amount_1, true)?;
+		let (share_after, _) =
+			<orml_rewards::Pallet<T>>::share_and_withdrawn_reward(&PoolId::DexIncentive(lp_currency_id), who);
+		ensure!(
+			share_after.saturating_sub(share_before) >= min_share_increment,
+			Error::<T>::InsufficientCompoundedShare
+		);
+
+		Self::deposit_event(Event::CompoundRewards(who.clone(), lp_currency_id, amount_0, amount_1));
+		Ok(())
+	}
+
+	/// Carry the pending rewards of `who` in `pool_id` into the snapshot,
+	/// they are marked as withdrawn so removing the share won't pay them out.
+	fn snapshot_rewards(who: &T::AccountId, pool_id: &PoolId<T::RelaychainAccountId>) {
+		let pending_rewards = Self::pending_rewards(pool_id, who);
+		if pending_rewards.is_zero() {
+			return;
+		}
+
+		orml_rewards::ShareAndWithdrawnReward::<T>::mutate(pool_id, who, |(_, withdrawn_rewards)| {
+			*withdrawn_rewards = withdrawn_rewards.saturating_add(pending_rewards);
+		});
+		orml_rewards::Pools::<T>::mutate(pool_id, |pool_info| {
+			pool_info.total_withdrawn_rewards = pool_info.total_withdrawn_rewards.saturating_add(pending_rewards);
+		});
+		RewardSnapshots::<T>::mutate(pool_id, who, |snapshot| {
+			*snapshot = snapshot.saturating_add(pending_rewards);
+		});
+		Self::deposit_event(Event::RewardsSnapshotted(who.clone(), pool_id.clone(), pending_rewards));
+	}
+
+	/// Move all shares of `who` in the retired `pool_id` out of it. The
+	/// accrued rewards are snapshotted and can be claimed by
+	/// `claim_snapshot_rewards`.
+	fn do_migrate_shares(who: &T::AccountId, pool_id: &PoolId<T::RelaychainAccountId>) -> DispatchResult {
+		let (share, _) = <orml_rewards::Pallet<T>>::share_and_withdrawn_reward(pool_id, who);
+
+		match pool_id {
+			PoolId::DexIncentive(lp_currency_id) | PoolId::DexSaving(lp_currency_id) => {
+				// both pools are left by withdrawing the dex share.
+				Self::snapshot_rewards(who, &PoolId::DexIncentive(*lp_currency_id));
+				Self::snapshot_rewards(who, &PoolId::DexSaving(*lp_currency_id));
+				// dex share is deposited to both pools, withdraw it entirely.
+				let amount = share.min(
+					<orml_rewards::Pallet<T>>::share_and_withdrawn_reward(&PoolId::DexIncentive(*lp_currency_id), who)
+						.0,
+				);
+				if !amount.is_zero() {
+					Self::do_withdraw_dex_share(who, *lp_currency_id, amount)?;
+				}
+			}
+			_ => {
+				Self::snapshot_rewards(who, pool_id);
+				<orml_rewards::Pallet<T>>::remove_share(who, pool_id, share);
+				if let Some(successor) = Self::active_pool_id(pool_id) {
+					<orml_rewards::Pallet<T>>::add_share(who, &successor, share);
+				}
+			}
+		}
+
+		orml_rewards::ShareAndWithdrawnReward::<T>::remove(pool_id, who);
+		Ok(())
+	}
+
+	/// Migrate the shares of `who` if `pool_id` is retired, and return the
+	/// pool which shares updates should apply to.
+	fn ensure_migrated(
+		who: &T::AccountId,
+		pool_id: &PoolId<T::RelaychainAccountId>,
+	) -> Option<PoolId<T::RelaychainAccountId>> {
+		if Self::is_retired(pool_id) {
+			if orml_rewards::ShareAndWithdrawnReward::<T>::contains_key(pool_id, who) {
+				if let Err(e) = Self::do_migrate_shares(who, pool_id) {
+					log::warn!(
+						target: "incentives",
+						"do_migrate_shares: failed to migrate shares of {:?} in {:?}: {:?}. \
+						This is unexpected but should be safe",
+						who, pool_id, e
+					);
+				}
+			}
+			Self::active_pool_id(pool_id)
+		} else {
+			Some(pool_id.clone())
+		}
+	}
 }
 
 impl<T: Config> DEXIncentives<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 	fn do_deposit_dex_share(who: &T::AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult {
 		ensure!(lp_currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
+		ensure!(
+			!Self::is_retired(&PoolId::DexIncentive(lp_currency_id))
+				&& !Self::is_retired(&PoolId::DexSaving(lp_currency_id)),
+			Error::<T>::PoolRetired
+		);
+		Self::ensure_within_deposit_caps(&PoolId::DexIncentive(lp_currency_id), who, amount)?;
+		Self::ensure_within_deposit_caps(&PoolId::DexSaving(lp_currency_id), who, amount)?;
 
 		T::Currency::transfer(lp_currency_id, who, &Self::account_id(), amount)?;
 		<orml_rewards::Pallet<T>>::add_share(
@@ -416,6 +1127,32 @@ impl<T: Config> DEXIncentives<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 	}
 }
 
+impl<T: Config> IncentivesManager<T::AccountId, CurrencyId, Balance> for Pallet<T> {
+	#[transactional]
+	fn deposit_dex_share(who: &T::AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+		Self::do_deposit_dex_share(who, lp_currency_id, amount)
+	}
+
+	#[transactional]
+	fn withdraw_dex_share(who: &T::AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+		Self::do_withdraw_dex_share(who, lp_currency_id, amount)
+	}
+
+	fn claim_dex_rewards(who: &T::AccountId, lp_currency_id: CurrencyId) -> DispatchResult {
+		ensure!(lp_currency_id.is_dex_share_currency_id(), Error::<T>::InvalidCurrencyId);
+		Self::do_claim_rewards(who, PoolId::DexIncentive(lp_currency_id));
+		Self::do_claim_rewards(who, PoolId::DexSaving(lp_currency_id));
+		Ok(())
+	}
+
+	fn get_pending_dex_rewards(who: &T::AccountId, lp_currency_id: CurrencyId) -> (Balance, Balance) {
+		(
+			Self::pending_rewards(&PoolId::DexIncentive(lp_currency_id), who),
+			Self::pending_rewards(&PoolId::DexSaving(lp_currency_id), who),
+		)
+	}
+}
+
 pub struct OnUpdateLoan<T>(sp_std::marker::PhantomData<T>);
 impl<T: Config> Happened<(T::AccountId, CurrencyId, Amount, Balance)> for OnUpdateLoan<T> {
 	fn happened(info: &(T::AccountId, CurrencyId, Amount, Balance)) {
@@ -430,7 +1167,9 @@ impl<T: Config> Happened<(T::AccountId, CurrencyId, Amount, Balance)> for OnUpda
 				previous_amount.saturating_sub(adjustment_abs)
 			};
 
-			<orml_rewards::Pallet<T>>::set_share(who, &PoolId::LoansIncentive(*currency_id), new_share_amount);
+			if let Some(pool_id) = Pallet::<T>::ensure_migrated(who, &PoolId::LoansIncentive(*currency_id)) {
+				<orml_rewards::Pallet<T>>::set_share(who, &pool_id, new_share_amount);
+			}
 		}
 	}
 }
@@ -439,7 +1178,9 @@ pub struct OnIncreaseGuarantee<T>(sp_std::marker::PhantomData<T>);
 impl<T: Config> Happened<(T::AccountId, T::RelaychainAccountId, Balance)> for OnIncreaseGuarantee<T> {
 	fn happened(info: &(T::AccountId, T::RelaychainAccountId, Balance)) {
 		let (who, validator, increment) = info;
-		<orml_rewards::Pallet<T>>::add_share(who, &PoolId::HomaValidatorAllowance(validator.clone()), *increment);
+		if let Some(pool_id) = Pallet::<T>::ensure_migrated(who, &PoolId::HomaValidatorAllowance(validator.clone())) {
+			<orml_rewards::Pallet<T>>::add_share(who, &pool_id, *increment);
+		}
 	}
 }
 
@@ -447,7 +1188,9 @@ pub struct OnDecreaseGuarantee<T>(sp_std::marker::PhantomData<T>);
 impl<T: Config> Happened<(T::AccountId, T::RelaychainAccountId, Balance)> for OnDecreaseGuarantee<T> {
 	fn happened(info: &(T::AccountId, T::RelaychainAccountId, Balance)) {
 		let (who, validator, decrement) = info;
-		<orml_rewards::Pallet<T>>::remove_share(who, &PoolId::HomaValidatorAllowance(validator.clone()), *decrement);
+		if let Some(pool_id) = Pallet::<T>::ensure_migrated(who, &PoolId::HomaValidatorAllowance(validator.clone())) {
+			<orml_rewards::Pallet<T>>::remove_share(who, &pool_id, *decrement);
+		}
 	}
 }
 
@@ -456,11 +1199,7 @@ impl<T: Config> RewardHandler<T::AccountId> for Pallet<T> {
 	type PoolId = PoolId<T::RelaychainAccountId>;
 
 	fn payout(who: &T::AccountId, pool_id: &Self::PoolId, amount: Self::Balance) {
-		let currency_id = match pool_id {
-			PoolId::LoansIncentive(_) | PoolId::DexIncentive(_) | PoolId::HomaIncentive => T::NativeCurrencyId::get(),
-			PoolId::DexSaving(_) => T::StableCurrencyId::get(),
-			PoolId::HomaValidatorAllowance(_) => T::LiquidCurrencyId::get(),
-		};
+		let currency_id = Pallet::<T>::reward_currency_id(pool_id);
 
 		// payout the reward to user from the pool. it should not affect the
 		// process, ignore the result to continue. if it fails, just the user will not
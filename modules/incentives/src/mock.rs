@@ -28,7 +28,7 @@ use frame_support::{
 };
 use frame_system::EnsureSignedBy;
 use orml_traits::parameter_type_with_key;
-use primitives::{DexShare, TokenSymbol};
+use primitives::{evm::EvmAddress, DexShare, TokenSymbol};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
 use sp_std::cell::RefCell;
@@ -152,6 +152,44 @@ impl CDPTreasury<AccountId> for MockCDPTreasury {
 	}
 }
 
+thread_local! {
+	static ADDED_LIQUIDITY: RefCell<Vec<(AccountId, CurrencyId, CurrencyId, Balance, Balance, bool)>> = RefCell::new(vec![]);
+}
+
+pub fn added_liquidity() -> Vec<(AccountId, CurrencyId, CurrencyId, Balance, Balance, bool)> {
+	ADDED_LIQUIDITY.with(|v| v.borrow().clone())
+}
+
+thread_local! {
+	static ERC20_REFERENCES: RefCell<Vec<(PalletId, CurrencyId)>> = RefCell::new(vec![]);
+}
+
+pub fn erc20_references() -> Vec<(PalletId, CurrencyId)> {
+	ERC20_REFERENCES.with(|v| v.borrow().clone())
+}
+
+/// Records the references to all currencies, not only `CurrencyId::Erc20`.
+pub struct MockErc20ReferenceCounter;
+impl Erc20ReferenceCounter for MockErc20ReferenceCounter {
+	fn inc_reference(id: PalletId, currency_id: CurrencyId) {
+		ERC20_REFERENCES.with(|v| v.borrow_mut().push((id, currency_id)));
+	}
+
+	fn dec_reference(id: PalletId, currency_id: CurrencyId) {
+		ERC20_REFERENCES.with(|v| {
+			let mut references = v.borrow_mut();
+			if let Some(position) = references.iter().position(|reference| *reference == (id, currency_id)) {
+				references.remove(position);
+			}
+		});
+	}
+
+	fn references(_address: EvmAddress) -> Vec<(PalletId, u32)> {
+		Default::default()
+	}
+}
+
+/// Swaps at 1:1 price and keeps the added liquidity in memory.
 pub struct MockDEX;
 impl DEXManager<AccountId, CurrencyId, Balance> for MockDEX {
 	fn get_liquidity_pool(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
@@ -173,13 +211,15 @@ impl DEXManager<AccountId, CurrencyId, Balance> for MockDEX {
 	}
 
 	fn swap_with_exact_supply(
-		_: &AccountId,
-		_: &[CurrencyId],
-		_: Balance,
+		who: &AccountId,
+		path: &[CurrencyId],
+		supply_amount: Balance,
 		_: Balance,
 		_: Option<Ratio>,
 	) -> sp_std::result::Result<Balance, DispatchError> {
-		unimplemented!()
+		TokensModule::withdraw(path[0], who, supply_amount)?;
+		TokensModule::deposit(path[path.len() - 1], who, supply_amount)?;
+		Ok(supply_amount)
 	}
 
 	fn swap_with_exact_target(
@@ -192,8 +232,37 @@ impl DEXManager<AccountId, CurrencyId, Balance> for MockDEX {
 		unimplemented!()
 	}
 
-	fn add_liquidity(_: &AccountId, _: CurrencyId, _: CurrencyId, _: Balance, _: Balance, _: bool) -> DispatchResult {
-		unimplemented!()
+	fn add_liquidity(
+		who: &AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		max_amount_a: Balance,
+		max_amount_b: Balance,
+		deposit_increment_share: bool,
+	) -> DispatchResult {
+		TokensModule::withdraw(currency_id_a, who, max_amount_a)?;
+		TokensModule::withdraw(currency_id_b, who, max_amount_b)?;
+		if deposit_increment_share {
+			// the lp shares increment is `max_amount_a`
+			let lp_currency_id = match (currency_id_a, currency_id_b) {
+				(BTC, AUSD) => BTC_AUSD_LP,
+				(DOT, AUSD) => DOT_AUSD_LP,
+				_ => unimplemented!(),
+			};
+			TokensModule::deposit(lp_currency_id, who, max_amount_a)?;
+			<IncentivesModule as DEXIncentives<_, _, _>>::do_deposit_dex_share(who, lp_currency_id, max_amount_a)?;
+		}
+		ADDED_LIQUIDITY.with(|v| {
+			v.borrow_mut().push((
+				*who,
+				currency_id_a,
+				currency_id_b,
+				max_amount_a,
+				max_amount_b,
+				deposit_increment_share,
+			))
+		});
+		Ok(())
 	}
 
 	fn remove_liquidity(_: &AccountId, _: CurrencyId, _: CurrencyId, _: Balance, _: bool) -> DispatchResult {
@@ -252,6 +321,7 @@ impl Config for Runtime {
 	type DEX = MockDEX;
 	type EmergencyShutdown = MockEmergencyShutdown;
 	type PalletId = IncentivesPalletId;
+	type Erc20ReferenceCounter = MockErc20ReferenceCounter;
 	type WeightInfo = ();
 }
 
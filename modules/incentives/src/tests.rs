@@ -280,6 +280,196 @@ fn update_dex_saving_rewards_works() {
 	});
 }
 
+#[test]
+fn update_deposit_caps_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			IncentivesModule::update_deposit_caps(Origin::signed(ALICE), vec![]),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::update_deposit_caps(
+				Origin::signed(4),
+				vec![(PoolId::DexIncentive(DOT), Some(100), None)]
+			),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			IncentivesModule::update_deposit_caps(Origin::signed(4), vec![(PoolId::HomaIncentive, Some(100), None)]),
+			Error::<Runtime>::InvalidPoolId
+		);
+
+		assert_ok!(IncentivesModule::update_deposit_caps(
+			Origin::signed(4),
+			vec![
+				(PoolId::DexIncentive(BTC_AUSD_LP), Some(1000), Some(600)),
+				(PoolId::DexSaving(BTC_AUSD_LP), None, Some(800)),
+			],
+		));
+		assert_eq!(
+			IncentivesModule::deposit_caps(PoolId::DexIncentive(BTC_AUSD_LP)),
+			DepositCap {
+				total: Some(1000),
+				per_account: Some(600),
+			}
+		);
+		assert_eq!(
+			IncentivesModule::deposit_caps(PoolId::DexSaving(BTC_AUSD_LP)),
+			DepositCap {
+				total: None,
+				per_account: Some(800),
+			}
+		);
+		let deposit_caps_updated_event = Event::incentives(crate::Event::DepositCapsUpdated(
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			Some(1000),
+			Some(600),
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == deposit_caps_updated_event));
+
+		assert_ok!(IncentivesModule::update_deposit_caps(
+			Origin::signed(4),
+			vec![(PoolId::DexSaving(BTC_AUSD_LP), None, None)],
+		));
+		assert_eq!(
+			DepositCaps::<Runtime>::contains_key(PoolId::DexSaving(BTC_AUSD_LP)),
+			false
+		);
+	});
+}
+
+#[test]
+fn update_accumulate_periods_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			IncentivesModule::update_accumulate_periods(Origin::signed(ALICE), vec![]),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::update_accumulate_periods(Origin::signed(4), vec![(PoolId::DexIncentive(DOT), Some(5))]),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			IncentivesModule::update_accumulate_periods(
+				Origin::signed(4),
+				vec![(PoolId::HomaValidatorAllowance(VALIDATOR), Some(5))]
+			),
+			Error::<Runtime>::InvalidPoolId
+		);
+		assert_noop!(
+			IncentivesModule::update_accumulate_periods(Origin::signed(4), vec![(PoolId::HomaIncentive, Some(0))]),
+			Error::<Runtime>::InvalidAccumulatePeriod
+		);
+
+		assert_eq!(IncentivesModule::accumulate_period(&PoolId::LoansIncentive(BTC)), 10);
+		assert_ok!(IncentivesModule::update_accumulate_periods(
+			Origin::signed(4),
+			vec![
+				(PoolId::LoansIncentive(BTC), Some(5)),
+				(PoolId::DexSaving(BTC_AUSD_LP), Some(20)),
+			],
+		));
+		assert_eq!(
+			IncentivesModule::accumulate_periods(PoolId::LoansIncentive(BTC)),
+			Some(5)
+		);
+		assert_eq!(IncentivesModule::accumulate_period(&PoolId::LoansIncentive(BTC)), 5);
+		assert_eq!(IncentivesModule::accumulate_period(&PoolId::DexSaving(BTC_AUSD_LP)), 20);
+		assert_eq!(AccumulatePeriodCounts::<Runtime>::get(5), 1);
+		assert_eq!(AccumulatePeriodCounts::<Runtime>::get(20), 1);
+		let accumulate_period_updated_event = Event::incentives(crate::Event::AccumulatePeriodUpdated(
+			PoolId::LoansIncentive(BTC),
+			Some(5),
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == accumulate_period_updated_event));
+
+		assert_ok!(IncentivesModule::update_accumulate_periods(
+			Origin::signed(4),
+			vec![(PoolId::LoansIncentive(BTC), None)],
+		));
+		assert_eq!(IncentivesModule::accumulate_periods(PoolId::LoansIncentive(BTC)), None);
+		assert_eq!(IncentivesModule::accumulate_period(&PoolId::LoansIncentive(BTC)), 10);
+		assert!(!AccumulatePeriodCounts::<Runtime>::contains_key(5));
+
+		assert_ok!(IncentivesModule::retire_pool(
+			Origin::signed(4),
+			PoolId::DexSaving(BTC_AUSD_LP),
+			None
+		));
+		assert_eq!(
+			IncentivesModule::accumulate_periods(PoolId::DexSaving(BTC_AUSD_LP)),
+			None
+		);
+		assert!(!AccumulatePeriodCounts::<Runtime>::contains_key(20));
+		assert_noop!(
+			IncentivesModule::update_accumulate_periods(
+				Origin::signed(4),
+				vec![(PoolId::DexSaving(BTC_AUSD_LP), Some(5))]
+			),
+			Error::<Runtime>::PoolRetired
+		);
+	});
+}
+
+#[test]
+fn deposit_dex_share_should_respect_deposit_caps() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TokensModule::deposit(BTC_AUSD_LP, &ALICE, 10000));
+		assert_ok!(TokensModule::deposit(BTC_AUSD_LP, &BOB, 10000));
+		assert_ok!(IncentivesModule::update_deposit_caps(
+			Origin::signed(4),
+			vec![(PoolId::DexIncentive(BTC_AUSD_LP), Some(1000), Some(600))],
+		));
+
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			500
+		));
+		assert_noop!(
+			IncentivesModule::deposit_dex_share(Origin::signed(ALICE), BTC_AUSD_LP, 101),
+			Error::<Runtime>::ExceedAccountDepositCap
+		);
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			100
+		));
+
+		assert_noop!(
+			IncentivesModule::deposit_dex_share(Origin::signed(BOB), BTC_AUSD_LP, 401),
+			Error::<Runtime>::ExceedPoolDepositCap
+		);
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(BOB),
+			BTC_AUSD_LP,
+			400
+		));
+		assert_eq!(
+			RewardsModule::pools(PoolId::DexIncentive(BTC_AUSD_LP)).total_shares,
+			1000
+		);
+
+		// withdrawing frees up the caps
+		assert_ok!(IncentivesModule::withdraw_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			200
+		));
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(BOB),
+			BTC_AUSD_LP,
+			200
+		));
+	});
+}
+
 #[test]
 fn add_allowance_works() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -572,3 +762,553 @@ fn on_initialize_should_work() {
 		assert_eq!(RewardsModule::pools(PoolId::DexSaving(DOT_AUSD_LP)).total_rewards, 8);
 	});
 }
+
+#[test]
+fn on_initialize_should_respect_pool_accumulate_period() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IncentivesModule::update_incentive_rewards(
+			Origin::signed(4),
+			vec![(PoolId::LoansIncentive(BTC), 1000), (PoolId::HomaIncentive, 30)],
+		));
+		assert_ok!(IncentivesModule::update_accumulate_periods(
+			Origin::signed(4),
+			vec![(PoolId::HomaIncentive, Some(3))],
+		));
+		RewardsModule::add_share(&ALICE, &PoolId::LoansIncentive(BTC), 1);
+		RewardsModule::add_share(&ALICE, &PoolId::HomaIncentive, 1);
+
+		// no pool accumulates at this block, only the period lookups are charged
+		assert_eq!(
+			IncentivesModule::on_initialize(4),
+			<Runtime as frame_system::Config>::DbWeight::get().reads(2)
+		);
+		assert_eq!(RewardsModule::pools(PoolId::HomaIncentive).total_rewards, 0);
+
+		// rewards are scaled by 3 / 10 to keep the per block emission
+		IncentivesModule::on_initialize(3);
+		assert_eq!(RewardsModule::pools(PoolId::LoansIncentive(BTC)).total_rewards, 0);
+		assert_eq!(RewardsModule::pools(PoolId::HomaIncentive).total_rewards, 9);
+
+		IncentivesModule::on_initialize(9);
+		assert_eq!(RewardsModule::pools(PoolId::LoansIncentive(BTC)).total_rewards, 0);
+		assert_eq!(RewardsModule::pools(PoolId::HomaIncentive).total_rewards, 18);
+
+		IncentivesModule::on_initialize(10);
+		assert_eq!(RewardsModule::pools(PoolId::LoansIncentive(BTC)).total_rewards, 1000);
+		assert_eq!(RewardsModule::pools(PoolId::HomaIncentive).total_rewards, 18);
+		assert_eq!(TokensModule::free_balance(ACA, &VAULT), 1018);
+	});
+}
+
+#[test]
+fn migrate_to_pool_accumulate_periods_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IncentivesModule::update_incentive_rewards(
+			Origin::signed(4),
+			vec![(PoolId::LoansIncentive(BTC), 1000)],
+		));
+		assert_ok!(IncentivesModule::update_dex_saving_rewards(
+			Origin::signed(4),
+			vec![(PoolId::DexSaving(BTC_AUSD_LP), Rate::saturating_from_rational(1, 100))],
+		));
+		assert_ok!(IncentivesModule::update_accumulate_periods(
+			Origin::signed(4),
+			vec![(PoolId::DexSaving(BTC_AUSD_LP), Some(3))],
+		));
+
+		IncentivesModule::on_runtime_upgrade();
+		assert_eq!(
+			IncentivesModule::accumulate_periods(PoolId::LoansIncentive(BTC)),
+			Some(10)
+		);
+		assert_eq!(
+			IncentivesModule::accumulate_periods(PoolId::DexSaving(BTC_AUSD_LP)),
+			Some(3)
+		);
+		assert_eq!(IncentivesModule::accumulate_periods(PoolId::HomaIncentive), None);
+		assert_eq!(AccumulatePeriodCounts::<Runtime>::get(10), 1);
+		assert_eq!(AccumulatePeriodCounts::<Runtime>::get(3), 1);
+
+		// only migrate once
+		assert_ok!(IncentivesModule::update_accumulate_periods(
+			Origin::signed(4),
+			vec![(PoolId::LoansIncentive(BTC), None)],
+		));
+		IncentivesModule::on_runtime_upgrade();
+		assert_eq!(IncentivesModule::accumulate_periods(PoolId::LoansIncentive(BTC)), None);
+	});
+}
+
+#[test]
+fn erc20_references_of_pools_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(IncentivesModule::update_incentive_rewards(
+			Origin::signed(4),
+			vec![(PoolId::LoansIncentive(BTC), 1000), (PoolId::HomaIncentive, 1000)],
+		));
+		assert_ok!(IncentivesModule::update_incentive_rewards(
+			Origin::signed(4),
+			vec![(PoolId::LoansIncentive(BTC), 2000)],
+		));
+		assert_eq!(erc20_references(), vec![(IncentivesPalletId::get(), BTC)]);
+
+		assert_ok!(IncentivesModule::update_dex_saving_rewards(
+			Origin::signed(4),
+			vec![(PoolId::DexSaving(DOT_AUSD_LP), Rate::saturating_from_rational(1, 100))],
+		));
+		assert_eq!(
+			erc20_references(),
+			vec![
+				(IncentivesPalletId::get(), BTC),
+				(IncentivesPalletId::get(), DOT),
+				(IncentivesPalletId::get(), AUSD)
+			]
+		);
+
+		assert_ok!(IncentivesModule::retire_pool(
+			Origin::signed(4),
+			PoolId::DexSaving(DOT_AUSD_LP),
+			None
+		));
+		assert_eq!(erc20_references(), vec![(IncentivesPalletId::get(), BTC)]);
+	});
+}
+
+#[test]
+fn migrate_to_erc20_references_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		IncentiveRewardAmount::<Runtime>::insert(PoolId::DexIncentive(DOT_AUSD_LP), 1000);
+
+		IncentivesModule::on_runtime_upgrade();
+		assert_eq!(
+			erc20_references(),
+			vec![(IncentivesPalletId::get(), DOT), (IncentivesPalletId::get(), AUSD)]
+		);
+		assert!(UpgradedToErc20References::<Runtime>::get());
+
+		// only migrate once
+		IncentivesModule::on_runtime_upgrade();
+		assert_eq!(erc20_references().len(), 2);
+	});
+}
+
+#[test]
+fn retire_pool_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(IncentivesModule::update_incentive_rewards(
+			Origin::signed(4),
+			vec![(PoolId::LoansIncentive(BTC), 1000)],
+		));
+
+		assert_noop!(
+			IncentivesModule::retire_pool(Origin::signed(ALICE), PoolId::LoansIncentive(BTC), None),
+			BadOrigin
+		);
+		assert_noop!(
+			IncentivesModule::retire_pool(
+				Origin::signed(4),
+				PoolId::LoansIncentive(BTC),
+				Some(PoolId::LoansIncentive(BTC))
+			),
+			Error::<Runtime>::InvalidSuccessor
+		);
+		assert_noop!(
+			IncentivesModule::retire_pool(
+				Origin::signed(4),
+				PoolId::LoansIncentive(BTC),
+				Some(PoolId::HomaIncentive)
+			),
+			Error::<Runtime>::InvalidSuccessor
+		);
+		assert_noop!(
+			IncentivesModule::retire_pool(
+				Origin::signed(4),
+				PoolId::DexIncentive(BTC_AUSD_LP),
+				Some(PoolId::DexIncentive(DOT_AUSD_LP))
+			),
+			Error::<Runtime>::InvalidSuccessor
+		);
+
+		assert_ok!(IncentivesModule::retire_pool(
+			Origin::signed(4),
+			PoolId::LoansIncentive(BTC),
+			Some(PoolId::LoansIncentive(DOT))
+		));
+		let pool_retired_event = Event::incentives(crate::Event::PoolRetired(
+			PoolId::LoansIncentive(BTC),
+			Some(PoolId::LoansIncentive(DOT)),
+		));
+		assert!(System::events().iter().any(|record| record.event == pool_retired_event));
+		assert_eq!(
+			IncentivesModule::retired_pools(PoolId::LoansIncentive(BTC)),
+			Some(Some(PoolId::LoansIncentive(DOT)))
+		);
+		assert_eq!(
+			IncentivesModule::incentive_reward_amount(PoolId::LoansIncentive(BTC)),
+			0
+		);
+
+		assert_noop!(
+			IncentivesModule::retire_pool(Origin::signed(4), PoolId::LoansIncentive(BTC), None),
+			Error::<Runtime>::PoolRetired
+		);
+		assert_noop!(
+			IncentivesModule::retire_pool(
+				Origin::signed(4),
+				PoolId::LoansIncentive(DOT),
+				Some(PoolId::LoansIncentive(BTC))
+			),
+			Error::<Runtime>::InvalidSuccessor
+		);
+		assert_noop!(
+			IncentivesModule::update_incentive_rewards(Origin::signed(4), vec![(PoolId::LoansIncentive(BTC), 1000)]),
+			Error::<Runtime>::PoolRetired
+		);
+	});
+}
+
+#[test]
+fn retired_dex_pool_only_allows_withdraw() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TokensModule::deposit(BTC_AUSD_LP, &ALICE, 10000));
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			5000
+		));
+		assert_ok!(IncentivesModule::retire_pool(
+			Origin::signed(4),
+			PoolId::DexSaving(BTC_AUSD_LP),
+			None
+		));
+
+		assert_noop!(
+			IncentivesModule::deposit_dex_share(Origin::signed(ALICE), BTC_AUSD_LP, 5000),
+			Error::<Runtime>::PoolRetired
+		);
+		assert_noop!(
+			IncentivesModule::update_dex_saving_rewards(
+				Origin::signed(4),
+				vec![(PoolId::DexSaving(BTC_AUSD_LP), Rate::saturating_from_rational(1, 100))]
+			),
+			Error::<Runtime>::PoolRetired
+		);
+		assert_ok!(IncentivesModule::withdraw_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			1000
+		));
+		assert_eq!(TokensModule::free_balance(BTC_AUSD_LP, &ALICE), 6000);
+	});
+}
+
+#[test]
+fn migrate_pool_shares_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 0));
+		OnUpdateLoan::<Runtime>::happened(&(BOB, BTC, 300, 0));
+		assert_ok!(TokensModule::deposit(ACA, &VAULT, 1000));
+		RewardsModule::accumulate_reward(&PoolId::LoansIncentive(BTC), 1000);
+
+		assert_noop!(
+			IncentivesModule::migrate_pool_shares(Origin::signed(ALICE), PoolId::LoansIncentive(BTC), 10),
+			Error::<Runtime>::PoolNotRetired
+		);
+		assert_ok!(IncentivesModule::retire_pool(
+			Origin::signed(4),
+			PoolId::LoansIncentive(BTC),
+			Some(PoolId::LoansIncentive(DOT))
+		));
+
+		assert_ok!(IncentivesModule::migrate_pool_shares(
+			Origin::signed(ALICE),
+			PoolId::LoansIncentive(BTC),
+			1
+		));
+		assert_ok!(IncentivesModule::migrate_pool_shares(
+			Origin::signed(ALICE),
+			PoolId::LoansIncentive(BTC),
+			1
+		));
+		let pool_shares_migrated_event =
+			Event::incentives(crate::Event::PoolSharesMigrated(PoolId::LoansIncentive(BTC), 1));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == pool_shares_migrated_event));
+
+		// accrued rewards are carried in the snapshot instead of paid out
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 0);
+		assert_eq!(TokensModule::free_balance(ACA, &BOB), 0);
+		assert_eq!(
+			IncentivesModule::reward_snapshots(PoolId::LoansIncentive(BTC), ALICE),
+			250
+		);
+		assert_eq!(
+			IncentivesModule::reward_snapshots(PoolId::LoansIncentive(BTC), BOB),
+			750
+		);
+		let rewards_snapshotted_event = Event::incentives(crate::Event::RewardsSnapshotted(
+			ALICE,
+			PoolId::LoansIncentive(BTC),
+			250,
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == rewards_snapshotted_event));
+		assert_eq!(RewardsModule::pools(PoolId::LoansIncentive(BTC)).total_shares, 0);
+		assert_eq!(
+			RewardsModule::pools(PoolId::LoansIncentive(DOT)),
+			PoolInfo {
+				total_shares: 400,
+				total_rewards: 0,
+				total_withdrawn_rewards: 0
+			}
+		);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::LoansIncentive(DOT), ALICE),
+			(100, 0)
+		);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::LoansIncentive(DOT), BOB),
+			(300, 0)
+		);
+
+		// loan updates of the retired pool apply to the successor
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 100));
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::LoansIncentive(DOT), ALICE),
+			(200, 0)
+		);
+	});
+}
+
+#[test]
+fn claim_snapshot_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		OnUpdateLoan::<Runtime>::happened(&(ALICE, BTC, 100, 0));
+		OnUpdateLoan::<Runtime>::happened(&(BOB, BTC, 300, 0));
+		RewardsModule::accumulate_reward(&PoolId::LoansIncentive(BTC), 1000);
+		assert_ok!(IncentivesModule::retire_pool(
+			Origin::signed(4),
+			PoolId::LoansIncentive(BTC),
+			Some(PoolId::LoansIncentive(DOT))
+		));
+		assert_ok!(IncentivesModule::migrate_pool_shares(
+			Origin::signed(ALICE),
+			PoolId::LoansIncentive(BTC),
+			10
+		));
+		assert_eq!(
+			IncentivesModule::reward_snapshots(PoolId::LoansIncentive(BTC), ALICE),
+			250
+		);
+
+		// the snapshot is kept if the vault can not afford it yet
+		assert!(IncentivesModule::claim_snapshot_rewards(Origin::signed(ALICE), PoolId::LoansIncentive(BTC)).is_err());
+		assert_eq!(
+			IncentivesModule::reward_snapshots(PoolId::LoansIncentive(BTC), ALICE),
+			250
+		);
+
+		assert_ok!(TokensModule::deposit(ACA, &VAULT, 1000));
+		assert_ok!(IncentivesModule::claim_snapshot_rewards(
+			Origin::signed(ALICE),
+			PoolId::LoansIncentive(BTC)
+		));
+		let claim_snapshot_rewards_event = Event::incentives(crate::Event::ClaimSnapshotRewards(
+			ALICE,
+			PoolId::LoansIncentive(BTC),
+			250,
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == claim_snapshot_rewards_event));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 250);
+		assert_eq!(
+			IncentivesModule::reward_snapshots(PoolId::LoansIncentive(BTC), ALICE),
+			0
+		);
+		assert_noop!(
+			IncentivesModule::claim_snapshot_rewards(Origin::signed(ALICE), PoolId::LoansIncentive(BTC)),
+			Error::<Runtime>::NoSnapshotRewards
+		);
+
+		assert_ok!(IncentivesModule::claim_snapshot_rewards(
+			Origin::signed(BOB),
+			PoolId::LoansIncentive(BTC)
+		));
+		assert_eq!(TokensModule::free_balance(ACA, &BOB), 750);
+	});
+}
+
+#[test]
+fn compound_rewards_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			IncentivesModule::compound_rewards(Origin::signed(ALICE), BTC, vec![], 0),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			IncentivesModule::compound_rewards(Origin::signed(ALICE), BTC_AUSD_LP, vec![], 0),
+			Error::<Runtime>::NoRewardsToCompound
+		);
+
+		assert_ok!(TokensModule::deposit(BTC_AUSD_LP, &ALICE, 100));
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			100
+		));
+		assert_ok!(TokensModule::deposit(ACA, &VAULT, 1000));
+		assert_ok!(TokensModule::deposit(AUSD, &VAULT, 100));
+		RewardsModule::accumulate_reward(&PoolId::DexIncentive(BTC_AUSD_LP), 1000);
+		RewardsModule::accumulate_reward(&PoolId::DexSaving(BTC_AUSD_LP), 100);
+
+		assert_noop!(
+			IncentivesModule::compound_rewards(Origin::signed(ALICE), BTC_AUSD_LP, vec![], 0),
+			Error::<Runtime>::InvalidSwapPath
+		);
+		assert_noop!(
+			IncentivesModule::compound_rewards(Origin::signed(ALICE), BTC_AUSD_LP, vec![vec![ACA, DOT]], 0),
+			Error::<Runtime>::InvalidSwapPath
+		);
+		assert_noop!(
+			IncentivesModule::compound_rewards(Origin::signed(ALICE), BTC_AUSD_LP, vec![vec![ACA, BTC]], 511),
+			Error::<Runtime>::InsufficientCompoundedShare
+		);
+
+		// 1000 ACA is swapped to 1000 BTC, the pool price of BTC is 5 AUSD, so
+		// (1000 - 100 / 5) / 2 = 490 BTC is swapped to AUSD.
+		assert_ok!(IncentivesModule::compound_rewards(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			vec![vec![ACA, BTC]],
+			510
+		));
+		assert_eq!(added_liquidity(), vec![(ALICE, BTC, AUSD, 510, 590, true)]);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::DexIncentive(BTC_AUSD_LP), ALICE).0,
+			610
+		);
+		let compound_rewards_event = Event::incentives(crate::Event::CompoundRewards(ALICE, BTC_AUSD_LP, 510, 590));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == compound_rewards_event));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 0);
+		assert_eq!(TokensModule::free_balance(BTC, &ALICE), 0);
+		assert_eq!(TokensModule::free_balance(AUSD, &ALICE), 0);
+	});
+}
+
+#[test]
+fn compound_rewards_swaps_to_currency_1() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TokensModule::deposit(BTC_AUSD_LP, &ALICE, 100));
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			100
+		));
+		assert_ok!(TokensModule::deposit(ACA, &VAULT, 1000));
+		assert_ok!(TokensModule::deposit(AUSD, &VAULT, 100));
+		RewardsModule::accumulate_reward(&PoolId::DexIncentive(BTC_AUSD_LP), 1000);
+		RewardsModule::accumulate_reward(&PoolId::DexSaving(BTC_AUSD_LP), 100);
+
+		// 1000 ACA is swapped to 1000 AUSD through DOT, then half of the
+		// 1100 AUSD is swapped to BTC.
+		assert_ok!(IncentivesModule::compound_rewards(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			vec![vec![ACA, DOT, AUSD]],
+			0
+		));
+		assert_eq!(added_liquidity(), vec![(ALICE, BTC, AUSD, 550, 550, true)]);
+	});
+}
+
+#[test]
+fn migrate_dex_pool_shares_forces_exit() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(TokensModule::deposit(BTC_AUSD_LP, &ALICE, 10000));
+		assert_ok!(IncentivesModule::deposit_dex_share(
+			Origin::signed(ALICE),
+			BTC_AUSD_LP,
+			10000
+		));
+		assert_ok!(IncentivesModule::retire_pool(
+			Origin::signed(4),
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			None
+		));
+
+		assert_ok!(IncentivesModule::migrate_pool_shares(
+			Origin::signed(BOB),
+			PoolId::DexIncentive(BTC_AUSD_LP),
+			10
+		));
+		assert_eq!(TokensModule::free_balance(BTC_AUSD_LP, &ALICE), 10000);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::DexIncentive(BTC_AUSD_LP), ALICE),
+			(0, 0)
+		);
+		assert_eq!(
+			RewardsModule::share_and_withdrawn_reward(PoolId::DexSaving(BTC_AUSD_LP), ALICE),
+			(0, 0)
+		);
+	});
+}
+
+#[test]
+fn incentives_manager_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TokensModule::deposit(BTC_AUSD_LP, &ALICE, 10000));
+		assert_ok!(TokensModule::deposit(ACA, &VAULT, 10000));
+		assert_ok!(TokensModule::deposit(AUSD, &VAULT, 10000));
+
+		assert_ok!(<IncentivesModule as IncentivesManager<_, _, _>>::deposit_dex_share(
+			&ALICE,
+			BTC_AUSD_LP,
+			10000
+		));
+		assert_eq!(TokensModule::free_balance(BTC_AUSD_LP, &ALICE), 0);
+		assert_eq!(IncentivesModule::get_pending_dex_rewards(&ALICE, BTC_AUSD_LP), (0, 0));
+
+		RewardsModule::accumulate_reward(&PoolId::DexIncentive(BTC_AUSD_LP), 1000);
+		RewardsModule::accumulate_reward(&PoolId::DexSaving(BTC_AUSD_LP), 100);
+		assert_eq!(
+			IncentivesModule::get_pending_dex_rewards(&ALICE, BTC_AUSD_LP),
+			(1000, 100)
+		);
+		assert_eq!(IncentivesModule::get_pending_dex_rewards(&BOB, BTC_AUSD_LP), (0, 0));
+
+		assert_noop!(
+			IncentivesModule::claim_dex_rewards(&ALICE, BTC),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_ok!(IncentivesModule::claim_dex_rewards(&ALICE, BTC_AUSD_LP));
+		assert_eq!(TokensModule::free_balance(ACA, &ALICE), 1000);
+		assert_eq!(TokensModule::free_balance(AUSD, &ALICE), 100);
+		assert_eq!(IncentivesModule::get_pending_dex_rewards(&ALICE, BTC_AUSD_LP), (0, 0));
+		let claim_rewards_event =
+			Event::incentives(crate::Event::ClaimRewards(ALICE, PoolId::DexIncentive(BTC_AUSD_LP)));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == claim_rewards_event));
+
+		assert_ok!(<IncentivesModule as IncentivesManager<_, _, _>>::withdraw_dex_share(
+			&ALICE,
+			BTC_AUSD_LP,
+			10000
+		));
+		assert_eq!(TokensModule::free_balance(BTC_AUSD_LP, &ALICE), 10000);
+		assert_noop!(
+			<IncentivesModule as IncentivesManager<_, _, _>>::withdraw_dex_share(&ALICE, BTC_AUSD_LP, 1),
+			Error::<Runtime>::NotEnough
+		);
+	});
+}
@@ -55,6 +55,12 @@ pub trait WeightInfo {
 	fn update_incentive_rewards(c: u32, ) -> Weight;
 	fn update_dex_saving_rewards(c: u32, ) -> Weight;
 	fn add_allowance() -> Weight;
+	fn retire_pool() -> Weight;
+	fn migrate_pool_shares(c: u32, ) -> Weight;
+	fn update_deposit_caps(c: u32, ) -> Weight;
+	fn update_accumulate_periods(c: u32, ) -> Weight;
+	fn claim_snapshot_rewards() -> Weight;
+	fn compound_rewards() -> Weight;
 }
 
 /// Weights for module_incentives using the Acala node and recommended hardware.
@@ -97,6 +103,41 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 		(2_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn retire_pool() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn migrate_pool_shares(c: u32, ) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((68_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(c as Weight)))
+	}
+	fn update_deposit_caps(c: u32, ) -> Weight {
+		(1_102_000 as Weight)
+			// Standard Error: 24_000
+			.saturating_add((2_153_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn update_accumulate_periods(c: u32, ) -> Weight {
+		(1_054_000 as Weight)
+			// Standard Error: 22_000
+			.saturating_add((2_312_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn claim_snapshot_rewards() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn compound_rewards() -> Weight {
+		(412_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(26 as Weight))
+			.saturating_add(T::DbWeight::get().writes(22 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -138,4 +179,39 @@ impl WeightInfo for () {
 		(2_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn retire_pool() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn migrate_pool_shares(c: u32, ) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((68_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(c as Weight)))
+	}
+	fn update_deposit_caps(c: u32, ) -> Weight {
+		(1_102_000 as Weight)
+			// Standard Error: 24_000
+			.saturating_add((2_153_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn update_accumulate_periods(c: u32, ) -> Weight {
+		(1_054_000 as Weight)
+			// Standard Error: 22_000
+			.saturating_add((2_312_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn claim_snapshot_rewards() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn compound_rewards() -> Weight {
+		(412_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(26 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(22 as Weight))
+	}
 }
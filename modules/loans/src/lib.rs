@@ -22,20 +22,36 @@
 //!
 //! Loans module manages CDP's collateral assets and the debits backed by these
 //! assets.
+//!
+//! The owner can attach a label to each position to tell apart the strategies
+//! of the positions under different collateral types, the label is removed
+//! along with the position.
+//!
+//! Governance can opt-in a collateral type of staking currency to be
+//! rehypothecated: the idle collateral beyond the buffer is deposited into
+//! Homa protocol, and the staking yield (after a haircut) is shared between
+//! the borrowers and CDP treasury. The buffer is hard for liquidation:
+//! confiscation is always paid out of the idle collateral and never redeems
+//! from Homa protocol, withdrawals which would eat into the buffer redeem the
+//! shortfall from Homa protocol first.
+//!
+//! All the position adjustments are checked by the circuit breaker, which may
+//! suspend new debits and large withdrawals.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 #![allow(clippy::collapsible_if)]
 
 use frame_support::{log, pallet_prelude::*, transactional, PalletId};
+use frame_system::pallet_prelude::*;
 use orml_traits::{Happened, MultiCurrency, MultiCurrencyExtended};
-use primitives::{Amount, Balance, CurrencyId};
+use primitives::{Amount, Balance, CurrencyId, EraIndex};
 use sp_runtime::{
-	traits::{AccountIdConversion, Convert, Zero},
-	DispatchResult, RuntimeDebug,
+	traits::{AccountIdConversion, Convert, Saturating, Zero},
+	DispatchResult, FixedPointNumber, RuntimeDebug,
 };
-use sp_std::{convert::TryInto, result};
-use support::{CDPTreasury, RiskManager};
+use sp_std::{convert::TryInto, prelude::*, result};
+use support::{CDPTreasury, CircuitBreaker, GuardedOperation, HomaProtocol, Rate, Ratio, RiskManager};
 
 mod mock;
 mod tests;
@@ -51,6 +67,21 @@ pub struct Position {
 	pub debit: Balance,
 }
 
+/// The params of rehypothecating collateral into Homa protocol.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, Default)]
+pub struct RehypothecationParams {
+	/// The ratio of collateral kept idle in loans module as the buffer for
+	/// liquidation.
+	pub buffer_ratio: Ratio,
+	/// The haircut applied to the staking value of liquid currency when
+	/// harvesting staking yield, also the margin of over-redeeming from Homa
+	/// protocol to cover the redemption fee.
+	pub haircut: Ratio,
+	/// The share of staking yield credited to borrowers, the rest goes to CDP
+	/// treasury.
+	pub borrowers_share: Ratio,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -85,6 +116,28 @@ pub mod module {
 
 		/// Event handler which calls when update loan.
 		type OnUpdateLoan: Happened<(Self::AccountId, CurrencyId, Amount, Balance)>;
+
+		/// The maximum length of the label of position.
+		#[pallet::constant]
+		type MaxPositionLabelLength: Get<u32>;
+
+		/// The origin which may update rehypothecation params.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Homa protocol to rehypothecate collateral of staking currency.
+		type Homa: HomaProtocol<Self::AccountId, Balance, EraIndex>;
+
+		/// The staking currency id(should be DOT in acala), the only collateral
+		/// type can be rehypothecated.
+		#[pallet::constant]
+		type StakingCurrencyId: Get<CurrencyId>;
+
+		/// The liquid currency id(should be LDOT in acala)
+		#[pallet::constant]
+		type LiquidCurrencyId: Get<CurrencyId>;
+
+		/// Circuit breaker to suspend new debits and large withdrawals
+		type CircuitBreaker: CircuitBreaker<Self::BlockNumber>;
 	}
 
 	#[pallet::error]
@@ -94,6 +147,14 @@ pub mod module {
 		CollateralOverflow,
 		CollateralTooLow,
 		AmountConvertFailed,
+		PositionLabelTooLong,
+		NoPosition,
+		InvalidCollateralType,
+		InvalidRehypothecationParams,
+		RehypothecationNotEnabled,
+		RehypothecationNotSettled,
+		InsufficientIdleCollateral,
+		OperationSuspended,
 	}
 
 	#[pallet::event]
@@ -108,6 +169,20 @@ pub mod module {
 		ConfiscateCollateralAndDebit(T::AccountId, CurrencyId, Balance, Balance),
 		/// Transfer loan. \[from, to, currency_id\]
 		TransferLoan(T::AccountId, T::AccountId, CurrencyId),
+		/// Set the label of position. \[owner, collateral_type, label\]
+		PositionLabelSet(T::AccountId, CurrencyId, Vec<u8>),
+		/// The rehypothecation params of collateral type updated.
+		/// \[collateral_type, new_params\]
+		RehypothecationParamsUpdated(CurrencyId, Option<RehypothecationParams>),
+		/// Idle collateral deposited into Homa protocol. \[collateral_type,
+		/// staking_amount, liquid_amount\]
+		CollateralRehypothecated(CurrencyId, Balance, Balance),
+		/// Rehypothecated collateral redeemed from Homa protocol.
+		/// \[collateral_type, liquid_amount, staking_amount\]
+		RehypothecatedCollateralRedeemed(CurrencyId, Balance, Balance),
+		/// Staking yield of rehypothecated collateral harvested.
+		/// \[collateral_type, borrowers_yield, treasury_yield\]
+		RehypothecationYieldHarvested(CurrencyId, Balance, Balance),
 	}
 
 	/// The collateralized debit positions, map from
@@ -123,6 +198,40 @@ pub mod module {
 	#[pallet::getter(fn total_positions)]
 	pub type TotalPositions<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Position, ValueQuery>;
 
+	/// The labels of positions set by owners, map from
+	/// CollateralType -> Owner -> Label
+	#[pallet::storage]
+	#[pallet::getter(fn position_labels)]
+	pub type PositionLabels<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyId, Twox64Concat, T::AccountId, Vec<u8>, ValueQuery>;
+
+	/// The rehypothecation params of collateral types which opt-in, map from
+	/// CollateralType -> RehypothecationParams
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_rehypothecations)]
+	pub type CollateralRehypothecations<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, RehypothecationParams, OptionQuery>;
+
+	/// The staking amount of collateral deposited into Homa protocol,
+	/// including the harvested yield of borrowers, map from
+	/// CollateralType -> Balance
+	#[pallet::storage]
+	#[pallet::getter(fn rehypothecated_collaterals)]
+	pub type RehypothecatedCollaterals<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// The accumulated staking yield of borrowers per unit of collateral, map
+	/// from CollateralType -> Rate
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_yield_rates)]
+	pub type CollateralYieldRates<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Rate, ValueQuery>;
+
+	/// The accumulated yield rate when the yield was last credited to the
+	/// position, map from CollateralType -> Owner -> Rate
+	#[pallet::storage]
+	#[pallet::getter(fn position_yield_rates)]
+	pub type PositionYieldRates<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyId, Twox64Concat, T::AccountId, Rate, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -130,7 +239,56 @@ pub mod module {
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
 
 	#[pallet::call]
-	impl<T: Config> Pallet<T> {}
+	impl<T: Config> Pallet<T> {
+		/// Opt-in or update the rehypothecation params of collateral type,
+		/// `None` opts-out it after all rehypothecated collateral is redeemed.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_rehypothecation_params(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			params: Option<RehypothecationParams>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				currency_id == T::StakingCurrencyId::get(),
+				Error::<T>::InvalidCollateralType
+			);
+
+			if let Some(params) = params {
+				ensure!(
+					params.buffer_ratio <= Ratio::one()
+						&& params.haircut <= Ratio::one()
+						&& params.borrowers_share <= Ratio::one(),
+					Error::<T>::InvalidRehypothecationParams
+				);
+				CollateralRehypothecations::<T>::insert(currency_id, params);
+			} else {
+				ensure!(
+					Self::rehypothecated_collaterals(currency_id).is_zero(),
+					Error::<T>::RehypothecationNotSettled
+				);
+				CollateralRehypothecations::<T>::remove(currency_id);
+			}
+
+			Self::deposit_event(Event::RehypothecationParamsUpdated(currency_id, params));
+			Ok(().into())
+		}
+
+		/// Harvest the staking yield of rehypothecated collateral, and
+		/// rebalance the idle collateral to the buffer ratio.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn rebalance_rehypothecation(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::do_rebalance_rehypothecation(currency_id)?;
+			Ok(().into())
+		}
+	}
 }
 
 impl<T: Config> Pallet<T> {
@@ -152,7 +310,8 @@ impl<T: Config> Pallet<T> {
 		let collateral_adjustment = Self::amount_try_from_balance(collateral_confiscate)?;
 		let debit_adjustment = Self::amount_try_from_balance(debit_decrease)?;
 
-		// transfer collateral to cdp treasury
+		// transfer collateral to cdp treasury, which is paid out of the idle
+		// collateral only and never depends on Homa protocol
 		T::CDPTreasury::deposit_collateral(&Self::account_id(), currency_id, collateral_confiscate)?;
 
 		// deposit debit to cdp treasury
@@ -185,6 +344,20 @@ impl<T: Config> Pallet<T> {
 		currency_id: CurrencyId,
 		collateral_adjustment: Amount,
 		debit_adjustment: Amount,
+	) -> DispatchResult {
+		Self::adjust_position_unchecked(who, currency_id, collateral_adjustment, debit_adjustment)?;
+		Self::check_position(who, currency_id)
+	}
+
+	/// adjust the position without the risk check of it, the caller must
+	/// call `check_position` after all adjustments.
+	///
+	/// Not ensured atomic.
+	pub fn adjust_position_unchecked(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral_adjustment: Amount,
+		debit_adjustment: Amount,
 	) -> DispatchResult {
 		// mutate collateral and debit
 		Self::update_loan(who, currency_id, collateral_adjustment, debit_adjustment)?;
@@ -196,10 +369,20 @@ impl<T: Config> Pallet<T> {
 		if collateral_adjustment.is_positive() {
 			T::Currency::transfer(currency_id, who, &module_account, collateral_balance_adjustment)?;
 		} else if collateral_adjustment.is_negative() {
+			ensure!(
+				T::CircuitBreaker::try_allow(GuardedOperation::Withdrawal, currency_id, collateral_balance_adjustment),
+				Error::<T>::OperationSuspended
+			);
+			Self::ensure_withdrawable(currency_id, collateral_balance_adjustment)?;
 			T::Currency::transfer(currency_id, &module_account, who, collateral_balance_adjustment)?;
 		}
 
 		if debit_adjustment.is_positive() {
+			ensure!(
+				T::CircuitBreaker::try_allow(GuardedOperation::NewLoan, currency_id, debit_balance_adjustment),
+				Error::<T>::OperationSuspended
+			);
+
 			// check debit cap when increase debit
 			T::RiskManager::check_debit_cap(currency_id, Self::total_positions(currency_id).debit)?;
 
@@ -211,10 +394,6 @@ impl<T: Config> Pallet<T> {
 			T::CDPTreasury::burn_debit(who, T::Convert::convert((currency_id, debit_balance_adjustment)))?;
 		}
 
-		// ensure pass risk check
-		let Position { collateral, debit } = Self::positions(currency_id, who);
-		T::RiskManager::check_position_valid(currency_id, collateral, debit)?;
-
 		Self::deposit_event(Event::PositionUpdated(
 			who.clone(),
 			currency_id,
@@ -224,8 +403,24 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// ensure the position of `who` passes the risk check.
+	pub fn check_position(who: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+		let Position { collateral, debit } = Self::positions(currency_id, who);
+		T::RiskManager::check_position_valid(currency_id, collateral, debit)
+	}
+
+	/// Deduct the debit of `who` without burning stable currency, used to
+	/// waive the accrued interest of CDP.
+	pub fn waive_debit(who: &T::AccountId, currency_id: CurrencyId, debit_decrement: Balance) -> DispatchResult {
+		let debit_adjustment = Self::amount_try_from_balance(debit_decrement)?;
+		Self::update_loan(who, currency_id, Zero::zero(), debit_adjustment.saturating_neg())
+	}
+
 	/// transfer whole loan of `from` to `to`
 	pub fn transfer_loan(from: &T::AccountId, to: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+		// credit the pending yield before transferring whole loan
+		Self::settle_yield(from, currency_id);
+
 		// get `from` position data
 		let Position { collateral, debit } = Self::positions(currency_id, from);
 
@@ -259,6 +454,49 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Set the label of the position of `who` under `currency_id`, empty
+	/// label will remove the existing label.
+	pub fn set_position_label(who: &T::AccountId, currency_id: CurrencyId, label: Vec<u8>) -> DispatchResult {
+		ensure!(
+			label.len() <= T::MaxPositionLabelLength::get() as usize,
+			Error::<T>::PositionLabelTooLong
+		);
+		ensure!(<Positions<T>>::contains_key(currency_id, who), Error::<T>::NoPosition);
+
+		if label.is_empty() {
+			<PositionLabels<T>>::remove(currency_id, who);
+		} else {
+			<PositionLabels<T>>::insert(currency_id, who, &label);
+		}
+
+		Self::deposit_event(Event::PositionLabelSet(who.clone(), currency_id, label));
+		Ok(())
+	}
+
+	/// Get the positions of `who` under `currency_ids` with their labels,
+	/// skip the first `start` positions and return at most `limit`
+	/// positions.
+	pub fn positions_of(
+		who: &T::AccountId,
+		currency_ids: Vec<CurrencyId>,
+		start: u32,
+		limit: u32,
+	) -> Vec<(CurrencyId, Position, Vec<u8>)> {
+		currency_ids
+			.into_iter()
+			.filter(|currency_id| <Positions<T>>::contains_key(currency_id, who))
+			.skip(start as usize)
+			.take(limit as usize)
+			.map(|currency_id| {
+				(
+					currency_id,
+					Self::positions(currency_id, who),
+					Self::position_labels(currency_id, who),
+				)
+			})
+			.collect()
+	}
+
 	/// mutate records of collaterals and debits
 	fn update_loan(
 		who: &T::AccountId,
@@ -269,6 +507,8 @@ impl<T: Config> Pallet<T> {
 		let collateral_balance = Self::balance_try_from_amount_abs(collateral_adjustment)?;
 		let debit_balance = Self::balance_try_from_amount_abs(debit_adjustment)?;
 
+		Self::settle_yield(who, currency_id);
+
 		<Positions<T>>::try_mutate_exists(currency_id, who, |may_be_position| -> DispatchResult {
 			let mut p = may_be_position.take().unwrap_or_default();
 			let new_collateral = if collateral_adjustment.is_positive() {
@@ -308,8 +548,10 @@ impl<T: Config> Pallet<T> {
 				// decrease account ref if zero position
 				frame_system::Pallet::<T>::dec_consumers(who);
 
-				// remove position storage if zero position
+				// remove position storage and its label if zero position
 				*may_be_position = None;
+				<PositionLabels<T>>::remove(currency_id, who);
+				<PositionYieldRates<T>>::remove(currency_id, who);
 			} else {
 				*may_be_position = Some(p);
 			}
@@ -348,6 +590,203 @@ impl<T: Config> Pallet<T> {
 }
 
 impl<T: Config> Pallet<T> {
+	/// The sub account of module to keep the liquid currency minted by
+	/// rehypothecated collateral.
+	pub fn rehypothecation_account_id() -> T::AccountId {
+		T::PalletId::get().into_sub_account("rehypothecation")
+	}
+
+	/// The staking value of the liquid currency kept by rehypothecation after
+	/// the haircut.
+	pub fn rehypothecation_value(currency_id: CurrencyId) -> Balance {
+		let haircut = Self::collateral_rehypothecations(currency_id)
+			.map(|params| params.haircut)
+			.unwrap_or_default();
+		let liquid_amount = T::Currency::free_balance(T::LiquidCurrencyId::get(), &Self::rehypothecation_account_id());
+		let value = T::Homa::liquid_exchange_rate().saturating_mul_int(liquid_amount);
+		value.saturating_sub(haircut.saturating_mul_int(value))
+	}
+
+	/// Harvest the staking yield and keep the idle collateral at the buffer
+	/// ratio by depositing into or redeeming from Homa protocol.
+	pub fn do_rebalance_rehypothecation(currency_id: CurrencyId) -> DispatchResult {
+		let params = Self::collateral_rehypothecations(currency_id).ok_or(Error::<T>::RehypothecationNotEnabled)?;
+		Self::harvest_yield(currency_id, params)?;
+
+		let idle = T::Currency::free_balance(currency_id, &Self::account_id());
+		let target_idle = params
+			.buffer_ratio
+			.saturating_mul_int(idle.saturating_add(Self::rehypothecated_collaterals(currency_id)));
+
+		if idle > target_idle {
+			Self::deposit_to_homa(currency_id, idle.saturating_sub(target_idle))
+		} else {
+			Self::redeem_from_homa(currency_id, target_idle.saturating_sub(idle))
+		}
+	}
+
+	/// Credit the staking yield beyond the rehypothecated collateral to
+	/// borrowers by accumulating the yield rate, and transfer the rest liquid
+	/// currency to CDP treasury.
+	fn harvest_yield(currency_id: CurrencyId, params: RehypothecationParams) -> DispatchResult {
+		let staking_yield =
+			Self::rehypothecation_value(currency_id).saturating_sub(Self::rehypothecated_collaterals(currency_id));
+		if staking_yield.is_zero() {
+			return Ok(());
+		}
+
+		let total_collateral = Self::total_positions(currency_id).collateral;
+		let borrowers_yield = if total_collateral.is_zero() {
+			Zero::zero()
+		} else {
+			params.borrowers_share.saturating_mul_int(staking_yield)
+		};
+		let treasury_yield = staking_yield.saturating_sub(borrowers_yield);
+		let liquid_to_treasury = T::Homa::liquid_exchange_rate()
+			.reciprocal()
+			.unwrap_or_default()
+			.saturating_mul_int(treasury_yield);
+
+		if !liquid_to_treasury.is_zero() {
+			T::CDPTreasury::deposit_collateral(
+				&Self::rehypothecation_account_id(),
+				T::LiquidCurrencyId::get(),
+				liquid_to_treasury,
+			)?;
+		}
+		if !borrowers_yield.is_zero() {
+			RehypothecatedCollaterals::<T>::mutate(currency_id, |amount| {
+				*amount = amount.saturating_add(borrowers_yield);
+			});
+			CollateralYieldRates::<T>::mutate(currency_id, |rate| {
+				*rate = rate
+					.saturating_add(Rate::checked_from_rational(borrowers_yield, total_collateral).unwrap_or_default());
+			});
+		}
+
+		Self::deposit_event(Event::RehypothecationYieldHarvested(
+			currency_id,
+			borrowers_yield,
+			treasury_yield,
+		));
+		Ok(())
+	}
+
+	/// Deposit idle collateral into Homa protocol.
+	fn deposit_to_homa(currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		let rehypothecation_account = Self::rehypothecation_account_id();
+		T::Currency::transfer(currency_id, &Self::account_id(), &rehypothecation_account, amount)?;
+		let liquid_amount = T::Homa::mint(&rehypothecation_account, amount)?;
+		RehypothecatedCollaterals::<T>::mutate(currency_id, |rehypothecated| {
+			*rehypothecated = rehypothecated.saturating_add(amount);
+		});
+
+		Self::deposit_event(Event::CollateralRehypothecated(currency_id, amount, liquid_amount));
+		Ok(())
+	}
+
+	/// Redeem rehypothecated collateral from the free pool of Homa protocol
+	/// instantly. Only the staking amount actually received is deducted from
+	/// the rehypothecated collateral, the redemption fee is covered by the
+	/// staking yield.
+	fn redeem_from_homa(currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+		let amount = amount.min(Self::rehypothecated_collaterals(currency_id));
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		let rehypothecation_account = Self::rehypothecation_account_id();
+		let liquid_before = T::Currency::free_balance(T::LiquidCurrencyId::get(), &rehypothecation_account);
+		let staking_before = T::Currency::free_balance(currency_id, &rehypothecation_account);
+		let liquid_amount = T::Homa::liquid_exchange_rate()
+			.reciprocal()
+			.unwrap_or_default()
+			.saturating_mul_int(amount)
+			.min(liquid_before);
+
+		T::Homa::redeem_by_free_unbonded(&rehypothecation_account, liquid_amount)?;
+
+		let liquid_redeemed = liquid_before.saturating_sub(T::Currency::free_balance(
+			T::LiquidCurrencyId::get(),
+			&rehypothecation_account,
+		));
+		let staking_received =
+			T::Currency::free_balance(currency_id, &rehypothecation_account).saturating_sub(staking_before);
+		T::Currency::transfer(
+			currency_id,
+			&rehypothecation_account,
+			&Self::account_id(),
+			staking_received,
+		)?;
+		RehypothecatedCollaterals::<T>::mutate(currency_id, |rehypothecated| {
+			*rehypothecated = rehypothecated.saturating_sub(staking_received);
+		});
+
+		Self::deposit_event(Event::RehypothecatedCollateralRedeemed(
+			currency_id,
+			liquid_redeemed,
+			staking_received,
+		));
+		Ok(())
+	}
+
+	/// Redeem rehypothecated collateral if paying out `amount` would eat into
+	/// the idle buffer. The shortfall is over-redeemed by the haircut to cover
+	/// the redemption fee charged by Homa protocol.
+	fn ensure_withdrawable(currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+		if Self::rehypothecated_collaterals(currency_id).is_zero() {
+			return Ok(());
+		}
+
+		let params = Self::collateral_rehypothecations(currency_id).unwrap_or_default();
+		let buffer = params
+			.buffer_ratio
+			.saturating_mul_int(Self::total_positions(currency_id).collateral);
+		let idle = T::Currency::free_balance(currency_id, &Self::account_id());
+		let required = amount.saturating_add(buffer);
+		if idle < required {
+			let shortfall = required.saturating_sub(idle);
+			let redeem_amount = Ratio::one()
+				.saturating_sub(params.haircut)
+				.reciprocal()
+				.map(|rate| rate.saturating_mul_int(shortfall))
+				.unwrap_or_else(|| Self::rehypothecated_collaterals(currency_id));
+			Self::redeem_from_homa(currency_id, redeem_amount)?;
+		}
+
+		ensure!(
+			T::Currency::free_balance(currency_id, &Self::account_id()) >= amount,
+			Error::<T>::InsufficientIdleCollateral
+		);
+		Ok(())
+	}
+
+	/// Credit the pending staking yield of rehypothecated collateral to the
+	/// position of `who`.
+	fn settle_yield(who: &T::AccountId, currency_id: CurrencyId) {
+		let yield_rate = Self::collateral_yield_rates(currency_id);
+		if yield_rate.is_zero() {
+			return;
+		}
+
+		let pending_yield = yield_rate
+			.saturating_sub(Self::position_yield_rates(currency_id, who))
+			.saturating_mul_int(Self::positions(currency_id, who).collateral);
+		if !pending_yield.is_zero() {
+			<Positions<T>>::mutate(currency_id, who, |position| {
+				position.collateral = position.collateral.saturating_add(pending_yield);
+			});
+			TotalPositions::<T>::mutate(currency_id, |total_positions| {
+				total_positions.collateral = total_positions.collateral.saturating_add(pending_yield);
+			});
+		}
+		<PositionYieldRates<T>>::insert(currency_id, who, yield_rate);
+	}
+
 	/// Convert `Balance` to `Amount`.
 	fn amount_try_from_balance(b: Balance) -> result::Result<Amount, Error<T>> {
 		TryInto::<Amount>::try_into(b).map_err(|_| Error::<T>::AmountConvertFailed)
@@ -29,8 +29,10 @@ use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{AccountIdConversion, IdentityLookup},
+	DispatchError, FixedPointNumber,
 };
-use support::{AuctionManager, RiskManager};
+use sp_std::cell::RefCell;
+use support::{AuctionManager, CircuitSignal, ExchangeRate, Price, PriceProvider, RiskManager, TriggerTier};
 
 pub type AccountId = u128;
 pub type AuctionId = u32;
@@ -41,6 +43,7 @@ pub const BOB: AccountId = 2;
 pub const ACA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+pub const LDOT: CurrencyId = CurrencyId::Token(TokenSymbol::LDOT);
 pub const BTC: CurrencyId = CurrencyId::Token(TokenSymbol::XBTC);
 
 mod loans {
@@ -159,6 +162,27 @@ parameter_types! {
 	pub TreasuryAccount: AccountId = PalletId(*b"aca/hztr").into_account();
 }
 
+pub struct MockPriceSource;
+impl PriceProvider<CurrencyId> for MockPriceSource {
+	fn get_relative_price(_base: CurrencyId, _quote: CurrencyId) -> Option<Price> {
+		None
+	}
+
+	fn get_price(_currency_id: CurrencyId) -> Option<Price> {
+		None
+	}
+
+	fn lock_price(_currency_id: CurrencyId) {}
+
+	fn unlock_price(_currency_id: CurrencyId) {}
+}
+
+parameter_types! {
+	pub const SurplusRebalancePeriod: BlockNumber = 10;
+	pub const MaxSurplusAllocations: u32 = 5;
+	pub MaxSurplusSwapSlippage: Ratio = Ratio::saturating_from_rational(5, 100);
+}
+
 impl cdp_treasury::Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
@@ -167,6 +191,10 @@ impl cdp_treasury::Config for Runtime {
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
 	type DEX = ();
 	type MaxAuctionsCount = MaxAuctionsCount;
+	type PriceSource = MockPriceSource;
+	type SurplusRebalancePeriod = SurplusRebalancePeriod;
+	type MaxSurplusAllocations = MaxSurplusAllocations;
+	type MaxSurplusSwapSlippage = MaxSurplusSwapSlippage;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
 	type WeightInfo = ();
@@ -208,8 +236,128 @@ impl RiskManager<AccountId, CurrencyId, Balance, Balance> for MockRiskManager {
 	}
 }
 
+thread_local! {
+	static LIQUID_EXCHANGE_RATE: RefCell<ExchangeRate> = RefCell::new(ExchangeRate::one());
+	static REDEEM_FEE_RATE: RefCell<Rate> = RefCell::new(Rate::zero());
+	static REBALANCE_UNFINISHED: RefCell<bool> = RefCell::new(false);
+	static TRIPPED_OPERATIONS: RefCell<Vec<GuardedOperation>> = RefCell::new(vec![]);
+}
+
+pub fn mock_trip(operation: GuardedOperation) {
+	TRIPPED_OPERATIONS.with(|v| v.borrow_mut().push(operation))
+}
+
+/// Suspends the tripped operations entirely.
+pub struct MockCircuitBreaker;
+impl CircuitBreaker<BlockNumber> for MockCircuitBreaker {
+	fn register_trigger(_signal: CircuitSignal, _tier: TriggerTier<BlockNumber>) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn report(_signal: CircuitSignal, _magnitude: Ratio) {}
+
+	fn try_allow(operation: GuardedOperation, _currency_id: CurrencyId, _amount: Balance) -> bool {
+		!TRIPPED_OPERATIONS.with(|v| v.borrow().contains(&operation))
+	}
+}
+
+pub fn set_liquid_exchange_rate(rate: ExchangeRate) {
+	LIQUID_EXCHANGE_RATE.with(|v| *v.borrow_mut() = rate);
+}
+
+pub fn set_redeem_fee_rate(rate: Rate) {
+	REDEEM_FEE_RATE.with(|v| *v.borrow_mut() = rate);
+}
+
+pub fn set_rebalance_unfinished(unfinished: bool) {
+	REBALANCE_UNFINISHED.with(|v| *v.borrow_mut() = unfinished);
+}
+
+fn ensure_rebalance_finished() -> DispatchResult {
+	if REBALANCE_UNFINISHED.with(|v| *v.borrow()) {
+		Err(DispatchError::Other("RebalanceUnfinished"))
+	} else {
+		Ok(())
+	}
+}
+
+/// Mints and redeems by free unbonded at `LIQUID_EXCHANGE_RATE`, redeeming
+/// charges `REDEEM_FEE_RATE`, and both fail when `REBALANCE_UNFINISHED`. Other
+/// operations are not supported.
+pub struct MockHoma;
+impl HomaProtocol<AccountId, Balance, EraIndex> for MockHoma {
+	type Balance = Balance;
+
+	fn mint(who: &AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		ensure_rebalance_finished()?;
+		let liquid_amount = Self::liquid_exchange_rate()
+			.reciprocal()
+			.unwrap_or_default()
+			.saturating_mul_int(amount);
+		Currencies::withdraw(DOT, who, amount)?;
+		Currencies::deposit(LDOT, who, liquid_amount)?;
+		Ok(liquid_amount)
+	}
+
+	fn redeem_by_unbond(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn redeem_by_free_unbonded(who: &AccountId, amount: Balance) -> DispatchResult {
+		ensure_rebalance_finished()?;
+		let staking_amount = Self::liquid_exchange_rate().saturating_mul_int(amount);
+		let fee = REDEEM_FEE_RATE.with(|v| *v.borrow()).saturating_mul_int(staking_amount);
+		Currencies::withdraw(LDOT, who, amount)?;
+		Currencies::deposit(DOT, who, staking_amount.saturating_sub(fee))
+	}
+
+	fn redeem_by_claim_unbonding(_who: &AccountId, _amount: Balance, _target_era: EraIndex) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn withdraw_redemption(_who: &AccountId) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn request_redeem(_who: &AccountId, _amount: Balance, _allow_fast_match: bool) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn cancel_redeem_request(_who: &AccountId, _amount: Balance) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn set_redeem_request_fast_match(_who: &AccountId, _allow_fast_match: bool) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn transfer_unbonding(
+		_from: &AccountId,
+		_to: &AccountId,
+		_target_era: EraIndex,
+		_amount: Balance,
+	) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn liquid_exchange_rate() -> ExchangeRate {
+		LIQUID_EXCHANGE_RATE.with(|v| *v.borrow())
+	}
+
+	fn current_era() -> EraIndex {
+		Default::default()
+	}
+
+	fn total_staking_amount() -> Balance {
+		Default::default()
+	}
+}
+
 parameter_types! {
 	pub const LoansPalletId: PalletId = PalletId(*b"aca/loan");
+	pub const MaxPositionLabelLength: u32 = 32;
+	pub const GetStakingCurrencyId: CurrencyId = DOT;
+	pub const GetLiquidCurrencyId: CurrencyId = LDOT;
 }
 
 impl Config for Runtime {
@@ -220,6 +368,12 @@ impl Config for Runtime {
 	type CDPTreasury = CDPTreasuryModule;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = ();
+	type MaxPositionLabelLength = MaxPositionLabelLength;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type Homa = MockHoma;
+	type StakingCurrencyId = GetStakingCurrencyId;
+	type LiquidCurrencyId = GetLiquidCurrencyId;
+	type CircuitBreaker = MockCircuitBreaker;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -23,6 +23,8 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{Event, *};
+use sp_runtime::{traits::BadOrigin, DispatchError};
+use support::ExchangeRate;
 
 #[test]
 fn debits_key() {
@@ -92,6 +94,30 @@ fn adjust_position_should_work() {
 	});
 }
 
+#[test]
+fn adjust_position_suspended_by_circuit_breaker() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(LoansModule::adjust_position(&ALICE, BTC, 500, 300));
+
+		mock_trip(GuardedOperation::NewLoan);
+		assert_noop!(
+			LoansModule::adjust_position(&ALICE, BTC, 0, 10),
+			Error::<Runtime>::OperationSuspended
+		);
+		assert_ok!(LoansModule::adjust_position(&ALICE, BTC, 100, -10));
+		assert_ok!(LoansModule::adjust_position(&ALICE, BTC, -100, 0));
+
+		mock_trip(GuardedOperation::Withdrawal);
+		assert_noop!(
+			LoansModule::adjust_position(&ALICE, BTC, -10, 0),
+			Error::<Runtime>::OperationSuspended
+		);
+		assert_ok!(LoansModule::adjust_position(&ALICE, BTC, 10, -10));
+		assert_eq!(LoansModule::positions(BTC, &ALICE).collateral, 510);
+		assert_eq!(LoansModule::positions(BTC, &ALICE).debit, 280);
+	});
+}
+
 #[test]
 fn update_loan_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -187,3 +213,279 @@ fn confiscate_collateral_and_debit_work() {
 		assert!(System::events().iter().any(|record| record.event == confiscate_event));
 	});
 }
+
+#[test]
+fn set_position_label_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			LoansModule::set_position_label(&ALICE, BTC, b"long".to_vec()),
+			Error::<Runtime>::NoPosition,
+		);
+
+		assert_ok!(LoansModule::adjust_position(&ALICE, BTC, 500, 300));
+		assert_noop!(
+			LoansModule::set_position_label(&ALICE, BTC, vec![0u8; 33]),
+			Error::<Runtime>::PositionLabelTooLong,
+		);
+		assert_ok!(LoansModule::set_position_label(&ALICE, BTC, b"long".to_vec()));
+		assert_eq!(LoansModule::position_labels(BTC, &ALICE), b"long".to_vec());
+		let label_event = Event::loans(crate::Event::PositionLabelSet(ALICE, BTC, b"long".to_vec()));
+		assert!(System::events().iter().any(|record| record.event == label_event));
+
+		// empty label removes the label
+		assert_ok!(LoansModule::set_position_label(&ALICE, BTC, vec![]));
+		assert_eq!(PositionLabels::<Runtime>::contains_key(BTC, &ALICE), false);
+
+		// the label is removed along with the position
+		assert_ok!(LoansModule::set_position_label(&ALICE, BTC, b"long".to_vec()));
+		assert_ok!(LoansModule::adjust_position(&ALICE, BTC, -500, -300));
+		assert_eq!(Positions::<Runtime>::contains_key(BTC, &ALICE), false);
+		assert_eq!(PositionLabels::<Runtime>::contains_key(BTC, &ALICE), false);
+	});
+}
+
+#[test]
+fn positions_of_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(LoansModule::update_loan(&ALICE, DOT, 100, 50));
+		assert_ok!(LoansModule::update_loan(&ALICE, BTC, 200, 100));
+		assert_ok!(LoansModule::update_loan(&BOB, BTC, 300, 150));
+		assert_ok!(LoansModule::set_position_label(&ALICE, BTC, b"long".to_vec()));
+
+		assert_eq!(
+			LoansModule::positions_of(&ALICE, vec![ACA, DOT, BTC], 0, 10),
+			vec![
+				(
+					DOT,
+					Position {
+						collateral: 100,
+						debit: 50
+					},
+					vec![]
+				),
+				(
+					BTC,
+					Position {
+						collateral: 200,
+						debit: 100
+					},
+					b"long".to_vec()
+				),
+			]
+		);
+		assert_eq!(
+			LoansModule::positions_of(&ALICE, vec![ACA, DOT, BTC], 1, 1),
+			vec![(
+				BTC,
+				Position {
+					collateral: 200,
+					debit: 100
+				},
+				b"long".to_vec()
+			)]
+		);
+		assert_eq!(LoansModule::positions_of(&ALICE, vec![ACA, DOT, BTC], 2, 10), vec![]);
+		assert_eq!(LoansModule::positions_of(&BOB, vec![ACA, DOT, BTC], 0, 0), vec![]);
+	});
+}
+
+#[test]
+fn set_rehypothecation_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let params = RehypothecationParams {
+			buffer_ratio: Ratio::saturating_from_rational(20, 100),
+			haircut: Ratio::saturating_from_rational(10, 100),
+			borrowers_share: Ratio::saturating_from_rational(50, 100),
+		};
+		assert_noop!(
+			LoansModule::set_rehypothecation_params(Origin::signed(BOB), DOT, Some(params)),
+			BadOrigin
+		);
+		assert_noop!(
+			LoansModule::set_rehypothecation_params(Origin::signed(ALICE), BTC, Some(params)),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_noop!(
+			LoansModule::set_rehypothecation_params(
+				Origin::signed(ALICE),
+				DOT,
+				Some(RehypothecationParams {
+					haircut: Ratio::saturating_from_rational(110, 100),
+					..params
+				})
+			),
+			Error::<Runtime>::InvalidRehypothecationParams
+		);
+
+		assert_ok!(LoansModule::set_rehypothecation_params(
+			Origin::signed(ALICE),
+			DOT,
+			Some(params)
+		));
+		assert_eq!(LoansModule::collateral_rehypothecations(DOT), Some(params));
+		let params_event = Event::loans(crate::Event::RehypothecationParamsUpdated(DOT, Some(params)));
+		assert!(System::events().iter().any(|record| record.event == params_event));
+
+		// can not opt-out before all rehypothecated collateral is redeemed
+		assert_ok!(LoansModule::adjust_position_unchecked(&ALICE, DOT, 500, 0));
+		assert_ok!(LoansModule::do_rebalance_rehypothecation(DOT));
+		assert_noop!(
+			LoansModule::set_rehypothecation_params(Origin::signed(ALICE), DOT, None),
+			Error::<Runtime>::RehypothecationNotSettled
+		);
+
+		assert_ok!(LoansModule::set_rehypothecation_params(
+			Origin::signed(ALICE),
+			DOT,
+			Some(RehypothecationParams {
+				buffer_ratio: Ratio::one(),
+				..params
+			})
+		));
+		assert_ok!(LoansModule::do_rebalance_rehypothecation(DOT));
+		assert_eq!(LoansModule::rehypothecated_collaterals(DOT), 0);
+		assert_ok!(LoansModule::set_rehypothecation_params(
+			Origin::signed(ALICE),
+			DOT,
+			None
+		));
+		assert_eq!(LoansModule::collateral_rehypothecations(DOT), None);
+	});
+}
+
+#[test]
+fn rehypothecation_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(LoansModule::adjust_position_unchecked(&ALICE, DOT, 500, 0));
+		assert_noop!(
+			LoansModule::rebalance_rehypothecation(Origin::signed(BOB), DOT),
+			BadOrigin
+		);
+		assert_noop!(
+			LoansModule::rebalance_rehypothecation(Origin::signed(ALICE), DOT),
+			Error::<Runtime>::RehypothecationNotEnabled
+		);
+		CollateralRehypothecations::<Runtime>::insert(
+			DOT,
+			RehypothecationParams {
+				buffer_ratio: Ratio::saturating_from_rational(20, 100),
+				haircut: Ratio::saturating_from_rational(10, 100),
+				borrowers_share: Ratio::saturating_from_rational(50, 100),
+			},
+		);
+
+		// deposit idle collateral beyond the buffer into homa
+		assert_ok!(LoansModule::rebalance_rehypothecation(Origin::signed(ALICE), DOT));
+		assert_eq!(Currencies::free_balance(DOT, &LoansModule::account_id()), 100);
+		assert_eq!(
+			Currencies::free_balance(LDOT, &LoansModule::rehypothecation_account_id()),
+			400
+		);
+		assert_eq!(LoansModule::rehypothecated_collaterals(DOT), 400);
+		let rehypothecated_event = Event::loans(crate::Event::CollateralRehypothecated(DOT, 400, 400));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == rehypothecated_event));
+
+		// harvest the yield after haircut: 400 * 2 * 90% - 400 = 320
+		set_liquid_exchange_rate(ExchangeRate::saturating_from_integer(2));
+		assert_ok!(LoansModule::rebalance_rehypothecation(Origin::signed(ALICE), DOT));
+		let harvested_event = Event::loans(crate::Event::RehypothecationYieldHarvested(DOT, 160, 160));
+		assert!(System::events().iter().any(|record| record.event == harvested_event));
+		assert_eq!(Currencies::free_balance(LDOT, &CDPTreasuryModule::account_id()), 80);
+		assert_eq!(
+			LoansModule::collateral_yield_rates(DOT),
+			Rate::saturating_from_rational(32, 100)
+		);
+
+		// redeem to refill the buffer: (100 + 560) * 20% - 100 = 32
+		assert_eq!(Currencies::free_balance(DOT, &LoansModule::account_id()), 132);
+		assert_eq!(
+			Currencies::free_balance(LDOT, &LoansModule::rehypothecation_account_id()),
+			304
+		);
+		assert_eq!(LoansModule::rehypothecated_collaterals(DOT), 528);
+		let redeemed_event = Event::loans(crate::Event::RehypothecatedCollateralRedeemed(DOT, 16, 32));
+		assert!(System::events().iter().any(|record| record.event == redeemed_event));
+
+		// the yield is credited to position, and withdraw eating into the buffer
+		// over-redeems the shortfall from homa instantly:
+		// (300 + 360 * 20% - 132) / 90% = 266
+		assert_ok!(LoansModule::adjust_position_unchecked(&ALICE, DOT, -300, 0));
+		assert_eq!(LoansModule::positions(DOT, &ALICE).collateral, 360);
+		assert_eq!(LoansModule::total_positions(DOT).collateral, 360);
+		assert_eq!(
+			LoansModule::position_yield_rates(DOT, &ALICE),
+			Rate::saturating_from_rational(32, 100)
+		);
+		assert_eq!(Currencies::free_balance(DOT, &ALICE), 800);
+		assert_eq!(Currencies::free_balance(DOT, &LoansModule::account_id()), 98);
+		assert_eq!(LoansModule::rehypothecated_collaterals(DOT), 262);
+	});
+}
+
+#[test]
+fn withdraw_over_redeem_to_cover_redemption_fee() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(LoansModule::adjust_position_unchecked(&ALICE, DOT, 500, 0));
+		CollateralRehypothecations::<Runtime>::insert(
+			DOT,
+			RehypothecationParams {
+				buffer_ratio: Ratio::saturating_from_rational(20, 100),
+				haircut: Ratio::saturating_from_rational(10, 100),
+				borrowers_share: Ratio::saturating_from_rational(50, 100),
+			},
+		);
+		assert_ok!(LoansModule::do_rebalance_rehypothecation(DOT));
+		assert_eq!(Currencies::free_balance(DOT, &LoansModule::account_id()), 100);
+		assert_eq!(LoansModule::rehypothecated_collaterals(DOT), 400);
+
+		// 5% redemption fee, shortfall: 150 + 350 * 20% - 100 = 120,
+		// redeem 120 / 90% = 133 and receive 133 * 95% = 127
+		set_redeem_fee_rate(Rate::saturating_from_rational(5, 100));
+		assert_ok!(LoansModule::adjust_position_unchecked(&ALICE, DOT, -150, 0));
+		assert_eq!(Currencies::free_balance(DOT, &ALICE), 650);
+		assert_eq!(Currencies::free_balance(DOT, &LoansModule::account_id()), 77);
+		assert_eq!(
+			Currencies::free_balance(LDOT, &LoansModule::rehypothecation_account_id()),
+			267
+		);
+		assert_eq!(LoansModule::rehypothecated_collaterals(DOT), 273);
+
+		// withdraw fails if homa can not redeem
+		set_rebalance_unfinished(true);
+		assert_noop!(
+			LoansModule::adjust_position(&ALICE, DOT, -50, 0),
+			DispatchError::Other("RebalanceUnfinished")
+		);
+	});
+}
+
+#[test]
+fn confiscate_never_redeems_from_homa() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(LoansModule::adjust_position_unchecked(&ALICE, DOT, 500, 0));
+		assert_ok!(LoansModule::adjust_position_unchecked(&BOB, DOT, 50, 0));
+		CollateralRehypothecations::<Runtime>::insert(
+			DOT,
+			RehypothecationParams {
+				buffer_ratio: Ratio::saturating_from_rational(20, 100),
+				haircut: Ratio::saturating_from_rational(10, 100),
+				borrowers_share: Ratio::saturating_from_rational(50, 100),
+			},
+		);
+		assert_ok!(LoansModule::do_rebalance_rehypothecation(DOT));
+		assert_eq!(Currencies::free_balance(DOT, &LoansModule::account_id()), 110);
+		assert_eq!(LoansModule::rehypothecated_collaterals(DOT), 440);
+
+		// liquidation is paid out of the idle buffer even if homa is unavailable
+		set_rebalance_unfinished(true);
+		assert_ok!(LoansModule::confiscate_collateral_and_debit(&BOB, DOT, 50, 0));
+		assert_eq!(Currencies::free_balance(DOT, &CDPTreasuryModule::account_id()), 50);
+		assert_eq!(Currencies::free_balance(DOT, &LoansModule::account_id()), 60);
+		assert_eq!(LoansModule::rehypothecated_collaterals(DOT), 440);
+	});
+}
@@ -29,7 +29,8 @@ use frame_system::RawOrigin;
 use sp_runtime::traits::{AccountIdConversion, StaticLookup, UniqueSaturatedInto};
 
 pub use crate::*;
-use primitives::Balance;
+use orml_traits::MultiCurrency;
+use primitives::{Balance, CurrencyId, TokenSymbol};
 
 pub struct Module<T: Config>(crate::Pallet<T>);
 
@@ -65,6 +66,38 @@ benchmarks! {
 		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
 	}: _(RawOrigin::Signed(module_account), to_lookup, 0u32.into(), vec![1], i)
 
+	// mint locked NFT tokens
+	mint_with_lock {
+		let i in 1 .. 1000;
+
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+	}: _(RawOrigin::Signed(module_account), to_lookup, 0u32.into(), vec![1], i, 100u32.into())
+
+	// mint a batch of NFT tokens
+	mint_batch {
+		let i in 1 .. T::MaxBatch::get();
+
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+	}: _(RawOrigin::Signed(module_account), to_lookup, 0u32.into(), vec![1], i)
+
 	// transfer NFT token to another account
 	transfer {
 		let caller: T::AccountId = account("caller", 0, SEED);
@@ -81,6 +114,25 @@ benchmarks! {
 		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![1], 1)?;
 	}: _(RawOrigin::Signed(to), caller_lookup, (0u32.into(), 0u32.into()))
 
+	// transfer a batch of NFT tokens to another account
+	transfer_batch {
+		let i in 1 .. T::MaxBatch::get();
+
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![1], i)?;
+		let tokens = (0..i).map(|token_id| (0u32.into(), token_id.into())).collect::<Vec<_>>();
+	}: _(RawOrigin::Signed(to), caller_lookup, tokens)
+
 	// burn NFT token
 	burn {
 		let caller: T::AccountId = account("caller", 0, SEED);
@@ -96,6 +148,21 @@ benchmarks! {
 		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![1], 1)?;
 	}: _(RawOrigin::Signed(to), (0u32.into(), 0u32.into()))
 
+	// update NFT token metadata
+	update_token_metadata {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Mutable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account.clone()).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+	}: _(RawOrigin::Signed(module_account), (0u32.into(), 0u32.into()), vec![1; 256])
+
 	// destroy NFT class
 	destroy_class {
 		let caller: T::AccountId = account("caller", 0, SEED);
@@ -108,6 +175,183 @@ benchmarks! {
 		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
 		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
 	}: _(RawOrigin::Signed(module_account), 0u32.into(), caller_lookup)
+
+	// destroy NFT class with sweeping the tokens owned by class owner
+	destroy_class_with_sweep {
+		let i in 1 .. T::MaxSweepBatch::get();
+
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		let module_account_lookup = T::Lookup::unlookup(module_account.clone());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account.clone()).into(), module_account_lookup, 0u32.into(), vec![1], i)?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), caller_lookup)
+
+	// transfer the ownership of NFT class
+	transfer_class {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), caller_lookup, to_lookup)
+
+	// set roles of NFT class
+	set_class_roles {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let minter: T::AccountId = account("minter", 0, SEED);
+		let freezer: T::AccountId = account("freezer", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), ClassRoles { minter: Some(minter), freezer: Some(freezer) })
+
+	// set max token count of NFT class
+	set_max_token_count {
+		let caller: T::AccountId = account("caller", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), 100)
+
+	// set royalty of NFT class
+	set_royalty {
+		let caller: T::AccountId = account("caller", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller.clone()).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+	}: _(RawOrigin::Signed(module_account), 0u32.into(), Some((caller, Permill::from_percent(5))))
+
+	// set properties of NFT token
+	set_token_properties {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account.clone()).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+	}: _(RawOrigin::Signed(module_account), (0u32.into(), 0u32.into()), Some(Properties(ClassProperty::Burnable.into())))
+
+	// list NFT token for sale
+	list_token {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+	}: _(RawOrigin::Signed(to), (0u32.into(), 0u32.into()), dollar(10), CurrencyId::Token(TokenSymbol::AUSD))
+
+	// unlist NFT token
+	unlist_token {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+		crate::Pallet::<T>::list_token(RawOrigin::Signed(to.clone()).into(), (0u32.into(), 0u32.into()), dollar(10), CurrencyId::Token(TokenSymbol::AUSD))?;
+	}: _(RawOrigin::Signed(to), (0u32.into(), 0u32.into()))
+
+	// buy listed NFT token, with royalty paid
+	buy_token {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+		let buyer: T::AccountId = account("buyer", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+		T::Currency::make_free_balance_be(&buyer, base_currency_amount.unique_saturated_into());
+		T::MultiCurrency::deposit(CurrencyId::Token(TokenSymbol::AUSD), &buyer, dollar(1000))?;
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller.clone()).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::set_royalty(RawOrigin::Signed(module_account.clone()).into(), 0u32.into(), Some((caller, Permill::from_percent(5))))?;
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+		crate::Pallet::<T>::list_token(RawOrigin::Signed(to).into(), (0u32.into(), 0u32.into()), dollar(10), CurrencyId::Token(TokenSymbol::AUSD))?;
+	}: _(RawOrigin::Signed(buyer), (0u32.into(), 0u32.into()))
+
+	// transfer NFT token on behalf of the owner by the approved account
+	transfer_from {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let caller_lookup = T::Lookup::unlookup(caller.clone());
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+		let spender: T::AccountId = account("spender", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup.clone(), 0u32.into(), vec![1], 1)?;
+		crate::Pallet::<T>::approve(RawOrigin::Signed(to).into(), Some(T::Lookup::unlookup(spender.clone())), (0u32.into(), 0u32.into()))?;
+	}: _(RawOrigin::Signed(spender), to_lookup, caller_lookup, (0u32.into(), 0u32.into()))
+
+	// approve an account to transfer NFT token
+	approve {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let to: T::AccountId = account("to", 0, SEED);
+		let to_lookup = T::Lookup::unlookup(to.clone());
+		let spender: T::AccountId = account("spender", 0, SEED);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+
+		let module_account: T::AccountId = T::PalletId::get().into_sub_account(orml_nft::Pallet::<T>::next_class_id());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+		T::Currency::make_free_balance_be(&module_account, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::mint(RawOrigin::Signed(module_account).into(), to_lookup, 0u32.into(), vec![1], 1)?;
+	}: _(RawOrigin::Signed(to), Some(T::Lookup::unlookup(spender)), (0u32.into(), 0u32.into()))
+
+	// approve an operator for all NFT tokens of class
+	set_approval_for_all {
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let operator: T::AccountId = account("operator", 0, SEED);
+		let operator_lookup = T::Lookup::unlookup(operator);
+
+		let base_currency_amount = dollar(1000);
+		T::Currency::make_free_balance_be(&caller, base_currency_amount.unique_saturated_into());
+		crate::Pallet::<T>::create_class(RawOrigin::Signed(caller.clone()).into(), vec![1], Properties(ClassProperty::Transferable | ClassProperty::Burnable))?;
+	}: _(RawOrigin::Signed(caller), 0u32.into(), operator_lookup, true)
 }
 
 #[cfg(test)]
@@ -122,6 +366,8 @@ mod mock {
 		weights::Weight,
 		PalletId, RuntimeDebug,
 	};
+	use orml_traits::parameter_type_with_key;
+	use primitives::{Amount, BlockNumber, CurrencyId, TokenSymbol};
 	use sp_core::{crypto::AccountId32, H256};
 	use sp_runtime::{
 		testing::Header,
@@ -237,16 +483,55 @@ mod mock {
 		type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	}
 
+	pub type NativeCurrency = module_currencies::BasicCurrencyAdapter<Runtime, Balances, Amount, BlockNumber>;
+
+	parameter_type_with_key! {
+		pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
+			Default::default()
+		};
+	}
+
+	impl orml_tokens::Config for Runtime {
+		type Event = ();
+		type Balance = Balance;
+		type Amount = Amount;
+		type CurrencyId = CurrencyId;
+		type WeightInfo = ();
+		type ExistentialDeposits = ExistentialDeposits;
+		type OnDust = ();
+	}
+
+	parameter_types! {
+		pub const GetNativeCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
+	}
+
+	impl module_currencies::Config for Runtime {
+		type Event = ();
+		type MultiCurrency = Tokens;
+		type NativeCurrency = NativeCurrency;
+		type GetNativeCurrencyId = GetNativeCurrencyId;
+		type WeightInfo = ();
+		type AddressMapping = support::mocks::MockAddressMapping;
+		type EVMBridge = ();
+	}
+
 	parameter_types! {
 		pub const CreateClassDeposit: Balance = 200;
 		pub const CreateTokenDeposit: Balance = 100;
+		pub const DataDepositPerByte: Balance = 1;
 		pub const NftPalletId: PalletId = PalletId(*b"aca/aNFT");
+		pub const MaxSweepBatch: u32 = 10;
+		pub const MaxBatch: u32 = 10;
 	}
 	impl crate::Config for Runtime {
 		type Event = ();
 		type CreateClassDeposit = CreateClassDeposit;
 		type CreateTokenDeposit = CreateTokenDeposit;
+		type DataDepositPerByte = DataDepositPerByte;
 		type PalletId = NftPalletId;
+		type MaxSweepBatch = MaxSweepBatch;
+		type MaxBatch = MaxBatch;
+		type MultiCurrency = Currencies;
 		type WeightInfo = ();
 	}
 
@@ -272,6 +557,8 @@ mod mock {
 			Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>},
 			OrmlNFT: orml_nft::{Pallet, Storage, Config<T>},
 			NFT: nft::{Pallet, Call, Event<T>},
+			Tokens: orml_tokens::{Pallet, Storage, Config<T>},
+			Currencies: module_currencies::{Pallet, Call},
 		}
 	);
 
@@ -308,6 +595,20 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_mint_with_lock() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_mint_with_lock::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_mint_batch() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_mint_batch::<Runtime>());
+		});
+	}
+
 	#[test]
 	fn test_transfer() {
 		new_test_ext().execute_with(|| {
@@ -315,6 +616,13 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_transfer_batch() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_transfer_batch::<Runtime>());
+		});
+	}
+
 	#[test]
 	fn test_burn() {
 		new_test_ext().execute_with(|| {
@@ -322,10 +630,101 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_update_token_metadata() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_token_metadata::<Runtime>());
+		});
+	}
+
 	#[test]
 	fn test_destroy_class() {
 		new_test_ext().execute_with(|| {
 			assert_ok!(test_benchmark_destroy_class::<Runtime>());
 		});
 	}
+
+	#[test]
+	fn test_destroy_class_with_sweep() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_destroy_class_with_sweep::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_transfer_class() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_transfer_class::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_set_class_roles() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_class_roles::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_set_max_token_count() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_max_token_count::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_set_royalty() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_royalty::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_set_token_properties() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_token_properties::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_list_token() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_list_token::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_unlist_token() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_unlist_token::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_buy_token() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_buy_token::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_transfer_from() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_transfer_from::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_approve() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_approve::<Runtime>());
+		});
+	}
+
+	#[test]
+	fn test_set_approval_for_all() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_approval_for_all::<Runtime>());
+		});
+	}
 }
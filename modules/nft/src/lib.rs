@@ -32,14 +32,16 @@ use frame_support::{
 	transactional, PalletId,
 };
 use frame_system::pallet_prelude::*;
-use orml_traits::NFT;
-use primitives::NFTBalance;
+use orml_traits::{MultiCurrency, NFT};
+use primitives::{Balance, CurrencyId, NFTBalance};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{
 	traits::{AccountIdConversion, Saturating, StaticLookup, Zero},
-	DispatchResult, RuntimeDebug,
+	DispatchError, DispatchResult, Permill, RuntimeDebug, SaturatedConversion,
 };
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+use support::{NFTApproval, NFTInfo, NFTMinter, PositionNFT};
 
 pub mod benchmarking;
 mod mock;
@@ -49,7 +51,7 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
-pub type CID = sp_std::vec::Vec<u8>;
+pub type CID = Vec<u8>;
 
 #[repr(u8)]
 #[derive(Encode, Decode, Clone, Copy, BitFlags, RuntimeDebug, PartialEq, Eq)]
@@ -58,6 +60,10 @@ pub enum ClassProperty {
 	Transferable = 0b00000001,
 	/// Token can be burned
 	Burnable = 0b00000010,
+	/// Token metadata can be updated by class owner
+	Mutable = 0b00000100,
+	/// Token metadata can be updated by token owner
+	MutableByTokenOwner = 0b00001000,
 }
 
 #[derive(Clone, Copy, PartialEq, Default, RuntimeDebug)]
@@ -95,6 +101,16 @@ pub struct TokenData<Balance> {
 	pub deposit: Balance,
 }
 
+/// The roles delegated by class owner, which don't have the right to
+/// destroy class.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default)]
+pub struct ClassRoles<AccountId> {
+	/// The account can mint tokens of class
+	pub minter: Option<AccountId>,
+	/// The account can set properties of tokens, e.g. freeze the transfer
+	pub freezer: Option<AccountId>,
+}
+
 pub type TokenIdOf<T> = <T as orml_nft::Config>::TokenId;
 pub type ClassIdOf<T> = <T as orml_nft::Config>::ClassId;
 pub type BalanceOf<T> =
@@ -120,10 +136,28 @@ pub mod module {
 		#[pallet::constant]
 		type CreateTokenDeposit: Get<BalanceOf<Self>>;
 
+		/// The deposit per byte of token metadata, charged when updating
+		/// metadata to a larger size
+		#[pallet::constant]
+		type DataDepositPerByte: Get<BalanceOf<Self>>;
+
 		/// The NFT's module id
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
 
+		/// The max count of tokens swept in a call of
+		/// `destroy_class_with_sweep`
+		#[pallet::constant]
+		type MaxSweepBatch: Get<u32>;
+
+		/// The max count of tokens minted or transferred in a call of
+		/// `mint_batch` or `transfer_batch`
+		#[pallet::constant]
+		type MaxBatch: Get<u32>;
+
+		/// The currency to settle the payments of NFT sales.
+		type MultiCurrency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -142,9 +176,30 @@ pub mod module {
 		NonTransferable,
 		/// Property of class don't support burn
 		NonBurnable,
+		/// Property of class don't support update metadata
+		Immutable,
 		/// Can not destroy class
 		/// Total issuance is not 0
 		CannotDestroyClass,
+		/// The token is not listed for sale
+		NotListed,
+		/// The price of listing is invalid. need > 0
+		InvalidPrice,
+		/// The token is listed for sale
+		TokenIsListed,
+		/// The token is locked until the unlock block
+		TokenLocked,
+		/// The unlock block is invalid. need > current block number
+		InvalidUnlockBlock,
+		/// The editions minted would exceed the max token count of class
+		ExceededMaxTokenCount,
+		/// The max token count is invalid. need >= minted editions and can
+		/// not be raised
+		InvalidMaxTokenCount,
+		/// The approved account or the operator is the owner
+		ApprovalToOwner,
+		/// The count of tokens in a batch exceeds the max batch
+		ExceededMaxBatch,
 	}
 
 	#[pallet::event]
@@ -154,19 +209,221 @@ pub mod module {
 		CreatedClass(T::AccountId, ClassIdOf<T>),
 		/// Minted NFT token. \[from, to, class_id, quantity\]
 		MintedToken(T::AccountId, T::AccountId, ClassIdOf<T>, u32),
+		/// Minted NFT token in a batch. \[to, class_id, token_id\]
+		BatchMintedToken(T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
 		/// Transferred NFT token. \[from, to, class_id, token_id\]
 		TransferredToken(T::AccountId, T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
 		/// Burned NFT token. \[owner, class_id, token_id\]
 		BurnedToken(T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
+		/// Locked NFT token until the unlock block. \[class_id, token_id,
+		/// unlock_at\]
+		LockedToken(ClassIdOf<T>, TokenIdOf<T>, T::BlockNumber),
+		/// Updated NFT token metadata. \[who, class_id, token_id\]
+		UpdatedTokenMetadata(T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
 		/// Destroyed NFT class. \[owner, class_id\]
 		DestroyedClass(T::AccountId, ClassIdOf<T>),
+		/// Swept NFT tokens owned by class owner to destroy class.
+		/// \[owner, class_id, quantity, refunded_deposit\]
+		SweptClassTokens(T::AccountId, ClassIdOf<T>, u32, BalanceOf<T>),
+		/// Transferred the ownership of NFT class. \[from, to, class_id\]
+		TransferredClass(T::AccountId, T::AccountId, ClassIdOf<T>),
+		/// Updated roles of NFT class. \[class_id, roles\]
+		ClassRolesUpdated(ClassIdOf<T>, ClassRoles<T::AccountId>),
+		/// Updated max token count of NFT class. \[class_id,
+		/// max_token_count\]
+		MaxTokenCountUpdated(ClassIdOf<T>, u32),
+		/// Updated royalty of NFT class. \[class_id, royalty\]
+		RoyaltyUpdated(ClassIdOf<T>, Option<(T::AccountId, Permill)>),
+		/// Paid royalty for the sale of NFT token. \[class_id, token_id,
+		/// beneficiary, currency_id, amount\]
+		RoyaltyPaid(ClassIdOf<T>, TokenIdOf<T>, T::AccountId, CurrencyId, Balance),
+		/// Listed NFT token for sale. \[seller, class_id, token_id,
+		/// currency_id, price\]
+		ListedToken(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, CurrencyId, Balance),
+		/// Unlisted NFT token. \[seller, class_id, token_id\]
+		UnlistedToken(T::AccountId, ClassIdOf<T>, TokenIdOf<T>),
+		/// Updated properties of NFT token, which override the properties of
+		/// class. \[class_id, token_id, properties\]
+		TokenPropertiesUpdated(ClassIdOf<T>, TokenIdOf<T>, Option<Properties>),
+		/// Approved the account to transfer NFT token, `None` to revoke.
+		/// \[owner, class_id, token_id, approved\]
+		ApprovedToken(T::AccountId, ClassIdOf<T>, TokenIdOf<T>, Option<T::AccountId>),
+		/// Approved or revoked the operator to transfer all NFT tokens of
+		/// class owned by the owner. \[owner, class_id, operator, approved\]
+		ApprovedForAll(T::AccountId, ClassIdOf<T>, T::AccountId, bool),
+		/// Sold NFT token. \[seller, buyer, class_id, token_id, currency_id,
+		/// price\]
+		SoldToken(
+			T::AccountId,
+			T::AccountId,
+			ClassIdOf<T>,
+			TokenIdOf<T>,
+			CurrencyId,
+			Balance,
+		),
 	}
 
+	/// The royalty of NFT class, paid to the beneficiary on each sale of
+	/// tokens belong to the class.
+	/// ClassId => (Beneficiary, RoyaltyRate)
+	#[pallet::storage]
+	#[pallet::getter(fn royalties)]
+	pub type Royalties<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, (T::AccountId, Permill), OptionQuery>;
+
+	/// The block number until which NFT token is locked, the locked token
+	/// can not be transferred or burned.
+	/// ClassId, TokenId => UnlockAt
+	#[pallet::storage]
+	#[pallet::getter(fn token_locks)]
+	pub type TokenLocks<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, TokenIdOf<T>, T::BlockNumber, OptionQuery>;
+
+	/// The roles of NFT class delegated by class owner.
+	/// ClassId => ClassRoles
+	#[pallet::storage]
+	#[pallet::getter(fn class_roles)]
+	pub type Roles<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, ClassRoles<T::AccountId>, ValueQuery>;
+
+	/// The NFT tokens listed for sale at fixed price, the listed tokens are
+	/// escrowed by module account.
+	/// ClassId, TokenId => (Seller, CurrencyId, Price)
+	#[pallet::storage]
+	#[pallet::getter(fn listings)]
+	pub type Listings<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ClassIdOf<T>,
+		Twox64Concat,
+		TokenIdOf<T>,
+		(T::AccountId, CurrencyId, Balance),
+		OptionQuery,
+	>;
+
+	/// The properties of NFT token which override the properties of class,
+	/// e.g. mark the token as soulbound.
+	/// ClassId, TokenId => Properties
+	#[pallet::storage]
+	#[pallet::getter(fn token_properties)]
+	pub type TokenProperties<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, TokenIdOf<T>, Properties, OptionQuery>;
+
+	/// The max count of tokens can be minted of NFT class, burned tokens
+	/// are counted as well.
+	/// ClassId => MaxTokenCount
+	#[pallet::storage]
+	#[pallet::getter(fn max_token_counts)]
+	pub type MaxTokenCounts<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, u32, OptionQuery>;
+
+	/// The count of editions minted of NFT class.
+	/// ClassId => MintedEditions
+	#[pallet::storage]
+	#[pallet::getter(fn minted_editions)]
+	pub type MintedEditions<T: Config> = StorageMap<_, Twox64Concat, ClassIdOf<T>, u32, ValueQuery>;
+
+	/// The edition number of NFT token, starts from 1 in each class.
+	/// ClassId, TokenId => Edition
+	#[pallet::storage]
+	#[pallet::getter(fn token_editions)]
+	pub type TokenEditions<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, ClassIdOf<T>, Twox64Concat, TokenIdOf<T>, u32, OptionQuery>;
+
+	/// The NFT class of the positions of module, owned by the module
+	/// account.
+	/// PalletId => ClassId
+	#[pallet::storage]
+	#[pallet::getter(fn position_classes)]
+	pub type PositionClasses<T: Config> = StorageMap<_, Twox64Concat, PalletId, ClassIdOf<T>, OptionQuery>;
+
+	/// True if the minted editions of the existing classes have been
+	/// initialized.
+	#[pallet::storage]
+	pub(crate) type UpgradedToMintedEditions<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The count of NFT tokens of class owned by account.
+	/// AccountId, ClassId => Count
+	#[pallet::storage]
+	#[pallet::getter(fn class_balances)]
+	pub type ClassBalances<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, ClassIdOf<T>, u64, ValueQuery>;
+
+	/// The account approved by the owner to transfer NFT token, the
+	/// approval is void once the token is transferred.
+	/// ClassId, TokenId => (Owner, Approved)
+	#[pallet::storage]
+	#[pallet::getter(fn token_approvals)]
+	pub type TokenApprovals<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ClassIdOf<T>,
+		Twox64Concat,
+		TokenIdOf<T>,
+		(T::AccountId, T::AccountId),
+		OptionQuery,
+	>;
+
+	/// The operators approved by the owner to transfer all NFT tokens of
+	/// class owned by the owner.
+	/// (Owner, ClassId), Operator => ()
+	#[pallet::storage]
+	pub type OperatorApprovals<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, (T::AccountId, ClassIdOf<T>), Twox64Concat, T::AccountId, (), OptionQuery>;
+
+	/// True if the class balances of the existing tokens have been
+	/// initialized.
+	#[pallet::storage]
+	pub(crate) type UpgradedToClassBalances<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Initialize the minted editions of the classes minted before the
+		/// editions were counted with their total issuance, so that the max
+		/// token count set later caps the tokens minted already. Initialize
+		/// the class balances of the tokens minted before the balances were
+		/// counted.
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight: Weight = 0;
+
+			if UpgradedToMintedEditions::<T>::get() {
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			} else {
+				let mut count: Weight = 0;
+				for (class_id, class_info) in orml_nft::Classes::<T>::iter() {
+					let total_issuance: u32 = class_info.total_issuance.saturated_into();
+					MintedEditions::<T>::mutate(class_id, |minted_editions| {
+						*minted_editions = (*minted_editions).max(total_issuance);
+					});
+					count += 1;
+				}
+				UpgradedToMintedEditions::<T>::put(true);
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(count.saturating_mul(2) + 1, count + 1));
+			}
+
+			if UpgradedToClassBalances::<T>::get() {
+				weight = weight.saturating_add(T::DbWeight::get().reads(1));
+			} else {
+				// recount the balances of all tokens, overwrite rather than accumulate
+				// so the tokens counted already are not counted twice.
+				let mut count: Weight = 0;
+				let mut balances: BTreeMap<(T::AccountId, ClassIdOf<T>), u64> = BTreeMap::new();
+				for (class_id, _, token_info) in orml_nft::Tokens::<T>::iter() {
+					let balance = balances.entry((token_info.owner, class_id)).or_default();
+					*balance = balance.saturating_add(1);
+					count += 1;
+				}
+				let writes = balances.len() as Weight;
+				for ((owner, class_id), balance) in balances {
+					ClassBalances::<T>::insert(owner, class_id, balance);
+				}
+				UpgradedToClassBalances::<T>::put(true);
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(count + 1, writes + 1));
+			}
+
+			weight
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -220,23 +477,65 @@ pub mod module {
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
 			let to = T::Lookup::lookup(to)?;
-			ensure!(quantity >= 1, Error::<T>::InvalidQuantity);
-			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
-			ensure!(who == class_info.owner, Error::<T>::NoPermission);
-			let deposit = T::CreateTokenDeposit::get();
-			let total_deposit = deposit.saturating_mul(quantity.into());
+			Self::do_mint(who, to, class_id, metadata, quantity)?;
+			Ok(().into())
+		}
 
-			// `repatriate_reserved` will check `to` account exist and may return
-			// `DeadAccount`.
-			T::Currency::transfer(&who, &to, total_deposit, KeepAlive)?;
-			T::Currency::reserve(&to, total_deposit)?;
+		/// Mint a batch of NFT tokens with the same metadata, the deposit is
+		/// transferred once for the batch and an event is emitted per token.
+		///
+		/// - `to`: the token owner's account
+		/// - `class_id`: token belong to the class id
+		/// - `metadata`: external metadata
+		/// - `quantity`: token quantity, at most `MaxBatch`
+		#[pallet::weight(<T as Config>::WeightInfo::mint_batch(*quantity))]
+		#[transactional]
+		pub fn mint_batch(
+			origin: OriginFor<T>,
+			to: <T::Lookup as StaticLookup>::Source,
+			class_id: ClassIdOf<T>,
+			metadata: CID,
+			quantity: u32,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			ensure!(quantity <= T::MaxBatch::get(), Error::<T>::ExceededMaxBatch);
 
-			let data = TokenData { deposit };
-			for _ in 0..quantity {
-				orml_nft::Pallet::<T>::mint(&to, class_id, metadata.clone(), data.clone())?;
+			for token_id in Self::do_mint(who, to.clone(), class_id, metadata, quantity)? {
+				Self::deposit_event(Event::BatchMintedToken(to.clone(), class_id, token_id));
 			}
+			Ok(().into())
+		}
 
-			Self::deposit_event(Event::MintedToken(who, to, class_id, quantity));
+		/// Mint NFT tokens which are locked until `unlock_at`, the locked
+		/// tokens can not be transferred or burned.
+		///
+		/// - `to`: the token owner's account
+		/// - `class_id`: token belong to the class id
+		/// - `metadata`: external metadata
+		/// - `quantity`: token quantity
+		/// - `unlock_at`: the block number to unlock tokens
+		#[pallet::weight(<T as Config>::WeightInfo::mint_with_lock(*quantity))]
+		#[transactional]
+		pub fn mint_with_lock(
+			origin: OriginFor<T>,
+			to: <T::Lookup as StaticLookup>::Source,
+			class_id: ClassIdOf<T>,
+			metadata: CID,
+			quantity: u32,
+			unlock_at: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			ensure!(
+				unlock_at > <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::InvalidUnlockBlock
+			);
+
+			for token_id in Self::do_mint(who, to, class_id, metadata, quantity)? {
+				TokenLocks::<T>::insert(class_id, token_id, unlock_at);
+				Self::deposit_event(Event::LockedToken(class_id, token_id, unlock_at));
+			}
 			Ok(().into())
 		}
 
@@ -257,6 +556,91 @@ pub mod module {
 			Ok(().into())
 		}
 
+		/// Transfer a batch of NFT tokens to another account
+		///
+		/// - `to`: the token owner's account
+		/// - `tokens`: the list of (class_id, token_id), at most `MaxBatch`
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_batch(tokens.len() as u32))]
+		#[transactional]
+		pub fn transfer_batch(
+			origin: OriginFor<T>,
+			to: <T::Lookup as StaticLookup>::Source,
+			tokens: Vec<(ClassIdOf<T>, TokenIdOf<T>)>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			ensure!(!tokens.is_empty(), Error::<T>::InvalidQuantity);
+			ensure!(
+				tokens.len() <= T::MaxBatch::get() as usize,
+				Error::<T>::ExceededMaxBatch
+			);
+
+			for token in tokens {
+				Self::do_transfer(&who, &to, token)?;
+			}
+			Ok(().into())
+		}
+
+		/// Transfer NFT token owned by `from` to another account, by the
+		/// owner, the approved account of token or the operator of owner.
+		///
+		/// - `from`: the token owner's account
+		/// - `to`: the account to receive token
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_from())]
+		#[transactional]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			from: <T::Lookup as StaticLookup>::Source,
+			to: <T::Lookup as StaticLookup>::Source,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let from = T::Lookup::lookup(from)?;
+			let to = T::Lookup::lookup(to)?;
+			Self::do_transfer_from(&who, &from, &to, token)?;
+			Ok(().into())
+		}
+
+		/// Approve the account to transfer NFT token, by the owner or the
+		/// operator of owner. The approval is void once the token is
+		/// transferred.
+		///
+		/// - `approved`: the approved account, `None` to revoke the approval
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::approve())]
+		#[transactional]
+		pub fn approve(
+			origin: OriginFor<T>,
+			approved: Option<<T::Lookup as StaticLookup>::Source>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let approved = approved.map(T::Lookup::lookup).transpose()?;
+			Self::do_approve(&who, approved, token)?;
+			Ok(().into())
+		}
+
+		/// Approve or revoke the operator to transfer all NFT tokens of class
+		/// owned by the caller.
+		///
+		/// - `class_id`: the class ID
+		/// - `operator`: the operator account
+		/// - `approved`: approve or revoke
+		#[pallet::weight(<T as Config>::WeightInfo::set_approval_for_all())]
+		#[transactional]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			operator: <T::Lookup as StaticLookup>::Source,
+			approved: bool,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let operator = T::Lookup::lookup(operator)?;
+			Self::do_set_approval_for_all(&who, class_id, &operator, approved)?;
+			Ok(().into())
+		}
+
 		/// Burn NFT token
 		///
 		/// - `token`: (class_id, token_id)
@@ -264,17 +648,18 @@ pub mod module {
 		#[transactional]
 		pub fn burn(origin: OriginFor<T>, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			let class_info = orml_nft::Pallet::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
-			let data = class_info.data;
-			ensure!(
-				data.properties.0.contains(ClassProperty::Burnable),
-				Error::<T>::NonBurnable
-			);
+			let properties = Self::properties_of(token)?;
+			ensure!(properties.0.contains(ClassProperty::Burnable), Error::<T>::NonBurnable);
 
 			let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
 			ensure!(who == token_info.owner, Error::<T>::NoPermission);
+			Self::ensure_unlocked(token)?;
 
 			orml_nft::Pallet::<T>::burn(&who, token)?;
+			TokenProperties::<T>::remove(token.0, token.1);
+			TokenEditions::<T>::remove(token.0, token.1);
+			TokenApprovals::<T>::remove(token.0, token.1);
+			Self::sub_class_balance(&who, token.0, 1);
 
 			T::Currency::unreserve(&who, token_info.data.deposit);
 
@@ -282,6 +667,64 @@ pub mod module {
 			Ok(().into())
 		}
 
+		/// Update NFT token metadata, the deposit of token is adjusted by the
+		/// size change of metadata.
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `metadata`: new external metadata
+		#[pallet::weight(<T as Config>::WeightInfo::update_token_metadata())]
+		#[transactional]
+		pub fn update_token_metadata(
+			origin: OriginFor<T>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+			metadata: CID,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Pallet::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
+			let properties = class_info.data.properties.0;
+			ensure!(
+				properties.intersects(ClassProperty::Mutable | ClassProperty::MutableByTokenOwner),
+				Error::<T>::Immutable
+			);
+
+			let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
+			let owner = token_info.owner;
+			ensure!(
+				(properties.contains(ClassProperty::Mutable) && who == class_info.owner)
+					|| (properties.contains(ClassProperty::MutableByTokenOwner) && who == owner),
+				Error::<T>::NoPermission
+			);
+
+			let old_len = token_info.metadata.len() as u32;
+			let new_len = metadata.len() as u32;
+			let mut deposit = token_info.data.deposit;
+			if new_len > old_len {
+				let extra = T::DataDepositPerByte::get().saturating_mul((new_len - old_len).into());
+				if who != owner {
+					T::Currency::transfer(&who, &owner, extra, KeepAlive)?;
+				}
+				T::Currency::reserve(&owner, extra)?;
+				deposit = deposit.saturating_add(extra);
+			} else {
+				// never refund the deposit to create token
+				let refund = T::DataDepositPerByte::get()
+					.saturating_mul((old_len - new_len).into())
+					.min(deposit.saturating_sub(T::CreateTokenDeposit::get()));
+				T::Currency::unreserve(&owner, refund);
+				deposit = deposit.saturating_sub(refund);
+			}
+
+			orml_nft::Tokens::<T>::mutate(token.0, token.1, |maybe_token_info| {
+				if let Some(token_info) = maybe_token_info {
+					token_info.metadata = metadata;
+					token_info.data.deposit = deposit;
+				}
+			});
+
+			Self::deposit_event(Event::UpdatedTokenMetadata(who, token.0, token.1));
+			Ok(().into())
+		}
+
 		/// Destroy NFT class, remove dest from proxy, and send all the free
 		/// balance to dest
 		///
@@ -303,37 +746,301 @@ pub mod module {
 				Error::<T>::CannotDestroyClass
 			);
 
-			let data = class_info.data;
+			Self::do_destroy_class(&who, class_id, &dest, class_info.data.deposit)?;
+			Ok(().into())
+		}
+
+		/// Destroy NFT class which still has tokens owned by class owner,
+		/// e.g. an abandoned collection. At most `MaxSweepBatch` tokens are
+		/// burned in a call and their deposits are refunded to `dest`, call
+		/// it again in later blocks until all tokens are swept and the class
+		/// is destroyed. The tokens must be burnable and unlocked.
+		///
+		/// - `class_id`: The class ID to destroy
+		/// - `dest`: The proxy account that will receive the refunded
+		///   deposits and free balance
+		#[pallet::weight(<T as Config>::WeightInfo::destroy_class_with_sweep(T::MaxSweepBatch::get()))]
+		#[transactional]
+		pub fn destroy_class_with_sweep(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			let tokens = orml_nft::Tokens::<T>::iter_prefix(class_id)
+				.take(T::MaxSweepBatch::get() as usize)
+				.collect::<Vec<_>>();
+			let swept = tokens.len() as u32;
+			let mut refund: BalanceOf<T> = Zero::zero();
+			for (token_id, token_info) in tokens {
+				// the tokens owned by others must be burned by their owners
+				ensure!(token_info.owner == who, Error::<T>::CannotDestroyClass);
+				// the tokens must be burnable, the same as burning them one by one
+				let properties = Self::token_properties(class_id, token_id).unwrap_or(class_info.data.properties);
+				ensure!(properties.0.contains(ClassProperty::Burnable), Error::<T>::NonBurnable);
+				Self::ensure_unlocked((class_id, token_id))?;
 
-			T::Currency::unreserve(&who, data.deposit);
+				orml_nft::Pallet::<T>::burn(&who, (class_id, token_id))?;
+				TokenProperties::<T>::remove(class_id, token_id);
+				TokenEditions::<T>::remove(class_id, token_id);
+				TokenApprovals::<T>::remove(class_id, token_id);
+				refund = refund.saturating_add(token_info.data.deposit);
+			}
 
-			orml_nft::Pallet::<T>::destroy_class(&who, class_id)?;
+			if !swept.is_zero() {
+				Self::sub_class_balance(&who, class_id, swept.into());
+				T::Currency::unreserve(&who, refund);
+				T::Currency::transfer(&who, &dest, refund, AllowDeath)?;
+				Self::deposit_event(Event::SweptClassTokens(who.clone(), class_id, swept, refund));
+			}
 
-			// this should unresere proxy deposit
-			pallet_proxy::Pallet::<T>::remove_proxy_delegate(&who, dest.clone(), Default::default(), Zero::zero())?;
+			if orml_nft::Pallet::<T>::classes(class_id)
+				.map_or(false, |class_info| class_info.total_issuance == Zero::zero())
+			{
+				Self::do_destroy_class(&who, class_id, &dest, class_info.data.deposit)?;
+			}
+			Ok(().into())
+		}
 
-			T::Currency::transfer(&who, &dest, T::Currency::free_balance(&who), AllowDeath)?;
+		/// Transfer the ownership of NFT class, replace the proxy of class
+		/// owner `from` with `to`.
+		///
+		/// - `class_id`: The class ID to transfer
+		/// - `from`: The proxy account of class owner to be removed
+		/// - `to`: The account to be added as the proxy of class owner
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_class())]
+		#[transactional]
+		pub fn transfer_class(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			from: <T::Lookup as StaticLookup>::Source,
+			to: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let from = T::Lookup::lookup(from)?;
+			let to = T::Lookup::lookup(to)?;
+			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			pallet_proxy::Pallet::<T>::remove_proxy_delegate(&who, from.clone(), Default::default(), Zero::zero())?;
+			pallet_proxy::Pallet::<T>::add_proxy_delegate(&who, to.clone(), Default::default(), Zero::zero())?;
 
-			Self::deposit_event(Event::DestroyedClass(who, class_id));
+			Self::deposit_event(Event::TransferredClass(from, to, class_id));
+			Ok(().into())
+		}
+
+		/// Set the minter and freezer roles of NFT class, so that minting and
+		/// freezing tokens can be delegated without the right to destroy
+		/// class.
+		///
+		/// - `class_id`: The class ID
+		/// - `roles`: the minter and freezer of class
+		#[pallet::weight(<T as Config>::WeightInfo::set_class_roles())]
+		#[transactional]
+		pub fn set_class_roles(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			roles: ClassRoles<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			if roles == Default::default() {
+				Roles::<T>::remove(class_id);
+			} else {
+				Roles::<T>::insert(class_id, roles.clone());
+			}
+
+			Self::deposit_event(Event::ClassRolesUpdated(class_id, roles));
+			Ok(().into())
+		}
+
+		/// Set the max count of tokens can be minted of NFT class. Once set,
+		/// it can only be lowered to keep the class provably scarce.
+		///
+		/// - `class_id`: The class ID to set max token count
+		/// - `max_token_count`: the max count of editions, must not be less
+		///   than the editions minted
+		#[pallet::weight(<T as Config>::WeightInfo::set_max_token_count())]
+		#[transactional]
+		pub fn set_max_token_count(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			max_token_count: u32,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+			ensure!(
+				max_token_count >= Self::minted_editions(class_id)
+					&& Self::max_token_counts(class_id).map_or(true, |count| max_token_count <= count),
+				Error::<T>::InvalidMaxTokenCount
+			);
+
+			MaxTokenCounts::<T>::insert(class_id, max_token_count);
+
+			Self::deposit_event(Event::MaxTokenCountUpdated(class_id, max_token_count));
+			Ok(().into())
+		}
+
+		/// Set the royalty of NFT class, which is paid on each sale of tokens
+		/// belong to the class.
+		///
+		/// - `class_id`: The class ID to set royalty
+		/// - `royalty`: (beneficiary, royalty_rate), `None` to remove royalty
+		#[pallet::weight(<T as Config>::WeightInfo::set_royalty())]
+		#[transactional]
+		pub fn set_royalty(
+			origin: OriginFor<T>,
+			class_id: ClassIdOf<T>,
+			royalty: Option<(T::AccountId, Permill)>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(who == class_info.owner, Error::<T>::NoPermission);
+
+			Royalties::<T>::mutate_exists(class_id, |maybe_royalty| *maybe_royalty = royalty.clone());
+
+			Self::deposit_event(Event::RoyaltyUpdated(class_id, royalty));
+			Ok(().into())
+		}
+
+		/// Set the properties of NFT token to override the properties of
+		/// class, e.g. make the token soulbound and only can be burned. The
+		/// freezer can only revoke the properties of token, only the class
+		/// owner can grant.
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `properties`: the properties of token, `None` to follow the
+		///   properties of class
+		#[pallet::weight(<T as Config>::WeightInfo::set_token_properties())]
+		#[transactional]
+		pub fn set_token_properties(
+			origin: OriginFor<T>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+			properties: Option<Properties>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let class_info = orml_nft::Pallet::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
+			ensure!(
+				who == class_info.owner || Self::class_roles(token.0).freezer.as_ref() == Some(&who),
+				Error::<T>::NoPermission
+			);
+			ensure!(
+				orml_nft::Pallet::<T>::tokens(token.0, token.1).is_some(),
+				Error::<T>::TokenIdNotFound
+			);
+			if who != class_info.owner {
+				// the freezer can not make the soulbound token transferable or burnable
+				let current = Self::token_properties(token.0, token.1).unwrap_or(class_info.data.properties);
+				let new = properties.unwrap_or(class_info.data.properties);
+				ensure!(current.0.contains(new.0), Error::<T>::NoPermission);
+			}
+			// the escrowed token must be transferable to be sold or unlisted
+			ensure!(
+				!Listings::<T>::contains_key(token.0, token.1),
+				Error::<T>::TokenIsListed
+			);
+
+			match properties {
+				Some(properties) => TokenProperties::<T>::insert(token.0, token.1, properties),
+				None => TokenProperties::<T>::remove(token.0, token.1),
+			}
+
+			Self::deposit_event(Event::TokenPropertiesUpdated(token.0, token.1, properties));
+			Ok(().into())
+		}
+
+		/// List NFT token for sale at fixed price, the token is escrowed until
+		/// it's sold or unlisted.
+		///
+		/// - `token`: (class_id, token_id)
+		/// - `price`: the price of token
+		/// - `currency_id`: the currency to pay the price
+		#[pallet::weight(<T as Config>::WeightInfo::list_token())]
+		#[transactional]
+		pub fn list_token(
+			origin: OriginFor<T>,
+			token: (ClassIdOf<T>, TokenIdOf<T>),
+			#[pallet::compact] price: Balance,
+			currency_id: CurrencyId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!price.is_zero(), Error::<T>::InvalidPrice);
+
+			Self::do_transfer(&who, &Self::account_id(), token)?;
+			Listings::<T>::insert(token.0, token.1, (who.clone(), currency_id, price));
+
+			Self::deposit_event(Event::ListedToken(who, token.0, token.1, currency_id, price));
+			Ok(().into())
+		}
+
+		/// Unlist NFT token and get back the escrowed token.
+		///
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::unlist_token())]
+		#[transactional]
+		pub fn unlist_token(origin: OriginFor<T>, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (seller, _, _) = Self::listings(token.0, token.1).ok_or(Error::<T>::NotListed)?;
+			ensure!(who == seller, Error::<T>::NoPermission);
+
+			Listings::<T>::remove(token.0, token.1);
+			Self::do_transfer(&Self::account_id(), &who, token)?;
+
+			Self::deposit_event(Event::UnlistedToken(who, token.0, token.1));
+			Ok(().into())
+		}
+
+		/// Buy the listed NFT token at the listing price, the royalty of the
+		/// class is deducted from the price.
+		///
+		/// - `token`: (class_id, token_id)
+		#[pallet::weight(<T as Config>::WeightInfo::buy_token())]
+		#[transactional]
+		pub fn buy_token(origin: OriginFor<T>, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (seller, currency_id, price) = Self::listings(token.0, token.1).ok_or(Error::<T>::NotListed)?;
+
+			Listings::<T>::remove(token.0, token.1);
+			Self::settle_payment(&seller, &who, token, currency_id, price)?;
+			Self::do_transfer(&Self::account_id(), &who, token)?;
+
+			Self::deposit_event(Event::SoldToken(seller, who, token.0, token.1, currency_id, price));
 			Ok(().into())
 		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// The module account to escrow listed tokens. Use sub account to avoid
+	/// conflicting with the owner of class 0.
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_sub_account("market")
+	}
+
 	/// Ensured atomic.
 	#[transactional]
 	fn do_transfer(from: &T::AccountId, to: &T::AccountId, token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResult {
-		let class_info = orml_nft::Pallet::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
-		let data = class_info.data;
+		let properties = Self::properties_of(token)?;
 		ensure!(
-			data.properties.0.contains(ClassProperty::Transferable),
+			properties.0.contains(ClassProperty::Transferable),
 			Error::<T>::NonTransferable
 		);
+		Self::ensure_unlocked(token)?;
 
 		let token_info = orml_nft::Pallet::<T>::tokens(token.0, token.1).ok_or(Error::<T>::TokenIdNotFound)?;
 
 		orml_nft::Pallet::<T>::transfer(from, to, token)?;
+		if from != to {
+			Self::sub_class_balance(from, token.0, 1);
+			Self::add_class_balance(to, token.0, 1);
+		}
 
 		T::Currency::unreserve(&from, token_info.data.deposit);
 		T::Currency::transfer(&from, &to, token_info.data.deposit, AllowDeath)?;
@@ -342,6 +1049,229 @@ impl<T: Config> Pallet<T> {
 		Self::deposit_event(Event::TransferredToken(from.clone(), to.clone(), token.0, token.1));
 		Ok(())
 	}
+
+	/// Destroy NFT class without tokens, unreserve the class deposit and
+	/// send all the free balance of class owner to `dest`.
+	fn do_destroy_class(
+		who: &T::AccountId,
+		class_id: ClassIdOf<T>,
+		dest: &T::AccountId,
+		deposit: BalanceOf<T>,
+	) -> DispatchResult {
+		T::Currency::unreserve(who, deposit);
+
+		orml_nft::Pallet::<T>::destroy_class(who, class_id)?;
+
+		// this should unresere proxy deposit
+		pallet_proxy::Pallet::<T>::remove_proxy_delegate(who, dest.clone(), Default::default(), Zero::zero())?;
+
+		T::Currency::transfer(who, dest, T::Currency::free_balance(who), AllowDeath)?;
+
+		Royalties::<T>::remove(class_id);
+		Roles::<T>::remove(class_id);
+		MaxTokenCounts::<T>::remove(class_id);
+		MintedEditions::<T>::remove(class_id);
+
+		Self::deposit_event(Event::DestroyedClass(who.clone(), class_id));
+		Ok(())
+	}
+
+	fn do_mint(
+		who: T::AccountId,
+		to: T::AccountId,
+		class_id: ClassIdOf<T>,
+		metadata: CID,
+		quantity: u32,
+	) -> sp_std::result::Result<Vec<TokenIdOf<T>>, DispatchError> {
+		ensure!(quantity >= 1, Error::<T>::InvalidQuantity);
+		let class_info = orml_nft::Pallet::<T>::classes(class_id).ok_or(Error::<T>::ClassIdNotFound)?;
+		ensure!(
+			who == class_info.owner || Self::class_roles(class_id).minter.as_ref() == Some(&who),
+			Error::<T>::NoPermission
+		);
+		let minted_editions = Self::minted_editions(class_id);
+		let new_minted_editions = minted_editions
+			.checked_add(quantity)
+			.ok_or(Error::<T>::ExceededMaxTokenCount)?;
+		if let Some(max_token_count) = Self::max_token_counts(class_id) {
+			ensure!(
+				new_minted_editions <= max_token_count,
+				Error::<T>::ExceededMaxTokenCount
+			);
+		}
+
+		let deposit = T::CreateTokenDeposit::get();
+		let total_deposit = deposit.saturating_mul(quantity.into());
+
+		// `repatriate_reserved` will check `to` account exist and may return
+		// `DeadAccount`.
+		T::Currency::transfer(&who, &to, total_deposit, KeepAlive)?;
+		T::Currency::reserve(&to, total_deposit)?;
+
+		let data = TokenData { deposit };
+		let token_ids = (0..quantity)
+			.map(|_| orml_nft::Pallet::<T>::mint(&to, class_id, metadata.clone(), data.clone()))
+			.collect::<sp_std::result::Result<Vec<_>, _>>()?;
+
+		for (edition, token_id) in (minted_editions + 1..).zip(token_ids.iter()) {
+			TokenEditions::<T>::insert(class_id, token_id, edition);
+		}
+		MintedEditions::<T>::insert(class_id, new_minted_editions);
+		Self::add_class_balance(&to, class_id, quantity.into());
+
+		Self::deposit_event(Event::MintedToken(who, to, class_id, quantity));
+		Ok(token_ids)
+	}
+
+	/// Ensure the NFT token is not locked, and remove the expired lock.
+	fn ensure_unlocked(token: (ClassIdOf<T>, TokenIdOf<T>)) -> DispatchResult {
+		if let Some(unlock_at) = Self::token_locks(token.0, token.1) {
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= unlock_at,
+				Error::<T>::TokenLocked
+			);
+			TokenLocks::<T>::remove(token.0, token.1);
+		}
+		Ok(())
+	}
+
+	fn add_class_balance(who: &T::AccountId, class_id: ClassIdOf<T>, amount: u64) {
+		ClassBalances::<T>::mutate(who, class_id, |balance| *balance = balance.saturating_add(amount));
+	}
+
+	fn sub_class_balance(who: &T::AccountId, class_id: ClassIdOf<T>, amount: u64) {
+		ClassBalances::<T>::mutate_exists(who, class_id, |maybe_balance| {
+			let balance = maybe_balance.unwrap_or_default().saturating_sub(amount);
+			*maybe_balance = if balance.is_zero() { None } else { Some(balance) };
+		});
+	}
+
+	/// The account approved to transfer NFT token by its current owner.
+	pub fn approved_of(token: (ClassIdOf<T>, TokenIdOf<T>)) -> Option<T::AccountId> {
+		let owner = orml_nft::Pallet::<T>::tokens(token.0, token.1)?.owner;
+		Self::token_approvals(token.0, token.1)
+			.filter(|(approved_by, _)| *approved_by == owner)
+			.map(|(_, approved)| approved)
+	}
+
+	/// Whether `operator` is approved to transfer all NFT tokens of class
+	/// owned by `owner`.
+	pub fn is_operator_of(owner: &T::AccountId, class_id: ClassIdOf<T>, operator: &T::AccountId) -> bool {
+		OperatorApprovals::<T>::contains_key((owner.clone(), class_id), operator)
+	}
+
+	fn do_approve(
+		who: &T::AccountId,
+		approved: Option<T::AccountId>,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+	) -> DispatchResult {
+		let owner = orml_nft::Pallet::<T>::tokens(token.0, token.1)
+			.ok_or(Error::<T>::TokenIdNotFound)?
+			.owner;
+		ensure!(
+			*who == owner || Self::is_operator_of(&owner, token.0, who),
+			Error::<T>::NoPermission
+		);
+
+		match &approved {
+			Some(approved) => {
+				ensure!(*approved != owner, Error::<T>::ApprovalToOwner);
+				TokenApprovals::<T>::insert(token.0, token.1, (owner.clone(), approved.clone()));
+			}
+			None => TokenApprovals::<T>::remove(token.0, token.1),
+		}
+
+		Self::deposit_event(Event::ApprovedToken(owner, token.0, token.1, approved));
+		Ok(())
+	}
+
+	fn do_set_approval_for_all(
+		who: &T::AccountId,
+		class_id: ClassIdOf<T>,
+		operator: &T::AccountId,
+		approved: bool,
+	) -> DispatchResult {
+		ensure!(who != operator, Error::<T>::ApprovalToOwner);
+		ensure!(
+			orml_nft::Pallet::<T>::classes(class_id).is_some(),
+			Error::<T>::ClassIdNotFound
+		);
+
+		if approved {
+			OperatorApprovals::<T>::insert((who.clone(), class_id), operator, ());
+		} else {
+			OperatorApprovals::<T>::remove((who.clone(), class_id), operator);
+		}
+
+		Self::deposit_event(Event::ApprovedForAll(who.clone(), class_id, operator.clone(), approved));
+		Ok(())
+	}
+
+	fn do_transfer_from(
+		who: &T::AccountId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+	) -> DispatchResult {
+		let owner = orml_nft::Pallet::<T>::tokens(token.0, token.1)
+			.ok_or(Error::<T>::TokenIdNotFound)?
+			.owner;
+		ensure!(*from == owner, Error::<T>::NoPermission);
+		ensure!(
+			*who == owner
+				|| Self::approved_of(token).as_ref() == Some(who)
+				|| Self::is_operator_of(&owner, token.0, who),
+			Error::<T>::NoPermission
+		);
+		TokenApprovals::<T>::remove(token.0, token.1);
+
+		Self::do_transfer(from, to, token)
+	}
+
+	/// The properties of NFT token, the properties of token override the
+	/// properties of class.
+	pub fn properties_of(token: (ClassIdOf<T>, TokenIdOf<T>)) -> sp_std::result::Result<Properties, DispatchError> {
+		let class_info = orml_nft::Pallet::<T>::classes(token.0).ok_or(Error::<T>::ClassIdNotFound)?;
+		Ok(Self::token_properties(token.0, token.1).unwrap_or(class_info.data.properties))
+	}
+
+	/// Transfer NFT token from seller `from` to buyer `to`, and settle the
+	/// `price` paid by buyer. The royalty of the class is deducted from the
+	/// price and paid to the beneficiary.
+	#[transactional]
+	pub fn transfer_with_payment(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+		currency_id: CurrencyId,
+		price: Balance,
+	) -> DispatchResult {
+		Self::settle_payment(from, to, token, currency_id, price)?;
+		Self::do_transfer(from, to, token)
+	}
+
+	/// Pay `price` from `buyer` to `seller` for the sale of NFT token, with
+	/// the royalty of the class paid to the beneficiary.
+	fn settle_payment(
+		seller: &T::AccountId,
+		buyer: &T::AccountId,
+		token: (ClassIdOf<T>, TokenIdOf<T>),
+		currency_id: CurrencyId,
+		price: Balance,
+	) -> DispatchResult {
+		let mut payment = price;
+
+		if let Some((beneficiary, royalty_rate)) = Self::royalties(token.0) {
+			let royalty = royalty_rate.mul_floor(price);
+			if beneficiary != *seller && !royalty.is_zero() {
+				T::MultiCurrency::transfer(currency_id, buyer, &beneficiary, royalty)?;
+				payment = payment.saturating_sub(royalty);
+				Self::deposit_event(Event::RoyaltyPaid(token.0, token.1, beneficiary, currency_id, royalty));
+			}
+		}
+
+		T::MultiCurrency::transfer(currency_id, buyer, seller, payment)
+	}
 }
 
 impl<T: Config> NFT<T::AccountId> for Pallet<T> {
@@ -361,3 +1291,145 @@ impl<T: Config> NFT<T::AccountId> for Pallet<T> {
 		Self::do_transfer(from, to, token)
 	}
 }
+
+impl<T: Config> NFTInfo<T::AccountId> for Pallet<T> {
+	type ClassId = ClassIdOf<T>;
+	type TokenId = TokenIdOf<T>;
+	type Balance = NFTBalance;
+
+	fn balance_of_class(who: &T::AccountId, class_id: Self::ClassId) -> Self::Balance {
+		Self::class_balances(who, class_id).into()
+	}
+
+	fn class_metadata(class_id: Self::ClassId) -> Option<Vec<u8>> {
+		orml_nft::Pallet::<T>::classes(class_id).map(|c| c.metadata)
+	}
+
+	fn token_metadata(token: (Self::ClassId, Self::TokenId)) -> Option<Vec<u8>> {
+		orml_nft::Pallet::<T>::tokens(token.0, token.1).map(|t| t.metadata)
+	}
+}
+
+impl<T: Config> NFTApproval<T::AccountId> for Pallet<T> {
+	type ClassId = ClassIdOf<T>;
+	type TokenId = TokenIdOf<T>;
+
+	fn approved(token: (Self::ClassId, Self::TokenId)) -> Option<T::AccountId> {
+		Self::approved_of(token)
+	}
+
+	fn is_approved_for_all(owner: &T::AccountId, class_id: Self::ClassId, operator: &T::AccountId) -> bool {
+		Self::is_operator_of(owner, class_id, operator)
+	}
+
+	#[transactional]
+	fn approve(
+		who: &T::AccountId,
+		approved: Option<T::AccountId>,
+		token: (Self::ClassId, Self::TokenId),
+	) -> DispatchResult {
+		Self::do_approve(who, approved, token)
+	}
+
+	#[transactional]
+	fn set_approval_for_all(
+		who: &T::AccountId,
+		class_id: Self::ClassId,
+		operator: &T::AccountId,
+		approved: bool,
+	) -> DispatchResult {
+		Self::do_set_approval_for_all(who, class_id, operator, approved)
+	}
+
+	#[transactional]
+	fn transfer_from(
+		who: &T::AccountId,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		token: (Self::ClassId, Self::TokenId),
+	) -> DispatchResult {
+		Self::do_transfer_from(who, from, to, token)
+	}
+}
+
+impl<T: Config> NFTMinter<T::AccountId> for Pallet<T> {
+	type ClassId = ClassIdOf<T>;
+	type TokenId = TokenIdOf<T>;
+
+	#[transactional]
+	fn mint(
+		who: &T::AccountId,
+		to: &T::AccountId,
+		class_id: Self::ClassId,
+		metadata: Vec<u8>,
+		quantity: u32,
+	) -> sp_std::result::Result<Vec<Self::TokenId>, DispatchError> {
+		Self::do_mint(who.clone(), to.clone(), class_id, metadata, quantity)
+	}
+}
+
+impl<T: Config> PositionNFT<T::AccountId> for Pallet<T> {
+	type TokenId = TokenIdOf<T>;
+
+	/// The position tokens are transferable but not burnable, they can only
+	/// be burned by the module when the position is closed.
+	#[transactional]
+	fn mint_position(
+		id: PalletId,
+		owner: &T::AccountId,
+		metadata: Vec<u8>,
+	) -> sp_std::result::Result<Self::TokenId, DispatchError> {
+		let class_id = match Self::position_classes(id) {
+			Some(class_id) => class_id,
+			None => {
+				let class_owner: T::AccountId = id.into_account();
+				let data = ClassData {
+					deposit: Zero::zero(),
+					properties: Properties(ClassProperty::Transferable.into()),
+				};
+				let class_id = orml_nft::Pallet::<T>::create_class(&class_owner, id.encode(), data)?;
+				PositionClasses::<T>::insert(id, class_id);
+				Self::deposit_event(Event::CreatedClass(class_owner, class_id));
+				class_id
+			}
+		};
+
+		let deposit = T::CreateTokenDeposit::get();
+		T::Currency::reserve(owner, deposit)?;
+		let token_id = orml_nft::Pallet::<T>::mint(owner, class_id, metadata, TokenData { deposit })?;
+
+		let edition = Self::minted_editions(class_id).saturating_add(1);
+		TokenEditions::<T>::insert(class_id, token_id, edition);
+		MintedEditions::<T>::insert(class_id, edition);
+		Self::add_class_balance(owner, class_id, 1);
+
+		Self::deposit_event(Event::MintedToken(id.into_account(), owner.clone(), class_id, 1));
+		Ok(token_id)
+	}
+
+	#[transactional]
+	fn burn_position(id: PalletId, owner: &T::AccountId, token_id: Self::TokenId) -> DispatchResult {
+		let class_id = Self::position_classes(id).ok_or(Error::<T>::ClassIdNotFound)?;
+		let token = (class_id, token_id);
+		let token_info = orml_nft::Pallet::<T>::tokens(class_id, token_id).ok_or(Error::<T>::TokenIdNotFound)?;
+		ensure!(*owner == token_info.owner, Error::<T>::NoPermission);
+		Self::ensure_unlocked(token)?;
+
+		orml_nft::Pallet::<T>::burn(owner, token)?;
+		TokenProperties::<T>::remove(class_id, token_id);
+		TokenEditions::<T>::remove(class_id, token_id);
+		TokenApprovals::<T>::remove(class_id, token_id);
+		Self::sub_class_balance(owner, class_id, 1);
+
+		T::Currency::unreserve(owner, token_info.data.deposit);
+
+		Self::deposit_event(Event::BurnedToken(owner.clone(), class_id, token_id));
+		Ok(())
+	}
+
+	fn position_owner(id: PalletId, token_id: Self::TokenId) -> Option<T::AccountId> {
+		Self::position_classes(id)
+			.and_then(|class_id| orml_nft::Pallet::<T>::tokens(class_id, token_id))
+			.map(|token_info| token_info.owner)
+	}
+}
@@ -178,13 +178,20 @@ impl module_currencies::Config for Runtime {
 parameter_types! {
 	pub const CreateClassDeposit: Balance = 200;
 	pub const CreateTokenDeposit: Balance = 100;
+	pub const DataDepositPerByte: Balance = 10;
 	pub const NftPalletId: PalletId = PalletId(*b"aca/aNFT");
+	pub const MaxSweepBatch: u32 = 10;
+	pub const MaxBatch: u32 = 10;
 }
 impl Config for Runtime {
 	type Event = Event;
 	type CreateClassDeposit = CreateClassDeposit;
 	type CreateTokenDeposit = CreateTokenDeposit;
+	type DataDepositPerByte = DataDepositPerByte;
 	type PalletId = NftPalletId;
+	type MaxSweepBatch = MaxSweepBatch;
+	type MaxBatch = MaxBatch;
+	type MultiCurrency = Currency;
 	type WeightInfo = ();
 }
 
@@ -169,6 +169,113 @@ fn mint_should_fail() {
 	});
 }
 
+#[test]
+fn mint_with_lock_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 2 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+
+		assert_noop!(
+			NFTModule::mint_with_lock(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 2, 0),
+			Error::<Runtime>::InvalidUnlockBlock
+		);
+		assert_ok!(NFTModule::mint_with_lock(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			2,
+			10
+		));
+		let event = Event::nft(crate::Event::LockedToken(CLASS_ID, 1, 10));
+		assert_eq!(last_event(), event);
+		assert_eq!(NFTModule::token_locks(CLASS_ID, 0), Some(10));
+		assert_eq!(NFTModule::token_locks(CLASS_ID, 1), Some(10));
+
+		assert_noop!(
+			NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, 0)),
+			Error::<Runtime>::TokenLocked
+		);
+		assert_noop!(
+			NFTModule::burn(Origin::signed(BOB), (CLASS_ID, 1)),
+			Error::<Runtime>::TokenLocked
+		);
+
+		System::set_block_number(10);
+		assert_ok!(NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, 0)));
+		assert_ok!(NFTModule::burn(Origin::signed(BOB), (CLASS_ID, 1)));
+		assert_eq!(NFTModule::token_locks(CLASS_ID, 0), None);
+		assert_eq!(NFTModule::token_locks(CLASS_ID, 1), None);
+	});
+}
+
+#[test]
+fn mint_batch_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 3 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+
+		assert_noop!(
+			NFTModule::mint_batch(Origin::signed(BOB), BOB, CLASS_ID, vec![1], 3),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::mint_batch(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 0),
+			Error::<Runtime>::InvalidQuantity
+		);
+		assert_noop!(
+			NFTModule::mint_batch(
+				Origin::signed(class_id_account()),
+				BOB,
+				CLASS_ID,
+				vec![1],
+				MaxBatch::get() + 1
+			),
+			Error::<Runtime>::ExceededMaxBatch
+		);
+
+		assert_ok!(NFTModule::mint_batch(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			3
+		));
+		let event = Event::nft(crate::Event::MintedToken(class_id_account(), BOB, CLASS_ID, 3));
+		assert!(System::events().iter().any(|record| record.event == event));
+		for token_id in 0..3 {
+			let event = Event::nft(crate::Event::BatchMintedToken(BOB, CLASS_ID, token_id));
+			assert!(System::events().iter().any(|record| record.event == event));
+			assert_eq!(
+				orml_nft::Pallet::<Runtime>::tokens(CLASS_ID, token_id).unwrap().owner,
+				BOB
+			);
+		}
+
+		assert_eq!(
+			reserved_balance(&BOB),
+			3 * <Runtime as Config>::CreateTokenDeposit::get()
+		);
+		assert_eq!(NFTModule::class_balances(&BOB, CLASS_ID), 3);
+	});
+}
+
 #[test]
 fn transfer_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -279,6 +386,69 @@ fn transfer_should_fail() {
 	});
 }
 
+#[test]
+fn transfer_batch_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 3 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			3
+		));
+
+		assert_noop!(
+			NFTModule::transfer_batch(Origin::signed(BOB), ALICE, vec![]),
+			Error::<Runtime>::InvalidQuantity
+		);
+		assert_noop!(
+			NFTModule::transfer_batch(
+				Origin::signed(BOB),
+				ALICE,
+				vec![(CLASS_ID, 0); MaxBatch::get() as usize + 1]
+			),
+			Error::<Runtime>::ExceededMaxBatch
+		);
+		assert_noop!(
+			NFTModule::transfer_batch(Origin::signed(BOB), ALICE, vec![(CLASS_ID, 0), (CLASS_ID, 3)]),
+			Error::<Runtime>::TokenIdNotFound
+		);
+
+		assert_ok!(NFTModule::transfer_batch(
+			Origin::signed(BOB),
+			ALICE,
+			vec![(CLASS_ID, 0), (CLASS_ID, 2)]
+		));
+		for token_id in [0, 2].iter() {
+			let event = Event::nft(crate::Event::TransferredToken(BOB, ALICE, CLASS_ID, *token_id));
+			assert!(System::events().iter().any(|record| record.event == event));
+			assert_eq!(
+				orml_nft::Pallet::<Runtime>::tokens(CLASS_ID, token_id).unwrap().owner,
+				ALICE
+			);
+		}
+
+		assert_eq!(
+			reserved_balance(&BOB),
+			1 * <Runtime as Config>::CreateTokenDeposit::get()
+		);
+		assert_eq!(
+			reserved_balance(&ALICE),
+			2 * <Runtime as Config>::CreateTokenDeposit::get()
+		);
+	});
+}
+
 #[test]
 fn burn_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -374,6 +544,120 @@ fn burn_should_fail() {
 	});
 }
 
+#[test]
+fn update_token_metadata_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Mutable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 2 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+
+		assert_noop!(
+			NFTModule::update_token_metadata(Origin::signed(BOB), (CLASS_ID, TOKEN_ID), vec![1, 2, 3]),
+			Error::<Runtime>::NoPermission
+		);
+
+		let class_account_balance = free_balance(&class_id_account());
+		assert_ok!(NFTModule::update_token_metadata(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			vec![1, 2, 3]
+		));
+		let event = Event::nft(crate::Event::UpdatedTokenMetadata(
+			class_id_account(),
+			CLASS_ID,
+			TOKEN_ID,
+		));
+		assert_eq!(last_event(), event);
+		let token_info = orml_nft::Pallet::<Runtime>::tokens(CLASS_ID, TOKEN_ID).unwrap();
+		assert_eq!(token_info.metadata, vec![1, 2, 3]);
+		assert_eq!(token_info.data.deposit, 120);
+		assert_eq!(free_balance(&class_id_account()), class_account_balance - 20);
+		assert_eq!(reserved_balance(&BOB), 120);
+
+		// only refund the deposit of metadata
+		assert_ok!(NFTModule::update_token_metadata(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			vec![]
+		));
+		let token_info = orml_nft::Pallet::<Runtime>::tokens(CLASS_ID, TOKEN_ID).unwrap();
+		assert_eq!(token_info.metadata, Vec::<u8>::new());
+		assert_eq!(token_info.data.deposit, 100);
+		assert_eq!(reserved_balance(&BOB), 100);
+		assert_eq!(free_balance(&BOB), 20);
+	});
+}
+
+#[test]
+fn update_token_metadata_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::MutableByTokenOwner)
+		));
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable.into())
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+
+		assert_noop!(
+			NFTModule::update_token_metadata(Origin::signed(ALICE), (CLASS_ID_NOT_EXIST + 1, TOKEN_ID), vec![2]),
+			Error::<Runtime>::ClassIdNotFound
+		);
+		assert_noop!(
+			NFTModule::update_token_metadata(Origin::signed(BOB), (CLASS_ID_NOT_EXIST, TOKEN_ID), vec![2]),
+			Error::<Runtime>::Immutable
+		);
+		assert_noop!(
+			NFTModule::update_token_metadata(Origin::signed(BOB), (CLASS_ID, TOKEN_ID_NOT_EXIST), vec![2]),
+			Error::<Runtime>::TokenIdNotFound
+		);
+		assert_noop!(
+			NFTModule::update_token_metadata(Origin::signed(class_id_account()), (CLASS_ID, TOKEN_ID), vec![2]),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTModule::update_token_metadata(
+			Origin::signed(BOB),
+			(CLASS_ID, TOKEN_ID),
+			vec![2]
+		));
+		assert_eq!(
+			orml_nft::Pallet::<Runtime>::tokens(CLASS_ID, TOKEN_ID)
+				.unwrap()
+				.metadata,
+			vec![2]
+		);
+	});
+}
+
 #[test]
 fn destroy_class_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -458,3 +742,1065 @@ fn destroy_class_should_fail() {
 		));
 	});
 }
+
+#[test]
+fn destroy_class_with_sweep_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			15 * <Runtime as Config>::CreateTokenDeposit::get()
+		)); // + 1500
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			class_id_account(),
+			CLASS_ID,
+			vec![1],
+			15
+		));
+		let alice_balance = free_balance(&ALICE);
+		let class_reserved_balance = reserved_balance(&class_id_account());
+
+		// sweep in batches of `MaxSweepBatch`
+		assert_ok!(NFTModule::destroy_class_with_sweep(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			ALICE
+		));
+		let event = Event::nft(crate::Event::SweptClassTokens(class_id_account(), CLASS_ID, 10, 1000));
+		assert_eq!(last_event(), event);
+		assert_eq!(
+			orml_nft::Pallet::<Runtime>::classes(CLASS_ID).unwrap().total_issuance,
+			5
+		);
+		assert_eq!(reserved_balance(&class_id_account()), class_reserved_balance - 1000);
+		assert_eq!(free_balance(&ALICE), alice_balance + 1000);
+
+		assert_ok!(NFTModule::destroy_class_with_sweep(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			ALICE
+		));
+		let event = Event::nft(crate::Event::SweptClassTokens(class_id_account(), CLASS_ID, 5, 500));
+		assert!(System::events().iter().any(|record| record.event == event));
+		let event = Event::nft(crate::Event::DestroyedClass(class_id_account(), CLASS_ID));
+		assert_eq!(last_event(), event);
+
+		assert_eq!(orml_nft::Pallet::<Runtime>::classes(CLASS_ID), None);
+		assert_eq!(orml_nft::Pallet::<Runtime>::tokens(CLASS_ID, TOKEN_ID), None);
+		assert_eq!(NFTModule::token_editions(CLASS_ID, TOKEN_ID), None);
+		assert_eq!(NFTModule::minted_editions(CLASS_ID), 0);
+		assert_eq!(free_balance(&class_id_account()), 0);
+		assert_eq!(reserved_balance(&class_id_account()), 0);
+		assert_eq!(free_balance(&ALICE), 100000 + 1500);
+	});
+}
+
+#[test]
+fn destroy_class_with_sweep_should_fail() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			1 * <Runtime as Config>::CreateTokenDeposit::get()
+		)); // + 100
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+
+		assert_noop!(
+			NFTModule::destroy_class_with_sweep(Origin::signed(class_id_account()), CLASS_ID_NOT_EXIST, ALICE),
+			Error::<Runtime>::ClassIdNotFound
+		);
+		assert_noop!(
+			NFTModule::destroy_class_with_sweep(Origin::signed(BOB), CLASS_ID, ALICE),
+			Error::<Runtime>::NoPermission
+		);
+		// the token owned by BOB can not be swept
+		assert_noop!(
+			NFTModule::destroy_class_with_sweep(Origin::signed(class_id_account()), CLASS_ID, ALICE),
+			Error::<Runtime>::CannotDestroyClass
+		);
+	});
+}
+
+#[test]
+fn destroy_class_with_sweep_should_respect_burnable() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable.into())
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			1 * <Runtime as Config>::CreateTokenDeposit::get()
+		)); // + 100
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			class_id_account(),
+			CLASS_ID,
+			vec![1],
+			1
+		));
+
+		assert_noop!(
+			NFTModule::destroy_class_with_sweep(Origin::signed(class_id_account()), CLASS_ID, ALICE),
+			Error::<Runtime>::NonBurnable
+		);
+	});
+}
+
+#[test]
+fn destroy_class_with_sweep_should_respect_locks() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_ok!(Balances::deposit_into_existing(
+			&class_id_account(),
+			2 * <Runtime as Config>::CreateTokenDeposit::get()
+		)); // + 200
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			class_id_account(),
+			CLASS_ID,
+			vec![1],
+			1
+		));
+		assert_ok!(NFTModule::mint_with_lock(
+			Origin::signed(class_id_account()),
+			class_id_account(),
+			CLASS_ID,
+			vec![1],
+			1,
+			10
+		));
+
+		// the locked token can not be swept
+		assert_noop!(
+			NFTModule::destroy_class_with_sweep(Origin::signed(class_id_account()), CLASS_ID, ALICE),
+			Error::<Runtime>::TokenLocked
+		);
+
+		System::set_block_number(10);
+		assert_ok!(NFTModule::destroy_class_with_sweep(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			ALICE
+		));
+		assert_eq!(NFTModule::token_locks(CLASS_ID, 1), None);
+		assert_eq!(orml_nft::Pallet::<Runtime>::classes(CLASS_ID), None);
+	});
+}
+
+#[test]
+fn transfer_class_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+
+		assert_noop!(
+			NFTModule::transfer_class(Origin::signed(ALICE), CLASS_ID, ALICE, BOB),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::transfer_class(Origin::signed(class_id_account()), CLASS_ID_NOT_EXIST, ALICE, BOB),
+			Error::<Runtime>::ClassIdNotFound
+		);
+		assert_noop!(
+			NFTModule::transfer_class(Origin::signed(class_id_account()), CLASS_ID, BOB, ALICE),
+			pallet_proxy::Error::<Runtime>::NotFound
+		);
+
+		assert_ok!(NFTModule::transfer_class(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			ALICE,
+			BOB
+		));
+		let event = Event::nft(crate::Event::TransferredClass(ALICE, BOB, CLASS_ID));
+		assert_eq!(last_event(), event);
+
+		let proxies = pallet_proxy::Pallet::<Runtime>::proxies(class_id_account()).0;
+		assert_eq!(proxies.len(), 1);
+		assert_eq!(proxies[0].delegate, BOB);
+	});
+}
+
+#[test]
+fn set_class_roles_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		let _ = Balances::deposit_creating(&BOB, 1000);
+
+		let roles = ClassRoles {
+			minter: Some(BOB),
+			freezer: Some(ALICE),
+		};
+		assert_noop!(
+			NFTModule::set_class_roles(Origin::signed(BOB), CLASS_ID, roles.clone()),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::mint(Origin::signed(BOB), BOB, CLASS_ID, vec![1], 1),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(NFTModule::set_class_roles(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			roles.clone()
+		));
+		let event = Event::nft(crate::Event::ClassRolesUpdated(CLASS_ID, roles.clone()));
+		assert_eq!(last_event(), event);
+		assert_eq!(NFTModule::class_roles(CLASS_ID), roles);
+
+		// minter can mint, but can not set properties of tokens
+		assert_ok!(NFTModule::mint(Origin::signed(BOB), BOB, CLASS_ID, vec![1], 1));
+		assert_noop!(
+			NFTModule::set_token_properties(
+				Origin::signed(BOB),
+				(CLASS_ID, TOKEN_ID),
+				Some(Properties(ClassProperty::Burnable.into()))
+			),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTModule::set_token_properties(
+			Origin::signed(ALICE),
+			(CLASS_ID, TOKEN_ID),
+			Some(Properties(ClassProperty::Burnable.into()))
+		));
+
+		// freezer can not grant properties to soulbound token
+		assert_noop!(
+			NFTModule::set_token_properties(
+				Origin::signed(ALICE),
+				(CLASS_ID, TOKEN_ID),
+				Some(Properties(ClassProperty::Transferable | ClassProperty::Burnable))
+			),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::set_token_properties(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID), None),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTModule::set_token_properties(
+			Origin::signed(ALICE),
+			(CLASS_ID, TOKEN_ID),
+			Some(Properties(Default::default()))
+		));
+		assert_noop!(
+			NFTModule::set_token_properties(
+				Origin::signed(ALICE),
+				(CLASS_ID, TOKEN_ID),
+				Some(Properties(ClassProperty::Burnable.into()))
+			),
+			Error::<Runtime>::NoPermission
+		);
+		// class owner can
+		assert_ok!(NFTModule::set_token_properties(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			None
+		));
+		assert_eq!(NFTModule::token_properties(CLASS_ID, TOKEN_ID), None);
+
+		// roles can not destroy class
+		assert_noop!(
+			NFTModule::destroy_class(Origin::signed(BOB), CLASS_ID, BOB),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(NFTModule::set_class_roles(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Default::default()
+		));
+		assert_eq!(NFTModule::class_roles(CLASS_ID), Default::default());
+		assert_eq!(Roles::<Runtime>::contains_key(CLASS_ID), false);
+	});
+}
+
+#[test]
+fn nft_minter_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		let _ = Balances::deposit_creating(&BOB, 1000);
+
+		assert_noop!(
+			<NFTModule as NFTMinter<AccountId>>::mint(&BOB, &ALICE, CLASS_ID, vec![1], 2),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(NFTModule::set_class_roles(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			ClassRoles {
+				minter: Some(BOB),
+				freezer: None,
+			}
+		));
+		assert_noop!(
+			<NFTModule as NFTMinter<AccountId>>::mint(&BOB, &ALICE, CLASS_ID_NOT_EXIST, vec![1], 2),
+			Error::<Runtime>::ClassIdNotFound
+		);
+		assert_eq!(
+			<NFTModule as NFTMinter<AccountId>>::mint(&BOB, &ALICE, CLASS_ID, vec![1], 2),
+			Ok(vec![0, 1])
+		);
+		let event = Event::nft(crate::Event::MintedToken(BOB, ALICE, CLASS_ID, 2));
+		assert_eq!(last_event(), event);
+		assert_eq!(
+			reserved_balance(&ALICE),
+			2 * <Runtime as Config>::CreateTokenDeposit::get()
+		);
+		assert_eq!(NFTModule::owner((CLASS_ID, TOKEN_ID)), Some(ALICE));
+	});
+}
+
+#[test]
+fn position_nft_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let dex_id = PalletId(*b"aca/dexm");
+		let _ = Balances::deposit_creating(&BOB, 1000);
+
+		assert_eq!(
+			<NFTModule as PositionNFT<AccountId>>::mint_position(dex_id, &ALICE, vec![1]),
+			Ok(TOKEN_ID)
+		);
+		assert_eq!(NFTModule::position_classes(dex_id), Some(CLASS_ID));
+		assert_eq!(
+			orml_nft::Pallet::<Runtime>::classes(CLASS_ID).map(|class_info| class_info.owner),
+			Some(dex_id.into_account())
+		);
+		assert_eq!(reserved_balance(&ALICE), <Runtime as Config>::CreateTokenDeposit::get());
+		assert_eq!(
+			<NFTModule as PositionNFT<AccountId>>::position_owner(dex_id, TOKEN_ID),
+			Some(ALICE)
+		);
+
+		// the position can be transferred, but only be burned by the module
+		assert_noop!(
+			NFTModule::burn(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NonBurnable
+		);
+		assert_ok!(NFTModule::transfer(Origin::signed(ALICE), BOB, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(
+			<NFTModule as PositionNFT<AccountId>>::position_owner(dex_id, TOKEN_ID),
+			Some(BOB)
+		);
+
+		assert_noop!(
+			<NFTModule as PositionNFT<AccountId>>::burn_position(PalletId(*b"aca/none"), &BOB, TOKEN_ID),
+			Error::<Runtime>::ClassIdNotFound
+		);
+		assert_noop!(
+			<NFTModule as PositionNFT<AccountId>>::burn_position(dex_id, &ALICE, TOKEN_ID),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(<NFTModule as PositionNFT<AccountId>>::burn_position(
+			dex_id, &BOB, TOKEN_ID
+		));
+		let event = Event::nft(crate::Event::BurnedToken(BOB, CLASS_ID, TOKEN_ID));
+		assert_eq!(last_event(), event);
+		assert_eq!(reserved_balance(&BOB), 0);
+		assert_eq!(
+			<NFTModule as PositionNFT<AccountId>>::position_owner(dex_id, TOKEN_ID),
+			None
+		);
+	});
+}
+
+#[test]
+fn set_royalty_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NFTModule::set_royalty(
+				Origin::signed(class_id_account()),
+				CLASS_ID,
+				Some((ALICE, Permill::from_percent(5)))
+			),
+			Error::<Runtime>::ClassIdNotFound
+		);
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_noop!(
+			NFTModule::set_royalty(Origin::signed(BOB), CLASS_ID, Some((ALICE, Permill::from_percent(5)))),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(NFTModule::set_royalty(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Some((ALICE, Permill::from_percent(5)))
+		));
+		assert_eq!(NFTModule::royalties(CLASS_ID), Some((ALICE, Permill::from_percent(5))));
+		let event = Event::nft(crate::Event::RoyaltyUpdated(
+			CLASS_ID,
+			Some((ALICE, Permill::from_percent(5))),
+		));
+		assert_eq!(last_event(), event);
+
+		assert_ok!(NFTModule::set_royalty(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			None
+		));
+		assert_eq!(NFTModule::royalties(CLASS_ID), None);
+	});
+}
+
+#[test]
+fn max_token_count_and_editions_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 3 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_noop!(
+			NFTModule::set_max_token_count(Origin::signed(BOB), CLASS_ID, 2),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTModule::set_max_token_count(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			2
+		));
+		assert_eq!(NFTModule::max_token_counts(CLASS_ID), Some(2));
+		let event = Event::nft(crate::Event::MaxTokenCountUpdated(CLASS_ID, 2));
+		assert_eq!(last_event(), event);
+
+		// the max token count can not be raised
+		assert_noop!(
+			NFTModule::set_max_token_count(Origin::signed(class_id_account()), CLASS_ID, 3),
+			Error::<Runtime>::InvalidMaxTokenCount
+		);
+
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+		assert_eq!(NFTModule::token_editions(CLASS_ID, TOKEN_ID), Some(1));
+		assert_ok!(NFTModule::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NFTModule::token_editions(CLASS_ID, TOKEN_ID), None);
+
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+		assert_eq!(NFTModule::token_editions(CLASS_ID, TOKEN_ID + 1), Some(2));
+		assert_eq!(NFTModule::minted_editions(CLASS_ID), 2);
+
+		// burned tokens are counted as minted editions
+		assert_noop!(
+			NFTModule::mint(Origin::signed(class_id_account()), BOB, CLASS_ID, vec![1], 1),
+			Error::<Runtime>::ExceededMaxTokenCount
+		);
+		assert_noop!(
+			NFTModule::set_max_token_count(Origin::signed(class_id_account()), CLASS_ID, 1),
+			Error::<Runtime>::InvalidMaxTokenCount
+		);
+	});
+}
+
+#[test]
+fn migrate_to_minted_editions_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 3 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			2
+		));
+		// the class minted before the editions were counted
+		MintedEditions::<Runtime>::remove(CLASS_ID);
+
+		NFTModule::on_runtime_upgrade();
+		assert_eq!(NFTModule::minted_editions(CLASS_ID), 2);
+		assert!(UpgradedToMintedEditions::<Runtime>::get());
+		assert_noop!(
+			NFTModule::set_max_token_count(Origin::signed(class_id_account()), CLASS_ID, 1),
+			Error::<Runtime>::InvalidMaxTokenCount
+		);
+
+		// only migrate once
+		MintedEditions::<Runtime>::remove(CLASS_ID);
+		NFTModule::on_runtime_upgrade();
+		assert_eq!(NFTModule::minted_editions(CLASS_ID), 0);
+	});
+}
+
+#[test]
+fn set_token_properties_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable.into())
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 2 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			2
+		));
+
+		assert_noop!(
+			NFTModule::set_token_properties(
+				Origin::signed(BOB),
+				(CLASS_ID, TOKEN_ID),
+				Some(Properties(ClassProperty::Burnable.into()))
+			),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::set_token_properties(
+				Origin::signed(class_id_account()),
+				(CLASS_ID, 2),
+				Some(Properties(ClassProperty::Burnable.into()))
+			),
+			Error::<Runtime>::TokenIdNotFound
+		);
+
+		// soulbound token can only be burned
+		assert_ok!(NFTModule::set_token_properties(
+			Origin::signed(class_id_account()),
+			(CLASS_ID, TOKEN_ID),
+			Some(Properties(ClassProperty::Burnable.into()))
+		));
+		let event = Event::nft(crate::Event::TokenPropertiesUpdated(
+			CLASS_ID,
+			TOKEN_ID,
+			Some(Properties(ClassProperty::Burnable.into())),
+		));
+		assert_eq!(last_event(), event);
+		assert_noop!(
+			NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NonTransferable
+		);
+		assert_noop!(
+			NFTModule::burn(Origin::signed(BOB), (CLASS_ID, 1)),
+			Error::<Runtime>::NonBurnable
+		);
+		assert_ok!(NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, 1)));
+		assert_ok!(NFTModule::burn(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NFTModule::token_properties(CLASS_ID, TOKEN_ID), None);
+
+		// listed token can not be updated
+		assert_ok!(NFTModule::list_token(
+			Origin::signed(ALICE),
+			(CLASS_ID, 1),
+			1000,
+			NATIVE_CURRENCY_ID
+		));
+		assert_noop!(
+			NFTModule::set_token_properties(
+				Origin::signed(class_id_account()),
+				(CLASS_ID, 1),
+				Some(Properties(ClassProperty::Burnable.into()))
+			),
+			Error::<Runtime>::TokenIsListed
+		);
+	});
+}
+
+#[test]
+fn transfer_with_payment_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+		let class_account_balance = free_balance(&class_id_account());
+		assert_ok!(NFTModule::set_royalty(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Some((class_id_account(), Permill::from_percent(10)))
+		));
+		let alice_balance = free_balance(&ALICE);
+		let bob_balance = free_balance(&BOB);
+
+		assert_ok!(NFTModule::transfer_with_payment(
+			&BOB,
+			&ALICE,
+			(CLASS_ID, TOKEN_ID),
+			NATIVE_CURRENCY_ID,
+			1000
+		));
+		assert_eq!(
+			orml_nft::Pallet::<Runtime>::tokens(CLASS_ID, TOKEN_ID).unwrap().owner,
+			ALICE
+		);
+		assert_eq!(free_balance(&class_id_account()), class_account_balance + 100);
+		assert_eq!(free_balance(&ALICE), alice_balance - 1000);
+		assert_eq!(free_balance(&BOB), bob_balance + 900);
+		let event = Event::nft(crate::Event::RoyaltyPaid(
+			CLASS_ID,
+			TOKEN_ID,
+			class_id_account(),
+			NATIVE_CURRENCY_ID,
+			100,
+		));
+		assert!(System::events().iter().any(|record| record.event == event));
+	});
+}
+
+#[test]
+fn list_and_unlist_token_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+
+		assert_noop!(
+			NFTModule::list_token(Origin::signed(BOB), (CLASS_ID, TOKEN_ID), 0, NATIVE_CURRENCY_ID),
+			Error::<Runtime>::InvalidPrice
+		);
+		assert_noop!(
+			NFTModule::list_token(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID), 1000, NATIVE_CURRENCY_ID),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTModule::list_token(
+			Origin::signed(BOB),
+			(CLASS_ID, TOKEN_ID),
+			1000,
+			NATIVE_CURRENCY_ID
+		));
+		let event = Event::nft(crate::Event::ListedToken(
+			BOB,
+			CLASS_ID,
+			TOKEN_ID,
+			NATIVE_CURRENCY_ID,
+			1000,
+		));
+		assert_eq!(last_event(), event);
+		assert_eq!(
+			NFTModule::listings(CLASS_ID, TOKEN_ID),
+			Some((BOB, NATIVE_CURRENCY_ID, 1000))
+		);
+		assert_eq!(
+			orml_nft::Pallet::<Runtime>::tokens(CLASS_ID, TOKEN_ID).unwrap().owner,
+			NFTModule::account_id()
+		);
+
+		assert_noop!(
+			NFTModule::unlist_token(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_ok!(NFTModule::unlist_token(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)));
+		let event = Event::nft(crate::Event::UnlistedToken(BOB, CLASS_ID, TOKEN_ID));
+		assert_eq!(last_event(), event);
+		assert_eq!(NFTModule::listings(CLASS_ID, TOKEN_ID), None);
+		assert_eq!(
+			orml_nft::Pallet::<Runtime>::tokens(CLASS_ID, TOKEN_ID).unwrap().owner,
+			BOB
+		);
+		assert_noop!(
+			NFTModule::unlist_token(Origin::signed(BOB), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NotListed
+		);
+	});
+}
+
+#[test]
+fn buy_token_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			1
+		));
+		assert_ok!(NFTModule::set_royalty(
+			Origin::signed(class_id_account()),
+			CLASS_ID,
+			Some((class_id_account(), Permill::from_percent(10)))
+		));
+
+		assert_noop!(
+			NFTModule::buy_token(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NotListed
+		);
+		assert_ok!(NFTModule::list_token(
+			Origin::signed(BOB),
+			(CLASS_ID, TOKEN_ID),
+			1000,
+			NATIVE_CURRENCY_ID
+		));
+
+		let class_account_balance = free_balance(&class_id_account());
+		let alice_balance = free_balance(&ALICE);
+		let bob_balance = free_balance(&BOB);
+
+		assert_ok!(NFTModule::buy_token(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID)));
+		let event = Event::nft(crate::Event::SoldToken(
+			BOB,
+			ALICE,
+			CLASS_ID,
+			TOKEN_ID,
+			NATIVE_CURRENCY_ID,
+			1000,
+		));
+		assert_eq!(last_event(), event);
+		assert_eq!(NFTModule::listings(CLASS_ID, TOKEN_ID), None);
+		assert_eq!(
+			orml_nft::Pallet::<Runtime>::tokens(CLASS_ID, TOKEN_ID).unwrap().owner,
+			ALICE
+		);
+		assert_eq!(free_balance(&class_id_account()), class_account_balance + 100);
+		assert_eq!(free_balance(&ALICE), alice_balance - 1000);
+		assert_eq!(free_balance(&BOB), bob_balance + 900);
+		assert_noop!(
+			NFTModule::buy_token(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NotListed
+		);
+	});
+}
+
+#[test]
+fn approve_and_transfer_from_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 2 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			2
+		));
+
+		assert_noop!(
+			NFTModule::approve(Origin::signed(ALICE), Some(ALICE), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			NFTModule::approve(Origin::signed(BOB), Some(BOB), (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::ApprovalToOwner
+		);
+		assert_noop!(
+			NFTModule::approve(Origin::signed(BOB), Some(ALICE), (CLASS_ID, 2)),
+			Error::<Runtime>::TokenIdNotFound
+		);
+		assert_noop!(
+			NFTModule::transfer_from(Origin::signed(ALICE), BOB, ALICE, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(NFTModule::approve(
+			Origin::signed(BOB),
+			Some(ALICE),
+			(CLASS_ID, TOKEN_ID)
+		));
+		let event = Event::nft(crate::Event::ApprovedToken(BOB, CLASS_ID, TOKEN_ID, Some(ALICE)));
+		assert_eq!(last_event(), event);
+		assert_eq!(NFTModule::approved_of((CLASS_ID, TOKEN_ID)), Some(ALICE));
+
+		// the approval covers the approved token only
+		assert_noop!(
+			NFTModule::transfer_from(Origin::signed(ALICE), BOB, ALICE, (CLASS_ID, 1)),
+			Error::<Runtime>::NoPermission
+		);
+		// `from` must be the owner
+		assert_noop!(
+			NFTModule::transfer_from(Origin::signed(ALICE), ALICE, BOB, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(NFTModule::transfer_from(
+			Origin::signed(ALICE),
+			BOB,
+			ALICE,
+			(CLASS_ID, TOKEN_ID)
+		));
+		let event = Event::nft(crate::Event::TransferredToken(BOB, ALICE, CLASS_ID, TOKEN_ID));
+		assert_eq!(last_event(), event);
+		assert_eq!(
+			reserved_balance(&ALICE),
+			1 * <Runtime as Config>::CreateTokenDeposit::get()
+		);
+		assert_eq!(NFTModule::class_balances(&ALICE, CLASS_ID), 1);
+		assert_eq!(NFTModule::class_balances(&BOB, CLASS_ID), 1);
+
+		// the approval is cleared once transferred
+		assert_eq!(NFTModule::approved_of((CLASS_ID, TOKEN_ID)), None);
+		assert_eq!(TokenApprovals::<Runtime>::get(CLASS_ID, TOKEN_ID), None);
+
+		// revoke
+		assert_ok!(NFTModule::approve(
+			Origin::signed(ALICE),
+			Some(BOB),
+			(CLASS_ID, TOKEN_ID)
+		));
+		assert_ok!(NFTModule::approve(Origin::signed(ALICE), None, (CLASS_ID, TOKEN_ID)));
+		let event = Event::nft(crate::Event::ApprovedToken(ALICE, CLASS_ID, TOKEN_ID, None));
+		assert_eq!(last_event(), event);
+		assert_noop!(
+			NFTModule::transfer_from(Origin::signed(BOB), ALICE, BOB, (CLASS_ID, TOKEN_ID)),
+			Error::<Runtime>::NoPermission
+		);
+
+		// the approval is void once the owner changes by transfer
+		assert_ok!(NFTModule::approve(
+			Origin::signed(ALICE),
+			Some(BOB),
+			(CLASS_ID, TOKEN_ID)
+		));
+		assert_ok!(NFTModule::transfer(Origin::signed(ALICE), BOB, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NFTModule::approved_of((CLASS_ID, TOKEN_ID)), None);
+	});
+}
+
+#[test]
+fn set_approval_for_all_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 2 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			2
+		));
+
+		assert_noop!(
+			NFTModule::set_approval_for_all(Origin::signed(BOB), CLASS_ID_NOT_EXIST, ALICE, true),
+			Error::<Runtime>::ClassIdNotFound
+		);
+		assert_noop!(
+			NFTModule::set_approval_for_all(Origin::signed(BOB), CLASS_ID, BOB, true),
+			Error::<Runtime>::ApprovalToOwner
+		);
+
+		assert_ok!(NFTModule::set_approval_for_all(
+			Origin::signed(BOB),
+			CLASS_ID,
+			ALICE,
+			true
+		));
+		let event = Event::nft(crate::Event::ApprovedForAll(BOB, CLASS_ID, ALICE, true));
+		assert_eq!(last_event(), event);
+		assert!(NFTModule::is_operator_of(&BOB, CLASS_ID, &ALICE));
+
+		// the operator can transfer and approve any token of owner
+		assert_ok!(NFTModule::transfer_from(
+			Origin::signed(ALICE),
+			BOB,
+			ALICE,
+			(CLASS_ID, TOKEN_ID)
+		));
+		assert_ok!(NFTModule::approve(Origin::signed(ALICE), Some(ALICE), (CLASS_ID, 1)));
+		assert_eq!(NFTModule::approved_of((CLASS_ID, 1)), Some(ALICE));
+		assert_eq!(NFTModule::class_balances(&ALICE, CLASS_ID), 1);
+		assert_eq!(NFTModule::class_balances(&BOB, CLASS_ID), 1);
+
+		assert_ok!(NFTModule::set_approval_for_all(
+			Origin::signed(BOB),
+			CLASS_ID,
+			ALICE,
+			false
+		));
+		let event = Event::nft(crate::Event::ApprovedForAll(BOB, CLASS_ID, ALICE, false));
+		assert_eq!(last_event(), event);
+		assert!(!NFTModule::is_operator_of(&BOB, CLASS_ID, &ALICE));
+		assert_noop!(
+			NFTModule::approve(Origin::signed(ALICE), None, (CLASS_ID, 1)),
+			Error::<Runtime>::NoPermission
+		);
+	});
+}
+
+#[test]
+fn class_balances_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 3 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			3
+		));
+		assert_eq!(NFTModule::class_balances(&BOB, CLASS_ID), 3);
+		assert_eq!(NFTModule::balance_of_class(&BOB, CLASS_ID), 3);
+
+		assert_ok!(NFTModule::transfer(Origin::signed(BOB), BOB, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NFTModule::class_balances(&BOB, CLASS_ID), 3);
+
+		assert_ok!(NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NFTModule::class_balances(&BOB, CLASS_ID), 2);
+		assert_eq!(NFTModule::class_balances(&ALICE, CLASS_ID), 1);
+
+		assert_ok!(NFTModule::approve(
+			Origin::signed(ALICE),
+			Some(BOB),
+			(CLASS_ID, TOKEN_ID)
+		));
+		assert_ok!(NFTModule::burn(Origin::signed(ALICE), (CLASS_ID, TOKEN_ID)));
+		assert_eq!(NFTModule::class_balances(&ALICE, CLASS_ID), 0);
+		assert!(!ClassBalances::<Runtime>::contains_key(&ALICE, CLASS_ID));
+		assert_eq!(TokenApprovals::<Runtime>::get(CLASS_ID, TOKEN_ID), None);
+	});
+}
+
+#[test]
+fn migrate_to_class_balances_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(ALICE),
+			vec![1],
+			Properties(ClassProperty::Transferable | ClassProperty::Burnable)
+		));
+		assert_eq!(
+			Balances::deposit_into_existing(&class_id_account(), 3 * <Runtime as Config>::CreateTokenDeposit::get())
+				.is_ok(),
+			true
+		);
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_id_account()),
+			BOB,
+			CLASS_ID,
+			vec![1],
+			3
+		));
+		assert_ok!(NFTModule::transfer(Origin::signed(BOB), ALICE, (CLASS_ID, TOKEN_ID)));
+
+		// the tokens minted before the balances were counted, and a stale
+		// balance counted already
+		UpgradedToClassBalances::<Runtime>::kill();
+		ClassBalances::<Runtime>::remove(&BOB, CLASS_ID);
+
+		NFTModule::on_runtime_upgrade();
+		assert!(UpgradedToClassBalances::<Runtime>::get());
+		assert_eq!(NFTModule::class_balances(&BOB, CLASS_ID), 2);
+		// recounted rather than accumulated
+		assert_eq!(NFTModule::class_balances(&ALICE, CLASS_ID), 1);
+
+		// only migrate once
+		ClassBalances::<Runtime>::remove(&BOB, CLASS_ID);
+		NFTModule::on_runtime_upgrade();
+		assert_eq!(NFTModule::class_balances(&BOB, CLASS_ID), 0);
+	});
+}
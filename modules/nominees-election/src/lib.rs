@@ -25,7 +25,7 @@ use frame_system::pallet_prelude::*;
 use orml_traits::{BasicCurrency, BasicLockableCurrency, Contains};
 use primitives::{Balance, EraIndex};
 use sp_runtime::{
-	traits::{MaybeDisplay, MaybeSerializeDeserialize, Member, Zero},
+	traits::{MaybeDisplay, MaybeSerializeDeserialize, Member, StaticLookup, Zero},
 	RuntimeDebug, SaturatedConversion,
 };
 use sp_std::{fmt::Debug, prelude::*};
@@ -114,6 +114,29 @@ impl BondingLedger {
 	}
 }
 
+/// The staking performance of a relaychain validator.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, Default)]
+pub struct ValidatorPerformance {
+	/// The era of the latest report.
+	pub last_reported_era: EraIndex,
+	/// The era points earned in the latest reported era.
+	pub era_points: u32,
+	/// The total count of reported slashes.
+	pub slash_count: u32,
+	/// The count of consecutive reported eras the validator is
+	/// underperforming.
+	pub underperforming_eras: u32,
+}
+
+/// The delegated voting power of a registered delegate.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, Default)]
+pub struct DelegateInfo {
+	/// The total active bonded delegated to the delegate.
+	pub delegated: Balance,
+	/// The count of delegators.
+	pub delegators: u32,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -131,6 +154,17 @@ pub mod module {
 		#[pallet::constant]
 		type MaxUnlockingChunks: Get<u32>;
 		type RelaychainValidatorFilter: Contains<Self::PolkadotAccountId>;
+		/// The origin which may report staking performances of validators and
+		/// rebalance nominees.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+		/// The validator earned era points less than this is underperforming
+		/// in that era.
+		#[pallet::constant]
+		type MinEraPoints: Get<u32>;
+		/// The validator underperforming for this count of consecutive eras
+		/// will not be elected as nominee.
+		#[pallet::constant]
+		type MaxUnderperformingEras: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -141,6 +175,13 @@ pub mod module {
 		NoBonded,
 		NoUnlockChunk,
 		InvalidRelaychainValidator,
+		StaleReport,
+		AlreadyDelegate,
+		NotDelegate,
+		AlreadyDelegated,
+		NotDelegated,
+		InvalidDelegate,
+		DelegateHasDelegators,
 	}
 
 	#[pallet::storage]
@@ -163,6 +204,25 @@ pub mod module {
 	#[pallet::getter(fn current_era)]
 	pub type CurrentEra<T: Config> = StorageValue<_, EraIndex, ValueQuery>;
 
+	/// The staking performances of relaychain validators.
+	#[pallet::storage]
+	#[pallet::getter(fn validator_performances)]
+	pub type ValidatorPerformances<T: Config> =
+		StorageMap<_, Twox64Concat, T::PolkadotAccountId, ValidatorPerformance, ValueQuery>;
+
+	/// The registered delegates who vote with the voting power delegated by
+	/// others.
+	/// Delegate => DelegateInfo
+	#[pallet::storage]
+	#[pallet::getter(fn delegates)]
+	pub type Delegates<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, DelegateInfo, OptionQuery>;
+
+	/// The delegate of delegators.
+	/// Delegator => Delegate
+	#[pallet::storage]
+	#[pallet::getter(fn delegations)]
+	pub type Delegations<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::AccountId, OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -187,9 +247,8 @@ pub mod module {
 					Error::<T>::BelowMinBondThreshold
 				);
 				ledger.total += extra;
-				let old_nominations = Self::nominations(&who);
 
-				Self::update_votes(old_active, &old_nominations, ledger.active, &old_nominations);
+				Self::on_active_changed(&who, old_active, ledger.active);
 				Self::update_ledger(&who, &ledger);
 			}
 			Ok(().into())
@@ -220,9 +279,8 @@ pub mod module {
 				// Note: in case there is no current era it is fine to bond one era more.
 				let era = Self::current_era() + T::BondingDuration::get();
 				ledger.unlocking.push(UnlockChunk { value: amount, era });
-				let old_nominations = Self::nominations(&who);
 
-				Self::update_votes(old_active, &old_nominations, ledger.active, &old_nominations);
+				Self::on_active_changed(&who, old_active, ledger.active);
 				Self::update_ledger(&who, &ledger);
 			}
 			Ok(().into())
@@ -235,10 +293,9 @@ pub mod module {
 			let ledger = Self::ledger(&who);
 			ensure!(!ledger.unlocking.is_empty(), Error::<T>::NoUnlockChunk,);
 			let old_active = ledger.active;
-			let old_nominations = Self::nominations(&who);
 			let ledger = ledger.rebond(amount);
 
-			Self::update_votes(old_active, &old_nominations, ledger.active, &old_nominations);
+			Self::on_active_changed(&who, old_active, ledger.active);
 			Self::update_ledger(&who, &ledger);
 			Ok(().into())
 		}
@@ -268,8 +325,12 @@ pub mod module {
 				Error::<T>::InvalidTargetsLength,
 			);
 
+			ensure!(Self::delegations(&who).is_none(), Error::<T>::AlreadyDelegated);
 			let ledger = Self::ledger(&who);
-			ensure!(!ledger.total.is_zero(), Error::<T>::NoBonded);
+			ensure!(
+				!ledger.total.is_zero() || Self::delegates(&who).is_some(),
+				Error::<T>::NoBonded
+			);
 
 			let mut targets = targets;
 			targets.sort();
@@ -283,9 +344,9 @@ pub mod module {
 			}
 
 			let old_nominations = Self::nominations(&who);
-			let old_active = Self::ledger(&who).active;
+			let voting_power = Self::voting_power(&who);
 
-			Self::update_votes(old_active, &old_nominations, old_active, &targets);
+			Self::update_votes(voting_power, &old_nominations, voting_power, &targets);
 			Nominations::<T>::insert(&who, &targets);
 			Ok(().into())
 		}
@@ -296,10 +357,137 @@ pub mod module {
 			let who = ensure_signed(origin)?;
 
 			let old_nominations = Self::nominations(&who);
-			let old_active = Self::ledger(&who).active;
+			let voting_power = Self::voting_power(&who);
+
+			Self::update_votes(voting_power, &old_nominations, Zero::zero(), &[]);
+			Nominations::<T>::remove(&who);
+			Ok(().into())
+		}
+
+		/// Register as a delegate, who nominates with its own active bonded
+		/// and the active bonded delegated by others.
+		#[pallet::weight(10000)]
+		#[transactional]
+		pub fn register_delegate(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::delegates(&who).is_none(), Error::<T>::AlreadyDelegate);
+			ensure!(Self::delegations(&who).is_none(), Error::<T>::AlreadyDelegated);
+
+			Delegates::<T>::insert(&who, DelegateInfo::default());
+			Ok(().into())
+		}
+
+		/// Unregister the delegate, only allowed when no delegators.
+		#[pallet::weight(10000)]
+		#[transactional]
+		pub fn unregister_delegate(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let info = Self::delegates(&who).ok_or(Error::<T>::NotDelegate)?;
+			ensure!(info.delegators.is_zero(), Error::<T>::DelegateHasDelegators);
+
+			Delegates::<T>::remove(&who);
+			Ok(().into())
+		}
+
+		/// Delegate the voting power of the active bonded to a registered
+		/// delegate. The nominations of the caller will be removed.
+		///
+		/// - `delegate`: the registered delegate.
+		#[pallet::weight(10000)]
+		#[transactional]
+		pub fn delegate(
+			origin: OriginFor<T>,
+			delegate: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+			ensure!(
+				who != delegate && Self::delegates(&who).is_none(),
+				Error::<T>::InvalidDelegate
+			);
+			ensure!(Self::delegations(&who).is_none(), Error::<T>::AlreadyDelegated);
+			ensure!(Self::delegates(&delegate).is_some(), Error::<T>::NotDelegate);
+			let active = Self::ledger(&who).active;
+			ensure!(!active.is_zero(), Error::<T>::NoBonded);
 
-			Self::update_votes(old_active, &old_nominations, Zero::zero(), &[]);
+			Self::update_votes(active, &Self::nominations(&who), Zero::zero(), &[]);
 			Nominations::<T>::remove(&who);
+			Delegations::<T>::insert(&who, &delegate);
+			Delegates::<T>::mutate(&delegate, |maybe_info| {
+				if let Some(info) = maybe_info {
+					info.delegators = info.delegators.saturating_add(1);
+				}
+			});
+			Self::update_delegated(&delegate, Zero::zero(), active);
+			Ok(().into())
+		}
+
+		/// Revoke the delegation, the caller needs to nominate again to
+		/// vote.
+		#[pallet::weight(10000)]
+		#[transactional]
+		pub fn undelegate(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let delegate = Self::delegations(&who).ok_or(Error::<T>::NotDelegated)?;
+
+			Self::update_delegated(&delegate, Self::ledger(&who).active, Zero::zero());
+			Self::remove_delegation(&who, &delegate);
+			Ok(().into())
+		}
+
+		/// Report the staking results of relaychain validators in `era`.
+		/// Validators underperforming for `MaxUnderperformingEras` will not
+		/// be elected as nominees.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `era`: the relaychain era of the reports.
+		/// - `reports`: list of (validator, era_points, is_slashed).
+		#[pallet::weight((10000 as Weight).saturating_mul(reports.len() as Weight))]
+		#[transactional]
+		pub fn report_validator_performances(
+			origin: OriginFor<T>,
+			era: EraIndex,
+			reports: Vec<(T::PolkadotAccountId, u32, bool)>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			for (validator, era_points, is_slashed) in reports {
+				ValidatorPerformances::<T>::try_mutate(&validator, |performance| -> DispatchResult {
+					ensure!(
+						*performance == Default::default() || era > performance.last_reported_era,
+						Error::<T>::StaleReport
+					);
+
+					performance.last_reported_era = era;
+					performance.era_points = era_points;
+					if is_slashed {
+						// slashed validator should not be nominated immediately.
+						performance.slash_count = performance.slash_count.saturating_add(1);
+						performance.underperforming_eras = performance
+							.underperforming_eras
+							.saturating_add(1)
+							.max(T::MaxUnderperformingEras::get());
+					} else if era_points < T::MinEraPoints::get() {
+						performance.underperforming_eras = performance.underperforming_eras.saturating_add(1);
+					} else {
+						performance.underperforming_eras = Zero::zero();
+					}
+					Ok(())
+				})?;
+			}
+			Ok(().into())
+		}
+
+		/// Re-elect nominees immediately rather than waiting for next era,
+		/// to nominate away from underperforming validators.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(10000)]
+		#[transactional]
+		pub fn rebalance_nominees(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::rebalance();
 			Ok(().into())
 		}
 	}
@@ -333,10 +521,64 @@ impl<T: Config> Pallet<T> {
 			debug_assert!(false);
 		}
 
+		if let Some(delegate) = Self::delegations(who) {
+			Self::remove_delegation(who, &delegate);
+		}
+		Self::update_votes(Self::voting_power(who), &Self::nominations(who), Zero::zero(), &[]);
+
 		Ledger::<T>::remove(who);
 		Nominations::<T>::remove(who);
 	}
 
+	fn remove_delegation(delegator: &T::AccountId, delegate: &T::AccountId) {
+		Delegations::<T>::remove(delegator);
+		Delegates::<T>::mutate(delegate, |maybe_info| {
+			if let Some(info) = maybe_info {
+				info.delegators = info.delegators.saturating_sub(1);
+			}
+		});
+	}
+
+	/// The voting power of `who`, which is zero if delegated to others,
+	/// otherwise is the active bonded plus the delegated active bonded.
+	pub fn voting_power(who: &T::AccountId) -> Balance {
+		if Self::delegations(who).is_some() {
+			return Zero::zero();
+		}
+
+		let delegated = Self::delegates(who).map_or(Zero::zero(), |info| info.delegated);
+		Self::ledger(who).active.saturating_add(delegated)
+	}
+
+	/// Update the votes after the active bonded of `who` changed.
+	fn on_active_changed(who: &T::AccountId, old_active: Balance, new_active: Balance) {
+		if let Some(delegate) = Self::delegations(who) {
+			Self::update_delegated(&delegate, old_active, new_active);
+		} else {
+			let delegated = Self::delegates(who).map_or(Zero::zero(), |info| info.delegated);
+			let nominations = Self::nominations(who);
+			Self::update_votes(
+				old_active.saturating_add(delegated),
+				&nominations,
+				new_active.saturating_add(delegated),
+				&nominations,
+			);
+		}
+	}
+
+	/// Replace `old_amount` with `new_amount` in the delegated active bonded
+	/// of `delegate`, and update the votes of its nominations.
+	fn update_delegated(delegate: &T::AccountId, old_amount: Balance, new_amount: Balance) {
+		let old_power = Self::voting_power(delegate);
+		Delegates::<T>::mutate(delegate, |maybe_info| {
+			if let Some(info) = maybe_info {
+				info.delegated = info.delegated.saturating_sub(old_amount).saturating_add(new_amount);
+			}
+		});
+		let nominations = Self::nominations(delegate);
+		Self::update_votes(old_power, &nominations, Self::voting_power(delegate), &nominations);
+	}
+
 	fn update_votes(
 		old_active: Balance,
 		old_nominations: &[T::PolkadotAccountId],
@@ -356,8 +598,25 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Whether the validator has been underperforming for
+	/// `MaxUnderperformingEras`.
+	pub fn is_underperforming(validator: &T::PolkadotAccountId) -> bool {
+		Self::validator_performances(validator).underperforming_eras >= T::MaxUnderperformingEras::get()
+	}
+
+	/// The current nominees which are underperforming, they will be replaced
+	/// at the next rebalance.
+	pub fn underperforming_nominees() -> Vec<T::PolkadotAccountId> {
+		Self::nominees()
+			.into_iter()
+			.filter(|nominee| Self::is_underperforming(nominee))
+			.collect()
+	}
+
 	fn rebalance() {
-		let mut voters = Votes::<T>::iter().collect::<Vec<(T::PolkadotAccountId, Balance)>>();
+		let mut voters = Votes::<T>::iter()
+			.filter(|(validator, _)| !Self::is_underperforming(validator))
+			.collect::<Vec<(T::PolkadotAccountId, Balance)>>();
 
 		voters.sort_by(|a, b| b.1.cmp(&a.1));
 
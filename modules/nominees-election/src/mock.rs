@@ -24,6 +24,7 @@ use super::*;
 
 use crate as nominees;
 use frame_support::{construct_runtime, parameter_types};
+use frame_system::EnsureRoot;
 use orml_traits::parameter_type_with_key;
 use primitives::{Amount, CurrencyId, TokenSymbol};
 use sp_core::H256;
@@ -118,6 +119,8 @@ parameter_types! {
 	pub const BondingDuration: EraIndex = 4;
 	pub const NominateesCount: u32 = 5;
 	pub const MaxUnlockingChunks: u32 = 3;
+	pub const MinEraPoints: u32 = 100;
+	pub const MaxUnderperformingEras: u32 = 2;
 }
 
 pub struct MockRelaychainValidatorFilter;
@@ -138,6 +141,9 @@ impl Config for Runtime {
 	type NominateesCount = NominateesCount;
 	type MaxUnlockingChunks = MaxUnlockingChunks;
 	type RelaychainValidatorFilter = MockRelaychainValidatorFilter;
+	type UpdateOrigin = EnsureRoot<AccountId>;
+	type MinEraPoints = MinEraPoints;
+	type MaxUnderperformingEras = MaxUnderperformingEras;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
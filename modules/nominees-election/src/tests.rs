@@ -23,6 +23,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::*;
+use sp_runtime::traits::BadOrigin;
 
 #[test]
 fn bond_below_min_bond_threshold() {
@@ -234,6 +235,85 @@ fn rebalance_work() {
 	});
 }
 
+#[test]
+fn report_validator_performances_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NomineesElectionModule::report_validator_performances(Origin::signed(ALICE), 1, vec![(1, 50, false)]),
+			BadOrigin
+		);
+
+		assert_ok!(NomineesElectionModule::report_validator_performances(
+			Origin::root(),
+			1,
+			vec![(1, 50, false), (2, 200, false), (3, 200, true)]
+		));
+		assert_eq!(
+			NomineesElectionModule::validator_performances(1),
+			ValidatorPerformance {
+				last_reported_era: 1,
+				era_points: 50,
+				slash_count: 0,
+				underperforming_eras: 1,
+			}
+		);
+		assert_eq!(
+			NomineesElectionModule::validator_performances(2).underperforming_eras,
+			0
+		);
+		assert_eq!(
+			NomineesElectionModule::validator_performances(3),
+			ValidatorPerformance {
+				last_reported_era: 1,
+				era_points: 200,
+				slash_count: 1,
+				underperforming_eras: 2,
+			}
+		);
+		assert_eq!(NomineesElectionModule::is_underperforming(&1), false);
+		assert_eq!(NomineesElectionModule::is_underperforming(&3), true);
+
+		assert_noop!(
+			NomineesElectionModule::report_validator_performances(Origin::root(), 1, vec![(1, 50, false)]),
+			Error::<Runtime>::StaleReport
+		);
+
+		assert_ok!(NomineesElectionModule::report_validator_performances(
+			Origin::root(),
+			2,
+			vec![(1, 50, false), (3, 200, false)]
+		));
+		assert_eq!(NomineesElectionModule::is_underperforming(&1), true);
+		assert_eq!(NomineesElectionModule::is_underperforming(&3), false);
+	});
+}
+
+#[test]
+fn rebalance_skip_underperforming_validators() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 500));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![1, 2, 3]));
+		NomineesElectionModule::rebalance();
+		assert_eq!(NomineesElectionModule::nominees().len(), 3);
+
+		assert_ok!(NomineesElectionModule::report_validator_performances(
+			Origin::root(),
+			1,
+			vec![(2, 200, true)]
+		));
+		assert_eq!(NomineesElectionModule::underperforming_nominees(), vec![2]);
+
+		assert_noop!(
+			NomineesElectionModule::rebalance_nominees(Origin::signed(ALICE)),
+			BadOrigin
+		);
+		assert_ok!(NomineesElectionModule::rebalance_nominees(Origin::root()));
+		assert_eq!(NomineesElectionModule::nominees().len(), 2);
+		assert_eq!(NomineesElectionModule::nominees().contains(&2), false);
+		assert_eq!(NomineesElectionModule::underperforming_nominees(), vec![]);
+	});
+}
+
 #[test]
 fn update_votes_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -254,3 +334,101 @@ fn update_votes_work() {
 		assert_eq!(NomineesElectionModule::votes(4), 10);
 	});
 }
+
+#[test]
+fn register_and_unregister_delegate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			NomineesElectionModule::unregister_delegate(Origin::signed(ALICE)),
+			Error::<Runtime>::NotDelegate,
+		);
+		assert_ok!(NomineesElectionModule::register_delegate(Origin::signed(ALICE)));
+		assert_eq!(NomineesElectionModule::delegates(&ALICE), Some(Default::default()));
+		assert_noop!(
+			NomineesElectionModule::register_delegate(Origin::signed(ALICE)),
+			Error::<Runtime>::AlreadyDelegate,
+		);
+
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(BOB), 500));
+		assert_ok!(NomineesElectionModule::delegate(Origin::signed(BOB), ALICE));
+		assert_noop!(
+			NomineesElectionModule::unregister_delegate(Origin::signed(ALICE)),
+			Error::<Runtime>::DelegateHasDelegators,
+		);
+		assert_ok!(NomineesElectionModule::undelegate(Origin::signed(BOB)));
+		assert_ok!(NomineesElectionModule::unregister_delegate(Origin::signed(ALICE)));
+		assert_eq!(NomineesElectionModule::delegates(&ALICE), None);
+	});
+}
+
+#[test]
+fn delegate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(NomineesElectionModule::register_delegate(Origin::signed(ALICE)));
+		assert_noop!(
+			NomineesElectionModule::delegate(Origin::signed(ALICE), ALICE),
+			Error::<Runtime>::InvalidDelegate,
+		);
+		assert_noop!(
+			NomineesElectionModule::delegate(Origin::signed(ALICE), BOB),
+			Error::<Runtime>::InvalidDelegate,
+		);
+		assert_noop!(
+			NomineesElectionModule::delegate(Origin::signed(BOB), ALICE),
+			Error::<Runtime>::NoBonded,
+		);
+
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(ALICE), 100));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![1, 2]));
+		assert_ok!(NomineesElectionModule::bond(Origin::signed(BOB), 500));
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(BOB), vec![3]));
+		assert_eq!(NomineesElectionModule::votes(1), 100);
+		assert_eq!(NomineesElectionModule::votes(3), 500);
+
+		// the votes of delegator are moved to the nominations of delegate
+		assert_ok!(NomineesElectionModule::delegate(Origin::signed(BOB), ALICE));
+		assert_eq!(NomineesElectionModule::delegations(&BOB), Some(ALICE));
+		assert_eq!(
+			NomineesElectionModule::delegates(&ALICE),
+			Some(DelegateInfo {
+				delegated: 500,
+				delegators: 1,
+			})
+		);
+		assert_eq!(NomineesElectionModule::nominations(&BOB), vec![]);
+		assert_eq!(NomineesElectionModule::votes(1), 600);
+		assert_eq!(NomineesElectionModule::votes(2), 600);
+		assert_eq!(NomineesElectionModule::votes(3), 0);
+		assert_eq!(NomineesElectionModule::voting_power(&ALICE), 600);
+		assert_eq!(NomineesElectionModule::voting_power(&BOB), 0);
+		assert_noop!(
+			NomineesElectionModule::nominate(Origin::signed(BOB), vec![3]),
+			Error::<Runtime>::AlreadyDelegated,
+		);
+
+		// the changes of delegator's active bonded are applied to delegate
+		assert_ok!(NomineesElectionModule::unbond(Origin::signed(BOB), 200));
+		assert_eq!(NomineesElectionModule::delegates(&ALICE).unwrap().delegated, 300);
+		assert_eq!(NomineesElectionModule::votes(1), 400);
+
+		// delegate changes nominations with the delegated voting power
+		assert_ok!(NomineesElectionModule::nominate(Origin::signed(ALICE), vec![3]));
+		assert_eq!(NomineesElectionModule::votes(1), 0);
+		assert_eq!(NomineesElectionModule::votes(3), 400);
+
+		assert_ok!(NomineesElectionModule::undelegate(Origin::signed(BOB)));
+		assert_eq!(NomineesElectionModule::delegations(&BOB), None);
+		assert_eq!(
+			NomineesElectionModule::delegates(&ALICE),
+			Some(DelegateInfo {
+				delegated: 0,
+				delegators: 0,
+			})
+		);
+		assert_eq!(NomineesElectionModule::votes(3), 100);
+		assert_noop!(
+			NomineesElectionModule::undelegate(Origin::signed(BOB)),
+			Error::<Runtime>::NotDelegated,
+		);
+	});
+}
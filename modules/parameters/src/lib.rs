@@ -0,0 +1,146 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Parameters Module
+//!
+//! ## Overview
+//!
+//! Allows governance to update multiple risk parameters across modules in
+//! one atomic call. The batched calls are dispatched in a storage transaction
+//! and the cross-parameter invariants are checked after all of them have been
+//! applied, the whole batch is reverted if any call fails or any invariant is
+//! violated. `validate_parameters` performs the same checks as a dry run and
+//! always discards the changes.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	pallet_prelude::*,
+	traits::Filter,
+	transactional,
+	weights::{GetDispatchInfo, PostDispatchInfo},
+};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{traits::Dispatchable, DispatchResult, TransactionOutcome};
+use sp_std::prelude::*;
+use support::ParameterInvariants;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The overarching call type.
+		type Call: Parameter + Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo> + GetDispatchInfo;
+
+		/// The calls allowed to be batched, should only include the calls
+		/// which update parameters.
+		type ParameterCallFilter: Filter<<Self as Config>::Call>;
+
+		/// The invariants across parameters, checked after the batched calls
+		/// applied.
+		type Invariants: ParameterInvariants;
+
+		/// The max count of calls in a batch
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The count of calls exceeds the max batch size
+		TooManyCalls,
+		/// The call is not allowed to be batched
+		CallNotAllowed,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Parameters updated by batched calls. \[calls_count\]
+		ParametersUpdated(u32),
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Dispatch the calls updating parameters in a batch atomically, the
+		/// invariants across parameters are checked after all calls applied.
+		///
+		/// The dispatch origin of this call is used to dispatch the batched
+		/// calls, so it must be able to pass their origin checks.
+		///
+		/// - `calls`: the calls updating parameters.
+		#[pallet::weight({
+			let dispatch_weight = calls
+				.iter()
+				.fold(0 as Weight, |total, call| total.saturating_add(call.get_dispatch_info().weight));
+			(
+				T::WeightInfo::set_parameters(calls.len() as u32).saturating_add(dispatch_weight),
+				DispatchClass::Operational,
+			)
+		})]
+		#[transactional]
+		pub fn set_parameters(origin: OriginFor<T>, calls: Vec<<T as Config>::Call>) -> DispatchResultWithPostInfo {
+			let calls_count = calls.len() as u32;
+			Self::do_set_parameters(origin, calls)?;
+			Self::deposit_event(Event::ParametersUpdated(calls_count));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Dry run the batched calls and check the invariants, the changes are
+	/// always discarded.
+	pub fn validate_parameters(origin: OriginFor<T>, calls: Vec<<T as Config>::Call>) -> DispatchResult {
+		frame_support::storage::with_transaction(|| {
+			TransactionOutcome::Rollback(Self::do_set_parameters(origin, calls))
+		})
+	}
+
+	fn do_set_parameters(origin: OriginFor<T>, calls: Vec<<T as Config>::Call>) -> DispatchResult {
+		ensure!(calls.len() as u32 <= T::MaxBatchSize::get(), Error::<T>::TooManyCalls);
+
+		for call in calls {
+			ensure!(T::ParameterCallFilter::filter(&call), Error::<T>::CallNotAllowed);
+			call.dispatch(origin.clone()).map_err(|e| e.error)?;
+		}
+
+		T::Invariants::check_invariants()
+	}
+}
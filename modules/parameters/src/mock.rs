@@ -0,0 +1,141 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the parameters module.
+
+#![cfg(test)]
+
+use super::*;
+use codec::{Decode, Encode};
+use frame_support::{construct_runtime, parameter_types};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchError};
+
+pub type AccountId = u128;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = 1;
+pub const MIN_KEY: &[u8] = b":min";
+pub const MAX_KEY: &[u8] = b":max";
+
+mod parameters {
+	pub use super::super::*;
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+pub struct MockParameterCallFilter;
+impl Filter<Call> for MockParameterCallFilter {
+	fn filter(call: &Call) -> bool {
+		matches!(call, Call::System(frame_system::Call::set_storage(..)))
+	}
+}
+
+pub fn get_param(key: &[u8]) -> u32 {
+	sp_io::storage::get(key)
+		.and_then(|v| u32::decode(&mut &v[..]).ok())
+		.unwrap_or_default()
+}
+
+pub fn set_param_call(key: &[u8], value: u32) -> Call {
+	Call::System(frame_system::Call::set_storage(vec![(key.to_vec(), value.encode())]))
+}
+
+pub struct MockInvariants;
+impl ParameterInvariants for MockInvariants {
+	fn check_invariants() -> DispatchResult {
+		ensure!(
+			get_param(MIN_KEY) <= get_param(MAX_KEY),
+			DispatchError::Other("InvalidParameters")
+		);
+		Ok(())
+	}
+}
+
+parameter_types! {
+	pub const MaxBatchSize: u32 = 3;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type ParameterCallFilter = MockParameterCallFilter;
+	type Invariants = MockInvariants;
+	type MaxBatchSize = MaxBatchSize;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		ParametersModule: parameters::{Pallet, Call, Event<T>},
+	}
+);
+
+pub struct ExtBuilder;
+
+impl Default for ExtBuilder {
+	fn default() -> Self {
+		ExtBuilder
+	}
+}
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		t.into()
+	}
+}
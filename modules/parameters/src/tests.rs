@@ -0,0 +1,117 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the parameters module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::{traits::BadOrigin, DispatchError};
+
+#[test]
+fn set_parameters_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ParametersModule::set_parameters(
+			Origin::root(),
+			vec![set_param_call(MAX_KEY, 20), set_param_call(MIN_KEY, 10)]
+		));
+		let event = Event::parameters(crate::Event::ParametersUpdated(2));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(get_param(MIN_KEY), 10);
+		assert_eq!(get_param(MAX_KEY), 20);
+	});
+}
+
+#[test]
+fn set_parameters_fails_if_invariants_violated() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ParametersModule::set_parameters(
+			Origin::root(),
+			vec![set_param_call(MAX_KEY, 20), set_param_call(MIN_KEY, 10)]
+		));
+
+		assert_noop!(
+			ParametersModule::set_parameters(
+				Origin::root(),
+				vec![set_param_call(MIN_KEY, 15), set_param_call(MAX_KEY, 5)]
+			),
+			DispatchError::Other("InvalidParameters")
+		);
+		assert_eq!(get_param(MIN_KEY), 10);
+		assert_eq!(get_param(MAX_KEY), 20);
+
+		assert_ok!(ParametersModule::set_parameters(
+			Origin::root(),
+			vec![set_param_call(MIN_KEY, 15), set_param_call(MAX_KEY, 15)]
+		));
+		assert_eq!(get_param(MIN_KEY), 15);
+		assert_eq!(get_param(MAX_KEY), 15);
+	});
+}
+
+#[test]
+fn set_parameters_fails_if_call_fails() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			ParametersModule::set_parameters(Origin::signed(ALICE), vec![set_param_call(MAX_KEY, 20)]),
+			BadOrigin
+		);
+		assert_noop!(
+			ParametersModule::set_parameters(
+				Origin::root(),
+				vec![
+					set_param_call(MAX_KEY, 20),
+					Call::System(frame_system::Call::remark(vec![]))
+				]
+			),
+			Error::<Runtime>::CallNotAllowed
+		);
+		assert_noop!(
+			ParametersModule::set_parameters(
+				Origin::root(),
+				vec![
+					set_param_call(MAX_KEY, 20),
+					set_param_call(MAX_KEY, 30),
+					set_param_call(MAX_KEY, 40),
+					set_param_call(MAX_KEY, 50)
+				]
+			),
+			Error::<Runtime>::TooManyCalls
+		);
+	});
+}
+
+#[test]
+fn validate_parameters_discards_changes() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ParametersModule::validate_parameters(
+			Origin::root(),
+			vec![set_param_call(MAX_KEY, 20), set_param_call(MIN_KEY, 10)]
+		));
+		assert_eq!(get_param(MIN_KEY), 0);
+		assert_eq!(get_param(MAX_KEY), 0);
+
+		assert_eq!(
+			ParametersModule::validate_parameters(Origin::root(), vec![set_param_call(MIN_KEY, 10)]),
+			Err(DispatchError::Other("InvalidParameters"))
+		);
+	});
+}
@@ -22,19 +22,24 @@
 use frame_support::{log, pallet_prelude::*, transactional};
 use frame_system::pallet_prelude::*;
 use orml_traits::BasicCurrency;
-use primitives::{Balance, EraIndex};
+use primitives::{Balance, BlockNumber as RelaychainBlockNumber, EraIndex};
 use sp_runtime::{
-	traits::{CheckedSub, MaybeDisplay, MaybeSerializeDeserialize, Member, StaticLookup, Zero},
+	traits::{CheckedSub, MaybeDisplay, MaybeSerializeDeserialize, Member, SaturatedConversion, StaticLookup, Zero},
 	DispatchResult, FixedPointNumber, RuntimeDebug,
 };
 use sp_std::{fmt::Debug, prelude::*};
 use support::{
-	OnNewEra, PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState, PolkadotBridgeType, PolkadotStakingLedger,
-	PolkadotUnlockChunk, Rate,
+	OnNewEra, PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState, PolkadotBridgeType, PolkadotStakingCall,
+	PolkadotStakingLedger, PolkadotUnlockChunk, Rate,
 };
 
+mod mock;
+mod tests;
+
 pub use module::*;
 
+type StakingCallOf<T> = PolkadotStakingCall<Balance, EraIndex, <T as Config>::PolkadotAccountId>;
+
 /// The params related to rebalance per era
 #[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, Default)]
 pub struct SubAccountStatus {
@@ -53,6 +58,7 @@ pub mod module {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 		type DOTCurrency: BasicCurrency<Self::AccountId, Balance = Balance>;
 		type OnNewEra: OnNewEra<EraIndex>;
 		#[pallet::constant]
@@ -60,6 +66,35 @@ pub mod module {
 		#[pallet::constant]
 		type EraLength: Get<Self::BlockNumber>;
 		type PolkadotAccountId: Parameter + Member + MaybeSerializeDeserialize + Debug + MaybeDisplay + Ord + Default;
+
+		/// The relaychain block number observed via parachain validation
+		/// data, `None` if it's unavailable.
+		type RelaychainBlockNumber: Get<Option<RelaychainBlockNumber>>;
+
+		/// The era length in relaychain blocks. Era will be bumped
+		/// automatically once this number of relaychain blocks has passed.
+		#[pallet::constant]
+		type RelaychainEraLength: Get<RelaychainBlockNumber>;
+
+		/// The relaychain weight of a single staking call.
+		#[pallet::constant]
+		type StakingCallWeight: Get<Weight>;
+
+		/// The max relaychain weight of a message. Batched staking calls
+		/// exceeding it are sent in following messages.
+		#[pallet::constant]
+		type MaxMessageWeight: Get<Weight>;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The era has been bumped automatically by relaychain block number.
+		/// \[new_era, relaychain_block_number\]
+		EraAutoBumped(EraIndex, RelaychainBlockNumber),
+		/// Staking calls have been sent to relaychain in a batched message.
+		/// \[calls_count, weight\]
+		StakingCallsBatched(u32, Weight),
 	}
 
 	#[pallet::error]
@@ -76,6 +111,11 @@ pub mod module {
 	#[pallet::getter(fn era_start_block_number)]
 	pub type EraStartBlockNumber<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
 
+	/// The relaychain block number at which the current era started.
+	#[pallet::storage]
+	#[pallet::getter(fn era_start_relaychain_block_number)]
+	pub type EraStartRelaychainBlockNumber<T: Config> = StorageValue<_, RelaychainBlockNumber, OptionQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn forced_era)]
 	pub type ForcedEra<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
@@ -98,10 +138,29 @@ pub mod module {
 					false
 				}
 			});
-			let len = now.checked_sub(&Self::era_start_block_number()).unwrap_or_default();
 
-			if len >= T::EraLength::get() || force_era {
+			if force_era {
 				Self::new_era(now);
+			} else if let Some(relaychain_now) = T::RelaychainBlockNumber::get() {
+				match Self::era_start_relaychain_block_number() {
+					// start tracking relaychain block number from the current era.
+					None => EraStartRelaychainBlockNumber::<T>::put(relaychain_now),
+					// ignore if the relaychain block number goes backwards.
+					Some(start) if relaychain_now > start => {
+						if relaychain_now - start >= T::RelaychainEraLength::get() {
+							let new_era = Self::new_era(now);
+							Self::deposit_event(Event::EraAutoBumped(new_era, relaychain_now));
+						}
+					}
+					_ => {}
+				}
+			} else {
+				// fallback to parachain block number if relaychain block number is
+				// unavailable.
+				let len = now.checked_sub(&Self::era_start_block_number()).unwrap_or_default();
+				if len >= T::EraLength::get() {
+					Self::new_era(now);
+				}
 			}
 		}
 	}
@@ -232,13 +291,17 @@ pub mod module {
 }
 
 impl<T: Config> Pallet<T> {
-	pub fn new_era(now: T::BlockNumber) {
+	pub fn new_era(now: T::BlockNumber) -> EraIndex {
 		let new_era = CurrentEra::<T>::mutate(|era| {
 			*era += 1;
 			*era
 		});
 		EraStartBlockNumber::<T>::put(now);
+		if let Some(relaychain_now) = T::RelaychainBlockNumber::get() {
+			EraStartRelaychainBlockNumber::<T>::put(relaychain_now);
+		}
 		T::OnNewEra::on_new_era(new_era);
+		new_era
 	}
 
 	/// simulate bond extra by sub account
@@ -356,6 +419,40 @@ impl<T: Config> Pallet<T> {
 	/// simulate nominate by sub account
 	fn sub_account_nominate(_account_index: u32, _targets: Vec<T::PolkadotAccountId>) {}
 
+	/// simulate sending staking calls in messages, each message carries as
+	/// many calls as `MaxMessageWeight` allows.
+	fn batch_staking_calls(calls: Vec<(u32, StakingCallOf<T>)>) -> DispatchResult {
+		let calls_per_message = T::MaxMessageWeight::get()
+			.checked_div(T::StakingCallWeight::get())
+			.unwrap_or(Weight::MAX)
+			.max(1);
+		for message in calls.chunks(calls_per_message.saturated_into()) {
+			Self::send_staking_message(message.to_vec())?;
+		}
+		Ok(())
+	}
+
+	/// simulate a batched message of staking calls, the calls in a message
+	/// are dispatched all or nothing.
+	#[transactional]
+	fn send_staking_message(calls: Vec<(u32, StakingCallOf<T>)>) -> DispatchResult {
+		let calls_count = calls.len() as u32;
+		for (account_index, call) in calls {
+			match call {
+				PolkadotStakingCall::BondExtra(amount) => Self::sub_account_bond_extra(account_index, amount)?,
+				PolkadotStakingCall::Unbond(amount) => Self::sub_account_unbond(account_index, amount)?,
+				PolkadotStakingCall::Rebond(amount) => Self::sub_account_rebond(account_index, amount)?,
+				PolkadotStakingCall::WithdrawUnbonded => Self::sub_account_withdraw_unbonded(account_index),
+				PolkadotStakingCall::Nominate(targets) => Self::sub_account_nominate(account_index, targets),
+				PolkadotStakingCall::PayoutStakers(era) => Self::sub_account_payout_stakers(account_index, era),
+			}
+		}
+
+		let weight = T::StakingCallWeight::get().saturating_mul(calls_count.into());
+		Self::deposit_event(Event::StakingCallsBatched(calls_count, weight));
+		Ok(())
+	}
+
 	/// simulate transfer dot from acala to parachain sub account in
 	/// polkadot
 	fn transfer_to_sub_account(account_index: u32, from: &T::AccountId, amount: Balance) -> DispatchResult {
@@ -413,6 +510,10 @@ impl<T: Config> PolkadotBridgeCall<T::AccountId, T::BlockNumber, Balance, EraInd
 	fn receive_from_bridge(account_index: u32, to: &T::AccountId, amount: Balance) -> DispatchResult {
 		Self::receive_from_sub_account(account_index, to, amount)
 	}
+
+	fn batch_calls(calls: Vec<(u32, StakingCallOf<T>)>) -> DispatchResult {
+		Self::batch_staking_calls(calls)
+	}
 }
 
 impl<T: Config> PolkadotBridgeState<Balance, EraIndex> for Pallet<T> {
@@ -0,0 +1,157 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for polkadot bridge module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+
+fn finalize_block(n: BlockNumber) {
+	System::set_block_number(n);
+	PolkadotBridgeModule::on_finalize(n);
+}
+
+#[test]
+fn bump_era_by_block_number_without_relaychain_block_number() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(PolkadotBridgeModule::current_era(), 0);
+
+		finalize_block(9);
+		assert_eq!(PolkadotBridgeModule::current_era(), 0);
+
+		finalize_block(10);
+		assert_eq!(PolkadotBridgeModule::current_era(), 1);
+		assert_eq!(PolkadotBridgeModule::era_start_block_number(), 10);
+		assert_eq!(PolkadotBridgeModule::era_start_relaychain_block_number(), None);
+	});
+}
+
+#[test]
+fn bump_era_by_relaychain_block_number() {
+	ExtBuilder::default().build().execute_with(|| {
+		// start tracking from the current era
+		set_relaychain_block_number(Some(100));
+		finalize_block(1);
+		assert_eq!(PolkadotBridgeModule::era_start_relaychain_block_number(), Some(100));
+		assert_eq!(PolkadotBridgeModule::current_era(), 0);
+
+		// the era length of parachain blocks is ignored
+		set_relaychain_block_number(Some(119));
+		finalize_block(15);
+		assert_eq!(PolkadotBridgeModule::current_era(), 0);
+
+		set_relaychain_block_number(Some(120));
+		finalize_block(16);
+		assert_eq!(PolkadotBridgeModule::current_era(), 1);
+		assert_eq!(PolkadotBridgeModule::era_start_block_number(), 16);
+		assert_eq!(PolkadotBridgeModule::era_start_relaychain_block_number(), Some(120));
+		let event = Event::polkadot_bridge(crate::Event::EraAutoBumped(1, 120));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		// only bump once for the era
+		finalize_block(17);
+		assert_eq!(PolkadotBridgeModule::current_era(), 1);
+	});
+}
+
+#[test]
+fn ignore_relaychain_block_number_going_backwards() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_relaychain_block_number(Some(100));
+		finalize_block(1);
+
+		set_relaychain_block_number(Some(50));
+		finalize_block(2);
+		assert_eq!(PolkadotBridgeModule::current_era(), 0);
+		assert_eq!(PolkadotBridgeModule::era_start_relaychain_block_number(), Some(100));
+
+		set_relaychain_block_number(Some(119));
+		finalize_block(3);
+		assert_eq!(PolkadotBridgeModule::current_era(), 0);
+
+		set_relaychain_block_number(Some(120));
+		finalize_block(4);
+		assert_eq!(PolkadotBridgeModule::current_era(), 1);
+	});
+}
+
+#[test]
+fn force_era_should_reset_relaychain_era_start() {
+	ExtBuilder::default().build().execute_with(|| {
+		set_relaychain_block_number(Some(100));
+		finalize_block(1);
+
+		assert_ok!(PolkadotBridgeModule::force_era(Origin::root(), 3));
+		set_relaychain_block_number(Some(105));
+		finalize_block(3);
+		assert_eq!(PolkadotBridgeModule::current_era(), 1);
+		assert_eq!(PolkadotBridgeModule::forced_era(), None);
+		assert_eq!(PolkadotBridgeModule::era_start_relaychain_block_number(), Some(105));
+		let event = Event::polkadot_bridge(crate::Event::EraAutoBumped(1, 105));
+		assert!(!System::events().iter().any(|record| record.event == event));
+
+		// the relaychain era length counts from the forced era
+		set_relaychain_block_number(Some(120));
+		finalize_block(4);
+		assert_eq!(PolkadotBridgeModule::current_era(), 1);
+
+		set_relaychain_block_number(Some(125));
+		finalize_block(5);
+		assert_eq!(PolkadotBridgeModule::current_era(), 2);
+	});
+}
+
+#[test]
+fn batch_calls_should_send_messages_within_weight_limit() {
+	ExtBuilder::default().build().execute_with(|| {
+		SubAccounts::<Runtime>::mutate(0, |status| status.available = 100);
+
+		// at most 2 calls in a message
+		assert_ok!(PolkadotBridgeModule::batch_calls(vec![
+			(0, PolkadotStakingCall::BondExtra(50)),
+			(0, PolkadotStakingCall::Nominate(vec![1, 2])),
+			(0, PolkadotStakingCall::Unbond(20)),
+		]));
+		let event = Event::polkadot_bridge(crate::Event::StakingCallsBatched(2, 20));
+		assert!(System::events().iter().any(|record| record.event == event));
+		let event = Event::polkadot_bridge(crate::Event::StakingCallsBatched(1, 10));
+		assert_eq!(System::events().last().unwrap().event, event);
+		assert_eq!(
+			PolkadotBridgeModule::sub_accounts(0),
+			SubAccountStatus {
+				bonded: 30,
+				available: 50,
+				unbonding: vec![(4, 20)],
+				mock_reward_rate: Default::default(),
+			}
+		);
+
+		// the calls in a message are all or nothing
+		assert_noop!(
+			PolkadotBridgeModule::batch_calls(vec![
+				(0, PolkadotStakingCall::BondExtra(10)),
+				(1, PolkadotStakingCall::BondExtra(10)),
+				(0, PolkadotStakingCall::BondExtra(10)),
+			]),
+			Error::<Runtime>::NotEnough
+		);
+	});
+}
@@ -0,0 +1,53 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for prices module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+// The `too_many_arguments` warning originates from `decl_runtime_apis` macro.
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::{Decode, Encode};
+use primitives::CurrencyId;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::FixedU128;
+
+/// The price of a currency with the decimals used to adjust it.
+#[derive(Eq, PartialEq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct NormalizedPrice {
+	/// The price of one whole unit, fed by the oracle or derived from the
+	/// prices of other currencies.
+	pub raw_price: FixedU128,
+	/// The decimals of the currency.
+	pub decimals: u8,
+	/// The price of one basic unit, scaled to 18 decimals, which is the
+	/// price used by the system.
+	pub normalized_price: FixedU128,
+}
+
+sp_api::decl_runtime_apis! {
+	pub trait PricesApi {
+		/// Get the normalized price of `currency_id`, None if it is unpriced
+		/// or its decimals are unknown.
+		fn normalized_price(currency_id: CurrencyId) -> Option<NormalizedPrice>;
+	}
+}
@@ -25,19 +25,28 @@
 //!   - specify a fixed price for stable currency
 //!   - feed price in USD or related price bewteen two currencies
 //!   - lock/unlock the price data get from oracle
+//!   - pause the price of a currency, it'll be resumed automatically after
+//!     receiving enough consecutive in-band updates from oracle
+//!   - derive the price of liquid staking currency by the exchange rate, and
+//!     the price of liquid crowdloan currency by a governance-set discount
+//!     curve
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
 use frame_support::{pallet_prelude::*, transactional};
 use frame_system::pallet_prelude::*;
-use orml_traits::{DataFeeder, DataProvider, MultiCurrency};
-use primitives::{currency::DexShare, Balance, CurrencyId};
+use orml_oracle::TimestampedValue;
+use orml_traits::{DataFeeder, DataProvider, DataProviderExtended, MultiCurrency};
+use primitives::{currency::DexShare, Balance, CurrencyId, Moment};
 use sp_runtime::{
-	traits::{CheckedDiv, CheckedMul},
-	FixedPointNumber,
+	traits::{CheckedDiv, CheckedMul, One, Saturating, UniqueSaturatedInto, Zero},
+	FixedPointNumber, RuntimeDebug,
+};
+use sp_std::prelude::*;
+use support::{
+	CurrencyIdMapping, DEXManager, ExchangeRateProvider, Price, PriceProvider, PriceTimestampProvider, Ratio,
 };
-use support::{CurrencyIdMapping, DEXManager, ExchangeRateProvider, Price, PriceProvider};
 
 mod mock;
 mod tests;
@@ -46,6 +55,28 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// The status of a paused price.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub struct PausedPriceInfo {
+	/// The latest oracle feed observed since the price was paused.
+	pub last_feed: Option<TimestampedValue<Price, Moment>>,
+	/// The count of consecutive in-band oracle updates.
+	pub in_band_updates: u32,
+}
+
+/// The discount of liquid crowdloan currency to staking currency, it
+/// decreases linearly from `start_discount` at `start` to zero at `end`,
+/// when the crowdloan lease ends and the staking currency is redeemable.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub struct DiscountCurve<BlockNumber> {
+	/// The block number the curve starts.
+	pub start: BlockNumber,
+	/// The block number the discount becomes zero.
+	pub end: BlockNumber,
+	/// The discount at `start`.
+	pub start_discount: Ratio,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -55,7 +86,9 @@ pub mod module {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
 		/// The data source, such as Oracle.
-		type Source: DataProvider<CurrencyId, Price> + DataFeeder<CurrencyId, Price, Self::AccountId>;
+		type Source: DataProvider<CurrencyId, Price>
+			+ DataProviderExtended<CurrencyId, TimestampedValue<Price, Moment>>
+			+ DataFeeder<CurrencyId, Price, Self::AccountId>;
 
 		/// The stable currency id, it should be AUSD in Acala.
 		#[pallet::constant]
@@ -73,9 +106,25 @@ pub mod module {
 		#[pallet::constant]
 		type GetLiquidCurrencyId: Get<CurrencyId>;
 
-		/// The origin which may lock and unlock prices feed to system.
+		/// The liquid crowdloan currency id, it should be LCDOT in Acala, None
+		/// if the chain has no liquid crowdloan currency.
+		#[pallet::constant]
+		type GetLiquidCrowdloanCurrencyId: Get<Option<CurrencyId>>;
+
+		/// The origin which may lock, unlock, pause and resume prices feed to
+		/// system, and set the discount curve of liquid crowdloan currency.
 		type LockOrigin: EnsureOrigin<Self::Origin>;
 
+		/// The max deviation between two consecutive oracle updates of a paused
+		/// price to be considered as in-band.
+		#[pallet::constant]
+		type MaxPriceDeviation: Get<Ratio>;
+
+		/// The count of consecutive in-band oracle updates required to resume
+		/// a paused price automatically.
+		#[pallet::constant]
+		type PriceResumeThreshold: Get<u32>;
+
 		/// The provider of the exchange rate between liquid currency and
 		/// staking currency.
 		type LiquidStakingExchangeRateProvider: ExchangeRateProvider;
@@ -93,6 +142,18 @@ pub mod module {
 		type WeightInfo: WeightInfo;
 	}
 
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The price of currency has already been paused
+		AlreadyPaused,
+		/// The price of currency is not paused
+		NotPaused,
+		/// The discount curve is invalid
+		InvalidDiscountCurve,
+		/// There is no liquid crowdloan currency
+		NoLiquidCrowdloanCurrency,
+	}
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -100,6 +161,13 @@ pub mod module {
 		LockPrice(CurrencyId, Price),
 		/// Unlock price. \[currency_id\]
 		UnlockPrice(CurrencyId),
+		/// Price paused. \[currency_id\]
+		PricePaused(CurrencyId),
+		/// Price resumed. \[currency_id\]
+		PriceResumed(CurrencyId),
+		/// The discount curve of liquid crowdloan currency updated.
+		/// \[start_discount, end\]
+		LiquidCrowdloanDiscountUpdated(Ratio, T::BlockNumber),
 	}
 
 	/// Mapping from currency id to it's locked price
@@ -107,11 +175,28 @@ pub mod module {
 	#[pallet::getter(fn locked_price)]
 	pub type LockedPrice<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
 
+	/// Mapping from currency id to the status of it's paused price
+	#[pallet::storage]
+	#[pallet::getter(fn paused_prices)]
+	pub type PausedPrices<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, PausedPriceInfo, OptionQuery>;
+
+	/// The discount curve of liquid crowdloan currency to staking currency
+	#[pallet::storage]
+	#[pallet::getter(fn liquid_crowdloan_discount)]
+	pub type LiquidCrowdloanDiscount<T: Config> = StorageValue<_, DiscountCurve<T::BlockNumber>, OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Track the oracle updates of paused prices and resume them once
+		/// received enough consecutive in-band updates.
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			let count = Self::track_paused_prices();
+			T::WeightInfo::on_initialize(count)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -140,6 +225,186 @@ pub mod module {
 			<Pallet<T> as PriceProvider<CurrencyId>>::unlock_price(currency_id);
 			Ok(().into())
 		}
+
+		/// Pause the price, the currency will be unpriced until it's resumed.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		#[pallet::weight((T::WeightInfo::pause_price(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn pause_price(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResultWithPostInfo {
+			T::LockOrigin::ensure_origin(origin)?;
+			ensure!(!Self::is_paused(currency_id), Error::<T>::AlreadyPaused);
+
+			PausedPrices::<T>::insert(
+				currency_id,
+				PausedPriceInfo {
+					last_feed: T::Source::get_no_op(&currency_id),
+					in_band_updates: 0,
+				},
+			);
+			Self::deposit_event(Event::PricePaused(currency_id));
+			Ok(().into())
+		}
+
+		/// Resume the paused price without waiting for in-band oracle
+		/// updates.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		#[pallet::weight((T::WeightInfo::resume_price(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn resume_price(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResultWithPostInfo {
+			T::LockOrigin::ensure_origin(origin)?;
+			ensure!(Self::is_paused(currency_id), Error::<T>::NotPaused);
+
+			PausedPrices::<T>::remove(currency_id);
+			Self::deposit_event(Event::PriceResumed(currency_id));
+			Ok(().into())
+		}
+
+		/// Set the discount curve of liquid crowdloan currency to staking
+		/// currency, the discount decreases linearly from now to zero at the
+		/// lease end.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `start_discount`: the discount at now.
+		/// - `lease_end`: the block number the discount becomes zero.
+		#[pallet::weight((T::WeightInfo::set_liquid_crowdloan_discount(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_liquid_crowdloan_discount(
+			origin: OriginFor<T>,
+			start_discount: Ratio,
+			lease_end: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			T::LockOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::GetLiquidCrowdloanCurrencyId::get().is_some(),
+				Error::<T>::NoLiquidCrowdloanCurrency
+			);
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				lease_end > now && start_discount <= Ratio::one(),
+				Error::<T>::InvalidDiscountCurve
+			);
+
+			LiquidCrowdloanDiscount::<T>::put(DiscountCurve {
+				start: now,
+				end: lease_end,
+				start_discount,
+			});
+			Self::deposit_event(Event::LiquidCrowdloanDiscountUpdated(start_discount, lease_end));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn is_paused(currency_id: CurrencyId) -> bool {
+		PausedPrices::<T>::contains_key(currency_id)
+	}
+
+	fn is_liquid_crowdloan_currency(currency_id: CurrencyId) -> bool {
+		T::GetLiquidCrowdloanCurrencyId::get() == Some(currency_id)
+	}
+
+	/// Check whether the deviation between two consecutive prices is within
+	/// `MaxPriceDeviation`.
+	fn is_in_band(previous: Price, current: Price) -> bool {
+		let diff = current.max(previous).saturating_sub(current.min(previous));
+		match diff.checked_div(&previous) {
+			Some(deviation) => deviation <= T::MaxPriceDeviation::get(),
+			None => current == previous,
+		}
+	}
+
+	/// Track the fresh oracle updates of all paused prices, returns the count
+	/// of paused prices.
+	fn track_paused_prices() -> u32 {
+		let mut count: u32 = 0;
+		let mut resumed: Vec<CurrencyId> = vec![];
+
+		PausedPrices::<T>::translate(|currency_id: CurrencyId, mut info: PausedPriceInfo| {
+			count += 1;
+
+			if let Some(feed) = T::Source::get_no_op(&currency_id) {
+				let is_fresh = info.last_feed.map_or(true, |last| feed.timestamp > last.timestamp);
+				if is_fresh {
+					let in_band = info
+						.last_feed
+						.map_or(false, |last| Self::is_in_band(last.value, feed.value));
+					info.in_band_updates = if in_band {
+						info.in_band_updates.saturating_add(1)
+					} else {
+						0
+					};
+					info.last_feed = Some(feed);
+
+					if info.in_band_updates >= T::PriceResumeThreshold::get() {
+						resumed.push(currency_id);
+						return None;
+					}
+				}
+			}
+
+			Some(info)
+		});
+
+		for currency_id in resumed {
+			Self::deposit_event(Event::PriceResumed(currency_id));
+		}
+
+		count
+	}
+
+	/// The discount of liquid crowdloan currency to staking currency at
+	/// current block, returns None if the discount curve is not set.
+	pub fn current_liquid_crowdloan_discount() -> Option<Ratio> {
+		let curve = Self::liquid_crowdloan_discount()?;
+		let now = frame_system::Pallet::<T>::block_number();
+		if now >= curve.end {
+			return Some(Ratio::zero());
+		}
+
+		let remaining: u128 = curve.end.saturating_sub(now.max(curve.start)).unique_saturated_into();
+		let duration: u128 = curve.end.saturating_sub(curve.start).unique_saturated_into();
+		Ratio::checked_from_rational(remaining, duration).map(|r| r.saturating_mul(curve.start_discount))
+	}
+
+	/// The price of one whole unit of the currency which is not derived from
+	/// other currencies.
+	fn get_feed_price(currency_id: CurrencyId) -> Option<Price> {
+		if currency_id == T::GetStableCurrencyId::get() {
+			// if is stable currency, return fixed price
+			Some(T::StableCurrencyFixedPrice::get())
+		} else {
+			// if locked price exists, return it, otherwise return latest price from oracle.
+			Self::locked_price(currency_id).or_else(|| T::Source::get(&currency_id))
+		}
+	}
+
+	/// Audit the decimals normalization of the price of `currency_id`.
+	/// Returns the raw price of one whole unit, the decimals used to adjust
+	/// it and the price of one basic unit scaled to 18 decimals.
+	pub fn normalized_price(currency_id: CurrencyId) -> Option<(Price, u8, Price)> {
+		let decimals = T::CurrencyIdMapping::decimals(currency_id)?;
+		let price = <Self as PriceProvider<CurrencyId>>::get_price(currency_id)?;
+		let raw_price = if currency_id == T::GetLiquidCurrencyId::get()
+			|| Self::is_liquid_crowdloan_currency(currency_id)
+			|| matches!(currency_id, CurrencyId::DexShare(..))
+		{
+			// the price of derived currency is only available as the price of one basic
+			// unit, left shift the decimal point (18 - decimals) places
+			let adjustment_multiplier = 10u128.checked_pow(decimals.into())?;
+			price.checked_mul(&Price::checked_from_rational(adjustment_multiplier, Price::accuracy())?)?
+		} else {
+			Self::get_feed_price(currency_id)?
+		};
+
+		Some((raw_price, decimals, price))
 	}
 }
 
@@ -159,14 +424,23 @@ impl<T: Config> PriceProvider<CurrencyId> for Pallet<T> {
 	/// get the exchange rate of specific currency to USD
 	/// Note: this returns the price for 1 basic unit
 	fn get_price(currency_id: CurrencyId) -> Option<Price> {
-		let maybe_feed_price = if currency_id == T::GetStableCurrencyId::get() {
-			// if is stable currency, return fixed price
-			Some(T::StableCurrencyFixedPrice::get())
-		} else if currency_id == T::GetLiquidCurrencyId::get() {
+		// paused price is treated as unpriced
+		if Self::is_paused(currency_id) {
+			return None;
+		}
+
+		let maybe_feed_price = if currency_id == T::GetLiquidCurrencyId::get() {
 			// if is homa liquid currency, return the product of staking currency price and
 			// liquid/staking exchange rate.
 			return Self::get_price(T::GetStakingCurrencyId::get())
 				.and_then(|n| n.checked_mul(&T::LiquidStakingExchangeRateProvider::get_exchange_rate()));
+		} else if Self::is_liquid_crowdloan_currency(currency_id) {
+			// if is liquid crowdloan currency, return the price of staking currency
+			// discounted by the current discount curve.
+			return Self::current_liquid_crowdloan_discount().and_then(|discount| {
+				Self::get_price(T::GetStakingCurrencyId::get())
+					.and_then(|n| n.checked_mul(&Ratio::one().saturating_sub(discount)))
+			});
 		} else if let CurrencyId::DexShare(symbol_0, symbol_1) = currency_id {
 			let token_0 = match symbol_0 {
 				DexShare::Token(token) => CurrencyId::Token(token),
@@ -192,8 +466,7 @@ impl<T: Config> PriceProvider<CurrencyId> for Pallet<T> {
 				}
 			};
 		} else {
-			// if locked price exists, return it, otherwise return latest price from oracle.
-			Self::locked_price(currency_id).or_else(|| T::Source::get(&currency_id))
+			Self::get_feed_price(currency_id)
 		};
 		let maybe_adjustment_multiplier = 10u128.checked_pow(T::CurrencyIdMapping::decimals(currency_id)?.into());
 
@@ -217,3 +490,34 @@ impl<T: Config> PriceProvider<CurrencyId> for Pallet<T> {
 		<Pallet<T>>::deposit_event(Event::UnlockPrice(currency_id));
 	}
 }
+
+impl<T: Config> PriceTimestampProvider<CurrencyId, Moment> for Pallet<T> {
+	/// get the time when the price of specific currency was fed by the oracle.
+	/// Note: the stable currency and locked prices have no timestamp, the
+	/// dex share takes the older timestamp of its tokens
+	fn get_price_timestamp(currency_id: CurrencyId) -> Option<Moment> {
+		if currency_id == T::GetStableCurrencyId::get() || Self::is_paused(currency_id) {
+			None
+		} else if currency_id == T::GetLiquidCurrencyId::get() || Self::is_liquid_crowdloan_currency(currency_id) {
+			Self::get_price_timestamp(T::GetStakingCurrencyId::get())
+		} else if let CurrencyId::DexShare(symbol_0, symbol_1) = currency_id {
+			// the dex share is as stale as the older oracle feed of its tokens
+			let token_0 = match symbol_0 {
+				DexShare::Token(token) => CurrencyId::Token(token),
+				DexShare::Erc20(address) => CurrencyId::Erc20(address),
+			};
+			let token_1 = match symbol_1 {
+				DexShare::Token(token) => CurrencyId::Token(token),
+				DexShare::Erc20(address) => CurrencyId::Erc20(address),
+			};
+			match (Self::get_price_timestamp(token_0), Self::get_price_timestamp(token_1)) {
+				(Some(timestamp_0), Some(timestamp_1)) => Some(timestamp_0.min(timestamp_1)),
+				(maybe_timestamp_0, maybe_timestamp_1) => maybe_timestamp_0.or(maybe_timestamp_1),
+			}
+		} else if Self::locked_price(currency_id).is_some() {
+			None
+		} else {
+			T::Source::get_no_op(&currency_id).map(|timestamped| timestamped.timestamp)
+		}
+	}
+}
@@ -27,6 +27,7 @@ use orml_traits::{parameter_type_with_key, DataFeeder};
 use primitives::{Amount, TokenSymbol};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup, DispatchError, FixedPointNumber};
+use sp_std::cell::RefCell;
 use support::{mocks::MockCurrencyIdMapping, ExchangeRate, Ratio};
 
 pub type AccountId = u128;
@@ -37,10 +38,13 @@ pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 pub const BTC: CurrencyId = CurrencyId::Token(TokenSymbol::XBTC);
 pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
 pub const LDOT: CurrencyId = CurrencyId::Token(TokenSymbol::LDOT);
+pub const LCDOT: CurrencyId = CurrencyId::Token(TokenSymbol::LCDOT);
 pub const LP_BTC_AUSD: CurrencyId =
 	CurrencyId::DexShare(DexShare::Token(TokenSymbol::XBTC), DexShare::Token(TokenSymbol::AUSD));
 pub const LP_AUSD_DOT: CurrencyId =
 	CurrencyId::DexShare(DexShare::Token(TokenSymbol::AUSD), DexShare::Token(TokenSymbol::DOT));
+pub const LP_DOT_BTC: CurrencyId =
+	CurrencyId::DexShare(DexShare::Token(TokenSymbol::DOT), DexShare::Token(TokenSymbol::XBTC));
 
 mod prices {
 	pub use super::super::*;
@@ -76,12 +80,24 @@ impl frame_system::Config for Runtime {
 	type OnSetCode = ();
 }
 
+thread_local! {
+	static BTC_FEED: RefCell<TimestampedValue<Price, Moment>> = RefCell::new(TimestampedValue {
+		value: Price::saturating_from_integer(50000),
+		timestamp: 100,
+	});
+}
+
 pub struct MockDataProvider;
+impl MockDataProvider {
+	pub fn feed_btc_price(value: Price, timestamp: Moment) {
+		BTC_FEED.with(|v| *v.borrow_mut() = TimestampedValue { value, timestamp });
+	}
+}
 impl DataProvider<CurrencyId, Price> for MockDataProvider {
 	fn get(currency_id: &CurrencyId) -> Option<Price> {
 		match *currency_id {
 			AUSD => Some(Price::saturating_from_rational(99, 100)),
-			BTC => Some(Price::saturating_from_integer(50000)),
+			BTC => Some(BTC_FEED.with(|v| v.borrow().value)),
 			DOT => Some(Price::saturating_from_integer(100)),
 			ACA => Some(Price::zero()),
 			_ => None,
@@ -89,6 +105,21 @@ impl DataProvider<CurrencyId, Price> for MockDataProvider {
 	}
 }
 
+impl DataProviderExtended<CurrencyId, TimestampedValue<Price, Moment>> for MockDataProvider {
+	fn get_no_op(currency_id: &CurrencyId) -> Option<TimestampedValue<Price, Moment>> {
+		let timestamp = match *currency_id {
+			BTC => BTC_FEED.with(|v| v.borrow().timestamp),
+			DOT => 200,
+			_ => 300,
+		};
+		Self::get(currency_id).map(|value| TimestampedValue { value, timestamp })
+	}
+
+	fn get_all_values() -> Vec<(CurrencyId, Option<TimestampedValue<Price, Moment>>)> {
+		unimplemented!()
+	}
+}
+
 impl DataFeeder<CurrencyId, Price, AccountId> for MockDataProvider {
 	fn feed_value(_: AccountId, _: CurrencyId, _: Price) -> sp_runtime::DispatchResult {
 		Ok(())
@@ -193,7 +224,10 @@ parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
 	pub const GetStakingCurrencyId: CurrencyId = DOT;
 	pub const GetLiquidCurrencyId: CurrencyId = LDOT;
+	pub const GetLiquidCrowdloanCurrencyId: Option<CurrencyId> = Some(LCDOT);
 	pub StableCurrencyFixedPrice: Price = Price::one();
+	pub MaxPriceDeviation: Ratio = Ratio::saturating_from_rational(5, 100);
+	pub const PriceResumeThreshold: u32 = 3;
 }
 
 impl Config for Runtime {
@@ -203,7 +237,10 @@ impl Config for Runtime {
 	type StableCurrencyFixedPrice = StableCurrencyFixedPrice;
 	type GetStakingCurrencyId = GetStakingCurrencyId;
 	type GetLiquidCurrencyId = GetLiquidCurrencyId;
+	type GetLiquidCrowdloanCurrencyId = GetLiquidCrowdloanCurrencyId;
 	type LockOrigin = EnsureSignedBy<One, AccountId>;
+	type MaxPriceDeviation = MaxPriceDeviation;
+	type PriceResumeThreshold = PriceResumeThreshold;
 	type LiquidStakingExchangeRateProvider = MockLiquidStakingExchangeProvider;
 	type DEX = MockDEX;
 	type Currency = Tokens;
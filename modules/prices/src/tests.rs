@@ -61,26 +61,91 @@ fn get_price_of_liquid_currency_id() {
 }
 
 #[test]
-fn get_price_of_lp_token_currency_id() {
+fn get_price_of_liquid_crowdloan_currency_id() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(MockDEX::get_liquidity_pool(AUSD, DOT), (10000, 200));
+		System::set_block_number(1);
+		assert_eq!(PricesModule::get_price(LCDOT), None);
+
+		assert_ok!(PricesModule::set_liquid_crowdloan_discount(
+			Origin::signed(1),
+			Ratio::saturating_from_rational(20, 100),
+			101
+		));
 		assert_eq!(
-			PricesModule::get_price(LP_AUSD_DOT),
-			None
+			PricesModule::get_price(LCDOT),
+			Some(Price::saturating_from_integer(8000000000u128))
+		); // 80 USD, right shift the decimal point (18-10) places
+
+		System::set_block_number(51);
+		assert_eq!(
+			PricesModule::get_price(LCDOT),
+			Some(Price::saturating_from_integer(9000000000u128))
+		); // 90 USD, right shift the decimal point (18-10) places
+
+		System::set_block_number(101);
+		assert_eq!(
+			PricesModule::get_price(LCDOT),
+			Some(Price::saturating_from_integer(10000000000u128))
+		); // 100 USD, right shift the decimal point (18-10) places
+		assert_eq!(
+			PricesModule::get_price_timestamp(LCDOT),
+			PricesModule::get_price_timestamp(DOT)
 		);
+	});
+}
+
+#[test]
+fn get_price_of_lp_token_currency_id() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(MockDEX::get_liquidity_pool(AUSD, DOT), (10000, 200));
+		assert_eq!(PricesModule::get_price(LP_AUSD_DOT), None);
 		assert_ok!(Tokens::deposit(LP_AUSD_DOT, &1, 100));
 		assert_eq!(Tokens::total_issuance(LP_AUSD_DOT), 100);
-		assert_eq!(PricesModule::get_price(AUSD), Some(Price::saturating_from_rational(1000000u128, 1)));
+		assert_eq!(
+			PricesModule::get_price(AUSD),
+			Some(Price::saturating_from_rational(1000000u128, 1))
+		);
 		assert_eq!(
 			PricesModule::get_price(LP_AUSD_DOT),
-			Some(Price::saturating_from_rational(200000000u128, 1))	// 10000/100 * Price::saturating_from_rational(1000000u128, 1) * 2
+			Some(Price::saturating_from_rational(200000000u128, 1)) // 10000/100 * Price::saturating_from_rational(1000000u128, 1) * 2
 		);
 
 		assert_eq!(MockDEX::get_liquidity_pool(BTC, AUSD), (0, 0));
+		assert_eq!(PricesModule::get_price(LP_BTC_AUSD), None);
+	});
+}
+
+#[test]
+fn normalized_price_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			PricesModule::normalized_price(BTC),
+			Some((
+				Price::saturating_from_integer(50000),
+				8,
+				Price::saturating_from_integer(500000000000000u128)
+			))
+		);
 		assert_eq!(
-			PricesModule::get_price(LP_BTC_AUSD),
-			None
+			PricesModule::normalized_price(AUSD),
+			Some((
+				Price::saturating_from_integer(1),
+				12,
+				Price::saturating_from_integer(1000000)
+			))
 		);
+		assert_eq!(
+			PricesModule::normalized_price(LDOT),
+			Some((
+				Price::saturating_from_integer(50),
+				10,
+				Price::saturating_from_integer(5000000000u128)
+			))
+		);
+		assert_eq!(PricesModule::normalized_price(LP_BTC_AUSD), None);
+
+		assert_ok!(PricesModule::pause_price(Origin::signed(1), BTC));
+		assert_eq!(PricesModule::normalized_price(BTC), None);
 	});
 }
 
@@ -124,6 +189,25 @@ fn lock_price_work() {
 	});
 }
 
+#[test]
+fn get_price_timestamp_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(PricesModule::get_price_timestamp(BTC), Some(100));
+		assert_eq!(PricesModule::get_price_timestamp(DOT), Some(200));
+		assert_eq!(PricesModule::get_price_timestamp(ACA), Some(300));
+		assert_eq!(PricesModule::get_price_timestamp(AUSD), None);
+		assert_eq!(PricesModule::get_price_timestamp(LDOT), Some(200));
+		assert_eq!(PricesModule::get_price_timestamp(LP_BTC_AUSD), Some(100));
+		assert_eq!(PricesModule::get_price_timestamp(LP_AUSD_DOT), Some(200));
+		assert_eq!(PricesModule::get_price_timestamp(LP_DOT_BTC), Some(100));
+
+		LockedPrice::<Runtime>::insert(BTC, Price::saturating_from_integer(80000));
+		assert_eq!(PricesModule::get_price_timestamp(BTC), None);
+		assert_eq!(PricesModule::get_price_timestamp(LP_BTC_AUSD), None);
+		assert_eq!(PricesModule::get_price_timestamp(LP_DOT_BTC), Some(200));
+	});
+}
+
 #[test]
 fn lock_price_call_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -154,3 +238,147 @@ fn unlock_price_call_work() {
 		assert_eq!(PricesModule::locked_price(BTC), None);
 	});
 }
+
+#[test]
+fn pause_price_call_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(PricesModule::pause_price(Origin::signed(5), BTC), BadOrigin);
+		assert_ok!(PricesModule::pause_price(Origin::signed(1), BTC));
+
+		let pause_price_event = Event::prices(crate::Event::PricePaused(BTC));
+		assert!(System::events().iter().any(|record| record.event == pause_price_event));
+		assert_eq!(
+			PricesModule::paused_prices(BTC),
+			Some(PausedPriceInfo {
+				last_feed: Some(TimestampedValue {
+					value: Price::saturating_from_integer(50000),
+					timestamp: 100,
+				}),
+				in_band_updates: 0,
+			})
+		);
+		assert_noop!(
+			PricesModule::pause_price(Origin::signed(1), BTC),
+			Error::<Runtime>::AlreadyPaused
+		);
+
+		// paused currency and the currencies derived from it are unpriced
+		assert_eq!(PricesModule::get_price(BTC), None);
+		assert_eq!(PricesModule::get_relative_price(BTC, AUSD), None);
+		assert_eq!(PricesModule::get_price_timestamp(BTC), None);
+		assert_eq!(PricesModule::get_price_timestamp(LP_BTC_AUSD), None);
+	});
+}
+
+#[test]
+fn resume_price_call_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			PricesModule::resume_price(Origin::signed(1), BTC),
+			Error::<Runtime>::NotPaused
+		);
+		assert_ok!(PricesModule::pause_price(Origin::signed(1), BTC));
+		assert_noop!(PricesModule::resume_price(Origin::signed(5), BTC), BadOrigin);
+		assert_ok!(PricesModule::resume_price(Origin::signed(1), BTC));
+
+		let resume_price_event = Event::prices(crate::Event::PriceResumed(BTC));
+		assert!(System::events().iter().any(|record| record.event == resume_price_event));
+		assert_eq!(PricesModule::paused_prices(BTC), None);
+		assert_eq!(
+			PricesModule::get_price(BTC),
+			Some(Price::saturating_from_integer(500000000000000u128))
+		);
+	});
+}
+
+#[test]
+fn set_liquid_crowdloan_discount_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(10);
+		assert_noop!(
+			PricesModule::set_liquid_crowdloan_discount(Origin::signed(5), Ratio::saturating_from_rational(1, 10), 100),
+			BadOrigin
+		);
+		assert_noop!(
+			PricesModule::set_liquid_crowdloan_discount(Origin::signed(1), Ratio::saturating_from_rational(1, 10), 10),
+			Error::<Runtime>::InvalidDiscountCurve
+		);
+		assert_noop!(
+			PricesModule::set_liquid_crowdloan_discount(
+				Origin::signed(1),
+				Ratio::saturating_from_rational(11, 10),
+				100
+			),
+			Error::<Runtime>::InvalidDiscountCurve
+		);
+
+		assert_ok!(PricesModule::set_liquid_crowdloan_discount(
+			Origin::signed(1),
+			Ratio::saturating_from_rational(1, 10),
+			100
+		));
+		let discount_event = Event::prices(crate::Event::LiquidCrowdloanDiscountUpdated(
+			Ratio::saturating_from_rational(1, 10),
+			100,
+		));
+		assert!(System::events().iter().any(|record| record.event == discount_event));
+		assert_eq!(
+			PricesModule::liquid_crowdloan_discount(),
+			Some(DiscountCurve {
+				start: 10,
+				end: 100,
+				start_discount: Ratio::saturating_from_rational(1, 10),
+			})
+		);
+	});
+}
+
+#[test]
+fn paused_price_resume_automatically_after_in_band_updates() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PricesModule::pause_price(Origin::signed(1), BTC));
+
+		// no fresh update
+		PricesModule::on_initialize(2);
+		assert_eq!(PricesModule::paused_prices(BTC).unwrap().in_band_updates, 0);
+
+		// in-band update
+		MockDataProvider::feed_btc_price(Price::saturating_from_integer(51000), 101);
+		PricesModule::on_initialize(3);
+		assert_eq!(PricesModule::paused_prices(BTC).unwrap().in_band_updates, 1);
+
+		// out-of-band update resets the count
+		MockDataProvider::feed_btc_price(Price::saturating_from_integer(60000), 102);
+		PricesModule::on_initialize(4);
+		assert_eq!(
+			PricesModule::paused_prices(BTC),
+			Some(PausedPriceInfo {
+				last_feed: Some(TimestampedValue {
+					value: Price::saturating_from_integer(60000),
+					timestamp: 102,
+				}),
+				in_band_updates: 0,
+			})
+		);
+
+		MockDataProvider::feed_btc_price(Price::saturating_from_integer(61000), 103);
+		PricesModule::on_initialize(5);
+		MockDataProvider::feed_btc_price(Price::saturating_from_integer(60000), 104);
+		PricesModule::on_initialize(6);
+		assert_eq!(PricesModule::paused_prices(BTC).unwrap().in_band_updates, 2);
+		assert_eq!(PricesModule::get_price(BTC), None);
+
+		MockDataProvider::feed_btc_price(Price::saturating_from_integer(60500), 105);
+		PricesModule::on_initialize(7);
+		assert_eq!(PricesModule::paused_prices(BTC), None);
+		let resume_price_event = Event::prices(crate::Event::PriceResumed(BTC));
+		assert!(System::events().iter().any(|record| record.event == resume_price_event));
+		assert_eq!(
+			PricesModule::get_price(BTC),
+			Some(Price::saturating_from_integer(605000000000000u128))
+		);
+	});
+}
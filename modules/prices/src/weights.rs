@@ -48,13 +48,24 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for module_prices.
 pub trait WeightInfo {
+	fn on_initialize(c: u32, ) -> Weight;
 	fn lock_price() -> Weight;
 	fn unlock_price() -> Weight;
+	fn pause_price() -> Weight;
+	fn resume_price() -> Weight;
+	fn set_liquid_crowdloan_discount() -> Weight;
 }
 
 /// Weights for module_prices using the Acala node and recommended hardware.
 pub struct AcalaWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
+	fn on_initialize(c: u32, ) -> Weight {
+		(1_000_000 as Weight)
+			.saturating_add((6_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
 	fn lock_price() -> Weight {
 		(53_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(11 as Weight))
@@ -64,10 +75,31 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 		(12_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn pause_price() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn resume_price() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_liquid_crowdloan_discount() -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	fn on_initialize(c: u32, ) -> Weight {
+		(1_000_000 as Weight)
+			.saturating_add((6_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
 	fn lock_price() -> Weight {
 		(53_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
@@ -77,4 +109,18 @@ impl WeightInfo for () {
 		(12_000_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn pause_price() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn resume_price() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_liquid_crowdloan_discount() -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
@@ -0,0 +1,678 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Protocol Liquidity Module
+//!
+//! ## Overview
+//!
+//! Manages the protocol owned liquidity, so the DAO can bootstrap DEX pools
+//! without external liquidity providers. The treasury funds are deployed
+//! into the selected trading pairs and the LP shares are held by the module
+//! account, tracked as a position per trading pair separately from the
+//! liquidity of other providers. Withdrawn funds are returned to the
+//! treasury.
+//!
+//! Liquidity can be added and removed by governance, or automatically by a
+//! band strategy of the trading pair: about every `StrategyPeriod` blocks,
+//! at a random block so that the runs can't be anticipated, the module
+//! deploys more liquidity if the pool reserve is below the lower bound of the
+//! band, and withdraws its liquidity if the reserve is above the upper bound,
+//! e.g. after external providers have joined the pool. The strategies only
+//! run while the DEX price is within `MaxPriceDeviation` of the oracle
+//! price, and the deployed and withdrawn amounts are bounded by the oracle
+//! price too.
+//!
+//! The fees earned by a position are estimated by the growth of the
+//! liquidity per share, measured by `sqrt(reserve_0 * reserve_1)` of the
+//! pool, since the position was entered. They are realized when the
+//! liquidity is removed.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{log, pallet_prelude::*, traits::Randomness, transactional, PalletId};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use primitives::{Balance, CurrencyId, TradingPair};
+use sp_core::U256;
+use sp_runtime::{
+	traits::{AccountIdConversion, CheckedDiv, One, Saturating, UniqueSaturatedInto, Zero},
+	FixedPointNumber,
+};
+use sp_std::{convert::TryInto, prelude::*};
+use support::{DEXManager, Price, PriceProvider, Ratio};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The protocol owned liquidity of a trading pair. The pairs of amounts are
+/// in the order of the currencies of the trading pair.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct Position<Balance> {
+	/// The LP shares held by the module account.
+	pub shares: Balance,
+	/// The total amounts deployed into the pool.
+	pub deposited: (Balance, Balance),
+	/// The total amounts withdrawn from the pool, including the fees.
+	pub withdrawn: (Balance, Balance),
+	/// The fees realized by the withdrawn liquidity.
+	pub realized_fees: (Balance, Balance),
+	/// The average liquidity per share when the shares were added.
+	pub entry_liquidity_per_share: Ratio,
+}
+
+/// The band of pool reserve which the protocol owned liquidity is managed to
+/// keep.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct BandStrategy<Balance> {
+	/// The currency of the trading pair whose pool reserve is measured.
+	pub currency_id: CurrencyId,
+	/// Deploy more liquidity if the reserve is below it.
+	pub lower: Balance,
+	/// Withdraw liquidity if the reserve is above it.
+	pub upper: Balance,
+	/// The max amount of `currency_id` to deploy or withdraw per period.
+	pub step: Balance,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Currency for transfer assets
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// DEX to provide liquidity
+		type DEX: DEXManager<Self::AccountId, CurrencyId, Balance>;
+
+		/// The treasury account which funds the protocol owned liquidity
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The module id, keep the LP shares of protocol owned liquidity
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The average period to run band strategies, each run is scheduled
+		/// randomly between half and one and a half of the period after the
+		/// last one
+		#[pallet::constant]
+		type StrategyPeriod: Get<Self::BlockNumber>;
+
+		/// The randomness to schedule the runs of band strategies
+		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+
+		/// The oracle price to check the DEX price against
+		type PriceSource: PriceProvider<CurrencyId>;
+
+		/// The max deviation of the DEX price from the oracle price for band
+		/// strategies to run, also the tolerance of the deployed and
+		/// withdrawn amounts against the oracle price
+		#[pallet::constant]
+		type MaxPriceDeviation: Get<Ratio>;
+
+		/// The max count of band strategies
+		#[pallet::constant]
+		type MaxStrategies: Get<u32>;
+
+		/// The origin which may manage the protocol owned liquidity
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Invalid currency id
+		InvalidCurrencyId,
+		/// The trading pair has no protocol owned liquidity
+		NoPosition,
+		/// The protocol owned shares are not enough
+		NotEnoughShares,
+		/// Invalid band strategy
+		InvalidStrategy,
+		/// Band strategies exceed the max count
+		TooManyStrategies,
+		/// The added shares or the withdrawn amounts are below the minimum
+		BelowMinimumAmount,
+		/// No oracle price of the trading pair
+		NoOraclePrice,
+		/// The DEX price deviates too much from the oracle price
+		PriceDeviationTooLarge,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Protocol owned liquidity added. \[currency_id_0, pool_0_increment,
+		/// currency_id_1, pool_1_increment, share_increment\]
+		LiquidityAdded(CurrencyId, Balance, CurrencyId, Balance, Balance),
+		/// Protocol owned liquidity removed. \[currency_id_0,
+		/// pool_0_decrement, currency_id_1, pool_1_decrement,
+		/// share_decrement\]
+		LiquidityRemoved(CurrencyId, Balance, CurrencyId, Balance, Balance),
+		/// Fees earned by the removed liquidity. \[currency_id_0, fee_0,
+		/// currency_id_1, fee_1\]
+		FeesRealized(CurrencyId, Balance, CurrencyId, Balance),
+		/// The band strategy of trading pair updated. \[trading_pair,
+		/// strategy\]
+		BandStrategyUpdated(TradingPair, Option<BandStrategy<Balance>>),
+	}
+
+	/// The protocol owned liquidity positions.
+	///
+	/// Positions: map TradingPair => Option<Position>
+	#[pallet::storage]
+	#[pallet::getter(fn positions)]
+	pub type Positions<T: Config> = StorageMap<_, Twox64Concat, TradingPair, Position<Balance>, OptionQuery>;
+
+	/// The band strategies of trading pairs.
+	///
+	/// Strategies: map TradingPair => Option<BandStrategy>
+	#[pallet::storage]
+	#[pallet::getter(fn strategies)]
+	pub type Strategies<T: Config> = StorageMap<_, Twox64Concat, TradingPair, BandStrategy<Balance>, OptionQuery>;
+
+	/// The block to run the band strategies next time.
+	///
+	/// NextStrategyRunAt: T::BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn next_strategy_run_at)]
+	pub type NextStrategyRunAt<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Run the band strategies at the scheduled block.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			if now < Self::next_strategy_run_at() {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut count: u32 = 0;
+			for (trading_pair, strategy) in Strategies::<T>::iter() {
+				count += 1;
+				if let Err(e) = Self::rebalance(trading_pair, strategy) {
+					log::warn!(
+						target: "protocol-liquidity",
+						"rebalance: skipped rebalancing the protocol owned liquidity of {:?}: {:?}.",
+						trading_pair, e
+					);
+				}
+			}
+			Self::schedule_next_strategy_run(now);
+			T::WeightInfo::on_initialize(count)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Deploy the treasury funds into the pool as protocol owned
+		/// liquidity. The unused amounts stay in the treasury.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `max_amount_a`: maximum amount of currency_id_a to deploy.
+		/// - `max_amount_b`: maximum amount of currency_id_b to deploy.
+		/// - `min_share_increment`: minimum LP shares to receive.
+		#[pallet::weight(T::WeightInfo::add_liquidity())]
+		#[transactional]
+		pub fn add_liquidity(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			#[pallet::compact] max_amount_a: Balance,
+			#[pallet::compact] max_amount_b: Balance,
+			#[pallet::compact] min_share_increment: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::do_add_liquidity(
+				currency_id_a,
+				currency_id_b,
+				max_amount_a,
+				max_amount_b,
+				min_share_increment,
+			)?;
+			Ok(().into())
+		}
+
+		/// Remove the protocol owned liquidity from the pool and return the
+		/// withdrawn funds to the treasury.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `remove_share`: the LP shares to remove.
+		/// - `min_withdrawn_a`: minimum amount of currency_id_a to withdraw.
+		/// - `min_withdrawn_b`: minimum amount of currency_id_b to withdraw.
+		#[pallet::weight(T::WeightInfo::remove_liquidity())]
+		#[transactional]
+		pub fn remove_liquidity(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			#[pallet::compact] remove_share: Balance,
+			#[pallet::compact] min_withdrawn_a: Balance,
+			#[pallet::compact] min_withdrawn_b: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::do_remove_liquidity(
+				currency_id_a,
+				currency_id_b,
+				remove_share,
+				min_withdrawn_a,
+				min_withdrawn_b,
+			)?;
+			Ok(().into())
+		}
+
+		/// Update the band strategy of the trading pair, `None` means to stop
+		/// managing its liquidity automatically.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `strategy`: the band strategy, measured by the reserve of
+		///   `strategy.currency_id` which must be one of the trading pair.
+		#[pallet::weight(T::WeightInfo::set_band_strategy())]
+		#[transactional]
+		pub fn set_band_strategy(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			strategy: Option<BandStrategy<Balance>>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let trading_pair = TradingPair::from_token_currency_ids(currency_id_a, currency_id_b)
+				.ok_or(Error::<T>::InvalidCurrencyId)?;
+
+			match strategy {
+				Some(strategy) => {
+					ensure!(
+						(strategy.currency_id == trading_pair.0 || strategy.currency_id == trading_pair.1)
+							&& strategy.lower < strategy.upper
+							&& !strategy.step.is_zero(),
+						Error::<T>::InvalidStrategy
+					);
+					ensure!(
+						Strategies::<T>::contains_key(trading_pair)
+							|| (Strategies::<T>::iter().count() as u32) < T::MaxStrategies::get(),
+						Error::<T>::TooManyStrategies
+					);
+					Strategies::<T>::insert(trading_pair, strategy);
+				}
+				None => Strategies::<T>::remove(trading_pair),
+			}
+
+			Self::deposit_event(Event::BandStrategyUpdated(trading_pair, strategy));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// The liquidity per LP share of the pool, `sqrt(reserve_0 * reserve_1)
+	/// / total_shares`.
+	fn liquidity_per_share(trading_pair: TradingPair, lp_share_currency_id: CurrencyId) -> Option<Ratio> {
+		let (pool_0, pool_1) = T::DEX::get_liquidity_pool(trading_pair.0, trading_pair.1);
+		let liquidity: Balance = U256::from(pool_0)
+			.saturating_mul(U256::from(pool_1))
+			.integer_sqrt()
+			.try_into()
+			.ok()?;
+		Ratio::checked_from_rational(liquidity, T::Currency::total_issuance(lp_share_currency_id))
+	}
+
+	/// The underlying amounts of the protocol owned liquidity of the trading
+	/// pair and the fees earned by them, in the order of the trading pair.
+	/// Return `((amount_0, amount_1), (fee_0, fee_1))`.
+	pub fn position_value(
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+	) -> ((Balance, Balance), (Balance, Balance)) {
+		let trading_pair = TradingPair::new(currency_id_a, currency_id_b);
+		let position = match Self::positions(trading_pair) {
+			Some(position) => position,
+			None => return Default::default(),
+		};
+		let lp_share_currency_id = match trading_pair.get_dex_share_currency_id() {
+			Some(currency_id) => currency_id,
+			None => return Default::default(),
+		};
+
+		let (pool_0, pool_1) = T::DEX::get_liquidity_pool(trading_pair.0, trading_pair.1);
+		let proportion =
+			Ratio::checked_from_rational(position.shares, T::Currency::total_issuance(lp_share_currency_id))
+				.unwrap_or_default();
+		let amounts = (
+			proportion.saturating_mul_int(pool_0),
+			proportion.saturating_mul_int(pool_1),
+		);
+
+		// the proportion of the fees in the underlying amounts
+		let fee_proportion = Self::liquidity_per_share(trading_pair, lp_share_currency_id)
+			.filter(|current| *current > position.entry_liquidity_per_share)
+			.and_then(|current| {
+				current
+					.saturating_sub(position.entry_liquidity_per_share)
+					.checked_div(&current)
+			})
+			.unwrap_or_default();
+		let fees = (
+			fee_proportion.saturating_mul_int(amounts.0),
+			fee_proportion.saturating_mul_int(amounts.1),
+		);
+
+		(amounts, fees)
+	}
+
+	/// The fees earned by the protocol owned liquidity of the trading pair
+	/// which are not realized yet, in the order of the trading pair.
+	pub fn earned_fees(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+		Self::position_value(currency_id_a, currency_id_b).1
+	}
+
+	#[transactional]
+	fn do_add_liquidity(
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		max_amount_a: Balance,
+		max_amount_b: Balance,
+		min_share_increment: Balance,
+	) -> DispatchResult {
+		let trading_pair =
+			TradingPair::from_token_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+		let lp_share_currency_id = trading_pair
+			.get_dex_share_currency_id()
+			.ok_or(Error::<T>::InvalidCurrencyId)?;
+		let module_account_id = Self::account_id();
+		let treasury_account = T::TreasuryAccount::get();
+
+		T::Currency::transfer(currency_id_a, &treasury_account, &module_account_id, max_amount_a)?;
+		T::Currency::transfer(currency_id_b, &treasury_account, &module_account_id, max_amount_b)?;
+		let balances_before = Self::balances_of(trading_pair, lp_share_currency_id);
+		T::DEX::add_liquidity(
+			&module_account_id,
+			currency_id_a,
+			currency_id_b,
+			max_amount_a,
+			max_amount_b,
+			false,
+		)?;
+		let balances_after = Self::balances_of(trading_pair, lp_share_currency_id);
+		let pool_0_increment = balances_before.0.saturating_sub(balances_after.0);
+		let pool_1_increment = balances_before.1.saturating_sub(balances_after.1);
+		let share_increment = balances_after.2.saturating_sub(balances_before.2);
+		ensure!(share_increment >= min_share_increment, Error::<T>::BelowMinimumAmount);
+
+		// return the unused amounts to treasury
+		let (unused_a, unused_b) = if currency_id_a == trading_pair.0 {
+			(
+				max_amount_a.saturating_sub(pool_0_increment),
+				max_amount_b.saturating_sub(pool_1_increment),
+			)
+		} else {
+			(
+				max_amount_a.saturating_sub(pool_1_increment),
+				max_amount_b.saturating_sub(pool_0_increment),
+			)
+		};
+		T::Currency::transfer(currency_id_a, &module_account_id, &treasury_account, unused_a)?;
+		T::Currency::transfer(currency_id_b, &module_account_id, &treasury_account, unused_b)?;
+
+		let liquidity_per_share = Self::liquidity_per_share(trading_pair, lp_share_currency_id).unwrap_or_default();
+		Positions::<T>::mutate(trading_pair, |maybe_position| {
+			let position = maybe_position.get_or_insert_with(Default::default);
+			let total_shares = position.shares.saturating_add(share_increment);
+			// weighted average of the entry liquidity per share
+			position.entry_liquidity_per_share = Ratio::checked_from_rational(position.shares, total_shares)
+				.unwrap_or_default()
+				.saturating_mul(position.entry_liquidity_per_share)
+				.saturating_add(
+					Ratio::checked_from_rational(share_increment, total_shares)
+						.unwrap_or_default()
+						.saturating_mul(liquidity_per_share),
+				);
+			position.shares = total_shares;
+			position.deposited.0 = position.deposited.0.saturating_add(pool_0_increment);
+			position.deposited.1 = position.deposited.1.saturating_add(pool_1_increment);
+		});
+
+		Self::deposit_event(Event::LiquidityAdded(
+			trading_pair.0,
+			pool_0_increment,
+			trading_pair.1,
+			pool_1_increment,
+			share_increment,
+		));
+		Ok(())
+	}
+
+	#[transactional]
+	fn do_remove_liquidity(
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		remove_share: Balance,
+		min_withdrawn_a: Balance,
+		min_withdrawn_b: Balance,
+	) -> DispatchResult {
+		let trading_pair = TradingPair::new(currency_id_a, currency_id_b);
+		let lp_share_currency_id = trading_pair
+			.get_dex_share_currency_id()
+			.ok_or(Error::<T>::InvalidCurrencyId)?;
+		let position = Self::positions(trading_pair).ok_or(Error::<T>::NoPosition)?;
+		ensure!(position.shares >= remove_share, Error::<T>::NotEnoughShares);
+
+		// the fees earned by the removed shares are realized
+		let (_, earned_fees) = Self::position_value(trading_pair.0, trading_pair.1);
+		let removed_proportion = Ratio::checked_from_rational(remove_share, position.shares).unwrap_or_default();
+		let realized_fees = (
+			removed_proportion.saturating_mul_int(earned_fees.0),
+			removed_proportion.saturating_mul_int(earned_fees.1),
+		);
+
+		let module_account_id = Self::account_id();
+		let treasury_account = T::TreasuryAccount::get();
+		let balances_before = Self::balances_of(trading_pair, lp_share_currency_id);
+		T::DEX::remove_liquidity(&module_account_id, trading_pair.0, trading_pair.1, remove_share, false)?;
+		let balances_after = Self::balances_of(trading_pair, lp_share_currency_id);
+		let pool_0_decrement = balances_after.0.saturating_sub(balances_before.0);
+		let pool_1_decrement = balances_after.1.saturating_sub(balances_before.1);
+		let (min_withdrawn_0, min_withdrawn_1) = if currency_id_a == trading_pair.0 {
+			(min_withdrawn_a, min_withdrawn_b)
+		} else {
+			(min_withdrawn_b, min_withdrawn_a)
+		};
+		ensure!(
+			pool_0_decrement >= min_withdrawn_0 && pool_1_decrement >= min_withdrawn_1,
+			Error::<T>::BelowMinimumAmount
+		);
+
+		T::Currency::transfer(trading_pair.0, &module_account_id, &treasury_account, pool_0_decrement)?;
+		T::Currency::transfer(trading_pair.1, &module_account_id, &treasury_account, pool_1_decrement)?;
+
+		Positions::<T>::mutate(trading_pair, |maybe_position| {
+			if let Some(position) = maybe_position {
+				position.shares = position.shares.saturating_sub(remove_share);
+				position.withdrawn.0 = position.withdrawn.0.saturating_add(pool_0_decrement);
+				position.withdrawn.1 = position.withdrawn.1.saturating_add(pool_1_decrement);
+				position.realized_fees.0 = position.realized_fees.0.saturating_add(realized_fees.0);
+				position.realized_fees.1 = position.realized_fees.1.saturating_add(realized_fees.1);
+				if position.shares.is_zero() {
+					position.entry_liquidity_per_share = Zero::zero();
+				}
+			}
+		});
+
+		Self::deposit_event(Event::LiquidityRemoved(
+			trading_pair.0,
+			pool_0_decrement,
+			trading_pair.1,
+			pool_1_decrement,
+			remove_share,
+		));
+		if !realized_fees.0.is_zero() || !realized_fees.1.is_zero() {
+			Self::deposit_event(Event::FeesRealized(
+				trading_pair.0,
+				realized_fees.0,
+				trading_pair.1,
+				realized_fees.1,
+			));
+		}
+		Ok(())
+	}
+
+	/// Deploy or withdraw a step of liquidity to keep the pool reserve in the
+	/// band of the strategy. Pools without liquidity are not bootstrapped
+	/// automatically, and pools whose price deviates from the oracle price
+	/// are not touched.
+	fn rebalance(trading_pair: TradingPair, strategy: BandStrategy<Balance>) -> DispatchResult {
+		let currency_id = strategy.currency_id;
+		let other_currency_id = if currency_id == trading_pair.0 {
+			trading_pair.1
+		} else {
+			trading_pair.0
+		};
+		let (reserve, other_reserve) = T::DEX::get_liquidity_pool(currency_id, other_currency_id);
+		if reserve.is_zero() || other_reserve.is_zero() {
+			return Ok(());
+		}
+
+		// the price of currency_id in other_currency_id
+		let oracle_price = T::PriceSource::get_relative_price(currency_id, other_currency_id)
+			.filter(|price| !price.is_zero())
+			.ok_or(Error::<T>::NoOraclePrice)?;
+		let dex_price = Price::checked_from_rational(other_reserve, reserve).unwrap_or_default();
+		let deviation = if dex_price > oracle_price {
+			dex_price.saturating_sub(oracle_price)
+		} else {
+			oracle_price.saturating_sub(dex_price)
+		};
+		let max_deviation = T::MaxPriceDeviation::get();
+		ensure!(
+			deviation.checked_div(&oracle_price).unwrap_or_else(Ratio::max_value) <= max_deviation,
+			Error::<T>::PriceDeviationTooLarge
+		);
+		let tolerance = Ratio::one().saturating_sub(max_deviation);
+
+		if reserve < strategy.lower {
+			let treasury_account = T::TreasuryAccount::get();
+			let lp_share_currency_id = trading_pair
+				.get_dex_share_currency_id()
+				.ok_or(Error::<T>::InvalidCurrencyId)?;
+			let amount = strategy
+				.step
+				.min(strategy.lower.saturating_sub(reserve))
+				.min(T::Currency::free_balance(currency_id, &treasury_account));
+			let other_amount = Ratio::one()
+				.saturating_add(max_deviation)
+				.saturating_mul(oracle_price)
+				.checked_mul_int(amount)
+				.unwrap_or_default()
+				.saturating_add(One::one())
+				.min(T::Currency::free_balance(other_currency_id, &treasury_account));
+			let min_share_increment = Ratio::checked_from_rational(amount, reserve)
+				.and_then(|proportion| proportion.checked_mul_int(T::Currency::total_issuance(lp_share_currency_id)))
+				.map(|share_increment| tolerance.saturating_mul_int(share_increment))
+				.unwrap_or_default();
+			if !amount.is_zero() && !other_amount.is_zero() {
+				Self::do_add_liquidity(
+					currency_id,
+					other_currency_id,
+					amount,
+					other_amount,
+					min_share_increment,
+				)?;
+			}
+		} else if reserve > strategy.upper {
+			if let Some(position) = Self::positions(trading_pair) {
+				let lp_share_currency_id = trading_pair
+					.get_dex_share_currency_id()
+					.ok_or(Error::<T>::InvalidCurrencyId)?;
+				let amount = strategy.step.min(reserve.saturating_sub(strategy.upper));
+				let remove_share = Ratio::checked_from_rational(amount, reserve)
+					.and_then(|proportion| {
+						proportion.checked_mul_int(T::Currency::total_issuance(lp_share_currency_id))
+					})
+					.unwrap_or_default()
+					.min(position.shares);
+				// the withdrawn amounts are in proportion to the removed shares
+				let withdrawn =
+					Ratio::checked_from_rational(remove_share, T::Currency::total_issuance(lp_share_currency_id))
+						.unwrap_or_default()
+						.saturating_mul_int(reserve);
+				let min_withdrawn = tolerance.saturating_mul_int(withdrawn);
+				let min_other_withdrawn = tolerance.saturating_mul(oracle_price).saturating_mul_int(withdrawn);
+				if !remove_share.is_zero() {
+					Self::do_remove_liquidity(
+						currency_id,
+						other_currency_id,
+						remove_share,
+						min_withdrawn,
+						min_other_withdrawn,
+					)?;
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Schedule the next run of band strategies randomly between half and one
+	/// and a half of `StrategyPeriod` later.
+	fn schedule_next_strategy_run(now: T::BlockNumber) {
+		let period = T::StrategyPeriod::get();
+		let seed = T::Randomness::random(&b"protocol-liquidity"[..]).0;
+		let random = u32::decode(&mut seed.as_ref()).unwrap_or_default();
+		let offset = random % UniqueSaturatedInto::<u32>::unique_saturated_into(period).max(1);
+		NextStrategyRunAt::<T>::put(
+			now.saturating_add(period / 2u32.into())
+				.saturating_add(offset.into())
+				.max(now.saturating_add(One::one())),
+		);
+	}
+
+	/// The free balances of the module account, in the order of
+	/// `(currency_id_0, currency_id_1, lp_share_currency_id)`.
+	fn balances_of(trading_pair: TradingPair, lp_share_currency_id: CurrencyId) -> (Balance, Balance, Balance) {
+		let module_account_id = Self::account_id();
+		(
+			T::Currency::free_balance(trading_pair.0, &module_account_id),
+			T::Currency::free_balance(trading_pair.1, &module_account_id),
+			T::Currency::free_balance(lp_share_currency_id, &module_account_id),
+		)
+	}
+}
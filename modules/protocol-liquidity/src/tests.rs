@@ -0,0 +1,401 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the protocol liquidity module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use primitives::{DexShare, TokenSymbol};
+use sp_runtime::traits::BadOrigin;
+
+const AUSD_BTC_LP: CurrencyId =
+	CurrencyId::DexShare(DexShare::Token(TokenSymbol::AUSD), DexShare::Token(TokenSymbol::XBTC));
+
+fn pol_account() -> AccountId {
+	ProtocolLiquidityModule::account_id()
+}
+
+#[test]
+fn add_liquidity_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			ProtocolLiquidityModule::add_liquidity(Origin::signed(ALICE), AUSD, BTC, 10_000, 1_000, 0),
+			BadOrigin
+		);
+		assert_noop!(
+			ProtocolLiquidityModule::add_liquidity(Origin::signed(BOB), AUSD_BTC_LP, BTC, 10_000, 1_000, 0),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			ProtocolLiquidityModule::add_liquidity(Origin::signed(BOB), AUSD, BTC, 10_000, 1_000, 20_001),
+			Error::<Runtime>::BelowMinimumAmount
+		);
+
+		assert_ok!(ProtocolLiquidityModule::add_liquidity(
+			Origin::signed(BOB),
+			AUSD,
+			BTC,
+			10_000,
+			1_000,
+			20_000
+		));
+		let liquidity_added_event =
+			Event::protocol_liquidity(crate::Event::LiquidityAdded(AUSD, 10_000, BTC, 1_000, 20_000));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == liquidity_added_event));
+		assert_eq!(Currencies::free_balance(AUSD, &TREASURY), 90_000);
+		assert_eq!(Currencies::free_balance(BTC, &TREASURY), 99_000);
+		assert_eq!(Currencies::free_balance(AUSD_BTC_LP, &pol_account()), 20_000);
+		assert_eq!(DEXModule::get_liquidity_pool(AUSD, BTC), (10_000, 1_000));
+		assert_eq!(
+			ProtocolLiquidityModule::positions(TradingPair::new(AUSD, BTC)),
+			Some(Position {
+				shares: 20_000,
+				deposited: (10_000, 1_000),
+				withdrawn: (0, 0),
+				realized_fees: (0, 0),
+				entry_liquidity_per_share: Ratio::saturating_from_rational(3_162, 20_000),
+			})
+		);
+
+		// the unused amounts stay in treasury
+		assert_ok!(ProtocolLiquidityModule::add_liquidity(
+			Origin::signed(BOB),
+			BTC,
+			AUSD,
+			1_000,
+			5_000,
+			0
+		));
+		let liquidity_added_event =
+			Event::protocol_liquidity(crate::Event::LiquidityAdded(AUSD, 5_000, BTC, 500, 10_000));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == liquidity_added_event));
+		assert_eq!(Currencies::free_balance(AUSD, &TREASURY), 85_000);
+		assert_eq!(Currencies::free_balance(BTC, &TREASURY), 98_500);
+		assert_eq!(Currencies::free_balance(AUSD, &pol_account()), 0);
+		assert_eq!(Currencies::free_balance(BTC, &pol_account()), 0);
+		let position = ProtocolLiquidityModule::positions(TradingPair::new(AUSD, BTC)).unwrap();
+		assert_eq!(position.shares, 30_000);
+		assert_eq!(position.deposited, (15_000, 1_500));
+	});
+}
+
+#[test]
+fn remove_liquidity_and_realize_fees_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ProtocolLiquidityModule::add_liquidity(
+			Origin::signed(BOB),
+			AUSD,
+			BTC,
+			50_000,
+			5_000,
+			0
+		));
+		assert_noop!(
+			ProtocolLiquidityModule::remove_liquidity(Origin::signed(ALICE), AUSD, BTC, 100, 0, 0),
+			BadOrigin
+		);
+		assert_noop!(
+			ProtocolLiquidityModule::remove_liquidity(Origin::signed(BOB), AUSD, DOT, 100, 0, 0),
+			Error::<Runtime>::NoPosition
+		);
+		assert_noop!(
+			ProtocolLiquidityModule::remove_liquidity(Origin::signed(BOB), AUSD, BTC, 100_001, 0, 0),
+			Error::<Runtime>::NotEnoughShares
+		);
+		assert_eq!(ProtocolLiquidityModule::earned_fees(AUSD, BTC), (0, 0));
+
+		// trades pay fees to the pool
+		assert_ok!(DEXModule::swap_with_exact_supply(
+			Origin::signed(ALICE),
+			vec![AUSD, BTC],
+			10_000,
+			0,
+			None,
+			None
+		));
+		assert_ok!(DEXModule::swap_with_exact_supply(
+			Origin::signed(ALICE),
+			vec![BTC, AUSD],
+			826,
+			0,
+			None,
+			None
+		));
+		assert_eq!(DEXModule::get_liquidity_pool(AUSD, BTC), (50_171, 5_000));
+		assert_eq!(
+			ProtocolLiquidityModule::position_value(BTC, AUSD),
+			((50_171, 5_000), (85, 8))
+		);
+		assert_eq!(ProtocolLiquidityModule::earned_fees(AUSD, BTC), (85, 8));
+
+		assert_noop!(
+			ProtocolLiquidityModule::remove_liquidity(Origin::signed(BOB), BTC, AUSD, 50_000, 2_501, 25_085),
+			Error::<Runtime>::BelowMinimumAmount
+		);
+		assert_ok!(ProtocolLiquidityModule::remove_liquidity(
+			Origin::signed(BOB),
+			BTC,
+			AUSD,
+			50_000,
+			2_500,
+			25_085
+		));
+		let liquidity_removed_event =
+			Event::protocol_liquidity(crate::Event::LiquidityRemoved(AUSD, 25_085, BTC, 2_500, 50_000));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == liquidity_removed_event));
+		let fees_realized_event = Event::protocol_liquidity(crate::Event::FeesRealized(AUSD, 42, BTC, 4));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == fees_realized_event));
+		assert_eq!(Currencies::free_balance(AUSD, &TREASURY), 75_085);
+		assert_eq!(Currencies::free_balance(BTC, &TREASURY), 97_500);
+		assert_eq!(Currencies::free_balance(AUSD_BTC_LP, &pol_account()), 50_000);
+
+		let position = ProtocolLiquidityModule::positions(TradingPair::new(AUSD, BTC)).unwrap();
+		assert_eq!(position.shares, 50_000);
+		assert_eq!(position.deposited, (50_000, 5_000));
+		assert_eq!(position.withdrawn, (25_085, 2_500));
+		assert_eq!(position.realized_fees, (42, 4));
+	});
+}
+
+#[test]
+fn set_band_strategy_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let strategy = BandStrategy {
+			currency_id: AUSD,
+			lower: 15_000,
+			upper: 30_000,
+			step: 2_000,
+		};
+		assert_noop!(
+			ProtocolLiquidityModule::set_band_strategy(Origin::signed(ALICE), AUSD, BTC, Some(strategy)),
+			BadOrigin
+		);
+		assert_noop!(
+			ProtocolLiquidityModule::set_band_strategy(
+				Origin::signed(BOB),
+				AUSD,
+				BTC,
+				Some(BandStrategy {
+					currency_id: DOT,
+					..strategy
+				})
+			),
+			Error::<Runtime>::InvalidStrategy
+		);
+		assert_noop!(
+			ProtocolLiquidityModule::set_band_strategy(
+				Origin::signed(BOB),
+				AUSD,
+				BTC,
+				Some(BandStrategy {
+					lower: 30_000,
+					..strategy
+				})
+			),
+			Error::<Runtime>::InvalidStrategy
+		);
+		assert_noop!(
+			ProtocolLiquidityModule::set_band_strategy(
+				Origin::signed(BOB),
+				AUSD,
+				BTC,
+				Some(BandStrategy { step: 0, ..strategy })
+			),
+			Error::<Runtime>::InvalidStrategy
+		);
+
+		assert_ok!(ProtocolLiquidityModule::set_band_strategy(
+			Origin::signed(BOB),
+			BTC,
+			AUSD,
+			Some(strategy)
+		));
+		let strategy_updated_event = Event::protocol_liquidity(crate::Event::BandStrategyUpdated(
+			TradingPair::new(AUSD, BTC),
+			Some(strategy),
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == strategy_updated_event));
+		assert_eq!(
+			ProtocolLiquidityModule::strategies(TradingPair::new(AUSD, BTC)),
+			Some(strategy)
+		);
+
+		assert_ok!(ProtocolLiquidityModule::set_band_strategy(
+			Origin::signed(BOB),
+			AUSD,
+			DOT,
+			Some(strategy)
+		));
+		assert_noop!(
+			ProtocolLiquidityModule::set_band_strategy(
+				Origin::signed(BOB),
+				BTC,
+				DOT,
+				Some(BandStrategy {
+					currency_id: BTC,
+					..strategy
+				})
+			),
+			Error::<Runtime>::TooManyStrategies
+		);
+		// update the existing strategy
+		assert_ok!(ProtocolLiquidityModule::set_band_strategy(
+			Origin::signed(BOB),
+			AUSD,
+			DOT,
+			Some(BandStrategy {
+				step: 1_000,
+				..strategy
+			})
+		));
+
+		assert_ok!(ProtocolLiquidityModule::set_band_strategy(
+			Origin::signed(BOB),
+			AUSD,
+			DOT,
+			None
+		));
+		assert_eq!(ProtocolLiquidityModule::strategies(TradingPair::new(AUSD, DOT)), None);
+	});
+}
+
+#[test]
+fn band_strategy_rebalance_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ProtocolLiquidityModule::set_band_strategy(
+			Origin::signed(BOB),
+			AUSD,
+			BTC,
+			Some(BandStrategy {
+				currency_id: AUSD,
+				lower: 15_000,
+				upper: 30_000,
+				step: 2_000,
+			})
+		));
+
+		// empty pool is not bootstrapped automatically
+		ProtocolLiquidityModule::on_initialize(10);
+		assert_eq!(ProtocolLiquidityModule::positions(TradingPair::new(AUSD, BTC)), None);
+		assert_eq!(ProtocolLiquidityModule::next_strategy_run_at(), 15);
+
+		assert_ok!(ProtocolLiquidityModule::add_liquidity(
+			Origin::signed(BOB),
+			AUSD,
+			BTC,
+			10_000,
+			1_000,
+			0
+		));
+
+		ProtocolLiquidityModule::on_initialize(14);
+		assert_eq!(DEXModule::get_liquidity_pool(AUSD, BTC), (10_000, 1_000));
+
+		// deploy a step of liquidity below the band
+		ProtocolLiquidityModule::on_initialize(15);
+		assert_eq!(DEXModule::get_liquidity_pool(AUSD, BTC), (12_000, 1_200));
+		assert_eq!(Currencies::free_balance(AUSD, &TREASURY), 88_000);
+		assert_eq!(Currencies::free_balance(BTC, &TREASURY), 98_800);
+		assert_eq!(
+			ProtocolLiquidityModule::positions(TradingPair::new(AUSD, BTC))
+				.unwrap()
+				.shares,
+			24_000
+		);
+		assert_eq!(ProtocolLiquidityModule::next_strategy_run_at(), 20);
+
+		// withdraw a step of liquidity after external providers joined
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			AUSD,
+			BTC,
+			30_000,
+			3_000,
+			false
+		));
+		assert_eq!(DEXModule::get_liquidity_pool(AUSD, BTC), (42_000, 4_200));
+		ProtocolLiquidityModule::on_initialize(20);
+		let liquidity_removed_event =
+			Event::protocol_liquidity(crate::Event::LiquidityRemoved(AUSD, 1_999, BTC, 199, 3_999));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == liquidity_removed_event));
+		assert_eq!(DEXModule::get_liquidity_pool(AUSD, BTC), (40_001, 4_001));
+		assert_eq!(Currencies::free_balance(AUSD, &TREASURY), 89_999);
+		assert_eq!(Currencies::free_balance(BTC, &TREASURY), 98_999);
+		assert_eq!(
+			ProtocolLiquidityModule::positions(TradingPair::new(AUSD, BTC))
+				.unwrap()
+				.shares,
+			20_001
+		);
+	});
+}
+
+#[test]
+fn band_strategy_skips_deviated_pool() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(ProtocolLiquidityModule::set_band_strategy(
+			Origin::signed(BOB),
+			AUSD,
+			BTC,
+			Some(BandStrategy {
+				currency_id: AUSD,
+				lower: 15_000,
+				upper: 30_000,
+				step: 2_000,
+			})
+		));
+		assert_ok!(ProtocolLiquidityModule::add_liquidity(
+			Origin::signed(BOB),
+			AUSD,
+			BTC,
+			10_000,
+			1_000,
+			0
+		));
+
+		// no oracle price
+		MockPriceSource::set_relative_price(None);
+		ProtocolLiquidityModule::on_initialize(10);
+		assert_eq!(DEXModule::get_liquidity_pool(AUSD, BTC), (10_000, 1_000));
+
+		// the DEX price deviates more than 10% from the oracle price
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(12, 100)));
+		ProtocolLiquidityModule::on_initialize(15);
+		assert_eq!(DEXModule::get_liquidity_pool(AUSD, BTC), (10_000, 1_000));
+
+		// within the deviation, the deployed amount is bounded by the oracle price
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_rational(105, 1000)));
+		ProtocolLiquidityModule::on_initialize(20);
+		assert_eq!(DEXModule::get_liquidity_pool(AUSD, BTC), (12_000, 1_200));
+	});
+}
@@ -0,0 +1,424 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Revocable Vesting Module
+//!
+//! ## Overview
+//!
+//! Vesting schedules for contributor compensation. Governance designates
+//! issuers, and an issuer may transfer native tokens to a beneficiary under a
+//! vesting schedule with a cliff. Nothing vests before the cliff, after which
+//! the schedule releases `per_period` every `period` blocks counted from
+//! `start`.
+//!
+//! Before the cliff, the issuer may request the revocation of a schedule. The
+//! schedule is frozen and the revocation can be disputed during
+//! `DisputeDelay` blocks, in which `DisputeOrigin` may cancel it. Once the
+//! delay has passed, anyone may execute the revocation, which returns the
+//! unvested funds to the issuer.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode, HasCompact};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement, LockIdentifier, LockableCurrency, WithdrawReasons},
+	transactional,
+};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{
+	traits::{AtLeast32Bit, CheckedMul, Saturating, StaticLookup, UniqueSaturatedInto, Zero},
+	RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub const VESTING_LOCK_ID: LockIdentifier = *b"rvesting";
+
+pub type ScheduleId = u32;
+
+/// A vesting schedule that its issuer may revoke before the cliff.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct RevocableVestingSchedule<AccountId, BlockNumber, Balance: HasCompact> {
+	/// The issuer, who receives the unvested funds on revocation.
+	pub issuer: AccountId,
+	/// Vesting starting block.
+	pub start: BlockNumber,
+	/// Nothing vests before this block.
+	pub cliff: BlockNumber,
+	/// Number of blocks between vest.
+	pub period: BlockNumber,
+	/// Number of vest.
+	pub period_count: u32,
+	/// Amount of tokens to release per vest.
+	#[codec(compact)]
+	pub per_period: Balance,
+	/// The block from which a requested revocation can be executed.
+	pub revocable_at: Option<BlockNumber>,
+}
+
+impl<AccountId, BlockNumber: AtLeast32Bit + Copy, Balance: AtLeast32Bit + Copy>
+	RevocableVestingSchedule<AccountId, BlockNumber, Balance>
+{
+	/// Returns the total amount of the schedule, or `None` on overflow.
+	pub fn total_amount(&self) -> Option<Balance> {
+		self.per_period.checked_mul(&self.period_count.into())
+	}
+
+	/// Returns the amount still locked at `time`. A schedule pending
+	/// revocation is frozen and stays fully locked.
+	pub fn locked_amount(&self, time: BlockNumber) -> Balance {
+		let total = self.total_amount().unwrap_or_else(Zero::zero);
+		if self.revocable_at.is_some() || time < self.cliff {
+			return total;
+		}
+
+		let periods = time.saturating_sub(self.start) / self.period;
+		let vested_periods = periods.min(self.period_count.into());
+		let vested_periods: u32 = vested_periods.unique_saturated_into();
+		let vested = self.per_period.saturating_mul(vested_periods.into());
+		total.saturating_sub(vested)
+	}
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type ScheduleOf<T> = RevocableVestingSchedule<
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+		BalanceOf<T>,
+	>;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency of the vested funds.
+		type Currency: LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>;
+
+		/// The minimum amount transferred to create a schedule.
+		#[pallet::constant]
+		type MinVestedTransfer: Get<BalanceOf<Self>>;
+
+		/// The maximum number of schedules per beneficiary.
+		#[pallet::constant]
+		type MaxVestingSchedules: Get<u32>;
+
+		/// The number of blocks in which a requested revocation can be
+		/// disputed.
+		#[pallet::constant]
+		type DisputeDelay: Get<Self::BlockNumber>;
+
+		/// The origin which may designate issuers.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may cancel a requested revocation.
+		type DisputeOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The caller is not a designated issuer
+		NotIssuer,
+		/// The caller is not the issuer of the schedule
+		NotScheduleIssuer,
+		/// The cliff is before the start, the period is zero or the period
+		/// count is zero
+		InvalidSchedule,
+		/// The total amount overflows
+		NumOverflow,
+		/// The total amount is below `MinVestedTransfer`
+		AmountLow,
+		/// The beneficiary has too many schedules
+		TooManyVestingSchedules,
+		/// The schedule does not exist
+		ScheduleNotFound,
+		/// The cliff has been reached
+		CliffReached,
+		/// A revocation has already been requested
+		RevocationPending,
+		/// No revocation has been requested
+		NoPendingRevocation,
+		/// The dispute delay has not passed yet
+		DisputeDelayNotPassed,
+		/// No more schedule ids are available
+		NoAvailableScheduleId,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId", T::BlockNumber = "BlockNumber", BalanceOf<T> = "Balance")]
+	pub enum Event<T: Config> {
+		/// Designated or removed an issuer. \[who, is_issuer\]
+		IssuerUpdated(T::AccountId, bool),
+		/// Added a vesting schedule. \[schedule_id, issuer, beneficiary,
+		/// total\]
+		VestingScheduleAdded(ScheduleId, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// Claimed vested funds. \[who, locked_amount\]
+		Claimed(T::AccountId, BalanceOf<T>),
+		/// Requested the revocation of a schedule. \[schedule_id, beneficiary,
+		/// revocable_at\]
+		RevocationRequested(ScheduleId, T::AccountId, T::BlockNumber),
+		/// Cancelled the revocation of a schedule. \[schedule_id,
+		/// beneficiary\]
+		RevocationCancelled(ScheduleId, T::AccountId),
+		/// Revoked a schedule and returned the unvested funds to the issuer.
+		/// \[schedule_id, beneficiary, issuer, amount\]
+		VestingScheduleRevoked(ScheduleId, T::AccountId, T::AccountId, BalanceOf<T>),
+	}
+
+	/// The designated issuers.
+	///
+	/// map AccountId => bool
+	#[pallet::storage]
+	#[pallet::getter(fn issuers)]
+	pub type Issuers<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, bool, ValueQuery>;
+
+	/// The id of the next schedule.
+	#[pallet::storage]
+	#[pallet::getter(fn next_schedule_id)]
+	pub type NextScheduleId<T: Config> = StorageValue<_, ScheduleId, ValueQuery>;
+
+	/// The vesting schedules of beneficiaries.
+	///
+	/// double_map Beneficiary, ScheduleId => Option<RevocableVestingSchedule>
+	#[pallet::storage]
+	#[pallet::getter(fn vesting_schedules)]
+	pub type VestingSchedules<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, ScheduleId, ScheduleOf<T>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Designate or remove an issuer.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::set_issuer())]
+		pub fn set_issuer(origin: OriginFor<T>, who: T::AccountId, is_issuer: bool) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if is_issuer {
+				Issuers::<T>::insert(&who, true);
+			} else {
+				Issuers::<T>::remove(&who);
+			}
+			Self::deposit_event(Event::IssuerUpdated(who, is_issuer));
+			Ok(().into())
+		}
+
+		/// Transfer funds to `dest` under a revocable vesting schedule.
+		///
+		/// The dispatch origin of this call must be a designated issuer.
+		#[pallet::weight(T::WeightInfo::vested_transfer())]
+		#[transactional]
+		pub fn vested_transfer(
+			origin: OriginFor<T>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			start: T::BlockNumber,
+			cliff: T::BlockNumber,
+			period: T::BlockNumber,
+			period_count: u32,
+			per_period: BalanceOf<T>,
+		) -> DispatchResultWithPostInfo {
+			let issuer = ensure_signed(origin)?;
+			ensure!(Self::issuers(&issuer), Error::<T>::NotIssuer);
+			let dest = T::Lookup::lookup(dest)?;
+
+			let schedule = RevocableVestingSchedule {
+				issuer: issuer.clone(),
+				start,
+				cliff,
+				period,
+				period_count,
+				per_period,
+				revocable_at: None,
+			};
+			ensure!(
+				cliff >= start && !period.is_zero() && period_count != 0,
+				Error::<T>::InvalidSchedule
+			);
+			let total = schedule.total_amount().ok_or(Error::<T>::NumOverflow)?;
+			ensure!(total >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+			ensure!(
+				(VestingSchedules::<T>::iter_prefix(&dest).count() as u32) < T::MaxVestingSchedules::get(),
+				Error::<T>::TooManyVestingSchedules
+			);
+
+			let schedule_id = NextScheduleId::<T>::try_mutate(|id| -> Result<ScheduleId, DispatchError> {
+				let current_id = *id;
+				*id = id.checked_add(1).ok_or(Error::<T>::NoAvailableScheduleId)?;
+				Ok(current_id)
+			})?;
+
+			T::Currency::transfer(&issuer, &dest, total, ExistenceRequirement::AllowDeath)?;
+			VestingSchedules::<T>::insert(&dest, schedule_id, schedule);
+			Self::update_lock(&dest);
+
+			Self::deposit_event(Event::VestingScheduleAdded(schedule_id, issuer, dest, total));
+			Ok(().into())
+		}
+
+		/// Unlock the vested funds of the caller.
+		#[pallet::weight(T::WeightInfo::claim())]
+		pub fn claim(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let locked = Self::update_lock(&who);
+			Self::deposit_event(Event::Claimed(who, locked));
+			Ok(().into())
+		}
+
+		/// Request the revocation of a schedule before its cliff. The
+		/// schedule is frozen until the revocation is executed or cancelled.
+		///
+		/// The dispatch origin of this call must be the issuer of the
+		/// schedule.
+		#[pallet::weight(T::WeightInfo::request_revocation())]
+		pub fn request_revocation(
+			origin: OriginFor<T>,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			schedule_id: ScheduleId,
+		) -> DispatchResultWithPostInfo {
+			let issuer = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			let revocable_at = VestingSchedules::<T>::try_mutate(
+				&beneficiary,
+				schedule_id,
+				|maybe_schedule| -> Result<T::BlockNumber, DispatchError> {
+					let schedule = maybe_schedule.as_mut().ok_or(Error::<T>::ScheduleNotFound)?;
+					ensure!(schedule.issuer == issuer, Error::<T>::NotScheduleIssuer);
+					ensure!(schedule.revocable_at.is_none(), Error::<T>::RevocationPending);
+					ensure!(now < schedule.cliff, Error::<T>::CliffReached);
+
+					let revocable_at = now.saturating_add(T::DisputeDelay::get());
+					schedule.revocable_at = Some(revocable_at);
+					Ok(revocable_at)
+				},
+			)?;
+
+			Self::deposit_event(Event::RevocationRequested(schedule_id, beneficiary, revocable_at));
+			Ok(().into())
+		}
+
+		/// Cancel a requested revocation, resuming the schedule.
+		///
+		/// The dispatch origin of this call must be `DisputeOrigin`.
+		#[pallet::weight(T::WeightInfo::cancel_revocation())]
+		pub fn cancel_revocation(
+			origin: OriginFor<T>,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			schedule_id: ScheduleId,
+		) -> DispatchResultWithPostInfo {
+			T::DisputeOrigin::ensure_origin(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+
+			VestingSchedules::<T>::try_mutate(&beneficiary, schedule_id, |maybe_schedule| -> DispatchResult {
+				let schedule = maybe_schedule.as_mut().ok_or(Error::<T>::ScheduleNotFound)?;
+				ensure!(schedule.revocable_at.take().is_some(), Error::<T>::NoPendingRevocation);
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::RevocationCancelled(schedule_id, beneficiary));
+			Ok(().into())
+		}
+
+		/// Execute a revocation once the dispute delay has passed, returning
+		/// the unvested funds to the issuer.
+		#[pallet::weight(T::WeightInfo::execute_revocation())]
+		#[transactional]
+		pub fn execute_revocation(
+			origin: OriginFor<T>,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			schedule_id: ScheduleId,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+
+			let schedule = Self::vesting_schedules(&beneficiary, schedule_id).ok_or(Error::<T>::ScheduleNotFound)?;
+			let revocable_at = schedule.revocable_at.ok_or(Error::<T>::NoPendingRevocation)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() >= revocable_at,
+				Error::<T>::DisputeDelayNotPassed
+			);
+
+			let amount = schedule.locked_amount(revocable_at);
+			VestingSchedules::<T>::remove(&beneficiary, schedule_id);
+			Self::update_lock(&beneficiary);
+			T::Currency::transfer(&beneficiary, &schedule.issuer, amount, ExistenceRequirement::AllowDeath)?;
+
+			Self::deposit_event(Event::VestingScheduleRevoked(
+				schedule_id,
+				beneficiary,
+				schedule.issuer,
+				amount,
+			));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns the amount locked for `who` at the current block.
+	pub fn locked_balance(who: &T::AccountId) -> BalanceOf<T> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		VestingSchedules::<T>::iter_prefix_values(who).fold(Zero::zero(), |acc: BalanceOf<T>, schedule| {
+			acc.saturating_add(schedule.locked_amount(now))
+		})
+	}
+
+	/// Set the vesting lock of `who` to the locked amount, removing fully
+	/// vested schedules. Returns the locked amount.
+	fn update_lock(who: &T::AccountId) -> BalanceOf<T> {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let mut locked: BalanceOf<T> = Zero::zero();
+		for (schedule_id, schedule) in VestingSchedules::<T>::iter_prefix(who).collect::<Vec<_>>() {
+			let amount = schedule.locked_amount(now);
+			if amount.is_zero() {
+				VestingSchedules::<T>::remove(who, schedule_id);
+			} else {
+				locked = locked.saturating_add(amount);
+			}
+		}
+
+		if locked.is_zero() {
+			T::Currency::remove_lock(VESTING_LOCK_ID, who);
+		} else {
+			T::Currency::set_lock(VESTING_LOCK_ID, who, locked, WithdrawReasons::all());
+		}
+		locked
+	}
+}
@@ -0,0 +1,240 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the revocable vesting module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, ExtBuilder, Origin, PalletBalances, RevocableVestingModule, Runtime, System, ALICE, BOB, CHARLIE};
+use pallet_balances::{BalanceLock, Reasons};
+use sp_runtime::traits::BadOrigin;
+
+const COUNCIL: mock::AccountId = 10;
+
+fn lock_of(amount: mock::Balance) -> Vec<BalanceLock<mock::Balance>> {
+	vec![BalanceLock {
+		id: VESTING_LOCK_ID,
+		amount,
+		reasons: Reasons::All,
+	}]
+}
+
+// start 1, cliff 11, 4 periods of 10 blocks releasing 25 each
+fn vest_to_bob() -> DispatchResultWithPostInfo {
+	RevocableVestingModule::vested_transfer(Origin::signed(ALICE), BOB, 1, 11, 10, 4, 25)
+}
+
+#[test]
+fn set_issuer_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			RevocableVestingModule::set_issuer(Origin::signed(ALICE), ALICE, true),
+			BadOrigin
+		);
+
+		assert_ok!(RevocableVestingModule::set_issuer(Origin::signed(COUNCIL), ALICE, true));
+		let issuer_updated_event = Event::revocable_vesting(crate::Event::IssuerUpdated(ALICE, true));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == issuer_updated_event));
+		assert!(RevocableVestingModule::issuers(ALICE));
+
+		assert_ok!(RevocableVestingModule::set_issuer(
+			Origin::signed(COUNCIL),
+			ALICE,
+			false
+		));
+		assert!(!RevocableVestingModule::issuers(ALICE));
+	});
+}
+
+#[test]
+fn vested_transfer_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(vest_to_bob(), Error::<Runtime>::NotIssuer);
+		assert_ok!(RevocableVestingModule::set_issuer(Origin::signed(COUNCIL), ALICE, true));
+
+		assert_noop!(
+			RevocableVestingModule::vested_transfer(Origin::signed(ALICE), BOB, 11, 1, 10, 4, 25),
+			Error::<Runtime>::InvalidSchedule
+		);
+		assert_noop!(
+			RevocableVestingModule::vested_transfer(Origin::signed(ALICE), BOB, 1, 11, 0, 4, 25),
+			Error::<Runtime>::InvalidSchedule
+		);
+		assert_noop!(
+			RevocableVestingModule::vested_transfer(Origin::signed(ALICE), BOB, 1, 11, 10, 4, 2),
+			Error::<Runtime>::AmountLow
+		);
+
+		assert_ok!(vest_to_bob());
+		let schedule_added_event = Event::revocable_vesting(crate::Event::VestingScheduleAdded(0, ALICE, BOB, 100));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == schedule_added_event));
+		assert_eq!(PalletBalances::free_balance(ALICE), 900);
+		assert_eq!(PalletBalances::free_balance(BOB), 100);
+		assert_eq!(PalletBalances::locks(BOB), lock_of(100));
+
+		assert_ok!(vest_to_bob());
+		assert_eq!(PalletBalances::locks(BOB), lock_of(200));
+		assert_noop!(vest_to_bob(), Error::<Runtime>::TooManyVestingSchedules);
+	});
+}
+
+#[test]
+fn claim_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(RevocableVestingModule::set_issuer(Origin::signed(COUNCIL), ALICE, true));
+		assert_ok!(vest_to_bob());
+
+		System::set_block_number(10);
+		assert_ok!(RevocableVestingModule::claim(Origin::signed(BOB)));
+		assert_eq!(PalletBalances::locks(BOB), lock_of(100));
+
+		System::set_block_number(11);
+		assert_ok!(RevocableVestingModule::claim(Origin::signed(BOB)));
+		let claimed_event = Event::revocable_vesting(crate::Event::Claimed(BOB, 75));
+		assert!(System::events().iter().any(|record| record.event == claimed_event));
+		assert_eq!(PalletBalances::locks(BOB), lock_of(75));
+
+		System::set_block_number(41);
+		assert_ok!(RevocableVestingModule::claim(Origin::signed(BOB)));
+		assert_eq!(PalletBalances::locks(BOB), vec![]);
+		assert_eq!(RevocableVestingModule::vesting_schedules(BOB, 0), None);
+	});
+}
+
+#[test]
+fn revocation_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(RevocableVestingModule::set_issuer(Origin::signed(COUNCIL), ALICE, true));
+		assert_ok!(RevocableVestingModule::set_issuer(
+			Origin::signed(COUNCIL),
+			CHARLIE,
+			true
+		));
+		assert_ok!(vest_to_bob());
+
+		assert_noop!(
+			RevocableVestingModule::request_revocation(Origin::signed(CHARLIE), BOB, 0),
+			Error::<Runtime>::NotScheduleIssuer
+		);
+		assert_noop!(
+			RevocableVestingModule::request_revocation(Origin::signed(ALICE), BOB, 1),
+			Error::<Runtime>::ScheduleNotFound
+		);
+		assert_noop!(
+			RevocableVestingModule::execute_revocation(Origin::signed(CHARLIE), BOB, 0),
+			Error::<Runtime>::NoPendingRevocation
+		);
+
+		System::set_block_number(8);
+		assert_ok!(RevocableVestingModule::request_revocation(
+			Origin::signed(ALICE),
+			BOB,
+			0
+		));
+		let revocation_requested_event = Event::revocable_vesting(crate::Event::RevocationRequested(0, BOB, 13));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == revocation_requested_event));
+		assert_noop!(
+			RevocableVestingModule::request_revocation(Origin::signed(ALICE), BOB, 0),
+			Error::<Runtime>::RevocationPending
+		);
+
+		// the schedule is frozen past the cliff while the revocation is pending
+		System::set_block_number(12);
+		assert_eq!(RevocableVestingModule::locked_balance(&BOB), 100);
+		assert_noop!(
+			RevocableVestingModule::execute_revocation(Origin::signed(CHARLIE), BOB, 0),
+			Error::<Runtime>::DisputeDelayNotPassed
+		);
+
+		System::set_block_number(13);
+		assert_ok!(RevocableVestingModule::execute_revocation(
+			Origin::signed(CHARLIE),
+			BOB,
+			0
+		));
+		let schedule_revoked_event = Event::revocable_vesting(crate::Event::VestingScheduleRevoked(0, BOB, ALICE, 100));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == schedule_revoked_event));
+		assert_eq!(PalletBalances::free_balance(ALICE), 1_000);
+		assert_eq!(PalletBalances::free_balance(BOB), 0);
+		assert_eq!(PalletBalances::locks(BOB), vec![]);
+		assert_eq!(RevocableVestingModule::vesting_schedules(BOB, 0), None);
+	});
+}
+
+#[test]
+fn cannot_request_revocation_after_cliff() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(RevocableVestingModule::set_issuer(Origin::signed(COUNCIL), ALICE, true));
+		assert_ok!(vest_to_bob());
+
+		System::set_block_number(11);
+		assert_noop!(
+			RevocableVestingModule::request_revocation(Origin::signed(ALICE), BOB, 0),
+			Error::<Runtime>::CliffReached
+		);
+	});
+}
+
+#[test]
+fn cancel_revocation_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(RevocableVestingModule::set_issuer(Origin::signed(COUNCIL), ALICE, true));
+		assert_ok!(vest_to_bob());
+		assert_ok!(RevocableVestingModule::request_revocation(
+			Origin::signed(ALICE),
+			BOB,
+			0
+		));
+
+		assert_noop!(
+			RevocableVestingModule::cancel_revocation(Origin::signed(ALICE), BOB, 0),
+			BadOrigin
+		);
+		assert_ok!(RevocableVestingModule::cancel_revocation(
+			Origin::signed(COUNCIL),
+			BOB,
+			0
+		));
+		let revocation_cancelled_event = Event::revocable_vesting(crate::Event::RevocationCancelled(0, BOB));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == revocation_cancelled_event));
+		assert_noop!(
+			RevocableVestingModule::cancel_revocation(Origin::signed(COUNCIL), BOB, 0),
+			Error::<Runtime>::NoPendingRevocation
+		);
+
+		// the schedule resumes after the cancellation
+		System::set_block_number(21);
+		assert_eq!(RevocableVestingModule::locked_balance(&BOB), 50);
+		assert_noop!(
+			RevocableVestingModule::execute_revocation(Origin::signed(ALICE), BOB, 0),
+			Error::<Runtime>::NoPendingRevocation
+		);
+	});
+}
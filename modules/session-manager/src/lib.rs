@@ -0,0 +1,232 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Session Manager Module
+//!
+//! ## Overview
+//!
+//! Ends the sessions of `pallet_session` every `SessionDuration` blocks
+//! counted from `DurationOffset`, as its `ShouldEndSession` and
+//! `NextSessionRotation`.
+//!
+//! Governance may schedule a change of the session duration from a future
+//! session. The change takes effect when the session starts, and the new
+//! duration is counted from its first block. The session durations must be
+//! multiples of `SlotDuration`, so that the sessions end at slot boundaries.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{pallet_prelude::*, traits::EstimateNextSessionRotation};
+use frame_system::pallet_prelude::*;
+use pallet_session::ShouldEndSession;
+use sp_runtime::{
+	traits::{One, Saturating, Zero},
+	Permill,
+};
+use sp_staking::SessionIndex;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_session::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may schedule the session duration changes
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The number of blocks of a collator slot, the session durations
+		/// must be multiples of it
+		#[pallet::constant]
+		type SlotDuration: Get<Self::BlockNumber>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The session is not a future session
+		InvalidSession,
+		/// The duration is zero or not a multiple of the slot duration
+		InvalidDuration,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	#[pallet::metadata(T::BlockNumber = "BlockNumber")]
+	pub enum Event<T: Config> {
+		/// Scheduled a session duration change. \[start_session, duration\]
+		SessionDurationScheduled(SessionIndex, T::BlockNumber),
+		/// The session duration changed as the session started.
+		/// \[session, duration, offset\]
+		SessionDurationChanged(SessionIndex, T::BlockNumber, T::BlockNumber),
+	}
+
+	/// The number of blocks of a session.
+	///
+	/// SessionDuration: BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn session_duration)]
+	pub type SessionDuration<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// The block from which the sessions of the current duration are counted.
+	///
+	/// DurationOffset: BlockNumber
+	#[pallet::storage]
+	#[pallet::getter(fn duration_offset)]
+	pub type DurationOffset<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	/// The scheduled session durations by the session they take effect from.
+	///
+	/// map SessionIndex => Option<BlockNumber>
+	#[pallet::storage]
+	#[pallet::getter(fn session_duration_changes)]
+	pub type SessionDurationChanges<T: Config> = StorageMap<_, Twox64Concat, SessionIndex, T::BlockNumber, OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub session_duration: T::BlockNumber,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			GenesisConfig {
+				session_duration: Default::default(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			assert!(
+				Pallet::<T>::is_valid_duration(self.session_duration),
+				"invalid session duration"
+			);
+			SessionDuration::<T>::put(self.session_duration);
+		}
+	}
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Schedule a session duration change taking effect from a future
+		/// session, replacing the change scheduled for the session if any.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `start_session`: the index of the first session of the new
+		///   duration
+		/// - `duration`: the number of blocks of a session
+		#[pallet::weight(<T as Config>::WeightInfo::schedule_session_duration())]
+		pub fn schedule_session_duration(
+			origin: OriginFor<T>,
+			#[pallet::compact] start_session: SessionIndex,
+			#[pallet::compact] duration: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				start_session > pallet_session::Pallet::<T>::current_index(),
+				Error::<T>::InvalidSession
+			);
+			ensure!(Self::is_valid_duration(duration), Error::<T>::InvalidDuration);
+
+			SessionDurationChanges::<T>::insert(start_session, duration);
+			Self::deposit_event(Event::SessionDurationScheduled(start_session, duration));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn is_valid_duration(duration: T::BlockNumber) -> bool {
+		!duration.is_zero() && (duration % T::SlotDuration::get().max(One::one())).is_zero()
+	}
+}
+
+impl<T: Config> ShouldEndSession<T::BlockNumber> for Pallet<T> {
+	/// Whether the current session ends at `now`. If so, the duration
+	/// change scheduled for the next session takes effect from `now`, as
+	/// `pallet_session` starts the next session in the same block.
+	fn should_end_session(now: T::BlockNumber) -> bool {
+		let offset = Self::duration_offset();
+		let duration = Self::session_duration();
+		if now <= offset || duration.is_zero() || !((now - offset) % duration).is_zero() {
+			return false;
+		}
+
+		let next_session = pallet_session::Pallet::<T>::current_index().saturating_add(1);
+		if let Some(duration) = SessionDurationChanges::<T>::take(next_session) {
+			SessionDuration::<T>::put(duration);
+			DurationOffset::<T>::put(now);
+			Self::deposit_event(Event::SessionDurationChanged(next_session, duration, now));
+		}
+		true
+	}
+}
+
+impl<T: Config> EstimateNextSessionRotation<T::BlockNumber> for Pallet<T> {
+	fn average_session_length() -> T::BlockNumber {
+		Self::session_duration()
+	}
+
+	fn estimate_current_session_progress(now: T::BlockNumber) -> (Option<Permill>, Weight) {
+		let offset = Self::duration_offset();
+		let duration = Self::session_duration();
+		let weight = T::DbWeight::get().reads(2);
+		if duration.is_zero() {
+			return (None, weight);
+		}
+
+		let current = (now.saturating_sub(offset) % duration).saturating_add(One::one());
+		(Some(Permill::from_rational(current, duration)), weight)
+	}
+
+	fn estimate_next_session_rotation(now: T::BlockNumber) -> (Option<T::BlockNumber>, Weight) {
+		let offset = Self::duration_offset();
+		let duration = Self::session_duration();
+		let weight = T::DbWeight::get().reads(2);
+		if duration.is_zero() {
+			return (None, weight);
+		}
+
+		let next_rotation = if now > offset {
+			let elapsed = (now - offset) % duration;
+			now.saturating_add(duration - elapsed)
+		} else {
+			offset.saturating_add(duration)
+		};
+		(Some(next_rotation), weight)
+	}
+}
@@ -0,0 +1,131 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the session manager module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
+use mock::{Event, ExtBuilder, Origin, Runtime, Session, SessionManagerModule, System, ALICE};
+use sp_runtime::traits::BadOrigin;
+
+fn run_to_block(n: mock::BlockNumber) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Session::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn schedule_session_duration_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			SessionManagerModule::schedule_session_duration(Origin::signed(2), 2, 6),
+			BadOrigin
+		);
+		assert_noop!(
+			SessionManagerModule::schedule_session_duration(Origin::signed(ALICE), 0, 6),
+			Error::<Runtime>::InvalidSession
+		);
+		assert_noop!(
+			SessionManagerModule::schedule_session_duration(Origin::signed(ALICE), 2, 0),
+			Error::<Runtime>::InvalidDuration
+		);
+		assert_noop!(
+			SessionManagerModule::schedule_session_duration(Origin::signed(ALICE), 2, 5),
+			Error::<Runtime>::InvalidDuration
+		);
+
+		assert_ok!(SessionManagerModule::schedule_session_duration(
+			Origin::signed(ALICE),
+			2,
+			6
+		));
+		let scheduled_event = Event::session_manager(crate::Event::SessionDurationScheduled(2, 6));
+		assert!(System::events().iter().any(|record| record.event == scheduled_event));
+		assert_eq!(SessionManagerModule::session_duration_changes(2), Some(6));
+
+		run_to_block(10);
+		assert_eq!(Session::current_index(), 1);
+		assert_noop!(
+			SessionManagerModule::schedule_session_duration(Origin::signed(ALICE), 1, 6),
+			Error::<Runtime>::InvalidSession
+		);
+	});
+}
+
+#[test]
+fn session_duration_change_take_effect_at_session_start() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(SessionManagerModule::schedule_session_duration(
+			Origin::signed(ALICE),
+			2,
+			6
+		));
+
+		run_to_block(9);
+		assert_eq!(Session::current_index(), 0);
+		run_to_block(10);
+		assert_eq!(Session::current_index(), 1);
+		assert_eq!(SessionManagerModule::session_duration(), 10);
+
+		run_to_block(19);
+		assert_eq!(Session::current_index(), 1);
+		run_to_block(20);
+		assert_eq!(Session::current_index(), 2);
+		let changed_event = Event::session_manager(crate::Event::SessionDurationChanged(2, 6, 20));
+		assert!(System::events().iter().any(|record| record.event == changed_event));
+		assert_eq!(SessionManagerModule::session_duration(), 6);
+		assert_eq!(SessionManagerModule::duration_offset(), 20);
+		assert_eq!(SessionManagerModule::session_duration_changes(2), None);
+
+		run_to_block(25);
+		assert_eq!(Session::current_index(), 2);
+		run_to_block(26);
+		assert_eq!(Session::current_index(), 3);
+		run_to_block(32);
+		assert_eq!(Session::current_index(), 4);
+	});
+}
+
+#[test]
+fn estimate_next_session_rotation_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(SessionManagerModule::average_session_length(), 10);
+		assert_eq!(
+			SessionManagerModule::estimate_current_session_progress(1).0,
+			Some(Permill::from_percent(20))
+		);
+		assert_eq!(SessionManagerModule::estimate_next_session_rotation(1).0, Some(10));
+		assert_eq!(SessionManagerModule::estimate_next_session_rotation(10).0, Some(20));
+
+		assert_ok!(SessionManagerModule::schedule_session_duration(
+			Origin::signed(ALICE),
+			1,
+			4
+		));
+		run_to_block(10);
+		assert_eq!(SessionManagerModule::average_session_length(), 4);
+		assert_eq!(SessionManagerModule::estimate_next_session_rotation(11).0, Some(14));
+		assert_eq!(
+			SessionManagerModule::estimate_current_session_progress(11).0,
+			Some(Permill::from_percent(50))
+		);
+	});
+}
@@ -40,6 +40,41 @@ pub struct BalanceInfo<Balance> {
 	pub amount: Balance,
 }
 
+/// Overview of the liquid staking protocol(Homa).
+#[derive(Eq, PartialEq, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Debug, Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct HomaInfo<Balance> {
+	/// The exchange rate for liquid currency to staking currency.
+	pub liquid_exchange_rate: support::ExchangeRate,
+	/// The staking currency amount bonded on relaychain.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub total_bonded: Balance,
+	/// The staking currency amount belong to liquid currency holders.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub total_staking: Balance,
+	/// The count of pending redeem requests.
+	pub redeem_requests_count: u32,
+	/// The liquid currency amount of pending redeem requests.
+	#[cfg_attr(feature = "std", serde(bound(serialize = "Balance: std::fmt::Display")))]
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(bound(deserialize = "Balance: std::str::FromStr")))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub redeem_requests_amount: Balance,
+	/// The estimated staking reward rate of one era.
+	pub estimated_era_reward_rate: support::Rate,
+	/// The estimated annual staking reward rate.
+	pub estimated_apy: support::Rate,
+	/// The count of eras needed for unbonding.
+	pub unbonding_duration: u32,
+}
+
 #[cfg(feature = "std")]
 fn serialize_as_string<S: Serializer, T: std::fmt::Display>(t: &T, serializer: S) -> Result<S::Ok, S::Error> {
 	serializer.serialize_str(&t.to_string())
@@ -63,4 +98,10 @@ sp_api::decl_runtime_apis! {
 
 		fn get_liquid_staking_exchange_rate() -> support::ExchangeRate;
 	}
+
+	pub trait HomaApi<Balance> where
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+	{
+		fn get_homa_info() -> HomaInfo<Balance>;
+	}
 }
@@ -31,8 +31,9 @@ use sp_runtime::{
 };
 use sp_std::prelude::*;
 use support::{
-	ExchangeRate, HomaProtocol, NomineesProvider, OnNewEra, PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState,
-	PolkadotBridgeType, PolkadotStakingLedger, PolkadotUnlockChunk, Rate, Ratio,
+	math, ExchangeRate, HomaProtocol, NomineesProvider, OnNewEra, ParameterInvariants, PolkadotBridge,
+	PolkadotBridgeCall, PolkadotBridgeState, PolkadotBridgeType, PolkadotStakingCall, PolkadotStakingLedger,
+	PolkadotUnlockChunk, Rate, Ratio,
 };
 
 mod mock;
@@ -100,7 +101,7 @@ impl Ledger {
 
 	/// Total amount of staking currency which is belong to liquid currency
 	/// holders.
-	fn total_belong_to_liquid_holders(&self) -> Balance {
+	pub fn total_belong_to_liquid_holders(&self) -> Balance {
 		let (_, claimed_to_unbond) = self.to_unbond_next_era;
 		self.total().saturating_sub(claimed_to_unbond)
 	}
@@ -140,6 +141,7 @@ type PolkadotAccountIdOf<T> = <<T as Config>::Bridge as PolkadotBridgeType<
 	<T as frame_system::Config>::BlockNumber,
 	EraIndex,
 >>::PolkadotAccountId;
+type StakingCallOf<T> = PolkadotStakingCall<Balance, EraIndex, PolkadotAccountIdOf<T>>;
 
 #[frame_support::pallet]
 pub mod module {
@@ -186,6 +188,19 @@ pub mod module {
 
 		/// The currency for managing assets related to Homa protocol.
 		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// The max number of redeem requests to match in a single mint.
+		#[pallet::constant]
+		type MaxFastMatchRedeemers: Get<u32>;
+
+		/// The max number of redeem requests to unbond in a block when new
+		/// era beginning.
+		#[pallet::constant]
+		type MaxRedeemRequestsProcessed: Get<u32>;
+
+		/// The max number of mint records of past eras to prune in a block.
+		#[pallet::constant]
+		type MaxMintRecordsPruned: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -200,6 +215,17 @@ pub mod module {
 		InvalidConfig,
 		/// Rebalance process is unfinished.
 		RebalanceUnfinished,
+		/// The unbonding claim is not enough.
+		UnbondingNotEnough,
+		/// The redeem request does not exist.
+		RedeemRequestNotFound,
+		/// The total staking amount would exceed the soft cap.
+		ExceededStakingSoftCap,
+		/// The staking amount minted by the account in this era would exceed
+		/// the limit.
+		ExceededMintLimitPerEra,
+		/// The cap can't be removed before the smoothing buffer is released.
+		SmoothingBufferNotReleased,
 	}
 
 	#[pallet::event]
@@ -221,6 +247,46 @@ pub mod module {
 		/// the unbonding_to_free of specific era. \[who, target_era,
 		/// fee_in_staking, liquid_amount_burned, staking_amount_redeemed\]
 		RedeemByClaimUnbonding(T::AccountId, EraIndex, Balance, Balance, Balance),
+		/// Transfer the claim of unbonding staking currency(DOT) of specific
+		/// era. \[from, to, target_era, amount\]
+		TransferUnbonding(T::AccountId, T::AccountId, EraIndex, Balance),
+		/// Request to redeem liquid currency(LDOT). \[who,
+		/// liquid_amount_requested, allow_fast_match\]
+		RequestRedeem(T::AccountId, Balance, bool),
+		/// Redeem request has been cancelled partially or entirely, the
+		/// liquid currency(LDOT) is returned. \[who, liquid_amount_cancelled\]
+		CancelRedeemRequest(T::AccountId, Balance),
+		/// The fast match flag of redeem request has been updated. \[who,
+		/// allow_fast_match\]
+		RedeemRequestFastMatchUpdated(T::AccountId, bool),
+		/// Redeem request has been matched by minter, the liquid currency(LDOT)
+		/// is transferred to minter and redeemer receives staking
+		/// currency(DOT) immediately. \[redeemer, minter, liquid_amount_matched,
+		/// staking_amount_redeemed, fee_in_staking\]
+		RedeemByFastMatch(T::AccountId, T::AccountId, Balance, Balance, Balance),
+		/// The fee rate of fast match has been updated. \[new_fee_rate\]
+		FastMatchFeeRateUpdated(Rate),
+		/// The ratio of staking rewards to fund insurance reserve has been
+		/// updated. \[new_ratio\]
+		InsuranceReserveRatioUpdated(Ratio),
+		/// A slice of staking rewards has been deposited to insurance
+		/// reserve. \[staking_amount\]
+		InsuranceReserveFunded(Balance),
+		/// Insurance reserve has been drawn to cover the shortfall caused by
+		/// slashing on relaychain. \[shortfall, staking_amount_covered\]
+		InsuranceReserveDrawn(Balance, Balance),
+		/// The cap of exchange rate increase per era has been updated.
+		/// \[new_max_increase\]
+		MaxEraRateIncreaseUpdated(Option<Rate>),
+		/// Staking rewards exceeding the cap of exchange rate increase have
+		/// been deposited to smoothing buffer. \[staking_amount\]
+		SmoothingBufferFunded(Balance),
+		/// Smoothing buffer has been released to liquid holders in a lean
+		/// era. \[staking_amount\]
+		SmoothingBufferReleased(Balance),
+		/// The mint caps have been updated. \[staking_soft_cap,
+		/// mint_limit_per_era\]
+		MintCapsUpdated(Option<Balance>, Option<Balance>),
 	}
 
 	/// Current era index of Polkadot.
@@ -263,6 +329,89 @@ pub mod module {
 	#[pallet::getter(fn staking_pool_params)]
 	pub type StakingPoolParams<T: Config> = StorageValue<_, Params, ValueQuery>;
 
+	/// The redeem requests waiting to be matched by minters or to be unbonded
+	/// when next era beginning. The requested liquid currency is kept by
+	/// staking pool, the part which can not be unbonded is returned to the
+	/// redeemer.
+	/// AccountId => (LiquidAmount, AllowFastMatch)
+	#[pallet::storage]
+	#[pallet::getter(fn redeem_requests)]
+	pub type RedeemRequests<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, (Balance, bool), OptionQuery>;
+
+	/// The count and total liquid amount of the redeem requests.
+	/// (RequestsCount, LiquidAmount)
+	#[pallet::storage]
+	#[pallet::getter(fn total_redeem_requests)]
+	pub type TotalRedeemRequests<T: Config> = StorageValue<_, (u32, Balance), ValueQuery>;
+
+	/// Whether the redeem requests are being unbonded. Set when new era
+	/// beginning, the rebalance of the era waits until all the requests are
+	/// processed.
+	#[pallet::storage]
+	#[pallet::getter(fn processing_redeem_requests)]
+	pub type ProcessingRedeemRequests<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// The fee rate deducted from the staking currency redeemed by fast
+	/// match.
+	#[pallet::storage]
+	#[pallet::getter(fn fast_match_fee_rate)]
+	pub type FastMatchFeeRate<T: Config> = StorageValue<_, Rate, ValueQuery>;
+
+	/// The liquid exchange rate snapshot taken at the beginning of current
+	/// era, used to estimate the staking reward rate of one era.
+	#[pallet::storage]
+	#[pallet::getter(fn last_era_exchange_rate)]
+	pub type LastEraExchangeRate<T: Config> = StorageValue<_, ExchangeRate, OptionQuery>;
+
+	/// The ratio of staking rewards deposited to insurance reserve.
+	#[pallet::storage]
+	#[pallet::getter(fn insurance_reserve_ratio)]
+	pub type InsuranceReserveRatio<T: Config> = StorageValue<_, Ratio, ValueQuery>;
+
+	/// The max increase rate of liquid exchange rate in one era. Staking
+	/// rewards beyond it are kept in smoothing buffer. None means no cap.
+	#[pallet::storage]
+	#[pallet::getter(fn max_era_rate_increase)]
+	pub type MaxEraRateIncrease<T: Config> = StorageValue<_, Rate, OptionQuery>;
+
+	/// The staking rewards beyond the cap of exchange rate increase which
+	/// are compounded to bonded on relaychain, and can't be moved to
+	/// smoothing buffer. They are excluded from the staking amount belong to
+	/// liquid holders until released.
+	#[pallet::storage]
+	#[pallet::getter(fn smoothing_bonded)]
+	pub type SmoothingBonded<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The soft cap of the total staking amount, mint is rejected once it
+	/// would be exceeded. None means no cap.
+	#[pallet::storage]
+	#[pallet::getter(fn staking_soft_cap)]
+	pub type StakingSoftCap<T: Config> = StorageValue<_, Balance, OptionQuery>;
+
+	/// The max staking amount each account can mint in one era. None means no
+	/// limit.
+	#[pallet::storage]
+	#[pallet::getter(fn mint_limit_per_era)]
+	pub type MintLimitPerEra<T: Config> = StorageValue<_, Balance, OptionQuery>;
+
+	/// The staking amount minted by an account in an era, only recorded while
+	/// `MintLimitPerEra` is set.
+	/// EraIndex, AccountId => MintedAmount
+	#[pallet::storage]
+	#[pallet::getter(fn minted_in_era)]
+	pub type MintedInEra<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, EraIndex, Twox64Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// The eras which have mint records, in ascending order. The records of
+	/// past eras are pruned in `on_initialize`.
+	#[pallet::storage]
+	#[pallet::getter(fn mint_record_eras)]
+	pub type MintRecordEras<T: Config> = StorageValue<_, Vec<EraIndex>, ValueQuery>;
+
+	/// Whether the total of the existing redeem requests has been recorded.
+	#[pallet::storage]
+	pub(crate) type UpgradedToTotalRedeemRequests<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(Default)]
 	pub struct GenesisConfig {
@@ -273,6 +422,7 @@ pub mod module {
 	impl<T: Config> GenesisBuild<T> for GenesisConfig {
 		fn build(&self) {
 			StakingPoolParams::<T>::put(self.staking_pool_params.clone());
+			UpgradedToTotalRedeemRequests::<T>::put(true);
 		}
 	}
 
@@ -298,11 +448,34 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Record the total of the redeem requests made before the total was
+		/// tracked.
+		fn on_runtime_upgrade() -> Weight {
+			if UpgradedToTotalRedeemRequests::<T>::get() {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let total = RedeemRequests::<T>::iter().fold(
+				(0u32, Balance::zero()),
+				|(count, amount), (_, (liquid_amount, _))| {
+					(count.saturating_add(1), amount.saturating_add(liquid_amount))
+				},
+			);
+			TotalRedeemRequests::<T>::put(total);
+			UpgradedToTotalRedeemRequests::<T>::put(true);
+
+			T::DbWeight::get().reads_writes(Weight::from(total.0).saturating_add(1), 2)
+		}
+
 		fn on_initialize(_: T::BlockNumber) -> Weight {
-			Self::rebalance();
+			// unbond the redeem requests before rebalance of the new era.
+			let process_weight = Self::process_redeem_requests();
+			if !Self::processing_redeem_requests() {
+				Self::rebalance();
+			}
 
 			// TODO: return different weight according rebalance phase.
-			0
+			process_weight.saturating_add(Self::prune_mint_records())
 		}
 	}
 
@@ -347,6 +520,77 @@ pub mod module {
 			})?;
 			Ok(().into())
 		}
+
+		/// Update the fee rate of fast match.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_fast_match_fee_rate(origin: OriginFor<T>, fee_rate: Rate) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(fee_rate <= Rate::one(), Error::<T>::InvalidConfig);
+			FastMatchFeeRate::<T>::put(fee_rate);
+			Self::deposit_event(Event::FastMatchFeeRateUpdated(fee_rate));
+			Ok(().into())
+		}
+
+		/// Update the ratio of staking rewards to fund insurance reserve.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_insurance_reserve_ratio(origin: OriginFor<T>, ratio: Ratio) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(ratio <= Ratio::one(), Error::<T>::InvalidConfig);
+			InsuranceReserveRatio::<T>::put(ratio);
+			Self::deposit_event(Event::InsuranceReserveRatioUpdated(ratio));
+			Ok(().into())
+		}
+
+		/// Update the cap of liquid exchange rate increase per era. `None`
+		/// removes the cap, which is only allowed after the smoothing buffer
+		/// is released, lower the cap to release it gradually.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_max_era_rate_increase(
+			origin: OriginFor<T>,
+			max_increase: Option<Rate>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				max_increase.is_some() || (Self::smoothing_buffer().is_zero() && Self::smoothing_bonded().is_zero()),
+				Error::<T>::SmoothingBufferNotReleased
+			);
+			MaxEraRateIncrease::<T>::set(max_increase);
+			Self::deposit_event(Event::MaxEraRateIncreaseUpdated(max_increase));
+			Ok(().into())
+		}
+
+		/// Update the mint caps, which protect the protocol while the
+		/// capacity of relaychain validators or XCM throughput is constrained.
+		/// The caps apply to every mint of staking pool.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `staking_soft_cap`: the soft cap of the total staking amount,
+		///   `None` means no cap.
+		/// - `mint_limit_per_era`: the max staking amount each account can
+		///   mint in one era, `None` means no limit.
+		#[pallet::weight((10_000, DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_mint_caps(
+			origin: OriginFor<T>,
+			staking_soft_cap: Option<Balance>,
+			mint_limit_per_era: Option<Balance>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			StakingSoftCap::<T>::set(staking_soft_cap);
+			MintLimitPerEra::<T>::set(mint_limit_per_era);
+			Self::deposit_event(Event::MintCapsUpdated(staking_soft_cap, mint_limit_per_era));
+			Ok(().into())
+		}
 	}
 }
 
@@ -402,11 +646,12 @@ impl<T: Config> Pallet<T> {
 			.collect::<Vec<_>>();
 		let distribution = Self::distribute_decrement(current_available, amount);
 
-		for (account_index, val) in distribution {
-			T::Bridge::bond_extra(account_index, val)?;
-		}
-
-		Ok(())
+		T::Bridge::batch_calls(
+			distribution
+				.into_iter()
+				.map(|(account_index, val)| (account_index, PolkadotStakingCall::BondExtra(val)))
+				.collect(),
+		)
 	}
 
 	/// Require bridge to unbond on relaychain.
@@ -425,11 +670,12 @@ impl<T: Config> Pallet<T> {
 		current_bonded.sort_by(|a, b| b.1.cmp(&a.1));
 		let distribution = Self::distribute_decrement(current_bonded, amount);
 
-		for (account_index, val) in distribution {
-			T::Bridge::unbond(account_index, val)?;
-		}
-
-		Ok(())
+		T::Bridge::batch_calls(
+			distribution
+				.into_iter()
+				.map(|(account_index, val)| (account_index, PolkadotStakingCall::Unbond(val)))
+				.collect(),
+		)
 	}
 
 	/// Require bridge to transfer staking currency to specific
@@ -488,24 +734,39 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Require bridge to withdraw unbonded on relaychain.
-	pub fn withdraw_unbonded() {
-		for sub_account_index in T::PoolAccountIndexes::get() {
-			T::Bridge::withdraw_unbonded(sub_account_index);
-		}
+	pub fn withdraw_unbonded() -> DispatchResult {
+		Self::batch_calls_of_sub_accounts(|| vec![PolkadotStakingCall::WithdrawUnbonded])
 	}
 
 	/// Require bridge to get staking rewards on relaychain.
-	pub fn payout_stakers(era: EraIndex) {
-		for sub_account_index in T::PoolAccountIndexes::get() {
-			T::Bridge::payout_stakers(sub_account_index, era);
-		}
+	pub fn payout_stakers(era: EraIndex) -> DispatchResult {
+		Self::batch_calls_of_sub_accounts(|| vec![PolkadotStakingCall::PayoutStakers(era)])
 	}
 
 	/// Require bridge to nominate validators of relaychain.
-	pub fn nominate(targets: Vec<PolkadotAccountIdOf<T>>) {
-		for sub_account_index in T::PoolAccountIndexes::get() {
-			T::Bridge::nominate(sub_account_index, targets.clone());
-		}
+	pub fn nominate(targets: Vec<PolkadotAccountIdOf<T>>) -> DispatchResult {
+		Self::batch_calls_of_sub_accounts(|| vec![PolkadotStakingCall::Nominate(targets.clone())])
+	}
+
+	/// Require bridge to update nominees, withdraw unbonded and get staking
+	/// rewards of `era` on relaychain at once.
+	pub fn update_relaychain(targets: Vec<PolkadotAccountIdOf<T>>, era: EraIndex) -> DispatchResult {
+		Self::batch_calls_of_sub_accounts(|| {
+			vec![
+				PolkadotStakingCall::Nominate(targets.clone()),
+				PolkadotStakingCall::WithdrawUnbonded,
+				PolkadotStakingCall::PayoutStakers(era),
+			]
+		})
+	}
+
+	/// Send the same staking calls for every sub account, batched by bridge.
+	fn batch_calls_of_sub_accounts(calls: impl Fn() -> Vec<StakingCallOf<T>>) -> DispatchResult {
+		let calls = T::PoolAccountIndexes::get()
+			.into_iter()
+			.flat_map(|account_index| calls().into_iter().map(move |call| (account_index, call)))
+			.collect::<Vec<_>>();
+		T::Bridge::batch_calls(calls)
 	}
 
 	/// Merge ledger of sub accounts on relaychain.
@@ -561,10 +822,150 @@ impl<T: Config> Pallet<T> {
 		T::PalletId::get().into_account()
 	}
 
+	/// The sub account of module to keep insurance reserve.
+	pub fn insurance_account_id() -> T::AccountId {
+		T::PalletId::get().into_sub_account("insurance")
+	}
+
+	/// Get the staking currency amount of insurance reserve.
+	pub fn insurance_reserve() -> Balance {
+		T::Currency::free_balance(T::StakingCurrencyId::get(), &Self::insurance_account_id())
+	}
+
+	/// The sub account of module to keep smoothing buffer.
+	pub fn smoothing_account_id() -> T::AccountId {
+		T::PalletId::get().into_sub_account("smoothing")
+	}
+
+	/// Get the staking currency amount of smoothing buffer.
+	pub fn smoothing_buffer() -> Balance {
+		T::Currency::free_balance(T::StakingCurrencyId::get(), &Self::smoothing_account_id())
+	}
+
+	/// Total staking currency amount of `ledger` which is belong to liquid
+	/// currency holders, excluding the rewards held by smoothing.
+	fn total_staking_of_liquid_holders(ledger: &Ledger) -> Balance {
+		ledger
+			.total_belong_to_liquid_holders()
+			.saturating_sub(Self::smoothing_bonded())
+	}
+
+	/// Bound the growth of liquid exchange rate in current era: the staking
+	/// amount beyond the cap is moved from free pool to smoothing buffer, and
+	/// the rest compounded to bonded is held by `SmoothingBonded`. In lean
+	/// eras, they are released until reaching the cap, the held bonded first.
+	fn smooth_exchange_rate(ledger: &mut Ledger) {
+		let last_era_exchange_rate = match Self::last_era_exchange_rate() {
+			Some(rate) => rate,
+			None => return,
+		};
+		let max_increase = match Self::max_era_rate_increase() {
+			Some(max_increase) => max_increase,
+			None => return,
+		};
+		let liquid_total_issuance = T::Currency::total_issuance(T::LiquidCurrencyId::get());
+		if liquid_total_issuance.is_zero() {
+			return;
+		}
+
+		let total = Self::total_staking_of_liquid_holders(ledger);
+		let max_total = last_era_exchange_rate
+			.saturating_mul(max_increase.saturating_add(Rate::one()))
+			.saturating_mul_int(liquid_total_issuance);
+
+		if total > max_total {
+			let excess = total.saturating_sub(max_total);
+			let mut buffered = excess.min(ledger.free_pool);
+
+			if !buffered.is_zero()
+				&& T::Currency::transfer(
+					T::StakingCurrencyId::get(),
+					&Self::account_id(),
+					&Self::smoothing_account_id(),
+					buffered,
+				)
+				.is_ok()
+			{
+				ledger.free_pool = ledger.free_pool.saturating_sub(buffered);
+			} else {
+				buffered = Zero::zero();
+			}
+			SmoothingBonded::<T>::mutate(|held| *held = held.saturating_add(excess.saturating_sub(buffered)));
+			Self::deposit_event(Event::SmoothingBufferFunded(excess));
+		} else {
+			let mut room = max_total.saturating_sub(total);
+			let released_bonded = Self::smoothing_bonded().min(room);
+			SmoothingBonded::<T>::mutate(|held| *held = held.saturating_sub(released_bonded));
+			room = room.saturating_sub(released_bonded);
+
+			let mut released = Self::smoothing_buffer().min(room);
+			if !released.is_zero()
+				&& T::Currency::transfer(
+					T::StakingCurrencyId::get(),
+					&Self::smoothing_account_id(),
+					&Self::account_id(),
+					released,
+				)
+				.is_ok()
+			{
+				ledger.free_pool = ledger.free_pool.saturating_add(released);
+			} else {
+				released = Zero::zero();
+			}
+
+			let total_released = released_bonded.saturating_add(released);
+			if !total_released.is_zero() {
+				Self::deposit_event(Event::SmoothingBufferReleased(total_released));
+			}
+		}
+	}
+
+	/// Settle the change of bonded on relaychain: deposit a slice of staking
+	/// rewards from free pool to insurance reserve, or draw insurance reserve
+	/// to free pool to cover the shortfall caused by slashing. The remaining
+	/// changes are reflected in the exchange rate.
+	fn settle_insurance(ledger: &mut Ledger, previous_bonded: Balance) {
+		if ledger.bonded > previous_bonded {
+			let reward = ledger.bonded.saturating_sub(previous_bonded);
+			let amount = Self::insurance_reserve_ratio()
+				.saturating_mul_int(reward)
+				.min(ledger.free_pool);
+
+			if !amount.is_zero()
+				&& T::Currency::transfer(
+					T::StakingCurrencyId::get(),
+					&Self::account_id(),
+					&Self::insurance_account_id(),
+					amount,
+				)
+				.is_ok()
+			{
+				ledger.free_pool = ledger.free_pool.saturating_sub(amount);
+				Self::deposit_event(Event::InsuranceReserveFunded(amount));
+			}
+		} else if ledger.bonded < previous_bonded {
+			let shortfall = previous_bonded.saturating_sub(ledger.bonded);
+			let amount = Self::insurance_reserve().min(shortfall);
+
+			if !amount.is_zero()
+				&& T::Currency::transfer(
+					T::StakingCurrencyId::get(),
+					&Self::insurance_account_id(),
+					&Self::account_id(),
+					amount,
+				)
+				.is_ok()
+			{
+				ledger.free_pool = ledger.free_pool.saturating_add(amount);
+				Self::deposit_event(Event::InsuranceReserveDrawn(shortfall, amount));
+			}
+		}
+	}
+
 	/// Get the exchange rate for liquid currency to staking currency.
 	pub fn liquid_exchange_rate() -> ExchangeRate {
 		let exchange_rate = ExchangeRate::checked_from_rational(
-			Self::staking_pool_ledger().total_belong_to_liquid_holders(),
+			Self::total_staking_of_liquid_holders(&Self::staking_pool_ledger()),
 			T::Currency::total_issuance(T::LiquidCurrencyId::get()),
 		)
 		.unwrap_or_default();
@@ -576,6 +977,47 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Estimate the staking reward rate of one era by the growth of liquid
+	/// exchange rate since the beginning of current era.
+	pub fn estimated_era_reward_rate() -> Rate {
+		Self::last_era_exchange_rate()
+			.and_then(|last_rate| Self::liquid_exchange_rate().checked_div(&last_rate))
+			.map(|growth| growth.saturating_sub(Rate::one()))
+			.unwrap_or_default()
+	}
+
+	/// Estimate the annual staking reward rate(APY) by compounding the
+	/// estimated era reward rate for `eras_per_year` eras.
+	pub fn estimated_apy(eras_per_year: u32) -> Rate {
+		math::compound_rate(Self::estimated_era_reward_rate(), eras_per_year.into())
+	}
+
+	/// The count and total liquid amount of redeem requests which are
+	/// waiting to be matched or unbonded.
+	pub fn pending_redeem_requests() -> (u32, Balance) {
+		Self::total_redeem_requests()
+	}
+
+	/// Keep `TotalRedeemRequests` in sync with the redeem request changed
+	/// from `old` to `new` liquid amount, `None` means no request.
+	fn update_total_redeem_requests(old: Option<Balance>, new: Option<Balance>) {
+		TotalRedeemRequests::<T>::mutate(|(count, amount)| {
+			if let Some(old) = old {
+				*count = count.saturating_sub(1);
+				*amount = amount.saturating_sub(old);
+			}
+			if let Some(new) = new {
+				*count = count.saturating_add(1);
+				*amount = amount.saturating_add(new);
+			}
+		});
+	}
+
+	/// The count of eras needed for unbonding on relaychain.
+	pub fn unbonding_duration() -> EraIndex {
+		<<T as Config>::Bridge as PolkadotBridgeType<_, _>>::BondingDuration::get()
+	}
+
 	/// Get how much available unbonded of `who` in current era.
 	pub fn get_available_unbonded(who: &T::AccountId) -> Balance {
 		Unbondings::<T>::iter_prefix(who)
@@ -585,17 +1027,268 @@ impl<T: Config> Pallet<T> {
 			})
 	}
 
+	/// Burn liquid currency(LDOT) of `payer` and unbond the staking
+	/// currency(DOT) for `who` when next era beginning. Return the actual
+	/// liquid amount burned.
+	fn do_redeem_by_unbond(
+		who: &T::AccountId,
+		payer: &T::AccountId,
+		amount: Balance,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		StakingPoolLedger::<T>::try_mutate(|ledger| -> sp_std::result::Result<Balance, DispatchError> {
+			let mut liquid_amount_to_burn = amount;
+			let liquid_exchange_rate = Self::liquid_exchange_rate();
+			let mut staking_amount_to_unbond = liquid_exchange_rate
+				.checked_mul_int(liquid_amount_to_burn)
+				.ok_or(Error::<T>::Overflow)?;
+			let communal_bonded_staking_amount = ledger.bonded_belong_to_liquid_holders();
+
+			if staking_amount_to_unbond.is_zero() || communal_bonded_staking_amount.is_zero() {
+				return Ok(Zero::zero());
+			}
+
+			// communal_bonded_staking_amount is not enough, re-calculate
+			if staking_amount_to_unbond > communal_bonded_staking_amount {
+				liquid_amount_to_burn = liquid_exchange_rate
+					.reciprocal()
+					.unwrap_or_default()
+					.saturating_mul_int(communal_bonded_staking_amount);
+				staking_amount_to_unbond = communal_bonded_staking_amount;
+			}
+
+			// burn liquid currency
+			T::Currency::withdraw(T::LiquidCurrencyId::get(), payer, liquid_amount_to_burn)?;
+
+			NextEraUnbonds::<T>::mutate(who, |unbond| {
+				*unbond = unbond.saturating_add(staking_amount_to_unbond);
+			});
+
+			let (total_unbond, claimed_unbond) = ledger.to_unbond_next_era;
+			ledger.to_unbond_next_era = (
+				total_unbond.saturating_add(staking_amount_to_unbond),
+				claimed_unbond.saturating_add(staking_amount_to_unbond),
+			);
+
+			Self::deposit_event(Event::RedeemByUnbond(
+				who.clone(),
+				liquid_amount_to_burn,
+				staking_amount_to_unbond,
+			));
+
+			Ok(liquid_amount_to_burn)
+		})
+	}
+
+	/// Match the redeem requests which allow fast match with the staking
+	/// currency(DOT) `minter` is minting with. The minter pays DOT to
+	/// redeemers directly and gets the LDOT of requests kept by staking pool.
+	/// Return the matched liquid amount and staking amount.
+	fn fast_match_redeems(
+		ledger: &mut Ledger,
+		minter: &T::AccountId,
+		amount: Balance,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		let liquid_exchange_rate = Self::liquid_exchange_rate();
+		let fast_match_fee_rate = Self::fast_match_fee_rate();
+		let mut remain_staking_amount = amount;
+		let mut liquid_amount_matched: Balance = Zero::zero();
+
+		// bound the requests read rather than the requests matched, so that the
+		// requests which do not allow fast match can not make the scan unbounded.
+		let redeem_requests = RedeemRequests::<T>::iter()
+			.take(T::MaxFastMatchRedeemers::get() as usize)
+			.filter(|(redeemer, (_, allow_fast_match))| *allow_fast_match && redeemer != minter)
+			.collect::<Vec<_>>();
+
+		for (redeemer, (request_liquid_amount, allow_fast_match)) in redeem_requests {
+			if remain_staking_amount.is_zero() {
+				break;
+			}
+
+			let mut liquid_amount_to_match = request_liquid_amount;
+			let mut staking_amount_to_match = liquid_exchange_rate
+				.checked_mul_int(liquid_amount_to_match)
+				.ok_or(Error::<T>::Overflow)?;
+
+			// remain_staking_amount is not enough, re-calculate
+			if staking_amount_to_match > remain_staking_amount {
+				liquid_amount_to_match = liquid_exchange_rate
+					.reciprocal()
+					.unwrap_or_default()
+					.saturating_mul_int(remain_staking_amount);
+				staking_amount_to_match = remain_staking_amount;
+			}
+			if liquid_amount_to_match.is_zero() {
+				break;
+			}
+
+			let fee_in_staking = fast_match_fee_rate.saturating_mul_int(staking_amount_to_match);
+			let staking_amount_to_redeemer = staking_amount_to_match.saturating_sub(fee_in_staking);
+
+			T::Currency::transfer(
+				T::StakingCurrencyId::get(),
+				minter,
+				&redeemer,
+				staking_amount_to_redeemer,
+			)?;
+			T::Currency::transfer(T::StakingCurrencyId::get(), minter, &Self::account_id(), fee_in_staking)?;
+			T::Currency::transfer(
+				T::LiquidCurrencyId::get(),
+				&Self::account_id(),
+				minter,
+				liquid_amount_to_match,
+			)?;
+
+			// the fee is kept by staking pool for liquid currency holders.
+			ledger.free_pool = ledger.free_pool.saturating_add(fee_in_staking);
+
+			let remain_liquid_amount = request_liquid_amount.saturating_sub(liquid_amount_to_match);
+			if remain_liquid_amount.is_zero() {
+				RedeemRequests::<T>::remove(&redeemer);
+				Self::update_total_redeem_requests(Some(request_liquid_amount), None);
+			} else {
+				RedeemRequests::<T>::insert(&redeemer, (remain_liquid_amount, allow_fast_match));
+				Self::update_total_redeem_requests(Some(request_liquid_amount), Some(remain_liquid_amount));
+			}
+
+			remain_staking_amount = remain_staking_amount.saturating_sub(staking_amount_to_match);
+			liquid_amount_matched = liquid_amount_matched.saturating_add(liquid_amount_to_match);
+
+			Self::deposit_event(Event::RedeemByFastMatch(
+				redeemer,
+				minter.clone(),
+				liquid_amount_to_match,
+				staking_amount_to_redeemer,
+				fee_in_staking,
+			));
+		}
+
+		Ok((liquid_amount_matched, amount.saturating_sub(remain_staking_amount)))
+	}
+
+	/// Add `amount` to the staking amount minted by `who` in current era,
+	/// and ensure it doesn't exceed `MintLimitPerEra`. Nothing is recorded
+	/// while there is no limit.
+	fn record_minted(who: &T::AccountId, amount: Balance) -> DispatchResult {
+		let limit = match Self::mint_limit_per_era() {
+			Some(limit) => limit,
+			None => return Ok(()),
+		};
+
+		let current_era = Self::current_era();
+		MintedInEra::<T>::try_mutate(current_era, who, |minted| -> DispatchResult {
+			let new_minted = minted.saturating_add(amount);
+			ensure!(new_minted <= limit, Error::<T>::ExceededMintLimitPerEra);
+			*minted = new_minted;
+			Ok(())
+		})?;
+
+		MintRecordEras::<T>::mutate(|eras| {
+			if eras.last() != Some(&current_era) {
+				eras.push(current_era);
+			}
+		});
+		Ok(())
+	}
+
+	/// Remove at most `MaxMintRecordsPruned` mint records of past eras.
+	/// Return the weight consumed.
+	fn prune_mint_records() -> Weight {
+		let current_era = Self::current_era();
+		let mut eras = Self::mint_record_eras();
+		let mut remaining = T::MaxMintRecordsPruned::get();
+		let mut reads: Weight = 1;
+		let mut writes: Weight = 0;
+
+		while let Some(&era) = eras.first() {
+			if era >= current_era || remaining.is_zero() {
+				break;
+			}
+
+			let accounts = MintedInEra::<T>::iter_prefix(era)
+				.take(remaining as usize)
+				.map(|(who, _)| who)
+				.collect::<Vec<_>>();
+			let pruned = accounts.len() as u32;
+			for who in accounts {
+				MintedInEra::<T>::remove(era, who);
+			}
+			reads = reads.saturating_add(pruned.saturating_add(1).into());
+			writes = writes.saturating_add(pruned.into());
+
+			if pruned < remaining {
+				eras.remove(0);
+				MintRecordEras::<T>::put(&eras);
+				writes = writes.saturating_add(1);
+			}
+			remaining = remaining.saturating_sub(pruned);
+		}
+
+		T::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	/// Unbond at most `MaxRedeemRequestsProcessed` redeem requests which have
+	/// not been matched. The remaining part which can not be unbonded is
+	/// returned to the redeemer, so every processed request is removed.
+	/// Return the weight consumed.
+	fn process_redeem_requests() -> Weight {
+		if !Self::processing_redeem_requests() {
+			return T::DbWeight::get().reads(1);
+		}
+
+		let max_processed = T::MaxRedeemRequestsProcessed::get();
+		let redeem_requests = RedeemRequests::<T>::iter()
+			.take(max_processed as usize)
+			.collect::<Vec<_>>();
+		let processed = redeem_requests.len() as u32;
+		let mut removed: u32 = 0;
+
+		for (redeemer, (request_liquid_amount, allow_fast_match)) in redeem_requests {
+			let remain_liquid_amount =
+				match Self::do_redeem_by_unbond(&redeemer, &Self::account_id(), request_liquid_amount) {
+					Ok(liquid_amount_burned) => request_liquid_amount.saturating_sub(liquid_amount_burned),
+					Err(_) => request_liquid_amount,
+				};
+
+			if remain_liquid_amount.is_zero()
+				|| T::Currency::transfer(
+					T::LiquidCurrencyId::get(),
+					&Self::account_id(),
+					&redeemer,
+					remain_liquid_amount,
+				)
+				.is_ok()
+			{
+				RedeemRequests::<T>::remove(&redeemer);
+				Self::update_total_redeem_requests(Some(request_liquid_amount), None);
+				removed = removed.saturating_add(1);
+			} else {
+				RedeemRequests::<T>::insert(&redeemer, (remain_liquid_amount, allow_fast_match));
+				Self::update_total_redeem_requests(Some(request_liquid_amount), Some(remain_liquid_amount));
+			}
+		}
+
+		// finished if all requests have been visited, or none of them can be removed.
+		if processed < max_processed || removed.is_zero() {
+			ProcessingRedeemRequests::<T>::kill();
+		}
+
+		// unbond every request: read ledger, exchange rate, pool balance, unbonds and
+		// requests total, write ledger, pool balance, issuance, unbonds, redeemer
+		// balance, request and requests total.
+		T::DbWeight::get().reads_writes(
+			processed.saturating_mul(7).saturating_add(2).into(),
+			processed.saturating_mul(7).saturating_add(1).into(),
+		)
+	}
+
 	pub fn rebalance() {
 		match Self::rebalance_phase() {
 			Phase::Started => {
-				// require relaychain to update nominees.
-				Self::nominate(T::Nominees::nominees());
-
-				// require relaychain to withdraw unbonded.
-				Self::withdraw_unbonded();
-
-				// require relaychain to payout stakers.
-				Self::payout_stakers(Self::current_era().saturating_sub(1));
+				// require relaychain to update nominees, withdraw unbonded and payout stakers.
+				// the staking pool ledger is synced from relaychain in the next phase even if
+				// some calls failed.
+				let _ = Self::update_relaychain(T::Nominees::nominees(), Self::current_era().saturating_sub(1));
 
 				RebalancePhase::<T>::put(Phase::RelaychainUpdated);
 			}
@@ -606,7 +1299,9 @@ impl<T: Config> Pallet<T> {
 					let relaychain_free_balance = Self::relaychain_free_balance();
 
 					// update bonded of staking pool to the active(bonded) of relaychain ledger.
+					let previous_bonded = ledger.bonded;
 					ledger.bonded = relaychain_staking_ledger.active;
+					Self::settle_insurance(ledger, previous_bonded);
 
 					// withdraw available staking currency from polkadot bridge to staking pool.
 					if Self::receive_from_bridge(&Self::account_id(), relaychain_free_balance).is_ok() {
@@ -630,6 +1325,8 @@ impl<T: Config> Pallet<T> {
 							.free_pool
 							.saturating_add(relaychain_free_balance.saturating_sub(total_claimed_unbonded));
 					}
+
+					Self::smooth_exchange_rate(ledger);
 				});
 
 				RebalancePhase::<T>::put(Phase::LedgerUpdated);
@@ -699,6 +1396,8 @@ impl<T: Config> Pallet<T> {
 impl<T: Config> OnNewEra<EraIndex> for Pallet<T> {
 	fn on_new_era(new_era: EraIndex) {
 		CurrentEra::<T>::put(new_era);
+		LastEraExchangeRate::<T>::put(Self::liquid_exchange_rate());
+		ProcessingRedeemRequests::<T>::put(true);
 		RebalancePhase::<T>::put(Phase::Started);
 	}
 }
@@ -716,21 +1415,44 @@ impl<T: Config> HomaProtocol<T::AccountId, Balance, EraIndex> for Pallet<T> {
 			Self::rebalance_phase() == Phase::Finished,
 			Error::<T>::RebalanceUnfinished
 		);
+		Self::record_minted(who, amount)?;
 
 		StakingPoolLedger::<T>::try_mutate(|ledger| -> sp_std::result::Result<Self::Balance, DispatchError> {
+			let (liquid_amount_matched, staking_amount_matched) = Self::fast_match_redeems(ledger, who, amount)?;
+			let staking_amount_to_inject = amount.saturating_sub(staking_amount_matched);
+			if let Some(soft_cap) = Self::staking_soft_cap() {
+				ensure!(
+					ledger
+						.total_belong_to_liquid_holders()
+						.saturating_add(staking_amount_to_inject)
+						<= soft_cap,
+					Error::<T>::ExceededStakingSoftCap
+				);
+			}
 			let liquid_amount_to_issue = Self::liquid_exchange_rate()
 				.reciprocal()
 				.unwrap_or_default()
-				.checked_mul_int(amount)
+				.checked_mul_int(staking_amount_to_inject)
 				.ok_or(Error::<T>::Overflow)?;
 
-			T::Currency::transfer(T::StakingCurrencyId::get(), who, &Self::account_id(), amount)?;
+			T::Currency::transfer(
+				T::StakingCurrencyId::get(),
+				who,
+				&Self::account_id(),
+				staking_amount_to_inject,
+			)?;
 			T::Currency::deposit(T::LiquidCurrencyId::get(), who, liquid_amount_to_issue)?;
 
-			ledger.free_pool = ledger.free_pool.saturating_add(amount);
+			ledger.free_pool = ledger.free_pool.saturating_add(staking_amount_to_inject);
 
-			Self::deposit_event(Event::MintLiquid(who.clone(), amount, liquid_amount_to_issue));
-			Ok(liquid_amount_to_issue)
+			if !staking_amount_to_inject.is_zero() {
+				Self::deposit_event(Event::MintLiquid(
+					who.clone(),
+					staking_amount_to_inject,
+					liquid_amount_to_issue,
+				));
+			}
+			Ok(liquid_amount_to_issue.saturating_add(liquid_amount_matched))
 		})
 	}
 
@@ -745,46 +1467,8 @@ impl<T: Config> HomaProtocol<T::AccountId, Balance, EraIndex> for Pallet<T> {
 			Error::<T>::RebalanceUnfinished
 		);
 
-		StakingPoolLedger::<T>::try_mutate(|ledger| -> DispatchResult {
-			let mut liquid_amount_to_burn = amount;
-			let liquid_exchange_rate = Self::liquid_exchange_rate();
-			let mut staking_amount_to_unbond = liquid_exchange_rate
-				.checked_mul_int(liquid_amount_to_burn)
-				.ok_or(Error::<T>::Overflow)?;
-			let communal_bonded_staking_amount = ledger.bonded_belong_to_liquid_holders();
-
-			if !staking_amount_to_unbond.is_zero() && !communal_bonded_staking_amount.is_zero() {
-				// communal_bonded_staking_amount is not enough, re-calculate
-				if staking_amount_to_unbond > communal_bonded_staking_amount {
-					liquid_amount_to_burn = liquid_exchange_rate
-						.reciprocal()
-						.unwrap_or_default()
-						.saturating_mul_int(communal_bonded_staking_amount);
-					staking_amount_to_unbond = communal_bonded_staking_amount;
-				}
-
-				// burn liquid currency
-				T::Currency::withdraw(T::LiquidCurrencyId::get(), who, liquid_amount_to_burn)?;
-
-				NextEraUnbonds::<T>::mutate(who, |unbond| {
-					*unbond = unbond.saturating_add(staking_amount_to_unbond);
-				});
-
-				let (total_unbond, claimed_unbond) = ledger.to_unbond_next_era;
-				ledger.to_unbond_next_era = (
-					total_unbond.saturating_add(staking_amount_to_unbond),
-					claimed_unbond.saturating_add(staking_amount_to_unbond),
-				);
-
-				Self::deposit_event(Event::RedeemByUnbond(
-					who.clone(),
-					liquid_amount_to_burn,
-					staking_amount_to_unbond,
-				));
-			}
-
-			Ok(())
-		})
+		Self::do_redeem_by_unbond(who, who, amount)?;
+		Ok(())
 	}
 
 	#[transactional]
@@ -962,6 +1646,125 @@ impl<T: Config> HomaProtocol<T::AccountId, Balance, EraIndex> for Pallet<T> {
 		T::Currency::transfer(T::StakingCurrencyId::get(), &Self::account_id(), who, withdrawn_amount)?;
 		Ok(withdrawn_amount)
 	}
+
+	#[transactional]
+	fn request_redeem(who: &T::AccountId, amount: Self::Balance, allow_fast_match: bool) -> DispatchResult {
+		if amount.is_zero() {
+			return Ok(());
+		}
+
+		T::Currency::transfer(T::LiquidCurrencyId::get(), who, &Self::account_id(), amount)?;
+		RedeemRequests::<T>::try_mutate(who, |maybe_request| -> DispatchResult {
+			let old = maybe_request.take().map(|(requested, _)| requested);
+			let new = old
+				.unwrap_or_default()
+				.checked_add(amount)
+				.ok_or(Error::<T>::Overflow)?;
+			*maybe_request = Some((new, allow_fast_match));
+			Self::update_total_redeem_requests(old, Some(new));
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::RequestRedeem(who.clone(), amount, allow_fast_match));
+		Ok(())
+	}
+
+	#[transactional]
+	fn cancel_redeem_request(
+		who: &T::AccountId,
+		amount: Self::Balance,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		if amount.is_zero() {
+			return Ok(Zero::zero());
+		}
+
+		let cancelled_amount = RedeemRequests::<T>::try_mutate_exists(
+			who,
+			|maybe_request| -> sp_std::result::Result<Balance, DispatchError> {
+				let (requested, allow_fast_match) = maybe_request.take().ok_or(Error::<T>::RedeemRequestNotFound)?;
+				let cancelled_amount = amount.min(requested);
+				let remain = requested.saturating_sub(cancelled_amount);
+				if remain.is_zero() {
+					Self::update_total_redeem_requests(Some(requested), None);
+				} else {
+					*maybe_request = Some((remain, allow_fast_match));
+					Self::update_total_redeem_requests(Some(requested), Some(remain));
+				}
+				Ok(cancelled_amount)
+			},
+		)?;
+		T::Currency::transfer(T::LiquidCurrencyId::get(), &Self::account_id(), who, cancelled_amount)?;
+
+		Self::deposit_event(Event::CancelRedeemRequest(who.clone(), cancelled_amount));
+		Ok(cancelled_amount)
+	}
+
+	fn set_redeem_request_fast_match(who: &T::AccountId, allow_fast_match: bool) -> DispatchResult {
+		RedeemRequests::<T>::try_mutate(who, |maybe_request| -> DispatchResult {
+			let (_, allow) = maybe_request.as_mut().ok_or(Error::<T>::RedeemRequestNotFound)?;
+			*allow = allow_fast_match;
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::RedeemRequestFastMatchUpdated(who.clone(), allow_fast_match));
+		Ok(())
+	}
+
+	#[transactional]
+	fn transfer_unbonding(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		target_era: EraIndex,
+		amount: Self::Balance,
+	) -> DispatchResult {
+		if amount.is_zero() || from == to {
+			return Ok(());
+		}
+
+		// matured unbonding should be withdrawn by `withdraw_redemption`.
+		ensure!(target_era > Self::current_era(), Error::<T>::InvalidEra);
+
+		Unbondings::<T>::try_mutate_exists(from, target_era, |maybe_unbonding| -> DispatchResult {
+			let unbonding = maybe_unbonding.take().unwrap_or_default();
+			let remain = unbonding.checked_sub(amount).ok_or(Error::<T>::UnbondingNotEnough)?;
+			if !remain.is_zero() {
+				*maybe_unbonding = Some(remain);
+			}
+			Ok(())
+		})?;
+		Unbondings::<T>::mutate(to, target_era, |unbonding| {
+			*unbonding = unbonding.saturating_add(amount);
+		});
+
+		Self::deposit_event(Event::TransferUnbonding(from.clone(), to.clone(), target_era, amount));
+		Ok(())
+	}
+
+	fn liquid_exchange_rate() -> ExchangeRate {
+		Self::liquid_exchange_rate()
+	}
+
+	fn current_era() -> EraIndex {
+		Self::current_era()
+	}
+
+	fn total_staking_amount() -> Balance {
+		Self::total_staking_of_liquid_holders(&Self::staking_pool_ledger())
+	}
+}
+
+impl<T: Config> ParameterInvariants for Pallet<T> {
+	fn check_invariants() -> DispatchResult {
+		let params = Self::staking_pool_params();
+		ensure!(
+			params.target_min_free_unbonded_ratio <= params.target_max_free_unbonded_ratio
+				&& params.base_fee_rate <= Rate::one()
+				&& Self::fast_match_fee_rate() <= Rate::one()
+				&& Self::insurance_reserve_ratio() <= Ratio::one(),
+			Error::<T>::InvalidConfig
+		);
+		Ok(())
+	}
 }
 
 pub struct OnSlash<T>(sp_std::marker::PhantomData<T>);
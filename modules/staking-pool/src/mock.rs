@@ -41,6 +41,7 @@ pub type PolkadotAccountId = u128;
 
 pub const ALICE: AccountId = 0;
 pub const BOB: AccountId = 1;
+pub const CHARLIE: AccountId = 2;
 pub const ACA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
 pub const LDOT: CurrencyId = CurrencyId::Token(TokenSymbol::LDOT);
@@ -342,6 +343,9 @@ parameter_types! {
 	pub DefaultExchangeRate: ExchangeRate = ExchangeRate::saturating_from_rational(10, 100);	// 1 : 10
 	pub const StakingPoolPalletId: PalletId = PalletId(*b"aca/stkp");
 	pub PoolAccountIndexes: Vec<u32> = vec![1, 2, 3, 4];
+	pub const MaxFastMatchRedeemers: u32 = 3;
+	pub const MaxRedeemRequestsProcessed: u32 = 2;
+	pub const MaxMintRecordsPruned: u32 = 2;
 }
 
 ord_parameter_types! {
@@ -360,6 +364,9 @@ impl Config for Runtime {
 	type Nominees = MockNomineesProvider;
 	type Bridge = MockBridge;
 	type Currency = CurrenciesModule;
+	type MaxFastMatchRedeemers = MaxFastMatchRedeemers;
+	type MaxRedeemRequestsProcessed = MaxRedeemRequestsProcessed;
+	type MaxMintRecordsPruned = MaxMintRecordsPruned;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -24,7 +24,7 @@ use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{
 	BondingDuration, CurrenciesModule, Event, ExtBuilder, One, Origin, Runtime, StakingPoolModule, Status, System,
-	ALICE, BOB, BRIDGE_STATUS, DOT, LDOT,
+	ALICE, BOB, BRIDGE_STATUS, CHARLIE, DOT, LDOT,
 };
 use sp_runtime::traits::BadOrigin;
 
@@ -498,7 +498,7 @@ fn withdraw_unbonded_work() {
 		});
 
 		CurrentEra::<Runtime>::put(3);
-		StakingPoolModule::withdraw_unbonded();
+		assert_ok!(StakingPoolModule::withdraw_unbonded());
 		assert_eq!(
 			*BRIDGE_STATUS
 				.with(|v| v.borrow().clone())
@@ -586,7 +586,7 @@ fn payout_stakers_work() {
 			*v.borrow_mut() = old_map;
 		});
 
-		StakingPoolModule::payout_stakers(0);
+		assert_ok!(StakingPoolModule::payout_stakers(0));
 		assert_eq!(
 			*BRIDGE_STATUS
 				.with(|v| v.borrow().clone())
@@ -689,6 +689,270 @@ fn liquid_exchange_rate_work() {
 	});
 }
 
+#[test]
+fn set_insurance_reserve_ratio_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			StakingPoolModule::set_insurance_reserve_ratio(Origin::signed(5), Ratio::saturating_from_rational(1, 10)),
+			BadOrigin
+		);
+		assert_noop!(
+			StakingPoolModule::set_insurance_reserve_ratio(
+				Origin::signed(One::get()),
+				Ratio::saturating_from_rational(11, 10)
+			),
+			Error::<Runtime>::InvalidConfig
+		);
+		assert_ok!(StakingPoolModule::set_insurance_reserve_ratio(
+			Origin::signed(One::get()),
+			Ratio::saturating_from_rational(1, 10)
+		));
+		assert_eq!(
+			StakingPoolModule::insurance_reserve_ratio(),
+			Ratio::saturating_from_rational(1, 10)
+		);
+		let ratio_updated_event = Event::staking_pool(crate::Event::InsuranceReserveRatioUpdated(
+			Ratio::saturating_from_rational(1, 10),
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == ratio_updated_event));
+	});
+}
+
+#[test]
+fn settle_insurance_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CurrenciesModule::deposit(DOT, &StakingPoolModule::account_id(), 100));
+		InsuranceReserveRatio::<Runtime>::put(Ratio::saturating_from_rational(1, 2));
+		let mut ledger = Ledger {
+			bonded: 1100,
+			free_pool: 100,
+			..Default::default()
+		};
+
+		// fund insurance reserve with half of rewards
+		StakingPoolModule::settle_insurance(&mut ledger, 1000);
+		assert_eq!(ledger.free_pool, 50);
+		assert_eq!(StakingPoolModule::insurance_reserve(), 50);
+		let funded_event = Event::staking_pool(crate::Event::InsuranceReserveFunded(50));
+		assert!(System::events().iter().any(|record| record.event == funded_event));
+
+		// shortfall is covered by insurance reserve
+		ledger.bonded = 1070;
+		StakingPoolModule::settle_insurance(&mut ledger, 1100);
+		assert_eq!(ledger.free_pool, 80);
+		assert_eq!(StakingPoolModule::insurance_reserve(), 20);
+		let drawn_event = Event::staking_pool(crate::Event::InsuranceReserveDrawn(30, 30));
+		assert!(System::events().iter().any(|record| record.event == drawn_event));
+
+		// shortfall exceeds insurance reserve, the rest is socialized
+		ledger.bonded = 1000;
+		StakingPoolModule::settle_insurance(&mut ledger, 1070);
+		assert_eq!(ledger.free_pool, 100);
+		assert_eq!(StakingPoolModule::insurance_reserve(), 0);
+		let drawn_event = Event::staking_pool(crate::Event::InsuranceReserveDrawn(70, 20));
+		assert!(System::events().iter().any(|record| record.event == drawn_event));
+	});
+}
+
+#[test]
+fn set_max_era_rate_increase_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			StakingPoolModule::set_max_era_rate_increase(
+				Origin::signed(5),
+				Some(Rate::saturating_from_rational(1, 100))
+			),
+			BadOrigin
+		);
+		assert_eq!(StakingPoolModule::max_era_rate_increase(), None);
+		assert_ok!(StakingPoolModule::set_max_era_rate_increase(
+			Origin::signed(One::get()),
+			Some(Rate::saturating_from_rational(1, 100))
+		));
+		assert_eq!(
+			StakingPoolModule::max_era_rate_increase(),
+			Some(Rate::saturating_from_rational(1, 100))
+		);
+		let updated_event = Event::staking_pool(crate::Event::MaxEraRateIncreaseUpdated(Some(
+			Rate::saturating_from_rational(1, 100),
+		)));
+		assert!(System::events().iter().any(|record| record.event == updated_event));
+
+		assert_ok!(StakingPoolModule::set_max_era_rate_increase(
+			Origin::signed(One::get()),
+			None
+		));
+		assert_eq!(StakingPoolModule::max_era_rate_increase(), None);
+	});
+}
+
+#[test]
+fn smooth_exchange_rate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CurrenciesModule::deposit(LDOT, &ALICE, 1000));
+		assert_ok!(CurrenciesModule::deposit(DOT, &StakingPoolModule::account_id(), 50));
+		LastEraExchangeRate::<Runtime>::put(ExchangeRate::one());
+		MaxEraRateIncrease::<Runtime>::put(Rate::saturating_from_rational(1, 100));
+		let mut ledger = Ledger {
+			bonded: 1000,
+			free_pool: 50,
+			..Default::default()
+		};
+
+		// rewards beyond the cap are deposited to smoothing buffer
+		StakingPoolModule::smooth_exchange_rate(&mut ledger);
+		assert_eq!(ledger.free_pool, 10);
+		assert_eq!(StakingPoolModule::smoothing_buffer(), 40);
+		let funded_event = Event::staking_pool(crate::Event::SmoothingBufferFunded(40));
+		assert!(System::events().iter().any(|record| record.event == funded_event));
+
+		// no rewards in this era, release smoothing buffer up to the cap
+		LastEraExchangeRate::<Runtime>::put(ExchangeRate::saturating_from_rational(1010, 1000));
+		StakingPoolModule::smooth_exchange_rate(&mut ledger);
+		assert_eq!(ledger.free_pool, 20);
+		assert_eq!(StakingPoolModule::smoothing_buffer(), 30);
+		let released_event = Event::staking_pool(crate::Event::SmoothingBufferReleased(10));
+		assert!(System::events().iter().any(|record| record.event == released_event));
+
+		// the cap can't be removed before the smoothing buffer is released
+		assert_noop!(
+			StakingPoolModule::set_max_era_rate_increase(Origin::signed(One::get()), None),
+			Error::<Runtime>::SmoothingBufferNotReleased
+		);
+
+		// raise the cap, release the rest of smoothing buffer
+		MaxEraRateIncrease::<Runtime>::put(Rate::saturating_from_rational(10, 100));
+		StakingPoolModule::smooth_exchange_rate(&mut ledger);
+		assert_eq!(ledger.free_pool, 50);
+		assert_eq!(StakingPoolModule::smoothing_buffer(), 0);
+		let released_event = Event::staking_pool(crate::Event::SmoothingBufferReleased(30));
+		assert!(System::events().iter().any(|record| record.event == released_event));
+		assert_ok!(StakingPoolModule::set_max_era_rate_increase(
+			Origin::signed(One::get()),
+			None
+		));
+	});
+}
+
+#[test]
+fn smooth_exchange_rate_holds_compounded_rewards() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CurrenciesModule::deposit(LDOT, &ALICE, 1000));
+		LastEraExchangeRate::<Runtime>::put(ExchangeRate::one());
+		MaxEraRateIncrease::<Runtime>::put(Rate::saturating_from_rational(1, 100));
+		let mut ledger = Ledger {
+			bonded: 1050,
+			..Default::default()
+		};
+
+		// the rewards compounded to bonded are held beyond the cap
+		StakingPoolModule::smooth_exchange_rate(&mut ledger);
+		assert_eq!(ledger.bonded, 1050);
+		assert_eq!(StakingPoolModule::smoothing_buffer(), 0);
+		assert_eq!(StakingPoolModule::smoothing_bonded(), 40);
+		let funded_event = Event::staking_pool(crate::Event::SmoothingBufferFunded(40));
+		assert!(System::events().iter().any(|record| record.event == funded_event));
+		StakingPoolLedger::<Runtime>::put(ledger.clone());
+		assert_eq!(
+			StakingPoolModule::liquid_exchange_rate(),
+			ExchangeRate::saturating_from_rational(1010, 1000)
+		);
+
+		// release the held rewards up to the cap
+		LastEraExchangeRate::<Runtime>::put(ExchangeRate::saturating_from_rational(1010, 1000));
+		StakingPoolModule::smooth_exchange_rate(&mut ledger);
+		assert_eq!(StakingPoolModule::smoothing_bonded(), 30);
+		let released_event = Event::staking_pool(crate::Event::SmoothingBufferReleased(10));
+		assert!(System::events().iter().any(|record| record.event == released_event));
+		assert_eq!(
+			StakingPoolModule::liquid_exchange_rate(),
+			ExchangeRate::saturating_from_rational(1020, 1000)
+		);
+	});
+}
+
+#[test]
+fn estimated_apy_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CurrenciesModule::deposit(LDOT, &ALICE, 1000));
+		StakingPoolLedger::<Runtime>::put(Ledger {
+			bonded: 100,
+			..Default::default()
+		});
+		assert_eq!(StakingPoolModule::last_era_exchange_rate(), None);
+		assert_eq!(StakingPoolModule::estimated_era_reward_rate(), Rate::zero());
+
+		StakingPoolModule::on_new_era(1);
+		assert_eq!(
+			StakingPoolModule::last_era_exchange_rate(),
+			Some(ExchangeRate::saturating_from_rational(100, 1000))
+		);
+
+		StakingPoolLedger::<Runtime>::mutate(|ledger| ledger.bonded = 101);
+		assert_eq!(
+			StakingPoolModule::estimated_era_reward_rate(),
+			Rate::saturating_from_rational(1, 100)
+		);
+		assert_eq!(
+			StakingPoolModule::estimated_apy(2),
+			Rate::saturating_from_rational(201, 10000)
+		);
+
+		// slash makes exchange rate decrease, the estimated rate is zero
+		StakingPoolLedger::<Runtime>::mutate(|ledger| ledger.bonded = 90);
+		assert_eq!(StakingPoolModule::estimated_era_reward_rate(), Rate::zero());
+		assert_eq!(StakingPoolModule::estimated_apy(365), Rate::zero());
+	});
+}
+
+#[test]
+fn pending_redeem_requests_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(StakingPoolModule::pending_redeem_requests(), (0, 0));
+		assert_eq!(StakingPoolModule::mint(&BOB, 1000), Ok(10000));
+		assert_ok!(CurrenciesModule::transfer(Origin::signed(BOB), ALICE, LDOT, 1000));
+
+		assert_ok!(StakingPoolModule::request_redeem(&ALICE, 1000, true));
+		assert_ok!(StakingPoolModule::request_redeem(&BOB, 500, false));
+		assert_eq!(StakingPoolModule::pending_redeem_requests(), (2, 1500));
+
+		assert_ok!(StakingPoolModule::request_redeem(&BOB, 500, true));
+		assert_eq!(StakingPoolModule::pending_redeem_requests(), (2, 2000));
+
+		assert_eq!(StakingPoolModule::cancel_redeem_request(&ALICE, 400), Ok(400));
+		assert_eq!(StakingPoolModule::pending_redeem_requests(), (2, 1600));
+
+		assert_eq!(StakingPoolModule::cancel_redeem_request(&ALICE, 1000), Ok(600));
+		assert_eq!(StakingPoolModule::pending_redeem_requests(), (1, 1000));
+	});
+}
+
+#[test]
+fn migrate_to_total_redeem_requests_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		UpgradedToTotalRedeemRequests::<Runtime>::kill();
+		RedeemRequests::<Runtime>::insert(ALICE, (1000, true));
+		RedeemRequests::<Runtime>::insert(BOB, (500, false));
+		assert_eq!(StakingPoolModule::total_redeem_requests(), (0, 0));
+
+		StakingPoolModule::on_runtime_upgrade();
+		assert_eq!(StakingPoolModule::total_redeem_requests(), (2, 1500));
+		assert!(UpgradedToTotalRedeemRequests::<Runtime>::get());
+
+		// only migrate once
+		RedeemRequests::<Runtime>::insert(CHARLIE, (500, false));
+		StakingPoolModule::on_runtime_upgrade();
+		assert_eq!(StakingPoolModule::total_redeem_requests(), (2, 1500));
+	});
+}
+
 #[test]
 fn get_available_unbonded_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -792,6 +1056,104 @@ fn mint_work() {
 	});
 }
 
+#[test]
+fn set_mint_caps_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			StakingPoolModule::set_mint_caps(Origin::signed(5), Some(1000), Some(100)),
+			BadOrigin
+		);
+		assert_ok!(StakingPoolModule::set_mint_caps(
+			Origin::signed(One::get()),
+			Some(1000),
+			Some(100)
+		));
+		assert_eq!(StakingPoolModule::staking_soft_cap(), Some(1000));
+		assert_eq!(StakingPoolModule::mint_limit_per_era(), Some(100));
+		let mint_caps_updated_event = Event::staking_pool(crate::Event::MintCapsUpdated(Some(1000), Some(100)));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == mint_caps_updated_event));
+
+		assert_ok!(StakingPoolModule::set_mint_caps(Origin::signed(One::get()), None, None));
+		assert_eq!(StakingPoolModule::staking_soft_cap(), None);
+		assert_eq!(StakingPoolModule::mint_limit_per_era(), None);
+	});
+}
+
+#[test]
+fn mint_caps_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(StakingPoolModule::mint(&ALICE, 100));
+		assert_eq!(StakingPoolModule::minted_in_era(0, ALICE), 0);
+		assert_eq!(StakingPoolModule::mint_record_eras(), vec![]);
+
+		assert_ok!(StakingPoolModule::set_mint_caps(
+			Origin::signed(One::get()),
+			Some(600),
+			Some(300)
+		));
+		assert_ok!(StakingPoolModule::mint(&ALICE, 300));
+		assert_eq!(StakingPoolModule::minted_in_era(0, ALICE), 300);
+		assert_eq!(StakingPoolModule::mint_record_eras(), vec![0]);
+		assert_noop!(
+			StakingPoolModule::mint(&ALICE, 1),
+			Error::<Runtime>::ExceededMintLimitPerEra
+		);
+
+		assert_noop!(
+			StakingPoolModule::mint(&BOB, 201),
+			Error::<Runtime>::ExceededStakingSoftCap
+		);
+		assert_ok!(StakingPoolModule::mint(&BOB, 200));
+		assert_eq!(
+			StakingPoolModule::staking_pool_ledger().total_belong_to_liquid_holders(),
+			600
+		);
+
+		CurrentEra::<Runtime>::put(1);
+		assert_ok!(StakingPoolModule::set_mint_caps(
+			Origin::signed(One::get()),
+			None,
+			Some(300)
+		));
+		assert_ok!(StakingPoolModule::mint(&ALICE, 300));
+		assert_eq!(StakingPoolModule::minted_in_era(1, ALICE), 300);
+		assert_eq!(StakingPoolModule::mint_record_eras(), vec![0, 1]);
+	});
+}
+
+#[test]
+fn prune_mint_records_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CurrenciesModule::deposit(DOT, &2, 1000));
+		assert_ok!(StakingPoolModule::set_mint_caps(
+			Origin::signed(One::get()),
+			None,
+			Some(300)
+		));
+		assert_ok!(StakingPoolModule::mint(&ALICE, 100));
+		assert_ok!(StakingPoolModule::mint(&BOB, 100));
+		assert_ok!(StakingPoolModule::mint(&2, 100));
+		StakingPoolModule::prune_mint_records();
+		assert_eq!(MintedInEra::<Runtime>::iter_prefix(0).count(), 3);
+
+		CurrentEra::<Runtime>::put(1);
+		assert_ok!(StakingPoolModule::mint(&ALICE, 100));
+		assert_eq!(StakingPoolModule::mint_record_eras(), vec![0, 1]);
+
+		StakingPoolModule::prune_mint_records();
+		assert_eq!(MintedInEra::<Runtime>::iter_prefix(0).count(), 1);
+		assert_eq!(StakingPoolModule::mint_record_eras(), vec![0, 1]);
+
+		StakingPoolModule::prune_mint_records();
+		assert_eq!(MintedInEra::<Runtime>::iter_prefix(0).count(), 0);
+		assert_eq!(StakingPoolModule::mint_record_eras(), vec![1]);
+		assert_eq!(StakingPoolModule::minted_in_era(1, ALICE), 100);
+	});
+}
+
 #[test]
 fn withdraw_redemption_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -817,6 +1179,308 @@ fn withdraw_redemption_work() {
 	});
 }
 
+#[test]
+fn transfer_unbonding_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		CurrentEra::<Runtime>::put(1);
+		Unbondings::<Runtime>::insert(ALICE, 1, 100);
+		Unbondings::<Runtime>::insert(ALICE, 3, 300);
+
+		assert_noop!(
+			StakingPoolModule::transfer_unbonding(&ALICE, &BOB, 1, 100),
+			Error::<Runtime>::InvalidEra
+		);
+		assert_noop!(
+			StakingPoolModule::transfer_unbonding(&ALICE, &BOB, 3, 301),
+			Error::<Runtime>::UnbondingNotEnough
+		);
+
+		assert_ok!(StakingPoolModule::transfer_unbonding(&ALICE, &BOB, 3, 200));
+		assert_eq!(StakingPoolModule::unbondings(&ALICE, 3), 100);
+		assert_eq!(StakingPoolModule::unbondings(&BOB, 3), 200);
+		let transfer_unbonding_event = Event::staking_pool(crate::Event::TransferUnbonding(ALICE, BOB, 3, 200));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == transfer_unbonding_event));
+
+		assert_ok!(StakingPoolModule::transfer_unbonding(&ALICE, &BOB, 3, 100));
+		assert!(!Unbondings::<Runtime>::contains_key(ALICE, 3));
+		assert_eq!(StakingPoolModule::unbondings(&BOB, 3), 300);
+
+		// settle after the era has matured
+		assert_ok!(CurrenciesModule::deposit(DOT, &StakingPoolModule::account_id(), 300));
+		CurrentEra::<Runtime>::put(3);
+		assert_eq!(StakingPoolModule::withdraw_redemption(&BOB), Ok(300));
+		assert_eq!(CurrenciesModule::free_balance(DOT, &BOB), 1300);
+	});
+}
+
+#[test]
+fn check_invariants_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(StakingPoolModule::check_invariants());
+
+		StakingPoolParams::<Runtime>::mutate(|params| {
+			params.target_min_free_unbonded_ratio = Ratio::saturating_from_rational(20, 100);
+		});
+		assert_noop!(StakingPoolModule::check_invariants(), Error::<Runtime>::InvalidConfig);
+
+		StakingPoolParams::<Runtime>::mutate(|params| {
+			params.target_min_free_unbonded_ratio = Ratio::saturating_from_rational(5, 100);
+		});
+		FastMatchFeeRate::<Runtime>::put(Rate::saturating_from_rational(101, 100));
+		assert_noop!(StakingPoolModule::check_invariants(), Error::<Runtime>::InvalidConfig);
+
+		FastMatchFeeRate::<Runtime>::put(Rate::saturating_from_rational(1, 100));
+		InsuranceReserveRatio::<Runtime>::put(Ratio::saturating_from_rational(101, 100));
+		assert_noop!(StakingPoolModule::check_invariants(), Error::<Runtime>::InvalidConfig);
+
+		InsuranceReserveRatio::<Runtime>::put(Ratio::saturating_from_rational(10, 100));
+		assert_ok!(StakingPoolModule::check_invariants());
+	});
+}
+
+#[test]
+fn set_fast_match_fee_rate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			StakingPoolModule::set_fast_match_fee_rate(Origin::signed(5), Rate::saturating_from_rational(1, 100)),
+			BadOrigin
+		);
+		assert_noop!(
+			StakingPoolModule::set_fast_match_fee_rate(
+				Origin::signed(One::get()),
+				Rate::saturating_from_rational(101, 100)
+			),
+			Error::<Runtime>::InvalidConfig
+		);
+		assert_ok!(StakingPoolModule::set_fast_match_fee_rate(
+			Origin::signed(One::get()),
+			Rate::saturating_from_rational(1, 100)
+		));
+		assert_eq!(
+			StakingPoolModule::fast_match_fee_rate(),
+			Rate::saturating_from_rational(1, 100)
+		);
+		let fee_rate_updated_event = Event::staking_pool(crate::Event::FastMatchFeeRateUpdated(
+			Rate::saturating_from_rational(1, 100),
+		));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == fee_rate_updated_event));
+	});
+}
+
+#[test]
+fn request_redeem_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(StakingPoolModule::mint(&BOB, 1000), Ok(10000));
+		assert_eq!(StakingPoolModule::redeem_requests(&BOB), None);
+
+		assert_ok!(StakingPoolModule::request_redeem(&BOB, 3000, false));
+		assert_eq!(StakingPoolModule::redeem_requests(&BOB), Some((3000, false)));
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &BOB), 7000);
+		assert_eq!(
+			CurrenciesModule::free_balance(LDOT, &StakingPoolModule::account_id()),
+			3000
+		);
+		let request_redeem_event = Event::staking_pool(crate::Event::RequestRedeem(BOB, 3000, false));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == request_redeem_event));
+
+		assert_ok!(StakingPoolModule::request_redeem(&BOB, 1000, true));
+		assert_eq!(StakingPoolModule::redeem_requests(&BOB), Some((4000, true)));
+		assert_eq!(
+			CurrenciesModule::free_balance(LDOT, &StakingPoolModule::account_id()),
+			4000
+		);
+	});
+}
+
+#[test]
+fn cancel_redeem_request_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(StakingPoolModule::mint(&BOB, 1000), Ok(10000));
+		assert_noop!(
+			StakingPoolModule::cancel_redeem_request(&BOB, 1000),
+			Error::<Runtime>::RedeemRequestNotFound
+		);
+
+		assert_ok!(StakingPoolModule::request_redeem(&BOB, 3000, true));
+		assert_eq!(StakingPoolModule::cancel_redeem_request(&BOB, 0), Ok(0));
+		assert_eq!(StakingPoolModule::cancel_redeem_request(&BOB, 1000), Ok(1000));
+		assert_eq!(StakingPoolModule::redeem_requests(&BOB), Some((2000, true)));
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &BOB), 8000);
+		assert_eq!(
+			CurrenciesModule::free_balance(LDOT, &StakingPoolModule::account_id()),
+			2000
+		);
+		let cancel_redeem_request_event = Event::staking_pool(crate::Event::CancelRedeemRequest(BOB, 1000));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == cancel_redeem_request_event));
+
+		assert_eq!(StakingPoolModule::cancel_redeem_request(&BOB, 5000), Ok(2000));
+		assert_eq!(StakingPoolModule::redeem_requests(&BOB), None);
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &BOB), 10000);
+		assert_eq!(
+			CurrenciesModule::free_balance(LDOT, &StakingPoolModule::account_id()),
+			0
+		);
+	});
+}
+
+#[test]
+fn set_redeem_request_fast_match_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(StakingPoolModule::mint(&BOB, 1000), Ok(10000));
+		assert_noop!(
+			StakingPoolModule::set_redeem_request_fast_match(&BOB, true),
+			Error::<Runtime>::RedeemRequestNotFound
+		);
+
+		assert_ok!(StakingPoolModule::request_redeem(&BOB, 3000, false));
+		assert_ok!(StakingPoolModule::set_redeem_request_fast_match(&BOB, true));
+		assert_eq!(StakingPoolModule::redeem_requests(&BOB), Some((3000, true)));
+		let fast_match_updated_event = Event::staking_pool(crate::Event::RedeemRequestFastMatchUpdated(BOB, true));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == fast_match_updated_event));
+
+		assert_ok!(StakingPoolModule::set_redeem_request_fast_match(&BOB, false));
+		assert_eq!(StakingPoolModule::redeem_requests(&BOB), Some((3000, false)));
+	});
+}
+
+#[test]
+fn mint_with_fast_match_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		FastMatchFeeRate::<Runtime>::put(Rate::saturating_from_rational(10, 100));
+		assert_eq!(StakingPoolModule::mint(&BOB, 1000), Ok(10000));
+		assert_ok!(StakingPoolModule::request_redeem(&BOB, 5000, true));
+
+		assert_eq!(StakingPoolModule::mint(&ALICE, 300), Ok(3000));
+		assert_eq!(CurrenciesModule::free_balance(DOT, &ALICE), 700);
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &ALICE), 3000);
+		assert_eq!(CurrenciesModule::free_balance(DOT, &BOB), 270);
+		assert_eq!(CurrenciesModule::free_balance(LDOT, &BOB), 5000);
+		assert_eq!(StakingPoolModule::redeem_requests(&BOB), Some((2000, true)));
+		assert_eq!(StakingPoolModule::total_redeem_requests(), (1, 2000));
+		assert_eq!(
+			CurrenciesModule::free_balance(LDOT, &StakingPoolModule::account_id()),
+			2000
+		);
+		assert_eq!(CurrenciesModule::total_issuance(LDOT), 10000);
+		assert_eq!(
+			StakingPoolModule::staking_pool_ledger(),
+			Ledger {
+				bonded: 0,
+				free_pool: 1030,
+				unbonding_to_free: 0,
+				to_unbond_next_era: (0, 0)
+			}
+		);
+		let fast_match_event = Event::staking_pool(crate::Event::RedeemByFastMatch(BOB, ALICE, 3000, 270, 30));
+		assert!(System::events().iter().any(|record| record.event == fast_match_event));
+
+		// requests of minter self will not be matched
+		assert_ok!(StakingPoolModule::request_redeem(&ALICE, 1000, true));
+		RedeemRequests::<Runtime>::remove(&BOB);
+		assert_eq!(StakingPoolModule::mint(&ALICE, 100), Ok(970));
+		assert_eq!(StakingPoolModule::redeem_requests(&ALICE), Some((1000, true)));
+	});
+}
+
+#[test]
+fn process_redeem_requests_on_new_era_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(StakingPoolModule::mint(&BOB, 1000), Ok(10000));
+		StakingPoolLedger::<Runtime>::mutate(|ledger| {
+			ledger.bonded = 1000;
+			ledger.free_pool = 0;
+		});
+		assert_ok!(StakingPoolModule::request_redeem(&BOB, 3000, false));
+
+		// requests not allow fast match will not be matched
+		assert_eq!(StakingPoolModule::mint(&ALICE, 100), Ok(1000));
+		assert_eq!(StakingPoolModule::redeem_requests(&BOB), Some((3000, false)));
+
+		// requests are unbonded in the blocks after new era beginning
+		StakingPoolModule::on_new_era(1);
+		assert_eq!(StakingPoolModule::redeem_requests(&BOB), Some((3000, false)));
+		assert!(StakingPoolModule::processing_redeem_requests());
+
+		StakingPoolModule::on_initialize(2);
+		assert!(!StakingPoolModule::processing_redeem_requests());
+		assert_eq!(StakingPoolModule::rebalance_phase(), Phase::RelaychainUpdated);
+		assert_eq!(StakingPoolModule::redeem_requests(&BOB), None);
+		assert_eq!(StakingPoolModule::total_redeem_requests(), (0, 0));
+		assert_eq!(
+			CurrenciesModule::free_balance(LDOT, &StakingPoolModule::account_id()),
+			0
+		);
+		assert_eq!(StakingPoolModule::next_era_unbonds(&BOB), 300);
+		assert_eq!(StakingPoolModule::staking_pool_ledger().to_unbond_next_era, (300, 300));
+		let redeem_by_unbond_event = Event::staking_pool(crate::Event::RedeemByUnbond(BOB, 3000, 300));
+		assert!(System::events()
+			.iter()
+			.any(|record| record.event == redeem_by_unbond_event));
+	});
+}
+
+#[test]
+fn process_redeem_requests_is_bounded_per_block() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(StakingPoolModule::mint(&BOB, 1000), Ok(10000));
+		assert_ok!(CurrenciesModule::transfer(Origin::signed(BOB), ALICE, LDOT, 4000));
+		assert_ok!(CurrenciesModule::transfer(Origin::signed(BOB), CHARLIE, LDOT, 2000));
+		StakingPoolLedger::<Runtime>::mutate(|ledger| {
+			ledger.bonded = 500;
+			ledger.free_pool = 500;
+		});
+		assert_ok!(StakingPoolModule::request_redeem(&ALICE, 4000, false));
+		assert_ok!(StakingPoolModule::request_redeem(&BOB, 2000, false));
+		assert_ok!(StakingPoolModule::request_redeem(&CHARLIE, 2000, false));
+
+		// at most 2 requests are processed in a block, the rebalance waits
+		StakingPoolModule::on_new_era(1);
+		StakingPoolModule::on_initialize(2);
+		assert!(StakingPoolModule::processing_redeem_requests());
+		assert_eq!(StakingPoolModule::rebalance_phase(), Phase::Started);
+		assert_eq!(RedeemRequests::<Runtime>::iter().count(), 1);
+		assert_eq!(StakingPoolModule::total_redeem_requests().0, 1);
+
+		StakingPoolModule::on_initialize(3);
+		assert!(!StakingPoolModule::processing_redeem_requests());
+		assert_eq!(StakingPoolModule::rebalance_phase(), Phase::RelaychainUpdated);
+		assert_eq!(RedeemRequests::<Runtime>::iter().count(), 0);
+		assert_eq!(StakingPoolModule::total_redeem_requests(), (0, 0));
+
+		// only 5000 LDOT can be unbonded by the communal bonded, the rest is returned
+		assert_eq!(StakingPoolModule::staking_pool_ledger().to_unbond_next_era, (500, 500));
+		assert_eq!(
+			CurrenciesModule::free_balance(LDOT, &StakingPoolModule::account_id()),
+			0
+		);
+		assert_eq!(CurrenciesModule::total_issuance(LDOT), 5000);
+		assert_eq!(
+			CurrenciesModule::free_balance(LDOT, &ALICE)
+				+ CurrenciesModule::free_balance(LDOT, &BOB)
+				+ CurrenciesModule::free_balance(LDOT, &CHARLIE),
+			5000
+		);
+	});
+}
+
 #[test]
 fn redeem_by_unbond_work() {
 	ExtBuilder::default().build().execute_with(|| {
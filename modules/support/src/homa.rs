@@ -47,6 +47,17 @@ pub struct PolkadotStakingLedger<Balance, EraIndex> {
 	pub unlocking: Vec<PolkadotUnlockChunk<Balance, EraIndex>>,
 }
 
+/// A staking call of a parachain sub account on relaychain.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub enum PolkadotStakingCall<Balance, EraIndex, PolkadotAccountId> {
+	BondExtra(Balance),
+	Unbond(Balance),
+	Rebond(Balance),
+	WithdrawUnbonded,
+	Nominate(Vec<PolkadotAccountId>),
+	PayoutStakers(EraIndex),
+}
+
 pub trait PolkadotBridgeType<BlockNumber, EraIndex> {
 	type BondingDuration: Get<EraIndex>;
 	type EraLength: Get<BlockNumber>;
@@ -64,6 +75,25 @@ pub trait PolkadotBridgeCall<AccountId, BlockNumber, Balance, EraIndex>:
 	fn transfer_to_bridge(account_index: u32, from: &AccountId, amount: Balance) -> DispatchResult;
 	fn receive_from_bridge(account_index: u32, to: &AccountId, amount: Balance) -> DispatchResult;
 	fn payout_stakers(account_index: u32, era: EraIndex);
+
+	/// Send the staking calls of sub accounts, the bridge may batch them
+	/// into fewer relaychain messages. The calls are sent one by one by
+	/// default.
+	fn batch_calls(
+		calls: Vec<(u32, PolkadotStakingCall<Balance, EraIndex, Self::PolkadotAccountId>)>,
+	) -> DispatchResult {
+		for (account_index, call) in calls {
+			match call {
+				PolkadotStakingCall::BondExtra(amount) => Self::bond_extra(account_index, amount)?,
+				PolkadotStakingCall::Unbond(amount) => Self::unbond(account_index, amount)?,
+				PolkadotStakingCall::Rebond(amount) => Self::rebond(account_index, amount)?,
+				PolkadotStakingCall::WithdrawUnbonded => Self::withdraw_unbonded(account_index),
+				PolkadotStakingCall::Nominate(targets) => Self::nominate(account_index, targets),
+				PolkadotStakingCall::PayoutStakers(era) => Self::payout_stakers(account_index, era),
+			}
+		}
+		Ok(())
+	}
 }
 
 pub trait PolkadotBridgeState<Balance, EraIndex> {
@@ -93,4 +123,72 @@ pub trait HomaProtocol<AccountId, Balance, EraIndex> {
 	fn redeem_by_free_unbonded(who: &AccountId, amount: Balance) -> DispatchResult;
 	fn redeem_by_claim_unbonding(who: &AccountId, amount: Balance, target_era: EraIndex) -> DispatchResult;
 	fn withdraw_redemption(who: &AccountId) -> sp_std::result::Result<Balance, DispatchError>;
+	fn request_redeem(who: &AccountId, amount: Balance, allow_fast_match: bool) -> DispatchResult;
+	fn cancel_redeem_request(who: &AccountId, amount: Balance) -> sp_std::result::Result<Balance, DispatchError>;
+	fn set_redeem_request_fast_match(who: &AccountId, allow_fast_match: bool) -> DispatchResult;
+	fn transfer_unbonding(from: &AccountId, to: &AccountId, target_era: EraIndex, amount: Balance) -> DispatchResult;
+	fn liquid_exchange_rate() -> ExchangeRate;
+	fn current_era() -> EraIndex;
+	fn total_staking_amount() -> Balance;
+}
+
+impl<AccountId, Balance, EraIndex> HomaProtocol<AccountId, Balance, EraIndex> for ()
+where
+	Balance: Decode + Encode + Debug + Eq + PartialEq + Clone + HasCompact + Default,
+	EraIndex: Default,
+{
+	type Balance = Balance;
+
+	fn mint(_who: &AccountId, _amount: Balance) -> sp_std::result::Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+
+	fn redeem_by_unbond(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+
+	fn redeem_by_free_unbonded(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+
+	fn redeem_by_claim_unbonding(_who: &AccountId, _amount: Balance, _target_era: EraIndex) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+
+	fn withdraw_redemption(_who: &AccountId) -> sp_std::result::Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+
+	fn request_redeem(_who: &AccountId, _amount: Balance, _allow_fast_match: bool) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+
+	fn cancel_redeem_request(_who: &AccountId, _amount: Balance) -> sp_std::result::Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+
+	fn set_redeem_request_fast_match(_who: &AccountId, _allow_fast_match: bool) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+
+	fn transfer_unbonding(
+		_from: &AccountId,
+		_to: &AccountId,
+		_target_era: EraIndex,
+		_amount: Balance,
+	) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented homa protocol"))
+	}
+
+	fn liquid_exchange_rate() -> ExchangeRate {
+		Default::default()
+	}
+
+	fn current_era() -> EraIndex {
+		Default::default()
+	}
+
+	fn total_staking_amount() -> Balance {
+		Default::default()
+	}
 }
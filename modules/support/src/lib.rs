@@ -20,7 +20,10 @@
 #![allow(clippy::upper_case_acronyms)]
 
 use codec::{Decode, Encode, FullCodec, HasCompact};
-use frame_support::pallet_prelude::{DispatchClass, Pays, Weight};
+use frame_support::{
+	pallet_prelude::{DispatchClass, Pays, Weight},
+	PalletId,
+};
 use primitives::{
 	evm::{CallInfo, EvmAddress},
 	CurrencyId,
@@ -36,12 +39,14 @@ use sp_std::{
 	fmt::Debug,
 	prelude::*,
 };
+use xcm::v0::{MultiAsset, MultiLocation};
 
 pub mod homa;
+pub mod math;
 pub mod mocks;
 pub use homa::{
 	HomaProtocol, NomineesProvider, OnCommission, OnNewEra, PolkadotBridge, PolkadotBridgeCall, PolkadotBridgeState,
-	PolkadotBridgeType, PolkadotStakingLedger, PolkadotUnlockChunk,
+	PolkadotBridgeType, PolkadotStakingCall, PolkadotStakingLedger, PolkadotUnlockChunk,
 };
 
 pub type Price = FixedU128;
@@ -212,6 +217,24 @@ where
 	}
 }
 
+/// An abstraction of loan operations for Honzon Protocol.
+pub trait HonzonManager<AccountId, CurrencyId, Amount, Balance> {
+	/// Adjust the loan of `who` under `currency_id`.
+	fn adjust_loan(
+		who: &AccountId,
+		currency_id: CurrencyId,
+		collateral_adjustment: Amount,
+		debit_adjustment: Amount,
+	) -> DispatchResult;
+
+	/// Close the loan of `who` which has debit under `currency_id` by DEX.
+	fn close_loan_by_dex(who: AccountId, currency_id: CurrencyId, maybe_path: Option<&[CurrencyId]>) -> DispatchResult;
+
+	/// Get the `(collateral, debit)` of the loan of `who` under
+	/// `currency_id`.
+	fn get_position(who: &AccountId, currency_id: CurrencyId) -> (Balance, Balance);
+}
+
 /// An abstraction of cdp treasury for Honzon Protocol.
 pub trait CDPTreasury<AccountId> {
 	type Balance;
@@ -288,14 +311,118 @@ pub trait PriceProvider<CurrencyId> {
 	fn unlock_price(currency_id: CurrencyId);
 }
 
+/// Provides the time when the price of a currency was fed by the oracle.
+pub trait PriceTimestampProvider<CurrencyId, Moment> {
+	/// Return `None` if the price is not from the oracle feed.
+	fn get_price_timestamp(currency_id: CurrencyId) -> Option<Moment>;
+}
+
 pub trait ExchangeRateProvider {
 	fn get_exchange_rate() -> ExchangeRate;
 }
 
+/// The onchain signals monitored by the circuit breaker.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug)]
+pub enum CircuitSignal {
+	/// Deviation between the oracle price and the DEX price
+	OracleDeviation,
+	/// Drop of the oracle price since the previous block
+	PriceCrash,
+	/// Increase of the bad debt in CDP treasury since the previous block, in
+	/// proportion to the issuance of stable currency
+	BadDebtSpike,
+}
+
+/// The operations which can be suspended by the circuit breaker.
+#[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug)]
+pub enum GuardedOperation {
+	/// Issue new debit
+	NewLoan,
+	/// Withdraw collateral above the configured total size
+	Withdrawal,
+}
+
+/// A tier of trigger, trips the `operations` for `cool_down` blocks when the
+/// magnitude of the signal reaches `threshold`.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug)]
+pub struct TriggerTier<BlockNumber> {
+	pub threshold: Ratio,
+	pub operations: Vec<GuardedOperation>,
+	pub cool_down: BlockNumber,
+}
+
+pub trait CircuitBreaker<BlockNumber> {
+	/// Register a trigger tier for the signal.
+	fn register_trigger(signal: CircuitSignal, tier: TriggerTier<BlockNumber>) -> DispatchResult;
+	/// Report the magnitude of the signal, trips all the tiers it reaches.
+	fn report(signal: CircuitSignal, magnitude: Ratio);
+	/// Return true if the operation on `amount` of `currency_id` is allowed,
+	/// the allowed `amount` is accounted while the operation is tripped.
+	fn try_allow(operation: GuardedOperation, currency_id: CurrencyId, amount: primitives::Balance) -> bool;
+}
+
+impl<BlockNumber> CircuitBreaker<BlockNumber> for () {
+	fn register_trigger(_signal: CircuitSignal, _tier: TriggerTier<BlockNumber>) -> DispatchResult {
+		Ok(())
+	}
+
+	fn report(_signal: CircuitSignal, _magnitude: Ratio) {}
+
+	fn try_allow(_operation: GuardedOperation, _currency_id: CurrencyId, _amount: primitives::Balance) -> bool {
+		true
+	}
+}
+
+/// The invariants across parameters, parameter changes violating them should
+/// be rejected.
+pub trait ParameterInvariants {
+	fn check_invariants() -> DispatchResult;
+}
+
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+impl ParameterInvariants for Tuple {
+	fn check_invariants() -> DispatchResult {
+		for_tuples!( #( Tuple::check_invariants()?; )* );
+		Ok(())
+	}
+}
+
 pub trait EmergencyShutdown {
 	fn is_shutdown() -> bool;
 }
 
+/// Liquidate the collateral of unsafe CDP by a keeper contract.
+pub trait LiquidationKeeper<CurrencyId, Balance> {
+	/// Whether `contract` is a keeper contract able to liquidate collateral.
+	fn is_keeper(contract: EvmAddress) -> bool;
+
+	/// Sell up to `collateral_amount` of `currency_id` held by CDP treasury
+	/// through the keeper `contract`, which must return at least
+	/// `target_stable_amount` stable currency to CDP treasury. Returns the
+	/// collateral amount actually sold.
+	fn liquidate(
+		contract: EvmAddress,
+		currency_id: CurrencyId,
+		collateral_amount: Balance,
+		target_stable_amount: Balance,
+	) -> sp_std::result::Result<Balance, DispatchError>;
+}
+
+impl<CurrencyId, Balance> LiquidationKeeper<CurrencyId, Balance> for () {
+	fn is_keeper(_contract: EvmAddress) -> bool {
+		false
+	}
+
+	fn liquidate(
+		_contract: EvmAddress,
+		_currency_id: CurrencyId,
+		_collateral_amount: Balance,
+		_target_stable_amount: Balance,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		Err(DispatchError::Other("unimplemented LiquidationKeeper"))
+	}
+}
+
 pub trait DEXIncentives<AccountId, CurrencyId, Balance> {
 	fn do_deposit_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
 	fn do_withdraw_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
@@ -311,11 +438,53 @@ impl<AccountId, CurrencyId, Balance> DEXIncentives<AccountId, CurrencyId, Balanc
 	}
 }
 
+/// An abstraction of dex liquidity farming for Incentives Protocol.
+pub trait IncentivesManager<AccountId, CurrencyId, Balance> {
+	/// Stake `amount` of dex share `lp_currency_id` of `who` to earn
+	/// rewards.
+	fn deposit_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
+
+	/// Unstake `amount` of dex share `lp_currency_id` of `who`.
+	fn withdraw_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult;
+
+	/// Claim the incentive and saving rewards of `who` for staking dex share
+	/// `lp_currency_id`.
+	fn claim_dex_rewards(who: &AccountId, lp_currency_id: CurrencyId) -> DispatchResult;
+
+	/// Get the `(incentive, saving)` pending rewards of `who` for staking
+	/// dex share `lp_currency_id`.
+	fn get_pending_dex_rewards(who: &AccountId, lp_currency_id: CurrencyId) -> (Balance, Balance);
+}
+
 /// Return true if the call of EVM precompile contract is allowed.
 pub trait PrecompileCallerFilter {
 	fn is_allowed(caller: H160) -> bool;
 }
 
+/// Return true if the contract `caller` is allowed to run the state-changing
+/// precompiles in its own context by DELEGATECALL or CALLCODE.
+pub trait PrecompileDelegateCallFilter {
+	fn is_allowed(caller: H160) -> bool;
+}
+
+impl PrecompileDelegateCallFilter for () {
+	fn is_allowed(_caller: H160) -> bool {
+		false
+	}
+}
+
+/// Return true if calling the EVM contract or precompile at `address` is
+/// allowed.
+pub trait EVMCallFilter {
+	fn is_allowed(address: &EvmAddress) -> bool;
+}
+
+impl EVMCallFilter for () {
+	fn is_allowed(_address: &EvmAddress) -> bool {
+		true
+	}
+}
+
 /// An abstraction of EVM for EVMBridge
 pub trait EVM<AccountId> {
 	type Balance: AtLeast32BitUnsigned + Copy + MaybeSerializeDeserialize + Default;
@@ -342,6 +511,9 @@ pub enum ExecutionMode {
 	View,
 	/// Also discard any state changes and use estimate gas mode for evm config
 	EstimateGas,
+	/// Execute with STATICCALL semantics, any state changing opcode fails the
+	/// call. Discard any state changes and skip the storage deposit.
+	Static,
 }
 
 #[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug)]
@@ -368,6 +540,9 @@ pub trait EVMBridge<AccountId, Balance> {
 	fn balance_of(context: InvokeContext, address: EvmAddress) -> Result<Balance, DispatchError>;
 	/// Execute ERC20.transfer(address, uint256) to transfer value to `to`
 	fn transfer(context: InvokeContext, to: EvmAddress, value: Balance) -> DispatchResult;
+	/// Execute `input` on the contract with STATICCALL semantics and return the
+	/// output. No storage deposit is charged.
+	fn static_call(context: InvokeContext, input: Vec<u8>) -> Result<Vec<u8>, DispatchError>;
 	/// Get the real origin account and charge storage rent from the origin.
 	fn get_origin() -> Option<AccountId>;
 	/// Provide a method to set origin for `on_initialize`
@@ -394,6 +569,9 @@ impl<AccountId, Balance: Default> EVMBridge<AccountId, Balance> for () {
 	fn transfer(_context: InvokeContext, _to: EvmAddress, _value: Balance) -> DispatchResult {
 		Err(DispatchError::Other("unimplemented evm bridge"))
 	}
+	fn static_call(_context: InvokeContext, _input: Vec<u8>) -> Result<Vec<u8>, DispatchError> {
+		Err(DispatchError::Other("unimplemented evm bridge"))
+	}
 	fn get_origin() -> Option<AccountId> {
 		None
 	}
@@ -416,6 +594,34 @@ pub trait EVMStateRentTrait<AccountId, Balance> {
 	fn transfer_maintainer(from: AccountId, contract: H160, new_maintainer: H160) -> DispatchResult;
 }
 
+/// The restriction on the callers of an EVM contract.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum CallerAccessMode {
+	/// Only the listed callers are allowed to call the contract.
+	AllowList,
+	/// The listed callers are not allowed to call the contract.
+	DenyList,
+}
+
+pub trait EVMCallerAccessControl<AccountId> {
+	/// Query the caller access mode of the contract, `None` if the contract is
+	/// not restricted.
+	fn query_caller_access_mode(contract: H160) -> Option<CallerAccessMode>;
+	/// Query whether the caller is in the caller list of the contract.
+	fn query_is_listed_caller(contract: H160, caller: H160) -> bool;
+	/// Query the number of callers in the caller list of the contract.
+	fn query_listed_caller_count(contract: H160) -> u32;
+	/// Return true if the caller is allowed to call the contract.
+	fn is_caller_allowed(contract: H160, caller: H160) -> bool;
+	/// Set the caller access mode of the contract, `None` removes the
+	/// restriction and clears the caller list.
+	fn set_caller_access_mode(from: AccountId, contract: H160, mode: Option<CallerAccessMode>) -> DispatchResult;
+	/// Add the caller to the caller list of the contract.
+	fn add_listed_caller(from: AccountId, contract: H160, caller: H160) -> DispatchResult;
+	/// Remove the caller from the caller list of the contract.
+	fn remove_listed_caller(from: AccountId, contract: H160, caller: H160) -> DispatchResult;
+}
+
 pub trait TransactionPayment<AccountId, Balance, NegativeImbalance> {
 	fn reserve_fee(who: &AccountId, weight: Weight) -> Result<Balance, DispatchError>;
 	fn unreserve_fee(who: &AccountId, fee: Balance);
@@ -496,6 +702,102 @@ pub trait AddressMapping<AccountId> {
 	fn is_linked(account_id: &AccountId, evm: &EvmAddress) -> bool;
 }
 
+/// The information of NFT classes and tokens, to expose NFT class as the
+/// ERC-721 compatible contract.
+pub trait NFTInfo<AccountId> {
+	type ClassId;
+	type TokenId;
+	type Balance;
+
+	/// Returns the number of tokens of `class_id` owned by `who`.
+	fn balance_of_class(who: &AccountId, class_id: Self::ClassId) -> Self::Balance;
+	/// Returns the metadata of class, None if the class doesn't exist.
+	fn class_metadata(class_id: Self::ClassId) -> Option<Vec<u8>>;
+	/// Returns the metadata of token, None if the token doesn't exist.
+	fn token_metadata(token: (Self::ClassId, Self::TokenId)) -> Option<Vec<u8>>;
+}
+
+/// The approvals of NFT tokens, to transfer NFT tokens by the approved
+/// accounts and operators as the ERC-721 compatible contract.
+pub trait NFTApproval<AccountId> {
+	type ClassId;
+	type TokenId;
+
+	/// Returns the account approved to transfer the token.
+	fn approved(token: (Self::ClassId, Self::TokenId)) -> Option<AccountId>;
+	/// Returns true if `operator` is approved to transfer all tokens of
+	/// `class_id` owned by `owner`.
+	fn is_approved_for_all(owner: &AccountId, class_id: Self::ClassId, operator: &AccountId) -> bool;
+	/// Approve `approved` to transfer the token, `None` to revoke. `who`
+	/// must be the owner or the operator of owner.
+	fn approve(who: &AccountId, approved: Option<AccountId>, token: (Self::ClassId, Self::TokenId)) -> DispatchResult;
+	/// Approve or revoke `operator` to transfer all tokens of `class_id`
+	/// owned by `who`.
+	fn set_approval_for_all(
+		who: &AccountId,
+		class_id: Self::ClassId,
+		operator: &AccountId,
+		approved: bool,
+	) -> DispatchResult;
+	/// Transfer the token owned by `from` to `to`, `who` must be the owner,
+	/// the approved account or the operator of owner.
+	fn transfer_from(
+		who: &AccountId,
+		from: &AccountId,
+		to: &AccountId,
+		token: (Self::ClassId, Self::TokenId),
+	) -> DispatchResult;
+}
+
+/// Mint NFT tokens by the owner or the minter of NFT class.
+pub trait NFTMinter<AccountId> {
+	type ClassId;
+	type TokenId;
+
+	/// Mint `quantity` tokens of `class_id` to `to`, `who` must be the owner
+	/// or the minter of the class. Returns the ids of minted tokens.
+	fn mint(
+		who: &AccountId,
+		to: &AccountId,
+		class_id: Self::ClassId,
+		metadata: Vec<u8>,
+		quantity: u32,
+	) -> Result<Vec<Self::TokenId>, DispatchError>;
+}
+
+/// Positions of modules represented as NFT tokens, which are minted in the
+/// class owned by the module, so that positions can be transferred and
+/// traded as NFT.
+pub trait PositionNFT<AccountId> {
+	type TokenId;
+
+	/// Mint a position token of module `id` to `owner`, the class of module
+	/// is created on first mint. Returns the id of minted token.
+	fn mint_position(id: PalletId, owner: &AccountId, metadata: Vec<u8>) -> Result<Self::TokenId, DispatchError>;
+	/// Burn the position token of module `id`, which must be owned by
+	/// `owner`.
+	fn burn_position(id: PalletId, owner: &AccountId, token_id: Self::TokenId) -> DispatchResult;
+	/// Returns the owner of the position token of module `id`, None if the
+	/// token doesn't exist.
+	fn position_owner(id: PalletId, token_id: Self::TokenId) -> Option<AccountId>;
+}
+
+impl<AccountId> PositionNFT<AccountId> for () {
+	type TokenId = u64;
+
+	fn mint_position(_id: PalletId, _owner: &AccountId, _metadata: Vec<u8>) -> Result<Self::TokenId, DispatchError> {
+		Err(DispatchError::Other("no position NFT"))
+	}
+
+	fn burn_position(_id: PalletId, _owner: &AccountId, _token_id: Self::TokenId) -> DispatchResult {
+		Err(DispatchError::Other("no position NFT"))
+	}
+
+	fn position_owner(_id: PalletId, _token_id: Self::TokenId) -> Option<AccountId> {
+		None
+	}
+}
+
 /// A mapping between u32 and Erc20 address.
 /// provide a way to encode/decode for CurrencyId;
 pub trait CurrencyIdMapping {
@@ -540,3 +842,138 @@ impl CurrencyIdMapping for () {
 		None
 	}
 }
+
+/// ERC20 style allowances of currencies, a spender can transfer up to the
+/// approved amount from the balance of the owner.
+pub trait MultiCurrencyAllowance<AccountId> {
+	type CurrencyId;
+	type Balance;
+
+	/// The amount of `currency_id` that `spender` is allowed to transfer on
+	/// behalf of `owner`.
+	fn allowance(currency_id: Self::CurrencyId, owner: &AccountId, spender: &AccountId) -> Self::Balance;
+	/// Allow `spender` to transfer up to `amount` of `currency_id` on behalf
+	/// of `owner`, replacing the current allowance.
+	fn approve(
+		currency_id: Self::CurrencyId,
+		owner: &AccountId,
+		spender: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult;
+	/// Transfer `amount` of `currency_id` from `from` to `to` by `spender`,
+	/// deducting the allowance.
+	fn transfer_from(
+		currency_id: Self::CurrencyId,
+		spender: &AccountId,
+		from: &AccountId,
+		to: &AccountId,
+		amount: Self::Balance,
+	) -> DispatchResult;
+}
+
+/// The metadata of the assets registered by governance.
+pub trait AssetMetadataProvider {
+	/// Returns the registered decimals of `currency_id`, `None` if the asset
+	/// is not registered. The decimals of `CurrencyId::DexShare` are not
+	/// registered, and the decimals of `CurrencyId::Erc20` are provided by
+	/// `CurrencyIdMapping`.
+	fn decimals(currency_id: CurrencyId) -> Option<u8>;
+}
+
+#[cfg(feature = "std")]
+impl AssetMetadataProvider for () {
+	fn decimals(_currency_id: CurrencyId) -> Option<u8> {
+		None
+	}
+}
+
+/// Reference counting of the modules which use a mapped Erc20 CurrencyId,
+/// so the dependencies can be inspected before an Erc20 is deprecated.
+pub trait Erc20ReferenceCounter {
+	/// Record that the module `id` starts to use `currency_id`.
+	/// Do nothing if `currency_id` is not `CurrencyId::Erc20`.
+	fn inc_reference(id: PalletId, currency_id: CurrencyId);
+	/// Record that the module `id` stops using `currency_id`.
+	/// Do nothing if `currency_id` is not `CurrencyId::Erc20`.
+	fn dec_reference(id: PalletId, currency_id: CurrencyId);
+	/// Returns the modules referencing the Erc20 `address` and their counters.
+	fn references(address: EvmAddress) -> Vec<(PalletId, u32)>;
+}
+
+impl Erc20ReferenceCounter for () {
+	fn inc_reference(_id: PalletId, _currency_id: CurrencyId) {}
+
+	fn dec_reference(_id: PalletId, _currency_id: CurrencyId) {}
+
+	fn references(_address: EvmAddress) -> Vec<(PalletId, u32)> {
+		Default::default()
+	}
+}
+
+/// Transfer assets to other chains by XCM.
+pub trait XcmTransfer<AccountId, Balance, CurrencyId> {
+	/// Transfer `amount` of `currency_id` from `who` to `dest`.
+	fn transfer(
+		who: AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		dest: MultiLocation,
+		dest_weight: Weight,
+	) -> DispatchResult;
+
+	/// Transfer `asset` from `who` to `dest`.
+	fn transfer_multi_asset(
+		who: AccountId,
+		asset: MultiAsset,
+		dest: MultiLocation,
+		dest_weight: Weight,
+	) -> DispatchResult;
+}
+
+impl<AccountId, Balance, CurrencyId> XcmTransfer<AccountId, Balance, CurrencyId> for () {
+	fn transfer(
+		_who: AccountId,
+		_currency_id: CurrencyId,
+		_amount: Balance,
+		_dest: MultiLocation,
+		_dest_weight: Weight,
+	) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented XcmTransfer"))
+	}
+
+	fn transfer_multi_asset(
+		_who: AccountId,
+		_asset: MultiAsset,
+		_dest: MultiLocation,
+		_dest_weight: Weight,
+	) -> DispatchResult {
+		Err(DispatchError::Other("unimplemented XcmTransfer"))
+	}
+}
+
+/// Calls into the Wasm contracts, i.e. `pallet_contracts`, on behalf of the
+/// other execution environments.
+pub trait WasmContracts<AccountId, Balance> {
+	/// Call the contract `dest` from `origin` with `value` transferred and
+	/// at most `gas_limit` weight, returns the output of the call and the
+	/// weight it used.
+	fn call(
+		origin: AccountId,
+		dest: AccountId,
+		value: Balance,
+		gas_limit: Weight,
+		input: Vec<u8>,
+	) -> sp_std::result::Result<(Vec<u8>, Weight), DispatchError>;
+}
+
+impl<AccountId, Balance> WasmContracts<AccountId, Balance> for () {
+	fn call(
+		_origin: AccountId,
+		_dest: AccountId,
+		_value: Balance,
+		_gas_limit: Weight,
+		_input: Vec<u8>,
+	) -> sp_std::result::Result<(Vec<u8>, Weight), DispatchError> {
+		Err(DispatchError::Other("unimplemented WasmContracts"))
+	}
+}
@@ -0,0 +1,280 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Fixed point and integer math helpers shared by the protocol modules, so
+//! that quoted and executed values are computed with the same rounding.
+
+use crate::Rate;
+use primitives::Balance;
+use sp_core::U256;
+use sp_runtime::{
+	traits::{checked_pow, CheckedAdd, CheckedSub, One, Saturating, UniqueSaturatedInto},
+	FixedPointNumber, FixedU128,
+};
+use sp_std::convert::TryInto;
+
+/// The rounding mode of a division.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Rounding {
+	/// Round towards zero.
+	Down,
+	/// Round away from zero.
+	Up,
+}
+
+/// Compound `rate` over `periods` periods, i.e. `(1 + rate) ^ periods - 1`.
+/// Saturates at the max value on overflow.
+pub fn compound_rate(rate: Rate, periods: u64) -> Rate {
+	rate.saturating_add(Rate::one())
+		.saturating_pow(periods.unique_saturated_into())
+		.saturating_sub(Rate::one())
+}
+
+/// Compound `rate` over `periods` periods, i.e. `(1 + rate) ^ periods - 1`.
+/// Returns `None` on overflow.
+pub fn checked_compound_rate(rate: Rate, periods: u64) -> Option<Rate> {
+	let base = rate.checked_add(&Rate::one())?;
+	checked_pow(base, periods.try_into().ok()?)?.checked_sub(&Rate::one())
+}
+
+/// Calculate `(n_1 * n_2 * ...) / (d_1 * d_2 * ...)` with 256 bits
+/// intermediate precision and the given `rounding`.
+///
+/// Returns `None` if the denominator is zero, or the intermediate products
+/// or the result overflow.
+pub fn ratio_of_products(numerators: &[Balance], denominators: &[Balance], rounding: Rounding) -> Option<Balance> {
+	let product = |factors: &[Balance]| {
+		factors
+			.iter()
+			.try_fold(U256::one(), |acc, factor| acc.checked_mul(U256::from(*factor)))
+	};
+	let numerator = product(numerators)?;
+	let denominator = product(denominators)?;
+	if denominator.is_zero() {
+		return None;
+	}
+
+	let (quotient, remainder) = numerator.div_mod(denominator);
+	let result = if rounding == Rounding::Up && !remainder.is_zero() {
+		quotient.checked_add(U256::one())?
+	} else {
+		quotient
+	};
+	result.try_into().ok()
+}
+
+/// Calculate `fixed * amount` with the given `rounding`. Returns `None` on
+/// overflow.
+pub fn checked_mul_int(fixed: FixedU128, amount: Balance, rounding: Rounding) -> Option<Balance> {
+	ratio_of_products(
+		&[fixed.into_inner(), amount],
+		&[<FixedU128 as FixedPointNumber>::DIV],
+		rounding,
+	)
+}
+
+/// Calculate `amount / fixed` with the given `rounding`. Returns `None` if
+/// `fixed` is zero or on overflow.
+pub fn checked_div_int(amount: Balance, fixed: FixedU128, rounding: Rounding) -> Option<Balance> {
+	ratio_of_products(
+		&[amount, <FixedU128 as FixedPointNumber>::DIV],
+		&[fixed.into_inner()],
+		rounding,
+	)
+}
+
+/// Calculate the integer square root of `a * b`, rounding down.
+pub fn sqrt_of_product(a: Balance, b: Balance) -> Balance {
+	// the product of two 128 bits values never overflows 256 bits, and its
+	// square root always fits in 128 bits.
+	U256::from(a)
+		.saturating_mul(U256::from(b))
+		.integer_sqrt()
+		.try_into()
+		.unwrap_or_else(|_| Balance::max_value())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_runtime::traits::Zero;
+
+	#[test]
+	fn compound_rate_works() {
+		assert_eq!(compound_rate(Rate::zero(), 100), Rate::zero());
+		assert_eq!(compound_rate(Rate::saturating_from_rational(1, 10), 0), Rate::zero());
+		assert_eq!(
+			compound_rate(Rate::saturating_from_rational(1, 10), 2),
+			Rate::saturating_from_rational(21, 100)
+		);
+		assert_eq!(compound_rate(Rate::saturating_from_integer(2), 200), Rate::max_value());
+		assert_eq!(checked_compound_rate(Rate::saturating_from_integer(2), 200), None);
+	}
+
+	#[test]
+	fn ratio_of_products_works() {
+		assert_eq!(ratio_of_products(&[10, 10], &[3], Rounding::Down), Some(33));
+		assert_eq!(ratio_of_products(&[10, 10], &[3], Rounding::Up), Some(34));
+		assert_eq!(ratio_of_products(&[10, 9], &[3], Rounding::Up), Some(30));
+		assert_eq!(ratio_of_products(&[10], &[0], Rounding::Down), None);
+		assert_eq!(
+			ratio_of_products(&[Balance::max_value(), 2], &[1], Rounding::Down),
+			None
+		);
+		assert_eq!(
+			ratio_of_products(
+				&[Balance::max_value(), Balance::max_value()],
+				&[Balance::max_value()],
+				Rounding::Down
+			),
+			Some(Balance::max_value())
+		);
+	}
+
+	#[test]
+	fn checked_mul_int_and_checked_div_int_work() {
+		let half = FixedU128::saturating_from_rational(1, 2);
+		assert_eq!(checked_mul_int(half, 3, Rounding::Down), Some(1));
+		assert_eq!(checked_mul_int(half, 3, Rounding::Up), Some(2));
+		assert_eq!(checked_div_int(3, half, Rounding::Down), Some(6));
+		assert_eq!(
+			checked_div_int(3, FixedU128::saturating_from_rational(2, 1), Rounding::Up),
+			Some(2)
+		);
+		assert_eq!(checked_div_int(3, FixedU128::zero(), Rounding::Down), None);
+	}
+
+	#[test]
+	fn sqrt_of_product_works() {
+		assert_eq!(sqrt_of_product(0, 100), 0);
+		assert_eq!(sqrt_of_product(4, 9), 6);
+		assert_eq!(sqrt_of_product(2, 4), 2);
+		assert_eq!(
+			sqrt_of_product(Balance::max_value(), Balance::max_value()),
+			Balance::max_value()
+		);
+	}
+
+	#[test]
+	fn checked_compound_rate_matches_saturating() {
+		let cases: &[(u128, u64)] = &[
+			(0, 0),
+			(0, 10_000),
+			(1, 1),
+			(100_000_000_000_000_000, 1),
+			(100_000_000_000_000_000, 10),
+			(500_000_000_000_000_000, 16),
+			(1_000_000_000_000_000_000, 64),
+			(2_000_000_000_000_000_000, 200),
+		];
+		for &(rate, periods) in cases {
+			let rate = Rate::from_inner(rate);
+			if let Some(compounded) = checked_compound_rate(rate, periods) {
+				assert_eq!(compounded, compound_rate(rate, periods));
+				if periods > 0 {
+					assert!(compounded >= rate);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn ratio_of_products_rounding_is_consistent() {
+		let cases: &[(u128, u128, u128)] = &[
+			(0, 0, 1),
+			(1, 1, 1),
+			(7, 3, 2),
+			(10, 10, 3),
+			(10, 9, 3),
+			(u64::MAX as u128, u64::MAX as u128, 7),
+			(Balance::max_value(), 3, 3),
+			(Balance::max_value(), Balance::max_value(), Balance::max_value()),
+			(Balance::max_value(), Balance::max_value(), Balance::max_value() - 1),
+		];
+		for &(a, b, c) in cases {
+			let down = ratio_of_products(&[a, b], &[c], Rounding::Down);
+			let up = ratio_of_products(&[a, b], &[c], Rounding::Up);
+			if let (Some(down), Some(up)) = (down, up) {
+				assert!(up == down || up == down + 1);
+				let exact = U256::from(a) * U256::from(b) % U256::from(c) == U256::zero();
+				assert_eq!(exact, up == down);
+				assert!(U256::from(down) * U256::from(c) <= U256::from(a) * U256::from(b));
+				assert!(U256::from(up) * U256::from(c) >= U256::from(a) * U256::from(b));
+			}
+		}
+	}
+
+	#[test]
+	fn checked_mul_int_matches_fixed_point() {
+		let cases: &[(u64, u64)] = &[
+			(0, 0),
+			(1, u64::MAX),
+			(500_000_000_000_000_000, 3),
+			(1_000_000_000_000_000_000, 12_345),
+			(u64::MAX, u64::MAX),
+		];
+		for &(inner, amount) in cases {
+			let fixed = FixedU128::from_inner(inner.into());
+			assert_eq!(
+				checked_mul_int(fixed, amount.into(), Rounding::Down),
+				fixed.checked_mul_int(Balance::from(amount))
+			);
+		}
+	}
+
+	#[test]
+	fn checked_div_int_round_up_is_minimal() {
+		let cases: &[(u128, u64)] = &[
+			(1, 0),
+			(1, u64::MAX),
+			(3, 10),
+			(333_333_333_333_333_333, 1_000),
+			(1_000_000_000_000_000_000, 12_345),
+			(u64::MAX as u128 - 1, u64::MAX),
+		];
+		for &(inner, amount) in cases {
+			let fixed = FixedU128::from_inner(inner);
+			let amount = Balance::from(amount);
+			if let Some(result) = checked_div_int(amount, fixed, Rounding::Up) {
+				assert!(fixed.saturating_mul_int(result) >= amount);
+				if result > 0 {
+					assert!(fixed.saturating_mul_int(result - 1) < amount);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn sqrt_of_product_is_floor() {
+		let cases: &[(u128, u128)] = &[
+			(0, 0),
+			(1, 1),
+			(2, 3),
+			(15, 17),
+			(u64::MAX as u128, u64::MAX as u128 + 2),
+			(Balance::max_value(), 2),
+			(Balance::max_value(), Balance::max_value()),
+		];
+		for &(a, b) in cases {
+			let root = U256::from(sqrt_of_product(a, b));
+			let product = U256::from(a) * U256::from(b);
+			assert!(root * root <= product);
+			assert!((root + 1).checked_mul(root + 1).map_or(true, |square| square > product));
+		}
+	}
+}
@@ -0,0 +1,247 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # Transaction Pause Module
+//!
+//! ## Overview
+//!
+//! Allows governance to pause problematic functionality without a runtime
+//! upgrade. All calls of a pallet, a specific call of a pallet, or calls to a
+//! specific EVM contract or precompile address can be paused. Paused calls
+//! are filtered out by `PausedTransactionFilter` as the base call filter,
+//! including the calls nested in other calls, and calls to paused EVM
+//! addresses are rejected by the EVM executor.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{
+	dispatch::{CallMetadata, GetCallMetadata},
+	pallet_prelude::*,
+	traits::{Filter, PalletInfo},
+};
+use frame_system::pallet_prelude::*;
+use primitives::evm::EvmAddress;
+use sp_std::{marker::PhantomData, prelude::*};
+use support::EVMCallFilter;
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may pause and unpause transactions
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Can not pause
+		CannotPause,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Paused transaction. \[pallet_name_bytes, function_name_bytes\]
+		TransactionPaused(Vec<u8>, Vec<u8>),
+		/// Unpaused transaction. \[pallet_name_bytes, function_name_bytes\]
+		TransactionUnpaused(Vec<u8>, Vec<u8>),
+		/// Paused all transactions of a pallet. \[pallet_name_bytes\]
+		PalletPaused(Vec<u8>),
+		/// Unpaused all transactions of a pallet. \[pallet_name_bytes\]
+		PalletUnpaused(Vec<u8>),
+		/// Paused calls to an EVM address. \[address\]
+		EvmAddressPaused(EvmAddress),
+		/// Unpaused calls to an EVM address. \[address\]
+		EvmAddressUnpaused(EvmAddress),
+	}
+
+	/// The paused transactions.
+	///
+	/// map (PalletNameBytes, FunctionNameBytes) => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn paused_transactions)]
+	pub type PausedTransactions<T: Config> = StorageMap<_, Twox64Concat, (Vec<u8>, Vec<u8>), (), OptionQuery>;
+
+	/// The pallets whose transactions are all paused.
+	///
+	/// map PalletNameBytes => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn paused_pallets)]
+	pub type PausedPallets<T: Config> = StorageMap<_, Twox64Concat, Vec<u8>, (), OptionQuery>;
+
+	/// The EVM contract or precompile addresses whose calls are paused.
+	///
+	/// map EvmAddress => Option<()>
+	#[pallet::storage]
+	#[pallet::getter(fn paused_evm_addresses)]
+	pub type PausedEvmAddresses<T: Config> = StorageMap<_, Twox64Concat, EvmAddress, (), OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pause a specific call of a pallet.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `pallet_name`: the name of the pallet, as declared in
+		///   `construct_runtime`
+		/// - `function_name`: the name of the call
+		#[pallet::weight(T::WeightInfo::pause_transaction())]
+		pub fn pause_transaction(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			function_name: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(!Self::is_self(&pallet_name), Error::<T>::CannotPause);
+
+			if !PausedTransactions::<T>::contains_key((&pallet_name, &function_name)) {
+				PausedTransactions::<T>::insert((&pallet_name, &function_name), ());
+				Self::deposit_event(Event::TransactionPaused(pallet_name, function_name));
+			}
+			Ok(().into())
+		}
+
+		/// Unpause a specific call of a pallet.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::unpause_transaction())]
+		pub fn unpause_transaction(
+			origin: OriginFor<T>,
+			pallet_name: Vec<u8>,
+			function_name: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if PausedTransactions::<T>::take((&pallet_name, &function_name)).is_some() {
+				Self::deposit_event(Event::TransactionUnpaused(pallet_name, function_name));
+			}
+			Ok(().into())
+		}
+
+		/// Pause all calls of a pallet.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `pallet_name`: the name of the pallet, as declared in
+		///   `construct_runtime`
+		#[pallet::weight(T::WeightInfo::pause_pallet())]
+		pub fn pause_pallet(origin: OriginFor<T>, pallet_name: Vec<u8>) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(!Self::is_self(&pallet_name), Error::<T>::CannotPause);
+
+			if !PausedPallets::<T>::contains_key(&pallet_name) {
+				PausedPallets::<T>::insert(&pallet_name, ());
+				Self::deposit_event(Event::PalletPaused(pallet_name));
+			}
+			Ok(().into())
+		}
+
+		/// Unpause all calls of a pallet.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::unpause_pallet())]
+		pub fn unpause_pallet(origin: OriginFor<T>, pallet_name: Vec<u8>) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if PausedPallets::<T>::take(&pallet_name).is_some() {
+				Self::deposit_event(Event::PalletUnpaused(pallet_name));
+			}
+			Ok(().into())
+		}
+
+		/// Pause calls to an EVM contract or precompile.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::pause_evm_address())]
+		pub fn pause_evm_address(origin: OriginFor<T>, address: EvmAddress) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if !PausedEvmAddresses::<T>::contains_key(&address) {
+				PausedEvmAddresses::<T>::insert(&address, ());
+				Self::deposit_event(Event::EvmAddressPaused(address));
+			}
+			Ok(().into())
+		}
+
+		/// Unpause calls to an EVM contract or precompile.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(T::WeightInfo::unpause_evm_address())]
+		pub fn unpause_evm_address(origin: OriginFor<T>, address: EvmAddress) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if PausedEvmAddresses::<T>::take(&address).is_some() {
+				Self::deposit_event(Event::EvmAddressUnpaused(address));
+			}
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns true if the call is paused, either by its pallet or by itself.
+	pub fn is_paused(pallet_name: &[u8], function_name: &[u8]) -> bool {
+		PausedPallets::<T>::contains_key(pallet_name)
+			|| PausedTransactions::<T>::contains_key((pallet_name, function_name))
+	}
+
+	fn is_self(pallet_name: &[u8]) -> bool {
+		T::PalletInfo::name::<Self>().map_or(false, |name| name.as_bytes() == pallet_name)
+	}
+}
+
+impl<T: Config> EVMCallFilter for Pallet<T> {
+	fn is_allowed(address: &EvmAddress) -> bool {
+		!PausedEvmAddresses::<T>::contains_key(address)
+	}
+}
+
+/// Filter out the calls whose call or pallet is paused. Used as the
+/// `BaseCallFilter` of the runtime, so the calls nested in batches, proxies
+/// and multisigs are filtered as well.
+pub struct PausedTransactionFilter<T>(PhantomData<T>);
+
+impl<T: Config> Filter<T::Call> for PausedTransactionFilter<T>
+where
+	T::Call: GetCallMetadata,
+{
+	fn filter(call: &T::Call) -> bool {
+		let CallMetadata {
+			function_name,
+			pallet_name,
+		} = call.get_call_metadata();
+		!Pallet::<T>::is_paused(pallet_name.as_bytes(), function_name.as_bytes())
+	}
+}
@@ -0,0 +1,171 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the transaction pause module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_runtime::traits::{BadOrigin, Dispatchable};
+
+#[test]
+fn pause_transaction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			TransactionPause::pause_transaction(Origin::signed(5), b"System".to_vec(), b"remark".to_vec()),
+			BadOrigin
+		);
+		assert_noop!(
+			TransactionPause::pause_transaction(
+				Origin::signed(ALICE),
+				b"TransactionPause".to_vec(),
+				b"pause_pallet".to_vec()
+			),
+			Error::<Runtime>::CannotPause
+		);
+
+		assert_eq!(
+			TransactionPause::paused_transactions((b"System".to_vec(), b"remark".to_vec())),
+			None
+		);
+		assert_ok!(TransactionPause::pause_transaction(
+			Origin::signed(ALICE),
+			b"System".to_vec(),
+			b"remark".to_vec()
+		));
+		let event = Event::transaction_pause(crate::Event::TransactionPaused(b"System".to_vec(), b"remark".to_vec()));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(
+			TransactionPause::paused_transactions((b"System".to_vec(), b"remark".to_vec())),
+			Some(())
+		);
+		assert!(TransactionPause::is_paused(b"System", b"remark"));
+		assert!(!TransactionPause::is_paused(b"System", b"remark_with_event"));
+
+		assert_noop!(
+			TransactionPause::unpause_transaction(Origin::signed(5), b"System".to_vec(), b"remark".to_vec()),
+			BadOrigin
+		);
+		assert_ok!(TransactionPause::unpause_transaction(
+			Origin::signed(ALICE),
+			b"System".to_vec(),
+			b"remark".to_vec()
+		));
+		let event = Event::transaction_pause(crate::Event::TransactionUnpaused(
+			b"System".to_vec(),
+			b"remark".to_vec(),
+		));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert!(!TransactionPause::is_paused(b"System", b"remark"));
+	});
+}
+
+#[test]
+fn pause_pallet_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			TransactionPause::pause_pallet(Origin::signed(5), b"System".to_vec()),
+			BadOrigin
+		);
+		assert_noop!(
+			TransactionPause::pause_pallet(Origin::signed(ALICE), b"TransactionPause".to_vec()),
+			Error::<Runtime>::CannotPause
+		);
+
+		assert_ok!(TransactionPause::pause_pallet(
+			Origin::signed(ALICE),
+			b"System".to_vec()
+		));
+		let event = Event::transaction_pause(crate::Event::PalletPaused(b"System".to_vec()));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(TransactionPause::paused_pallets(b"System".to_vec()), Some(()));
+		assert!(TransactionPause::is_paused(b"System", b"remark"));
+		assert!(TransactionPause::is_paused(b"System", b"set_code"));
+
+		assert_ok!(TransactionPause::unpause_pallet(
+			Origin::signed(ALICE),
+			b"System".to_vec()
+		));
+		let event = Event::transaction_pause(crate::Event::PalletUnpaused(b"System".to_vec()));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert!(!TransactionPause::is_paused(b"System", b"remark"));
+	});
+}
+
+#[test]
+fn pause_evm_address_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let address = EvmAddress::from_low_u64_be(0x400);
+
+		assert_noop!(
+			TransactionPause::pause_evm_address(Origin::signed(5), address),
+			BadOrigin
+		);
+		assert!(<TransactionPause as EVMCallFilter>::is_allowed(&address));
+
+		assert_ok!(TransactionPause::pause_evm_address(Origin::signed(ALICE), address));
+		let event = Event::transaction_pause(crate::Event::EvmAddressPaused(address));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert!(!<TransactionPause as EVMCallFilter>::is_allowed(&address));
+		assert!(<TransactionPause as EVMCallFilter>::is_allowed(
+			&EvmAddress::from_low_u64_be(0x401)
+		));
+
+		assert_ok!(TransactionPause::unpause_evm_address(Origin::signed(ALICE), address));
+		let event = Event::transaction_pause(crate::Event::EvmAddressUnpaused(address));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert!(<TransactionPause as EVMCallFilter>::is_allowed(&address));
+	});
+}
+
+#[test]
+fn paused_transaction_filter_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let call = Call::System(frame_system::Call::remark(vec![]));
+		assert!(PausedTransactionFilter::<Runtime>::filter(&call));
+		assert_ok!(call.clone().dispatch(Origin::signed(ALICE)));
+
+		assert_ok!(TransactionPause::pause_transaction(
+			Origin::signed(ALICE),
+			b"System".to_vec(),
+			b"remark".to_vec()
+		));
+		assert!(!PausedTransactionFilter::<Runtime>::filter(&call));
+		assert_noop!(call.clone().dispatch(Origin::signed(ALICE)), BadOrigin);
+
+		assert_ok!(TransactionPause::unpause_transaction(
+			Origin::signed(ALICE),
+			b"System".to_vec(),
+			b"remark".to_vec()
+		));
+		assert!(PausedTransactionFilter::<Runtime>::filter(&call));
+		assert_ok!(TransactionPause::pause_pallet(
+			Origin::signed(ALICE),
+			b"System".to_vec()
+		));
+		assert!(!PausedTransactionFilter::<Runtime>::filter(&call));
+		assert_noop!(call.dispatch(Origin::signed(ALICE)), BadOrigin);
+	});
+}
@@ -0,0 +1,43 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for transaction payment module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use codec::Codec;
+use sp_runtime::{
+	traits::{MaybeDisplay, MaybeFromStr},
+	FixedU128,
+};
+
+sp_api::decl_runtime_apis! {
+	pub trait TransactionPaymentRuntimeApi<Balance, CurrencyId> where
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+		CurrencyId: Codec,
+	{
+		/// The fee multiplier of the next block for EVM calls.
+		fn query_evm_fee_multiplier() -> FixedU128;
+
+		/// The fee of `uxt` paid in `currency_id`, which is the supply amount
+		/// to swap the native fee through DEX. Returns `None` if no DEX path
+		/// is available.
+		fn query_fee_in_currency(uxt: Block::Extrinsic, currency_id: CurrencyId) -> Option<Balance>;
+	}
+}
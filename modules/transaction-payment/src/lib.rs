@@ -22,6 +22,10 @@
 //!
 //! Transaction payment module is responsible for charge fee and tip in
 //! different currencies
+//!
+//! The tips are split between the block author, treasury and burn by the
+//! governance, and the fees distributed in a block are summarized in an event
+//! at the end of the block.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -30,24 +34,27 @@ use frame_support::{
 	dispatch::{DispatchResult, Dispatchable},
 	pallet_prelude::*,
 	traits::{
-		Currency, ExistenceRequirement, Imbalance, OnUnbalanced, ReservableCurrency, SameOrOther, WithdrawReasons,
+		Currency, ExistenceRequirement, Filter, GetCallMetadata, Imbalance, IsSubType, LockIdentifier,
+		LockableCurrency, OnUnbalanced, ReservableCurrency, SameOrOther, WithdrawReasons,
 	},
+	transactional,
 	weights::{DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo, WeightToFeePolynomial},
+	PalletId,
 };
 use frame_system::pallet_prelude::*;
 use orml_traits::MultiCurrency;
 use pallet_transaction_payment_rpc_runtime_api::RuntimeDispatchInfo;
 use pallet_transaction_payment_rpc_runtime_api::{FeeDetails, InclusionFee};
-use primitives::{Balance, CurrencyId};
+use primitives::{Balance, CurrencyId, EraIndex};
 use sp_runtime::{
 	traits::{
-		CheckedSub, Convert, DispatchInfoOf, PostDispatchInfoOf, SaturatedConversion, Saturating, SignedExtension,
-		UniqueSaturatedInto, Zero,
+		AccountIdConversion, CheckedSub, Convert, DispatchInfoOf, PostDispatchInfoOf, SaturatedConversion, Saturating,
+		SignedExtension, UniqueSaturatedInto, Zero,
 	},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionValidity, TransactionValidityError, ValidTransaction,
 	},
-	FixedPointNumber, FixedPointOperand, FixedU128, Perquintill,
+	FixedPointNumber, FixedPointOperand, FixedU128, PerThing, Permill, Perquintill,
 };
 use sp_std::{prelude::*, vec};
 use support::{DEXManager, Ratio, TransactionPayment};
@@ -62,6 +69,25 @@ pub use weights::WeightInfo;
 /// Fee multiplier.
 pub type Multiplier = FixedU128;
 
+/// The lock of the native currency staked for fee rebates.
+pub const REBATE_STAKE_LOCK_ID: LockIdentifier = *b"feerebat";
+
+/// The name of a call, the name of the pallet in the runtime and the name of
+/// the call in the pallet, as in the call metadata.
+pub type CallName = (Vec<u8>, Vec<u8>);
+
+/// The sponsor info to pay the fee of calls for other accounts.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct SponsorInfo {
+	/// The remaining budget of native currency to pay fee, which is reserved
+	/// from the sponsor.
+	pub budget: Balance,
+	/// The max count of sponsored calls for one beneficiary in a period.
+	pub max_calls_per_period: u32,
+	/// The calls allowed to be sponsored, any call is allowed if empty.
+	pub allowed_calls: Vec<CallName>,
+}
+
 type PalletBalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 type NegativeImbalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
@@ -165,12 +191,6 @@ where
 	M: Get<Multiplier>,
 {
 	fn convert(previous: Multiplier) -> Multiplier {
-		// Defensive only. The multiplier in storage should always be at most positive.
-		// Nonetheless we recover here in case of errors, because any value below this
-		// would be stale and can never change.
-		let min_multiplier = M::get();
-		let previous = previous.max(min_multiplier);
-
 		let weights = T::BlockWeights::get();
 		// the computed ratio is only among the normal class.
 		let normal_max_weight = weights
@@ -180,34 +200,94 @@ where
 		let current_block_weight = <frame_system::Pallet<T>>::block_weight();
 		let normal_block_weight = *current_block_weight.get(DispatchClass::Normal).min(&normal_max_weight);
 
-		let s = S::get();
-		let v = V::get();
-
-		let target_weight = (s * normal_max_weight) as u128;
-		let block_weight = normal_block_weight as u128;
+		adjust_multiplier::<S, V, M>(previous, normal_block_weight, normal_max_weight)
+	}
+}
 
-		// determines if the first_term is positive
-		let positive = block_weight >= target_weight;
-		let diff_abs = block_weight.max(target_weight) - block_weight.min(target_weight);
+/// A struct to update the EVM fee multiplier per block, which is the same
+/// as `TargetedFeeAdjustment` except that `s` is the weight consumed by the
+/// EVM calls in the previous block, rather than the whole normal class. So
+/// the congestion of EVM only raises the fee of EVM calls.
+pub struct TargetedEvmFeeAdjustment<T, S, V, M>(sp_std::marker::PhantomData<(T, S, V, M)>);
 
-		// defensive only, a test case assures that the maximum weight diff can fit in
-		// Multiplier without any saturation.
-		let diff = Multiplier::saturating_from_rational(diff_abs, normal_max_weight.max(1));
-		let diff_squared = diff.saturating_mul(diff);
+impl<T, S, V, M> MultiplierUpdate for TargetedEvmFeeAdjustment<T, S, V, M>
+where
+	T: Config,
+	S: Get<Perquintill>,
+	V: Get<Multiplier>,
+	M: Get<Multiplier>,
+{
+	fn min() -> Multiplier {
+		M::get()
+	}
+	fn target() -> Perquintill {
+		S::get()
+	}
+	fn variability() -> Multiplier {
+		V::get()
+	}
+}
 
-		let v_squared_2 = v.saturating_mul(v) / Multiplier::saturating_from_integer(2);
+impl<T, S, V, M> Convert<Multiplier, Multiplier> for TargetedEvmFeeAdjustment<T, S, V, M>
+where
+	T: Config,
+	S: Get<Perquintill>,
+	V: Get<Multiplier>,
+	M: Get<Multiplier>,
+{
+	fn convert(previous: Multiplier) -> Multiplier {
+		let weights = T::BlockWeights::get();
+		let normal_max_weight = weights
+			.get(DispatchClass::Normal)
+			.max_total
+			.unwrap_or(weights.max_block);
+		let evm_block_weight = EvmBlockWeight::<T>::get().min(normal_max_weight);
 
-		let first_term = v.saturating_mul(diff);
-		let second_term = v_squared_2.saturating_mul(diff_squared);
+		adjust_multiplier::<S, V, M>(previous, evm_block_weight, normal_max_weight)
+	}
+}
 
-		if positive {
-			let excess = first_term.saturating_add(second_term).saturating_mul(previous);
-			previous.saturating_add(excess).max(min_multiplier)
-		} else {
-			// Defensive-only: first_term > second_term. Safe subtraction.
-			let negative = first_term.saturating_sub(second_term).saturating_mul(previous);
-			previous.saturating_sub(negative).max(min_multiplier)
-		}
+/// Compute the next multiplier with the consumed `block_weight` among
+/// `max_weight`, see `TargetedFeeAdjustment` for details.
+fn adjust_multiplier<S, V, M>(previous: Multiplier, block_weight: Weight, max_weight: Weight) -> Multiplier
+where
+	S: Get<Perquintill>,
+	V: Get<Multiplier>,
+	M: Get<Multiplier>,
+{
+	// Defensive only. The multiplier in storage should always be at most positive.
+	// Nonetheless we recover here in case of errors, because any value below this
+	// would be stale and can never change.
+	let min_multiplier = M::get();
+	let previous = previous.max(min_multiplier);
+
+	let s = S::get();
+	let v = V::get();
+
+	let target_weight = (s * max_weight) as u128;
+	let block_weight = block_weight as u128;
+
+	// determines if the first_term is positive
+	let positive = block_weight >= target_weight;
+	let diff_abs = block_weight.max(target_weight) - block_weight.min(target_weight);
+
+	// defensive only, a test case assures that the maximum weight diff can fit in
+	// Multiplier without any saturation.
+	let diff = Multiplier::saturating_from_rational(diff_abs, max_weight.max(1));
+	let diff_squared = diff.saturating_mul(diff);
+
+	let v_squared_2 = v.saturating_mul(v) / Multiplier::saturating_from_integer(2);
+
+	let first_term = v.saturating_mul(diff);
+	let second_term = v_squared_2.saturating_mul(diff_squared);
+
+	if positive {
+		let excess = first_term.saturating_add(second_term).saturating_mul(previous);
+		previous.saturating_add(excess).max(min_multiplier)
+	} else {
+		// Defensive-only: first_term > second_term. Safe subtraction.
+		let negative = first_term.saturating_sub(second_term).saturating_mul(previous);
+		previous.saturating_sub(negative).max(min_multiplier)
 	}
 }
 
@@ -217,6 +297,14 @@ pub mod module {
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
+		/// The overarching call type.
+		type Call: Parameter
+			+ Dispatchable<Origin = Self::Origin, PostInfo = PostDispatchInfo>
+			+ GetDispatchInfo
+			+ GetCallMetadata
+			+ IsSubType<Call<Self>>
+			+ IsType<<Self as frame_system::Config>::Call>;
+
 		/// All non-native currency ids in Acala.
 		#[pallet::constant]
 		type AllNonNativeCurrencyIds: Get<Vec<CurrencyId>>;
@@ -231,16 +319,28 @@ pub mod module {
 		type StableCurrencyId: Get<CurrencyId>;
 
 		/// The currency type in which fees will be paid.
-		type Currency: Currency<Self::AccountId> + ReservableCurrency<Self::AccountId> + Send + Sync;
+		type Currency: Currency<Self::AccountId>
+			+ ReservableCurrency<Self::AccountId>
+			+ LockableCurrency<Self::AccountId, Moment = Self::BlockNumber>
+			+ Send
+			+ Sync;
 
 		/// Currency to transfer, reserve/unreserve, lock/unlock assets
 		type MultiCurrency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
 
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
 		/// Handler for the unbalanced reduction when taking transaction fees.
 		/// This is either one or two separate imbalances, the first is the
-		/// transaction fee paid, the second is the tip paid, if any.
+		/// transaction fee paid, the second is the treasury's share of the tip
+		/// paid, if any.
 		type OnTransactionPayment: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
+		/// The account receiving the block author share of the tips, usually
+		/// the author of the current block. The share goes to treasury if
+		/// it's `None`.
+		type BlockAuthor: Get<Option<Self::AccountId>>;
+
 		/// The fee to be paid for making a transaction; the per-byte portion.
 		#[pallet::constant]
 		type TransactionByteFee: Get<PalletBalanceOf<Self>>;
@@ -253,6 +353,14 @@ pub mod module {
 		/// block's weight.
 		type FeeMultiplierUpdate: MultiplierUpdate;
 
+		/// Update the EVM fee multiplier of the next block, based on the
+		/// weight consumed by EVM calls in the previous block.
+		type EvmFeeMultiplierUpdate: MultiplierUpdate;
+
+		/// Filter the EVM calls, including the ones wrapped in other calls such
+		/// as batch and proxy, which are priced by the EVM fee multiplier.
+		type EvmCallFilter: Filter<<Self as Config>::Call>;
+
 		/// DEX to exchange currencies.
 		type DEX: DEXManager<Self::AccountId, CurrencyId, Balance>;
 
@@ -260,37 +368,312 @@ pub mod module {
 		#[pallet::constant]
 		type MaxSlippageSwapWithDEX: Get<Ratio>;
 
+		/// The max length of DEX path to swap fee, include the supply and
+		/// target currencies.
+		#[pallet::constant]
+		type TradingPathLimit: Get<u32>;
+
+		/// The period to limit the count of sponsored calls for one
+		/// beneficiary.
+		#[pallet::constant]
+		type SponsorPeriod: Get<Self::BlockNumber>;
+
+		/// The max count of allowed calls of a sponsor.
+		#[pallet::constant]
+		type MaxSponsorAllowedCalls: Get<u32>;
+
+		/// The origin which may update the rebate tiers.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The account to pay the fee rebates.
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// The period of rebate era, the paid fees are rebated at the end of
+		/// each era.
+		#[pallet::constant]
+		type RebatePeriod: Get<Self::BlockNumber>;
+
+		/// The max count of rebate tiers.
+		#[pallet::constant]
+		type MaxRebateTiers: Get<u32>;
+
+		/// The max count of accounts to settle rebate in a block.
+		#[pallet::constant]
+		type MaxRebatesPerBlock: Get<u32>;
+
+		/// The period to report the burned fees by `TotalBurned` event.
+		#[pallet::constant]
+		type FeeBurnReportPeriod: Get<Self::BlockNumber>;
+
+		/// The module id of the alternative fee pools, the pool of each
+		/// currency is a sub account of it.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The max weight of refilling the alternative fee pools in a block.
+		#[pallet::constant]
+		type FeePoolRefillWeightLimit: Get<Weight>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
 
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Too many allowed calls of the sponsor
+		TooManyAllowedCalls,
+		/// The sponsor is not registered
+		SponsorNotFound,
+		/// The allowed call of the sponsor is not found in the runtime
+		InvalidCallName,
+		/// Too many rebate tiers
+		TooManyRebateTiers,
+		/// The thresholds and the rebate rates of tiers are not increasing
+		InvalidRebateTiers,
+		/// The staked amount exceeds the balance
+		InsufficientBalanceToStake,
+		/// No pending rebate to claim
+		NoPendingRebate,
+		/// The sum of the shares of tip exceeds 100%
+		InvalidTipSplit,
+		/// The currency can not be used to pay fees by the alternative fee pool
+		InvalidFeePoolCurrency,
+		/// The alternative fee pool of the currency is not enabled
+		FeePoolNotFound,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	pub enum Event<T: Config> {
+		/// The split of tips is updated, the rest of tips is burned.
+		/// \[author_share, treasury_share\]
+		TipSplitUpdated(Permill, Permill),
+		/// The fees distributed in the block. \[fee_to_treasury,
+		/// tip_to_author, tip_to_treasury, tip_burned\]
+		FeeDistributed(Balance, Balance, Balance, Balance),
+		/// The burn rate of fees is updated. \[burn_rate\]
+		FeeBurnRateUpdated(Permill),
+		/// The fees burned in the last report period and in total.
+		/// \[burned_in_period, total_burned\]
+		TotalBurned(Balance, Balance),
+		/// The sponsor is registered, and the budget is reserved.
+		/// \[sponsor, budget\]
+		SponsorRegistered(T::AccountId, Balance),
+		/// The sponsor is unregistered, and the remaining budget is
+		/// unreserved. \[sponsor, remaining_budget\]
+		SponsorUnregistered(T::AccountId, Balance),
+		/// The fee of the call is paid by the sponsor. \[sponsor,
+		/// beneficiary, fee\]
+		FeeSponsored(T::AccountId, T::AccountId, Balance),
+		/// The rebate tiers are updated. \[tiers\]
+		RebateTiersUpdated(Vec<(Balance, Permill)>),
+		/// The native currency staked for fee rebates is updated. \[who,
+		/// amount\]
+		RebateStakeUpdated(T::AccountId, Balance),
+		/// The fees paid in the rebate era are rebated. \[who, era, rebate\]
+		FeeRebated(T::AccountId, EraIndex, Balance),
+		/// The rebate failed to pay is kept to claim later. \[who, era,
+		/// rebate\]
+		RebatePending(T::AccountId, EraIndex, Balance),
+		/// The pending rebate is claimed. \[who, rebate\]
+		RebateClaimed(T::AccountId, Balance),
+		/// The refill threshold of the alternative fee pool is updated, the
+		/// pool is disabled if it's `None`. \[currency_id, threshold\]
+		FeePoolUpdated(CurrencyId, Option<Balance>),
+		/// The collected currency of the alternative fee pool is swapped to
+		/// refill native currency. \[currency_id, supply_amount,
+		/// native_amount\]
+		FeePoolRefilled(CurrencyId, Balance, Balance),
+	}
+
 	#[pallet::type_value]
 	pub fn DefaultFeeMultiplier() -> Multiplier {
 		Multiplier::saturating_from_integer(1)
 	}
 
+	#[pallet::type_value]
+	pub fn DefaultTipSplit() -> (Permill, Permill) {
+		(Permill::zero(), Permill::one())
+	}
+
 	#[pallet::storage]
 	#[pallet::getter(fn next_fee_multiplier)]
 	pub type NextFeeMultiplier<T: Config> = StorageValue<_, Multiplier, ValueQuery, DefaultFeeMultiplier>;
 
+	#[pallet::storage]
+	#[pallet::getter(fn next_evm_fee_multiplier)]
+	pub type NextEvmFeeMultiplier<T: Config> = StorageValue<_, Multiplier, ValueQuery, DefaultFeeMultiplier>;
+
+	/// The weight consumed by EVM calls in the current block.
+	///
+	/// EvmBlockWeight: Weight
+	#[pallet::storage]
+	pub type EvmBlockWeight<T: Config> = StorageValue<_, Weight, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn default_fee_currency_id)]
 	pub type DefaultFeeCurrencyId<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, CurrencyId, OptionQuery>;
 
+	/// The registered sponsors.
+	///
+	/// Sponsors: map AccountId => Option<SponsorInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn sponsors)]
+	pub type Sponsors<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, SponsorInfo, OptionQuery>;
+
+	/// The count of sponsored calls for the beneficiary in the period.
+	///
+	/// SponsoredCalls: double_map (sponsor, beneficiary) => (period, count)
+	#[pallet::storage]
+	#[pallet::getter(fn sponsored_calls)]
+	pub type SponsoredCalls<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, T::AccountId, (T::BlockNumber, u32), ValueQuery>;
+
+	/// The rebate tiers, the rebate rate of paid fees for the accounts whose
+	/// staked native currency reach the threshold, both increase by tier.
+	///
+	/// RebateTiers: Vec<(threshold, rebate_rate)>
+	#[pallet::storage]
+	#[pallet::getter(fn rebate_tiers)]
+	pub type RebateTiers<T: Config> = StorageValue<_, Vec<(Balance, Permill)>, ValueQuery>;
+
+	/// The current rebate era.
+	///
+	/// RebateEra: EraIndex
+	#[pallet::storage]
+	#[pallet::getter(fn rebate_era)]
+	pub type RebateEra<T: Config> = StorageValue<_, EraIndex, ValueQuery>;
+
+	/// The earliest rebate era which is not settled yet.
+	///
+	/// NextSettleEra: EraIndex
+	#[pallet::storage]
+	#[pallet::getter(fn next_settle_era)]
+	pub type NextSettleEra<T: Config> = StorageValue<_, EraIndex, ValueQuery>;
+
+	/// The fees paid by accounts in the rebate era, and the rebates of them
+	/// at the rebate rates when they are paid.
+	///
+	/// PaidFees: double_map EraIndex, AccountId => (paid_fee, rebate)
+	#[pallet::storage]
+	#[pallet::getter(fn paid_fees)]
+	pub type PaidFees<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, EraIndex, Twox64Concat, T::AccountId, (Balance, Balance), ValueQuery>;
+
+	/// The native currency staked by accounts for fee rebates, which is
+	/// locked.
+	///
+	/// RebateStakes: map AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn rebate_stakes)]
+	pub type RebateStakes<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// The rebates failed to pay from treasury, which can be claimed later.
+	///
+	/// PendingRebates: map AccountId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn pending_rebates)]
+	pub type PendingRebates<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, Balance, ValueQuery>;
+
+	/// The shares of tips to the block author and treasury, the rest of tips
+	/// is burned. All tips go to treasury by default.
+	///
+	/// TipSplit: (author_share, treasury_share)
+	#[pallet::storage]
+	#[pallet::getter(fn tip_split)]
+	pub type TipSplit<T: Config> = StorageValue<_, (Permill, Permill), ValueQuery, DefaultTipSplit>;
+
+	/// The fees distributed in the current block.
+	///
+	/// BlockFeeDistribution: (fee_to_treasury, tip_to_author, tip_to_treasury,
+	/// tip_burned)
+	#[pallet::storage]
+	#[pallet::getter(fn block_fee_distribution)]
+	pub type BlockFeeDistribution<T: Config> = StorageValue<_, (Balance, Balance, Balance, Balance), ValueQuery>;
+
+	/// The share of transaction fees to burn, the rest goes to
+	/// `OnTransactionPayment`. Tips are split by `TipSplit` instead.
+	///
+	/// FeeBurnRate: Permill
+	#[pallet::storage]
+	#[pallet::getter(fn fee_burn_rate)]
+	pub type FeeBurnRate<T: Config> = StorageValue<_, Permill, ValueQuery>;
+
+	/// The fees burned in the current report period.
+	///
+	/// PeriodBurnedFee: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn period_burned_fee)]
+	pub type PeriodBurnedFee<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The fees burned in total.
+	///
+	/// TotalBurnedFee: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_burned_fee)]
+	pub type TotalBurnedFee<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The refill thresholds of the alternative fee pools. The pool of a
+	/// currency pays fees in native currency for the accounts paying with the
+	/// currency, and is refilled once its native balance is below the
+	/// threshold.
+	///
+	/// FeePoolThresholds: map CurrencyId => Option<Balance>
+	#[pallet::storage]
+	#[pallet::getter(fn fee_pool_thresholds)]
+	pub type FeePoolThresholds<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
 		/// `on_initialize` to return the weight used in `on_finalize`.
-		fn on_initialize(_: T::BlockNumber) -> Weight {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let rebate_period = T::RebatePeriod::get();
+			if !rebate_period.is_zero() && (now % rebate_period).is_zero() {
+				RebateEra::<T>::mutate(|era| *era = era.saturating_add(1));
+			}
+			let settled = Self::settle_rebates(T::MaxRebatesPerBlock::get());
+			let refill_weight = Self::refill_fee_pools(T::FeePoolRefillWeightLimit::get());
+
 			<T as Config>::WeightInfo::on_finalize()
+				.saturating_add(<T as Config>::WeightInfo::settle_rebates(settled))
+				.saturating_add(refill_weight)
 		}
 
-		fn on_finalize(_: T::BlockNumber) {
+		fn on_finalize(now: T::BlockNumber) {
 			NextFeeMultiplier::<T>::mutate(|fm| {
 				*fm = T::FeeMultiplierUpdate::convert(*fm);
 			});
+			NextEvmFeeMultiplier::<T>::mutate(|fm| {
+				*fm = T::EvmFeeMultiplierUpdate::convert(*fm);
+			});
+			EvmBlockWeight::<T>::kill();
+
+			let (fee_to_treasury, tip_to_author, tip_to_treasury, tip_burned) = BlockFeeDistribution::<T>::take();
+			if !fee_to_treasury.is_zero()
+				|| !tip_to_author.is_zero()
+				|| !tip_to_treasury.is_zero()
+				|| !tip_burned.is_zero()
+			{
+				Self::deposit_event(Event::FeeDistributed(
+					fee_to_treasury,
+					tip_to_author,
+					tip_to_treasury,
+					tip_burned,
+				));
+			}
+
+			let report_period = T::FeeBurnReportPeriod::get();
+			if !report_period.is_zero() && (now % report_period).is_zero() {
+				let burned_in_period = PeriodBurnedFee::<T>::take();
+				if !burned_in_period.is_zero() {
+					Self::deposit_event(Event::TotalBurned(burned_in_period, Self::total_burned_fee()));
+				}
+			}
 		}
 
 		#[cfg(feature = "std")]
@@ -353,6 +736,478 @@ pub mod module {
 			}
 			Ok(().into())
 		}
+
+		/// Dispatch the call and pay the fee of it with `currency_id` in
+		/// priority, which override the default fee token for this call.
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(
+				<T as Config>::WeightInfo::with_fee_currency().saturating_add(dispatch_info.weight),
+				dispatch_info.class,
+			)
+		})]
+		pub fn with_fee_currency(
+			origin: OriginFor<T>,
+			_currency_id: CurrencyId,
+			call: Box<<T as Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin.clone())?;
+			call.dispatch(origin)
+		}
+
+		/// Register as sponsor to pay the fee of the `allowed_calls` wrapped
+		/// by `with_sponsor` for other accounts, which will override the
+		/// previous registration. The `budget` is reserved from the sponsor,
+		/// the tips are paid by the senders.
+		#[pallet::weight(<T as Config>::WeightInfo::register_sponsor())]
+		#[transactional]
+		pub fn register_sponsor(
+			origin: OriginFor<T>,
+			#[pallet::compact] budget: Balance,
+			max_calls_per_period: u32,
+			allowed_calls: Vec<CallName>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				allowed_calls.len() <= T::MaxSponsorAllowedCalls::get() as usize,
+				Error::<T>::TooManyAllowedCalls
+			);
+			ensure!(
+				allowed_calls
+					.iter()
+					.all(|(pallet_name, call_name)| Self::is_call_name(pallet_name, call_name)),
+				Error::<T>::InvalidCallName
+			);
+
+			if let Some(info) = Self::sponsors(&who) {
+				<T as Config>::Currency::unreserve(&who, info.budget.unique_saturated_into());
+			}
+			<T as Config>::Currency::reserve(&who, budget.unique_saturated_into())?;
+
+			Sponsors::<T>::insert(
+				&who,
+				SponsorInfo {
+					budget,
+					max_calls_per_period,
+					allowed_calls,
+				},
+			);
+			Self::deposit_event(Event::SponsorRegistered(who, budget));
+			Ok(().into())
+		}
+
+		/// Update the rebate tiers.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `tiers`: the thresholds of staked native currency and the rebate
+		///   rates, both strictly increase by tier.
+		#[pallet::weight(<T as Config>::WeightInfo::set_rebate_tiers())]
+		pub fn set_rebate_tiers(origin: OriginFor<T>, tiers: Vec<(Balance, Permill)>) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				tiers.len() <= T::MaxRebateTiers::get() as usize,
+				Error::<T>::TooManyRebateTiers
+			);
+			ensure!(
+				tiers.iter().all(|(_, rate)| !rate.is_zero())
+					&& tiers
+						.windows(2)
+						.all(|pair| pair[0].0 < pair[1].0 && pair[0].1 < pair[1].1),
+				Error::<T>::InvalidRebateTiers
+			);
+
+			RebateTiers::<T>::put(tiers.clone());
+			Self::deposit_event(Event::RebateTiersUpdated(tiers));
+			Ok(().into())
+		}
+
+		/// Stake native currency for fee rebates, the staked amount is locked
+		/// and decides the rebate tier.
+		///
+		/// - `amount`: the new staked amount, zero to unstake all.
+		#[pallet::weight(<T as Config>::WeightInfo::set_rebate_stake())]
+		#[transactional]
+		pub fn set_rebate_stake(
+			origin: OriginFor<T>,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			if amount.is_zero() {
+				<T as Config>::Currency::remove_lock(REBATE_STAKE_LOCK_ID, &who);
+				RebateStakes::<T>::remove(&who);
+			} else {
+				let amount_to_lock: PalletBalanceOf<T> = amount.unique_saturated_into();
+				ensure!(
+					<T as Config>::Currency::free_balance(&who) >= amount_to_lock,
+					Error::<T>::InsufficientBalanceToStake
+				);
+				<T as Config>::Currency::set_lock(REBATE_STAKE_LOCK_ID, &who, amount_to_lock, WithdrawReasons::all());
+				RebateStakes::<T>::insert(&who, amount);
+			}
+
+			Self::deposit_event(Event::RebateStakeUpdated(who, amount));
+			Ok(().into())
+		}
+
+		/// Claim the rebates which failed to pay from treasury.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_rebate())]
+		#[transactional]
+		pub fn claim_rebate(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let rebate = PendingRebates::<T>::take(&who);
+			ensure!(!rebate.is_zero(), Error::<T>::NoPendingRebate);
+
+			<T as Config>::Currency::transfer(
+				&T::TreasuryAccount::get(),
+				&who,
+				rebate.unique_saturated_into(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			Self::deposit_event(Event::RebateClaimed(who, rebate));
+			Ok(().into())
+		}
+
+		/// Update the shares of tips to the block author and treasury, the
+		/// rest of tips is burned.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `author_share`: the share of tips to the block author, which goes
+		///   to treasury if the block author is unknown.
+		/// - `treasury_share`: the share of tips to treasury.
+		#[pallet::weight((<T as Config>::WeightInfo::set_tip_split(), DispatchClass::Operational))]
+		pub fn set_tip_split(
+			origin: OriginFor<T>,
+			author_share: Permill,
+			treasury_share: Permill,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				author_share.deconstruct().saturating_add(treasury_share.deconstruct()) <= Permill::one().deconstruct(),
+				Error::<T>::InvalidTipSplit
+			);
+
+			TipSplit::<T>::put((author_share, treasury_share));
+			Self::deposit_event(Event::TipSplitUpdated(author_share, treasury_share));
+			Ok(().into())
+		}
+
+		/// Update the share of transaction fees to burn.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `burn_rate`: the share of fees to burn, the rest goes to
+		///   `OnTransactionPayment`.
+		#[pallet::weight((<T as Config>::WeightInfo::set_fee_burn_rate(), DispatchClass::Operational))]
+		pub fn set_fee_burn_rate(origin: OriginFor<T>, burn_rate: Permill) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			FeeBurnRate::<T>::put(burn_rate);
+			Self::deposit_event(Event::FeeBurnRateUpdated(burn_rate));
+			Ok(().into())
+		}
+
+		/// Unregister the sponsor and unreserve the remaining budget.
+		///
+		/// The counts of sponsored calls are kept, they are reset once the
+		/// period passes.
+		#[pallet::weight(<T as Config>::WeightInfo::unregister_sponsor())]
+		pub fn unregister_sponsor(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let info = Sponsors::<T>::take(&who).ok_or(Error::<T>::SponsorNotFound)?;
+
+			<T as Config>::Currency::unreserve(&who, info.budget.unique_saturated_into());
+			Self::deposit_event(Event::SponsorUnregistered(who, info.budget));
+			Ok(().into())
+		}
+
+		/// Dispatch the call and the fee of it will be paid by `sponsor` from
+		/// the reserved budget, the refund of the unused weight goes to the
+		/// free balance of `sponsor`.
+		#[pallet::weight({
+			let dispatch_info = call.get_dispatch_info();
+			(
+				<T as Config>::WeightInfo::with_sponsor().saturating_add(dispatch_info.weight),
+				dispatch_info.class,
+			)
+		})]
+		pub fn with_sponsor(
+			origin: OriginFor<T>,
+			_sponsor: T::AccountId,
+			call: Box<<T as Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin.clone())?;
+			call.dispatch(origin)
+		}
+
+		/// Enable the alternative fee pool of `currency_id` with the refill
+		/// threshold, or disable it. The pool is funded by transferring native
+		/// currency to `fee_pool_account_id(currency_id)`, the funds are kept
+		/// in the pool once it's disabled.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: the non-native currency to pay fees with.
+		/// - `threshold`: the native balance below which the pool is
+		///   refilled by swapping the collected currency, `None` to disable
+		///   the pool.
+		#[pallet::weight((<T as Config>::WeightInfo::set_fee_pool(), DispatchClass::Operational))]
+		pub fn set_fee_pool(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			threshold: Option<Balance>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::AllNonNativeCurrencyIds::get().contains(&currency_id),
+				Error::<T>::InvalidFeePoolCurrency
+			);
+
+			if let Some(threshold) = threshold {
+				FeePoolThresholds::<T>::insert(currency_id, threshold);
+			} else {
+				FeePoolThresholds::<T>::remove(currency_id);
+			}
+			Self::deposit_event(Event::FeePoolUpdated(currency_id, threshold));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Split the tip to the block author, treasury and burn by `TipSplit`,
+	/// and pass the fee and the treasury's share of the tip to
+	/// `OnTransactionPayment`.
+	fn distribute_fee(fee: NegativeImbalanceOf<T>, tip: NegativeImbalanceOf<T>) {
+		let fee = Self::burn_fee(fee);
+		let (author_share, treasury_share) = Self::tip_split();
+		let tip_amount = tip.peek();
+		let (author_tip, rest_tip) = tip.split(author_share.mul_floor(tip_amount));
+		let (mut treasury_tip, burned_tip) = rest_tip.split(treasury_share.mul_floor(tip_amount));
+
+		let mut tip_to_author: Balance = Zero::zero();
+		match T::BlockAuthor::get() {
+			Some(author) if !author_tip.peek().is_zero() => {
+				tip_to_author = author_tip.peek().unique_saturated_into();
+				<T as Config>::Currency::resolve_creating(&author, author_tip);
+			}
+			// the share of unknown block author goes to treasury
+			_ => treasury_tip.subsume(author_tip),
+		}
+
+		let fee_to_treasury: Balance = fee.peek().unique_saturated_into();
+		let tip_to_treasury: Balance = treasury_tip.peek().unique_saturated_into();
+		let tip_burned: Balance = burned_tip.peek().unique_saturated_into();
+		BlockFeeDistribution::<T>::mutate(|(fee_total, author_total, treasury_total, burned_total)| {
+			*fee_total = fee_total.saturating_add(fee_to_treasury);
+			*author_total = author_total.saturating_add(tip_to_author);
+			*treasury_total = treasury_total.saturating_add(tip_to_treasury);
+			*burned_total = burned_total.saturating_add(tip_burned);
+		});
+
+		// the burned tip reduces the total issuance when dropped
+		drop(burned_tip);
+		<T as Config>::OnTransactionPayment::on_unbalanceds(Some(fee).into_iter().chain(Some(treasury_tip)));
+	}
+
+	/// The weight of `distribute_fee`: reads `FeeBurnRate` and `TipSplit`,
+	/// updates the burned fee totals, `BlockFeeDistribution` and the block
+	/// author account.
+	fn fee_distribution_weight() -> Weight {
+		T::DbWeight::get().reads_writes(6, 4)
+	}
+
+	/// Burn the `FeeBurnRate` share of `fee`, returns the rest.
+	fn burn_fee(fee: NegativeImbalanceOf<T>) -> NegativeImbalanceOf<T> {
+		let fee_amount = fee.peek();
+		let (burned_fee, rest_fee) = fee.split(Self::fee_burn_rate().mul_floor(fee_amount));
+
+		let burned: Balance = burned_fee.peek().unique_saturated_into();
+		if !burned.is_zero() {
+			PeriodBurnedFee::<T>::mutate(|total| *total = total.saturating_add(burned));
+			TotalBurnedFee::<T>::mutate(|total| *total = total.saturating_add(burned));
+		}
+
+		// the burned fee reduces the total issuance when dropped
+		drop(burned_fee);
+		rest_fee
+	}
+
+	/// Record the fee paid by `who` in current rebate era and its rebate at
+	/// the current rebate rate of `who`, only if the rebate program is
+	/// enabled.
+	fn record_paid_fee(who: &T::AccountId, fee: Balance) {
+		let tiers = Self::rebate_tiers();
+		if !fee.is_zero() && !tiers.is_empty() {
+			let rebate = Self::rebate_rate_of(&tiers, who).map_or(Zero::zero(), |rate| rate.mul_floor(fee));
+			PaidFees::<T>::mutate(Self::rebate_era(), who, |(paid_fee, paid_rebate)| {
+				*paid_fee = paid_fee.saturating_add(fee);
+				*paid_rebate = paid_rebate.saturating_add(rebate);
+			});
+		}
+	}
+
+	/// The rebate rate of `who` decided by the native currency staked for
+	/// rebates.
+	fn rebate_rate_of(tiers: &[(Balance, Permill)], who: &T::AccountId) -> Option<Permill> {
+		let staked = Self::rebate_stakes(who);
+		tiers
+			.iter()
+			.filter(|(threshold, _)| staked >= *threshold)
+			.map(|(_, rate)| *rate)
+			.max()
+	}
+
+	/// Settle the rebates of the ended eras, at most `max` accounts. Returns
+	/// the count of settled accounts.
+	fn settle_rebates(max: u32) -> u32 {
+		let current_era = Self::rebate_era();
+		let tiers = Self::rebate_tiers();
+		let treasury_account = T::TreasuryAccount::get();
+		let mut settled: u32 = 0;
+
+		while settled < max {
+			let era = Self::next_settle_era();
+			if era >= current_era {
+				break;
+			}
+
+			let remain = max - settled;
+			let mut count: u32 = 0;
+			for (who, (paid_fee, rebate)) in PaidFees::<T>::drain_prefix(era).take(remain as usize) {
+				count += 1;
+				// the rebate is capped by the rate at settlement, so the stake must be kept
+				// through the era
+				let rebate = Self::rebate_rate_of(&tiers, &who)
+					.map_or(Zero::zero(), |rate| rate.mul_floor(paid_fee))
+					.min(rebate);
+				if rebate.is_zero() {
+					continue;
+				}
+
+				if <T as Config>::Currency::transfer(
+					&treasury_account,
+					&who,
+					rebate.unique_saturated_into(),
+					ExistenceRequirement::KeepAlive,
+				)
+				.is_ok()
+				{
+					Self::deposit_event(Event::FeeRebated(who, era, rebate));
+				} else {
+					PendingRebates::<T>::mutate(&who, |pending| *pending = pending.saturating_add(rebate));
+					Self::deposit_event(Event::RebatePending(who, era, rebate));
+				}
+			}
+			settled += count;
+
+			if count < remain {
+				// all the accounts of this era are settled
+				NextSettleEra::<T>::put(era + 1);
+			}
+		}
+
+		settled
+	}
+
+	/// The account of the alternative fee pool of `currency_id`.
+	pub fn fee_pool_account_id(currency_id: CurrencyId) -> T::AccountId {
+		T::PalletId::get().into_sub_account(currency_id)
+	}
+
+	/// Refill the alternative fee pools whose native balance is below the
+	/// threshold, by swapping all the collected currency to native currency
+	/// with DEX. Stops before the weight exceeds `weight_limit`, returns the
+	/// weight used.
+	fn refill_fee_pools(weight_limit: Weight) -> Weight {
+		let check_weight = T::DbWeight::get().reads(3);
+		let swap_weight = <T as Config>::WeightInfo::charge_fee_by_swap(Self::max_fee_swap_paths());
+		let price_impact_limit = Some(T::MaxSlippageSwapWithDEX::get());
+		let mut used_weight: Weight = Zero::zero();
+
+		for (currency_id, threshold) in FeePoolThresholds::<T>::iter() {
+			if used_weight.saturating_add(check_weight).saturating_add(swap_weight) > weight_limit {
+				break;
+			}
+			used_weight = used_weight.saturating_add(check_weight);
+
+			let pool_account = Self::fee_pool_account_id(currency_id);
+			let native_balance: Balance = <T as Config>::Currency::free_balance(&pool_account).unique_saturated_into();
+			let collected = <T as Config>::MultiCurrency::free_balance(currency_id, &pool_account);
+			if native_balance >= threshold || collected.is_zero() {
+				continue;
+			}
+
+			used_weight = used_weight.saturating_add(swap_weight);
+			if let Some((trading_path, _)) = Self::best_refill_swap_path(currency_id, collected) {
+				if let Ok(native_amount) = T::DEX::swap_with_exact_supply(
+					&pool_account,
+					&trading_path,
+					collected,
+					Zero::zero(),
+					price_impact_limit,
+				) {
+					Self::deposit_event(Event::FeePoolRefilled(currency_id, collected, native_amount));
+				}
+			}
+		}
+
+		used_weight
+	}
+
+	/// The max number of the DEX paths priced to find the fee swap paths of
+	/// all the currencies tried to charge fee.
+	pub fn max_fee_swap_paths() -> u32 {
+		let currency_count = T::AllNonNativeCurrencyIds::get().len() as u32;
+		let path_limit = T::TradingPathLimit::get();
+
+		// the paths of a currency with `k` intermediate currencies
+		let mut paths_per_currency: u32 = 0;
+		let mut permutations: u32 = 1;
+		for k in 0..path_limit.saturating_sub(1) {
+			paths_per_currency = paths_per_currency.saturating_add(permutations);
+			permutations = permutations.saturating_mul(currency_count.saturating_sub(k));
+		}
+
+		// the fee currency of `who` may not be in `AllNonNativeCurrencyIds`
+		paths_per_currency.saturating_mul(currency_count.saturating_add(1))
+	}
+
+	/// The DEX path to swap `supply_amount` of `currency_id` to the most
+	/// native currency, and the native amount.
+	fn best_refill_swap_path(currency_id: CurrencyId, supply_amount: Balance) -> Option<(Vec<CurrencyId>, Balance)> {
+		let price_impact_limit = Some(T::MaxSlippageSwapWithDEX::get());
+		let mut paths: Vec<Vec<CurrencyId>> = vec![];
+		Self::collect_fee_swap_paths(&mut vec![currency_id], &mut paths);
+
+		paths
+			.into_iter()
+			.filter_map(|path| {
+				T::DEX::get_swap_target_amount(&path, supply_amount, price_impact_limit)
+					.map(|target_amount| (path, target_amount))
+			})
+			.max_by_key(|(_, target_amount)| *target_amount)
+	}
+
+	/// Collect all the paths start with `path` and end with native currency,
+	/// the intermediate currencies are non-native currencies.
+	fn collect_fee_swap_paths(path: &mut Vec<CurrencyId>, paths: &mut Vec<Vec<CurrencyId>>) {
+		let native_currency_id = T::NativeCurrencyId::get();
+		let path_limit = T::TradingPathLimit::get() as usize;
+
+		if path.len() < path_limit {
+			paths.push([&path[..], &[native_currency_id]].concat());
+		}
+		if path.len() + 1 < path_limit {
+			for currency_id in T::AllNonNativeCurrencyIds::get() {
+				if !path.contains(&currency_id) {
+					path.push(currency_id);
+					Self::collect_fee_swap_paths(path, paths);
+					path.pop();
+				}
+			}
+		}
 	}
 }
 
@@ -369,10 +1224,7 @@ where
 	///
 	/// All dispatchables must be annotated with weight and will have some
 	/// fee info. This function always returns.
-	pub fn query_info<Extrinsic: GetDispatchInfo>(
-		unchecked_extrinsic: Extrinsic,
-		len: u32,
-	) -> RuntimeDispatchInfo<PalletBalanceOf<T>>
+	pub fn query_info(call: &<T as frame_system::Config>::Call, len: u32) -> RuntimeDispatchInfo<PalletBalanceOf<T>>
 	where
 		T: Send + Sync,
 		PalletBalanceOf<T>: Send + Sync,
@@ -384,9 +1236,9 @@ where
 		// execute the tx's per-dispatch and record the balance of the sender before and
 		// after the pipeline.. but this is way too much hassle for a very very little
 		// potential gain in the future.
-		let dispatch_info = <Extrinsic as GetDispatchInfo>::get_dispatch_info(&unchecked_extrinsic);
+		let dispatch_info = <T as Config>::Call::from_ref(call).get_dispatch_info();
 
-		let partial_fee = Self::compute_fee(len, &dispatch_info, 0u32.into());
+		let partial_fee = Self::compute_call_fee_details(len, call, &dispatch_info, 0u32.into()).final_fee();
 		let DispatchInfo { weight, class, .. } = dispatch_info;
 
 		RuntimeDispatchInfo {
@@ -396,16 +1248,50 @@ where
 		}
 	}
 
+	/// Query the fee of a given `call` paid in `currency_id`. For non-native
+	/// currency, it's the amount to supply to DEX to swap the fee in native
+	/// currency through the best path, which has included the exchange fee
+	/// and the price impact of the swap. Returns `None` if there's no
+	/// available path.
+	pub fn query_fee_in_currency(
+		call: &<T as frame_system::Config>::Call,
+		len: u32,
+		currency_id: CurrencyId,
+	) -> Option<Balance> {
+		let dispatch_info = <T as Config>::Call::from_ref(call).get_dispatch_info();
+		let fee: Balance = Self::compute_call_fee_details(len, call, &dispatch_info, 0u32.into())
+			.final_fee()
+			.unique_saturated_into();
+
+		if currency_id == T::NativeCurrencyId::get() || fee.is_zero() {
+			Some(fee)
+		} else {
+			Self::best_fee_swap_path(currency_id, fee).map(|(_, supply_amount)| supply_amount)
+		}
+	}
+
 	/// Query the detailed fee of a given `call`.
-	pub fn query_fee_details<Extrinsic: GetDispatchInfo>(
-		unchecked_extrinsic: Extrinsic,
+	pub fn query_fee_details(call: &<T as frame_system::Config>::Call, len: u32) -> FeeDetails<PalletBalanceOf<T>> {
+		let dispatch_info = <T as Config>::Call::from_ref(call).get_dispatch_info();
+		Self::compute_call_fee_details(len, call, &dispatch_info, 0u32.into())
+	}
+
+	/// Compute the fee details of `call`, which is priced by the EVM fee
+	/// multiplier if it's an EVM call.
+	fn compute_call_fee_details(
 		len: u32,
-	) -> FeeDetails<PalletBalanceOf<T>>
-	where
-		T::Call: Dispatchable<Info = DispatchInfo>,
-	{
-		let dispatch_info = <Extrinsic as GetDispatchInfo>::get_dispatch_info(&unchecked_extrinsic);
-		Self::compute_fee_details(len, &dispatch_info, 0u32.into())
+		call: &<T as frame_system::Config>::Call,
+		info: &DispatchInfo,
+		tip: PalletBalanceOf<T>,
+	) -> FeeDetails<PalletBalanceOf<T>> {
+		Self::compute_fee_raw(
+			len,
+			info.weight,
+			tip,
+			info.pays_fee,
+			info.class,
+			Self::next_fee_multiplier_of(Self::is_evm_call(call)),
+		)
 	}
 
 	/// Compute the fee details for a particular transaction.
@@ -417,7 +1303,14 @@ where
 	where
 		T::Call: Dispatchable<Info = DispatchInfo>,
 	{
-		Self::compute_fee_raw(len, info.weight, tip, info.pays_fee, info.class)
+		Self::compute_fee_raw(
+			len,
+			info.weight,
+			tip,
+			info.pays_fee,
+			info.class,
+			Self::next_fee_multiplier(),
+		)
 	}
 
 	/// Compute the final fee value for a particular transaction.
@@ -472,6 +1365,7 @@ where
 			tip,
 			post_info.pays_fee(info),
 			info.class,
+			Self::next_fee_multiplier(),
 		)
 	}
 
@@ -497,6 +1391,7 @@ where
 		tip: PalletBalanceOf<T>,
 		pays_fee: Pays,
 		class: DispatchClass,
+		multiplier: Multiplier,
 	) -> FeeDetails<PalletBalanceOf<T>> {
 		if pays_fee == Pays::Yes {
 			let len = <PalletBalanceOf<T>>::from(len);
@@ -507,7 +1402,6 @@ where
 
 			// the adjustable part of the fee.
 			let unadjusted_weight_fee = Self::weight_to_fee(weight);
-			let multiplier = Self::next_fee_multiplier();
 			// final adjusted weight fee.
 			let adjusted_weight_fee = multiplier.saturating_mul_int(unadjusted_weight_fee);
 
@@ -535,13 +1429,46 @@ where
 		T::WeightToFee::calc(&capped_weight)
 	}
 
+	/// The fee multiplier of the next block, the EVM fee multiplier for EVM
+	/// calls, otherwise the general one.
+	pub fn next_fee_multiplier_of(is_evm_call: bool) -> Multiplier {
+		if is_evm_call {
+			Self::next_evm_fee_multiplier()
+		} else {
+			Self::next_fee_multiplier()
+		}
+	}
+
+	/// The weight fee of `weight` for EVM calls in the next block, adjusted
+	/// by the EVM fee multiplier.
+	pub fn next_evm_weight_fee(weight: Weight) -> PalletBalanceOf<T> {
+		Self::next_evm_fee_multiplier().saturating_mul_int(Self::weight_to_fee(weight))
+	}
+
+	/// Whether `call` is an EVM call, including the ones wrapped in other
+	/// calls, which is up to `EvmCallFilter`.
+	pub fn is_evm_call(call: &<T as frame_system::Config>::Call) -> bool {
+		T::EvmCallFilter::filter(<T as Config>::Call::from_ref(call))
+	}
+
 	pub fn ensure_can_charge_fee(who: &T::AccountId, fee: PalletBalanceOf<T>, reason: WithdrawReasons) {
+		Self::ensure_can_charge_fee_with_currency(who, None, fee, reason)
+	}
+
+	/// Ensure `who` has enough native currency to pay `fee`, swap other
+	/// currency to native currency by DEX if not. `fee_currency_id` is
+	/// used in priority, otherwise the default fee token of `who`.
+	pub fn ensure_can_charge_fee_with_currency(
+		who: &T::AccountId,
+		fee_currency_id: Option<CurrencyId>,
+		fee: PalletBalanceOf<T>,
+		reason: WithdrawReasons,
+	) {
 		let native_currency_id = T::NativeCurrencyId::get();
-		let stable_currency_id = T::StableCurrencyId::get();
 		let other_currency_ids = T::AllNonNativeCurrencyIds::get();
 		let mut charge_fee_order: Vec<CurrencyId> =
-			if let Some(default_fee_currency_id) = DefaultFeeCurrencyId::<T>::get(who) {
-				vec![vec![default_fee_currency_id, native_currency_id], other_currency_ids].concat()
+			if let Some(fee_currency_id) = fee_currency_id.or_else(|| DefaultFeeCurrencyId::<T>::get(who)) {
+				vec![vec![fee_currency_id, native_currency_id], other_currency_ids].concat()
 			} else {
 				vec![vec![native_currency_id], other_currency_ids].concat()
 			};
@@ -552,40 +1479,160 @@ where
 		// iterator charge fee order to get enough fee
 		for currency_id in charge_fee_order {
 			if currency_id == native_currency_id {
-				// check native balance if is enough
-				let native_is_enough =
-					<T as Config>::Currency::free_balance(who)
-						.checked_sub(&fee)
-						.map_or(false, |new_free_balance| {
-							<T as Config>::Currency::ensure_can_withdraw(who, fee, reason, new_free_balance).is_ok()
-						});
-				if native_is_enough {
+				if Self::native_is_enough(who, fee, reason) {
 					// native balance is enough, break iteration
 					break;
 				}
 			} else {
-				// try to use non-native currency to swap native currency by exchange with DEX
-				let trading_path = if currency_id == stable_currency_id {
-					vec![stable_currency_id, native_currency_id]
-				} else {
-					vec![currency_id, stable_currency_id, native_currency_id]
-				};
-
-				if T::DEX::swap_with_exact_target(
-					who,
-					&trading_path,
-					fee.unique_saturated_into(),
-					<T as Config>::MultiCurrency::free_balance(currency_id, who),
-					price_impact_limit,
-				)
-				.is_ok()
+				// try to use non-native currency to pay native currency by the alternative fee
+				// pool at the DEX price, or swap native currency by exchange with DEX
+				if let Some((trading_path, supply_amount)) =
+					Self::best_fee_swap_path(currency_id, fee.unique_saturated_into())
 				{
-					// successfully swap, break iteration
-					break;
+					if Self::charge_fee_by_pool(who, currency_id, supply_amount, fee).is_ok()
+						|| T::DEX::swap_with_exact_target(
+							who,
+							&trading_path,
+							fee.unique_saturated_into(),
+							<T as Config>::MultiCurrency::free_balance(currency_id, who),
+							price_impact_limit,
+						)
+						.is_ok()
+					{
+						// successfully swap, break iteration
+						break;
+					}
 				}
 			}
 		}
 	}
+
+	/// Pay `fee` in native currency from the alternative fee pool of
+	/// `currency_id`, which collects `amount` of `currency_id` from `who`
+	/// for it.
+	#[transactional]
+	fn charge_fee_by_pool(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		fee: PalletBalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(
+			FeePoolThresholds::<T>::contains_key(currency_id),
+			Error::<T>::FeePoolNotFound
+		);
+		let pool_account = Self::fee_pool_account_id(currency_id);
+		<T as Config>::MultiCurrency::transfer(currency_id, who, &pool_account, amount)?;
+		<T as Config>::Currency::transfer(&pool_account, who, fee, ExistenceRequirement::KeepAlive)
+	}
+
+	/// Whether the free native balance of `who` is enough to pay `fee`.
+	fn native_is_enough(who: &T::AccountId, fee: PalletBalanceOf<T>, reason: WithdrawReasons) -> bool {
+		<T as Config>::Currency::free_balance(who)
+			.checked_sub(&fee)
+			.map_or(false, |new_free_balance| {
+				<T as Config>::Currency::ensure_can_withdraw(who, fee, reason, new_free_balance).is_ok()
+			})
+	}
+
+	/// The weight of swapping other currencies to native currency to charge
+	/// `fee` of `who`, zero if the native currency is enough.
+	fn fee_swap_weight(who: &T::AccountId, fee: PalletBalanceOf<T>, reason: WithdrawReasons) -> Weight {
+		if Self::native_is_enough(who, fee, reason) {
+			Zero::zero()
+		} else {
+			<T as Config>::WeightInfo::charge_fee_by_swap(Self::max_fee_swap_paths())
+		}
+	}
+
+	/// Whether `call_name` of the pallet `pallet_name` is in the runtime.
+	fn is_call_name(pallet_name: &[u8], call_name: &[u8]) -> bool {
+		<T as Config>::Call::get_module_names()
+			.iter()
+			.find(|name| name.as_bytes() == pallet_name)
+			.map_or(false, |name| {
+				<T as Config>::Call::get_call_names(name)
+					.iter()
+					.any(|name| name.as_bytes() == call_name)
+			})
+	}
+
+	/// Check whether `sponsor` can pay `fee` of `call` for `beneficiary`,
+	/// and deduct the budget, count the sponsored call and withdraw `fee`
+	/// from the reserved budget if so.
+	fn charge_sponsor(
+		sponsor: &T::AccountId,
+		beneficiary: &T::AccountId,
+		call: &<T as Config>::Call,
+		fee: PalletBalanceOf<T>,
+	) -> Result<NegativeImbalanceOf<T>, TransactionValidityError> {
+		Sponsors::<T>::try_mutate(sponsor, |maybe_info| -> Result<(), TransactionValidityError> {
+			let info = maybe_info.as_mut().ok_or(InvalidTransaction::Call)?;
+
+			if !info.allowed_calls.is_empty() {
+				let metadata = call.get_call_metadata();
+				ensure!(
+					info.allowed_calls.iter().any(|(pallet_name, call_name)| {
+						pallet_name.as_slice() == metadata.pallet_name.as_bytes()
+							&& call_name.as_slice() == metadata.function_name.as_bytes()
+					}),
+					InvalidTransaction::Call
+				);
+			}
+
+			info.budget = info
+				.budget
+				.checked_sub(fee.unique_saturated_into())
+				.ok_or(InvalidTransaction::Payment)?;
+			ensure!(
+				<T as Config>::Currency::reserved_balance(sponsor) >= fee,
+				InvalidTransaction::Payment
+			);
+
+			let period = <frame_system::Pallet<T>>::block_number() / T::SponsorPeriod::get().max(1u32.into());
+			SponsoredCalls::<T>::try_mutate(
+				sponsor,
+				beneficiary,
+				|(last_period, count)| -> Result<(), TransactionValidityError> {
+					if *last_period != period {
+						*last_period = period;
+						*count = 0;
+					}
+					ensure!(
+						*count < info.max_calls_per_period,
+						InvalidTransaction::ExhaustsResources
+					);
+					*count += 1;
+					Ok(())
+				},
+			)
+		})?;
+
+		let (imbalance, _) = <T as Config>::Currency::slash_reserved(sponsor, fee);
+		Self::deposit_event(Event::FeeSponsored(
+			sponsor.clone(),
+			beneficiary.clone(),
+			fee.unique_saturated_into(),
+		));
+		Ok(imbalance)
+	}
+
+	/// Find the DEX path within `TradingPathLimit` to swap `currency_id` to
+	/// `target_amount` native currency, which requires the least supply
+	/// amount. Returns the path and the supply amount.
+	pub fn best_fee_swap_path(currency_id: CurrencyId, target_amount: Balance) -> Option<(Vec<CurrencyId>, Balance)> {
+		let price_impact_limit = Some(T::MaxSlippageSwapWithDEX::get());
+		let mut paths: Vec<Vec<CurrencyId>> = vec![];
+		Self::collect_fee_swap_paths(&mut vec![currency_id], &mut paths);
+
+		paths
+			.into_iter()
+			.filter_map(|path| {
+				T::DEX::get_swap_supply_amount(&path, target_amount, price_impact_limit)
+					.map(|supply_amount| (path, supply_amount))
+			})
+			.min_by_key(|(_, supply_amount)| *supply_amount)
+	}
 }
 
 impl<T> Convert<Weight, PalletBalanceOf<T>> for Pallet<T>
@@ -633,12 +1680,16 @@ where
 	fn withdraw_fee(
 		&self,
 		who: &T::AccountId,
-		_call: &<T as frame_system::Config>::Call,
+		call: &<T as frame_system::Config>::Call,
 		info: &DispatchInfoOf<<T as frame_system::Config>::Call>,
 		len: usize,
-	) -> Result<(PalletBalanceOf<T>, Option<NegativeImbalanceOf<T>>), TransactionValidityError> {
+	) -> Result<(T::AccountId, PalletBalanceOf<T>, Option<NegativeImbalanceOf<T>>, Weight), TransactionValidityError> {
 		let tip = self.0;
-		let fee = Pallet::<T>::compute_fee(len as u32, info, tip);
+		let multiplier = Pallet::<T>::next_fee_multiplier_of(Pallet::<T>::is_evm_call(call));
+		let compute_fee = |weight: Weight| {
+			Pallet::<T>::compute_fee_raw(len as u32, weight, tip, info.pays_fee, info.class, multiplier).final_fee()
+		};
+		let fee = compute_fee(info.weight);
 
 		let reason = if tip.is_zero() {
 			WithdrawReasons::TRANSACTION_PAYMENT
@@ -646,11 +1697,36 @@ where
 			WithdrawReasons::TRANSACTION_PAYMENT | WithdrawReasons::TIP
 		};
 
-		Pallet::<T>::ensure_can_charge_fee(who, fee, reason);
+		let fee_currency_id = match <T as Config>::Call::from_ref(call).is_sub_type() {
+			Some(Call::with_fee_currency(currency_id, _)) => Some(*currency_id),
+			Some(Call::with_sponsor(sponsor, sponsored_call)) => {
+				// the sponsor pays the fee except the tip, which is paid by the sender
+				let mut imbalance =
+					<T as Config>::Currency::withdraw(who, tip, WithdrawReasons::TIP, ExistenceRequirement::KeepAlive)
+						.map_err(|_| InvalidTransaction::Payment)?;
+				imbalance.subsume(Pallet::<T>::charge_sponsor(
+					sponsor,
+					who,
+					sponsored_call,
+					fee.saturating_sub(tip),
+				)?);
+				return Ok((sponsor.clone(), fee, Some(imbalance), Zero::zero()));
+			}
+			_ => None,
+		};
+
+		// the fee covers the weight of the swap if the native currency is not enough
+		let swap_weight = Pallet::<T>::fee_swap_weight(who, fee, reason);
+		let fee = if swap_weight.is_zero() {
+			fee
+		} else {
+			compute_fee(info.weight.saturating_add(swap_weight))
+		};
+		Pallet::<T>::ensure_can_charge_fee_with_currency(who, fee_currency_id, fee, reason);
 
 		// withdraw native currency as fee
 		match <T as Config>::Currency::withdraw(who, fee, reason, ExistenceRequirement::KeepAlive) {
-			Ok(imbalance) => Ok((fee, Some(imbalance))),
+			Ok(imbalance) => Ok((who.clone(), fee, Some(imbalance), swap_weight)),
 			Err(_) => Err(InvalidTransaction::Payment.into()),
 		}
 	}
@@ -698,6 +1774,8 @@ where
 		Self::AccountId,
 		Option<NegativeImbalanceOf<T>>,
 		PalletBalanceOf<T>,
+		bool,
+		Weight,
 	);
 
 	fn additional_signed(&self) -> sp_std::result::Result<(), TransactionValidityError> {
@@ -711,7 +1789,7 @@ where
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> TransactionValidity {
-		let (fee, _) = self.withdraw_fee(who, call, info, len)?;
+		let (_, fee, _, _) = self.withdraw_fee(who, call, info, len)?;
 		Ok(ValidTransaction {
 			priority: Self::get_priority(len, info, fee),
 			..Default::default()
@@ -725,8 +1803,15 @@ where
 		info: &DispatchInfoOf<Self::Call>,
 		len: usize,
 	) -> Result<Self::Pre, TransactionValidityError> {
-		let (fee, imbalance) = self.withdraw_fee(who, call, info, len)?;
-		Ok((self.0, who.clone(), imbalance, fee))
+		let is_evm_call = Pallet::<T>::is_evm_call(call);
+		let (payer, fee, imbalance, swap_weight) = self.withdraw_fee(who, call, info, len)?;
+		if !swap_weight.is_zero() {
+			// the fee swap is not included in the weight of the call
+			<frame_system::Pallet<T>>::register_extra_weight_unchecked(swap_weight, info.class);
+		}
+		// the fee distribution is not included in the weight of the call either
+		<frame_system::Pallet<T>>::register_extra_weight_unchecked(Pallet::<T>::fee_distribution_weight(), info.class);
+		Ok((self.0, payer, imbalance, fee, is_evm_call, swap_weight))
 	}
 
 	fn post_dispatch(
@@ -736,9 +1821,23 @@ where
 		len: usize,
 		_result: &DispatchResult,
 	) -> Result<(), TransactionValidityError> {
-		let (tip, who, imbalance, fee) = pre;
+		let (tip, who, imbalance, fee, is_evm_call, swap_weight) = pre;
+		let actual_weight = post_info.calc_actual_weight(info);
+		if is_evm_call {
+			// record the weight consumed by EVM calls to update the EVM fee multiplier
+			EvmBlockWeight::<T>::mutate(|weight| *weight = weight.saturating_add(actual_weight));
+		}
+
 		if let Some(payed) = imbalance {
-			let actual_fee = Pallet::<T>::compute_actual_fee(len as u32, info, post_info, tip);
+			let actual_fee = Pallet::<T>::compute_fee_raw(
+				len as u32,
+				actual_weight.saturating_add(swap_weight),
+				tip,
+				post_info.pays_fee(info),
+				info.class,
+				Pallet::<T>::next_fee_multiplier_of(is_evm_call),
+			)
+			.final_fee();
 			let refund = fee.saturating_sub(actual_fee);
 			let actual_payment = match <T as Config>::Currency::deposit_into_existing(&who, refund) {
 				Ok(refund_imbalance) => {
@@ -754,12 +1853,11 @@ where
 				// is gone in that case.
 				Err(_) => payed,
 			};
-			let imbalances = actual_payment.split(tip);
+			let (tip_imbalance, fee_imbalance) = actual_payment.split(tip);
+			Pallet::<T>::record_paid_fee(&who, fee_imbalance.peek().unique_saturated_into());
 
 			// distribute fee
-			<T as Config>::OnTransactionPayment::on_unbalanceds(
-				Some(imbalances.0).into_iter().chain(Some(imbalances.1)),
-			);
+			Pallet::<T>::distribute_fee(fee_imbalance, tip_imbalance);
 		}
 		Ok(())
 	}
@@ -820,7 +1918,7 @@ where
 		};
 
 		// distribute fee
-		<T as Config>::OnTransactionPayment::on_unbalanced(actual_payment);
+		<T as Config>::OnTransactionPayment::on_unbalanced(Pallet::<T>::burn_fee(actual_payment));
 
 		Ok(())
 	}
@@ -833,7 +1931,8 @@ where
 		pays_fee: Pays,
 		class: DispatchClass,
 	) -> Result<(), TransactionValidityError> {
-		let fee = Pallet::<T>::compute_fee_raw(len, weight, tip, pays_fee, class).final_fee();
+		let fee = Pallet::<T>::compute_fee_raw(len, weight, tip, pays_fee, class, Pallet::<T>::next_fee_multiplier())
+			.final_fee();
 
 		Pallet::<T>::ensure_can_charge_fee(who, fee, WithdrawReasons::TRANSACTION_PAYMENT);
 
@@ -847,7 +1946,7 @@ where
 		.map_err(|_| InvalidTransaction::Payment)?;
 
 		// distribute fee
-		<T as Config>::OnTransactionPayment::on_unbalanced(actual_payment);
+		<T as Config>::OnTransactionPayment::on_unbalanced(Pallet::<T>::burn_fee(actual_payment));
 		Ok(())
 	}
 }
@@ -23,12 +23,12 @@
 use super::*;
 use crate as transaction_payment;
 use frame_support::{
-	construct_runtime, ord_parameter_types, parameter_types, weights::WeightToFeeCoefficients, PalletId,
+	construct_runtime, ord_parameter_types, parameter_types, traits::Filter, weights::WeightToFeeCoefficients, PalletId,
 };
 use orml_traits::parameter_type_with_key;
 use primitives::{Amount, TokenSymbol, TradingPair};
 use smallvec::smallvec;
-use sp_core::{crypto::AccountId32, H256};
+use sp_core::{crypto::AccountId32, H160, H256};
 use sp_runtime::{testing::Header, traits::IdentityLookup, FixedPointNumber, Perbill};
 use sp_std::cell::RefCell;
 use support::{mocks::MockAddressMapping, Ratio};
@@ -38,6 +38,7 @@ pub type BlockNumber = u64;
 
 pub const ALICE: AccountId = AccountId::new([1u8; 32]);
 pub const BOB: AccountId = AccountId::new([2u8; 32]);
+pub const CHARLIE: AccountId = AccountId::new([4u8; 32]);
 pub const ACA: CurrencyId = CurrencyId::Token(TokenSymbol::ACA);
 pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
@@ -146,7 +147,19 @@ parameter_types! {
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub const TradingPathLimit: u32 = 3;
-	pub EnabledTradingPairs : Vec<TradingPair> = vec![TradingPair::new(AUSD, ACA), TradingPair::new(AUSD, DOT)];
+	pub const ProvisioningPeriod: BlockNumber = 1000;
+	pub const AmplificationRampPeriod: BlockNumber = 10;
+	pub const MaxRangeBuckets: u32 = 10;
+	pub const MaxBasketInputs: u32 = 3;
+	pub const VolumeBucketPeriod: BlockNumber = 10;
+	pub const MaxVolumeBuckets: u32 = 3;
+	pub const ListingBond: Balance = 100;
+	pub const ListingChallengePeriod: BlockNumber = 10;
+	pub EnabledTradingPairs : Vec<TradingPair> = vec![
+		TradingPair::new(AUSD, ACA),
+		TradingPair::new(AUSD, DOT),
+		TradingPair::new(DOT, ACA),
+	];
 }
 
 impl module_dex::Config for Runtime {
@@ -156,9 +169,22 @@ impl module_dex::Config for Runtime {
 	type TradingPathLimit = TradingPathLimit;
 	type PalletId = DEXPalletId;
 	type CurrencyIdMapping = ();
+	type Erc20ReferenceCounter = ();
 	type DEXIncentives = ();
 	type WeightInfo = ();
 	type ListingOrigin = frame_system::EnsureSignedBy<Zero, AccountId>;
+	type ProvisioningPeriod = ProvisioningPeriod;
+	type AmplificationRampPeriod = AmplificationRampPeriod;
+	type MaxRangeBuckets = MaxRangeBuckets;
+	type PositionNFT = ();
+	type MaxBasketInputs = MaxBasketInputs;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type ListingBond = ListingBond;
+	type ListingChallengePeriod = ListingChallengePeriod;
+	type ListingVetoOrigin = frame_system::EnsureSignedBy<Zero, AccountId>;
+	type TreasuryAccount = TreasuryAccount;
+	type VolumeBucketPeriod = VolumeBucketPeriod;
+	type MaxVolumeBuckets = MaxVolumeBuckets;
 }
 
 parameter_types! {
@@ -166,21 +192,165 @@ parameter_types! {
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::one();
 	pub const StableCurrencyId: CurrencyId = AUSD;
 	pub static TransactionByteFee: u128 = 1;
+	pub const SponsorPeriod: BlockNumber = 10;
+	pub const MaxSponsorAllowedCalls: u32 = 2;
+	pub const TreasuryAccount: AccountId = AccountId::new([3u8; 32]);
+	pub const RebatePeriod: BlockNumber = 10;
+	pub const MaxRebateTiers: u32 = 2;
+	pub const MaxRebatesPerBlock: u32 = 1;
+	pub const FeeBurnReportPeriod: BlockNumber = 10;
+	pub const TransactionPaymentPalletId: PalletId = PalletId(*b"aca/fees");
+	pub static FeePoolRefillWeightLimit: Weight = 0;
+	pub static BlockAuthor: Option<AccountId> = None;
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 1000;
+}
+
+impl pallet_timestamp::Config for Runtime {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const NewContractExtraBytes: u32 = 1;
+	pub NetworkContractSource: H160 = H160::default();
+	pub const StorageDepositPerByte: Balance = 10;
+	pub const MaxCodeSize: u32 = 60 * 1024;
+	pub const DeveloperDeposit: Balance = 1000;
+	pub const DeploymentFee: Balance = 200;
+}
+
+impl module_evm::Config for Runtime {
+	type AddressMapping = MockAddressMapping;
+	type Currency = PalletBalances;
+	type MergeAccount = ();
+	type NewContractExtraBytes = NewContractExtraBytes;
+	type StorageDepositPerByte = StorageDepositPerByte;
+	type MaxCodeSize = MaxCodeSize;
+
+	type Event = Event;
+	type Precompiles = ();
+	type GasToWeight = ();
+	type ChargeTransactionPayment = ();
+	type NetworkContractOrigin = frame_system::EnsureSignedBy<Zero, AccountId>;
+	type NetworkContractSource = NetworkContractSource;
+
+	type DeveloperDeposit = DeveloperDeposit;
+	type DeploymentFee = DeploymentFee;
+	type TreasuryAccount = TreasuryAccount;
+	type FreeDeploymentOrigin = frame_system::EnsureSignedBy<Zero, AccountId>;
+	type CallFilter = ();
+	type ReapInactivityPeriod = ();
+	type ReapedDepositPot = TreasuryAccount;
+	type ReceiptsHistoryDepth = ();
+	type MaxListedCallers = ();
+
+	type WeightInfo = ();
+}
+
+impl pallet_utility::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type WeightInfo = ();
+}
+
+/// The EVM calls, including the ones wrapped in batch.
+pub struct EvmCallFilter;
+impl Filter<Call> for EvmCallFilter {
+	fn filter(call: &Call) -> bool {
+		match call {
+			Call::EVM(_) => true,
+			Call::Utility(pallet_utility::Call::batch(calls)) => calls.iter().any(Self::filter),
+			_ => false,
+		}
+	}
+}
+
+parameter_types! {
+	pub static FeeSwapWeight: Weight = 0;
+}
+
+/// The weights of `()`, except the weight of the fee swap is
+/// `FeeSwapWeight`, so that the fees in tests are not dominated by it.
+pub struct MockWeightInfo;
+impl WeightInfo for MockWeightInfo {
+	fn set_default_fee_token() -> Weight {
+		<() as WeightInfo>::set_default_fee_token()
+	}
+	fn on_finalize() -> Weight {
+		<() as WeightInfo>::on_finalize()
+	}
+	fn with_fee_currency() -> Weight {
+		<() as WeightInfo>::with_fee_currency()
+	}
+	fn register_sponsor() -> Weight {
+		<() as WeightInfo>::register_sponsor()
+	}
+	fn unregister_sponsor() -> Weight {
+		<() as WeightInfo>::unregister_sponsor()
+	}
+	fn with_sponsor() -> Weight {
+		<() as WeightInfo>::with_sponsor()
+	}
+	fn set_rebate_tiers() -> Weight {
+		<() as WeightInfo>::set_rebate_tiers()
+	}
+	fn set_tip_split() -> Weight {
+		<() as WeightInfo>::set_tip_split()
+	}
+	fn set_rebate_stake() -> Weight {
+		<() as WeightInfo>::set_rebate_stake()
+	}
+	fn claim_rebate() -> Weight {
+		<() as WeightInfo>::claim_rebate()
+	}
+	fn set_fee_burn_rate() -> Weight {
+		<() as WeightInfo>::set_fee_burn_rate()
+	}
+	fn set_fee_pool() -> Weight {
+		<() as WeightInfo>::set_fee_pool()
+	}
+	fn settle_rebates(i: u32) -> Weight {
+		<() as WeightInfo>::settle_rebates(i)
+	}
+	fn charge_fee_by_swap(_p: u32) -> Weight {
+		FeeSwapWeight::get()
+	}
 }
 
 impl Config for Runtime {
+	type Event = Event;
+	type Call = Call;
 	type AllNonNativeCurrencyIds = AllNonNativeCurrencyIds;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type StableCurrencyId = StableCurrencyId;
 	type Currency = PalletBalances;
 	type MultiCurrency = Currencies;
 	type OnTransactionPayment = ();
+	type BlockAuthor = BlockAuthor;
 	type TransactionByteFee = TransactionByteFee;
 	type WeightToFee = WeightToFee;
 	type FeeMultiplierUpdate = ();
+	type EvmFeeMultiplierUpdate = ();
+	type EvmCallFilter = EvmCallFilter;
 	type DEX = DEXModule;
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
-	type WeightInfo = ();
+	type TradingPathLimit = TradingPathLimit;
+	type SponsorPeriod = SponsorPeriod;
+	type MaxSponsorAllowedCalls = MaxSponsorAllowedCalls;
+	type UpdateOrigin = frame_system::EnsureRoot<AccountId>;
+	type TreasuryAccount = TreasuryAccount;
+	type RebatePeriod = RebatePeriod;
+	type MaxRebateTiers = MaxRebateTiers;
+	type MaxRebatesPerBlock = MaxRebatesPerBlock;
+	type FeeBurnReportPeriod = FeeBurnReportPeriod;
+	type PalletId = TransactionPaymentPalletId;
+	type FeePoolRefillWeightLimit = FeePoolRefillWeightLimit;
+	type WeightInfo = MockWeightInfo;
 }
 
 thread_local! {
@@ -211,11 +381,14 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TransactionPayment: transaction_payment::{Pallet, Call, Storage},
+		TransactionPayment: transaction_payment::{Pallet, Call, Storage, Event<T>},
 		PalletBalances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
 		Currencies: module_currencies::{Pallet, Call, Event<T>},
 		DEXModule: module_dex::{Pallet, Storage, Call, Event<T>, Config<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		EVM: module_evm::{Pallet, Config<T>, Call, Storage, Event<T>},
+		Utility: pallet_utility::{Pallet, Call, Event},
 	}
 );
 
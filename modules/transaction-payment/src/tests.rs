@@ -22,15 +22,17 @@
 
 use super::*;
 use frame_support::{
-	assert_ok,
+	assert_noop, assert_ok,
+	traits::{OnFinalize, OnInitialize},
 	weights::{DispatchClass, DispatchInfo, Pays},
 };
 use mock::{
-	AccountId, BlockWeights, Call, Currencies, DEXModule, ExtBuilder, Origin, Runtime, TransactionPayment, ACA, ALICE,
-	AUSD, BOB, DOT,
+	AccountId, BlockAuthor, BlockWeights, Call, Currencies, DEXModule, Event, ExtBuilder, FeePoolRefillWeightLimit,
+	FeeSwapWeight, Origin, PalletBalances, Runtime, System, TransactionPayment, TreasuryAccount, ACA, ALICE, AUSD, BOB,
+	CHARLIE, DOT,
 };
 use orml_traits::MultiCurrency;
-use sp_runtime::testing::TestXt;
+use sp_runtime::{testing::TestXt, traits::BadOrigin};
 
 const CALL: &<Runtime as frame_system::Config>::Call =
 	&Call::Currencies(module_currencies::Call::transfer(BOB, AUSD, 12));
@@ -103,6 +105,66 @@ fn refund_fee_according_to_actual_when_post_dispatch_and_native_currency_is_enou
 	});
 }
 
+#[test]
+fn charges_fee_with_evm_fee_multiplier_for_evm_calls() {
+	ExtBuilder::default().build().execute_with(|| {
+		NextEvmFeeMultiplier::<Runtime>::put(Multiplier::saturating_from_integer(2));
+		let evm_call: &<Runtime as frame_system::Config>::Call =
+			&Call::EVM(module_evm::Call::call(Default::default(), vec![], 0, 1000, 0));
+
+		// the fee of plain calls is not affected
+		let fee = 23 * 2 + 1000; // len * byte + weight
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.unwrap();
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee);
+		assert!(ChargeTransactionPayment::<Runtime>::post_dispatch(pre, &INFO, &POST_INFO, 23, &Ok(())).is_ok());
+		assert_eq!(EvmBlockWeight::<Runtime>::get(), 0);
+
+		let evm_fee = 23 * 2 + 1000 * 2; // len * byte + weight * evm_multiplier
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, evm_call, &INFO, 23)
+			.unwrap();
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - 800 - 46 - evm_fee);
+
+		let refund = 200 * 2; // (1000 - 800) * evm_multiplier
+		assert!(ChargeTransactionPayment::<Runtime>::post_dispatch(pre, &INFO, &POST_INFO, 23, &Ok(())).is_ok());
+		assert_eq!(
+			Currencies::free_balance(ACA, &ALICE),
+			100000 - 800 - 46 - evm_fee + refund
+		);
+		assert_eq!(EvmBlockWeight::<Runtime>::get(), 800);
+
+		TransactionPayment::on_finalize(1);
+		assert_eq!(EvmBlockWeight::<Runtime>::get(), 0);
+	});
+}
+
+#[test]
+fn evm_fee_multiplier_applies_to_wrapped_evm_calls() {
+	ExtBuilder::default().build().execute_with(|| {
+		let evm_call = Call::EVM(module_evm::Call::call(Default::default(), vec![], 0, 1000, 0));
+		let batch_evm_call = Call::Utility(pallet_utility::Call::batch(vec![CALL.clone(), evm_call.clone()]));
+		let batch_call = Call::Utility(pallet_utility::Call::batch(vec![CALL.clone(), CALL2.clone()]));
+		assert!(TransactionPayment::is_evm_call(&evm_call));
+		assert!(TransactionPayment::is_evm_call(&batch_evm_call));
+		assert!(!TransactionPayment::is_evm_call(&batch_call));
+
+		NextEvmFeeMultiplier::<Runtime>::put(Multiplier::saturating_from_integer(2));
+		let weight_fee = |call: &Call| {
+			let info = call.get_dispatch_info();
+			TransactionPayment::query_fee_details(call, 0)
+				.inclusion_fee
+				.map(|fee| (info.weight, fee.adjusted_weight_fee))
+				.unwrap()
+		};
+		let (weight, fee) = weight_fee(&batch_evm_call);
+		assert_eq!(fee, weight as u128 * 2);
+		let (weight, fee) = weight_fee(&batch_call);
+		assert_eq!(fee, weight as u128);
+	});
+}
+
 #[test]
 fn charges_fee_when_validate_and_native_is_not_enough() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -136,6 +198,43 @@ fn charges_fee_when_validate_and_native_is_not_enough() {
 	});
 }
 
+#[test]
+fn charges_fee_swap_weight_when_native_is_not_enough() {
+	ExtBuilder::default().build().execute_with(|| {
+		FeeSwapWeight::set(&100);
+		assert_eq!(TransactionPayment::max_fee_swap_paths(), 9);
+
+		// native is enough, no swap
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.unwrap();
+		assert_eq!(pre.3, 23 * 2 + 1000);
+		assert_eq!(pre.5, 0);
+		assert_eq!(System::block_weight().get(DispatchClass::Normal), &0);
+
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(AUSD, &ALICE, &BOB, 1000));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			ACA,
+			AUSD,
+			10000,
+			1000,
+			false
+		));
+
+		// the fee covers the weight of the swap, which is registered to the block
+		let fee = 500 * 2 + 1000 + 100; // len * byte + weight + swap weight
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&BOB, CALL2, &INFO, 500)
+			.unwrap();
+		assert_eq!(pre.3, fee);
+		assert_eq!(pre.5, 100);
+		assert_eq!(System::block_weight().get(DispatchClass::Normal), &100);
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
+		assert_eq!(DEXModule::get_liquidity_pool(ACA, AUSD), (10000 - fee, 1266));
+	});
+}
+
 #[test]
 fn set_default_fee_token_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -199,6 +298,702 @@ fn charge_fee_by_default_fee_token() {
 	});
 }
 
+#[test]
+fn best_fee_swap_path_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(TransactionPayment::best_fee_swap_path(DOT, 2000), None);
+
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			ACA,
+			AUSD,
+			10000,
+			1000,
+			false
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			DOT,
+			AUSD,
+			100,
+			1000,
+			false
+		));
+		assert_eq!(
+			TransactionPayment::best_fee_swap_path(DOT, 2000),
+			Some((vec![DOT, AUSD, ACA], 34))
+		);
+		assert_eq!(
+			TransactionPayment::best_fee_swap_path(AUSD, 2000),
+			Some((vec![AUSD, ACA], 251))
+		);
+
+		// the direct path requires less supply amount
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			DOT,
+			ACA,
+			100,
+			50000,
+			false
+		));
+		assert_eq!(
+			TransactionPayment::best_fee_swap_path(DOT, 2000),
+			Some((vec![DOT, ACA], 5))
+		);
+	});
+}
+
+#[test]
+fn charge_fee_by_with_fee_currency() {
+	ExtBuilder::default().build().execute_with(|| {
+		// add liquidity to DEX
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			ACA,
+			AUSD,
+			10000,
+			1000,
+			false
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			DOT,
+			AUSD,
+			100,
+			1000,
+			false
+		));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(AUSD, &ALICE, &BOB, 1000));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(DOT, &ALICE, &BOB, 100));
+		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(ACA, &BOB), 0);
+
+		let call: &<Runtime as frame_system::Config>::Call =
+			&Call::TransactionPayment(crate::Call::with_fee_currency(DOT, Box::new(CALL2.clone())));
+		let fee = 500 * 2 + 1000; // len * byte + weight
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::from(0)
+				.validate(&BOB, call, &INFO, 500)
+				.unwrap()
+				.priority,
+			fee
+		);
+
+		// swap DOT for fee rather than AUSD
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(Currencies::free_balance(DOT, &BOB), 100 - 34);
+
+		assert_ok!(TransactionPayment::with_fee_currency(
+			Origin::signed(ALICE),
+			DOT,
+			Box::new(CALL2.clone())
+		));
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 12);
+	});
+}
+
+#[test]
+fn set_fee_pool_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(TransactionPayment::fee_pool_thresholds(AUSD), None);
+		assert_noop!(
+			TransactionPayment::set_fee_pool(Origin::signed(ALICE), AUSD, Some(1000)),
+			BadOrigin
+		);
+		assert_noop!(
+			TransactionPayment::set_fee_pool(Origin::root(), ACA, Some(1000)),
+			Error::<Runtime>::InvalidFeePoolCurrency
+		);
+
+		assert_ok!(TransactionPayment::set_fee_pool(Origin::root(), AUSD, Some(1000)));
+		assert_eq!(TransactionPayment::fee_pool_thresholds(AUSD), Some(1000));
+		let update_event = Event::transaction_payment(crate::Event::FeePoolUpdated(AUSD, Some(1000)));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+
+		assert_ok!(TransactionPayment::set_fee_pool(Origin::root(), AUSD, None));
+		assert_eq!(TransactionPayment::fee_pool_thresholds(AUSD), None);
+		let update_event = Event::transaction_payment(crate::Event::FeePoolUpdated(AUSD, None));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+	});
+}
+
+#[test]
+fn charge_fee_by_fee_pool() {
+	ExtBuilder::default().build().execute_with(|| {
+		let pool_account = TransactionPayment::fee_pool_account_id(AUSD);
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			ACA,
+			AUSD,
+			10000,
+			1000,
+			false
+		));
+		assert_ok!(TransactionPayment::set_fee_pool(Origin::root(), AUSD, Some(1000)));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(
+			ACA,
+			&ALICE,
+			&pool_account,
+			10000
+		));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(AUSD, &ALICE, &BOB, 1000));
+		assert_ok!(TransactionPayment::set_default_fee_token(
+			Origin::signed(BOB),
+			Some(AUSD)
+		));
+
+		let fee = 500 * 2 + 1000; // len * byte + weight
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::from(0)
+				.validate(&BOB, CALL2, &INFO, 500)
+				.unwrap()
+				.priority,
+			fee
+		);
+
+		// paid by the pool at the DEX price, the DEX pool is not touched
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), 1000 - 251);
+		assert_eq!(Currencies::free_balance(ACA, &pool_account), 10000 - 2000);
+		assert_eq!(Currencies::free_balance(AUSD, &pool_account), 251);
+		assert_eq!(DEXModule::get_liquidity_pool(ACA, AUSD), (10000, 1000));
+
+		// swap with DEX once the pool is disabled
+		assert_ok!(TransactionPayment::set_fee_pool(Origin::root(), AUSD, None));
+		assert_ok!(ChargeTransactionPayment::<Runtime>::from(0).validate(&BOB, CALL2, &INFO, 500));
+		assert_eq!(Currencies::free_balance(ACA, &pool_account), 10000 - 2000);
+		assert_eq!(Currencies::free_balance(AUSD, &pool_account), 251);
+		assert_eq!(DEXModule::get_liquidity_pool(ACA, AUSD), (10000 - 2000, 1251));
+	});
+}
+
+#[test]
+fn refill_fee_pools_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let pool_account = TransactionPayment::fee_pool_account_id(AUSD);
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			ACA,
+			AUSD,
+			10000,
+			1000,
+			false
+		));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(
+			ACA,
+			&ALICE,
+			&pool_account,
+			500
+		));
+		assert_ok!(<Currencies as MultiCurrency<_>>::transfer(
+			AUSD,
+			&ALICE,
+			&pool_account,
+			100
+		));
+		FeeSwapWeight::set(&100);
+
+		// the native balance is not below the threshold
+		assert_ok!(TransactionPayment::set_fee_pool(Origin::root(), AUSD, Some(500)));
+		assert_eq!(TransactionPayment::refill_fee_pools(100), 0);
+		assert_eq!(Currencies::free_balance(AUSD, &pool_account), 100);
+
+		// the refill exceeds the weight limit
+		assert_ok!(TransactionPayment::set_fee_pool(Origin::root(), AUSD, Some(1000)));
+		assert_eq!(TransactionPayment::refill_fee_pools(99), 0);
+		assert_eq!(Currencies::free_balance(AUSD, &pool_account), 100);
+
+		FeePoolRefillWeightLimit::set(&100);
+		TransactionPayment::on_initialize(2);
+		let (dex_native, dex_other) = DEXModule::get_liquidity_pool(ACA, AUSD);
+		let native_amount = 10000 - dex_native;
+		assert_eq!(dex_other, 1000 + 100);
+		assert_eq!(Currencies::free_balance(AUSD, &pool_account), 0);
+		assert_eq!(Currencies::free_balance(ACA, &pool_account), 500 + native_amount);
+		let refill_event = Event::transaction_payment(crate::Event::FeePoolRefilled(AUSD, 100, native_amount));
+		assert!(System::events().iter().any(|record| record.event == refill_event));
+	});
+}
+
+fn call_name(pallet_name: &str, call_name: &str) -> CallName {
+	(pallet_name.as_bytes().to_vec(), call_name.as_bytes().to_vec())
+}
+
+#[test]
+fn register_sponsor_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let transfer_native_currency = call_name("Currencies", "transfer_native_currency");
+		assert_noop!(
+			TransactionPayment::register_sponsor(
+				Origin::signed(ALICE),
+				5000,
+				1,
+				vec![
+					transfer_native_currency.clone(),
+					call_name("Currencies", "transfer"),
+					call_name("System", "remark")
+				]
+			),
+			Error::<Runtime>::TooManyAllowedCalls
+		);
+		assert_noop!(
+			TransactionPayment::register_sponsor(Origin::signed(ALICE), 5000, 1, vec![call_name("Currencies", "mint")]),
+			Error::<Runtime>::InvalidCallName
+		);
+		assert_noop!(
+			TransactionPayment::unregister_sponsor(Origin::signed(ALICE)),
+			Error::<Runtime>::SponsorNotFound
+		);
+
+		assert_ok!(TransactionPayment::register_sponsor(
+			Origin::signed(ALICE),
+			5000,
+			1,
+			vec![transfer_native_currency.clone()]
+		));
+		assert_eq!(
+			TransactionPayment::sponsors(&ALICE),
+			Some(SponsorInfo {
+				budget: 5000,
+				max_calls_per_period: 1,
+				allowed_calls: vec![transfer_native_currency.clone()],
+			})
+		);
+		assert_eq!(PalletBalances::reserved_balance(&ALICE), 5000);
+		let registered_event = Event::transaction_payment(crate::Event::SponsorRegistered(ALICE, 5000));
+		assert!(System::events().iter().any(|record| record.event == registered_event));
+
+		// override the previous registration
+		assert_ok!(TransactionPayment::register_sponsor(
+			Origin::signed(ALICE),
+			3000,
+			1,
+			vec![]
+		));
+		assert_eq!(PalletBalances::reserved_balance(&ALICE), 3000);
+		assert_eq!(PalletBalances::free_balance(&ALICE), 97000);
+
+		assert_ok!(TransactionPayment::unregister_sponsor(Origin::signed(ALICE)));
+		assert_eq!(TransactionPayment::sponsors(&ALICE), None);
+		assert_eq!(PalletBalances::reserved_balance(&ALICE), 0);
+		let unregistered_event = Event::transaction_payment(crate::Event::SponsorUnregistered(ALICE, 3000));
+		assert!(System::events().iter().any(|record| record.event == unregistered_event));
+	});
+}
+
+#[test]
+fn charge_fee_by_sponsor() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TransactionPayment::register_sponsor(
+			Origin::signed(ALICE),
+			3000,
+			1,
+			vec![call_name("Currencies", "transfer_native_currency")]
+		));
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 0);
+
+		let call: &<Runtime as frame_system::Config>::Call =
+			&Call::TransactionPayment(crate::Call::with_sponsor(ALICE, Box::new(CALL2.clone())));
+		let fee = 23 * 2 + 1000; // len * byte + weight
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&BOB, call, &INFO, 23)
+			.unwrap();
+		assert_eq!(pre.1, ALICE);
+		// the fee is paid from the reserved budget
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - 3000);
+		assert_eq!(PalletBalances::reserved_balance(&ALICE), 3000 - fee);
+		assert_eq!(TransactionPayment::sponsors(&ALICE).unwrap().budget, 3000 - fee);
+		assert_eq!(TransactionPayment::sponsored_calls(&ALICE, &BOB), (0, 1));
+		let sponsored_event = Event::transaction_payment(crate::Event::FeeSponsored(ALICE, BOB, fee));
+		assert!(System::events().iter().any(|record| record.event == sponsored_event));
+
+		// exceed the rate limit in this period
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::from(0)
+				.pre_dispatch(&BOB, call, &INFO, 23)
+				.err(),
+			Some(InvalidTransaction::ExhaustsResources.into())
+		);
+
+		// the call is not allowed
+		let not_allowed_call: &<Runtime as frame_system::Config>::Call =
+			&Call::TransactionPayment(crate::Call::with_sponsor(ALICE, Box::new(CALL.clone())));
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::from(0)
+				.pre_dispatch(&BOB, not_allowed_call, &INFO, 23)
+				.err(),
+			Some(InvalidTransaction::Call.into())
+		);
+
+		// the tip is paid by the sender
+		System::set_block_number(10);
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::from(10)
+				.pre_dispatch(&BOB, call, &INFO, 23)
+				.err(),
+			Some(InvalidTransaction::Payment.into())
+		);
+		let _ = PalletBalances::deposit_creating(&BOB, 100);
+		let pre = ChargeTransactionPayment::<Runtime>::from(10)
+			.pre_dispatch(&BOB, call, &INFO, 23)
+			.unwrap();
+		assert_eq!(pre.3, fee + 10);
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 90);
+		assert_eq!(PalletBalances::reserved_balance(&ALICE), 3000 - fee * 2);
+		assert_eq!(TransactionPayment::sponsors(&ALICE).unwrap().budget, 3000 - fee * 2);
+		assert_eq!(TransactionPayment::sponsored_calls(&ALICE, &BOB), (1, 1));
+
+		// the budget is not enough
+		System::set_block_number(20);
+		assert_eq!(
+			ChargeTransactionPayment::<Runtime>::from(0)
+				.pre_dispatch(&BOB, call, &INFO, 23)
+				.err(),
+			Some(InvalidTransaction::Payment.into())
+		);
+		assert_eq!(Currencies::free_balance(ACA, &BOB), 90);
+	});
+}
+
+#[test]
+fn set_rebate_tiers_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let tiers = vec![(100, Permill::from_percent(10)), (1000, Permill::from_percent(50))];
+		assert_noop!(
+			TransactionPayment::set_rebate_tiers(Origin::signed(ALICE), tiers.clone()),
+			BadOrigin
+		);
+		assert_noop!(
+			TransactionPayment::set_rebate_tiers(
+				Origin::root(),
+				vec![
+					(100, Permill::from_percent(10)),
+					(1000, Permill::from_percent(50)),
+					(10000, Permill::from_percent(80))
+				]
+			),
+			Error::<Runtime>::TooManyRebateTiers
+		);
+		assert_noop!(
+			TransactionPayment::set_rebate_tiers(
+				Origin::root(),
+				vec![(1000, Permill::from_percent(10)), (100, Permill::from_percent(50))]
+			),
+			Error::<Runtime>::InvalidRebateTiers
+		);
+		assert_noop!(
+			TransactionPayment::set_rebate_tiers(
+				Origin::root(),
+				vec![(100, Permill::from_percent(50)), (1000, Permill::from_percent(10))]
+			),
+			Error::<Runtime>::InvalidRebateTiers
+		);
+		assert_noop!(
+			TransactionPayment::set_rebate_tiers(Origin::root(), vec![(100, Permill::zero())]),
+			Error::<Runtime>::InvalidRebateTiers
+		);
+
+		assert_ok!(TransactionPayment::set_rebate_tiers(Origin::root(), tiers.clone()));
+		assert_eq!(TransactionPayment::rebate_tiers(), tiers);
+		let update_event = Event::transaction_payment(crate::Event::RebateTiersUpdated(tiers));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+	});
+}
+
+#[test]
+fn set_rebate_stake_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			TransactionPayment::set_rebate_stake(Origin::signed(ALICE), 100001),
+			Error::<Runtime>::InsufficientBalanceToStake
+		);
+
+		assert_ok!(TransactionPayment::set_rebate_stake(Origin::signed(ALICE), 1000));
+		assert_eq!(TransactionPayment::rebate_stakes(&ALICE), 1000);
+		let stake_event = Event::transaction_payment(crate::Event::RebateStakeUpdated(ALICE, 1000));
+		assert!(System::events().iter().any(|record| record.event == stake_event));
+		assert!(
+			<PalletBalances as Currency<_>>::transfer(&ALICE, &BOB, 99500, ExistenceRequirement::AllowDeath).is_err()
+		);
+
+		assert_ok!(TransactionPayment::set_rebate_stake(Origin::signed(ALICE), 0));
+		assert_eq!(TransactionPayment::rebate_stakes(&ALICE), 0);
+		assert_ok!(<PalletBalances as Currency<_>>::transfer(
+			&ALICE,
+			&BOB,
+			99500,
+			ExistenceRequirement::AllowDeath
+		));
+	});
+}
+
+#[test]
+fn set_tip_split_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(TransactionPayment::tip_split(), (Permill::zero(), Permill::one()));
+		assert_noop!(
+			TransactionPayment::set_tip_split(
+				Origin::signed(ALICE),
+				Permill::from_percent(50),
+				Permill::from_percent(30)
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			TransactionPayment::set_tip_split(Origin::root(), Permill::from_percent(60), Permill::from_percent(50)),
+			Error::<Runtime>::InvalidTipSplit
+		);
+
+		assert_ok!(TransactionPayment::set_tip_split(
+			Origin::root(),
+			Permill::from_percent(50),
+			Permill::from_percent(30)
+		));
+		assert_eq!(
+			TransactionPayment::tip_split(),
+			(Permill::from_percent(50), Permill::from_percent(30))
+		);
+		let update_event = Event::transaction_payment(crate::Event::TipSplitUpdated(
+			Permill::from_percent(50),
+			Permill::from_percent(30),
+		));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+	});
+}
+
+#[test]
+fn tip_split_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		BlockAuthor::set(&Some(CHARLIE));
+		assert_ok!(TransactionPayment::set_tip_split(
+			Origin::root(),
+			Permill::from_percent(50),
+			Permill::from_percent(30)
+		));
+
+		let fee = 23 * 2 + 1000; // len * byte + weight
+		let tip = 1000;
+		let pre = ChargeTransactionPayment::<Runtime>::from(tip)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.unwrap();
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee - tip);
+
+		// refund 200, 50% tip to author, 30% to treasury and 20% is burned
+		assert!(ChargeTransactionPayment::<Runtime>::post_dispatch(pre, &INFO, &POST_INFO, 23, &Ok(())).is_ok());
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee - tip + 200);
+		assert_eq!(Currencies::free_balance(ACA, &CHARLIE), 500);
+		assert_eq!(TransactionPayment::block_fee_distribution(), (846, 500, 300, 200));
+
+		// the share of unknown author goes to treasury
+		BlockAuthor::set(&None);
+		let pre = ChargeTransactionPayment::<Runtime>::from(tip)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.unwrap();
+		assert!(ChargeTransactionPayment::<Runtime>::post_dispatch(pre, &INFO, &POST_INFO, 23, &Ok(())).is_ok());
+		assert_eq!(Currencies::free_balance(ACA, &CHARLIE), 500);
+		assert_eq!(TransactionPayment::block_fee_distribution(), (1692, 500, 1100, 400));
+
+		TransactionPayment::on_finalize(1);
+		let distribute_event = Event::transaction_payment(crate::Event::FeeDistributed(1692, 500, 1100, 400));
+		assert!(System::events().iter().any(|record| record.event == distribute_event));
+		assert_eq!(TransactionPayment::block_fee_distribution(), (0, 0, 0, 0));
+	});
+}
+
+#[test]
+fn fee_burn_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(TransactionPayment::fee_burn_rate(), Permill::zero());
+		assert_noop!(
+			TransactionPayment::set_fee_burn_rate(Origin::signed(ALICE), Permill::from_percent(25)),
+			BadOrigin
+		);
+		assert_ok!(TransactionPayment::set_fee_burn_rate(
+			Origin::root(),
+			Permill::from_percent(25)
+		));
+		let update_event = Event::transaction_payment(crate::Event::FeeBurnRateUpdated(Permill::from_percent(25)));
+		assert!(System::events().iter().any(|record| record.event == update_event));
+
+		let fee = 23 * 2 + 1000; // len * byte + weight
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.unwrap();
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee);
+
+		// refund 200, 25% of the actual fee is burned
+		assert!(ChargeTransactionPayment::<Runtime>::post_dispatch(pre, &INFO, &POST_INFO, 23, &Ok(())).is_ok());
+		assert_eq!(Currencies::free_balance(ACA, &ALICE), 100000 - fee + 200);
+		assert_eq!(TransactionPayment::block_fee_distribution(), (635, 0, 0, 0));
+		assert_eq!(TransactionPayment::period_burned_fee(), 211);
+		assert_eq!(TransactionPayment::total_burned_fee(), 211);
+
+		// report at the end of the period
+		TransactionPayment::on_finalize(1);
+		assert_eq!(TransactionPayment::period_burned_fee(), 211);
+		TransactionPayment::on_finalize(10);
+		let burned_event = Event::transaction_payment(crate::Event::TotalBurned(211, 211));
+		assert!(System::events().iter().any(|record| record.event == burned_event));
+		assert_eq!(TransactionPayment::period_burned_fee(), 0);
+		assert_eq!(TransactionPayment::total_burned_fee(), 211);
+	});
+}
+
+#[test]
+fn fee_rebate_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let _ = PalletBalances::deposit_creating(&TreasuryAccount::get(), 10000);
+		let _ = PalletBalances::deposit_creating(&BOB, 10000);
+		let _ = PalletBalances::deposit_creating(&CHARLIE, 10000);
+		assert_ok!(TransactionPayment::set_rebate_stake(Origin::signed(ALICE), 1000));
+		assert_ok!(TransactionPayment::set_rebate_stake(Origin::signed(BOB), 100));
+
+		// no fee is recorded if the rebate program is disabled
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.unwrap();
+		assert!(ChargeTransactionPayment::<Runtime>::post_dispatch(pre, &INFO, &POST_INFO, 23, &Ok(())).is_ok());
+		assert_eq!(TransactionPayment::paid_fees(0, &ALICE), (0, 0));
+
+		assert_ok!(TransactionPayment::set_rebate_tiers(
+			Origin::root(),
+			vec![(100, Permill::from_percent(10)), (1000, Permill::from_percent(50))]
+		));
+
+		// the rebates are recorded at the rates when the fees are paid
+		let fee = 23 * 2 + 800; // len * byte + actual weight
+		for who in [ALICE, BOB, CHARLIE].iter() {
+			let pre = ChargeTransactionPayment::<Runtime>::from(0)
+				.pre_dispatch(who, CALL, &INFO, 23)
+				.unwrap();
+			assert!(ChargeTransactionPayment::<Runtime>::post_dispatch(pre, &INFO, &POST_INFO, 23, &Ok(())).is_ok());
+		}
+		assert_eq!(TransactionPayment::paid_fees(0, &ALICE), (fee, fee / 2));
+		assert_eq!(TransactionPayment::paid_fees(0, &BOB), (fee, fee / 10));
+		assert_eq!(TransactionPayment::paid_fees(0, &CHARLIE), (fee, 0));
+		assert_eq!(PalletBalances::free_balance(&ALICE), 100000 - fee * 2);
+		assert_eq!(PalletBalances::free_balance(&BOB), 10000 - fee);
+
+		// staking after the fees are paid doesn't increase the rebate, and the rebate is
+		// capped by the rate at settlement
+		assert_ok!(TransactionPayment::set_rebate_stake(Origin::signed(CHARLIE), 1000));
+		assert_ok!(TransactionPayment::set_rebate_stake(Origin::signed(BOB), 0));
+
+		// settle one account per block after the era ends
+		TransactionPayment::on_initialize(9);
+		assert_eq!(TransactionPayment::rebate_era(), 0);
+		TransactionPayment::on_initialize(10);
+		assert_eq!(TransactionPayment::rebate_era(), 1);
+		assert_eq!(PaidFees::<Runtime>::iter_prefix(0).count(), 2);
+		TransactionPayment::on_initialize(11);
+		TransactionPayment::on_initialize(12);
+		assert_eq!(PaidFees::<Runtime>::iter_prefix(0).count(), 0);
+		assert_eq!(TransactionPayment::next_settle_era(), 0);
+		TransactionPayment::on_initialize(13);
+		assert_eq!(TransactionPayment::next_settle_era(), 1);
+
+		assert_eq!(PalletBalances::free_balance(&ALICE), 100000 - fee * 2 + fee / 2);
+		assert_eq!(PalletBalances::free_balance(&BOB), 10000 - fee);
+		assert_eq!(PalletBalances::free_balance(&CHARLIE), 10000 - fee);
+		assert_eq!(PalletBalances::free_balance(&TreasuryAccount::get()), 10000 - fee / 2);
+		let rebated_event = Event::transaction_payment(crate::Event::FeeRebated(ALICE, 0, fee / 2));
+		assert!(System::events().iter().any(|record| record.event == rebated_event));
+	});
+}
+
+#[test]
+fn pending_rebate_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TransactionPayment::set_rebate_stake(Origin::signed(ALICE), 1000));
+		assert_ok!(TransactionPayment::set_rebate_tiers(
+			Origin::root(),
+			vec![(1000, Permill::from_percent(50))]
+		));
+		assert_noop!(
+			TransactionPayment::claim_rebate(Origin::signed(ALICE)),
+			Error::<Runtime>::NoPendingRebate
+		);
+
+		let fee = 23 * 2 + 800; // len * byte + actual weight
+		let pre = ChargeTransactionPayment::<Runtime>::from(0)
+			.pre_dispatch(&ALICE, CALL, &INFO, 23)
+			.unwrap();
+		assert!(ChargeTransactionPayment::<Runtime>::post_dispatch(pre, &INFO, &POST_INFO, 23, &Ok(())).is_ok());
+
+		// the treasury can't pay the rebate
+		TransactionPayment::on_initialize(10);
+		TransactionPayment::on_initialize(11);
+		assert_eq!(TransactionPayment::pending_rebates(&ALICE), fee / 2);
+		let pending_event = Event::transaction_payment(crate::Event::RebatePending(ALICE, 0, fee / 2));
+		assert!(System::events().iter().any(|record| record.event == pending_event));
+		assert!(TransactionPayment::claim_rebate(Origin::signed(ALICE)).is_err());
+		assert_eq!(TransactionPayment::pending_rebates(&ALICE), fee / 2);
+
+		let _ = PalletBalances::deposit_creating(&TreasuryAccount::get(), 10000);
+		assert_ok!(TransactionPayment::claim_rebate(Origin::signed(ALICE)));
+		assert_eq!(TransactionPayment::pending_rebates(&ALICE), 0);
+		assert_eq!(PalletBalances::free_balance(&ALICE), 100000 - fee + fee / 2);
+		let claimed_event = Event::transaction_payment(crate::Event::RebateClaimed(ALICE, fee / 2));
+		assert!(System::events().iter().any(|record| record.event == claimed_event));
+	});
+}
+
+#[test]
+fn query_fee_in_currency_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		let xt = TestXt::new(CALL2.clone(), Some((111111, ())));
+		let info = xt.get_dispatch_info();
+		let len = xt.encode().len() as u32;
+		let fee = len as u128 * 2 + info.weight as u128; // len * byte + weight
+
+		assert_eq!(TransactionPayment::query_fee_in_currency(&xt.call, len, ACA), Some(fee));
+		assert_eq!(TransactionPayment::query_fee_in_currency(&xt.call, len, DOT), None);
+
+		// EVM calls are priced by the EVM fee multiplier
+		NextEvmFeeMultiplier::<Runtime>::put(Multiplier::saturating_from_integer(2));
+		let evm_call = Call::EVM(module_evm::Call::call(Default::default(), vec![], 0, 1000, 0));
+		let evm_weight = evm_call.get_dispatch_info().weight;
+		assert_eq!(
+			TransactionPayment::query_fee_in_currency(&evm_call, len, ACA),
+			Some(len as u128 * 2 + evm_weight.min(BlockWeights::get().max_block) as u128 * 2)
+		);
+
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			ACA,
+			AUSD,
+			10000,
+			1000,
+			false
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(ALICE),
+			DOT,
+			AUSD,
+			100,
+			1000,
+			false
+		));
+		let (_, supply_amount) = TransactionPayment::best_fee_swap_path(DOT, fee).unwrap();
+		assert_eq!(
+			TransactionPayment::query_fee_in_currency(&xt.call, len, DOT),
+			Some(supply_amount)
+		);
+	});
+}
+
 #[test]
 fn query_info_works() {
 	ExtBuilder::default()
@@ -219,7 +1014,7 @@ fn query_info_works() {
 			NextFeeMultiplier::<Runtime>::put(Multiplier::saturating_from_rational(3, 2));
 
 			assert_eq!(
-				TransactionPayment::query_info(xt, len),
+				TransactionPayment::query_info(&xt.call, len),
 				RuntimeDispatchInfo {
 					weight: info.weight,
 					class: info.class,
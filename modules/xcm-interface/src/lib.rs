@@ -0,0 +1,1171 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # XCM Interface Module
+//!
+//! ## Overview
+//!
+//! XCM interface module provides cross-chain transfers with remote
+//! execution. The transferred asset is deposited to the account derived from
+//! the sender on the destination, which is the location of the sender
+//! relative to the destination, and then the call built from the call
+//! template of the destination is dispatched by the derived account, to
+//! deposit the asset into the target pallet for the recipient immediately,
+//! such as a DEX swap or a Homa mint. The call is never dispatched by the
+//! sovereign account of this chain, so the sender can only act as itself on
+//! the destination. The outcome of the call is not reported back to this
+//! chain, if it fails the asset is kept in the derived account and can be
+//! withdrawn back to the sender by `withdraw_remote_deposit`.
+//!
+//! The outbound transfers of each currency can be capped by governance in a
+//! rolling window of blocks, to bound the loss if the mapped asset or the
+//! remote chain is compromised. The cap is consumed by `LimitOutboundTransfer`
+//! when the asset is withdrawn from a local account by XCM, which covers all
+//! outbound transfers including XTokens.
+//!
+//! The inbound deposits failed by the asset transactor, such as the asset
+//! without mapping or below the existential deposit, are trapped instead of
+//! dropped by `TrapFailedDeposit`. The trapped assets can be claimed by the
+//! beneficiary once the deposit is possible, or recovered by governance. Only
+//! the fungible assets not below `MinTrappedAmount` are trapped, to prevent
+//! spamming the storage with dust deposits.
+//!
+//! The relay chain sub-accounts derived from the sovereign account of this
+//! chain, which are used by Homa, are managed by governance by dispatching
+//! the proxy calls via `Transact` on the relay chain. The outcome of
+//! `Transact` is not reported back to this chain, so each call is recorded as
+//! the pending operation of the sub-account, and applied to the proxies and
+//! the status tracked on this chain only after governance reconciles it with
+//! the outcome on the relay chain.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::Compact;
+use frame_support::{pallet_prelude::*, transactional};
+use frame_system::pallet_prelude::*;
+use orml_xcm_support::XcmHandler;
+use primitives::{Balance, CurrencyId};
+use sp_runtime::{
+	traits::{Convert, Saturating, UniqueSaturatedInto, Zero},
+	FixedPointNumber, FixedU128, RuntimeDebug,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+use xcm::v0::{
+	Error as XcmError,
+	Junction::{AccountId32, Parachain, Parent},
+	MultiAsset,
+	MultiLocation::{self, X1, X2, X3},
+	NetworkId, Order, OriginKind, Result as XcmResult, Xcm,
+};
+use xcm_executor::{
+	traits::{LocationConversion, TransactAsset},
+	Assets,
+};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+/// The template to build the call dispatched on the destination.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct CallTemplate {
+	/// The encoded pallet index, call index and leading arguments of the
+	/// call, the recipient and the compact received amount are appended as
+	/// the last arguments.
+	pub call_prefix: Vec<u8>,
+	/// The max weight to dispatch the call on the destination.
+	pub require_weight_at_most: u64,
+}
+
+/// The cap of outbound transfers of a currency.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct TransferLimit<BlockNumber> {
+	/// The max amount transferred out in `window`.
+	pub amount: Balance,
+	/// The number of blocks of the rolling window.
+	pub window: BlockNumber,
+}
+
+/// The status of the relay chain sub-account.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum SubAccountStatus {
+	/// The sub-account is managed by the proxies.
+	Active,
+	/// All proxies of the sub-account have been removed, new proxies must be
+	/// added before using it.
+	Recovered,
+}
+
+/// The proxy management operation dispatched by the relay chain
+/// sub-account.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub enum SubAccountOperation<AccountId> {
+	/// Add the proxy of the delegate and the proxy type.
+	AddProxy(AccountId, u8),
+	/// Remove the proxy of the delegate and the proxy type.
+	RemoveProxy(AccountId, u8),
+	/// Replace the old proxy by the new proxy, as the delegate and the
+	/// proxy type of each.
+	RotateProxy((AccountId, u8), (AccountId, u8)),
+	/// Create the anonymous proxy of the proxy type and the disambiguation
+	/// index.
+	CreateAnonymousProxy(u8, u16),
+	/// Remove all proxies.
+	RemoveProxies,
+}
+
+/// The metadata of the relay chain sub-account derived from the sovereign
+/// account of this chain.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct SubAccountInfo<AccountId, BlockNumber> {
+	/// The status of the sub-account.
+	pub status: SubAccountStatus,
+	/// The proxies of the sub-account and their proxy types.
+	pub proxies: Vec<(AccountId, u8)>,
+	/// The anonymous proxies created by the sub-account, as the anonymous
+	/// account, the proxy type and the disambiguation index.
+	pub anonymous_proxies: Vec<(AccountId, u8, u16)>,
+	/// The operation dispatched on the relay chain and not reconciled yet.
+	pub pending_operation: Option<SubAccountOperation<AccountId>>,
+	/// The block number the sub-account was last updated.
+	pub updated_at: BlockNumber,
+}
+
+/// The inbound asset failed to deposit.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq)]
+pub struct TrappedAsset {
+	pub asset: MultiAsset,
+	pub beneficiary: MultiLocation,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The origin which may update the call templates and the transfer
+		/// limits, and recover trapped assets.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Convert `CurrencyId` to `MultiLocation`.
+		type CurrencyIdConvert: Convert<CurrencyId, Option<MultiLocation>>;
+
+		/// Convert `AccountId` to `[u8; 32]`.
+		type AccountId32Convert: Convert<Self::AccountId, [u8; 32]>;
+
+		/// The location of this chain.
+		type SelfLocation: Get<MultiLocation>;
+
+		/// The XCM handler to execute XCM.
+		type XcmHandler: XcmHandler<Self::AccountId>;
+
+		/// The asset transactor to deposit the trapped assets.
+		type AssetTransactor: TransactAsset;
+
+		/// Convert `MultiLocation` to `AccountId`, to check the beneficiary
+		/// of trapped assets.
+		type LocationConverter: LocationConversion<Self::AccountId>;
+
+		/// The max length of the call prefix of call templates.
+		#[pallet::constant]
+		type MaxCallPrefixLength: Get<u32>;
+
+		/// The min amount of the inbound fungible asset to be trapped, the
+		/// failed deposits below it are rejected to bound the trapped assets.
+		#[pallet::constant]
+		type MinTrappedAmount: Get<Balance>;
+
+		/// The origin which may manage the relay chain sub-accounts.
+		type SubAccountOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The account paying the relay chain fee of the sub-account
+		/// management calls.
+		#[pallet::constant]
+		type RelayChainFeePayer: Get<Self::AccountId>;
+
+		/// The relay chain fee of each sub-account management call.
+		#[pallet::constant]
+		type RelayChainTransactFee: Get<Balance>;
+
+		/// The max weight to dispatch the sub-account management call on the
+		/// relay chain.
+		#[pallet::constant]
+		type RelayChainTransactWeight: Get<Weight>;
+
+		/// The index of the utility pallet on the relay chain.
+		#[pallet::constant]
+		type RelayChainUtilityPalletIndex: Get<u8>;
+
+		/// The index of the proxy pallet on the relay chain.
+		#[pallet::constant]
+		type RelayChainProxyPalletIndex: Get<u8>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The call template is not found
+		CallTemplateNotFound,
+		/// The call prefix is too long
+		CallPrefixTooLong,
+		/// The currency is not the cross-chain asset reserved by this chain
+		NotSelfReserveAsset,
+		/// The fee on the destination is not less than the amount
+		InvalidDestFee,
+		/// The window of the transfer limit is zero
+		InvalidTransferLimit,
+		/// The transfer exceeds the transfer limit of the currency
+		TransferLimitExceeded,
+		/// The trapped asset is not found
+		TrappedAssetNotFound,
+		/// The origin is not the beneficiary of the trapped asset
+		NotBeneficiary,
+		/// Failed to deposit the trapped asset
+		DepositFailed,
+		/// The asset is not fungible or below the min trapped amount
+		BelowMinTrappedAmount,
+		/// The trapped asset index overflowed
+		TrappedAssetIndexOverflow,
+		/// The self location is not a parachain of the relay chain
+		InvalidSelfLocation,
+		/// The amount to withdraw is zero
+		ZeroWithdrawAmount,
+		/// The sub-account is not found
+		SubAccountNotFound,
+		/// The proxy of the sub-account already exists
+		ProxyExisted,
+		/// The proxy of the sub-account is not found
+		ProxyNotFound,
+		/// The anonymous proxy of the sub-account already exists
+		AnonymousProxyExisted,
+		/// The sub-account has an operation not reconciled yet
+		OperationPending,
+		/// The sub-account has no operation to reconcile
+		NoPendingOperation,
+		/// The account of the created anonymous proxy is not provided
+		AnonymousProxyRequired,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Call template updated. \[para_id, template_id\]
+		CallTemplateUpdated(u32, u32),
+		/// Transferred asset and called on the destination. \[sender,
+		/// currency_id, amount, para_id, template_id, recipient\]
+		TransferredAndCalled(T::AccountId, CurrencyId, Balance, u32, u32, T::AccountId),
+		/// Withdrawal of the asset deposited to the account derived from the
+		/// sender on the destination initiated. \[sender, currency_id,
+		/// amount, para_id\]
+		RemoteDepositWithdrawn(T::AccountId, CurrencyId, Balance, u32),
+		/// Transfer limit updated. \[currency_id\]
+		TransferLimitUpdated(CurrencyId),
+		/// Transfer throttled by the transfer limit. \[sender, currency_id,
+		/// amount\]
+		TransferThrottled(T::AccountId, CurrencyId, Balance),
+		/// Inbound asset failed to deposit and trapped. \[index, asset,
+		/// beneficiary\]
+		AssetTrapped(u32, MultiAsset, MultiLocation),
+		/// Trapped asset claimed by the beneficiary. \[index, beneficiary\]
+		TrappedAssetClaimed(u32, T::AccountId),
+		/// Trapped asset recovered by governance. \[index, dest\]
+		TrappedAssetRecovered(u32, MultiLocation),
+		/// Proxy added to the relay chain sub-account. \[index, delegate,
+		/// proxy_type\]
+		SubAccountProxyAdded(u16, T::AccountId, u8),
+		/// Proxy removed from the relay chain sub-account. \[index,
+		/// delegate, proxy_type\]
+		SubAccountProxyRemoved(u16, T::AccountId, u8),
+		/// Anonymous proxy created by the relay chain sub-account. \[index,
+		/// anonymous_proxy, proxy_type, anonymous_index\]
+		SubAccountAnonymousProxyCreated(u16, T::AccountId, u8, u16),
+		/// All proxies of the relay chain sub-account removed. \[index\]
+		SubAccountRecovered(u16),
+		/// Operation of the relay chain sub-account dispatched via
+		/// `Transact`, pending to be reconciled. \[index, operation\]
+		SubAccountOperationDispatched(u16, SubAccountOperation<T::AccountId>),
+		/// Operation of the relay chain sub-account failed on the relay
+		/// chain and discarded. \[index, operation\]
+		SubAccountOperationFailed(u16, SubAccountOperation<T::AccountId>),
+	}
+
+	/// The call templates of destination parachains.
+	///
+	/// CallTemplates: double_map ParaId, TemplateId => Option<CallTemplate>
+	#[pallet::storage]
+	#[pallet::getter(fn call_templates)]
+	pub type CallTemplates<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u32, Twox64Concat, u32, CallTemplate, OptionQuery>;
+
+	/// The transfer limits of currencies.
+	///
+	/// TransferLimits: map CurrencyId => Option<TransferLimit>
+	#[pallet::storage]
+	#[pallet::getter(fn transfer_limits)]
+	pub type TransferLimits<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, TransferLimit<T::BlockNumber>, OptionQuery>;
+
+	/// The amount transferred out in the rolling window and the block it was
+	/// last updated.
+	///
+	/// OutboundTransfers: map CurrencyId => (BlockNumber, Balance)
+	#[pallet::storage]
+	#[pallet::getter(fn outbound_transfers)]
+	pub type OutboundTransfers<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, (T::BlockNumber, Balance), ValueQuery>;
+
+	/// Next index of trapped assets.
+	///
+	/// NextTrappedAssetIndex: u32
+	#[pallet::storage]
+	#[pallet::getter(fn next_trapped_asset_index)]
+	pub type NextTrappedAssetIndex<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The trapped inbound assets.
+	///
+	/// TrappedAssets: map u32 => Option<TrappedAsset>
+	#[pallet::storage]
+	#[pallet::getter(fn trapped_assets)]
+	pub type TrappedAssets<T: Config> = StorageMap<_, Twox64Concat, u32, TrappedAsset, OptionQuery>;
+
+	/// The metadata of the relay chain sub-accounts.
+	///
+	/// SubAccounts: map u16 => Option<SubAccountInfo>
+	#[pallet::storage]
+	#[pallet::getter(fn sub_accounts)]
+	pub type SubAccounts<T: Config> =
+		StorageMap<_, Twox64Concat, u16, SubAccountInfo<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set or remove the call template of the destination parachain.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::set_call_template())]
+		#[transactional]
+		pub fn set_call_template(
+			origin: OriginFor<T>,
+			para_id: u32,
+			template_id: u32,
+			template: Option<CallTemplate>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			if let Some(template) = template {
+				ensure!(
+					template.call_prefix.len() <= T::MaxCallPrefixLength::get() as usize,
+					Error::<T>::CallPrefixTooLong
+				);
+				CallTemplates::<T>::insert(para_id, template_id, template);
+			} else {
+				CallTemplates::<T>::remove(para_id, template_id);
+			}
+
+			Self::deposit_event(Event::CallTemplateUpdated(para_id, template_id));
+			Ok(().into())
+		}
+
+		/// Set or remove the transfer limit of `currency_id`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::set_transfer_limit())]
+		#[transactional]
+		pub fn set_transfer_limit(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			limit: Option<TransferLimit<T::BlockNumber>>,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			if let Some(limit) = limit {
+				ensure!(!limit.window.is_zero(), Error::<T>::InvalidTransferLimit);
+				TransferLimits::<T>::insert(currency_id, limit);
+			} else {
+				TransferLimits::<T>::remove(currency_id);
+				OutboundTransfers::<T>::remove(currency_id);
+			}
+
+			Self::deposit_event(Event::TransferLimitUpdated(currency_id));
+			Ok(().into())
+		}
+
+		/// Claim the trapped asset `index` to its beneficiary.
+		///
+		/// The dispatch origin of this call must be the beneficiary.
+		#[pallet::weight(<T as Config>::WeightInfo::claim_trapped_asset())]
+		#[transactional]
+		pub fn claim_trapped_asset(origin: OriginFor<T>, index: u32) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let trapped = Self::trapped_assets(index).ok_or(Error::<T>::TrappedAssetNotFound)?;
+			ensure!(
+				T::LocationConverter::from_location(&trapped.beneficiary).as_ref() == Some(&who),
+				Error::<T>::NotBeneficiary
+			);
+
+			T::AssetTransactor::deposit_asset(&trapped.asset, &trapped.beneficiary)
+				.map_err(|_| Error::<T>::DepositFailed)?;
+			TrappedAssets::<T>::remove(index);
+
+			Self::deposit_event(Event::TrappedAssetClaimed(index, who));
+			Ok(().into())
+		}
+
+		/// Deposit the trapped asset `index` to `dest`.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::recover_trapped_asset())]
+		#[transactional]
+		pub fn recover_trapped_asset(
+			origin: OriginFor<T>,
+			index: u32,
+			dest: MultiLocation,
+		) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			let trapped = Self::trapped_assets(index).ok_or(Error::<T>::TrappedAssetNotFound)?;
+
+			T::AssetTransactor::deposit_asset(&trapped.asset, &dest).map_err(|_| Error::<T>::DepositFailed)?;
+			TrappedAssets::<T>::remove(index);
+
+			Self::deposit_event(Event::TrappedAssetRecovered(index, dest));
+			Ok(().into())
+		}
+
+		/// Transfer `amount` of `currency_id` to the parachain `para_id`, and
+		/// dispatch the call built from the call template `template_id` for
+		/// `recipient` on it. `dest_fee` of the amount is used to buy the
+		/// execution on the destination, the rest is deposited to the account
+		/// derived from the sender and deposited by the call dispatched by
+		/// the derived account, and the unused fee is deposited to
+		/// `recipient`.
+		///
+		/// Fails with `TransferLimitExceeded` if the transfer limit of
+		/// `currency_id` is exceeded.
+		#[pallet::weight(<T as Config>::WeightInfo::transfer_and_call())]
+		pub fn transfer_and_call(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
+			#[pallet::compact] dest_fee: Balance,
+			para_id: u32,
+			template_id: u32,
+			recipient: T::AccountId,
+			dest_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let result = Self::do_transfer_and_call(
+				who.clone(),
+				currency_id,
+				amount,
+				dest_fee,
+				para_id,
+				template_id,
+				recipient,
+				dest_weight,
+			);
+			// the call is not transactional to keep the throttled event
+			if result == Err(Error::<T>::TransferLimitExceeded.into()) {
+				Self::deposit_event(Event::TransferThrottled(who, currency_id, amount));
+			}
+			result?;
+			Ok(().into())
+		}
+
+		/// Withdraw `amount` of `currency_id` from the account derived from
+		/// the sender on the parachain `para_id` back to the sender, such as
+		/// the asset left by a failed call of `transfer_and_call`. `dest_fee`
+		/// of `currency_id` is transferred from the sender to buy the
+		/// execution on the destination, and the unused fee is kept in the
+		/// derived account.
+		#[pallet::weight(<T as Config>::WeightInfo::withdraw_remote_deposit())]
+		#[transactional]
+		pub fn withdraw_remote_deposit(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
+			#[pallet::compact] dest_fee: Balance,
+			para_id: u32,
+			dest_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroWithdrawAmount);
+
+			let asset_location = T::CurrencyIdConvert::convert(currency_id)
+				.filter(|location| Self::is_self_reserve(location))
+				.ok_or(Error::<T>::NotSelfReserveAsset)?;
+			let xcm = Self::withdraw_remote_deposit_xcm(
+				asset_location,
+				amount,
+				dest_fee,
+				para_id,
+				T::AccountId32Convert::convert(who.clone()),
+				dest_weight,
+			)?;
+			T::XcmHandler::execute_xcm(who.clone(), xcm)?;
+
+			Self::deposit_event(Event::RemoteDepositWithdrawn(who, currency_id, amount, para_id));
+			Ok(().into())
+		}
+
+		/// Add `delegate` as the proxy of `proxy_type` to the relay chain
+		/// sub-account `index`.
+		///
+		/// The dispatch origin of this call must be `SubAccountOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::add_sub_account_proxy())]
+		#[transactional]
+		pub fn add_sub_account_proxy(
+			origin: OriginFor<T>,
+			index: u16,
+			delegate: T::AccountId,
+			proxy_type: u8,
+		) -> DispatchResultWithPostInfo {
+			T::SubAccountOrigin::ensure_origin(origin)?;
+			Self::dispatch_operation(index, SubAccountOperation::AddProxy(delegate, proxy_type))?;
+			Ok(().into())
+		}
+
+		/// Remove the proxy `delegate` of `proxy_type` from the relay chain
+		/// sub-account `index`.
+		///
+		/// The dispatch origin of this call must be `SubAccountOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::remove_sub_account_proxy())]
+		#[transactional]
+		pub fn remove_sub_account_proxy(
+			origin: OriginFor<T>,
+			index: u16,
+			delegate: T::AccountId,
+			proxy_type: u8,
+		) -> DispatchResultWithPostInfo {
+			T::SubAccountOrigin::ensure_origin(origin)?;
+			Self::dispatch_operation(index, SubAccountOperation::RemoveProxy(delegate, proxy_type))?;
+			Ok(().into())
+		}
+
+		/// Replace the proxy `old` of the relay chain sub-account `index` by
+		/// `new` in a batch, to rotate the delegate or change the proxy type.
+		///
+		/// The dispatch origin of this call must be `SubAccountOrigin`.
+		///
+		/// - `old`: the delegate and the proxy type of the proxy to remove.
+		/// - `new`: the delegate and the proxy type of the proxy to add.
+		#[pallet::weight(<T as Config>::WeightInfo::rotate_sub_account_proxy())]
+		#[transactional]
+		pub fn rotate_sub_account_proxy(
+			origin: OriginFor<T>,
+			index: u16,
+			old: (T::AccountId, u8),
+			new: (T::AccountId, u8),
+		) -> DispatchResultWithPostInfo {
+			T::SubAccountOrigin::ensure_origin(origin)?;
+			Self::dispatch_operation(index, SubAccountOperation::RotateProxy(old, new))?;
+			Ok(().into())
+		}
+
+		/// Create an anonymous proxy of `proxy_type` by the relay chain
+		/// sub-account `index`.
+		///
+		/// The dispatch origin of this call must be `SubAccountOrigin`.
+		///
+		/// - `anonymous_index`: the disambiguation index of the anonymous
+		///   proxy.
+		#[pallet::weight(<T as Config>::WeightInfo::create_sub_account_anonymous_proxy())]
+		#[transactional]
+		pub fn create_sub_account_anonymous_proxy(
+			origin: OriginFor<T>,
+			index: u16,
+			proxy_type: u8,
+			anonymous_index: u16,
+		) -> DispatchResultWithPostInfo {
+			T::SubAccountOrigin::ensure_origin(origin)?;
+			Self::dispatch_operation(
+				index,
+				SubAccountOperation::CreateAnonymousProxy(proxy_type, anonymous_index),
+			)?;
+			Ok(().into())
+		}
+
+		/// Remove all proxies of the relay chain sub-account `index`, to
+		/// recover it from compromised proxies.
+		///
+		/// The dispatch origin of this call must be `SubAccountOrigin`.
+		#[pallet::weight(<T as Config>::WeightInfo::recover_sub_account())]
+		#[transactional]
+		pub fn recover_sub_account(origin: OriginFor<T>, index: u16) -> DispatchResultWithPostInfo {
+			T::SubAccountOrigin::ensure_origin(origin)?;
+			Self::dispatch_operation(index, SubAccountOperation::RemoveProxies)?;
+			Ok(().into())
+		}
+
+		/// Reconcile the pending operation of the relay chain sub-account
+		/// `index` with its outcome on the relay chain. The operation is
+		/// applied to the tracked proxies and status if `succeeded`, or
+		/// discarded otherwise.
+		///
+		/// The dispatch origin of this call must be `SubAccountOrigin`.
+		///
+		/// - `anonymous_proxy`: the account of the anonymous proxy created on
+		///   the relay chain, required if the pending operation creates an
+		///   anonymous proxy and succeeded.
+		#[pallet::weight(<T as Config>::WeightInfo::reconcile_sub_account())]
+		#[transactional]
+		pub fn reconcile_sub_account(
+			origin: OriginFor<T>,
+			index: u16,
+			succeeded: bool,
+			anonymous_proxy: Option<T::AccountId>,
+		) -> DispatchResultWithPostInfo {
+			T::SubAccountOrigin::ensure_origin(origin)?;
+
+			SubAccounts::<T>::try_mutate(index, |maybe_info| -> DispatchResult {
+				let info = maybe_info.as_mut().ok_or(Error::<T>::SubAccountNotFound)?;
+				let operation = info.pending_operation.take().ok_or(Error::<T>::NoPendingOperation)?;
+				if succeeded {
+					Self::apply_operation(index, info, operation, anonymous_proxy)?;
+				} else {
+					Self::deposit_event(Event::SubAccountOperationFailed(index, operation));
+				}
+				info.updated_at = frame_system::Pallet::<T>::block_number();
+				Ok(())
+			})?;
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Dispatch `operation` by the sub-account `index` on the relay chain,
+	/// and record it as the pending operation of the sub-account. The
+	/// sub-account is created if the operation adds a proxy.
+	fn dispatch_operation(index: u16, operation: SubAccountOperation<T::AccountId>) -> DispatchResult {
+		SubAccounts::<T>::try_mutate(index, |maybe_info| -> DispatchResult {
+			let now = frame_system::Pallet::<T>::block_number();
+			let adds_proxy = matches!(
+				operation,
+				SubAccountOperation::AddProxy(..) | SubAccountOperation::CreateAnonymousProxy(..)
+			);
+			if maybe_info.is_none() && adds_proxy {
+				*maybe_info = Some(SubAccountInfo {
+					status: SubAccountStatus::Active,
+					proxies: vec![],
+					anonymous_proxies: vec![],
+					pending_operation: None,
+					updated_at: now,
+				});
+			}
+			let info = maybe_info.as_mut().ok_or(Error::<T>::SubAccountNotFound)?;
+			ensure!(info.pending_operation.is_none(), Error::<T>::OperationPending);
+			Self::ensure_operation_valid(info, &operation)?;
+			info.pending_operation = Some(operation.clone());
+			info.updated_at = now;
+			Ok(())
+		})?;
+
+		let call = match &operation {
+			SubAccountOperation::AddProxy(delegate, proxy_type) => Self::add_proxy_call(delegate, *proxy_type),
+			SubAccountOperation::RemoveProxy(delegate, proxy_type) => Self::remove_proxy_call(delegate, *proxy_type),
+			// add the new proxy first to keep the sub-account managed
+			SubAccountOperation::RotateProxy(old, new) => Self::batch_all_call(vec![
+				Self::add_proxy_call(&new.0, new.1),
+				Self::remove_proxy_call(&old.0, old.1),
+			]),
+			SubAccountOperation::CreateAnonymousProxy(proxy_type, anonymous_index) => {
+				Self::anonymous_call(*proxy_type, *anonymous_index)
+			}
+			SubAccountOperation::RemoveProxies => Self::remove_proxies_call(),
+		};
+		Self::transact_as_sub_account(index, call)?;
+
+		Self::deposit_event(Event::SubAccountOperationDispatched(index, operation));
+		Ok(())
+	}
+
+	/// Ensure `operation` can be applied to the tracked proxies of the
+	/// sub-account.
+	fn ensure_operation_valid(
+		info: &SubAccountInfo<T::AccountId, T::BlockNumber>,
+		operation: &SubAccountOperation<T::AccountId>,
+	) -> DispatchResult {
+		match operation {
+			SubAccountOperation::AddProxy(delegate, proxy_type) => {
+				ensure!(
+					!info.proxies.contains(&(delegate.clone(), *proxy_type)),
+					Error::<T>::ProxyExisted
+				);
+			}
+			SubAccountOperation::RemoveProxy(delegate, proxy_type) => {
+				ensure!(
+					info.proxies.contains(&(delegate.clone(), *proxy_type)),
+					Error::<T>::ProxyNotFound
+				);
+			}
+			SubAccountOperation::RotateProxy(old, new) => {
+				ensure!(info.proxies.contains(old), Error::<T>::ProxyNotFound);
+				ensure!(!info.proxies.contains(new), Error::<T>::ProxyExisted);
+			}
+			SubAccountOperation::CreateAnonymousProxy(proxy_type, anonymous_index) => {
+				ensure!(
+					!info
+						.anonymous_proxies
+						.iter()
+						.any(|(_, t, i)| t == proxy_type && i == anonymous_index),
+					Error::<T>::AnonymousProxyExisted
+				);
+			}
+			SubAccountOperation::RemoveProxies => {}
+		}
+		Ok(())
+	}
+
+	/// Apply the succeeded `operation` to the tracked proxies and status of
+	/// the sub-account `index`.
+	fn apply_operation(
+		index: u16,
+		info: &mut SubAccountInfo<T::AccountId, T::BlockNumber>,
+		operation: SubAccountOperation<T::AccountId>,
+		anonymous_proxy: Option<T::AccountId>,
+	) -> DispatchResult {
+		match operation {
+			SubAccountOperation::AddProxy(delegate, proxy_type) => {
+				info.proxies.push((delegate.clone(), proxy_type));
+				info.status = SubAccountStatus::Active;
+				Self::deposit_event(Event::SubAccountProxyAdded(index, delegate, proxy_type));
+			}
+			SubAccountOperation::RemoveProxy(delegate, proxy_type) => {
+				info.proxies.retain(|proxy| proxy != &(delegate.clone(), proxy_type));
+				Self::deposit_event(Event::SubAccountProxyRemoved(index, delegate, proxy_type));
+			}
+			SubAccountOperation::RotateProxy(old, new) => {
+				info.proxies.retain(|proxy| proxy != &old);
+				info.proxies.push(new.clone());
+				info.status = SubAccountStatus::Active;
+				Self::deposit_event(Event::SubAccountProxyAdded(index, new.0, new.1));
+				Self::deposit_event(Event::SubAccountProxyRemoved(index, old.0, old.1));
+			}
+			SubAccountOperation::CreateAnonymousProxy(proxy_type, anonymous_index) => {
+				let anonymous_proxy = anonymous_proxy.ok_or(Error::<T>::AnonymousProxyRequired)?;
+				info.anonymous_proxies
+					.push((anonymous_proxy.clone(), proxy_type, anonymous_index));
+				Self::deposit_event(Event::SubAccountAnonymousProxyCreated(
+					index,
+					anonymous_proxy,
+					proxy_type,
+					anonymous_index,
+				));
+			}
+			SubAccountOperation::RemoveProxies => {
+				info.proxies.clear();
+				info.status = SubAccountStatus::Recovered;
+				Self::deposit_event(Event::SubAccountRecovered(index));
+			}
+		}
+		Ok(())
+	}
+
+	/// `proxy.add_proxy(delegate, proxy_type, 0)` on the relay chain.
+	fn add_proxy_call(delegate: &T::AccountId, proxy_type: u8) -> Vec<u8> {
+		(
+			T::RelayChainProxyPalletIndex::get(),
+			1u8,
+			T::AccountId32Convert::convert(delegate.clone()),
+			proxy_type,
+			0u32,
+		)
+			.encode()
+	}
+
+	/// `proxy.remove_proxy(delegate, proxy_type, 0)` on the relay chain.
+	fn remove_proxy_call(delegate: &T::AccountId, proxy_type: u8) -> Vec<u8> {
+		(
+			T::RelayChainProxyPalletIndex::get(),
+			2u8,
+			T::AccountId32Convert::convert(delegate.clone()),
+			proxy_type,
+			0u32,
+		)
+			.encode()
+	}
+
+	/// `proxy.remove_proxies()` on the relay chain.
+	fn remove_proxies_call() -> Vec<u8> {
+		(T::RelayChainProxyPalletIndex::get(), 3u8).encode()
+	}
+
+	/// `proxy.anonymous(proxy_type, 0, index)` on the relay chain.
+	fn anonymous_call(proxy_type: u8, index: u16) -> Vec<u8> {
+		(T::RelayChainProxyPalletIndex::get(), 4u8, proxy_type, 0u32, index).encode()
+	}
+
+	/// `utility.batch_all(calls)` on the relay chain.
+	fn batch_all_call(calls: Vec<Vec<u8>>) -> Vec<u8> {
+		let mut call = (T::RelayChainUtilityPalletIndex::get(), 2u8, Compact(calls.len() as u32)).encode();
+		calls.into_iter().for_each(|c| call.extend(c));
+		call
+	}
+
+	/// Dispatch `call` by the sub-account `index` derived from the
+	/// sovereign account of this chain on the relay chain, which is
+	/// `utility.as_derivative(index, call)` dispatched by the sovereign
+	/// account. The fee is paid by `RelayChainFeePayer`.
+	fn transact_as_sub_account(index: u16, call: Vec<u8>) -> DispatchResult {
+		let self_location = match T::SelfLocation::get() {
+			X2(Parent, Parachain { id }) => X1(Parachain { id }),
+			_ => return Err(Error::<T>::InvalidSelfLocation.into()),
+		};
+		let mut as_derivative = (T::RelayChainUtilityPalletIndex::get(), 1u8, index).encode();
+		as_derivative.extend(call);
+
+		let weight = T::RelayChainTransactWeight::get();
+		let xcm = Xcm::WithdrawAsset {
+			assets: vec![MultiAsset::ConcreteFungible {
+				id: X1(Parent),
+				amount: T::RelayChainTransactFee::get(),
+			}],
+			effects: vec![Order::InitiateReserveWithdraw {
+				assets: vec![MultiAsset::All],
+				reserve: X1(Parent),
+				effects: vec![
+					Order::BuyExecution {
+						fees: MultiAsset::All,
+						weight,
+						debt: weight,
+						halt_on_error: true,
+						xcm: vec![Xcm::Transact {
+							origin_type: OriginKind::SovereignAccount,
+							require_weight_at_most: weight,
+							call: as_derivative.into(),
+						}],
+					},
+					// refund the unused fee to the sovereign account of this chain
+					Order::DepositAsset {
+						assets: vec![MultiAsset::All],
+						dest: self_location,
+					},
+				],
+			}],
+		};
+		T::XcmHandler::execute_xcm(T::RelayChainFeePayer::get(), xcm)
+	}
+
+	/// Trap the inbound `asset` failed to deposit to `beneficiary`.
+	fn trap_asset(asset: MultiAsset, beneficiary: MultiLocation) -> DispatchResult {
+		match &asset {
+			MultiAsset::ConcreteFungible { amount, .. } | MultiAsset::AbstractFungible { amount, .. }
+				if *amount >= T::MinTrappedAmount::get() => {}
+			_ => return Err(Error::<T>::BelowMinTrappedAmount.into()),
+		}
+
+		let index = NextTrappedAssetIndex::<T>::try_mutate(|index| -> Result<u32, DispatchError> {
+			let current = *index;
+			*index = index.checked_add(1).ok_or(Error::<T>::TrappedAssetIndexOverflow)?;
+			Ok(current)
+		})?;
+		TrappedAssets::<T>::insert(
+			index,
+			TrappedAsset {
+				asset: asset.clone(),
+				beneficiary: beneficiary.clone(),
+			},
+		);
+		Self::deposit_event(Event::AssetTrapped(index, asset, beneficiary));
+		Ok(())
+	}
+
+	/// The outbound transfers of `currency_id` after transferring `amount`,
+	/// `None` if there is no transfer limit. The transferred amount is
+	/// released linearly through the window.
+	fn outbound_transfers_after(
+		currency_id: CurrencyId,
+		amount: Balance,
+	) -> Result<Option<(T::BlockNumber, Balance)>, DispatchError> {
+		if let Some(limit) = Self::transfer_limits(currency_id) {
+			let now = frame_system::Pallet::<T>::block_number();
+			let (last_updated, transferred) = Self::outbound_transfers(currency_id);
+			let elapsed: u128 = now.saturating_sub(last_updated).unique_saturated_into();
+			let window: u128 = limit.window.unique_saturated_into();
+			let released = FixedU128::saturating_from_rational(elapsed.min(window), window.max(1))
+				.saturating_mul_int(limit.amount);
+
+			let transferred = transferred.saturating_sub(released).saturating_add(amount);
+			ensure!(transferred <= limit.amount, Error::<T>::TransferLimitExceeded);
+			Ok(Some((now, transferred)))
+		} else {
+			Ok(None)
+		}
+	}
+
+	/// Consume `amount` of the transfer limit of `currency_id`.
+	fn try_consume_transfer_limit(currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+		if let Some(outbound_transfers) = Self::outbound_transfers_after(currency_id, amount)? {
+			OutboundTransfers::<T>::insert(currency_id, outbound_transfers);
+		}
+		Ok(())
+	}
+
+	#[transactional]
+	#[allow(clippy::too_many_arguments)]
+	fn do_transfer_and_call(
+		who: T::AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		dest_fee: Balance,
+		para_id: u32,
+		template_id: u32,
+		recipient: T::AccountId,
+		dest_weight: Weight,
+	) -> DispatchResult {
+		let template = Self::call_templates(para_id, template_id).ok_or(Error::<T>::CallTemplateNotFound)?;
+		ensure!(dest_fee < amount, Error::<T>::InvalidDestFee);
+
+		let asset_location = T::CurrencyIdConvert::convert(currency_id)
+			.filter(|location| Self::is_self_reserve(location))
+			.ok_or(Error::<T>::NotSelfReserveAsset)?;
+		// fail early, the limit is consumed by `LimitOutboundTransfer` when the
+		// asset is withdrawn
+		Self::outbound_transfers_after(currency_id, amount)?;
+
+		let xcm = Self::transfer_and_call_xcm(
+			asset_location,
+			amount,
+			dest_fee,
+			para_id,
+			template,
+			T::AccountId32Convert::convert(who.clone()),
+			T::AccountId32Convert::convert(recipient.clone()),
+			dest_weight,
+		)?;
+		T::XcmHandler::execute_xcm(who.clone(), xcm)?;
+
+		Self::deposit_event(Event::TransferredAndCalled(
+			who,
+			currency_id,
+			amount,
+			para_id,
+			template_id,
+			recipient,
+		));
+		Ok(())
+	}
+
+	/// Whether the asset at `location` is reserved by this chain.
+	fn is_self_reserve(location: &MultiLocation) -> bool {
+		match (location, T::SelfLocation::get()) {
+			(X3(Parent, Parachain { id }, _), X2(Parent, Parachain { id: self_id })) => *id == self_id,
+			_ => false,
+		}
+	}
+
+	/// The location of `sender` relative to the other parachains, which is
+	/// converted to the account derived from `sender` on them.
+	fn sender_location(sender: [u8; 32]) -> Result<MultiLocation, DispatchError> {
+		match T::SelfLocation::get() {
+			X2(Parent, Parachain { id }) => Ok(X3(
+				Parent,
+				Parachain { id },
+				AccountId32 {
+					network: NetworkId::Any,
+					id: sender,
+				},
+			)),
+			_ => Err(Error::<T>::InvalidSelfLocation.into()),
+		}
+	}
+
+	/// Build the XCM to transfer the self reserve asset to the parachain
+	/// `para_id` and dispatch the call built from `template` on it by the
+	/// account derived from `sender`.
+	#[allow(clippy::too_many_arguments)]
+	fn transfer_and_call_xcm(
+		asset_location: MultiLocation,
+		amount: Balance,
+		dest_fee: Balance,
+		para_id: u32,
+		template: CallTemplate,
+		sender: [u8; 32],
+		recipient: [u8; 32],
+		dest_weight: Weight,
+	) -> Result<Xcm, DispatchError> {
+		let sender_location = Self::sender_location(sender)?;
+		let deposit_amount = amount.saturating_sub(dest_fee);
+		let call = [
+			template.call_prefix,
+			recipient.encode(),
+			Compact(deposit_amount).encode(),
+		]
+		.concat();
+
+		Ok(Xcm::WithdrawAsset {
+			assets: vec![MultiAsset::ConcreteFungible {
+				id: asset_location.clone(),
+				amount,
+			}],
+			effects: vec![Order::DepositReserveAsset {
+				assets: vec![MultiAsset::All],
+				dest: X2(Parent, Parachain { id: para_id }),
+				effects: vec![
+					// deposit to the account derived from the sender, to be used by the call
+					Order::DepositAsset {
+						assets: vec![MultiAsset::ConcreteFungible {
+							id: asset_location,
+							amount: deposit_amount,
+						}],
+						dest: sender_location,
+					},
+					Order::BuyExecution {
+						fees: MultiAsset::All,
+						weight: template.require_weight_at_most,
+						debt: dest_weight,
+						halt_on_error: true,
+						// dispatch the call by the account derived from the sender
+						xcm: vec![Xcm::RelayedFrom {
+							who: X1(AccountId32 {
+								network: NetworkId::Any,
+								id: sender,
+							}),
+							message: Box::new(Xcm::Transact {
+								origin_type: OriginKind::SovereignAccount,
+								require_weight_at_most: template.require_weight_at_most,
+								call: call.into(),
+							}),
+						}],
+					},
+					// refund the unused fee to the recipient
+					Order::DepositAsset {
+						assets: vec![MultiAsset::All],
+						dest: X1(AccountId32 {
+							network: NetworkId::Any,
+							id: recipient,
+						}),
+					},
+				],
+			}],
+		})
+	}
+
+	/// Build the XCM to withdraw `amount` of the self reserve asset from the
+	/// account derived from `sender` on the parachain `para_id` back to
+	/// `sender`.
+	fn withdraw_remote_deposit_xcm(
+		asset_location: MultiLocation,
+		amount: Balance,
+		dest_fee: Balance,
+		para_id: u32,
+		sender: [u8; 32],
+		dest_weight: Weight,
+	) -> Result<Xcm, DispatchError> {
+		let sender_location = Self::sender_location(sender)?;
+		let sender_account = X1(AccountId32 {
+			network: NetworkId::Any,
+			id: sender,
+		});
+
+		Ok(Xcm::WithdrawAsset {
+			assets: vec![MultiAsset::ConcreteFungible {
+				id: asset_location.clone(),
+				amount: dest_fee,
+			}],
+			effects: vec![Order::DepositReserveAsset {
+				assets: vec![MultiAsset::All],
+				dest: X2(Parent, Parachain { id: para_id }),
+				effects: vec![
+					Order::BuyExecution {
+						fees: MultiAsset::All,
+						weight: dest_weight,
+						debt: dest_weight,
+						halt_on_error: true,
+						// withdraw by the account derived from the sender, and send back to this chain
+						xcm: vec![Xcm::RelayedFrom {
+							who: sender_account.clone(),
+							message: Box::new(Xcm::WithdrawAsset {
+								assets: vec![MultiAsset::ConcreteFungible {
+									id: asset_location,
+									amount,
+								}],
+								effects: vec![Order::InitiateReserveWithdraw {
+									assets: vec![MultiAsset::All],
+									reserve: T::SelfLocation::get(),
+									effects: vec![Order::DepositAsset {
+										assets: vec![MultiAsset::All],
+										dest: sender_account,
+									}],
+								}],
+							}),
+						}],
+					},
+					// keep the unused fee in the account derived from the sender
+					Order::DepositAsset {
+						assets: vec![MultiAsset::All],
+						dest: sender_location,
+					},
+				],
+			}],
+		})
+	}
+}
+
+/// The asset transactor which traps the failed deposits of `Transactor`.
+pub struct TrapFailedDeposit<T, Transactor>(PhantomData<(T, Transactor)>);
+
+impl<T: Config, Transactor: TransactAsset> TransactAsset for TrapFailedDeposit<T, Transactor> {
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> XcmResult {
+		Transactor::deposit_asset(what, who).or_else(|e| {
+			// the failed deposit is rejected if it can't be trapped
+			Pallet::<T>::trap_asset(what.clone(), who.clone()).map_err(|_| e)
+		})
+	}
+
+	fn withdraw_asset(what: &MultiAsset, who: &MultiLocation) -> Result<Assets, XcmError> {
+		Transactor::withdraw_asset(what, who)
+	}
+}
+
+/// The asset transactor which consumes the transfer limits of the assets
+/// withdrawn from the local accounts by `Transactor`.
+pub struct LimitOutboundTransfer<T, CurrencyIdConvert, Transactor>(PhantomData<(T, CurrencyIdConvert, Transactor)>);
+
+impl<T: Config, CurrencyIdConvert: Convert<MultiAsset, Option<CurrencyId>>, Transactor: TransactAsset> TransactAsset
+	for LimitOutboundTransfer<T, CurrencyIdConvert, Transactor>
+{
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> XcmResult {
+		Transactor::deposit_asset(what, who)
+	}
+
+	fn withdraw_asset(what: &MultiAsset, who: &MultiLocation) -> Result<Assets, XcmError> {
+		if let (X1(AccountId32 { .. }), MultiAsset::ConcreteFungible { amount, .. }) = (who, what) {
+			if let Some(currency_id) = CurrencyIdConvert::convert(what.clone()) {
+				Pallet::<T>::try_consume_transfer_limit(currency_id, *amount)
+					.map_err(|_| XcmError::FailedToTransactAsset("transfer limit exceeded"))?;
+			}
+		}
+		Transactor::withdraw_asset(what, who)
+	}
+}
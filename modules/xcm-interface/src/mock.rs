@@ -0,0 +1,249 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mocks for the xcm interface module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use primitives::TokenSymbol;
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, AccountId32, DispatchError, DispatchResult};
+use sp_std::cell::RefCell;
+use xcm::v0::Junction;
+
+pub type AccountId = AccountId32;
+pub type BlockNumber = u64;
+
+pub const ALICE: AccountId = AccountId32::new([1u8; 32]);
+pub const BOB: AccountId = AccountId32::new([2u8; 32]);
+pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
+pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
+pub const SELF_PARA_ID: u32 = 2000;
+pub const SIBLING_PARA_ID: u32 = 3000;
+
+mod xcm_interface {
+	pub use super::super::*;
+}
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+	type Origin = Origin;
+	type Index = u64;
+	type BlockNumber = BlockNumber;
+	type Call = Call;
+	type Hash = H256;
+	type Hashing = ::sp_runtime::traits::BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type DbWeight = ();
+	type BaseCallFilter = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+pub struct CurrencyIdConvert;
+impl Convert<CurrencyId, Option<MultiLocation>> for CurrencyIdConvert {
+	fn convert(id: CurrencyId) -> Option<MultiLocation> {
+		match id {
+			DOT => Some(X1(Parent)),
+			AUSD => Some(X3(
+				Parent,
+				Parachain { id: SELF_PARA_ID },
+				Junction::GeneralKey(id.encode()),
+			)),
+			_ => None,
+		}
+	}
+}
+impl Convert<MultiAsset, Option<CurrencyId>> for CurrencyIdConvert {
+	fn convert(asset: MultiAsset) -> Option<CurrencyId> {
+		match asset {
+			MultiAsset::ConcreteFungible { id: X1(Parent), .. } => Some(DOT),
+			MultiAsset::ConcreteFungible {
+				id: X3(Parent, Parachain { id: SELF_PARA_ID }, Junction::GeneralKey(_)),
+				..
+			} => Some(AUSD),
+			_ => None,
+		}
+	}
+}
+
+pub struct AccountId32Convert;
+impl Convert<AccountId, [u8; 32]> for AccountId32Convert {
+	fn convert(account_id: AccountId) -> [u8; 32] {
+		account_id.into()
+	}
+}
+
+thread_local! {
+	static EXECUTED_XCM: RefCell<Vec<(AccountId, Xcm)>> = RefCell::new(vec![]);
+}
+
+pub fn executed_xcm() -> Vec<(AccountId, Xcm)> {
+	EXECUTED_XCM.with(|v| v.borrow().clone())
+}
+
+/// Withdraws the assets of `WithdrawAsset` from `origin` by
+/// `LimitOutboundTransfer`, the rest of XCM is not executed.
+pub struct MockXcmHandler;
+impl XcmHandler<AccountId> for MockXcmHandler {
+	fn execute_xcm(origin: AccountId, xcm: Xcm) -> DispatchResult {
+		if let Xcm::WithdrawAsset { assets, .. } = &xcm {
+			let who = X1(Junction::AccountId32 {
+				network: NetworkId::Any,
+				id: origin.clone().into(),
+			});
+			for asset in assets {
+				LimitOutboundTransfer::<Runtime, CurrencyIdConvert, MockAssetTransactor>::withdraw_asset(asset, &who)
+					.map_err(|_| DispatchError::Other("xcm execution failed"))?;
+			}
+		}
+		EXECUTED_XCM.with(|v| v.borrow_mut().push((origin, xcm)));
+		Ok(())
+	}
+}
+
+thread_local! {
+	static MIN_DEPOSIT: RefCell<Balance> = RefCell::new(10);
+	static DEPOSITED_ASSETS: RefCell<Vec<(MultiAsset, MultiLocation)>> = RefCell::new(vec![]);
+}
+
+pub fn set_min_deposit(amount: Balance) {
+	MIN_DEPOSIT.with(|v| *v.borrow_mut() = amount);
+}
+
+pub fn deposited_assets() -> Vec<(MultiAsset, MultiLocation)> {
+	DEPOSITED_ASSETS.with(|v| v.borrow().clone())
+}
+
+/// Fails to deposit the amount below `MIN_DEPOSIT`.
+pub struct MockAssetTransactor;
+impl TransactAsset for MockAssetTransactor {
+	fn deposit_asset(what: &MultiAsset, who: &MultiLocation) -> XcmResult {
+		match what {
+			MultiAsset::ConcreteFungible { amount, .. } if *amount >= MIN_DEPOSIT.with(|v| *v.borrow()) => {
+				DEPOSITED_ASSETS.with(|v| v.borrow_mut().push((what.clone(), who.clone())));
+				Ok(())
+			}
+			_ => Err(XcmError::FailedToTransactAsset("below minimal deposit")),
+		}
+	}
+
+	fn withdraw_asset(what: &MultiAsset, _who: &MultiLocation) -> Result<Assets, XcmError> {
+		Ok(what.clone().into())
+	}
+}
+
+pub struct AccountId32Converter;
+impl LocationConversion<AccountId> for AccountId32Converter {
+	fn from_location(location: &MultiLocation) -> Option<AccountId> {
+		if let X1(Junction::AccountId32 { id, .. }) = location {
+			Some((*id).into())
+		} else {
+			None
+		}
+	}
+
+	fn try_into_location(who: AccountId) -> Result<MultiLocation, AccountId> {
+		Ok(X1(Junction::AccountId32 {
+			network: NetworkId::Any,
+			id: who.into(),
+		}))
+	}
+}
+
+ord_parameter_types! {
+	pub const One: AccountId = ALICE;
+}
+
+parameter_types! {
+	pub SelfLocation: MultiLocation = X2(Parent, Parachain { id: SELF_PARA_ID });
+	pub const MaxCallPrefixLength: u32 = 8;
+	pub const MinTrappedAmount: Balance = 5;
+	pub const RelayChainFeePayer: AccountId = BOB;
+	pub const RelayChainTransactFee: Balance = 100;
+	pub const RelayChainTransactWeight: Weight = 1_000;
+	pub const RelayChainUtilityPalletIndex: u8 = 26;
+	pub const RelayChainProxyPalletIndex: u8 = 29;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type CurrencyIdConvert = CurrencyIdConvert;
+	type AccountId32Convert = AccountId32Convert;
+	type SelfLocation = SelfLocation;
+	type XcmHandler = MockXcmHandler;
+	type AssetTransactor = MockAssetTransactor;
+	type LocationConverter = AccountId32Converter;
+	type MaxCallPrefixLength = MaxCallPrefixLength;
+	type MinTrappedAmount = MinTrappedAmount;
+	type SubAccountOrigin = EnsureSignedBy<One, AccountId>;
+	type RelayChainFeePayer = RelayChainFeePayer;
+	type RelayChainTransactFee = RelayChainTransactFee;
+	type RelayChainTransactWeight = RelayChainTransactWeight;
+	type RelayChainUtilityPalletIndex = RelayChainUtilityPalletIndex;
+	type RelayChainProxyPalletIndex = RelayChainProxyPalletIndex;
+	type WeightInfo = ();
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		XcmInterface: xcm_interface::{Pallet, Storage, Call, Event<T>},
+	}
+);
+
+#[derive(Default)]
+pub struct ExtBuilder;
+
+impl ExtBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let t = frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap();
+
+		let mut ext = sp_io::TestExternalities::new(t);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
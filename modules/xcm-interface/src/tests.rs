@@ -0,0 +1,795 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the xcm interface module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{
+	deposited_assets, executed_xcm, set_min_deposit, AccountId, CurrencyIdConvert, Event, ExtBuilder,
+	MockAssetTransactor, Origin, Runtime, System, XcmInterface, ALICE, AUSD, BOB, DOT, SELF_PARA_ID, SIBLING_PARA_ID,
+};
+use sp_runtime::traits::BadOrigin;
+
+fn template() -> CallTemplate {
+	CallTemplate {
+		call_prefix: vec![10, 1],
+		require_weight_at_most: 1_000,
+	}
+}
+
+#[test]
+fn set_call_template_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmInterface::set_call_template(Origin::signed(BOB), SIBLING_PARA_ID, 0, Some(template())),
+			BadOrigin
+		);
+		assert_noop!(
+			XcmInterface::set_call_template(
+				Origin::signed(ALICE),
+				SIBLING_PARA_ID,
+				0,
+				Some(CallTemplate {
+					call_prefix: vec![0; 9],
+					require_weight_at_most: 1_000,
+				})
+			),
+			Error::<Runtime>::CallPrefixTooLong
+		);
+
+		assert_ok!(XcmInterface::set_call_template(
+			Origin::signed(ALICE),
+			SIBLING_PARA_ID,
+			0,
+			Some(template())
+		));
+		let event = Event::xcm_interface(crate::Event::CallTemplateUpdated(SIBLING_PARA_ID, 0));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(XcmInterface::call_templates(SIBLING_PARA_ID, 0), Some(template()));
+
+		assert_ok!(XcmInterface::set_call_template(
+			Origin::signed(ALICE),
+			SIBLING_PARA_ID,
+			0,
+			None
+		));
+		assert_eq!(XcmInterface::call_templates(SIBLING_PARA_ID, 0), None);
+	});
+}
+
+#[test]
+fn transfer_and_call_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmInterface::transfer_and_call(Origin::signed(ALICE), AUSD, 1_000, 100, SIBLING_PARA_ID, 0, BOB, 500),
+			Error::<Runtime>::CallTemplateNotFound
+		);
+
+		assert_ok!(XcmInterface::set_call_template(
+			Origin::signed(ALICE),
+			SIBLING_PARA_ID,
+			0,
+			Some(template())
+		));
+		assert_noop!(
+			XcmInterface::transfer_and_call(Origin::signed(ALICE), AUSD, 1_000, 1_000, SIBLING_PARA_ID, 0, BOB, 500),
+			Error::<Runtime>::InvalidDestFee
+		);
+		assert_noop!(
+			XcmInterface::transfer_and_call(Origin::signed(ALICE), DOT, 1_000, 100, SIBLING_PARA_ID, 0, BOB, 500),
+			Error::<Runtime>::NotSelfReserveAsset
+		);
+
+		assert_ok!(XcmInterface::transfer_and_call(
+			Origin::signed(ALICE),
+			AUSD,
+			1_000,
+			100,
+			SIBLING_PARA_ID,
+			0,
+			BOB,
+			500
+		));
+		let event = Event::xcm_interface(crate::Event::TransferredAndCalled(
+			ALICE,
+			AUSD,
+			1_000,
+			SIBLING_PARA_ID,
+			0,
+			BOB,
+		));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		let ausd_location = X3(
+			Parent,
+			Parachain { id: SELF_PARA_ID },
+			xcm::v0::Junction::GeneralKey(AUSD.encode()),
+		);
+		let call = [vec![10, 1], BOB.encode(), Compact(900u128).encode()].concat();
+		assert_eq!(
+			executed_xcm(),
+			vec![(
+				ALICE,
+				Xcm::WithdrawAsset {
+					assets: vec![MultiAsset::ConcreteFungible {
+						id: ausd_location.clone(),
+						amount: 1_000,
+					}],
+					effects: vec![Order::DepositReserveAsset {
+						assets: vec![MultiAsset::All],
+						dest: X2(Parent, Parachain { id: SIBLING_PARA_ID }),
+						effects: vec![
+							Order::DepositAsset {
+								assets: vec![MultiAsset::ConcreteFungible {
+									id: ausd_location,
+									amount: 900,
+								}],
+								dest: X3(
+									Parent,
+									Parachain { id: SELF_PARA_ID },
+									AccountId32 {
+										network: NetworkId::Any,
+										id: ALICE.into(),
+									}
+								),
+							},
+							Order::BuyExecution {
+								fees: MultiAsset::All,
+								weight: 1_000,
+								debt: 500,
+								halt_on_error: true,
+								xcm: vec![Xcm::RelayedFrom {
+									who: account_location(ALICE.into()),
+									message: Box::new(Xcm::Transact {
+										origin_type: OriginKind::SovereignAccount,
+										require_weight_at_most: 1_000,
+										call: call.into(),
+									}),
+								}],
+							},
+							Order::DepositAsset {
+								assets: vec![MultiAsset::All],
+								dest: X1(AccountId32 {
+									network: NetworkId::Any,
+									id: BOB.into(),
+								}),
+							},
+						],
+					}],
+				}
+			)]
+		);
+	});
+}
+
+#[test]
+fn withdraw_remote_deposit_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmInterface::withdraw_remote_deposit(Origin::signed(ALICE), AUSD, 0, 100, SIBLING_PARA_ID, 500),
+			Error::<Runtime>::ZeroWithdrawAmount
+		);
+		assert_noop!(
+			XcmInterface::withdraw_remote_deposit(Origin::signed(ALICE), DOT, 900, 100, SIBLING_PARA_ID, 500),
+			Error::<Runtime>::NotSelfReserveAsset
+		);
+
+		assert_ok!(XcmInterface::withdraw_remote_deposit(
+			Origin::signed(ALICE),
+			AUSD,
+			900,
+			100,
+			SIBLING_PARA_ID,
+			500
+		));
+		let event = Event::xcm_interface(crate::Event::RemoteDepositWithdrawn(ALICE, AUSD, 900, SIBLING_PARA_ID));
+		assert!(System::events().iter().any(|record| record.event == event));
+
+		let ausd_location = X3(
+			Parent,
+			Parachain { id: SELF_PARA_ID },
+			xcm::v0::Junction::GeneralKey(AUSD.encode()),
+		);
+		assert_eq!(
+			executed_xcm(),
+			vec![(
+				ALICE,
+				Xcm::WithdrawAsset {
+					assets: vec![MultiAsset::ConcreteFungible {
+						id: ausd_location.clone(),
+						amount: 100,
+					}],
+					effects: vec![Order::DepositReserveAsset {
+						assets: vec![MultiAsset::All],
+						dest: X2(Parent, Parachain { id: SIBLING_PARA_ID }),
+						effects: vec![
+							Order::BuyExecution {
+								fees: MultiAsset::All,
+								weight: 500,
+								debt: 500,
+								halt_on_error: true,
+								xcm: vec![Xcm::RelayedFrom {
+									who: account_location(ALICE.into()),
+									message: Box::new(Xcm::WithdrawAsset {
+										assets: vec![MultiAsset::ConcreteFungible {
+											id: ausd_location,
+											amount: 900,
+										}],
+										effects: vec![Order::InitiateReserveWithdraw {
+											assets: vec![MultiAsset::All],
+											reserve: X2(Parent, Parachain { id: SELF_PARA_ID }),
+											effects: vec![Order::DepositAsset {
+												assets: vec![MultiAsset::All],
+												dest: account_location(ALICE.into()),
+											}],
+										}],
+									}),
+								}],
+							},
+							Order::DepositAsset {
+								assets: vec![MultiAsset::All],
+								dest: X3(
+									Parent,
+									Parachain { id: SELF_PARA_ID },
+									AccountId32 {
+										network: NetworkId::Any,
+										id: ALICE.into(),
+									}
+								),
+							},
+						],
+					}],
+				}
+			)]
+		);
+	});
+}
+
+#[test]
+fn set_transfer_limit_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let limit = TransferLimit {
+			amount: 1_000,
+			window: 10,
+		};
+		assert_noop!(
+			XcmInterface::set_transfer_limit(Origin::signed(BOB), AUSD, Some(limit.clone())),
+			BadOrigin
+		);
+		assert_noop!(
+			XcmInterface::set_transfer_limit(
+				Origin::signed(ALICE),
+				AUSD,
+				Some(TransferLimit {
+					amount: 1_000,
+					window: 0,
+				})
+			),
+			Error::<Runtime>::InvalidTransferLimit
+		);
+
+		assert_ok!(XcmInterface::set_transfer_limit(
+			Origin::signed(ALICE),
+			AUSD,
+			Some(limit.clone())
+		));
+		let event = Event::xcm_interface(crate::Event::TransferLimitUpdated(AUSD));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(XcmInterface::transfer_limits(AUSD), Some(limit));
+
+		OutboundTransfers::<Runtime>::insert(AUSD, (1, 500));
+		assert_ok!(XcmInterface::set_transfer_limit(Origin::signed(ALICE), AUSD, None));
+		assert_eq!(XcmInterface::transfer_limits(AUSD), None);
+		assert_eq!(XcmInterface::outbound_transfers(AUSD), (0, 0));
+	});
+}
+
+#[test]
+fn transfer_and_call_with_transfer_limit() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(XcmInterface::set_call_template(
+			Origin::signed(ALICE),
+			SIBLING_PARA_ID,
+			0,
+			Some(template())
+		));
+		assert_ok!(XcmInterface::set_transfer_limit(
+			Origin::signed(ALICE),
+			AUSD,
+			Some(TransferLimit {
+				amount: 1_000,
+				window: 10,
+			})
+		));
+
+		assert_ok!(XcmInterface::transfer_and_call(
+			Origin::signed(ALICE),
+			AUSD,
+			800,
+			100,
+			SIBLING_PARA_ID,
+			0,
+			BOB,
+			500
+		));
+		assert_eq!(XcmInterface::outbound_transfers(AUSD), (1, 800));
+
+		assert_eq!(
+			XcmInterface::transfer_and_call(Origin::signed(ALICE), AUSD, 300, 100, SIBLING_PARA_ID, 0, BOB, 500),
+			Err(Error::<Runtime>::TransferLimitExceeded.into())
+		);
+		let event = Event::xcm_interface(crate::Event::TransferThrottled(ALICE, AUSD, 300));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(XcmInterface::outbound_transfers(AUSD), (1, 800));
+		assert_eq!(executed_xcm().len(), 1);
+
+		// 200 released in 2 blocks
+		System::set_block_number(3);
+		assert_ok!(XcmInterface::transfer_and_call(
+			Origin::signed(ALICE),
+			AUSD,
+			300,
+			100,
+			SIBLING_PARA_ID,
+			0,
+			BOB,
+			500
+		));
+		assert_eq!(XcmInterface::outbound_transfers(AUSD), (3, 900));
+
+		// all released after the window
+		System::set_block_number(13);
+		assert_ok!(XcmInterface::transfer_and_call(
+			Origin::signed(ALICE),
+			AUSD,
+			1_000,
+			100,
+			SIBLING_PARA_ID,
+			0,
+			BOB,
+			500
+		));
+		assert_eq!(XcmInterface::outbound_transfers(AUSD), (13, 1_000));
+		assert_eq!(executed_xcm().len(), 3);
+	});
+}
+
+#[test]
+fn limit_outbound_transfer_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		type Transactor = LimitOutboundTransfer<Runtime, CurrencyIdConvert, MockAssetTransactor>;
+		let dot = |amount| MultiAsset::ConcreteFungible { id: X1(Parent), amount };
+		let alice = X1(AccountId32 {
+			network: NetworkId::Any,
+			id: ALICE.into(),
+		});
+		assert_ok!(XcmInterface::set_transfer_limit(
+			Origin::signed(ALICE),
+			DOT,
+			Some(TransferLimit {
+				amount: 1_000,
+				window: 10,
+			})
+		));
+
+		// withdrawn from local account, such as XTokens transfers
+		assert!(Transactor::withdraw_asset(&dot(800), &alice).is_ok());
+		assert_eq!(XcmInterface::outbound_transfers(DOT), (1, 800));
+		assert_eq!(
+			Transactor::withdraw_asset(&dot(300), &alice),
+			Err(XcmError::FailedToTransactAsset("transfer limit exceeded"))
+		);
+		assert_eq!(XcmInterface::outbound_transfers(DOT), (1, 800));
+
+		// withdrawn from the sovereign account of other chains is not limited
+		assert!(Transactor::withdraw_asset(&dot(300), &X1(Parent)).is_ok());
+		assert!(Transactor::withdraw_asset(&dot(300), &X2(Parent, Parachain { id: SIBLING_PARA_ID })).is_ok());
+		assert_eq!(XcmInterface::outbound_transfers(DOT), (1, 800));
+	});
+}
+
+fn account_location(who: [u8; 32]) -> MultiLocation {
+	X1(AccountId32 {
+		network: NetworkId::Any,
+		id: who,
+	})
+}
+
+fn dot(amount: Balance) -> MultiAsset {
+	MultiAsset::ConcreteFungible { id: X1(Parent), amount }
+}
+
+type Transactor = TrapFailedDeposit<Runtime, MockAssetTransactor>;
+
+#[test]
+fn trap_failed_deposit_works() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			Transactor::deposit_asset(&dot(10), &account_location(ALICE.into())),
+			Ok(())
+		);
+		assert_eq!(deposited_assets(), vec![(dot(10), account_location(ALICE.into()))]);
+		assert_eq!(XcmInterface::trapped_assets(0), None);
+
+		assert_eq!(
+			Transactor::deposit_asset(&dot(9), &account_location(ALICE.into())),
+			Ok(())
+		);
+		assert_eq!(deposited_assets().len(), 1);
+		assert_eq!(
+			XcmInterface::trapped_assets(0),
+			Some(TrappedAsset {
+				asset: dot(9),
+				beneficiary: account_location(ALICE.into()),
+			})
+		);
+		assert_eq!(XcmInterface::next_trapped_asset_index(), 1);
+		let event = Event::xcm_interface(crate::Event::AssetTrapped(0, dot(9), account_location(ALICE.into())));
+		assert!(System::events().iter().any(|record| record.event == event));
+	});
+}
+
+#[test]
+fn trap_failed_deposit_rejects_dust_and_index_overflow() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			Transactor::deposit_asset(&dot(4), &account_location(ALICE.into())),
+			Err(XcmError::FailedToTransactAsset("below minimal deposit"))
+		);
+		assert_eq!(XcmInterface::trapped_assets(0), None);
+		assert_eq!(XcmInterface::next_trapped_asset_index(), 0);
+
+		NextTrappedAssetIndex::<Runtime>::put(u32::MAX);
+		assert_eq!(
+			Transactor::deposit_asset(&dot(9), &account_location(ALICE.into())),
+			Err(XcmError::FailedToTransactAsset("below minimal deposit"))
+		);
+		assert_eq!(XcmInterface::trapped_assets(u32::MAX), None);
+		assert_eq!(XcmInterface::next_trapped_asset_index(), u32::MAX);
+	});
+}
+
+#[test]
+fn claim_trapped_asset_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmInterface::claim_trapped_asset(Origin::signed(ALICE), 0),
+			Error::<Runtime>::TrappedAssetNotFound
+		);
+		assert_ok!(Transactor::deposit_asset(&dot(9), &account_location(ALICE.into())));
+
+		assert_noop!(
+			XcmInterface::claim_trapped_asset(Origin::signed(BOB), 0),
+			Error::<Runtime>::NotBeneficiary
+		);
+		assert_noop!(
+			XcmInterface::claim_trapped_asset(Origin::signed(ALICE), 0),
+			Error::<Runtime>::DepositFailed
+		);
+
+		set_min_deposit(5);
+		assert_ok!(XcmInterface::claim_trapped_asset(Origin::signed(ALICE), 0));
+		let event = Event::xcm_interface(crate::Event::TrappedAssetClaimed(0, ALICE));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(XcmInterface::trapped_assets(0), None);
+		assert_eq!(deposited_assets(), vec![(dot(9), account_location(ALICE.into()))]);
+	});
+}
+
+#[test]
+fn recover_trapped_asset_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(Transactor::deposit_asset(&dot(9), &X1(Parent)));
+		assert_noop!(
+			XcmInterface::recover_trapped_asset(Origin::signed(BOB), 0, account_location(BOB.into())),
+			BadOrigin
+		);
+		assert_noop!(
+			XcmInterface::recover_trapped_asset(Origin::signed(ALICE), 1, account_location(BOB.into())),
+			Error::<Runtime>::TrappedAssetNotFound
+		);
+		assert_noop!(
+			XcmInterface::recover_trapped_asset(Origin::signed(ALICE), 0, account_location(BOB.into())),
+			Error::<Runtime>::DepositFailed
+		);
+
+		set_min_deposit(5);
+		assert_ok!(XcmInterface::recover_trapped_asset(
+			Origin::signed(ALICE),
+			0,
+			account_location(BOB.into())
+		));
+		let event = Event::xcm_interface(crate::Event::TrappedAssetRecovered(0, account_location(BOB.into())));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(XcmInterface::trapped_assets(0), None);
+		assert_eq!(deposited_assets(), vec![(dot(9), account_location(BOB.into()))]);
+	});
+}
+
+fn sub_account_xcm(index: u16, call: Vec<u8>) -> (AccountId, Xcm) {
+	let mut as_derivative = vec![26, 1];
+	as_derivative.extend(index.encode());
+	as_derivative.extend(call);
+
+	(
+		BOB,
+		Xcm::WithdrawAsset {
+			assets: vec![dot(100)],
+			effects: vec![Order::InitiateReserveWithdraw {
+				assets: vec![MultiAsset::All],
+				reserve: X1(Parent),
+				effects: vec![
+					Order::BuyExecution {
+						fees: MultiAsset::All,
+						weight: 1_000,
+						debt: 1_000,
+						halt_on_error: true,
+						xcm: vec![Xcm::Transact {
+							origin_type: OriginKind::SovereignAccount,
+							require_weight_at_most: 1_000,
+							call: as_derivative.into(),
+						}],
+					},
+					Order::DepositAsset {
+						assets: vec![MultiAsset::All],
+						dest: X1(Parachain { id: SELF_PARA_ID }),
+					},
+				],
+			}],
+		},
+	)
+}
+
+fn proxy_call(call_index: u8, delegate: AccountId, proxy_type: u8) -> Vec<u8> {
+	(29u8, call_index, <[u8; 32]>::from(delegate), proxy_type, 0u32).encode()
+}
+
+#[test]
+fn add_sub_account_proxy_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmInterface::add_sub_account_proxy(Origin::signed(BOB), 0, BOB, 1),
+			BadOrigin
+		);
+
+		assert_ok!(XcmInterface::add_sub_account_proxy(Origin::signed(ALICE), 0, BOB, 1));
+		let event = Event::xcm_interface(crate::Event::SubAccountOperationDispatched(
+			0,
+			SubAccountOperation::AddProxy(BOB, 1),
+		));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(
+			XcmInterface::sub_accounts(0),
+			Some(SubAccountInfo {
+				status: SubAccountStatus::Active,
+				proxies: vec![],
+				anonymous_proxies: vec![],
+				pending_operation: Some(SubAccountOperation::AddProxy(BOB, 1)),
+				updated_at: 1,
+			})
+		);
+		assert_eq!(executed_xcm(), vec![sub_account_xcm(0, proxy_call(1, BOB, 1))]);
+
+		assert_noop!(
+			XcmInterface::add_sub_account_proxy(Origin::signed(ALICE), 0, ALICE, 1),
+			Error::<Runtime>::OperationPending
+		);
+
+		assert_ok!(XcmInterface::reconcile_sub_account(
+			Origin::signed(ALICE),
+			0,
+			true,
+			None
+		));
+		let event = Event::xcm_interface(crate::Event::SubAccountProxyAdded(0, BOB, 1));
+		assert!(System::events().iter().any(|record| record.event == event));
+		let info = XcmInterface::sub_accounts(0).unwrap();
+		assert_eq!(info.proxies, vec![(BOB, 1)]);
+		assert_eq!(info.pending_operation, None);
+
+		assert_noop!(
+			XcmInterface::add_sub_account_proxy(Origin::signed(ALICE), 0, BOB, 1),
+			Error::<Runtime>::ProxyExisted
+		);
+	});
+}
+
+#[test]
+fn remove_and_rotate_sub_account_proxy_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmInterface::remove_sub_account_proxy(Origin::signed(ALICE), 0, BOB, 1),
+			Error::<Runtime>::SubAccountNotFound
+		);
+		assert_ok!(XcmInterface::add_sub_account_proxy(Origin::signed(ALICE), 0, BOB, 1));
+		assert_ok!(XcmInterface::reconcile_sub_account(
+			Origin::signed(ALICE),
+			0,
+			true,
+			None
+		));
+		assert_noop!(
+			XcmInterface::remove_sub_account_proxy(Origin::signed(ALICE), 0, BOB, 2),
+			Error::<Runtime>::ProxyNotFound
+		);
+
+		// change the proxy type
+		System::set_block_number(2);
+		assert_ok!(XcmInterface::rotate_sub_account_proxy(
+			Origin::signed(ALICE),
+			0,
+			(BOB, 1),
+			(BOB, 2)
+		));
+		assert_eq!(XcmInterface::sub_accounts(0).unwrap().proxies, vec![(BOB, 1)]);
+		assert_eq!(XcmInterface::sub_accounts(0).unwrap().updated_at, 2);
+
+		let mut batch_all = vec![26, 2, 8];
+		batch_all.extend(proxy_call(1, BOB, 2));
+		batch_all.extend(proxy_call(2, BOB, 1));
+		assert_eq!(executed_xcm().last(), Some(&sub_account_xcm(0, batch_all)));
+
+		assert_ok!(XcmInterface::reconcile_sub_account(
+			Origin::signed(ALICE),
+			0,
+			true,
+			None
+		));
+		let event = Event::xcm_interface(crate::Event::SubAccountProxyRemoved(0, BOB, 1));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(XcmInterface::sub_accounts(0).unwrap().proxies, vec![(BOB, 2)]);
+
+		assert_ok!(XcmInterface::remove_sub_account_proxy(Origin::signed(ALICE), 0, BOB, 2));
+		assert_eq!(executed_xcm().last(), Some(&sub_account_xcm(0, proxy_call(2, BOB, 2))));
+		assert_ok!(XcmInterface::reconcile_sub_account(
+			Origin::signed(ALICE),
+			0,
+			true,
+			None
+		));
+		let event = Event::xcm_interface(crate::Event::SubAccountProxyRemoved(0, BOB, 2));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(XcmInterface::sub_accounts(0).unwrap().proxies, vec![]);
+	});
+}
+
+#[test]
+fn create_sub_account_anonymous_proxy_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(XcmInterface::create_sub_account_anonymous_proxy(
+			Origin::signed(ALICE),
+			1,
+			3,
+			0
+		));
+		assert_eq!(XcmInterface::sub_accounts(1).unwrap().anonymous_proxies, vec![]);
+		assert_eq!(
+			executed_xcm(),
+			vec![sub_account_xcm(1, (29u8, 4u8, 3u8, 0u32, 0u16).encode())]
+		);
+
+		assert_noop!(
+			XcmInterface::reconcile_sub_account(Origin::signed(ALICE), 1, true, None),
+			Error::<Runtime>::AnonymousProxyRequired
+		);
+		assert_ok!(XcmInterface::reconcile_sub_account(
+			Origin::signed(ALICE),
+			1,
+			true,
+			Some(BOB)
+		));
+		let event = Event::xcm_interface(crate::Event::SubAccountAnonymousProxyCreated(1, BOB, 3, 0));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(
+			XcmInterface::sub_accounts(1).unwrap().anonymous_proxies,
+			vec![(BOB, 3, 0)]
+		);
+
+		assert_noop!(
+			XcmInterface::create_sub_account_anonymous_proxy(Origin::signed(ALICE), 1, 3, 0),
+			Error::<Runtime>::AnonymousProxyExisted
+		);
+	});
+}
+
+#[test]
+fn recover_sub_account_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmInterface::recover_sub_account(Origin::signed(ALICE), 0),
+			Error::<Runtime>::SubAccountNotFound
+		);
+		assert_ok!(XcmInterface::add_sub_account_proxy(Origin::signed(ALICE), 0, BOB, 1));
+		assert_ok!(XcmInterface::reconcile_sub_account(
+			Origin::signed(ALICE),
+			0,
+			true,
+			None
+		));
+		assert_ok!(XcmInterface::add_sub_account_proxy(Origin::signed(ALICE), 0, ALICE, 0));
+		assert_ok!(XcmInterface::reconcile_sub_account(
+			Origin::signed(ALICE),
+			0,
+			true,
+			None
+		));
+
+		assert_ok!(XcmInterface::recover_sub_account(Origin::signed(ALICE), 0));
+		assert_eq!(executed_xcm().last(), Some(&sub_account_xcm(0, vec![29, 3])));
+		assert_eq!(XcmInterface::sub_accounts(0).unwrap().status, SubAccountStatus::Active);
+
+		assert_ok!(XcmInterface::reconcile_sub_account(
+			Origin::signed(ALICE),
+			0,
+			true,
+			None
+		));
+		let event = Event::xcm_interface(crate::Event::SubAccountRecovered(0));
+		assert!(System::events().iter().any(|record| record.event == event));
+		let info = XcmInterface::sub_accounts(0).unwrap();
+		assert_eq!(info.status, SubAccountStatus::Recovered);
+		assert_eq!(info.proxies, vec![]);
+	});
+}
+
+#[test]
+fn reconcile_sub_account_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			XcmInterface::reconcile_sub_account(Origin::signed(ALICE), 0, true, None),
+			Error::<Runtime>::SubAccountNotFound
+		);
+		assert_ok!(XcmInterface::add_sub_account_proxy(Origin::signed(ALICE), 0, BOB, 1));
+		assert_noop!(
+			XcmInterface::reconcile_sub_account(Origin::signed(BOB), 0, true, None),
+			BadOrigin
+		);
+
+		// the operation failed on the relay chain
+		System::set_block_number(2);
+		assert_ok!(XcmInterface::reconcile_sub_account(
+			Origin::signed(ALICE),
+			0,
+			false,
+			None
+		));
+		let event = Event::xcm_interface(crate::Event::SubAccountOperationFailed(
+			0,
+			SubAccountOperation::AddProxy(BOB, 1),
+		));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(
+			XcmInterface::sub_accounts(0),
+			Some(SubAccountInfo {
+				status: SubAccountStatus::Active,
+				proxies: vec![],
+				anonymous_proxies: vec![],
+				pending_operation: None,
+				updated_at: 2,
+			})
+		);
+
+		assert_noop!(
+			XcmInterface::reconcile_sub_account(Origin::signed(ALICE), 0, true, None),
+			Error::<Runtime>::NoPendingOperation
+		);
+	});
+}
@@ -128,6 +128,7 @@ create_currency_id! {
 		PHA("Phala", 12) = 8,
 		HDT("HydraDX", 12) = 9,
 		BCG("Bit.Country", 18) = 11,
+		LCDOT("Liquid Crowdloan DOT", 10) = 12,
 
 		// Kusama Ecosystem
 		KAR("Karura", 12) = 128,
@@ -145,6 +146,9 @@ create_currency_id! {
 	}
 }
 
+/// Id of a foreign asset registered by the asset registry.
+pub type ForeignAssetId = u16;
+
 pub trait GetDecimals {
 	fn decimals(&self) -> Option<u8>;
 }
@@ -162,6 +166,7 @@ pub enum CurrencyId {
 	Token(TokenSymbol),
 	DexShare(DexShare, DexShare),
 	Erc20(EvmAddress),
+	ForeignAsset(ForeignAssetId),
 }
 
 impl CurrencyId {
@@ -177,6 +182,10 @@ impl CurrencyId {
 		matches!(self, CurrencyId::Erc20(_))
 	}
 
+	pub fn is_foreign_asset_currency_id(&self) -> bool {
+		matches!(self, CurrencyId::ForeignAsset(_))
+	}
+
 	pub fn split_dex_share_currency_id(&self) -> Option<(Self, Self)> {
 		match self {
 			CurrencyId::DexShare(token_symbol_0, token_symbol_1) => {
@@ -21,7 +21,7 @@ use codec::{Decode, Encode};
 use evm::ExitReason;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_core::{H160, U256};
+use sp_core::{keccak_256, H160, U256};
 use sp_runtime::RuntimeDebug;
 use sp_std::vec::Vec;
 
@@ -69,6 +69,55 @@ pub struct Erc20Info {
 	pub decimals: u8,
 }
 
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ContractStorageInfo<Balance> {
+	/// The bytes of storage used by the contract, include the code
+	pub used_storage: u32,
+	/// The deposit reserved by the contract account
+	pub deposit: Balance,
+	/// The maintainer of the contract
+	pub maintainer: EvmAddress,
+	/// Whether the contract is deployed (published)
+	pub deployed: bool,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EstimateResourcesInfo<Balance> {
+	pub exit_reason: ExitReason,
+	/// The gas used by the call or create
+	pub used_gas: U256,
+	/// The incremental bytes of storage, negative if storage is released
+	pub used_storage: i32,
+	/// The storage deposit required for the incremental storage
+	pub storage_deposit: Balance,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct BlockGasInfo<Balance> {
+	/// The gas used by the EVM transactions of the block
+	pub gas_used: u64,
+	/// The max gas of the block
+	pub gas_limit: u64,
+	/// The weight fee per gas of EVM transactions in the next block,
+	/// adjusted by the EVM fee multiplier
+	pub next_base_fee_per_gas: Balance,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AccountNonceInfo<AccountId, Index> {
+	/// The account the evm address is mapped to
+	pub account_id: AccountId,
+	/// The nonce of the mapped account, used to sign extrinsics
+	pub account_nonce: Index,
+	/// The nonce of the evm address, used to derive the addresses of
+	/// created contracts
+	pub evm_nonce: Index,
+}
+
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct EstimateResourcesRequest {
@@ -85,3 +134,81 @@ pub struct EstimateResourcesRequest {
 	/// Data
 	pub data: Option<Vec<u8>>,
 }
+
+/// The 2048-bit bloom filter of the logs, as defined in the Ethereum yellow
+/// paper.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct Bloom(pub [u8; 256]);
+
+impl Default for Bloom {
+	fn default() -> Self {
+		Bloom([0u8; 256])
+	}
+}
+
+impl Bloom {
+	/// Add the keccak hash of `input` to the bloom filter.
+	pub fn accrue(&mut self, input: &[u8]) {
+		let hash = keccak_256(input);
+		for i in [0usize, 2, 4].iter() {
+			let bit = (((hash[*i] as usize) << 8) | hash[*i + 1] as usize) & 0x7ff;
+			self.0[255 - bit / 8] |= 1 << (bit % 8);
+		}
+	}
+
+	/// Add the address and topics of `log` to the bloom filter.
+	pub fn accrue_log(&mut self, log: &Log) {
+		self.accrue(log.address.as_bytes());
+		for topic in log.topics.iter() {
+			self.accrue(topic.as_bytes());
+		}
+	}
+
+	/// Add all the entries of `bloom` to the bloom filter.
+	pub fn accrue_bloom(&mut self, bloom: &Bloom) {
+		for (byte, other) in self.0.iter_mut().zip(bloom.0.iter()) {
+			*byte |= *other;
+		}
+	}
+
+	/// Whether the bloom filter may contain all the entries of `bloom`.
+	pub fn contains_bloom(&self, bloom: &Bloom) -> bool {
+		self.0
+			.iter()
+			.zip(bloom.0.iter())
+			.all(|(byte, other)| byte & other == *other)
+	}
+}
+
+/// The logs emitted by the extrinsics of a block.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct BlockLogsInfo {
+	/// The logs in the order of emission, with the index of the extrinsic
+	/// emitting it
+	pub logs: Vec<(u32, Log)>,
+	/// The bloom filter of the logs of the block
+	pub logs_bloom: Bloom,
+}
+
+/// The compact Ethereum-style receipt of an EVM transaction.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug)]
+pub struct Receipt {
+	/// The index of the extrinsic in the block, the events of the
+	/// transaction are deposited in the phase of this extrinsic
+	pub extrinsic_index: u32,
+	/// Whether the execution succeeded
+	pub status: bool,
+	/// The gas used by the transaction
+	pub used_gas: u64,
+	/// The total gas used in the block up to and including the transaction
+	pub cumulative_used_gas: u64,
+	/// The created contract address, if the transaction is a contract
+	/// creation
+	pub contract_address: Option<EvmAddress>,
+	/// The block-wide index of the first log of the transaction
+	pub first_log_index: u32,
+	/// The count of logs of the transaction
+	pub logs_count: u32,
+	/// The bloom filter of the logs of the transaction
+	pub logs_bloom: Bloom,
+}
@@ -80,3 +80,44 @@ fn currency_id_try_into_u32_works() {
 	let currency_id = CurrencyId::Erc20(EvmAddress::from_str("0x0000000000000000000000000000000000000000").unwrap());
 	assert_eq!(currency_id.try_into(), Ok(0x00));
 }
+
+#[test]
+fn bloom_accrue_works() {
+	use crate::evm::Bloom;
+
+	// keccak256("") = 0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470
+	let mut bloom = Bloom::default();
+	bloom.accrue(&[]);
+
+	let mut expected = [0u8; 256];
+	expected[69] = 0x04; // bit 1490
+	expected[63] = 0x02; // bit 1537
+	expected[33] = 0x80; // bit 1783
+	assert_eq!(bloom, Bloom(expected));
+
+	// accrue the same input again doesn't change the bloom
+	bloom.accrue(&[]);
+	assert_eq!(bloom, Bloom(expected));
+}
+
+#[test]
+fn bloom_accrue_bloom_works() {
+	use crate::evm::Bloom;
+
+	let mut empty_input = Bloom::default();
+	empty_input.accrue(&[]);
+	let mut other_input = Bloom::default();
+	other_input.accrue(&[1u8]);
+
+	let mut bloom = Bloom::default();
+	assert!(!bloom.contains_bloom(&empty_input));
+	bloom.accrue_bloom(&empty_input);
+	assert_eq!(bloom, empty_input);
+	assert!(bloom.contains_bloom(&empty_input));
+	assert!(!bloom.contains_bloom(&other_input));
+
+	bloom.accrue_bloom(&other_input);
+	assert!(bloom.contains_bloom(&empty_input));
+	assert!(bloom.contains_bloom(&other_input));
+	assert!(bloom.contains_bloom(&Bloom::default()));
+}
@@ -21,7 +21,10 @@
 #![warn(missing_docs)]
 
 use primitives::{AccountId, Balance, Block, CurrencyId, DataProviderId, Hash, Nonce};
-use sc_client_api::light::{Fetcher, RemoteBlockchain};
+use sc_client_api::{
+	light::{Fetcher, RemoteBlockchain},
+	BlockBackend, BlockchainEvents,
+};
 pub use sc_rpc_api::DenyUnsafe;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
@@ -47,7 +50,7 @@ mod standalone_use {
 
 pub use sc_rpc::SubscriptionTaskExecutor;
 
-pub use evm_rpc::{EVMApi, EVMApiServer, EVMRuntimeRPCApi};
+pub use evm_rpc::{EVMApi, EVMApiServer, EVMPubSub, EVMPubSubApiServer, EVMRuntimeRPCApi};
 
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpc_core::IoHandler<sc_rpc::Metadata>;
@@ -118,6 +121,7 @@ mod standalone {
 	where
 		C: ProvideRuntimeApi<Block>,
 		C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+		C: BlockchainEvents<Block> + BlockBackend<Block>,
 		C: Send + Sync + 'static,
 		C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 		C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
@@ -126,7 +130,7 @@ mod standalone {
 		C::Api: EVMRuntimeRPCApi<Block, Balance>,
 		C::Api: BabeApi<Block>,
 		C::Api: BlockBuilder<Block>,
-		P: TransactionPool + Sync + Send + 'static,
+		P: TransactionPool<Block = Block, Hash = Hash> + Sync + Send + 'static,
 		SC: SelectChain<Block> + 'static,
 		B: sc_client_api::Backend<Block> + Send + Sync + 'static,
 		B::State: sc_client_api::backend::StateBackend<sp_runtime::traits::HashFor<Block>>,
@@ -162,7 +166,7 @@ mod standalone {
 
 		io.extend_with(SystemApi::to_delegate(FullSystem::new(
 			client.clone(),
-			pool,
+			pool.clone(),
 			deny_unsafe,
 		)));
 		io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(
@@ -182,7 +186,7 @@ mod standalone {
 				shared_authority_set.clone(),
 				shared_voter_state,
 				justification_stream,
-				subscription_executor,
+				subscription_executor.clone(),
 				finality_provider,
 			),
 		));
@@ -192,7 +196,12 @@ mod standalone {
 		// These RPCs should use an asynchronous caller instead.
 		io.extend_with(OracleApi::to_delegate(Oracle::new(client.clone())));
 		io.extend_with(StakingPoolApi::to_delegate(StakingPool::new(client.clone())));
-		io.extend_with(EVMApiServer::to_delegate(EVMApi::new(client)));
+		io.extend_with(EVMApiServer::to_delegate(EVMApi::new(client.clone(), pool.clone())));
+		io.extend_with(EVMPubSubApiServer::to_delegate(EVMPubSub::new(
+			client,
+			pool,
+			subscription_executor,
+		)));
 
 		io
 	}
@@ -212,6 +221,9 @@ mod parachain {
 		pub pool: Arc<P>,
 		/// Whether to deny unsafe calls
 		pub deny_unsafe: DenyUnsafe,
+		/// Executor to drive the subscription manager in the EVM pubsub RPC
+		/// handler.
+		pub subscription_executor: SubscriptionTaskExecutor,
 	}
 
 	/// Instantiate all Full RPC extensions.
@@ -219,6 +231,7 @@ mod parachain {
 	where
 		C: ProvideRuntimeApi<Block>,
 		C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+		C: BlockchainEvents<Block> + BlockBackend<Block>,
 		C: Send + Sync + 'static,
 		C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 		C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
@@ -226,7 +239,7 @@ mod parachain {
 		C::Api: module_staking_pool_rpc::StakingPoolRuntimeApi<Block, AccountId, Balance>,
 		C::Api: EVMRuntimeRPCApi<Block, Balance>,
 		C::Api: BlockBuilder<Block>,
-		P: TransactionPool + Sync + Send + 'static,
+		P: TransactionPool<Block = Block, Hash = Hash> + Sync + Send + 'static,
 	{
 		use module_staking_pool_rpc::{StakingPool, StakingPoolApi};
 		use orml_oracle_rpc::{Oracle, OracleApi};
@@ -238,11 +251,12 @@ mod parachain {
 			client,
 			pool,
 			deny_unsafe,
+			subscription_executor,
 		} = deps;
 
 		io.extend_with(SystemApi::to_delegate(FullSystem::new(
 			client.clone(),
-			pool,
+			pool.clone(),
 			deny_unsafe,
 		)));
 		io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(
@@ -253,7 +267,12 @@ mod parachain {
 		// These RPCs should use an asynchronous caller instead.
 		io.extend_with(OracleApi::to_delegate(Oracle::new(client.clone())));
 		io.extend_with(StakingPoolApi::to_delegate(StakingPool::new(client.clone())));
-		io.extend_with(EVMApiServer::to_delegate(EVMApi::new(client)));
+		io.extend_with(EVMApiServer::to_delegate(EVMApi::new(client.clone(), pool.clone())));
+		io.extend_with(EVMPubSubApiServer::to_delegate(EVMPubSub::new(
+			client,
+			pool,
+			subscription_executor,
+		)));
 
 		io
 	}
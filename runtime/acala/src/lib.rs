@@ -54,11 +54,13 @@ use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
 
 use frame_system::{EnsureOneOf, EnsureRoot, RawOrigin};
+use module_asset_registry::AssetIdConvert;
 use module_currencies::{BasicCurrencyAdapter, Currency};
 use module_evm::{CallInfo, CreateInfo};
 use module_evm_accounts::EvmAddressMapping;
 use module_evm_manager::EvmCurrencyIdMapping;
-use module_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+use module_transaction_payment::{Multiplier, TargetedEvmFeeAdjustment, TargetedFeeAdjustment};
+use module_xcm_interface::{LimitOutboundTransfer, TrapFailedDeposit};
 use orml_tokens::CurrencyAdapter;
 use orml_traits::{create_median_value_data_provider, parameter_type_with_key, DataFeeder, DataProviderExtended};
 use pallet_transaction_payment::RuntimeDispatchInfo;
@@ -99,13 +101,17 @@ pub use sp_runtime::{Perbill, Percent, Permill, Perquintill};
 pub use authority::AuthorityConfigImpl;
 pub use constants::{fee::*, time::*};
 pub use primitives::{
-	evm::EstimateResourcesRequest, AccountId, AccountIndex, Amount, AuctionId, AuthoritysOriginId, Balance,
-	BlockNumber, CurrencyId, DataProviderId, EraIndex, Hash, Moment, Nonce, Share, Signature, TokenSymbol, TradingPair,
+	evm::{
+		AccountNonceInfo, BlockGasInfo, BlockLogsInfo, ContractStorageInfo, EstimateResourcesInfo,
+		EstimateResourcesRequest,
+	},
+	AccountId, AccountIndex, Amount, AuctionId, AuthoritysOriginId, Balance, BlockNumber, CurrencyId, DataProviderId,
+	EraIndex, Hash, Moment, Nonce, Share, Signature, TokenSymbol, TradingPair,
 };
 pub use runtime_common::{
 	cent, deposit, dollar, microcent, millicent, CurveFeeModel, ExchangeRate, GasToWeight, OffchainSolutionWeightLimit,
-	Price, Rate, Ratio, RuntimeBlockLength, RuntimeBlockWeights, SystemContractsFilter, TimeStampedPrice, ACA, AUSD,
-	DOT, LDOT, PHA, POLKABTC, RENBTC, SDN, XBTC,
+	Price, Rate, Ratio, RelaychainBlockNumberProvider, RuntimeBlockLength, RuntimeBlockWeights, SystemContractsFilter,
+	TimeStampedPrice, ACA, AUSD, DOT, LDOT, PHA, POLKABTC, RENBTC, SDN, XBTC,
 };
 
 mod authority;
@@ -116,10 +122,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("acala"),
 	impl_name: create_runtime_str!("acala"),
 	authoring_version: 1,
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 };
 
 /// The version infromation used to identify this runtime when compiled
@@ -150,6 +156,12 @@ parameter_types! {
 	pub const DSWFPalletId: PalletId = PalletId(*b"aca/dswf");
 	pub const ElectionsPhragmenPalletId: LockIdentifier = *b"aca/phre";
 	pub const NftPalletId: PalletId = PalletId(*b"aca/aNFT");
+	pub const EVMReapPotPalletId: PalletId = PalletId(*b"aca/evmr");
+	pub const ProtocolLiquidityPalletId: PalletId = PalletId(*b"aca/polm");
+	// The block author share of tips to collators, distributed by governance
+	pub const CollatorsRewardPotPalletId: PalletId = PalletId(*b"aca/clrp");
+	// Alternative fee pools swapping collected fees back to native currency
+	pub const TransactionPaymentPalletId: PalletId = PalletId(*b"aca/fees");
 	pub UnreleasedNativeVaultAccountId: AccountId = PalletId(*b"aca/urls").into_account();
 }
 
@@ -164,6 +176,9 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 		HomaTreasuryPalletId::get().into_account(),
 		IncentivesPalletId::get().into_account(),
 		DSWFPalletId::get().into_account(),
+		EVMReapPotPalletId::get().into_account(),
+		ProtocolLiquidityPalletId::get().into_account(),
+		CollatorsRewardPotPalletId::get().into_account(),
 		ZeroAccountId::get(),
 	]
 }
@@ -197,7 +212,7 @@ impl frame_system::Config for Runtime {
 		module_evm_accounts::CallKillAccount<Runtime>,
 	);
 	type DbWeight = RocksDbWeight;
-	type BaseCallFilter = ();
+	type BaseCallFilter = module_transaction_pause::PausedTransactionFilter<Runtime>;
 	type SystemWeightInfo = ();
 	type SS58Prefix = SS58Prefix;
 	type OnSetCode = cumulus_pallet_parachain_system::ParachainSetCode<Self>;
@@ -247,6 +262,7 @@ impl pallet_balances::Config for Runtime {
 parameter_types! {
 	pub TransactionByteFee: Balance = 10 * millicent(ACA);
 	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	pub const EvmTargetBlockFullness: Perquintill = Perquintill::from_percent(10);
 	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 100_000);
 	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000_000u128);
 }
@@ -656,13 +672,17 @@ impl DataFeeder<CurrencyId, Price, AccountId> for AggregatedDataProvider {
 }
 
 parameter_type_with_key! {
-	pub ExistentialDeposits: |_currency_id: CurrencyId| -> Balance {
-		Zero::zero()
+	pub ExistentialDeposits: |currency_id: CurrencyId| -> Balance {
+		match currency_id {
+			CurrencyId::Token(_) | CurrencyId::ForeignAsset(_) => AssetRegistry::minimal_balance(*currency_id),
+			_ => Zero::zero(),
+		}
 	};
 }
 
 parameter_types! {
 	pub AcalaTreasuryAccount: AccountId = AcalaTreasuryPalletId::get().into_account();
+	pub CollatorsRewardPot: Option<AccountId> = Some(CollatorsRewardPotPalletId::get().into_account());
 }
 
 impl orml_tokens::Config for Runtime {
@@ -675,8 +695,30 @@ impl orml_tokens::Config for Runtime {
 	type OnDust = orml_tokens::TransferDust<Runtime, AcalaTreasuryAccount>;
 }
 
+parameter_types! {
+	pub const AssetStringLimit: u32 = 32;
+}
+
+impl module_asset_registry::Config for Runtime {
+	type Event = Event;
+	type RegisterOrigin = EnsureRootOrHalfGeneralCouncil;
+	type StringLimit = AssetStringLimit;
+	type EVMBridge = EVMBridge;
+	type CurrencyIdMapping = EvmCurrencyIdMapping<Runtime>;
+	type Currency = Currencies;
+	type WeightInfo = ();
+}
+
+impl module_transaction_pause::Config for Runtime {
+	type Event = Event;
+	type UpdateOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+	type WeightInfo = ();
+}
+
 parameter_types! {
 	pub StableCurrencyFixedPrice: Price = Price::saturating_from_rational(1, 1);
+	pub MaxPriceDeviation: Ratio = Ratio::saturating_from_rational(5, 100);
+	pub const PriceResumeThreshold: u32 = 5;
 }
 
 impl module_prices::Config for Runtime {
@@ -686,7 +728,10 @@ impl module_prices::Config for Runtime {
 	type StableCurrencyFixedPrice = StableCurrencyFixedPrice;
 	type GetStakingCurrencyId = GetStakingCurrencyId;
 	type GetLiquidCurrencyId = GetLiquidCurrencyId;
+	type GetLiquidCrowdloanCurrencyId = GetLiquidCrowdloanCurrencyId;
 	type LockOrigin = EnsureRootOrTwoThirdsGeneralCouncil;
+	type MaxPriceDeviation = MaxPriceDeviation;
+	type PriceResumeThreshold = PriceResumeThreshold;
 	type LiquidStakingExchangeRateProvider = LiquidStakingExchangeRateProvider;
 	type DEX = Dex;
 	type Currency = Currencies;
@@ -784,6 +829,10 @@ parameter_types! {
 	pub MinimumIncrementSize: Rate = Rate::saturating_from_rational(2, 100);
 	pub const AuctionTimeToClose: BlockNumber = 15 * MINUTES;
 	pub const AuctionDurationSoftCap: BlockNumber = 2 * HOURS;
+	pub const InstallmentCount: u32 = 4;
+	pub const InstallmentPeriod: BlockNumber = DAYS;
+	pub InstallmentBondRatio: Ratio = Ratio::saturating_from_rational(20, 100);
+	pub MaxRefundSwapSlippage: Ratio = Ratio::saturating_from_rational(3, 100);
 }
 
 impl module_auction_manager::Config for Runtime {
@@ -797,11 +846,20 @@ impl module_auction_manager::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type DEX = Dex;
 	type PriceSource = Prices;
+	type MaxRefundSwapSlippage = MaxRefundSwapSlippage;
 	type UnsignedPriority = runtime_common::AuctionManagerUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type UpdateOrigin = EnsureRootOrHalfHonzonCouncil;
+	type InstallmentCount = InstallmentCount;
+	type InstallmentPeriod = InstallmentPeriod;
+	type InstallmentBondRatio = InstallmentBondRatio;
 	type WeightInfo = weights::module_auction_manager::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const MaxPositionLabelLength: u32 = 64;
+}
+
 impl module_loans::Config for Runtime {
 	type Event = Event;
 	type Convert = module_cdp_engine::DebitExchangeRateConvertor<Runtime>;
@@ -810,6 +868,12 @@ impl module_loans::Config for Runtime {
 	type CDPTreasury = CdpTreasury;
 	type PalletId = LoansPalletId;
 	type OnUpdateLoan = module_incentives::OnUpdateLoan<Runtime>;
+	type MaxPositionLabelLength = MaxPositionLabelLength;
+	type UpdateOrigin = EnsureRootOrHalfHonzonCouncil;
+	type Homa = StakingPool;
+	type StakingCurrencyId = GetStakingCurrencyId;
+	type LiquidCurrencyId = GetLiquidCurrencyId;
+	type CircuitBreaker = CircuitBreaker;
 }
 
 impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
@@ -878,6 +942,9 @@ parameter_types! {
 	pub DefaultLiquidationPenalty: Rate = Rate::saturating_from_rational(5, 100);
 	pub MinimumDebitValue: Balance = dollar(AUSD);
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::saturating_from_rational(5, 100);
+	pub const MaxStabilityFeeExemptions: u32 = 10;
+	pub const MaxLiquidationSteps: u32 = 4;
+	pub MaxLiquidationValuePerBlock: Balance = 1_000_000 * dollar(AUSD);
 }
 
 impl module_cdp_engine::Config for Runtime {
@@ -894,7 +961,12 @@ impl module_cdp_engine::Config for Runtime {
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
 	type UnsignedPriority = runtime_common::CdpEngineUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type MaxStabilityFeeExemptions = MaxStabilityFeeExemptions;
+	type MaxLiquidationSteps = MaxLiquidationSteps;
+	type LiquidationKeeper = ();
+	type MaxLiquidationValuePerBlock = MaxLiquidationValuePerBlock;
 	type UnixTime = Timestamp;
+	type Erc20ReferenceCounter = EvmManager;
 	type WeightInfo = weights::module_cdp_engine::WeightInfo<Runtime>;
 }
 
@@ -903,6 +975,22 @@ impl module_honzon::Config for Runtime {
 	type WeightInfo = weights::module_honzon::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const MaxTriggerTiers: u32 = 5;
+}
+
+impl module_circuit_breaker::Config for Runtime {
+	type Event = Event;
+	type MonitoredCurrencyIds = CollateralCurrencyIds;
+	type GetStableCurrencyId = GetStableCurrencyId;
+	type PriceSource = Prices;
+	type DEX = Dex;
+	type CDPTreasury = CdpTreasury;
+	type MaxTriggerTiers = MaxTriggerTiers;
+	type UpdateOrigin = EnsureRootOrHalfHonzonCouncil;
+	type WeightInfo = weights::module_circuit_breaker::WeightInfo<Runtime>;
+}
+
 impl module_emergency_shutdown::Config for Runtime {
 	type Event = Event;
 	type CollateralCurrencyIds = CollateralCurrencyIds;
@@ -916,6 +1004,14 @@ impl module_emergency_shutdown::Config for Runtime {
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 1000);	// 0.1%
 	pub const TradingPathLimit: u32 = 3;
+	pub const ProvisioningPeriod: BlockNumber = 7 * DAYS;
+	pub const AmplificationRampPeriod: BlockNumber = DAYS;
+	pub const MaxRangeBuckets: u32 = 50;
+	pub const MaxBasketInputs: u32 = 8;
+	pub const VolumeBucketPeriod: BlockNumber = DAYS;
+	pub const MaxVolumeBuckets: u32 = 30;
+	pub ListingBond: Balance = 100 * dollar(ACA);
+	pub const ListingChallengePeriod: BlockNumber = 3 * DAYS;
 	pub EnabledTradingPairs: Vec<TradingPair> = vec![
 		TradingPair::new(AUSD, ACA),
 		TradingPair::new(AUSD, DOT),
@@ -934,13 +1030,50 @@ impl module_dex::Config for Runtime {
 	type TradingPathLimit = TradingPathLimit;
 	type PalletId = DEXPalletId;
 	type CurrencyIdMapping = EvmCurrencyIdMapping<Runtime>;
+	type Erc20ReferenceCounter = EvmManager;
 	type DEXIncentives = Incentives;
 	type WeightInfo = weights::module_dex::WeightInfo<Runtime>;
 	type ListingOrigin = EnsureRootOrHalfGeneralCouncil;
+	type ProvisioningPeriod = ProvisioningPeriod;
+	type AmplificationRampPeriod = AmplificationRampPeriod;
+	type MaxRangeBuckets = MaxRangeBuckets;
+	type PositionNFT = NFT;
+	type MaxBasketInputs = MaxBasketInputs;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type ListingBond = ListingBond;
+	type ListingChallengePeriod = ListingChallengePeriod;
+	type ListingVetoOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
+	type TreasuryAccount = AcalaTreasuryAccount;
+	type VolumeBucketPeriod = VolumeBucketPeriod;
+	type MaxVolumeBuckets = MaxVolumeBuckets;
+}
+
+parameter_types! {
+	pub const ProtocolLiquidityStrategyPeriod: BlockNumber = HOURS;
+	pub MaxProtocolLiquidityPriceDeviation: Ratio = Ratio::saturating_from_rational(2, 100);
+	pub const MaxProtocolLiquidityStrategies: u32 = 10;
+}
+
+impl module_protocol_liquidity::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type DEX = Dex;
+	type TreasuryAccount = AcalaTreasuryAccount;
+	type PalletId = ProtocolLiquidityPalletId;
+	type StrategyPeriod = ProtocolLiquidityStrategyPeriod;
+	type Randomness = RandomnessCollectiveFlip;
+	type PriceSource = Prices;
+	type MaxPriceDeviation = MaxProtocolLiquidityPriceDeviation;
+	type MaxStrategies = MaxProtocolLiquidityStrategies;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type WeightInfo = weights::module_protocol_liquidity::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	pub const MaxAuctionsCount: u32 = 100;
+	pub const SurplusRebalancePeriod: BlockNumber = HOURS;
+	pub const MaxSurplusAllocations: u32 = 5;
+	pub MaxSurplusSwapSlippage: Ratio = Ratio::saturating_from_rational(3, 100);
 	pub HonzonTreasuryAccount: AccountId = HonzonTreasuryPalletId::get().into_account();
 }
 
@@ -952,6 +1085,10 @@ impl module_cdp_treasury::Config for Runtime {
 	type UpdateOrigin = EnsureRootOrHalfHonzonCouncil;
 	type DEX = Dex;
 	type MaxAuctionsCount = MaxAuctionsCount;
+	type PriceSource = Prices;
+	type SurplusRebalancePeriod = SurplusRebalancePeriod;
+	type MaxSurplusAllocations = MaxSurplusAllocations;
+	type MaxSurplusSwapSlippage = MaxSurplusSwapSlippage;
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = HonzonTreasuryAccount;
 	type WeightInfo = weights::module_cdp_treasury::WeightInfo<Runtime>;
@@ -960,20 +1097,61 @@ impl module_cdp_treasury::Config for Runtime {
 parameter_types! {
 	// All currency types except for native currency, Sort by fee charge order
 	pub AllNonNativeCurrencyIds: Vec<CurrencyId> = vec![AUSD, LDOT, DOT, XBTC, RENBTC, POLKABTC, PHA];
+	pub const SponsorPeriod: BlockNumber = DAYS;
+	pub const MaxSponsorAllowedCalls: u32 = 32;
+	pub const RebatePeriod: BlockNumber = 7 * DAYS;
+	pub const MaxRebateTiers: u32 = 10;
+	pub const MaxRebatesPerBlock: u32 = 50;
+	pub FeePoolRefillWeightLimit: Weight = Perbill::from_percent(5) * RuntimeBlockWeights::get().max_block;
+	pub const FeeBurnReportPeriod: BlockNumber = DAYS;
+}
+
+pub struct EvmCallFilter;
+impl Filter<Call> for EvmCallFilter {
+	fn filter(call: &Call) -> bool {
+		match call {
+			Call::EVM(_) => true,
+			// the EVM calls wrapped in other calls
+			Call::Utility(pallet_utility::Call::batch(calls))
+			| Call::Utility(pallet_utility::Call::batch_all(calls)) => calls.iter().any(Self::filter),
+			Call::Utility(pallet_utility::Call::as_derivative(_, call))
+			| Call::Proxy(pallet_proxy::Call::proxy(_, _, call))
+			| Call::Proxy(pallet_proxy::Call::proxy_announced(_, _, _, call))
+			| Call::Multisig(pallet_multisig::Call::as_multi_threshold_1(_, call)) => Self::filter(call),
+			_ => false,
+		}
+	}
 }
 
 impl module_transaction_payment::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
 	type AllNonNativeCurrencyIds = AllNonNativeCurrencyIds;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type StableCurrencyId = GetStableCurrencyId;
 	type Currency = Balances;
 	type MultiCurrency = Currencies;
 	type OnTransactionPayment = AcalaTreasury;
+	type BlockAuthor = CollatorsRewardPot;
 	type TransactionByteFee = TransactionByteFee;
 	type WeightToFee = WeightToFee;
 	type FeeMultiplierUpdate = TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+	type EvmFeeMultiplierUpdate =
+		TargetedEvmFeeAdjustment<Self, EvmTargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+	type EvmCallFilter = EvmCallFilter;
 	type DEX = Dex;
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
+	type TradingPathLimit = TradingPathLimit;
+	type SponsorPeriod = SponsorPeriod;
+	type MaxSponsorAllowedCalls = MaxSponsorAllowedCalls;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type TreasuryAccount = AcalaTreasuryAccount;
+	type RebatePeriod = RebatePeriod;
+	type MaxRebateTiers = MaxRebateTiers;
+	type MaxRebatesPerBlock = MaxRebatesPerBlock;
+	type PalletId = TransactionPaymentPalletId;
+	type FeePoolRefillWeightLimit = FeePoolRefillWeightLimit;
+	type FeeBurnReportPeriod = FeeBurnReportPeriod;
 	type WeightInfo = weights::module_transaction_payment::WeightInfo<Runtime>;
 }
 
@@ -983,12 +1161,16 @@ impl module_evm_accounts::Config for Runtime {
 	type AddressMapping = EvmAddressMapping<Runtime>;
 	type MergeAccount = Currencies;
 	type OnClaim = (); // TODO: update implementation to something similar to Mandala
+	type EVMBridge = EVMBridge;
+	type ChainId = module_evm::EvmChainId<Runtime>;
 	type WeightInfo = weights::module_evm_accounts::WeightInfo<Runtime>;
 }
 
 impl module_evm_manager::Config for Runtime {
+	type Event = Event;
 	type Currency = Balances;
 	type EVMBridge = EVMBridge;
+	type AssetMetadataProvider = AssetRegistry;
 }
 
 impl orml_rewards::Config for Runtime {
@@ -1017,27 +1199,40 @@ impl module_incentives::Config for Runtime {
 	type DEX = Dex;
 	type EmergencyShutdown = EmergencyShutdown;
 	type PalletId = IncentivesPalletId;
+	type Erc20ReferenceCounter = EvmManager;
 	type WeightInfo = weights::module_incentives::WeightInfo<Runtime>;
 }
 
 parameter_types! {
 	pub const PolkadotBondingDuration: EraIndex = 7;
 	pub const EraLength: BlockNumber = DAYS;
+	pub const RelaychainEraLength: BlockNumber = 14_400;	// 1 day of relaychain blocks
+	pub const RelaychainStakingCallWeight: Weight = 1_000_000_000;
+	pub const RelaychainMaxMessageWeight: Weight = 10_000_000_000;	// 10 staking calls per message
 }
 
 impl module_polkadot_bridge::Config for Runtime {
+	type Event = Event;
 	type DOTCurrency = Currency<Runtime, GetStakingCurrencyId>;
 	type OnNewEra = (NomineesElection, StakingPool);
 	type BondingDuration = PolkadotBondingDuration;
 	type EraLength = EraLength;
 	type PolkadotAccountId = AccountId;
+	type RelaychainBlockNumber = RelaychainBlockNumberProvider<Runtime>;
+	type RelaychainEraLength = RelaychainEraLength;
+	type StakingCallWeight = RelaychainStakingCallWeight;
+	type MaxMessageWeight = RelaychainMaxMessageWeight;
 }
 
 parameter_types! {
 	pub const GetLiquidCurrencyId: CurrencyId = LDOT;
+	pub const GetLiquidCrowdloanCurrencyId: Option<CurrencyId> = Some(CurrencyId::Token(TokenSymbol::LCDOT));
 	pub const GetStakingCurrencyId: CurrencyId = DOT;
 	pub DefaultExchangeRate: ExchangeRate = ExchangeRate::saturating_from_rational(10, 100);	// 1 : 10
 	pub PoolAccountIndexes: Vec<u32> = vec![1, 2, 3, 4];
+	pub const MaxFastMatchRedeemers: u32 = 20;
+	pub const MaxRedeemRequestsProcessed: u32 = 50;
+	pub const MaxMintRecordsPruned: u32 = 100;
 }
 
 impl module_staking_pool::Config for Runtime {
@@ -1052,10 +1247,14 @@ impl module_staking_pool::Config for Runtime {
 	type Nominees = NomineesElection;
 	type Bridge = PolkadotBridge;
 	type Currency = Currencies;
+	type MaxFastMatchRedeemers = MaxFastMatchRedeemers;
+	type MaxRedeemRequestsProcessed = MaxRedeemRequestsProcessed;
+	type MaxMintRecordsPruned = MaxMintRecordsPruned;
 }
 
 impl module_homa::Config for Runtime {
 	type Homa = StakingPool;
+	type MaxFastMatchRedeemers = MaxFastMatchRedeemers;
 	type WeightInfo = weights::module_homa::WeightInfo<Runtime>;
 }
 
@@ -1064,6 +1263,8 @@ parameter_types! {
 	pub const NominateesCount: u32 = 7;
 	pub const MaxUnlockingChunks: u32 = 7;
 	pub const NomineesElectionBondingDuration: EraIndex = 7;
+	pub const MinEraPoints: u32 = 1_000;
+	pub const MaxUnderperformingEras: u32 = 3;
 }
 
 impl module_nominees_election::Config for Runtime {
@@ -1074,6 +1275,9 @@ impl module_nominees_election::Config for Runtime {
 	type NominateesCount = NominateesCount;
 	type MaxUnlockingChunks = MaxUnlockingChunks;
 	type RelaychainValidatorFilter = runtime_common::RelaychainValidatorFilter;
+	type UpdateOrigin = EnsureRootOrHalfHomaCouncil;
+	type MinEraPoints = MinEraPoints;
+	type MaxUnderperformingEras = MaxUnderperformingEras;
 }
 
 parameter_types! {
@@ -1100,13 +1304,20 @@ impl module_homa_validator_list::Config for Runtime {
 parameter_types! {
 	pub CreateClassDeposit: Balance = 500 * millicent(ACA);
 	pub CreateTokenDeposit: Balance = 100 * millicent(ACA);
+	pub DataDepositPerByte: Balance = millicent(ACA);
+	pub const MaxSweepBatch: u32 = 100;
+	pub const MaxBatch: u32 = 100;
 }
 
 impl module_nft::Config for Runtime {
 	type Event = Event;
 	type CreateClassDeposit = CreateClassDeposit;
 	type CreateTokenDeposit = CreateTokenDeposit;
+	type DataDepositPerByte = DataDepositPerByte;
 	type PalletId = NftPalletId;
+	type MaxSweepBatch = MaxSweepBatch;
+	type MaxBatch = MaxBatch;
+	type MultiCurrency = Currencies;
 	type WeightInfo = weights::module_nft::WeightInfo<Runtime>;
 }
 
@@ -1159,13 +1370,20 @@ impl ecosystem_renvm_bridge::Config for Runtime {
 
 parameter_types! {
 	// TODO: update
+	// the EVM chain ID of the existing chain, only used by migration
 	pub const ChainId: u64 = 787;
 	pub const NewContractExtraBytes: u32 = 10_000;
 	pub StorageDepositPerByte: Balance = microcent(ACA);
 	pub const MaxCodeSize: u32 = 60 * 1024;
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(0);
+	pub EvmReapedDepositPot: AccountId = EVMReapPotPalletId::get().into_account();
+	pub const ReapInactivityPeriod: BlockNumber = 180 * DAYS;
+	pub const ReceiptsHistoryDepth: BlockNumber = DAYS;
+	pub const MaxListedCallers: u32 = 100;
 	pub DeveloperDeposit: Balance = dollar(ACA);
 	pub DeploymentFee: Balance = dollar(ACA);
+	pub const ScheduleCallMaxDelay: BlockNumber = 7 * DAYS;
+	pub ScheduleCallDeposit: Balance = dollar(ACA);
 }
 
 pub type MultiCurrencyPrecompile = runtime_common::MultiCurrencyPrecompile<
@@ -1173,12 +1391,26 @@ pub type MultiCurrencyPrecompile = runtime_common::MultiCurrencyPrecompile<
 	EvmAddressMapping<Runtime>,
 	EvmCurrencyIdMapping<Runtime>,
 	Currencies,
+	GasToWeight,
+	weights::module_currencies::WeightInfo<Runtime>,
 >;
 
-pub type NFTPrecompile =
-	runtime_common::NFTPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, NFT>;
-pub type StateRentPrecompile =
-	runtime_common::StateRentPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, EVM>;
+pub type NFTPrecompile = runtime_common::NFTPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	NFT,
+	GasToWeight,
+	weights::module_nft::WeightInfo<Runtime>,
+>;
+pub type StateRentPrecompile = runtime_common::StateRentPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	EVM,
+	GasToWeight,
+	weights::module_evm::WeightInfo<Runtime>,
+>;
 pub type OraclePrecompile =
 	runtime_common::OraclePrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Prices>;
 pub type ScheduleCallPrecompile = runtime_common::ScheduleCallPrecompile<
@@ -1191,10 +1423,73 @@ pub type ScheduleCallPrecompile = runtime_common::ScheduleCallPrecompile<
 	Origin,
 	OriginCaller,
 	Runtime,
+	ScheduleCallMaxDelay,
+	ScheduleCallDeposit,
+	GasToWeight,
 >;
 
-pub type DexPrecompile =
-	runtime_common::DexPrecompile<AccountId, EvmAddressMapping<Runtime>, EvmCurrencyIdMapping<Runtime>, Dex>;
+pub type DexPrecompile = runtime_common::DexPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	Dex,
+	GasToWeight,
+	weights::module_dex::WeightInfo<Runtime>,
+	MaxRangeBuckets,
+>;
+pub type HomaPrecompile = runtime_common::HomaPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	StakingPool,
+	GasToWeight,
+	weights::module_homa::WeightInfo<Runtime>,
+	MaxFastMatchRedeemers,
+>;
+pub type AccessControlPrecompile = runtime_common::AccessControlPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	EVM,
+	GasToWeight,
+	weights::module_evm::WeightInfo<Runtime>,
+>;
+// pallet-contracts is not in the runtime yet, the calls are rejected.
+pub type WasmContractsPrecompile = runtime_common::WasmContractsPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	(),
+	GasToWeight,
+>;
+pub type HonzonPrecompile = runtime_common::HonzonPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	Honzon,
+	GasToWeight,
+	weights::module_honzon::WeightInfo<Runtime>,
+>;
+pub type IncentivesPrecompile = runtime_common::IncentivesPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	Incentives,
+	GasToWeight,
+	weights::module_incentives::WeightInfo<Runtime>,
+>;
+parameter_types! {
+	pub const XcmPrecompileTransferWeight: Weight = 200_000_000;
+}
+
+pub type XcmPrecompile = runtime_common::XcmPrecompile<
+	AccountId,
+	EvmAddressMapping<Runtime>,
+	EvmCurrencyIdMapping<Runtime>,
+	XTokensTransfer,
+	GasToWeight,
+	XcmPrecompileTransferWeight,
+>;
 
 impl module_evm::Config for Runtime {
 	type AddressMapping = EvmAddressMapping<Runtime>;
@@ -1206,14 +1501,20 @@ impl module_evm::Config for Runtime {
 	type Event = Event;
 	type Precompiles = runtime_common::AllPrecompiles<
 		SystemContractsFilter,
+		EVM,
 		MultiCurrencyPrecompile,
 		NFTPrecompile,
 		StateRentPrecompile,
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		XcmPrecompile,
+		HonzonPrecompile,
+		IncentivesPrecompile,
+		HomaPrecompile,
+		AccessControlPrecompile,
+		WasmContractsPrecompile,
 	>;
-	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Runtime>;
 	type NetworkContractOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
@@ -1222,6 +1523,11 @@ impl module_evm::Config for Runtime {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = AcalaTreasuryAccount;
 	type FreeDeploymentOrigin = EnsureRootOrHalfGeneralCouncil;
+	type CallFilter = TransactionPause;
+	type ReapInactivityPeriod = ReapInactivityPeriod;
+	type ReapedDepositPot = EvmReapedDepositPot;
+	type ReceiptsHistoryDepth = ReceiptsHistoryDepth;
+	type MaxListedCallers = MaxListedCallers;
 	type WeightInfo = weights::module_evm::WeightInfo<Runtime>;
 }
 
@@ -1286,7 +1592,8 @@ pub struct XcmConfig;
 impl Config for XcmConfig {
 	type Call = Call;
 	type XcmSender = XcmHandler;
-	type AssetTransactor = LocalAssetTransactor;
+	type AssetTransactor =
+		TrapFailedDeposit<Runtime, LimitOutboundTransfer<Runtime, CurrencyIdConvert, LocalAssetTransactor>>;
 	type OriginConverter = LocalOriginConverter;
 	type IsReserve = MultiNativeAsset;
 	type IsTeleporter = ();
@@ -1325,33 +1632,38 @@ impl Convert<CurrencyId, Option<MultiLocation>> for CurrencyIdConvert {
 	fn convert(id: CurrencyId) -> Option<MultiLocation> {
 		use CurrencyId::Token;
 		use TokenSymbol::*;
-		match id {
+		// the registered location takes precedence over the default location
+		AssetIdConvert::<Runtime>::convert(id).or_else(|| match id {
 			Token(DOT) => Some(X1(Parent)),
 			Token(ACA) | Token(AUSD) | Token(LDOT) | Token(RENBTC) => Some(native_currency_location(id)),
 			_ => None,
-		}
+		})
 	}
 }
 impl Convert<MultiLocation, Option<CurrencyId>> for CurrencyIdConvert {
 	fn convert(location: MultiLocation) -> Option<CurrencyId> {
 		use CurrencyId::Token;
 		use TokenSymbol::*;
-		match location {
-			X1(Parent) => Some(Token(DOT)),
-			X3(Parent, Parachain { id }, GeneralKey(key)) if ParaId::from(id) == ParachainInfo::get() => {
-				// decode the general key
-				if let Ok(currency_id) = CurrencyId::decode(&mut &key[..]) {
-					// check `currency_id` is cross-chain asset
-					match currency_id {
-						Token(ACA) | Token(AUSD) | Token(LDOT) | Token(RENBTC) => Some(currency_id),
-						_ => None,
+		AssetIdConvert::<Runtime>::convert(location.clone()).or_else(|| {
+			match location {
+				X1(Parent) => Some(Token(DOT)),
+				X3(Parent, Parachain { id }, GeneralKey(key)) if ParaId::from(id) == ParachainInfo::get() => {
+					// decode the general key
+					if let Ok(currency_id) = CurrencyId::decode(&mut &key[..]) {
+						// check `currency_id` is cross-chain asset
+						match currency_id {
+							Token(ACA) | Token(AUSD) | Token(LDOT) | Token(RENBTC) => Some(currency_id),
+							_ => None,
+						}
+					} else {
+						None
 					}
-				} else {
-					None
 				}
+				_ => None,
 			}
-			_ => None,
-		}
+			// the default location of the native token with registered location is not accepted
+			.filter(|currency_id| AssetIdConvert::<Runtime>::convert(*currency_id).is_none())
+		})
 	}
 }
 impl Convert<MultiAsset, Option<CurrencyId>> for CurrencyIdConvert {
@@ -1378,6 +1690,70 @@ impl orml_xtokens::Config for Runtime {
 	type XcmHandler = HandleXcm;
 }
 
+parameter_types! {
+	pub const MaxCallPrefixLength: u32 = 64;
+	pub const MinTrappedAmount: Balance = 1_000_000_000;
+	pub const RelayChainTransactWeight: Weight = 5_000_000_000;
+	pub const RelayChainUtilityPalletIndex: u8 = 26;
+	pub const RelayChainProxyPalletIndex: u8 = 29;
+}
+
+/// The relay chain fee of `Transact` priced by the XCM fee of the relay
+/// chain currency registered in the asset registry.
+pub struct RelayChainTransactFee;
+impl Get<Balance> for RelayChainTransactFee {
+	fn get() -> Balance {
+		AssetRegistry::xcm_fee(CurrencyId::Token(TokenSymbol::DOT), RelayChainTransactWeight::get())
+			.unwrap_or_else(|| 10 * cent(DOT))
+	}
+}
+
+impl module_xcm_interface::Config for Runtime {
+	type Event = Event;
+	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type CurrencyIdConvert = CurrencyIdConvert;
+	type AccountId32Convert = AccountId32Convert;
+	type SelfLocation = SelfLocation;
+	type XcmHandler = HandleXcm;
+	type AssetTransactor = LocalAssetTransactor;
+	type LocationConverter = LocationConverter;
+	type MaxCallPrefixLength = MaxCallPrefixLength;
+	type MinTrappedAmount = MinTrappedAmount;
+	type SubAccountOrigin = EnsureRootOrHalfHomaCouncil;
+	type RelayChainFeePayer = AcalaTreasuryAccount;
+	type RelayChainTransactFee = RelayChainTransactFee;
+	type RelayChainTransactWeight = RelayChainTransactWeight;
+	type RelayChainUtilityPalletIndex = RelayChainUtilityPalletIndex;
+	type RelayChainProxyPalletIndex = RelayChainProxyPalletIndex;
+	type WeightInfo = weights::module_xcm_interface::WeightInfo<Runtime>;
+}
+
+pub struct XTokensTransfer;
+impl module_support::XcmTransfer<AccountId, Balance, CurrencyId> for XTokensTransfer {
+	fn transfer(
+		who: AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		dest: MultiLocation,
+		dest_weight: Weight,
+	) -> DispatchResult {
+		XTokens::transfer(Origin::signed(who), currency_id, amount, dest, dest_weight)
+			.map(|_| ())
+			.map_err(|e| e.error)
+	}
+
+	fn transfer_multi_asset(
+		who: AccountId,
+		asset: MultiAsset,
+		dest: MultiLocation,
+		dest_weight: Weight,
+	) -> DispatchResult {
+		XTokens::transfer_multiasset(Origin::signed(who), asset, dest, dest_weight)
+			.map(|_| ())
+			.map_err(|e| e.error)
+	}
+}
+
 impl orml_unknown_tokens::Config for Runtime {
 	type Event = Event;
 }
@@ -1397,9 +1773,9 @@ construct_runtime!(
 		// Tokens & Related
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>} = 3,
 
-		TransactionPayment: module_transaction_payment::{Pallet, Call, Storage} = 4,
+		TransactionPayment: module_transaction_payment::{Pallet, Call, Storage, Event<T>} = 4,
 		EvmAccounts: module_evm_accounts::{Pallet, Call, Storage, Event<T>} = 5,
-		EvmManager: module_evm_manager::{Pallet, Storage} = 6,
+		EvmManager: module_evm_manager::{Pallet, Storage, Event<T>} = 6,
 		Currencies: module_currencies::{Pallet, Call, Event<T>} = 7,
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>} = 8,
 		Vesting: orml_vesting::{Pallet, Storage, Call, Event<T>, Config<T>} = 9,
@@ -1448,6 +1824,7 @@ construct_runtime!(
 
 		// DEX
 		Dex: module_dex::{Pallet, Storage, Call, Event<T>, Config<T>} = 38,
+		ProtocolLiquidity: module_protocol_liquidity::{Pallet, Storage, Call, Event<T>} = 63,
 
 		// Honzon
 		AuctionManager: module_auction_manager::{Pallet, Storage, Call, Event<T>, ValidateUnsigned} = 39,
@@ -1456,12 +1833,13 @@ construct_runtime!(
 		CdpTreasury: module_cdp_treasury::{Pallet, Storage, Call, Config, Event<T>} = 42,
 		CdpEngine: module_cdp_engine::{Pallet, Storage, Call, Event<T>, Config, ValidateUnsigned} = 43,
 		EmergencyShutdown: module_emergency_shutdown::{Pallet, Storage, Call, Event<T>} = 44,
+		CircuitBreaker: module_circuit_breaker::{Pallet, Storage, Call, Event<T>} = 64,
 
 		// Homa
 		Homa: module_homa::{Pallet, Call} = 45,
 		NomineesElection: module_nominees_election::{Pallet, Call, Storage} = 46,
 		StakingPool: module_staking_pool::{Pallet, Call, Storage, Event<T>, Config} = 47,
-		PolkadotBridge: module_polkadot_bridge::{Pallet, Call, Storage} = 48,
+		PolkadotBridge: module_polkadot_bridge::{Pallet, Call, Storage, Event<T>} = 48,
 		HomaValidatorListModule: module_homa_validator_list::{Pallet, Call, Storage, Event<T>} = 49,
 
 		// Acala Other
@@ -1481,6 +1859,9 @@ construct_runtime!(
 		XcmHandler: cumulus_pallet_xcm_handler::{Pallet, Event<T>, Origin} = 57,
 		XTokens: orml_xtokens::{Pallet, Storage, Call, Event<T>} = 58,
 		UnknownTokens: orml_unknown_tokens::{Pallet, Storage, Event} = 59,
+		XcmInterface: module_xcm_interface::{Pallet, Storage, Call, Event<T>} = 61,
+		AssetRegistry: module_asset_registry::{Pallet, Storage, Call, Event<T>} = 62,
+		TransactionPause: module_transaction_pause::{Pallet, Storage, Call, Event<T>} = 65,
 
 		// Dev
 		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>} = 60,
@@ -1515,8 +1896,14 @@ pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 /// Extrinsic type that has already been checked.
 pub type CheckedExtrinsic = generic::CheckedExtrinsic<AccountId, Call, SignedExtra>;
 /// Executive: handles dispatch to the various modules.
-pub type Executive =
-	frame_executive::Executive<Runtime, Block, frame_system::ChainContext<Runtime>, Runtime, AllPallets>;
+pub type Executive = frame_executive::Executive<
+	Runtime,
+	Block,
+	frame_system::ChainContext<Runtime>,
+	Runtime,
+	AllPallets,
+	module_evm::MigrateChainId<Runtime, ChainId>,
+>;
 
 #[cfg(not(feature = "disable-runtime-api"))]
 impl_runtime_apis! {
@@ -1603,11 +1990,11 @@ impl_runtime_apis! {
 		Balance,
 	> for Runtime {
 		fn query_info(uxt: <Block as BlockT>::Extrinsic, len: u32) -> RuntimeDispatchInfo<Balance> {
-			TransactionPayment::query_info(uxt, len)
+			TransactionPayment::query_info(&uxt.function, len)
 		}
 
 		fn query_fee_details(uxt: <Block as BlockT>::Extrinsic, len: u32) -> pallet_transaction_payment_rpc_runtime_api::FeeDetails<Balance> {
-			TransactionPayment::query_fee_details(uxt, len)
+			TransactionPayment::query_fee_details(&uxt.function, len)
 		}
 	}
 
@@ -1648,6 +2035,145 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_staking_pool_rpc_runtime_api::HomaApi<Block, Balance> for Runtime {
+		fn get_homa_info() -> module_staking_pool_rpc_runtime_api::HomaInfo<Balance> {
+			runtime_common::homa_info::<Runtime>((365 * DAYS).checked_div(EraLength::get()).unwrap_or_default())
+		}
+	}
+
+	impl module_dex_rpc_runtime_api::DexApi<
+		Block,
+		AccountId,
+		Balance,
+	> for Runtime {
+		fn get_liquidity_share_holders(
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			start_key: Option<Vec<u8>>,
+			max_scan: u32,
+		) -> module_dex_rpc_runtime_api::ShareHoldersPage<AccountId, Balance> {
+			let max_scan = max_scan.min(module_dex_rpc_runtime_api::MAX_SHARE_HOLDERS_SCAN);
+			let (holders, next_key) = TradingPair::from_token_currency_ids(currency_id_a, currency_id_b)
+				.and_then(|trading_pair| trading_pair.get_dex_share_currency_id())
+				.map(|lp_currency_id| runtime_common::dex_share_holders::<Runtime>(lp_currency_id, start_key, max_scan))
+				.unwrap_or_default();
+
+			module_dex_rpc_runtime_api::ShareHoldersPage {
+				holders: holders
+					.into_iter()
+					.map(|(who, share)| module_dex_rpc_runtime_api::ShareHolder { who, share })
+					.collect(),
+				next_key,
+			}
+		}
+
+		fn get_trading_volume(
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) -> Option<module_dex_rpc_runtime_api::TradingVolume<Balance>> {
+			runtime_common::dex_trading_volume::<Runtime>(currency_id_a, currency_id_b)
+		}
+	}
+
+	impl module_currencies_rpc_runtime_api::CurrenciesApi<
+		Block,
+		AccountId,
+		Balance,
+	> for Runtime {
+		fn account_assets(who: AccountId) -> Vec<module_currencies_rpc_runtime_api::AssetBalance<Balance>> {
+			runtime_common::account_assets::<Runtime>(&who, module_currencies_rpc_runtime_api::MAX_ERC20_ASSETS)
+				.into_iter()
+				.map(|(currency_id, free, reserved)| module_currencies_rpc_runtime_api::AssetBalance {
+					currency_id,
+					free,
+					reserved,
+				})
+				.collect()
+		}
+	}
+
+	impl module_cdp_engine_rpc_runtime_api::CdpEngineApi<
+		Block,
+		AccountId,
+		Balance,
+	> for Runtime {
+		fn simulate_adjust_loan(
+			who: AccountId,
+			currency_id: CurrencyId,
+			collateral_adjustment: Amount,
+			debit_adjustment: Amount,
+		) -> module_cdp_engine_rpc_runtime_api::LoanSimulation<Balance> {
+			let (position, result) =
+				CdpEngine::simulate_adjust_position(&who, currency_id, collateral_adjustment, debit_adjustment);
+
+			module_cdp_engine_rpc_runtime_api::LoanSimulation {
+				collateral: position.collateral,
+				debit: position.debit,
+				debit_value: CdpEngine::get_debit_value(currency_id, position.debit),
+				stable_adjustment: CdpEngine::get_debit_value(
+					currency_id,
+					debit_adjustment.saturating_abs().saturated_into(),
+				),
+				collateral_ratio: CdpEngine::get_collateral_ratio(currency_id, position.collateral, position.debit),
+				liquidation_ratio: CdpEngine::get_liquidation_ratio(currency_id),
+				required_collateral_ratio: CdpEngine::required_collateral_ratio(currency_id),
+				debit_exchange_rate: CdpEngine::get_debit_exchange_rate(currency_id),
+				interest_rate_per_sec: CdpEngine::get_interest_rate_per_sec(currency_id),
+				succeeded: result.is_ok(),
+				error: result.err().map(|e| <&'static str>::from(e).as_bytes().to_vec()),
+			}
+		}
+	}
+
+	impl module_loans_rpc_runtime_api::LoansApi<
+		Block,
+		AccountId,
+		Balance,
+	> for Runtime {
+		fn positions_of(
+			who: AccountId,
+			start: u32,
+			limit: u32,
+		) -> Vec<module_loans_rpc_runtime_api::LoanPosition<Balance>> {
+			Loans::positions_of(&who, CollateralCurrencyIds::get(), start, limit)
+				.into_iter()
+				.map(|(currency_id, position, label)| module_loans_rpc_runtime_api::LoanPosition {
+					currency_id,
+					collateral: position.collateral,
+					debit: position.debit,
+					label,
+				})
+				.collect()
+		}
+	}
+
+	impl module_prices_rpc_runtime_api::PricesApi<Block> for Runtime {
+		fn normalized_price(currency_id: CurrencyId) -> Option<module_prices_rpc_runtime_api::NormalizedPrice> {
+			Prices::normalized_price(currency_id).map(|(raw_price, decimals, normalized_price)| {
+				module_prices_rpc_runtime_api::NormalizedPrice {
+					raw_price,
+					decimals,
+					normalized_price,
+				}
+			})
+		}
+	}
+
+	impl module_transaction_payment_rpc_runtime_api::TransactionPaymentRuntimeApi<
+		Block,
+		Balance,
+		CurrencyId,
+	> for Runtime {
+		fn query_evm_fee_multiplier() -> Multiplier {
+			TransactionPayment::next_evm_fee_multiplier()
+		}
+
+		fn query_fee_in_currency(uxt: <Block as BlockT>::Extrinsic, currency_id: CurrencyId) -> Option<Balance> {
+			let len = uxt.encoded_size() as u32;
+			TransactionPayment::query_fee_in_currency(&uxt.function, len, currency_id)
+		}
+	}
+
 	impl module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance> for Runtime {
 		fn call(
 			from: H160,
@@ -1734,6 +2260,52 @@ impl_runtime_apis! {
 
 			request.ok_or(sp_runtime::DispatchError::Other("Invalid parameter extrinsic, not evm Call"))
 		}
+
+		fn estimate_resources(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+		) -> Result<EstimateResourcesInfo<Balance>, sp_runtime::DispatchError> {
+			EVM::estimate_resources(from, to, data, value, gas_limit, storage_limit)
+		}
+
+		fn contract_storage_info(address: H160) -> Option<ContractStorageInfo<Balance>> {
+			EVM::contract_storage_info(&address)
+		}
+
+		fn account_nonce_info(address: H160) -> AccountNonceInfo<AccountId, Nonce> {
+			EVM::account_nonce_info(&address)
+		}
+
+		fn block_gas_info() -> BlockGasInfo<Balance> {
+			let max_weight = RuntimeBlockWeights::get().max_block;
+			let gas_limit = <GasToWeight as runtime_common::GasWeightMapping>::weight_to_gas(max_weight);
+			BlockGasInfo {
+				gas_used: EVM::block_gas_used(System::block_number()),
+				gas_limit,
+				// the weight fee of a full block shared by its gas
+				next_base_fee_per_gas: TransactionPayment::next_evm_weight_fee(max_weight) / Balance::from(gas_limit.max(1)),
+			}
+		}
+
+		fn block_logs() -> BlockLogsInfo {
+			let logs = System::events()
+				.into_iter()
+				.filter_map(|record| match (record.phase, record.event) {
+					(frame_system::Phase::ApplyExtrinsic(index), Event::module_evm(module_evm::Event::Log(log))) => {
+						Some((index, log))
+					}
+					_ => None,
+				})
+				.collect();
+			BlockLogsInfo {
+				logs,
+				logs_bloom: EVM::logs_bloom(System::block_number()),
+			}
+		}
 	}
 
 
@@ -48,8 +48,26 @@ use sp_std::marker::PhantomData;
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> module_auction_manager::WeightInfo for WeightInfo<T> {
 	fn cancel_collateral_auction() -> Weight {
-		(76_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+		(176_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(16 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
+	fn set_institutional_bidder() -> Weight {
+		(12_463_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn pay_installment() -> Weight {
+		(96_821_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn default_installment() -> Weight {
+		(71_378_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
 			.saturating_add(T::DbWeight::get().writes(7 as Weight))
 	}
+	fn bid_with_swap() -> Weight {
+		(248_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(17 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
 }
@@ -50,6 +50,26 @@ impl<T: frame_system::Config> module_dex::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn enable_stable_swap_trading_pair() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn enable_range_trading_pair() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn add_range_liquidity() -> Weight {
+		(471_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(61 as Weight))
+			.saturating_add(T::DbWeight::get().writes(63 as Weight))
+	}
+	fn remove_range_liquidity() -> Weight {
+		(431_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(60 as Weight))
+			.saturating_add(T::DbWeight::get().writes(61 as Weight))
+	}
 	fn disable_trading_pair() -> Weight {
 		(20_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
@@ -80,18 +100,74 @@ impl<T: frame_system::Config> module_dex::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(12 as Weight))
 			.saturating_add(T::DbWeight::get().writes(12 as Weight))
 	}
-	fn swap_with_exact_supply(u: u32) -> Weight {
+	fn swap_with_exact_supply(u: u32, b: u32) -> Weight {
 		(98_000_000 as Weight)
-			// Standard Error: 360_000
-			.saturating_add((2_100_000 as Weight).saturating_mul(u as Weight))
-			.saturating_add(T::DbWeight::get().reads(10 as Weight))
-			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+			// Standard Error: 412_000
+			.saturating_add((9_734_000 as Weight).saturating_mul(u as Weight))
+			// Standard Error: 31_000
+			.saturating_add((3_482_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(b as Weight)))
 	}
-	fn swap_with_exact_target(u: u32) -> Weight {
+	fn swap_with_exact_target(u: u32, b: u32) -> Weight {
 		(99_600_000 as Weight)
-			// Standard Error: 268_000
-			.saturating_add((1_100_000 as Weight).saturating_mul(u as Weight))
-			.saturating_add(T::DbWeight::get().reads(10 as Weight))
-			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+			// Standard Error: 412_000
+			.saturating_add((10_215_000 as Weight).saturating_mul(u as Weight))
+			// Standard Error: 31_000
+			.saturating_add((3_517_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(b as Weight)))
+	}
+	fn swap_basket_for_exact_target(u: u32) -> Weight {
+		(13_420_000 as Weight)
+			// Standard Error: 183_000
+			.saturating_add((171_204_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(T::DbWeight::get().reads((15 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes((12 as Weight).saturating_mul(u as Weight)))
+	}
+	fn set_exchange_fee() -> Weight {
+		(21_400_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn claim_refund() -> Weight {
+		(146_723_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn set_referrer_fee_share() -> Weight {
+		(20_912_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn claim_referrer_rewards() -> Weight {
+		(92_418_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn propose_listing() -> Weight {
+		(58_216_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn veto_listing() -> Weight {
+		(61_734_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn on_initialize(c: u32) -> Weight {
+		(3_412_000 as Weight)
+			// Standard Error: 41_000
+			.saturating_add((72_583_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)))
+	}
+	fn set_max_price_impact() -> Weight {
+		(22_018_000 as Weight).saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
 }
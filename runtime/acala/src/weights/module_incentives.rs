@@ -82,4 +82,39 @@ impl<T: frame_system::Config> module_incentives::WeightInfo for WeightInfo<T> {
 	fn add_allowance() -> Weight {
 		(2_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn retire_pool() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn migrate_pool_shares(c: u32) -> Weight {
+		(5_000_000 as Weight)
+			.saturating_add((68_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((4 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(c as Weight)))
+	}
+	fn update_deposit_caps(c: u32) -> Weight {
+		(1_102_000 as Weight)
+			// Standard Error: 24_000
+			.saturating_add((2_153_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn update_accumulate_periods(c: u32) -> Weight {
+		(1_058_000 as Weight)
+			// Standard Error: 22_000
+			.saturating_add((2_309_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn claim_snapshot_rewards() -> Weight {
+		(41_552_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn compound_rewards() -> Weight {
+		(418_916_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(26 as Weight))
+			.saturating_add(T::DbWeight::get().writes(22 as Weight))
+	}
 }
@@ -45,6 +45,14 @@ use sp_std::marker::PhantomData;
 /// Weight functions for module_prices.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> module_prices::WeightInfo for WeightInfo<T> {
+	fn on_initialize(c: u32) -> Weight {
+		(2_000_000 as Weight)
+			// Standard Error: 15_000
+			.saturating_add((12_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
 	fn lock_price() -> Weight {
 		(73_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(11 as Weight))
@@ -53,4 +61,17 @@ impl<T: frame_system::Config> module_prices::WeightInfo for WeightInfo<T> {
 	fn unlock_price() -> Weight {
 		(15_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn pause_price() -> Weight {
+		(50_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn resume_price() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_liquid_crowdloan_discount() -> Weight {
+		(25_617_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
@@ -50,7 +50,61 @@ impl<T: frame_system::Config> module_transaction_payment::WeightInfo for WeightI
 	}
 	fn on_finalize() -> Weight {
 		(8_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn with_fee_currency() -> Weight {
+		(12_000_000 as Weight)
+	}
+	fn register_sponsor() -> Weight {
+		(31_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn unregister_sponsor() -> Weight {
+		(14_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn with_sponsor() -> Weight {
+		(12_000_000 as Weight)
+	}
+	fn set_rebate_tiers() -> Weight {
+		(4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_tip_split() -> Weight {
+		(4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_rebate_stake() -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn claim_rebate() -> Weight {
+		(48_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn set_fee_burn_rate() -> Weight {
+		(4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_fee_pool() -> Weight {
+		(5_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn settle_rebates(i: u32) -> Weight {
+		(5_000_000 as Weight)
+			// Standard Error: 8_000
+			.saturating_add((32_000_000 as Weight).saturating_mul(i as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(i as Weight)))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(i as Weight)))
+	}
+	fn charge_fee_by_swap(p: u32) -> Weight {
+		(120_000_000 as Weight)
+			.saturating_add((4_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(p as Weight)))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
 	}
 }
@@ -0,0 +1,303 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! The EVM side of the chain extension of `pallet_contracts`, the Wasm
+//! (ink!) contracts call the EVM contracts through it.
+//!
+//! The runtime implements `pallet_contracts::chain_extension::ChainExtension`
+//! by reading the input of the extension call, passing it to
+//! `EvmChainExtension::call` with the function id and the account id of the
+//! calling contract, charging the returned weight and writing the output
+//! back. The input and output are SCALE encoded:
+//! - `EVM_CALL_FUNC_ID`: `EvmCallRequest` => `EvmCallResult`
+//! - `EVM_ADDRESS_FUNC_ID`: `AccountId` => `Option<EvmAddress>`
+//! - `ACCOUNT_ID_FUNC_ID`: `EvmAddress` => `AccountId`
+//!
+//! The EVM contracts call the Wasm contracts through
+//! `WasmContractsPrecompile`.
+
+use crate::precompile::gas::GasWeightMapping as GasWeightMappingT;
+use codec::{Decode, Encode};
+use frame_support::{log, traits::Get, weights::Weight};
+use module_support::{AddressMapping as AddressMappingT, ExecutionMode, InvokeContext, EVM as EVMT};
+use primitives::{evm::EvmAddress, Balance};
+use sp_runtime::{DispatchError, RuntimeDebug, SaturatedConversion};
+use sp_std::{fmt::Debug, marker::PhantomData, prelude::*, result};
+
+/// Call an EVM contract.
+pub const EVM_CALL_FUNC_ID: u32 = 1;
+/// Get the EVM address of an account id.
+pub const EVM_ADDRESS_FUNC_ID: u32 = 2;
+/// Get the account id of an EVM address.
+pub const ACCOUNT_ID_FUNC_ID: u32 = 3;
+
+/// The EVM call of a Wasm contract.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug)]
+pub struct EvmCallRequest {
+	/// The EVM contract to call
+	pub contract: EvmAddress,
+	/// The ABI encoded input of the EVM contract
+	pub input: Vec<u8>,
+	/// The balance to transfer
+	pub value: Balance,
+	/// The gas limit of the call
+	pub gas_limit: u64,
+	/// The storage limit of the call
+	pub storage_limit: u32,
+}
+
+/// The result of the EVM call of a Wasm contract.
+#[derive(Encode, Decode, Eq, PartialEq, Clone, RuntimeDebug)]
+pub struct EvmCallResult {
+	/// Whether the EVM call succeeded, the state changes are reverted if not
+	pub succeeded: bool,
+	/// The ABI encoded output, or the revert data
+	pub output: Vec<u8>,
+	/// The gas used by the call
+	pub used_gas: u64,
+}
+
+/// Serves the chain extension calls of the Wasm contracts.
+///
+/// The account ids are mapped to the EVM addresses by `AddressMapping`, the
+/// calling contract is the sender and the origin of the EVM call. The gas
+/// used by the EVM call is charged as weight by `GasWeightMapping`, the
+/// mapping lookups are charged by `MappingWeight`.
+pub struct EvmChainExtension<AccountId, AddressMapping, EVM, GasWeightMapping, MappingWeight>(
+	PhantomData<(AccountId, AddressMapping, EVM, GasWeightMapping, MappingWeight)>,
+);
+
+impl<AccountId, AddressMapping, EVM, GasWeightMapping, MappingWeight>
+	EvmChainExtension<AccountId, AddressMapping, EVM, GasWeightMapping, MappingWeight>
+where
+	AccountId: Debug + Encode + Decode,
+	AddressMapping: AddressMappingT<AccountId>,
+	EVM: EVMT<AccountId, Balance = Balance>,
+	GasWeightMapping: GasWeightMappingT,
+	MappingWeight: Get<Weight>,
+{
+	/// The max weight of the call of `func_id` with `input`, to be charged
+	/// before the call and refunded to the weight returned by `call`.
+	pub fn max_weight(func_id: u32, input: &[u8]) -> Weight {
+		match func_id {
+			EVM_CALL_FUNC_ID => EvmCallRequest::decode(&mut &input[..])
+				.map(|request| GasWeightMapping::gas_to_weight(request.gas_limit))
+				.unwrap_or_default()
+				.saturating_add(MappingWeight::get()),
+			_ => MappingWeight::get(),
+		}
+	}
+
+	/// Serve the call of `func_id` from the Wasm contract `caller`, returns
+	/// the SCALE encoded output and the weight used.
+	pub fn call(func_id: u32, caller: AccountId, input: &[u8]) -> result::Result<(Vec<u8>, Weight), DispatchError> {
+		match func_id {
+			EVM_CALL_FUNC_ID => {
+				let request = EvmCallRequest::decode(&mut &input[..])
+					.map_err(|_| DispatchError::Other("Decode EvmCallRequest failed"))?;
+				let sender = AddressMapping::get_or_create_evm_address(&caller);
+				log::debug!(
+					target: "evm",
+					"chain_extension: evm call caller: {:?}, contract: {:?}, value: {:?}, gas_limit: {:?}",
+					caller, request.contract, request.value, request.gas_limit
+				);
+
+				let info = EVM::execute(
+					InvokeContext {
+						contract: request.contract,
+						sender,
+						origin: sender,
+					},
+					request.input,
+					request.value,
+					request.gas_limit,
+					request.storage_limit,
+					ExecutionMode::Execute,
+				)?;
+
+				let used_gas = info.used_gas.saturated_into::<u64>();
+				let result = EvmCallResult {
+					succeeded: info.exit_reason.is_succeed(),
+					output: info.output,
+					used_gas,
+				};
+				Ok((
+					result.encode(),
+					GasWeightMapping::gas_to_weight(used_gas).saturating_add(MappingWeight::get()),
+				))
+			}
+			EVM_ADDRESS_FUNC_ID => {
+				let account_id =
+					AccountId::decode(&mut &input[..]).map_err(|_| DispatchError::Other("Decode AccountId failed"))?;
+				Ok((
+					AddressMapping::get_evm_address(&account_id).encode(),
+					MappingWeight::get(),
+				))
+			}
+			ACCOUNT_ID_FUNC_ID => {
+				let address = EvmAddress::decode(&mut &input[..])
+					.map_err(|_| DispatchError::Other("Decode EvmAddress failed"))?;
+				Ok((AddressMapping::get_account_id(&address).encode(), MappingWeight::get()))
+			}
+			_ => Err(DispatchError::Other("unknown chain extension func_id")),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use module_evm::{ExitReason, ExitRevert, ExitSucceed};
+	use module_support::mocks::MockAddressMapping;
+	use primitives::evm::CallInfo;
+	use sp_core::{crypto::AccountId32, U256};
+	use sp_std::cell::RefCell;
+
+	thread_local! {
+		static EVM_CALLS: RefCell<Vec<(InvokeContext, Vec<u8>, Balance)>> = RefCell::new(vec![]);
+	}
+
+	/// Records the calls, returns the input as the output, reverts if the
+	/// value is zero.
+	pub struct MockEVM;
+	impl EVMT<AccountId32> for MockEVM {
+		type Balance = Balance;
+
+		fn execute(
+			context: InvokeContext,
+			input: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			_storage_limit: u32,
+			_mode: ExecutionMode,
+		) -> result::Result<CallInfo, DispatchError> {
+			EVM_CALLS.with(|v| v.borrow_mut().push((context, input.clone(), value)));
+			let exit_reason = if value == 0 {
+				ExitReason::Revert(ExitRevert::Reverted)
+			} else {
+				ExitReason::Succeed(ExitSucceed::Returned)
+			};
+			Ok(CallInfo {
+				exit_reason,
+				output: input,
+				used_gas: U256::from(gas_limit / 2),
+				used_storage: 0,
+			})
+		}
+
+		fn get_origin() -> Option<AccountId32> {
+			None
+		}
+
+		fn set_origin(_origin: AccountId32) {}
+	}
+
+	pub struct GasToWeight;
+	impl GasWeightMappingT for GasToWeight {
+		fn gas_to_weight(gas: u64) -> Weight {
+			gas
+		}
+		fn weight_to_gas(weight: Weight) -> u64 {
+			weight
+		}
+	}
+
+	frame_support::parameter_types! {
+		pub const MappingWeight: Weight = 10;
+	}
+
+	type ChainExtension = EvmChainExtension<AccountId32, MockAddressMapping, MockEVM, GasToWeight, MappingWeight>;
+
+	const CALLER: AccountId32 = AccountId32::new([1u8; 32]);
+
+	#[test]
+	fn evm_call_works() {
+		let sender = MockAddressMapping::get_or_create_evm_address(&CALLER);
+		let request = EvmCallRequest {
+			contract: EvmAddress::from_low_u64_be(1),
+			input: vec![1, 2, 3],
+			value: 1_000,
+			gas_limit: 800,
+			storage_limit: 100,
+		};
+		assert_eq!(ChainExtension::max_weight(EVM_CALL_FUNC_ID, &request.encode()), 810);
+		assert_eq!(
+			ChainExtension::call(EVM_CALL_FUNC_ID, CALLER, &request.encode()),
+			Ok((
+				EvmCallResult {
+					succeeded: true,
+					output: vec![1, 2, 3],
+					used_gas: 400,
+				}
+				.encode(),
+				410
+			))
+		);
+
+		let request = EvmCallRequest { value: 0, ..request };
+		assert_eq!(
+			ChainExtension::call(EVM_CALL_FUNC_ID, CALLER, &request.encode()),
+			Ok((
+				EvmCallResult {
+					succeeded: false,
+					output: vec![1, 2, 3],
+					used_gas: 400,
+				}
+				.encode(),
+				410
+			))
+		);
+
+		let context = InvokeContext {
+			contract: EvmAddress::from_low_u64_be(1),
+			sender,
+			origin: sender,
+		};
+		assert_eq!(
+			EVM_CALLS.with(|v| v.borrow().clone()),
+			vec![(context, vec![1, 2, 3], 1_000), (context, vec![1, 2, 3], 0)]
+		);
+
+		assert_eq!(
+			ChainExtension::call(EVM_CALL_FUNC_ID, CALLER, &[0xff]),
+			Err(DispatchError::Other("Decode EvmCallRequest failed"))
+		);
+	}
+
+	#[test]
+	fn address_mapping_works() {
+		let address = EvmAddress::from_low_u64_be(1);
+		let account_id = MockAddressMapping::get_account_id(&address);
+
+		assert_eq!(
+			ChainExtension::call(EVM_ADDRESS_FUNC_ID, account_id.clone(), &account_id.encode()),
+			Ok((Some(address).encode(), 10))
+		);
+		assert_eq!(
+			ChainExtension::call(EVM_ADDRESS_FUNC_ID, CALLER, &CALLER.encode()),
+			Ok((None::<EvmAddress>.encode(), 10))
+		);
+		assert_eq!(
+			ChainExtension::call(ACCOUNT_ID_FUNC_ID, CALLER, &address.encode()),
+			Ok((account_id.encode(), 10))
+		);
+		assert_eq!(
+			ChainExtension::call(0, CALLER, &[]),
+			Err(DispatchError::Other("unknown chain extension func_id"))
+		);
+	}
+}
@@ -20,28 +20,38 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::{Decode, Encode};
 use frame_support::{
 	parameter_types,
+	storage::{generator::StorageDoubleMap as _, unhashed, StoragePrefixedMap},
+	traits::Get,
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, WEIGHT_PER_SECOND},
 		DispatchClass, Weight,
 	},
+	Blake2_128Concat, ReversibleStorageHasher, Twox64Concat,
 };
 use frame_system::limits;
+use module_dex_rpc_runtime_api::{TradingVolume, VolumeAndFee};
+use module_staking_pool_rpc_runtime_api::HomaInfo;
 pub use module_support::{ExchangeRate, PrecompileCallerFilter, Price, Rate, Ratio};
-use primitives::{Balance, CurrencyId, PRECOMPILE_ADDRESS_START, PREDEPLOY_ADDRESS_START};
+use orml_traits::{MultiCurrency, MultiReservableCurrency};
+use primitives::{Balance, BlockNumber, CurrencyId, TradingPair, PRECOMPILE_ADDRESS_START, PREDEPLOY_ADDRESS_START};
 use sp_core::H160;
 use sp_runtime::{
-	traits::{Convert, Saturating},
+	traits::{Convert, Saturating, Zero},
 	transaction_validity::TransactionPriority,
 	FixedPointNumber, FixedPointOperand, Perbill,
 };
+use sp_std::{marker::PhantomData, prelude::*, vec};
 use static_assertions::const_assert;
 
+pub mod chain_extension;
 pub mod precompile;
 pub use precompile::{
-	AllPrecompiles, DexPrecompile, MultiCurrencyPrecompile, NFTPrecompile, OraclePrecompile, ScheduleCallPrecompile,
-	StateRentPrecompile,
+	gas::GasWeightMapping, AccessControlPrecompile, AllPrecompiles, DexPrecompile, HomaPrecompile, HonzonPrecompile,
+	IncentivesPrecompile, MultiCurrencyPrecompile, NFTPrecompile, OraclePrecompile, ScheduleCallPrecompile,
+	StateRentPrecompile, WasmContractsPrecompile, XcmPrecompile,
 };
 pub use primitives::currency::{
 	GetDecimals, ACA, AUSD, DOT, KAR, KILT, KSM, KUSD, LDOT, LKSM, PHA, PLM, POLKABTC, RENBTC, SDN, XBTC,
@@ -313,6 +323,16 @@ impl Convert<u64, Weight> for GasToWeight {
 	}
 }
 
+impl GasWeightMapping for GasToWeight {
+	fn gas_to_weight(gas: u64) -> Weight {
+		Self::convert(gas)
+	}
+
+	fn weight_to_gas(weight: Weight) -> u64 {
+		weight as u64
+	}
+}
+
 pub const AVERAGE_ON_INITIALIZE_RATIO: Perbill = Perbill::from_perthousand(25);
 /// We allow `Normal` extrinsics to fill up the block up to 75%, the rest can be
 /// used by  Operational  extrinsics.
@@ -366,6 +386,15 @@ impl<AccountId> orml_traits::Contains<AccountId> for RelaychainValidatorFilter {
 	}
 }
 
+/// The relaychain block number observed via the parachain validation data,
+/// `None` if the validation data is unavailable.
+pub struct RelaychainBlockNumberProvider<T>(PhantomData<T>);
+impl<T: cumulus_pallet_parachain_system::Config> Get<Option<BlockNumber>> for RelaychainBlockNumberProvider<T> {
+	fn get() -> Option<BlockNumber> {
+		cumulus_pallet_parachain_system::Pallet::<T>::validation_data().map(|data| data.relay_parent_number)
+	}
+}
+
 pub fn dollar(currency_id: CurrencyId) -> Balance {
 	10u128.saturating_pow(currency_id.decimals().expect("Not support Erc20 decimals").into())
 }
@@ -386,6 +415,171 @@ pub fn deposit(items: u32, bytes: u32, currency_id: CurrencyId) -> Balance {
 	items as Balance * 15 * cent(currency_id) + (bytes as Balance) * 6 * cent(currency_id)
 }
 
+/// Scan at most `max_scan` storage entries for the holders of the LP share
+/// `lp_currency_id`, from the storage key cursor `start_key`, or from the
+/// beginning if it's `None`. The LP shares held directly are scanned first,
+/// then the ones deposited to incentives, so an account holding both appears
+/// twice. Returns the holders found and the cursor to continue from, which is
+/// `None` once the scan is completed.
+pub fn dex_share_holders<T>(
+	lp_currency_id: CurrencyId,
+	start_key: Option<Vec<u8>>,
+	max_scan: u32,
+) -> (Vec<(T::AccountId, Balance)>, Option<Vec<u8>>)
+where
+	T: orml_tokens::Config<CurrencyId = CurrencyId, Balance = Balance> + module_incentives::Config,
+{
+	let incentives_account = module_incentives::Pallet::<T>::account_id();
+	let accounts_prefix = orml_tokens::Accounts::<T>::final_prefix().to_vec();
+	let deposits_prefix = orml_rewards::ShareAndWithdrawnReward::<T>::storage_double_map_final_key1(
+		module_incentives::PoolId::DexIncentive(lp_currency_id),
+	);
+
+	let mut holders = vec![];
+	let mut key = start_key.unwrap_or_else(|| accounts_prefix.clone());
+	for _ in 0..max_scan {
+		if key.starts_with(&accounts_prefix) {
+			// the LP shares held directly, keyed by `(who, currency_id)`
+			match sp_io::storage::next_key(&key).filter(|next| next.starts_with(&accounts_prefix)) {
+				Some(next) => {
+					let mut raw_key = Blake2_128Concat::reverse(&next[accounts_prefix.len()..]);
+					if let Ok(who) = T::AccountId::decode(&mut raw_key) {
+						if Twox64Concat::reverse(raw_key) == &lp_currency_id.encode()[..] && who != incentives_account {
+							let account_data =
+								unhashed::get::<orml_tokens::AccountData<Balance>>(&next).unwrap_or_default();
+							holders.push((who, account_data.free.saturating_add(account_data.reserved)));
+						}
+					}
+					key = next;
+				}
+				None => key = deposits_prefix.clone(),
+			}
+		} else if key.starts_with(&deposits_prefix) {
+			// the LP shares deposited to incentives, keyed by `(pool_id, who)`
+			match sp_io::storage::next_key(&key).filter(|next| next.starts_with(&deposits_prefix)) {
+				Some(next) => {
+					let mut raw_key = Twox64Concat::reverse(&next[deposits_prefix.len()..]);
+					if let Ok(who) = T::AccountId::decode(&mut raw_key) {
+						let (share, _) = unhashed::get::<(Balance, Balance)>(&next).unwrap_or_default();
+						holders.push((who, share));
+					}
+					key = next;
+				}
+				None => {
+					return (
+						holders.into_iter().filter(|(_, share)| !share.is_zero()).collect(),
+						None,
+					)
+				}
+			}
+		} else {
+			// invalid cursor
+			return (vec![], None);
+		}
+	}
+
+	(
+		holders.into_iter().filter(|(_, share)| !share.is_zero()).collect(),
+		Some(key),
+	)
+}
+
+/// Collect the non-zero `(currency_id, free, reserved)` balances of `who` in
+/// the native currency, the tokens, and at most `max_erc20_assets` registered
+/// ERC20 tokens.
+pub fn account_assets<T>(who: &T::AccountId, max_erc20_assets: u32) -> Vec<(CurrencyId, Balance, Balance)>
+where
+	T: module_currencies::Config
+		+ module_evm_manager::Config
+		+ orml_tokens::Config<CurrencyId = CurrencyId, Balance = Balance>,
+	module_currencies::Pallet<T>: MultiReservableCurrency<T::AccountId, CurrencyId = CurrencyId, Balance = Balance>,
+{
+	let native_currency_id = <T as module_currencies::Config>::GetNativeCurrencyId::get();
+	let mut assets = vec![(
+		native_currency_id,
+		module_currencies::Pallet::<T>::free_balance(native_currency_id, who),
+		module_currencies::Pallet::<T>::reserved_balance(native_currency_id, who),
+	)];
+
+	assets.extend(
+		orml_tokens::Accounts::<T>::iter_prefix(who)
+			.map(|(currency_id, account_data)| (currency_id, account_data.free, account_data.reserved)),
+	);
+
+	// ERC20 balances are queried via evm-bridge, bound the number of calls.
+	assets.extend(
+		module_evm_manager::CurrencyIdMap::<T>::iter()
+			.take(max_erc20_assets as usize)
+			.map(|(_, erc20_info)| {
+				let currency_id = CurrencyId::Erc20(erc20_info.address);
+				(
+					currency_id,
+					module_currencies::Pallet::<T>::free_balance(currency_id, who),
+					Zero::zero(),
+				)
+			}),
+	);
+
+	assets
+		.into_iter()
+		.filter(|(_, free, reserved)| !free.is_zero() || !reserved.is_zero())
+		.collect()
+}
+
+/// The recorded swap volume and fees of the trading pair of `currency_id_a`
+/// and `currency_id_b`, in the order of the queried currencies.
+pub fn dex_trading_volume<T: module_dex::Config>(
+	currency_id_a: CurrencyId,
+	currency_id_b: CurrencyId,
+) -> Option<TradingVolume<Balance>> {
+	let trading_pair = TradingPair::from_token_currency_ids(currency_id_a, currency_id_b)?;
+	let is_a_0 = trading_pair.0 == currency_id_a;
+	let to_volume_and_fee = |record: module_dex::VolumeRecord| {
+		if is_a_0 {
+			VolumeAndFee {
+				volume_a: record.volume_0,
+				volume_b: record.volume_1,
+				fee_a: record.fee_0,
+				fee_b: record.fee_1,
+			}
+		} else {
+			VolumeAndFee {
+				volume_a: record.volume_1,
+				volume_b: record.volume_0,
+				fee_a: record.fee_1,
+				fee_b: record.fee_0,
+			}
+		}
+	};
+
+	Some(TradingVolume {
+		cumulative: to_volume_and_fee(module_dex::Pallet::<T>::cumulative_volumes(trading_pair)),
+		rolling: to_volume_and_fee(module_dex::Pallet::<T>::rolling_volume(trading_pair)),
+		periods: module_dex::Pallet::<T>::volume_history(trading_pair)
+			.into_iter()
+			.map(to_volume_and_fee)
+			.collect(),
+	})
+}
+
+/// The overview of Homa, the estimated APY compounds the estimated era reward
+/// rate for `eras_per_year` eras.
+pub fn homa_info<T: module_staking_pool::Config>(eras_per_year: u32) -> HomaInfo<Balance> {
+	let ledger = module_staking_pool::Pallet::<T>::staking_pool_ledger();
+	let (redeem_requests_count, redeem_requests_amount) = module_staking_pool::Pallet::<T>::pending_redeem_requests();
+
+	HomaInfo {
+		liquid_exchange_rate: module_staking_pool::Pallet::<T>::liquid_exchange_rate(),
+		total_bonded: ledger.bonded,
+		total_staking: ledger.total_belong_to_liquid_holders(),
+		redeem_requests_count,
+		redeem_requests_amount,
+		estimated_era_reward_rate: module_staking_pool::Pallet::<T>::estimated_era_reward_rate(),
+		estimated_apy: module_staking_pool::Pallet::<T>::estimated_apy(eras_per_year),
+		unbonding_duration: module_staking_pool::Pallet::<T>::unbonding_duration(),
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -0,0 +1,193 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use frame_support::log;
+use module_evm::{Context, ExitError, ExitSucceed, Precompile, WeightInfo as WeightInfoT};
+use sp_core::U256;
+use sp_std::{borrow::Cow, convert::TryFrom, marker::PhantomData, prelude::*, result};
+
+use module_support::{
+	AddressMapping as AddressMappingT, CallerAccessMode, CurrencyIdMapping as CurrencyIdMappingT,
+	EVMCallerAccessControl,
+};
+
+use super::{
+	gas::{charge_weight, GasWeightMapping as GasWeightMappingT},
+	input::{Input, InputT},
+};
+
+/// The `AccessControl` impl precompile.
+///
+/// The caller access mode of a contract is encoded as `0` for unrestricted,
+/// `1` for allow list and `2` for deny list.
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - QueryCallerAccessMode. Rest `input` bytes: `contract`.
+/// - QueryIsListedCaller. Rest `input` bytes: `contract`, `caller`.
+/// - QueryIsCallerAllowed. Rest `input` bytes: `contract`, `caller`.
+/// - SetCallerAccessMode. Rest `input` bytes: `from`, `contract`, `mode`.
+/// - AddListedCaller. Rest `input` bytes: `from`, `contract`, `caller`.
+/// - RemoveListedCaller. Rest `input` bytes: `from`, `contract`, `caller`.
+///
+/// The actions updating the caller access control charge the gas converted
+/// from their weight by `GasWeightMapping`. A listed caller takes a storage
+/// deposit from `from`, which is refunded when the caller is removed.
+pub struct AccessControlPrecompile<AccountId, AddressMapping, CurrencyIdMapping, EVM, GasWeightMapping, WeightInfo>(
+	PhantomData<(
+		AccountId,
+		AddressMapping,
+		CurrencyIdMapping,
+		EVM,
+		GasWeightMapping,
+		WeightInfo,
+	)>,
+);
+
+enum Action {
+	QueryCallerAccessMode,
+	QueryIsListedCaller,
+	QueryIsCallerAllowed,
+	SetCallerAccessMode,
+	AddListedCaller,
+	RemoveListedCaller,
+}
+
+impl TryFrom<u8> for Action {
+	type Error = ();
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		// reserve 0 - 127 for query, 128 - 255 for action
+		match value {
+			0 => Ok(Action::QueryCallerAccessMode),
+			1 => Ok(Action::QueryIsListedCaller),
+			2 => Ok(Action::QueryIsCallerAllowed),
+			128 => Ok(Action::SetCallerAccessMode),
+			129 => Ok(Action::AddListedCaller),
+			130 => Ok(Action::RemoveListedCaller),
+			_ => Err(()),
+		}
+	}
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, EVM, GasWeightMapping, WeightInfo> Precompile
+	for AccessControlPrecompile<AccountId, AddressMapping, CurrencyIdMapping, EVM, GasWeightMapping, WeightInfo>
+where
+	AccountId: Clone,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	EVM: EVMCallerAccessControl<AccountId>,
+	GasWeightMapping: GasWeightMappingT,
+	WeightInfo: WeightInfoT,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		log::debug!(target: "evm", "access_control input: {:?}", input);
+		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(input);
+
+		let action = input.action()?;
+
+		match action {
+			Action::QueryCallerAccessMode => {
+				let contract = input.evm_address_at(1)?;
+
+				let mode = match EVM::query_caller_access_mode(contract) {
+					None => 0u32,
+					Some(CallerAccessMode::AllowList) => 1,
+					Some(CallerAccessMode::DenyList) => 2,
+				};
+
+				Ok((ExitSucceed::Returned, vec_u8_from_u32(mode), 0))
+			}
+			Action::QueryIsListedCaller => {
+				let contract = input.evm_address_at(1)?;
+				let caller = input.evm_address_at(2)?;
+
+				let listed = EVM::query_is_listed_caller(contract, caller);
+
+				Ok((ExitSucceed::Returned, vec_u8_from_bool(listed), 0))
+			}
+			Action::QueryIsCallerAllowed => {
+				let contract = input.evm_address_at(1)?;
+				let caller = input.evm_address_at(2)?;
+
+				let allowed = EVM::is_caller_allowed(contract, caller);
+
+				Ok((ExitSucceed::Returned, vec_u8_from_bool(allowed), 0))
+			}
+			Action::SetCallerAccessMode => {
+				let from = input.account_id_at(1)?;
+				let contract = input.evm_address_at(2)?;
+				let mode = match input.u32_at(3)? {
+					0 => None,
+					1 => Some(CallerAccessMode::AllowList),
+					2 => Some(CallerAccessMode::DenyList),
+					_ => return Err(ExitError::Other("invalid caller access mode".into())),
+				};
+
+				let listed_caller_count = EVM::query_listed_caller_count(contract);
+				let used_gas = charge_weight::<GasWeightMapping>(
+					WeightInfo::set_caller_access_mode(listed_caller_count),
+					target_gas,
+				)?;
+
+				EVM::set_caller_access_mode(from, contract, mode)
+					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+			Action::AddListedCaller => {
+				let from = input.account_id_at(1)?;
+				let contract = input.evm_address_at(2)?;
+				let caller = input.evm_address_at(3)?;
+
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::add_listed_caller(), target_gas)?;
+
+				EVM::add_listed_caller(from, contract, caller)
+					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+			Action::RemoveListedCaller => {
+				let from = input.account_id_at(1)?;
+				let contract = input.evm_address_at(2)?;
+				let caller = input.evm_address_at(3)?;
+
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::remove_listed_caller(), target_gas)?;
+
+				EVM::remove_listed_caller(from, contract, caller)
+					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+		}
+	}
+}
+
+fn vec_u8_from_u32(b: u32) -> Vec<u8> {
+	let mut be_bytes = [0u8; 32];
+	U256::from(b).to_big_endian(&mut be_bytes[..]);
+	be_bytes.to_vec()
+}
+
+fn vec_u8_from_bool(b: bool) -> Vec<u8> {
+	vec_u8_from_u32(b as u32)
+}
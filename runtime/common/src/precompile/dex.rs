@@ -16,8 +16,12 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use super::input::{Input, InputT};
-use frame_support::log;
+use super::{
+	gas::{charge_weight, GasWeightMapping as GasWeightMappingT},
+	input::{Input, InputT},
+};
+use frame_support::{log, traits::Get};
+use module_dex::WeightInfo as WeightInfoT;
 use module_evm::{Context, ExitError, ExitSucceed, Precompile};
 use module_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, DEXManager};
 use primitives::{Balance, CurrencyId};
@@ -33,8 +37,29 @@ use sp_std::{convert::TryFrom, fmt::Debug, marker::PhantomData, prelude::*, resu
 /// - Get liquidity. Rest `input` bytes: `currency_id_a`, `currency_id_b`.
 /// - Swap with exact supply. Rest `input` bytes: `who`, `currency_id_a`,
 ///   `currency_id_b`, `supply_amount`, `min_target_amount`.
-pub struct DexPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Dex>(
-	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, Dex)>,
+///
+/// The actions executing dex operations charge the gas converted from the
+/// weight of the corresponding dispatchables by `GasWeightMapping`, the
+/// swaps are charged for walking up to `MaxRangeBuckets` range buckets of
+/// each trading pair in path.
+pub struct DexPrecompile<
+	AccountId,
+	AddressMapping,
+	CurrencyIdMapping,
+	Dex,
+	GasWeightMapping,
+	WeightInfo,
+	MaxRangeBuckets,
+>(
+	PhantomData<(
+		AccountId,
+		AddressMapping,
+		CurrencyIdMapping,
+		Dex,
+		GasWeightMapping,
+		WeightInfo,
+		MaxRangeBuckets,
+	)>,
 );
 
 enum Action {
@@ -64,21 +89,22 @@ impl TryFrom<u8> for Action {
 	}
 }
 
-impl<AccountId, AddressMapping, CurrencyIdMapping, Dex> Precompile
-	for DexPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Dex>
+impl<AccountId, AddressMapping, CurrencyIdMapping, Dex, GasWeightMapping, WeightInfo, MaxRangeBuckets> Precompile
+	for DexPrecompile<AccountId, AddressMapping, CurrencyIdMapping, Dex, GasWeightMapping, WeightInfo, MaxRangeBuckets>
 where
 	AccountId: Debug + Clone,
 	AddressMapping: AddressMappingT<AccountId>,
 	CurrencyIdMapping: CurrencyIdMappingT,
 	Dex: DEXManager<AccountId, CurrencyId, Balance>,
+	GasWeightMapping: GasWeightMappingT,
+	WeightInfo: WeightInfoT,
+	MaxRangeBuckets: Get<u32>,
 {
 	fn execute(
 		input: &[u8],
-		_target_gas: Option<u64>,
+		target_gas: Option<u64>,
 		_context: &Context,
 	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
-		//TODO: evaluate cost
-
 		log::debug!(target: "evm", "input: {:?}", input);
 
 		// Solidity dynamic arrays will add the array size to the front of the array,
@@ -165,6 +191,14 @@ where
 					who, path, supply_amount, min_target_amount
 				);
 
+				let used_gas = charge_weight::<GasWeightMapping>(
+					WeightInfo::swap_with_exact_supply(
+						path_len,
+						MaxRangeBuckets::get().saturating_mul(path_len.saturating_sub(1)),
+					),
+					target_gas,
+				)?;
+
 				let value =
 					Dex::swap_with_exact_supply(&who, &path, supply_amount, min_target_amount, None).map_err(|e| {
 						let err_msg: &str = e.into();
@@ -175,7 +209,7 @@ where
 				let mut be_bytes = [0u8; 32];
 				U256::from(value).to_big_endian(&mut be_bytes[..32]);
 
-				Ok((ExitSucceed::Returned, be_bytes.to_vec(), 0))
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), used_gas))
 			}
 			Action::SwapWithExactTarget => {
 				let who = input.account_id_at(1)?;
@@ -192,6 +226,14 @@ where
 					who, path, target_amount, max_supply_amount
 				);
 
+				let used_gas = charge_weight::<GasWeightMapping>(
+					WeightInfo::swap_with_exact_target(
+						path_len,
+						MaxRangeBuckets::get().saturating_mul(path_len.saturating_sub(1)),
+					),
+					target_gas,
+				)?;
+
 				let value =
 					Dex::swap_with_exact_target(&who, &path, target_amount, max_supply_amount, None).map_err(|e| {
 						let err_msg: &str = e.into();
@@ -202,7 +244,7 @@ where
 				let mut be_bytes = [0u8; 32];
 				U256::from(value).to_big_endian(&mut be_bytes[..32]);
 
-				Ok((ExitSucceed::Returned, be_bytes.to_vec(), 0))
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), used_gas))
 			}
 			Action::AddLiquidity => {
 				let who = input.account_id_at(1)?;
@@ -216,6 +258,8 @@ where
 					who, currency_id_a, currency_id_b, max_amount_a, max_amount_b,
 				);
 
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::add_liquidity(), target_gas)?;
+
 				Dex::add_liquidity(&who, currency_id_a, currency_id_b, max_amount_a, max_amount_b, false).map_err(
 					|e| {
 						let err_msg: &str = e.into();
@@ -223,7 +267,7 @@ where
 					},
 				)?;
 
-				Ok((ExitSucceed::Returned, vec![], 0))
+				Ok((ExitSucceed::Returned, vec![], used_gas))
 			}
 			Action::RemoveLiquidity => {
 				let who = input.account_id_at(1)?;
@@ -236,12 +280,14 @@ where
 					who, currency_id_a, currency_id_b, remove_share
 				);
 
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::remove_liquidity(), target_gas)?;
+
 				Dex::remove_liquidity(&who, currency_id_a, currency_id_b, remove_share, false).map_err(|e| {
 					let err_msg: &str = e.into();
 					ExitError::Other(err_msg.into())
 				})?;
 
-				Ok((ExitSucceed::Returned, vec![], 0))
+				Ok((ExitSucceed::Returned, vec![], used_gas))
 			}
 		}
 	}
@@ -0,0 +1,48 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Charge the gas of precompiles by the benchmarked weight of the
+//! dispatchables they execute.
+
+use frame_support::{ensure, weights::Weight};
+use module_evm::ExitError;
+use sp_std::result;
+
+/// Mapping between gas and weight.
+pub trait GasWeightMapping {
+	/// Convert gas to weight.
+	fn gas_to_weight(gas: u64) -> Weight;
+	/// Convert weight to gas.
+	fn weight_to_gas(weight: Weight) -> u64;
+}
+
+/// Returns the gas to charge for executing a dispatchable of `weight`, or
+/// `OutOfGas` if it exceeds `target_gas`.
+///
+/// The weight of the EVM extrinsic is calculated from the used gas, so the
+/// weight of the dispatchable is recorded against the block as well.
+pub fn charge_weight<Mapping: GasWeightMapping>(
+	weight: Weight,
+	target_gas: Option<u64>,
+) -> result::Result<u64, ExitError> {
+	let gas = Mapping::weight_to_gas(weight);
+	if let Some(target_gas) = target_gas {
+		ensure!(gas <= target_gas, ExitError::OutOfGas);
+	}
+	Ok(gas)
+}
@@ -0,0 +1,168 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	gas::{charge_weight, GasWeightMapping as GasWeightMappingT},
+	input::{Input, InputT},
+};
+use frame_support::{log, traits::Get};
+use module_evm::{Context, ExitError, ExitSucceed, Precompile};
+use module_homa::WeightInfo as WeightInfoT;
+use module_support::{
+	AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, HomaProtocol as HomaProtocolT,
+};
+use primitives::{Balance, EraIndex};
+use sp_core::U256;
+use sp_runtime::FixedPointNumber;
+use sp_std::{convert::TryFrom, fmt::Debug, marker::PhantomData, prelude::*, result};
+
+/// The `Homa` impl precompile.
+///
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Mint. Rest `input` bytes: `who`, `amount`.
+/// - Request redeem. Rest `input` bytes: `who`, `amount`, `allow_fast_match`.
+/// - Get exchange rate.
+///
+/// The mint and redeem actions charge the gas converted from the weight of
+/// the corresponding dispatchables by `GasWeightMapping`, the mint is
+/// charged for fast matching up to `MaxFastMatchRedeemers` redeem requests.
+pub struct HomaPrecompile<
+	AccountId,
+	AddressMapping,
+	CurrencyIdMapping,
+	Homa,
+	GasWeightMapping,
+	WeightInfo,
+	MaxFastMatchRedeemers,
+>(
+	PhantomData<(
+		AccountId,
+		AddressMapping,
+		CurrencyIdMapping,
+		Homa,
+		GasWeightMapping,
+		WeightInfo,
+		MaxFastMatchRedeemers,
+	)>,
+);
+
+enum Action {
+	Mint,
+	RequestRedeem,
+	GetExchangeRate,
+}
+
+impl TryFrom<u8> for Action {
+	type Error = ();
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(Action::Mint),
+			1 => Ok(Action::RequestRedeem),
+			2 => Ok(Action::GetExchangeRate),
+			_ => Err(()),
+		}
+	}
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, Homa, GasWeightMapping, WeightInfo, MaxFastMatchRedeemers> Precompile
+	for HomaPrecompile<
+		AccountId,
+		AddressMapping,
+		CurrencyIdMapping,
+		Homa,
+		GasWeightMapping,
+		WeightInfo,
+		MaxFastMatchRedeemers,
+	> where
+	AccountId: Debug + Clone,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	Homa: HomaProtocolT<AccountId, Balance, EraIndex>,
+	GasWeightMapping: GasWeightMappingT,
+	WeightInfo: WeightInfoT,
+	MaxFastMatchRedeemers: Get<u32>,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		log::debug!(target: "evm", "input: {:?}", input);
+
+		// Solidity dynamic arrays will add the array size to the front of the array,
+		// pre-compile needs to deal with the `size`.
+		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(&input[32..]);
+
+		let action = input.action()?;
+
+		match action {
+			Action::Mint => {
+				let who = input.account_id_at(1)?;
+				let amount = input.balance_at(2)?;
+				log::debug!(target: "evm", "homa: mint who: {:?}, amount: {:?}", who, amount);
+
+				let used_gas =
+					charge_weight::<GasWeightMapping>(WeightInfo::mint(MaxFastMatchRedeemers::get()), target_gas)?;
+
+				let liquid_amount = Homa::mint(&who, amount).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				// output
+				let mut be_bytes = [0u8; 32];
+				U256::from(liquid_amount).to_big_endian(&mut be_bytes[..32]);
+
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), used_gas))
+			}
+			Action::RequestRedeem => {
+				let who = input.account_id_at(1)?;
+				let amount = input.balance_at(2)?;
+				let allow_fast_match = input.bool_at(3)?;
+				log::debug!(
+					target: "evm",
+					"homa: request_redeem who: {:?}, amount: {:?}, allow_fast_match: {:?}",
+					who, amount, allow_fast_match
+				);
+
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::request_redeem(), target_gas)?;
+
+				Homa::request_redeem(&who, amount, allow_fast_match).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+			Action::GetExchangeRate => {
+				let exchange_rate = Homa::liquid_exchange_rate();
+				log::debug!(target: "evm", "homa: exchange_rate: {:?}", exchange_rate);
+
+				// output
+				let mut be_bytes = [0u8; 32];
+				U256::from(exchange_rate.into_inner()).to_big_endian(&mut be_bytes[..32]);
+
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), 0))
+			}
+		}
+	}
+}
@@ -0,0 +1,168 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	gas::{charge_weight, GasWeightMapping as GasWeightMappingT},
+	input::{Input, InputT},
+};
+use frame_support::log;
+use module_evm::{Context, ExitError, ExitSucceed, Precompile};
+use module_honzon::WeightInfo as WeightInfoT;
+use module_support::{
+	AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, HonzonManager as HonzonManagerT,
+};
+use primitives::{Amount, Balance, CurrencyId};
+use sp_core::U256;
+use sp_std::{convert::TryFrom, fmt::Debug, marker::PhantomData, prelude::*, result};
+
+/// The `Honzon` impl precompile.
+///
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Adjust loan. Rest `input` bytes: `who`, `currency_id`,
+///   `collateral_adjustment`, `debit_adjustment`.
+/// - Close loan by DEX. Rest `input` bytes: `who`, `currency_id`,
+///   `path_len`, `path`.
+/// - Get position. Rest `input` bytes: `who`, `currency_id`.
+///
+/// Adjusting and closing loans are charged by the weight of `adjust_loan`
+/// and `close_loan_has_debit_by_dex` of the honzon module.
+pub struct HonzonPrecompile<AccountId, AddressMapping, CurrencyIdMapping, HonzonManager, GasWeightMapping, WeightInfo>(
+	PhantomData<(
+		AccountId,
+		AddressMapping,
+		CurrencyIdMapping,
+		HonzonManager,
+		GasWeightMapping,
+		WeightInfo,
+	)>,
+);
+
+enum Action {
+	AdjustLoan,
+	CloseLoanByDex,
+	GetPosition,
+}
+
+impl TryFrom<u8> for Action {
+	type Error = ();
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(Action::AdjustLoan),
+			1 => Ok(Action::CloseLoanByDex),
+			2 => Ok(Action::GetPosition),
+			_ => Err(()),
+		}
+	}
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, HonzonManager, GasWeightMapping, WeightInfo> Precompile
+	for HonzonPrecompile<AccountId, AddressMapping, CurrencyIdMapping, HonzonManager, GasWeightMapping, WeightInfo>
+where
+	AccountId: Debug + Clone,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	HonzonManager: HonzonManagerT<AccountId, CurrencyId, Amount, Balance>,
+	GasWeightMapping: GasWeightMappingT,
+	WeightInfo: WeightInfoT,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		log::debug!(target: "evm", "input: {:?}", input);
+
+		// Solidity dynamic arrays will add the array size to the front of the array,
+		// pre-compile needs to deal with the `size`.
+		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(&input[32..]);
+
+		let action = input.action()?;
+
+		match action {
+			Action::AdjustLoan => {
+				let who = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+				let collateral_adjustment = input.amount_at(3)?;
+				let debit_adjustment = input.amount_at(4)?;
+				log::debug!(
+					target: "evm",
+					"honzon: adjust_loan who: {:?}, currency_id: {:?}, collateral_adjustment: {:?}, debit_adjustment: {:?}",
+					who, currency_id, collateral_adjustment, debit_adjustment
+				);
+
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::adjust_loan(), target_gas)?;
+
+				HonzonManager::adjust_loan(&who, currency_id, collateral_adjustment, debit_adjustment).map_err(
+					|e| {
+						let err_msg: &str = e.into();
+						ExitError::Other(err_msg.into())
+					},
+				)?;
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+			Action::CloseLoanByDex => {
+				let who = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+				let path_len = input.u32_at(3)?;
+				let mut path = vec![];
+				for i in 0..path_len {
+					path.push(input.currency_id_at((4 + i) as usize)?);
+				}
+				log::debug!(
+					target: "evm",
+					"honzon: close_loan_by_dex who: {:?}, currency_id: {:?}, path: {:?}",
+					who, currency_id, path
+				);
+
+				let used_gas =
+					charge_weight::<GasWeightMapping>(WeightInfo::close_loan_has_debit_by_dex(), target_gas)?;
+
+				let maybe_path = if path.is_empty() { None } else { Some(&path[..]) };
+				HonzonManager::close_loan_by_dex(who, currency_id, maybe_path).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+			Action::GetPosition => {
+				let who = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+				log::debug!(
+					target: "evm",
+					"honzon: get_position who: {:?}, currency_id: {:?}",
+					who, currency_id
+				);
+
+				let (collateral, debit) = HonzonManager::get_position(&who, currency_id);
+
+				// output
+				let mut be_bytes = [0u8; 64];
+				U256::from(collateral).to_big_endian(&mut be_bytes[..32]);
+				U256::from(debit).to_big_endian(&mut be_bytes[32..64]);
+
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), 0))
+			}
+		}
+	}
+}
@@ -0,0 +1,187 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	gas::{charge_weight, GasWeightMapping as GasWeightMappingT},
+	input::{Input, InputT},
+};
+use frame_support::log;
+use module_evm::{Context, ExitError, ExitSucceed, Precompile};
+use module_incentives::WeightInfo as WeightInfoT;
+use module_support::{
+	AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, IncentivesManager as IncentivesManagerT,
+};
+use primitives::{Balance, CurrencyId};
+use sp_core::U256;
+use sp_std::{convert::TryFrom, fmt::Debug, marker::PhantomData, prelude::*, result};
+
+/// The `Incentives` impl precompile.
+///
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Deposit dex share. Rest `input` bytes: `who`, `lp_currency_id`,
+///   `amount`.
+/// - Withdraw dex share. Rest `input` bytes: `who`, `lp_currency_id`,
+///   `amount`.
+/// - Claim rewards. Rest `input` bytes: `who`, `lp_currency_id`.
+/// - Get pending rewards. Rest `input` bytes: `who`, `lp_currency_id`.
+///
+/// Depositing, withdrawing and claiming cost the gas mapped from the weight
+/// of the same calls of the incentives module.
+pub struct IncentivesPrecompile<
+	AccountId,
+	AddressMapping,
+	CurrencyIdMapping,
+	IncentivesManager,
+	GasWeightMapping,
+	WeightInfo,
+>(
+	PhantomData<(
+		AccountId,
+		AddressMapping,
+		CurrencyIdMapping,
+		IncentivesManager,
+		GasWeightMapping,
+		WeightInfo,
+	)>,
+);
+
+enum Action {
+	DepositDexShare,
+	WithdrawDexShare,
+	ClaimRewards,
+	GetPendingRewards,
+}
+
+impl TryFrom<u8> for Action {
+	type Error = ();
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(Action::DepositDexShare),
+			1 => Ok(Action::WithdrawDexShare),
+			2 => Ok(Action::ClaimRewards),
+			3 => Ok(Action::GetPendingRewards),
+			_ => Err(()),
+		}
+	}
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, IncentivesManager, GasWeightMapping, WeightInfo> Precompile
+	for IncentivesPrecompile<AccountId, AddressMapping, CurrencyIdMapping, IncentivesManager, GasWeightMapping, WeightInfo>
+where
+	AccountId: Debug + Clone,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	IncentivesManager: IncentivesManagerT<AccountId, CurrencyId, Balance>,
+	GasWeightMapping: GasWeightMappingT,
+	WeightInfo: WeightInfoT,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		log::debug!(target: "evm", "input: {:?}", input);
+
+		// Solidity dynamic arrays will add the array size to the front of the array,
+		// pre-compile needs to deal with the `size`.
+		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(&input[32..]);
+
+		let action = input.action()?;
+
+		match action {
+			Action::DepositDexShare => {
+				let who = input.account_id_at(1)?;
+				let lp_currency_id = input.currency_id_at(2)?;
+				let amount = input.balance_at(3)?;
+				log::debug!(
+					target: "evm",
+					"incentives: deposit_dex_share who: {:?}, lp_currency_id: {:?}, amount: {:?}",
+					who, lp_currency_id, amount
+				);
+
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::deposit_dex_share(), target_gas)?;
+
+				IncentivesManager::deposit_dex_share(&who, lp_currency_id, amount).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+			Action::WithdrawDexShare => {
+				let who = input.account_id_at(1)?;
+				let lp_currency_id = input.currency_id_at(2)?;
+				let amount = input.balance_at(3)?;
+				log::debug!(
+					target: "evm",
+					"incentives: withdraw_dex_share who: {:?}, lp_currency_id: {:?}, amount: {:?}",
+					who, lp_currency_id, amount
+				);
+
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::withdraw_dex_share(), target_gas)?;
+
+				IncentivesManager::withdraw_dex_share(&who, lp_currency_id, amount).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+			Action::ClaimRewards => {
+				let who = input.account_id_at(1)?;
+				let lp_currency_id = input.currency_id_at(2)?;
+				log::debug!(
+					target: "evm",
+					"incentives: claim_rewards who: {:?}, lp_currency_id: {:?}",
+					who, lp_currency_id
+				);
+
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::claim_rewards(), target_gas)?;
+
+				IncentivesManager::claim_dex_rewards(&who, lp_currency_id).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+			Action::GetPendingRewards => {
+				let who = input.account_id_at(1)?;
+				let lp_currency_id = input.currency_id_at(2)?;
+				log::debug!(
+					target: "evm",
+					"incentives: get_pending_rewards who: {:?}, lp_currency_id: {:?}",
+					who, lp_currency_id
+				);
+
+				let (incentive, saving) = IncentivesManager::get_pending_dex_rewards(&who, lp_currency_id);
+
+				// output
+				let mut be_bytes = [0u8; 64];
+				U256::from(incentive).to_big_endian(&mut be_bytes[..32]);
+				U256::from(saving).to_big_endian(&mut be_bytes[32..64]);
+
+				Ok((ExitSucceed::Returned, be_bytes.to_vec(), 0))
+			}
+		}
+	}
+}
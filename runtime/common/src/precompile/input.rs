@@ -55,6 +55,7 @@ pub trait InputT {
 
 	fn u64_at(&self, index: usize) -> Result<u64, Self::Error>;
 	fn u32_at(&self, index: usize) -> Result<u32, Self::Error>;
+	fn bool_at(&self, index: usize) -> Result<bool, Self::Error>;
 
 	fn bytes_at(&self, start: usize, len: usize) -> Result<Vec<u8>, Self::Error>;
 }
@@ -171,6 +172,14 @@ where
 		Ok(u32::from_be_bytes(num))
 	}
 
+	fn bool_at(&self, index: usize) -> Result<bool, Self::Error> {
+		match self.u32_at(index)? {
+			0 => Ok(false),
+			1 => Ok(true),
+			_ => Err(ExitError::Other("invalid bool input".into())),
+		}
+	}
+
 	fn bytes_at(&self, start: usize, len: usize) -> Result<Vec<u8>, Self::Error> {
 		let end = start + len;
 
@@ -303,4 +312,19 @@ mod tests {
 		let input = TestInput::new(&raw_input[..]);
 		assert_ok!(input.u64_at(0), u64_num);
 	}
+
+	#[test]
+	fn bool_works() {
+		let input = TestInput::new(&[0u8; 32][..]);
+		assert_ok!(input.bool_at(0), false);
+
+		let mut raw_input = [0u8; 32];
+		raw_input[31] = 1;
+		let input = TestInput::new(&raw_input[..]);
+		assert_ok!(input.bool_at(0), true);
+
+		raw_input[31] = 2;
+		let input = TestInput::new(&raw_input[..]);
+		assert_err!(input.bool_at(0), ExitError::Other("invalid bool input".into()));
+	}
 }
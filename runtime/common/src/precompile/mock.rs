@@ -18,26 +18,30 @@
 
 #![cfg(test)]
 
-use crate::{AllPrecompiles, Ratio, RuntimeBlockWeights, SystemContractsFilter, Weight};
+use crate::{
+	precompile::gas::GasWeightMapping, AllPrecompiles, Ratio, RuntimeBlockWeights, SystemContractsFilter, Weight,
+};
 use codec::{Decode, Encode};
 use frame_support::{
-	assert_ok, ord_parameter_types, parameter_types,
-	traits::{GenesisBuild, InstanceFilter, OnFinalize, OnInitialize},
+	assert_ok, ensure, ord_parameter_types, parameter_types,
+	traits::{Filter, GenesisBuild, InstanceFilter, OnFinalize, OnInitialize},
 	weights::IdentityFee,
 	PalletId, RuntimeDebug,
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
 use module_support::{
 	mocks::MockAddressMapping, AddressMapping as AddressMappingT, DEXIncentives, ExchangeRate, ExchangeRateProvider,
+	HomaProtocol, HonzonManager, IncentivesManager, WasmContracts, XcmTransfer,
 };
 use orml_traits::{parameter_type_with_key, MultiReservableCurrency};
-pub use primitives::{Amount, BlockNumber, CurrencyId, Header, Nonce, TokenSymbol, TradingPair};
+pub use primitives::{Amount, BlockNumber, CurrencyId, EraIndex, Header, Nonce, TokenSymbol, TradingPair};
 use sp_core::{bytes::from_hex, crypto::AccountId32, Bytes, H160, H256};
 use sp_runtime::{
 	traits::{BlakeTwo256, Convert, IdentityLookup},
-	DispatchResult, FixedPointNumber, FixedU128, Perbill,
+	DispatchError, DispatchResult, FixedPointNumber, FixedU128, Perbill,
 };
-use sp_std::{collections::btree_map::BTreeMap, str::FromStr};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap, str::FromStr};
+use xcm::v0::{MultiAsset, MultiLocation};
 
 pub type AccountId = AccountId32;
 type Key = CurrencyId;
@@ -152,20 +156,29 @@ impl module_evm_bridge::Config for Test {
 }
 
 impl module_evm_manager::Config for Test {
+	type Event = Event;
 	type Currency = Balances;
 	type EVMBridge = EVMBridge;
+	type AssetMetadataProvider = ();
 }
 
 parameter_types! {
 	pub const CreateClassDeposit: Balance = 200;
 	pub const CreateTokenDeposit: Balance = 100;
+	pub const DataDepositPerByte: Balance = 1;
 	pub const NftPalletId: PalletId = PalletId(*b"aca/aNFT");
+	pub const MaxSweepBatch: u32 = 10;
+	pub const MaxBatch: u32 = 10;
 }
 impl module_nft::Config for Test {
 	type Event = Event;
 	type CreateClassDeposit = CreateClassDeposit;
 	type CreateTokenDeposit = CreateTokenDeposit;
+	type DataDepositPerByte = DataDepositPerByte;
 	type PalletId = NftPalletId;
+	type MaxSweepBatch = MaxSweepBatch;
+	type MaxBatch = MaxBatch;
+	type MultiCurrency = Currencies;
 	type WeightInfo = ();
 }
 
@@ -181,20 +194,47 @@ parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 	pub AllNonNativeCurrencyIds: Vec<CurrencyId> = vec![CurrencyId::Token(TokenSymbol::AUSD)];
 	pub MaxSlippageSwapWithDEX: Ratio = Ratio::one();
+	pub const SponsorPeriod: BlockNumber = 10;
+	pub const MaxSponsorAllowedCalls: u32 = 2;
+	pub const RebatePeriod: BlockNumber = 10;
+	pub const MaxRebateTiers: u32 = 2;
+	pub const MaxRebatesPerBlock: u32 = 10;
+	pub const FeeBurnReportPeriod: BlockNumber = 10;
+}
+
+pub struct EvmCallFilter;
+impl Filter<Call> for EvmCallFilter {
+	fn filter(call: &Call) -> bool {
+		matches!(call, Call::ModuleEVM(_))
+	}
 }
 
 impl module_transaction_payment::Config for Test {
+	type Event = Event;
+	type Call = Call;
 	type AllNonNativeCurrencyIds = AllNonNativeCurrencyIds;
 	type NativeCurrencyId = GetNativeCurrencyId;
 	type StableCurrencyId = GetStableCurrencyId;
 	type Currency = Balances;
 	type MultiCurrency = Currencies;
 	type OnTransactionPayment = ();
+	type BlockAuthor = ();
 	type TransactionByteFee = TransactionByteFee;
 	type WeightToFee = IdentityFee<Balance>;
 	type FeeMultiplierUpdate = ();
+	type EvmFeeMultiplierUpdate = ();
+	type EvmCallFilter = EvmCallFilter;
 	type DEX = ();
 	type MaxSlippageSwapWithDEX = MaxSlippageSwapWithDEX;
+	type TradingPathLimit = TradingPathLimit;
+	type SponsorPeriod = SponsorPeriod;
+	type MaxSponsorAllowedCalls = MaxSponsorAllowedCalls;
+	type UpdateOrigin = EnsureRoot<AccountId32>;
+	type TreasuryAccount = TreasuryAccount;
+	type RebatePeriod = RebatePeriod;
+	type MaxRebateTiers = MaxRebateTiers;
+	type MaxRebatesPerBlock = MaxRebatesPerBlock;
+	type FeeBurnReportPeriod = FeeBurnReportPeriod;
 	type WeightInfo = ();
 }
 pub type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Test>;
@@ -288,6 +328,14 @@ ord_parameter_types! {
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 100);
 	pub const TradingPathLimit: u32 = 3;
+	pub const ProvisioningPeriod: BlockNumber = 1000;
+	pub const AmplificationRampPeriod: BlockNumber = 10;
+	pub const MaxRangeBuckets: u32 = 10;
+	pub const MaxBasketInputs: u32 = 3;
+	pub const VolumeBucketPeriod: BlockNumber = 10;
+	pub const MaxVolumeBuckets: u32 = 3;
+	pub const ListingBond: Balance = 100;
+	pub const ListingChallengePeriod: BlockNumber = 10;
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
 }
 
@@ -298,20 +346,34 @@ impl module_dex::Config for Test {
 	type TradingPathLimit = TradingPathLimit;
 	type PalletId = DEXPalletId;
 	type CurrencyIdMapping = EvmCurrencyIdMapping;
+	type Erc20ReferenceCounter = EVMManager;
 	type WeightInfo = ();
 	type DEXIncentives = MockDEXIncentives;
 	type ListingOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
+	type ProvisioningPeriod = ProvisioningPeriod;
+	type AmplificationRampPeriod = AmplificationRampPeriod;
+	type MaxRangeBuckets = MaxRangeBuckets;
+	type PositionNFT = ();
+	type MaxBasketInputs = MaxBasketInputs;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
+	type ListingBond = ListingBond;
+	type ListingChallengePeriod = ListingChallengePeriod;
+	type ListingVetoOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
+	type TreasuryAccount = TreasuryAccount;
+	type VolumeBucketPeriod = VolumeBucketPeriod;
+	type MaxVolumeBuckets = MaxVolumeBuckets;
 }
 
 pub type AdaptedBasicCurrency = module_currencies::BasicCurrencyAdapter<Test, Balances, Amount, BlockNumber>;
 
 pub type EvmCurrencyIdMapping = module_evm_manager::EvmCurrencyIdMapping<Test>;
 pub type MultiCurrencyPrecompile =
-	crate::MultiCurrencyPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, Currencies>;
+	crate::MultiCurrencyPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, Currencies, GasToWeight, ()>;
 
-pub type NFTPrecompile = crate::NFTPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, NFTModule>;
+pub type NFTPrecompile =
+	crate::NFTPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, NFTModule, GasToWeight, ()>;
 pub type StateRentPrecompile =
-	crate::StateRentPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, ModuleEVM>;
+	crate::StateRentPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, ModuleEVM, GasToWeight, ()>;
 pub type OraclePrecompile = crate::OraclePrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, Prices>;
 pub type ScheduleCallPrecompile = crate::ScheduleCallPrecompile<
 	AccountId,
@@ -323,8 +385,270 @@ pub type ScheduleCallPrecompile = crate::ScheduleCallPrecompile<
 	Origin,
 	OriginCaller,
 	Test,
+	ScheduleCallMaxDelay,
+	ScheduleCallDeposit,
+	GasToWeight,
+>;
+pub type DexPrecompile = crate::DexPrecompile<
+	AccountId,
+	MockAddressMapping,
+	EvmCurrencyIdMapping,
+	DexModule,
+	GasToWeight,
+	(),
+	MaxRangeBuckets,
 >;
-pub type DexPrecompile = crate::DexPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, DexModule>;
+parameter_types! {
+	pub const XcmTransferWeight: Weight = 200_000_000;
+}
+
+pub type XcmPrecompile = crate::XcmPrecompile<
+	AccountId,
+	MockAddressMapping,
+	EvmCurrencyIdMapping,
+	MockXcmTransfer,
+	GasToWeight,
+	XcmTransferWeight,
+>;
+pub type HonzonPrecompile =
+	crate::HonzonPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, MockHonzon, GasToWeight, ()>;
+pub type IncentivesPrecompile =
+	crate::IncentivesPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, MockIncentives, GasToWeight, ()>;
+parameter_types! {
+	pub const MaxFastMatchRedeemers: u32 = 10;
+}
+
+pub type HomaPrecompile = crate::HomaPrecompile<
+	AccountId,
+	MockAddressMapping,
+	EvmCurrencyIdMapping,
+	MockHoma,
+	GasToWeight,
+	(),
+	MaxFastMatchRedeemers,
+>;
+pub type AccessControlPrecompile =
+	crate::AccessControlPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, ModuleEVM, GasToWeight, ()>;
+pub type WasmContractsPrecompile =
+	crate::WasmContractsPrecompile<AccountId, MockAddressMapping, EvmCurrencyIdMapping, MockWasmContracts, GasToWeight>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum XcmTransferRecord {
+	Transfer(AccountId, CurrencyId, Balance, MultiLocation, Weight),
+	TransferMultiAsset(AccountId, MultiAsset, MultiLocation, Weight),
+}
+
+thread_local! {
+	static XCM_TRANSFERS: RefCell<Vec<XcmTransferRecord>> = RefCell::new(vec![]);
+	static POSITIONS: RefCell<BTreeMap<(AccountId, CurrencyId), (Balance, Balance)>> = RefCell::new(BTreeMap::new());
+	static DEX_SHARES: RefCell<BTreeMap<(AccountId, CurrencyId), Balance>> = RefCell::new(BTreeMap::new());
+	static REDEEM_REQUESTS: RefCell<Vec<(AccountId, Balance, bool)>> = RefCell::new(vec![]);
+	static WASM_CONTRACT_CALLS: RefCell<Vec<(AccountId, AccountId, Balance, Weight, Vec<u8>)>> = RefCell::new(vec![]);
+}
+
+pub fn xcm_transfers() -> Vec<XcmTransferRecord> {
+	XCM_TRANSFERS.with(|v| v.borrow().clone())
+}
+
+pub fn wasm_contract_calls() -> Vec<(AccountId, AccountId, Balance, Weight, Vec<u8>)> {
+	WASM_CONTRACT_CALLS.with(|v| v.borrow().clone())
+}
+
+/// Records the calls, returns the input as the output and uses half of the
+/// weight limit.
+pub struct MockWasmContracts;
+impl WasmContracts<AccountId, Balance> for MockWasmContracts {
+	fn call(
+		origin: AccountId,
+		dest: AccountId,
+		value: Balance,
+		gas_limit: Weight,
+		input: Vec<u8>,
+	) -> Result<(Vec<u8>, Weight), DispatchError> {
+		WASM_CONTRACT_CALLS.with(|v| v.borrow_mut().push((origin, dest, value, gas_limit, input.clone())));
+		Ok((input, gas_limit / 2))
+	}
+}
+
+pub struct MockXcmTransfer;
+impl XcmTransfer<AccountId, Balance, CurrencyId> for MockXcmTransfer {
+	fn transfer(
+		who: AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		dest: MultiLocation,
+		dest_weight: Weight,
+	) -> DispatchResult {
+		XCM_TRANSFERS.with(|v| {
+			v.borrow_mut()
+				.push(XcmTransferRecord::Transfer(who, currency_id, amount, dest, dest_weight))
+		});
+		Ok(())
+	}
+
+	fn transfer_multi_asset(
+		who: AccountId,
+		asset: MultiAsset,
+		dest: MultiLocation,
+		dest_weight: Weight,
+	) -> DispatchResult {
+		XCM_TRANSFERS.with(|v| {
+			v.borrow_mut()
+				.push(XcmTransferRecord::TransferMultiAsset(who, asset, dest, dest_weight))
+		});
+		Ok(())
+	}
+}
+
+/// Keeps the positions in memory, the loan with debit can't be closed.
+pub struct MockHonzon;
+impl HonzonManager<AccountId, CurrencyId, Amount, Balance> for MockHonzon {
+	fn adjust_loan(
+		who: &AccountId,
+		currency_id: CurrencyId,
+		collateral_adjustment: Amount,
+		debit_adjustment: Amount,
+	) -> DispatchResult {
+		POSITIONS.with(|v| {
+			let mut positions = v.borrow_mut();
+			let (collateral, debit) = positions.entry((who.clone(), currency_id)).or_default();
+			*collateral = (*collateral as Amount)
+				.checked_add(collateral_adjustment)
+				.filter(|x| *x >= 0)
+				.ok_or(DispatchError::Other("invalid collateral adjustment"))? as Balance;
+			*debit = (*debit as Amount)
+				.checked_add(debit_adjustment)
+				.filter(|x| *x >= 0)
+				.ok_or(DispatchError::Other("invalid debit adjustment"))? as Balance;
+			Ok(())
+		})
+	}
+
+	fn close_loan_by_dex(
+		_who: AccountId,
+		_currency_id: CurrencyId,
+		_maybe_path: Option<&[CurrencyId]>,
+	) -> DispatchResult {
+		Err(DispatchError::Other("close loan by dex failed"))
+	}
+
+	fn get_position(who: &AccountId, currency_id: CurrencyId) -> (Balance, Balance) {
+		POSITIONS.with(|v| v.borrow().get(&(who.clone(), currency_id)).cloned().unwrap_or_default())
+	}
+}
+
+/// Keeps the staked dex shares in memory, the pending rewards are 10% and 1%
+/// of the staked amount.
+pub struct MockIncentives;
+impl IncentivesManager<AccountId, CurrencyId, Balance> for MockIncentives {
+	fn deposit_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+		DEX_SHARES.with(|v| {
+			let mut shares = v.borrow_mut();
+			let share = shares.entry((who.clone(), lp_currency_id)).or_default();
+			*share = share.saturating_add(amount);
+		});
+		Ok(())
+	}
+
+	fn withdraw_dex_share(who: &AccountId, lp_currency_id: CurrencyId, amount: Balance) -> DispatchResult {
+		DEX_SHARES.with(|v| {
+			let mut shares = v.borrow_mut();
+			let share = shares.entry((who.clone(), lp_currency_id)).or_default();
+			*share = share
+				.checked_sub(amount)
+				.ok_or(DispatchError::Other("dex share not enough"))?;
+			Ok(())
+		})
+	}
+
+	fn claim_dex_rewards(who: &AccountId, lp_currency_id: CurrencyId) -> DispatchResult {
+		let (incentive, saving) = Self::get_pending_dex_rewards(who, lp_currency_id);
+		ensure!(
+			incentive != 0 || saving != 0,
+			DispatchError::Other("no rewards to claim")
+		);
+		Ok(())
+	}
+
+	fn get_pending_dex_rewards(who: &AccountId, lp_currency_id: CurrencyId) -> (Balance, Balance) {
+		let share = DEX_SHARES.with(|v| {
+			v.borrow()
+				.get(&(who.clone(), lp_currency_id))
+				.cloned()
+				.unwrap_or_default()
+		});
+		(share / 10, share / 100)
+	}
+}
+
+pub fn redeem_requests() -> Vec<(AccountId, Balance, bool)> {
+	REDEEM_REQUESTS.with(|v| v.borrow().clone())
+}
+
+/// Mints at the exchange rate of `MockLiquidStakingExchangeProvider` and keeps
+/// the redeem requests in memory, other operations are not supported.
+pub struct MockHoma;
+impl HomaProtocol<AccountId, Balance, EraIndex> for MockHoma {
+	type Balance = Balance;
+
+	fn mint(_who: &AccountId, amount: Balance) -> Result<Balance, DispatchError> {
+		ensure!(amount != 0, DispatchError::Other("mint amount is zero"));
+		Ok(Self::liquid_exchange_rate()
+			.reciprocal()
+			.unwrap_or_default()
+			.saturating_mul_int(amount))
+	}
+
+	fn redeem_by_unbond(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn redeem_by_free_unbonded(_who: &AccountId, _amount: Balance) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn redeem_by_claim_unbonding(_who: &AccountId, _amount: Balance, _target_era: EraIndex) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn withdraw_redemption(_who: &AccountId) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn request_redeem(who: &AccountId, amount: Balance, allow_fast_match: bool) -> DispatchResult {
+		REDEEM_REQUESTS.with(|v| v.borrow_mut().push((who.clone(), amount, allow_fast_match)));
+		Ok(())
+	}
+
+	fn cancel_redeem_request(_who: &AccountId, _amount: Balance) -> Result<Balance, DispatchError> {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn set_redeem_request_fast_match(_who: &AccountId, _allow_fast_match: bool) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn transfer_unbonding(
+		_from: &AccountId,
+		_to: &AccountId,
+		_target_era: EraIndex,
+		_amount: Balance,
+	) -> DispatchResult {
+		Err(DispatchError::Other("not supported"))
+	}
+
+	fn liquid_exchange_rate() -> ExchangeRate {
+		MockLiquidStakingExchangeProvider::get_exchange_rate()
+	}
+
+	fn current_era() -> EraIndex {
+		Default::default()
+	}
+
+	fn total_staking_amount() -> Balance {
+		Default::default()
+	}
+}
 
 parameter_types! {
 	pub NetworkContractSource: H160 = alice();
@@ -338,8 +662,10 @@ ord_parameter_types! {
 	pub const StorageDepositPerByte: u64 = 10;
 	pub const DeveloperDeposit: u64 = 1000;
 	pub const DeploymentFee: u64 = 200;
+	pub const ScheduleCallMaxDelay: BlockNumber = 10;
+	pub const ScheduleCallDeposit: Balance = 100;
 	pub const MaxCodeSize: u32 = 60 * 1024;
-	pub const ChainId: u64 = 1;
+	pub const MaxListedCallers: u32 = 10;
 }
 
 pub struct GasToWeight;
@@ -349,6 +675,16 @@ impl Convert<u64, Weight> for GasToWeight {
 	}
 }
 
+impl GasWeightMapping for GasToWeight {
+	fn gas_to_weight(gas: u64) -> Weight {
+		Self::convert(gas)
+	}
+
+	fn weight_to_gas(weight: Weight) -> u64 {
+		weight as u64
+	}
+}
+
 impl module_evm::Config for Test {
 	type AddressMapping = MockAddressMapping;
 	type Currency = Balances;
@@ -359,14 +695,20 @@ impl module_evm::Config for Test {
 	type Event = Event;
 	type Precompiles = AllPrecompiles<
 		SystemContractsFilter,
+		ModuleEVM,
 		MultiCurrencyPrecompile,
 		NFTPrecompile,
 		StateRentPrecompile,
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		XcmPrecompile,
+		HonzonPrecompile,
+		IncentivesPrecompile,
+		HomaPrecompile,
+		AccessControlPrecompile,
+		WasmContractsPrecompile,
 	>;
-	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = ChargeTransactionPayment;
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId>;
@@ -375,6 +717,11 @@ impl module_evm::Config for Test {
 	type DeploymentFee = DeploymentFee;
 	type TreasuryAccount = TreasuryAccount;
 	type FreeDeploymentOrigin = EnsureSignedBy<CouncilAccount, AccountId>;
+	type CallFilter = ();
+	type ReapInactivityPeriod = ();
+	type ReapedDepositPot = TreasuryAccount;
+	type ReceiptsHistoryDepth = ();
+	type MaxListedCallers = MaxListedCallers;
 	type WeightInfo = ();
 }
 
@@ -387,8 +734,11 @@ impl ExchangeRateProvider for MockLiquidStakingExchangeProvider {
 
 parameter_types! {
 	pub StableCurrencyFixedPrice: Price = Price::saturating_from_rational(1, 1);
+	pub MaxPriceDeviation: Ratio = Ratio::saturating_from_rational(5, 100);
+	pub const PriceResumeThreshold: u32 = 5;
 	pub const GetStakingCurrencyId: CurrencyId = DOT;
 	pub const GetLiquidCurrencyId: CurrencyId = LDOT;
+	pub const GetLiquidCrowdloanCurrencyId: Option<CurrencyId> = Some(CurrencyId::Token(TokenSymbol::LCDOT));
 }
 
 ord_parameter_types! {
@@ -402,7 +752,10 @@ impl module_prices::Config for Test {
 	type StableCurrencyFixedPrice = StableCurrencyFixedPrice;
 	type GetStakingCurrencyId = GetStakingCurrencyId;
 	type GetLiquidCurrencyId = GetLiquidCurrencyId;
+	type GetLiquidCrowdloanCurrencyId = GetLiquidCrowdloanCurrencyId;
 	type LockOrigin = EnsureSignedBy<One, AccountId>;
+	type MaxPriceDeviation = MaxPriceDeviation;
+	type PriceResumeThreshold = PriceResumeThreshold;
 	type LiquidStakingExchangeRateProvider = MockLiquidStakingExchangeProvider;
 	type DEX = DexModule;
 	type Currency = Currencies;
@@ -463,9 +816,9 @@ frame_support::construct_runtime!(
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Currencies: module_currencies::{Pallet, Call, Event<T>},
 		EVMBridge: module_evm_bridge::{Pallet},
-		EVMManager: module_evm_manager::{Pallet, Storage},
+		EVMManager: module_evm_manager::{Pallet, Storage, Event<T>},
 		NFTModule: module_nft::{Pallet, Call, Event<T>},
-		TransactionPayment: module_transaction_payment::{Pallet, Call, Storage},
+		TransactionPayment: module_transaction_payment::{Pallet, Call, Storage, Event<T>},
 		Prices: module_prices::{Pallet, Storage, Call, Event<T>},
 		Proxy: pallet_proxy::{Pallet, Call, Storage, Event<T>},
 		Utility: pallet_utility::{Pallet, Call, Event},
@@ -512,7 +865,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 	pallet_balances::GenesisConfig::<Test>::default()
 		.assimilate_storage(&mut storage)
 		.unwrap();
-	module_evm::GenesisConfig::<Test> { accounts }
+	module_evm::GenesisConfig::<Test> { chain_id: 1, accounts }
 		.assimilate_storage(&mut storage)
 		.unwrap();
 
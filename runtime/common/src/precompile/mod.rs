@@ -30,25 +30,40 @@ use module_evm::{
 	precompiles::{Precompile, Precompiles},
 	Context, ExitError, ExitSucceed,
 };
-use module_support::PrecompileCallerFilter as PrecompileCallerFilterT;
+use module_support::{
+	PrecompileCallerFilter as PrecompileCallerFilterT, PrecompileDelegateCallFilter as PrecompileDelegateCallFilterT,
+};
 use primitives::PRECOMPILE_ADDRESS_START;
 use sp_core::H160;
 use sp_std::{marker::PhantomData, prelude::*};
 
+pub mod access_control;
 pub mod dex;
+pub mod gas;
+pub mod homa;
+pub mod honzon;
+pub mod incentives;
 pub mod input;
 pub mod multicurrency;
 pub mod nft;
 pub mod oracle;
 pub mod schedule_call;
 pub mod state_rent;
+pub mod wasm_contracts;
+pub mod xcm;
 
+pub use access_control::AccessControlPrecompile;
 pub use dex::DexPrecompile;
+pub use homa::HomaPrecompile;
+pub use honzon::HonzonPrecompile;
+pub use incentives::IncentivesPrecompile;
 pub use multicurrency::MultiCurrencyPrecompile;
 pub use nft::NFTPrecompile;
 pub use oracle::OraclePrecompile;
 pub use schedule_call::ScheduleCallPrecompile;
 pub use state_rent::StateRentPrecompile;
+pub use wasm_contracts::WasmContractsPrecompile;
+pub use xcm::XcmPrecompile;
 
 pub type EthereumPrecompiles = (
 	module_evm::precompiles::ECRecover,
@@ -60,43 +75,77 @@ pub type EthereumPrecompiles = (
 	module_evm::precompiles::Sha3FIPS512,
 );
 
+/// The Acala precompiles which never change state.
+fn is_read_only_precompile(address: H160) -> bool {
+	// OraclePrecompile
+	address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 3)
+}
+
 pub struct AllPrecompiles<
 	PrecompileCallerFilter,
+	PrecompileDelegateCallFilter,
 	MultiCurrencyPrecompile,
 	NFTPrecompile,
 	StateRentPrecompile,
 	OraclePrecompile,
 	ScheduleCallPrecompile,
 	DexPrecompile,
+	XcmPrecompile,
+	HonzonPrecompile,
+	IncentivesPrecompile,
+	HomaPrecompile,
+	AccessControlPrecompile,
+	WasmContractsPrecompile,
 >(
 	PhantomData<(
 		PrecompileCallerFilter,
+		PrecompileDelegateCallFilter,
 		MultiCurrencyPrecompile,
 		NFTPrecompile,
 		StateRentPrecompile,
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		XcmPrecompile,
+		HonzonPrecompile,
+		IncentivesPrecompile,
+		HomaPrecompile,
+		AccessControlPrecompile,
+		WasmContractsPrecompile,
 	)>,
 );
 
 impl<
 		PrecompileCallerFilter,
+		PrecompileDelegateCallFilter,
 		MultiCurrencyPrecompile,
 		NFTPrecompile,
 		StateRentPrecompile,
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		XcmPrecompile,
+		HonzonPrecompile,
+		IncentivesPrecompile,
+		HomaPrecompile,
+		AccessControlPrecompile,
+		WasmContractsPrecompile,
 	> Precompiles
 	for AllPrecompiles<
 		PrecompileCallerFilter,
+		PrecompileDelegateCallFilter,
 		MultiCurrencyPrecompile,
 		NFTPrecompile,
 		StateRentPrecompile,
 		OraclePrecompile,
 		ScheduleCallPrecompile,
 		DexPrecompile,
+		XcmPrecompile,
+		HonzonPrecompile,
+		IncentivesPrecompile,
+		HomaPrecompile,
+		AccessControlPrecompile,
+		WasmContractsPrecompile,
 	> where
 	MultiCurrencyPrecompile: Precompile,
 	NFTPrecompile: Precompile,
@@ -104,7 +153,14 @@ impl<
 	OraclePrecompile: Precompile,
 	ScheduleCallPrecompile: Precompile,
 	PrecompileCallerFilter: PrecompileCallerFilterT,
+	PrecompileDelegateCallFilter: PrecompileDelegateCallFilterT,
 	DexPrecompile: Precompile,
+	XcmPrecompile: Precompile,
+	HonzonPrecompile: Precompile,
+	IncentivesPrecompile: Precompile,
+	HomaPrecompile: Precompile,
+	AccessControlPrecompile: Precompile,
+	WasmContractsPrecompile: Precompile,
 {
 	#[allow(clippy::type_complexity)]
 	fn execute(
@@ -119,6 +175,17 @@ impl<
 				return Some(Err(ExitError::Other("no permission".into())));
 			}
 
+			// DELEGATECALL and CALLCODE run the precompile in the context of the calling
+			// contract, only the approved contracts can do this to the state-changing ones.
+			if is_acala_precompile(address)
+				&& context.address != address
+				&& !is_read_only_precompile(address)
+				&& !PrecompileDelegateCallFilter::is_allowed(context.address)
+			{
+				log::debug!(target: "evm", "Precompile delegate call no permission");
+				return Some(Err(ExitError::Other("delegate call no permission".into())));
+			}
+
 			if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START) {
 				Some(MultiCurrencyPrecompile::execute(input, target_gas, context))
 			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 1) {
@@ -131,6 +198,18 @@ impl<
 				Some(ScheduleCallPrecompile::execute(input, target_gas, context))
 			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 5) {
 				Some(DexPrecompile::execute(input, target_gas, context))
+			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 6) {
+				Some(XcmPrecompile::execute(input, target_gas, context))
+			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 7) {
+				Some(HonzonPrecompile::execute(input, target_gas, context))
+			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 8) {
+				Some(IncentivesPrecompile::execute(input, target_gas, context))
+			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 9) {
+				Some(HomaPrecompile::execute(input, target_gas, context))
+			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 10) {
+				Some(AccessControlPrecompile::execute(input, target_gas, context))
+			} else if address == H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 11) {
+				Some(WasmContractsPrecompile::execute(input, target_gas, context))
 			} else {
 				None
 			}
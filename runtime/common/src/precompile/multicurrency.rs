@@ -17,14 +17,21 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use frame_support::log;
+use module_currencies::WeightInfo as WeightInfoT;
 use module_evm::{Context, ExitError, ExitSucceed, Precompile};
-use module_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT};
+use module_support::{
+	AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT,
+	MultiCurrencyAllowance as MultiCurrencyAllowanceT,
+};
 use sp_core::U256;
 use sp_std::{convert::TryFrom, fmt::Debug, marker::PhantomData, prelude::*, result};
 
 use orml_traits::MultiCurrency as MultiCurrencyT;
 
-use super::input::{Input, InputT};
+use super::{
+	gas::{charge_weight, GasWeightMapping as GasWeightMappingT},
+	input::{Input, InputT},
+};
 use primitives::{Balance, CurrencyId};
 
 /// The `MultiCurrency` impl precompile.
@@ -36,14 +43,39 @@ use primitives::{Balance, CurrencyId};
 /// - Query total issuance.
 /// - Query balance. Rest `input` bytes: `account_id`.
 /// - Transfer. Rest `input` bytes: `from`, `to`, `amount`.
-pub struct MultiCurrencyPrecompile<AccountId, AddressMapping, CurrencyIdMapping, MultiCurrency>(
-	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, MultiCurrency)>,
+/// - Query allowance. Rest `input` bytes: `owner`, `spender`.
+/// - Approve. Rest `input` bytes: `owner`, `spender`, `amount`.
+/// - Transfer from. Rest `input` bytes: `spender`, `from`, `to`, `amount`.
+///
+/// The actions changing balances charge the gas converted from the weight of
+/// the corresponding dispatchables by `GasWeightMapping`, the transfer is
+/// charged as a non-native currency transfer, the heavier one. The allowance
+/// actions are charged by the weights of `approve` and `transfer_from`.
+pub struct MultiCurrencyPrecompile<
+	AccountId,
+	AddressMapping,
+	CurrencyIdMapping,
+	MultiCurrency,
+	GasWeightMapping,
+	WeightInfo,
+>(
+	PhantomData<(
+		AccountId,
+		AddressMapping,
+		CurrencyIdMapping,
+		MultiCurrency,
+		GasWeightMapping,
+		WeightInfo,
+	)>,
 );
 
 enum Action {
 	QueryTotalIssuance,
 	QueryBalance,
 	Transfer,
+	QueryAllowance,
+	Approve,
+	TransferFrom,
 }
 
 impl TryFrom<u8> for Action {
@@ -54,26 +86,30 @@ impl TryFrom<u8> for Action {
 			0 => Ok(Action::QueryTotalIssuance),
 			1 => Ok(Action::QueryBalance),
 			2 => Ok(Action::Transfer),
+			3 => Ok(Action::QueryAllowance),
+			4 => Ok(Action::Approve),
+			5 => Ok(Action::TransferFrom),
 			_ => Err(()),
 		}
 	}
 }
 
-impl<AccountId, AddressMapping, CurrencyIdMapping, MultiCurrency> Precompile
-	for MultiCurrencyPrecompile<AccountId, AddressMapping, CurrencyIdMapping, MultiCurrency>
+impl<AccountId, AddressMapping, CurrencyIdMapping, MultiCurrency, GasWeightMapping, WeightInfo> Precompile
+	for MultiCurrencyPrecompile<AccountId, AddressMapping, CurrencyIdMapping, MultiCurrency, GasWeightMapping, WeightInfo>
 where
 	AccountId: Debug + Clone,
 	AddressMapping: AddressMappingT<AccountId>,
 	CurrencyIdMapping: CurrencyIdMappingT,
-	MultiCurrency: MultiCurrencyT<AccountId, Balance = Balance, CurrencyId = CurrencyId>,
+	MultiCurrency: MultiCurrencyT<AccountId, Balance = Balance, CurrencyId = CurrencyId>
+		+ MultiCurrencyAllowanceT<AccountId, Balance = Balance, CurrencyId = CurrencyId>,
+	GasWeightMapping: GasWeightMappingT,
+	WeightInfo: WeightInfoT,
 {
 	fn execute(
 		input: &[u8],
-		_target_gas: Option<u64>,
+		target_gas: Option<u64>,
 		_context: &Context,
 	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
-		//TODO: evaluate cost
-
 		log::debug!(target: "evm", "input: {:?}", input);
 
 		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(input);
@@ -108,6 +144,9 @@ where
 				log::debug!(target: "evm", "to: {:?}", to);
 				log::debug!(target: "evm", "amount: {:?}", amount);
 
+				let used_gas =
+					charge_weight::<GasWeightMapping>(WeightInfo::transfer_non_native_currency(), target_gas)?;
+
 				MultiCurrency::transfer(currency_id, &from, &to, amount).map_err(|e| {
 					let err_msg: &str = e.into();
 					ExitError::Other(err_msg.into())
@@ -115,7 +154,60 @@ where
 
 				log::debug!(target: "evm", "transfer success!");
 
-				Ok((ExitSucceed::Returned, vec![], 0))
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+			Action::QueryAllowance => {
+				let owner = input.account_id_at(2)?;
+				let spender = input.account_id_at(3)?;
+				log::debug!(target: "evm", "owner: {:?}", owner);
+				log::debug!(target: "evm", "spender: {:?}", spender);
+
+				let allowance = vec_u8_from_balance(MultiCurrency::allowance(currency_id, &owner, &spender));
+				log::debug!(target: "evm", "allowance: {:?}", allowance);
+
+				Ok((ExitSucceed::Returned, allowance, 0))
+			}
+			Action::Approve => {
+				let owner = input.account_id_at(2)?;
+				let spender = input.account_id_at(3)?;
+				let amount = input.balance_at(4)?;
+
+				log::debug!(target: "evm", "owner: {:?}", owner);
+				log::debug!(target: "evm", "spender: {:?}", spender);
+				log::debug!(target: "evm", "amount: {:?}", amount);
+
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::approve(), target_gas)?;
+
+				MultiCurrency::approve(currency_id, &owner, &spender, amount).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				log::debug!(target: "evm", "approve success!");
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+			Action::TransferFrom => {
+				let spender = input.account_id_at(2)?;
+				let from = input.account_id_at(3)?;
+				let to = input.account_id_at(4)?;
+				let amount = input.balance_at(5)?;
+
+				log::debug!(target: "evm", "spender: {:?}", spender);
+				log::debug!(target: "evm", "from: {:?}", from);
+				log::debug!(target: "evm", "to: {:?}", to);
+				log::debug!(target: "evm", "amount: {:?}", amount);
+
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::transfer_from(), target_gas)?;
+
+				MultiCurrency::transfer_from(currency_id, &spender, &from, &to, amount).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				log::debug!(target: "evm", "transfer from success!");
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
 			}
 		}
 	}
@@ -17,13 +17,20 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use module_evm::{Context, ExitError, ExitSucceed, Precompile};
-use module_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT};
+use module_nft::WeightInfo as WeightInfoT;
+use module_support::{
+	AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, NFTApproval as NFTApprovalT,
+	NFTInfo as NFTInfoT, NFTMinter as NFTMinterT,
+};
 use sp_core::{H160, U256};
 use sp_std::{borrow::Cow, convert::TryFrom, marker::PhantomData, prelude::*, result};
 
 use orml_traits::NFT as NFTT;
 
-use super::input::{Input, InputT};
+use super::{
+	gas::{charge_weight, GasWeightMapping as GasWeightMappingT},
+	input::{Input, InputT, PER_PARAM_BYTES},
+};
 use primitives::NFTBalance;
 
 /// The `NFT` impl precompile.
@@ -34,14 +41,54 @@ use primitives::NFTBalance;
 /// - Query balance. Rest `input` bytes: `account_id`.
 /// - Query owner. Rest `input` bytes: `class_id`, `token_id`.
 /// - Transfer. Rest `input`bytes: `from`, `to`, `class_id`, `token_id`.
-pub struct NFTPrecompile<AccountId, AddressMapping, CurrencyIdMapping, NFT>(
-	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, NFT)>,
+///
+/// Actions to expose NFT class as ERC-721 compatible contract, `balanceOf`,
+/// `ownerOf` and `tokenURI` map to query balance of class, query owner and
+/// query token metadata:
+/// - Query balance of class. Rest `input` bytes: `account_id`, `class_id`.
+/// - Query class metadata. Rest `input` bytes: `class_id`.
+/// - Query token metadata as token URI. Rest `input` bytes: `class_id`,
+///   `token_id`.
+/// - Approve. Rest `input` bytes: `who`, `approved`, `class_id`, `token_id`.
+///   The zero `approved` address revokes the approval.
+/// - Get approved. Rest `input` bytes: `class_id`, `token_id`. Returns the
+///   zero address if none.
+/// - Set approval for all. Rest `input` bytes: `who`, `class_id`,
+///   `operator`, `approved`.
+/// - Is approved for all. Rest `input` bytes: `owner`, `class_id`,
+///   `operator`.
+/// - Transfer from. Rest `input` bytes: `who`, `from`, `to`, `class_id`,
+///   `token_id`. `who` must be the owner, the approved account or the
+///   operator of owner.
+///
+/// Actions to issue NFT tokens from contracts:
+/// - Mint. Rest `input` bytes: `who`, `to`, `class_id`, `quantity`,
+///   `metadata_len`, `metadata`. `who` must be the owner or the minter of the
+///   class. Charged by the weight of minting `quantity` tokens.
+pub struct NFTPrecompile<AccountId, AddressMapping, CurrencyIdMapping, NFT, GasWeightMapping, WeightInfo>(
+	PhantomData<(
+		AccountId,
+		AddressMapping,
+		CurrencyIdMapping,
+		NFT,
+		GasWeightMapping,
+		WeightInfo,
+	)>,
 );
 
 enum Action {
 	QueryBalance,
 	QueryOwner,
 	Transfer,
+	QueryBalanceOfClass,
+	QueryClassMetadata,
+	QueryTokenMetadata,
+	Mint,
+	Approve,
+	GetApproved,
+	SetApprovalForAll,
+	IsApprovedForAll,
+	TransferFrom,
 }
 
 impl TryFrom<u8> for Action {
@@ -52,22 +99,36 @@ impl TryFrom<u8> for Action {
 			0 => Ok(Action::QueryBalance),
 			1 => Ok(Action::QueryOwner),
 			2 => Ok(Action::Transfer),
+			3 => Ok(Action::QueryBalanceOfClass),
+			4 => Ok(Action::QueryClassMetadata),
+			5 => Ok(Action::QueryTokenMetadata),
+			6 => Ok(Action::Mint),
+			7 => Ok(Action::Approve),
+			8 => Ok(Action::GetApproved),
+			9 => Ok(Action::SetApprovalForAll),
+			10 => Ok(Action::IsApprovedForAll),
+			11 => Ok(Action::TransferFrom),
 			_ => Err(()),
 		}
 	}
 }
 
-impl<AccountId, AddressMapping, CurrencyIdMapping, NFT> Precompile
-	for NFTPrecompile<AccountId, AddressMapping, CurrencyIdMapping, NFT>
+impl<AccountId, AddressMapping, CurrencyIdMapping, NFT, GasWeightMapping, WeightInfo> Precompile
+	for NFTPrecompile<AccountId, AddressMapping, CurrencyIdMapping, NFT, GasWeightMapping, WeightInfo>
 where
 	AccountId: Clone,
 	AddressMapping: AddressMappingT<AccountId>,
 	CurrencyIdMapping: CurrencyIdMappingT,
-	NFT: NFTT<AccountId, Balance = NFTBalance, ClassId = u32, TokenId = u64>,
+	NFT: NFTT<AccountId, Balance = NFTBalance, ClassId = u32, TokenId = u64>
+		+ NFTInfoT<AccountId, Balance = NFTBalance, ClassId = u32, TokenId = u64>
+		+ NFTMinterT<AccountId, ClassId = u32, TokenId = u64>
+		+ NFTApprovalT<AccountId, ClassId = u32, TokenId = u64>,
+	GasWeightMapping: GasWeightMappingT,
+	WeightInfo: WeightInfoT,
 {
 	fn execute(
 		input: &[u8],
-		_target_gas: Option<u64>,
+		target_gas: Option<u64>,
 		_context: &Context,
 	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
 		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(input);
@@ -85,16 +146,13 @@ where
 				let class_id = input.u32_at(1)?;
 				let token_id = input.u64_at(2)?;
 
-				let owner: H160 = if let Some(o) = NFT::owner((class_id, token_id)) {
-					AddressMapping::get_evm_address(&o).unwrap_or_else(|| AddressMapping::get_default_evm_address(&o))
-				} else {
-					Default::default()
-				};
-
-				let mut address = [0u8; 32];
-				address[12..].copy_from_slice(&owner.as_bytes().to_vec());
+				let owner = NFT::owner((class_id, token_id));
 
-				Ok((ExitSucceed::Returned, address.to_vec(), 0))
+				Ok((
+					ExitSucceed::Returned,
+					vec_u8_from_account_id::<AccountId, AddressMapping>(owner),
+					0,
+				))
 			}
 			Action::Transfer => {
 				let from = input.account_id_at(1)?;
@@ -103,17 +161,161 @@ where
 				let class_id = input.u32_at(3)?;
 				let token_id = input.u64_at(4)?;
 
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::transfer(), target_gas)?;
+
 				NFT::transfer(&from, &to, (class_id, token_id))
 					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
 
-				Ok((ExitSucceed::Returned, vec![], 0))
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+			Action::QueryBalanceOfClass => {
+				let who = input.account_id_at(1)?;
+				let class_id = input.u32_at(2)?;
+				let balance = vec_u8_from_balance(NFT::balance_of_class(&who, class_id));
+
+				Ok((ExitSucceed::Returned, balance, 0))
+			}
+			Action::QueryClassMetadata => {
+				let class_id = input.u32_at(1)?;
+				let metadata =
+					NFT::class_metadata(class_id).ok_or_else(|| ExitError::Other("invalid class id".into()))?;
+
+				Ok((ExitSucceed::Returned, vec_u8_from_bytes(metadata), 0))
+			}
+			Action::QueryTokenMetadata => {
+				let class_id = input.u32_at(1)?;
+				let token_id = input.u64_at(2)?;
+				let metadata = NFT::token_metadata((class_id, token_id))
+					.ok_or_else(|| ExitError::Other("invalid token id".into()))?;
+
+				Ok((ExitSucceed::Returned, vec_u8_from_bytes(metadata), 0))
+			}
+			Action::Mint => {
+				let who = input.account_id_at(1)?;
+				let to = input.account_id_at(2)?;
+				let class_id = input.u32_at(3)?;
+				let quantity = input.u32_at(4)?;
+				let metadata_len = input.u32_at(5)?;
+				let metadata = input.bytes_at(6 * PER_PARAM_BYTES, metadata_len as usize)?;
+
+				// charge before minting, the quantity bounds the loop of minting tokens.
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::mint(quantity), target_gas)?;
+
+				let token_ids = <NFT as NFTMinterT<AccountId>>::mint(&who, &to, class_id, metadata, quantity)
+					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+
+				Ok((ExitSucceed::Returned, vec_u8_from_token_ids(token_ids), used_gas))
+			}
+			Action::Approve => {
+				let who = input.account_id_at(1)?;
+				let approved = input.evm_address_at(2)?;
+				let class_id = input.u32_at(3)?;
+				let token_id = input.u64_at(4)?;
+
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::approve(), target_gas)?;
+
+				// the zero address revokes the approval
+				let approved = if approved == H160::zero() {
+					None
+				} else {
+					Some(AddressMapping::get_account_id(&approved))
+				};
+				<NFT as NFTApprovalT<AccountId>>::approve(&who, approved, (class_id, token_id))
+					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+			Action::GetApproved => {
+				let class_id = input.u32_at(1)?;
+				let token_id = input.u64_at(2)?;
+				let approved = NFT::approved((class_id, token_id));
+
+				Ok((
+					ExitSucceed::Returned,
+					vec_u8_from_account_id::<AccountId, AddressMapping>(approved),
+					0,
+				))
+			}
+			Action::SetApprovalForAll => {
+				let who = input.account_id_at(1)?;
+				let class_id = input.u32_at(2)?;
+				let operator = input.account_id_at(3)?;
+				let approved = input.bool_at(4)?;
+
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::set_approval_for_all(), target_gas)?;
+
+				<NFT as NFTApprovalT<AccountId>>::set_approval_for_all(&who, class_id, &operator, approved)
+					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+			Action::IsApprovedForAll => {
+				let owner = input.account_id_at(1)?;
+				let class_id = input.u32_at(2)?;
+				let operator = input.account_id_at(3)?;
+
+				let mut result = [0u8; 32];
+				if NFT::is_approved_for_all(&owner, class_id, &operator) {
+					result[31] = 1;
+				}
+
+				Ok((ExitSucceed::Returned, result.to_vec(), 0))
+			}
+			Action::TransferFrom => {
+				let who = input.account_id_at(1)?;
+				let from = input.account_id_at(2)?;
+				let to = input.account_id_at(3)?;
+				let class_id = input.u32_at(4)?;
+				let token_id = input.u64_at(5)?;
+
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::transfer_from(), target_gas)?;
+
+				<NFT as NFTApprovalT<AccountId>>::transfer_from(&who, &from, &to, (class_id, token_id))
+					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
 			}
 		}
 	}
 }
 
+/// Encode the EVM address of the account, or the zero address if none.
+fn vec_u8_from_account_id<AccountId, AddressMapping: AddressMappingT<AccountId>>(
+	account_id: Option<AccountId>,
+) -> Vec<u8> {
+	let address: H160 = if let Some(a) = account_id {
+		AddressMapping::get_evm_address(&a).unwrap_or_else(|| AddressMapping::get_default_evm_address(&a))
+	} else {
+		Default::default()
+	};
+
+	let mut bytes = [0u8; 32];
+	bytes[12..].copy_from_slice(address.as_bytes());
+	bytes.to_vec()
+}
+
 fn vec_u8_from_balance(b: NFTBalance) -> Vec<u8> {
 	let mut be_bytes = [0u8; 32];
 	U256::from(b).to_big_endian(&mut be_bytes[..]);
 	be_bytes.to_vec()
 }
+
+/// Encode the token ids with the length prefix.
+fn vec_u8_from_token_ids(ids: Vec<u64>) -> Vec<u8> {
+	let mut bytes = vec![0u8; 32 * (ids.len() + 1)];
+	U256::from(ids.len()).to_big_endian(&mut bytes[..32]);
+	for (i, id) in ids.into_iter().enumerate() {
+		U256::from(id).to_big_endian(&mut bytes[32 * (i + 1)..32 * (i + 2)]);
+	}
+	bytes
+}
+
+/// Encode the bytes with the length prefix, and pad to 32 bytes.
+pub(crate) fn vec_u8_from_bytes(b: Vec<u8>) -> Vec<u8> {
+	let mut bytes = [0u8; 32].to_vec();
+	U256::from(b.len()).to_big_endian(&mut bytes[..]);
+	let padded_len = (b.len() + 31) / 32 * 32;
+	bytes.extend_from_slice(&b);
+	bytes.resize(32 + padded_len, 0);
+	bytes
+}
@@ -18,13 +18,14 @@
 
 use frame_support::{log, sp_runtime::FixedPointNumber};
 use module_evm::{Context, ExitError, ExitSucceed, Precompile};
-use primitives::CurrencyId;
+use primitives::{CurrencyId, Moment};
 use sp_core::U256;
 use sp_std::{convert::TryFrom, fmt::Debug, marker::PhantomData, prelude::*, result};
 
 use super::input::{Input, InputT};
 use module_support::{
 	AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, Price, PriceProvider as PriceProviderT,
+	PriceTimestampProvider as PriceTimestampProviderT,
 };
 
 /// The `Oracle` impl precompile.
@@ -34,12 +35,15 @@ use module_support::{
 ///
 /// Actions:
 /// - Get price. Rest `input` bytes: `currency_id`.
+/// - Get price with timestamp. Rest `input` bytes: `currency_id`. The
+///   timestamp is zero if the price is not from the oracle feed.
 pub struct OraclePrecompile<AccountId, AddressMapping, CurrencyIdMapping, PriceProvider>(
 	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, PriceProvider)>,
 );
 
 enum Action {
 	GetPrice,
+	GetPriceWithTimestamp,
 }
 
 impl TryFrom<u8> for Action {
@@ -48,6 +52,7 @@ impl TryFrom<u8> for Action {
 	fn try_from(value: u8) -> Result<Self, Self::Error> {
 		match value {
 			0 => Ok(Action::GetPrice),
+			1 => Ok(Action::GetPriceWithTimestamp),
 			_ => Err(()),
 		}
 	}
@@ -59,7 +64,7 @@ where
 	AccountId: Debug + Clone,
 	AddressMapping: AddressMappingT<AccountId>,
 	CurrencyIdMapping: CurrencyIdMappingT,
-	PriceProvider: PriceProviderT<CurrencyId>,
+	PriceProvider: PriceProviderT<CurrencyId> + PriceTimestampProviderT<CurrencyId, Moment>,
 {
 	fn execute(
 		input: &[u8],
@@ -81,6 +86,22 @@ where
 				log::debug!(target: "evm", "oracle currency_id: {:?}, price: {:?}", key, value);
 				Ok((ExitSucceed::Returned, vec_u8_from_price(value), 0))
 			}
+			Action::GetPriceWithTimestamp => {
+				let key = input.currency_id_at(1)?;
+				let value = PriceProvider::get_price(key).unwrap_or_else(Default::default);
+				let timestamp = PriceProvider::get_price_timestamp(key).unwrap_or_else(Default::default);
+				log::debug!(
+					target: "evm",
+					"oracle currency_id: {:?}, price: {:?}, timestamp: {:?}",
+					key, value, timestamp
+				);
+
+				let mut output = vec_u8_from_price(value);
+				let mut be_bytes = [0u8; 32];
+				U256::from(timestamp).to_big_endian(&mut be_bytes[..32]);
+				output.extend_from_slice(&be_bytes);
+				Ok((ExitSucceed::Returned, output, 0))
+			}
 		}
 	}
 }
@@ -24,8 +24,9 @@ use frame_support::{
 	ensure, log, parameter_types,
 	traits::{
 		schedule::{DispatchTime, Named as ScheduleNamed},
-		Currency, IsType, OriginTrait,
+		Currency, Get, IsType, OriginTrait, ReservableCurrency,
 	},
+	weights::Weight,
 };
 use module_evm::{Context, ExitError, ExitSucceed, Precompile};
 use module_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, TransactionPayment};
@@ -34,7 +35,10 @@ use sp_core::{H160, U256};
 use sp_runtime::RuntimeDebug;
 use sp_std::{convert::TryFrom, fmt::Debug, marker::PhantomData, prelude::*, result};
 
-use super::input::{Input, InputT, PER_PARAM_BYTES};
+use super::{
+	gas::{charge_weight, GasWeightMapping as GasWeightMappingT},
+	input::{Input, InputT, PER_PARAM_BYTES},
+};
 use codec::{Decode, Encode};
 use pallet_scheduler::TaskAddress;
 
@@ -51,6 +55,19 @@ pub struct TaskInfo {
 	pub fee: Balance,
 }
 
+#[derive(RuntimeDebug, PartialEq, Encode, Decode)]
+pub struct SelfCallTaskInfo {
+	pub prefix: Vec<u8>,
+	pub id: u32,
+	pub sender: H160,
+	#[codec(compact)]
+	pub fee: Balance,
+	#[codec(compact)]
+	pub deposit: Balance,
+}
+
+const SELF_CALL_PREFIX: &[u8] = b"ScheduleSelfCall";
+
 /// The `ScheduleCall` impl precompile.
 ///
 ///
@@ -59,6 +76,16 @@ pub struct TaskInfo {
 /// Actions:
 /// - ScheduleCall. Rest `input` bytes: `from`, `target`, `value`, `gas_limit`,
 ///   `storage_limit`, `min_delay`, `input_len`, `input_data`.
+/// - Cancel. Rest `input` bytes: `from`, `task_id_len`, `task_id`.
+/// - Reschedule. Rest `input` bytes: `from`, `min_delay`, `task_id_len`,
+///   `task_id`.
+/// - ScheduleSelfCall. Rest `input` bytes: `contract`, `when`, `gas_limit`,
+///   `storage_limit`, `input_len`, `input_data`. Only deployed contracts can
+///   schedule a call to themselves, no later than `MaxDelay` blocks ahead, and
+///   `Deposit` is reserved until the call is dispatched or cancelled.
+///
+/// The actions charge the gas converted from the weight of the scheduler
+/// operations by `GasWeightMapping`, for an agenda of `MaxScheduledPerBlock`.
 pub struct ScheduleCallPrecompile<
 	AccountId,
 	AddressMapping,
@@ -69,6 +96,9 @@ pub struct ScheduleCallPrecompile<
 	Origin,
 	PalletsOrigin,
 	Runtime,
+	MaxDelay,
+	Deposit,
+	GasWeightMapping,
 >(
 	PhantomData<(
 		AccountId,
@@ -80,6 +110,9 @@ pub struct ScheduleCallPrecompile<
 		Origin,
 		PalletsOrigin,
 		Runtime,
+		MaxDelay,
+		Deposit,
+		GasWeightMapping,
 	)>,
 );
 
@@ -87,6 +120,7 @@ enum Action {
 	Schedule,
 	Cancel,
 	Reschedule,
+	ScheduleSelfCall,
 }
 
 impl TryFrom<u8> for Action {
@@ -97,6 +131,7 @@ impl TryFrom<u8> for Action {
 			0 => Ok(Action::Schedule),
 			1 => Ok(Action::Cancel),
 			2 => Ok(Action::Reschedule),
+			3 => Ok(Action::ScheduleSelfCall),
 			_ => Err(()),
 		}
 	}
@@ -107,6 +142,16 @@ type PalletBalanceOf<T> =
 type NegativeImbalanceOf<T> =
 	<<T as module_evm::Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
 
+/// The weight of scheduling a named task into a full agenda.
+fn schedule_named_weight<T: pallet_scheduler::Config>() -> Weight {
+	<T::WeightInfo as pallet_scheduler::WeightInfo>::schedule_named(T::MaxScheduledPerBlock::get())
+}
+
+/// The weight of cancelling a named task from a full agenda.
+fn cancel_named_weight<T: pallet_scheduler::Config>() -> Weight {
+	<T::WeightInfo as pallet_scheduler::WeightInfo>::cancel_named(T::MaxScheduledPerBlock::get())
+}
+
 impl<
 		AccountId,
 		AddressMapping,
@@ -117,6 +162,9 @@ impl<
 		Origin,
 		PalletsOrigin,
 		Runtime,
+		MaxDelay,
+		Deposit,
+		GasWeightMapping,
 	> Precompile
 	for ScheduleCallPrecompile<
 		AccountId,
@@ -128,6 +176,9 @@ impl<
 		Origin,
 		PalletsOrigin,
 		Runtime,
+		MaxDelay,
+		Deposit,
+		GasWeightMapping,
 	> where
 	AccountId: Debug + Clone,
 	AddressMapping: AddressMappingT<AccountId>,
@@ -138,12 +189,17 @@ impl<
 	Origin: IsType<<Runtime as frame_system::Config>::Origin>
 		+ OriginTrait<AccountId = AccountId, PalletsOrigin = PalletsOrigin>,
 	PalletsOrigin: Into<<Runtime as frame_system::Config>::Origin> + From<frame_system::RawOrigin<AccountId>> + Clone,
-	Runtime: module_evm::Config + frame_system::Config<AccountId = AccountId>,
+	Runtime: module_evm::Config
+		+ pallet_scheduler::Config
+		+ frame_system::Config<AccountId = AccountId, BlockNumber = BlockNumber>,
 	PalletBalanceOf<Runtime>: IsType<Balance>,
+	MaxDelay: Get<BlockNumber>,
+	Deposit: Get<Balance>,
+	GasWeightMapping: GasWeightMappingT,
 {
 	fn execute(
 		input: &[u8],
-		_target_gas: Option<u64>,
+		target_gas: Option<u64>,
 		_context: &Context,
 	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
 		log::debug!(target: "evm", "schedule call: input: {:?}", input);
@@ -179,6 +235,8 @@ impl<
 					input_data,
 				);
 
+				let used_gas = charge_weight::<GasWeightMapping>(schedule_named_weight::<Runtime>(), target_gas)?;
+
 				let mut _fee: PalletBalanceOf<Runtime> = Default::default();
 				#[cfg(not(feature = "with-ethereum-compatibility"))]
 				{
@@ -237,7 +295,7 @@ impl<
 				U256::from(task_id.len()).to_big_endian(&mut task_id_with_len[0..32]);
 				task_id_with_len[32..32 + task_id.len()].copy_from_slice(&task_id[..]);
 
-				Ok((ExitSucceed::Returned, task_id_with_len.to_vec(), 0))
+				Ok((ExitSucceed::Returned, task_id_with_len.to_vec(), used_gas))
 			}
 			Action::Cancel => {
 				let from = input.evm_address_at(1)?;
@@ -251,20 +309,32 @@ impl<
 					task_id,
 				);
 
+				let used_gas = charge_weight::<GasWeightMapping>(cancel_named_weight::<Runtime>(), target_gas)?;
+
 				let task_info = TaskInfo::decode(&mut &task_id[..])
 					.map_err(|_| ExitError::Other("Decode task_id failed".into()))?;
 				ensure!(task_info.sender == from, ExitError::Other("NoPermission".into()));
 
+				let deposit = if task_info.prefix == SELF_CALL_PREFIX {
+					SelfCallTaskInfo::decode(&mut &task_id[..])
+						.map_err(|_| ExitError::Other("Decode task_id failed".into()))?
+						.deposit
+				} else {
+					Default::default()
+				};
+
 				Scheduler::cancel_named(task_id).map_err(|_| ExitError::Other("Cancel schedule failed".into()))?;
 
+				let from_account = AddressMapping::get_account_id(&from);
 				#[cfg(not(feature = "with-ethereum-compatibility"))]
 				{
 					// unreserve the transaction fee for gas_limit
-					let from_account = AddressMapping::get_account_id(&from);
 					ChargeTransactionPayment::unreserve_fee(&from_account, task_info.fee.into());
 				}
+				// unreserve the deposit of the self call
+				<Runtime as module_evm::Config>::Currency::unreserve(&from_account, deposit.into());
 
-				Ok((ExitSucceed::Returned, vec![], 0))
+				Ok((ExitSucceed::Returned, vec![], used_gas))
 			}
 			Action::Reschedule => {
 				let from = input.evm_address_at(1)?;
@@ -280,16 +350,122 @@ impl<
 					min_delay,
 				);
 
+				// rescheduling cancels the task and schedules it again
+				let used_gas = charge_weight::<GasWeightMapping>(
+					cancel_named_weight::<Runtime>().saturating_add(schedule_named_weight::<Runtime>()),
+					target_gas,
+				)?;
+
 				let task_info = TaskInfo::decode(&mut &task_id[..])
 					.map_err(|_| ExitError::Other("Decode task_id failed".into()))?;
 				ensure!(task_info.sender == from, ExitError::Other("NoPermission".into()));
+				ensure!(
+					task_info.prefix != SELF_CALL_PREFIX || min_delay <= MaxDelay::get(),
+					ExitError::Other("ExceedMaxDelay".into())
+				);
 
 				Scheduler::reschedule_named(task_id, DispatchTime::After(min_delay)).map_err(|e| {
 					let err_msg: &str = e.into();
 					ExitError::Other(err_msg.into())
 				})?;
 
-				Ok((ExitSucceed::Returned, vec![], 0))
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+			Action::ScheduleSelfCall => {
+				let contract = input.evm_address_at(1)?;
+				let when = input.u32_at(2)?;
+				let gas_limit = input.u64_at(3)?;
+				let storage_limit = input.u32_at(4)?;
+				let input_len = input.u32_at(5)?;
+				let input_data = input.bytes_at(6 * PER_PARAM_BYTES, input_len as usize)?;
+
+				log::debug!(
+					target: "evm",
+					"schedule self call: contract: {:?}, when: {:?}, gas_limit: {:?}, storage_limit: {:?}, input_len: {:?}, input_data: {:?}",
+					contract,
+					when,
+					gas_limit,
+					storage_limit,
+					input_len,
+					input_data,
+				);
+
+				let used_gas = charge_weight::<GasWeightMapping>(schedule_named_weight::<Runtime>(), target_gas)?;
+
+				ensure!(
+					module_evm::Pallet::<Runtime>::is_deployed_contract(&contract),
+					ExitError::Other("NotDeployedContract".into())
+				);
+
+				let now = frame_system::Pallet::<Runtime>::block_number();
+				ensure!(when > now, ExitError::Other("InvalidScheduleTime".into()));
+				ensure!(when - now <= MaxDelay::get(), ExitError::Other("ExceedMaxDelay".into()));
+
+				let contract_account = AddressMapping::get_account_id(&contract);
+				let mut _fee: PalletBalanceOf<Runtime> = Default::default();
+				#[cfg(not(feature = "with-ethereum-compatibility"))]
+				{
+					// reserve the transaction fee for gas_limit
+					use sp_runtime::traits::Convert;
+					let weight = <Runtime as module_evm::Config>::GasToWeight::convert(gas_limit);
+					_fee = ChargeTransactionPayment::reserve_fee(&contract_account, weight).map_err(|e| {
+						let err_msg: &str = e.into();
+						ExitError::Other(err_msg.into())
+					})?;
+				}
+
+				let deposit = Deposit::get();
+				<Runtime as module_evm::Config>::Currency::reserve(&contract_account, deposit.into()).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				let call = module_evm::Call::<Runtime>::scheduled_self_call(
+					contract,
+					input_data,
+					gas_limit,
+					storage_limit,
+					deposit.into(),
+				)
+				.into();
+
+				let current_id = EvmSchedulerNextID::get();
+				let next_id = current_id
+					.checked_add(1)
+					.ok_or_else(|| ExitError::Other("Scheduler next id overflow".into()))?;
+				EvmSchedulerNextID::set(&next_id);
+
+				let task_id = SelfCallTaskInfo {
+					prefix: SELF_CALL_PREFIX.to_vec(),
+					id: current_id,
+					sender: contract,
+					fee: _fee.into(),
+					deposit,
+				}
+				.encode();
+
+				log::debug!(
+					target: "evm",
+					"schedule self call: task_id: {:?}",
+					task_id,
+				);
+
+				Scheduler::schedule_named(
+					task_id.clone(),
+					DispatchTime::At(when),
+					None,
+					0,
+					Origin::root().caller().clone(),
+					call,
+				)
+				.map_err(|_| ExitError::Other("Schedule failed".into()))?;
+
+				// add task_id len prefix
+				let mut task_id_with_len = [0u8; 128];
+				U256::from(task_id.len()).to_big_endian(&mut task_id_with_len[0..32]);
+				task_id_with_len[32..32 + task_id.len()].copy_from_slice(&task_id[..]);
+
+				Ok((ExitSucceed::Returned, task_id_with_len.to_vec(), used_gas))
 			}
 		}
 	}
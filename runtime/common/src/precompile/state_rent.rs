@@ -17,13 +17,16 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use frame_support::log;
-use module_evm::{Context, ExitError, ExitSucceed, Precompile};
+use module_evm::{Context, ExitError, ExitSucceed, Precompile, WeightInfo as WeightInfoT};
 use sp_core::U256;
 use sp_std::{borrow::Cow, convert::TryFrom, marker::PhantomData, prelude::*, result};
 
 use module_support::{AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, EVMStateRentTrait};
 
-use super::input::{Input, InputT};
+use super::{
+	gas::{charge_weight, GasWeightMapping as GasWeightMappingT},
+	input::{Input, InputT},
+};
 use primitives::Balance;
 
 /// The `EVM` impl precompile.
@@ -37,9 +40,17 @@ use primitives::Balance;
 /// - QueryDeveloperDeposit.
 /// - QueryDeploymentFee.
 /// - TransferMaintainer. Rest `input` bytes: `from`, `contract`,
-///   `new_maintainer`.
-pub struct StateRentPrecompile<AccountId, AddressMapping, CurrencyIdMapping, EVM>(
-	PhantomData<(AccountId, AddressMapping, CurrencyIdMapping, EVM)>,
+///   `new_maintainer`. Charges the gas converted from its weight by
+///   `GasWeightMapping`.
+pub struct StateRentPrecompile<AccountId, AddressMapping, CurrencyIdMapping, EVM, GasWeightMapping, WeightInfo>(
+	PhantomData<(
+		AccountId,
+		AddressMapping,
+		CurrencyIdMapping,
+		EVM,
+		GasWeightMapping,
+		WeightInfo,
+	)>,
 );
 
 enum Action {
@@ -68,17 +79,19 @@ impl TryFrom<u8> for Action {
 	}
 }
 
-impl<AccountId, AddressMapping, CurrencyIdMapping, EVM> Precompile
-	for StateRentPrecompile<AccountId, AddressMapping, CurrencyIdMapping, EVM>
+impl<AccountId, AddressMapping, CurrencyIdMapping, EVM, GasWeightMapping, WeightInfo> Precompile
+	for StateRentPrecompile<AccountId, AddressMapping, CurrencyIdMapping, EVM, GasWeightMapping, WeightInfo>
 where
 	AccountId: Clone,
 	AddressMapping: AddressMappingT<AccountId>,
 	CurrencyIdMapping: CurrencyIdMappingT,
 	EVM: EVMStateRentTrait<AccountId, Balance>,
+	GasWeightMapping: GasWeightMappingT,
+	WeightInfo: WeightInfoT,
 {
 	fn execute(
 		input: &[u8],
-		_target_gas: Option<u64>,
+		target_gas: Option<u64>,
 		_context: &Context,
 	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
 		log::debug!(target: "evm", "state_rent input: {:?}", input);
@@ -119,10 +132,12 @@ where
 				let contract = input.evm_address_at(2)?;
 				let new_maintainer = input.evm_address_at(3)?;
 
+				let used_gas = charge_weight::<GasWeightMapping>(WeightInfo::transfer_maintainer(), target_gas)?;
+
 				EVM::transfer_maintainer(from, contract, new_maintainer)
 					.map_err(|e| ExitError::Other(Cow::Borrowed(e.into())))?;
 
-				Ok((ExitSucceed::Returned, vec![], 0))
+				Ok((ExitSucceed::Returned, vec![], used_gas))
 			}
 		}
 	}
@@ -20,9 +20,13 @@
 use super::*;
 use crate::precompile::{
 	mock::{
-		alice, bob, get_task_id, new_test_ext, run_to_block, Balances, DexModule, DexPrecompile, Event as TestEvent,
-		Oracle, OraclePrecompile, Origin, Price, ScheduleCallPrecompile, System, Test, ACA_ERC20_ADDRESS, ALICE, AUSD,
-		XBTC,
+		alice, bob, get_task_id, new_test_ext, redeem_requests, run_to_block, wasm_contract_calls, xcm_transfers,
+		AccessControlPrecompile, AccountId, Balances, Currencies, DexModule, DexPrecompile, Event as TestEvent,
+		HomaPrecompile, HonzonPrecompile, IncentivesPrecompile, MaxFastMatchRedeemers, MaxRangeBuckets,
+		MaxScheduledPerBlock, ModuleEVM, MultiCurrencyPrecompile, NFTModule, NFTPrecompile, NetworkContractAccount,
+		NftPalletId, Oracle, OraclePrecompile, Origin, Price, ScheduleCallPrecompile, System, Test,
+		WasmContractsPrecompile, XcmPrecompile, XcmTransferRecord, XcmTransferWeight, ACA_ERC20_ADDRESS, ALICE, AUSD,
+		BOB, XBTC,
 	},
 	schedule_call::TaskInfo,
 };
@@ -30,11 +34,12 @@ use codec::Encode;
 use frame_support::{assert_noop, assert_ok};
 use hex_literal::hex;
 use module_evm::ExitError;
-use module_support::AddressMapping;
-use orml_traits::DataFeeder;
+use module_nft::{ClassProperty, ClassRoles, Properties};
+use module_support::{mocks::MockAddressMapping, AddressMapping, ExchangeRate, NFTInfo};
+use orml_traits::{DataFeeder, MultiCurrency};
 use primitives::{currency::GetDecimals, Balance, PREDEPLOY_ADDRESS_START};
 use sp_core::{H160, H256, U256};
-use sp_runtime::FixedPointNumber;
+use sp_runtime::{traits::AccountIdConversion, FixedPointNumber};
 
 pub struct DummyPrecompile;
 impl Precompile for DummyPrecompile {
@@ -49,6 +54,30 @@ impl Precompile for DummyPrecompile {
 
 pub type WithSystemContractFilter = AllPrecompiles<
 	crate::SystemContractsFilter,
+	(),
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+>;
+
+pub type WithDelegateCallFilter = AllPrecompiles<
+	crate::SystemContractsFilter,
+	ModuleEVM,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
+	DummyPrecompile,
 	DummyPrecompile,
 	DummyPrecompile,
 	DummyPrecompile,
@@ -109,6 +138,217 @@ fn precompile_filter_does_not_work_on_non_system_contracts() {
 	);
 }
 
+#[test]
+fn precompile_filter_rejects_delegate_call_to_state_changing_precompiles() {
+	let multicurrency = H160::from_low_u64_be(PRECOMPILE_ADDRESS_START);
+	let oracle = H160::from_low_u64_be(PRECOMPILE_ADDRESS_START + 3);
+	let system = H160::from_low_u64_be(PREDEPLOY_ADDRESS_START);
+	let proxy = H160::from_low_u64_be(PREDEPLOY_ADDRESS_START + 1);
+
+	// the precompile runs in the context of the delegating contract
+	let delegate_call_context = Context {
+		address: proxy,
+		caller: system,
+		apparent_value: 0.into(),
+	};
+	assert_eq!(
+		WithSystemContractFilter::execute(multicurrency, &[0u8; 1], None, &delegate_call_context),
+		Some(Err(ExitError::Other("delegate call no permission".into()))),
+	);
+	// read only precompile is allowed
+	assert_eq!(
+		WithSystemContractFilter::execute(oracle, &[0u8; 1], None, &delegate_call_context),
+		Some(Ok((ExitSucceed::Stopped, vec![], 0))),
+	);
+	// normal call is allowed
+	let call_context = Context {
+		address: multicurrency,
+		caller: system,
+		apparent_value: 0.into(),
+	};
+	assert_eq!(
+		WithSystemContractFilter::execute(multicurrency, &[0u8; 1], None, &call_context),
+		Some(Ok((ExitSucceed::Stopped, vec![], 0))),
+	);
+
+	new_test_ext().execute_with(|| {
+		assert_eq!(
+			WithDelegateCallFilter::execute(multicurrency, &[0u8; 1], None, &delegate_call_context),
+			Some(Err(ExitError::Other("delegate call no permission".into()))),
+		);
+		assert_ok!(ModuleEVM::add_precompile_delegate_caller(
+			Origin::signed(NetworkContractAccount::get()),
+			proxy
+		));
+		assert_eq!(
+			WithDelegateCallFilter::execute(multicurrency, &[0u8; 1], None, &delegate_call_context),
+			Some(Ok((ExitSucceed::Stopped, vec![], 0))),
+		);
+	});
+}
+
+#[test]
+fn multicurrency_precompile_transfer_should_charge_gas() {
+	new_test_ext().execute_with(|| {
+		let alice_account = MockAddressMapping::get_account_id(&alice());
+		let bob_account = MockAddressMapping::get_account_id(&bob());
+
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+
+		// action + currency_id + from + to + amount
+		let mut input = [0u8; 5 * 32];
+		U256::from(2).to_big_endian(&mut input[0 * 32..1 * 32]);
+		let mut id = [0u8; 32];
+		id[15] = 4; // XBTC
+		U256::from_big_endian(&id.to_vec()).to_big_endian(&mut input[1 * 32..2 * 32]);
+		input[2 * 32 + 12..3 * 32].copy_from_slice(&alice()[..]);
+		input[3 * 32 + 12..4 * 32].copy_from_slice(&bob()[..]);
+		U256::from(100).to_big_endian(&mut input[4 * 32..5 * 32]);
+
+		let expected_gas = <() as module_currencies::WeightInfo>::transfer_non_native_currency();
+		assert_noop!(
+			MultiCurrencyPrecompile::execute(&input, Some(expected_gas - 1), &context),
+			ExitError::OutOfGas
+		);
+
+		let (reason, output, used_gas) =
+			MultiCurrencyPrecompile::execute(&input, Some(expected_gas), &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, vec![]);
+		assert_eq!(used_gas, expected_gas);
+		assert_eq!(Currencies::free_balance(XBTC, &alice_account), 900);
+		assert_eq!(Currencies::free_balance(XBTC, &bob_account), 100);
+	});
+}
+
+#[test]
+fn multicurrency_precompile_approve_should_work() {
+	new_test_ext().execute_with(|| {
+		let alice_account = MockAddressMapping::get_account_id(&alice());
+		let bob_account = MockAddressMapping::get_account_id(&bob());
+
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+
+		// action + currency_id + owner + spender + amount
+		let mut input = [0u8; 5 * 32];
+		U256::from(4).to_big_endian(&mut input[0 * 32..1 * 32]);
+		let mut id = [0u8; 32];
+		id[15] = 4; // XBTC
+		U256::from_big_endian(&id.to_vec()).to_big_endian(&mut input[1 * 32..2 * 32]);
+		input[2 * 32 + 12..3 * 32].copy_from_slice(&alice()[..]);
+		input[3 * 32 + 12..4 * 32].copy_from_slice(&bob()[..]);
+		U256::from(100).to_big_endian(&mut input[4 * 32..5 * 32]);
+
+		let expected_gas = <() as module_currencies::WeightInfo>::approve();
+		assert_noop!(
+			MultiCurrencyPrecompile::execute(&input, Some(expected_gas - 1), &context),
+			ExitError::OutOfGas
+		);
+
+		let (reason, output, used_gas) =
+			MultiCurrencyPrecompile::execute(&input, Some(expected_gas), &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, vec![]);
+		assert_eq!(used_gas, expected_gas);
+		assert_eq!(
+			Currencies::allowances((alice_account.clone(), XBTC), bob_account.clone()),
+			100
+		);
+
+		// action + currency_id + owner + spender
+		let mut query_input = [0u8; 4 * 32];
+		query_input.copy_from_slice(&input[..4 * 32]);
+		U256::from(3).to_big_endian(&mut query_input[0 * 32..1 * 32]);
+
+		let mut expected_output = [0u8; 32];
+		U256::from(100).to_big_endian(&mut expected_output[..]);
+
+		let (reason, output, used_gas) = MultiCurrencyPrecompile::execute(&query_input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+
+		// approving zero removes the allowance
+		U256::from(0).to_big_endian(&mut input[4 * 32..5 * 32]);
+		assert_ok!(MultiCurrencyPrecompile::execute(&input, None, &context));
+		assert_eq!(Currencies::allowances((alice_account, XBTC), bob_account), 0);
+
+		let (_, output, _) = MultiCurrencyPrecompile::execute(&query_input, None, &context).unwrap();
+		assert_eq!(output, [0u8; 32]);
+	});
+}
+
+#[test]
+fn multicurrency_precompile_transfer_from_should_work() {
+	new_test_ext().execute_with(|| {
+		let alice_account = MockAddressMapping::get_account_id(&alice());
+		let bob_account = MockAddressMapping::get_account_id(&bob());
+
+		let context = Context {
+			address: Default::default(),
+			caller: bob(),
+			apparent_value: Default::default(),
+		};
+
+		// action + currency_id + spender + from + to + amount
+		let mut input = [0u8; 6 * 32];
+		U256::from(5).to_big_endian(&mut input[0 * 32..1 * 32]);
+		let mut id = [0u8; 32];
+		id[15] = 4; // XBTC
+		U256::from_big_endian(&id.to_vec()).to_big_endian(&mut input[1 * 32..2 * 32]);
+		input[2 * 32 + 12..3 * 32].copy_from_slice(&bob()[..]);
+		input[3 * 32 + 12..4 * 32].copy_from_slice(&alice()[..]);
+		input[4 * 32 + 12..5 * 32].copy_from_slice(&bob()[..]);
+		U256::from(60).to_big_endian(&mut input[5 * 32..6 * 32]);
+
+		// no allowance yet
+		assert_eq!(
+			MultiCurrencyPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("AllowanceTooLow".into()))
+		);
+
+		assert_ok!(Currencies::approve(
+			Origin::signed(alice_account.clone()),
+			bob_account.clone(),
+			XBTC,
+			100
+		));
+
+		let expected_gas = <() as module_currencies::WeightInfo>::transfer_from();
+		assert_noop!(
+			MultiCurrencyPrecompile::execute(&input, Some(expected_gas - 1), &context),
+			ExitError::OutOfGas
+		);
+
+		let (reason, output, used_gas) =
+			MultiCurrencyPrecompile::execute(&input, Some(expected_gas), &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, vec![]);
+		assert_eq!(used_gas, expected_gas);
+		assert_eq!(Currencies::free_balance(XBTC, &alice_account), 940);
+		assert_eq!(Currencies::free_balance(XBTC, &bob_account), 60);
+		assert_eq!(
+			Currencies::allowances((alice_account.clone(), XBTC), bob_account.clone()),
+			40
+		);
+
+		// exceeds the remaining allowance
+		assert_eq!(
+			MultiCurrencyPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("AllowanceTooLow".into()))
+		);
+		assert_eq!(Currencies::free_balance(XBTC, &alice_account), 940);
+	});
+}
+
 #[test]
 fn oracle_precompile_should_work() {
 	new_test_ext().execute_with(|| {
@@ -153,6 +393,26 @@ fn oracle_precompile_should_work() {
 		assert_eq!(reason, ExitSucceed::Returned);
 		assert_eq!(output, expected_output);
 		assert_eq!(used_gas, 0);
+
+		// returned price with timestamp
+		U256::from(1).to_big_endian(&mut input[..32]);
+		let mut expected_output = [0u8; 64];
+		U256::from(price.into_inner()).to_big_endian(&mut expected_output[..32]);
+		U256::from(1).to_big_endian(&mut expected_output[32..64]);
+
+		let (reason, output, used_gas) = OraclePrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output.to_vec());
+		assert_eq!(used_gas, 0);
+
+		// stable currency has no timestamp
+		let mut id = [0u8; 32];
+		id[15] = 1; // AUSD
+		U256::from_big_endian(&id.to_vec()).to_big_endian(&mut input[32..64]);
+		let (reason, output, used_gas) = OraclePrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output[32..64], [0u8; 32]);
+		assert_eq!(used_gas, 0);
 	});
 }
 
@@ -203,6 +463,9 @@ fn oracle_precompile_should_handle_invalid_input() {
 #[test]
 fn schedule_call_precompile_should_work() {
 	new_test_ext().execute_with(|| {
+		let schedule_gas = <() as pallet_scheduler::WeightInfo>::schedule_named(MaxScheduledPerBlock::get());
+		let cancel_gas = <() as pallet_scheduler::WeightInfo>::cancel_named(MaxScheduledPerBlock::get());
+
 		let context = Context {
 			address: Default::default(),
 			caller: alice(),
@@ -244,7 +507,7 @@ fn schedule_call_precompile_should_work() {
 
 		let (reason, output, used_gas) = ScheduleCallPrecompile::execute(&input, None, &context).unwrap();
 		assert_eq!(reason, ExitSucceed::Returned);
-		assert_eq!(used_gas, 0);
+		assert_eq!(used_gas, schedule_gas);
 		let event = TestEvent::pallet_scheduler(pallet_scheduler::RawEvent::Scheduled(3, 0));
 		assert!(System::events().iter().any(|record| record.event == event));
 
@@ -264,13 +527,13 @@ fn schedule_call_precompile_should_work() {
 
 		let (reason, _output, used_gas) = ScheduleCallPrecompile::execute(&cancel_input, None, &context).unwrap();
 		assert_eq!(reason, ExitSucceed::Returned);
-		assert_eq!(used_gas, 0);
+		assert_eq!(used_gas, cancel_gas);
 		let event = TestEvent::pallet_scheduler(pallet_scheduler::RawEvent::Canceled(3, 0));
 		assert!(System::events().iter().any(|record| record.event == event));
 
 		let (reason, output, used_gas) = ScheduleCallPrecompile::execute(&input, None, &context).unwrap();
 		assert_eq!(reason, ExitSucceed::Returned);
-		assert_eq!(used_gas, 0);
+		assert_eq!(used_gas, schedule_gas);
 
 		run_to_block(2);
 
@@ -292,7 +555,7 @@ fn schedule_call_precompile_should_work() {
 
 		let (reason, _output, used_gas) = ScheduleCallPrecompile::execute(&reschedule_input, None, &context).unwrap();
 		assert_eq!(reason, ExitSucceed::Returned);
-		assert_eq!(used_gas, 0);
+		assert_eq!(used_gas, cancel_gas + schedule_gas);
 		let event = TestEvent::pallet_scheduler(pallet_scheduler::RawEvent::Scheduled(5, 0));
 		assert!(System::events().iter().any(|record| record.event == event));
 
@@ -330,6 +593,8 @@ fn schedule_call_precompile_should_work() {
 #[test]
 fn schedule_call_precompile_should_handle_invalid_input() {
 	new_test_ext().execute_with(|| {
+		let schedule_gas = <() as pallet_scheduler::WeightInfo>::schedule_named(MaxScheduledPerBlock::get());
+
 		let context = Context {
 			address: Default::default(),
 			caller: alice(),
@@ -361,7 +626,7 @@ fn schedule_call_precompile_should_handle_invalid_input() {
 
 		let (reason, output, used_gas) = ScheduleCallPrecompile::execute(&input, None, &context).unwrap();
 		assert_eq!(reason, ExitSucceed::Returned);
-		assert_eq!(used_gas, 0);
+		assert_eq!(used_gas, schedule_gas);
 
 		let from_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&alice());
 		let to_account = <Test as module_evm::Config>::AddressMapping::get_account_id(&bob());
@@ -404,6 +669,126 @@ fn schedule_call_precompile_should_handle_invalid_input() {
 	});
 }
 
+#[test]
+fn schedule_call_precompile_should_schedule_self_call() {
+	new_test_ext().execute_with(|| {
+		let schedule_gas = <() as pallet_scheduler::WeightInfo>::schedule_named(MaxScheduledPerBlock::get());
+		let cancel_gas = <() as pallet_scheduler::WeightInfo>::cancel_named(MaxScheduledPerBlock::get());
+
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+
+		let contract = H160::from_low_u64_be(0x0100_0000);
+		let contract_account = MockAddressMapping::get_account_id(&contract);
+		let alice_account = MockAddressMapping::get_account_id(&alice());
+		assert_ok!(Balances::transfer(
+			Origin::signed(alice_account),
+			contract_account.clone(),
+			1_000_000
+		));
+
+		let mut input = [0u8; 8 * 32 + 4];
+		// array size
+		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
+		// action
+		U256::from(3).to_big_endian(&mut input[1 * 32..2 * 32]);
+		// contract
+		U256::from(alice().as_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+		// when
+		U256::from(5).to_big_endian(&mut input[3 * 32..4 * 32]);
+		// gas_limit
+		U256::from(300000).to_big_endian(&mut input[4 * 32..5 * 32]);
+		// storage_limit
+		U256::from(100).to_big_endian(&mut input[5 * 32..6 * 32]);
+		// input_len
+		U256::from(4).to_big_endian(&mut input[6 * 32..7 * 32]);
+		// input_data: totalSupply bytes4(keccak256(signature)) 0x18160ddd
+		input[7 * 32..7 * 32 + 4].copy_from_slice(&hex!("18160ddd"));
+
+		// only deployed contracts can schedule calls to themselves
+		assert_eq!(
+			ScheduleCallPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("NotDeployedContract".into()))
+		);
+
+		U256::from(contract.as_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+
+		// when must be in the future
+		U256::from(1).to_big_endian(&mut input[3 * 32..4 * 32]);
+		assert_eq!(
+			ScheduleCallPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("InvalidScheduleTime".into()))
+		);
+
+		// when must not exceed the max delay
+		U256::from(12).to_big_endian(&mut input[3 * 32..4 * 32]);
+		assert_eq!(
+			ScheduleCallPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("ExceedMaxDelay".into()))
+		);
+
+		U256::from(5).to_big_endian(&mut input[3 * 32..4 * 32]);
+		let (reason, output, used_gas) = ScheduleCallPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(used_gas, schedule_gas);
+		let event = TestEvent::pallet_scheduler(pallet_scheduler::RawEvent::Scheduled(5, 0));
+		assert!(System::events().iter().any(|record| record.event == event));
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		assert_eq!(Balances::reserved_balance(contract_account.clone()), 300100);
+		#[cfg(feature = "with-ethereum-compatibility")]
+		assert_eq!(Balances::reserved_balance(contract_account.clone()), 100);
+
+		// cancel schedule releases the deposit
+		let task_id = get_task_id(output);
+		let mut cancel_input = [0u8; 7 * 32];
+		// action
+		U256::from(1).to_big_endian(&mut cancel_input[1 * 32..2 * 32]);
+		// from
+		U256::from(contract.as_bytes()).to_big_endian(&mut cancel_input[2 * 32..3 * 32]);
+		// task_id_len
+		U256::from(task_id.len()).to_big_endian(&mut cancel_input[3 * 32..4 * 32]);
+		// task_id
+		cancel_input[4 * 32..4 * 32 + task_id.len()].copy_from_slice(&task_id[..]);
+
+		let (reason, _output, used_gas) = ScheduleCallPrecompile::execute(&cancel_input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(used_gas, cancel_gas);
+		let event = TestEvent::pallet_scheduler(pallet_scheduler::RawEvent::Canceled(5, 0));
+		assert!(System::events().iter().any(|record| record.event == event));
+		assert_eq!(Balances::reserved_balance(contract_account.clone()), 0);
+
+		let (reason, output, used_gas) = ScheduleCallPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(used_gas, schedule_gas);
+
+		// reschedule can not exceed the max delay
+		let task_id = get_task_id(output);
+		let mut reschedule_input = [0u8; 9 * 32];
+		// action
+		U256::from(2).to_big_endian(&mut reschedule_input[1 * 32..2 * 32]);
+		// from
+		U256::from(contract.as_bytes()).to_big_endian(&mut reschedule_input[2 * 32..3 * 32]);
+		// min_delay
+		U256::from(11).to_big_endian(&mut reschedule_input[3 * 32..4 * 32]);
+		// task_id_len
+		U256::from(task_id.len()).to_big_endian(&mut reschedule_input[4 * 32..5 * 32]);
+		// task_id
+		reschedule_input[5 * 32..5 * 32 + task_id.len()].copy_from_slice(&task_id[..]);
+
+		assert_eq!(
+			ScheduleCallPrecompile::execute(&reschedule_input, None, &context),
+			Err(ExitError::Other("ExceedMaxDelay".into()))
+		);
+
+		// the deposit is released once the call is dispatched
+		run_to_block(5);
+		assert_eq!(Balances::reserved_balance(contract_account), 0);
+	});
+}
+
 #[test]
 fn dex_precompile_get_liquidity_should_work() {
 	new_test_ext().execute_with(|| {
@@ -581,10 +966,16 @@ fn dex_precompile_swap_with_exact_supply_should_work() {
 		let mut expected_output = [0u8; 32];
 		U256::from(989).to_big_endian(&mut expected_output[..32]);
 
-		let (reason, output, used_gas) = DexPrecompile::execute(&input, None, &context).unwrap();
+		let expected_gas = <() as module_dex::WeightInfo>::swap_with_exact_supply(2, MaxRangeBuckets::get());
+		assert_noop!(
+			DexPrecompile::execute(&input, Some(expected_gas - 1), &context),
+			ExitError::OutOfGas
+		);
+
+		let (reason, output, used_gas) = DexPrecompile::execute(&input, Some(expected_gas), &context).unwrap();
 		assert_eq!(reason, ExitSucceed::Returned);
 		assert_eq!(output, expected_output);
-		assert_eq!(used_gas, 0);
+		assert_eq!(used_gas, expected_gas);
 	});
 }
 
@@ -629,32 +1020,728 @@ fn dex_precompile_swap_with_exact_target_should_work() {
 		let mut expected_output = [0u8; 32];
 		U256::from(1).to_big_endian(&mut expected_output[..32]);
 
-		let (reason, output, used_gas) = DexPrecompile::execute(&input, None, &context).unwrap();
+		let expected_gas = <() as module_dex::WeightInfo>::swap_with_exact_target(2, MaxRangeBuckets::get());
+		assert_noop!(
+			DexPrecompile::execute(&input, Some(expected_gas - 1), &context),
+			ExitError::OutOfGas
+		);
+
+		let (reason, output, used_gas) = DexPrecompile::execute(&input, Some(expected_gas), &context).unwrap();
 		assert_eq!(reason, ExitSucceed::Returned);
 		assert_eq!(output, expected_output);
-		assert_eq!(used_gas, 0);
+		assert_eq!(used_gas, expected_gas);
 	});
 }
 
 #[test]
-fn task_id_max_and_min() {
-	let task_id = TaskInfo {
-		prefix: b"ScheduleCall".to_vec(),
-		id: u32::MAX,
-		sender: H160::default(),
-		fee: Balance::MAX,
-	}
-	.encode();
+fn nft_precompile_erc721_queries_should_work() {
+	new_test_ext().execute_with(|| {
+		let alice_account = MockAddressMapping::get_account_id(&alice());
+		let class_owner: AccountId = NftPalletId::get().into_sub_account(0u32);
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(alice_account.clone()),
+			vec![1],
+			Properties(ClassProperty::Transferable.into())
+		));
+		assert_ok!(Balances::transfer(
+			Origin::signed(alice_account.clone()),
+			class_owner.clone(),
+			1_000
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_owner),
+			alice_account,
+			0,
+			vec![0x12, 0x34],
+			2
+		));
 
-	assert_eq!(54, task_id.len());
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
 
-	let task_id = TaskInfo {
-		prefix: b"ScheduleCall".to_vec(),
-		id: u32::MIN,
-		sender: H160::default(),
-		fee: Balance::MIN,
-	}
-	.encode();
+		// action + account_id + class_id
+		let mut input = [0u8; 3 * 32];
+		U256::from(3).to_big_endian(&mut input[0 * 32..1 * 32]);
+		input[1 * 32 + 12..2 * 32].copy_from_slice(&alice()[..]);
+		U256::from(0).to_big_endian(&mut input[2 * 32..3 * 32]);
 
-	assert_eq!(38, task_id.len());
+		let mut expected_output = [0u8; 32];
+		U256::from(2).to_big_endian(&mut expected_output[..]);
+
+		let (reason, output, used_gas) = NFTPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+
+		// action + class_id
+		let mut input = [0u8; 2 * 32];
+		U256::from(4).to_big_endian(&mut input[0 * 32..1 * 32]);
+		U256::from(0).to_big_endian(&mut input[1 * 32..2 * 32]);
+
+		let mut expected_output = [0u8; 64];
+		U256::from(1).to_big_endian(&mut expected_output[..32]);
+		expected_output[32] = 1;
+
+		let (reason, output, used_gas) = NFTPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+
+		// action + class_id + token_id
+		let mut input = [0u8; 3 * 32];
+		U256::from(5).to_big_endian(&mut input[0 * 32..1 * 32]);
+		U256::from(0).to_big_endian(&mut input[1 * 32..2 * 32]);
+		U256::from(1).to_big_endian(&mut input[2 * 32..3 * 32]);
+
+		let mut expected_output = [0u8; 64];
+		U256::from(2).to_big_endian(&mut expected_output[..32]);
+		expected_output[32..34].copy_from_slice(&[0x12, 0x34]);
+
+		let (reason, output, used_gas) = NFTPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+
+		// token not exist
+		U256::from(2).to_big_endian(&mut input[2 * 32..3 * 32]);
+		assert_noop!(
+			NFTPrecompile::execute(&input, None, &context),
+			ExitError::Other("invalid token id".into())
+		);
+	});
+}
+
+#[test]
+fn nft_precompile_mint_should_work() {
+	new_test_ext().execute_with(|| {
+		let alice_account = MockAddressMapping::get_account_id(&alice());
+		let bob_account = MockAddressMapping::get_account_id(&bob());
+		let class_owner: AccountId = NftPalletId::get().into_sub_account(0u32);
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(alice_account.clone()),
+			vec![1],
+			Properties(ClassProperty::Transferable.into())
+		));
+
+		let context = Context {
+			address: Default::default(),
+			caller: bob(),
+			apparent_value: Default::default(),
+		};
+
+		// action + who + to + class_id + quantity + metadata_len + metadata
+		let mut input = [0u8; 7 * 32];
+		U256::from(6).to_big_endian(&mut input[0 * 32..1 * 32]);
+		input[1 * 32 + 12..2 * 32].copy_from_slice(&bob()[..]);
+		input[2 * 32 + 12..3 * 32].copy_from_slice(&alice()[..]);
+		U256::from(0).to_big_endian(&mut input[3 * 32..4 * 32]);
+		U256::from(2).to_big_endian(&mut input[4 * 32..5 * 32]);
+		U256::from(2).to_big_endian(&mut input[5 * 32..6 * 32]);
+		input[6 * 32..6 * 32 + 2].copy_from_slice(&[0x12, 0x34]);
+
+		// bob is not the minter
+		assert_noop!(
+			NFTPrecompile::execute(&input, None, &context),
+			ExitError::Other("NoPermission".into())
+		);
+
+		assert_ok!(NFTModule::set_class_roles(
+			Origin::signed(class_owner),
+			0,
+			ClassRoles {
+				minter: Some(bob_account),
+				freezer: None,
+			}
+		));
+
+		let mut expected_output = [0u8; 3 * 32];
+		U256::from(2).to_big_endian(&mut expected_output[0 * 32..1 * 32]);
+		U256::from(0).to_big_endian(&mut expected_output[1 * 32..2 * 32]);
+		U256::from(1).to_big_endian(&mut expected_output[2 * 32..3 * 32]);
+
+		let expected_gas = <() as module_nft::WeightInfo>::mint(2);
+		assert_eq!(
+			NFTPrecompile::execute(&input, Some(expected_gas - 1), &context),
+			Err(ExitError::OutOfGas)
+		);
+		let (reason, output, used_gas) = NFTPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, <() as module_nft::WeightInfo>::mint(2));
+		assert_eq!(NFTModule::token_metadata((0, 1)), Some(vec![0x12, 0x34]));
+		assert_eq!(NFTModule::balance_of_class(&alice_account, 0), 2);
+	});
+}
+
+#[test]
+fn nft_precompile_approval_should_work() {
+	new_test_ext().execute_with(|| {
+		let alice_account = MockAddressMapping::get_account_id(&alice());
+		let bob_account = MockAddressMapping::get_account_id(&bob());
+		let class_owner: AccountId = NftPalletId::get().into_sub_account(0u32);
+		assert_ok!(NFTModule::create_class(
+			Origin::signed(alice_account.clone()),
+			vec![1],
+			Properties(ClassProperty::Transferable.into())
+		));
+		assert_ok!(Balances::transfer(
+			Origin::signed(alice_account.clone()),
+			class_owner.clone(),
+			1_000
+		));
+		assert_ok!(NFTModule::mint(
+			Origin::signed(class_owner),
+			alice_account.clone(),
+			0,
+			vec![1],
+			2
+		));
+
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+
+		// action + who + approved + class_id + token_id
+		let mut input = [0u8; 5 * 32];
+		U256::from(7).to_big_endian(&mut input[0 * 32..1 * 32]);
+		input[1 * 32 + 12..2 * 32].copy_from_slice(&alice()[..]);
+		input[2 * 32 + 12..3 * 32].copy_from_slice(&bob()[..]);
+		U256::from(0).to_big_endian(&mut input[3 * 32..4 * 32]);
+		U256::from(0).to_big_endian(&mut input[4 * 32..5 * 32]);
+
+		let expected_gas = <() as module_nft::WeightInfo>::approve();
+		assert_eq!(
+			NFTPrecompile::execute(&input, Some(expected_gas - 1), &context),
+			Err(ExitError::OutOfGas)
+		);
+		let (reason, output, used_gas) = NFTPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, vec![]);
+		assert_eq!(used_gas, expected_gas);
+		assert_eq!(NFTModule::approved_of((0, 0)), Some(bob_account.clone()));
+
+		// action + class_id + token_id
+		let mut query_input = [0u8; 3 * 32];
+		U256::from(8).to_big_endian(&mut query_input[0 * 32..1 * 32]);
+		U256::from(0).to_big_endian(&mut query_input[1 * 32..2 * 32]);
+		U256::from(0).to_big_endian(&mut query_input[2 * 32..3 * 32]);
+
+		let mut expected_output = [0u8; 32];
+		expected_output[12..].copy_from_slice(&bob()[..]);
+
+		let (reason, output, used_gas) = NFTPrecompile::execute(&query_input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+
+		// the zero address revokes the approval
+		input[2 * 32..3 * 32].copy_from_slice(&[0u8; 32]);
+		assert_ok!(NFTPrecompile::execute(&input, None, &context));
+		assert_eq!(NFTModule::approved_of((0, 0)), None);
+
+		let (_, output, _) = NFTPrecompile::execute(&query_input, None, &context).unwrap();
+		assert_eq!(output, [0u8; 32]);
+
+		// action + who + class_id + operator + approved
+		let mut input = [0u8; 5 * 32];
+		U256::from(9).to_big_endian(&mut input[0 * 32..1 * 32]);
+		input[1 * 32 + 12..2 * 32].copy_from_slice(&alice()[..]);
+		U256::from(0).to_big_endian(&mut input[2 * 32..3 * 32]);
+		input[3 * 32 + 12..4 * 32].copy_from_slice(&bob()[..]);
+		U256::from(1).to_big_endian(&mut input[4 * 32..5 * 32]);
+
+		let (reason, output, used_gas) = NFTPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, vec![]);
+		assert_eq!(used_gas, <() as module_nft::WeightInfo>::set_approval_for_all());
+
+		// action + owner + class_id + operator
+		let mut input = [0u8; 4 * 32];
+		U256::from(10).to_big_endian(&mut input[0 * 32..1 * 32]);
+		input[1 * 32 + 12..2 * 32].copy_from_slice(&alice()[..]);
+		U256::from(0).to_big_endian(&mut input[2 * 32..3 * 32]);
+		input[3 * 32 + 12..4 * 32].copy_from_slice(&bob()[..]);
+
+		let mut expected_output = [0u8; 32];
+		U256::from(1).to_big_endian(&mut expected_output[..]);
+
+		let (reason, output, used_gas) = NFTPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+
+		// action + who + from + to + class_id + token_id
+		let mut input = [0u8; 6 * 32];
+		U256::from(11).to_big_endian(&mut input[0 * 32..1 * 32]);
+		input[1 * 32 + 12..2 * 32].copy_from_slice(&bob()[..]);
+		input[2 * 32 + 12..3 * 32].copy_from_slice(&alice()[..]);
+		input[3 * 32 + 12..4 * 32].copy_from_slice(&bob()[..]);
+		U256::from(0).to_big_endian(&mut input[4 * 32..5 * 32]);
+		U256::from(1).to_big_endian(&mut input[5 * 32..6 * 32]);
+
+		let (reason, output, used_gas) = NFTPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, vec![]);
+		assert_eq!(used_gas, <() as module_nft::WeightInfo>::transfer_from());
+		assert_eq!(NFTModule::balance_of_class(&alice_account, 0), 1);
+		assert_eq!(NFTModule::balance_of_class(&bob_account, 0), 1);
+
+		// bob is no longer the operator of alice
+		U256::from(0).to_big_endian(&mut input[5 * 32..6 * 32]);
+		assert_ok!(NFTModule::set_approval_for_all(
+			Origin::signed(alice_account),
+			0,
+			bob_account,
+			false
+		));
+		assert_noop!(
+			NFTPrecompile::execute(&input, None, &context),
+			ExitError::Other("NoPermission".into())
+		);
+	});
+}
+
+#[test]
+fn task_id_max_and_min() {
+	let task_id = TaskInfo {
+		prefix: b"ScheduleCall".to_vec(),
+		id: u32::MAX,
+		sender: H160::default(),
+		fee: Balance::MAX,
+	}
+	.encode();
+
+	assert_eq!(54, task_id.len());
+
+	let task_id = TaskInfo {
+		prefix: b"ScheduleCall".to_vec(),
+		id: u32::MIN,
+		sender: H160::default(),
+		fee: Balance::MIN,
+	}
+	.encode();
+
+	assert_eq!(38, task_id.len());
+}
+
+#[test]
+fn xcm_precompile_transfer_should_work() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+
+		let dest = ::xcm::v0::MultiLocation::X1(::xcm::v0::Junction::Parent).encode();
+
+		// array_size + action + who + currency_id + amount + dest_weight + dest_len +
+		// dest
+		let mut input = [0u8; 8 * 32];
+		// array size
+		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
+		U256::from(0).to_big_endian(&mut input[1 * 32..2 * 32]);
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+		let mut id = [0u8; 32];
+		id[15] = 1; // AUSD
+		U256::from_big_endian(&id.to_vec()).to_big_endian(&mut input[3 * 32..4 * 32]);
+		U256::from(1_000).to_big_endian(&mut input[4 * 32..5 * 32]);
+		U256::from(500).to_big_endian(&mut input[5 * 32..6 * 32]);
+		U256::from(dest.len()).to_big_endian(&mut input[6 * 32..7 * 32]);
+		input[7 * 32..7 * 32 + dest.len()].copy_from_slice(&dest[..]);
+
+		let (reason, output, used_gas) = XcmPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, vec![]);
+		assert_eq!(used_gas, XcmTransferWeight::get());
+		assert_eq!(
+			xcm_transfers(),
+			vec![XcmTransferRecord::Transfer(
+				MockAddressMapping::get_account_id(&alice()),
+				AUSD,
+				1_000,
+				::xcm::v0::MultiLocation::X1(::xcm::v0::Junction::Parent),
+				500
+			)]
+		);
+
+		// invalid dest
+		input[7 * 32] = 0xff;
+		assert_eq!(
+			XcmPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("Decode dest failed".into()))
+		);
+	});
+}
+
+#[test]
+fn xcm_precompile_transfer_multi_asset_should_work() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+
+		let asset = ::xcm::v0::MultiAsset::ConcreteFungible {
+			id: ::xcm::v0::MultiLocation::X1(::xcm::v0::Junction::Parent),
+			amount: 1_000,
+		};
+		let dest = ::xcm::v0::MultiLocation::X1(::xcm::v0::Junction::Parent);
+		let bytes = [asset.encode(), dest.encode()].concat();
+
+		// array_size + action + who + dest_weight + asset_len + dest_len + asset +
+		// dest
+		let mut input = vec![0u8; 6 * 32 + bytes.len()];
+		// array size
+		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
+		U256::from(1).to_big_endian(&mut input[1 * 32..2 * 32]);
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+		U256::from(500).to_big_endian(&mut input[3 * 32..4 * 32]);
+		U256::from(asset.encode().len()).to_big_endian(&mut input[4 * 32..5 * 32]);
+		U256::from(dest.encode().len()).to_big_endian(&mut input[5 * 32..6 * 32]);
+		input[6 * 32..].copy_from_slice(&bytes[..]);
+
+		let (reason, output, used_gas) = XcmPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, vec![]);
+		assert_eq!(used_gas, XcmTransferWeight::get());
+		assert_eq!(
+			xcm_transfers(),
+			vec![XcmTransferRecord::TransferMultiAsset(
+				MockAddressMapping::get_account_id(&alice()),
+				asset,
+				dest,
+				500
+			)]
+		);
+	});
+}
+
+#[test]
+fn honzon_precompile_should_work() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+
+		// array_size + action + who + currency_id + collateral_adjustment +
+		// debit_adjustment
+		let mut input = [0u8; 6 * 32];
+		// array size
+		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
+		U256::from(0).to_big_endian(&mut input[1 * 32..2 * 32]);
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+		let mut id = [0u8; 32];
+		id[15] = 4; // XBTC
+		U256::from_big_endian(&id.to_vec()).to_big_endian(&mut input[3 * 32..4 * 32]);
+		U256::from(1_000).to_big_endian(&mut input[4 * 32..5 * 32]);
+		U256::from(500).to_big_endian(&mut input[5 * 32..6 * 32]);
+
+		let (reason, output, used_gas) = HonzonPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, vec![]);
+		assert_eq!(used_gas, <() as module_honzon::WeightInfo>::adjust_loan());
+
+		// get position
+		let mut input = [0u8; 4 * 32];
+		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
+		U256::from(2).to_big_endian(&mut input[1 * 32..2 * 32]);
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+		U256::from_big_endian(&id.to_vec()).to_big_endian(&mut input[3 * 32..4 * 32]);
+
+		let mut expected_output = [0u8; 64];
+		U256::from(1_000).to_big_endian(&mut expected_output[..32]);
+		U256::from(500).to_big_endian(&mut expected_output[32..64]);
+
+		let (reason, output, used_gas) = HonzonPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+
+		// close loan by dex with path XBTC -> AUSD
+		let mut input = [0u8; 7 * 32];
+		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
+		U256::from(1).to_big_endian(&mut input[1 * 32..2 * 32]);
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+		U256::from_big_endian(&id.to_vec()).to_big_endian(&mut input[3 * 32..4 * 32]);
+		U256::from(2).to_big_endian(&mut input[4 * 32..5 * 32]);
+		U256::from_big_endian(&id.to_vec()).to_big_endian(&mut input[5 * 32..6 * 32]);
+		let mut id = [0u8; 32];
+		id[15] = 1; // AUSD
+		U256::from_big_endian(&id.to_vec()).to_big_endian(&mut input[6 * 32..7 * 32]);
+
+		assert_eq!(
+			HonzonPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("close loan by dex failed".into()))
+		);
+	});
+}
+
+#[test]
+fn incentives_precompile_should_work() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+
+		// array_size + action + who + lp_currency_id + amount
+		let mut input = [0u8; 5 * 32];
+		// array size
+		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
+		U256::from(0).to_big_endian(&mut input[1 * 32..2 * 32]);
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+		let mut id = [0u8; 32];
+		id[11] = 1; // DexShare
+		id[15] = 4; // XBTC
+		id[19] = 1; // AUSD
+		U256::from_big_endian(&id.to_vec()).to_big_endian(&mut input[3 * 32..4 * 32]);
+		U256::from(1_000).to_big_endian(&mut input[4 * 32..5 * 32]);
+
+		let (reason, output, used_gas) = IncentivesPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, vec![]);
+		assert_eq!(used_gas, <() as module_incentives::WeightInfo>::deposit_dex_share());
+
+		// withdraw more than deposited
+		U256::from(1).to_big_endian(&mut input[1 * 32..2 * 32]);
+		U256::from(2_000).to_big_endian(&mut input[4 * 32..5 * 32]);
+		assert_eq!(
+			IncentivesPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("dex share not enough".into()))
+		);
+
+		// get pending rewards
+		let mut input = [0u8; 4 * 32];
+		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
+		U256::from(3).to_big_endian(&mut input[1 * 32..2 * 32]);
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+		U256::from_big_endian(&id.to_vec()).to_big_endian(&mut input[3 * 32..4 * 32]);
+
+		let mut expected_output = [0u8; 64];
+		U256::from(100).to_big_endian(&mut expected_output[..32]);
+		U256::from(10).to_big_endian(&mut expected_output[32..64]);
+
+		let (reason, output, used_gas) = IncentivesPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+
+		// claim rewards
+		U256::from(2).to_big_endian(&mut input[1 * 32..2 * 32]);
+		let (reason, output, used_gas) = IncentivesPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, vec![]);
+		assert_eq!(used_gas, <() as module_incentives::WeightInfo>::claim_rewards());
+
+		U256::from(H256::from(bob()).to_fixed_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+		assert_eq!(
+			IncentivesPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("no rewards to claim".into()))
+		);
+	});
+}
+
+#[test]
+fn homa_precompile_should_work() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+
+		// array_size + action + who + amount
+		let mut input = [0u8; 4 * 32];
+		// array size
+		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
+		U256::from(0).to_big_endian(&mut input[1 * 32..2 * 32]);
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+		U256::from(1_000).to_big_endian(&mut input[3 * 32..4 * 32]);
+
+		let mut expected_output = [0u8; 32];
+		U256::from(2_000).to_big_endian(&mut expected_output[..32]);
+
+		let expected_gas = <() as module_homa::WeightInfo>::mint(MaxFastMatchRedeemers::get());
+		assert_noop!(
+			HomaPrecompile::execute(&input, Some(expected_gas - 1), &context),
+			ExitError::OutOfGas
+		);
+
+		let (reason, output, used_gas) = HomaPrecompile::execute(&input, Some(expected_gas), &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, expected_gas);
+
+		U256::from(0).to_big_endian(&mut input[3 * 32..4 * 32]);
+		assert_eq!(
+			HomaPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("mint amount is zero".into()))
+		);
+
+		// array_size + action + who + amount + allow_fast_match
+		let mut input = [0u8; 5 * 32];
+		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
+		U256::from(1).to_big_endian(&mut input[1 * 32..2 * 32]);
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+		U256::from(500).to_big_endian(&mut input[3 * 32..4 * 32]);
+		U256::from(1).to_big_endian(&mut input[4 * 32..5 * 32]);
+
+		let expected_gas = <() as module_homa::WeightInfo>::request_redeem();
+		assert_noop!(
+			HomaPrecompile::execute(&input, Some(expected_gas - 1), &context),
+			ExitError::OutOfGas
+		);
+
+		let (reason, output, used_gas) = HomaPrecompile::execute(&input, Some(expected_gas), &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, vec![]);
+		assert_eq!(used_gas, expected_gas);
+		assert_eq!(
+			redeem_requests(),
+			vec![(MockAddressMapping::get_account_id(&alice()), 500, true)]
+		);
+
+		U256::from(2).to_big_endian(&mut input[4 * 32..5 * 32]);
+		assert_eq!(
+			HomaPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("invalid bool input".into()))
+		);
+
+		// get exchange rate
+		let mut input = [0u8; 2 * 32];
+		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
+		U256::from(2).to_big_endian(&mut input[1 * 32..2 * 32]);
+
+		let mut expected_output = [0u8; 32];
+		U256::from(ExchangeRate::saturating_from_rational(1, 2).into_inner()).to_big_endian(&mut expected_output[..32]);
+
+		let (reason, output, used_gas) = HomaPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+	});
+}
+
+#[test]
+fn access_control_precompile_should_work() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+
+		// action + contract
+		let mut input = [0u8; 2 * 32];
+		// action
+		input[31] = 0;
+		// contract
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[1 * 32..2 * 32]);
+
+		// unrestricted
+		let expected_output = [0u8; 32];
+
+		let (reason, output, used_gas) = AccessControlPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+
+		// action + contract + caller
+		let mut input = [0u8; 3 * 32];
+		// action
+		input[31] = 2;
+		// contract
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[1 * 32..2 * 32]);
+		// caller
+		U256::from(H256::from(bob()).to_fixed_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+
+		let mut expected_output = [0u8; 32];
+		U256::from(1).to_big_endian(&mut expected_output[..32]);
+
+		let (reason, output, used_gas) = AccessControlPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output);
+		assert_eq!(used_gas, 0);
+
+		// action + from + contract + mode
+		let mut input = [0u8; 4 * 32];
+		// action
+		input[31] = 128;
+		// from
+		U256::from(H256::from(bob()).to_fixed_bytes()).to_big_endian(&mut input[1 * 32..2 * 32]);
+		// contract
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+		// invalid mode
+		U256::from(3).to_big_endian(&mut input[3 * 32..4 * 32]);
+
+		assert_eq!(
+			AccessControlPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other("invalid caller access mode".into()))
+		);
+	});
+}
+
+#[test]
+fn wasm_contracts_precompile_call_should_work() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice(),
+			apparent_value: Default::default(),
+		};
+
+		let data = vec![1u8, 2, 3, 4, 5];
+
+		// array_size + action + who + dest + value + gas_limit + data_len + data
+		let mut input = [0u8; 8 * 32];
+		// array size
+		U256::default().to_big_endian(&mut input[0 * 32..1 * 32]);
+		U256::from(0).to_big_endian(&mut input[1 * 32..2 * 32]);
+		U256::from(H256::from(alice()).to_fixed_bytes()).to_big_endian(&mut input[2 * 32..3 * 32]);
+		input[3 * 32..4 * 32].copy_from_slice(BOB.as_ref());
+		U256::from(1_000).to_big_endian(&mut input[4 * 32..5 * 32]);
+		U256::from(800).to_big_endian(&mut input[5 * 32..6 * 32]);
+		U256::from(data.len()).to_big_endian(&mut input[6 * 32..7 * 32]);
+		input[7 * 32..7 * 32 + data.len()].copy_from_slice(&data[..]);
+
+		// the output is the input data as `bytes`
+		let mut expected_output = [0u8; 2 * 32];
+		U256::from(data.len()).to_big_endian(&mut expected_output[0..32]);
+		expected_output[32..32 + data.len()].copy_from_slice(&data[..]);
+
+		let (reason, output, used_gas) = WasmContractsPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output.to_vec());
+		assert_eq!(used_gas, 400);
+
+		// the gas limit is capped by the gas left
+		let (reason, output, used_gas) = WasmContractsPrecompile::execute(&input, Some(600), &context).unwrap();
+		assert_eq!(reason, ExitSucceed::Returned);
+		assert_eq!(output, expected_output.to_vec());
+		assert_eq!(used_gas, 300);
+
+		assert_eq!(
+			wasm_contract_calls(),
+			vec![
+				(
+					MockAddressMapping::get_account_id(&alice()),
+					BOB,
+					1_000,
+					800,
+					data.clone()
+				),
+				(MockAddressMapping::get_account_id(&alice()), BOB, 1_000, 600, data),
+			]
+		);
+	});
 }
@@ -0,0 +1,126 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+use super::{
+	gas::{charge_weight, GasWeightMapping as GasWeightMappingT},
+	input::{Input, InputT, PER_PARAM_BYTES},
+	nft::vec_u8_from_bytes,
+};
+use codec::Decode;
+use frame_support::log;
+use module_evm::{Context, ExitError, ExitSucceed, Precompile};
+use module_support::{
+	AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, WasmContracts as WasmContractsT,
+};
+use primitives::Balance;
+use sp_std::{convert::TryFrom, fmt::Debug, marker::PhantomData, prelude::*, result};
+
+/// The `WasmContracts` impl precompile, EVM contracts call the Wasm (ink!)
+/// contracts of `pallet_contracts` through it.
+///
+///
+/// `input` data starts with `action`. `who` is mapped to its account id by
+/// `AddressMapping` as the caller of the Wasm contract, `dest` is the 32
+/// bytes account id of the Wasm contract, `value` is the `u128` balance to
+/// transfer, and `data` is the SCALE encoded message of the Wasm contract,
+/// i.e. the selector followed by the arguments.
+///
+/// Actions:
+/// - Call. Rest `input` bytes: `who`, `dest`, `value`, `gas_limit`,
+///   `data_len`, `data`. Returns the output of the Wasm contract as `bytes`.
+///
+/// `gas_limit` is converted to the weight limit of the Wasm contract by
+/// `GasWeightMapping`, capped by the gas left. The gas of the weight used by
+/// the Wasm contract is charged.
+pub struct WasmContractsPrecompile<AccountId, AddressMapping, CurrencyIdMapping, WasmContracts, GasWeightMapping>(
+	PhantomData<(
+		AccountId,
+		AddressMapping,
+		CurrencyIdMapping,
+		WasmContracts,
+		GasWeightMapping,
+	)>,
+);
+
+enum Action {
+	Call,
+}
+
+impl TryFrom<u8> for Action {
+	type Error = ();
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(Action::Call),
+			_ => Err(()),
+		}
+	}
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, WasmContracts, GasWeightMapping> Precompile
+	for WasmContractsPrecompile<AccountId, AddressMapping, CurrencyIdMapping, WasmContracts, GasWeightMapping>
+where
+	AccountId: Debug + Clone + Decode,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	WasmContracts: WasmContractsT<AccountId, Balance>,
+	GasWeightMapping: GasWeightMappingT,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		log::debug!(target: "evm", "input: {:?}", input);
+
+		// Solidity dynamic arrays will add the array size to the front of the array,
+		// pre-compile needs to deal with the `size`.
+		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(&input[32..]);
+
+		let action = input.action()?;
+
+		match action {
+			Action::Call => {
+				let who = input.account_id_at(1)?;
+				let dest = AccountId::decode(&mut input.nth_param(2)?)
+					.map_err(|_| ExitError::Other("Decode dest failed".into()))?;
+				let value = input.balance_at(3)?;
+				let gas_limit = input.u64_at(4)?;
+				let data_len = input.u32_at(5)?;
+				let data = input.bytes_at(6 * PER_PARAM_BYTES, data_len as usize)?;
+				log::debug!(
+					target: "evm",
+					"wasm_contracts: call who: {:?}, dest: {:?}, value: {:?}, gas_limit: {:?}",
+					who, dest, value, gas_limit
+				);
+
+				let gas_limit = target_gas.map_or(gas_limit, |target_gas| gas_limit.min(target_gas));
+				let (output, used_weight) =
+					WasmContracts::call(who, dest, value, GasWeightMapping::gas_to_weight(gas_limit), data).map_err(
+						|e| {
+							let err_msg: &str = e.into();
+							ExitError::Other(err_msg.into())
+						},
+					)?;
+
+				let used_gas = charge_weight::<GasWeightMapping>(used_weight, target_gas)?;
+
+				Ok((ExitSucceed::Returned, vec_u8_from_bytes(output), used_gas))
+			}
+		}
+	}
+}
@@ -0,0 +1,152 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::{
+	gas::{charge_weight, GasWeightMapping as GasWeightMappingT},
+	input::{Input, InputT, PER_PARAM_BYTES},
+};
+use codec::Decode;
+use frame_support::{log, traits::Get, weights::Weight};
+use module_evm::{Context, ExitError, ExitSucceed, Precompile};
+use module_support::{
+	AddressMapping as AddressMappingT, CurrencyIdMapping as CurrencyIdMappingT, XcmTransfer as XcmTransferT,
+};
+use primitives::{Balance, CurrencyId};
+use sp_std::{convert::TryFrom, fmt::Debug, marker::PhantomData, prelude::*, result};
+use xcm::v0::{MultiAsset, MultiLocation};
+
+/// The `XCM` impl precompile.
+///
+///
+/// `input` data starts with `action`. `dest` and `asset` are SCALE encoded
+/// `MultiLocation` and `MultiAsset`.
+///
+/// Actions:
+/// - Transfer. Rest `input` bytes: `who`, `currency_id`, `amount`,
+///   `dest_weight`, `dest_len`, `dest`.
+/// - Transfer multi asset. Rest `input` bytes: `who`, `dest_weight`,
+///   `asset_len`, `dest_len`, `asset`, `dest`.
+///
+/// The transfers are not benchmarked, each of them is charged by the local
+/// execution weight `TransferWeight`. `dest_weight` is paid on the
+/// destination chain and not charged here.
+pub struct XcmPrecompile<AccountId, AddressMapping, CurrencyIdMapping, XcmTransfer, GasWeightMapping, TransferWeight>(
+	PhantomData<(
+		AccountId,
+		AddressMapping,
+		CurrencyIdMapping,
+		XcmTransfer,
+		GasWeightMapping,
+		TransferWeight,
+	)>,
+);
+
+enum Action {
+	Transfer,
+	TransferMultiAsset,
+}
+
+impl TryFrom<u8> for Action {
+	type Error = ();
+
+	fn try_from(value: u8) -> Result<Self, Self::Error> {
+		match value {
+			0 => Ok(Action::Transfer),
+			1 => Ok(Action::TransferMultiAsset),
+			_ => Err(()),
+		}
+	}
+}
+
+impl<AccountId, AddressMapping, CurrencyIdMapping, XcmTransfer, GasWeightMapping, TransferWeight> Precompile
+	for XcmPrecompile<AccountId, AddressMapping, CurrencyIdMapping, XcmTransfer, GasWeightMapping, TransferWeight>
+where
+	AccountId: Debug + Clone,
+	AddressMapping: AddressMappingT<AccountId>,
+	CurrencyIdMapping: CurrencyIdMappingT,
+	XcmTransfer: XcmTransferT<AccountId, Balance, CurrencyId>,
+	GasWeightMapping: GasWeightMappingT,
+	TransferWeight: Get<Weight>,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> result::Result<(ExitSucceed, Vec<u8>, u64), ExitError> {
+		log::debug!(target: "evm", "input: {:?}", input);
+
+		// Solidity dynamic arrays will add the array size to the front of the array,
+		// pre-compile needs to deal with the `size`.
+		let input = Input::<Action, AccountId, AddressMapping, CurrencyIdMapping>::new(&input[32..]);
+
+		let action = input.action()?;
+
+		match action {
+			Action::Transfer => {
+				let who = input.account_id_at(1)?;
+				let currency_id = input.currency_id_at(2)?;
+				let amount = input.balance_at(3)?;
+				let dest_weight = input.u64_at(4)?;
+				let dest_len = input.u32_at(5)?;
+				let dest_bytes = input.bytes_at(6 * PER_PARAM_BYTES, dest_len as usize)?;
+				let dest = MultiLocation::decode(&mut &dest_bytes[..])
+					.map_err(|_| ExitError::Other("Decode dest failed".into()))?;
+				log::debug!(
+					target: "evm",
+					"xcm: transfer who: {:?}, currency_id: {:?}, amount: {:?}, dest: {:?}, dest_weight: {:?}",
+					who, currency_id, amount, dest, dest_weight
+				);
+
+				let used_gas = charge_weight::<GasWeightMapping>(TransferWeight::get(), target_gas)?;
+
+				XcmTransfer::transfer(who, currency_id, amount, dest, dest_weight).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+			Action::TransferMultiAsset => {
+				let who = input.account_id_at(1)?;
+				let dest_weight = input.u64_at(2)?;
+				let asset_len = input.u32_at(3)? as usize;
+				let dest_len = input.u32_at(4)? as usize;
+				let asset_bytes = input.bytes_at(5 * PER_PARAM_BYTES, asset_len)?;
+				let dest_bytes = input.bytes_at(5 * PER_PARAM_BYTES + asset_len, dest_len)?;
+				let asset = MultiAsset::decode(&mut &asset_bytes[..])
+					.map_err(|_| ExitError::Other("Decode asset failed".into()))?;
+				let dest = MultiLocation::decode(&mut &dest_bytes[..])
+					.map_err(|_| ExitError::Other("Decode dest failed".into()))?;
+				log::debug!(
+					target: "evm",
+					"xcm: transfer_multi_asset who: {:?}, asset: {:?}, dest: {:?}, dest_weight: {:?}",
+					who, asset, dest, dest_weight
+				);
+
+				let used_gas = charge_weight::<GasWeightMapping>(TransferWeight::get(), target_gas)?;
+
+				XcmTransfer::transfer_multi_asset(who, asset, dest, dest_weight).map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok((ExitSucceed::Returned, vec![], used_gas))
+			}
+		}
+	}
+}
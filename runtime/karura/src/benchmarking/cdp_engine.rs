@@ -18,8 +18,9 @@
 
 use crate::{
 	dollar, AcalaOracle, AccountId, Address, Amount, Balance, CdpEngine, CollateralCurrencyIds, CurrencyId,
-	DefaultDebitExchangeRate, Dex, EmergencyShutdown, GetStableCurrencyId, MaxSlippageSwapWithDEX, MinimumDebitValue,
-	Price, Rate, Ratio, Runtime, Timestamp, KSM, KUSD, MILLISECS_PER_BLOCK,
+	DefaultDebitExchangeRate, Dex, EmergencyShutdown, GetStableCurrencyId, MaxLiquidationSteps, MaxSlippageSwapWithDEX,
+	MaxStabilityFeeExemptions, MinimumDebitValue, Price, Rate, Ratio, Runtime, Timestamp, KSM, KUSD,
+	MILLISECS_PER_BLOCK,
 };
 
 use super::utils::set_balance;
@@ -27,12 +28,13 @@ use core::convert::TryInto;
 use frame_benchmarking::account;
 use frame_support::traits::OnInitialize;
 use frame_system::RawOrigin;
+use module_cdp_engine::LiquidationStep;
 use module_support::DEXManager;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::Change;
 use sp_runtime::{
 	traits::{AccountIdLookup, StaticLookup, UniqueSaturatedInto},
-	FixedPointNumber,
+	FixedPointNumber, Permill,
 };
 use sp_std::prelude::*;
 
@@ -267,6 +269,27 @@ runtime_benchmarks! {
 		// shutdown
 		EmergencyShutdown::emergency_shutdown(RawOrigin::Root.into())?;
 	}: _(RawOrigin::None, currency_id, owner_lookup)
+
+	// worst: grant the last stability fee exemption of the collateral type
+	set_stability_fee_exemption {
+		let owner: AccountId = account("owner", 0, SEED);
+		let owner_lookup: Address = AccountIdLookup::unlookup(owner.clone());
+		for i in 1 .. MaxStabilityFeeExemptions::get() {
+			let exempted: AccountId = account("exempted", i, SEED);
+			CdpEngine::set_stability_fee_exemption(RawOrigin::Root.into(), KSM, AccountIdLookup::unlookup(exempted), Permill::one(), 100_000 * dollar(KUSD))?;
+		}
+	}: _(RawOrigin::Root, KSM, owner_lookup, Permill::one(), 100_000 * dollar(KUSD))
+
+	revoke_stability_fee_exemption {
+		let owner: AccountId = account("owner", 0, SEED);
+		let owner_lookup: Address = AccountIdLookup::unlookup(owner.clone());
+		CdpEngine::set_stability_fee_exemption(RawOrigin::Root.into(), KSM, owner_lookup.clone(), Permill::one(), 100_000 * dollar(KUSD))?;
+	}: _(RawOrigin::Root, KSM, owner_lookup)
+
+	set_liquidation_steps {
+		let mut steps = vec![LiquidationStep::DexSwap(MaxSlippageSwapWithDEX::get()); MaxLiquidationSteps::get() as usize - 1];
+		steps.push(LiquidationStep::Auction);
+	}: _(RawOrigin::Root, KSM, steps)
 }
 
 #[cfg(test)]
@@ -315,4 +338,25 @@ mod tests {
 			assert_ok!(test_benchmark_settle());
 		});
 	}
+
+	#[test]
+	fn test_set_stability_fee_exemption() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_stability_fee_exemption());
+		});
+	}
+
+	#[test]
+	fn test_revoke_stability_fee_exemption() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_revoke_stability_fee_exemption());
+		});
+	}
+
+	#[test]
+	fn test_set_liquidation_steps() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_liquidation_steps());
+		});
+	}
 }
@@ -16,19 +16,75 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{dollar, CdpTreasury, Currencies, CurrencyId, Runtime, KSM, KUSD};
-
+use crate::{
+	dollar, AcalaOracle, AccountId, Balance, CdpTreasury, CollateralCurrencyIds, Currencies, CurrencyId, Dex,
+	GetStableCurrencyId, MaxSurplusAllocations, Price, Runtime, SurplusRebalancePeriod, KSM, KUSD,
+};
+
+use super::utils::set_balance;
+use frame_benchmarking::account;
+use frame_support::traits::OnInitialize;
 use frame_system::RawOrigin;
 use module_support::CDPTreasury;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::MultiCurrency;
+use sp_runtime::{FixedPointNumber, Permill};
 use sp_std::prelude::*;
 
+const SEED: u32 = 0;
+
+fn inject_liquidity(
+	maker: AccountId,
+	currency_id: CurrencyId,
+	max_amount: Balance,
+	max_other_currency_amount: Balance,
+) -> Result<(), &'static str> {
+	let base_currency_id = GetStableCurrencyId::get();
+
+	// set balance
+	set_balance(currency_id, &maker, max_other_currency_amount);
+	set_balance(base_currency_id, &maker, max_amount);
+
+	let _ = Dex::enable_trading_pair(RawOrigin::Root.into(), currency_id, base_currency_id);
+
+	Dex::add_liquidity(
+		RawOrigin::Signed(maker.clone()).into(),
+		base_currency_id,
+		currency_id,
+		max_amount,
+		max_other_currency_amount,
+		false,
+	)?;
+
+	Ok(())
+}
+
 runtime_benchmarks! {
 	{ Runtime, module_cdp_treasury }
 
 	_ {}
 
+	on_initialize {
+		let c in 1 .. CollateralCurrencyIds::get().len().min(MaxSurplusAllocations::get() as usize) as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let maker: AccountId = account("maker", 0, SEED);
+		let mut allocations = vec![];
+		let mut feed_data = vec![];
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			inject_liquidity(maker.clone(), currency_id, 1_000_000 * dollar(KUSD), 1_000_000 * dollar(currency_id))?;
+			allocations.push((currency_id, Permill::from_percent(100 / (c + 1))));
+			feed_data.push((currency_id, Price::one()));
+		}
+
+		AcalaOracle::feed_values(RawOrigin::Root.into(), feed_data)?;
+		CdpTreasury::on_system_surplus(10_000 * dollar(KUSD))?;
+		CdpTreasury::set_surplus_allocations(RawOrigin::Root.into(), allocations)?;
+	}: {
+		CdpTreasury::on_initialize(SurplusRebalancePeriod::get());
+	}
+
 	auction_collateral {
 		let currency_id: CurrencyId = KSM;
 		Currencies::deposit(currency_id, &CdpTreasury::account_id(), 10_000 * dollar(currency_id))?;
@@ -41,6 +97,16 @@ runtime_benchmarks! {
 	extract_surplus_to_treasury {
 		CdpTreasury::on_system_surplus(1_000 * dollar(KUSD))?;
 	}: _(RawOrigin::Root, 200 * dollar(KUSD))
+
+	set_surplus_allocations {
+		let c in 0 .. CollateralCurrencyIds::get().len().min(MaxSurplusAllocations::get() as usize) as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let mut allocations = vec![];
+
+		for i in 0 .. c {
+			allocations.push((currency_ids[i as usize], Permill::from_percent(10)));
+		}
+	}: _(RawOrigin::Root, allocations)
 }
 
 #[cfg(test)]
@@ -55,6 +121,13 @@ mod tests {
 			.into()
 	}
 
+	#[test]
+	fn test_on_initialize() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_on_initialize());
+		});
+	}
+
 	#[test]
 	fn test_auction_collateral() {
 		new_test_ext().execute_with(|| {
@@ -75,4 +148,11 @@ mod tests {
 			assert_ok!(test_benchmark_extract_surplus_to_treasury());
 		});
 	}
+
+	#[test]
+	fn test_set_surplus_allocations() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_surplus_allocations());
+		});
+	}
 }
@@ -16,15 +16,19 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{dollar, AccountId, EvmAccounts, Runtime, KAR};
+use crate::{dollar, AccountId, Event, EvmAccounts, Origin, Runtime, System, EVM, KAR};
 
 use super::utils::set_aca_balance;
 use codec::Encode;
 use frame_benchmarking::{account, whitelisted_caller};
+use frame_support::dispatch::DispatchError;
 use frame_system::RawOrigin;
+use module_evm_accounts::EvmAddressMapping;
+use module_support::AddressMapping;
 use orml_benchmarking::runtime_benchmarks;
+use sp_core::{H160, H256};
 use sp_io::hashing::keccak_256;
-use sp_std::prelude::*;
+use sp_std::{prelude::*, vec};
 
 const SEED: u32 = 0;
 
@@ -36,6 +40,33 @@ fn bob() -> secp256k1::SecretKey {
 	secp256k1::SecretKey::parse(&keccak_256(b"Bob")).unwrap()
 }
 
+fn deploy_contract_wallet(caller: AccountId) -> Result<H160, DispatchError> {
+	// A contract wallet accepting any signature, its runtime code returns the
+	// ERC-1271 magic value `0x1626ba7e` for any call:
+	//
+	// PUSH4 0x1626ba7e PUSH1 0xe0 SHL PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+	let contract = hex_literal::hex!("601080600b6000396000f3631626ba7e60e01b60005260206000f3").to_vec();
+
+	System::set_block_number(1);
+	EVM::create(Origin::signed(caller), contract, 0, 1000000000, 1000000000)
+		.map_or_else(|e| Err(e.error), |_| Ok(()))?;
+
+	if let Event::module_evm(module_evm::Event::Created(address)) = System::events().iter().last().unwrap().event {
+		EVM::deploy_free(Origin::root(), address).map_err(|e| e.error)?;
+		Ok(address)
+	} else {
+		Err("deploy_contract_wallet failed".into())
+	}
+}
+
+pub fn alice_account_id() -> AccountId {
+	let address = EvmAccounts::eth_address(&alice());
+	let mut data = [0u8; 32];
+	data[0..4].copy_from_slice(b"evm:");
+	data[4..24].copy_from_slice(&address[..]);
+	AccountId::from(Into::<[u8; 32]>::into(data))
+}
+
 pub fn bob_account_id() -> AccountId {
 	let address = EvmAccounts::eth_address(&bob());
 	let mut data = [0u8; 32];
@@ -58,6 +89,20 @@ runtime_benchmarks! {
 	claim_default_account {
 		let caller = whitelisted_caller();
   }: _(RawOrigin::Signed(caller))
+
+	claim_salted_account {
+		let caller: AccountId = account("caller", 0, SEED);
+		let salt = H256::repeat_byte(1);
+		let salted_address = EvmAccounts::salted_evm_address(&caller, &salt);
+		set_aca_balance(&EvmAddressMapping::<Runtime>::get_account_id(&salted_address), 1_000 * dollar(KAR));
+	}: _(RawOrigin::Signed(caller), salt)
+
+	claim_contract_account {
+		let caller: AccountId = account("caller", 0, SEED);
+		set_aca_balance(&alice_account_id(), 1_000 * dollar(KAR));
+		let wallet = deploy_contract_wallet(alice_account_id())?;
+		set_aca_balance(&EvmAddressMapping::<Runtime>::get_account_id(&wallet), 1_000 * dollar(KAR));
+	}: _(RawOrigin::Signed(caller), wallet, vec![0u8; 65])
 }
 
 #[cfg(test)]
@@ -85,4 +130,18 @@ mod tests {
 			assert_ok!(test_benchmark_claim_account());
 		});
 	}
+
+	#[test]
+	fn test_claim_salted_account() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim_salted_account());
+		});
+	}
+
+	#[test]
+	fn test_claim_contract_account() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim_contract_account());
+		});
+	}
 }
@@ -17,8 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	dollar, AcalaOracle, AccountId, Amount, CdpEngine, CollateralCurrencyIds, CurrencyId, Dex, Honzon, Price, Rate,
-	Ratio, Runtime, KSM, KUSD,
+	dollar, AcalaOracle, AccountId, Amount, CdpEngine, CollateralCurrencyIds, CurrencyId, Dex, Honzon,
+	MaxPositionLabelLength, Price, Rate, Ratio, Runtime, KSM, KUSD,
 };
 
 use super::utils::set_balance;
@@ -105,6 +105,49 @@ runtime_benchmarks! {
 		)?;
 	}: _(RawOrigin::Signed(caller), currency_id, collateral_amount.try_into().unwrap(), debit_amount)
 
+	// `adjust_loans`, worst case:
+	// adjust both collateral and debit of different collateral types
+	adjust_loans {
+		let c in 1 .. CollateralCurrencyIds::get().len() as u32;
+
+		let caller: AccountId = account("caller", 0, SEED);
+		let currency_ids = CollateralCurrencyIds::get();
+		let debit_value = 100 * dollar(KUSD);
+		let mut adjustments = vec![];
+
+		// feed price
+		AcalaOracle::feed_values(
+			RawOrigin::Root.into(),
+			currency_ids.iter().map(|currency_id| (*currency_id, Price::one())).collect(),
+		)?;
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
+			let debit_amount = debit_exchange_rate.reciprocal().unwrap().saturating_mul_int(debit_value);
+			let debit_amount: Amount = debit_amount.unique_saturated_into();
+			// the price of liquid currency is derived by the exchange rate, keep enough collateral for it
+			let collateral_value = 100 * debit_value;
+			let collateral_amount = Price::saturating_from_rational(dollar(currency_id), dollar(KUSD)).saturating_mul_int(collateral_value);
+
+			// set balance
+			set_balance(currency_id, &caller, collateral_amount);
+
+			// set risk params
+			CdpEngine::set_collateral_params(
+				RawOrigin::Root.into(),
+				currency_id,
+				Change::NoChange,
+				Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+				Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+				Change::NewValue(debit_value * 100),
+			)?;
+
+			adjustments.push((currency_id, collateral_amount.try_into().unwrap(), debit_amount));
+		}
+	}: _(RawOrigin::Signed(caller), adjustments)
+
 	transfer_loan_from {
 		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
 		let sender: AccountId = account("sender", 0, SEED);
@@ -202,6 +245,27 @@ runtime_benchmarks! {
 			debit_amount,
 		)?;
 	}: _(RawOrigin::Signed(sender), currency_id, None)
+
+	set_position_label {
+		let caller: AccountId = account("caller", 0, SEED);
+		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+		let collateral_amount = 1_000 * dollar(currency_id);
+		let label = vec![0u8; MaxPositionLabelLength::get() as usize];
+
+		// set balance
+		set_balance(currency_id, &caller, collateral_amount);
+
+		// feed price
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(currency_id, Price::one())])?;
+
+		// initialize caller's loan
+		Honzon::adjust_loan(
+			RawOrigin::Signed(caller.clone()).into(),
+			currency_id,
+			collateral_amount.try_into().unwrap(),
+			0,
+		)?;
+	}: _(RawOrigin::Signed(caller), currency_id, label)
 }
 
 #[cfg(test)]
@@ -244,10 +308,24 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_adjust_loans() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_adjust_loans());
+		});
+	}
+
 	#[test]
 	fn test_close_loan_has_debit_by_dex() {
 		new_test_ext().execute_with(|| {
 			assert_ok!(test_benchmark_close_loan_has_debit_by_dex());
 		});
 	}
+
+	#[test]
+	fn test_set_position_label() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_position_label());
+		});
+	}
 }
@@ -17,8 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	dollar, AccountId, AccumulatePeriod, CollateralCurrencyIds, Currencies, CurrencyId, GetNativeCurrencyId,
-	GetStableCurrencyId, Incentives, Rate, Rewards, Runtime, System, TokenSymbol, KAR, KSM, KUSD, LKSM,
+	dollar, AccountId, AccumulatePeriod, CollateralCurrencyIds, Currencies, CurrencyId, Dex, GetNativeCurrencyId,
+	GetStableCurrencyId, Incentives, Rate, Rewards, Runtime, System, TokenSymbol, ZeroAccountId, KAR, KSM, KUSD, LKSM,
 };
 
 use super::utils::set_balance;
@@ -121,6 +121,73 @@ runtime_benchmarks! {
 		set_balance(LKSM, &caller, 10_000 * dollar(KUSD));
 		let pool_id = PoolId::HomaValidatorAllowance(caller.clone());
 	}: _(RawOrigin::Signed(caller), pool_id, 1_000)
+
+	retire_pool {
+		let pool_id = PoolId::LoansIncentive(KSM);
+		Incentives::update_incentive_rewards(RawOrigin::Root.into(), vec![(pool_id.clone(), 100 * dollar(KAR))])?;
+	}: _(RawOrigin::Root, pool_id, Some(PoolId::LoansIncentive(KAR)))
+
+	migrate_pool_shares {
+		let c in 0 .. 100;
+		let caller: AccountId = account("caller", 0, SEED);
+		let pool_id = PoolId::LoansIncentive(KSM);
+
+		for i in 0 .. c {
+			let staker: AccountId = account("staker", i, SEED);
+			Rewards::add_share(&staker, &pool_id, 100);
+		}
+		Incentives::retire_pool(RawOrigin::Root.into(), pool_id.clone(), Some(PoolId::LoansIncentive(KAR)))?;
+	}: _(RawOrigin::Signed(caller), pool_id, c)
+
+	update_deposit_caps {
+		let c in 0 .. 100;
+		let mut values = vec![];
+
+		for _ in 0 .. c {
+			values.push((PoolId::DexIncentive(BTC_AUSD_LP), Some(1_000_000 * dollar(KUSD)), Some(10_000 * dollar(KUSD))));
+		}
+	}: _(RawOrigin::Root, values)
+
+	update_accumulate_periods {
+		let c in 0 .. 100;
+		let mut values = vec![];
+
+		for _ in 0 .. c {
+			values.push((PoolId::DexIncentive(BTC_AUSD_LP), Some(AccumulatePeriod::get())));
+		}
+	}: _(RawOrigin::Root, values)
+
+	claim_snapshot_rewards {
+		let caller: AccountId = account("caller", 0, SEED);
+		let pool_id = PoolId::LoansIncentive(KSM);
+		let native_currency_id = GetNativeCurrencyId::get();
+
+		set_balance(native_currency_id, &ZeroAccountId::get(), 100 * dollar(native_currency_id));
+		module_incentives::RewardSnapshots::<Runtime>::insert(&pool_id, &caller, 100 * dollar(native_currency_id));
+	}: _(RawOrigin::Signed(caller), pool_id)
+
+	compound_rewards {
+		let caller: AccountId = account("caller", 0, SEED);
+		let maker: AccountId = account("maker", 0, SEED);
+		let native_currency_id = GetNativeCurrencyId::get();
+		let stable_currency_id = GetStableCurrencyId::get();
+		let btc_currency_id = CurrencyId::Token(TokenSymbol::XBTC);
+
+		set_balance(native_currency_id, &maker, 10_000 * dollar(native_currency_id));
+		set_balance(stable_currency_id, &maker, 10_000 * dollar(stable_currency_id));
+		set_balance(btc_currency_id, &maker, 20_000 * dollar(btc_currency_id));
+		let _ = Dex::enable_trading_pair(RawOrigin::Root.into(), btc_currency_id, stable_currency_id);
+		let _ = Dex::enable_trading_pair(RawOrigin::Root.into(), native_currency_id, btc_currency_id);
+		Dex::add_liquidity(RawOrigin::Signed(maker.clone()).into(), btc_currency_id, stable_currency_id, 10_000 * dollar(btc_currency_id), 10_000 * dollar(stable_currency_id), false)?;
+		Dex::add_liquidity(RawOrigin::Signed(maker).into(), native_currency_id, btc_currency_id, 10_000 * dollar(native_currency_id), 10_000 * dollar(btc_currency_id), false)?;
+
+		set_balance(BTC_AUSD_LP, &caller, 10_000 * dollar(KUSD));
+		Incentives::deposit_dex_share(RawOrigin::Signed(caller.clone()).into(), BTC_AUSD_LP, 10_000 * dollar(KUSD))?;
+		Rewards::accumulate_reward(&PoolId::DexIncentive(BTC_AUSD_LP), 100 * dollar(native_currency_id));
+		Rewards::accumulate_reward(&PoolId::DexSaving(BTC_AUSD_LP), 100 * dollar(stable_currency_id));
+		set_balance(native_currency_id, &ZeroAccountId::get(), 100 * dollar(native_currency_id));
+		set_balance(stable_currency_id, &ZeroAccountId::get(), 100 * dollar(stable_currency_id));
+	}: _(RawOrigin::Signed(caller), BTC_AUSD_LP, vec![vec![native_currency_id, btc_currency_id]], 0)
 }
 
 #[cfg(test)]
@@ -176,4 +243,46 @@ mod tests {
 			assert_ok!(test_benchmark_update_dex_saving_rewards());
 		});
 	}
+
+	#[test]
+	fn test_retire_pool() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_retire_pool());
+		});
+	}
+
+	#[test]
+	fn test_migrate_pool_shares() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_migrate_pool_shares());
+		});
+	}
+
+	#[test]
+	fn test_update_deposit_caps() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_deposit_caps());
+		});
+	}
+
+	#[test]
+	fn test_update_accumulate_periods() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_update_accumulate_periods());
+		});
+	}
+
+	#[test]
+	fn test_claim_snapshot_rewards() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim_snapshot_rewards());
+		});
+	}
+
+	#[test]
+	fn test_compound_rewards() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_compound_rewards());
+		});
+	}
 }
@@ -28,6 +28,7 @@
 pub mod auction_manager;
 pub mod cdp_engine;
 pub mod cdp_treasury;
+pub mod circuit_breaker;
 pub mod dex;
 pub mod emergency_shutdown;
 pub mod evm;
@@ -36,7 +37,9 @@ pub mod homa;
 pub mod honzon;
 pub mod incentives;
 pub mod prices;
+pub mod protocol_liquidity;
 pub mod transaction_payment;
+pub mod xcm_interface;
 
 // orml benchmarking
 pub mod auction;
@@ -16,8 +16,9 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{AcalaOracle, CollateralCurrencyIds, CurrencyId, Origin, Price, Prices, Runtime, KSM};
+use crate::{AcalaOracle, CollateralCurrencyIds, CurrencyId, Origin, Price, Prices, Ratio, Runtime, KSM};
 
+use frame_support::traits::OnInitialize;
 use frame_system::RawOrigin;
 use orml_benchmarking::runtime_benchmarks;
 use sp_runtime::FixedPointNumber;
@@ -28,6 +29,23 @@ runtime_benchmarks! {
 
 	_ {}
 
+	on_initialize {
+		let c in 0 .. CollateralCurrencyIds::get().len().saturating_sub(1) as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let mut values = vec![];
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			values.push((currency_id, Price::one()));
+			Prices::pause_price(Origin::root(), currency_id)?;
+		}
+
+		// feed fresh prices
+		AcalaOracle::feed_values(RawOrigin::Root.into(), values)?;
+	}: {
+		Prices::on_initialize(1);
+	}
+
 	lock_price {
 		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
 
@@ -42,6 +60,24 @@ runtime_benchmarks! {
 		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(currency_id, Price::one())])?;
 		Prices::lock_price(Origin::root(), KSM)?;
 	}: _(RawOrigin::Root, KSM)
+
+	pause_price {
+		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+
+		// feed price
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(currency_id, Price::one())])?;
+	}: _(RawOrigin::Root, KSM)
+
+	resume_price {
+		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+
+		// feed price
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(currency_id, Price::one())])?;
+		Prices::pause_price(Origin::root(), KSM)?;
+	}: _(RawOrigin::Root, KSM)
+
+	set_liquid_crowdloan_discount {
+	}: _(RawOrigin::Root, Ratio::saturating_from_rational(10, 100), 100u32)
 }
 
 #[cfg(test)]
@@ -56,6 +92,13 @@ mod tests {
 			.into()
 	}
 
+	#[test]
+	fn test_on_initialize() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_on_initialize());
+		});
+	}
+
 	#[test]
 	fn test_lock_price() {
 		new_test_ext().execute_with(|| {
@@ -69,4 +112,25 @@ mod tests {
 			assert_ok!(test_benchmark_unlock_price());
 		});
 	}
+
+	#[test]
+	fn test_pause_price() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_pause_price());
+		});
+	}
+
+	#[test]
+	fn test_resume_price() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_resume_price());
+		});
+	}
+
+	#[test]
+	fn test_set_liquid_crowdloan_discount() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_liquid_crowdloan_discount());
+		});
+	}
 }
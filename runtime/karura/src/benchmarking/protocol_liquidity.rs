@@ -0,0 +1,159 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	dollar, AcalaOracle, Balance, CollateralCurrencyIds, CurrencyId, Dex, GetStableCurrencyId, KaruraTreasuryAccount,
+	MaxProtocolLiquidityStrategies, Price, Prices, ProtocolLiquidity, Runtime, KSM,
+};
+
+use super::utils::set_balance;
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+use module_protocol_liquidity::BandStrategy;
+use module_support::PriceProvider;
+use orml_benchmarking::runtime_benchmarks;
+use primitives::TradingPair;
+use sp_runtime::FixedPointNumber;
+use sp_std::prelude::*;
+
+// deploy the treasury funds into the pool of currency and stable currency in
+// the ratio of the oracle price, return the deployed stable amount.
+fn inject_liquidity(currency_id: CurrencyId) -> Result<Balance, &'static str> {
+	let stable_currency_id = GetStableCurrencyId::get();
+	let price = Prices::get_relative_price(currency_id, stable_currency_id).ok_or("no oracle price")?;
+	let amount = 100 * dollar(currency_id);
+	let stable_amount = price.saturating_mul_int(amount);
+	set_balance(currency_id, &KaruraTreasuryAccount::get(), 10 * amount);
+
+	let _ = Dex::enable_trading_pair(RawOrigin::Root.into(), currency_id, stable_currency_id);
+	ProtocolLiquidity::add_liquidity(
+		RawOrigin::Root.into(),
+		currency_id,
+		stable_currency_id,
+		amount,
+		stable_amount,
+		0,
+	)?;
+
+	Ok(stable_amount)
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_protocol_liquidity }
+
+	_ {}
+
+	// run the band strategies which all deploy liquidity
+	on_initialize {
+		let c in 0 .. CollateralCurrencyIds::get().len().min(MaxProtocolLiquidityStrategies::get() as usize) as u32;
+		let currency_ids = CollateralCurrencyIds::get();
+		let stable_currency_id = GetStableCurrencyId::get();
+		set_balance(stable_currency_id, &KaruraTreasuryAccount::get(), 1_000_000 * dollar(stable_currency_id));
+
+		// feed price
+		let mut feed_data: Vec<(CurrencyId, Price)> = vec![];
+		for i in 0 .. c {
+			feed_data.push((currency_ids[i as usize], Price::one()));
+		}
+		AcalaOracle::feed_values(RawOrigin::Root.into(), feed_data)?;
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			let stable_amount = inject_liquidity(currency_id)?;
+			ProtocolLiquidity::set_band_strategy(
+				RawOrigin::Root.into(),
+				currency_id,
+				stable_currency_id,
+				Some(BandStrategy {
+					currency_id: stable_currency_id,
+					lower: 2 * stable_amount,
+					upper: 4 * stable_amount,
+					step: stable_amount / 10,
+				}),
+			)?;
+		}
+	}: {
+		ProtocolLiquidity::on_initialize(1);
+	}
+
+	add_liquidity {
+		let stable_currency_id = GetStableCurrencyId::get();
+		set_balance(stable_currency_id, &KaruraTreasuryAccount::get(), 1_000_000 * dollar(stable_currency_id));
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(KSM, Price::one())])?;
+		let stable_amount = inject_liquidity(KSM)?;
+	}: _(RawOrigin::Root, KSM, stable_currency_id, 100 * dollar(KSM), stable_amount, 0)
+
+	remove_liquidity {
+		let stable_currency_id = GetStableCurrencyId::get();
+		set_balance(stable_currency_id, &KaruraTreasuryAccount::get(), 1_000_000 * dollar(stable_currency_id));
+		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(KSM, Price::one())])?;
+		inject_liquidity(KSM)?;
+		let shares = ProtocolLiquidity::positions(TradingPair::new(KSM, stable_currency_id)).ok_or("no position")?.shares;
+	}: _(RawOrigin::Root, KSM, stable_currency_id, shares / 2, 0, 0)
+
+	set_band_strategy {
+		let stable_currency_id = GetStableCurrencyId::get();
+		let strategy = BandStrategy {
+			currency_id: stable_currency_id,
+			lower: 1_000 * dollar(stable_currency_id),
+			upper: 2_000 * dollar(stable_currency_id),
+			step: 100 * dollar(stable_currency_id),
+		};
+	}: _(RawOrigin::Root, KSM, stable_currency_id, Some(strategy))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::assert_ok;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	#[test]
+	fn test_on_initialize() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_on_initialize());
+		});
+	}
+
+	#[test]
+	fn test_add_liquidity() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_liquidity());
+		});
+	}
+
+	#[test]
+	fn test_remove_liquidity() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_remove_liquidity());
+		});
+	}
+
+	#[test]
+	fn test_set_band_strategy() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_band_strategy());
+		});
+	}
+}
@@ -0,0 +1,252 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::utils::set_balance;
+use crate::{
+	dollar, AccountId, CurrencyId, KaruraTreasuryAccount, MaxCallPrefixLength, Runtime, TokenSymbol, XcmInterface,
+};
+
+use frame_benchmarking::account;
+use frame_system::RawOrigin;
+use module_xcm_interface::{CallTemplate, TransferLimit, TrappedAsset, TrappedAssets};
+use orml_benchmarking::runtime_benchmarks;
+use sp_std::prelude::*;
+use xcm::v0::{
+	Junction::{AccountId32, Parent},
+	MultiAsset,
+	MultiLocation::{self, X1},
+	NetworkId,
+};
+
+const SEED: u32 = 0;
+const SIBLING_PARA_ID: u32 = 2000;
+
+const KSM: CurrencyId = CurrencyId::Token(TokenSymbol::KSM);
+const KUSD: CurrencyId = CurrencyId::Token(TokenSymbol::KUSD);
+
+fn account_location(who: AccountId) -> MultiLocation {
+	X1(AccountId32 {
+		network: NetworkId::Any,
+		id: who.into(),
+	})
+}
+
+fn trap_ksm(index: u32, beneficiary: AccountId) {
+	TrappedAssets::<Runtime>::insert(
+		index,
+		TrappedAsset {
+			asset: MultiAsset::ConcreteFungible {
+				id: X1(Parent),
+				amount: dollar(KSM),
+			},
+			beneficiary: account_location(beneficiary),
+		},
+	);
+}
+
+fn add_sub_account_proxy(index: u16, delegate: AccountId, proxy_type: u8) -> Result<(), &'static str> {
+	XcmInterface::add_sub_account_proxy(RawOrigin::Root.into(), index, delegate, proxy_type)?;
+	XcmInterface::reconcile_sub_account(RawOrigin::Root.into(), index, true, None)?;
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_xcm_interface }
+
+	_ {}
+
+	set_call_template {
+		let template = CallTemplate {
+			call_prefix: vec![0u8; MaxCallPrefixLength::get() as usize],
+			require_weight_at_most: 1_000_000_000,
+		};
+	}: _(RawOrigin::Root, SIBLING_PARA_ID, 0, Some(template))
+
+	set_transfer_limit {
+		let limit = TransferLimit {
+			amount: 1_000 * dollar(KUSD),
+			window: 100,
+		};
+	}: _(RawOrigin::Root, KUSD, Some(limit))
+
+	claim_trapped_asset {
+		let caller: AccountId = account("caller", 0, SEED);
+		trap_ksm(0, caller.clone());
+	}: _(RawOrigin::Signed(caller), 0)
+
+	recover_trapped_asset {
+		let beneficiary: AccountId = account("beneficiary", 0, SEED);
+		let dest: AccountId = account("dest", 0, SEED);
+		trap_ksm(0, beneficiary);
+	}: _(RawOrigin::Root, 0, account_location(dest))
+
+	transfer_and_call {
+		let caller: AccountId = account("caller", 0, SEED);
+		let recipient: AccountId = account("recipient", 0, SEED);
+		let template = CallTemplate {
+			call_prefix: vec![0u8; MaxCallPrefixLength::get() as usize],
+			require_weight_at_most: 1_000_000_000,
+		};
+		XcmInterface::set_call_template(RawOrigin::Root.into(), SIBLING_PARA_ID, 0, Some(template))?;
+		XcmInterface::set_transfer_limit(RawOrigin::Root.into(), KUSD, Some(TransferLimit {
+			amount: 1_000 * dollar(KUSD),
+			window: 100,
+		}))?;
+		set_balance(KUSD, &caller, 100 * dollar(KUSD));
+	}: _(RawOrigin::Signed(caller), KUSD, 10 * dollar(KUSD), dollar(KUSD), SIBLING_PARA_ID, 0, recipient, 1_000_000_000)
+
+	withdraw_remote_deposit {
+		let caller: AccountId = account("caller", 0, SEED);
+		XcmInterface::set_transfer_limit(RawOrigin::Root.into(), KUSD, Some(TransferLimit {
+			amount: 1_000 * dollar(KUSD),
+			window: 100,
+		}))?;
+		set_balance(KUSD, &caller, 100 * dollar(KUSD));
+	}: _(RawOrigin::Signed(caller), KUSD, 10 * dollar(KUSD), dollar(KUSD), SIBLING_PARA_ID, 1_000_000_000)
+
+	add_sub_account_proxy {
+		let delegate: AccountId = account("delegate", 0, SEED);
+		set_balance(KSM, &KaruraTreasuryAccount::get(), 100 * dollar(KSM));
+	}: _(RawOrigin::Root, 0, delegate, 1)
+
+	remove_sub_account_proxy {
+		let delegate: AccountId = account("delegate", 0, SEED);
+		set_balance(KSM, &KaruraTreasuryAccount::get(), 100 * dollar(KSM));
+		add_sub_account_proxy(0, delegate.clone(), 1)?;
+	}: _(RawOrigin::Root, 0, delegate, 1)
+
+	rotate_sub_account_proxy {
+		let old: AccountId = account("old", 0, SEED);
+		let new: AccountId = account("new", 0, SEED);
+		set_balance(KSM, &KaruraTreasuryAccount::get(), 100 * dollar(KSM));
+		add_sub_account_proxy(0, old.clone(), 1)?;
+	}: _(RawOrigin::Root, 0, (old, 1), (new, 1))
+
+	create_sub_account_anonymous_proxy {
+		set_balance(KSM, &KaruraTreasuryAccount::get(), 100 * dollar(KSM));
+	}: _(RawOrigin::Root, 0, 1, 0)
+
+	recover_sub_account {
+		let delegate: AccountId = account("delegate", 0, SEED);
+		set_balance(KSM, &KaruraTreasuryAccount::get(), 100 * dollar(KSM));
+		add_sub_account_proxy(0, delegate, 1)?;
+	}: _(RawOrigin::Root, 0)
+
+	reconcile_sub_account {
+		let anonymous_proxy: AccountId = account("anonymous_proxy", 0, SEED);
+		set_balance(KSM, &KaruraTreasuryAccount::get(), 100 * dollar(KSM));
+		XcmInterface::create_sub_account_anonymous_proxy(RawOrigin::Root.into(), 0, 1, 0)?;
+	}: _(RawOrigin::Root, 0, true, Some(anonymous_proxy))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::assert_ok;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	#[test]
+	fn test_set_call_template() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_call_template());
+		});
+	}
+
+	#[test]
+	fn test_set_transfer_limit() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_transfer_limit());
+		});
+	}
+
+	#[test]
+	fn test_claim_trapped_asset() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim_trapped_asset());
+		});
+	}
+
+	#[test]
+	fn test_recover_trapped_asset() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_recover_trapped_asset());
+		});
+	}
+
+	#[test]
+	fn test_transfer_and_call() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_transfer_and_call());
+		});
+	}
+
+	#[test]
+	fn test_withdraw_remote_deposit() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_withdraw_remote_deposit());
+		});
+	}
+
+	#[test]
+	fn test_add_sub_account_proxy() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_sub_account_proxy());
+		});
+	}
+
+	#[test]
+	fn test_remove_sub_account_proxy() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_remove_sub_account_proxy());
+		});
+	}
+
+	#[test]
+	fn test_rotate_sub_account_proxy() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_rotate_sub_account_proxy());
+		});
+	}
+
+	#[test]
+	fn test_create_sub_account_anonymous_proxy() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create_sub_account_anonymous_proxy());
+		});
+	}
+
+	#[test]
+	fn test_recover_sub_account() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_recover_sub_account());
+		});
+	}
+
+	#[test]
+	fn test_reconcile_sub_account() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_reconcile_sub_account());
+		});
+	}
+}
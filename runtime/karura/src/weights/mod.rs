@@ -22,6 +22,7 @@
 pub mod module_auction_manager;
 pub mod module_cdp_engine;
 pub mod module_cdp_treasury;
+pub mod module_circuit_breaker;
 pub mod module_currencies;
 pub mod module_dex;
 pub mod module_emergency_shutdown;
@@ -32,7 +33,9 @@ pub mod module_honzon;
 pub mod module_incentives;
 pub mod module_nft;
 pub mod module_prices;
+pub mod module_protocol_liquidity;
 pub mod module_transaction_payment;
+pub mod module_xcm_interface;
 
 pub mod orml_auction;
 pub mod orml_authority;
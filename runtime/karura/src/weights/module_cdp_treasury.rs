@@ -46,6 +46,14 @@ use sp_std::marker::PhantomData;
 /// Weight functions for module_cdp_treasury.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> module_cdp_treasury::WeightInfo for WeightInfo<T> {
+	fn on_initialize(c: u32) -> Weight {
+		(21_467_000 as Weight)
+			// Standard Error: 196_000
+			.saturating_add((318_240_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(c as Weight)))
+	}
 	fn extract_surplus_to_treasury() -> Weight {
 		(124_000_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
@@ -59,4 +67,10 @@ impl<T: frame_system::Config> module_cdp_treasury::WeightInfo for WeightInfo<T>
 	fn set_expected_collateral_auction_size() -> Weight {
 		(25_940_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn set_surplus_allocations(c: u32) -> Weight {
+		(26_102_000 as Weight)
+			// Standard Error: 7_000
+			.saturating_add((1_210_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
@@ -0,0 +1,72 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Autogenerated weights for module_circuit_breaker
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
+//! DATE: 2021-05-27, STEPS: [50, ], REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("karura-latest"), DB
+//! CACHE: 128
+
+// Executed Command:
+// target/release/acala
+// benchmark
+// --chain=karura-latest
+// --steps=50
+// --repeat=20
+// --pallet=module_circuit_breaker
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --header=./HEADER-GPL3
+// --output=./runtime/karura/src/weights/
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for module_circuit_breaker.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> module_circuit_breaker::WeightInfo for WeightInfo<T> {
+	fn on_initialize(c: u32) -> Weight {
+		(28_762_000 as Weight)
+			// Standard Error: 103_000
+			.saturating_add((43_519_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn set_trigger_tiers() -> Weight {
+		(27_811_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_withdrawal_threshold() -> Weight {
+		(23_174_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn force_trip() -> Weight {
+		(27_225_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn force_reset() -> Weight {
+		(23_310_000 as Weight).saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
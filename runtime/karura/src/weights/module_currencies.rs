@@ -65,4 +65,12 @@ impl<T: frame_system::Config> module_currencies::WeightInfo for WeightInfo<T> {
 	fn update_balance_native_currency_killing() -> Weight {
 		(27_024_000 as Weight)
 	}
+	fn approve() -> Weight {
+		(38_203_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn transfer_from() -> Weight {
+		(120_875_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
 }
@@ -64,7 +64,7 @@ impl<T: frame_system::Config> module_evm::WeightInfo for WeightInfo<T> {
 	fn enable_contract_development() -> Weight {
 		(180_210_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	fn disable_contract_development() -> Weight {
 		(180_952_000 as Weight)
@@ -81,4 +81,47 @@ impl<T: frame_system::Config> module_evm::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(7 as Weight))
 			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
+	fn reap_account() -> Weight {
+		(178_604_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn claim_reaped_deposit() -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn set_deployment_mode() -> Weight {
+		(33_815_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn add_allowed_deployer() -> Weight {
+		(34_472_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_allowed_deployer() -> Weight {
+		(33_918_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn add_precompile_delegate_caller() -> Weight {
+		(34_472_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_precompile_delegate_caller() -> Weight {
+		(33_918_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_caller_access_mode(c: u32) -> Weight {
+		(79_634_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((7_903_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn add_listed_caller() -> Weight {
+		(130_218_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn remove_listed_caller() -> Weight {
+		(115_947_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 }
@@ -63,6 +63,14 @@ impl<T: frame_system::Config> module_honzon::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(26 as Weight))
 			.saturating_add(T::DbWeight::get().writes(11 as Weight))
 	}
+	fn adjust_loans(c: u32) -> Weight {
+		(57_906_000 as Weight)
+			// Standard Error: 352_000
+			.saturating_add((318_117_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads((17 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((11 as Weight).saturating_mul(c as Weight)))
+	}
 	fn transfer_loan_from() -> Weight {
 		(287_545_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(22 as Weight))
@@ -73,4 +81,9 @@ impl<T: frame_system::Config> module_honzon::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(22 as Weight))
 			.saturating_add(T::DbWeight::get().writes(7 as Weight))
 	}
+	fn set_position_label() -> Weight {
+		(40_512_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
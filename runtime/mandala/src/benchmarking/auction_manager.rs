@@ -17,8 +17,8 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	dollar, AcalaOracle, AccountId, AuctionId, AuctionManager, CdpTreasury, Currencies, EmergencyShutdown,
-	GetStableCurrencyId, Price, Runtime, DOT,
+	dollar, AcalaOracle, AccountId, AuctionId, AuctionManager, CdpTreasury, Currencies, Dex, EmergencyShutdown,
+	GetStableCurrencyId, InstallmentPeriod, Price, Runtime, System, DOT,
 };
 
 use frame_benchmarking::account;
@@ -26,7 +26,7 @@ use frame_system::RawOrigin;
 use module_support::AuctionManager as AuctionManagerTrait;
 use module_support::CDPTreasury;
 use orml_benchmarking::runtime_benchmarks;
-use orml_traits::MultiCurrency;
+use orml_traits::{AuctionHandler, MultiCurrency};
 use sp_runtime::FixedPointNumber;
 use sp_std::prelude::*;
 
@@ -38,17 +38,24 @@ runtime_benchmarks! {
 	_ {}
 
 	// `cancel` a collateral auction, worst case:
-	// auction have been already bid
+	// auction have been already bid with swap
 	cancel_collateral_auction {
 		let bidder: AccountId = account("bidder", 0, SEED);
 		let funder: AccountId = account("funder", 0, SEED);
+		let maker: AccountId = account("maker", 0, SEED);
 		let stable_currency_id = GetStableCurrencyId::get();
 
 		// set balance
-		Currencies::deposit(stable_currency_id, &bidder, 80 * dollar(stable_currency_id))?;
+		Currencies::deposit(DOT, &bidder, 10 * dollar(DOT))?;
 		Currencies::deposit(DOT, &funder, dollar(DOT))?;
 		CdpTreasury::deposit_collateral(&funder, DOT, dollar(DOT))?;
 
+		// inject liquidity
+		Currencies::deposit(DOT, &maker, 100 * dollar(DOT))?;
+		Currencies::deposit(stable_currency_id, &maker, 10_000 * dollar(stable_currency_id))?;
+		let _ = Dex::enable_trading_pair(RawOrigin::Root.into(), DOT, stable_currency_id);
+		Dex::add_liquidity(RawOrigin::Signed(maker).into(), DOT, stable_currency_id, 100 * dollar(DOT), 10_000 * dollar(stable_currency_id), false)?;
+
 		// feed price
 		AcalaOracle::feed_values(RawOrigin::Root.into(), vec![(DOT, Price::saturating_from_integer(120))])?;
 
@@ -56,12 +63,90 @@ runtime_benchmarks! {
 		AuctionManager::new_collateral_auction(&funder, DOT, dollar(DOT), 100 * dollar(stable_currency_id))?;
 		let auction_id: AuctionId = Default::default();
 
-		// bid collateral auction
-		let _ = AuctionManager::collateral_auction_bid_handler(1, auction_id, (bidder, 80 * dollar(stable_currency_id)), None);
+		// bid collateral auction with swap
+		AuctionManager::bid_with_swap(RawOrigin::Signed(bidder).into(), auction_id, DOT, 10 * dollar(DOT), 80 * dollar(stable_currency_id))?;
 
 		// shutdown
 		EmergencyShutdown::emergency_shutdown(RawOrigin::Root.into())?;
 	}: cancel(RawOrigin::None, auction_id)
+
+	set_institutional_bidder {
+		let bidder: AccountId = account("bidder", 0, SEED);
+	}: _(RawOrigin::Root, bidder, true)
+
+	// pay an installment of the settlement, worst case:
+	// settlement still has installments remaining
+	pay_installment {
+		let bidder: AccountId = account("bidder", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+		let stable_currency_id = GetStableCurrencyId::get();
+
+		// set balance
+		Currencies::deposit(stable_currency_id, &bidder, 100 * dollar(stable_currency_id))?;
+		Currencies::deposit(DOT, &funder, dollar(DOT))?;
+		CdpTreasury::deposit_collateral(&funder, DOT, dollar(DOT))?;
+
+		// create collateral auction
+		AuctionManager::new_collateral_auction(&funder, DOT, dollar(DOT), 100 * dollar(stable_currency_id))?;
+		let auction_id: AuctionId = Default::default();
+
+		// institutional bidder wins the collateral auction
+		AuctionManager::set_institutional_bidder(RawOrigin::Root.into(), bidder.clone(), true)?;
+		AuctionManager::collateral_auction_bid_handler(1, auction_id, (bidder.clone(), 80 * dollar(stable_currency_id)), None)?;
+		AuctionManager::on_auction_ended(auction_id, Some((bidder.clone(), 80 * dollar(stable_currency_id))));
+	}: _(RawOrigin::Signed(bidder), auction_id)
+
+	default_installment {
+		let caller: AccountId = account("caller", 0, SEED);
+		let bidder: AccountId = account("bidder", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+		let stable_currency_id = GetStableCurrencyId::get();
+
+		// set balance
+		Currencies::deposit(stable_currency_id, &bidder, 100 * dollar(stable_currency_id))?;
+		Currencies::deposit(DOT, &funder, dollar(DOT))?;
+		CdpTreasury::deposit_collateral(&funder, DOT, dollar(DOT))?;
+
+		// create collateral auction
+		AuctionManager::new_collateral_auction(&funder, DOT, dollar(DOT), 100 * dollar(stable_currency_id))?;
+		let auction_id: AuctionId = Default::default();
+
+		// institutional bidder wins the collateral auction
+		AuctionManager::set_institutional_bidder(RawOrigin::Root.into(), bidder.clone(), true)?;
+		AuctionManager::collateral_auction_bid_handler(1, auction_id, (bidder.clone(), 80 * dollar(stable_currency_id)), None)?;
+		AuctionManager::on_auction_ended(auction_id, Some((bidder.clone(), 80 * dollar(stable_currency_id))));
+
+		System::set_block_number(System::block_number() + InstallmentPeriod::get() + 1);
+	}: _(RawOrigin::Signed(caller), auction_id)
+
+	// bid a collateral auction with swap, worst case:
+	// auction have been already bid
+	bid_with_swap {
+		let bidder: AccountId = account("bidder", 0, SEED);
+		let last_bidder: AccountId = account("last_bidder", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+		let maker: AccountId = account("maker", 0, SEED);
+		let stable_currency_id = GetStableCurrencyId::get();
+
+		// set balance
+		Currencies::deposit(DOT, &bidder, 10 * dollar(DOT))?;
+		Currencies::deposit(DOT, &last_bidder, 10 * dollar(DOT))?;
+		Currencies::deposit(DOT, &funder, dollar(DOT))?;
+		CdpTreasury::deposit_collateral(&funder, DOT, dollar(DOT))?;
+
+		// inject liquidity
+		Currencies::deposit(DOT, &maker, 100 * dollar(DOT))?;
+		Currencies::deposit(stable_currency_id, &maker, 10_000 * dollar(stable_currency_id))?;
+		let _ = Dex::enable_trading_pair(RawOrigin::Root.into(), DOT, stable_currency_id);
+		Dex::add_liquidity(RawOrigin::Signed(maker).into(), DOT, stable_currency_id, 100 * dollar(DOT), 10_000 * dollar(stable_currency_id), false)?;
+
+		// create collateral auction
+		AuctionManager::new_collateral_auction(&funder, DOT, dollar(DOT), 100 * dollar(stable_currency_id))?;
+		let auction_id: AuctionId = Default::default();
+
+		// bid collateral auction with swap
+		AuctionManager::bid_with_swap(RawOrigin::Signed(last_bidder).into(), auction_id, DOT, 10 * dollar(DOT), 80 * dollar(stable_currency_id))?;
+	}: _(RawOrigin::Signed(bidder), auction_id, DOT, 10 * dollar(DOT), 90 * dollar(stable_currency_id))
 }
 
 #[cfg(test)]
@@ -82,4 +167,32 @@ mod tests {
 			assert_ok!(test_benchmark_cancel_collateral_auction());
 		});
 	}
+
+	#[test]
+	fn test_set_institutional_bidder() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_institutional_bidder());
+		});
+	}
+
+	#[test]
+	fn test_pay_installment() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_pay_installment());
+		});
+	}
+
+	#[test]
+	fn test_default_installment() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_default_installment());
+		});
+	}
+
+	#[test]
+	fn test_bid_with_swap() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_bid_with_swap());
+		});
+	}
 }
@@ -18,8 +18,9 @@
 
 use crate::{
 	dollar, AcalaOracle, AccountId, Amount, Balance, CdpEngine, CollateralCurrencyIds, CurrencyId,
-	DefaultDebitExchangeRate, Dex, EmergencyShutdown, GetStableCurrencyId, Indices, MaxSlippageSwapWithDEX,
-	MinimumDebitValue, Price, Rate, Ratio, Runtime, Timestamp, AUSD, DOT, MILLISECS_PER_BLOCK,
+	DefaultDebitExchangeRate, Dex, EmergencyShutdown, GetStableCurrencyId, Indices, MaxLiquidationSteps,
+	MaxSlippageSwapWithDEX, MaxStabilityFeeExemptions, MinimumDebitValue, Price, Rate, Ratio, Runtime, Timestamp, AUSD,
+	DOT, MILLISECS_PER_BLOCK,
 };
 
 use super::utils::set_balance;
@@ -27,12 +28,13 @@ use core::convert::TryInto;
 use frame_benchmarking::account;
 use frame_support::traits::OnInitialize;
 use frame_system::RawOrigin;
+use module_cdp_engine::LiquidationStep;
 use module_support::DEXManager;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::Change;
 use sp_runtime::{
 	traits::{StaticLookup, UniqueSaturatedInto},
-	FixedPointNumber,
+	FixedPointNumber, Permill,
 };
 use sp_std::prelude::*;
 
@@ -267,6 +269,27 @@ runtime_benchmarks! {
 		// shutdown
 		EmergencyShutdown::emergency_shutdown(RawOrigin::Root.into())?;
 	}: _(RawOrigin::None, currency_id, owner_lookup)
+
+	// worst: grant the last stability fee exemption of the collateral type
+	set_stability_fee_exemption {
+		let owner: AccountId = account("owner", 0, SEED);
+		let owner_lookup = Indices::unlookup(owner.clone());
+		for i in 1 .. MaxStabilityFeeExemptions::get() {
+			let exempted: AccountId = account("exempted", i, SEED);
+			CdpEngine::set_stability_fee_exemption(RawOrigin::Root.into(), DOT, Indices::unlookup(exempted), Permill::one(), 100_000 * dollar(AUSD))?;
+		}
+	}: _(RawOrigin::Root, DOT, owner_lookup, Permill::one(), 100_000 * dollar(AUSD))
+
+	revoke_stability_fee_exemption {
+		let owner: AccountId = account("owner", 0, SEED);
+		let owner_lookup = Indices::unlookup(owner.clone());
+		CdpEngine::set_stability_fee_exemption(RawOrigin::Root.into(), DOT, owner_lookup.clone(), Permill::one(), 100_000 * dollar(AUSD))?;
+	}: _(RawOrigin::Root, DOT, owner_lookup)
+
+	set_liquidation_steps {
+		let mut steps = vec![LiquidationStep::DexSwap(MaxSlippageSwapWithDEX::get()); MaxLiquidationSteps::get() as usize - 1];
+		steps.push(LiquidationStep::Auction);
+	}: _(RawOrigin::Root, DOT, steps)
 }
 
 #[cfg(test)]
@@ -322,4 +345,25 @@ mod tests {
 			assert_ok!(test_benchmark_settle());
 		});
 	}
+
+	#[test]
+	fn test_set_stability_fee_exemption() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_stability_fee_exemption());
+		});
+	}
+
+	#[test]
+	fn test_revoke_stability_fee_exemption() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_revoke_stability_fee_exemption());
+		});
+	}
+
+	#[test]
+	fn test_set_liquidation_steps() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_liquidation_steps());
+		});
+	}
 }
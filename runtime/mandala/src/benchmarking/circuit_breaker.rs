@@ -0,0 +1,123 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2021 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	AcalaOracle, BlockNumber, CircuitBreaker, CollateralCurrencyIds, CurrencyId, MaxTriggerTiers, Price, Runtime, DOT,
+};
+
+use frame_support::traits::OnInitialize;
+use frame_system::RawOrigin;
+use module_support::{CircuitSignal, GuardedOperation, Ratio, TriggerTier};
+use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::FixedPointNumber;
+use sp_std::prelude::*;
+
+fn feed_prices(currency_ids: &[CurrencyId], price: Price) -> Result<(), &'static str> {
+	let feed_data: Vec<(CurrencyId, Price)> = currency_ids.iter().map(|currency_id| (*currency_id, price)).collect();
+	AcalaOracle::feed_values(RawOrigin::Root.into(), feed_data)?;
+	Ok(())
+}
+
+fn tier() -> TriggerTier<BlockNumber> {
+	TriggerTier {
+		threshold: Ratio::saturating_from_rational(1, 10),
+		operations: vec![GuardedOperation::NewLoan, GuardedOperation::Withdrawal],
+		cool_down: 100,
+	}
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_circuit_breaker }
+
+	_ {}
+
+	// the oracle prices of all monitored currencies crash
+	on_initialize {
+		let c in 0 .. CollateralCurrencyIds::get().len() as u32;
+		let currency_ids: Vec<CurrencyId> = CollateralCurrencyIds::get().into_iter().take(c as usize).collect();
+
+		CircuitBreaker::set_trigger_tiers(RawOrigin::Root.into(), CircuitSignal::PriceCrash, vec![tier()])?;
+		feed_prices(&currency_ids, Price::one())?;
+		CircuitBreaker::on_initialize(1);
+		feed_prices(&currency_ids, Price::saturating_from_rational(1, 2))?;
+	}: {
+		CircuitBreaker::on_initialize(2);
+	}
+
+	set_trigger_tiers {
+		let tiers = vec![tier(); MaxTriggerTiers::get() as usize];
+	}: _(RawOrigin::Root, CircuitSignal::OracleDeviation, tiers)
+
+	set_withdrawal_threshold {
+	}: _(RawOrigin::Root, DOT, 1_000)
+
+	force_trip {
+	}: _(RawOrigin::Root, GuardedOperation::Withdrawal, 100)
+
+	force_reset {
+		CircuitBreaker::force_trip(RawOrigin::Root.into(), GuardedOperation::Withdrawal, 100)?;
+	}: _(RawOrigin::Root, GuardedOperation::Withdrawal)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use frame_support::assert_ok;
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		frame_system::GenesisConfig::default()
+			.build_storage::<Runtime>()
+			.unwrap()
+			.into()
+	}
+
+	#[test]
+	fn test_on_initialize() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_on_initialize());
+		});
+	}
+
+	#[test]
+	fn test_set_trigger_tiers() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_trigger_tiers());
+		});
+	}
+
+	#[test]
+	fn test_set_withdrawal_threshold() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_withdrawal_threshold());
+		});
+	}
+
+	#[test]
+	fn test_force_trip() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_force_trip());
+		});
+	}
+
+	#[test]
+	fn test_force_reset() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_force_reset());
+		});
+	}
+}
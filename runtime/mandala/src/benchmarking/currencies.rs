@@ -25,6 +25,7 @@ use frame_benchmarking::account;
 use frame_system::RawOrigin;
 use sp_runtime::traits::UniqueSaturatedInto;
 
+use module_support::MultiCurrencyAllowance;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::MultiCurrency;
 
@@ -122,6 +123,31 @@ runtime_benchmarks! {
 	verify {
 		assert_eq!(<Currencies as MultiCurrency<_>>::free_balance(native_currency_id, &who), 0);
 	}
+
+	approve {
+		let currency_id = DOT;
+		let amount: Balance = 1_000 * dollar(currency_id);
+		let owner: AccountId = account("owner", 0, SEED);
+		let spender: AccountId = account("spender", 0, SEED);
+		let spender_lookup = lookup_of_account(spender.clone());
+	}: _(RawOrigin::Signed(owner.clone()), spender_lookup, currency_id, amount)
+	verify {
+		assert_eq!(<Currencies as MultiCurrencyAllowance<_>>::allowance(currency_id, &owner, &spender), amount);
+	}
+
+	transfer_from {
+		let currency_id = DOT;
+		let amount: Balance = 1_000 * dollar(currency_id);
+		let from: AccountId = account("from", 0, SEED);
+		let spender: AccountId = account("spender", 0, SEED);
+		set_balance(currency_id, &from, amount);
+		Currencies::approve(RawOrigin::Signed(from.clone()).into(), lookup_of_account(spender.clone()), currency_id, amount)?;
+
+		let to: AccountId = account("to", 0, SEED);
+	}: _(RawOrigin::Signed(spender), lookup_of_account(from), lookup_of_account(to.clone()), currency_id, amount)
+	verify {
+		assert_eq!(<Currencies as MultiCurrency<_>>::total_balance(currency_id, &to), amount);
+	}
 }
 
 #[cfg(test)]
@@ -170,4 +196,18 @@ mod tests {
 			assert_ok!(test_benchmark_update_balance_native_currency_killing());
 		});
 	}
+
+	#[test]
+	fn approve() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_approve());
+		});
+	}
+
+	#[test]
+	fn transfer_from() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_transfer_from());
+		});
+	}
 }
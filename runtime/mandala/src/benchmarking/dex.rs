@@ -17,15 +17,17 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	dollar, AccountId, Balance, BlockNumber, Currencies, CurrencyId, Dex, EnabledTradingPairs, Runtime,
-	TradingPathLimit,
+	dollar, AccountId, Balance, BlockNumber, Currencies, CurrencyId, Dex, EnabledTradingPairs, GetNativeCurrencyId,
+	ListingBond, ListingChallengePeriod, MaxBasketInputs, MaxRangeBuckets, Price, ProvisioningPeriod, Ratio, Runtime,
+	System, TradingPathLimit,
 };
 
 use frame_benchmarking::account;
+use frame_support::traits::OnInitialize;
 use frame_system::RawOrigin;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::MultiCurrencyExtended;
-use sp_runtime::traits::UniqueSaturatedInto;
+use sp_runtime::{traits::UniqueSaturatedInto, FixedPointNumber, Permill};
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
@@ -64,6 +66,77 @@ fn inject_liquidity(
 	Ok(())
 }
 
+fn enable_stable_swap_trading_pair(
+	currency_id_a: CurrencyId,
+	currency_id_b: CurrencyId,
+	amplification: u32,
+) -> Result<(), &'static str> {
+	let _ = Dex::disable_trading_pair(RawOrigin::Root.into(), currency_id_a, currency_id_b);
+	Dex::enable_stable_swap_trading_pair(RawOrigin::Root.into(), currency_id_a, currency_id_b, amplification)?;
+
+	Ok(())
+}
+
+fn enable_range_trading_pair(
+	currency_id_a: CurrencyId,
+	currency_id_b: CurrencyId,
+	initial_bucket: u32,
+) -> Result<(), &'static str> {
+	let bucket_count = MaxRangeBuckets::get();
+	let _ = Dex::disable_trading_pair(RawOrigin::Root.into(), currency_id_a, currency_id_b);
+	Dex::enable_range_trading_pair(
+		RawOrigin::Root.into(),
+		currency_id_a,
+		currency_id_b,
+		Price::saturating_from_rational(1, 10),
+		Price::saturating_from_rational(1, 100),
+		bucket_count,
+		initial_bucket,
+	)?;
+
+	Ok(())
+}
+
+fn inject_range_liquidity(
+	maker: AccountId,
+	currency_id_a: CurrencyId,
+	currency_id_b: CurrencyId,
+	(lower_bucket, upper_bucket): (u32, u32),
+	shares: Balance,
+) -> Result<(), &'static str> {
+	let max_amount_a = 1_000_000 * dollar(currency_id_a);
+	let max_amount_b = 1_000_000 * dollar(currency_id_b);
+	// set balance, the native currency pays the deposit of position NFT
+	<Currencies as MultiCurrencyExtended<_>>::update_balance(
+		GetNativeCurrencyId::get(),
+		&maker,
+		dollar(GetNativeCurrencyId::get()).unique_saturated_into(),
+	)?;
+	<Currencies as MultiCurrencyExtended<_>>::update_balance(
+		currency_id_a,
+		&maker,
+		max_amount_a.unique_saturated_into(),
+	)?;
+	<Currencies as MultiCurrencyExtended<_>>::update_balance(
+		currency_id_b,
+		&maker,
+		max_amount_b.unique_saturated_into(),
+	)?;
+
+	Dex::add_range_liquidity(
+		RawOrigin::Signed(maker).into(),
+		currency_id_a,
+		currency_id_b,
+		lower_bucket,
+		upper_bucket,
+		shares,
+		max_amount_a,
+		max_amount_b,
+	)?;
+
+	Ok(())
+}
+
 runtime_benchmarks! {
 	{ Runtime, module_dex }
 
@@ -77,6 +150,52 @@ runtime_benchmarks! {
 		let _ = Dex::disable_trading_pair(RawOrigin::Root.into(), currency_id_a, currency_id_b);
 	}: _(RawOrigin::Root, currency_id_a, currency_id_b)
 
+	// worst: ramp the amplification of a non-empty stable swap trading pair
+	enable_stable_swap_trading_pair {
+		let maker: AccountId = account("maker", 0, SEED);
+		let trading_pair = EnabledTradingPairs::get()[0];
+		let currency_id_a = trading_pair.0;
+		let currency_id_b = trading_pair.1;
+		enable_stable_swap_trading_pair(currency_id_a, currency_id_b, 100)?;
+		inject_liquidity(maker, currency_id_a, currency_id_b, 100 * dollar(currency_id_a), 100 * dollar(currency_id_b), false)?;
+		Dex::disable_trading_pair(RawOrigin::Root.into(), currency_id_a, currency_id_b)?;
+	}: _(RawOrigin::Root, currency_id_a, currency_id_b, 200)
+
+	// enable a new trading pair with concentrated liquidity in all buckets
+	enable_range_trading_pair {
+		let trading_pair = EnabledTradingPairs::get()[0];
+		let currency_id_a = trading_pair.0;
+		let currency_id_b = trading_pair.1;
+		let bucket_count = MaxRangeBuckets::get();
+		let _ = Dex::disable_trading_pair(RawOrigin::Root.into(), currency_id_a, currency_id_b);
+	}: _(RawOrigin::Root, currency_id_a, currency_id_b, Price::saturating_from_rational(1, 10), Price::saturating_from_rational(1, 100), bucket_count, bucket_count / 2)
+
+	// add range liquidity to all buckets which already have liquidity
+	add_range_liquidity {
+		let first_maker: AccountId = account("first_maker", 0, SEED);
+		let second_maker: AccountId = account("second_maker", 0, SEED);
+		let trading_pair = EnabledTradingPairs::get()[0];
+		let amount_a = 1_000_000 * dollar(trading_pair.0);
+		let amount_b = 1_000_000 * dollar(trading_pair.1);
+
+		enable_range_trading_pair(trading_pair.0, trading_pair.1, MaxRangeBuckets::get() / 2)?;
+		inject_range_liquidity(first_maker, trading_pair.0, trading_pair.1, (0, MaxRangeBuckets::get() - 1), 100 * dollar(trading_pair.1))?;
+
+		// set balance
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.0, &second_maker, amount_a.unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.1, &second_maker, amount_b.unique_saturated_into())?;
+	}: _(RawOrigin::Signed(second_maker), trading_pair.0, trading_pair.1, 0, MaxRangeBuckets::get() - 1, 100 * dollar(trading_pair.1), amount_a, amount_b)
+
+	// remove range liquidity from all buckets
+	remove_range_liquidity {
+		let maker: AccountId = account("maker", 0, SEED);
+		let trading_pair = EnabledTradingPairs::get()[0];
+
+		enable_range_trading_pair(trading_pair.0, trading_pair.1, MaxRangeBuckets::get() / 2)?;
+		inject_range_liquidity(maker.clone(), trading_pair.0, trading_pair.1, (0, MaxRangeBuckets::get() - 1), 100 * dollar(trading_pair.1))?;
+		let position_id = module_dex::RangePositions::<Runtime>::iter_keys().next().ok_or("no range position")?;
+	}: _(RawOrigin::Signed(maker), position_id)
+
 	// disable a Enabled trading pair
 	disable_trading_pair {
 		let trading_pair = EnabledTradingPairs::get()[0];
@@ -151,6 +270,7 @@ runtime_benchmarks! {
 
 	swap_with_exact_supply {
 		let u in 2 .. TradingPathLimit::get() as u32;
+		let b in 0 .. MaxRangeBuckets::get() - 1;
 
 		let trading_pair = EnabledTradingPairs::get()[0];
 		let mut path: Vec<CurrencyId> = vec![];
@@ -169,13 +289,27 @@ runtime_benchmarks! {
 
 		let maker: AccountId = account("maker", 0, SEED);
 		let taker: AccountId = account("taker", 0, SEED);
-		inject_liquidity(maker, trading_pair.0, trading_pair.1, 10_000 * dollar(trading_pair.0), 10_000 * dollar(trading_pair.1), false)?;
+		let referrer: AccountId = account("referrer", 0, SEED);
+		Dex::set_referrer_fee_share(RawOrigin::Root.into(), Permill::from_percent(50))?;
+		// worst: the price impact is checked against both the default and the limit of the swap
+		Dex::set_max_price_impact(RawOrigin::Root.into(), trading_pair.0, trading_pair.1, Some(Ratio::one()))?;
+		if b == 0 {
+			// worst: the stable swap invariant is solved by iterations
+			enable_stable_swap_trading_pair(trading_pair.0, trading_pair.1, 100)?;
+			inject_liquidity(maker, trading_pair.0, trading_pair.1, 10_000 * dollar(trading_pair.0), 10_000 * dollar(trading_pair.1), false)?;
+		} else {
+			// the first swap walks down b buckets to the lowest bucket
+			enable_range_trading_pair(trading_pair.0, trading_pair.1, b - 1)?;
+			inject_range_liquidity(maker.clone(), trading_pair.0, trading_pair.1, (0, 0), 100 * dollar(trading_pair.1))?;
+			inject_range_liquidity(maker, trading_pair.0, trading_pair.1, (b, MaxRangeBuckets::get() - 1), 100 * dollar(trading_pair.1))?;
+		}
 
 		<Currencies as MultiCurrencyExtended<_>>::update_balance(path[0], &taker, (10_000 * dollar(path[0])).unique_saturated_into())?;
-	}: swap_with_exact_supply(RawOrigin::Signed(taker), path.clone(), 100 * dollar(path[0]), 0)
+	}: swap_with_exact_supply(RawOrigin::Signed(taker), path.clone(), 100 * dollar(path[0]), 0, Some(Ratio::one()), Some(referrer))
 
 	swap_with_exact_target {
 		let u in 2 .. TradingPathLimit::get() as u32;
+		let b in 0 .. MaxRangeBuckets::get() - 1;
 
 		let trading_pair = EnabledTradingPairs::get()[0];
 		let mut path: Vec<CurrencyId> = vec![];
@@ -194,10 +328,114 @@ runtime_benchmarks! {
 
 		let maker: AccountId = account("maker", 0, SEED);
 		let taker: AccountId = account("taker", 0, SEED);
-		inject_liquidity(maker, trading_pair.0, trading_pair.1, 10_000 * dollar(trading_pair.0), 10_000 * dollar(trading_pair.1), false)?;
+		let referrer: AccountId = account("referrer", 0, SEED);
+		Dex::set_referrer_fee_share(RawOrigin::Root.into(), Permill::from_percent(50))?;
+		// worst: the price impact is checked against both the default and the limit of the swap
+		Dex::set_max_price_impact(RawOrigin::Root.into(), trading_pair.0, trading_pair.1, Some(Ratio::one()))?;
+		if b == 0 {
+			// worst: the stable swap invariant is solved by iterations
+			enable_stable_swap_trading_pair(trading_pair.0, trading_pair.1, 100)?;
+			inject_liquidity(maker, trading_pair.0, trading_pair.1, 10_000 * dollar(trading_pair.0), 10_000 * dollar(trading_pair.1), false)?;
+		} else {
+			// the first swap walks down b buckets to the lowest bucket
+			enable_range_trading_pair(trading_pair.0, trading_pair.1, b - 1)?;
+			inject_range_liquidity(maker.clone(), trading_pair.0, trading_pair.1, (0, 0), 100 * dollar(trading_pair.1))?;
+			inject_range_liquidity(maker, trading_pair.0, trading_pair.1, (b, MaxRangeBuckets::get() - 1), 100 * dollar(trading_pair.1))?;
+		}
 
 		<Currencies as MultiCurrencyExtended<_>>::update_balance(path[0], &taker, (10_000 * dollar(path[0])).unique_saturated_into())?;
-	}: swap_with_exact_target(RawOrigin::Signed(taker), path.clone(), 10 * dollar(path[path.len() - 1]), 100 * dollar(path[0]))
+	}: swap_with_exact_target(RawOrigin::Signed(taker), path.clone(), 10 * dollar(path[path.len() - 1]), 1_000 * dollar(path[0]), Some(Ratio::one()), Some(referrer))
+
+	// worst: all inputs except the last one are drained
+	swap_basket_for_exact_target {
+		let u in 1 .. MaxBasketInputs::get() as u32;
+
+		let trading_pair = EnabledTradingPairs::get()[0];
+		let inputs: Vec<(CurrencyId, Balance)> = vec![(trading_pair.0, 10 * dollar(trading_pair.0)); u as usize];
+
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = account("taker", 0, SEED);
+		inject_liquidity(maker, trading_pair.0, trading_pair.1, 10_000 * dollar(trading_pair.0), 10_000 * dollar(trading_pair.1), false)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.0, &taker, (10_000 * dollar(trading_pair.0)).unique_saturated_into())?;
+	}: _(RawOrigin::Signed(taker), inputs, trading_pair.1, 9 * u as Balance * dollar(trading_pair.1))
+
+	set_exchange_fee {
+	}: _(RawOrigin::Root, 1, 1000)
+
+	// worst: claim the last refund of a failed Provisioning trading pair
+	claim_refund {
+		let maker: AccountId = account("maker", 0, SEED);
+		let trading_pair = EnabledTradingPairs::get()[0];
+		let min_contribution_a = dollar(trading_pair.0);
+		let min_contribution_b = dollar(trading_pair.1);
+		let not_before: BlockNumber = Default::default();
+
+		// set balance
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.0, &maker, min_contribution_a.unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.1, &maker, min_contribution_b.unique_saturated_into())?;
+
+		// list the trading pair and add provision which doesn't reach the target
+		let _ = Dex::disable_trading_pair(RawOrigin::Root.into(), trading_pair.0, trading_pair.1);
+		Dex::list_trading_pair(RawOrigin::Root.into(), trading_pair.0, trading_pair.1, min_contribution_a, min_contribution_b, 200 * min_contribution_a, 1_000 * min_contribution_b, not_before)?;
+		Dex::add_liquidity(RawOrigin::Signed(maker.clone()).into(), trading_pair.0, trading_pair.1, min_contribution_a, min_contribution_b, false)?;
+
+		System::set_block_number(not_before + ProvisioningPeriod::get() + 1);
+	}: _(RawOrigin::Signed(maker), trading_pair.0, trading_pair.1)
+
+	set_referrer_fee_share {
+	}: _(RawOrigin::Root, Permill::from_percent(10))
+
+	claim_referrer_rewards {
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = account("taker", 0, SEED);
+		let referrer: AccountId = account("referrer", 0, SEED);
+		let trading_pair = EnabledTradingPairs::get()[0];
+		Dex::set_referrer_fee_share(RawOrigin::Root.into(), Permill::from_percent(50))?;
+		inject_liquidity(maker, trading_pair.0, trading_pair.1, 10_000 * dollar(trading_pair.0), 10_000 * dollar(trading_pair.1), false)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.0, &taker, (10_000 * dollar(trading_pair.0)).unique_saturated_into())?;
+		Dex::swap_with_exact_supply(RawOrigin::Signed(taker).into(), vec![trading_pair.0, trading_pair.1], 100 * dollar(trading_pair.0), 0, None, Some(referrer.clone()))?;
+	}: _(RawOrigin::Signed(referrer), trading_pair.0)
+
+	// worst: the default is removed with the block start price
+	set_max_price_impact {
+		let trading_pair = EnabledTradingPairs::get()[0];
+		Dex::set_max_price_impact(RawOrigin::Root.into(), trading_pair.0, trading_pair.1, Some(Ratio::saturating_from_rational(1, 10)))?;
+	}: _(RawOrigin::Root, trading_pair.0, trading_pair.1, None)
+
+	propose_listing {
+		let proposer: AccountId = account("proposer", 0, SEED);
+		let trading_pair = EnabledTradingPairs::get()[0];
+		let _ = Dex::disable_trading_pair(RawOrigin::Root.into(), trading_pair.0, trading_pair.1);
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(GetNativeCurrencyId::get(), &proposer, (2 * ListingBond::get()).unique_saturated_into())?;
+	}: _(RawOrigin::Signed(proposer), trading_pair.0, trading_pair.1, dollar(trading_pair.0), dollar(trading_pair.1), 100 * dollar(trading_pair.0), 1_000 * dollar(trading_pair.1), Default::default())
+
+	veto_listing {
+		let proposer: AccountId = account("proposer", 0, SEED);
+		let trading_pair = EnabledTradingPairs::get()[0];
+		let _ = Dex::disable_trading_pair(RawOrigin::Root.into(), trading_pair.0, trading_pair.1);
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(GetNativeCurrencyId::get(), &proposer, (2 * ListingBond::get()).unique_saturated_into())?;
+		Dex::propose_listing(RawOrigin::Signed(proposer).into(), trading_pair.0, trading_pair.1, dollar(trading_pair.0), dollar(trading_pair.1), 100 * dollar(trading_pair.0), 1_000 * dollar(trading_pair.1), Default::default())?;
+	}: _(RawOrigin::Root, trading_pair.0, trading_pair.1)
+
+	// activate listing proposals of c trading pairs
+	on_initialize {
+		let c in 1 .. EnabledTradingPairs::get().len() as u32;
+
+		let proposer: AccountId = account("proposer", 0, SEED);
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(GetNativeCurrencyId::get(), &proposer, ((c + 1) as Balance * ListingBond::get()).unique_saturated_into())?;
+
+		for i in 0 .. c {
+			let trading_pair = EnabledTradingPairs::get()[i as usize];
+			let _ = Dex::disable_trading_pair(RawOrigin::Root.into(), trading_pair.0, trading_pair.1);
+			Dex::propose_listing(RawOrigin::Signed(proposer.clone()).into(), trading_pair.0, trading_pair.1, dollar(trading_pair.0), dollar(trading_pair.1), 100 * dollar(trading_pair.0), 1_000 * dollar(trading_pair.1), Default::default())?;
+		}
+	}: {
+		Dex::on_initialize(System::block_number() + ListingChallengePeriod::get());
+	}
 }
 
 #[cfg(test)]
@@ -268,10 +506,101 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn enable_stable_swap_trading_pair() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_enable_stable_swap_trading_pair());
+		});
+	}
+
+	#[test]
+	fn enable_range_trading_pair() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_enable_range_trading_pair());
+		});
+	}
+
+	#[test]
+	fn add_range_liquidity() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_range_liquidity());
+		});
+	}
+
+	#[test]
+	fn remove_range_liquidity() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_remove_range_liquidity());
+		});
+	}
+
 	#[test]
 	fn disable_trading_pair() {
 		new_test_ext().execute_with(|| {
 			assert_ok!(test_benchmark_disable_trading_pair());
 		});
 	}
+
+	#[test]
+	fn swap_basket_for_exact_target() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_swap_basket_for_exact_target());
+		});
+	}
+
+	#[test]
+	fn set_exchange_fee() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_exchange_fee());
+		});
+	}
+
+	#[test]
+	fn claim_refund() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim_refund());
+		});
+	}
+
+	#[test]
+	fn set_referrer_fee_share() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_referrer_fee_share());
+		});
+	}
+
+	#[test]
+	fn claim_referrer_rewards() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim_referrer_rewards());
+		});
+	}
+
+	#[test]
+	fn set_max_price_impact() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_max_price_impact());
+		});
+	}
+
+	#[test]
+	fn propose_listing() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_propose_listing());
+		});
+	}
+
+	#[test]
+	fn veto_listing() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_veto_listing());
+		});
+	}
+
+	#[test]
+	fn on_initialize() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_on_initialize());
+		});
+	}
 }
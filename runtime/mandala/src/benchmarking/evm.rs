@@ -16,11 +16,15 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{dollar, AccountId, Event, EvmAccounts, Origin, Runtime, System, ACA, EVM};
+use crate::{
+	dollar, AccountId, DeveloperDeposit, Event, EvmAccounts, MaxListedCallers, Origin, ReapInactivityPeriod, Runtime,
+	System, ACA, EVM,
+};
 
 use super::utils::set_aca_balance;
 use frame_support::dispatch::DispatchError;
 use frame_system::RawOrigin;
+use module_evm::{CallerAccessMode, DeploymentMode, EVMCallerAccessControl};
 use orml_benchmarking::runtime_benchmarks;
 use sp_core::H160;
 use sp_io::hashing::keccak_256;
@@ -121,6 +125,72 @@ runtime_benchmarks! {
 		set_aca_balance(&alice_account_id(), 1_000 * dollar(ACA));
 		let contract = deploy_contract(alice_account_id())?;
 	}: _(RawOrigin::Signed(alice_account_id()), contract)
+
+	reap_account {
+		set_aca_balance(&alice_account_id(), DeveloperDeposit::get());
+		EVM::enable_contract_development(Origin::signed(alice_account_id()))?;
+		System::set_block_number(System::block_number() + ReapInactivityPeriod::get());
+		let alice_address = EvmAccounts::eth_address(&alice());
+	}: _(RawOrigin::Signed(bob_account_id()), alice_address)
+
+	claim_reaped_deposit {
+		set_aca_balance(&alice_account_id(), DeveloperDeposit::get());
+		EVM::enable_contract_development(Origin::signed(alice_account_id()))?;
+		System::set_block_number(System::block_number() + ReapInactivityPeriod::get());
+		let alice_address = EvmAccounts::eth_address(&alice());
+		EVM::reap_account(Origin::signed(bob_account_id()), alice_address)?;
+	}: _(RawOrigin::Signed(alice_account_id()))
+
+	set_deployment_mode {
+	}: _(RawOrigin::Root, DeploymentMode::AllowedDeployers)
+
+	add_allowed_deployer {
+		let alice_address = EvmAccounts::eth_address(&alice());
+	}: _(RawOrigin::Root, alice_address)
+
+	remove_allowed_deployer {
+		let alice_address = EvmAccounts::eth_address(&alice());
+		EVM::add_allowed_deployer(RawOrigin::Root.into(), alice_address)?;
+	}: _(RawOrigin::Root, alice_address)
+
+	add_precompile_delegate_caller {
+		let alice_address = EvmAccounts::eth_address(&alice());
+	}: _(RawOrigin::Root, alice_address)
+
+	remove_precompile_delegate_caller {
+		let alice_address = EvmAccounts::eth_address(&alice());
+		EVM::add_precompile_delegate_caller(RawOrigin::Root.into(), alice_address)?;
+	}: _(RawOrigin::Root, alice_address)
+
+	set_caller_access_mode {
+		let c in 0 .. MaxListedCallers::get();
+
+		set_aca_balance(&alice_account_id(), 1_000 * dollar(ACA));
+		let contract = deploy_contract(alice_account_id())?;
+		<EVM as EVMCallerAccessControl<AccountId>>::set_caller_access_mode(alice_account_id(), contract, Some(CallerAccessMode::AllowList))?;
+		for i in 0 .. c {
+			<EVM as EVMCallerAccessControl<AccountId>>::add_listed_caller(alice_account_id(), contract, H160::from_low_u64_be(i.into()))?;
+		}
+	}: {
+		<EVM as EVMCallerAccessControl<AccountId>>::set_caller_access_mode(alice_account_id(), contract, None)?;
+	}
+
+	add_listed_caller {
+		set_aca_balance(&alice_account_id(), 1_000 * dollar(ACA));
+		let contract = deploy_contract(alice_account_id())?;
+		let bob_address = EvmAccounts::eth_address(&bob());
+	}: {
+		<EVM as EVMCallerAccessControl<AccountId>>::add_listed_caller(alice_account_id(), contract, bob_address)?;
+	}
+
+	remove_listed_caller {
+		set_aca_balance(&alice_account_id(), 1_000 * dollar(ACA));
+		let contract = deploy_contract(alice_account_id())?;
+		let bob_address = EvmAccounts::eth_address(&bob());
+		<EVM as EVMCallerAccessControl<AccountId>>::add_listed_caller(alice_account_id(), contract, bob_address)?;
+	}: {
+		<EVM as EVMCallerAccessControl<AccountId>>::remove_listed_caller(alice_account_id(), contract, bob_address)?;
+	}
 }
 
 #[cfg(test)]
@@ -186,4 +256,74 @@ mod tests {
 			assert_ok!(test_benchmark_selfdestruct());
 		});
 	}
+
+	#[test]
+	fn test_reap_account() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_reap_account());
+		});
+	}
+
+	#[test]
+	fn test_claim_reaped_deposit() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim_reaped_deposit());
+		});
+	}
+
+	#[test]
+	fn test_set_deployment_mode() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_deployment_mode());
+		});
+	}
+
+	#[test]
+	fn test_add_allowed_deployer() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_allowed_deployer());
+		});
+	}
+
+	#[test]
+	fn test_remove_allowed_deployer() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_remove_allowed_deployer());
+		});
+	}
+
+	#[test]
+	fn test_add_precompile_delegate_caller() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_precompile_delegate_caller());
+		});
+	}
+
+	#[test]
+	fn test_remove_precompile_delegate_caller() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_remove_precompile_delegate_caller());
+		});
+	}
+
+	#[test]
+	fn test_set_caller_access_mode() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_caller_access_mode());
+		});
+	}
+
+	#[test]
+	fn test_add_listed_caller() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_add_listed_caller());
+		});
+	}
+
+	#[test]
+	fn test_remove_listed_caller() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_remove_listed_caller());
+		});
+	}
 }
@@ -16,10 +16,10 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use super::utils::set_balance;
+use super::utils::{lookup_of_account, set_balance};
 use crate::{
-	dollar, AccountId, Currencies, GetStakingCurrencyId, Homa, PolkadotBondingDuration, PolkadotBridge, Runtime,
-	StakingPool,
+	dollar, AccountId, Currencies, GetLiquidCurrencyId, GetStakingCurrencyId, Homa, MaxFastMatchRedeemers,
+	PolkadotBondingDuration, PolkadotBridge, Runtime, StakingPool,
 };
 use frame_benchmarking::account;
 use frame_system::RawOrigin;
@@ -37,24 +37,47 @@ fn new_era() {
 	StakingPool::rebalance();
 }
 
+fn inject_redeem_requests(n: u32) -> Result<(), &'static str> {
+	let currency_id = GetStakingCurrencyId::get();
+	for i in 0..n {
+		let redeemer: AccountId = account("redeemer", i, SEED);
+		set_balance(currency_id, &redeemer, dollar(currency_id));
+		Homa::mint(RawOrigin::Signed(redeemer.clone()).into(), dollar(currency_id), 0)?;
+		let liquid_amount = <Currencies as MultiCurrency<_>>::free_balance(GetLiquidCurrencyId::get(), &redeemer);
+		Homa::request_redeem(RawOrigin::Signed(redeemer).into(), liquid_amount, true)?;
+	}
+	Ok(())
+}
+
 runtime_benchmarks! {
 	{ Runtime, module_homa }
 
 	_ {}
 
-	// inject DOT to staking pool and mint LDOT
+	// inject DOT to staking pool and mint LDOT, matching `n` redeem requests
 	mint {
+		let n in 0 .. MaxFastMatchRedeemers::get();
+		inject_redeem_requests(n)?;
+		let caller: AccountId = account("caller", 0, SEED);
+		let currency_id = GetStakingCurrencyId::get();
+		set_balance(currency_id, &caller, 1_000 * dollar(currency_id));
+	}: _(RawOrigin::Signed(caller), 1_000 * dollar(currency_id), 0)
+
+	// inject DOT to staking pool to mint exact amount of LDOT
+	mint_for_exact_liquid {
+		let n in 0 .. MaxFastMatchRedeemers::get();
+		inject_redeem_requests(n)?;
 		let caller: AccountId = account("caller", 0, SEED);
 		let currency_id = GetStakingCurrencyId::get();
 		set_balance(currency_id, &caller, 1_000 * dollar(currency_id));
-	}: _(RawOrigin::Signed(caller), 1_000 * dollar(currency_id))
+	}: _(RawOrigin::Signed(caller), 1_000 * dollar(currency_id), 1_000 * dollar(currency_id))
 
 	// redeem DOT from free pool
 	redeem_immediately {
 		let caller: AccountId = account("caller", 0, SEED);
 		let currency_id = GetStakingCurrencyId::get();
 		set_balance(currency_id, &caller, 1_000 * dollar(currency_id));
-		Homa::mint(RawOrigin::Signed(caller.clone()).into(), 1_000 * dollar(currency_id))?;
+		Homa::mint(RawOrigin::Signed(caller.clone()).into(), 1_000 * dollar(currency_id), 0)?;
 		for era_index in 0..=PolkadotBondingDuration::get() {
 			new_era();
 		}
@@ -68,7 +91,7 @@ runtime_benchmarks! {
 		let caller: AccountId = account("caller", 0, SEED);
 		let currency_id = GetStakingCurrencyId::get();
 		set_balance(currency_id, &caller, 1_000 * dollar(currency_id));
-		Homa::mint(RawOrigin::Signed(caller.clone()).into(), 1_000 * dollar(currency_id))?;
+		Homa::mint(RawOrigin::Signed(caller.clone()).into(), 1_000 * dollar(currency_id), 0)?;
 		new_era();
 	}: redeem(RawOrigin::Signed(caller), dollar(currency_id), RedeemStrategy::WaitForUnbonding)
 
@@ -77,7 +100,7 @@ runtime_benchmarks! {
 		let caller: AccountId = account("caller", 0, SEED);
 		let currency_id = GetStakingCurrencyId::get();
 		set_balance(currency_id, &caller, 1_000 * dollar(currency_id));
-		Homa::mint(RawOrigin::Signed(caller.clone()).into(), 1_000 * dollar(currency_id))?;
+		Homa::mint(RawOrigin::Signed(caller.clone()).into(), 1_000 * dollar(currency_id), 0)?;
 		new_era();
 		new_era();
 	}: redeem(RawOrigin::Signed(caller.clone()), dollar(currency_id), RedeemStrategy::Target(PolkadotBondingDuration::get() + 2))
@@ -86,7 +109,7 @@ runtime_benchmarks! {
 		let caller: AccountId = account("caller", 0, SEED);
 		let currency_id = GetStakingCurrencyId::get();
 		set_balance(currency_id, &caller, 1_000 * dollar(currency_id));
-		Homa::mint(RawOrigin::Signed(caller.clone()).into(), 1_000 * dollar(currency_id))?;
+		Homa::mint(RawOrigin::Signed(caller.clone()).into(), 1_000 * dollar(currency_id), 0)?;
 		new_era();
 		Homa::redeem(RawOrigin::Signed(caller.clone()).into(), dollar(currency_id), RedeemStrategy::WaitForUnbonding)?;
 		for era_index in 0..=PolkadotBondingDuration::get() {
@@ -96,6 +119,44 @@ runtime_benchmarks! {
 	verify {
 		assert!(<Currencies as MultiCurrency<_>>::total_balance(GetStakingCurrencyId::get(), &caller) > 0);
 	}
+
+	transfer_unbonding_claim {
+		let caller: AccountId = account("caller", 0, SEED);
+		let receiver: AccountId = account("receiver", 0, SEED);
+		let currency_id = GetStakingCurrencyId::get();
+		set_balance(currency_id, &caller, 1_000 * dollar(currency_id));
+		Homa::mint(RawOrigin::Signed(caller.clone()).into(), 1_000 * dollar(currency_id), 0)?;
+		new_era();
+		Homa::redeem(RawOrigin::Signed(caller.clone()).into(), dollar(currency_id), RedeemStrategy::WaitForUnbonding)?;
+		new_era();
+		let (target_era, amount) = module_staking_pool::Unbondings::<Runtime>::iter_prefix(&caller).next().ok_or("no unbonding")?;
+	}: _(RawOrigin::Signed(caller), lookup_of_account(receiver.clone()), target_era, amount)
+	verify {
+		assert_eq!(StakingPool::unbondings(&receiver, target_era), amount);
+	}
+
+	request_redeem {
+		let caller: AccountId = account("caller", 0, SEED);
+		let currency_id = GetStakingCurrencyId::get();
+		set_balance(currency_id, &caller, 1_000 * dollar(currency_id));
+		Homa::mint(RawOrigin::Signed(caller.clone()).into(), 1_000 * dollar(currency_id), 0)?;
+	}: _(RawOrigin::Signed(caller), dollar(currency_id), true)
+
+	cancel_redeem_request {
+		let caller: AccountId = account("caller", 0, SEED);
+		let currency_id = GetStakingCurrencyId::get();
+		set_balance(currency_id, &caller, 1_000 * dollar(currency_id));
+		Homa::mint(RawOrigin::Signed(caller.clone()).into(), 1_000 * dollar(currency_id), 0)?;
+		Homa::request_redeem(RawOrigin::Signed(caller.clone()).into(), 2 * dollar(currency_id), true)?;
+	}: _(RawOrigin::Signed(caller), dollar(currency_id))
+
+	set_redeem_request_fast_match {
+		let caller: AccountId = account("caller", 0, SEED);
+		let currency_id = GetStakingCurrencyId::get();
+		set_balance(currency_id, &caller, 1_000 * dollar(currency_id));
+		Homa::mint(RawOrigin::Signed(caller.clone()).into(), 1_000 * dollar(currency_id), 0)?;
+		Homa::request_redeem(RawOrigin::Signed(caller.clone()).into(), dollar(currency_id), true)?;
+	}: _(RawOrigin::Signed(caller), false)
 }
 
 #[cfg(test)]
@@ -130,6 +191,13 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_mint_for_exact_liquid() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_mint_for_exact_liquid());
+		});
+	}
+
 	#[test]
 	fn test_redeem_immediately() {
 		new_test_ext().execute_with(|| {
@@ -157,4 +225,32 @@ mod tests {
 			assert_ok!(test_benchmark_withdraw_redemption());
 		});
 	}
+
+	#[test]
+	fn test_transfer_unbonding_claim() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_transfer_unbonding_claim());
+		});
+	}
+
+	#[test]
+	fn test_request_redeem() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_request_redeem());
+		});
+	}
+
+	#[test]
+	fn test_cancel_redeem_request() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_redeem_request());
+		});
+	}
+
+	#[test]
+	fn test_set_redeem_request_fast_match() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_redeem_request_fast_match());
+		});
+	}
 }
@@ -16,11 +16,16 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{AccountId, CurrencyId, Runtime, System, TokenSymbol, TransactionPayment};
+use super::utils::set_aca_balance;
+use crate::{
+	AcalaTreasuryAccount, AccountId, Call, CurrencyId, MaxRebatesPerBlock, MaxSponsorAllowedCalls, Runtime, System,
+	TokenSymbol, TransactionPayment,
+};
 use frame_benchmarking::account;
-use frame_support::traits::OnFinalize;
+use frame_support::traits::{Get, OnFinalize, OnInitialize};
 use frame_system::RawOrigin;
 use orml_benchmarking::runtime_benchmarks;
+use sp_runtime::Permill;
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
@@ -38,6 +43,108 @@ runtime_benchmarks! {
 		assert_eq!(TransactionPayment::default_fee_currency_id(&caller), Some(currency_id));
 	}
 
+	with_fee_currency {
+		let caller: AccountId = account("caller", 0, SEED);
+		let currency_id = CurrencyId::Token(TokenSymbol::AUSD);
+		let call = Box::new(Call::System(frame_system::Call::remark(vec![])));
+	}: _(RawOrigin::Signed(caller), currency_id, call)
+
+	register_sponsor {
+		let caller: AccountId = account("caller", 0, SEED);
+		set_aca_balance(&caller, 10_000_000_000_000);
+		TransactionPayment::register_sponsor(
+			RawOrigin::Signed(caller.clone()).into(),
+			1_000_000_000_000,
+			10,
+			vec![],
+		)?;
+		let allowed_calls = vec![(b"System".to_vec(), b"remark".to_vec()); MaxSponsorAllowedCalls::get() as usize];
+	}: _(RawOrigin::Signed(caller.clone()), 2_000_000_000_000, 10, allowed_calls)
+	verify {
+		assert!(TransactionPayment::sponsors(&caller).is_some());
+	}
+
+	unregister_sponsor {
+		let caller: AccountId = account("caller", 0, SEED);
+		set_aca_balance(&caller, 10_000_000_000_000);
+		TransactionPayment::register_sponsor(
+			RawOrigin::Signed(caller.clone()).into(),
+			1_000_000_000_000,
+			10,
+			vec![],
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(TransactionPayment::sponsors(&caller), None);
+	}
+
+	with_sponsor {
+		let caller: AccountId = account("caller", 0, SEED);
+		let sponsor: AccountId = account("sponsor", 0, SEED);
+		let call = Box::new(Call::System(frame_system::Call::remark(vec![])));
+	}: _(RawOrigin::Signed(caller), sponsor, call)
+
+	set_rebate_tiers {
+		let tiers = vec![(1_000_000_000_000, Permill::from_percent(10))];
+	}: _(RawOrigin::Root, tiers.clone())
+	verify {
+		assert_eq!(TransactionPayment::rebate_tiers(), tiers);
+	}
+
+	set_rebate_stake {
+		let caller: AccountId = account("caller", 0, SEED);
+		set_aca_balance(&caller, 10_000_000_000_000);
+		TransactionPayment::set_rebate_stake(RawOrigin::Signed(caller.clone()).into(), 1_000_000_000_000)?;
+	}: _(RawOrigin::Signed(caller.clone()), 2_000_000_000_000)
+	verify {
+		assert_eq!(TransactionPayment::rebate_stakes(&caller), 2_000_000_000_000);
+	}
+
+	claim_rebate {
+		let caller: AccountId = account("caller", 0, SEED);
+		set_aca_balance(&AcalaTreasuryAccount::get(), 1_000_000_000_000_000);
+		module_transaction_payment::PendingRebates::<Runtime>::insert(&caller, 1_000_000_000_000);
+	}: _(RawOrigin::Signed(caller.clone()))
+	verify {
+		assert_eq!(TransactionPayment::pending_rebates(&caller), 0);
+	}
+
+	set_tip_split {
+	}: _(RawOrigin::Root, Permill::from_percent(50), Permill::from_percent(30))
+	verify {
+		assert_eq!(TransactionPayment::tip_split(), (Permill::from_percent(50), Permill::from_percent(30)));
+	}
+
+	set_fee_burn_rate {
+	}: _(RawOrigin::Root, Permill::from_percent(20))
+	verify {
+		assert_eq!(TransactionPayment::fee_burn_rate(), Permill::from_percent(20));
+	}
+
+	set_fee_pool {
+		let currency_id = CurrencyId::Token(TokenSymbol::AUSD);
+	}: _(RawOrigin::Root, currency_id, Some(1_000_000_000_000))
+	verify {
+		assert_eq!(TransactionPayment::fee_pool_thresholds(currency_id), Some(1_000_000_000_000));
+	}
+
+	settle_rebates {
+		let n in 1 .. MaxRebatesPerBlock::get();
+
+		set_aca_balance(&AcalaTreasuryAccount::get(), 1_000_000_000_000_000);
+		TransactionPayment::set_rebate_tiers(RawOrigin::Root.into(), vec![(0, Permill::from_percent(10))])?;
+		for i in 0 .. n {
+			let payer: AccountId = account("payer", i, SEED);
+			module_transaction_payment::PaidFees::<Runtime>::insert(0, payer, (10_000_000_000_000, 1_000_000_000_000));
+		}
+		module_transaction_payment::RebateEra::<Runtime>::put(1);
+	}: {
+		TransactionPayment::on_initialize(1);
+	}
+	verify {
+		assert_eq!(TransactionPayment::next_settle_era(), 1);
+	}
+
 	on_finalize {
 	}: {
 		TransactionPayment::on_finalize(System::block_number());
@@ -63,6 +170,83 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn test_with_fee_currency() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_with_fee_currency());
+		});
+	}
+
+	#[test]
+	fn test_register_sponsor() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_register_sponsor());
+		});
+	}
+
+	#[test]
+	fn test_unregister_sponsor() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_unregister_sponsor());
+		});
+	}
+
+	#[test]
+	fn test_with_sponsor() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_with_sponsor());
+		});
+	}
+
+	#[test]
+	fn test_set_rebate_tiers() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_rebate_tiers());
+		});
+	}
+
+	#[test]
+	fn test_set_rebate_stake() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_rebate_stake());
+		});
+	}
+
+	#[test]
+	fn test_claim_rebate() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_claim_rebate());
+		});
+	}
+
+	#[test]
+	fn test_set_tip_split() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_tip_split());
+		});
+	}
+
+	#[test]
+	fn test_set_fee_burn_rate() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_fee_burn_rate());
+		});
+	}
+
+	#[test]
+	fn test_set_fee_pool() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_set_fee_pool());
+		});
+	}
+
+	#[test]
+	fn test_settle_rebates() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_settle_rebates());
+		});
+	}
+
 	#[test]
 	fn test_on_finalize() {
 		new_test_ext().execute_with(|| {
@@ -63,7 +63,7 @@ impl<T: frame_system::Config> module_evm::WeightInfo for WeightInfo<T> {
 	fn enable_contract_development() -> Weight {
 		(158_995_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	fn disable_contract_development() -> Weight {
 		(159_124_000 as Weight)
@@ -80,4 +80,47 @@ impl<T: frame_system::Config> module_evm::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(7 as Weight))
 			.saturating_add(T::DbWeight::get().writes(5 as Weight))
 	}
+	fn reap_account() -> Weight {
+		(165_217_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn claim_reaped_deposit() -> Weight {
+		(62_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn set_deployment_mode() -> Weight {
+		(31_240_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn add_allowed_deployer() -> Weight {
+		(32_107_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_allowed_deployer() -> Weight {
+		(31_562_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn add_precompile_delegate_caller() -> Weight {
+		(32_107_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn remove_precompile_delegate_caller() -> Weight {
+		(31_562_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_caller_access_mode(c: u32) -> Weight {
+		(74_318_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((7_412_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
+	}
+	fn add_listed_caller() -> Weight {
+		(121_540_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn remove_listed_caller() -> Weight {
+		(108_263_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 }
@@ -62,6 +62,14 @@ impl<T: frame_system::Config> module_honzon::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(24 as Weight))
 			.saturating_add(T::DbWeight::get().writes(11 as Weight))
 	}
+	fn adjust_loans(c: u32) -> Weight {
+		(58_214_000 as Weight)
+			// Standard Error: 341_000
+			.saturating_add((316_532_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads((17 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((11 as Weight).saturating_mul(c as Weight)))
+	}
 	fn transfer_loan_from() -> Weight {
 		(221_574_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(21 as Weight))
@@ -72,4 +80,9 @@ impl<T: frame_system::Config> module_honzon::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(21 as Weight))
 			.saturating_add(T::DbWeight::get().writes(7 as Weight))
 	}
+	fn set_position_label() -> Weight {
+		(40_512_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
@@ -70,6 +70,7 @@ pub type AuctionManagerModule = module_auction_manager::Pallet<Runtime>;
 pub type AuthorityModule = orml_authority::Pallet<Runtime>;
 pub type Currencies = module_currencies::Pallet<Runtime>;
 pub type SchedulerModule = pallet_scheduler::Pallet<Runtime>;
+pub type IncentivesModule = module_incentives::Pallet<Runtime>;
 
 fn run_to_block(n: u32) {
 	while SystemModule::block_number() < n {
@@ -541,6 +542,74 @@ fn test_dex_module() {
 		});
 }
 
+#[test]
+fn test_dex_share_holders() {
+	ExtBuilder::default()
+		.balances(vec![
+			(AccountId::from(ALICE), AUSD, (1_000_000_000_000_000_000u128)),
+			(AccountId::from(ALICE), XBTC, (1_000_000_000_000_000_000u128)),
+			(AccountId::from(BOB), AUSD, (1_000_000_000_000_000_000u128)),
+			(AccountId::from(BOB), XBTC, (1_000_000_000_000_000_000u128)),
+		])
+		.build()
+		.execute_with(|| {
+			assert_ok!(DexModule::add_liquidity(
+				origin_of(AccountId::from(ALICE)),
+				XBTC,
+				AUSD,
+				10000,
+				10000000,
+				false,
+			));
+			assert_ok!(DexModule::add_liquidity(
+				origin_of(AccountId::from(BOB)),
+				XBTC,
+				AUSD,
+				1,
+				1000,
+				false,
+			));
+			assert_ok!(IncentivesModule::deposit_dex_share(
+				origin_of(AccountId::from(BOB)),
+				LPTOKEN,
+				1500
+			));
+
+			let expected_holders = vec![
+				(AccountId::from(ALICE), 20000000),
+				(AccountId::from(BOB), 500),
+				(AccountId::from(BOB), 1500),
+			];
+			let sorted = |mut holders: Vec<(AccountId, Balance)>| {
+				holders.sort();
+				holders
+			};
+
+			let (holders, next_key) = runtime_common::dex_share_holders::<Runtime>(LPTOKEN, None, 1000);
+			assert_eq!(sorted(holders), expected_holders);
+			assert_eq!(next_key, None);
+
+			// scan page by page from the cursor
+			let mut holders = vec![];
+			let mut start_key = None;
+			loop {
+				let (page, next_key) = runtime_common::dex_share_holders::<Runtime>(LPTOKEN, start_key, 1);
+				assert!(page.len() <= 1);
+				holders.extend(page);
+				match next_key {
+					Some(key) => start_key = Some(key),
+					None => break,
+				}
+			}
+			assert_eq!(sorted(holders), expected_holders);
+
+			assert_eq!(
+				runtime_common::dex_share_holders::<Runtime>(LPTOKEN, Some(vec![1, 2, 3]), 1000),
+				(vec![], None)
+			);
+		});
+}
+
 #[test]
 fn test_honzon_module() {
 	ExtBuilder::default()